@@ -18,6 +18,7 @@
 //! - `Array<Variable<'v>, Ix2>`    <- Work in progress
 
 use super::variable::Variable;
+use crate::Float;
 
 /// Trait to reverse accumulate the gradient for different types.
 pub trait Accumulate<OUT> {
@@ -25,18 +26,18 @@ pub trait Accumulate<OUT> {
     fn accumulate(&self) -> OUT;
 }
 
-impl Accumulate<Vec<f64>> for Variable<'_> {
+impl<T: Float> Accumulate<Vec<T>> for Variable<'_, T> {
     /// Function to reverse accumulate the gradient for a `Variable`.
     /// 1. Allocate the array of adjoints.
     /// 2. Set the seed (dx/dx = 1).
     /// 3. Traverse the graph backwards, updating the adjoints for the parent vertices.
     #[inline]
-    fn accumulate(&self) -> Vec<f64> {
+    fn accumulate(&self) -> Vec<T> {
         // Set the seed.
         // The seed is the derivative of the output with respect to itself.
         // dy/dy = 1
-        let mut adjoints = vec![0.0; self.graph.len()];
-        adjoints[self.index] = 1.0; // SEED
+        let mut adjoints = vec![T::zero(); self.graph.len()];
+        adjoints[self.index] = T::one(); // SEED
 
         // Traverse the graph backwards and update the adjoints for the parent vertices.
         // This is simply the generalised chain rule.
@@ -0,0 +1,196 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Checkpointing for functions iterated over many steps, such as the time
+//! steps of a Monte Carlo path or a finite difference scheme.
+//!
+//! Recording every step on the graph makes the tape grow with the number of
+//! steps. Instead, the forward pass only stores the state (as plain values)
+//! every `interval` steps. The reverse pass then recomputes one segment at a
+//! time from its checkpoint, propagates the adjoints back through it, and
+//! truncates the graph before moving to the previous segment.
+//!
+//! The graph never holds more than one segment, at the cost of evaluating
+//! each step twice.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Accumulate, Gradient, Graph, Variable};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Value and gradient of `output(step(...step(initial, 0)..., steps - 1))`,
+/// with respect to the initial state, using checkpoints every `interval` steps.
+///
+/// - `step` maps the state before step `k` to the state after it.
+/// - `output` maps the final state to a scalar.
+///
+/// Parameters that the steps depend on (e.g. a volatility) should be part
+/// of the state, carried unchanged, to get their sensitivities.
+///
+/// ```
+/// # use RustQuant_autodiff::*;
+///
+/// // x_{k+1} = x_k * (1 + r), for 1000 steps, with r carried in the state.
+/// let (value, gradient) = checkpointed_gradient(
+///     &[1.0, 0.001],
+///     1000,
+///     50,
+///     |state, _| vec![state[0] * (1.0 + state[1]), state[1]],
+///     |state| state[0],
+/// );
+///
+/// let growth = 1.001_f64.powi(1000);
+///
+/// assert!((value - growth).abs() < 1e-10);
+/// assert!((gradient[0] - growth).abs() < 1e-10);
+/// assert!((gradient[1] - 1000.0 * 1.001_f64.powi(999)).abs() < 1e-8);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `interval` is zero, or the state is empty.
+pub fn checkpointed_gradient<S, O>(
+    initial: &[f64],
+    steps: usize,
+    interval: usize,
+    step: S,
+    output: O,
+) -> (f64, Vec<f64>)
+where
+    S: for<'v> Fn(&[Variable<'v>], usize) -> Vec<Variable<'v>>,
+    O: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+{
+    assert!(interval > 0, "Checkpoint interval must be positive.");
+    assert!(!initial.is_empty(), "State must not be empty.");
+
+    let graph = Graph::new();
+
+    // Forward pass, keeping only the states at the checkpoints.
+    let mut checkpoints = Vec::with_capacity(steps.div_ceil(interval));
+    let mut state = initial.to_vec();
+
+    for k in 0..steps {
+        if k % interval == 0 {
+            checkpoints.push(state.clone());
+        }
+
+        state = graph.scope(|g| {
+            step(&g.vars(&state), k)
+                .iter()
+                .map(Variable::value)
+                .collect()
+        });
+    }
+
+    let (value, mut adjoints) = graph.scope(|g| {
+        let state = g.vars(&state);
+        let y = output(&state);
+
+        (y.value, y.accumulate().wrt(&state))
+    });
+
+    // Reverse pass, recomputing each segment from its checkpoint.
+    for (segment, checkpoint) in checkpoints.iter().enumerate().rev() {
+        let start = segment * interval;
+        let end = usize::min(start + interval, steps);
+
+        adjoints = graph.scope(|g| {
+            let inputs = g.vars(checkpoint);
+            let mut state = inputs.clone();
+
+            for k in start..end {
+                state = step(&state, k);
+            }
+
+            // Vector-Jacobian product of the segment with the adjoints.
+            let weighted: Variable = state.iter().zip(&adjoints).map(|(x, a)| *x * *a).sum();
+
+            weighted.accumulate().wrt(&inputs)
+        });
+    }
+
+    (value, adjoints)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_checkpoint {
+    use super::*;
+    use crate::Powi;
+    use RustQuant_utils::assert_approx_equal;
+
+    const NORMALS: [f64; 7] = [0.3, -1.2, 0.8, 0.05, -0.4, 1.7, -0.9];
+
+    // Log-Euler step of a GBM path, with state [S, r, sigma].
+    fn gbm_step<'v>(state: &[Variable<'v>], k: usize) -> Vec<Variable<'v>> {
+        let (s, r, sigma) = (state[0], state[1], state[2]);
+        let dt: f64 = 0.01;
+        let z = NORMALS[k % NORMALS.len()];
+
+        let drift = (r - 0.5 * sigma * sigma) * dt;
+        let diffusion = sigma * dt.sqrt() * z;
+
+        vec![s * (drift + diffusion).exp(), r, sigma]
+    }
+
+    fn payoff<'v>(state: &[Variable<'v>]) -> Variable<'v> {
+        (state[0] - 90.0).powi(2) / 100.0
+    }
+
+    // Gradient recorded on a single tape, for reference.
+    fn full_tape_gradient(initial: &[f64], steps: usize) -> (f64, Vec<f64>) {
+        let g = Graph::new();
+        let inputs = g.vars(initial);
+
+        let mut state = inputs.clone();
+        for k in 0..steps {
+            state = gbm_step(&state, k);
+        }
+        let y = payoff(&state);
+
+        (y.value, y.accumulate().wrt(&inputs))
+    }
+
+    #[test]
+    fn test_matches_full_tape() {
+        let initial = [100.0, 0.05, 0.2];
+        let (value, gradient) = full_tape_gradient(&initial, 250);
+
+        for interval in [1, 7, 50, 250, 1000] {
+            let (v, grad) = checkpointed_gradient(&initial, 250, interval, gbm_step, payoff);
+
+            assert_approx_equal!(v, value, 1e-10);
+            for (a, b) in grad.iter().zip(&gradient) {
+                assert_approx_equal!(*a, *b, 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_steps() {
+        let (value, gradient) = checkpointed_gradient(&[100.0, 0.05, 0.2], 0, 10, gbm_step, payoff);
+
+        assert_approx_equal!(value, 1.0, 1e-12);
+        assert_eq!(gradient, vec![0.2, 0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval must be positive")]
+    fn test_zero_interval() {
+        let _ = checkpointed_gradient(&[1.0], 10, 0, |s, _| s.to_vec(), |s| s[0]);
+    }
+}
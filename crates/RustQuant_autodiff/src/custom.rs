@@ -0,0 +1,275 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! User-defined elemental functions, with their own derivatives.
+//!
+//! Some functions cannot be written with the overloaded operators, e.g. an
+//! interpolated curve lookup or a special function from another crate, or
+//! are cheaper to differentiate by hand. These can be recorded on the graph
+//! from their value and partial derivatives, and the graph then treats them
+//! as a single operation.
+//!
+//! ```
+//! # use RustQuant_autodiff::*;
+//!
+//! // A zero curve, linearly interpolated between its pillars.
+//! let times = [1.0, 2.0, 5.0];
+//! let rates = [0.02, 0.025, 0.03];
+//!
+//! let zero_rate = |t: &[f64]| {
+//!     let i = times.partition_point(|&x| x < t[0]).clamp(1, times.len() - 1);
+//!     let slope = (rates[i] - rates[i - 1]) / (times[i] - times[i - 1]);
+//!
+//!     (rates[i - 1] + slope * (t[0] - times[i - 1]), vec![slope])
+//! };
+//!
+//! let g = Graph::new();
+//! let t = g.var(1.5);
+//!
+//! // Discount factor exp(-r(t) t).
+//! let discount = (-zero_rate.apply(&[t]) * t).exp();
+//! let gradient = discount.accumulate();
+//!
+//! let expected = -(0.0225 + 0.005 * 1.5) * (-0.0225_f64 * 1.5).exp();
+//!
+//! assert!((gradient.wrt(&t) - expected).abs() < 1e-12);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Arity, Float, Graph, Operation, Variable};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CUSTOM FUNCTION TRAIT AND IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A user-defined elemental function $f: \mathbb{R}^n \rightarrow \mathbb{R}$,
+/// with its own derivatives.
+///
+/// Closures `Fn(&[T]) -> (T, Vec<T>)`, returning the value and the partial
+/// derivatives, implement this trait.
+pub trait CustomFunction<T: Float = f64> {
+    /// Value and partial derivatives of the function at the inputs.
+    fn evaluate(&self, inputs: &[T]) -> (T, Vec<T>);
+
+    /// Evaluates the function and records it on the graph of the inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no inputs, or the function does not return one
+    /// partial per input.
+    fn apply<'v>(&self, inputs: &[Variable<'v, T>]) -> Variable<'v, T> {
+        assert!(!inputs.is_empty(), "Custom functions need an input.");
+
+        let values: Vec<T> = inputs.iter().map(Variable::value).collect();
+        let (value, partials) = self.evaluate(&values);
+
+        inputs[0].graph.custom(inputs, value, &partials)
+    }
+}
+
+impl<T: Float, F> CustomFunction<T> for F
+where
+    F: Fn(&[T]) -> (T, Vec<T>),
+{
+    #[inline]
+    fn evaluate(&self, inputs: &[T]) -> (T, Vec<T>) {
+        self(inputs)
+    }
+}
+
+impl<T: Float> Graph<T> {
+    /// Records an elemental function of the inputs, from its value and
+    /// its partial derivatives with respect to each input.
+    ///
+    /// Functions of one or two inputs are recorded as a single vertex.
+    /// Functions of more inputs are recorded as a chain of `n - 1` linear
+    /// vertices, each adding one input, which gives the same adjoints.
+    ///
+    /// Second order partials are not recorded, so Hessians through
+    /// custom functions only contain the curvature of the other operations.
+    ///
+    /// ```
+    /// # use RustQuant_autodiff::*;
+    ///
+    /// let g = Graph::new();
+    /// let x = g.var(0.5);
+    ///
+    /// // sin(x), recorded by hand.
+    /// let y = g.custom(&[x], x.value.sin(), &[x.value.cos()]);
+    ///
+    /// assert_eq!(y.value, 0.5_f64.sin());
+    /// assert_eq!(y.accumulate().wrt(&x), 0.5_f64.cos());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no inputs, the number of partials does not
+    /// match the number of inputs, or an input is on another graph.
+    pub fn custom<'v>(
+        &'v self,
+        inputs: &[Variable<'v, T>],
+        value: T,
+        partials: &[T],
+    ) -> Variable<'v, T> {
+        assert!(!inputs.is_empty(), "Custom functions need an input.");
+        assert_eq!(
+            inputs.len(),
+            partials.len(),
+            "Custom functions need one partial per input."
+        );
+        assert!(inputs.iter().all(|x| std::ptr::eq(self, x.graph)));
+
+        let index = match inputs {
+            [x] => self.push(Operation::Custom, Arity::Unary, &[x.index], partials),
+            [x, y] => self.push(
+                Operation::Custom,
+                Arity::Binary,
+                &[x.index, y.index],
+                partials,
+            ),
+            [x, y, rest @ ..] => {
+                let first = self.push(
+                    Operation::Custom,
+                    Arity::Binary,
+                    &[x.index, y.index],
+                    &partials[..2],
+                );
+
+                rest.iter()
+                    .zip(&partials[2..])
+                    .fold(first, |index, (z, &partial)| {
+                        self.push(
+                            Operation::Custom,
+                            Arity::Binary,
+                            &[index, z.index],
+                            &[T::one(), partial],
+                        )
+                    })
+            }
+            [] => unreachable!(),
+        };
+
+        Variable {
+            graph: self,
+            index,
+            value,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_custom {
+    use crate::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Black-Scholes call price, with its delta, vega, rho and theta (wrt T).
+    struct BlackScholesCall {
+        strike: f64,
+    }
+
+    impl CustomFunction for BlackScholesCall {
+        fn evaluate(&self, inputs: &[f64]) -> (f64, Vec<f64>) {
+            let (s, sigma, r, t) = (inputs[0], inputs[1], inputs[2], inputs[3]);
+            let k = self.strike;
+            let n = |x: f64| 0.5 * errorfunctions::RealErrorFunctions::erfc(-x / 2_f64.sqrt());
+            let pdf = |x: f64| (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt();
+
+            let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+            let d2 = d1 - sigma * t.sqrt();
+            let df = (-r * t).exp();
+
+            (
+                s * n(d1) - k * df * n(d2),
+                vec![
+                    n(d1),
+                    s * pdf(d1) * t.sqrt(),
+                    k * t * df * n(d2),
+                    s * pdf(d1) * sigma / (2.0 * t.sqrt()) + r * k * df * n(d2),
+                ],
+            )
+        }
+    }
+
+    fn black_scholes<'v>(x: &[Variable<'v>], k: f64) -> Variable<'v> {
+        let (s, sigma, r, t) = (x[0], x[1], x[2], x[3]);
+
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+
+        s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+    }
+
+    #[test]
+    fn test_matches_recorded_operations() {
+        let inputs = [100.0, 0.2, 0.05, 0.75];
+
+        let g = Graph::new();
+        let x = g.vars(&inputs);
+        let expected = black_scholes(&x, 110.0);
+        let expected_gradient = expected.accumulate().wrt(&x);
+
+        let g = Graph::new();
+        let x = g.vars(&inputs);
+        let price = BlackScholesCall { strike: 110.0 }.apply(&x);
+        let gradient = price.accumulate().wrt(&x);
+
+        // Four inputs are recorded as three vertices.
+        assert_eq!(g.len(), 4 + 3);
+        assert_approx_equal!(price.value, expected.value, 1e-12);
+        for (a, b) in gradient.iter().zip(&expected_gradient) {
+            assert_approx_equal!(*a, *b, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_composition() {
+        let g = Graph::new();
+
+        let x = g.var(0.3);
+        let y = g.var(1.7);
+
+        // h(x, y) = x y^2, composed with the built-in operations.
+        let h = |v: &[f64]| (v[0] * v[1] * v[1], vec![v[1] * v[1], 2.0 * v[0] * v[1]]);
+
+        let f = h.apply(&[x.sin(), y]) * y.exp();
+        let grad = f.accumulate();
+
+        let (a, b) = (0.3_f64, 1.7_f64);
+        assert_eq!(g.len(), 6);
+        assert_approx_equal!(grad.wrt(&x), a.cos() * b * b * b.exp(), 1e-12);
+        assert_approx_equal!(grad.wrt(&y), a.sin() * (2.0 * b + b * b) * b.exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_f32() {
+        let g = Graph::<f32>::default();
+
+        let x = g.var(2.0);
+        let y = g.custom(&[x], 4.0, &[4.0]);
+        let z = y * x;
+
+        assert_eq!(z.accumulate().wrt(&x), 12.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "one partial per input")]
+    fn test_partials_mismatch() {
+        let g = Graph::new();
+        let x = g.var(1.0);
+
+        let _ = g.custom(&[x, x], 1.0, &[1.0]);
+    }
+}
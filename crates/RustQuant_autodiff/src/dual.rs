@@ -0,0 +1,876 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Forward (tangent) mode automatic differentiation with dual numbers.
+//!
+//! A dual number $a + b \epsilon$, with $\epsilon^2 = 0$, carries a value
+//! and a directional derivative (tangent). Evaluating a function on
+//! $x + \epsilon$ gives $f(x) + f'(x) \epsilon$, so every output of a
+//! function is differentiated with respect to the seeded input in a single
+//! evaluation, and no graph is recorded.
+//!
+//! For functions $f:\mathbb{R}^n \rightarrow \mathbb{R}^m$ this takes $n$
+//! evaluations for the full Jacobian, compared to $m$ reverse sweeps.
+//!
+//! ```
+//! # use RustQuant_autodiff::*;
+//!
+//! // Seed the input we differentiate with respect to.
+//! let x = Dual::variable(2.0);
+//!
+//! // Several outputs, one evaluation.
+//! let f = x.powi(3);
+//! let g = x.sin() * x;
+//!
+//! assert_eq!(f.value, 8.0);
+//! assert_eq!(f.derivative, 12.0);
+//! assert_eq!(g.derivative, 2.0_f64.sin() + 2.0 * 2.0_f64.cos());
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::overload::{standard_normal_density, Log, Max, Min, Powf, Powi};
+use std::f64::consts::{LN_10, LN_2, PI, SQRT_2};
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign};
+use std::ops::{Div, DivAssign};
+use std::ops::{Mul, MulAssign};
+use std::ops::{Neg, Sub, SubAssign};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCT AND IMPLEMENTATION
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Dual number: a value and its derivative along the seeded direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dual {
+    /// Value of the number.
+    pub value: f64,
+    /// Derivative (tangent) of the number.
+    pub derivative: f64,
+}
+
+impl Dual {
+    /// Create a new dual number.
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f64, derivative: f64) -> Self {
+        Self { value, derivative }
+    }
+
+    /// Input variable, seeded with a unit tangent.
+    #[must_use]
+    #[inline]
+    pub const fn variable(value: f64) -> Self {
+        Self::new(value, 1.0)
+    }
+
+    /// Constant, with a zero tangent.
+    #[must_use]
+    #[inline]
+    pub const fn constant(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+
+    /// Function to return the value.
+    #[must_use]
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Function to return the derivative.
+    #[must_use]
+    #[inline]
+    pub fn derivative(&self) -> f64 {
+        self.derivative
+    }
+
+    // Chain rule: f(x + x'e) = f(x) + f'(x) x' e
+    #[inline]
+    fn chain(self, value: f64, derivative: f64) -> Self {
+        Self::new(value, derivative * self.derivative)
+    }
+}
+
+/// Implement formatting for the `Dual` struct.
+impl Display for Dual {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} + {:?}ε", self.value, self.derivative)
+    }
+}
+
+impl From<f64> for Dual {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl PartialEq<f64> for Dual {
+    #[inline]
+    fn eq(&self, other: &f64) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialOrd for Dual {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: STANDARD OPERATORS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Dual + Dual
+/// (a + a'e) + (b + b'e) = (a + b) + (a' + b')e
+impl Add<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn add(self, other: Dual) -> Self::Output {
+        Dual::new(self.value + other.value, self.derivative + other.derivative)
+    }
+}
+
+/// Dual - Dual
+/// (a + a'e) - (b + b'e) = (a - b) + (a' - b')e
+impl Sub<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn sub(self, other: Dual) -> Self::Output {
+        Dual::new(self.value - other.value, self.derivative - other.derivative)
+    }
+}
+
+/// Dual * Dual
+/// (a + a'e) * (b + b'e) = ab + (a'b + ab')e
+impl Mul<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn mul(self, other: Dual) -> Self::Output {
+        Dual::new(
+            self.value * other.value,
+            self.derivative * other.value + self.value * other.derivative,
+        )
+    }
+}
+
+/// Dual / Dual
+/// (a + a'e) / (b + b'e) = a/b + ((a'b - ab') / b^2)e
+impl Div<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn div(self, other: Dual) -> Self::Output {
+        Dual::new(
+            self.value / other.value,
+            (self.derivative * other.value - self.value * other.derivative)
+                / (other.value * other.value),
+        )
+    }
+}
+
+/// -Dual
+impl Neg for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+/// Dual + f64
+impl Add<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn add(self, other: f64) -> Self::Output {
+        Dual::new(self.value + other, self.derivative)
+    }
+}
+
+/// f64 + Dual
+impl Add<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn add(self, other: Dual) -> Self::Output {
+        other + self
+    }
+}
+
+/// Dual - f64
+impl Sub<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn sub(self, other: f64) -> Self::Output {
+        Dual::new(self.value - other, self.derivative)
+    }
+}
+
+/// f64 - Dual
+impl Sub<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn sub(self, other: Dual) -> Self::Output {
+        Dual::new(self - other.value, -other.derivative)
+    }
+}
+
+/// Dual * f64
+impl Mul<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self::Output {
+        Dual::new(self.value * other, self.derivative * other)
+    }
+}
+
+/// f64 * Dual
+impl Mul<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn mul(self, other: Dual) -> Self::Output {
+        other * self
+    }
+}
+
+/// Dual / f64
+impl Div<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn div(self, other: f64) -> Self::Output {
+        Dual::new(self.value / other, self.derivative / other)
+    }
+}
+
+/// f64 / Dual
+impl Div<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn div(self, other: Dual) -> Self::Output {
+        Dual::constant(self) / other
+    }
+}
+
+/// Dual += Dual
+impl AddAssign<Dual> for Dual {
+    #[inline]
+    fn add_assign(&mut self, other: Dual) {
+        *self = *self + other;
+    }
+}
+
+/// Dual += f64
+impl AddAssign<f64> for Dual {
+    #[inline]
+    fn add_assign(&mut self, other: f64) {
+        *self = *self + other;
+    }
+}
+
+/// Dual -= Dual
+impl SubAssign<Dual> for Dual {
+    #[inline]
+    fn sub_assign(&mut self, other: Dual) {
+        *self = *self - other;
+    }
+}
+
+/// Dual -= f64
+impl SubAssign<f64> for Dual {
+    #[inline]
+    fn sub_assign(&mut self, other: f64) {
+        *self = *self - other;
+    }
+}
+
+/// Dual *= Dual
+impl MulAssign<Dual> for Dual {
+    #[inline]
+    fn mul_assign(&mut self, other: Dual) {
+        *self = *self * other;
+    }
+}
+
+/// Dual *= f64
+impl MulAssign<f64> for Dual {
+    #[inline]
+    fn mul_assign(&mut self, other: f64) {
+        *self = *self * other;
+    }
+}
+
+/// Dual /= Dual
+impl DivAssign<Dual> for Dual {
+    #[inline]
+    fn div_assign(&mut self, other: Dual) {
+        *self = *self / other;
+    }
+}
+
+/// Dual /= f64
+impl DivAssign<f64> for Dual {
+    #[inline]
+    fn div_assign(&mut self, other: f64) {
+        *self = *self / other;
+    }
+}
+
+/// Sum of dual numbers.
+impl Sum<Dual> for Dual {
+    #[inline]
+    fn sum<I: Iterator<Item = Dual>>(iter: I) -> Self {
+        iter.fold(Dual::constant(0.0), |acc, x| acc + x)
+    }
+}
+
+/// Product of dual numbers.
+impl Product<Dual> for Dual {
+    #[inline]
+    fn product<I: Iterator<Item = Dual>>(iter: I) -> Self {
+        iter.fold(Dual::constant(1.0), |acc, x| acc * x)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: PRIMITIVE FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Dual {
+    /// Absolute value function.
+    /// d/dx abs(x) = sign(x)
+    #[must_use]
+    #[inline]
+    pub fn abs(self) -> Self {
+        self.chain(self.value.abs(), self.value.signum())
+    }
+
+    /// Inverse cosine function.
+    /// d/dx cos^-1(x) = - 1 / sqrt(1 - x^2)
+    #[must_use]
+    #[inline]
+    pub fn acos(self) -> Self {
+        let x = self.value;
+        self.chain(x.acos(), -(1.0 - x * x).sqrt().recip())
+    }
+
+    /// Inverse hyperbolic cosine function.
+    /// d/dx cosh^-1(x) = 1 / sqrt(x^2 - 1)
+    #[must_use]
+    #[inline]
+    pub fn acosh(self) -> Self {
+        let x = self.value;
+        self.chain(x.acosh(), (x * x - 1.0).sqrt().recip())
+    }
+
+    /// Inverse sine function.
+    /// d/dx sin^-1(x) = 1 / sqrt(1 - x^2)
+    #[must_use]
+    #[inline]
+    pub fn asin(self) -> Self {
+        let x = self.value;
+        self.chain(x.asin(), (1.0 - x * x).sqrt().recip())
+    }
+
+    /// Inverse hyperbolic sine function.
+    /// d/dx sinh^-1(x) =  1 / sqrt(1 + x^2)
+    #[must_use]
+    #[inline]
+    pub fn asinh(self) -> Self {
+        let x = self.value;
+        self.chain(x.asinh(), (1.0 + x * x).sqrt().recip())
+    }
+
+    /// Inverse tangent function.
+    /// d/dx tan^-1(x) = 1 / (1 + x^2)
+    #[must_use]
+    #[inline]
+    pub fn atan(self) -> Self {
+        let x = self.value;
+        self.chain(x.atan(), (1.0 + x * x).recip())
+    }
+
+    /// Inverse hyperbolic tangent function.
+    /// d/dx tanh^-1(x) = 1 / (1 - x^2)
+    #[must_use]
+    #[inline]
+    pub fn atanh(self) -> Self {
+        let x = self.value;
+        self.chain(x.atanh(), (1.0 - x * x).recip())
+    }
+
+    /// Cuberoot function.
+    /// d/dx cuberoot(x) = 1 / ( 3 * x^(2/3) )
+    #[must_use]
+    #[inline]
+    pub fn cbrt(self) -> Self {
+        let f = self.value.cbrt();
+        self.chain(f, (3.0 * f * f).recip())
+    }
+
+    /// Cosine function.
+    /// d/dx cos(x) = -sin(x)
+    #[must_use]
+    #[inline]
+    pub fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    /// Hyperbolic cosine function.
+    /// d/dx cosh(x) = sinh(x)
+    #[must_use]
+    #[inline]
+    pub fn cosh(self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    /// Exponential function (base *e*).
+    /// d/dx exp(x) = exp(x)
+    #[must_use]
+    #[inline]
+    pub fn exp(self) -> Self {
+        let f = self.value.exp();
+        self.chain(f, f)
+    }
+
+    /// Exponential function (base 2)
+    /// d/dx 2^x = 2^x * ln(2)
+    #[must_use]
+    #[inline]
+    pub fn exp2(self) -> Self {
+        let f = self.value.exp2();
+        self.chain(f, f * LN_2)
+    }
+
+    /// Exponential function minus 1 function.
+    /// d/dx exp(x) - 1 = exp(x)
+    #[must_use]
+    #[inline]
+    pub fn exp_m1(self) -> Self {
+        self.chain(self.value.exp_m1(), self.value.exp())
+    }
+
+    /// Logarithm (natural) of `x`.
+    /// d/dx ln(x) = 1 / x
+    #[must_use]
+    #[inline]
+    pub fn ln(self) -> Self {
+        self.chain(self.value.ln(), self.value.recip())
+    }
+
+    /// Logarithm (natural) of `1 + x`.
+    /// d/dx ln(1+x) = 1 / (1+x)
+    #[must_use]
+    #[inline]
+    pub fn ln_1p(self) -> Self {
+        self.chain(self.value.ln_1p(), (1.0 + self.value).recip())
+    }
+
+    /// Logarithm (base 10).
+    /// d/dx log_10(x) = 1 / (x * ln(10))
+    #[must_use]
+    #[inline]
+    pub fn log10(self) -> Self {
+        self.chain(self.value.log10(), (self.value * LN_10).recip())
+    }
+
+    /// Logarithm (base 2).
+    /// d/dx log_2(x) = 1 / (x * ln(2))
+    #[must_use]
+    #[inline]
+    pub fn log2(self) -> Self {
+        self.chain(self.value.log2(), (self.value * LN_2).recip())
+    }
+
+    /// Reciprocal function.
+    /// d/dx 1 / x =  - 1 / x^2
+    #[must_use]
+    #[inline]
+    pub fn recip(self) -> Self {
+        let f = self.value.recip();
+        self.chain(f, -f * f)
+    }
+
+    /// Sine function.
+    /// d/dx sin(x) = cos(x)
+    #[must_use]
+    #[inline]
+    pub fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    /// Hyperbolic sine function.
+    /// d/dx sinh(x) =  cosh(x)
+    #[must_use]
+    #[inline]
+    pub fn sinh(self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    /// Square root function.
+    /// d/dx sqrt(x) =  1 / 2*sqrt(x)
+    #[must_use]
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        let f = self.value.sqrt();
+        self.chain(f, (2.0 * f).recip())
+    }
+
+    /// Tangent function.
+    /// d/dx tan(x) = 1 / cos^2(x) = 1 + tan^2(x)
+    #[must_use]
+    #[inline]
+    pub fn tan(self) -> Self {
+        let f = self.value.tan();
+        self.chain(f, 1.0 + f * f)
+    }
+
+    /// Hyperbolic tangent function.
+    /// d/dx tanh(x) = 1 - tanh^2(x)
+    #[must_use]
+    #[inline]
+    pub fn tanh(self) -> Self {
+        let f = self.value.tanh();
+        self.chain(f, 1.0 - f * f)
+    }
+
+    /// Error function.
+    /// d/dx erf(x) = 2e^(-x^2) / sqrt(PI)
+    #[must_use]
+    #[inline]
+    pub fn erf(self) -> Self {
+        let x = self.value;
+        self.chain(
+            errorfunctions::RealErrorFunctions::erf(x),
+            2.0 * (-x * x).exp() / PI.sqrt(),
+        )
+    }
+
+    /// Error function (complementary).
+    /// d/dx erfc(x) = -2e^(-x^2) / sqrt(PI)
+    #[must_use]
+    #[inline]
+    pub fn erfc(self) -> Self {
+        let x = self.value;
+        self.chain(
+            errorfunctions::RealErrorFunctions::erfc(x),
+            -2.0 * (-x * x).exp() / PI.sqrt(),
+        )
+    }
+
+    /// Standard normal cumulative distribution function.
+    /// d/dx N(x) = n(x)
+    #[must_use]
+    #[inline]
+    pub fn norm_cdf(self) -> Self {
+        let x = self.value;
+        self.chain(
+            0.5 * errorfunctions::RealErrorFunctions::erfc(-x / SQRT_2),
+            standard_normal_density(x),
+        )
+    }
+
+    /// Standard normal probability density function.
+    /// d/dx n(x) = -x n(x)
+    #[must_use]
+    #[inline]
+    pub fn norm_pdf(self) -> Self {
+        let density = standard_normal_density(self.value);
+        self.chain(density, -self.value * density)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: POWER, LOGARITHM, MIN AND MAX TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Dual ^ Dual
+// d(x^y) = y x^(y-1) dx + x^y ln(x) dy
+impl Powf<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn powf(&self, other: Dual) -> Self::Output {
+        let f = self.value.powf(other.value);
+
+        // Skip the ln(x) term for constant exponents, so x <= 0 stays finite.
+        let d_exponent = if other.derivative == 0.0 {
+            0.0
+        } else {
+            f * self.value.ln() * other.derivative
+        };
+
+        Dual::new(
+            f,
+            other.value * self.value.powf(other.value - 1.0) * self.derivative + d_exponent,
+        )
+    }
+}
+
+// Dual ^ f64
+impl Powf<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn powf(&self, n: f64) -> Self::Output {
+        self.chain(self.value.powf(n), n * self.value.powf(n - 1.0))
+    }
+}
+
+// f64 ^ Dual
+impl Powf<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn powf(&self, other: Dual) -> Self::Output {
+        let f = f64::powf(*self, other.value);
+        other.chain(f, f * f64::ln(*self))
+    }
+}
+
+// Dual ^ i32
+impl Powi<i32> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn powi(&self, n: i32) -> Self::Output {
+        self.chain(self.value.powi(n), f64::from(n) * self.value.powi(n - 1))
+    }
+}
+
+// log_{Dual}(Dual) = ln(y) / ln(x)
+impl Log<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn log(&self, base: Dual) -> Self::Output {
+        self.ln() / base.ln()
+    }
+}
+
+// log_{f64}(Dual)
+impl Log<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn log(&self, base: f64) -> Self::Output {
+        self.chain(self.value.log(base), (self.value * base.ln()).recip())
+    }
+}
+
+// log_{Dual}(f64)
+impl Log<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn log(&self, base: Dual) -> Self::Output {
+        self.ln() / base.ln()
+    }
+}
+
+// min{ Dual, Dual }
+impl Min<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn min(&self, rhs: Dual) -> Self::Output {
+        if self.value < rhs.value {
+            *self
+        } else {
+            rhs
+        }
+    }
+}
+
+// min{ Dual, f64 }
+impl Min<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn min(&self, rhs: f64) -> Self::Output {
+        Min::min(self, Dual::constant(rhs))
+    }
+}
+
+// min{ f64, Dual }
+impl Min<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn min(&self, rhs: Dual) -> Self::Output {
+        Min::min(&Dual::constant(*self), rhs)
+    }
+}
+
+// max{ Dual, Dual }
+impl Max<Dual> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn max(&self, rhs: Dual) -> Self::Output {
+        if self.value > rhs.value {
+            *self
+        } else {
+            rhs
+        }
+    }
+}
+
+// max{ Dual, f64 }
+impl Max<f64> for Dual {
+    type Output = Dual;
+
+    #[inline]
+    fn max(&self, rhs: f64) -> Self::Output {
+        Max::max(self, Dual::constant(rhs))
+    }
+}
+
+// max{ f64, Dual }
+impl Max<Dual> for f64 {
+    type Output = Dual;
+
+    #[inline]
+    fn max(&self, rhs: Dual) -> Self::Output {
+        Max::max(&Dual::constant(*self), rhs)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_dual {
+    use super::*;
+    use crate::{Accumulate, Gradient, Graph, Variable};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_arithmetic() {
+        let x = Dual::variable(3.0);
+
+        let z = 2.0 * x * x - x / 4.0 + 1.0 - 1.0 / x;
+
+        // d/dx = 4x - 1/4 + 1/x^2
+        assert_approx_equal!(z.value, 18.0 - 0.75 + 1.0 - 1.0 / 3.0, 1e-12);
+        assert_approx_equal!(z.derivative, 12.0 - 0.25 + 1.0 / 9.0, 1e-12);
+    }
+
+    #[test]
+    fn test_assign_sum_product() {
+        let xs = [
+            Dual::variable(2.0),
+            Dual::constant(3.0),
+            Dual::variable(4.0),
+        ];
+
+        let sum: Dual = xs.iter().copied().sum();
+        let product: Dual = xs.iter().copied().product();
+        assert_eq!(sum, Dual::new(9.0, 2.0));
+        assert_eq!(product, Dual::new(24.0, 3.0 * (4.0 + 2.0)));
+
+        let mut z = Dual::variable(2.0);
+        z *= z;
+        z += 1.0;
+        z /= 5.0;
+        z -= Dual::constant(1.0);
+        assert_eq!(z, Dual::new(0.0, 0.8));
+    }
+
+    #[test]
+    fn test_primitives_match_reverse_mode() {
+        type Primitive = (fn(Dual) -> Dual, for<'v> fn(Variable<'v>) -> Variable<'v>);
+
+        let functions: [Primitive; 14] = [
+            (Dual::sin, |x| x.sin()),
+            (Dual::cos, |x| x.cos()),
+            (Dual::tan, |x| x.tan()),
+            (Dual::exp, |x| x.exp()),
+            (Dual::ln, |x| x.ln()),
+            (Dual::sqrt, |x| x.sqrt()),
+            (Dual::cbrt, |x| x.cbrt()),
+            (Dual::asin, |x| x.asin()),
+            (Dual::atan, |x| x.atan()),
+            (Dual::tanh, |x| x.tanh()),
+            (Dual::sinh, |x| x.sinh()),
+            (Dual::erf, |x| x.erf()),
+            (Dual::norm_cdf, |x| x.norm_cdf()),
+            (Dual::norm_pdf, |x| x.norm_pdf()),
+        ];
+
+        for (forward, reverse) in functions {
+            let g = Graph::new();
+            let x = g.var(0.3);
+            let z = reverse(x);
+
+            let dual = forward(Dual::variable(0.3));
+
+            assert_approx_equal!(dual.value, z.value, 1e-12);
+            assert_approx_equal!(dual.derivative, z.accumulate().wrt(&x), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_power_and_log() {
+        let x = Dual::variable(2.0);
+
+        assert_eq!(x.powi(3), Dual::new(8.0, 12.0));
+        assert_approx_equal!(x.powf(0.5).derivative, 0.5 / 2.0_f64.sqrt(), 1e-15);
+        assert_approx_equal!(x.powf(x).derivative, 4.0 * (1.0 + 2.0_f64.ln()), 1e-12);
+        assert_approx_equal!(3.0.powf(x).derivative, 9.0 * 3.0_f64.ln(), 1e-12);
+        assert_approx_equal!(x.log(10.0).derivative, 1.0 / (2.0 * LN_10), 1e-12);
+
+        // Constant exponent of a negative base.
+        assert_eq!(
+            Dual::variable(-2.0).powf(Dual::constant(2.0)).derivative,
+            -4.0
+        );
+    }
+
+    #[test]
+    fn test_min_max() {
+        let x = Dual::variable(2.0);
+
+        assert_eq!(Min::min(&x, 3.0), x);
+        assert_eq!(Max::max(&x, 3.0), Dual::constant(3.0));
+        assert_eq!(Max::max(&1.0, x), x);
+    }
+
+    #[test]
+    fn test_vector_valued_function() {
+        // f(r) = [e^(-r), e^(-2r), e^(-3r)], discount factors in the rate.
+        let r = Dual::variable(0.05);
+
+        let dfs: Vec<Dual> = (1..=3).map(|t| (-r * f64::from(t)).exp()).collect();
+
+        for (t, df) in (1..=3).zip(dfs) {
+            let t = f64::from(t);
+            assert_approx_equal!(df.derivative, -t * (-0.05 * t).exp(), 1e-12);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! The floating point types that can be recorded on a `Graph`.
+//!
+//! The graph, its vertices and variables are generic over the `Float` trait,
+//! and default to `f64`. Single precision (`f32`) tapes use half the memory,
+//! which matters for long tapes, e.g. Monte Carlo simulations, at the cost
+//! of precision in the adjoints.
+//!
+//! ```
+//! # use RustQuant_autodiff::*;
+//!
+//! let g = Graph::<f32>::default();
+//!
+//! let x = g.var(2.0);
+//! let y = g.var(3.0);
+//! let z = x * y + x.sin();
+//!
+//! let grad = z.accumulate();
+//!
+//! assert!((grad.wrt(&x) - (3.0 + 2_f32.cos())).abs() < 1e-6);
+//! assert!((grad.wrt(&y) - 2.0).abs() < 1e-6);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use errorfunctions::RealErrorFunctions;
+use num::traits::FloatConst;
+use std::fmt::{Debug, Display};
+use std::iter::{Product, Sum};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FLOAT TRAIT AND IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Floating point type of the values and partials stored on the graph.
+pub trait Float:
+    num::Float
+    + FloatConst
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + Product
+    + Default
+    + Debug
+    + Display
+    + Send
+    + Sync
+    + 'static
+{
+    /// Convert an `f64` constant, e.g. a coefficient of a derivative.
+    fn from_f64(x: f64) -> Self;
+
+    /// Error function.
+    fn erf(x: Self) -> Self;
+
+    /// Complementary error function.
+    fn erfc(x: Self) -> Self;
+}
+
+impl Float for f64 {
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+
+    #[inline]
+    fn erf(x: Self) -> Self {
+        RealErrorFunctions::erf(x)
+    }
+
+    #[inline]
+    fn erfc(x: Self) -> Self {
+        RealErrorFunctions::erfc(x)
+    }
+}
+
+// The error functions are evaluated in double precision.
+impl Float for f32 {
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+
+    #[inline]
+    fn erf(x: Self) -> Self {
+        RealErrorFunctions::erf(f64::from(x)) as f32
+    }
+
+    #[inline]
+    fn erfc(x: Self) -> Self {
+        RealErrorFunctions::erfc(f64::from(x)) as f32
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_float {
+    use crate::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn black_scholes<'v, T: Float>(inputs: &[Variable<'v, T>]) -> Variable<'v, T> {
+        let (s, k, r, sigma, t) = (inputs[0], inputs[1], inputs[2], inputs[3], inputs[4]);
+
+        let d1 = ((s / k).ln() + (r + sigma * sigma / T::from_f64(2.0)) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+
+        s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+    }
+
+    #[test]
+    fn test_f32_matches_f64() {
+        let inputs = [100.0, 95.0, 0.05, 0.2, 0.5];
+
+        let g64 = Graph::new();
+        let x64 = g64.vars(&inputs);
+        let y64 = black_scholes(&x64);
+        let grad64 = y64.accumulate().wrt(&x64);
+
+        let g32 = Graph::<f32>::default();
+        let x32 = g32.vars(&inputs.map(|x| x as f32));
+        let y32 = black_scholes(&x32);
+        let grad32 = y32.accumulate().wrt(&x32);
+
+        assert_approx_equal!(f64::from(y32.value), y64.value, 1e-3);
+        for (a, b) in grad32.iter().zip(&grad64) {
+            assert_approx_equal!(f64::from(*a), *b, 1e-3);
+        }
+
+        // The f32 tape stores half as many bytes per vertex.
+        assert_eq!(g32.len(), g64.len());
+        assert!(std::mem::size_of::<Vertex<f32>>() < std::mem::size_of::<Vertex<f64>>());
+    }
+
+    #[test]
+    fn test_f32_scalars_on_the_left() {
+        let g = Graph::<f32>::default();
+
+        let x = g.var(2.0);
+        let z = 3.0 * x - 1.0 / x + (1.0 - x) * 2.0;
+
+        assert_approx_equal!(z.value, 3.5, 1e-6);
+        assert_approx_equal!(z.accumulate().wrt(&x), 1.25, 1e-6);
+    }
+
+    #[test]
+    fn test_f32_hessian() {
+        let g = Graph::<f32>::default();
+
+        let x = g.var(1.5);
+        let y = g.var(-0.5);
+
+        // f(x, y) = x^2 y + e^y
+        let f = x.powi(2) * y + y.exp();
+        let hessian = f.hessian().wrt([x, y]);
+
+        assert_approx_equal!(hessian[[0, 0]], -1.0, 1e-6);
+        assert_approx_equal!(hessian[[0, 1]], 3.0, 1e-6);
+        assert_approx_equal!(hessian[[1, 1]], (-0.5_f32).exp(), 1e-6);
+    }
+}
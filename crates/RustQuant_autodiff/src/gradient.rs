@@ -14,7 +14,7 @@
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::variable::Variable;
+use crate::{variable::Variable, Float};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // GRADIENT TRAIT AND IMPLEMENTATIONS
@@ -32,41 +32,41 @@ pub trait Gradient<IN, OUT> {
 }
 
 /// `wrt` a single variable.
-impl<'v> Gradient<&Variable<'v>, f64> for Vec<f64> {
+impl<'v, T: Float> Gradient<&Variable<'v, T>, T> for Vec<T> {
     #[inline]
-    fn wrt(&self, variable: &Variable) -> f64 {
+    fn wrt(&self, variable: &Variable<'v, T>) -> T {
         self[variable.index]
     }
 }
 
 /// `wrt` a borrowed vector of variables.
-impl<'v> Gradient<&Vec<Variable<'v>>, Vec<f64>> for Vec<f64> {
+impl<'v, T: Float> Gradient<&Vec<Variable<'v, T>>, Vec<T>> for Vec<T> {
     #[inline]
-    fn wrt(&self, variables: &Vec<Variable<'v>>) -> Vec<f64> {
+    fn wrt(&self, variables: &Vec<Variable<'v, T>>) -> Vec<T> {
         variables.iter().map(|&var| self[var.index]).collect()
     }
 }
 
 /// `wrt` a borrowed slice of variables.
-impl<'v> Gradient<&[Variable<'v>], Vec<f64>> for Vec<f64> {
+impl<'v, T: Float> Gradient<&[Variable<'v, T>], Vec<T>> for Vec<T> {
     #[inline]
-    fn wrt(&self, variables: &[Variable<'v>]) -> Vec<f64> {
+    fn wrt(&self, variables: &[Variable<'v, T>]) -> Vec<T> {
         variables.iter().map(|&var| self[var.index]).collect()
     }
 }
 
 /// `wrt` an array of variables.
-impl<'v, const N: usize> Gradient<[Variable<'v>; N], Vec<f64>> for Vec<f64> {
+impl<'v, T: Float, const N: usize> Gradient<[Variable<'v, T>; N], Vec<T>> for Vec<T> {
     #[inline]
-    fn wrt(&self, variables: [Variable<'v>; N]) -> Vec<f64> {
+    fn wrt(&self, variables: [Variable<'v, T>; N]) -> Vec<T> {
         variables.iter().map(|&var| self[var.index]).collect()
     }
 }
 
 /// `wrt` a borrowed array of variables.
-impl<'v, const N: usize> Gradient<&[Variable<'v>; N], Vec<f64>> for Vec<f64> {
+impl<'v, T: Float, const N: usize> Gradient<&[Variable<'v, T>; N], Vec<T>> for Vec<T> {
     #[inline]
-    fn wrt(&self, variables: &[Variable<'v>; N]) -> Vec<f64> {
+    fn wrt(&self, variables: &[Variable<'v, T>; N]) -> Vec<T> {
         variables.iter().map(|&var| self[var.index]).collect()
     }
 }
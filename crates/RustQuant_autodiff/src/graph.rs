@@ -17,7 +17,7 @@
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::{variable::Variable, Arity, Vertex};
+use crate::{variable::Variable, Arity, Float, Operation, Vertex};
 use std::cell::RefCell;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -25,21 +25,27 @@ use std::cell::RefCell;
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Struct to contain the graph (Wengert list), as a vector of `Vertex`s.
+///
+/// The values and partials are stored as `T`, which defaults to `f64`.
 #[derive(Debug, Clone)]
-pub struct Graph {
+pub struct Graph<T: Float = f64> {
     /// Vector containing the vertices in the Wengert List.
-    pub vertices: RefCell<Vec<Vertex>>,
+    pub vertices: RefCell<Vec<Vertex<T>>>,
 }
 // pub struct Graph(RefCell<Rc<[Vertex]>>);
 
-impl Default for Graph {
+/// Graphs of other float types are created with `Graph::<f32>::default()`.
+impl<T: Float> Default for Graph<T> {
     #[inline]
     fn default() -> Self {
-        Self::new()
+        Self {
+            vertices: RefCell::new(Vec::new()),
+        }
     }
 }
 
-/// Implementation for the `Graph` struct.
+/// Constructors for `f64` graphs.
+/// They are not generic, so that `Graph::new()` does not need a type annotation.
 impl Graph {
     /// Instantiate a new graph.
     #[must_use]
@@ -60,7 +66,10 @@ impl Graph {
             // vertices: RefCell::new(Rc::new([])),
         }
     }
+}
 
+/// Implementation for the `Graph` struct.
+impl<T: Float> Graph<T> {
     /// Join two graphs together.
     #[must_use]
     #[inline]
@@ -74,18 +83,18 @@ impl Graph {
     /// Add a new variable to the graph.
     /// Returns a new `Variable` instance (the contents of a vertex).
     #[inline]
-    pub fn var(&self, value: f64) -> Variable {
+    pub fn var(&self, value: T) -> Variable<T> {
         Variable {
             graph: self,
             value,
-            index: self.push(Arity::Nullary, &[], &[]),
+            index: self.push(Operation::Input(value), Arity::Nullary, &[], &[]),
         }
     }
 
     /// Add multiple variables (a slice) to the graph.
     /// Useful for larger functions with many inputs.
     #[inline]
-    pub fn vars<'v>(&'v self, values: &[f64]) -> Vec<Variable<'v>> {
+    pub fn vars<'v>(&'v self, values: &[T]) -> Vec<Variable<'v, T>> {
         values.iter().map(|&val| self.var(val)).collect()
     }
 
@@ -107,13 +116,55 @@ impl Graph {
         self.vertices.borrow_mut().clear();
     }
 
+    /// Truncates the graph to its first `len` vertices.
+    /// Variables pushed after those are invalidated.
+    #[inline]
+    pub fn truncate(&self, len: usize) {
+        self.vertices.borrow_mut().truncate(len);
+    }
+
+    /// Records a region of the graph, and truncates the graph back to its
+    /// current length once the region returns, so the tape can be reused
+    /// (e.g. once per Monte Carlo path).
+    ///
+    /// Variables created inside the region cannot be returned from it,
+    /// so results should be extracted as values and gradients.
+    ///
+    /// ```
+    /// # use RustQuant_autodiff::*;
+    ///
+    /// let g = Graph::new();
+    /// let x = g.var(2.0);
+    ///
+    /// let mut sum = 0.0;
+    ///
+    /// for k in 0..100 {
+    ///     sum += g.scope(|s| {
+    ///         let y = s.var(f64::from(k)) * x.exp();
+    ///         y.accumulate().wrt(&x)
+    ///     });
+    /// }
+    ///
+    /// // Only `x` is left on the graph.
+    /// assert_eq!(g.len(), 1);
+    /// assert!((sum - 4950.0 * 2_f64.exp()).abs() < 1e-9);
+    /// ```
+    #[inline]
+    pub fn scope<R>(&self, region: impl for<'s> FnOnce(&'s Graph<T>) -> R) -> R {
+        let len = self.len();
+        let result = region(self);
+        self.truncate(len);
+
+        result
+    }
+
     /// Zeroes the adjoints in the graph.
     #[inline]
     pub fn zero(&self) {
-        self.vertices
-            .borrow_mut()
-            .iter_mut()
-            .for_each(|vertex| vertex.partials = [0.0; 2]);
+        self.vertices.borrow_mut().iter_mut().for_each(|vertex| {
+            vertex.partials = [T::zero(); 2];
+            vertex.second_partials = [T::zero(); 3];
+        });
     }
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -122,7 +173,28 @@ impl Graph {
 
     /// Pushes a vertex to the graph.
     #[inline]
-    pub fn push(&self, arity: Arity, parents: &[usize], partials: &[f64]) -> usize {
+    pub fn push(
+        &self,
+        operation: Operation<T>,
+        arity: Arity,
+        parents: &[usize],
+        partials: &[T],
+    ) -> usize {
+        self.push_second_order(operation, arity, parents, partials, [T::zero(); 3])
+    }
+
+    /// Pushes a vertex to the graph, along with the second order partials
+    /// of the operation, `[d2/dx2, d2/dxdy, d2/dy2]`.
+    /// Linear operations can use `push`, since their second order partials are zero.
+    #[inline]
+    pub fn push_second_order(
+        &self,
+        operation: Operation<T>,
+        arity: Arity,
+        parents: &[usize],
+        partials: &[T],
+        second_partials: [T; 3],
+    ) -> usize {
         let mut vertices = self.vertices.borrow_mut();
         let len = vertices.len();
 
@@ -140,8 +212,10 @@ impl Graph {
                 assert!(parents.is_empty());
 
                 Vertex {
-                    partials: [0.0, 0.0],
+                    partials: [T::zero(); 2],
                     parents: [len, len],
+                    second_partials: [T::zero(); 3],
+                    operation,
                 }
             }
             // Unary operator pushback.
@@ -157,8 +231,10 @@ impl Graph {
                 assert!(parents.len() == 1);
 
                 Vertex {
-                    partials: [partials[0], 0.0],
+                    partials: [partials[0], T::zero()],
                     parents: [parents[0], len],
+                    second_partials: [second_partials[0], T::zero(), T::zero()],
+                    operation,
                 }
             }
             // Binary operator pushback.
@@ -176,6 +252,8 @@ impl Graph {
                 Vertex {
                     partials: [partials[0], partials[1]],
                     parents: [parents[0], parents[1]],
+                    second_partials,
+                    operation,
                 }
             }
         };
@@ -185,3 +263,47 @@ impl Graph {
         len
     }
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_graph {
+    use crate::*;
+
+    #[test]
+    fn test_truncate() {
+        let g = Graph::new();
+
+        let x = g.var(1.0);
+        let y = g.var(2.0);
+        let _z = x * y;
+        assert_eq!(g.len(), 3);
+
+        g.truncate(2);
+        assert_eq!(g.len(), 2);
+
+        // The tape is reused from the truncation point.
+        let z = x * y;
+        assert_eq!(z.index, 2);
+        assert_eq!(z.accumulate().wrt(&[x, y]), vec![2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_scope() {
+        let g = Graph::new();
+        let x = g.var(3.0);
+
+        let gradient = g.scope(|s| {
+            let y = s.var(2.0);
+            let z = (x * y).sin();
+            assert_eq!(s.len(), 4);
+
+            z.accumulate().wrt(&x)
+        });
+
+        assert_eq!(gradient, 2.0 * 6.0_f64.cos());
+        assert_eq!(g.len(), 1);
+    }
+}
@@ -0,0 +1,241 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! This module contains the Hessian computation.
+//!
+//! Hessians are accumulated forward-over-reverse on the graph:
+//! for each chosen variable, a forward sweep propagates its tangent through
+//! the graph, and a reverse sweep propagates the tangents of the adjoints,
+//! using the second order partials stored in each vertex.
+//! Each sweep gives one column of the Hessian, so the cost is one pass over
+//! the graph per chosen variable.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{accumulate::Accumulate, variable::Variable, Float};
+use ndarray::Array2;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// HESSIAN STRUCT AND IMPLEMENTATION
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Hessian of a `Variable`, with respect to variables chosen with `wrt`.
+#[derive(Clone, Copy, Debug)]
+pub struct Hessian<'v, T: Float = f64> {
+    /// The (scalar) output of the function.
+    pub output: Variable<'v, T>,
+}
+
+impl<'v, T: Float> Variable<'v, T> {
+    /// Hessian of the variable.
+    ///
+    /// ```
+    /// # use RustQuant_autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let x = g.var(2.0);
+    /// let y = g.var(3.0);
+    ///
+    /// // f(x, y) = x^2 y + y^3
+    /// let f = x.powi(2) * y + y.powi(3);
+    ///
+    /// let hessian = f.hessian().wrt([x, y]);
+    ///
+    /// assert_eq!(hessian, ndarray::array![[6.0, 4.0], [4.0, 18.0]]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn hessian(&self) -> Hessian<'v, T> {
+        Hessian { output: *self }
+    }
+}
+
+impl<'v, T: Float> Hessian<'v, T> {
+    /// Returns the second derivatives *with-respect-to* the chosen variables,
+    /// as a symmetric matrix.
+    pub fn wrt<V: AsRef<[Variable<'v, T>]>>(&self, variables: V) -> Array2<T> {
+        let variables = variables.as_ref();
+        let n = variables.len();
+
+        let adjoints = self.output.accumulate();
+        let vertices = self.output.graph.vertices.borrow();
+        let vertices = &vertices[..=self.output.index];
+
+        let mut hessian = Array2::<T>::zeros((n, n));
+
+        for (column, variable) in variables.iter().enumerate() {
+            assert!(std::ptr::eq(self.output.graph, variable.graph));
+
+            // Forward sweep: tangents of each vertex in the direction of the variable.
+            let mut tangents = vec![T::zero(); vertices.len()];
+            if variable.index < vertices.len() {
+                tangents[variable.index] = T::one();
+            }
+
+            for (index, vertex) in vertices.iter().enumerate() {
+                let [x, y] = vertex.parents;
+                let tangent = vertex.partials[0] * tangents[x] + vertex.partials[1] * tangents[y];
+
+                tangents[index] += tangent;
+            }
+
+            // Reverse sweep: tangents of the adjoints.
+            // The chain rule term is as for the adjoints, the second order
+            // term is the adjoint times the tangent of the partials.
+            let mut adjoint_tangents = vec![T::zero(); vertices.len()];
+
+            for (index, vertex) in vertices.iter().enumerate().rev() {
+                let [x, y] = vertex.parents;
+                let [xx, xy, yy] = vertex.second_partials;
+                let (adjoint, adjoint_tangent) = (adjoints[index], adjoint_tangents[index]);
+
+                let (tangent_x, tangent_y) = (tangents[x], tangents[y]);
+
+                adjoint_tangents[x] += vertex.partials[0] * adjoint_tangent
+                    + adjoint * (xx * tangent_x + xy * tangent_y);
+                adjoint_tangents[y] += vertex.partials[1] * adjoint_tangent
+                    + adjoint * (xy * tangent_x + yy * tangent_y);
+            }
+
+            for (row, other) in variables.iter().enumerate() {
+                if other.index < vertices.len() {
+                    hessian[[row, column]] = adjoint_tangents[other.index];
+                }
+            }
+        }
+
+        // Symmetrise, to remove rounding differences between the sweeps.
+        let half = T::from_f64(0.5);
+        Array2::from_shape_fn((n, n), |(i, j)| half * (hessian[[i, j]] + hessian[[j, i]]))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_hessian {
+    use crate::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_quadratic() {
+        let g = Graph::new();
+
+        let x = g.var(1.5);
+        let y = g.var(-2.0);
+
+        // f(x, y) = 3x^2 + 2xy - y^2 + x
+        let f = 3.0 * x * x + 2.0 * x * y - y * y + x;
+        let hessian = f.hessian().wrt([x, y]);
+
+        assert_eq!(hessian, ndarray::array![[6.0, 2.0], [2.0, -2.0]]);
+    }
+
+    #[test]
+    fn test_symmetric() {
+        let g = Graph::new();
+
+        let x = g.var(0.5);
+        let y = g.var(1.2);
+        let z = g.var(2.0);
+
+        // f(x, y, z) = sin(xy) e^z / z
+        let f = (x * y).sin() * z.exp() / z;
+        let hessian = f.hessian().wrt([x, y, z]);
+
+        let (a, b, c) = (0.5_f64, 1.2_f64, 2.0_f64);
+        let (sin, cos, e) = ((a * b).sin(), (a * b).cos(), c.exp() / c);
+        let dz = c.exp() * (c - 1.0) / (c * c);
+        let dzz = c.exp() * (c * c - 2.0 * c + 2.0) / (c * c * c);
+
+        let expected = [
+            [-b * b * sin * e, (cos - a * b * sin) * e, b * cos * dz],
+            [(cos - a * b * sin) * e, -a * a * sin * e, a * cos * dz],
+            [b * cos * dz, a * cos * dz, sin * dzz],
+        ];
+
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_equal!(hessian[[i, j]], expected[i][j], 1e-12);
+                assert_eq!(hessian[[i, j]], hessian[[j, i]]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unary_functions() {
+        let g = Graph::new();
+
+        let x = g.var(0.3);
+
+        let functions: [(Variable, f64); 9] = [
+            (x.exp(), 0.3_f64.exp()),
+            (x.ln(), -1.0 / 0.09),
+            (x.sqrt(), -0.25 / 0.3_f64.powf(1.5)),
+            (x.sin(), -0.3_f64.sin()),
+            (x.tanh(), -2.0 * 0.3_f64.tanh() / 0.3_f64.cosh().powi(2)),
+            (x.atan(), -0.6 / 1.09_f64.powi(2)),
+            (x.recip(), 2.0 / 0.027),
+            (
+                x.erf(),
+                -4.0 * 0.3 * (-0.09_f64).exp() / std::f64::consts::PI.sqrt(),
+            ),
+            (
+                2.0.powf(x),
+                2.0_f64.powf(0.3) * std::f64::consts::LN_2.powi(2),
+            ),
+        ];
+
+        for (f, expected) in functions {
+            assert_approx_equal!(f.hessian().wrt([x])[[0, 0]], expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_matches_hyper_dual() {
+        let g = Graph::new();
+
+        let (a, b) = (1.3, 0.8);
+        let x = g.var(a);
+        let y = g.var(b);
+
+        let f = x.powf(y) + (x / y).ln() * x.cos();
+        let hessian = f.hessian().wrt([x, y]);
+
+        let h = |x: HyperDual, y: HyperDual| x.powf(y) + (x / y).ln() * x.cos();
+        let xx = h(HyperDual::variable(a), HyperDual::constant(b));
+        let yy = h(HyperDual::constant(a), HyperDual::variable(b));
+        let xy = h(
+            HyperDual::new(a, 1.0, 0.0, 0.0),
+            HyperDual::new(b, 0.0, 1.0, 0.0),
+        );
+
+        assert_approx_equal!(hessian[[0, 0]], xx.e1e2, 1e-12);
+        assert_approx_equal!(hessian[[1, 1]], yy.e1e2, 1e-12);
+        assert_approx_equal!(hessian[[0, 1]], xy.e1e2, 1e-12);
+    }
+
+    #[test]
+    fn test_unused_variable() {
+        let g = Graph::new();
+
+        let x = g.var(2.0);
+        let f = x * x;
+        let y = g.var(1.0);
+
+        let hessian = f.hessian().wrt([x, y]);
+
+        assert_eq!(hessian, ndarray::array![[2.0, 0.0], [0.0, 0.0]]);
+    }
+}
@@ -0,0 +1,938 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Second order forward mode with hyper-dual numbers.
+//!
+//! A hyper-dual number $a + b \epsilon_1 + c \epsilon_2 + d \epsilon_1 \epsilon_2$
+//! has two nilpotent parts, $\epsilon_1^2 = \epsilon_2^2 = 0$.
+//! Seeding $x$ along $\epsilon_1$ and $y$ along $\epsilon_2$ gives
+//! $\partial f / \partial x$, $\partial f / \partial y$ and
+//! $\partial^2 f / \partial x \partial y$ in one evaluation, exactly and
+//! without the cancellation error of finite differences.
+//!
+//! ```
+//! # use RustQuant_autodiff::*;
+//!
+//! // Second derivative of x^3 at x = 2.
+//! let x = HyperDual::variable(2.0);
+//! let f = x.powi(3);
+//!
+//! assert_eq!(f.value, 8.0);
+//! assert_eq!(f.first_derivative(), 12.0);
+//! assert_eq!(f.second_derivative(), 12.0);
+//!
+//! // Cross derivative of x * y^2 at (x, y) = (3, 5).
+//! let x = HyperDual::new(3.0, 1.0, 0.0, 0.0);
+//! let y = HyperDual::new(5.0, 0.0, 1.0, 0.0);
+//! let f = x * y.powi(2);
+//!
+//! assert_eq!(f.e1, 25.0);
+//! assert_eq!(f.e2, 30.0);
+//! assert_eq!(f.e1e2, 10.0);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::overload::{standard_normal_density, Log, Max, Min, Powf, Powi};
+use std::f64::consts::{LN_10, LN_2, PI, SQRT_2};
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign};
+use std::ops::{Div, DivAssign};
+use std::ops::{Mul, MulAssign};
+use std::ops::{Neg, Sub, SubAssign};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCT AND IMPLEMENTATION
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Hyper-dual number: a value, two tangents and their cross term.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HyperDual {
+    /// Value of the number.
+    pub value: f64,
+    /// Derivative along the first direction.
+    pub e1: f64,
+    /// Derivative along the second direction.
+    pub e2: f64,
+    /// Second derivative along both directions.
+    pub e1e2: f64,
+}
+
+impl HyperDual {
+    /// Create a new hyper-dual number.
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f64, e1: f64, e2: f64, e1e2: f64) -> Self {
+        Self {
+            value,
+            e1,
+            e2,
+            e1e2,
+        }
+    }
+
+    /// Input variable, seeded along both directions, for the first and
+    /// second derivative with respect to a single input.
+    #[must_use]
+    #[inline]
+    pub const fn variable(value: f64) -> Self {
+        Self::new(value, 1.0, 1.0, 0.0)
+    }
+
+    /// Constant, with zero tangents.
+    #[must_use]
+    #[inline]
+    pub const fn constant(value: f64) -> Self {
+        Self::new(value, 0.0, 0.0, 0.0)
+    }
+
+    /// First derivative, along the first direction.
+    #[must_use]
+    #[inline]
+    pub fn first_derivative(&self) -> f64 {
+        self.e1
+    }
+
+    /// Second derivative, the cross term of both directions.
+    #[must_use]
+    #[inline]
+    pub fn second_derivative(&self) -> f64 {
+        self.e1e2
+    }
+
+    // Chain rule, to second order:
+    // f(x) + f'(x) x1 e1 + f'(x) x2 e2 + (f'(x) x12 + f''(x) x1 x2) e1e2
+    #[inline]
+    fn chain(self, value: f64, d1: f64, d2: f64) -> Self {
+        Self::new(
+            value,
+            d1 * self.e1,
+            d1 * self.e2,
+            d1 * self.e1e2 + d2 * self.e1 * self.e2,
+        )
+    }
+}
+
+/// Implement formatting for the `HyperDual` struct.
+impl Display for HyperDual {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} + {:?}ε1 + {:?}ε2 + {:?}ε1ε2",
+            self.value, self.e1, self.e2, self.e1e2
+        )
+    }
+}
+
+impl From<f64> for HyperDual {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl PartialEq<f64> for HyperDual {
+    #[inline]
+    fn eq(&self, other: &f64) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialOrd for HyperDual {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: STANDARD OPERATORS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// HyperDual + HyperDual
+impl Add<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn add(self, other: HyperDual) -> Self::Output {
+        HyperDual::new(
+            self.value + other.value,
+            self.e1 + other.e1,
+            self.e2 + other.e2,
+            self.e1e2 + other.e1e2,
+        )
+    }
+}
+
+/// HyperDual - HyperDual
+impl Sub<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn sub(self, other: HyperDual) -> Self::Output {
+        self + (-other)
+    }
+}
+
+/// HyperDual * HyperDual
+/// The e1e2 part collects the cross terms a1 b2 + a2 b1.
+impl Mul<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn mul(self, other: HyperDual) -> Self::Output {
+        HyperDual::new(
+            self.value * other.value,
+            self.e1 * other.value + self.value * other.e1,
+            self.e2 * other.value + self.value * other.e2,
+            self.e1e2 * other.value
+                + self.e1 * other.e2
+                + self.e2 * other.e1
+                + self.value * other.e1e2,
+        )
+    }
+}
+
+/// HyperDual / HyperDual
+impl Div<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn div(self, other: HyperDual) -> Self::Output {
+        Mul::mul(self, other.recip())
+    }
+}
+
+/// -HyperDual
+impl Neg for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        HyperDual::new(-self.value, -self.e1, -self.e2, -self.e1e2)
+    }
+}
+
+/// HyperDual + f64
+impl Add<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn add(self, other: f64) -> Self::Output {
+        HyperDual {
+            value: self.value + other,
+            ..self
+        }
+    }
+}
+
+/// f64 + HyperDual
+impl Add<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn add(self, other: HyperDual) -> Self::Output {
+        other + self
+    }
+}
+
+/// HyperDual - f64
+impl Sub<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn sub(self, other: f64) -> Self::Output {
+        self + (-other)
+    }
+}
+
+/// f64 - HyperDual
+impl Sub<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn sub(self, other: HyperDual) -> Self::Output {
+        -other + self
+    }
+}
+
+/// HyperDual * f64
+impl Mul<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self::Output {
+        HyperDual::new(
+            self.value * other,
+            self.e1 * other,
+            self.e2 * other,
+            self.e1e2 * other,
+        )
+    }
+}
+
+/// f64 * HyperDual
+impl Mul<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn mul(self, other: HyperDual) -> Self::Output {
+        other * self
+    }
+}
+
+/// HyperDual / f64
+impl Div<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn div(self, other: f64) -> Self::Output {
+        HyperDual::new(
+            self.value / other,
+            self.e1 / other,
+            self.e2 / other,
+            self.e1e2 / other,
+        )
+    }
+}
+
+/// f64 / HyperDual
+impl Div<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn div(self, other: HyperDual) -> Self::Output {
+        Mul::mul(self, other.recip())
+    }
+}
+
+/// HyperDual += HyperDual
+impl AddAssign<HyperDual> for HyperDual {
+    #[inline]
+    fn add_assign(&mut self, other: HyperDual) {
+        *self = *self + other;
+    }
+}
+
+/// HyperDual += f64
+impl AddAssign<f64> for HyperDual {
+    #[inline]
+    fn add_assign(&mut self, other: f64) {
+        *self = *self + other;
+    }
+}
+
+/// HyperDual -= HyperDual
+impl SubAssign<HyperDual> for HyperDual {
+    #[inline]
+    fn sub_assign(&mut self, other: HyperDual) {
+        *self = *self - other;
+    }
+}
+
+/// HyperDual -= f64
+impl SubAssign<f64> for HyperDual {
+    #[inline]
+    fn sub_assign(&mut self, other: f64) {
+        *self = *self - other;
+    }
+}
+
+/// HyperDual *= HyperDual
+impl MulAssign<HyperDual> for HyperDual {
+    #[inline]
+    fn mul_assign(&mut self, other: HyperDual) {
+        *self = *self * other;
+    }
+}
+
+/// HyperDual *= f64
+impl MulAssign<f64> for HyperDual {
+    #[inline]
+    fn mul_assign(&mut self, other: f64) {
+        *self = *self * other;
+    }
+}
+
+/// HyperDual /= HyperDual
+impl DivAssign<HyperDual> for HyperDual {
+    #[inline]
+    fn div_assign(&mut self, other: HyperDual) {
+        *self = *self / other;
+    }
+}
+
+/// HyperDual /= f64
+impl DivAssign<f64> for HyperDual {
+    #[inline]
+    fn div_assign(&mut self, other: f64) {
+        *self = *self / other;
+    }
+}
+
+/// Sum of hyper-dual numbers.
+impl Sum<HyperDual> for HyperDual {
+    #[inline]
+    fn sum<I: Iterator<Item = HyperDual>>(iter: I) -> Self {
+        iter.fold(HyperDual::constant(0.0), |acc, x| acc + x)
+    }
+}
+
+/// Product of hyper-dual numbers.
+impl Product<HyperDual> for HyperDual {
+    #[inline]
+    fn product<I: Iterator<Item = HyperDual>>(iter: I) -> Self {
+        iter.fold(HyperDual::constant(1.0), |acc, x| acc * x)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: PRIMITIVE FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl HyperDual {
+    /// Absolute value function.
+    /// d/dx abs(x) = sign(x), d2/dx2 abs(x) = 0
+    #[must_use]
+    #[inline]
+    pub fn abs(self) -> Self {
+        self.chain(self.value.abs(), self.value.signum(), 0.0)
+    }
+
+    /// Inverse cosine function.
+    /// d/dx cos^-1(x) = -1 / sqrt(1 - x^2)
+    /// d2/dx2 cos^-1(x) = -x / (1 - x^2)^(3/2)
+    #[must_use]
+    #[inline]
+    pub fn acos(self) -> Self {
+        let x = self.value;
+        let u = 1.0 - x * x;
+        self.chain(x.acos(), -u.sqrt().recip(), -x / (u * u.sqrt()))
+    }
+
+    /// Inverse hyperbolic cosine function.
+    /// d/dx cosh^-1(x) = 1 / sqrt(x^2 - 1)
+    /// d2/dx2 cosh^-1(x) = -x / (x^2 - 1)^(3/2)
+    #[must_use]
+    #[inline]
+    pub fn acosh(self) -> Self {
+        let x = self.value;
+        let u = x * x - 1.0;
+        self.chain(x.acosh(), u.sqrt().recip(), -x / (u * u.sqrt()))
+    }
+
+    /// Inverse sine function.
+    /// d/dx sin^-1(x) = 1 / sqrt(1 - x^2)
+    /// d2/dx2 sin^-1(x) = x / (1 - x^2)^(3/2)
+    #[must_use]
+    #[inline]
+    pub fn asin(self) -> Self {
+        let x = self.value;
+        let u = 1.0 - x * x;
+        self.chain(x.asin(), u.sqrt().recip(), x / (u * u.sqrt()))
+    }
+
+    /// Inverse hyperbolic sine function.
+    /// d/dx sinh^-1(x) = 1 / sqrt(1 + x^2)
+    /// d2/dx2 sinh^-1(x) = -x / (1 + x^2)^(3/2)
+    #[must_use]
+    #[inline]
+    pub fn asinh(self) -> Self {
+        let x = self.value;
+        let u = 1.0 + x * x;
+        self.chain(x.asinh(), u.sqrt().recip(), -x / (u * u.sqrt()))
+    }
+
+    /// Inverse tangent function.
+    /// d/dx tan^-1(x) = 1 / (1 + x^2)
+    /// d2/dx2 tan^-1(x) = -2x / (1 + x^2)^2
+    #[must_use]
+    #[inline]
+    pub fn atan(self) -> Self {
+        let x = self.value;
+        let u = 1.0 + x * x;
+        self.chain(x.atan(), u.recip(), -2.0 * x / (u * u))
+    }
+
+    /// Inverse hyperbolic tangent function.
+    /// d/dx tanh^-1(x) = 1 / (1 - x^2)
+    /// d2/dx2 tanh^-1(x) = 2x / (1 - x^2)^2
+    #[must_use]
+    #[inline]
+    pub fn atanh(self) -> Self {
+        let x = self.value;
+        let u = 1.0 - x * x;
+        self.chain(x.atanh(), u.recip(), 2.0 * x / (u * u))
+    }
+
+    /// Cuberoot function.
+    /// d/dx cuberoot(x) = 1 / (3 x^(2/3))
+    /// d2/dx2 cuberoot(x) = -2 / (9 x^(5/3))
+    #[must_use]
+    #[inline]
+    pub fn cbrt(self) -> Self {
+        let f = self.value.cbrt();
+        self.chain(f, (3.0 * f * f).recip(), -2.0 / (9.0 * self.value * f * f))
+    }
+
+    /// Cosine function.
+    /// d/dx cos(x) = -sin(x), d2/dx2 cos(x) = -cos(x)
+    #[must_use]
+    #[inline]
+    pub fn cos(self) -> Self {
+        let (s, c) = self.value.sin_cos();
+        self.chain(c, -s, -c)
+    }
+
+    /// Hyperbolic cosine function.
+    /// d/dx cosh(x) = sinh(x), d2/dx2 cosh(x) = cosh(x)
+    #[must_use]
+    #[inline]
+    pub fn cosh(self) -> Self {
+        let c = self.value.cosh();
+        self.chain(c, self.value.sinh(), c)
+    }
+
+    /// Exponential function (base *e*).
+    /// d/dx exp(x) = d2/dx2 exp(x) = exp(x)
+    #[must_use]
+    #[inline]
+    pub fn exp(self) -> Self {
+        let f = self.value.exp();
+        self.chain(f, f, f)
+    }
+
+    /// Exponential function (base 2).
+    /// d/dx 2^x = 2^x ln(2), d2/dx2 2^x = 2^x ln^2(2)
+    #[must_use]
+    #[inline]
+    pub fn exp2(self) -> Self {
+        let f = self.value.exp2();
+        self.chain(f, f * LN_2, f * LN_2 * LN_2)
+    }
+
+    /// Exponential function minus 1 function.
+    /// d/dx exp(x) - 1 = d2/dx2 exp(x) - 1 = exp(x)
+    #[must_use]
+    #[inline]
+    pub fn exp_m1(self) -> Self {
+        let d = self.value.exp();
+        self.chain(self.value.exp_m1(), d, d)
+    }
+
+    /// Logarithm (natural) of `x`.
+    /// d/dx ln(x) = 1 / x, d2/dx2 ln(x) = -1 / x^2
+    #[must_use]
+    #[inline]
+    pub fn ln(self) -> Self {
+        let r = self.value.recip();
+        self.chain(self.value.ln(), r, -r * r)
+    }
+
+    /// Logarithm (natural) of `1 + x`.
+    /// d/dx ln(1+x) = 1 / (1+x), d2/dx2 ln(1+x) = -1 / (1+x)^2
+    #[must_use]
+    #[inline]
+    pub fn ln_1p(self) -> Self {
+        let r = (1.0 + self.value).recip();
+        self.chain(self.value.ln_1p(), r, -r * r)
+    }
+
+    /// Logarithm (base 10).
+    /// d/dx log_10(x) = 1 / (x ln(10)), d2/dx2 log_10(x) = -1 / (x^2 ln(10))
+    #[must_use]
+    #[inline]
+    pub fn log10(self) -> Self {
+        let r = self.value.recip();
+        self.chain(self.value.log10(), r / LN_10, -r * r / LN_10)
+    }
+
+    /// Logarithm (base 2).
+    /// d/dx log_2(x) = 1 / (x ln(2)), d2/dx2 log_2(x) = -1 / (x^2 ln(2))
+    #[must_use]
+    #[inline]
+    pub fn log2(self) -> Self {
+        let r = self.value.recip();
+        self.chain(self.value.log2(), r / LN_2, -r * r / LN_2)
+    }
+
+    /// Reciprocal function.
+    /// d/dx 1 / x = -1 / x^2, d2/dx2 1 / x = 2 / x^3
+    #[must_use]
+    #[inline]
+    pub fn recip(self) -> Self {
+        let r = self.value.recip();
+        self.chain(r, -r * r, 2.0 * r * r * r)
+    }
+
+    /// Sine function.
+    /// d/dx sin(x) = cos(x), d2/dx2 sin(x) = -sin(x)
+    #[must_use]
+    #[inline]
+    pub fn sin(self) -> Self {
+        let (s, c) = self.value.sin_cos();
+        self.chain(s, c, -s)
+    }
+
+    /// Hyperbolic sine function.
+    /// d/dx sinh(x) = cosh(x), d2/dx2 sinh(x) = sinh(x)
+    #[must_use]
+    #[inline]
+    pub fn sinh(self) -> Self {
+        let s = self.value.sinh();
+        self.chain(s, self.value.cosh(), s)
+    }
+
+    /// Square root function.
+    /// d/dx sqrt(x) = 1 / (2 sqrt(x)), d2/dx2 sqrt(x) = -1 / (4 x^(3/2))
+    #[must_use]
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        let f = self.value.sqrt();
+        self.chain(f, (2.0 * f).recip(), -(4.0 * self.value * f).recip())
+    }
+
+    /// Tangent function.
+    /// d/dx tan(x) = 1 + tan^2(x), d2/dx2 tan(x) = 2 tan(x) (1 + tan^2(x))
+    #[must_use]
+    #[inline]
+    pub fn tan(self) -> Self {
+        let f = self.value.tan();
+        let d = 1.0 + f * f;
+        self.chain(f, d, 2.0 * f * d)
+    }
+
+    /// Hyperbolic tangent function.
+    /// d/dx tanh(x) = 1 - tanh^2(x), d2/dx2 tanh(x) = -2 tanh(x) (1 - tanh^2(x))
+    #[must_use]
+    #[inline]
+    pub fn tanh(self) -> Self {
+        let f = self.value.tanh();
+        let d = 1.0 - f * f;
+        self.chain(f, d, -2.0 * f * d)
+    }
+
+    /// Error function.
+    /// d/dx erf(x) = 2e^(-x^2) / sqrt(PI), d2/dx2 erf(x) = -2x d/dx erf(x)
+    #[must_use]
+    #[inline]
+    pub fn erf(self) -> Self {
+        let x = self.value;
+        let d = 2.0 * (-x * x).exp() / PI.sqrt();
+        self.chain(errorfunctions::RealErrorFunctions::erf(x), d, -2.0 * x * d)
+    }
+
+    /// Error function (complementary).
+    /// d/dx erfc(x) = -2e^(-x^2) / sqrt(PI), d2/dx2 erfc(x) = -2x d/dx erfc(x)
+    #[must_use]
+    #[inline]
+    pub fn erfc(self) -> Self {
+        let x = self.value;
+        let d = -2.0 * (-x * x).exp() / PI.sqrt();
+        self.chain(errorfunctions::RealErrorFunctions::erfc(x), d, -2.0 * x * d)
+    }
+
+    /// Standard normal cumulative distribution function.
+    /// d/dx N(x) = n(x), d2/dx2 N(x) = -x n(x)
+    #[must_use]
+    #[inline]
+    pub fn norm_cdf(self) -> Self {
+        let x = self.value;
+        let d = standard_normal_density(x);
+        self.chain(
+            0.5 * errorfunctions::RealErrorFunctions::erfc(-x / SQRT_2),
+            d,
+            -x * d,
+        )
+    }
+
+    /// Standard normal probability density function.
+    /// d/dx n(x) = -x n(x), d2/dx2 n(x) = (x^2 - 1) n(x)
+    #[must_use]
+    #[inline]
+    pub fn norm_pdf(self) -> Self {
+        let x = self.value;
+        let d = standard_normal_density(x);
+        self.chain(d, -x * d, (x * x - 1.0) * d)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: POWER, LOGARITHM, MIN AND MAX TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// HyperDual ^ HyperDual = exp(y ln(x))
+impl Powf<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn powf(&self, other: HyperDual) -> Self::Output {
+        (other * self.ln()).exp()
+    }
+}
+
+// HyperDual ^ f64
+impl Powf<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn powf(&self, n: f64) -> Self::Output {
+        let x = self.value;
+        self.chain(
+            x.powf(n),
+            n * x.powf(n - 1.0),
+            n * (n - 1.0) * x.powf(n - 2.0),
+        )
+    }
+}
+
+// f64 ^ HyperDual
+impl Powf<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn powf(&self, other: HyperDual) -> Self::Output {
+        let (f, ln) = (f64::powf(*self, other.value), f64::ln(*self));
+        other.chain(f, f * ln, f * ln * ln)
+    }
+}
+
+// HyperDual ^ i32
+impl Powi<i32> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn powi(&self, n: i32) -> Self::Output {
+        let (x, m) = (self.value, f64::from(n));
+        self.chain(x.powi(n), m * x.powi(n - 1), m * (m - 1.0) * x.powi(n - 2))
+    }
+}
+
+// log_{HyperDual}(HyperDual) = ln(y) / ln(x)
+impl Log<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn log(&self, base: HyperDual) -> Self::Output {
+        self.ln() / base.ln()
+    }
+}
+
+// log_{f64}(HyperDual)
+impl Log<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn log(&self, base: f64) -> Self::Output {
+        self.ln() / base.ln()
+    }
+}
+
+// log_{HyperDual}(f64)
+impl Log<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn log(&self, base: HyperDual) -> Self::Output {
+        self.ln() / base.ln()
+    }
+}
+
+// min{ HyperDual, HyperDual }
+impl Min<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn min(&self, rhs: HyperDual) -> Self::Output {
+        if self.value < rhs.value {
+            *self
+        } else {
+            rhs
+        }
+    }
+}
+
+// min{ HyperDual, f64 }
+impl Min<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn min(&self, rhs: f64) -> Self::Output {
+        Min::min(self, HyperDual::constant(rhs))
+    }
+}
+
+// min{ f64, HyperDual }
+impl Min<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn min(&self, rhs: HyperDual) -> Self::Output {
+        Min::min(&HyperDual::constant(*self), rhs)
+    }
+}
+
+// max{ HyperDual, HyperDual }
+impl Max<HyperDual> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn max(&self, rhs: HyperDual) -> Self::Output {
+        if self.value > rhs.value {
+            *self
+        } else {
+            rhs
+        }
+    }
+}
+
+// max{ HyperDual, f64 }
+impl Max<f64> for HyperDual {
+    type Output = HyperDual;
+
+    #[inline]
+    fn max(&self, rhs: f64) -> Self::Output {
+        Max::max(self, HyperDual::constant(rhs))
+    }
+}
+
+// max{ f64, HyperDual }
+impl Max<HyperDual> for f64 {
+    type Output = HyperDual;
+
+    #[inline]
+    fn max(&self, rhs: HyperDual) -> Self::Output {
+        Max::max(&HyperDual::constant(*self), rhs)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_hyper_dual {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Central second difference, to check the analytic second derivatives.
+    fn second_difference(f: fn(f64) -> f64, x: f64) -> f64 {
+        let h = 1e-4;
+        (f(x + h) - 2.0 * f(x) + f(x - h)) / (h * h)
+    }
+
+    #[test]
+    fn test_primitives_second_derivative() {
+        type Primitive = (fn(HyperDual) -> HyperDual, fn(f64) -> f64);
+
+        let functions: [Primitive; 15] = [
+            (HyperDual::sin, f64::sin),
+            (HyperDual::cos, f64::cos),
+            (HyperDual::tan, f64::tan),
+            (HyperDual::exp, f64::exp),
+            (HyperDual::ln, f64::ln),
+            (HyperDual::sqrt, f64::sqrt),
+            (HyperDual::cbrt, f64::cbrt),
+            (HyperDual::recip, f64::recip),
+            (HyperDual::acos, f64::acos),
+            (HyperDual::asinh, f64::asinh),
+            (HyperDual::atan, f64::atan),
+            (HyperDual::atanh, f64::atanh),
+            (HyperDual::tanh, f64::tanh),
+            (HyperDual::log10, f64::log10),
+            (HyperDual::norm_pdf, |x| {
+                (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+            }),
+        ];
+
+        for (hyper_dual, f) in functions {
+            let z = hyper_dual(HyperDual::variable(0.4));
+
+            assert_approx_equal!(z.value, f(0.4), 1e-12);
+            assert_approx_equal!(z.e1, z.e2, 1e-15);
+            assert_approx_equal!(z.e1e2, second_difference(f, 0.4), 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let x = HyperDual::variable(2.0);
+
+        // f = (x^2 + 1) / x, f' = 1 - 1/x^2, f'' = 2/x^3
+        let z = (x * x + 1.0) / x;
+        assert_approx_equal!(z.value, 2.5, 1e-15);
+        assert_approx_equal!(z.first_derivative(), 0.75, 1e-15);
+        assert_approx_equal!(z.second_derivative(), 0.25, 1e-15);
+
+        // f = 1 - 3x, linear, so no curvature.
+        let z = 1.0 - 3.0 * x;
+        assert_eq!(z, HyperDual::new(-5.0, -3.0, -3.0, 0.0));
+    }
+
+    #[test]
+    fn test_mixed_partial() {
+        // f(x, y) = sin(x y) + x^y
+        let (a, b) = (0.7, 1.3);
+        let x = HyperDual::new(a, 1.0, 0.0, 0.0);
+        let y = HyperDual::new(b, 0.0, 1.0, 0.0);
+
+        let f = (x * y).sin() + x.powf(y);
+
+        let df_dx = (a * b).cos() * b + b * a.powf(b - 1.0);
+        let df_dy = (a * b).cos() * a + a.powf(b) * a.ln();
+        let d2f_dxdy = (a * b).cos() - a * b * (a * b).sin() + a.powf(b - 1.0) * (1.0 + b * a.ln());
+
+        assert_approx_equal!(f.e1, df_dx, 1e-12);
+        assert_approx_equal!(f.e2, df_dy, 1e-12);
+        assert_approx_equal!(f.e1e2, d2f_dxdy, 1e-12);
+    }
+
+    #[test]
+    fn test_black_scholes_gamma() {
+        // Gamma of a call, as the second derivative of the price in spot.
+        let (k, r, v, t) = (100.0, 0.05, 0.2, 1.0);
+        let price = |s: HyperDual| {
+            let d1 = ((s / k).ln() + (r + 0.5 * v * v) * t) / (v * f64::sqrt(t));
+            let d2 = d1 - v * f64::sqrt(t);
+
+            s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+        };
+
+        let z = price(HyperDual::variable(100.0));
+
+        assert_approx_equal!(z.value, 10.450583572185565, 1e-8);
+        assert_approx_equal!(z.first_derivative(), 0.6368306511756191, 1e-8);
+        assert_approx_equal!(z.second_derivative(), 0.018762017345846895, 1e-8);
+    }
+
+    #[test]
+    fn test_traits() {
+        let x = HyperDual::variable(3.0);
+
+        let sum: HyperDual = [x, x, HyperDual::constant(1.0)].into_iter().sum();
+        let product: HyperDual = [x, x].into_iter().product();
+        assert_eq!(sum, HyperDual::new(7.0, 2.0, 2.0, 0.0));
+        assert_eq!(product, HyperDual::new(9.0, 6.0, 6.0, 2.0));
+
+        assert_approx_equal!(2.0.powf(x).e1e2, 8.0 * LN_2 * LN_2, 1e-12);
+        assert_approx_equal!(x.log(3.0).e1e2, -1.0 / (9.0 * 3.0_f64.ln()), 1e-12);
+        assert_eq!(Max::max(&x, 1.0), x);
+        assert_eq!(Min::min(&1.0, x), HyperDual::constant(1.0));
+
+        let mut z = x;
+        z *= x;
+        z -= 1.0;
+        z /= 2.0;
+        assert_eq!(z, HyperDual::new(4.0, 3.0, 3.0, 1.0));
+    }
+}
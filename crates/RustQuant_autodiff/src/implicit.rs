@@ -0,0 +1,210 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Sensitivities of calibrated parameters, via the implicit function theorem.
+//!
+//! A calibration finds the parameters $\theta$ that solve
+//! $F(\theta, q) = 0$ for the market quotes $q$, e.g. model prices minus
+//! quoted prices. Differentiating through the solver's iterations is slow
+//! and depends on its convergence. Instead, since $F(\theta(q), q) = 0$
+//! for all quotes,
+//!
+//! $$
+//! \frac{\partial \theta}{\partial q} = - \left( \frac{\partial F}{\partial \theta} \right)^{-1} \frac{\partial F}{\partial q}
+//! $$
+//!
+//! at the solution, which only needs the Jacobians of the residuals.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Accumulate, Gradient, Graph, Variable};
+use nalgebra::DMatrix;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sensitivities of the calibrated `parameters` to the `quotes`, as the
+/// matrix with entries $\partial \theta_i / \partial q_j$.
+///
+/// - `parameters` must solve the calibration, i.e. the residuals are zero.
+/// - `residuals` maps the parameters and the quotes to the residuals of
+///   the calibration, one per parameter.
+///
+/// Least squares calibrations should pass the first order conditions,
+/// i.e. the gradient of the objective with respect to the parameters.
+///
+/// ```
+/// # use RustQuant_autodiff::*;
+///
+/// // Continuously compounded zero rate from a discount factor quote:
+/// // F(r, P) = exp(-r T) - P = 0.
+/// let maturity = 2.0;
+/// let quote = 0.95;
+/// let rate = -f64::ln(quote) / maturity;
+///
+/// let sensitivities = implicit_sensitivities(&[rate], &[quote], |r, p| {
+///     vec![(-r[0] * maturity).exp() - p[0]]
+/// })
+/// .unwrap();
+///
+/// // dr/dP = -1 / (T P)
+/// assert!((sensitivities[(0, 0)] + 1.0 / (maturity * quote)).abs() < 1e-12);
+/// ```
+///
+/// # Errors
+///
+/// - `RustQuantError::InvalidArgument` if there is not one residual per parameter.
+/// - `RustQuantError::MatrixInversionFailed` if the Jacobian of the
+///   residuals with respect to the parameters is singular, i.e. the
+///   parameters are not locally determined by the quotes.
+pub fn implicit_sensitivities<F>(
+    parameters: &[f64],
+    quotes: &[f64],
+    residuals: F,
+) -> Result<DMatrix<f64>, RustQuantError>
+where
+    F: for<'v> Fn(&[Variable<'v>], &[Variable<'v>]) -> Vec<Variable<'v>>,
+{
+    let graph = Graph::new();
+    let theta = graph.vars(parameters);
+    let q = graph.vars(quotes);
+
+    let residuals = residuals(&theta, &q);
+
+    if residuals.len() != theta.len() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "{} residuals for {} parameters, expected one residual per parameter.",
+            residuals.len(),
+            theta.len()
+        )));
+    }
+
+    let (n, m) = (theta.len(), q.len());
+    let mut jacobian_theta = DMatrix::zeros(n, n);
+    let mut jacobian_q = DMatrix::zeros(n, m);
+
+    for (i, residual) in residuals.iter().enumerate() {
+        let adjoints = residual.accumulate();
+
+        for (j, x) in theta.iter().enumerate() {
+            jacobian_theta[(i, j)] = adjoints.wrt(x);
+        }
+        for (j, x) in q.iter().enumerate() {
+            jacobian_q[(i, j)] = adjoints.wrt(x);
+        }
+    }
+
+    jacobian_theta
+        .lu()
+        .solve(&(-jacobian_q))
+        .ok_or(RustQuantError::MatrixInversionFailed)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_implicit {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn black_scholes_call<'v>(sigma: Variable<'v>, s: f64, k: f64, r: f64, t: f64) -> Variable<'v> {
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+
+        s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+    }
+
+    #[test]
+    fn test_implied_volatility() {
+        let (s, k, r, t) = (100.0, 105.0, 0.03, 0.5);
+
+        // Solve for the implied volatility by Newton's method.
+        let price = 6.5;
+        let mut sigma = 0.3;
+        for _ in 0..50 {
+            let g = Graph::new();
+            let x = g.var(sigma);
+            let model = black_scholes_call(x, s, k, r, t);
+            sigma -= (model.value - price) / model.accumulate().wrt(&x);
+        }
+
+        let sensitivities = implicit_sensitivities(&[sigma], &[price], |x, p| {
+            vec![black_scholes_call(x[0], s, k, r, t) - p[0]]
+        })
+        .unwrap();
+
+        // d(sigma)/d(price) = 1 / vega
+        let g = Graph::new();
+        let x = g.var(sigma);
+        let vega = black_scholes_call(x, s, k, r, t).accumulate().wrt(&x);
+
+        assert_approx_equal!(sensitivities[(0, 0)], 1.0 / vega, 1e-12);
+    }
+
+    #[test]
+    fn test_linear_system() {
+        // A theta = q, so d(theta)/dq = A^-1.
+        let a = DMatrix::from_row_slice(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+        let quotes = [1.0, 2.0];
+        let theta = a
+            .clone()
+            .lu()
+            .solve(&DMatrix::from_column_slice(2, 1, &quotes));
+
+        let sensitivities = implicit_sensitivities(theta.unwrap().as_slice(), &quotes, |x, q| {
+            vec![2.0 * x[0] + x[1] - q[0], x[0] + 3.0 * x[1] - q[1]]
+        })
+        .unwrap();
+
+        let inverse = a.try_inverse().unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_approx_equal!(sensitivities[(i, j)], inverse[(i, j)], 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_more_quotes_than_parameters() {
+        // theta = (q_1 + q_2 + q_3) / 3, from the first order condition
+        // of the least squares fit of theta to the quotes.
+        let quotes = [1.0, 2.0, 4.0];
+        let theta = 7.0 / 3.0;
+
+        let sensitivities = implicit_sensitivities(&[theta], &quotes, |x, q| {
+            vec![q.iter().map(|&q| 2.0 * (x[0] - q)).sum()]
+        })
+        .unwrap();
+
+        assert_eq!(sensitivities.shape(), (1, 3));
+        for j in 0..3 {
+            assert_approx_equal!(sensitivities[(0, j)], 1.0 / 3.0, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let singular = implicit_sensitivities(&[1.0, 1.0], &[2.0], |x, q| {
+            vec![x[0] + x[1] - q[0], 2.0 * x[0] + 2.0 * x[1] - 2.0 * q[0]]
+        });
+        assert!(matches!(
+            singular,
+            Err(RustQuantError::MatrixInversionFailed)
+        ));
+
+        let mismatch = implicit_sensitivities(&[1.0, 1.0], &[2.0], |x, q| vec![x[0] - q[0]]);
+        assert!(matches!(mismatch, Err(RustQuantError::InvalidArgument(_))));
+    }
+}
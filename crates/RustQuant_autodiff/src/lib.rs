@@ -2,10 +2,9 @@
 // AUTOMATIC DIFFERENTIATION MODULE
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-//! Reverse mode automatic differentation.
-//! Currently only gradients can be computed.
-//! Suggestions on how to extend the functionality to Hessian matrices are
-//! definitely welcome.
+//! Reverse and forward mode automatic differentation.
+//! Gradients and Hessians can be computed in reverse mode, the latter by
+//! forward-over-reverse accumulation on the graph (see [`Hessian`]).
 //!
 //! Additionally, only functions $f: \mathbb{R}^n \rightarrow \mathbb{R}$
 //! (scalar output) are supported. However, you can manually apply the
@@ -15,10 +14,11 @@
 //!   - Implementation via Operator and Function Overloading.
 //!   - Useful when number of outputs is *smaller* than number of inputs.
 //!     - i.e for functions $f:\mathbb{R}^n \rightarrow \mathbb{R}^m$, where $m \ll n$
-//! - [ ] Forward (Tangent) Mode
+//! - [x] Forward (Tangent) Mode
 //!   - Implementation via Dual Numbers.
 //!   - Useful when number of outputs is *larger* than number of inputs.
 //!     - i.e. for functions $f:\mathbb{R}^n \rightarrow \mathbb{R}^m$, where $m \gg n$
+//!   - Second order derivatives via Hyper-Dual Numbers.
 //!
 //! ```
 //! # use RustQuant_autodiff::*;
@@ -69,18 +69,58 @@ pub use accumulate::*;
 pub mod gradient;
 pub use gradient::*;
 
+/// Checkpointing for functions iterated over many steps.
+pub mod checkpoint;
+pub use checkpoint::*;
+
+/// User-defined elemental functions with custom derivatives.
+pub mod custom;
+pub use custom::*;
+
+/// Forward mode with [`Dual`] numbers.
+pub mod dual;
+pub use dual::*;
+
 /// The Graph (aka. tape or Wengert List).
 pub mod graph;
 pub use graph::*;
 
+/// Floating point types that can be recorded on the graph.
+pub mod float;
+pub use float::*;
+
 /// Visualisation of the [`Graph`].
 pub mod graphviz;
 pub use graphviz::*;
 
+/// Implements the Hessian computation.
+pub mod hessian;
+pub use hessian::*;
+
+/// Second order forward mode with [`HyperDual`] numbers.
+pub mod hyper_dual;
+pub use hyper_dual::*;
+
+/// Sensitivities of calibrated parameters via the implicit function theorem.
+pub mod implicit;
+pub use implicit::*;
+
+/// Vectors and matrices of [`Variable`]s.
+pub mod linalg;
+pub use linalg::*;
+
 /// Implements [`Vertex`] (nodes) for the `Graph`.
 pub mod vertex;
 pub use vertex::*;
 
+/// Parallel reverse accumulation with per-thread graphs.
+pub mod parallel;
+pub use parallel::*;
+
+/// Export, import and replay of recorded functions.
+pub mod tape;
+pub use tape::*;
+
 /// Operator/function overloading.
 /// This module contains the overloaded operators and primitive functions.
 /// In Griewank and Walther - Evaluating Derivatives, they refer to this
@@ -0,0 +1,518 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Vectors and matrices of `Variable`s, built from and converted to
+//! `nalgebra` vectors and matrices.
+//!
+//! `nalgebra` requires its scalars to be `'static`, which a `Variable`
+//! borrowing its graph is not, so `DVector<Variable>` and `DMatrix<Variable>`
+//! cannot be used directly. [`VariableVector`] and [`VariableMatrix`] hold
+//! the variables instead (column-major, as in `nalgebra`), and record the
+//! linear algebra operations on the graph element by element.
+//!
+//! ```
+//! # use RustQuant_autodiff::*;
+//! # use nalgebra::{DMatrix, DVector};
+//!
+//! let g = Graph::new();
+//!
+//! // Portfolio variance w' S w, and its gradient 2 S w.
+//! let covariance = DMatrix::from_row_slice(2, 2, &[0.04, 0.01, 0.01, 0.09]);
+//! let weights = g.vector(&DVector::from_vec(vec![0.6, 0.4]));
+//!
+//! let variance = weights.quadratic_form(&covariance);
+//! let gradient = variance.accumulate().wrt(&weights);
+//!
+//! assert!((variance.value - 0.0336).abs() < 1e-12);
+//! assert!((gradient - 2.0 * &covariance * weights.value()).norm() < 1e-12);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Gradient, Graph, Variable};
+use nalgebra::{DMatrix, DVector};
+use std::ops::{Add, Index, Mul, Sub};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A vector of `Variable`s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableVector<'v> {
+    /// The elements of the vector.
+    pub data: Vec<Variable<'v>>,
+}
+
+/// A matrix of `Variable`s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableMatrix<'v> {
+    /// Number of rows.
+    pub nrows: usize,
+    /// Number of columns.
+    pub ncols: usize,
+    /// The elements of the matrix, in column-major order.
+    pub data: Vec<Variable<'v>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// GRAPH CONSTRUCTORS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Graph {
+    /// Add a vector of variables to the graph.
+    #[inline]
+    pub fn vector<'v>(&'v self, values: &DVector<f64>) -> VariableVector<'v> {
+        VariableVector {
+            data: self.vars(values.as_slice()),
+        }
+    }
+
+    /// Add a matrix of variables to the graph.
+    #[inline]
+    pub fn matrix<'v>(&'v self, values: &DMatrix<f64>) -> VariableMatrix<'v> {
+        VariableMatrix {
+            nrows: values.nrows(),
+            ncols: values.ncols(),
+            data: self.vars(values.as_slice()),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// VECTOR IMPLEMENTATION
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<'v> VariableVector<'v> {
+    /// Create a vector from its elements.
+    #[must_use]
+    pub fn new(data: Vec<Variable<'v>>) -> Self {
+        Self { data }
+    }
+
+    /// Number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Check if the vector is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterator over the elements.
+    pub fn iter(&self) -> std::slice::Iter<'_, Variable<'v>> {
+        self.data.iter()
+    }
+
+    /// Values of the elements.
+    #[must_use]
+    pub fn value(&self) -> DVector<f64> {
+        DVector::from_iterator(self.len(), self.iter().map(Variable::value))
+    }
+
+    /// Apply a function to each element.
+    #[must_use]
+    pub fn map<F: Fn(Variable<'v>) -> Variable<'v>>(&self, f: F) -> Self {
+        Self::new(self.iter().map(|&x| f(x)).collect())
+    }
+
+    /// Sum of the elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is empty.
+    #[must_use]
+    pub fn sum(&self) -> Variable<'v> {
+        self.iter().copied().sum()
+    }
+
+    /// Dot product with another vector of variables.
+    #[must_use]
+    pub fn dot(&self, other: &Self) -> Variable<'v> {
+        assert_eq!(self.len(), other.len(), "Vector dimensions must match.");
+
+        self.iter().zip(other.iter()).map(|(&x, &y)| x * y).sum()
+    }
+
+    /// Dot product with a vector of constants.
+    #[must_use]
+    pub fn dot_values(&self, other: &DVector<f64>) -> Variable<'v> {
+        assert_eq!(self.len(), other.len(), "Vector dimensions must match.");
+
+        self.iter().zip(other.iter()).map(|(&x, &y)| x * y).sum()
+    }
+
+    /// Quadratic form x' A x with a matrix of constants.
+    #[must_use]
+    pub fn quadratic_form(&self, matrix: &DMatrix<f64>) -> Variable<'v> {
+        self.dot(&(matrix * self))
+    }
+
+    /// Element-wise product.
+    #[must_use]
+    pub fn component_mul(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len(), "Vector dimensions must match.");
+
+        Self::new(
+            self.iter()
+                .zip(other.iter())
+                .map(|(&x, &y)| x * y)
+                .collect(),
+        )
+    }
+
+    /// Squared Euclidean norm.
+    #[must_use]
+    pub fn norm_squared(&self) -> Variable<'v> {
+        self.iter().map(|&x| x * x).sum()
+    }
+
+    /// Euclidean (L2) norm.
+    #[must_use]
+    pub fn norm(&self) -> Variable<'v> {
+        self.norm_squared().sqrt()
+    }
+
+    /// L1 norm, the sum of absolute values.
+    #[must_use]
+    pub fn norm_l1(&self) -> Variable<'v> {
+        self.iter().map(|&x| x.abs()).sum()
+    }
+}
+
+impl<'v> Index<usize> for VariableVector<'v> {
+    type Output = Variable<'v>;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index]
+    }
+}
+
+/// VariableVector + VariableVector
+impl<'v> Add<&VariableVector<'v>> for &VariableVector<'v> {
+    type Output = VariableVector<'v>;
+
+    fn add(self, other: &VariableVector<'v>) -> Self::Output {
+        assert_eq!(self.len(), other.len(), "Vector dimensions must match.");
+
+        VariableVector::new(
+            self.iter()
+                .zip(other.iter())
+                .map(|(&x, &y)| x + y)
+                .collect(),
+        )
+    }
+}
+
+/// VariableVector - VariableVector
+impl<'v> Sub<&VariableVector<'v>> for &VariableVector<'v> {
+    type Output = VariableVector<'v>;
+
+    fn sub(self, other: &VariableVector<'v>) -> Self::Output {
+        assert_eq!(self.len(), other.len(), "Vector dimensions must match.");
+
+        VariableVector::new(
+            self.iter()
+                .zip(other.iter())
+                .map(|(&x, &y)| x - y)
+                .collect(),
+        )
+    }
+}
+
+/// VariableVector - DVector<f64>
+impl<'v> Sub<&DVector<f64>> for &VariableVector<'v> {
+    type Output = VariableVector<'v>;
+
+    fn sub(self, other: &DVector<f64>) -> Self::Output {
+        assert_eq!(self.len(), other.len(), "Vector dimensions must match.");
+
+        VariableVector::new(
+            self.iter()
+                .zip(other.iter())
+                .map(|(&x, &y)| x - y)
+                .collect(),
+        )
+    }
+}
+
+/// VariableVector * f64
+impl<'v> Mul<f64> for &VariableVector<'v> {
+    type Output = VariableVector<'v>;
+
+    fn mul(self, scalar: f64) -> Self::Output {
+        self.map(|x| x * scalar)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MATRIX IMPLEMENTATION
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<'v> VariableMatrix<'v> {
+    /// Create a matrix from its elements, in column-major order.
+    #[must_use]
+    pub fn from_column_slice(nrows: usize, ncols: usize, data: &[Variable<'v>]) -> Self {
+        assert_eq!(nrows * ncols, data.len(), "Matrix dimensions must match.");
+
+        Self {
+            nrows,
+            ncols,
+            data: data.to_vec(),
+        }
+    }
+
+    /// Element at a row and column.
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> Variable<'v> {
+        self.data[row + col * self.nrows]
+    }
+
+    /// Values of the elements.
+    #[must_use]
+    pub fn value(&self) -> DMatrix<f64> {
+        DMatrix::from_iterator(
+            self.nrows,
+            self.ncols,
+            self.data.iter().map(Variable::value),
+        )
+    }
+
+    /// Column of the matrix.
+    #[must_use]
+    pub fn column(&self, col: usize) -> VariableVector<'v> {
+        VariableVector::new(self.data[col * self.nrows..(col + 1) * self.nrows].to_vec())
+    }
+
+    /// Row of the matrix.
+    #[must_use]
+    pub fn row(&self, row: usize) -> VariableVector<'v> {
+        VariableVector::new((0..self.ncols).map(|col| self.get(row, col)).collect())
+    }
+
+    /// Transpose of the matrix.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let data = (0..self.nrows)
+            .flat_map(|row| (0..self.ncols).map(move |col| (row, col)))
+            .map(|(row, col)| self.get(row, col))
+            .collect();
+
+        Self {
+            nrows: self.ncols,
+            ncols: self.nrows,
+            data,
+        }
+    }
+
+    /// Sum of the diagonal elements.
+    #[must_use]
+    pub fn trace(&self) -> Variable<'v> {
+        (0..self.nrows.min(self.ncols))
+            .map(|i| self.get(i, i))
+            .sum()
+    }
+
+    /// Frobenius norm.
+    #[must_use]
+    pub fn norm(&self) -> Variable<'v> {
+        self.data.iter().map(|&x| x * x).sum::<Variable>().sqrt()
+    }
+}
+
+/// VariableMatrix * VariableVector
+impl<'v> Mul<&VariableVector<'v>> for &VariableMatrix<'v> {
+    type Output = VariableVector<'v>;
+
+    fn mul(self, vector: &VariableVector<'v>) -> Self::Output {
+        assert_eq!(self.ncols, vector.len(), "Matrix dimensions must match.");
+
+        VariableVector::new(
+            (0..self.nrows)
+                .map(|row| self.row(row).dot(vector))
+                .collect(),
+        )
+    }
+}
+
+/// VariableMatrix * DVector<f64>
+impl<'v> Mul<&DVector<f64>> for &VariableMatrix<'v> {
+    type Output = VariableVector<'v>;
+
+    fn mul(self, vector: &DVector<f64>) -> Self::Output {
+        assert_eq!(self.ncols, vector.len(), "Matrix dimensions must match.");
+
+        VariableVector::new(
+            (0..self.nrows)
+                .map(|row| self.row(row).dot_values(vector))
+                .collect(),
+        )
+    }
+}
+
+/// DMatrix<f64> * VariableVector
+impl<'v> Mul<&VariableVector<'v>> for &DMatrix<f64> {
+    type Output = VariableVector<'v>;
+
+    fn mul(self, vector: &VariableVector<'v>) -> Self::Output {
+        assert_eq!(self.ncols(), vector.len(), "Matrix dimensions must match.");
+
+        VariableVector::new(
+            self.row_iter()
+                .map(|row| vector.dot_values(&row.transpose()))
+                .collect(),
+        )
+    }
+}
+
+/// VariableMatrix * VariableMatrix
+impl<'v> Mul<&VariableMatrix<'v>> for &VariableMatrix<'v> {
+    type Output = VariableMatrix<'v>;
+
+    fn mul(self, other: &VariableMatrix<'v>) -> Self::Output {
+        assert_eq!(self.ncols, other.nrows, "Matrix dimensions must match.");
+
+        let data = (0..other.ncols)
+            .flat_map(|col| (0..self.nrows).map(move |row| (row, col)))
+            .map(|(row, col)| self.row(row).dot(&other.column(col)))
+            .collect();
+
+        VariableMatrix {
+            nrows: self.nrows,
+            ncols: other.ncols,
+            data,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// GRADIENTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// `wrt` a vector of variables.
+impl<'v> Gradient<&VariableVector<'v>, DVector<f64>> for Vec<f64> {
+    #[inline]
+    fn wrt(&self, variables: &VariableVector<'v>) -> DVector<f64> {
+        DVector::from_iterator(variables.len(), variables.iter().map(|var| self[var.index]))
+    }
+}
+
+/// `wrt` a matrix of variables.
+impl<'v> Gradient<&VariableMatrix<'v>, DMatrix<f64>> for Vec<f64> {
+    #[inline]
+    fn wrt(&self, variables: &VariableMatrix<'v>) -> DMatrix<f64> {
+        DMatrix::from_iterator(
+            variables.nrows,
+            variables.ncols,
+            variables.data.iter().map(|var| self[var.index]),
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_linalg {
+    use super::*;
+    use crate::Accumulate;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_dot_and_norms() {
+        let g = Graph::new();
+
+        let x = g.vector(&DVector::from_vec(vec![3.0, -4.0]));
+        let y = g.vector(&DVector::from_vec(vec![2.0, 5.0]));
+
+        let dot = x.dot(&y);
+        let grad = dot.accumulate();
+        assert_eq!(dot.value, -14.0);
+        assert_eq!(grad.wrt(&x), y.value());
+        assert_eq!(grad.wrt(&y), x.value());
+
+        let norm = x.norm();
+        assert_eq!(norm.value, 5.0);
+        let grad = norm.accumulate().wrt(&x);
+        assert_approx_equal!(grad[0], 0.6, 1e-12);
+        assert_approx_equal!(grad[1], -0.8, 1e-12);
+
+        let l1 = x.norm_l1();
+        assert_eq!(l1.value, 7.0);
+        assert_eq!(l1.accumulate().wrt(&x), DVector::from_vec(vec![1.0, -1.0]));
+    }
+
+    #[test]
+    fn test_matrix_vector() {
+        let g = Graph::new();
+
+        let a = DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let x = DVector::from_vec(vec![0.5, -1.0, 2.0]);
+
+        let a_var = g.matrix(&a);
+        let x_var = g.vector(&x);
+
+        assert_eq!((&a_var * &x_var).value(), &a * &x);
+        assert_eq!((&a_var * &x).value(), &a * &x);
+        assert_eq!((&a * &x_var).value(), &a * &x);
+        assert_eq!(a_var.transpose().value(), a.transpose());
+        assert_eq!(a_var.row(1).value(), DVector::from_vec(vec![4.0, 5.0, 6.0]));
+
+        // d/dA (1' A x) = 1 x'
+        let y = (&a_var * &x_var).sum();
+        let grad = y.accumulate();
+        assert_eq!(grad.wrt(&a_var), DMatrix::from_fn(2, 3, |_, j| x[j]));
+        assert_eq!(grad.wrt(&x_var), a.row_sum().transpose());
+    }
+
+    #[test]
+    fn test_matrix_matrix() {
+        let g = Graph::new();
+
+        let a = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+        let b = DMatrix::from_row_slice(2, 2, &[0.5, -1.0, 1.5, 2.0]);
+
+        let a_var = g.matrix(&a);
+        let b_var = g.matrix(&b);
+        let c = &a_var * &b_var;
+
+        assert_eq!(c.value(), &a * &b);
+        assert_eq!(c.trace().value, (&a * &b).trace());
+        assert_approx_equal!(c.norm().value, (&a * &b).norm(), 1e-12);
+
+        // d/dA tr(AB) = B'
+        assert_eq!(c.trace().accumulate().wrt(&a_var), b.transpose());
+    }
+
+    #[test]
+    fn test_least_squares_gradient() {
+        let g = Graph::new();
+
+        let x = DMatrix::from_row_slice(4, 2, &[1.0, 0.5, 1.0, 1.5, 1.0, 2.0, 1.0, 3.5]);
+        let y = DVector::from_vec(vec![1.0, 2.2, 2.9, 5.1]);
+        let beta = DVector::from_vec(vec![0.1, 1.3]);
+
+        // L(b) = |X b - y|^2, dL/db = 2 X' (X b - y)
+        let beta_var = g.vector(&beta);
+        let residuals = &(&x * &beta_var) - &y;
+        let loss = residuals.norm_squared();
+
+        let expected = 2.0 * x.transpose() * (&x * &beta - &y);
+        let gradient = loss.accumulate().wrt(&beta_var);
+
+        assert_approx_equal!((&gradient - &expected).norm(), 0.0, 1e-12);
+    }
+}
@@ -7,7 +7,7 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::{variable::Variable, vertex::Arity};
+use crate::{variable::Variable, vertex::Arity, Float, Operation};
 use std::iter::{Product, Sum};
 use std::ops::Neg;
 use std::ops::{Add, AddAssign};
@@ -20,35 +20,27 @@ use std::ops::{Sub, SubAssign};
 /// d/dx x + y = 1
 /// d/dy x + y = 1
 
-/// AddAssign: Variable<'v> += Variable<'v>
-impl<'v> AddAssign<Variable<'v>> for Variable<'v> {
+/// AddAssign: Variable<'v, T> += Variable<'v, T>
+impl<'v, T: Float> AddAssign<Variable<'v, T>> for Variable<'v, T> {
     #[inline]
-    fn add_assign(&mut self, other: Variable<'v>) {
+    fn add_assign(&mut self, other: Variable<'v, T>) {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         *self = *self + other;
     }
 }
 
-/// AddAssign: Variable<'v> += f64
-impl<'v> AddAssign<f64> for Variable<'v> {
+/// AddAssign: Variable<'v, T> += T
+impl<'v, T: Float> AddAssign<T> for Variable<'v, T> {
     #[inline]
-    fn add_assign(&mut self, other: f64) {
+    fn add_assign(&mut self, other: T) {
         *self = *self + other;
     }
 }
 
-/// AddAssign: f64 += Variable<'v>
-impl<'v> AddAssign<Variable<'v>> for f64 {
-    #[inline]
-    fn add_assign(&mut self, other: Variable<'v>) {
-        *self = *self + other.value;
-    }
-}
-
-/// Variable<'v> + Variable<'v>
-impl<'v> Add<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> + Variable<'v, T>
+impl<'v, T: Float> Add<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -66,22 +58,25 @@ impl<'v> Add<Variable<'v>> for Variable<'v> {
     /// assert_eq!(grad.wrt(&y), 1.0);
     /// ```
     #[inline]
-    fn add(self, other: Variable<'v>) -> Self::Output {
+    fn add(self, other: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         Variable {
             graph: self.graph,
             value: self.value + other.value,
-            index: self
-                .graph
-                .push(Arity::Binary, &[self.index, other.index], &[1.0, 1.0]),
+            index: self.graph.push(
+                Operation::Add,
+                Arity::Binary,
+                &[self.index, other.index],
+                &[T::one(), T::one()],
+            ),
         }
     }
 }
 
-/// Variable<'v> + f64
-impl<'v> Add<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> + T
+impl<'v, T: Float> Add<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -98,75 +93,46 @@ impl<'v> Add<f64> for Variable<'v> {
     /// assert_eq!(grad.wrt(&x), 1.0);
     /// ```
     #[inline]
-    fn add(self, other: f64) -> Self::Output {
+    fn add(self, other: T) -> Self::Output {
         Variable {
             graph: self.graph,
             value: self.value + other,
-            index: self
-                .graph
-                .push(Arity::Binary, &[self.index, self.index], &[1.0, 0.0]),
+            index: self.graph.push(
+                Operation::AddScalar(other),
+                Arity::Binary,
+                &[self.index, self.index],
+                &[T::one(), T::zero()],
+            ),
         }
     }
 }
 
-/// f64 + Variable<'v>
-impl<'v> Add<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    /// ```
-    /// # use RustQuant_autodiff::*;    
-    ///
-    /// let g = Graph::new();
-    ///
-    /// let a = 5.0;
-    /// let x = g.var(2.0);
-    /// let z = a + x;
-    ///
-    /// let grad = z.accumulate();
-    ///
-    /// assert_eq!(z.value, 7.0);
-    /// assert_eq!(grad.wrt(&x), 1.0);
-    /// ```
-    #[inline]
-    fn add(self, other: Variable<'v>) -> Self::Output {
-        other + self
-    }
-}
-
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 /// Overload the standard division operator (`/`).
 /// d/dx x/y = 1/y
 /// d/dy x/y = -x/y^2
 
-/// `DivAssign`: Variable<'v> /= Variable<'v>
-impl<'v> DivAssign<Variable<'v>> for Variable<'v> {
+/// `DivAssign`: Variable<'v, T> /= Variable<'v, T>
+impl<'v, T: Float> DivAssign<Variable<'v, T>> for Variable<'v, T> {
     #[inline]
-    fn div_assign(&mut self, other: Variable<'v>) {
+    fn div_assign(&mut self, other: Variable<'v, T>) {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         *self = *self / other;
     }
 }
 
-/// `DivAssign`: Variable<'v> /= f64
-impl<'v> DivAssign<f64> for Variable<'v> {
+/// `DivAssign`: Variable<'v, T> /= T
+impl<'v, T: Float> DivAssign<T> for Variable<'v, T> {
     #[inline]
-    fn div_assign(&mut self, other: f64) {
+    fn div_assign(&mut self, other: T) {
         *self = *self / other;
     }
 }
 
-/// `DivAssign`: f64 /= Variable<'v>
-impl<'v> DivAssign<Variable<'v>> for f64 {
-    #[inline]
-    fn div_assign(&mut self, other: Variable<'v>) {
-        *self = *self / other.value;
-    }
-}
-
-/// Variable<'v> / Variable<'v>
-impl<'v> Div<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> / Variable<'v, T>
+impl<'v, T: Float> Div<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -184,16 +150,16 @@ impl<'v> Div<Variable<'v>> for Variable<'v> {
     /// assert_eq!(grad.wrt(&y), - 5.0 / (2.0 * 2.0));
     /// ```
     #[inline]
-    fn div(self, other: Variable<'v>) -> Self::Output {
+    fn div(self, other: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         self * other.recip()
     }
 }
 
-/// Variable<'v> / f64
-impl<'v> Div<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> / T
+impl<'v, T: Float> Div<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -211,53 +177,21 @@ impl<'v> Div<f64> for Variable<'v> {
     /// ```
     #[inline]
     #[allow(clippy::suspicious_arithmetic_impl)]
-    fn div(self, other: f64) -> Self::Output {
+    fn div(self, other: T) -> Self::Output {
         self * other.recip()
     }
 }
 
-/// f64 / Variable<'v>
-impl<'v> Div<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    /// ```
-    /// # use RustQuant_autodiff::*;
-    ///   
-    /// let g = Graph::new();
-    ///
-    /// let a = 5.0;
-    /// let x = g.var(2.0);
-    /// let z = a / x;
-    ///
-    /// let grad = z.accumulate();
-    ///
-    /// assert_eq!(z.value, 5.0 / 2.0);
-    /// assert_eq!(grad.wrt(&x), - 5.0 / (2.0*2.0));
-    /// ```
-    #[inline]
-    fn div(self, other: Variable<'v>) -> Self::Output {
-        Variable {
-            graph: other.graph,
-            value: self / other.value,
-            index: other.graph.push(
-                Arity::Binary,
-                &[other.index, other.index],
-                &[0.0, -self / (other.value * other.value)],
-            ),
-        }
-    }
-}
-
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // OVERLOADING: STANDARD MATH OPERATORS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<'v> std::ops::Neg for Variable<'v> {
+impl<'v, T: Float> std::ops::Neg for Variable<'v, T> {
     type Output = Self;
 
     #[inline]
     fn neg(self) -> Self::Output {
-        self * -1.0
+        self * -T::one()
     }
 }
 
@@ -265,7 +199,7 @@ impl<'v> std::ops::Neg for Variable<'v> {
 // OVERLOADING: PRIMITIVE FUNCTIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<'v> Variable<'v> {
+impl<'v, T: Float> Variable<'v, T> {
     /// Absolute value function.
     /// d/dx abs(x) = sign(x)
     ///
@@ -292,9 +226,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.abs(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.signum()]),
+            index: self.graph.push_second_order(
+                Operation::Abs,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.signum()],
+                [T::zero(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -318,10 +256,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.acos(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Acos,
                 Arity::Unary,
                 &[self.index],
-                &[((1.0 - self.value.powi(2)).sqrt()).recip().neg()],
+                &[((T::one() - self.value.powi(2)).sqrt()).recip().neg()],
+                [
+                    -self.value / (T::one() - self.value.powi(2)).powf(T::from_f64(1.5)),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -346,10 +290,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.acosh(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Acosh,
                 Arity::Unary,
                 &[self.index],
-                &[((self.value - 1.0).sqrt() * (self.value + 1.0).sqrt()).recip()],
+                &[((self.value - T::one()).sqrt() * (self.value + T::one()).sqrt()).recip()],
+                [
+                    -self.value / (self.value.powi(2) - T::one()).powf(T::from_f64(1.5)),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -376,14 +326,20 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.asin(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Asin,
                 Arity::Unary,
                 &[self.index],
-                &[if (self.value > -1.0) && (self.value < 1.0) {
-                    ((1.0 - self.value.powi(2)).sqrt()).recip()
+                &[if (self.value > -T::one()) && (self.value < T::one()) {
+                    ((T::one() - self.value.powi(2)).sqrt()).recip()
                 } else {
-                    f64::NAN
+                    T::nan()
                 }],
+                [
+                    self.value / (T::one() - self.value.powi(2)).powf(T::from_f64(1.5)),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -409,10 +365,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.asinh(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Asinh,
                 Arity::Unary,
                 &[self.index],
-                &[((1.0 + self.value.powi(2)).sqrt()).recip()],
+                &[((T::one() + self.value.powi(2)).sqrt()).recip()],
+                [
+                    -self.value / (T::one() + self.value.powi(2)).powf(T::from_f64(1.5)),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -438,10 +400,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.atan(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Atan,
                 Arity::Unary,
                 &[self.index],
-                &[((1.0 + self.value.powi(2)).recip())],
+                &[((T::one() + self.value.powi(2)).recip())],
+                [
+                    -T::from_f64(2.0) * self.value / (T::one() + self.value.powi(2)).powi(2),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -468,10 +436,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.atanh(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Atanh,
                 Arity::Unary,
                 &[self.index],
-                &[((1.0 - self.value.powi(2)).recip())],
+                &[((T::one() - self.value.powi(2)).recip())],
+                [
+                    T::from_f64(2.0) * self.value / (T::one() - self.value.powi(2)).powi(2),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -498,10 +472,17 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.cbrt(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Cbrt,
                 Arity::Unary,
                 &[self.index],
-                &[((3.0 * self.value.powf(2.0 / 3.0)).recip())],
+                &[((T::from_f64(3.0) * self.value.powf(T::from_f64(2.0 / 3.0))).recip())],
+                [
+                    -T::from_f64(2.0)
+                        / (T::from_f64(9.0) * self.value.powf(T::from_f64(5.0 / 3.0))),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -529,9 +510,13 @@ impl<'v> Variable<'v> {
             graph: self.graph,
             value: self.value.cos(),
             // index: self.graph.push_unary(self.index, self.value.sin().neg()),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.sin().neg()]),
+            index: self.graph.push_second_order(
+                Operation::Cos,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.sin().neg()],
+                [self.value.cos().neg(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -557,9 +542,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.cosh(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.sinh()]),
+            index: self.graph.push_second_order(
+                Operation::Cosh,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.sinh()],
+                [self.value.cosh(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -584,9 +573,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.exp(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.exp()]),
+            index: self.graph.push_second_order(
+                Operation::Exp,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.exp()],
+                [self.value.exp(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -612,10 +605,12 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.exp2(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Exp2,
                 Arity::Unary,
                 &[self.index],
-                &[2_f64.powf(self.value) * 2_f64.ln()],
+                &[self.value.exp2() * T::LN_2()],
+                [self.value.exp2() * T::LN_2().powi(2), T::zero(), T::zero()],
             ),
         }
     }
@@ -643,9 +638,13 @@ impl<'v> Variable<'v> {
             graph: self.graph,
             value: self.value.exp_m1(),
             // index: self.graph.push_unary(self.index, self.value.exp()),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.exp()]),
+            index: self.graph.push_second_order(
+                Operation::ExpM1,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.exp()],
+                [self.value.exp(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -670,9 +669,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.ln(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.recip()]),
+            index: self.graph.push_second_order(
+                Operation::Ln,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.recip()],
+                [self.value.powi(2).recip().neg(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -698,9 +701,17 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.ln_1p(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[(1.0 + self.value).recip()]),
+            index: self.graph.push_second_order(
+                Operation::Ln1p,
+                Arity::Unary,
+                &[self.index],
+                &[(T::one() + self.value).recip()],
+                [
+                    (T::one() + self.value).powi(2).recip().neg(),
+                    T::zero(),
+                    T::zero(),
+                ],
+            ),
         }
     }
 
@@ -726,9 +737,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.log10(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.recip()]),
+            index: self.graph.push_second_order(
+                Operation::Log10,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.recip()],
+                [self.value.powi(2).recip().neg(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -754,9 +769,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.log2(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.recip()]),
+            index: self.graph.push_second_order(
+                Operation::Log2,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.recip()],
+                [self.value.powi(2).recip().neg(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -793,10 +812,12 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.recip(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Recip,
                 Arity::Unary,
                 &[self.index],
                 &[self.value.powi(2).recip().neg()],
+                [T::from_f64(2.0) / self.value.powi(3), T::zero(), T::zero()],
             ),
         }
     }
@@ -823,9 +844,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.sin(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.cos()]),
+            index: self.graph.push_second_order(
+                Operation::Sin,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.cos()],
+                [self.value.sin().neg(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -851,9 +876,13 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.sinh(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.cosh()]),
+            index: self.graph.push_second_order(
+                Operation::Sinh,
+                Arity::Unary,
+                &[self.index],
+                &[self.value.cosh()],
+                [self.value.sinh(), T::zero(), T::zero()],
+            ),
         }
     }
 
@@ -878,10 +907,18 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.sqrt(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Sqrt,
                 Arity::Unary,
                 &[self.index],
-                &[(2.0 * self.value.sqrt()).recip()],
+                &[(T::from_f64(2.0) * self.value.sqrt()).recip()],
+                [
+                    (T::from_f64(4.0) * self.value.powf(T::from_f64(1.5)))
+                        .recip()
+                        .neg(),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -908,10 +945,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.tan(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Tan,
                 Arity::Unary,
                 &[self.index],
                 &[(self.value.cos().powi(2)).recip()],
+                [
+                    T::from_f64(2.0) * self.value.tan() / self.value.cos().powi(2),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -938,10 +981,16 @@ impl<'v> Variable<'v> {
         Variable {
             graph: self.graph,
             value: self.value.tanh(),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Tanh,
                 Arity::Unary,
                 &[self.index],
                 &[(self.value.cosh().powi(2)).recip()],
+                [
+                    -T::from_f64(2.0) * self.value.tanh() / self.value.cosh().powi(2),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -951,7 +1000,7 @@ impl<'v> Variable<'v> {
 // OVERLOADING: ITERATORS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<'v> Sum<Variable<'v>> for Variable<'v> {
+impl<'v, T: Float> Sum<Variable<'v, T>> for Variable<'v, T> {
     /// ```
     /// # use RustQuant_autodiff::*;
     ///
@@ -968,13 +1017,13 @@ impl<'v> Sum<Variable<'v>> for Variable<'v> {
     /// }
     /// ```
     #[inline]
-    fn sum<I: Iterator<Item = Variable<'v>>>(iter: I) -> Self {
+    fn sum<I: Iterator<Item = Variable<'v, T>>>(iter: I) -> Self {
         iter.reduce(|x, y| x + y)
             .expect("Cannot call sum() since vector is empty. Exiting ...")
     }
 }
 
-impl<'v> Product<Variable<'v>> for Variable<'v> {
+impl<'v, T: Float> Product<Variable<'v, T>> for Variable<'v, T> {
     /// ```
     /// # use RustQuant_autodiff::*;
     ///
@@ -996,7 +1045,7 @@ impl<'v> Product<Variable<'v>> for Variable<'v> {
     /// }
     /// ```
     #[inline]
-    fn product<I: Iterator<Item = Variable<'v>>>(iter: I) -> Self {
+    fn product<I: Iterator<Item = Variable<'v, T>>>(iter: I) -> Self {
         iter.reduce(|x, y| x * y)
             .expect("Cannot call product() since vector is empty. Exiting ...")
     }
@@ -1015,70 +1064,59 @@ pub trait Log<T> {
     fn log(&self, base: T) -> Self::Output;
 }
 
-// log_{Variable<'v>}(Variable<'v>)
+// log_{Variable<'v, T>}(Variable<'v, T>)
 // f(x,y) = log_{x}(y)
 // df/dx = -ln(y) / (x * ln^2(x))
 // df/dy = 1 / (y * ln(x))
-impl<'v> Log<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+impl<'v, T: Float> Log<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn log(&self, base: Variable) -> Self::Output {
+    fn log(&self, base: Variable<'v, T>) -> Self::Output {
         Self::Output {
             graph: self.graph,
-            value: f64::log(self.value, base.value),
-            index: self.graph.push(
+            value: T::log(self.value, base.value),
+            index: self.graph.push_second_order(
+                Operation::Log,
                 Arity::Binary,
                 &[self.index, base.index],
                 &[
-                    -f64::ln(self.value) / (base.value * f64::ln(base.value).powi(2)),
-                    1.0 / (self.value * f64::ln(base.value)),
+                    -T::ln(self.value) / (base.value * T::ln(base.value).powi(2)),
+                    T::one() / (self.value * T::ln(base.value)),
                 ],
-            ),
-        }
-    }
-}
-
-// log_{Variable<'v>}(f64)
-// f(x,y) = log_{x}(b)
-// df/dx = -ln(b) / (x * ln^2(x))
-// df/db = 0
-impl<'v> Log<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    #[inline]
-    fn log(&self, base: Variable<'v>) -> Self::Output {
-        Self::Output {
-            graph: base.graph,
-            value: f64::log(*self, base.value),
-            index: base.graph.push(
-                Arity::Binary,
-                &[base.index, base.index],
-                &[
-                    -f64::ln(*self) / (base.value * f64::ln(base.value).powi(2)),
-                    0.0,
+                [
+                    -T::one() / (self.value.powi(2) * T::ln(base.value)),
+                    -T::one() / (self.value * base.value * T::ln(base.value).powi(2)),
+                    T::ln(self.value) * (T::ln(base.value) + T::from_f64(2.0))
+                        / (base.value.powi(2) * T::ln(base.value).powi(3)),
                 ],
             ),
         }
     }
 }
 
-// log_{f64}(Variable<'v>)
+// log_{T}(Variable<'v, T>)
 // f(x,y) = log_{b}(y)
 // df/db = 0
 // df/dy = 1 / (y * ln(b))
-impl<'v> Log<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+impl<'v, T: Float> Log<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn log(&self, base: f64) -> Self::Output {
+    fn log(&self, base: T) -> Self::Output {
         Self::Output {
             graph: self.graph,
-            value: f64::log(self.value, base),
-            index: self.graph.push(
+            value: T::log(self.value, base),
+            index: self.graph.push_second_order(
+                Operation::LogScalar(base),
                 Arity::Binary,
                 &[self.index, self.index],
-                &[0.0, 1.0 / (f64::ln(base) * self.value)],
+                &[T::zero(), T::one() / (T::ln(base) * self.value)],
+                [
+                    T::zero(),
+                    T::zero(),
+                    -T::one() / (T::ln(base) * self.value.powi(2)),
+                ],
             ),
         }
     }
@@ -1097,60 +1135,59 @@ pub trait Min<T> {
     fn min(&self, other: T) -> Self::Output;
 }
 
-// min{ Variable<'v> , Variable<'v> }
-impl<'v> Min<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+// min{ Variable<'v, T> , Variable<'v, T> }
+impl<'v, T: Float> Min<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn min(&self, rhs: Variable<'v>) -> Self::Output {
+    fn min(&self, rhs: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, rhs.graph));
 
         Self::Output {
             graph: self.graph,
             value: self.value.min(rhs.value),
             index: self.graph.push(
+                Operation::Min,
                 Arity::Binary,
                 &[self.index, rhs.index],
                 &[
-                    if self.value < rhs.value { 1.0 } else { 0.0 },
-                    if self.value > rhs.value { 1.0 } else { 0.0 },
+                    if self.value < rhs.value {
+                        T::one()
+                    } else {
+                        T::zero()
+                    },
+                    if self.value > rhs.value {
+                        T::one()
+                    } else {
+                        T::zero()
+                    },
                 ],
             ),
         }
     }
 }
 
-// min{ Variable<'v> , f64 }
-impl<'v> Min<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+// min{ Variable<'v, T> , T }
+impl<'v, T: Float> Min<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn min(&self, rhs: f64) -> Self::Output {
+    fn min(&self, rhs: T) -> Self::Output {
         Self::Output {
             graph: self.graph,
             value: self.value.min(rhs),
             index: self.graph.push(
+                Operation::MinScalar(rhs),
                 Arity::Binary,
                 &[self.index, self.index],
-                &[if self.value < rhs { 1.0 } else { 0.0 }, 0.0],
-            ),
-        }
-    }
-}
-
-// min{ f64 , Variable<'v> }
-impl<'v> Min<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    #[inline]
-    fn min(&self, rhs: Variable<'v>) -> Self::Output {
-        Self::Output {
-            graph: rhs.graph,
-            value: f64::min(*self, rhs.value),
-            index: rhs.graph.push(
-                Arity::Binary,
-                &[rhs.index, rhs.index],
-                &[0.0, if self < &rhs.value { 1.0 } else { 0.0 }],
+                &[
+                    if self.value < rhs {
+                        T::one()
+                    } else {
+                        T::zero()
+                    },
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -1169,60 +1206,59 @@ pub trait Max<T> {
     fn max(&self, other: T) -> Self::Output;
 }
 
-// max{ Variable<'v> , Variable<'v> }
-impl<'v> Max<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+// max{ Variable<'v, T> , Variable<'v, T> }
+impl<'v, T: Float> Max<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn max(&self, rhs: Variable<'v>) -> Self::Output {
+    fn max(&self, rhs: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, rhs.graph));
 
         Self::Output {
             graph: self.graph,
             value: self.value.max(rhs.value),
             index: self.graph.push(
+                Operation::Max,
                 Arity::Binary,
                 &[self.index, rhs.index],
                 &[
-                    if self.value > rhs.value { 1.0 } else { 0.0 },
-                    if self.value < rhs.value { 1.0 } else { 0.0 },
+                    if self.value > rhs.value {
+                        T::one()
+                    } else {
+                        T::zero()
+                    },
+                    if self.value < rhs.value {
+                        T::one()
+                    } else {
+                        T::zero()
+                    },
                 ],
             ),
         }
     }
 }
 
-// max{ Variable<'v> , f64 }
-impl<'v> Max<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+// max{ Variable<'v, T> , T }
+impl<'v, T: Float> Max<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn max(&self, rhs: f64) -> Self::Output {
+    fn max(&self, rhs: T) -> Self::Output {
         Self::Output {
             graph: self.graph,
             value: self.value.max(rhs),
             index: self.graph.push(
+                Operation::MaxScalar(rhs),
                 Arity::Binary,
                 &[self.index, self.index],
-                &[if self.value > rhs { 1.0 } else { 0.0 }, 0.0],
-            ),
-        }
-    }
-}
-
-// max{ f64 , Variable<'v> }
-impl<'v> Max<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    #[inline]
-    fn max(&self, rhs: Variable<'v>) -> Self::Output {
-        Self::Output {
-            graph: rhs.graph,
-            value: f64::max(*self, rhs.value),
-            index: rhs.graph.push(
-                Arity::Binary,
-                &[rhs.index, rhs.index],
-                &[0.0, if self > &rhs.value { 1.0 } else { 0.0 }],
+                &[
+                    if self.value > rhs {
+                        T::one()
+                    } else {
+                        T::zero()
+                    },
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -1233,35 +1269,27 @@ impl<'v> Max<Variable<'v>> for f64 {
 /// d/dx x * y = y
 /// d/dy x * y = x
 
-/// MulAssign: Variable<'v> *= Variable<'v>
-impl<'v> MulAssign<Variable<'v>> for Variable<'v> {
+/// MulAssign: Variable<'v, T> *= Variable<'v, T>
+impl<'v, T: Float> MulAssign<Variable<'v, T>> for Variable<'v, T> {
     #[inline]
-    fn mul_assign(&mut self, other: Variable<'v>) {
+    fn mul_assign(&mut self, other: Variable<'v, T>) {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         *self = *self * other;
     }
 }
 
-/// MulAssign: Variable<'v> *= f64
-impl<'v> MulAssign<f64> for Variable<'v> {
+/// MulAssign: Variable<'v, T> *= T
+impl<'v, T: Float> MulAssign<T> for Variable<'v, T> {
     #[inline]
-    fn mul_assign(&mut self, other: f64) {
+    fn mul_assign(&mut self, other: T) {
         *self = *self * other;
     }
 }
 
-/// MulAssign: f64 *= Variable<'v>
-impl<'v> MulAssign<Variable<'v>> for f64 {
-    #[inline]
-    fn mul_assign(&mut self, other: Variable<'v>) {
-        *self = *self * other.value;
-    }
-}
-
-/// Variable<'v> * Variable<'v>
-impl<'v> Mul<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> * Variable<'v, T>
+impl<'v, T: Float> Mul<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -1279,24 +1307,26 @@ impl<'v> Mul<Variable<'v>> for Variable<'v> {
     /// assert_eq!(grad.wrt(&y), 5.0);
     /// ```
     #[inline]
-    fn mul(self, other: Variable<'v>) -> Self::Output {
+    fn mul(self, other: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         Variable {
             graph: self.graph,
             value: self.value * other.value,
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Mul,
                 Arity::Binary,
                 &[self.index, other.index],
                 &[other.value, self.value],
+                [T::zero(), T::one(), T::zero()],
             ),
         }
     }
 }
 
-/// Variable<'v> * f64
-impl<'v> Mul<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> * T
+impl<'v, T: Float> Mul<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -1313,41 +1343,20 @@ impl<'v> Mul<f64> for Variable<'v> {
     /// assert_eq!(grad.wrt(&x), 2.0);
     /// ```
     #[inline]
-    fn mul(self, other: f64) -> Self::Output {
+    fn mul(self, other: T) -> Self::Output {
         Variable {
             graph: self.graph,
             value: self.value * other,
-            index: self
-                .graph
-                .push(Arity::Binary, &[self.index, self.index], &[other, 0.0]),
+            index: self.graph.push(
+                Operation::MulScalar(other),
+                Arity::Binary,
+                &[self.index, self.index],
+                &[other, T::zero()],
+            ),
         }
     }
 }
 
-/// f64 * Variable<'v>
-impl<'v> Mul<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    /// ```
-    /// # use RustQuant_autodiff::*;
-    ///
-    /// let g = Graph::new();
-    ///
-    /// let a = 5.0;
-    /// let x = g.var(2.0);
-    /// let z = a * x;
-    ///
-    /// let grad = z.accumulate();
-    ///
-    /// assert_eq!(z.value, 10.0);
-    /// assert_eq!(grad.wrt(&x), 5.0);
-    /// ```
-    #[inline]
-    fn mul(self, other: Variable<'v>) -> Self::Output {
-        other * self
-    }
-}
-
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // OVERLOADING: POWER FUNCTION TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1360,60 +1369,57 @@ pub trait Powf<T> {
     fn powf(&self, other: T) -> Self::Output;
 }
 
-// Variable<'v> ^ Variable<'v>
-impl<'v> Powf<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+// Variable<'v, T> ^ Variable<'v, T>
+impl<'v, T: Float> Powf<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn powf(&self, other: Variable<'v>) -> Self::Output {
+    fn powf(&self, other: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         Self::Output {
             graph: self.graph,
             value: self.value.powf(other.value),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Powf,
                 Arity::Binary,
                 &[self.index, other.index],
                 &[
-                    other.value * f64::powf(self.value, other.value - 1.),
-                    f64::powf(self.value, other.value) * f64::ln(self.value),
+                    other.value * T::powf(self.value, other.value - T::one()),
+                    T::powf(self.value, other.value) * T::ln(self.value),
+                ],
+                [
+                    other.value
+                        * (other.value - T::one())
+                        * T::powf(self.value, other.value - T::from_f64(2.0)),
+                    T::powf(self.value, other.value - T::one())
+                        * (T::one() + other.value * T::ln(self.value)),
+                    T::powf(self.value, other.value) * T::ln(self.value).powi(2),
                 ],
             ),
         }
     }
 }
 
-// Variable<'v> ^ f64
-impl<'v> Powf<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+// Variable<'v, T> ^ T
+impl<'v, T: Float> Powf<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn powf(&self, n: f64) -> Self::Output {
+    fn powf(&self, n: T) -> Self::Output {
         Self::Output {
             graph: self.graph,
-            value: f64::powf(self.value, n),
-            index: self.graph.push(
+            value: T::powf(self.value, n),
+            index: self.graph.push_second_order(
+                Operation::PowfScalar(n),
                 Arity::Binary,
                 &[self.index, self.index],
-                &[n * f64::powf(self.value, n - 1.0), 0.0],
-            ),
-        }
-    }
-}
-
-// f64 ^ Variable<'v>
-impl<'v> Powf<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    #[inline]
-    fn powf(&self, other: Variable<'v>) -> Self::Output {
-        Self::Output {
-            graph: other.graph,
-            value: f64::powf(*self, other.value),
-            index: other.graph.push(
-                Arity::Binary,
-                &[other.index, other.index],
-                &[0.0, other.value * f64::powf(*self, other.value - 1.0)],
+                &[n * T::powf(self.value, n - T::one()), T::zero()],
+                [
+                    n * (n - T::one()) * T::powf(self.value, n - T::from_f64(2.0)),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -1428,68 +1434,68 @@ pub trait Powi<T> {
     fn powi(&self, other: T) -> Self::Output;
 }
 
-// Variable<'v> ^ Variable<'v>
-impl<'v> Powi<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+// Variable<'v, T> ^ Variable<'v, T>
+impl<'v, T: Float> Powi<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
-    fn powi(&self, other: Variable<'v>) -> Self::Output {
+    fn powi(&self, other: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         Self::Output {
             graph: self.graph,
             value: self.value.powf(other.value),
-            index: self.graph.push(
+            index: self.graph.push_second_order(
+                Operation::Powi,
                 Arity::Binary,
                 &[self.index, other.index],
                 &[
-                    other.value * f64::powf(self.value, other.value - 1.),
-                    f64::powf(self.value, other.value) * f64::ln(self.value),
+                    other.value * T::powf(self.value, other.value - T::one()),
+                    T::powf(self.value, other.value) * T::ln(self.value),
+                ],
+                [
+                    other.value
+                        * (other.value - T::one())
+                        * T::powf(self.value, other.value - T::from_f64(2.0)),
+                    T::powf(self.value, other.value - T::one())
+                        * (T::one() + other.value * T::ln(self.value)),
+                    T::powf(self.value, other.value) * T::ln(self.value).powi(2),
                 ],
             ),
         }
     }
 }
 
-// Variable<'v> ^ f64
-impl<'v> Powi<i32> for Variable<'v> {
-    type Output = Variable<'v>;
+// Variable<'v, T> ^ i32
+impl<'v, T: Float> Powi<i32> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     #[inline]
     fn powi(&self, n: i32) -> Self::Output {
         Self::Output {
             graph: self.graph,
-            value: f64::powi(self.value, n),
-            index: self.graph.push(
+            value: T::powi(self.value, n),
+            index: self.graph.push_second_order(
+                Operation::PowiInteger(n),
                 Arity::Binary,
                 &[self.index, self.index],
-                &[f64::from(n) * f64::powi(self.value, n - 1), 0.0],
-            ),
-        }
-    }
-}
-
-// f64 ^ Variable<'v>
-impl<'v> Powi<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
-
-    #[inline]
-    fn powi(&self, other: Variable<'v>) -> Self::Output {
-        Self::Output {
-            graph: other.graph,
-            value: f64::powf(*self, other.value),
-            index: other.graph.push(
-                Arity::Binary,
-                &[other.index, other.index],
-                &[0.0, other.value * f64::powf(*self, other.value - 1.0)],
+                &[
+                    T::from_f64(f64::from(n)) * T::powi(self.value, n - 1),
+                    T::zero(),
+                ],
+                [
+                    T::from_f64(f64::from(n))
+                        * T::from_f64(f64::from(n - 1))
+                        * T::powi(self.value, n - 2),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
 }
 
-use std::f64::consts::PI;
-
-impl<'v> Variable<'v> {
+impl<'v, T: Float> Variable<'v, T> {
     /// Error function.
     /// d/dx erf(x) = 2e^(-x^2) / sqrt(PI)
     ///
@@ -1513,11 +1519,18 @@ impl<'v> Variable<'v> {
 
         Variable {
             graph: self.graph,
-            value: errorfunctions::RealErrorFunctions::erf(self.value),
-            index: self.graph.push(
+            value: T::erf(self.value),
+            index: self.graph.push_second_order(
+                Operation::Erf,
                 Arity::Unary,
                 &[self.index],
-                &[2.0 * self.value.powi(2).neg().exp() / PI.sqrt()],
+                &[T::from_f64(2.0) * self.value.powi(2).neg().exp() / T::PI().sqrt()],
+                [
+                    -T::from_f64(4.0) * self.value * self.value.powi(2).neg().exp()
+                        / T::PI().sqrt(),
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
@@ -1545,50 +1558,128 @@ impl<'v> Variable<'v> {
 
         Variable {
             graph: self.graph,
-            value: errorfunctions::RealErrorFunctions::erfc(self.value),
-            index: self.graph.push(
+            value: T::erfc(self.value),
+            index: self.graph.push_second_order(
+                Operation::Erfc,
                 Arity::Unary,
                 &[self.index],
-                &[((2.0 * self.value.powi(2).neg().exp()).neg() / PI.sqrt())],
+                &[((T::from_f64(2.0) * self.value.powi(2).neg().exp()).neg() / T::PI().sqrt())],
+                [
+                    T::from_f64(4.0) * self.value * self.value.powi(2).neg().exp() / T::PI().sqrt(),
+                    T::zero(),
+                    T::zero(),
+                ],
+            ),
+        }
+    }
+
+    /// Standard normal cumulative distribution function.
+    /// N(x) = erfc(-x / sqrt(2)) / 2
+    /// d/dx N(x) = n(x), the standard normal density.
+    ///
+    /// ```
+    /// # use RustQuant_utils::assert_approx_equal;
+    /// # use RustQuant_autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let x = g.var(1.0);
+    /// let z = x.norm_cdf();
+    /// let grad = z.accumulate();
+    ///
+    /// assert_approx_equal!(z.value,      0.84134474607, 1e-10);
+    /// assert_approx_equal!(grad.wrt(&x), 0.24197072451, 1e-10);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn norm_cdf(self) -> Self {
+        let density = standard_normal_density(self.value);
+
+        Variable {
+            graph: self.graph,
+            value: T::from_f64(0.5) * T::erfc(-self.value / T::SQRT_2()),
+            index: self.graph.push_second_order(
+                Operation::NormCdf,
+                Arity::Unary,
+                &[self.index],
+                &[density],
+                [-self.value * density, T::zero(), T::zero()],
+            ),
+        }
+    }
+
+    /// Standard normal probability density function.
+    /// n(x) = e^(-x^2 / 2) / sqrt(2 PI)
+    /// d/dx n(x) = -x n(x)
+    ///
+    /// ```
+    /// # use RustQuant_utils::assert_approx_equal;
+    /// # use RustQuant_autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let x = g.var(1.0);
+    /// let z = x.norm_pdf();
+    /// let grad = z.accumulate();
+    ///
+    /// assert_approx_equal!(z.value,       0.24197072451, 1e-10);
+    /// assert_approx_equal!(grad.wrt(&x), -0.24197072451, 1e-10);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn norm_pdf(self) -> Self {
+        let density = standard_normal_density(self.value);
+
+        Variable {
+            graph: self.graph,
+            value: density,
+            index: self.graph.push_second_order(
+                Operation::NormPdf,
+                Arity::Unary,
+                &[self.index],
+                &[-self.value * density],
+                [
+                    (self.value.powi(2) - T::one()) * density,
+                    T::zero(),
+                    T::zero(),
+                ],
             ),
         }
     }
 }
 
+/// Standard normal density, n(x) = e^(-x^2 / 2) / sqrt(2 PI).
+#[inline]
+pub(crate) fn standard_normal_density<T: Float>(x: T) -> T {
+    (-T::from_f64(0.5) * x * x).exp() / (T::from_f64(2.0) * T::PI()).sqrt()
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 /// Overload the standard subtraction operator (`-`).
 /// d/dx x - y = 1
 /// d/dy x - y = -1
 
-/// `SubAssign`: Variable<'v> -= Variable<'v>
-impl<'v> SubAssign<Variable<'v>> for Variable<'v> {
+/// `SubAssign`: Variable<'v, T> -= Variable<'v, T>
+impl<'v, T: Float> SubAssign<Variable<'v, T>> for Variable<'v, T> {
     #[inline]
-    fn sub_assign(&mut self, other: Variable<'v>) {
+    fn sub_assign(&mut self, other: Variable<'v, T>) {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         *self = *self - other;
     }
 }
 
-/// `SubAssign`: Variable<'v> -= f64
-impl<'v> SubAssign<f64> for Variable<'v> {
+/// `SubAssign`: Variable<'v, T> -= T
+impl<'v, T: Float> SubAssign<T> for Variable<'v, T> {
     #[inline]
-    fn sub_assign(&mut self, other: f64) {
+    fn sub_assign(&mut self, other: T) {
         *self = *self - other;
     }
 }
 
-/// `SubAssign`: f64 -= Variable<'v>
-impl<'v> SubAssign<Variable<'v>> for f64 {
-    #[inline]
-    fn sub_assign(&mut self, other: Variable<'v>) {
-        *self = *self - other.value;
-    }
-}
-
-/// Variable<'v> - Variable<'v>
-impl<'v> Sub<Variable<'v>> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> - Variable<'v, T>
+impl<'v, T: Float> Sub<Variable<'v, T>> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;    
@@ -1606,16 +1697,16 @@ impl<'v> Sub<Variable<'v>> for Variable<'v> {
     /// assert_eq!(grad.wrt(&y), -1.0);
     /// ```
     #[inline]
-    fn sub(self, other: Variable<'v>) -> Self::Output {
+    fn sub(self, other: Variable<'v, T>) -> Self::Output {
         assert!(std::ptr::eq(self.graph, other.graph));
 
         self.add(other.neg())
     }
 }
 
-/// Variable<'v> - f64
-impl<'v> Sub<f64> for Variable<'v> {
-    type Output = Variable<'v>;
+/// Variable<'v, T> - T
+impl<'v, T: Float> Sub<T> for Variable<'v, T> {
+    type Output = Variable<'v, T>;
 
     /// ```
     /// # use RustQuant_autodiff::*;
@@ -1632,41 +1723,247 @@ impl<'v> Sub<f64> for Variable<'v> {
     /// assert_eq!(grad.wrt(&x), 1.0);
     /// ```
     #[inline]
-    fn sub(self, other: f64) -> Self::Output {
+    fn sub(self, other: T) -> Self::Output {
         self.add(other.neg())
     }
 }
 
-/// f64 - Variable<'v>
-impl<'v> Sub<Variable<'v>> for f64 {
-    type Output = Variable<'v>;
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: SCALARS ON THE LEFT
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-    /// ```
-    /// # use RustQuant_autodiff::*;   
-    ///  
-    /// let g = Graph::new();
-    ///
-    /// let a = 5.0;
-    /// let x = g.var(2.0);
-    /// let z = a - x;
-    ///
-    /// let grad = z.accumulate();
-    ///
-    /// assert_eq!(z.value, 3.0);
-    /// assert_eq!(grad.wrt(&x), -1.0);
-    /// ```
-    #[inline]
-    fn sub(self, other: Variable<'v>) -> Self::Output {
-        Variable {
-            graph: other.graph,
-            value: self - other.value,
-            index: other
-                .graph
-                .push(Arity::Binary, &[other.index, other.index], &[0.0, -1.0]),
-        }
+// Operators and functions with a float on the left, e.g. `2.0 * x`.
+// These cannot be implemented for a generic `T: Float`, since `T` is not a
+// local type, so they are implemented for each float type by the macro
+// below, which calls these functions (also used to replay a `Tape`).
+
+// T / Variable<'v, T>
+pub(crate) fn scalar_div<T: Float>(c: T, x: Variable<'_, T>) -> Variable<'_, T> {
+    Variable {
+        graph: x.graph,
+        value: c / x.value,
+        index: x.graph.push_second_order(
+            Operation::ScalarDiv(c),
+            Arity::Binary,
+            &[x.index, x.index],
+            &[T::zero(), -c / (x.value * x.value)],
+            [T::zero(), T::zero(), T::from_f64(2.0) * c / x.value.powi(3)],
+        ),
+    }
+}
+
+// log_{Variable<'v, T>}(T)
+// f(x,y) = log_{x}(b)
+// df/dx = -ln(b) / (x * ln^2(x))
+// df/db = 0
+pub(crate) fn scalar_log<T: Float>(c: T, base: Variable<'_, T>) -> Variable<'_, T> {
+    Variable {
+        graph: base.graph,
+        value: T::log(c, base.value),
+        index: base.graph.push_second_order(
+            Operation::ScalarLog(c),
+            Arity::Binary,
+            &[base.index, base.index],
+            &[
+                -T::ln(c) / (base.value * T::ln(base.value).powi(2)),
+                T::zero(),
+            ],
+            [
+                T::ln(c) * (T::ln(base.value) + T::from_f64(2.0))
+                    / (base.value.powi(2) * T::ln(base.value).powi(3)),
+                T::zero(),
+                T::zero(),
+            ],
+        ),
+    }
+}
+
+// min{ T , Variable<'v, T> }
+pub(crate) fn scalar_min<T: Float>(c: T, x: Variable<'_, T>) -> Variable<'_, T> {
+    Variable {
+        graph: x.graph,
+        value: T::min(c, x.value),
+        index: x.graph.push(
+            Operation::ScalarMin(c),
+            Arity::Binary,
+            &[x.index, x.index],
+            &[T::zero(), if c < x.value { T::one() } else { T::zero() }],
+        ),
+    }
+}
+
+// max{ T , Variable<'v, T> }
+pub(crate) fn scalar_max<T: Float>(c: T, x: Variable<'_, T>) -> Variable<'_, T> {
+    Variable {
+        graph: x.graph,
+        value: T::max(c, x.value),
+        index: x.graph.push(
+            Operation::ScalarMax(c),
+            Arity::Binary,
+            &[x.index, x.index],
+            &[T::zero(), if c > x.value { T::one() } else { T::zero() }],
+        ),
+    }
+}
+
+// T ^ Variable<'v, T>, for both `powf` and `powi`.
+pub(crate) fn scalar_pow<T: Float>(c: T, x: Variable<'_, T>) -> Variable<'_, T> {
+    Variable {
+        graph: x.graph,
+        value: T::powf(c, x.value),
+        index: x.graph.push_second_order(
+            Operation::ScalarPow(c),
+            Arity::Binary,
+            &[x.index, x.index],
+            &[T::zero(), T::powf(c, x.value) * T::ln(c)],
+            [T::zero(), T::zero(), T::powf(c, x.value) * T::ln(c).powi(2)],
+        ),
+    }
+}
+
+// T - Variable<'v, T>
+pub(crate) fn scalar_sub<T: Float>(c: T, x: Variable<'_, T>) -> Variable<'_, T> {
+    Variable {
+        graph: x.graph,
+        value: c - x.value,
+        index: x.graph.push(
+            Operation::ScalarSub(c),
+            Arity::Binary,
+            &[x.index, x.index],
+            &[T::zero(), -T::one()],
+        ),
     }
 }
 
+macro_rules! impl_scalar_lhs {
+    ($t:ty) => {
+        // AddAssign: $t += Variable<'v, $t>
+        impl<'v> AddAssign<Variable<'v, $t>> for $t {
+            #[inline]
+            fn add_assign(&mut self, other: Variable<'v, $t>) {
+                *self = *self + other.value;
+            }
+        }
+
+        // $t + Variable<'v, $t>
+        impl<'v> Add<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn add(self, other: Variable<'v, $t>) -> Self::Output {
+                other + self
+            }
+        }
+
+        // `DivAssign`: $t /= Variable<'v, $t>
+        impl<'v> DivAssign<Variable<'v, $t>> for $t {
+            #[inline]
+            fn div_assign(&mut self, other: Variable<'v, $t>) {
+                *self = *self / other.value;
+            }
+        }
+
+        // $t / Variable<'v, $t>
+        impl<'v> Div<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn div(self, other: Variable<'v, $t>) -> Self::Output {
+                scalar_div(self, other)
+            }
+        }
+
+        // log_{Variable<'v, $t>}($t)
+        impl<'v> Log<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn log(&self, base: Variable<'v, $t>) -> Self::Output {
+                scalar_log(*self, base)
+            }
+        }
+
+        // min{ $t , Variable<'v, $t> }
+        impl<'v> Min<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn min(&self, rhs: Variable<'v, $t>) -> Self::Output {
+                scalar_min(*self, rhs)
+            }
+        }
+
+        // max{ $t , Variable<'v, $t> }
+        impl<'v> Max<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn max(&self, rhs: Variable<'v, $t>) -> Self::Output {
+                scalar_max(*self, rhs)
+            }
+        }
+
+        // MulAssign: $t *= Variable<'v, $t>
+        impl<'v> MulAssign<Variable<'v, $t>> for $t {
+            #[inline]
+            fn mul_assign(&mut self, other: Variable<'v, $t>) {
+                *self = *self * other.value;
+            }
+        }
+
+        // $t * Variable<'v, $t>
+        impl<'v> Mul<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn mul(self, other: Variable<'v, $t>) -> Self::Output {
+                other * self
+            }
+        }
+
+        // $t ^ Variable<'v, $t>
+        impl<'v> Powf<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn powf(&self, other: Variable<'v, $t>) -> Self::Output {
+                scalar_pow(*self, other)
+            }
+        }
+
+        // $t ^ Variable<'v, $t>
+        impl<'v> Powi<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn powi(&self, other: Variable<'v, $t>) -> Self::Output {
+                scalar_pow(*self, other)
+            }
+        }
+
+        // `SubAssign`: $t -= Variable<'v, $t>
+        impl<'v> SubAssign<Variable<'v, $t>> for $t {
+            #[inline]
+            fn sub_assign(&mut self, other: Variable<'v, $t>) {
+                *self = *self - other.value;
+            }
+        }
+
+        // $t - Variable<'v, $t>
+        impl<'v> Sub<Variable<'v, $t>> for $t {
+            type Output = Variable<'v, $t>;
+
+            #[inline]
+            fn sub(self, other: Variable<'v, $t>) -> Self::Output {
+                scalar_sub(self, other)
+            }
+        }
+    };
+}
+
+impl_scalar_lhs!(f32);
+impl_scalar_lhs!(f64);
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1675,6 +1972,7 @@ impl<'v> Sub<Variable<'v>> for f64 {
 mod test_overloading {
     use super::*;
     use crate::*;
+    use std::f64::consts::{LN_2, PI, SQRT_2};
     use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
 
     #[test]
@@ -1751,6 +2049,42 @@ mod test_overloading {
         assert_approx_equal!(grad.wrt(&y), 1.0, EPS);
     }
 
+    // Scalars of either float type on the left of a variable.
+    macro_rules! test_scalar_lhs {
+        ($t:ty) => {{
+            let g = Graph::<$t>::default();
+
+            let a: $t = 5.0;
+            let x = g.var(2.0);
+
+            let z = a + x;
+            assert_eq!(z.value, 7.0);
+            assert_eq!(z.accumulate().wrt(&x), 1.0);
+
+            let z = a / x;
+            assert_eq!(z.value, 5.0 / 2.0);
+            assert_eq!(z.accumulate().wrt(&x), -5.0 / (2.0 * 2.0));
+
+            let z = a * x;
+            assert_eq!(z.value, 10.0);
+            assert_eq!(z.accumulate().wrt(&x), 5.0);
+
+            let z = a - x;
+            assert_eq!(z.value, 3.0);
+            assert_eq!(z.accumulate().wrt(&x), -1.0);
+        }};
+    }
+
+    #[test]
+    fn test_scalar_lhs_f32() {
+        test_scalar_lhs!(f32);
+    }
+
+    #[test]
+    fn test_scalar_lhs_f64() {
+        test_scalar_lhs!(f64);
+    }
+
     #[test]
     fn test_values() {
         let g = Graph::new();
@@ -1812,6 +2146,8 @@ mod test_overloading {
         assert_approx_equal!(x.tanh().accumulate().wrt(&x), 0.419_974_341_614_026_14, EPS);
         assert_approx_equal!(x.asinh().accumulate().wrt(&x), 1.0 / 2_f64.sqrt(), EPS);
         assert_approx_equal!(x.abs().accumulate().wrt(&x), 1.0, EPS);
+        assert_approx_equal!(2.0.powf(x).accumulate().wrt(&x), 2.0 * LN_2, EPS);
+        assert_approx_equal!(3.0.powi(x).accumulate().wrt(&x), 3.0 * 3_f64.ln(), EPS);
         assert!(x.atanh().accumulate().wrt(&x).is_nan());
         assert!(x.acosh().accumulate().wrt(&x).is_nan());
         assert!(x.asin().accumulate().wrt(&x).is_nan());
@@ -1925,6 +2261,67 @@ mod test_overloading {
         assert_approx_equal!(grad.wrt(&y), 1.0, EPS);
     }
 
+    #[test]
+    fn test_normal_distribution() {
+        let g = Graph::new();
+
+        for value in [-2.5, -0.3, 0.0, 1.7] {
+            let x = g.var(value);
+
+            let cdf = x.norm_cdf();
+            let pdf = x.norm_pdf();
+
+            let density = (-0.5 * value * value).exp() / (2.0 * PI).sqrt();
+            assert_approx_equal!(
+                cdf.value,
+                0.5 * (1.0 + errorfunctions::RealErrorFunctions::erf(value / SQRT_2)),
+                EPS
+            );
+            assert_approx_equal!(cdf.accumulate().wrt(&x), density, EPS);
+            assert_approx_equal!(pdf.value, density, EPS);
+            assert_approx_equal!(pdf.accumulate().wrt(&x), -value * density, EPS);
+            assert_approx_equal!(cdf.hessian().wrt([x])[[0, 0]], -value * density, EPS);
+        }
+    }
+
+    #[test]
+    fn test_black_scholes_greeks() {
+        let g = Graph::new();
+
+        let s = g.var(100.0);
+        let k = g.var(110.0);
+        let r = g.var(0.05);
+        let v = g.var(0.3);
+        let t = g.var(0.75);
+
+        let d1 = ((s / k).ln() + (r + v * v / 2.0) * t) / (v * t.sqrt());
+        let d2 = d1 - v * t.sqrt();
+        let call = s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf();
+
+        let grad = call.accumulate();
+
+        // Closed form Greeks.
+        let (d1, d2) = (d1.value, d2.value);
+        let discount = (-0.05_f64 * 0.75).exp();
+        let n = |x: f64| (-0.5 * x * x).exp() / (2.0 * PI).sqrt();
+        let big_n = |x: f64| 0.5 * (1.0 + errorfunctions::RealErrorFunctions::erf(x / SQRT_2));
+
+        assert_approx_equal!(
+            call.value,
+            100.0 * big_n(d1) - 110.0 * discount * big_n(d2),
+            1e-10
+        );
+        assert_approx_equal!(grad.wrt(&s), big_n(d1), 1e-10);
+        assert_approx_equal!(grad.wrt(&k), -discount * big_n(d2), 1e-10);
+        assert_approx_equal!(grad.wrt(&v), 100.0 * n(d1) * 0.75_f64.sqrt(), 1e-10);
+        assert_approx_equal!(grad.wrt(&r), 110.0 * 0.75 * discount * big_n(d2), 1e-10);
+        assert_approx_equal!(
+            call.hessian().wrt([s])[[0, 0]],
+            n(d1) / (100.0 * 0.3 * 0.75_f64.sqrt()),
+            1e-10
+        );
+    }
+
     #[test]
     fn test_sub() {
         // Variable - Variable
@@ -0,0 +1,575 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! A recorded function, which can be exported, imported and replayed.
+//!
+//! Each vertex of a `Graph` stores the operation that produced it, so the
+//! recording of a function can be re-evaluated with new input values,
+//! without running (and recording) the function again. This is useful to
+//! compute the Greeks of the same pricing function for many market states.
+//!
+//! Large tapes can be shrunk before they are replayed: `prune` removes the
+//! vertices that the output does not depend on, and `fold_constants`
+//! evaluates the sub-expressions that do not depend on the inputs once.
+//!
+//! With the `serde` feature, tapes can be serialized, e.g. to record a
+//! function once and replay it in another process.
+//!
+//! ```
+//! # use RustQuant_autodiff::*;
+//!
+//! let g = Graph::new();
+//!
+//! let x = g.var(1.0);
+//! let y = g.var(2.0);
+//! let z = x * y.exp() + (x / y).sin();
+//!
+//! let mut tape = Tape::record(&[x, y], z);
+//!
+//! // Replay with x = 3 and y = 0.5.
+//! let value = tape.replay(&[3.0, 0.5]).unwrap();
+//! let gradient = tape.gradient();
+//!
+//! let (x, y) = (3.0_f64, 0.5_f64);
+//! assert!((value - (x * y.exp() + (x / y).sin())).abs() < 1e-12);
+//! assert!((gradient[0] - (y.exp() + (x / y).cos() / y)).abs() < 1e-12);
+//! assert!((gradient[1] - (x * y.exp() - (x / y).cos() * x / (y * y))).abs() < 1e-12);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::overload::{scalar_div, scalar_log, scalar_max, scalar_min, scalar_pow, scalar_sub};
+use crate::{Float, Graph, Log, Max, Min, Operation, Powf, Powi, Variable, Vertex};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The recording of a function, from its inputs to its output.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tape<T: Float = f64> {
+    /// The vertices of the graph, up to and including the output.
+    pub vertices: Vec<Vertex<T>>,
+
+    /// Indices of the input vertices.
+    pub inputs: Vec<usize>,
+
+    /// Index of the output vertex.
+    pub output: usize,
+
+    /// Value of the output, at the last recorded or replayed inputs.
+    pub value: T,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T: Float> Tape<T> {
+    /// Records the graph of `output`, as a function of the `inputs`.
+    ///
+    /// Other variables on the graph are recorded as constants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an input is not a variable created with `Graph::var`,
+    /// or is on another graph than the output.
+    #[must_use]
+    pub fn record(inputs: &[Variable<'_, T>], output: Variable<'_, T>) -> Self {
+        let vertices = output.graph.vertices.borrow()[..=output.index].to_vec();
+
+        for input in inputs {
+            assert!(std::ptr::eq(input.graph, output.graph));
+            let operation = vertices.get(input.index).map(|vertex| vertex.operation);
+
+            assert!(
+                matches!(operation, Some(Operation::Input(_))),
+                "Tape inputs must be variables created with `Graph::var`."
+            );
+        }
+
+        Self {
+            vertices,
+            inputs: inputs.iter().map(|input| input.index).collect(),
+            output: output.index,
+            value: output.value,
+        }
+    }
+
+    /// Number of vertices on the tape.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Returns true if the tape has no vertices.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Re-evaluates the tape with new values of the inputs,
+    /// updating the partials, and returns the new value of the output.
+    ///
+    /// The branches taken by the recorded function (e.g. in `if` statements
+    /// on the values of variables) are not re-evaluated, so the inputs
+    /// should stay in the region where the recording is valid.
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if the number of values does not
+    ///   match the number of inputs.
+    /// - `RustQuantError::ComputationError` if the tape contains a custom
+    ///   function, since its derivatives are not known at the new inputs.
+    pub fn replay(&mut self, inputs: &[T]) -> Result<T, RustQuantError> {
+        if inputs.len() != self.inputs.len() {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "{} values for {} tape inputs.",
+                inputs.len(),
+                self.inputs.len()
+            )));
+        }
+
+        for (&index, &value) in self.inputs.iter().zip(inputs) {
+            self.vertices[index].operation = Operation::Input(value);
+        }
+
+        let scratch = Graph::<T>::default();
+        let mut values: Vec<T> = Vec::with_capacity(self.vertices.len());
+
+        for (index, vertex) in self.vertices.iter_mut().enumerate() {
+            let value = match vertex.operation {
+                Operation::Input(value) => value,
+                Operation::Custom => {
+                    return Err(RustQuantError::ComputationError(
+                        "Custom functions cannot be replayed.".to_string(),
+                    ))
+                }
+                operation => {
+                    let [x, y] = operands(vertex, index, |parent| values[parent]);
+                    let (value, replayed) = operation.evaluate(&scratch, x, y);
+
+                    vertex.partials = replayed.partials;
+                    vertex.second_partials = replayed.second_partials;
+
+                    value
+                }
+            };
+
+            values.push(value);
+        }
+
+        self.value = values[self.output];
+
+        Ok(self.value)
+    }
+
+    /// Gradient of the output with respect to the inputs,
+    /// at the last recorded or replayed inputs.
+    #[must_use]
+    pub fn gradient(&self) -> Vec<T> {
+        let mut adjoints = vec![T::zero(); self.vertices.len()];
+        adjoints[self.output] = T::one();
+
+        for (index, vertex) in self.vertices.iter().enumerate().rev() {
+            let deriv = adjoints[index];
+
+            adjoints[vertex.parents[0]] += vertex.partials[0] * deriv;
+            adjoints[vertex.parents[1]] += vertex.partials[1] * deriv;
+        }
+
+        self.inputs.iter().map(|&index| adjoints[index]).collect()
+    }
+
+    /// Removes the vertices that the output does not depend on, e.g. other
+    /// results recorded on the same graph, and returns how many were removed.
+    ///
+    /// The inputs are always kept, so the replay and gradient functions
+    /// take and return the same inputs as before.
+    pub fn prune(&mut self) -> usize {
+        let len = self.vertices.len();
+
+        let mut reachable = vec![false; len];
+        reachable[self.output] = true;
+        for &input in &self.inputs {
+            reachable[input] = true;
+        }
+
+        for index in (0..len).rev() {
+            if reachable[index] {
+                let [x, y] = self.vertices[index].parents;
+                reachable[x] = true;
+                reachable[y] = true;
+            }
+        }
+
+        // New index of each kept vertex.
+        let mut indices = vec![0; len];
+        let mut vertices = Vec::with_capacity(len);
+
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            if reachable[index] {
+                indices[index] = vertices.len();
+                vertices.push(*vertex);
+            }
+        }
+        for vertex in &mut vertices {
+            vertex.parents = vertex.parents.map(|parent| indices[parent]);
+        }
+
+        self.vertices = vertices;
+        self.output = indices[self.output];
+        self.inputs
+            .iter_mut()
+            .for_each(|input| *input = indices[*input]);
+
+        len - self.vertices.len()
+    }
+
+    /// Folds the operations that only depend on constants (variables that
+    /// are not inputs of the tape) into constants, then prunes the tape,
+    /// and returns how many vertices were removed.
+    ///
+    /// Custom functions are not folded, since their values are not recorded.
+    pub fn fold_constants(&mut self) -> usize {
+        let scratch = Graph::<T>::default();
+
+        // Values of the constant vertices.
+        let mut constants: Vec<Option<T>> = Vec::with_capacity(self.vertices.len());
+
+        for (index, vertex) in self.vertices.iter_mut().enumerate() {
+            let constant = match vertex.operation {
+                Operation::Input(_) if self.inputs.contains(&index) => None,
+                Operation::Input(value) => Some(value),
+                Operation::Custom => None,
+                operation => match operands(vertex, index, |parent| constants[parent]) {
+                    [Some(x), Some(y)] => {
+                        let (value, _) = operation.evaluate(&scratch, x, y);
+                        *vertex = Vertex {
+                            partials: [T::zero(); 2],
+                            parents: [index, index],
+                            second_partials: [T::zero(); 3],
+                            operation: Operation::Input(value),
+                        };
+
+                        Some(value)
+                    }
+                    _ => None,
+                },
+            };
+
+            constants.push(constant);
+        }
+
+        self.prune()
+    }
+}
+
+/// Values of the operands of a vertex.
+/// Unary operations are their own second parent, so their first operand is repeated.
+fn operands<T: Float, V: Copy>(
+    vertex: &Vertex<T>,
+    index: usize,
+    value: impl Fn(usize) -> V,
+) -> [V; 2] {
+    let x = value(vertex.parents[0]);
+
+    match vertex.parents[1] {
+        parent if parent == index => [x, x],
+        parent => [x, value(parent)],
+    }
+}
+
+impl<T: Float> Operation<T> {
+    /// Evaluates the operation on a scratch graph of its operands, with the
+    /// same code that recorded it, and returns its value and vertex.
+    fn evaluate(self, scratch: &Graph<T>, x: T, y: T) -> (T, Vertex<T>) {
+        scratch.scope(|s| {
+            let z = self.apply(s.var(x), s.var(y));
+            let vertex = s.vertices.borrow()[z.index];
+
+            (z.value, vertex)
+        })
+    }
+
+    /// Records the operation on the graph of its operands.
+    /// Unary operations and operations with a constant ignore `y`.
+    fn apply<'v>(self, x: Variable<'v, T>, y: Variable<'v, T>) -> Variable<'v, T> {
+        match self {
+            Operation::Input(_) | Operation::Custom => {
+                unreachable!("Inputs and custom functions are not replayed.")
+            }
+
+            Operation::Add => x + y,
+            Operation::Mul => x * y,
+            Operation::Log => Log::log(&x, y),
+            Operation::Min => Min::min(&x, y),
+            Operation::Max => Max::max(&x, y),
+            Operation::Powf => Powf::powf(&x, y),
+            Operation::Powi => Powi::powi(&x, y),
+
+            Operation::AddScalar(c) => x + c,
+            Operation::MulScalar(c) => x * c,
+            Operation::LogScalar(c) => Log::log(&x, c),
+            Operation::MinScalar(c) => Min::min(&x, c),
+            Operation::MaxScalar(c) => Max::max(&x, c),
+            Operation::PowfScalar(c) => Powf::powf(&x, c),
+            Operation::PowiInteger(n) => Powi::powi(&x, n),
+
+            Operation::ScalarDiv(c) => scalar_div(c, x),
+            Operation::ScalarLog(c) => scalar_log(c, x),
+            Operation::ScalarMin(c) => scalar_min(c, x),
+            Operation::ScalarMax(c) => scalar_max(c, x),
+            Operation::ScalarPow(c) => scalar_pow(c, x),
+            Operation::ScalarSub(c) => scalar_sub(c, x),
+
+            Operation::Abs => x.abs(),
+            Operation::Acos => x.acos(),
+            Operation::Acosh => x.acosh(),
+            Operation::Asin => x.asin(),
+            Operation::Asinh => x.asinh(),
+            Operation::Atan => x.atan(),
+            Operation::Atanh => x.atanh(),
+            Operation::Cbrt => x.cbrt(),
+            Operation::Cos => x.cos(),
+            Operation::Cosh => x.cosh(),
+            Operation::Exp => x.exp(),
+            Operation::Exp2 => x.exp2(),
+            Operation::ExpM1 => x.exp_m1(),
+            Operation::Ln => x.ln(),
+            Operation::Ln1p => x.ln_1p(),
+            Operation::Log10 => x.log10(),
+            Operation::Log2 => x.log2(),
+            Operation::Recip => x.recip(),
+            Operation::Sin => x.sin(),
+            Operation::Sinh => x.sinh(),
+            Operation::Sqrt => x.sqrt(),
+            Operation::Tan => x.tan(),
+            Operation::Tanh => x.tanh(),
+            Operation::Erf => x.erf(),
+            Operation::Erfc => x.erfc(),
+            Operation::NormCdf => x.norm_cdf(),
+            Operation::NormPdf => x.norm_pdf(),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_tape {
+    use crate::*;
+    use RustQuant_error::RustQuantError;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Black-Scholes call price, as a function of (S, sigma), with the
+    // operations recorded with a constant on either side.
+    fn black_scholes<'v>(s: Variable<'v>, sigma: Variable<'v>) -> Variable<'v> {
+        let (k, r, t) = (100.0, 0.05, 0.5);
+
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+
+        s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+    }
+
+    #[test]
+    fn test_replay_matches_recording() {
+        let g = Graph::new();
+        let s = g.var(100.0);
+        let sigma = g.var(0.2);
+        let mut tape = Tape::record(&[s, sigma], black_scholes(s, sigma));
+
+        for (spot, vol) in [(90.0, 0.3), (105.0, 0.15), (120.0, 0.25)] {
+            let g = Graph::new();
+            let s = g.var(spot);
+            let sigma = g.var(vol);
+            let price = black_scholes(s, sigma);
+            let expected = price.accumulate().wrt(&[s, sigma]);
+
+            assert_approx_equal!(tape.replay(&[spot, vol]).unwrap(), price.value, 1e-12);
+            for (a, b) in tape.gradient().iter().zip(&expected) {
+                assert_approx_equal!(*a, *b, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_replay_all_operations() {
+        fn f<'v>(x: Variable<'v>, y: Variable<'v>) -> Variable<'v> {
+            let a = x.abs() + x.acosh() + (x / 10.0).asin() + x.asinh() + x.atan();
+            let b = (x / 10.0).atanh() + x.cbrt() + x.cos() + x.cosh() + x.exp2();
+            let c = x.exp_m1() + x.ln_1p() + x.log10() + x.log2() + x.sinh() + x.tan();
+            let d = x.tanh() + x.erf() + x.erfc() + x.norm_pdf() + (x / 10.0).acos();
+            let e = x.log(y) + Min::min(&x, y) + Max::max(&x, y) + x.powf(y) + x.powi(y);
+            let h = x.log(3.0) + Min::min(&x, 2.5) + Max::max(&x, 2.5) + x.powf(1.5);
+            let i = 2.0 / x + 3.0.log(y) + 2.5.min(y) + 2.5.max(y) + 2.0.powf(y) - 1.0;
+            let j = 5.0 - x + x.powi(3) + y.sqrt() * x.sin();
+
+            a + b + c + d + e + h + i + j
+        }
+
+        let g = Graph::new();
+        let x = g.var(1.5);
+        let y = g.var(2.0);
+        let mut tape = Tape::record(&[x, y], f(x, y));
+
+        let g = Graph::new();
+        let x = g.var(3.0);
+        let y = g.var(1.5);
+        let z = f(x, y);
+
+        assert_approx_equal!(tape.replay(&[3.0, 1.5]).unwrap(), z.value, 1e-12);
+        assert_eq!(tape.len(), g.len());
+        for (replayed, recorded) in tape.vertices.iter().zip(g.vertices.borrow().iter()) {
+            assert_eq!(replayed.parents, recorded.parents);
+            for (a, b) in replayed.partials.iter().zip(&recorded.partials) {
+                assert_approx_equal!(*a, *b, 1e-12);
+            }
+            for (a, b) in replayed
+                .second_partials
+                .iter()
+                .zip(&recorded.second_partials)
+            {
+                assert_approx_equal!(*a, *b, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_constants_are_kept() {
+        let g = Graph::new();
+        let x = g.var(2.0);
+        let c = g.var(3.0);
+        let mut tape = Tape::record(&[x], x * c);
+
+        assert_eq!(tape.replay(&[5.0]).unwrap(), 15.0);
+        assert_eq!(tape.gradient(), vec![3.0]);
+    }
+
+    #[test]
+    fn test_prune() {
+        let g = Graph::new();
+        let x = g.var(1.0);
+        let y = g.var(2.0);
+
+        // Unrelated results recorded on the same graph.
+        let _ = (x * y).sin();
+        let unused = g.var(3.0);
+        let z = x.exp() * y;
+        let _ = z.ln() + unused;
+
+        let mut tape = Tape::record(&[x, y], z);
+        let gradient = tape.gradient();
+
+        assert_eq!(tape.prune(), 3);
+        assert_eq!(tape.len(), 4);
+        assert_eq!(tape.output, 3);
+        assert_eq!(tape.gradient(), gradient);
+
+        assert_approx_equal!(
+            tape.replay(&[0.5, 3.0]).unwrap(),
+            0.5_f64.exp() * 3.0,
+            1e-12
+        );
+        assert_eq!(tape.gradient(), vec![0.5_f64.exp() * 3.0, 0.5_f64.exp()]);
+
+        // Unused inputs are kept.
+        let mut tape = Tape::record(&[x, unused, y], z);
+        tape.prune();
+        assert_eq!(tape.inputs, vec![0, 2, 1]);
+        assert_eq!(tape.gradient()[1], 0.0);
+    }
+
+    #[test]
+    fn test_fold_constants() {
+        let g = Graph::new();
+        let x = g.var(0.5);
+        let r = g.var(0.05);
+        let t = g.var(2.0);
+
+        // The discount factor only depends on constants.
+        let discount = (-r * t).exp();
+        let z = (x * x + 1.0).ln() * discount;
+
+        let mut tape = Tape::record(&[x], z);
+        let before = tape.len();
+
+        // r, t, -r and -r t are removed, and exp(-r t) becomes a constant.
+        assert_eq!(tape.fold_constants(), 4);
+        assert_eq!(tape.len(), before - 4);
+
+        let expected = |x: f64| (x * x + 1.0).ln() * (-0.1_f64).exp();
+        let derivative = |x: f64| 2.0 * x / (x * x + 1.0) * (-0.1_f64).exp();
+
+        assert_approx_equal!(tape.replay(&[1.5]).unwrap(), expected(1.5), 1e-12);
+        assert_approx_equal!(tape.gradient()[0], derivative(1.5), 1e-12);
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        let g = Graph::new();
+        let x = g.var(2.0);
+        let y = g.custom(&[x], 4.0, &[4.0]);
+        let mut tape = Tape::record(&[x], y * x);
+
+        assert!(matches!(
+            tape.replay(&[1.0, 2.0]),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            tape.replay(&[1.0]),
+            Err(RustQuantError::ComputationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_f32_replay() {
+        let g = Graph::<f32>::default();
+        let x = g.var(1.0);
+        let mut tape = Tape::record(&[x], x * x.exp());
+
+        let value = tape.replay(&[2.0]).unwrap();
+
+        assert_approx_equal!(value, 2.0 * 2_f32.exp(), 1e-5);
+        assert_approx_equal!(tape.gradient()[0], 3.0 * 2_f32.exp(), 1e-5);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_tape_json_round_trip() {
+        let g = Graph::new();
+        let s = g.var(100.0);
+        let sigma = g.var(0.2);
+        let mut tape = Tape::record(&[s, sigma], black_scholes(s, sigma));
+
+        let json = serde_json::to_string(&tape).unwrap();
+        assert!(json.contains("\"NormCdf\""));
+
+        // JSON floats may differ in the last digit, so compare the replays.
+        let mut restored: Tape = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), tape.len());
+        assert_eq!(restored.inputs, tape.inputs);
+
+        assert_approx_equal!(
+            restored.replay(&[95.0, 0.22]).unwrap(),
+            tape.replay(&[95.0, 0.22]).unwrap(),
+            1e-12
+        );
+        for (a, b) in restored.gradient().iter().zip(&tape.gradient()) {
+            assert_approx_equal!(*a, *b, 1e-12);
+        }
+    }
+}
@@ -18,7 +18,7 @@
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::graph::Graph;
+use crate::{graph::Graph, Float};
 use std::fmt::Display;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -27,20 +27,20 @@ use std::fmt::Display;
 
 /// Struct to contain the initial variables.
 #[derive(Clone, Copy, Debug)]
-pub struct Variable<'v> {
+pub struct Variable<'v, T: Float = f64> {
     /// Pointer to the graph.
-    pub graph: &'v Graph,
+    pub graph: &'v Graph<T>,
     /// Index to the vertex.
     pub index: usize,
     /// Value associated to the vertex.
-    pub value: f64, // Value,
+    pub value: T, // Value,
 }
 
-impl<'v> Variable<'v> {
+impl<'v, T: Float> Variable<'v, T> {
     /// Instantiate a new variable.
     #[must_use]
     #[inline]
-    pub const fn new(graph: &'v Graph, index: usize, value: f64) -> Self {
+    pub const fn new(graph: &'v Graph<T>, index: usize, value: T) -> Self {
         Variable {
             graph,
             index,
@@ -51,7 +51,7 @@ impl<'v> Variable<'v> {
     /// Function to return the value contained in a vertex.
     #[must_use]
     #[inline]
-    pub fn value(&self) -> f64 {
+    pub fn value(&self) -> T {
         self.value
     }
 
@@ -65,7 +65,7 @@ impl<'v> Variable<'v> {
     /// Function to return the graph.
     #[must_use]
     #[inline]
-    pub fn graph(&self) -> &'v Graph {
+    pub fn graph(&self) -> &'v Graph<T> {
         self.graph
     }
 
@@ -108,7 +108,7 @@ impl<'v> Variable<'v> {
     #[must_use]
     #[inline]
     pub fn is_zero(&self) -> bool {
-        self.value == 0.0
+        self.value.is_zero()
     }
 
     /// Check if variable is positive.
@@ -134,27 +134,27 @@ impl<'v> Variable<'v> {
     /// Returns the sign of the variable.
     #[must_use]
     #[inline]
-    pub fn signum(&self) -> f64 {
+    pub fn signum(&self) -> T {
         self.value.signum()
     }
 }
 
 /// Implement formatting for the `Variable` struct.
-impl<'v> Display for Variable<'v> {
+impl<'v, T: Float> Display for Variable<'v, T> {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.value)
     }
 }
 
-impl<'v> PartialEq<f64> for Variable<'v> {
+impl<'v, T: Float> PartialEq<T> for Variable<'v, T> {
     #[inline]
-    fn eq(&self, other: &f64) -> bool {
+    fn eq(&self, other: &T) -> bool {
         self.value == *other
     }
 }
 
-impl<'v> PartialEq for Variable<'v> {
+impl<'v, T: Float> PartialEq for Variable<'v, T> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         std::ptr::eq(self.graph, other.graph)
@@ -163,16 +163,16 @@ impl<'v> PartialEq for Variable<'v> {
     }
 }
 
-impl<'v> Eq for Variable<'v> {}
+impl<'v, T: Float> Eq for Variable<'v, T> {}
 
-impl<'v> PartialOrd for Variable<'v> {
+impl<'v, T: Float> PartialOrd for Variable<'v, T> {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<'v> Ord for Variable<'v> {
+impl<'v, T: Float> Ord for Variable<'v, T> {
     #[inline]
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.value
@@ -7,6 +7,7 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::Float;
 use std::fmt;
 
 /// Struct defining the vertex of the computational graph.
@@ -16,12 +17,22 @@ use std::fmt;
 /// To deal with unary or nullary operations, we just adjust the weights
 /// (partials) and the dependencies (parents).
 #[derive(Clone, Copy, Debug)]
-pub struct Vertex {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vertex<T: Float = f64> {
     /// Array that contains the partial derivatives wrt to x and y.
-    pub partials: [f64; 2],
+    pub partials: [T; 2],
 
     /// Array that contains the indices of the parent vertices.
     pub parents: [usize; 2],
+
+    /// Array that contains the second order partial derivatives
+    /// wrt (x, x), (x, y) and (y, y).
+    /// These are only needed to accumulate Hessians.
+    pub second_partials: [T; 3],
+
+    /// The operation that produced the vertex.
+    /// This is only needed to replay the graph with new inputs (see `Tape`).
+    pub operation: Operation<T>,
 }
 
 /// Enumeration for the operation type.
@@ -43,13 +54,132 @@ pub enum Arity {
     Binary,
 }
 
-impl Vertex {
+/// Enumeration of the operations recorded on the graph.
+///
+/// Operations with a constant store it, e.g. `x + 2.0` is recorded as
+/// `AddScalar(2.0)` and `2.0 - x` as `ScalarSub(2.0)`.
+/// Subtraction, division and negation are recorded as the
+/// additions, multiplications and reciprocals they are computed with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation<T: Float = f64> {
+    /// An input variable, with its value.
+    Input(T),
+    /// A user-defined function, which cannot be replayed.
+    Custom,
+
+    /// `x + y`
+    Add,
+    /// `x * y`
+    Mul,
+    /// `x.log(y)`
+    Log,
+    /// `x.min(y)`
+    Min,
+    /// `x.max(y)`
+    Max,
+    /// `x.powf(y)`
+    Powf,
+    /// `x.powi(y)`
+    Powi,
+
+    /// `x + c`
+    AddScalar(T),
+    /// `x * c`
+    MulScalar(T),
+    /// `x.log(c)`
+    LogScalar(T),
+    /// `x.min(c)`
+    MinScalar(T),
+    /// `x.max(c)`
+    MaxScalar(T),
+    /// `x.powf(c)`
+    PowfScalar(T),
+    /// `x.powi(n)`
+    PowiInteger(i32),
+
+    /// `c / x`
+    ScalarDiv(T),
+    /// `c.log(x)`
+    ScalarLog(T),
+    /// `c.min(x)`
+    ScalarMin(T),
+    /// `c.max(x)`
+    ScalarMax(T),
+    /// `c.powf(x)` and `c.powi(x)`
+    ScalarPow(T),
+    /// `c - x`
+    ScalarSub(T),
+
+    /// `x.abs()`
+    Abs,
+    /// `x.acos()`
+    Acos,
+    /// `x.acosh()`
+    Acosh,
+    /// `x.asin()`
+    Asin,
+    /// `x.asinh()`
+    Asinh,
+    /// `x.atan()`
+    Atan,
+    /// `x.atanh()`
+    Atanh,
+    /// `x.cbrt()`
+    Cbrt,
+    /// `x.cos()`
+    Cos,
+    /// `x.cosh()`
+    Cosh,
+    /// `x.exp()`
+    Exp,
+    /// `x.exp2()`
+    Exp2,
+    /// `x.exp_m1()`
+    ExpM1,
+    /// `x.ln()`
+    Ln,
+    /// `x.ln_1p()`
+    Ln1p,
+    /// `x.log10()`
+    Log10,
+    /// `x.log2()`
+    Log2,
+    /// `x.recip()`
+    Recip,
+    /// `x.sin()`
+    Sin,
+    /// `x.sinh()`
+    Sinh,
+    /// `x.sqrt()`
+    Sqrt,
+    /// `x.tan()`
+    Tan,
+    /// `x.tanh()`
+    Tanh,
+    /// `x.erf()`
+    Erf,
+    /// `x.erfc()`
+    Erfc,
+    /// `x.norm_cdf()`
+    NormCdf,
+    /// `x.norm_pdf()`
+    NormPdf,
+}
+
+impl<T: Float> Vertex<T> {
     /// Get the partials of the vertex.
     #[must_use]
-    pub const fn get_partials(&self) -> [f64; 2] {
+    pub const fn get_partials(&self) -> [T; 2] {
         self.partials
     }
 
+    /// Get the second order partials of the vertex.
+    #[must_use]
+    pub const fn get_second_partials(&self) -> [T; 3] {
+        self.second_partials
+    }
+
     /// Get the parents of the vertex.
     #[must_use]
     pub const fn get_parents(&self) -> [usize; 2] {
@@ -58,46 +188,50 @@ impl Vertex {
 
     /// Instantiate a new vertex from a binary operation.
     #[must_use]
-    pub const fn new_binary(
-        partial_x: f64,
-        parent_x: usize,
-        partial_y: f64,
-        parent_y: usize,
-    ) -> Self {
+    pub fn new_binary(partial_x: T, parent_x: usize, partial_y: T, parent_y: usize) -> Self {
         Self {
             partials: [partial_x, partial_y],
             parents: [parent_x, parent_y],
+            second_partials: [T::zero(); 3],
+            operation: Operation::Custom,
         }
     }
 
     /// Instantiate a new vertex from a unary operation.
     #[must_use]
-    pub const fn new_unary(partial_x: f64, parent_x: usize) -> Self {
+    pub fn new_unary(partial_x: T, parent_x: usize) -> Self {
         Self {
-            partials: [partial_x, 0.0],
+            partials: [partial_x, T::zero()],
             parents: [parent_x, 0],
+            second_partials: [T::zero(); 3],
+            operation: Operation::Custom,
         }
     }
 
     /// Instantiate a new vertex from a nullary operation.
     #[must_use]
-    pub const fn new_nullary() -> Self {
+    pub fn new_nullary() -> Self {
         Self {
-            partials: [0.0; 2],
+            partials: [T::zero(); 2],
             parents: [0; 2],
+            second_partials: [T::zero(); 3],
+            operation: Operation::Custom,
         }
     }
 }
 
-impl PartialEq for Vertex {
+impl<T: Float> PartialEq for Vertex<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.partials == other.partials && self.parents == other.parents
+        self.partials == other.partials
+            && self.parents == other.parents
+            && self.second_partials == other.second_partials
+            && self.operation == other.operation
     }
 }
 
-impl Eq for Vertex {}
+impl<T: Float> Eq for Vertex<T> {}
 
-impl fmt::Display for Vertex {
+impl<T: Float> fmt::Display for Vertex<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
@@ -131,7 +265,7 @@ mod tests_vertex {
 
     #[test]
     fn test_new_nullary() {
-        let vertex = Vertex::new_nullary();
+        let vertex = Vertex::<f64>::new_nullary();
         assert_eq!(vertex.get_partials(), [0.0, 0.0]);
         assert_eq!(vertex.get_parents(), [0, 0]);
     }
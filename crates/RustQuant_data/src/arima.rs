@@ -0,0 +1,796 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! ARIMA(p, d, q) models of time series, such as the returns of a
+//! [`PriceSeries`](crate::PriceSeries).
+//!
+//! The series is differenced $d$ times, and the differences $w_t$ follow
+//! the ARMA(p, q) model
+//!
+//! $$
+//! w_t - \mu = \sum_{i=1}^{p} \phi_i (w_{t-i} - \mu) + \varepsilon_t + \sum_{j=1}^{q} \theta_j \varepsilon_{t-j},
+//! $$
+//!
+//! with independent $\varepsilon_t \sim N(0, \sigma^2)$. The parameters are
+//! estimated by conditional sum of squares or exact maximum likelihood (with
+//! the Kalman filter), restricted to stationary and invertible models.
+//!
+//! ```
+//! # use RustQuant::data::*;
+//! let series = vec![
+//!     0.12, 0.35, 0.21, -0.05, 0.10, 0.42, 0.30, 0.08, -0.12, 0.05,
+//!     0.25, 0.40, 0.18, 0.02, -0.10, 0.15, 0.33, 0.27, 0.01, -0.04,
+//! ];
+//!
+//! let model = Arima::new(1, 0, 0).fit(&series).unwrap();
+//! let forecasts = model.forecast(3, 0.95).unwrap();
+//!
+//! // Forecasts revert to the mean, with widening intervals.
+//! assert!(forecasts[0].lower < forecasts[0].mean && forecasts[0].mean < forecasts[0].upper);
+//! assert!(forecasts[2].standard_error > forecasts[0].standard_error);
+//! ```
+
+use nalgebra::{DMatrix, DVector};
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::distributions::{Distribution, Gaussian};
+use RustQuant_math::polynomial::polynomial_roots;
+use RustQuant_math::{solve_linear_system, NelderMead};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS AND ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Estimation method of an ARIMA model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArimaMethod {
+    /// Conditional sum of squares: minimises the squared residuals from
+    /// observation $p$ on, with the earlier residuals set to zero.
+    ConditionalSumOfSquares,
+
+    /// Exact Gaussian maximum likelihood, started from the conditional sum
+    /// of squares estimates.
+    MaximumLikelihood,
+}
+
+/// Information criterion for the selection of the order of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InformationCriterion {
+    /// Akaike: $-2 \ln L + 2 k$.
+    Aic,
+
+    /// Bayesian (Schwarz): $-2 \ln L + k \ln n$.
+    Bic,
+}
+
+/// Specification of an ARIMA(p, d, q) model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arima {
+    /// Autoregressive order $p$.
+    pub p: usize,
+
+    /// Order of differencing $d$.
+    pub d: usize,
+
+    /// Moving average order $q$.
+    pub q: usize,
+
+    /// Whether the differenced series has a non-zero mean $\mu$ (a drift,
+    /// if $d > 0$).
+    pub constant: bool,
+
+    /// Estimation method.
+    pub method: ArimaMethod,
+}
+
+/// ARIMA model fitted to a series.
+#[derive(Debug, Clone)]
+pub struct FittedArima {
+    /// The model specification.
+    pub model: Arima,
+
+    /// Autoregressive coefficients $\phi_1, \dots, \phi_p$.
+    pub ar: Vec<f64>,
+
+    /// Moving average coefficients $\theta_1, \dots, \theta_q$.
+    pub ma: Vec<f64>,
+
+    /// Mean $\mu$ of the differenced series (zero without a constant).
+    pub mean: f64,
+
+    /// Innovation variance $\sigma^2$.
+    pub sigma2: f64,
+
+    /// Gaussian log-likelihood (conditional, for the conditional sum of
+    /// squares).
+    pub log_likelihood: f64,
+
+    /// Akaike information criterion.
+    pub aic: f64,
+
+    /// Bayesian information criterion.
+    pub bic: f64,
+
+    /// Residuals of the differenced series: the innovations of the Kalman
+    /// filter for maximum likelihood, or the conditional residuals (zero
+    /// for the first $p$) for the conditional sum of squares.
+    pub residuals: Vec<f64>,
+
+    /// The series the model was fitted to.
+    series: Vec<f64>,
+}
+
+/// Forecast of an ARIMA model for one period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArimaForecast {
+    /// Number of periods ahead.
+    pub horizon: usize,
+
+    /// Forecast (conditional mean).
+    pub mean: f64,
+
+    /// Standard error of the forecast.
+    pub standard_error: f64,
+
+    /// Lower bound of the confidence interval.
+    pub lower: f64,
+
+    /// Upper bound of the confidence interval.
+    pub upper: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Arima {
+    /// ARIMA(p, d, q) model estimated by maximum likelihood, with a mean if
+    /// the series is not differenced ($d = 0$).
+    #[must_use]
+    pub fn new(p: usize, d: usize, q: usize) -> Self {
+        Self {
+            p,
+            d,
+            q,
+            constant: d == 0,
+            method: ArimaMethod::MaximumLikelihood,
+        }
+    }
+
+    /// Set whether the differenced series has a non-zero mean.
+    #[must_use]
+    pub fn with_constant(mut self, constant: bool) -> Self {
+        self.constant = constant;
+        self
+    }
+
+    /// Set the estimation method.
+    #[must_use]
+    pub fn with_method(mut self, method: ArimaMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Number of estimated parameters, including $\sigma^2$.
+    fn parameters(&self) -> usize {
+        self.p + self.q + usize::from(self.constant) + 1
+    }
+
+    /// Fit the model to a series of dated values (such as the returns of a
+    /// [`PriceSeries`](crate::PriceSeries)), in date order.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Arima::fit`].
+    pub fn fit_series(&self, series: &[(Date, f64)]) -> Result<FittedArima, RustQuantError> {
+        let values: Vec<f64> = series.iter().map(|(_, value)| *value).collect();
+
+        self.fit(&values)
+    }
+
+    /// Fit the model to a series of values, in time order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the series is not finite, too short for the
+    /// order of the model, or constant after differencing, or if the
+    /// optimisation fails.
+    pub fn fit(&self, series: &[f64]) -> Result<FittedArima, RustQuantError> {
+        if series.iter().any(|x| !x.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(
+                "ARIMA series must be finite.".to_string(),
+            ));
+        }
+
+        let differenced = difference(series, self.d);
+        let n = differenced.len();
+
+        if n < self.p + self.q + self.parameters() + 1 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Series of length {} is too short for ARIMA({}, {}, {}).",
+                series.len(),
+                self.p,
+                self.d,
+                self.q
+            )));
+        }
+
+        // Standardise, so the tolerances of the optimiser are scale free.
+        let location = if self.constant {
+            differenced.iter().sum::<f64>() / n as f64
+        } else {
+            0.0
+        };
+        let scale = (differenced
+            .iter()
+            .map(|w| (w - location).powi(2))
+            .sum::<f64>()
+            / n as f64)
+            .sqrt();
+
+        if scale == 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "ARIMA series must not be constant after differencing.".to_string(),
+            ));
+        }
+
+        let w: Vec<f64> = differenced.iter().map(|w| (w - location) / scale).collect();
+
+        let css = |x: &[f64]| {
+            let (mean, ar, ma) = self.unpack(x);
+            if !admissible(ar, ma) {
+                return f64::INFINITY;
+            }
+            let residuals = conditional_residuals(&w, mean, ar, ma);
+            residuals[self.p..].iter().map(|e| e * e).sum::<f64>()
+        };
+
+        let free = self.parameters() - 1;
+        let mut estimate = if free == 0 {
+            Vec::new()
+        } else {
+            NelderMead::default()
+                .optimize(css, &vec![0.0; free])
+                .minimizer
+        };
+
+        if self.method == ArimaMethod::MaximumLikelihood && free > 0 {
+            let likelihood = |x: &[f64]| {
+                let (mean, ar, ma) = self.unpack(x);
+                if !admissible(ar, ma) {
+                    return f64::INFINITY;
+                }
+                kalman_filter(&w, mean, ar, ma).map_or(f64::INFINITY, |(ll, _, _)| -ll)
+            };
+
+            estimate = NelderMead::default()
+                .optimize(likelihood, &estimate)
+                .minimizer;
+        }
+
+        let (mean, ar, ma) = self.unpack(&estimate);
+
+        if !admissible(ar, ma) {
+            return Err(RustQuantError::ComputationError(
+                "ARIMA estimation did not find a stationary and invertible model.".to_string(),
+            ));
+        }
+
+        // Back to the scale of the series.
+        let (log_likelihood, sigma2, residuals, observations) = match self.method {
+            ArimaMethod::ConditionalSumOfSquares => {
+                let residuals = conditional_residuals(&w, mean, ar, ma);
+                let m = n - self.p;
+                let sigma2 = residuals[self.p..].iter().map(|e| e * e).sum::<f64>() / m as f64;
+                let ll = -0.5 * m as f64 * ((2.0 * std::f64::consts::PI * sigma2).ln() + 1.0);
+
+                (ll, sigma2, residuals, m)
+            }
+            ArimaMethod::MaximumLikelihood => {
+                let (ll, sigma2, innovations) = kalman_filter(&w, mean, ar, ma).ok_or(
+                    RustQuantError::ComputationError("ARIMA Kalman filter failed.".to_string()),
+                )?;
+
+                (ll, sigma2, innovations, n)
+            }
+        };
+
+        let log_likelihood = log_likelihood - observations as f64 * scale.ln();
+        let k = self.parameters() as f64;
+
+        Ok(FittedArima {
+            model: *self,
+            ar: ar.to_vec(),
+            ma: ma.to_vec(),
+            mean: location + scale * mean,
+            sigma2: sigma2 * scale * scale,
+            log_likelihood,
+            aic: -2.0 * log_likelihood + 2.0 * k,
+            bic: -2.0 * log_likelihood + k * (observations as f64).ln(),
+            residuals: residuals.iter().map(|e| e * scale).collect(),
+            series: series.to_vec(),
+        })
+    }
+
+    /// Splits the parameter vector into the mean, and the AR and MA
+    /// coefficients.
+    fn unpack<'a>(&self, x: &'a [f64]) -> (f64, &'a [f64], &'a [f64]) {
+        let (mean, rest) = if self.constant {
+            (x[0], &x[1..])
+        } else {
+            (0.0, x)
+        };
+
+        (mean, &rest[..self.p], &rest[self.p..])
+    }
+}
+
+impl FittedArima {
+    /// Forecasts of the series for the next `horizon` periods, with
+    /// confidence intervals at level `confidence` (e.g. 0.95).
+    ///
+    /// The standard error of the $h$-step forecast is
+    /// $\sigma \sqrt{\sum_{j<h} \psi_j^2}$, with the weights $\psi_j$ of the
+    /// infinite moving average representation of the (undifferenced)
+    /// model.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the confidence is not in (0, 1).
+    pub fn forecast(
+        &self,
+        horizon: usize,
+        confidence: f64,
+    ) -> Result<Vec<ArimaForecast>, RustQuantError> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Confidence level must be in (0, 1), got {confidence}."
+            )));
+        }
+
+        let (p, d, q) = (self.model.p, self.model.d, self.model.q);
+
+        // Forecasts of the differenced series, with the future innovations
+        // at zero.
+        let mut w = difference(&self.series, d);
+        let mut e = self.residuals.clone();
+        let n = w.len();
+
+        for t in n..n + horizon {
+            let value = self.mean
+                + (1..=p)
+                    .map(|i| self.ar[i - 1] * (w[t - i] - self.mean))
+                    .sum::<f64>()
+                + (1..=q)
+                    .filter(|j| *j <= t)
+                    .map(|j| self.ma[j - 1] * e[t - j])
+                    .sum::<f64>();
+
+            w.push(value);
+            e.push(0.0);
+        }
+
+        // Undifference: each level is the cumulative sum of the next one,
+        // from its last observed value.
+        let mut forecasts = w[n..].to_vec();
+        for level in (0..d).rev() {
+            let mut last = *difference(&self.series, level)
+                .last()
+                .expect("The series is longer than the order of differencing.");
+
+            for value in &mut forecasts {
+                last += *value;
+                *value = last;
+            }
+        }
+
+        // Psi weights of phi(B) (1 - B)^d psi(B) = theta(B).
+        let mut phi = vec![1.0];
+        phi.extend(self.ar.iter().map(|a| -a));
+        for _ in 0..d {
+            let mut next = phi.clone();
+            next.push(0.0);
+            for k in 1..next.len() {
+                next[k] -= phi[k - 1];
+            }
+            phi = next;
+        }
+
+        let mut psi = vec![1.0];
+        for j in 1..horizon {
+            let value = self.ma.get(j - 1).copied().unwrap_or(0.0)
+                - (1..phi.len().min(j + 1))
+                    .map(|k| phi[k] * psi[j - k])
+                    .sum::<f64>();
+            psi.push(value);
+        }
+
+        let z = Gaussian::default().inv_cdf(0.5 + 0.5 * confidence);
+        let mut variance = 0.0;
+
+        Ok(forecasts
+            .into_iter()
+            .enumerate()
+            .map(|(h, mean)| {
+                variance += self.sigma2 * psi[h] * psi[h];
+                let standard_error = variance.sqrt();
+
+                ArimaForecast {
+                    horizon: h + 1,
+                    mean,
+                    standard_error,
+                    lower: mean - z * standard_error,
+                    upper: mean + z * standard_error,
+                }
+            })
+            .collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fits ARIMA(p, d, q) models for all $p \le$ `max_p` and $q \le$ `max_q`
+/// and returns the one with the lowest information criterion.
+///
+/// The criteria are only comparable between models fitted to the same
+/// observations, so maximum likelihood is the better method here: the
+/// conditional sum of squares drops the first $p$ observations.
+///
+/// # Errors
+///
+/// Returns an error if no model could be fitted.
+pub fn select_arima(
+    series: &[f64],
+    max_p: usize,
+    d: usize,
+    max_q: usize,
+    criterion: InformationCriterion,
+    method: ArimaMethod,
+) -> Result<FittedArima, RustQuantError> {
+    let mut best: Option<FittedArima> = None;
+    let mut error = None;
+
+    for p in 0..=max_p {
+        for q in 0..=max_q {
+            match Arima::new(p, d, q).with_method(method).fit(series) {
+                Ok(fitted) => {
+                    let score = |model: &FittedArima| match criterion {
+                        InformationCriterion::Aic => model.aic,
+                        InformationCriterion::Bic => model.bic,
+                    };
+
+                    if best.as_ref().is_none_or(|b| score(&fitted) < score(b)) {
+                        best = Some(fitted);
+                    }
+                }
+                Err(e) => error = Some(e),
+            }
+        }
+    }
+
+    best.ok_or_else(|| {
+        error.unwrap_or(RustQuantError::InvalidArgument(
+            "No ARIMA model to select from.".to_string(),
+        ))
+    })
+}
+
+/// The series differenced `d` times.
+fn difference(series: &[f64], d: usize) -> Vec<f64> {
+    let mut w = series.to_vec();
+
+    for _ in 0..d {
+        w = w.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    }
+
+    w
+}
+
+/// Whether the AR polynomial $1 - \sum \phi_i z^i$ and the MA polynomial
+/// $1 + \sum \theta_j z^j$ have all their roots outside the unit circle.
+fn admissible(ar: &[f64], ma: &[f64]) -> bool {
+    let outside = |coefficients: Vec<f64>| {
+        polynomial_roots(&coefficients).is_ok_and(|roots| roots.iter().all(|z| z.norm() > 1.0))
+    };
+
+    let ar_polynomial = ar.iter().rev().map(|a| -a).chain([1.0]).collect();
+    let ma_polynomial = ma.iter().rev().copied().chain([1.0]).collect();
+
+    ar.iter().chain(ma).all(|x| x.is_finite()) && outside(ar_polynomial) && outside(ma_polynomial)
+}
+
+/// Residuals of the ARMA recursion, with the residuals before observation
+/// $p$ set to zero.
+fn conditional_residuals(w: &[f64], mean: f64, ar: &[f64], ma: &[f64]) -> Vec<f64> {
+    let p = ar.len();
+    let mut e = vec![0.0; w.len()];
+
+    for t in p..w.len() {
+        e[t] = w[t]
+            - mean
+            - (1..=p).map(|i| ar[i - 1] * (w[t - i] - mean)).sum::<f64>()
+            - (1..=ma.len())
+                .filter(|j| *j <= t)
+                .map(|j| ma[j - 1] * e[t - j])
+                .sum::<f64>();
+    }
+
+    e
+}
+
+/// Exact Gaussian log-likelihood of the ARMA model, with $\sigma^2$
+/// concentrated out, by the Kalman filter of its state space form (Harvey,
+/// 1989): the state is $r = \max(p, q + 1)$ dimensional, started from its
+/// stationary distribution. Returns the log-likelihood, the estimate of
+/// $\sigma^2$ and the innovations.
+fn kalman_filter(w: &[f64], mean: f64, ar: &[f64], ma: &[f64]) -> Option<(f64, f64, Vec<f64>)> {
+    let r = ar.len().max(ma.len() + 1);
+    let n = w.len();
+
+    let transition = DMatrix::from_fn(r, r, |i, j| {
+        if j == 0 {
+            ar.get(i).copied().unwrap_or(0.0)
+        } else if i + 1 == j {
+            1.0
+        } else {
+            0.0
+        }
+    });
+    let loading = DVector::from_fn(r, |i, _| {
+        if i == 0 {
+            1.0
+        } else {
+            ma.get(i - 1).copied().unwrap_or(0.0)
+        }
+    });
+    let noise = &loading * loading.transpose();
+
+    // Stationary covariance: vec(P) = (I - T (x) T)^-1 vec(R R').
+    let system = DMatrix::identity(r * r, r * r) - transition.kronecker(&transition);
+    let stationary =
+        solve_linear_system(&system, &DVector::from_column_slice(noise.as_slice())).ok()?;
+    let mut covariance = DMatrix::from_column_slice(r, r, stationary.as_slice());
+    let mut state = DVector::zeros(r);
+
+    let (mut sum_squares, mut sum_log_variance) = (0.0, 0.0);
+    let mut innovations = Vec::with_capacity(n);
+
+    for &y in w {
+        let innovation = y - mean - state[0];
+        let variance = covariance[(0, 0)];
+
+        if variance <= 0.0 || !variance.is_finite() {
+            return None;
+        }
+
+        sum_squares += innovation * innovation / variance;
+        sum_log_variance += variance.ln();
+        innovations.push(innovation);
+
+        let gain = &transition * covariance.column(0) / variance;
+        state = &transition * state + &gain * innovation;
+        covariance = &transition * &covariance * transition.transpose() + &noise
+            - &gain * gain.transpose() * variance;
+    }
+
+    let sigma2 = sum_squares / n as f64;
+    let log_likelihood = -0.5 * n as f64 * ((2.0 * std::f64::consts::PI * sigma2).ln() + 1.0)
+        - 0.5 * sum_log_variance;
+
+    Some((log_likelihood, sigma2, innovations))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_arima {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+    use RustQuant_utils::assert_approx_equal;
+
+    /// ARMA series with N(0, sigma^2) innovations, after a burn-in.
+    fn simulate(mean: f64, ar: &[f64], ma: &[f64], sigma: f64, n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let burn_in = 500;
+        let (mut w, mut e) = (vec![mean; burn_in + n], vec![0.0; burn_in + n]);
+
+        for t in 0..burn_in + n {
+            e[t] = sigma * rng.sample::<f64, _>(StandardNormal);
+            w[t] = mean
+                + e[t]
+                + (1..=ar.len())
+                    .filter(|i| *i <= t)
+                    .map(|i| ar[i - 1] * (w[t - i] - mean))
+                    .sum::<f64>()
+                + (1..=ma.len())
+                    .filter(|j| *j <= t)
+                    .map(|j| ma[j - 1] * e[t - j])
+                    .sum::<f64>();
+        }
+
+        w.split_off(burn_in)
+    }
+
+    #[test]
+    fn test_arima_ar1() {
+        let series = simulate(0.01, &[0.6], &[], 0.02, 2000, 1);
+
+        for method in [
+            ArimaMethod::ConditionalSumOfSquares,
+            ArimaMethod::MaximumLikelihood,
+        ] {
+            let fitted = Arima::new(1, 0, 0)
+                .with_method(method)
+                .fit(&series)
+                .unwrap();
+
+            assert_approx_equal!(fitted.ar[0], 0.6, 0.05);
+            assert_approx_equal!(fitted.mean, 0.01, 0.003);
+            assert_approx_equal!(fitted.sigma2.sqrt(), 0.02, 0.001);
+        }
+    }
+
+    #[test]
+    fn test_arima_arma11() {
+        let series = simulate(0.0, &[0.5], &[0.3], 1.0, 3000, 2);
+
+        let fitted = Arima::new(1, 0, 1).fit(&series).unwrap();
+
+        assert_approx_equal!(fitted.ar[0], 0.5, 0.06);
+        assert_approx_equal!(fitted.ma[0], 0.3, 0.06);
+        assert_eq!(fitted.residuals.len(), series.len());
+
+        // The residuals are (nearly) uncorrelated.
+        let n = fitted.residuals.len() as f64;
+        let lag1 = fitted
+            .residuals
+            .windows(2)
+            .map(|e| e[0] * e[1])
+            .sum::<f64>()
+            / n;
+        assert!(lag1.abs() / fitted.sigma2 < 0.05);
+    }
+
+    #[test]
+    fn test_arima_likelihood_against_css() {
+        // The two estimates agree for a long series.
+        let series = simulate(0.0, &[0.3, -0.2], &[], 1.0, 1000, 3);
+
+        let css = Arima::new(2, 0, 0)
+            .with_method(ArimaMethod::ConditionalSumOfSquares)
+            .fit(&series)
+            .unwrap();
+        let mle = Arima::new(2, 0, 0).fit(&series).unwrap();
+
+        for (a, b) in css.ar.iter().zip(&mle.ar) {
+            assert_approx_equal!(*a, *b, 0.01);
+        }
+        assert!(mle.log_likelihood.is_finite());
+        assert_eq!(css.residuals.len(), series.len());
+        assert_eq!(css.residuals[..2], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_arima_select_order() {
+        let series = simulate(0.0, &[0.6, -0.5], &[], 1.0, 300, 4);
+
+        let selected = select_arima(
+            &series,
+            3,
+            0,
+            2,
+            InformationCriterion::Bic,
+            ArimaMethod::ConditionalSumOfSquares,
+        )
+        .unwrap();
+
+        assert_eq!((selected.model.p, selected.model.q), (2, 0));
+    }
+
+    #[test]
+    fn test_arima_random_walk_forecast() {
+        // ARIMA(0, 1, 0): the forecasts are the last value, with standard
+        // errors sigma sqrt(h).
+        let series = vec![1.0, 2.0, 1.5, 2.5, 3.0, 2.0, 2.5];
+        let fitted = Arima::new(0, 1, 0).fit(&series).unwrap();
+
+        // Mean squared difference.
+        assert_approx_equal!(fitted.sigma2, 3.75 / 6.0, 1e-12);
+
+        let forecasts = fitted.forecast(4, 0.95).unwrap();
+        for forecast in &forecasts {
+            assert_approx_equal!(forecast.mean, 2.5, 1e-12);
+            assert_approx_equal!(
+                forecast.standard_error,
+                (fitted.sigma2 * forecast.horizon as f64).sqrt(),
+                1e-12
+            );
+            assert_approx_equal!(
+                forecast.upper - forecast.mean,
+                1.959_963_984_540_054 * forecast.standard_error,
+                1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_arima_ar1_forecast() {
+        let series = simulate(0.5, &[0.8], &[], 0.1, 500, 5);
+        let fitted = Arima::new(1, 0, 0).fit(&series).unwrap();
+        let forecasts = fitted.forecast(3, 0.9).unwrap();
+
+        let (phi, mu) = (fitted.ar[0], fitted.mean);
+        let last = series[series.len() - 1];
+        let mut variance = 0.0;
+
+        for (h, forecast) in forecasts.iter().enumerate() {
+            variance += fitted.sigma2 * phi.powi(2 * h as i32);
+
+            assert_approx_equal!(
+                forecast.mean,
+                mu + phi.powi(h as i32 + 1) * (last - mu),
+                1e-12
+            );
+            assert_approx_equal!(forecast.standard_error, variance.sqrt(), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_arima_integrated_forecast() {
+        // ARIMA(1, 1, 0) with drift on a trending series: the forecasts of
+        // the levels are the cumulative forecasts of the differences.
+        let differences = simulate(0.2, &[0.4], &[], 0.1, 400, 6);
+        let series: Vec<f64> = differences
+            .iter()
+            .scan(100.0, |level, w| {
+                *level += w;
+                Some(*level)
+            })
+            .collect();
+
+        let fitted = Arima::new(1, 1, 0)
+            .with_constant(true)
+            .fit(&series)
+            .unwrap();
+        assert_approx_equal!(fitted.mean, 0.2, 0.03);
+
+        let forecasts = fitted.forecast(2, 0.95).unwrap();
+        let (phi, mu) = (fitted.ar[0], fitted.mean);
+        let last = series[series.len() - 1];
+        let w1 = mu + phi * (differences[differences.len() - 1] - mu);
+        let w2 = mu + phi * (w1 - mu);
+
+        assert_approx_equal!(forecasts[0].mean, last + w1, 1e-9);
+        assert_approx_equal!(forecasts[1].mean, last + w1 + w2, 1e-9);
+
+        // psi_1 = 1 + phi for the integrated model.
+        assert_approx_equal!(
+            forecasts[1].standard_error,
+            (fitted.sigma2 * (1.0 + (1.0 + phi).powi(2))).sqrt(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_arima_invalid_series() {
+        assert!(Arima::new(1, 0, 0).fit(&[1.0, 2.0]).is_err());
+        assert!(Arima::new(0, 0, 0).fit(&[1.0; 10]).is_err());
+        assert!(Arima::new(1, 0, 0)
+            .fit(&[1.0, f64::NAN, 2.0, 3.0, 1.0])
+            .is_err());
+
+        let fitted = Arima::new(0, 1, 0).fit(&[1.0, 2.0, 1.5, 2.5]).unwrap();
+        assert!(fitted.forecast(1, 1.0).is_err());
+    }
+}
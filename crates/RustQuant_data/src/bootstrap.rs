@@ -0,0 +1,624 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Discount curve bootstrapping.
+//!
+//! A [`CurveBootstrapper`] takes quoted deposits, FRAs, futures and par swaps,
+//! sorts them by maturity, and solves for one discount factor per instrument
+//! so that each instrument reprices exactly, given the discount factors
+//! already found for the shorter instruments.
+//!
+//! Between pillars the curve is interpolated linearly in the chosen
+//! [`BootstrapInterpolation`] quantity, which also determines the discount
+//! factors of swap coupon dates that fall between pillars.
+//!
+//! This is a single-curve bootstrap: floating legs are assumed to be
+//! discounted and projected off the same curve, and instruments start on the
+//! valuation date (no spot lag).
+//!
+//! ```
+//! use RustQuant_data::*;
+//! use RustQuant_time::Frequency;
+//! use time::macros::date;
+//!
+//! let curve = CurveBootstrapper::new(date!(2024 - 01 - 02))
+//!     .with_instrument(CurveInstrument::Deposit { maturity: date!(2024 - 04 - 02), rate: 0.05 })
+//!     .with_instrument(CurveInstrument::Swap {
+//!         maturity: date!(2029 - 01 - 02),
+//!         rate: 0.04,
+//!         fixed_frequency: Frequency::Annually,
+//!     })
+//!     .bootstrap()
+//!     .unwrap();
+//!
+//! assert!(curve.discount_factor(date!(2026 - 06 - 30)) < 1.0);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Curve, RateCurve};
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_time::{DayCountConvention, Frequency, Tenor};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Quoted instrument used to bootstrap a discount curve.
+/// Rates are simple annual rates accrued with the bootstrapper's day count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveInstrument {
+    /// Deposit from the valuation date to `maturity`.
+    Deposit {
+        /// Maturity date.
+        maturity: Date,
+
+        /// Quoted deposit rate.
+        rate: f64,
+    },
+
+    /// Forward rate agreement from `start` to `end`.
+    Fra {
+        /// Start of the forward period.
+        start: Date,
+
+        /// End of the forward period.
+        end: Date,
+
+        /// Quoted forward rate.
+        rate: f64,
+    },
+
+    /// Interest rate future on the period from `start` to `end`, quoted as
+    /// `100 - rate`. The convexity adjustment is subtracted from the futures
+    /// rate to get the forward rate.
+    Future {
+        /// Start of the underlying period.
+        start: Date,
+
+        /// End of the underlying period.
+        end: Date,
+
+        /// Quoted price, e.g. `95.25`.
+        price: f64,
+
+        /// Futures rate minus forward rate.
+        convexity_adjustment: f64,
+    },
+
+    /// Par swap from the valuation date to `maturity`. Fixed coupons are
+    /// rolled back from the maturity, with a short first period if needed.
+    Swap {
+        /// Maturity date.
+        maturity: Date,
+
+        /// Quoted par rate.
+        rate: f64,
+
+        /// Fixed leg payment frequency (a whole number of months).
+        fixed_frequency: Frequency,
+    },
+}
+
+/// Quantity interpolated linearly between bootstrapped pillars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BootstrapInterpolation {
+    /// Discount factors.
+    DiscountFactor,
+
+    /// Continuously compounded zero rates.
+    ZeroRate,
+
+    /// Log discount factors (piecewise flat forward rates).
+    #[default]
+    LogDiscountFactor,
+}
+
+/// Bootstraps a discount curve from quoted instruments.
+#[derive(Debug, Clone)]
+pub struct CurveBootstrapper {
+    /// Valuation date, where the discount factor is one.
+    pub valuation_date: Date,
+
+    /// Day count for accruals and for the time axis of the curve.
+    pub day_count_convention: DayCountConvention,
+
+    /// Interpolation between pillars.
+    pub interpolation: BootstrapInterpolation,
+
+    /// Instruments to fit.
+    pub instruments: Vec<CurveInstrument>,
+}
+
+/// Discount curve produced by a [`CurveBootstrapper`].
+#[derive(Debug, Clone)]
+pub struct BootstrappedCurve {
+    /// Valuation date.
+    pub valuation_date: Date,
+
+    /// Day count of the time axis.
+    pub day_count_convention: DayCountConvention,
+
+    /// Interpolation between pillars.
+    pub interpolation: BootstrapInterpolation,
+
+    /// Discount factors at the valuation date and at each pillar.
+    pub curve: Curve<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CurveInstrument {
+    /// Date of the pillar this instrument determines.
+    pub fn maturity(&self) -> Date {
+        match self {
+            Self::Deposit { maturity, .. } | Self::Swap { maturity, .. } => *maturity,
+            Self::Fra { end, .. } | Self::Future { end, .. } => *end,
+        }
+    }
+
+    /// The same instrument with its quote shifted by `shift` in rate terms,
+    /// i.e. a futures price moves down by `100 * shift`.
+    pub fn shifted(&self, shift: f64) -> Self {
+        let mut instrument = *self;
+
+        match &mut instrument {
+            Self::Deposit { rate, .. } | Self::Fra { rate, .. } | Self::Swap { rate, .. } => {
+                *rate += shift
+            }
+            Self::Future { price, .. } => *price -= 100.0 * shift,
+        }
+
+        instrument
+    }
+
+    /// Present value of the instrument per unit notional on `curve`,
+    /// zero when the curve reprices the quote.
+    pub fn residual(&self, curve: &BootstrappedCurve) -> Result<f64, RustQuantError> {
+        let accrual =
+            |start: Date, end: Date| curve.day_count_convention.day_count_factor(start, end);
+
+        match *self {
+            Self::Deposit { maturity, rate } => {
+                let tau = accrual(curve.valuation_date, maturity);
+                Ok((1.0 + rate * tau) * curve.discount_factor(maturity) - 1.0)
+            }
+            Self::Fra { start, end, rate } => {
+                let tau = accrual(start, end);
+                Ok((1.0 + rate * tau) * curve.discount_factor(end) - curve.discount_factor(start))
+            }
+            Self::Future {
+                start,
+                end,
+                price,
+                convexity_adjustment,
+            } => {
+                let rate = (100.0 - price) / 100.0 - convexity_adjustment;
+                let tau = accrual(start, end);
+                Ok((1.0 + rate * tau) * curve.discount_factor(end) - curve.discount_factor(start))
+            }
+            Self::Swap {
+                maturity,
+                rate,
+                fixed_frequency,
+            } => {
+                let dates = fixed_leg_dates(curve.valuation_date, maturity, fixed_frequency)?;
+                let annuity: f64 = dates
+                    .windows(2)
+                    .map(|period| accrual(period[0], period[1]) * curve.discount_factor(period[1]))
+                    .sum();
+
+                Ok(rate * annuity + curve.discount_factor(maturity) - 1.0)
+            }
+        }
+    }
+
+    /// Check the instrument's dates against the valuation date.
+    fn validate(&self, valuation_date: Date) -> Result<(), RustQuantError> {
+        let start = match self {
+            Self::Fra { start, .. } | Self::Future { start, .. } => *start,
+            _ => valuation_date,
+        };
+
+        if start < valuation_date || self.maturity() <= start {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Instrument maturing {} must start on or after the valuation date {} and end after it starts.",
+                self.maturity(),
+                valuation_date
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl CurveBootstrapper {
+    /// Maximum number of bisection steps per pillar.
+    const MAX_ITERATIONS: usize = 200;
+
+    /// Tolerance on the pillar discount factor.
+    const TOLERANCE: f64 = 1e-14;
+
+    /// New bootstrapper with no instruments, Act/365F accruals and
+    /// log-linear discount factors.
+    pub fn new(valuation_date: Date) -> Self {
+        Self {
+            valuation_date,
+            day_count_convention: DayCountConvention::Actual_365_Fixed,
+            interpolation: BootstrapInterpolation::default(),
+            instruments: Vec::new(),
+        }
+    }
+
+    /// Set the day count convention.
+    pub fn with_day_count_convention(mut self, day_count_convention: DayCountConvention) -> Self {
+        self.day_count_convention = day_count_convention;
+        self
+    }
+
+    /// Set the interpolation between pillars.
+    pub fn with_interpolation(mut self, interpolation: BootstrapInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Add a quoted instrument.
+    pub fn with_instrument(mut self, instrument: CurveInstrument) -> Self {
+        self.instruments.push(instrument);
+        self
+    }
+
+    /// Add several quoted instruments.
+    pub fn with_instruments(mut self, instruments: &[CurveInstrument]) -> Self {
+        self.instruments.extend_from_slice(instruments);
+        self
+    }
+
+    /// Bootstrap the curve, one pillar per instrument in order of maturity.
+    ///
+    /// Each pillar's discount factor is found by bisection, which is safe
+    /// because every instrument's value increases with its own pillar.
+    pub fn bootstrap(&self) -> Result<BootstrappedCurve, RustQuantError> {
+        if self.instruments.is_empty() {
+            return Err(RustQuantError::MissingInput(
+                "No instruments to bootstrap.".to_string(),
+            ));
+        }
+
+        let mut instruments = self.instruments.clone();
+        instruments.sort_by_key(CurveInstrument::maturity);
+
+        for pair in instruments.windows(2) {
+            if pair[0].maturity() == pair[1].maturity() {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Two instruments mature on {}.",
+                    pair[0].maturity()
+                )));
+            }
+        }
+
+        let mut curve = BootstrappedCurve {
+            valuation_date: self.valuation_date,
+            day_count_convention: self.day_count_convention,
+            interpolation: self.interpolation,
+            curve: Curve::<Date>::new(),
+        };
+        curve.curve.insert(self.valuation_date, 1.0);
+
+        for instrument in &instruments {
+            instrument.validate(self.valuation_date)?;
+
+            let pillar = instrument.maturity();
+            let mut residual = |df: f64| {
+                curve.curve.insert(pillar, df);
+                instrument.residual(&curve)
+            };
+
+            // Bracket the discount factor, allowing for negative rates.
+            let (mut low, mut high) = (1e-8, 2.0);
+            let (residual_low, residual_high) = (residual(low)?, residual(high)?);
+
+            if residual_low.signum() == residual_high.signum() {
+                return Err(RustQuantError::ComputationError(format!(
+                    "No discount factor in ({}, {}) reprices the instrument maturing {}.",
+                    low, high, pillar
+                )));
+            }
+
+            for _ in 0..Self::MAX_ITERATIONS {
+                let mid = 0.5 * (low + high);
+
+                if residual(mid)?.signum() == residual_low.signum() {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+
+                if high - low < Self::TOLERANCE {
+                    break;
+                }
+            }
+
+            curve.curve.insert(pillar, 0.5 * (low + high));
+        }
+
+        Ok(curve)
+    }
+}
+
+impl RateCurve for BootstrappedCurve {
+    fn valuation_date(&self) -> Date {
+        self.valuation_date
+    }
+
+    fn day_count_convention(&self) -> DayCountConvention {
+        self.day_count_convention
+    }
+
+    /// Discount factor for a date, interpolated between pillars.
+    /// Beyond the last pillar the zero rate is held flat.
+    fn discount_factor(&self, date: Date) -> f64 {
+        if let Some(df) = self.curve.get(date) {
+            return *df;
+        }
+
+        let time = |date: Date| {
+            self.day_count_convention
+                .day_count_factor(self.valuation_date, date)
+        };
+        let t = time(date);
+
+        if t <= 0.0 {
+            return 1.0;
+        }
+
+        let right = self.curve.nodes.range(date..).next();
+        let left = self.curve.nodes.range(..date).next_back();
+
+        let ((d0, df0), (d1, df1)) = match (left, right) {
+            (Some(left), Some(right)) => (left, right),
+            (Some((d0, df0)), None) => {
+                let zero_rate = -df0.ln() / time(*d0);
+                return (-zero_rate * t).exp();
+            }
+            _ => return 1.0,
+        };
+
+        let (t0, t1) = (time(*d0), time(*d1));
+        let w = (t - t0) / (t1 - t0);
+
+        match self.interpolation {
+            BootstrapInterpolation::DiscountFactor => (1.0 - w) * df0 + w * df1,
+            BootstrapInterpolation::LogDiscountFactor => {
+                ((1.0 - w) * df0.ln() + w * df1.ln()).exp()
+            }
+            BootstrapInterpolation::ZeroRate => {
+                let z1 = -df1.ln() / t1;
+                // Flat zero rate back to the valuation date.
+                let z0 = if t0 > 0.0 { -df0.ln() / t0 } else { z1 };
+
+                (-((1.0 - w) * z0 + w * z1) * t).exp()
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fixed leg dates from `start` to `maturity`, rolled back from the
+/// maturity, including both ends.
+fn fixed_leg_dates(
+    start: Date,
+    maturity: Date,
+    frequency: Frequency,
+) -> Result<Vec<Date>, RustQuantError> {
+    let per_year = frequency.times_in_year();
+
+    if per_year <= 0 || 12 % per_year != 0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Swap fixed leg frequency must be a whole number of months, got {:?}.",
+            frequency
+        )));
+    }
+
+    let months = (12 / per_year) as i32;
+    let mut dates = vec![maturity];
+
+    for k in 1.. {
+        let date = maturity - Tenor::months(months * k);
+        if date <= start {
+            break;
+        }
+        dates.push(date);
+    }
+
+    dates.push(start);
+    dates.reverse();
+
+    Ok(dates)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bootstrap {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    const TODAY: Date = date!(2024 - 01 - 02);
+
+    fn instruments() -> Vec<CurveInstrument> {
+        vec![
+            CurveInstrument::Deposit {
+                maturity: date!(2024 - 02 - 02),
+                rate: 0.0530,
+            },
+            CurveInstrument::Deposit {
+                maturity: date!(2024 - 04 - 02),
+                rate: 0.0525,
+            },
+            CurveInstrument::Fra {
+                start: date!(2024 - 04 - 02),
+                end: date!(2024 - 07 - 02),
+                rate: 0.0505,
+            },
+            CurveInstrument::Future {
+                start: date!(2024 - 07 - 02),
+                end: date!(2024 - 10 - 02),
+                price: 95.20,
+                convexity_adjustment: 0.0002,
+            },
+            CurveInstrument::Swap {
+                maturity: date!(2026 - 01 - 02),
+                rate: 0.0440,
+                fixed_frequency: Frequency::Annually,
+            },
+            CurveInstrument::Swap {
+                maturity: date!(2029 - 01 - 02),
+                rate: 0.0395,
+                fixed_frequency: Frequency::SemiAnnually,
+            },
+            CurveInstrument::Swap {
+                maturity: date!(2034 - 01 - 02),
+                rate: 0.0390,
+                fixed_frequency: Frequency::Annually,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_reprices_instruments() {
+        for interpolation in [
+            BootstrapInterpolation::DiscountFactor,
+            BootstrapInterpolation::ZeroRate,
+            BootstrapInterpolation::LogDiscountFactor,
+        ] {
+            // Shuffled input, sorted by the bootstrapper.
+            let mut quotes = instruments();
+            quotes.reverse();
+
+            let curve = CurveBootstrapper::new(TODAY)
+                .with_interpolation(interpolation)
+                .with_instruments(&quotes)
+                .bootstrap()
+                .unwrap();
+
+            assert_eq!(curve.curve.len(), quotes.len() + 1);
+            for instrument in &quotes {
+                assert_approx_equal!(instrument.residual(&curve).unwrap(), 0.0, 1e-12);
+            }
+
+            // Discount factors decrease with these positive rates.
+            let values = curve.curve.values();
+            assert!(values.windows(2).all(|pair| pair[1] < pair[0]));
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        let curve = CurveBootstrapper::new(TODAY)
+            .with_instruments(&instruments())
+            .bootstrap()
+            .unwrap();
+
+        // Deposit: DF = 1 / (1 + r tau).
+        let tau = 91.0 / 365.0;
+        assert_approx_equal!(
+            curve.discount_factor(date!(2024 - 04 - 02)),
+            1.0 / (1.0 + 0.0525 * tau),
+            1e-14
+        );
+
+        // FRA and future forwards are recovered.
+        assert_approx_equal!(
+            curve.forward_rate(date!(2024 - 04 - 02), date!(2024 - 07 - 02)),
+            0.0505,
+            1e-12
+        );
+        assert_approx_equal!(
+            curve.forward_rate(date!(2024 - 07 - 02), date!(2024 - 10 - 02)),
+            0.048 - 0.0002,
+            1e-12
+        );
+
+        // Log-linear discount factors give flat forwards between pillars.
+        let (d0, d1, d2) = (
+            date!(2024 - 10 - 02),
+            date!(2025 - 01 - 02),
+            date!(2025 - 06 - 02),
+        );
+        let forward = |a: Date, b: Date| {
+            (curve.discount_factor(a) / curve.discount_factor(b)).ln()
+                / DayCountConvention::Actual_365_Fixed.day_count_factor(a, b)
+        };
+        assert_approx_equal!(forward(d0, d1), forward(d1, d2), 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_instruments() {
+        assert!(CurveBootstrapper::new(TODAY).bootstrap().is_err());
+
+        let duplicate = CurveBootstrapper::new(TODAY).with_instruments(&[
+            CurveInstrument::Deposit {
+                maturity: date!(2024 - 04 - 02),
+                rate: 0.05,
+            },
+            CurveInstrument::Fra {
+                start: date!(2024 - 01 - 02),
+                end: date!(2024 - 04 - 02),
+                rate: 0.05,
+            },
+        ]);
+        assert!(duplicate.bootstrap().is_err());
+
+        let expired = CurveBootstrapper::new(TODAY).with_instrument(CurveInstrument::Deposit {
+            maturity: date!(2023 - 12 - 01),
+            rate: 0.05,
+        });
+        assert!(expired.bootstrap().is_err());
+
+        let weekly = CurveBootstrapper::new(TODAY).with_instrument(CurveInstrument::Swap {
+            maturity: date!(2026 - 01 - 02),
+            rate: 0.04,
+            fixed_frequency: Frequency::Weekly,
+        });
+        assert!(weekly.bootstrap().is_err());
+    }
+
+    #[test]
+    fn test_fixed_leg_dates() {
+        let dates = fixed_leg_dates(
+            date!(2024 - 01 - 02),
+            date!(2025 - 08 - 31),
+            Frequency::SemiAnnually,
+        )
+        .unwrap();
+
+        assert_eq!(
+            dates,
+            vec![
+                date!(2024 - 01 - 02),
+                date!(2024 - 02 - 29),
+                date!(2024 - 08 - 31),
+                date!(2025 - 02 - 28),
+                date!(2025 - 08 - 31),
+            ]
+        );
+    }
+}
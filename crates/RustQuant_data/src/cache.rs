@@ -0,0 +1,371 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Local cache of downloaded market data.
+//!
+//! Frames are stored as parquet files in a directory, one file per ticker
+//! and date range: `<dir>/<ticker>_<start>_<end>.parquet`. Characters of
+//! the ticker that are not safe in file names are replaced by `_`.
+//!
+//! ```no_run
+//! use RustQuant_data::{CacheMode, MarketDataCache, YahooFinanceData, YahooFinanceReader};
+//!
+//! let mut yfd = YahooFinanceData::new("AAPL".to_string());
+//! yfd.set_cache(MarketDataCache::new("market_data").with_mode(CacheMode::Offline));
+//!
+//! // Reads `market_data/AAPL_<start>_<end>.parquet`, without downloading.
+//! yfd.get_price_history().unwrap();
+//! ```
+
+use polars::prelude::*;
+use std::path::PathBuf;
+use time::Date;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How the cache is used when data is requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Read cached data, and cache whatever has to be downloaded.
+    #[default]
+    ReadWrite,
+
+    /// Always download, and overwrite the cached data.
+    Refresh,
+
+    /// Never download: data that is not cached is an error.
+    Offline,
+
+    /// Always download, and leave the cache untouched.
+    Disabled,
+}
+
+/// Directory of cached market data frames.
+#[derive(Clone, Debug)]
+pub struct MarketDataCache {
+    /// Directory of the cached files.
+    pub dir: PathBuf,
+
+    /// How the cache is used.
+    pub mode: CacheMode,
+
+    /// Age after which cached data is downloaded again (never, if `None`).
+    /// Ignored in offline mode.
+    pub max_age: Option<std::time::Duration>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarketDataCache {
+    /// Create a read-write cache in a directory (created when first written).
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: CacheMode::default(),
+            max_age: None,
+        }
+    }
+
+    /// Use the cache in another mode.
+    pub fn with_mode(mut self, mode: CacheMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Download data again once its cached copy is older than `max_age`.
+    pub fn with_max_age(mut self, max_age: std::time::Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Path of the cached frame for a ticker and date range.
+    pub fn path(&self, ticker: &str, start: Date, end: Date) -> PathBuf {
+        self.dir
+            .join(format!("{}_{}_{}.parquet", sanitise(ticker), start, end))
+    }
+
+    /// Cached frame for a ticker and date range, if there is one
+    /// and it is not older than the maximum age.
+    pub fn load(
+        &self,
+        ticker: &str,
+        start: Date,
+        end: Date,
+    ) -> Result<Option<DataFrame>, RustQuantError> {
+        let path = self.path(ticker, start, end);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        if let (Some(max_age), false) = (self.max_age, self.mode == CacheMode::Offline) {
+            let age = std::fs::metadata(&path)?
+                .modified()?
+                .elapsed()
+                .unwrap_or_default();
+
+            if age > max_age {
+                return Ok(None);
+            }
+        }
+
+        let mut file = std::fs::File::open(&path)?;
+
+        Ok(Some(ParquetReader::new(&mut file).finish()?))
+    }
+
+    /// Cache a frame for a ticker and date range.
+    pub fn store(
+        &self,
+        ticker: &str,
+        start: Date,
+        end: Date,
+        frame: &mut DataFrame,
+    ) -> Result<(), RustQuantError> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        // Write to a temporary file first, so that an interrupted write
+        // never leaves a truncated frame in the cache.
+        let path = self.path(ticker, start, end);
+        let partial = path.with_extension("parquet.partial");
+
+        let mut file = std::fs::File::create(&partial)?;
+        ParquetWriter::new(&mut file).finish(frame)?;
+        std::fs::rename(&partial, &path)?;
+
+        Ok(())
+    }
+
+    /// Frame for a ticker and date range, from the cache or from `fetch`,
+    /// depending on the mode.
+    ///
+    /// # Errors
+    /// - `RustQuantError::MissingInput` in offline mode, if the frame is not cached.
+    /// - Any error of `fetch`, or of reading or writing the cache.
+    pub fn get_or_fetch<F>(
+        &self,
+        ticker: &str,
+        start: Date,
+        end: Date,
+        fetch: F,
+    ) -> Result<DataFrame, RustQuantError>
+    where
+        F: FnOnce() -> Result<DataFrame, RustQuantError>,
+    {
+        match self.mode {
+            CacheMode::Disabled => fetch(),
+            CacheMode::Offline => self.load(ticker, start, end)?.ok_or_else(|| {
+                RustQuantError::MissingInput(format!(
+                    "{} from {} to {} is not cached (offline mode).",
+                    ticker, start, end
+                ))
+            }),
+            CacheMode::ReadWrite | CacheMode::Refresh => {
+                if self.mode == CacheMode::ReadWrite {
+                    if let Some(frame) = self.load(ticker, start, end)? {
+                        return Ok(frame);
+                    }
+                }
+
+                let mut frame = fetch()?;
+                self.store(ticker, start, end, &mut frame)?;
+
+                Ok(frame)
+            }
+        }
+    }
+
+    /// Remove every cached frame of a ticker, returning how many were removed.
+    pub fn invalidate(&self, ticker: &str) -> Result<usize, RustQuantError> {
+        let ticker = sanitise(ticker);
+
+        self.remove_where(|name| {
+            // Tickers may contain `_`, but dates do not.
+            name.rsplitn(3, '_').nth(2) == Some(ticker.as_str())
+        })
+    }
+
+    /// Remove every cached frame, returning how many were removed.
+    pub fn clear(&self) -> Result<usize, RustQuantError> {
+        self.remove_where(|_| true)
+    }
+
+    fn remove_where<P>(&self, predicate: P) -> Result<usize, RustQuantError>
+    where
+        P: Fn(&str) -> bool,
+    {
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+
+            if path.extension().and_then(|extension| extension.to_str()) != Some("parquet") {
+                continue;
+            }
+
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                if predicate(name) {
+                    std::fs::remove_file(&path)?;
+                    removed += 1;
+                }
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Ticker with the characters that are not safe in file names replaced,
+/// e.g. `^GSPC` becomes `_GSPC` and `EURUSD=X` becomes `EURUSD_X`.
+fn sanitise(ticker: &str) -> String {
+    ticker
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cache {
+    use super::*;
+    use std::cell::Cell;
+    use time::macros::date;
+
+    const START: Date = date!(2024 - 01 - 01);
+    const END: Date = date!(2024 - 06 - 30);
+
+    fn cache(name: &str) -> MarketDataCache {
+        let dir =
+            std::env::temp_dir().join(format!("rustquant_cache_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        MarketDataCache::new(dir)
+    }
+
+    fn frame(close: f64) -> DataFrame {
+        df!("close" => [close, close + 1.0]).unwrap()
+    }
+
+    #[test]
+    fn test_read_write_and_refresh() {
+        let cache = cache("read_write");
+        let downloads = Cell::new(0);
+        let fetch = |close| {
+            downloads.set(downloads.get() + 1);
+            Ok(frame(close))
+        };
+
+        let first = cache
+            .get_or_fetch("AAPL", START, END, || fetch(1.0))
+            .unwrap();
+        let second = cache
+            .get_or_fetch("AAPL", START, END, || fetch(2.0))
+            .unwrap();
+        assert_eq!(downloads.get(), 1);
+        assert!(first.equals(&second));
+
+        // Another date range is another entry.
+        cache
+            .get_or_fetch("AAPL", START, START, || fetch(3.0))
+            .unwrap();
+        assert_eq!(downloads.get(), 2);
+
+        let refresh = cache.clone().with_mode(CacheMode::Refresh);
+        refresh
+            .get_or_fetch("AAPL", START, END, || fetch(4.0))
+            .unwrap();
+        let cached = cache.load("AAPL", START, END).unwrap().unwrap();
+        assert!(cached.equals(&frame(4.0)));
+
+        let disabled = cache.clone().with_mode(CacheMode::Disabled);
+        disabled
+            .get_or_fetch("AAPL", START, END, || fetch(5.0))
+            .unwrap();
+        assert!(cache
+            .load("AAPL", START, END)
+            .unwrap()
+            .unwrap()
+            .equals(&frame(4.0)));
+        assert_eq!(downloads.get(), 4);
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_offline_and_max_age() {
+        let cache = cache("offline");
+        let offline = cache.clone().with_mode(CacheMode::Offline);
+        let unreachable = || -> Result<DataFrame, RustQuantError> { panic!("downloaded") };
+
+        assert!(offline
+            .get_or_fetch("MSFT", START, END, unreachable)
+            .is_err());
+
+        cache.store("MSFT", START, END, &mut frame(1.0)).unwrap();
+        assert!(offline
+            .get_or_fetch("MSFT", START, END, unreachable)
+            .unwrap()
+            .equals(&frame(1.0)));
+
+        // Stale data is downloaded again, except offline.
+        let stale = cache.clone().with_max_age(std::time::Duration::ZERO);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert!(stale.load("MSFT", START, END).unwrap().is_none());
+        assert!(stale
+            .with_mode(CacheMode::Offline)
+            .load("MSFT", START, END)
+            .unwrap()
+            .is_some());
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalidate_and_clear() {
+        let cache = cache("invalidate");
+
+        for ticker in ["BRK", "BRK_B", "^GSPC"] {
+            cache.store(ticker, START, END, &mut frame(1.0)).unwrap();
+        }
+        cache.store("BRK", START, START, &mut frame(1.0)).unwrap();
+
+        assert!(cache
+            .path("^GSPC", START, END)
+            .ends_with("_GSPC_2024-01-01_2024-06-30.parquet"));
+
+        assert_eq!(cache.invalidate("BRK").unwrap(), 2);
+        assert!(cache.load("BRK_B", START, END).unwrap().is_some());
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert_eq!(cache.clear().unwrap(), 0);
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+}
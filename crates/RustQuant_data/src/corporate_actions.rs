@@ -0,0 +1,260 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Corporate actions (dividends and splits) and back-adjustment of prices.
+//!
+//! Back-adjustment rescales every price before an action so that the
+//! series has no jump on the ex-date, and the latest prices are unchanged:
+//!
+//! - a split of `ratio` new shares per old share divides the earlier prices
+//!   by `ratio`, and multiplies the earlier volumes by `ratio`;
+//! - a dividend of `amount` multiplies the earlier prices by
+//!   `1 - amount / close`, where `close` is the last close before the
+//!   ex-date.
+//!
+//! Dividend amounts are per share outstanding on the ex-date, in the same
+//! units as the unadjusted prices of that date.
+
+use crate::{Bar, PriceSeries};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Cash dividend.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dividend {
+    /// Ex-dividend date.
+    pub date: Date,
+
+    /// Dividend per share.
+    pub amount: f64,
+}
+
+/// Stock split (a reverse split has a ratio below one).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Split {
+    /// Date from which the shares trade split.
+    pub date: Date,
+
+    /// New shares per old share, e.g. 4 for a 4-for-1 split.
+    pub ratio: f64,
+}
+
+/// Dividend and split history of a security.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CorporateActions {
+    /// Dividends, in date order.
+    pub dividends: Vec<Dividend>,
+
+    /// Splits, in date order.
+    pub splits: Vec<Split>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CorporateActions {
+    /// Create a history from dividends and splits in any order.
+    pub fn new(mut dividends: Vec<Dividend>, mut splits: Vec<Split>) -> Self {
+        dividends.sort_by_key(|dividend| dividend.date);
+        splits.sort_by_key(|split| split.date);
+
+        Self { dividends, splits }
+    }
+
+    /// Whether there are no dividends and no splits.
+    pub fn is_empty(&self) -> bool {
+        self.dividends.is_empty() && self.splits.is_empty()
+    }
+
+    /// Actions from `start` to `end` inclusive.
+    pub fn between(&self, start: Date, end: Date) -> Self {
+        let within = |date: Date| date >= start && date <= end;
+
+        Self {
+            dividends: self
+                .dividends
+                .iter()
+                .filter(|dividend| within(dividend.date))
+                .copied()
+                .collect(),
+            splits: self
+                .splits
+                .iter()
+                .filter(|split| within(split.date))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Price adjustment factor of each bar of a series, for splits and
+    /// (optionally) dividends. The factors of the bars on or after the
+    /// last action are one.
+    pub fn adjustment_factors(&self, series: &PriceSeries, dividends: bool) -> Vec<f64> {
+        self.factors(series.bars(), dividends)
+            .into_iter()
+            .map(|(price, _)| price)
+            .collect()
+    }
+
+    /// Series with prices adjusted for splits and dividends,
+    /// and volumes adjusted for splits.
+    ///
+    /// All prices (including `adjusted`) of the result are the adjusted
+    /// closes, so returns computed from it are total returns.
+    pub fn back_adjust(&self, series: &PriceSeries) -> PriceSeries {
+        self.adjust(series, true)
+    }
+
+    /// Series with prices and volumes adjusted for splits only.
+    ///
+    /// The `adjusted` column is left as it was.
+    pub fn split_adjust(&self, series: &PriceSeries) -> PriceSeries {
+        self.adjust(series, false)
+    }
+
+    fn adjust(&self, series: &PriceSeries, dividends: bool) -> PriceSeries {
+        let bars = series.bars();
+        let factors = self.factors(bars, dividends);
+
+        bars.iter()
+            .zip(factors)
+            .map(|(bar, (price, volume))| Bar {
+                date: bar.date,
+                open: bar.open * price,
+                high: bar.high * price,
+                low: bar.low * price,
+                close: bar.close * price,
+                volume: bar.volume * volume,
+                adjusted: if dividends {
+                    bar.close * price
+                } else {
+                    bar.adjusted
+                },
+            })
+            .collect::<Vec<Bar>>()
+            .into()
+    }
+
+    /// Price and volume factors of each bar, accumulated backwards in time.
+    fn factors(&self, bars: &[Bar], dividends: bool) -> Vec<(f64, f64)> {
+        let mut factors = vec![(1.0, 1.0); bars.len()];
+
+        let mut splits = self.splits.iter().rev().peekable();
+        let mut payments = self.dividends.iter().rev().peekable();
+        let (mut price, mut volume) = (1.0, 1.0);
+
+        for (i, bar) in bars.iter().enumerate().rev() {
+            // Every action after this bar applies to it, and `bar` is the
+            // last bar before the actions that were not applied yet.
+            while let Some(split) = splits.next_if(|split| split.date > bar.date) {
+                price /= split.ratio;
+                volume *= split.ratio;
+            }
+
+            while let Some(dividend) = payments.next_if(|dividend| dividend.date > bar.date) {
+                if dividends && bar.close > 0.0 {
+                    price *= 1.0 - dividend.amount / bar.close;
+                }
+            }
+
+            factors[i] = (price, volume);
+        }
+
+        factors
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_corporate_actions {
+    use super::*;
+    use crate::ReturnsType;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn bar(date: Date, close: f64, volume: f64) -> Bar {
+        Bar {
+            date,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+            adjusted: close,
+        }
+    }
+
+    /// A 2-for-1 split on the 3rd, and a dividend of 1 going ex on the 5th.
+    fn series() -> PriceSeries {
+        PriceSeries::new(vec![
+            bar(date!(2024 - 06 - 01), 200.0, 10.0),
+            bar(date!(2024 - 06 - 02), 202.0, 10.0),
+            bar(date!(2024 - 06 - 03), 101.0, 20.0),
+            bar(date!(2024 - 06 - 04), 100.0, 20.0),
+            bar(date!(2024 - 06 - 05), 99.0, 20.0),
+        ])
+    }
+
+    fn actions() -> CorporateActions {
+        CorporateActions::new(
+            vec![Dividend {
+                date: date!(2024 - 06 - 05),
+                amount: 1.0,
+            }],
+            vec![Split {
+                date: date!(2024 - 06 - 03),
+                ratio: 2.0,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_split_adjust() {
+        let adjusted = actions().split_adjust(&series());
+
+        assert_eq!(adjusted.closes(), vec![100.0, 101.0, 101.0, 100.0, 99.0]);
+        assert!(adjusted.bars().iter().all(|bar| bar.volume == 20.0));
+    }
+
+    #[test]
+    fn test_back_adjust() {
+        let series = series();
+        let adjusted = actions().back_adjust(&series);
+        let factors = actions().adjustment_factors(&series, true);
+
+        // The dividend is 1% of the last close before the ex-date.
+        assert_approx_equal!(factors[3], 0.99, 1e-12);
+        assert_approx_equal!(factors[0], 0.495, 1e-12);
+        assert_eq!(factors[4], 1.0);
+
+        // Total returns: no jump at the split, and the dividend is reinvested.
+        let returns = adjusted.returns(ReturnsType::Arithmetic);
+        assert_approx_equal!(returns[1].1, 101.0 / 202.0 * 2.0 - 1.0, 1e-12);
+        assert_approx_equal!(returns[3].1, (99.0 + 1.0) / 100.0 - 1.0, 1e-12);
+        assert_eq!(adjusted.adjusted(), adjusted.closes());
+    }
+
+    #[test]
+    fn test_no_actions() {
+        let series = series();
+
+        assert!(CorporateActions::default().is_empty());
+        assert_eq!(CorporateActions::default().back_adjust(&series), series);
+        assert!(actions()
+            .between(date!(2024 - 07 - 01), date!(2024 - 12 - 31))
+            .is_empty());
+    }
+}
@@ -0,0 +1,399 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Key-rate and bucketed curve sensitivities.
+//!
+//! Any curve-dependent position is given as a pricer, a function from a
+//! curve to a value, and its rate risk is found by repricing on bumped
+//! curves (central differences, reported per basis point):
+//!
+//! - [key_rate_dv01] shifts the continuously compounded zero curve by a
+//!   triangle around each key date, flat beyond the first and last keys, so
+//!   that the buckets add up to the parallel DV01.
+//! - [quote_dv01] shifts each quote of a [CurveBootstrapper] in turn and
+//!   rebootstraps, giving the risk to the curve's hedge instruments.
+//! - [zero_rate_jacobian] is the change in the key zero rates per change in
+//!   each quote, which maps key-rate risk to quote risk with
+//!   [quote_risk_from_key_rates].
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Compounding, CurveBootstrapper, RateCurve, SharedRateCurve};
+use std::sync::Arc;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_time::DayCountConvention;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// One basis point.
+pub const BASIS_POINT: f64 = 1e-4;
+
+/// Sensitivity of a position to one point of a curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveBucket {
+    /// Key date, or maturity of the bumped instrument.
+    pub date: Date,
+
+    /// Change in value for a one basis point increase.
+    pub dv01: f64,
+}
+
+/// A curve with its continuously compounded zero rates shifted by a
+/// triangle peaking at one key date and falling to zero at the neighbouring
+/// keys. The first and last triangles extend flat to either end of the curve.
+#[derive(Clone)]
+pub struct KeyRateShiftedCurve {
+    base: SharedRateCurve,
+    key_dates: Vec<Date>,
+    key: usize,
+    shift: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl KeyRateShiftedCurve {
+    /// Shift `base` by `shift` at `key_dates[key]`.
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if the key dates are not strictly
+    ///   increasing, or `key` is not one of them.
+    pub fn new(
+        base: SharedRateCurve,
+        key_dates: &[Date],
+        key: usize,
+        shift: f64,
+    ) -> Result<Self, RustQuantError> {
+        if key >= key_dates.len() || key_dates.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Key {} must index strictly increasing key dates ({} given).",
+                key,
+                key_dates.len()
+            )));
+        }
+
+        Ok(Self {
+            base,
+            key_dates: key_dates.to_vec(),
+            key,
+            shift,
+        })
+    }
+
+    /// Fraction of the shift applied at `date`, linear in time between keys.
+    pub fn weight(&self, date: Date) -> f64 {
+        let time = |date: Date| self.year_fraction(date);
+        let (t, k) = (time(date), time(self.key_dates[self.key]));
+
+        if t <= k {
+            match self.key.checked_sub(1).map(|i| time(self.key_dates[i])) {
+                None => 1.0,
+                Some(previous) => ((t - previous) / (k - previous)).max(0.0),
+            }
+        } else {
+            match self.key_dates.get(self.key + 1).map(|d| time(*d)) {
+                None => 1.0,
+                Some(next) => ((next - t) / (next - k)).max(0.0),
+            }
+        }
+    }
+
+    fn year_fraction(&self, date: Date) -> f64 {
+        self.base
+            .day_count_convention()
+            .day_count_factor(self.base.valuation_date(), date)
+    }
+}
+
+impl RateCurve for KeyRateShiftedCurve {
+    fn valuation_date(&self) -> Date {
+        self.base.valuation_date()
+    }
+
+    fn day_count_convention(&self) -> DayCountConvention {
+        self.base.day_count_convention()
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        let t = self.year_fraction(date);
+
+        self.base.discount_factor(date) * (-self.shift * self.weight(date) * t).exp()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Key-rate DV01s of a position, one per key date.
+///
+/// # Arguments
+///
+/// * `curve` - The curve the position is priced on.
+/// * `key_dates` - Strictly increasing key dates.
+/// * `bump` - Size of the zero rate shift, e.g. [BASIS_POINT].
+/// * `pricer` - Value of the position on a curve.
+pub fn key_rate_dv01<F>(
+    curve: &SharedRateCurve,
+    key_dates: &[Date],
+    bump: f64,
+    pricer: F,
+) -> Result<Vec<CurveBucket>, RustQuantError>
+where
+    F: Fn(&SharedRateCurve) -> Result<f64, RustQuantError>,
+{
+    (0..key_dates.len())
+        .map(|key| {
+            let shifted = |shift: f64| -> Result<SharedRateCurve, RustQuantError> {
+                Ok(Arc::new(KeyRateShiftedCurve::new(
+                    curve.clone(),
+                    key_dates,
+                    key,
+                    shift,
+                )?))
+            };
+
+            let up = pricer(&shifted(bump)?)?;
+            let down = pricer(&shifted(-bump)?)?;
+
+            Ok(CurveBucket {
+                date: key_dates[key],
+                dv01: (up - down) / (2.0 * bump) * BASIS_POINT,
+            })
+        })
+        .collect()
+}
+
+/// DV01s of a position to each quote of a bootstrapped curve, dated by the
+/// instruments' maturities.
+///
+/// # Arguments
+///
+/// * `bootstrapper` - The curve's quotes.
+/// * `bump` - Size of the quote shift, e.g. [BASIS_POINT].
+/// * `pricer` - Value of the position on a curve.
+pub fn quote_dv01<F>(
+    bootstrapper: &CurveBootstrapper,
+    bump: f64,
+    pricer: F,
+) -> Result<Vec<CurveBucket>, RustQuantError>
+where
+    F: Fn(&SharedRateCurve) -> Result<f64, RustQuantError>,
+{
+    (0..bootstrapper.instruments.len())
+        .map(|i| {
+            let rebootstrapped = |shift: f64| -> Result<SharedRateCurve, RustQuantError> {
+                Ok(Arc::new(shifted_quote(bootstrapper, i, shift).bootstrap()?))
+            };
+
+            let up = pricer(&rebootstrapped(bump)?)?;
+            let down = pricer(&rebootstrapped(-bump)?)?;
+
+            Ok(CurveBucket {
+                date: bootstrapper.instruments[i].maturity(),
+                dv01: (up - down) / (2.0 * bump) * BASIS_POINT,
+            })
+        })
+        .collect()
+}
+
+/// Jacobian of the key zero rates with respect to the quotes.
+///
+/// Entry `[i][j]` is the change in the continuously compounded zero rate at
+/// `key_dates[j]` per unit change in the quote of instrument `i`, with zero
+/// rates in the curve's own day count.
+pub fn zero_rate_jacobian(
+    bootstrapper: &CurveBootstrapper,
+    key_dates: &[Date],
+    bump: f64,
+) -> Result<Vec<Vec<f64>>, RustQuantError> {
+    let zero_rates = |bootstrapper: &CurveBootstrapper| -> Result<Vec<f64>, RustQuantError> {
+        let curve = bootstrapper.bootstrap()?;
+
+        Ok(key_dates
+            .iter()
+            .map(|date| curve.zero_rate(*date, Compounding::Continuous, curve.day_count_convention))
+            .collect())
+    };
+
+    (0..bootstrapper.instruments.len())
+        .map(|i| {
+            let up = zero_rates(&shifted_quote(bootstrapper, i, bump))?;
+            let down = zero_rates(&shifted_quote(bootstrapper, i, -bump))?;
+
+            Ok(up
+                .iter()
+                .zip(down)
+                .map(|(up, down)| (up - down) / (2.0 * bump))
+                .collect())
+        })
+        .collect()
+}
+
+/// Quote DV01s from key-rate DV01s, by the chain rule through the
+/// [zero_rate_jacobian] for the same key dates.
+///
+/// # Panics
+///
+/// Panics if a row of the Jacobian is shorter than `key_rates`.
+pub fn quote_risk_from_key_rates(jacobian: &[Vec<f64>], key_rates: &[CurveBucket]) -> Vec<f64> {
+    jacobian
+        .iter()
+        .map(|row| {
+            key_rates
+                .iter()
+                .enumerate()
+                .map(|(j, bucket)| row[j] * bucket.dv01)
+                .sum()
+        })
+        .collect()
+}
+
+/// The bootstrapper with the quote of instrument `i` shifted.
+fn shifted_quote(bootstrapper: &CurveBootstrapper, i: usize, shift: f64) -> CurveBootstrapper {
+    let mut shifted = bootstrapper.clone();
+    shifted.instruments[i] = shifted.instruments[i].shifted(shift);
+    shifted
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_curve_sensitivities {
+    use super::*;
+    use crate::{BootstrapInterpolation, CurveInstrument};
+    use time::macros::date;
+    use RustQuant_time::Frequency;
+    use RustQuant_utils::assert_approx_equal;
+
+    const TODAY: Date = date!(2024 - 01 - 02);
+
+    fn bootstrapper() -> CurveBootstrapper {
+        let swap = |years: i32, rate: f64| CurveInstrument::Swap {
+            maturity: TODAY.replace_year(2024 + years).unwrap(),
+            rate,
+            fixed_frequency: Frequency::Annually,
+        };
+
+        CurveBootstrapper::new(TODAY)
+            .with_interpolation(BootstrapInterpolation::ZeroRate)
+            .with_instruments(&[
+                CurveInstrument::Deposit {
+                    maturity: date!(2024 - 07 - 02),
+                    rate: 0.050,
+                },
+                swap(2, 0.045),
+                swap(5, 0.040),
+                swap(10, 0.042),
+            ])
+    }
+
+    /// Fixed rate bond paying annual coupons, per unit notional.
+    fn bond(curve: &SharedRateCurve) -> Result<f64, RustQuantError> {
+        let coupons: f64 = (1..=7)
+            .map(|year| 0.045 * curve.discount_factor(TODAY.replace_year(2024 + year).unwrap()))
+            .sum();
+
+        Ok(coupons + curve.discount_factor(date!(2031 - 01 - 02)))
+    }
+
+    #[test]
+    fn test_key_rates_add_up_to_parallel() {
+        let curve: SharedRateCurve = Arc::new(bootstrapper().bootstrap().unwrap());
+        let keys = [
+            date!(2025 - 01 - 02),
+            date!(2027 - 01 - 02),
+            date!(2029 - 01 - 02),
+            date!(2034 - 01 - 02),
+        ];
+
+        let buckets = key_rate_dv01(&curve, &keys, BASIS_POINT, bond).unwrap();
+        let parallel = key_rate_dv01(&curve, &keys[..1], BASIS_POINT, bond).unwrap();
+        let total: f64 = buckets.iter().map(|bucket| bucket.dv01).sum();
+
+        assert_approx_equal!(total, parallel[0].dv01, 1e-10);
+        assert!(buckets.iter().all(|bucket| bucket.dv01 < 0.0));
+
+        // A seven year bond is mostly exposed to the five and ten year keys.
+        assert!(buckets[2].dv01.abs() > buckets[0].dv01.abs());
+
+        // Roughly -duration x price x 1bp.
+        assert!(total < -4e-4 && total > -8e-4);
+    }
+
+    #[test]
+    fn test_quote_dv01() {
+        let bootstrapper = bootstrapper();
+
+        // A deposit booked at the quoted rate only depends on the deposit quote.
+        let deposit = |curve: &SharedRateCurve| {
+            let maturity = date!(2024 - 07 - 02);
+            let tau = curve
+                .day_count_convention()
+                .day_count_factor(TODAY, maturity);
+            Ok((1.0 + 0.05 * tau) * curve.discount_factor(maturity))
+        };
+
+        let buckets = quote_dv01(&bootstrapper, BASIS_POINT, deposit).unwrap();
+
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(buckets[0].date, date!(2024 - 07 - 02));
+        assert!(buckets[0].dv01 < 0.0);
+        for bucket in &buckets[1..] {
+            assert_approx_equal!(bucket.dv01, 0.0, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_jacobian_maps_key_rates_to_quotes() {
+        let bootstrapper = bootstrapper();
+        let curve: SharedRateCurve = Arc::new(bootstrapper.bootstrap().unwrap());
+        let keys = bootstrapper
+            .instruments
+            .iter()
+            .map(|instrument| instrument.maturity())
+            .collect::<Vec<_>>();
+
+        let jacobian = zero_rate_jacobian(&bootstrapper, &keys, BASIS_POINT).unwrap();
+        let key_rates = key_rate_dv01(&curve, &keys, BASIS_POINT, bond).unwrap();
+        let quotes = quote_dv01(&bootstrapper, BASIS_POINT, bond).unwrap();
+
+        // Linear zero rate interpolation matches the key-rate triangles.
+        for (mapped, bucket) in quote_risk_from_key_rates(&jacobian, &key_rates)
+            .iter()
+            .zip(&quotes)
+        {
+            assert_approx_equal!(*mapped, bucket.dv01, 1e-9);
+        }
+
+        // The deposit quote moves the short zero rate about one for one,
+        // and longer quotes do not move it at all.
+        assert!(jacobian[0][0] > 0.9 && jacobian[0][0] < 1.1);
+        assert_approx_equal!(jacobian[3][0], 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let curve: SharedRateCurve = Arc::new(bootstrapper().bootstrap().unwrap());
+        let keys = [date!(2027 - 01 - 02), date!(2025 - 01 - 02)];
+
+        assert!(KeyRateShiftedCurve::new(curve.clone(), &keys, 0, BASIS_POINT).is_err());
+        assert!(key_rate_dv01(&curve, &keys[..1], BASIS_POINT, bond).is_ok());
+        assert!(KeyRateShiftedCurve::new(curve, &keys[..1], 1, BASIS_POINT).is_err());
+    }
+}
@@ -25,6 +25,10 @@
 //! fitting takes place until the user requests a rate for a date that is not
 //! present in the curve. At that point, the curve is fit to the model and the
 //! rate is interpolated.
+//!
+//! The base [`Curve`] instead interpolates directly between its nodes, with
+//! a [`CurveInterpolation`] method and a [`CurveExtrapolation`] policy chosen
+//! per curve.
 
 use argmin::{
     core::{CostFunction, Executor, State},
@@ -34,8 +38,12 @@ use derive_builder::Builder;
 use plotly::{color::NamedColor, common::Marker, common::Mode, Plot, Scatter};
 use std::{collections::BTreeMap, hash::Hash, iter::zip};
 use time::Date;
+use RustQuant_error::RustQuantError;
 use RustQuant_math::{
-    interpolation::{ExponentialInterpolator, Interpolator, LinearInterpolator},
+    interpolation::{
+        AkimaInterpolator, CubicSplineInterpolator, ExponentialInterpolator, Interpolator,
+        LinearInterpolator, MonotoneConvexInterpolator,
+    },
     InterpolationIndex,
 };
 use RustQuant_stochastics::{CurveModel, NelsonSiegelSvensson};
@@ -49,14 +57,72 @@ use RustQuant_time::{Calendar, DateRollingConvention, DayCountConvention};
 pub trait CurveIndex: Ord + Hash + InterpolationIndex + Clone + Copy {}
 impl<T> CurveIndex for T where T: Ord + Hash + InterpolationIndex + Clone + Copy {}
 
+/// Interpolation method between the nodes of a [`Curve`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveInterpolation {
+    /// Linear in the values.
+    #[default]
+    Linear,
+
+    /// Linear in the logarithm of the values, e.g. for discount factors.
+    LogLinear,
+
+    /// Natural cubic spline through the values.
+    CubicSpline,
+
+    /// Akima cubic through the values, less prone to oscillate than a spline.
+    Akima,
+
+    /// Hagan-West monotone convex, for zero rates from the curve's origin
+    /// (see [`Curve::with_origin`]), with Actual/365 Fixed year fractions.
+    MonotoneConvex,
+}
+
+/// What a [`Curve`] returns outside of its first and last nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveExtrapolation {
+    /// The value of the nearest end node.
+    #[default]
+    Flat,
+
+    /// Continue the line through the two nearest end nodes
+    /// (in log space for [`CurveInterpolation::LogLinear`]).
+    Linear,
+
+    /// Return `RustQuantError::OutsideOfRange`.
+    Error,
+}
+
 /// Curve data structure.
 #[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Curve<C>
 where
     C: CurveIndex,
 {
     /// The nodes of the curve.
     pub nodes: BTreeMap<C, f64>,
+
+    /// Interpolation method between the nodes.
+    pub interpolation: CurveInterpolation,
+
+    /// Extrapolation policy outside the nodes.
+    pub extrapolation: CurveExtrapolation,
+
+    /// Time origin (e.g. the valuation date) of zero rate curves.
+    pub origin: Option<C>,
+}
+
+/// Fit an interpolator and evaluate it at a point.
+fn fit_and_interpolate<I, T>(mut interpolator: T, index: I) -> Result<f64, RustQuantError>
+where
+    T: Interpolator<I, f64>,
+    I: InterpolationIndex<DeltaDiv = f64>,
+{
+    interpolator.fit()?;
+    interpolator.interpolate(index)
 }
 
 macro_rules! impl_curve {
@@ -66,9 +132,31 @@ macro_rules! impl_curve {
             pub fn new() -> Self {
                 Self {
                     nodes: BTreeMap::new(),
+                    interpolation: CurveInterpolation::default(),
+                    extrapolation: CurveExtrapolation::default(),
+                    origin: None,
                 }
             }
 
+            /// Set the interpolation method.
+            pub fn with_interpolation(mut self, interpolation: CurveInterpolation) -> Self {
+                self.interpolation = interpolation;
+                self
+            }
+
+            /// Set the extrapolation policy.
+            pub fn with_extrapolation(mut self, extrapolation: CurveExtrapolation) -> Self {
+                self.extrapolation = extrapolation;
+                self
+            }
+
+            /// Set the time origin, e.g. the valuation date, of the zero rates
+            /// (needed by [`CurveInterpolation::MonotoneConvex`]).
+            pub fn with_origin(mut self, origin: $index) -> Self {
+                self.origin = Some(origin);
+                self
+            }
+
             /// Get the first key in the curve.
             pub fn first_key(&self) -> Option<&$index> {
                 self.nodes.keys().next()
@@ -186,37 +274,106 @@ macro_rules! impl_curve {
                 }
             }
 
-            /// Interpolate the curve at a specific index.
+            /// Interpolate the curve at a specific index, with the curve's
+            /// interpolation method between nodes and its extrapolation
+            /// policy outside of them.
             ///
-            /// Note: This method modifies the curve by adding the interpolated value.
-            pub fn interpolate(&mut self, index: $index) -> f64 {
-                if self.nodes.contains_key(&index) {
-                    return *self.nodes.get(&index).unwrap();
+            /// # Errors
+            /// - `RustQuantError::MissingInput` if the curve has no nodes, or
+            ///   no origin with [`CurveInterpolation::MonotoneConvex`].
+            /// - `RustQuantError::OutsideOfRange` outside the nodes with
+            ///   [`CurveExtrapolation::Error`].
+            pub fn interpolate(&self, index: $index) -> Result<f64, RustQuantError> {
+                if let Some(value) = self.nodes.get(&index) {
+                    return Ok(*value);
                 }
 
-                let xs: Vec<$index> = self.nodes.keys().cloned().collect();
-                let ys: Vec<f64> = self.nodes.values().cloned().collect();
+                let (first, last) = match (self.first_key(), self.last_key()) {
+                    (Some(first), Some(last)) => (*first, *last),
+                    _ => {
+                        return Err(RustQuantError::MissingInput(
+                            "Cannot interpolate a curve without nodes.".to_string(),
+                        ))
+                    }
+                };
 
-                let interpolator = LinearInterpolator::new(xs, ys).unwrap();
+                if index < first || index > last {
+                    return self.extrapolate(index, index < first);
+                }
 
-                self.insert(index, interpolator.interpolate(index).unwrap());
+                let (xs, ys) = (self.keys(), self.values());
 
-                *self.nodes.get(&index).unwrap()
+                match self.interpolation {
+                    CurveInterpolation::Linear => {
+                        fit_and_interpolate(LinearInterpolator::new(xs, ys)?, index)
+                    }
+                    CurveInterpolation::LogLinear => {
+                        fit_and_interpolate(ExponentialInterpolator::new(xs, ys)?, index)
+                    }
+                    CurveInterpolation::CubicSpline => {
+                        fit_and_interpolate(CubicSplineInterpolator::new(xs, ys)?, index)
+                    }
+                    CurveInterpolation::Akima => {
+                        fit_and_interpolate(AkimaInterpolator::new(xs, ys)?, index)
+                    }
+                    CurveInterpolation::MonotoneConvex => {
+                        let origin = self.origin.ok_or_else(|| {
+                            RustQuantError::MissingInput(
+                                "Monotone convex interpolation needs the curve's origin."
+                                    .to_string(),
+                            )
+                        })?;
+                        let year = time::Duration::days(365);
+
+                        fit_and_interpolate(
+                            MonotoneConvexInterpolator::new(xs, ys, origin, year)?,
+                            index,
+                        )
+                    }
+                }
             }
 
             /// Interpolate the curve at multiple indices.
             ///
             /// Note: This method modifies the curve by adding the interpolated values.
-            pub fn interpolate_many(&mut self, indices: &[$index]) {
-                let xs: Vec<$index> = self.nodes.keys().cloned().collect();
-                let ys: Vec<f64> = self.nodes.values().cloned().collect();
+            /// All values are computed from the existing nodes before any is added.
+            pub fn interpolate_many(&mut self, indices: &[$index]) -> Result<(), RustQuantError> {
+                let values = indices
+                    .iter()
+                    .map(|index| self.interpolate(*index))
+                    .collect::<Result<Vec<f64>, RustQuantError>>()?;
 
-                // let interpolator = LinearInterpolator::new(xs, ys).unwrap();
-                let interpolator = ExponentialInterpolator::new(xs, ys).unwrap();
+                for (index, value) in indices.iter().zip(values) {
+                    self.insert(*index, value);
+                }
 
-                for index in indices {
-                    if !self.nodes.contains_key(index) {
-                        self.insert(*index, interpolator.interpolate(*index).unwrap());
+                Ok(())
+            }
+
+            /// Value outside of the nodes, left or right of the curve.
+            fn extrapolate(&self, index: $index, left: bool) -> Result<f64, RustQuantError> {
+                let mut ends: Vec<($index, f64)> = if left {
+                    self.nodes.iter().take(2).map(|(x, y)| (*x, *y)).collect()
+                } else {
+                    self.nodes
+                        .iter()
+                        .rev()
+                        .take(2)
+                        .map(|(x, y)| (*x, *y))
+                        .collect()
+                };
+                let (x0, y0) = ends.remove(0);
+
+                match (self.extrapolation, ends.first()) {
+                    (CurveExtrapolation::Error, _) => Err(RustQuantError::OutsideOfRange),
+                    (CurveExtrapolation::Flat, _) | (CurveExtrapolation::Linear, None) => Ok(y0),
+                    (CurveExtrapolation::Linear, Some(&(x1, y1))) => {
+                        let fraction = (index - x0) / (x1 - x0);
+
+                        match self.interpolation {
+                            CurveInterpolation::LogLinear => Ok(y0 * (y1 / y0).powf(fraction)),
+                            _ => Ok(y0 + (y1 - y0) * fraction),
+                        }
                     }
                 }
             }
@@ -410,6 +567,7 @@ macro_rules! impl_specific_curve {
                     calendar: None,
                     day_count_convention: None,
                     date_rolling_convention: None,
+                    valuation_date: None,
                     nss: NelsonSiegelSvensson::default(),
                     fitted: false,
                     fitted_curve: None,
@@ -546,6 +704,10 @@ where
     /// Date rolling convention.
     pub date_rolling_convention: Option<DateRollingConvention>,
 
+    /// Valuation date (defaults to the first date of the curve).
+    #[builder(default)]
+    pub valuation_date: Option<Date>,
+
     /// Nelson-Siegel-Svensson parameters.
     /// Backend for fitting the curve to interpolate missing rates.
     #[builder(default)]
@@ -586,6 +748,10 @@ where
     /// Date rolling convention.
     pub date_rolling_convention: Option<DateRollingConvention>,
 
+    /// Valuation date (defaults to the first date of the curve).
+    #[builder(default)]
+    pub valuation_date: Option<Date>,
+
     /// Nelson-Siegel-Svensson parameters.
     /// Backend for fitting the curve to interpolate missing rates.
     #[builder(default)]
@@ -603,6 +769,9 @@ where
 impl_specific_curve_cost_function!(SpotCurve, spot_rate);
 impl_specific_curve!(SpotCurve, spot_rate);
 
+/// Zero curve, an alias of [`SpotCurve`]: spot (zero coupon) rates by date.
+pub type ZeroCurve<I, C> = SpotCurve<I, C>;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // FORWARD CURVE
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -626,6 +795,10 @@ where
     /// Date rolling convention.
     pub date_rolling_convention: Option<DateRollingConvention>,
 
+    /// Valuation date (defaults to the first date of the curve).
+    #[builder(default)]
+    pub valuation_date: Option<Date>,
+
     /// Nelson-Siegel-Svensson parameters.
     /// Backend for fitting the curve to interpolate missing rates.
     #[builder(default)]
@@ -825,6 +998,117 @@ where
 // Unit tests
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+#[cfg(test)]
+mod tests_curve_interpolation {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn curve() -> Curve<Date> {
+        let dates = [
+            date!(2024 - 01 - 01),
+            date!(2025 - 01 - 01),
+            date!(2026 - 01 - 01),
+            date!(2027 - 01 - 01),
+        ];
+
+        Curve::<Date>::new_from_slice(&dates, &[0.0, 0.03, 0.04, 0.045])
+    }
+
+    #[test]
+    fn test_curve_interpolation_methods() {
+        let mid = date!(2025 - 07 - 02);
+        let fraction = 182.0 / 365.0;
+
+        let linear = curve().interpolate(mid).unwrap();
+        assert_approx_equal!(linear, 0.03 + 0.01 * fraction, 1e-12);
+
+        let log_linear = curve()
+            .with_interpolation(CurveInterpolation::LogLinear)
+            .interpolate(mid)
+            .unwrap();
+        assert_approx_equal!(log_linear, 0.03 * (0.04_f64 / 0.03).powf(fraction), 1e-12);
+
+        for interpolation in [
+            CurveInterpolation::CubicSpline,
+            CurveInterpolation::Akima,
+            CurveInterpolation::MonotoneConvex,
+        ] {
+            let curve = curve()
+                .with_interpolation(interpolation)
+                .with_origin(date!(2024 - 01 - 01));
+
+            assert_approx_equal!(
+                curve.interpolate(date!(2026 - 01 - 01)).unwrap(),
+                0.04,
+                1e-12
+            );
+            assert!(curve.interpolate(mid).unwrap() > linear);
+        }
+    }
+
+    #[test]
+    fn test_curve_monotone_convex_origin() {
+        // Zero rates from a valuation date a year before the first node.
+        let curve = Curve::<Date>::new_from_slice(
+            &[date!(2025 - 01 - 01), date!(2026 - 01 - 01)],
+            &[0.02, 0.03],
+        )
+        .with_interpolation(CurveInterpolation::MonotoneConvex);
+
+        assert!(matches!(
+            curve.interpolate(date!(2025 - 07 - 02)),
+            Err(RustQuantError::MissingInput(_))
+        ));
+
+        let curve = curve.with_origin(date!(2024 - 01 - 01));
+        let t_1 = 366.0 / 365.0;
+        let t = t_1 + 182.0 / 365.0;
+
+        // The forward from the first node is near the 4% discrete forward
+        // (dipping towards the 3% node forward), not the 3% of a curve
+        // starting at the first node.
+        let zero = curve.interpolate(date!(2025 - 07 - 02)).unwrap();
+        let forward = (zero * t - 0.02 * t_1) / (t - t_1);
+        assert!(forward > 0.035 && forward < 0.04);
+    }
+
+    #[test]
+    fn test_curve_extrapolation_policies() {
+        let after = date!(2028 - 01 - 01);
+        let before = date!(2023 - 01 - 01);
+
+        assert_approx_equal!(curve().interpolate(after).unwrap(), 0.045, 1e-12);
+        assert_approx_equal!(curve().interpolate(before).unwrap(), 0.0, 1e-12);
+
+        let linear = curve().with_extrapolation(CurveExtrapolation::Linear);
+        assert_approx_equal!(linear.interpolate(after).unwrap(), 0.05, 1e-12);
+
+        let strict = curve().with_extrapolation(CurveExtrapolation::Error);
+        assert!(matches!(
+            strict.interpolate(after),
+            Err(RustQuantError::OutsideOfRange)
+        ));
+        assert!(Curve::<Date>::new().interpolate(after).is_err());
+    }
+
+    #[test]
+    fn test_curve_interpolate_many() {
+        let mut curve = curve();
+        let dates = [date!(2025 - 07 - 02), date!(2028 - 01 - 01)];
+
+        curve.interpolate_many(&dates).unwrap();
+
+        assert_eq!(curve.len(), 6);
+        assert_approx_equal!(
+            *curve.get(dates[0]).unwrap(),
+            0.03 + 0.01 * 182.0 / 365.0,
+            1e-12
+        );
+        assert_approx_equal!(*curve.get(dates[1]).unwrap(), 0.045, 1e-12);
+    }
+}
+
 #[cfg(test)]
 mod tests_curves {
     // use super::*;
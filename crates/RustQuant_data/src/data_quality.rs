@@ -0,0 +1,640 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Validation and cleaning of price series and curves.
+//!
+//! [`DataQuality`] checks data for:
+//!
+//! - missing dates: business days of a calendar without a bar;
+//! - stale prices: runs of bars with the same close;
+//! - outliers: log returns (or, for curves, node-to-node changes) whose
+//!   z-score or MAD score exceeds a threshold;
+//! - invalid values: negative (and, for prices, zero), infinite or NaN.
+//!
+//! and repairs the flagged data by dropping it, carrying the last good
+//! value forward, or interpolating linearly in time between good values.
+
+use crate::{Bar, Curve, PriceSeries};
+use std::collections::BTreeSet;
+use time::Date;
+use RustQuant_time::Calendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Problem found in a price series or a curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataIssue {
+    /// Business day without a bar.
+    MissingDate(Date),
+
+    /// Bar with the same close as the previous one, in a run of at least
+    /// the stale threshold. The first bar of the run is not flagged.
+    StalePrice(Date),
+
+    /// Value whose move from the previous value is an outlier.
+    Outlier {
+        /// Date of the value.
+        date: Date,
+
+        /// Outlier score of the move.
+        score: f64,
+    },
+
+    /// Negative (or zero, for prices), infinite or NaN value.
+    InvalidValue {
+        /// Date of the value.
+        date: Date,
+
+        /// The value.
+        value: f64,
+    },
+}
+
+/// How outliers are scored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutlierMethod {
+    /// Distance from the mean, in standard deviations.
+    ZScore(f64),
+
+    /// Modified z-score of Iglewicz and Hoaglin: distance from the median,
+    /// in median absolute deviations scaled to standard deviations.
+    /// Robust to the outliers themselves.
+    Mad(f64),
+}
+
+/// How flagged data is repaired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairStrategy {
+    /// Remove the flagged values (missing dates are left missing).
+    Drop,
+
+    /// Replace flagged and missing values by the last good value.
+    #[default]
+    CarryForward,
+
+    /// Replace flagged and missing values by linear interpolation in time
+    /// between the surrounding good values.
+    Interpolate,
+}
+
+/// Data quality checks and repairs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DataQuality {
+    /// Minimum number of bars with the same close flagged as stale.
+    pub stale_threshold: usize,
+
+    /// How outliers are scored, with the score above which a move is an outlier.
+    pub outlier_method: OutlierMethod,
+
+    /// Whether negative curve values are valid (e.g. rates, but not discount factors).
+    pub allow_negative: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DataIssue {
+    /// Date of the issue.
+    pub fn date(&self) -> Date {
+        match self {
+            Self::MissingDate(date) | Self::StalePrice(date) => *date,
+            Self::Outlier { date, .. } | Self::InvalidValue { date, .. } => *date,
+        }
+    }
+}
+
+impl Default for DataQuality {
+    fn default() -> Self {
+        Self {
+            stale_threshold: 5,
+            outlier_method: OutlierMethod::Mad(5.0),
+            allow_negative: false,
+        }
+    }
+}
+
+impl DataQuality {
+    /// Checks with the default settings: runs of five identical closes are
+    /// stale, outliers have a MAD score above five, and negative values are invalid.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flag runs of at least `bars` identical closes.
+    pub fn with_stale_threshold(mut self, bars: usize) -> Self {
+        self.stale_threshold = bars;
+        self
+    }
+
+    /// Score outliers with another method or threshold.
+    pub fn with_outlier_method(mut self, method: OutlierMethod) -> Self {
+        self.outlier_method = method;
+        self
+    }
+
+    /// Accept negative curve values.
+    pub fn with_negative_values(mut self, allow: bool) -> Self {
+        self.allow_negative = allow;
+        self
+    }
+
+    /// Issues of a price series, in date order. Missing dates are the
+    /// business days of `calendar` between the first and last bars.
+    pub fn check_series<C: Calendar>(&self, series: &PriceSeries, calendar: &C) -> Vec<DataIssue> {
+        let bars = series.bars();
+        let mut issues = Vec::new();
+
+        if let (Some(first), Some(last)) = (bars.first(), bars.last()) {
+            let dates = bars.iter().map(|bar| bar.date).collect::<BTreeSet<Date>>();
+
+            issues.extend(
+                calendar
+                    .all_business_days_between(first.date, last.date)
+                    .into_iter()
+                    .filter(|date| !dates.contains(date))
+                    .map(DataIssue::MissingDate),
+            );
+        }
+
+        let invalid = |bar: &Bar| {
+            [bar.open, bar.high, bar.low, bar.close, bar.adjusted]
+                .into_iter()
+                .find(|price| !(price.is_finite() && *price > 0.0))
+        };
+
+        let mut valid = Vec::with_capacity(bars.len());
+
+        for bar in bars {
+            match invalid(bar) {
+                Some(value) => issues.push(DataIssue::InvalidValue {
+                    date: bar.date,
+                    value,
+                }),
+                None => valid.push(*bar),
+            }
+        }
+
+        // Stale runs, among the valid bars.
+        let mut start = 0;
+        for end in 1..=valid.len() {
+            if end == valid.len() || valid[end].close != valid[start].close {
+                if end - start >= self.stale_threshold.max(2) {
+                    issues.extend(
+                        valid[start + 1..end]
+                            .iter()
+                            .map(|bar| DataIssue::StalePrice(bar.date)),
+                    );
+                }
+                start = end;
+            }
+        }
+
+        let moves = valid
+            .windows(2)
+            .map(|pair| (pair[1].date, (pair[1].adjusted / pair[0].adjusted).ln()))
+            .collect::<Vec<(Date, f64)>>();
+        issues.extend(self.outliers(&moves));
+
+        issues.sort_by_key(DataIssue::date);
+        issues
+    }
+
+    /// Issues of a curve (invalid values and outlying node-to-node changes),
+    /// in date order.
+    pub fn check_curve(&self, curve: &Curve<Date>) -> Vec<DataIssue> {
+        let mut issues = Vec::new();
+        let mut valid = Vec::with_capacity(curve.len());
+
+        for (date, value) in curve.keys().into_iter().zip(curve.values()) {
+            if value.is_finite() && (self.allow_negative || value >= 0.0) {
+                valid.push((date, value));
+            } else {
+                issues.push(DataIssue::InvalidValue { date, value });
+            }
+        }
+
+        let moves = valid
+            .windows(2)
+            .map(|pair| (pair[1].0, pair[1].1 - pair[0].1))
+            .collect::<Vec<(Date, f64)>>();
+        issues.extend(self.outliers(&moves));
+
+        issues.sort_by_key(DataIssue::date);
+        issues
+    }
+
+    /// Repair the issues of a price series.
+    ///
+    /// Repaired bars keep their volume; inserted bars have no volume.
+    pub fn repair_series(
+        &self,
+        series: &PriceSeries,
+        issues: &[DataIssue],
+        strategy: RepairStrategy,
+    ) -> PriceSeries {
+        let (flagged, missing) = split_issues(issues);
+
+        let good = series
+            .bars()
+            .iter()
+            .filter(|bar| !flagged.contains(&bar.date))
+            .copied()
+            .collect::<Vec<Bar>>();
+
+        if strategy == RepairStrategy::Drop {
+            return PriceSeries::new(good);
+        }
+
+        let dates = series
+            .dates()
+            .into_iter()
+            .chain(missing.iter().copied())
+            .collect::<BTreeSet<Date>>();
+
+        let bars = dates
+            .into_iter()
+            .filter_map(|date| {
+                let volume = series
+                    .bars()
+                    .iter()
+                    .find(|bar| bar.date == date)
+                    .map_or(0.0, |bar| bar.volume);
+
+                if let Some(bar) = good.iter().find(|bar| bar.date == date) {
+                    return Some(*bar);
+                }
+
+                let i = good.partition_point(|bar| bar.date < date);
+                let (before, after) = (i.checked_sub(1).map(|j| &good[j]), good.get(i));
+
+                let value = |f: fn(&Bar) -> f64| {
+                    fill(
+                        before.map(|bar| (bar.date, f(bar))),
+                        after.map(|bar| (bar.date, f(bar))),
+                        date,
+                        strategy,
+                    )
+                };
+
+                Some(Bar {
+                    date,
+                    open: value(|bar| bar.open)?,
+                    high: value(|bar| bar.high)?,
+                    low: value(|bar| bar.low)?,
+                    close: value(|bar| bar.close)?,
+                    volume,
+                    adjusted: value(|bar| bar.adjusted)?,
+                })
+            })
+            .collect();
+
+        PriceSeries::new(bars)
+    }
+
+    /// Repair the issues of a curve. Missing dates do not apply to curves.
+    pub fn repair_curve(
+        &self,
+        curve: &Curve<Date>,
+        issues: &[DataIssue],
+        strategy: RepairStrategy,
+    ) -> Curve<Date> {
+        let (flagged, _) = split_issues(issues);
+
+        let good = curve
+            .keys()
+            .into_iter()
+            .zip(curve.values())
+            .filter(|(date, _)| !flagged.contains(date))
+            .collect::<Vec<(Date, f64)>>();
+
+        let mut repaired = Curve::<Date>::new()
+            .with_interpolation(curve.interpolation)
+            .with_extrapolation(curve.extrapolation);
+
+        for date in curve.keys() {
+            let i = good.partition_point(|(key, _)| *key < date);
+
+            let value = match good.get(i) {
+                Some((key, value)) if *key == date => Some(*value),
+                _ if strategy == RepairStrategy::Drop => None,
+                after => fill(
+                    i.checked_sub(1).map(|j| good[j]),
+                    after.copied(),
+                    date,
+                    strategy,
+                ),
+            };
+
+            if let Some(value) = value {
+                repaired.insert(date, value);
+            }
+        }
+
+        repaired
+    }
+
+    /// Moves whose score exceeds the threshold.
+    fn outliers(&self, moves: &[(Date, f64)]) -> Vec<DataIssue> {
+        let values = moves.iter().map(|(_, value)| *value).collect::<Vec<f64>>();
+
+        let (scores, threshold) = match self.outlier_method {
+            OutlierMethod::ZScore(threshold) => (z_scores(&values), threshold),
+            OutlierMethod::Mad(threshold) => (mad_scores(&values), threshold),
+        };
+
+        moves
+            .iter()
+            .zip(scores)
+            .filter(|(_, score)| score.abs() > threshold)
+            .map(|((date, _), score)| DataIssue::Outlier { date: *date, score })
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Dates of the flagged values, and the missing dates.
+fn split_issues(issues: &[DataIssue]) -> (BTreeSet<Date>, BTreeSet<Date>) {
+    let mut flagged = BTreeSet::new();
+    let mut missing = BTreeSet::new();
+
+    for issue in issues {
+        match issue {
+            DataIssue::MissingDate(date) => missing.insert(*date),
+            _ => flagged.insert(issue.date()),
+        };
+    }
+
+    (flagged, missing)
+}
+
+/// Replacement value at `date` from the good values around it. Either
+/// strategy falls back on the one neighbour there is at the ends.
+fn fill(
+    before: Option<(Date, f64)>,
+    after: Option<(Date, f64)>,
+    date: Date,
+    strategy: RepairStrategy,
+) -> Option<f64> {
+    match (before, after, strategy) {
+        (Some((start, x0)), Some((end, x1)), RepairStrategy::Interpolate) => {
+            let weight = (date - start).whole_days() as f64 / (end - start).whole_days() as f64;
+            Some(x0 + weight * (x1 - x0))
+        }
+        (Some((_, x0)), _, _) => Some(x0),
+        (None, Some((_, x1)), _) => Some(x1),
+        (None, None, _) => None,
+    }
+}
+
+fn z_scores(values: &[f64]) -> Vec<f64> {
+    let n = values.len() as f64;
+
+    if values.len() < 2 {
+        return vec![0.0; values.len()];
+    }
+
+    let mean = values.iter().sum::<f64>() / n;
+    let std = (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+    values
+        .iter()
+        .map(|x| if std > 0.0 { (x - mean) / std } else { 0.0 })
+        .collect()
+}
+
+/// Modified z-scores, `0.6745 (x - median) / MAD`. When more than half the
+/// values are equal (MAD of zero), the mean absolute deviation is used instead.
+fn mad_scores(values: &[f64]) -> Vec<f64> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    let median = |values: &mut Vec<f64>| {
+        values.sort_by(f64::total_cmp);
+        let n = values.len();
+
+        if n % 2 == 1 {
+            values[n / 2]
+        } else {
+            0.5 * (values[n / 2 - 1] + values[n / 2])
+        }
+    };
+
+    let centre = median(&mut values.to_vec());
+    let mut deviations = values
+        .iter()
+        .map(|x| (x - centre).abs())
+        .collect::<Vec<f64>>();
+    let mean_deviation = deviations.iter().sum::<f64>() / deviations.len() as f64;
+    let mad = median(&mut deviations);
+
+    let scale = if mad > 0.0 {
+        mad / 0.6745
+    } else {
+        1.253314 * mean_deviation
+    };
+
+    values
+        .iter()
+        .map(|x| {
+            if scale > 0.0 {
+                (x - centre) / scale
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_data_quality {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_time::UnitedStatesCalendar;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn bar(date: Date, close: f64) -> Bar {
+        Bar {
+            date,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1000.0,
+            adjusted: close,
+        }
+    }
+
+    /// Business days of January 2024 (New Year's Day and MLK Day are holidays),
+    /// with a gap on the 10th, a bad tick on the 18th, a negative price on
+    /// the 24th, and a stale run from the 26th.
+    fn series() -> PriceSeries {
+        let calendar = UnitedStatesCalendar;
+        let mut bars = Vec::new();
+
+        for (i, date) in calendar
+            .all_business_days_between(date!(2024 - 01 - 02), date!(2024 - 01 - 31))
+            .into_iter()
+            .enumerate()
+        {
+            let close = match date.day() {
+                10 => continue,
+                18 => 150.0,
+                24 => -1.0,
+                26..=31 => 120.0,
+                _ if i % 2 == 0 => 100.5,
+                _ => 99.5,
+            };
+
+            bars.push(bar(date, close));
+        }
+
+        PriceSeries::new(bars)
+    }
+
+    #[test]
+    fn test_check_series() {
+        let issues = DataQuality::new().check_series(&series(), &UnitedStatesCalendar);
+
+        assert!(issues.contains(&DataIssue::MissingDate(date!(2024 - 01 - 10))));
+        assert!(!issues.contains(&DataIssue::MissingDate(date!(2024 - 01 - 15))));
+        assert!(issues.contains(&DataIssue::InvalidValue {
+            date: date!(2024 - 01 - 24),
+            value: -1.0
+        }));
+        assert!(issues
+            .iter()
+            .any(|issue| matches!(issue, DataIssue::Outlier { date, .. } if *date == date!(2024 - 01 - 18))));
+
+        // The run 26th, 29th, 30th, 31st is four bars long.
+        assert!(!issues
+            .iter()
+            .any(|issue| matches!(issue, DataIssue::StalePrice(_))));
+
+        let stale = DataQuality::new()
+            .with_stale_threshold(4)
+            .check_series(&series(), &UnitedStatesCalendar)
+            .into_iter()
+            .filter(|issue| matches!(issue, DataIssue::StalePrice(_)))
+            .count();
+        assert_eq!(stale, 3);
+
+        assert!(issues
+            .windows(2)
+            .all(|pair| pair[0].date() <= pair[1].date()));
+    }
+
+    #[test]
+    fn test_repair_series() {
+        let series = series();
+        let quality = DataQuality::new();
+        let issues = vec![
+            DataIssue::MissingDate(date!(2024 - 01 - 10)),
+            DataIssue::Outlier {
+                date: date!(2024 - 01 - 18),
+                score: 10.0,
+            },
+            DataIssue::InvalidValue {
+                date: date!(2024 - 01 - 24),
+                value: -1.0,
+            },
+        ];
+
+        let dropped = quality.repair_series(&series, &issues, RepairStrategy::Drop);
+        assert_eq!(dropped.len(), series.len() - 2);
+
+        let carried = quality.repair_series(&series, &issues, RepairStrategy::CarryForward);
+        let close = |s: &PriceSeries, date: Date| {
+            s.bars().iter().find(|bar| bar.date == date).unwrap().close
+        };
+        assert_eq!(carried.len(), series.len() + 1);
+        assert_eq!(
+            close(&carried, date!(2024 - 01 - 10)),
+            close(&series, date!(2024 - 01 - 09))
+        );
+        assert_eq!(
+            close(&carried, date!(2024 - 01 - 24)),
+            close(&series, date!(2024 - 01 - 23))
+        );
+
+        let interpolated = quality.repair_series(&series, &issues, RepairStrategy::Interpolate);
+        let expected = close(&series, date!(2024 - 01 - 23))
+            + (close(&series, date!(2024 - 01 - 25)) - close(&series, date!(2024 - 01 - 23))) / 2.0;
+        assert_approx_equal!(close(&interpolated, date!(2024 - 01 - 24)), expected, 1e-12);
+
+        let inserted = interpolated
+            .bars()
+            .iter()
+            .find(|bar| bar.date == date!(2024 - 01 - 10))
+            .unwrap();
+        assert_eq!(inserted.volume, 0.0);
+    }
+
+    #[test]
+    fn test_curve() {
+        let dates = [
+            date!(2025 - 01 - 02),
+            date!(2026 - 01 - 02),
+            date!(2027 - 01 - 02),
+            date!(2028 - 01 - 02),
+            date!(2029 - 01 - 02),
+            date!(2030 - 01 - 02),
+            date!(2031 - 01 - 02),
+        ];
+        let curve =
+            Curve::<Date>::new_from_slice(&dates, &[0.97, 0.94, 0.91, -0.5, 0.85, 0.82, 0.79]);
+
+        let issues = DataQuality::new()
+            .with_outlier_method(OutlierMethod::ZScore(3.0))
+            .check_curve(&curve);
+        assert_eq!(
+            issues,
+            vec![DataIssue::InvalidValue {
+                date: dates[3],
+                value: -0.5
+            }]
+        );
+        assert!(DataQuality::new()
+            .with_negative_values(true)
+            .check_curve(&curve)
+            .iter()
+            .all(|issue| !matches!(issue, DataIssue::InvalidValue { .. })));
+
+        let repaired =
+            DataQuality::new().repair_curve(&curve, &issues, RepairStrategy::Interpolate);
+        assert_approx_equal!(*repaired.get(dates[3]).unwrap(), 0.88, 1e-3);
+        assert_eq!(
+            DataQuality::new()
+                .repair_curve(&curve, &issues, RepairStrategy::Drop)
+                .len(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_scores() {
+        let values = [0.01, -0.01, 0.02, -0.02, 0.0, 0.5];
+
+        let mad = mad_scores(&values);
+        assert!(mad[5] > 5.0 && mad[..5].iter().all(|score| score.abs() < 2.0));
+
+        assert!(z_scores(&values)[5] > 2.0);
+        assert_eq!(mad_scores(&[1.0, 1.0, 1.0]), vec![0.0; 3]);
+    }
+}
@@ -0,0 +1,275 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Fixings store for floating rate resets.
+//!
+//! Floating instruments that are part-way through an accrual period
+//! (seasoned swaps, FRNs) need the rates that have already been published.
+//! These are held by each [InterestRateIndex], and the `FixingStore`
+//! resolves each reset as either a historical fixing (with the index's
+//! [FixingInterpolation](RustQuant_instruments::FixingInterpolation)) or a
+//! forecast, depending on the valuation date.
+
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::InterestRateIndex;
+use RustQuant_time::Calendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// What to do when a past reset has no fixing in the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingFixingPolicy {
+    /// Return an error.
+    #[default]
+    Error,
+
+    /// Fall back to the forecast (estimated) rate.
+    Estimate,
+}
+
+/// Resolver of resets against the fixing history of the indices.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixingStore {
+    /// Policy for past resets that have no fixing.
+    pub missing_fixing_policy: MissingFixingPolicy,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl FixingStore {
+    /// Create a new fixing store.
+    pub fn new(missing_fixing_policy: MissingFixingPolicy) -> Self {
+        Self {
+            missing_fixing_policy,
+        }
+    }
+
+    /// Published (or interpolated) fixing of an index, or an error if there is none.
+    pub fn fixing<C: Calendar>(
+        &self,
+        index: &InterestRateIndex<C>,
+        date: Date,
+    ) -> Result<f64, RustQuantError> {
+        index.fixing(date).ok_or_else(|| {
+            RustQuantError::MissingInput(format!("No {} fixing for {}.", index.name, date))
+        })
+    }
+
+    /// Rate for a single reset, as seen on the valuation date.
+    ///
+    /// - Resets before the valuation date must have a fixing in the index's
+    ///   history, after its interpolation rule; if not, the
+    ///   [MissingFixingPolicy] decides between an error and the forecast.
+    /// - A reset on the valuation date uses the fixing if already published,
+    ///   otherwise the forecast.
+    /// - Future resets use the forecast.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index.
+    /// * `fixing_date` - The fixing date of the reset.
+    /// * `valuation_date` - The valuation date.
+    /// * `forecast` - Estimated fixing for a date (e.g. from a projection curve).
+    pub fn reset_rate<C, F>(
+        &self,
+        index: &InterestRateIndex<C>,
+        fixing_date: Date,
+        valuation_date: Date,
+        forecast: F,
+    ) -> Result<f64, RustQuantError>
+    where
+        C: Calendar,
+        F: Fn(Date) -> Result<f64, RustQuantError>,
+    {
+        if fixing_date > valuation_date {
+            return forecast(fixing_date);
+        }
+
+        // Today's fixing is not interpolated from earlier ones.
+        if fixing_date == valuation_date {
+            return match index.fixings.get(&fixing_date) {
+                Some(rate) => Ok(*rate),
+                None => forecast(fixing_date),
+            };
+        }
+
+        match (index.fixing(fixing_date), self.missing_fixing_policy) {
+            (Some(rate), _) => Ok(rate),
+            (None, MissingFixingPolicy::Estimate) => forecast(fixing_date),
+            (None, MissingFixingPolicy::Error) => self.fixing(index, fixing_date),
+        }
+    }
+
+    /// Compounded (in arrears) overnight rate over an accrual period,
+    /// as used by SOFR/SONIA/ESTR swap coupons.
+    ///
+    /// Fixings already published are used for the elapsed part of the
+    /// period and the forecast for the remainder, so that coupons
+    /// part-way through their accrual period are valued correctly.
+    ///
+    /// $$ R = \frac{1}{\tau} \left( \prod_i (1 + r_i \delta_i) - 1 \right) $$
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The overnight index.
+    /// * `accrual_start` - Start of the accrual period.
+    /// * `accrual_end` - End of the accrual period.
+    /// * `valuation_date` - The valuation date.
+    /// * `forecast` - Estimated fixing for a date (e.g. from a projection curve).
+    pub fn compounded_rate<C, F>(
+        &self,
+        index: &InterestRateIndex<C>,
+        accrual_start: Date,
+        accrual_end: Date,
+        valuation_date: Date,
+        forecast: F,
+    ) -> Result<f64, RustQuantError>
+    where
+        C: Calendar,
+        F: Fn(Date) -> Result<f64, RustQuantError>,
+    {
+        if accrual_start >= accrual_end {
+            return Err(RustQuantError::InvalidArgument(
+                "Accrual start must be before accrual end.".to_string(),
+            ));
+        }
+
+        let mut growth = 1.0;
+        let mut date = accrual_start;
+
+        while !index.is_valid_fixing_date(date) {
+            date = date.next_day().unwrap();
+        }
+
+        while date < accrual_end {
+            let next = index.maturity_date(date).min(accrual_end);
+            let rate = self.reset_rate(index, date, valuation_date, &forecast)?;
+
+            growth *= 1.0 + rate * index.year_fraction(date, next);
+            date = next;
+        }
+
+        Ok((growth - 1.0) / index.year_fraction(accrual_start, accrual_end))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_fixings {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_instruments::FixingInterpolation;
+    use RustQuant_time::Tenor;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_reset_rate() {
+        let mut euribor = InterestRateIndex::euribor(Tenor::months(3));
+        euribor.add_fixing(date!(2024 - 03 - 01), 0.039);
+
+        let mut store = FixingStore::new(MissingFixingPolicy::Error);
+        let valuation = date!(2024 - 04 - 15);
+        let forecast = |_| Ok(0.035);
+
+        // Past reset with a fixing.
+        let rate = store.reset_rate(&euribor, date!(2024 - 03 - 01), valuation, forecast);
+        assert_eq!(rate.unwrap(), 0.039);
+
+        // Past reset without a fixing.
+        let rate = store.reset_rate(&euribor, date!(2024 - 02 - 01), valuation, forecast);
+        assert!(rate.is_err());
+
+        // Reset on the valuation date, not yet published.
+        let rate = store.reset_rate(&euribor, valuation, valuation, forecast);
+        assert_eq!(rate.unwrap(), 0.035);
+
+        // Future reset.
+        let rate = store.reset_rate(&euribor, date!(2024 - 06 - 03), valuation, forecast);
+        assert_eq!(rate.unwrap(), 0.035);
+
+        // Estimation fallback.
+        store.missing_fixing_policy = MissingFixingPolicy::Estimate;
+        let rate = store.reset_rate(&euribor, date!(2024 - 02 - 01), valuation, forecast);
+        assert_eq!(rate.unwrap(), 0.035);
+    }
+
+    #[test]
+    fn test_reset_rate_interpolation() {
+        let mut sonia = InterestRateIndex::sonia();
+        sonia.add_fixing(date!(2024 - 03 - 01), 0.0519);
+
+        let store = FixingStore::default();
+        let valuation = date!(2024 - 03 - 04);
+
+        // Saturday takes Friday's fixing, as the index rolls to the previous one.
+        let rate = store.reset_rate(&sonia, date!(2024 - 03 - 02), valuation, |_| Ok(0.05));
+        assert_eq!(rate.unwrap(), 0.0519);
+
+        // Today's fixing is not published yet.
+        let rate = store.reset_rate(&sonia, valuation, valuation, |_| Ok(0.05));
+        assert_eq!(rate.unwrap(), 0.05);
+
+        let exact = sonia.with_fixing_interpolation(FixingInterpolation::Exact);
+        assert!(store.fixing(&exact, date!(2024 - 03 - 02)).is_err());
+    }
+
+    #[test]
+    fn test_compounded_rate_seasoned() {
+        let mut sofr = InterestRateIndex::sofr();
+        let store = FixingStore::new(MissingFixingPolicy::Error);
+
+        // Monday to Thursday fixings, valuing on Friday 8 March 2024.
+        sofr.add_fixings([
+            (date!(2024 - 03 - 04), 0.0531),
+            (date!(2024 - 03 - 05), 0.0532),
+            (date!(2024 - 03 - 06), 0.0531),
+            (date!(2024 - 03 - 07), 0.0531),
+        ]);
+
+        let rate = store
+            .compounded_rate(
+                &sofr,
+                date!(2024 - 03 - 04),
+                date!(2024 - 03 - 11),
+                date!(2024 - 03 - 08),
+                |_| Ok(0.0530),
+            )
+            .unwrap();
+
+        // Friday's forecast accrues over the weekend (3 days).
+        let growth = (1.0 + 0.0531 / 360.0)
+            * (1.0 + 0.0532 / 360.0)
+            * (1.0 + 0.0531 / 360.0)
+            * (1.0 + 0.0531 / 360.0)
+            * (1.0 + 0.0530 * 3.0 / 360.0);
+
+        assert_approx_equal!(rate, (growth - 1.0) * 360.0 / 7.0, EPS);
+
+        // Missing Tuesday fixing is an error, unless the index rolls to the previous one.
+        sofr.fixings.remove(&date!(2024 - 03 - 05));
+        let sofr = sofr.with_fixing_interpolation(FixingInterpolation::Exact);
+        assert!(store
+            .compounded_rate(
+                &sofr,
+                date!(2024 - 03 - 04),
+                date!(2024 - 03 - 11),
+                date!(2024 - 03 - 08),
+                |_| Ok(0.0530),
+            )
+            .is_err());
+    }
+}
@@ -12,7 +12,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use polars::prelude::*;
-
+use time::{Date, Month};
 use RustQuant_error::RustQuantError;
 
 /// Data struct.
@@ -151,6 +151,31 @@ impl DataScanner for Data {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parse a `YYYY-MM-DD` date.
+pub fn parse_date(text: &str) -> Result<Date, RustQuantError> {
+    let invalid = || RustQuantError::InvalidArgument(format!("Invalid date: {}.", text));
+
+    let mut parts = text.trim().splitn(3, '-');
+    let mut next = || parts.next().and_then(|part| part.parse::<i32>().ok());
+
+    let (year, month, day) = match (next(), next(), next()) {
+        (Some(year), Some(month), Some(day)) => (year, month, day),
+        _ => return Err(invalid()),
+    };
+
+    let month = u8::try_from(month)
+        .ok()
+        .and_then(|month| Month::try_from(month).ok())
+        .ok_or_else(invalid)?;
+    let day = u8::try_from(day).map_err(|_| invalid())?;
+
+    Date::from_calendar_date(year, month, day).map_err(|_| invalid())
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -113,6 +113,48 @@ pub use io::*;
 pub mod yahoo;
 pub use yahoo::*;
 
+/// Local cache of downloaded market data.
+pub mod cache;
+pub use cache::*;
+
+/// Typed OHLCV bars and price series.
+pub mod price_series;
+pub use price_series::*;
+
+/// ARIMA time series models and forecasts.
+pub mod arima;
+pub use arima::*;
+
+/// Dividends, splits and back-adjusted prices.
+pub mod corporate_actions;
+pub use corporate_actions::*;
+
+/// Listed option chains.
+pub mod option_chain;
+pub use option_chain::*;
+
+/// Validation and cleaning of price series and curves.
+pub mod data_quality;
+pub use data_quality::*;
+
+/// Streaming trades and quotes over websockets.
+#[cfg(feature = "live")]
+pub mod live_feed;
+#[cfg(feature = "live")]
+pub use live_feed::*;
+
+/// Websocket client used by the live feeds.
+#[cfg(feature = "live")]
+mod websocket;
+
+/// Market data providers with a common schema (Alpha Vantage, Polygon.io, FRED).
+pub mod providers;
+pub use providers::*;
+
+/// Downloaders for official risk-free curves (US Treasury, ECB AAA).
+pub mod official_curves;
+pub use official_curves::*;
+
 /// Base curve data structure and implementations.
 /// Curves (in the financial sense) are functions that map
 /// a time to a value, such as a yield curve or a swap curve.
@@ -120,16 +162,68 @@ pub use yahoo::*;
 pub mod curves;
 pub use curves::*;
 
+/// Discount, zero and forward rates from the typed curves.
+pub mod rate_curves;
+pub use rate_curves::*;
+
+/// Discount curve bootstrapping from deposits, FRAs, futures and swaps.
+pub mod bootstrap;
+pub use bootstrap::*;
+
 /// Market data structures and implementations.
 pub mod market_data;
 pub use market_data::*;
 
+/// Discount and projection curves for dual-curve pricing.
+pub mod market_context;
+pub use market_context::*;
+
+/// Key-rate and quote sensitivities of curve-dependent positions.
+pub mod curve_sensitivities;
+pub use curve_sensitivities::*;
+
 /// Context data structures and implementations.
 pub mod context_data;
 pub use context_data::*;
 
-// /// Base surface data structure and implementations.
-// /// Surfaces are simply [Curve]s with an additional dimension.
-// /// For example, a volatility surface is a function of time and strike/moneyness.
-// pub mod surface;
-// pub use surface::*;
+/// Historical fixings for floating rate resets.
+pub mod fixings;
+pub use fixings::*;
+
+/// Implied volatility surfaces (pillar grid and SVI).
+pub mod volatility_surface;
+pub use volatility_surface::*;
+
+/// Vega bucketing against volatility surface parameters.
+pub mod vega_buckets;
+pub use vega_buckets::*;
+
+/// Incremental volatility surface updates from live quotes.
+pub mod live_surface;
+pub use live_surface::*;
+
+/// Dupire local volatility and the local volatility process.
+pub mod local_volatility;
+pub use local_volatility::*;
+
+/// Compressed archive of historical daily curves.
+pub mod curve_archive;
+pub use curve_archive::*;
+
+/// Fitting short rate model drifts to a discount curve.
+pub mod short_rate_calibration;
+pub use short_rate_calibration::*;
+
+/// Seeded synthetic market data for examples and tests.
+pub mod synthetic;
+pub use synthetic::*;
+
+/// Instrument identifiers and a registry of shared market objects.
+pub mod registry;
+pub use registry::*;
+
+/// Base surface data structure and implementations.
+/// Surfaces are simply [Curve]s with an additional dimension.
+/// For example, a volatility surface is a function of time and strike/moneyness.
+pub mod surfaces;
+pub use surfaces::*;
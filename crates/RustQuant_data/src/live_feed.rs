@@ -0,0 +1,381 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Streaming market data.
+//!
+//! A [`LiveFeed`] pushes [`MarketEvent`]s (trades and top-of-book quotes)
+//! into a Tokio channel for as long as the receiving end is alive, so order
+//! books and strategies consume live data the same way whichever venue it
+//! comes from. The feed runs on the caller's runtime:
+//!
+//! ```no_run
+//! use RustQuant_data::{BinanceFeed, LiveFeed, MarketEvent};
+//!
+//! # async fn run() -> Result<(), RustQuant_error::RustQuantError> {
+//! let (sender, mut receiver) = tokio::sync::mpsc::channel(1024);
+//! let feed = BinanceFeed::new();
+//!
+//! # let consume = async move {
+//! // Typically in a task of its own.
+//! while let Some(event) = receiver.recv().await {
+//!     if let MarketEvent::Quote(quote) = event {
+//!         println!("{} {:.2}", quote.symbol, quote.mid());
+//!     }
+//! }
+//! # };
+//! feed.stream(&["btcusdt", "ethusdt"], sender).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::providers::field;
+use crate::websocket::{Message, WebSocket};
+use serde_json::Value;
+use std::future::Future;
+use time::OffsetDateTime;
+use tokio::sync::mpsc::Sender;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Source of streaming trades and quotes.
+pub trait LiveFeed {
+    /// Name of the venue.
+    fn name(&self) -> &str;
+
+    /// Stream the events of `symbols` into `sender`.
+    ///
+    /// Completes when the receiver is dropped or the venue closes the
+    /// connection, and fails if the connection cannot be established or
+    /// breaks. Reconnecting is left to the caller.
+    fn stream(
+        &self,
+        symbols: &[&str],
+        sender: Sender<MarketEvent>,
+    ) -> impl Future<Output = Result<(), RustQuantError>> + Send;
+}
+
+/// Executed trade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    /// Symbol, as the venue reports it.
+    pub symbol: String,
+
+    /// Trade price.
+    pub price: f64,
+
+    /// Traded quantity.
+    pub quantity: f64,
+
+    /// Whether the buyer initiated the trade (lifted the offer).
+    pub buyer_initiated: bool,
+
+    /// Trade time.
+    pub timestamp: OffsetDateTime,
+}
+
+/// Best bid and offer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    /// Symbol, as the venue reports it.
+    pub symbol: String,
+
+    /// Best bid price.
+    pub bid: f64,
+
+    /// Quantity at the best bid.
+    pub bid_size: f64,
+
+    /// Best ask price.
+    pub ask: f64,
+
+    /// Quantity at the best ask.
+    pub ask_size: f64,
+
+    /// Quote time (the time of receipt if the venue does not send one).
+    pub timestamp: OffsetDateTime,
+}
+
+/// Event delivered by a [`LiveFeed`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketEvent {
+    /// A trade.
+    Trade(Trade),
+
+    /// A change of the best bid or offer.
+    Quote(Quote),
+}
+
+/// Binance spot market streams: trades and best bid/offer (`bookTicker`).
+///
+/// Market data streams are public, so no API key is needed.
+#[derive(Debug, Clone)]
+pub struct BinanceFeed {
+    /// Websocket endpoint, e.g. `wss://stream.binance.com:9443`.
+    pub base_url: String,
+
+    /// Subscribe to trades.
+    pub trades: bool,
+
+    /// Subscribe to best bid/offer updates.
+    pub quotes: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarketEvent {
+    /// Symbol the event is for.
+    pub fn symbol(&self) -> &str {
+        match self {
+            Self::Trade(trade) => &trade.symbol,
+            Self::Quote(quote) => &quote.symbol,
+        }
+    }
+
+    /// Time of the event.
+    pub fn timestamp(&self) -> OffsetDateTime {
+        match self {
+            Self::Trade(trade) => trade.timestamp,
+            Self::Quote(quote) => quote.timestamp,
+        }
+    }
+}
+
+impl Quote {
+    /// Mid price.
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.bid + self.ask)
+    }
+
+    /// Bid-ask spread.
+    pub fn spread(&self) -> f64 {
+        self.ask - self.bid
+    }
+}
+
+impl Default for BinanceFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinanceFeed {
+    /// Trades and quotes from the Binance spot endpoint.
+    pub fn new() -> Self {
+        Self {
+            base_url: "wss://stream.binance.com:9443".to_string(),
+            trades: true,
+            quotes: true,
+        }
+    }
+
+    /// Use another endpoint, e.g. the testnet or a local relay.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Whether to subscribe to trades.
+    pub fn with_trades(mut self, trades: bool) -> Self {
+        self.trades = trades;
+        self
+    }
+
+    /// Whether to subscribe to best bid/offer updates.
+    pub fn with_quotes(mut self, quotes: bool) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    /// Combined stream URL for the symbols.
+    pub fn url(&self, symbols: &[&str]) -> Result<String, RustQuantError> {
+        let kinds = [(self.trades, "trade"), (self.quotes, "bookTicker")];
+
+        let streams = symbols
+            .iter()
+            .flat_map(|symbol| {
+                kinds
+                    .iter()
+                    .filter(|(enabled, _)| *enabled)
+                    .map(move |(_, kind)| format!("{}@{}", symbol.to_lowercase(), kind))
+            })
+            .collect::<Vec<_>>();
+
+        if streams.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "No symbols or no stream kinds to subscribe to.".to_string(),
+            ));
+        }
+
+        Ok(format!(
+            "{}/stream?streams={}",
+            self.base_url,
+            streams.join("/")
+        ))
+    }
+}
+
+impl LiveFeed for BinanceFeed {
+    fn name(&self) -> &str {
+        "Binance"
+    }
+
+    async fn stream(
+        &self,
+        symbols: &[&str],
+        sender: Sender<MarketEvent>,
+    ) -> Result<(), RustQuantError> {
+        let mut socket = WebSocket::connect(&self.url(symbols)?).await?;
+
+        while let Some(message) = socket.recv().await? {
+            let Message::Text(text) = message else {
+                continue;
+            };
+
+            if let Some(event) = parse_binance_message(&text)? {
+                if sender.send(event).await.is_err() {
+                    socket.close().await.ok();
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parse a Binance stream message (combined or raw).
+///
+/// Messages that are not trades or book tickers, such as subscription
+/// acknowledgements, give `None`.
+fn parse_binance_message(text: &str) -> Result<Option<MarketEvent>, RustQuantError> {
+    let message: Value = serde_json::from_str(text)?;
+
+    // Combined streams wrap the payload: {"stream": "...", "data": {...}}.
+    let data = message.get("data").unwrap_or(&message);
+
+    let symbol = match data.get("s").and_then(Value::as_str) {
+        Some(symbol) => symbol.to_string(),
+        None => return Ok(None),
+    };
+
+    let millis = |key: &str| {
+        data.get(key).and_then(Value::as_i64).and_then(|ms| {
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(ms) * 1_000_000).ok()
+        })
+    };
+
+    match data.get("e").and_then(Value::as_str) {
+        Some("trade") => Ok(Some(MarketEvent::Trade(Trade {
+            symbol,
+            price: field(data, "p")?,
+            quantity: field(data, "q")?,
+            // "m": the buyer was the maker, so the seller initiated.
+            buyer_initiated: !data.get("m").and_then(Value::as_bool).unwrap_or(false),
+            timestamp: millis("T")
+                .or_else(|| millis("E"))
+                .unwrap_or_else(OffsetDateTime::now_utc),
+        }))),
+
+        // Spot book tickers carry no event type (futures ones do).
+        None | Some("bookTicker") if data.get("b").is_some() => {
+            Ok(Some(MarketEvent::Quote(Quote {
+                symbol,
+                bid: field(data, "b")?,
+                bid_size: field(data, "B")?,
+                ask: field(data, "a")?,
+                ask_size: field(data, "A")?,
+                timestamp: millis("T")
+                    .or_else(|| millis("E"))
+                    .unwrap_or_else(OffsetDateTime::now_utc),
+            })))
+        }
+
+        _ => Ok(None),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_live_feed {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_binance_url() {
+        let feed = BinanceFeed::new().with_base_url("wss://example.com/");
+
+        assert_eq!(
+            feed.url(&["BTCUSDT", "ethusdt"]).unwrap(),
+            "wss://example.com/stream?streams=\
+             btcusdt@trade/btcusdt@bookTicker/ethusdt@trade/ethusdt@bookTicker"
+        );
+        assert_eq!(
+            feed.clone().with_trades(false).url(&["btcusdt"]).unwrap(),
+            "wss://example.com/stream?streams=btcusdt@bookTicker"
+        );
+        assert!(feed.url(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_binance_trade() {
+        let text = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1718000000123,
+            "s":"BTCUSDT","t":12345,"p":"67000.50","q":"0.015","T":1718000000120,
+            "m":true,"M":true}}"#;
+
+        let event = parse_binance_message(text).unwrap().unwrap();
+
+        assert_eq!(
+            event,
+            MarketEvent::Trade(Trade {
+                symbol: "BTCUSDT".into(),
+                price: 67000.5,
+                quantity: 0.015,
+                buyer_initiated: false,
+                timestamp: datetime!(2024-06-10 06:13:20.120 UTC),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_binance_quote() {
+        let text = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000",
+            "B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+
+        let MarketEvent::Quote(quote) = parse_binance_message(text).unwrap().unwrap() else {
+            panic!("expected a quote");
+        };
+
+        assert_eq!(quote.symbol, "BNBUSDT");
+        assert_eq!((quote.bid_size, quote.ask_size), (31.21, 40.66));
+        assert!((quote.mid() - 25.35855).abs() < 1e-12);
+        assert!((quote.spread() - 0.0133).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_binance_other_messages() {
+        assert_eq!(
+            parse_binance_message(r#"{"result":null,"id":1}"#).unwrap(),
+            None
+        );
+        assert!(parse_binance_message("not json").is_err());
+        assert!(parse_binance_message(r#"{"e":"trade","s":"BTCUSDT","q":"1"}"#).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Incremental updates of an SVI surface from live quotes.
+//!
+//! A [`LiveSviSurface`] keeps the latest implied volatility quote per
+//! (expiry, strike). When a quote arrives, only the smile for its expiry is
+//! refitted, starting from that smile's current parameters, so a refit
+//! takes a few hundred function evaluations rather than a full calibration.
+//!
+//! Readers take a [`LiveSviSurface::snapshot`]: an `Arc` to an immutable
+//! surface that is swapped atomically after each refit. A pricer holding a
+//! snapshot is never affected by later updates, and refits of different
+//! expiries can run concurrently.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::volatility_surface::{SviSlice, SviVolatilitySurface, VolatilitySurface};
+use argmin::{
+    core::{CostFunction, Executor, State},
+    solver::neldermead::NelderMead,
+};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// An implied volatility quote.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolatilityQuote {
+    /// Expiry in years.
+    pub expiry: f64,
+
+    /// Strike.
+    pub strike: f64,
+
+    /// Implied volatility.
+    pub volatility: f64,
+}
+
+/// What happened to a smile after an update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SliceUpdate {
+    /// The quote was stored, but the smile has too few quotes to refit.
+    Buffered {
+        /// Expiry of the smile.
+        expiry: f64,
+
+        /// Number of quotes on the smile.
+        quotes: usize,
+    },
+
+    /// The smile was refitted.
+    Refitted {
+        /// Expiry of the smile.
+        expiry: f64,
+
+        /// Root mean square error of the fit, in volatility.
+        rmse: f64,
+    },
+}
+
+/// SVI surface that is refitted one smile at a time as quotes arrive.
+#[derive(Debug)]
+pub struct LiveSviSurface {
+    /// Current surface. Writers replace the `Arc`, readers clone it.
+    surface: RwLock<Arc<SviVolatilitySurface>>,
+
+    /// Latest quote per strike, one book per slice (in the surface's order).
+    books: Vec<Mutex<BTreeMap<u64, f64>>>,
+
+    /// Quotes within this distance of a slice's expiry belong to it.
+    expiry_tolerance: f64,
+
+    /// Minimum number of quotes on a smile before it is refitted.
+    min_quotes: usize,
+}
+
+/// Least squares fit of one smile, in unconstrained coordinates:
+/// `[a, ln b, atanh rho, m, ln sigma]`.
+struct SmileFit {
+    expiry: f64,
+
+    /// Log-moneyness and quoted total variance.
+    points: Vec<(f64, f64)>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LiveSviSurface {
+    /// Start from an existing surface. Its slices define the expiries that
+    /// quotes can update.
+    pub fn new(surface: SviVolatilitySurface) -> Self {
+        let books = surface
+            .slices
+            .iter()
+            .map(|_| Mutex::new(BTreeMap::new()))
+            .collect();
+
+        Self {
+            surface: RwLock::new(Arc::new(surface)),
+            books,
+            expiry_tolerance: 1e-6,
+            min_quotes: 5,
+        }
+    }
+
+    /// Set the tolerance for matching a quote's expiry to a slice.
+    pub fn with_expiry_tolerance(mut self, tolerance: f64) -> Self {
+        self.expiry_tolerance = tolerance;
+        self
+    }
+
+    /// Set the minimum number of quotes on a smile before it is refitted
+    /// (at least 5, the number of SVI parameters).
+    pub fn with_min_quotes(mut self, quotes: usize) -> Self {
+        self.min_quotes = quotes.max(5);
+        self
+    }
+
+    /// The current surface. Later updates do not affect the returned surface.
+    pub fn snapshot(&self) -> Arc<SviVolatilitySurface> {
+        Arc::clone(&self.surface.read().unwrap())
+    }
+
+    /// Implied volatility on the current surface.
+    pub fn volatility(&self, expiry: f64, strike: f64) -> f64 {
+        self.snapshot().volatility(expiry, strike)
+    }
+
+    /// Number of quotes stored for a slice.
+    pub fn quote_count(&self, expiry: f64) -> Option<usize> {
+        let i = self.slice_index(expiry).ok()?;
+
+        Some(self.books[i].lock().unwrap().len())
+    }
+
+    /// Add a quote and refit its smile.
+    pub fn update(&self, quote: VolatilityQuote) -> Result<SliceUpdate, RustQuantError> {
+        let i = self.store(quote)?;
+
+        self.refit(i)
+    }
+
+    /// Add a batch of quotes, refitting each affected smile once.
+    ///
+    /// The updates are returned in expiry order.
+    pub fn update_many(
+        &self,
+        quotes: &[VolatilityQuote],
+    ) -> Result<Vec<SliceUpdate>, RustQuantError> {
+        let mut affected = Vec::new();
+
+        for quote in quotes {
+            affected.push(self.store(*quote)?);
+        }
+
+        affected.sort_unstable();
+        affected.dedup();
+
+        affected.into_iter().map(|i| self.refit(i)).collect()
+    }
+
+    /// Index of the slice a quote's expiry belongs to.
+    fn slice_index(&self, expiry: f64) -> Result<usize, RustQuantError> {
+        self.snapshot()
+            .slices
+            .iter()
+            .position(|slice| (slice.expiry - expiry).abs() <= self.expiry_tolerance)
+            .ok_or_else(|| {
+                RustQuantError::InvalidArgument(format!("No smile for expiry {}.", expiry))
+            })
+    }
+
+    /// Store a quote in its smile's book, returning the slice index.
+    fn store(&self, quote: VolatilityQuote) -> Result<usize, RustQuantError> {
+        if !(quote.strike > 0.0 && quote.volatility > 0.0 && quote.volatility.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Invalid quote: {:?}.",
+                quote
+            )));
+        }
+
+        let i = self.slice_index(quote.expiry)?;
+
+        // Positive floats order like their bit patterns.
+        self.books[i]
+            .lock()
+            .unwrap()
+            .insert(quote.strike.to_bits(), quote.volatility);
+
+        Ok(i)
+    }
+
+    /// Refit one smile from its book and publish the new surface.
+    fn refit(&self, i: usize) -> Result<SliceUpdate, RustQuantError> {
+        // Holding the book's lock serialises refits of the same smile.
+        let book = self.books[i].lock().unwrap();
+        let start = self.snapshot();
+        let slice = start.slices[i];
+
+        if book.len() < self.min_quotes {
+            return Ok(SliceUpdate::Buffered {
+                expiry: slice.expiry,
+                quotes: book.len(),
+            });
+        }
+
+        let forward = start.forward(slice.expiry);
+        let fit = SmileFit {
+            expiry: slice.expiry,
+            points: book
+                .iter()
+                .map(|(strike, vol)| {
+                    let k = (f64::from_bits(*strike) / forward).ln();
+                    (k, vol * vol * slice.expiry)
+                })
+                .collect(),
+        };
+
+        let fitted = fit.run(&slice)?;
+        let rmse = fit.volatility_rmse(&fitted);
+
+        // Swap in a copy of the latest surface (not `start`, which another
+        // smile's refit may have replaced) with this smile changed.
+        let mut current = self.surface.write().unwrap();
+        let mut surface = SviVolatilitySurface::clone(&current);
+        surface.slices[i] = fitted;
+        *current = Arc::new(surface);
+
+        Ok(SliceUpdate::Refitted {
+            expiry: slice.expiry,
+            rmse,
+        })
+    }
+}
+
+impl SmileFit {
+    fn to_coordinates(slice: &SviSlice) -> Vec<f64> {
+        vec![
+            slice.a,
+            slice.b.max(1e-8).ln(),
+            slice.rho.clamp(-0.999, 0.999).atanh(),
+            slice.m,
+            slice.sigma.max(1e-8).ln(),
+        ]
+    }
+
+    fn to_slice(&self, p: &[f64]) -> SviSlice {
+        SviSlice {
+            expiry: self.expiry,
+            a: p[0],
+            b: p[1].exp(),
+            rho: p[2].tanh(),
+            m: p[3],
+            sigma: p[4].exp(),
+        }
+    }
+
+    /// Refit, starting from `start`.
+    fn run(&self, start: &SviSlice) -> Result<SviSlice, RustQuantError> {
+        let x0 = Self::to_coordinates(start);
+        let steps = [0.1 * start.a.abs().max(0.01), 0.2, 0.2, 0.05, 0.2];
+
+        let mut simplex = vec![x0.clone()];
+        for (j, step) in steps.iter().enumerate() {
+            let mut vertex = x0.clone();
+            vertex[j] += step;
+            simplex.push(vertex);
+        }
+
+        let solver = NelderMead::new(simplex)
+            .with_sd_tolerance(1e-16)
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?;
+
+        let result = Executor::new(self, solver)
+            .configure(|state| state.max_iters(2000))
+            .run()
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?;
+
+        let best = result.state().get_best_param().ok_or_else(|| {
+            RustQuantError::ComputationError("SVI refit did not converge.".to_string())
+        })?;
+
+        Ok(self.to_slice(best))
+    }
+
+    fn volatility_rmse(&self, slice: &SviSlice) -> f64 {
+        let squares: f64 = self
+            .points
+            .iter()
+            .map(|(k, w)| (slice.volatility(*k) - (w / self.expiry).sqrt()).powi(2))
+            .sum();
+
+        (squares / self.points.len() as f64).sqrt()
+    }
+}
+
+impl CostFunction for &SmileFit {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let slice = self.to_slice(p);
+
+        let squares: f64 = self
+            .points
+            .iter()
+            .map(|(k, w)| (slice.total_variance(*k) - w).powi(2))
+            .sum();
+
+        // Minimum total variance, a + b sigma sqrt(1 - rho^2), must be non-negative.
+        let minimum = slice.a + slice.b * slice.sigma * (1.0 - slice.rho * slice.rho).sqrt();
+        let penalty = if minimum < 0.0 {
+            1e3 * minimum * minimum
+        } else {
+            0.0
+        };
+
+        Ok(squares + penalty)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_live_surface {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn slice(expiry: f64, a: f64, rho: f64) -> SviSlice {
+        SviSlice {
+            expiry,
+            a,
+            b: 0.1,
+            rho,
+            m: 0.0,
+            sigma: 0.2,
+        }
+    }
+
+    fn quotes(surface: &SviVolatilitySurface, expiry: f64) -> Vec<VolatilityQuote> {
+        (0..9)
+            .map(|i| {
+                let strike = 70.0 + 7.5 * i as f64;
+                VolatilityQuote {
+                    expiry,
+                    strike,
+                    volatility: surface.volatility(expiry, strike),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_refits_only_the_quoted_smile() {
+        let market = SviVolatilitySurface::new(
+            100.0,
+            0.01,
+            vec![slice(0.5, 0.01, -0.3), slice(1.0, 0.025, -0.5)],
+        );
+        let stale = SviVolatilitySurface::new(
+            100.0,
+            0.01,
+            vec![slice(0.5, 0.01, -0.3), slice(1.0, 0.02, -0.2)],
+        );
+        let live = LiveSviSurface::new(stale.clone());
+        let before = live.snapshot();
+
+        let mut last = None;
+        for quote in quotes(&market, 1.0) {
+            last = Some(live.update(quote).unwrap());
+        }
+
+        match last.unwrap() {
+            SliceUpdate::Refitted { expiry, rmse } => {
+                assert_eq!(expiry, 1.0);
+                assert!(rmse < 1e-5, "rmse {}", rmse);
+            }
+            other => panic!("expected a refit, got {:?}", other),
+        }
+
+        // The quoted smile now matches the market, the other is untouched.
+        for strike in [75.0, 100.0, 125.0] {
+            assert_approx_equal!(
+                live.volatility(1.0, strike),
+                market.volatility(1.0, strike),
+                1e-5
+            );
+        }
+        assert_eq!(live.snapshot().slices[0], stale.slices[0]);
+
+        // Earlier snapshots are unaffected.
+        assert_eq!(*before, stale);
+    }
+
+    #[test]
+    fn test_buffering_and_errors() {
+        let surface = SviVolatilitySurface::new(100.0, 0.0, vec![slice(1.0, 0.02, -0.3)]);
+        let live = LiveSviSurface::new(surface.clone()).with_min_quotes(6);
+        let quotes = quotes(&surface, 1.0);
+
+        assert_eq!(
+            live.update(quotes[0]).unwrap(),
+            SliceUpdate::Buffered {
+                expiry: 1.0,
+                quotes: 1
+            }
+        );
+
+        // A new quote for the same strike replaces the old one.
+        live.update(quotes[0]).unwrap();
+        assert_eq!(live.quote_count(1.0), Some(1));
+
+        let updates = live.update_many(&quotes[1..6]).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert!(matches!(updates[0], SliceUpdate::Refitted { .. }));
+
+        let unknown = VolatilityQuote {
+            expiry: 2.0,
+            ..quotes[0]
+        };
+        assert!(live.update(unknown).is_err());
+
+        let invalid = VolatilityQuote {
+            volatility: f64::NAN,
+            ..quotes[0]
+        };
+        assert!(live.update(invalid).is_err());
+    }
+
+    #[test]
+    fn test_concurrent_readers() {
+        let market = SviVolatilitySurface::new(
+            100.0,
+            0.0,
+            vec![slice(0.5, 0.012, -0.4), slice(1.0, 0.025, -0.5)],
+        );
+        let stale = SviVolatilitySurface::new(
+            100.0,
+            0.0,
+            vec![slice(0.5, 0.01, -0.2), slice(1.0, 0.02, -0.2)],
+        );
+        let live = LiveSviSurface::new(stale);
+
+        std::thread::scope(|scope| {
+            for expiry in [0.5, 1.0] {
+                let live = &live;
+                let quotes = quotes(&market, expiry);
+                scope.spawn(move || live.update_many(&quotes).unwrap());
+            }
+
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..200 {
+                        let snapshot = live.snapshot();
+                        assert_eq!(snapshot.slices.len(), 2);
+                        assert!(snapshot.volatility(0.75, 90.0).is_finite());
+                    }
+                });
+            }
+        });
+
+        // Both refits were published: neither overwrote the other.
+        for expiry in [0.5, 1.0] {
+            assert_approx_equal!(
+                live.volatility(expiry, 90.0),
+                market.volatility(expiry, 90.0),
+                1e-5
+            );
+        }
+    }
+}
@@ -0,0 +1,284 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Dupire local volatility.
+//!
+//! [`LocalVolatilitySurface`] holds local volatilities $\sigma(t, S)$ on a
+//! (time, spot) grid, and can be built from any implied [`VolatilitySurface`]
+//! with Dupire's formula in total implied variance $w(y, T) = \sigma_{imp}^2 T$
+//! at log-moneyness $y = \ln(K / F_T)$:
+//! $$ \sigma^2(T, K) = \frac{\partial_T w}{1 - \frac{y}{w} \partial_y w
+//!   + \frac{1}{4} \left( -\frac{1}{4} - \frac{1}{w} + \frac{y^2}{w^2} \right) (\partial_y w)^2
+//!   + \frac{1}{2} \partial_{yy} w} $$
+//!
+//! [`LocalVolProcess`] simulates
+//! $$ dS_t = (r - q) S_t dt + \sigma(t, S_t) S_t dW_t $$
+//! which reprices the European options of the implied surface it came from.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::volatility_surface::{bilinear_grid, interpolate_flat, VolatilitySurface};
+use RustQuant_math::interpolation::GridInterpolator;
+use RustQuant_stochastics::StochasticProcess;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Local volatilities on a (time, spot) grid, interpolated bilinearly and
+/// extrapolated flat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVolatilitySurface {
+    /// Times in years, spot levels, and local volatilities.
+    grid: GridInterpolator<f64, f64, f64>,
+}
+
+/// Spot process driven by a local volatility surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVolProcess {
+    /// Continuously compounded risk-free rate ($r$).
+    pub risk_free_rate: f64,
+
+    /// Continuous dividend yield ($q$).
+    pub dividend_yield: f64,
+
+    /// Local volatility surface ($\sigma(t, S)$).
+    pub surface: LocalVolatilitySurface,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LocalVolatilitySurface {
+    /// Shortest maturity used in Dupire's formula, so that $t = 0$ can be on the grid.
+    const MIN_TIME: f64 = 1.0 / 365.0;
+
+    /// Create a new local volatility surface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty or the volatilities do not match its shape.
+    pub fn new(times: Vec<f64>, spots: Vec<f64>, volatilities: Vec<Vec<f64>>) -> Self {
+        Self {
+            grid: bilinear_grid(times, spots, volatilities),
+        }
+    }
+
+    /// Times in years (increasing).
+    pub fn times(&self) -> &[f64] {
+        &self.grid.xs
+    }
+
+    /// Spot levels (increasing).
+    pub fn spots(&self) -> &[f64] {
+        &self.grid.ys
+    }
+
+    /// Local volatilities, `volatilities[i][j]` for `times[i]` and `spots[j]`.
+    pub fn volatilities(&self) -> &[Vec<f64>] {
+        &self.grid.values
+    }
+
+    /// Local volatilities from an implied volatility surface, with Dupire's
+    /// formula evaluated by finite differences at every grid point.
+    ///
+    /// Where the implied surface has calendar or butterfly arbitrage, and the
+    /// local variance would be negative or infinite, the implied volatility
+    /// is used instead.
+    ///
+    /// # Arguments:
+    /// * `implied` - Implied volatilities by expiry and strike.
+    /// * `spot` - Spot price of the underlying.
+    /// * `risk_free_rate` - Continuously compounded risk-free rate.
+    /// * `dividend_yield` - Continuous dividend yield.
+    /// * `times` - Time grid (in years).
+    /// * `spots` - Spot grid.
+    pub fn from_implied<S: VolatilitySurface>(
+        implied: &S,
+        spot: f64,
+        risk_free_rate: f64,
+        dividend_yield: f64,
+        times: Vec<f64>,
+        spots: Vec<f64>,
+    ) -> Self {
+        let forward = |t: f64| spot * ((risk_free_rate - dividend_yield) * t).exp();
+        let total_variance =
+            |y: f64, t: f64| implied.volatility(t, forward(t) * y.exp()).powi(2) * t;
+
+        let volatilities = times
+            .iter()
+            .map(|&t| {
+                let t = t.max(Self::MIN_TIME);
+                let dt = 1e-4 * t;
+
+                spots
+                    .iter()
+                    .map(|&s| {
+                        let y = (s / forward(t)).ln();
+                        let dy = 1e-3;
+
+                        let w = total_variance(y, t);
+                        let w_t =
+                            (total_variance(y, t + dt) - total_variance(y, t - dt)) / (2.0 * dt);
+                        let (w_up, w_down) = (total_variance(y + dy, t), total_variance(y - dy, t));
+                        let w_y = (w_up - w_down) / (2.0 * dy);
+                        let w_yy = (w_up - 2.0 * w + w_down) / (dy * dy);
+
+                        let denominator = 1.0 - y / w * w_y
+                            + 0.25 * (-0.25 - 1.0 / w + y * y / (w * w)) * w_y * w_y
+                            + 0.5 * w_yy;
+                        let local_variance = w_t / denominator;
+
+                        if local_variance.is_finite() && local_variance > 0.0 && denominator > 0.0 {
+                            local_variance.sqrt()
+                        } else {
+                            (w / t).sqrt()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self::new(times, spots, volatilities)
+    }
+
+    /// Local volatility at time `t` and spot `spot`.
+    pub fn local_volatility(&self, t: f64, spot: f64) -> f64 {
+        interpolate_flat(&self.grid, t, spot)
+    }
+}
+
+impl LocalVolProcess {
+    /// Create a new local volatility process.
+    pub fn new(risk_free_rate: f64, dividend_yield: f64, surface: LocalVolatilitySurface) -> Self {
+        Self {
+            risk_free_rate,
+            dividend_yield,
+            surface,
+        }
+    }
+}
+
+impl StochasticProcess for LocalVolProcess {
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        (self.risk_free_rate - self.dividend_yield) * x
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        self.surface.local_volatility(t, x) * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.risk_free_rate, self.dividend_yield]
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_local_volatility {
+    use super::*;
+    use crate::{PillarVolatilitySurface, SviSlice, SviVolatilitySurface};
+    use RustQuant_instruments::options::{GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+    use RustQuant_stochastics::StochasticProcessConfig;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn grid(start: f64, end: f64, n: usize) -> Vec<f64> {
+        (0..=n)
+            .map(|i| start + (end - start) * i as f64 / n as f64)
+            .collect()
+    }
+
+    #[test]
+    fn test_flat_implied_surface() {
+        let implied = PillarVolatilitySurface::new(vec![1.0], vec![100.0], vec![vec![0.25]]);
+        let local = LocalVolatilitySurface::from_implied(
+            &implied,
+            100.0,
+            0.03,
+            0.01,
+            grid(0.0, 2.0, 4),
+            grid(50.0, 200.0, 6),
+        );
+
+        for row in local.volatilities() {
+            for vol in row {
+                assert_approx_equal!(*vol, 0.25, 1e-6);
+            }
+        }
+        assert_approx_equal!(local.local_volatility(0.3, 123.0), 0.25, 1e-6);
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let surface = LocalVolatilitySurface::new(
+            vec![0.0, 1.0],
+            vec![90.0, 110.0],
+            vec![vec![0.3, 0.2], vec![0.25, 0.15]],
+        );
+
+        assert_approx_equal!(surface.local_volatility(0.5, 100.0), 0.225, 1e-12);
+        assert_approx_equal!(surface.local_volatility(5.0, 50.0), 0.25, 1e-12);
+    }
+
+    #[test]
+    fn test_reprices_smile() {
+        let (spot, r, q) = (100.0, 0.03, 0.01);
+        let slice = |expiry: f64| SviSlice {
+            expiry,
+            a: 0.02 * expiry,
+            b: 0.08,
+            rho: -0.6,
+            m: 0.0,
+            sigma: 0.2,
+        };
+        let implied = SviVolatilitySurface::new(spot, r - q, vec![slice(0.5), slice(1.0)]);
+        let local = LocalVolatilitySurface::from_implied(
+            &implied,
+            spot,
+            r,
+            q,
+            grid(0.0, 1.0, 40),
+            grid(30.0, 250.0, 220),
+        );
+
+        // Downside skew turns into higher local volatility at low spots.
+        assert!(local.local_volatility(1.0, 80.0) > local.local_volatility(1.0, 120.0));
+
+        let process = LocalVolProcess::new(r, q, local);
+        let config = StochasticProcessConfig::new(spot, 0.0, 1.0, 200, 40_000, true).with_seed(17);
+        let terminal = process.euler_maruyama(&config).terminal_values();
+
+        for strike in [80.0, 100.0, 120.0] {
+            let monte_carlo = (-r).exp()
+                * terminal.iter().map(|s| (s - strike).max(0.0)).sum::<f64>()
+                / terminal.len() as f64;
+            let black_scholes = Merton73::new(spot, r, q, implied.volatility(1.0, strike)).price(
+                strike,
+                1.0,
+                TypeFlag::Call,
+            );
+
+            assert_approx_equal!(monte_carlo, black_scholes, 0.2);
+        }
+    }
+}
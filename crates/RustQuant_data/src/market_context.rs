@@ -0,0 +1,430 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Market data context for dual-curve pricing.
+//!
+//! Since the switch to collateralised (OIS) discounting, a swap's cash flows
+//! are discounted on one curve while its floating coupons are projected on
+//! another, one for each index and tenor (3M and 6M EURIBOR trade at a basis
+//! to each other). [`MarketDataContext`] holds one discount curve per
+//! currency and one projection curve per index and tenor, and implements
+//! [`CurveContext`] so that swaps and floating rate notes can be priced on it.
+//! Coupons of seasoned instruments are resolved against the fixing history
+//! of their index by the context's [`FixingStore`]: term rates take the
+//! reset fixing, and overnight rates compound the daily fixings to date
+//! with the forecast for the rest of the period.
+//!
+//! ```rust
+//! use RustQuant_data::*;
+//! use RustQuant_instruments::{CurveContext, InterestRateIndex, EUR};
+//! use RustQuant_time::{AustraliaCalendar, DayCountConvention, Tenor};
+//! use std::sync::Arc;
+//! use time::macros::date;
+//!
+//! let today = date!(2024 - 03 - 05);
+//! let flat = |rate: f64| {
+//!     let dates = [today, date!(2054 - 03 - 05)];
+//!     let dfs = [1.0, (-rate * 30.0_f64).exp()];
+//!     let mut curve = DiscountCurve::<_, AustraliaCalendar>::new(&dates, &dfs);
+//!     curve.day_count_convention = Some(DayCountConvention::Actual_365_Fixed);
+//!     Arc::new(curve)
+//! };
+//!
+//! let euribor = InterestRateIndex::euribor(Tenor::months(3));
+//! let context = MarketDataContext::new(today)
+//!     .with_discount_curve(EUR, flat(0.030))
+//!     .with_projection_curve(&euribor.name, euribor.tenor, flat(0.035));
+//!
+//! let forward = context
+//!     .forward_rate(&euribor, date!(2025 - 03 - 05), date!(2025 - 06 - 05))
+//!     .unwrap();
+//! assert!((forward - 0.035).abs() < 1e-3);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{FixingStore, RateCurve};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::{Currency, CurveContext, InterestRateIndex};
+use RustQuant_time::{Calendar, Tenor};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A curve shared between contexts and instruments.
+pub type SharedRateCurve = Arc<dyn RateCurve + Send + Sync>;
+
+/// Discount curves by currency and projection curves by index and tenor.
+#[derive(Clone)]
+pub struct MarketDataContext {
+    /// Valuation date. Discount factors are rebased to this date.
+    pub valuation_date: Date,
+
+    /// Resolver of the resets of seasoned coupons.
+    pub fixing_store: FixingStore,
+
+    discount_curves: HashMap<Currency, SharedRateCurve>,
+    projection_curves: HashMap<(String, Tenor), SharedRateCurve>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarketDataContext {
+    /// Create an empty context.
+    pub fn new(valuation_date: Date) -> Self {
+        Self {
+            valuation_date,
+            fixing_store: FixingStore::default(),
+            discount_curves: HashMap::new(),
+            projection_curves: HashMap::new(),
+        }
+    }
+
+    /// Set the fixing store used for seasoned coupons.
+    pub fn with_fixing_store(mut self, fixing_store: FixingStore) -> Self {
+        self.fixing_store = fixing_store;
+        self
+    }
+
+    /// Set the discount curve for a currency.
+    pub fn with_discount_curve(mut self, currency: Currency, curve: SharedRateCurve) -> Self {
+        self.discount_curves.insert(currency, curve);
+        self
+    }
+
+    /// Set the projection curve for an index and tenor.
+    ///
+    /// For single-curve pricing, pass the same curve as for discounting.
+    pub fn with_projection_curve(
+        mut self,
+        index: &str,
+        tenor: Tenor,
+        curve: SharedRateCurve,
+    ) -> Self {
+        self.projection_curves
+            .insert((index.to_string(), tenor), curve);
+        self
+    }
+
+    /// Discount curve for a currency.
+    pub fn discount_curve(&self, currency: Currency) -> Option<&SharedRateCurve> {
+        self.discount_curves.get(&currency)
+    }
+
+    /// Projection curve for an index and tenor.
+    pub fn projection_curve(&self, index: &str, tenor: Tenor) -> Option<&SharedRateCurve> {
+        self.projection_curves.get(&(index.to_string(), tenor))
+    }
+
+    /// Discount factor from the valuation date, on a curve that may have
+    /// been built as of an earlier date.
+    fn rebased_discount_factor(&self, curve: &SharedRateCurve, date: Date) -> f64 {
+        curve.discount_factor(date) / curve.discount_factor(self.valuation_date)
+    }
+}
+
+impl CurveContext for MarketDataContext {
+    fn valuation_date(&self) -> Date {
+        self.valuation_date
+    }
+
+    fn discount_factor(&self, currency: Currency, date: Date) -> Result<f64, RustQuantError> {
+        let curve = self.discount_curve(currency).ok_or_else(|| {
+            RustQuantError::MissingInput(format!("No discount curve for {}.", currency.name))
+        })?;
+
+        Ok(self.rebased_discount_factor(curve, date))
+    }
+
+    fn projection_discount_factor(
+        &self,
+        index: &str,
+        tenor: Tenor,
+        date: Date,
+    ) -> Result<f64, RustQuantError> {
+        let curve = self.projection_curve(index, tenor).ok_or_else(|| {
+            RustQuantError::MissingInput(format!("No projection curve for {} {}.", index, tenor))
+        })?;
+
+        Ok(self.rebased_discount_factor(curve, date))
+    }
+
+    fn coupon_rate<C: Calendar>(
+        &self,
+        index: &InterestRateIndex<C>,
+        start: Date,
+        end: Date,
+    ) -> Result<f64, RustQuantError> {
+        if index.fixing_date(start) > self.valuation_date {
+            return self.forward_rate(index, start, end);
+        }
+
+        match index.is_overnight() {
+            true => {
+                self.fixing_store
+                    .compounded_rate(index, start, end, self.valuation_date, |date| {
+                        self.forward_rate(index, date, index.maturity_date(date))
+                    })
+            }
+            false => self.fixing_store.reset_rate(
+                index,
+                index.fixing_date(start),
+                self.valuation_date,
+                |_| self.forward_rate(index, start, end),
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for MarketDataContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut projections = self
+            .projection_curves
+            .keys()
+            .map(|(index, tenor)| format!("{} {}", index, tenor))
+            .collect::<Vec<_>>();
+        projections.sort();
+
+        f.debug_struct("MarketDataContext")
+            .field("valuation_date", &self.valuation_date)
+            .field("fixing_store", &self.fixing_store)
+            .field(
+                "discount_curves",
+                &self
+                    .discount_curves
+                    .keys()
+                    .map(|currency| currency.name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("projection_curves", &projections)
+            .finish()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_market_context {
+    use super::*;
+    use crate::{CurveBootstrapper, CurveInstrument, Curves, DiscountCurve, MissingFixingPolicy};
+    use time::macros::date;
+    use RustQuant_instruments::{FloatingRateNote, InterestRateSwap, EUR, USD};
+    use RustQuant_time::{AustraliaCalendar, DayCountConvention, Frequency};
+    use RustQuant_utils::assert_approx_equal;
+
+    const TODAY: Date = date!(2024 - 03 - 05);
+    const SPOT: Date = date!(2024 - 03 - 07);
+
+    /// Deposit and annual swaps at a flat quote, from the spot date.
+    fn curve(rate: f64) -> SharedRateCurve {
+        let mut bootstrapper = CurveBootstrapper::new(SPOT)
+            .with_day_count_convention(DayCountConvention::Actual_360)
+            .with_instrument(CurveInstrument::Deposit {
+                maturity: date!(2024 - 06 - 07),
+                rate,
+            });
+
+        for year in 1..=10 {
+            bootstrapper = bootstrapper.with_instrument(CurveInstrument::Swap {
+                maturity: date!(2024 - 03 - 07).replace_year(2024 + year).unwrap(),
+                rate,
+                fixed_frequency: Frequency::Annually,
+            });
+        }
+
+        Arc::new(bootstrapper.bootstrap().unwrap())
+    }
+
+    /// Flat continuously compounded curve from today.
+    fn flat(rate: f64) -> SharedRateCurve {
+        let dates = [TODAY, date!(2054 - 03 - 05)];
+        let dfs = [1.0, (-rate * 30.0_f64).exp()];
+        let mut curve = DiscountCurve::<_, AustraliaCalendar>::new(&dates, &dfs);
+        curve.day_count_convention = Some(DayCountConvention::Actual_365_Fixed);
+
+        Arc::new(curve)
+    }
+
+    fn quarterly_schedule(years: usize) -> Vec<Date> {
+        let index = InterestRateIndex::euribor(Tenor::months(3));
+
+        (0..4 * years).fold(vec![SPOT], |mut dates, _| {
+            let last = *dates.last().unwrap();
+            dates.push(last + index.tenor);
+            dates
+        })
+    }
+
+    #[test]
+    fn test_missing_curves() {
+        let euribor = InterestRateIndex::euribor(Tenor::months(3));
+        let context = MarketDataContext::new(TODAY).with_discount_curve(USD, curve(0.05));
+
+        assert!(context.discount_factor(EUR, SPOT).is_err());
+        assert!(context.forward_rate(&euribor, SPOT, TODAY).is_err());
+        assert!(context.discount_factor(USD, date!(2025 - 03 - 07)).unwrap() < 1.0);
+        assert_approx_equal!(context.discount_factor(USD, TODAY).unwrap(), 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_single_curve_matches_bootstrap() {
+        let euribor = InterestRateIndex::euribor(Tenor::months(3));
+        let estr = curve(0.03);
+        let context = MarketDataContext::new(TODAY)
+            .with_discount_curve(EUR, estr.clone())
+            .with_projection_curve(&euribor.name, euribor.tenor, estr);
+
+        // A floater projected and discounted on the same curve is at par on its start date.
+        let schedule = quarterly_schedule(5);
+        let frn = FloatingRateNote::new(1.0, euribor, 0.0, schedule);
+        let df_spot = context.discount_factor(EUR, SPOT).unwrap();
+
+        assert_approx_equal!(frn.npv(&context).unwrap(), df_spot, 1e-12);
+    }
+
+    #[test]
+    fn test_dual_curve_par_rate() {
+        let euribor = || InterestRateIndex::euribor(Tenor::months(3));
+        let floating = quarterly_schedule(5);
+        let fixed = floating.iter().step_by(4).copied().collect::<Vec<_>>();
+
+        let swap = InterestRateSwap::new(
+            1e7,
+            true,
+            0.0,
+            fixed,
+            DayCountConvention::Actual_360,
+            euribor(),
+            floating,
+        );
+
+        let ois = curve(0.030);
+        let single = MarketDataContext::new(TODAY)
+            .with_discount_curve(EUR, ois.clone())
+            .with_projection_curve(&euribor().name, Tenor::months(3), ois.clone());
+        let dual =
+            single
+                .clone()
+                .with_projection_curve(&euribor().name, Tenor::months(3), curve(0.034));
+
+        // Single curve: par rate is the bootstrap quote, the forward starting
+        // swap being worth the same as one from the curve's spot date.
+        assert_approx_equal!(swap.par_rate(&single).unwrap(), 0.030, 1e-10);
+
+        // The projection basis passes through to the par rate, but the
+        // discounting stays on the OIS curve.
+        let par = swap.par_rate(&dual).unwrap();
+        assert_approx_equal!(par, 0.034, 2e-4);
+
+        let priced = InterestRateSwap {
+            fixed_rate: par,
+            ..swap
+        };
+        assert_approx_equal!(priced.npv(&dual).unwrap(), 0.0, 1e-6);
+    }
+
+    #[test]
+    fn test_seasoned_floater_uses_fixing() {
+        let start = date!(2024 - 01 - 05);
+        let first_payment = date!(2024 - 04 - 05);
+        let schedule = vec![start, first_payment, date!(2024 - 07 - 05)];
+
+        let mut euribor = InterestRateIndex::euribor(Tenor::months(3));
+        let context = MarketDataContext::new(TODAY)
+            .with_discount_curve(EUR, flat(0.03))
+            .with_projection_curve(&euribor.name, euribor.tenor, flat(0.03));
+
+        // No fixing: an error, unless the reset is estimated.
+        let unfixed = InterestRateIndex::euribor(Tenor::months(3));
+        let frn = FloatingRateNote::new(1.0, unfixed, 0.0, schedule.clone());
+        assert!(frn.npv(&context).is_err());
+
+        let estimated = context
+            .clone()
+            .with_fixing_store(FixingStore::new(MissingFixingPolicy::Estimate));
+        let forward = context
+            .forward_rate(&euribor, start, first_payment)
+            .unwrap();
+        let tau = euribor.year_fraction(start, first_payment);
+        let df = context.discount_factor(EUR, first_payment).unwrap();
+        assert_approx_equal!(frn.npv(&estimated).unwrap(), forward * tau * df + df, 1e-12);
+
+        // Known first coupon, then the rest is worth par on its start date.
+        euribor.add_fixing(euribor.fixing_date(start), 0.039);
+        let frn = FloatingRateNote::new(1.0, euribor, 0.0, schedule);
+        assert_approx_equal!(frn.npv(&context).unwrap(), 0.039 * tau * df + df, 1e-12);
+    }
+
+    #[test]
+    fn test_seasoned_sofr_swap() {
+        // Quarterly SOFR swap that started on Monday 5 February 2024,
+        // valued on Tuesday 5 March 2024.
+        let start = date!(2024 - 02 - 05);
+        let end = date!(2024 - 05 - 06);
+
+        let mut sofr = InterestRateIndex::sofr();
+        sofr.add_fixings((0..29).map(|day| (start + time::Duration::days(day), 0.0531)));
+
+        let context = MarketDataContext::new(TODAY)
+            .with_discount_curve(USD, flat(0.05))
+            .with_projection_curve(&sofr.name, sofr.tenor, flat(0.05));
+        let swap = InterestRateSwap::new(
+            1e6,
+            true,
+            0.05,
+            vec![start, end],
+            DayCountConvention::Actual_360,
+            sofr,
+            vec![start, end],
+        );
+        let sofr = &swap.index;
+
+        // Daily fixings compounded to yesterday (Presidents' Day is not a
+        // business day), then the projection curve to the end of the period.
+        let mut growth = 1.0;
+        let mut date = start;
+        while date < TODAY {
+            let next = sofr.maturity_date(date);
+            growth *= 1.0 + 0.0531 * sofr.year_fraction(date, next);
+            date = next;
+        }
+        growth *= context
+            .projection_discount_factor(&sofr.name, sofr.tenor, TODAY)
+            .unwrap()
+            / context
+                .projection_discount_factor(&sofr.name, sofr.tenor, end)
+                .unwrap();
+
+        let tau = sofr.year_fraction(start, end);
+        let rate = (growth - 1.0) / tau;
+        let df = context.discount_factor(USD, end).unwrap();
+
+        assert_approx_equal!(
+            swap.floating_leg_npv(&context).unwrap(),
+            1e6 * rate * tau * df,
+            1e-6
+        );
+        assert_approx_equal!(swap.par_rate(&context).unwrap(), rate, 1e-12);
+
+        // The coupon blends the fixings to date with the forecast, rather
+        // than taking a single overnight fixing for the whole period.
+        assert!(rate > 0.0505 && rate < 0.0531);
+    }
+}
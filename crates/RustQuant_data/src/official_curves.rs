@@ -0,0 +1,401 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Downloaders for official risk-free curves.
+//!
+//! - US Treasury par yields, from the FRED constant maturity series
+//!   (`DGS1MO`, ..., `DGS30`).
+//! - ECB euro area AAA spot curve, from the ECB data portal
+//!   (`YC/B.U2.EUR.4F.G_N_A.SV_C_YM.SR_*`).
+//!
+//! Curves are returned as a [`Curve<Date>`] whose keys are the maturity
+//! dates of each tenor from the curve date, and whose values are rates as
+//! decimals (the sources quote percentages). If a source has no
+//! observation on the curve date (weekends and holidays), the latest
+//! observation of the previous two weeks is used.
+//!
+//! With a cache directory, every downloaded curve is written to
+//! `<cache>/<curve>_<date>.csv` and read back instead of downloading it
+//! again.
+
+use crate::parse_date;
+use crate::{Curve, DataProvider, FredProvider};
+use polars::prelude::*;
+use std::io::Cursor;
+use std::path::PathBuf;
+use time::{Date, Duration};
+use RustQuant_error::RustQuantError;
+use RustQuant_time::Tenor;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Official risk-free curves that can be downloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OfficialCurve {
+    /// US Treasury par yields (constant maturity), from FRED.
+    UsTreasuryPar,
+
+    /// ECB euro area AAA government bond spot rates.
+    EcbAaaSpot,
+}
+
+/// Downloads official curves, optionally caching them on disk.
+#[derive(Debug)]
+pub struct CurveDownloader {
+    /// FRED provider, needed for the US Treasury curve.
+    pub fred: Option<FredProvider>,
+
+    /// Base URL of the ECB data portal.
+    pub ecb_base_url: String,
+
+    /// Directory of cached curves.
+    pub cache_dir: Option<PathBuf>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl OfficialCurve {
+    /// Tenors of the curve in months, with the source's code for each.
+    pub fn tenors(&self) -> &'static [(u32, &'static str)] {
+        match self {
+            Self::UsTreasuryPar => &[
+                (1, "DGS1MO"),
+                (3, "DGS3MO"),
+                (6, "DGS6MO"),
+                (12, "DGS1"),
+                (24, "DGS2"),
+                (36, "DGS3"),
+                (60, "DGS5"),
+                (84, "DGS7"),
+                (120, "DGS10"),
+                (240, "DGS20"),
+                (360, "DGS30"),
+            ],
+            Self::EcbAaaSpot => &[
+                (3, "SR_3M"),
+                (6, "SR_6M"),
+                (12, "SR_1Y"),
+                (24, "SR_2Y"),
+                (36, "SR_3Y"),
+                (60, "SR_5Y"),
+                (84, "SR_7Y"),
+                (120, "SR_10Y"),
+                (180, "SR_15Y"),
+                (240, "SR_20Y"),
+                (360, "SR_30Y"),
+            ],
+        }
+    }
+
+    /// Name used for cache files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UsTreasuryPar => "us_treasury_par",
+            Self::EcbAaaSpot => "ecb_aaa_spot",
+        }
+    }
+}
+
+impl Default for CurveDownloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CurveDownloader {
+    /// Number of days before the curve date searched for observations.
+    pub const LOOKBACK_DAYS: i64 = 14;
+
+    /// Create a downloader without a FRED provider or a cache.
+    pub fn new() -> Self {
+        Self {
+            fred: None,
+            ecb_base_url: "https://data-api.ecb.europa.eu".to_string(),
+            cache_dir: None,
+        }
+    }
+
+    /// Use a FRED provider for the US Treasury curve.
+    pub fn with_fred(mut self, fred: FredProvider) -> Self {
+        self.fred = Some(fred);
+        self
+    }
+
+    /// Use another base URL for the ECB data portal.
+    pub fn with_ecb_base_url(mut self, base_url: &str) -> Self {
+        self.ecb_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Cache downloaded curves in a directory (created if needed).
+    pub fn with_cache_dir<P: Into<PathBuf>>(mut self, cache_dir: P) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// US Treasury par yield curve on a date.
+    pub fn us_treasury_par(&self, date: Date) -> Result<Curve<Date>, RustQuantError> {
+        self.download(OfficialCurve::UsTreasuryPar, date)
+    }
+
+    /// ECB AAA spot curve on a date.
+    pub fn ecb_aaa_spot(&self, date: Date) -> Result<Curve<Date>, RustQuantError> {
+        self.download(OfficialCurve::EcbAaaSpot, date)
+    }
+
+    /// Official curve on a date, from the cache if it is there.
+    ///
+    /// # Errors
+    /// - `RustQuantError::MissingInput` if the US Treasury curve is requested
+    ///   without a FRED provider, or if the source has no observations.
+    pub fn download(
+        &self,
+        curve: OfficialCurve,
+        date: Date,
+    ) -> Result<Curve<Date>, RustQuantError> {
+        let cache_path = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}_{}.csv", curve.name(), date)));
+
+        let nodes = match &cache_path {
+            Some(path) if path.exists() => read_nodes(path)?,
+            _ => {
+                let nodes = match curve {
+                    OfficialCurve::UsTreasuryPar => self.fetch_treasury(date)?,
+                    OfficialCurve::EcbAaaSpot => self.fetch_ecb(date)?,
+                };
+
+                if let Some(path) = &cache_path {
+                    write_nodes(path, &nodes)?;
+                }
+
+                nodes
+            }
+        };
+
+        Ok(curve_from_nodes(date, &nodes))
+    }
+
+    fn fetch_treasury(&self, date: Date) -> Result<Vec<(u32, f64)>, RustQuantError> {
+        let fred = self.fred.as_ref().ok_or_else(|| {
+            RustQuantError::MissingInput("The US Treasury curve needs a FRED provider.".into())
+        })?;
+
+        let start = date - Duration::days(Self::LOOKBACK_DAYS);
+        let mut nodes = Vec::new();
+
+        for &(months, id) in OfficialCurve::UsTreasuryPar.tenors() {
+            let series = fred.series(id, start, date)?;
+            let values = series.column("value")?.f64()?;
+
+            // Observations are sorted, so the last one is the latest.
+            if let Some(value) = values.into_no_null_iter().last() {
+                nodes.push((months, value / 100.0));
+            }
+        }
+
+        if nodes.is_empty() {
+            return Err(RustQuantError::MissingInput(format!(
+                "No US Treasury yields on or before {}.",
+                date
+            )));
+        }
+
+        Ok(nodes)
+    }
+
+    fn fetch_ecb(&self, date: Date) -> Result<Vec<(u32, f64)>, RustQuantError> {
+        let codes = OfficialCurve::EcbAaaSpot
+            .tenors()
+            .iter()
+            .map(|(_, code)| *code)
+            .collect::<Vec<&str>>();
+
+        let url = format!(
+            "{}/service/data/YC/B.U2.EUR.4F.G_N_A.SV_C_YM.{}",
+            self.ecb_base_url,
+            codes.join("+")
+        );
+        let query = [
+            (
+                "startPeriod",
+                (date - Duration::days(Self::LOOKBACK_DAYS)).to_string(),
+            ),
+            ("endPeriod", date.to_string()),
+            ("format", "csvdata".to_string()),
+        ];
+
+        let request = reqwest::Client::new().get(url).query(&query).send();
+        let text = tokio_test::block_on(async { request.await?.error_for_status()?.text().await })?;
+
+        parse_ecb_csv(&text, date)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Curve with a node at the maturity of each tenor from `date`.
+fn curve_from_nodes(date: Date, nodes: &[(u32, f64)]) -> Curve<Date> {
+    let mut curve = Curve::<Date>::new();
+
+    for &(months, rate) in nodes {
+        curve.insert(date + Tenor::months(months as i32), rate);
+    }
+
+    curve
+}
+
+/// Latest rate of each tenor on or before `date`, from an ECB `csvdata` response.
+fn parse_ecb_csv(text: &str, date: Date) -> Result<Vec<(u32, f64)>, RustQuantError> {
+    let frame = CsvReadOptions::default()
+        .with_has_header(true)
+        .into_reader_with_file_handle(Cursor::new(text.as_bytes()))
+        .finish()?;
+
+    let codes = frame.column("DATA_TYPE_FM")?.str()?;
+    let periods = frame.column("TIME_PERIOD")?.str()?;
+    let values = frame.column("OBS_VALUE")?.cast(&DataType::Float64)?;
+    let values = values.f64()?;
+
+    let mut nodes = Vec::new();
+
+    for &(months, code) in OfficialCurve::EcbAaaSpot.tenors() {
+        let mut latest: Option<(Date, f64)> = None;
+
+        for i in 0..frame.height() {
+            if codes.get(i) != Some(code) {
+                continue;
+            }
+
+            let (Some(period), Some(value)) = (periods.get(i), values.get(i)) else {
+                continue;
+            };
+            let observed = parse_date(period)?;
+
+            if observed <= date && latest.is_none_or(|(last, _)| observed > last) {
+                latest = Some((observed, value));
+            }
+        }
+
+        if let Some((_, value)) = latest {
+            nodes.push((months, value / 100.0));
+        }
+    }
+
+    if nodes.is_empty() {
+        return Err(RustQuantError::MissingInput(format!(
+            "No ECB spot rates on or before {}.",
+            date
+        )));
+    }
+
+    Ok(nodes)
+}
+
+fn write_nodes(path: &std::path::Path, nodes: &[(u32, f64)]) -> Result<(), RustQuantError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut frame = df!(
+        "months" => nodes.iter().map(|(months, _)| *months).collect::<Vec<u32>>(),
+        "rate" => nodes.iter().map(|(_, rate)| *rate).collect::<Vec<f64>>()
+    )?;
+
+    let mut file = std::fs::File::create(path)?;
+    CsvWriter::new(&mut file).finish(&mut frame)?;
+
+    Ok(())
+}
+
+fn read_nodes(path: &std::path::Path) -> Result<Vec<(u32, f64)>, RustQuantError> {
+    let frame = CsvReadOptions::default()
+        .try_into_reader_with_file_path(Some(path.to_path_buf()))?
+        .finish()?;
+
+    let months = frame.column("months")?.cast(&DataType::UInt32)?;
+    let rates = frame.column("rate")?.cast(&DataType::Float64)?;
+
+    Ok(months
+        .u32()?
+        .into_no_null_iter()
+        .zip(rates.f64()?.into_no_null_iter())
+        .collect())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_official_curves {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    const ECB_CSV: &str = "\
+KEY,FREQ,REF_AREA,CURRENCY,PROVIDER_FM,INSTRUMENT_FM,PROVIDER_FM_ID,DATA_TYPE_FM,TIME_PERIOD,OBS_VALUE,TITLE
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.SR_1Y,B,U2,EUR,4F,G_N_A,SV_C_YM,SR_1Y,2024-03-27,3.41,\"Yield curve spot rate, 1-year maturity\"
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.SR_1Y,B,U2,EUR,4F,G_N_A,SV_C_YM,SR_1Y,2024-03-28,3.38,\"Yield curve spot rate, 1-year maturity\"
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.SR_10Y,B,U2,EUR,4F,G_N_A,SV_C_YM,SR_10Y,2024-03-27,2.49,\"Yield curve spot rate, 10-year maturity\"
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.SR_10Y,B,U2,EUR,4F,G_N_A,SV_C_YM,SR_10Y,2024-03-28,2.46,\"Yield curve spot rate, 10-year maturity\"
+";
+
+    #[test]
+    fn test_parse_ecb_csv() {
+        // Good Friday: the latest observations are from the Thursday.
+        let nodes = parse_ecb_csv(ECB_CSV, date!(2024 - 03 - 29)).unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].0, 12);
+        assert_approx_equal!(nodes[0].1, 0.0338, 1e-12);
+        assert_eq!(nodes[1].0, 120);
+        assert_approx_equal!(nodes[1].1, 0.0246, 1e-12);
+
+        let earlier = parse_ecb_csv(ECB_CSV, date!(2024 - 03 - 27)).unwrap();
+        assert_approx_equal!(earlier[0].1, 0.0341, 1e-12);
+
+        assert!(parse_ecb_csv(ECB_CSV, date!(2024 - 03 - 26)).is_err());
+    }
+
+    #[test]
+    fn test_curve_from_cache() {
+        let dir =
+            std::env::temp_dir().join(format!("rustquant_official_curves_{}", std::process::id()));
+        let today = date!(2024 - 01 - 31);
+        let nodes = vec![(1, 0.0538), (12, 0.0479), (120, 0.0391)];
+
+        write_nodes(&dir.join(format!("us_treasury_par_{}.csv", today)), &nodes).unwrap();
+
+        // No FRED provider: the curve can only come from the cache.
+        let downloader = CurveDownloader::new().with_cache_dir(&dir);
+        let curve = downloader.us_treasury_par(today).unwrap();
+
+        assert_eq!(
+            curve.keys(),
+            vec![
+                date!(2024 - 02 - 29),
+                date!(2025 - 01 - 31),
+                date!(2034 - 01 - 31)
+            ]
+        );
+        assert_eq!(curve.values(), vec![0.0538, 0.0479, 0.0391]);
+
+        assert!(downloader.us_treasury_par(date!(2024 - 02 - 01)).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,431 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Listed option chains.
+//!
+//! An [`OptionChain`] holds the quotes of every listed option on an
+//! underlying at one point in time, and turns them into the inputs of the
+//! volatility surface builders and calibrators:
+//!
+//! - [`OptionChain::volatility_quotes`] for [`LiveSviSurface`](crate::LiveSviSurface);
+//! - [`OptionChain::smile`] for single-expiry calibrators (e.g. SABR);
+//! - [`OptionChain::pillar_surface`] for a [`PillarVolatilitySurface`].
+//!
+//! Only out-of-the-money options are used for volatilities (puts below the
+//! spot, calls at or above it), since they are the more liquid side.
+//! Times to expiry are Actual/365 Fixed year fractions from the valuation date.
+
+use crate::{PillarVolatilitySurface, VolatilityQuote};
+use polars::prelude::*;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::options::{implied_volatility, TypeFlag};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Source of option chains.
+pub trait OptionChainProvider {
+    /// Current option chain of an underlying.
+    fn option_chain(&self, underlying: &str) -> Result<OptionChain, RustQuantError>;
+}
+
+/// Quote of one listed option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionQuote {
+    /// Expiry date.
+    pub expiry: Date,
+
+    /// Strike.
+    pub strike: f64,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// Best bid (zero if there is none).
+    pub bid: f64,
+
+    /// Best ask (zero if there is none).
+    pub ask: f64,
+
+    /// Last traded price.
+    pub last: f64,
+
+    /// Traded volume of the day.
+    pub volume: f64,
+
+    /// Open interest.
+    pub open_interest: f64,
+
+    /// Implied volatility, if quoted or computed.
+    pub implied_volatility: Option<f64>,
+}
+
+/// Quotes of the listed options on an underlying.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChain {
+    /// Symbol of the underlying.
+    pub underlying: String,
+
+    /// Spot price of the underlying.
+    pub spot: f64,
+
+    /// Date of the quotes.
+    pub valuation_date: Date,
+
+    /// Quotes, ordered by expiry, strike, and calls before puts.
+    pub quotes: Vec<OptionQuote>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl OptionQuote {
+    /// Mid price, if there is both a bid and an ask.
+    pub fn mid(&self) -> Option<f64> {
+        (self.bid > 0.0 && self.ask >= self.bid).then_some(0.5 * (self.bid + self.ask))
+    }
+
+    /// Bid-ask spread, if there is both a bid and an ask.
+    pub fn spread(&self) -> Option<f64> {
+        self.mid().map(|_| self.ask - self.bid)
+    }
+
+    /// Whether the option is out of the money (calls at the money included).
+    pub fn is_out_of_the_money(&self, spot: f64) -> bool {
+        match self.option_type {
+            TypeFlag::Call => self.strike >= spot,
+            TypeFlag::Put => self.strike < spot,
+        }
+    }
+}
+
+impl OptionChain {
+    /// Create a chain from quotes in any order.
+    pub fn new(
+        underlying: &str,
+        spot: f64,
+        valuation_date: Date,
+        mut quotes: Vec<OptionQuote>,
+    ) -> Self {
+        quotes.sort_by(|a, b| {
+            a.expiry
+                .cmp(&b.expiry)
+                .then(a.strike.total_cmp(&b.strike))
+                .then((a.option_type == TypeFlag::Put).cmp(&(b.option_type == TypeFlag::Put)))
+        });
+
+        Self {
+            underlying: underlying.to_string(),
+            spot,
+            valuation_date,
+            quotes,
+        }
+    }
+
+    /// Number of quotes.
+    pub fn len(&self) -> usize {
+        self.quotes.len()
+    }
+
+    /// Whether the chain has no quotes.
+    pub fn is_empty(&self) -> bool {
+        self.quotes.is_empty()
+    }
+
+    /// Expiry dates, in increasing order.
+    pub fn expiries(&self) -> Vec<Date> {
+        let mut expiries = self.quotes.iter().map(|q| q.expiry).collect::<Vec<Date>>();
+        expiries.dedup();
+        expiries
+    }
+
+    /// Strikes listed for an expiry, in increasing order.
+    pub fn strikes(&self, expiry: Date) -> Vec<f64> {
+        let mut strikes = self
+            .quotes
+            .iter()
+            .filter(|q| q.expiry == expiry)
+            .map(|q| q.strike)
+            .collect::<Vec<f64>>();
+        strikes.dedup();
+        strikes
+    }
+
+    /// Quotes of one expiry.
+    pub fn expiry_slice(&self, expiry: Date) -> Vec<&OptionQuote> {
+        self.quotes.iter().filter(|q| q.expiry == expiry).collect()
+    }
+
+    /// Actual/365 Fixed year fraction from the valuation date to an expiry.
+    pub fn time_to_expiry(&self, expiry: Date) -> f64 {
+        (expiry - self.valuation_date).whole_days() as f64 / 365.0
+    }
+
+    /// Chain with the quotes satisfying a predicate, e.g. a minimum open
+    /// interest or a maximum spread.
+    pub fn filter<P>(&self, predicate: P) -> Self
+    where
+        P: Fn(&OptionQuote) -> bool,
+    {
+        Self {
+            quotes: self
+                .quotes
+                .iter()
+                .filter(|q| predicate(q))
+                .copied()
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Compute the implied volatility of every quote from its mid price,
+    /// with a continuously compounded `rate`.
+    ///
+    /// Quotes without a mid, expired quotes, and prices outside the
+    /// no-arbitrage bounds get no implied volatility.
+    pub fn with_implied_volatilities(mut self, rate: f64) -> Self {
+        let (spot, valuation_date) = (self.spot, self.valuation_date);
+
+        for quote in &mut self.quotes {
+            let expiry = (quote.expiry - valuation_date).whole_days() as f64 / 365.0;
+
+            quote.implied_volatility = quote
+                .mid()
+                .filter(|_| expiry > 0.0)
+                .map(|mid| {
+                    implied_volatility(mid, spot, quote.strike, expiry, rate, quote.option_type)
+                })
+                .filter(|vol| vol.is_finite() && *vol > 0.0);
+        }
+
+        self
+    }
+
+    /// Out-of-the-money implied volatilities of one expiry: strikes and volatilities.
+    pub fn smile(&self, expiry: Date) -> (Vec<f64>, Vec<f64>) {
+        self.quotes
+            .iter()
+            .filter(|q| q.expiry == expiry && q.is_out_of_the_money(self.spot))
+            .filter_map(|q| q.implied_volatility.map(|vol| (q.strike, vol)))
+            .unzip()
+    }
+
+    /// Out-of-the-money implied volatilities of unexpired options,
+    /// with expiries in years.
+    pub fn volatility_quotes(&self) -> Vec<VolatilityQuote> {
+        self.quotes
+            .iter()
+            .filter(|q| q.expiry > self.valuation_date && q.is_out_of_the_money(self.spot))
+            .filter_map(|q| {
+                q.implied_volatility.map(|volatility| VolatilityQuote {
+                    expiry: self.time_to_expiry(q.expiry),
+                    strike: q.strike,
+                    volatility,
+                })
+            })
+            .collect()
+    }
+
+    /// Pillar surface on the strikes with an out-of-the-money implied
+    /// volatility for every unexpired expiry.
+    ///
+    /// # Errors
+    /// - `RustQuantError::MissingInput` if no strike is quoted on every expiry.
+    pub fn pillar_surface(&self) -> Result<PillarVolatilitySurface, RustQuantError> {
+        let expiries = self
+            .expiries()
+            .into_iter()
+            .filter(|expiry| *expiry > self.valuation_date)
+            .collect::<Vec<Date>>();
+        let smiles = expiries
+            .iter()
+            .map(|expiry| self.smile(*expiry))
+            .collect::<Vec<(Vec<f64>, Vec<f64>)>>();
+
+        let strikes = smiles
+            .first()
+            .map(|(strikes, _)| strikes.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|strike| smiles.iter().all(|(strikes, _)| strikes.contains(strike)))
+            .collect::<Vec<f64>>();
+
+        if strikes.is_empty() {
+            return Err(RustQuantError::MissingInput(format!(
+                "No strike of {} has a volatility on every expiry.",
+                self.underlying
+            )));
+        }
+
+        let volatilities = smiles
+            .iter()
+            .map(|(smile_strikes, vols)| {
+                strikes
+                    .iter()
+                    .map(|strike| vols[smile_strikes.iter().position(|k| k == strike).unwrap()])
+                    .collect()
+            })
+            .collect();
+
+        Ok(PillarVolatilitySurface::new(
+            expiries.iter().map(|e| self.time_to_expiry(*e)).collect(),
+            strikes,
+            volatilities,
+        ))
+    }
+
+    /// Quotes as a frame, with the columns `expiry`, `strike`, `type`
+    /// (`"call"` or `"put"`), `bid`, `ask`, `last`, `volume`,
+    /// `open_interest` and `implied_volatility` (null if unknown).
+    pub fn to_dataframe(&self) -> Result<DataFrame, RustQuantError> {
+        let epoch = Date::from_calendar_date(1970, time::Month::January, 1).unwrap();
+        let column = |f: fn(&OptionQuote) -> f64| self.quotes.iter().map(f).collect::<Vec<f64>>();
+
+        let expiries = self
+            .quotes
+            .iter()
+            .map(|q| (q.expiry - epoch).whole_days() as i32)
+            .collect::<Vec<i32>>();
+        let types = self
+            .quotes
+            .iter()
+            .map(|q| match q.option_type {
+                TypeFlag::Call => "call",
+                TypeFlag::Put => "put",
+            })
+            .collect::<Vec<&str>>();
+        let volatilities = self
+            .quotes
+            .iter()
+            .map(|q| q.implied_volatility)
+            .collect::<Vec<Option<f64>>>();
+
+        Ok(df!(
+            "expiry" => Series::new("expiry".into(), expiries).cast(&DataType::Date)?,
+            "strike" => column(|q| q.strike),
+            "type" => types,
+            "bid" => column(|q| q.bid),
+            "ask" => column(|q| q.ask),
+            "last" => column(|q| q.last),
+            "volume" => column(|q| q.volume),
+            "open_interest" => column(|q| q.open_interest),
+            "implied_volatility" => volatilities
+        )?)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_option_chain {
+    use super::*;
+    use crate::VolatilitySurface;
+    use time::macros::date;
+    use RustQuant_instruments::options::{GeneralisedBlackScholesMerton, Merton73};
+    use RustQuant_utils::assert_approx_equal;
+
+    const TODAY: Date = date!(2024 - 01 - 02);
+    const SPOT: f64 = 100.0;
+    const RATE: f64 = 0.05;
+
+    fn volatility(strike: f64) -> f64 {
+        0.2 + 0.1 * (strike / SPOT).ln().powi(2) - 0.05 * (strike / SPOT).ln()
+    }
+
+    /// Black-Scholes quotes with a 2% spread around the model price.
+    fn chain() -> OptionChain {
+        let mut quotes = Vec::new();
+
+        for expiry in [date!(2024 - 04 - 01), date!(2024 - 12 - 31)] {
+            let t = (expiry - TODAY).whole_days() as f64 / 365.0;
+
+            for strike in [80.0, 90.0, 100.0, 110.0, 120.0] {
+                for option_type in [TypeFlag::Put, TypeFlag::Call] {
+                    let price = Merton73::new(SPOT, RATE, 0.0, volatility(strike)).price(
+                        strike,
+                        t,
+                        option_type,
+                    );
+
+                    quotes.push(OptionQuote {
+                        expiry,
+                        strike,
+                        option_type,
+                        bid: price * 0.99,
+                        ask: price * 1.01,
+                        last: price,
+                        volume: 10.0,
+                        open_interest: strike,
+                        implied_volatility: None,
+                    });
+                }
+            }
+        }
+
+        OptionChain::new("XYZ", SPOT, TODAY, quotes).with_implied_volatilities(RATE)
+    }
+
+    #[test]
+    fn test_structure() {
+        let chain = chain();
+
+        assert_eq!(chain.len(), 20);
+        assert_eq!(
+            chain.expiries(),
+            vec![date!(2024 - 04 - 01), date!(2024 - 12 - 31)]
+        );
+        assert_eq!(
+            chain.strikes(date!(2024 - 04 - 01)),
+            vec![80.0, 90.0, 100.0, 110.0, 120.0]
+        );
+        assert_eq!(chain.quotes[0].option_type, TypeFlag::Call);
+        assert_eq!(chain.filter(|q| q.open_interest >= 100.0).len(), 12);
+    }
+
+    #[test]
+    fn test_implied_volatilities() {
+        let chain = chain();
+        let (strikes, vols) = chain.smile(date!(2024 - 12 - 31));
+
+        assert_eq!(strikes, vec![80.0, 90.0, 100.0, 110.0, 120.0]);
+        for (strike, vol) in strikes.iter().zip(&vols) {
+            assert_approx_equal!(*vol, volatility(*strike), 1e-6);
+        }
+
+        let quotes = chain.volatility_quotes();
+        assert_eq!(quotes.len(), 10);
+        assert_approx_equal!(quotes[0].expiry, 90.0 / 365.0, 1e-12);
+
+        let surface = chain.pillar_surface().unwrap();
+        assert_eq!(surface.strikes(), vec![80.0, 90.0, 100.0, 110.0, 120.0]);
+        assert_approx_equal!(surface.volatility(0.5, 110.0), volatility(110.0), 1e-6);
+    }
+
+    #[test]
+    fn test_missing_quotes() {
+        let mut quote = chain().quotes[0];
+        quote.bid = 0.0;
+
+        assert!(quote.mid().is_none());
+        assert!(quote.spread().is_none());
+
+        let chain =
+            OptionChain::new("XYZ", SPOT, TODAY, vec![quote]).with_implied_volatilities(RATE);
+        assert!(chain.quotes[0].implied_volatility.is_none());
+        assert!(chain.pillar_surface().is_err());
+
+        let frame = chain.to_dataframe().unwrap();
+        assert_eq!(frame.column("implied_volatility").unwrap().null_count(), 1);
+    }
+}
@@ -0,0 +1,501 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Typed OHLCV price series.
+//!
+//! A [`PriceSeries`] is a date-ordered list of [`Bar`]s. It converts to and
+//! from the price history frames of [`YahooFinanceData`](crate::YahooFinanceData)
+//! and the [`DataProvider`](crate::DataProvider)s (columns `date`, `open`,
+//! `high`, `low`, `close`, `volume` and `adjusted`).
+//!
+//! Returns, volatilities and rolling statistics are computed on the
+//! adjusted close, so that dividends and splits do not show up as jumps.
+
+use crate::ReturnsType;
+use polars::prelude::*;
+use time::{Date, Month};
+use RustQuant_error::RustQuantError;
+use RustQuant_time::Frequency;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// One bar of a price series.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bar {
+    /// Date of the bar (the last trading day, for resampled bars).
+    pub date: Date,
+
+    /// Opening price.
+    pub open: f64,
+
+    /// Highest price.
+    pub high: f64,
+
+    /// Lowest price.
+    pub low: f64,
+
+    /// Closing price.
+    pub close: f64,
+
+    /// Traded volume.
+    pub volume: f64,
+
+    /// Closing price adjusted for dividends and splits.
+    pub adjusted: f64,
+}
+
+/// Date-ordered series of bars, with at most one bar per date.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PriceSeries {
+    bars: Vec<Bar>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PriceSeries {
+    /// Create a series from bars in any order.
+    /// If two bars have the same date, the last one is kept.
+    pub fn new(mut bars: Vec<Bar>) -> Self {
+        // Stable sort, so the last bar of a date comes last.
+        bars.sort_by_key(|bar| bar.date);
+        bars.reverse();
+        bars.dedup_by_key(|bar| bar.date);
+        bars.reverse();
+
+        Self { bars }
+    }
+
+    /// Read a price history frame. The `adjusted` column is optional
+    /// (the close is used instead); the other columns are required.
+    pub fn from_dataframe(frame: &DataFrame) -> Result<Self, RustQuantError> {
+        let epoch = Date::from_calendar_date(1970, Month::January, 1).unwrap();
+
+        let dates = frame.column("date")?.cast(&DataType::Int32)?;
+        let dates = dates
+            .i32()?
+            .into_iter()
+            .map(|days| {
+                days.map(|days| epoch + time::Duration::days(days as i64))
+                    .ok_or_else(|| RustQuantError::MissingInput("Missing date.".to_string()))
+            })
+            .collect::<Result<Vec<Date>, RustQuantError>>()?;
+
+        let column = |name: &str| -> Result<Vec<f64>, RustQuantError> {
+            let values = frame.column(name)?.cast(&DataType::Float64)?;
+
+            Ok(values
+                .f64()?
+                .into_iter()
+                .map(|value| value.unwrap_or(f64::NAN))
+                .collect())
+        };
+
+        let open = column("open")?;
+        let high = column("high")?;
+        let low = column("low")?;
+        let close = column("close")?;
+        let volume = column("volume")?;
+        let adjusted = match frame.column("adjusted") {
+            Ok(_) => column("adjusted")?,
+            Err(_) => close.clone(),
+        };
+
+        let bars = (0..dates.len())
+            .map(|i| Bar {
+                date: dates[i],
+                open: open[i],
+                high: high[i],
+                low: low[i],
+                close: close[i],
+                volume: volume[i],
+                adjusted: adjusted[i],
+            })
+            .collect();
+
+        Ok(Self::new(bars))
+    }
+
+    /// Price history frame of the series.
+    pub fn to_dataframe(&self) -> Result<DataFrame, RustQuantError> {
+        let epoch = Date::from_calendar_date(1970, Month::January, 1).unwrap();
+
+        let column = |f: fn(&Bar) -> f64| self.bars.iter().map(f).collect::<Vec<f64>>();
+        let dates = self
+            .bars
+            .iter()
+            .map(|bar| (bar.date - epoch).whole_days() as i32)
+            .collect::<Vec<i32>>();
+
+        Ok(df!(
+            "date" => Series::new("date".into(), dates).cast(&DataType::Date)?,
+            "open" => column(|bar| bar.open),
+            "high" => column(|bar| bar.high),
+            "low" => column(|bar| bar.low),
+            "close" => column(|bar| bar.close),
+            "volume" => column(|bar| bar.volume),
+            "adjusted" => column(|bar| bar.adjusted)
+        )?)
+    }
+
+    /// Bars of the series, in date order.
+    pub fn bars(&self) -> &[Bar] {
+        &self.bars
+    }
+
+    /// Number of bars.
+    pub fn len(&self) -> usize {
+        self.bars.len()
+    }
+
+    /// Whether the series has no bars.
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// Dates of the bars.
+    pub fn dates(&self) -> Vec<Date> {
+        self.bars.iter().map(|bar| bar.date).collect()
+    }
+
+    /// Closing prices.
+    pub fn closes(&self) -> Vec<f64> {
+        self.bars.iter().map(|bar| bar.close).collect()
+    }
+
+    /// Adjusted closing prices.
+    pub fn adjusted(&self) -> Vec<f64> {
+        self.bars.iter().map(|bar| bar.adjusted).collect()
+    }
+
+    /// Bars from `start` to `end` inclusive.
+    pub fn between(&self, start: Date, end: Date) -> Self {
+        Self {
+            bars: self
+                .bars
+                .iter()
+                .filter(|bar| bar.date >= start && bar.date <= end)
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Aggregate the bars of each week, month, quarter, half-year or year.
+    ///
+    /// A resampled bar opens at the first open, closes at the last close,
+    /// spans the highest high and lowest low, sums the volumes, and is dated
+    /// on the last trading day of the period. Weeks are ISO weeks.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` for other frequencies.
+    pub fn resample(&self, frequency: Frequency) -> Result<Self, RustQuantError> {
+        let period: fn(Date) -> (i32, u8) = match frequency {
+            Frequency::Daily => return Ok(self.clone()),
+            Frequency::Weekly => |date| {
+                let (year, week, _) = date.to_iso_week_date();
+                (year, week)
+            },
+            Frequency::Monthly => |date| (date.year(), date.month() as u8),
+            Frequency::Quarterly => |date| (date.year(), (date.month() as u8 - 1) / 3),
+            Frequency::SemiAnnually => |date| (date.year(), (date.month() as u8 - 1) / 6),
+            Frequency::Annually => |date| (date.year(), 0),
+            _ => {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Cannot resample to {:?} bars.",
+                    frequency
+                )))
+            }
+        };
+
+        let mut bars: Vec<Bar> = Vec::new();
+
+        for bar in &self.bars {
+            match bars.last_mut() {
+                Some(last) if period(last.date) == period(bar.date) => {
+                    last.date = bar.date;
+                    last.high = last.high.max(bar.high);
+                    last.low = last.low.min(bar.low);
+                    last.close = bar.close;
+                    last.volume += bar.volume;
+                    last.adjusted = bar.adjusted;
+                }
+                _ => bars.push(*bar),
+            }
+        }
+
+        Ok(Self { bars })
+    }
+
+    /// Returns of the adjusted close, dated at the end of each period.
+    pub fn returns(&self, returns_type: ReturnsType) -> Vec<(Date, f64)> {
+        self.bars
+            .windows(2)
+            .map(|pair| {
+                let (previous, current) = (pair[0].adjusted, pair[1].adjusted);
+
+                let value = match returns_type {
+                    ReturnsType::Arithmetic => current / previous - 1.0,
+                    ReturnsType::Logarithmic => (current / previous).ln(),
+                    ReturnsType::Absolute => current - previous,
+                };
+
+                (pair[1].date, value)
+            })
+            .collect()
+    }
+
+    /// Annualised realised volatility: the sample standard deviation of the
+    /// log returns, times the square root of `periods_per_year`
+    /// (e.g. 252 for daily bars, 52 for weekly bars).
+    ///
+    /// `None` with fewer than two returns.
+    pub fn realised_volatility(&self, periods_per_year: f64) -> Option<f64> {
+        let returns = self
+            .returns(ReturnsType::Logarithmic)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect::<Vec<f64>>();
+
+        sample_std(&returns).map(|std| std * periods_per_year.sqrt())
+    }
+
+    /// Rolling mean of the adjusted close over `window` bars,
+    /// dated at the last bar of each window.
+    pub fn rolling_mean(&self, window: usize) -> Vec<(Date, f64)> {
+        self.rolling(window, |prices| {
+            Some(prices.iter().sum::<f64>() / prices.len() as f64)
+        })
+    }
+
+    /// Rolling sample standard deviation of the adjusted close over `window` bars.
+    pub fn rolling_std(&self, window: usize) -> Vec<(Date, f64)> {
+        self.rolling(window, sample_std)
+    }
+
+    /// Rolling annualised realised volatility over `window` log returns.
+    pub fn rolling_volatility(&self, window: usize, periods_per_year: f64) -> Vec<(Date, f64)> {
+        let returns = self.returns(ReturnsType::Logarithmic);
+
+        if window < 2 {
+            return Vec::new();
+        }
+
+        returns
+            .windows(window)
+            .filter_map(|slice| {
+                let values = slice.iter().map(|(_, value)| *value).collect::<Vec<f64>>();
+                let date = slice[window - 1].0;
+
+                sample_std(&values).map(|std| (date, std * periods_per_year.sqrt()))
+            })
+            .collect()
+    }
+
+    fn rolling<F>(&self, window: usize, statistic: F) -> Vec<(Date, f64)>
+    where
+        F: Fn(&[f64]) -> Option<f64>,
+    {
+        if window == 0 {
+            return Vec::new();
+        }
+
+        let prices = self.adjusted();
+
+        prices
+            .windows(window)
+            .zip(&self.bars[window - 1..])
+            .filter_map(|(slice, bar)| statistic(slice).map(|value| (bar.date, value)))
+            .collect()
+    }
+}
+
+impl From<Vec<Bar>> for PriceSeries {
+    fn from(bars: Vec<Bar>) -> Self {
+        Self::new(bars)
+    }
+}
+
+impl TryFrom<&DataFrame> for PriceSeries {
+    type Error = RustQuantError;
+
+    fn try_from(frame: &DataFrame) -> Result<Self, Self::Error> {
+        Self::from_dataframe(frame)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sample standard deviation (`None` with fewer than two values).
+fn sample_std(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    Some(variance.sqrt())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_price_series {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn bar(date: Date, close: f64) -> Bar {
+        Bar {
+            date,
+            open: close - 0.5,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 100.0,
+            adjusted: close,
+        }
+    }
+
+    /// Business days from Thursday 2024-01-25 to Tuesday 2024-02-06.
+    fn series() -> PriceSeries {
+        let dates = [
+            date!(2024 - 01 - 25),
+            date!(2024 - 01 - 26),
+            date!(2024 - 01 - 29),
+            date!(2024 - 01 - 30),
+            date!(2024 - 01 - 31),
+            date!(2024 - 02 - 01),
+            date!(2024 - 02 - 02),
+            date!(2024 - 02 - 05),
+            date!(2024 - 02 - 06),
+        ];
+
+        dates
+            .iter()
+            .enumerate()
+            .map(|(i, date)| bar(*date, 100.0 + i as f64))
+            .collect::<Vec<Bar>>()
+            .into()
+    }
+
+    #[test]
+    fn test_new_sorts_and_deduplicates() {
+        let series = PriceSeries::new(vec![
+            bar(date!(2024 - 01 - 03), 3.0),
+            bar(date!(2024 - 01 - 02), 2.0),
+            bar(date!(2024 - 01 - 03), 4.0),
+        ]);
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.closes(), vec![2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_dataframe_round_trip() {
+        let series = series();
+        let frame = series.to_dataframe().unwrap();
+
+        assert_eq!(
+            frame.get_column_names_str(),
+            ["date", "open", "high", "low", "close", "volume", "adjusted"]
+        );
+        assert_eq!(PriceSeries::try_from(&frame).unwrap(), series);
+
+        let unadjusted = frame.drop("adjusted").unwrap();
+        assert_eq!(
+            PriceSeries::from_dataframe(&unadjusted).unwrap().adjusted(),
+            series.closes()
+        );
+    }
+
+    #[test]
+    fn test_resample() {
+        let series = series();
+
+        let weekly = series.resample(Frequency::Weekly).unwrap();
+        assert_eq!(
+            weekly.dates(),
+            vec![
+                date!(2024 - 01 - 26),
+                date!(2024 - 02 - 02),
+                date!(2024 - 02 - 06)
+            ]
+        );
+        assert_eq!(
+            weekly.bars()[1],
+            Bar {
+                date: date!(2024 - 02 - 02),
+                open: 101.5,
+                high: 107.0,
+                low: 101.0,
+                close: 106.0,
+                volume: 500.0,
+                adjusted: 106.0,
+            }
+        );
+
+        let monthly = series.resample(Frequency::Monthly).unwrap();
+        assert_eq!(monthly.closes(), vec![104.0, 108.0]);
+        assert_eq!(monthly.bars()[1].volume, 400.0);
+
+        assert!(series.resample(Frequency::BiWeekly).is_err());
+    }
+
+    #[test]
+    fn test_returns_and_volatility() {
+        let series = series();
+
+        let arithmetic = series.returns(ReturnsType::Arithmetic);
+        assert_eq!(arithmetic.len(), 8);
+        assert_eq!(arithmetic[0].0, date!(2024 - 01 - 26));
+        assert_approx_equal!(arithmetic[0].1, 0.01, 1e-12);
+        assert_approx_equal!(
+            series.returns(ReturnsType::Logarithmic)[0].1,
+            (101.0_f64 / 100.0).ln(),
+            1e-12
+        );
+        assert_eq!(series.returns(ReturnsType::Absolute)[0].1, 1.0);
+
+        let log_returns = (0..8)
+            .map(|i| ((101.0 + i as f64) / (100.0 + i as f64)).ln())
+            .collect::<Vec<f64>>();
+        let expected = sample_std(&log_returns).unwrap() * 252_f64.sqrt();
+        assert_approx_equal!(series.realised_volatility(252.0).unwrap(), expected, 1e-12);
+        assert!(PriceSeries::default().realised_volatility(252.0).is_none());
+
+        let rolling = series.rolling_volatility(8, 252.0);
+        assert_eq!(rolling.len(), 1);
+        assert_approx_equal!(rolling[0].1, expected, 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_statistics() {
+        let series = series();
+
+        let mean = series.rolling_mean(3);
+        assert_eq!(mean.len(), 7);
+        assert_eq!(mean[0], (date!(2024 - 01 - 29), 101.0));
+        assert_eq!(mean[6], (date!(2024 - 02 - 06), 107.0));
+
+        let std = series.rolling_std(3);
+        assert!(std.iter().all(|(_, value)| (value - 1.0).abs() < 1e-12));
+
+        assert!(series.rolling_mean(0).is_empty());
+        assert!(series.rolling_mean(10).is_empty());
+    }
+}
@@ -0,0 +1,686 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Market data providers.
+//!
+//! Every [`DataProvider`] returns Polars DataFrames with the same schema,
+//! whichever service the data comes from:
+//!
+//! - price histories have the columns `date`, `open`, `high`, `low`,
+//!   `close`, `volume` and `adjusted`, as in
+//!   [`YahooFinanceData::price_history`](crate::YahooFinanceData);
+//! - series (rates and macroeconomic data) have the columns `date` and
+//!   `value`, with missing observations dropped.
+//!
+//! Providers are configured with an API key, either directly or from an
+//! environment variable, and space out their requests to stay within the
+//! service's rate limit.
+//!
+//! ```no_run
+//! use RustQuant_data::{DataProvider, FredProvider};
+//! use time::macros::date;
+//!
+//! let fred = FredProvider::from_env().unwrap();
+//! let ten_year = fred
+//!     .series("DGS10", date!(2024 - 01 - 01), date!(2024 - 12 - 31))
+//!     .unwrap();
+//! ```
+
+use crate::parse_date;
+use crate::{Bar, PriceSeries};
+use polars::prelude::*;
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use time::Date;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, TRAITS, AND ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Source of daily price histories and time series.
+pub trait DataProvider {
+    /// Name of the provider.
+    fn name(&self) -> &str;
+
+    /// Daily OHLCV bars for a symbol, from `start` to `end` inclusive.
+    fn price_history(
+        &self,
+        symbol: &str,
+        start: Date,
+        end: Date,
+    ) -> Result<DataFrame, RustQuantError>;
+
+    /// Observations of a series, from `start` to `end` inclusive.
+    fn series(&self, id: &str, start: Date, end: Date) -> Result<DataFrame, RustQuantError>;
+}
+
+/// Spaces out requests to at most a given number per minute.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+/// [Alpha Vantage](https://www.alphavantage.co) provider.
+///
+/// Price histories come from `TIME_SERIES_DAILY_ADJUSTED`, and series are
+/// the economic indicator functions, e.g. `"REAL_GDP"` or `"FEDERAL_FUNDS_RATE"`.
+#[derive(Debug)]
+pub struct AlphaVantageProvider {
+    /// API key.
+    pub api_key: String,
+
+    /// Base URL of the API.
+    pub base_url: String,
+
+    rate_limiter: RateLimiter,
+}
+
+/// [Polygon.io](https://polygon.io) provider, for daily aggregates
+/// (split and dividend adjusted). Series are not available.
+#[derive(Debug)]
+pub struct PolygonProvider {
+    /// API key.
+    pub api_key: String,
+
+    /// Base URL of the API.
+    pub base_url: String,
+
+    rate_limiter: RateLimiter,
+}
+
+/// [FRED](https://fred.stlouisfed.org) provider, for rates and
+/// macroeconomic series, e.g. `"DGS10"` or `"SOFR"`. Price histories are
+/// not available.
+#[derive(Debug)]
+pub struct FredProvider {
+    /// API key.
+    pub api_key: String,
+
+    /// Base URL of the API.
+    pub base_url: String,
+
+    rate_limiter: RateLimiter,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RateLimiter {
+    /// Allow `requests` requests per minute (at least one).
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(60) / requests.max(1),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Minimum time between two requests.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Block until the next request is allowed, and record it.
+    pub fn wait(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+
+            if elapsed < self.interval {
+                std::thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
+
+macro_rules! impl_provider_config {
+    ($provider:ident, $base_url:expr, $env_var:expr, $requests_per_minute:expr) => {
+        impl $provider {
+            #[doc = concat!("Environment variable read by [`", stringify!($provider), "::from_env`].")]
+            pub const API_KEY_VARIABLE: &'static str = $env_var;
+
+            /// Create a provider with the default base URL and rate limit.
+            pub fn new(api_key: &str) -> Self {
+                Self {
+                    api_key: api_key.to_string(),
+                    base_url: $base_url.to_string(),
+                    rate_limiter: RateLimiter::per_minute($requests_per_minute),
+                }
+            }
+
+            /// Create a provider with the API key from the environment.
+            ///
+            /// # Errors
+            /// - `RustQuantError::MissingInput` if the variable is not set.
+            pub fn from_env() -> Result<Self, RustQuantError> {
+                std::env::var(Self::API_KEY_VARIABLE)
+                    .map(|api_key| Self::new(&api_key))
+                    .map_err(|_| {
+                        RustQuantError::MissingInput(format!(
+                            "Set {} to an API key.",
+                            Self::API_KEY_VARIABLE
+                        ))
+                    })
+            }
+
+            /// Use another base URL, e.g. a proxy.
+            pub fn with_base_url(mut self, base_url: &str) -> Self {
+                self.base_url = base_url.trim_end_matches('/').to_string();
+                self
+            }
+
+            /// Limit requests to `requests` per minute, e.g. for a paid plan.
+            pub fn with_rate_limit(mut self, requests: u32) -> Self {
+                self.rate_limiter = RateLimiter::per_minute(requests);
+                self
+            }
+
+            /// The provider's rate limiter.
+            pub fn rate_limiter(&self) -> &RateLimiter {
+                &self.rate_limiter
+            }
+
+            /// Wait for the rate limiter, then get a JSON response.
+            fn get(&self, url: &str, query: &[(&str, String)]) -> Result<Value, RustQuantError> {
+                self.rate_limiter.wait();
+
+                let request = reqwest::Client::new().get(url).query(query).send();
+
+                tokio_test::block_on(async { request.await?.error_for_status()?.json().await })
+                    .map_err(RustQuantError::from)
+            }
+        }
+    };
+}
+
+impl_provider_config!(
+    AlphaVantageProvider,
+    "https://www.alphavantage.co",
+    "ALPHA_VANTAGE_API_KEY",
+    5
+);
+impl_provider_config!(
+    PolygonProvider,
+    "https://api.polygon.io",
+    "POLYGON_API_KEY",
+    5
+);
+impl_provider_config!(
+    FredProvider,
+    "https://api.stlouisfed.org",
+    "FRED_API_KEY",
+    120
+);
+
+impl DataProvider for AlphaVantageProvider {
+    fn name(&self) -> &str {
+        "Alpha Vantage"
+    }
+
+    fn price_history(
+        &self,
+        symbol: &str,
+        start: Date,
+        end: Date,
+    ) -> Result<DataFrame, RustQuantError> {
+        let response = self.get(
+            &format!("{}/query", self.base_url),
+            &[
+                ("function", "TIME_SERIES_DAILY_ADJUSTED".to_string()),
+                ("symbol", symbol.to_string()),
+                ("outputsize", "full".to_string()),
+                ("apikey", self.api_key.clone()),
+            ],
+        )?;
+
+        parse_alpha_vantage_daily(&response, start, end)
+    }
+
+    fn series(&self, id: &str, start: Date, end: Date) -> Result<DataFrame, RustQuantError> {
+        let response = self.get(
+            &format!("{}/query", self.base_url),
+            &[
+                ("function", id.to_string()),
+                ("apikey", self.api_key.clone()),
+            ],
+        )?;
+
+        parse_alpha_vantage_series(&response, start, end)
+    }
+}
+
+impl DataProvider for PolygonProvider {
+    fn name(&self) -> &str {
+        "Polygon.io"
+    }
+
+    fn price_history(
+        &self,
+        symbol: &str,
+        start: Date,
+        end: Date,
+    ) -> Result<DataFrame, RustQuantError> {
+        let response = self.get(
+            &format!(
+                "{}/v2/aggs/ticker/{}/range/1/day/{}/{}",
+                self.base_url, symbol, start, end
+            ),
+            &[
+                ("adjusted", "true".to_string()),
+                ("sort", "asc".to_string()),
+                ("limit", "50000".to_string()),
+                ("apiKey", self.api_key.clone()),
+            ],
+        )?;
+
+        parse_polygon_aggregates(&response)
+    }
+
+    fn series(&self, id: &str, _start: Date, _end: Date) -> Result<DataFrame, RustQuantError> {
+        Err(RustQuantError::NotImplemented(format!(
+            "Polygon.io does not provide series ({}).",
+            id
+        )))
+    }
+}
+
+impl DataProvider for FredProvider {
+    fn name(&self) -> &str {
+        "FRED"
+    }
+
+    fn price_history(
+        &self,
+        symbol: &str,
+        _start: Date,
+        _end: Date,
+    ) -> Result<DataFrame, RustQuantError> {
+        Err(RustQuantError::NotImplemented(format!(
+            "FRED does not provide price histories ({}).",
+            symbol
+        )))
+    }
+
+    fn series(&self, id: &str, start: Date, end: Date) -> Result<DataFrame, RustQuantError> {
+        let response = self.get(
+            &format!("{}/fred/series/observations", self.base_url),
+            &[
+                ("series_id", id.to_string()),
+                ("observation_start", start.to_string()),
+                ("observation_end", end.to_string()),
+                ("file_type", "json".to_string()),
+                ("api_key", self.api_key.clone()),
+            ],
+        )?;
+
+        parse_fred_observations(&response)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Series frame, sorted by date.
+fn series_frame(mut observations: Vec<(Date, f64)>) -> Result<DataFrame, RustQuantError> {
+    observations.sort_by_key(|(date, _)| *date);
+
+    let (dates, values): (Vec<i32>, Vec<f64>) = observations
+        .into_iter()
+        .map(|(date, value)| (days_since_epoch(date), value))
+        .unzip();
+
+    Ok(df!(
+        "date" => Series::new("date".into(), dates).cast(&DataType::Date)?,
+        "value" => values
+    )?)
+}
+
+/// Polars dates are days since 1970-01-01.
+fn days_since_epoch(date: Date) -> i32 {
+    (date - Date::from_calendar_date(1970, time::Month::January, 1).unwrap()).whole_days() as i32
+}
+
+/// A number that may be quoted as a string, with `"."` or an empty string
+/// for a missing value.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+pub(crate) fn field(object: &Value, key: &str) -> Result<f64, RustQuantError> {
+    object
+        .get(key)
+        .and_then(number)
+        .ok_or_else(|| RustQuantError::MissingInput(format!("No {} in the response.", key)))
+}
+
+/// Alpha Vantage reports errors and throttling in the body of a 200 response.
+fn alpha_vantage_error(response: &Value) -> Option<RustQuantError> {
+    ["Error Message", "Note", "Information"]
+        .iter()
+        .find_map(|key| response.get(*key).and_then(Value::as_str))
+        .map(|message| RustQuantError::ComputationError(format!("Alpha Vantage: {}", message)))
+}
+
+fn parse_alpha_vantage_daily(
+    response: &Value,
+    start: Date,
+    end: Date,
+) -> Result<DataFrame, RustQuantError> {
+    if let Some(error) = alpha_vantage_error(response) {
+        return Err(error);
+    }
+
+    let series = response
+        .get("Time Series (Daily)")
+        .and_then(Value::as_object)
+        .ok_or_else(|| RustQuantError::MissingInput("No daily time series.".to_string()))?;
+
+    let mut bars = Vec::with_capacity(series.len());
+
+    for (date, bar) in series {
+        let date = parse_date(date)?;
+
+        if date < start || date > end {
+            continue;
+        }
+
+        bars.push(Bar {
+            date,
+            open: field(bar, "1. open")?,
+            high: field(bar, "2. high")?,
+            low: field(bar, "3. low")?,
+            close: field(bar, "4. close")?,
+            adjusted: field(bar, "5. adjusted close")?,
+            volume: field(bar, "6. volume")?,
+        });
+    }
+
+    PriceSeries::new(bars).to_dataframe()
+}
+
+fn parse_alpha_vantage_series(
+    response: &Value,
+    start: Date,
+    end: Date,
+) -> Result<DataFrame, RustQuantError> {
+    if let Some(error) = alpha_vantage_error(response) {
+        return Err(error);
+    }
+
+    let data = response
+        .get("data")
+        .and_then(Value::as_array)
+        .ok_or_else(|| RustQuantError::MissingInput("No series data.".to_string()))?;
+
+    let mut observations = Vec::with_capacity(data.len());
+
+    for observation in data {
+        let date = observation
+            .get("date")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RustQuantError::MissingInput("Observation without a date.".to_string()))
+            .and_then(parse_date)?;
+
+        if let Some(value) = observation.get("value").and_then(number) {
+            if date >= start && date <= end {
+                observations.push((date, value));
+            }
+        }
+    }
+
+    series_frame(observations)
+}
+
+fn parse_polygon_aggregates(response: &Value) -> Result<DataFrame, RustQuantError> {
+    if let Some(Value::String(status)) = response.get("status") {
+        if status == "ERROR" || status == "NOT_AUTHORIZED" {
+            let message = response
+                .get("error")
+                .or_else(|| response.get("message"))
+                .and_then(Value::as_str)
+                .unwrap_or(status);
+
+            return Err(RustQuantError::ComputationError(format!(
+                "Polygon.io: {}",
+                message
+            )));
+        }
+    }
+
+    // No results key at all means no trading days in the range.
+    let empty = Vec::new();
+    let results = response
+        .get("results")
+        .and_then(Value::as_array)
+        .unwrap_or(&empty);
+
+    let mut bars = Vec::with_capacity(results.len());
+
+    for result in results {
+        // Timestamps are milliseconds since the epoch, at the start of the day.
+        let millis = field(result, "t")? as i64;
+        let date = Date::from_calendar_date(1970, time::Month::January, 1).unwrap()
+            + time::Duration::days(millis.div_euclid(86_400_000));
+
+        let close = field(result, "c")?;
+
+        bars.push(Bar {
+            date,
+            open: field(result, "o")?,
+            high: field(result, "h")?,
+            low: field(result, "l")?,
+            close,
+            volume: field(result, "v")?,
+            adjusted: close,
+        });
+    }
+
+    PriceSeries::new(bars).to_dataframe()
+}
+
+fn parse_fred_observations(response: &Value) -> Result<DataFrame, RustQuantError> {
+    if let Some(message) = response.get("error_message").and_then(Value::as_str) {
+        return Err(RustQuantError::ComputationError(format!(
+            "FRED: {}",
+            message
+        )));
+    }
+
+    let data = response
+        .get("observations")
+        .and_then(Value::as_array)
+        .ok_or_else(|| RustQuantError::MissingInput("No observations.".to_string()))?;
+
+    let mut observations = Vec::with_capacity(data.len());
+
+    for observation in data {
+        let date = observation
+            .get("date")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RustQuantError::MissingInput("Observation without a date.".to_string()))
+            .and_then(parse_date)?;
+
+        // FRED marks missing observations (e.g. holidays) with ".".
+        if let Some(value) = observation.get("value").and_then(number) {
+            observations.push((date, value));
+        }
+    }
+
+    series_frame(observations)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_providers {
+    use super::*;
+    use serde_json::json;
+    use time::macros::date;
+
+    fn column(frame: &DataFrame, name: &str) -> Vec<f64> {
+        frame
+            .column(name)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_alpha_vantage_daily() {
+        let response = json!({
+            "Meta Data": { "2. Symbol": "IBM" },
+            "Time Series (Daily)": {
+                "2024-01-03": {
+                    "1. open": "160.00", "2. high": "161.50", "3. low": "159.00",
+                    "4. close": "160.10", "5. adjusted close": "155.20",
+                    "6. volume": "4000000", "7. dividend amount": "0.0000"
+                },
+                "2024-01-02": {
+                    "1. open": "161.00", "2. high": "162.00", "3. low": "160.00",
+                    "4. close": "161.50", "5. adjusted close": "156.55",
+                    "6. volume": "3500000", "7. dividend amount": "0.0000"
+                },
+                "2023-12-29": {
+                    "1. open": "162.00", "2. high": "163.00", "3. low": "161.00",
+                    "4. close": "162.00", "5. adjusted close": "157.00",
+                    "6. volume": "3000000", "7. dividend amount": "0.0000"
+                }
+            }
+        });
+
+        let frame =
+            parse_alpha_vantage_daily(&response, date!(2024 - 01 - 01), date!(2024 - 01 - 31))
+                .unwrap();
+
+        assert_eq!(
+            frame.get_column_names_str(),
+            ["date", "open", "high", "low", "close", "volume", "adjusted"]
+        );
+        assert_eq!(column(&frame, "close"), vec![161.5, 160.1]);
+        assert_eq!(column(&frame, "adjusted"), vec![156.55, 155.2]);
+
+        let throttled = json!({ "Note": "Thank you for using Alpha Vantage!" });
+        assert!(parse_alpha_vantage_daily(
+            &throttled,
+            date!(2024 - 01 - 01),
+            date!(2024 - 01 - 31)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_polygon_aggregates() {
+        let response = json!({
+            "ticker": "AAPL",
+            "status": "OK",
+            "resultsCount": 2,
+            "results": [
+                { "v": 5.0e7, "o": 187.15, "c": 185.64, "h": 188.44, "l": 183.885, "t": 1704171600000_i64 },
+                { "v": 5.8e7, "o": 184.22, "c": 184.25, "h": 185.88, "l": 183.43, "t": 1704258000000_i64 }
+            ]
+        });
+
+        let frame = parse_polygon_aggregates(&response).unwrap();
+        let dates = frame.column("date").unwrap().date().unwrap().clone();
+
+        assert_eq!(frame.height(), 2);
+        // 2024-01-02 and 2024-01-03.
+        assert_eq!(
+            dates.into_no_null_iter().collect::<Vec<i32>>(),
+            vec![19724, 19725]
+        );
+        assert_eq!(column(&frame, "adjusted"), column(&frame, "close"));
+
+        let empty = json!({ "status": "OK", "resultsCount": 0 });
+        assert_eq!(parse_polygon_aggregates(&empty).unwrap().height(), 0);
+
+        let unauthorised = json!({ "status": "NOT_AUTHORIZED", "message": "Unknown API Key" });
+        assert!(parse_polygon_aggregates(&unauthorised).is_err());
+    }
+
+    #[test]
+    fn test_fred_and_alpha_vantage_series() {
+        let fred = json!({
+            "observations": [
+                { "date": "2024-01-01", "value": "." },
+                { "date": "2024-01-02", "value": "3.95" },
+                { "date": "2024-01-03", "value": "3.91" }
+            ]
+        });
+
+        let frame = parse_fred_observations(&fred).unwrap();
+        assert_eq!(frame.get_column_names_str(), ["date", "value"]);
+        assert_eq!(column(&frame, "value"), vec![3.95, 3.91]);
+
+        let alpha_vantage = json!({
+            "name": "Effective Federal Funds Rate",
+            "data": [
+                { "date": "2024-03-01", "value": "5.33" },
+                { "date": "2024-02-01", "value": "5.33" },
+                { "date": "2023-12-01", "value": "5.33" }
+            ]
+        });
+
+        let frame = parse_alpha_vantage_series(
+            &alpha_vantage,
+            date!(2024 - 01 - 01),
+            date!(2024 - 12 - 31),
+        )
+        .unwrap();
+        assert_eq!(frame.height(), 2);
+
+        assert!(FredProvider::new("key")
+            .price_history("AAPL", date!(2024 - 01 - 01), date!(2024 - 12 - 31))
+            .is_err());
+    }
+
+    #[test]
+    fn test_configuration_and_rate_limit() {
+        let provider = PolygonProvider::new("key")
+            .with_base_url("http://localhost:8080/")
+            .with_rate_limit(1200);
+
+        assert_eq!(provider.base_url, "http://localhost:8080");
+        assert_eq!(
+            provider.rate_limiter().interval(),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            FredProvider::new("key").rate_limiter().interval(),
+            Duration::from_millis(500)
+        );
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            provider.rate_limiter().wait();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        std::env::remove_var(AlphaVantageProvider::API_KEY_VARIABLE);
+        assert!(AlphaVantageProvider::from_env().is_err());
+    }
+}
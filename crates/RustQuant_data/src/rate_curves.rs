@@ -0,0 +1,505 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Domain methods for the typed interest rate curves.
+//!
+//! The [`RateCurve`] trait gives discount factors, zero rates in any
+//! [`Compounding`] and day count, and simple forward rates, for each of:
+//!
+//! - [`DiscountCurve`]: discount factors by date, interpolated log-linearly
+//!   (piecewise flat forward rates).
+//! - [`ZeroCurve`] (alias of [`SpotCurve`]): continuously compounded zero
+//!   rates by date, interpolated linearly and extrapolated flat.
+//! - [`ForwardCurve`]: instantaneous forward rates by date, each applying
+//!   from the previous date up to its own, extrapolated flat.
+//!
+//! The curves can be converted into one another. Converting a discount curve
+//! to a forward curve (and back) is exact everywhere; conversions involving
+//! zero rates are exact at the nodes.
+//!
+//! Times are measured from the curve's valuation date (its first date if
+//! none is set) with its day count convention (Actual/Actual ISDA if none is
+//! set). Unlike the Nelson-Siegel-Svensson fit behind [`Curves::get_rate`],
+//! these methods only read the nodes and never refit the curve.
+//!
+//! [`Curves::get_rate`]: crate::Curves::get_rate
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Curve, DiscountCurve, ForwardCurve, SpotCurve, ZeroCurve};
+use time::Date;
+use RustQuant_stochastics::NelsonSiegelSvensson;
+use RustQuant_time::{Calendar, DayCountConvention, Frequency};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Compounding convention of an interest rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compounding {
+    /// Simple interest: $P = 1 / (1 + r t)$.
+    Simple,
+
+    /// Compounded $n$ times a year: $P = (1 + r / n)^{-n t}$.
+    Periodic(Frequency),
+
+    /// Continuous compounding: $P = e^{-r t}$.
+    Continuous,
+}
+
+/// Common methods of discount, zero and forward curves.
+pub trait RateCurve {
+    /// Date from which times are measured, where the discount factor is one.
+    fn valuation_date(&self) -> Date;
+
+    /// Day count convention of the curve's time axis.
+    fn day_count_convention(&self) -> DayCountConvention;
+
+    /// Discount factor from the valuation date to `date`.
+    fn discount_factor(&self, date: Date) -> f64;
+
+    /// Zero rate to `date` in the given compounding and day count.
+    ///
+    /// At the valuation date itself, the one day rate is returned.
+    fn zero_rate(
+        &self,
+        date: Date,
+        compounding: Compounding,
+        day_count_convention: DayCountConvention,
+    ) -> f64 {
+        let valuation_date = self.valuation_date();
+
+        let date = match date > valuation_date {
+            true => date,
+            false => valuation_date.next_day().unwrap(),
+        };
+
+        compounding.rate(
+            self.discount_factor(date),
+            day_count_convention.day_count_factor(valuation_date, date),
+        )
+    }
+
+    /// Simple forward rate from `start` to `end`, accrued with the curve's
+    /// day count convention.
+    fn forward_rate(&self, start: Date, end: Date) -> f64 {
+        let tau = self.day_count_convention().day_count_factor(start, end);
+
+        (self.discount_factor(start) / self.discount_factor(end) - 1.0) / tau
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Compounding {
+    /// Discount factor for a rate over `t` years.
+    ///
+    /// # Panics
+    ///
+    /// Panics for periodic compounding with a zero frequency.
+    pub fn discount_factor(&self, rate: f64, t: f64) -> f64 {
+        match self {
+            Self::Simple => 1.0 / (1.0 + rate * t),
+            Self::Periodic(frequency) => {
+                let n = Self::periods(frequency);
+                (1.0 + rate / n).powf(-n * t)
+            }
+            Self::Continuous => (-rate * t).exp(),
+        }
+    }
+
+    /// Rate implied by a discount factor over `t` years.
+    ///
+    /// # Panics
+    ///
+    /// Panics for periodic compounding with a zero frequency.
+    pub fn rate(&self, discount_factor: f64, t: f64) -> f64 {
+        match self {
+            Self::Simple => (1.0 / discount_factor - 1.0) / t,
+            Self::Periodic(frequency) => {
+                let n = Self::periods(frequency);
+                n * (discount_factor.powf(-1.0 / (n * t)) - 1.0)
+            }
+            Self::Continuous => -discount_factor.ln() / t,
+        }
+    }
+
+    fn periods(frequency: &Frequency) -> f64 {
+        let n = frequency.times_in_year();
+        assert!(n > 0, "Periodic compounding needs a non-zero frequency.");
+
+        n as f64
+    }
+}
+
+/// Build a curve of another kind, keeping the conventions of `$from`.
+macro_rules! convert_curve {
+    ($target:ident, $from:expr, $nodes:expr) => {
+        $target {
+            curve: $nodes,
+            calendar: $from.calendar.clone(),
+            day_count_convention: $from.day_count_convention,
+            date_rolling_convention: $from.date_rolling_convention,
+            valuation_date: Some($from.valuation_date()),
+            nss: NelsonSiegelSvensson::default(),
+            fitted: false,
+            fitted_curve: None,
+        }
+    };
+}
+
+macro_rules! impl_rate_curve {
+    ($curve:ident, $discount_factor:expr) => {
+        impl<C> RateCurve for $curve<Date, C>
+        where
+            C: Calendar,
+        {
+            fn valuation_date(&self) -> Date {
+                self.valuation_date
+                    .or(self.curve.first_key().copied())
+                    .expect("Curve has no valuation date and no nodes.")
+            }
+
+            fn day_count_convention(&self) -> DayCountConvention {
+                self.day_count_convention.unwrap_or_default()
+            }
+
+            fn discount_factor(&self, date: Date) -> f64 {
+                let t = self.year_fraction(date);
+
+                match t > 0.0 {
+                    true => $discount_factor(&self.times_and_values(), t),
+                    false => 1.0,
+                }
+            }
+        }
+
+        impl<C> $curve<Date, C>
+        where
+            C: Calendar,
+        {
+            /// Year fraction from the valuation date.
+            fn year_fraction(&self, date: Date) -> f64 {
+                RateCurve::day_count_convention(self)
+                    .day_count_factor(RateCurve::valuation_date(self), date)
+            }
+
+            /// Nodes as (year fraction, value) pairs, from the valuation date on.
+            fn times_and_values(&self) -> Vec<(f64, f64)> {
+                self.curve
+                    .nodes
+                    .range(RateCurve::valuation_date(self)..)
+                    .map(|(date, value)| (self.year_fraction(*date), *value))
+                    .collect()
+            }
+        }
+    };
+}
+
+impl_rate_curve!(DiscountCurve, log_linear_discount_factor);
+impl_rate_curve!(SpotCurve, zero_rate_discount_factor);
+impl_rate_curve!(ForwardCurve, forward_rate_discount_factor);
+
+impl<C> DiscountCurve<Date, C>
+where
+    C: Calendar + Clone,
+{
+    /// Continuously compounded zero rates at the nodes after the valuation date.
+    pub fn to_zero_curve(&self) -> ZeroCurve<Date, C> {
+        let mut nodes = Curve::<Date>::new();
+
+        for (date, df) in self.curve.nodes.range(RateCurve::valuation_date(self)..) {
+            let t = self.year_fraction(*date);
+
+            if t > 0.0 {
+                nodes.insert(*date, -df.ln() / t);
+            }
+        }
+
+        convert_curve!(SpotCurve, self, nodes)
+    }
+
+    /// Forward rates between consecutive nodes, reproducing this curve's
+    /// discount factors on every date.
+    pub fn to_forward_curve(&self) -> ForwardCurve<Date, C> {
+        let mut nodes = Curve::<Date>::new();
+        let (mut t_0, mut df_0) = (0.0, 1.0);
+
+        for (date, df) in self.curve.nodes.range(RateCurve::valuation_date(self)..) {
+            let t = self.year_fraction(*date);
+
+            if t > t_0 {
+                nodes.insert(*date, (df_0 / df).ln() / (t - t_0));
+                (t_0, df_0) = (t, *df);
+            }
+        }
+
+        convert_curve!(ForwardCurve, self, nodes)
+    }
+}
+
+impl<C> SpotCurve<Date, C>
+where
+    C: Calendar + Clone,
+{
+    /// Discount factors at the valuation date and at each node.
+    pub fn to_discount_curve(&self) -> DiscountCurve<Date, C> {
+        convert_curve!(
+            DiscountCurve,
+            self,
+            node_discount_factors(self, &self.curve.keys())
+        )
+    }
+
+    /// Forward rates between consecutive nodes.
+    pub fn to_forward_curve(&self) -> ForwardCurve<Date, C> {
+        self.to_discount_curve().to_forward_curve()
+    }
+}
+
+impl<C> ForwardCurve<Date, C>
+where
+    C: Calendar + Clone,
+{
+    /// Discount factors at the valuation date and at each node.
+    pub fn to_discount_curve(&self) -> DiscountCurve<Date, C> {
+        convert_curve!(
+            DiscountCurve,
+            self,
+            node_discount_factors(self, &self.curve.keys())
+        )
+    }
+
+    /// Continuously compounded zero rates at each node.
+    pub fn to_zero_curve(&self) -> ZeroCurve<Date, C> {
+        self.to_discount_curve().to_zero_curve()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Discount factors of a curve at its valuation date and nodes.
+fn node_discount_factors<R: RateCurve>(curve: &R, dates: &[Date]) -> Curve<Date> {
+    let valuation_date = curve.valuation_date();
+    let mut nodes = Curve::<Date>::new();
+
+    nodes.insert(valuation_date, 1.0);
+    for date in dates.iter().filter(|date| **date > valuation_date) {
+        nodes.insert(*date, curve.discount_factor(*date));
+    }
+
+    nodes
+}
+
+/// Log-linear interpolation of discount factors, flat forward beyond the
+/// last node.
+fn log_linear_discount_factor(nodes: &[(f64, f64)], t: f64) -> f64 {
+    let mut points = vec![(0.0, 0.0)];
+    points.extend(
+        nodes
+            .iter()
+            .filter(|(t_i, _)| *t_i > 0.0)
+            .map(|(t_i, df_i)| (*t_i, df_i.ln())),
+    );
+
+    let i = match points.iter().position(|(t_i, _)| *t_i >= t) {
+        Some(i) => i,
+        None if points.len() > 1 => points.len() - 1,
+        None => return 1.0,
+    };
+
+    let ((t_0, y_0), (t_1, y_1)) = (points[i - 1], points[i]);
+
+    (y_0 + (y_1 - y_0) * (t - t_0) / (t_1 - t_0)).exp()
+}
+
+/// Linear interpolation of zero rates, flat beyond both ends.
+fn zero_rate_discount_factor(nodes: &[(f64, f64)], t: f64) -> f64 {
+    let zero_rate = match nodes.iter().position(|(t_i, _)| *t_i >= t) {
+        None => nodes.last().map_or(0.0, |(_, z)| *z),
+        Some(0) => nodes[0].1,
+        Some(i) => {
+            let ((t_0, z_0), (t_1, z_1)) = (nodes[i - 1], nodes[i]);
+            z_0 + (z_1 - z_0) * (t - t_0) / (t_1 - t_0)
+        }
+    };
+
+    (-zero_rate * t).exp()
+}
+
+/// Integral of piecewise flat forward rates, each applying up to its node.
+fn forward_rate_discount_factor(nodes: &[(f64, f64)], t: f64) -> f64 {
+    let mut integral = 0.0;
+    let mut t_0 = 0.0;
+
+    for &(t_i, f_i) in nodes.iter().filter(|(t_i, _)| *t_i > 0.0) {
+        integral += f_i * (t.min(t_i) - t_0);
+
+        if t <= t_i {
+            return (-integral).exp();
+        }
+
+        t_0 = t_i;
+    }
+
+    let last = nodes.last().map_or(0.0, |(_, f)| *f);
+
+    (-(integral + last * (t - t_0))).exp()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rate_curves {
+    use super::*;
+    use crate::Curves;
+    use time::macros::date;
+    use RustQuant_time::AustraliaCalendar;
+    use RustQuant_utils::assert_approx_equal;
+
+    const TODAY: Date = date!(2024 - 01 - 02);
+    const ACT365: DayCountConvention = DayCountConvention::Actual_365_Fixed;
+
+    fn discount_curve() -> DiscountCurve<Date, AustraliaCalendar> {
+        let mut curve = DiscountCurve::new(
+            &[
+                TODAY,
+                date!(2024 - 07 - 02),
+                date!(2025 - 01 - 02),
+                date!(2027 - 01 - 02),
+                date!(2034 - 01 - 02),
+            ],
+            &[1.0, 0.975, 0.952, 0.87, 0.68],
+        );
+        curve.day_count_convention = Some(ACT365);
+        curve
+    }
+
+    #[test]
+    fn test_compounding_round_trip() {
+        for compounding in [
+            Compounding::Simple,
+            Compounding::Periodic(Frequency::SemiAnnually),
+            Compounding::Continuous,
+        ] {
+            let df = compounding.discount_factor(0.04, 2.5);
+            assert_approx_equal!(compounding.rate(df, 2.5), 0.04, 1e-12);
+        }
+
+        assert_approx_equal!(
+            Compounding::Periodic(Frequency::Annually).discount_factor(0.05, 2.0),
+            1.0 / 1.05_f64.powi(2),
+            1e-14
+        );
+    }
+
+    #[test]
+    fn test_discount_curve() {
+        let curve = discount_curve();
+
+        assert_eq!(curve.valuation_date(), TODAY);
+        assert_approx_equal!(curve.discount_factor(TODAY), 1.0, 1e-14);
+        assert_approx_equal!(curve.discount_factor(date!(2025 - 01 - 02)), 0.952, 1e-14);
+
+        // Log-linear interpolation.
+        let (d_0, d_1) = (date!(2025 - 01 - 02), date!(2027 - 01 - 02));
+        let mid = date!(2026 - 01 - 02);
+        let w = ACT365.day_count_factor(d_0, mid) / ACT365.day_count_factor(d_0, d_1);
+        assert_approx_equal!(
+            curve.discount_factor(mid),
+            0.952_f64.powf(1.0 - w) * 0.87_f64.powf(w),
+            1e-12
+        );
+
+        // Zero rates in several conventions agree on the discount factor.
+        let t = ACT365.day_count_factor(TODAY, d_1);
+        let simple = curve.zero_rate(d_1, Compounding::Simple, ACT365);
+        let annual = curve.zero_rate(d_1, Compounding::Periodic(Frequency::Annually), ACT365);
+        assert_approx_equal!(1.0 / (1.0 + simple * t), 0.87, 1e-12);
+        assert_approx_equal!((1.0 + annual).powf(-t), 0.87, 1e-12);
+
+        // Flat forward beyond the last node.
+        let (d_2, d_3) = (date!(2034 - 01 - 02), date!(2040 - 01 - 02));
+        assert_approx_equal!(
+            curve.forward_rate(d_2, d_3),
+            curve.to_forward_curve().forward_rate(d_2, d_3),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let discount = discount_curve();
+        let forward = discount.to_forward_curve();
+        let zero = discount.to_zero_curve();
+
+        // Discount <-> forward is exact on any date.
+        for date in [
+            date!(2024 - 03 - 15),
+            date!(2025 - 01 - 02),
+            date!(2030 - 06 - 30),
+            date!(2045 - 01 - 02),
+        ] {
+            assert_approx_equal!(
+                forward.discount_factor(date),
+                discount.discount_factor(date),
+                1e-12
+            );
+        }
+
+        // Zero conversions are exact at the nodes.
+        for (date, df) in &discount.curve.nodes {
+            assert_approx_equal!(zero.discount_factor(*date), *df, 1e-12);
+            assert_approx_equal!(zero.to_discount_curve().discount_factor(*date), *df, 1e-12);
+            assert_approx_equal!(forward.to_zero_curve().discount_factor(*date), *df, 1e-12);
+        }
+
+        assert_eq!(zero.valuation_date(), TODAY);
+        assert_eq!(zero.curve.len(), discount.curve.len() - 1);
+    }
+
+    #[test]
+    fn test_zero_curve() {
+        let mut zero: ZeroCurve<Date, AustraliaCalendar> = SpotCurve::new(
+            &[date!(2025 - 01 - 02), date!(2026 - 01 - 02)],
+            &[0.04, 0.05],
+        );
+        zero.valuation_date = Some(TODAY);
+        zero.day_count_convention = Some(ACT365);
+
+        // 366, 548 and 731 days from the valuation date.
+        let z: f64 = 0.04 + 0.01 * (548.0 - 366.0) / (731.0 - 366.0);
+        assert_approx_equal!(
+            zero.discount_factor(date!(2025 - 07 - 03)),
+            (-z * 548.0 / 365.0).exp(),
+            1e-14
+        );
+
+        // Flat at both ends.
+        assert_approx_equal!(
+            zero.zero_rate(date!(2024 - 06 - 01), Compounding::Continuous, ACT365),
+            0.04,
+            1e-12
+        );
+        assert_approx_equal!(
+            zero.zero_rate(date!(2030 - 06 - 01), Compounding::Continuous, ACT365),
+            0.05,
+            1e-12
+        );
+    }
+}
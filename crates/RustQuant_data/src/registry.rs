@@ -0,0 +1,373 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Instrument identifiers and a registry of shared market objects.
+//!
+//! A book of thousands of instruments typically references a handful of
+//! curves, surfaces and calendars. The `MarketRegistry` stores each of these
+//! once, behind an `Arc`, and maps every instrument (by ISIN or ticker and
+//! MIC) to the objects it uses, so instruments share the same allocation.
+//!
+//! Replacing an object swaps the `Arc` in the registry: instruments resolved
+//! afterwards see the new object, while anything still holding the old `Arc`
+//! keeps a consistent view. Cloning the registry only clones the `Arc`s, so
+//! a clone is a cheap, frozen snapshot for a portfolio revaluation.
+//!
+//! ```rust
+//! use RustQuant::data::*;
+//! use RustQuant::iso::XNAS;
+//! use std::sync::Arc;
+//!
+//! let mut registry = MarketRegistry::new();
+//! registry.insert("USD-SOFR", vec![0.05, 0.048, 0.045]);
+//!
+//! let apple = InstrumentId::isin("US0378331005").unwrap();
+//! let msft = InstrumentId::ticker("MSFT", XNAS);
+//! registry.link(apple.clone(), "discount", "USD-SOFR");
+//! registry.link(msft.clone(), "discount", "USD-SOFR");
+//!
+//! let a = registry.resolve::<Vec<f64>>(&apple, "discount").unwrap();
+//! let b = registry.resolve::<Vec<f64>>(&msft, "discount").unwrap();
+//! assert!(Arc::ptr_eq(&a, &b));
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use RustQuant_error::RustQuantError;
+use RustQuant_iso::ISO_10383;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Identifier of an instrument.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InstrumentId {
+    /// ISIN (International Securities Identification Number), e.g. `US0378331005`.
+    ISIN(String),
+
+    /// Ticker symbol on a venue, identified by its ISO 10383 MIC.
+    Ticker {
+        /// Ticker symbol (e.g. AAPL).
+        symbol: String,
+
+        /// ISO 10383 market identifier code (e.g. XNAS).
+        mic: ISO_10383,
+    },
+}
+
+/// Registry of shared market objects (curves, surfaces, calendars, ...),
+/// and of the objects each instrument depends on.
+#[derive(Clone, Default)]
+pub struct MarketRegistry {
+    /// Objects, keyed by their type and name.
+    objects: HashMap<(TypeId, String), Arc<dyn Any + Send + Sync>>,
+
+    /// Object names used by each instrument, keyed by role (e.g. "discount").
+    links: HashMap<InstrumentId, BTreeMap<String, String>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl InstrumentId {
+    /// Create an ISIN identifier, checking its format and check digit.
+    pub fn isin(code: &str) -> Result<Self, RustQuantError> {
+        let code = code.trim().to_ascii_uppercase();
+        let bytes = code.as_bytes();
+
+        let well_formed = bytes.len() == 12
+            && bytes[..2].iter().all(u8::is_ascii_uppercase)
+            && bytes[2..11].iter().all(u8::is_ascii_alphanumeric)
+            && bytes[11].is_ascii_digit();
+
+        if !well_formed || !isin_check_digit_is_valid(bytes) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Invalid ISIN: {}.",
+                code
+            )));
+        }
+
+        Ok(Self::ISIN(code))
+    }
+
+    /// Create a ticker identifier.
+    pub fn ticker(symbol: &str, mic: ISO_10383) -> Self {
+        Self::Ticker {
+            symbol: symbol.to_ascii_uppercase(),
+            mic,
+        }
+    }
+}
+
+impl fmt::Display for InstrumentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ISIN(code) => write!(f, "{}", code),
+            Self::Ticker { symbol, mic } => write!(f, "{}.{}", symbol, mic.operating_mic),
+        }
+    }
+}
+
+impl MarketRegistry {
+    /// Create a new, empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert (or replace) an object, returning the shared handle.
+    ///
+    /// Handles obtained before a replacement keep pointing to the old object.
+    pub fn insert<T>(&mut self, name: impl Into<String>, object: T) -> Arc<T>
+    where
+        T: Any + Send + Sync,
+    {
+        let object = Arc::new(object);
+
+        self.objects
+            .insert((TypeId::of::<T>(), name.into()), object.clone());
+
+        object
+    }
+
+    /// Get the object with the given name, creating it if it is not in the registry.
+    pub fn get_or_insert_with<T, F>(&mut self, name: impl Into<String>, make: F) -> Arc<T>
+    where
+        T: Any + Send + Sync,
+        F: FnOnce() -> T,
+    {
+        let object = self
+            .objects
+            .entry((TypeId::of::<T>(), name.into()))
+            .or_insert_with(|| Arc::new(make()))
+            .clone();
+
+        object
+            .downcast::<T>()
+            .expect("Objects are keyed by their type.")
+    }
+
+    /// Get the object with the given name and type.
+    pub fn get<T>(&self, name: &str) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.objects
+            .get(&(TypeId::of::<T>(), name.to_string()))
+            .and_then(|object| object.clone().downcast::<T>().ok())
+    }
+
+    /// Remove the object with the given name and type.
+    ///
+    /// Links to it are kept, and fail to resolve until it is inserted again.
+    pub fn remove<T>(&mut self, name: &str) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.objects
+            .remove(&(TypeId::of::<T>(), name.to_string()))
+            .and_then(|object| object.downcast::<T>().ok())
+    }
+
+    /// Record that an instrument uses the named object in the given role.
+    pub fn link(&mut self, id: InstrumentId, role: impl Into<String>, name: impl Into<String>) {
+        self.links
+            .entry(id)
+            .or_default()
+            .insert(role.into(), name.into());
+    }
+
+    /// Roles and object names linked to an instrument.
+    pub fn links(&self, id: &InstrumentId) -> Option<&BTreeMap<String, String>> {
+        self.links.get(id)
+    }
+
+    /// Get the object an instrument uses in the given role.
+    pub fn resolve<T>(&self, id: &InstrumentId, role: &str) -> Result<Arc<T>, RustQuantError>
+    where
+        T: Any + Send + Sync,
+    {
+        let name = self
+            .links
+            .get(id)
+            .and_then(|roles| roles.get(role))
+            .ok_or_else(|| {
+                RustQuantError::MissingInput(format!("No {} object linked to {}.", role, id))
+            })?;
+
+        self.get::<T>(name).ok_or_else(|| {
+            RustQuantError::MissingInput(format!(
+                "Object {} ({} for {}) is not in the registry.",
+                name, role, id
+            ))
+        })
+    }
+
+    /// Instruments that use the named object in any role, sorted.
+    pub fn instruments_using(&self, name: &str) -> Vec<&InstrumentId> {
+        let mut ids: Vec<&InstrumentId> = self
+            .links
+            .iter()
+            .filter(|(_, roles)| roles.values().any(|n| n == name))
+            .map(|(id, _)| id)
+            .collect();
+
+        ids.sort();
+        ids
+    }
+
+    /// Number of distinct objects in the registry.
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Number of instruments with at least one link.
+    pub fn instrument_count(&self) -> usize {
+        self.links.len()
+    }
+}
+
+impl fmt::Debug for MarketRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&String> = self.objects.keys().map(|(_, name)| name).collect();
+        names.sort();
+
+        f.debug_struct("MarketRegistry")
+            .field("objects", &names)
+            .field("links", &self.links)
+            .finish()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Luhn check of an ISIN, with letters expanded to two digits (A = 10, ..., Z = 35).
+fn isin_check_digit_is_valid(code: &[u8]) -> bool {
+    let digits: Vec<u32> = code
+        .iter()
+        .flat_map(|&c| {
+            let value = (c as char).to_digit(36).unwrap_or(0);
+
+            if value < 10 {
+                vec![value]
+            } else {
+                vec![value / 10, value % 10]
+            }
+        })
+        .collect();
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match i % 2 {
+            0 => d,
+            _ => (2 * d) / 10 + (2 * d) % 10,
+        })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_registry {
+    use super::*;
+    use crate::Curve;
+    use time::{macros::date, Date};
+    use RustQuant_iso::{XNAS, XNMS, XNYS};
+    use RustQuant_time::{Calendar, UnitedStatesCalendar};
+
+    #[test]
+    fn test_instrument_ids() {
+        assert!(InstrumentId::isin("US0378331005").is_ok());
+        assert!(InstrumentId::isin("gb0002634946").is_ok());
+        assert!(InstrumentId::isin("US0378331006").is_err());
+        assert!(InstrumentId::isin("US03783310").is_err());
+
+        assert_eq!(InstrumentId::ticker("aapl", XNAS).to_string(), "AAPL.XNAS");
+
+        // A segment is a different venue from its operating MIC.
+        assert_ne!(
+            InstrumentId::ticker("AAPL", XNAS),
+            InstrumentId::ticker("AAPL", XNMS)
+        );
+    }
+
+    #[test]
+    fn test_shared_objects() {
+        let mut registry = MarketRegistry::new();
+        let curve = Curve::<Date>::new_from_slice(
+            &[date!(2025 - 01 - 01), date!(2030 - 01 - 01)],
+            &[0.04, 0.045],
+        );
+
+        // A thousand instruments share one curve and one calendar.
+        for i in 0..1000 {
+            let id = InstrumentId::ticker(&format!("BOND{}", i), XNYS);
+
+            registry.get_or_insert_with("USD-SOFR", || curve.clone());
+            registry.get_or_insert_with("US", || UnitedStatesCalendar);
+            registry.link(id.clone(), "discount", "USD-SOFR");
+            registry.link(id, "calendar", "US");
+        }
+
+        assert_eq!(registry.object_count(), 2);
+        assert_eq!(registry.instrument_count(), 1000);
+        assert_eq!(registry.instruments_using("USD-SOFR").len(), 1000);
+
+        let a = InstrumentId::ticker("BOND1", XNYS);
+        let b = InstrumentId::ticker("BOND2", XNYS);
+        let curve_a = registry.resolve::<Curve<Date>>(&a, "discount").unwrap();
+        let curve_b = registry.resolve::<Curve<Date>>(&b, "discount").unwrap();
+        assert!(Arc::ptr_eq(&curve_a, &curve_b));
+
+        let calendar = registry
+            .resolve::<UnitedStatesCalendar>(&a, "calendar")
+            .unwrap();
+        assert!(!calendar.is_business_day(date!(2024 - 12 - 25)));
+
+        // Wrong type, role or instrument.
+        assert!(registry.resolve::<Vec<f64>>(&a, "discount").is_err());
+        assert!(registry.resolve::<Curve<Date>>(&a, "projection").is_err());
+        assert!(registry
+            .resolve::<Curve<Date>>(&InstrumentId::ticker("X", XNYS), "discount")
+            .is_err());
+    }
+
+    #[test]
+    fn test_snapshot_consistency() {
+        let mut registry = MarketRegistry::new();
+        let id = InstrumentId::isin("US0378331005").unwrap();
+
+        registry.insert("spot", 100.0_f64);
+        registry.link(id.clone(), "spot", "spot");
+
+        let snapshot = registry.clone();
+        let held = registry.resolve::<f64>(&id, "spot").unwrap();
+
+        // Market update after the snapshot was taken.
+        registry.insert("spot", 101.0_f64);
+
+        assert_eq!(*registry.resolve::<f64>(&id, "spot").unwrap(), 101.0);
+        assert_eq!(*snapshot.resolve::<f64>(&id, "spot").unwrap(), 100.0);
+        assert_eq!(*held, 100.0);
+
+        assert_eq!(registry.remove::<f64>("spot").as_deref(), Some(&101.0));
+        assert!(registry.resolve::<f64>(&id, "spot").is_err());
+    }
+}
@@ -0,0 +1,399 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Fitting the drift of the Ho-Lee and Hull-White models to a discount curve.
+//!
+//! Both models reprice the initial curve when $\theta(t)$ is chosen from the
+//! instantaneous forward curve $f(0, t) = -\partial_t \ln P(0, t)$:
+//!
+//! - Ho-Lee: $\theta(t) = \partial_t f(0, t) + \sigma^2 t$,
+//! - Hull-White: $\theta(t) = \partial_t f(0, t) + \alpha f(0, t)
+//!   + \frac{\sigma^2}{2 \alpha} (1 - e^{-2 \alpha t})$,
+//!
+//! with the initial short rate $r(0) = f(0, 0)$.
+//!
+//! The forward curve needs a smooth $\ln P(0, t)$, so the curve's nodes are
+//! interpolated with a natural cubic spline in $-\ln P(0, t)$, with
+//! $P(0, 0) = 1$. Beyond the last node the forward rate is held flat.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::Curve;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_stochastics::{HoLee, HullWhite};
+use RustQuant_time::DayCountConvention;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Smooth instantaneous forward curve implied by a discount curve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialForwardCurve {
+    /// Knot times, in years, starting at zero.
+    times: Vec<f64>,
+
+    /// $-\ln P(0, t)$ at the knots.
+    log_discounts: Vec<f64>,
+
+    /// Second derivatives of the spline at the knots.
+    curvatures: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl InitialForwardCurve {
+    /// Build the forward curve from a curve of discount factors.
+    ///
+    /// # Arguments:
+    /// * `curve` - Discount factors by date.
+    /// * `valuation_date` - Date of $t = 0$. Nodes on or before it are ignored.
+    /// * `day_count` - Convention for converting dates to times.
+    pub fn from_discount_curve(
+        curve: &Curve<Date>,
+        valuation_date: Date,
+        day_count: DayCountConvention,
+    ) -> Result<Self, RustQuantError> {
+        let nodes: Vec<(f64, f64)> = curve
+            .nodes
+            .range(valuation_date.next_day().unwrap_or(valuation_date)..)
+            .map(|(date, df)| (day_count.day_count_factor(valuation_date, *date), *df))
+            .collect();
+
+        Self::from_discount_factors(&nodes)
+    }
+
+    /// Build the forward curve from `(time, discount factor)` pairs.
+    pub fn from_discount_factors(nodes: &[(f64, f64)]) -> Result<Self, RustQuantError> {
+        if nodes.len() < 2 {
+            return Err(RustQuantError::MissingInput(
+                "At least two discount factors after the valuation date are needed.".to_string(),
+            ));
+        }
+
+        let mut times = vec![0.0];
+        let mut log_discounts = vec![0.0];
+
+        for (t, df) in nodes {
+            if *t <= *times.last().unwrap() {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Discount factor times must be positive and increasing, got {}.",
+                    t
+                )));
+            }
+            if df.is_nan() || *df <= 0.0 {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Discount factors must be positive, got {} at {}.",
+                    df, t
+                )));
+            }
+
+            times.push(*t);
+            log_discounts.push(-df.ln());
+        }
+
+        let curvatures = natural_spline_curvatures(&times, &log_discounts);
+
+        Ok(Self {
+            times,
+            log_discounts,
+            curvatures,
+        })
+    }
+
+    /// Discount factor $P(0, t)$.
+    pub fn discount_factor(&self, t: f64) -> f64 {
+        (-self.evaluate(t).0).exp()
+    }
+
+    /// Instantaneous forward rate $f(0, t)$.
+    pub fn forward(&self, t: f64) -> f64 {
+        self.evaluate(t).1
+    }
+
+    /// Slope of the forward curve, $\partial_t f(0, t)$.
+    pub fn forward_slope(&self, t: f64) -> f64 {
+        self.evaluate(t).2
+    }
+
+    /// Initial short rate, $r(0) = f(0, 0)$.
+    pub fn short_rate(&self) -> f64 {
+        self.forward(0.0)
+    }
+
+    /// Ho-Lee drift that reprices the curve.
+    pub fn ho_lee_theta(&self, sigma: f64) -> impl Fn(f64) -> f64 + Clone + Send + Sync + 'static {
+        let curve = self.clone();
+
+        move |t| curve.forward_slope(t) + sigma * sigma * t
+    }
+
+    /// Hull-White drift that reprices the curve, for mean reversion `alpha > 0`.
+    pub fn hull_white_theta(
+        &self,
+        alpha: f64,
+        sigma: f64,
+    ) -> impl Fn(f64) -> f64 + Clone + Send + Sync + 'static {
+        let curve = self.clone();
+
+        move |t| {
+            curve.forward_slope(t)
+                + alpha * curve.forward(t)
+                + sigma * sigma / (2.0 * alpha) * (1.0 - (-2.0 * alpha * t).exp())
+        }
+    }
+
+    /// Ho-Lee model fitted to the curve. Simulate it from [`Self::short_rate`].
+    pub fn ho_lee(&self, sigma: f64) -> HoLee {
+        HoLee::new(sigma, self.ho_lee_theta(sigma))
+    }
+
+    /// Hull-White model fitted to the curve. Simulate it from [`Self::short_rate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not positive.
+    pub fn hull_white(&self, alpha: f64, sigma: f64) -> HullWhite {
+        assert!(alpha > 0.0, "Hull-White mean reversion must be positive.");
+
+        HullWhite::new(alpha, sigma, self.hull_white_theta(alpha, sigma))
+    }
+
+    /// $-\ln P(0, t)$ and its first two derivatives.
+    fn evaluate(&self, t: f64) -> (f64, f64, f64) {
+        let n = self.times.len() - 1;
+        let (x, y, m) = (&self.times, &self.log_discounts, &self.curvatures);
+
+        // Flat forward beyond the last node.
+        if t >= x[n] {
+            let h = x[n] - x[n - 1];
+            let slope = (y[n] - y[n - 1]) / h + h * (2.0 * m[n] + m[n - 1]) / 6.0;
+
+            return (y[n] + slope * (t - x[n]), slope, 0.0);
+        }
+
+        let t = t.max(0.0);
+        let i = x.partition_point(|knot| *knot <= t).clamp(1, n) - 1;
+        let h = x[i + 1] - x[i];
+        let a = (x[i + 1] - t) / h;
+        let b = (t - x[i]) / h;
+
+        let value = a * y[i]
+            + b * y[i + 1]
+            + ((a.powi(3) - a) * m[i] + (b.powi(3) - b) * m[i + 1]) * h * h / 6.0;
+        let first = (y[i + 1] - y[i]) / h - (3.0 * a * a - 1.0) * h * m[i] / 6.0
+            + (3.0 * b * b - 1.0) * h * m[i + 1] / 6.0;
+        let second = a * m[i] + b * m[i + 1];
+
+        (value, first, second)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Second derivatives of the natural cubic spline through `(x, y)`,
+/// from the tridiagonal system (Thomas algorithm).
+fn natural_spline_curvatures(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let n = x.len() - 1;
+    let mut m = vec![0.0; n + 1];
+
+    if n < 2 {
+        return m;
+    }
+
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+
+    for i in 1..n {
+        let (h0, h1) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+
+        diagonal[i] = 2.0 * (h0 + h1);
+        rhs[i] = 6.0 * ((y[i + 1] - y[i]) / h1 - (y[i] - y[i - 1]) / h0);
+
+        if i > 1 {
+            let w = h0 / diagonal[i - 1];
+            diagonal[i] -= w * h0;
+            rhs[i] -= w * rhs[i - 1];
+        }
+    }
+
+    for i in (1..n).rev() {
+        let h1 = x[i + 1] - x[i];
+        m[i] = (rhs[i] - h1 * m[i + 1]) / diagonal[i];
+    }
+
+    m
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_short_rate_calibration {
+    use super::*;
+    use time::{macros::date, Duration};
+    use RustQuant_math::Statistic;
+    use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+    use RustQuant_utils::assert_approx_equal;
+
+    /// $-\ln P(0, t)$ of an upward sloping curve, with forward
+    /// $f(t) = 0.03 + 0.01 (1 - e^{-t})$.
+    fn log_discount(t: f64) -> f64 {
+        0.04 * t - 0.01 * (1.0 - (-t).exp())
+    }
+
+    fn curve() -> InitialForwardCurve {
+        let nodes: Vec<(f64, f64)> = (1..=120)
+            .map(|i| {
+                let t = 0.25 * i as f64;
+                (t, (-log_discount(t)).exp())
+            })
+            .collect();
+
+        InitialForwardCurve::from_discount_factors(&nodes).unwrap()
+    }
+
+    /// Mean discount factor $E[e^{-\int_0^T r}]$ over simulated short rate paths.
+    fn simulated_discount_factor<P: StochasticProcess>(
+        process: &P,
+        r_0: f64,
+        maturity: f64,
+    ) -> f64 {
+        let steps = 200;
+        let config =
+            StochasticProcessConfig::new(r_0, 0.0, maturity, steps, 4000, false).with_seed(11);
+        let dt = maturity / steps as f64;
+
+        let discount_factors: Vec<f64> = process
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|path| {
+                let integral: f64 = path.windows(2).map(|r| 0.5 * (r[0] + r[1]) * dt).sum();
+                (-integral).exp()
+            })
+            .collect();
+
+        discount_factors.mean()
+    }
+
+    #[test]
+    fn test_forward_curve() {
+        let curve = curve();
+
+        for t in [1.0_f64, 4.9, 20.0] {
+            let forward = 0.03 + 0.01 * (1.0 - (-t).exp());
+            let slope = 0.01 * (-t).exp();
+
+            assert_approx_equal!(curve.discount_factor(t), (-log_discount(t)).exp(), 1e-8);
+            assert_approx_equal!(curve.forward(t), forward, 1e-5);
+            assert_approx_equal!(curve.forward_slope(t), slope, 1e-3);
+        }
+
+        // The natural end condition sets the forward slope to zero at t = 0,
+        // so the first interval is less accurate.
+        assert_approx_equal!(curve.discount_factor(0.1), (-log_discount(0.1)).exp(), 1e-4);
+        assert_approx_equal!(curve.short_rate(), 0.03, 1e-3);
+
+        // Flat beyond the last node.
+        assert_approx_equal!(curve.forward(40.0), curve.forward(30.0), 1e-6);
+        assert_eq!(curve.forward_slope(40.0), 0.0);
+    }
+
+    #[test]
+    fn test_theta() {
+        let curve = curve();
+        let (alpha, sigma) = (0.1, 0.01);
+
+        let ho_lee = curve.ho_lee_theta(sigma);
+        let hull_white = curve.hull_white_theta(alpha, sigma);
+
+        let t: f64 = 2.0;
+        let forward = 0.03 + 0.01 * (1.0 - (-t).exp());
+        let slope = 0.01 * (-t).exp();
+
+        assert_approx_equal!(ho_lee(t), slope + sigma * sigma * t, 1e-4);
+        assert_approx_equal!(
+            hull_white(t),
+            slope
+                + alpha * forward
+                + sigma * sigma / (2.0 * alpha) * (1.0 - (-2.0 * alpha * t).exp()),
+            1e-4
+        );
+    }
+
+    #[test]
+    fn test_simulated_rates_reprice_curve() {
+        let curve = curve();
+        let maturity = 5.0;
+        let target = curve.discount_factor(maturity);
+
+        let hull_white = curve.hull_white(0.1, 0.01);
+        assert_approx_equal!(
+            simulated_discount_factor(&hull_white, curve.short_rate(), maturity),
+            target,
+            2e-3
+        );
+
+        let ho_lee = curve.ho_lee(0.01);
+        assert_approx_equal!(
+            simulated_discount_factor(&ho_lee, curve.short_rate(), maturity),
+            target,
+            2e-3
+        );
+
+        // Without the fitted drift the curve is missed.
+        let unfitted = HullWhite::new(0.1, 0.01, 0.0);
+        assert!(
+            (simulated_discount_factor(&unfitted, curve.short_rate(), maturity) - target).abs()
+                > 0.05
+        );
+    }
+
+    #[test]
+    fn test_from_dated_curve() {
+        let today = date!(2024 - 01 - 02);
+        let mut dated = Curve::<Date>::new();
+        dated.insert(today, 1.0);
+
+        for years in 1..=10 {
+            let date = today + Duration::days(365 * years);
+            dated.insert(date, (-log_discount(years as f64)).exp());
+        }
+
+        let curve = InitialForwardCurve::from_discount_curve(
+            &dated,
+            today,
+            DayCountConvention::Actual_365_Fixed,
+        )
+        .unwrap();
+        assert_approx_equal!(
+            curve.discount_factor(3.0),
+            (-log_discount(3.0)).exp(),
+            1e-10
+        );
+
+        let mut invalid = dated.clone();
+        invalid.insert(today + Duration::days(30), -1.0);
+        assert!(InitialForwardCurve::from_discount_curve(
+            &invalid,
+            today,
+            DayCountConvention::Actual_365_Fixed
+        )
+        .is_err());
+    }
+}
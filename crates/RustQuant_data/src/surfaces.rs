@@ -7,13 +7,43 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use super::Curve;
-use crate::data::TermStructure;
-use num::Float;
+//! Surfaces module.
+//!
+//! A [`Surface`] is a set of [`Curve`] slices, one per node of its first
+//! axis (e.g. option expiry), each with its own nodes on the second axis.
+//! Every slice is read off at the nodes of all slices, along its own
+//! interpolation, and the resulting grid is interpolated by a
+//! [`GridInterpolator`], bilinearly ([`SurfaceInterpolation::Bilinear`]) or
+//! bicubically ([`SurfaceInterpolation::Bicubic`]).
+
+use crate::{Curve, CurveExtrapolation, CurveIndex, CurveInterpolation};
 use std::collections::BTreeMap;
-use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::{
+    interpolation::{GridInterpolation, GridInterpolator},
+    InterpolationIndex,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS AND ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Two dimensional interpolation method of a [`Surface`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SurfaceInterpolation {
+    /// Linear along the slices, then linear across them.
+    #[default]
+    Bilinear,
+
+    /// Natural cubic splines along the slices, then bicubic Hermite
+    /// across them.
+    Bicubic,
+}
 
 /// Surface data structure.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Surface<S, C>
 where
     S: CurveIndex,
@@ -21,73 +51,475 @@ where
 {
     /// The curves that make up the surface.
     pub nodes: BTreeMap<S, Curve<C>>,
-}
 
-/// Surface data.
-#[allow(dead_code)] // never used
-pub struct Surface {
-    /// Nodes of the surface.
-    pub nodes: BTreeMap<u64, TermStructure>,
+    /// Interpolation method, along and across the slices.
+    pub interpolation: SurfaceInterpolation,
+
+    /// Extrapolation policy, along and across the slices.
+    pub extrapolation: CurveExtrapolation,
 }
 
-#[allow(dead_code)]
-impl Surface {
-    /// Create a new surface.
-    pub fn new() -> Self {
-        Self {
-            nodes: BTreeMap::new(),
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SurfaceInterpolation {
+    /// Matching interpolation method along one axis.
+    pub fn curve_interpolation(&self) -> CurveInterpolation {
+        match self {
+            Self::Bilinear => CurveInterpolation::Linear,
+            Self::Bicubic => CurveInterpolation::CubicSpline,
         }
     }
 
-    /// Add a term structure node to the surface.
-    pub fn add_node(&mut self, time: u64, term_structure: TermStructure) {
-        self.nodes.insert(time, term_structure);
+    /// Matching interpolation method of the grid of slices.
+    pub fn grid_interpolation(&self) -> GridInterpolation {
+        match self {
+            Self::Bilinear => GridInterpolation::Bilinear,
+            Self::Bicubic => GridInterpolation::Bicubic,
+        }
     }
+}
 
-    /// Get a term structure for a specific time.
-    pub fn get_term_structure(&self, time: u64) -> Option<&TermStructure> {
-        self.nodes.get(&time)
-    }
+macro_rules! impl_surface {
+    ($index:ty) => {
+        impl<S> Surface<S, $index>
+        where
+            S: CurveIndex + InterpolationIndex<DeltaDiv = f64>,
+        {
+            /// Create a new surface.
+            pub fn new() -> Self {
+                Self {
+                    nodes: BTreeMap::new(),
+                    interpolation: SurfaceInterpolation::default(),
+                    extrapolation: CurveExtrapolation::default(),
+                }
+            }
+
+            /// Create a surface from a grid, with `values[i][j]` at
+            /// `(slices[i], indices[j])`.
+            ///
+            /// # Errors
+            /// - `RustQuantError::UnequalLength` if the values do not match the grid.
+            pub fn from_grid(
+                slices: &[S],
+                indices: &[$index],
+                values: &[Vec<f64>],
+            ) -> Result<Self, RustQuantError> {
+                if values.len() != slices.len() || values.iter().any(|v| v.len() != indices.len()) {
+                    return Err(RustQuantError::UnequalLength);
+                }
+
+                let mut surface = Self::new();
+
+                for (slice, row) in slices.iter().zip(values) {
+                    surface.insert_slice(*slice, Curve::<$index>::new_from_slice(indices, row));
+                }
+
+                Ok(surface)
+            }
+
+            /// Set the interpolation method, on the surface and its slices.
+            pub fn with_interpolation(mut self, interpolation: SurfaceInterpolation) -> Self {
+                self.interpolation = interpolation;
+                self.configure_slices();
+                self
+            }
+
+            /// Set the extrapolation policy, on the surface and its slices.
+            pub fn with_extrapolation(mut self, extrapolation: CurveExtrapolation) -> Self {
+                self.extrapolation = extrapolation;
+                self.configure_slices();
+                self
+            }
+
+            /// Number of nodes over all slices.
+            pub fn len(&self) -> usize {
+                self.nodes.values().map(|curve| curve.len()).sum()
+            }
+
+            /// Whether the surface has no nodes.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Get the value of a node.
+            pub fn get(&self, slice: S, index: $index) -> Option<&f64> {
+                self.nodes.get(&slice).and_then(|curve| curve.get(index))
+            }
+
+            /// Insert a node, creating its slice if needed.
+            pub fn insert(&mut self, slice: S, index: $index, value: f64) {
+                match self.nodes.get_mut(&slice) {
+                    Some(curve) => curve.insert(index, value),
+                    None => self
+                        .insert_slice(slice, Curve::<$index>::new_from_slice(&[index], &[value])),
+                }
+            }
+
+            /// Remove a node, and its slice if it was the last node in it.
+            pub fn remove(&mut self, slice: S, index: $index) -> Option<f64> {
+                let curve = self.nodes.get_mut(&slice)?;
+                let value = curve.nodes.remove(&index);
+
+                if curve.is_empty() {
+                    self.nodes.remove(&slice);
+                }
+
+                value
+            }
+
+            /// Insert a whole slice, replacing any existing one. The slice
+            /// takes the surface's interpolation and extrapolation.
+            pub fn insert_slice(&mut self, slice: S, curve: Curve<$index>) {
+                let curve = curve
+                    .with_interpolation(self.interpolation.curve_interpolation())
+                    .with_extrapolation(self.extrapolation);
+
+                self.nodes.insert(slice, curve);
+            }
+
+            /// Remove a whole slice.
+            pub fn remove_slice(&mut self, slice: S) -> Option<Curve<$index>> {
+                self.nodes.remove(&slice)
+            }
+
+            /// The curve at a fixed point of the first axis.
+            ///
+            /// Between slices, the curve has the nodes of both neighbouring
+            /// slices, with values interpolated across the surface.
+            ///
+            /// # Errors
+            /// - As for [`Surface::interpolate`].
+            pub fn slice(&self, slice: S) -> Result<Curve<$index>, RustQuantError> {
+                if let Some(curve) = self.nodes.get(&slice) {
+                    return Ok(curve.clone());
+                }
+
+                let left = self.nodes.range(..slice).next_back();
+                let right = self.nodes.range(slice..).next();
+
+                let mut indices = left
+                    .into_iter()
+                    .chain(right)
+                    .flat_map(|(_, curve)| curve.keys())
+                    .collect::<Vec<$index>>();
+                indices.sort();
+                indices.dedup();
+
+                let values = indices
+                    .iter()
+                    .map(|index| self.interpolate(slice, *index))
+                    .collect::<Result<Vec<f64>, RustQuantError>>()?;
+
+                Ok(Curve::<$index>::new_from_slice(&indices, &values)
+                    .with_interpolation(self.interpolation.curve_interpolation())
+                    .with_extrapolation(self.extrapolation))
+            }
+
+            /// Interpolate the surface at a point.
+            ///
+            /// # Errors
+            /// - `RustQuantError::MissingInput` if the surface has no nodes.
+            /// - `RustQuantError::OutsideOfRange` outside the nodes with
+            ///   [`CurveExtrapolation::Error`].
+            pub fn interpolate(&self, slice: S, index: $index) -> Result<f64, RustQuantError> {
+                if self.nodes.is_empty() {
+                    return Err(RustQuantError::MissingInput(
+                        "Cannot interpolate a surface without nodes.".to_string(),
+                    ));
+                }
+
+                if let Some(curve) = self.nodes.get(&slice) {
+                    return curve.interpolate(index);
+                }
+
+                let xs = self.nodes.keys().copied().collect::<Vec<S>>();
+                let (first, last) = (xs[0], xs[xs.len() - 1]);
+
+                if slice < first || slice > last {
+                    let ys = self
+                        .nodes
+                        .values()
+                        .map(|curve| curve.interpolate(index))
+                        .collect::<Result<Vec<f64>, RustQuantError>>()?;
+                    let n = xs.len();
+                    let (end, next) = if slice < first {
+                        (0, 1)
+                    } else {
+                        (n - 1, n - 2)
+                    };
+
+                    return match self.extrapolation {
+                        CurveExtrapolation::Error => Err(RustQuantError::OutsideOfRange),
+                        CurveExtrapolation::Linear if n > 1 => {
+                            let fraction = (slice - xs[end]) / (xs[next] - xs[end]);
+                            Ok(ys[end] + (ys[next] - ys[end]) * fraction)
+                        }
+                        _ => Ok(ys[end]),
+                    };
+                }
+
+                // The nodes shared by the range of every slice, and the point.
+                let lower = self
+                    .nodes
+                    .values()
+                    .filter_map(|curve| curve.first_key())
+                    .max();
+                let upper = self
+                    .nodes
+                    .values()
+                    .filter_map(|curve| curve.last_key())
+                    .min();
+
+                let mut indices = self
+                    .nodes
+                    .values()
+                    .flat_map(|curve| curve.keys())
+                    .filter(|key| Some(key) >= lower && Some(key) <= upper)
+                    .chain([index])
+                    .collect::<Vec<$index>>();
+                indices.sort();
+                indices.dedup();
+
+                let values = self
+                    .nodes
+                    .values()
+                    .map(|curve| {
+                        indices
+                            .iter()
+                            .map(|key| curve.interpolate(*key))
+                            .collect::<Result<Vec<f64>, RustQuantError>>()
+                    })
+                    .collect::<Result<Vec<Vec<f64>>, RustQuantError>>()?;
+
+                let mut interpolator = GridInterpolator::new(xs, indices, values)?
+                    .with_interpolation(self.interpolation.grid_interpolation());
+                interpolator.fit()?;
+                interpolator.interpolate(slice, index)
+            }
+
+            /// Apply the surface's settings to all of its slices.
+            fn configure_slices(&mut self) {
+                for curve in self.nodes.values_mut() {
+                    curve.interpolation = self.interpolation.curve_interpolation();
+                    curve.extrapolation = self.extrapolation;
+                }
+            }
+        }
+    };
 }
 
+// Implement the Surface for slices over temporal types.
+impl_surface!(time::Date);
+impl_surface!(time::Time);
+impl_surface!(time::OffsetDateTime);
+impl_surface!(time::PrimitiveDateTime);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Unit tests
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 #[cfg(test)]
-mod tests {
+mod tests_surfaces {
     use super::*;
-    use time::{Date, Month};
+    use time::macros::date;
+    use time::Date;
+    use RustQuant_utils::assert_approx_equal;
+
+    /// ATM swaption volatilities by option expiry and swap maturity.
+    fn swaption_surface() -> Surface<Date, Date> {
+        let expiries = [
+            date!(2025 - 01 - 01),
+            date!(2026 - 01 - 01),
+            date!(2027 - 01 - 01),
+        ];
+        let maturities = [
+            date!(2030 - 01 - 01),
+            date!(2035 - 01 - 01),
+            date!(2040 - 01 - 01),
+        ];
+        let vols = vec![
+            vec![0.30, 0.27, 0.25],
+            vec![0.28, 0.26, 0.24],
+            vec![0.26, 0.25, 0.23],
+        ];
+
+        Surface::<Date, Date>::from_grid(&expiries, &maturities, &vols).unwrap()
+    }
+
+    #[test]
+    fn test_surface_from_grid() {
+        let surface = swaption_surface();
+
+        assert_eq!(surface.len(), 9);
+        assert_eq!(
+            surface.get(date!(2026 - 01 - 01), date!(2035 - 01 - 01)),
+            Some(&0.26)
+        );
+        assert_approx_equal!(
+            surface
+                .interpolate(date!(2027 - 01 - 01), date!(2040 - 01 - 01))
+                .unwrap(),
+            0.23,
+            1e-12
+        );
+
+        let ragged = vec![vec![0.30, 0.27, 0.25], vec![0.28, 0.26]];
+        assert!(Surface::<Date, Date>::from_grid(
+            &[date!(2025 - 01 - 01), date!(2026 - 01 - 01)],
+            &[
+                date!(2030 - 01 - 01),
+                date!(2035 - 01 - 01),
+                date!(2040 - 01 - 01)
+            ],
+            &ragged,
+        )
+        .is_err());
+    }
 
     #[test]
-    fn test_surface_creation() {
-        let dates = vec![
-            Date::from_calendar_date(2023, Month::January, 1).unwrap(),
-            Date::from_calendar_date(2023, Month::February, 1).unwrap(),
-            Date::from_calendar_date(2023, Month::March, 1).unwrap(),
+    fn test_surface_bilinear() {
+        let surface = swaption_surface();
+
+        // Half way between 2025 and 2026 expiries (365 days apart), on the 2030 maturity.
+        let expiry = date!(2025 - 07 - 02);
+        let w = 182.0 / 365.0;
+        assert_approx_equal!(
+            surface.interpolate(expiry, date!(2030 - 01 - 01)).unwrap(),
+            0.30 + (0.28 - 0.30) * w,
+            1e-12
+        );
+
+        // Both directions at once.
+        let maturity = date!(2032 - 07 - 02);
+        let v =
+            (maturity - date!(2030 - 01 - 01)) / (date!(2035 - 01 - 01) - date!(2030 - 01 - 01));
+        let top = 0.30 + (0.27 - 0.30) * v;
+        let bottom = 0.28 + (0.26 - 0.28) * v;
+        assert_approx_equal!(
+            surface.interpolate(expiry, maturity).unwrap(),
+            top + (bottom - top) * w,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_surface_bicubic() {
+        let surface = swaption_surface().with_interpolation(SurfaceInterpolation::Bicubic);
+
+        assert!(surface
+            .nodes
+            .values()
+            .all(|curve| curve.interpolation == CurveInterpolation::CubicSpline));
+        assert_approx_equal!(
+            surface
+                .interpolate(date!(2026 - 01 - 01), date!(2035 - 01 - 01))
+                .unwrap(),
+            0.26,
+            1e-12
+        );
+
+        // A surface linear in both directions is reproduced exactly.
+        let expiries = [
+            date!(2025 - 01 - 01),
+            date!(2026 - 01 - 01),
+            date!(2028 - 01 - 01),
+        ];
+        let maturities = [
+            date!(2030 - 01 - 01),
+            date!(2035 - 01 - 01),
+            date!(2040 - 01 - 01),
         ];
-        let rates = [0.05, 0.06, 0.07];
+        let origin = date!(2025 - 01 - 01);
+        let f = |e: Date, m: Date| {
+            0.2 + 0.01 * ((e - origin).whole_days() as f64 / 365.0)
+                - 0.002 * ((m - origin).whole_days() as f64 / 365.0)
+        };
+        let vols = expiries
+            .iter()
+            .map(|e| maturities.iter().map(|m| f(*e, *m)).collect())
+            .collect::<Vec<Vec<f64>>>();
+
+        let linear = Surface::<Date, Date>::from_grid(&expiries, &maturities, &vols)
+            .unwrap()
+            .with_interpolation(SurfaceInterpolation::Bicubic);
 
-        let term_structure = TermStructure::new(&dates, &rates);
+        let (e, m) = (date!(2026 - 09 - 15), date!(2033 - 03 - 01));
+        assert_approx_equal!(linear.interpolate(e, m).unwrap(), f(e, m), 1e-12);
+    }
 
-        let mut surface = Surface::new();
-        surface.add_node(1, term_structure);
+    #[test]
+    fn test_surface_slice() {
+        let surface = swaption_surface();
 
-        assert_eq!(surface.nodes.len(), 1);
-        assert!(surface.get_term_structure(1).is_some());
+        let node = surface.slice(date!(2026 - 01 - 01)).unwrap();
+        assert_eq!(node.values(), vec![0.28, 0.26, 0.24]);
 
-        let ts = surface.get_term_structure(1).unwrap();
-        assert_eq!(ts.nodes.len(), 3);
-        assert_eq!(
-            ts.nodes
-                .get(&Date::from_calendar_date(2023, Month::January, 1).unwrap()),
-            Some(&0.05)
+        let between = surface.slice(date!(2025 - 07 - 02)).unwrap();
+        let w = 182.0 / 365.0;
+        assert_eq!(between.len(), 3);
+        assert_approx_equal!(
+            *between.get(date!(2040 - 01 - 01)).unwrap(),
+            0.25 + (0.24 - 0.25) * w,
+            1e-12
         );
+    }
+
+    #[test]
+    fn test_surface_insert_remove_and_extrapolation() {
+        let mut surface = swaption_surface();
+
+        surface.insert(date!(2028 - 01 - 01), date!(2035 - 01 - 01), 0.22);
+        assert_eq!(surface.nodes.len(), 4);
+        assert_eq!(surface.len(), 10);
+
         assert_eq!(
-            ts.nodes
-                .get(&Date::from_calendar_date(2023, Month::February, 1).unwrap()),
-            Some(&0.06)
+            surface.remove(date!(2028 - 01 - 01), date!(2035 - 01 - 01)),
+            Some(0.22)
         );
+        assert_eq!(surface.nodes.len(), 3);
         assert_eq!(
-            ts.nodes
-                .get(&Date::from_calendar_date(2023, Month::March, 1).unwrap()),
-            Some(&0.07)
+            surface.remove(date!(2028 - 01 - 01), date!(2035 - 01 - 01)),
+            None
+        );
+
+        // Flat by default, in both directions.
+        assert_approx_equal!(
+            surface
+                .interpolate(date!(2030 - 01 - 01), date!(2050 - 01 - 01))
+                .unwrap(),
+            0.23,
+            1e-12
         );
+
+        let strict = surface.with_extrapolation(CurveExtrapolation::Error);
+        assert!(strict
+            .interpolate(date!(2024 - 01 - 01), date!(2035 - 01 - 01))
+            .is_err());
+        assert!(strict
+            .interpolate(date!(2026 - 01 - 01), date!(2045 - 01 - 01))
+            .is_err());
+        assert!(Surface::<Date, Date>::new()
+            .interpolate(date!(2026 - 01 - 01), date!(2035 - 01 - 01))
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_surface_json_round_trip() {
+        let surface = swaption_surface().with_interpolation(SurfaceInterpolation::Bicubic);
+
+        let json = serde_json::to_string(&surface).unwrap();
+        assert!(json.contains("\"2026-01-01\""));
+
+        let restored: Surface<Date, Date> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, surface);
+
+        let slice: Curve<Date> = serde_json::from_str(
+            &serde_json::to_string(&surface.nodes[&date!(2025 - 01 - 01)]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(slice.interpolation, CurveInterpolation::CubicSpline);
     }
 }
@@ -0,0 +1,578 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Seeded synthetic market data, for examples, benchmarks and tests that
+//! should not depend on downloads.
+//!
+//! [`SyntheticMarket`] generates:
+//! - daily OHLCV price histories with GARCH(1,1) volatility clustering and
+//!   Merton jumps, in the same layout as [`crate::YahooFinanceData`],
+//! - option chains priced off any [`VolatilitySurface`], with quote noise
+//!   and a bid-ask spread,
+//! - discount curves from a Nelson-Siegel zero curve, with quote noise.
+//!
+//! The same seed always produces the same data.
+//!
+//! ```
+//! use RustQuant_data::*;
+//! use time::macros::date;
+//!
+//! let mut market = SyntheticMarket::new(42);
+//!
+//! let prices = market
+//!     .price_history(date!(2024 - 01 - 01), 252, &PriceHistoryConfig::default())
+//!     .unwrap();
+//! assert_eq!(prices.height(), 252);
+//!
+//! let curve = market.discount_curve(
+//!     date!(2024 - 01 - 01),
+//!     &NelsonSiegel::default(),
+//!     &[0.5, 1.0, 2.0, 5.0, 10.0],
+//!     0.0,
+//! );
+//! assert_eq!(curve.len(), 5);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Curve, VolatilitySurface};
+use polars::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use time::{Date, Duration, Weekday};
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::options::{GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Seeded generator of synthetic market data.
+#[derive(Debug, Clone)]
+pub struct SyntheticMarket {
+    rng: StdRng,
+}
+
+/// Dynamics of a synthetic daily price history.
+///
+/// Daily log-returns are
+/// $$ r_t = (\mu - \tfrac{1}{2} \sigma^2) \Delta t + \sqrt{h_t} z_t + J_t $$
+/// with GARCH(1,1) variance
+/// $h_t = \omega + \alpha h_{t-1} z_{t-1}^2 + \beta h_{t-1}$, where $\omega$
+/// is chosen so that the long-run volatility is $\sigma$, and $J_t$ is a
+/// normal jump that occurs with probability $\lambda \Delta t$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceHistoryConfig {
+    /// Price on the first day.
+    pub initial_price: f64,
+
+    /// Annual drift ($\mu$).
+    pub drift: f64,
+
+    /// Long-run annual volatility ($\sigma$).
+    pub volatility: f64,
+
+    /// Reaction of variance to the last shock ($\alpha$).
+    pub garch_alpha: f64,
+
+    /// Persistence of variance ($\beta$), with $\alpha + \beta < 1$.
+    pub garch_beta: f64,
+
+    /// Expected number of jumps per year ($\lambda$).
+    pub jump_intensity: f64,
+
+    /// Mean of the log-jump size.
+    pub jump_mean: f64,
+
+    /// Standard deviation of the log-jump size.
+    pub jump_volatility: f64,
+
+    /// Average daily traded volume.
+    pub mean_volume: f64,
+}
+
+/// Quotes for an option chain generated from a volatility surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChainConfig {
+    /// Spot price of the underlying.
+    pub spot: f64,
+
+    /// Continuously compounded risk-free rate.
+    pub risk_free_rate: f64,
+
+    /// Continuous dividend yield.
+    pub dividend_yield: f64,
+
+    /// Expiries in years.
+    pub expiries: Vec<f64>,
+
+    /// Strikes, quoted for every expiry.
+    pub strikes: Vec<f64>,
+
+    /// Standard deviation of the noise added to each mid volatility.
+    pub volatility_noise: f64,
+
+    /// Bid-ask spread in volatility points (e.g. 0.01 for one point).
+    pub volatility_spread: f64,
+}
+
+/// A synthetic option quote.
+#[derive(Debug, Clone, Copy)]
+pub struct SyntheticOptionQuote {
+    /// Expiry in years.
+    pub expiry: f64,
+
+    /// Strike.
+    pub strike: f64,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// Mid implied volatility.
+    pub implied_volatility: f64,
+
+    /// Bid price.
+    pub bid: f64,
+
+    /// Mid price.
+    pub mid: f64,
+
+    /// Ask price.
+    pub ask: f64,
+}
+
+/// Nelson-Siegel zero curve:
+/// $$ z(t) = \beta_0 + \beta_1 \frac{1 - e^{-t/\tau}}{t/\tau}
+///   + \beta_2 \left( \frac{1 - e^{-t/\tau}}{t/\tau} - e^{-t/\tau} \right) $$
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NelsonSiegel {
+    /// Long-term level ($\beta_0$).
+    pub beta0: f64,
+
+    /// Short-term component ($\beta_1$), the short rate is $\beta_0 + \beta_1$.
+    pub beta1: f64,
+
+    /// Medium-term hump ($\beta_2$).
+    pub beta2: f64,
+
+    /// Decay time in years ($\tau$).
+    pub tau: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for PriceHistoryConfig {
+    fn default() -> Self {
+        Self {
+            initial_price: 100.0,
+            drift: 0.07,
+            volatility: 0.2,
+            garch_alpha: 0.08,
+            garch_beta: 0.9,
+            jump_intensity: 2.0,
+            jump_mean: -0.02,
+            jump_volatility: 0.04,
+            mean_volume: 1e6,
+        }
+    }
+}
+
+impl Default for NelsonSiegel {
+    fn default() -> Self {
+        Self {
+            beta0: 0.04,
+            beta1: -0.01,
+            beta2: 0.01,
+            tau: 2.0,
+        }
+    }
+}
+
+impl NelsonSiegel {
+    /// Continuously compounded zero rate for a maturity in years.
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return self.beta0 + self.beta1;
+        }
+
+        let x = t / self.tau;
+        let loading = (1.0 - (-x).exp()) / x;
+
+        self.beta0 + self.beta1 * loading + self.beta2 * (loading - (-x).exp())
+    }
+
+    /// Discount factor for a maturity in years.
+    pub fn discount_factor(&self, t: f64) -> f64 {
+        (-self.zero_rate(t) * t).exp()
+    }
+}
+
+/// Julian day of 1970-01-01, the epoch of Polars dates.
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+impl SyntheticMarket {
+    /// Days per year used for daily price histories.
+    const TRADING_DAYS: f64 = 252.0;
+
+    /// New generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Daily price history over `days` weekdays from `start`, with columns
+    /// `date`, `open`, `high`, `low`, `close`, `volume` and `adjusted`.
+    pub fn price_history(
+        &mut self,
+        start: Date,
+        days: usize,
+        config: &PriceHistoryConfig,
+    ) -> Result<DataFrame, RustQuantError> {
+        let persistence = config.garch_alpha + config.garch_beta;
+        if config.garch_alpha < 0.0 || config.garch_beta < 0.0 || persistence >= 1.0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "GARCH parameters must be non-negative with alpha + beta < 1, got {} and {}.",
+                config.garch_alpha, config.garch_beta
+            )));
+        }
+        if config.initial_price <= 0.0 || config.volatility <= 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "Initial price and volatility must be positive.".to_string(),
+            ));
+        }
+
+        let dt = 1.0 / Self::TRADING_DAYS;
+        let long_run_variance = config.volatility.powi(2) * dt;
+        let omega = long_run_variance * (1.0 - persistence);
+        let jump_probability = (config.jump_intensity * dt).min(1.0);
+
+        let mut dates = Vec::with_capacity(days);
+        let mut open = Vec::with_capacity(days);
+        let mut high = Vec::with_capacity(days);
+        let mut low = Vec::with_capacity(days);
+        let mut close = Vec::with_capacity(days);
+        let mut volume = Vec::with_capacity(days);
+
+        let mut date = start;
+        let mut previous_close = config.initial_price;
+        let mut variance = long_run_variance;
+
+        for _ in 0..days {
+            while matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday) {
+                date += Duration::days(1);
+            }
+
+            let daily_volatility = variance.sqrt();
+            let z: f64 = self.rng.sample(StandardNormal);
+            let mut log_return =
+                (config.drift - 0.5 * config.volatility.powi(2)) * dt + daily_volatility * z;
+
+            if self.rng.gen::<f64>() < jump_probability {
+                let size: f64 = self.rng.sample(StandardNormal);
+                log_return += config.jump_mean + config.jump_volatility * size;
+            }
+
+            // Overnight gap, then the intraday range around open and close.
+            let gap: f64 = self.rng.sample(StandardNormal);
+            let day_open = previous_close * (0.2 * daily_volatility * gap).exp();
+            let day_close = previous_close * log_return.exp();
+            let (up, down): (f64, f64) = (
+                self.rng.sample(StandardNormal),
+                self.rng.sample(StandardNormal),
+            );
+
+            dates.push(date.to_julian_day() - UNIX_EPOCH_JULIAN_DAY);
+            open.push(day_open);
+            close.push(day_close);
+            high.push(day_open.max(day_close) * (0.5 * daily_volatility * up.abs()).exp());
+            low.push(day_open.min(day_close) * (-0.5 * daily_volatility * down.abs()).exp());
+
+            // Volume rises with the size of the move.
+            let noise: f64 = self.rng.sample(StandardNormal);
+            let shock = log_return.abs() / long_run_variance.sqrt();
+            volume.push((config.mean_volume * (0.25 * noise).exp() * (0.5 + 0.5 * shock)).round());
+
+            variance = omega + (config.garch_alpha * z * z + config.garch_beta) * variance;
+            previous_close = day_close;
+            date += Duration::days(1);
+        }
+
+        let df = df!(
+            "date" => Series::new("date".into(), dates).cast(&DataType::Date)?,
+            "open" => open,
+            "high" => high,
+            "low" => low,
+            "close" => close.clone(),
+            "volume" => volume,
+            "adjusted" => close
+        )?;
+
+        Ok(df)
+    }
+
+    /// Call and put quotes for every expiry and strike in `config`, with mid
+    /// volatilities read from `surface`.
+    pub fn option_chain<S: VolatilitySurface>(
+        &mut self,
+        surface: &S,
+        config: &OptionChainConfig,
+    ) -> Vec<SyntheticOptionQuote> {
+        let mut quotes = Vec::with_capacity(2 * config.expiries.len() * config.strikes.len());
+
+        for &expiry in &config.expiries {
+            for &strike in &config.strikes {
+                let noise: f64 = self.rng.sample(StandardNormal);
+                let volatility = (surface.volatility(expiry, strike)
+                    + config.volatility_noise * noise)
+                    .max(0.5 * config.volatility_spread + 1e-4);
+
+                let model = |volatility: f64| {
+                    Merton73::new(
+                        config.spot,
+                        config.risk_free_rate,
+                        config.dividend_yield,
+                        volatility,
+                    )
+                };
+                let half_spread = 0.5 * config.volatility_spread;
+
+                for option_type in [TypeFlag::Call, TypeFlag::Put] {
+                    quotes.push(SyntheticOptionQuote {
+                        expiry,
+                        strike,
+                        option_type,
+                        implied_volatility: volatility,
+                        bid: model(volatility - half_spread).price(strike, expiry, option_type),
+                        mid: model(volatility).price(strike, expiry, option_type),
+                        ask: model(volatility + half_spread).price(strike, expiry, option_type),
+                    });
+                }
+            }
+        }
+
+        quotes
+    }
+
+    /// Discount factors at `tenors` (in years) from `valuation_date`, with
+    /// normal noise of `noise_bp` basis points on each zero rate.
+    ///
+    /// Tenors are converted to dates as whole days on an Actual/365 basis.
+    pub fn discount_curve(
+        &mut self,
+        valuation_date: Date,
+        zero_curve: &NelsonSiegel,
+        tenors: &[f64],
+        noise_bp: f64,
+    ) -> Curve<Date> {
+        let mut curve = Curve::<Date>::new();
+
+        for &tenor in tenors {
+            let days = (tenor * 365.0).round() as i64;
+            let t = days as f64 / 365.0;
+            let noise: f64 = self.rng.sample(StandardNormal);
+            let zero_rate = zero_curve.zero_rate(t) + noise_bp * 1e-4 * noise;
+
+            curve.insert(
+                valuation_date + Duration::days(days),
+                (-zero_rate * t).exp(),
+            );
+        }
+
+        curve
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_synthetic {
+    use super::*;
+    use crate::{SviSlice, SviVolatilitySurface};
+    use time::macros::date;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn column(df: &DataFrame, name: &str) -> Vec<f64> {
+        df.column(name)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_price_history_is_reproducible() {
+        let config = PriceHistoryConfig::default();
+        let start = date!(2024 - 01 - 06);
+
+        let first = SyntheticMarket::new(1)
+            .price_history(start, 50, &config)
+            .unwrap();
+        let second = SyntheticMarket::new(1)
+            .price_history(start, 50, &config)
+            .unwrap();
+        let other = SyntheticMarket::new(2)
+            .price_history(start, 50, &config)
+            .unwrap();
+
+        assert!(first.equals(&second));
+        assert!(!first.equals(&other));
+        assert_eq!(
+            first.get_column_names_str(),
+            vec!["date", "open", "high", "low", "close", "volume", "adjusted"]
+        );
+
+        // Starts on a Saturday, so the first row is the Monday.
+        let dates = first.column("date").unwrap().date().unwrap();
+        assert_eq!(dates.get(0), Some(19730));
+
+        let (open, high, low, close) = (
+            column(&first, "open"),
+            column(&first, "high"),
+            column(&first, "low"),
+            column(&first, "close"),
+        );
+        for i in 0..50 {
+            assert!(high[i] >= open[i].max(close[i]));
+            assert!(low[i] <= open[i].min(close[i]));
+        }
+    }
+
+    #[test]
+    fn test_price_history_stylised_facts() {
+        let log_returns = |config: &PriceHistoryConfig| -> Vec<f64> {
+            let prices = SyntheticMarket::new(3)
+                .price_history(date!(2020 - 01 - 01), 5000, config)
+                .unwrap();
+
+            column(&prices, "close")
+                .windows(2)
+                .map(|p| (p[1] / p[0]).ln())
+                .collect()
+        };
+
+        let jumps = PriceHistoryConfig {
+            jump_intensity: 5.0,
+            ..PriceHistoryConfig::default()
+        };
+        let returns = log_returns(&jumps);
+
+        // Diffusive and jump variance add up: 0.2^2 + 5 * (0.02^2 + 0.04^2).
+        let volatility = returns.standard_deviation() * 252_f64.sqrt();
+        assert_approx_equal!(volatility, 0.05_f64.sqrt(), 0.02);
+
+        // Fat tails.
+        let mean = returns.mean();
+        let variance = returns.variance();
+        let kurtosis = returns.iter().map(|r| (r - mean).powi(4)).sum::<f64>()
+            / (returns.len() as f64 * variance * variance);
+        assert!(kurtosis > 4.0, "kurtosis {}", kurtosis);
+
+        // Volatility clustering: squared returns are autocorrelated.
+        let returns = log_returns(&PriceHistoryConfig {
+            jump_intensity: 0.0,
+            ..jumps
+        });
+        let squares: Vec<f64> = returns.iter().map(|r| r * r).collect();
+        let square_mean = squares.mean();
+        let lagged: f64 = squares
+            .windows(2)
+            .map(|s| (s[0] - square_mean) * (s[1] - square_mean))
+            .sum();
+        let total: f64 = squares.iter().map(|s| (s - square_mean).powi(2)).sum();
+        assert!(lagged / total > 0.05, "autocorrelation {}", lagged / total);
+
+        assert!(SyntheticMarket::new(0)
+            .price_history(
+                date!(2020 - 01 - 01),
+                10,
+                &PriceHistoryConfig {
+                    garch_alpha: 0.2,
+                    garch_beta: 0.8,
+                    ..jumps
+                }
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_option_chain() {
+        let slice = |expiry| SviSlice {
+            expiry,
+            a: 0.02 * expiry,
+            b: 0.1,
+            rho: -0.5,
+            m: 0.0,
+            sigma: 0.2,
+        };
+        let surface = SviVolatilitySurface::new(100.0, 0.02, vec![slice(0.5), slice(1.0)]);
+        let config = OptionChainConfig {
+            spot: 100.0,
+            risk_free_rate: 0.03,
+            dividend_yield: 0.01,
+            expiries: vec![0.5, 1.0],
+            strikes: vec![80.0, 100.0, 120.0],
+            volatility_noise: 0.0,
+            volatility_spread: 0.01,
+        };
+
+        let quotes = SyntheticMarket::new(4).option_chain(&surface, &config);
+        assert_eq!(quotes.len(), 12);
+
+        for pair in quotes.chunks(2) {
+            let (call, put) = (pair[0], pair[1]);
+            let (t, k) = (call.expiry, call.strike);
+
+            assert_approx_equal!(call.implied_volatility, surface.volatility(t, k), 1e-12);
+            assert!(call.bid < call.mid && call.mid < call.ask);
+            assert!(put.bid < put.mid && put.mid < put.ask);
+
+            // Put-call parity on mids.
+            assert_approx_equal!(
+                call.mid - put.mid,
+                100.0 * (-0.01 * t).exp() - k * (-0.03 * t).exp(),
+                1e-9
+            );
+        }
+
+        // Downside skew.
+        assert!(quotes[0].implied_volatility > quotes[4].implied_volatility);
+    }
+
+    #[test]
+    fn test_discount_curve() {
+        let zero_curve = NelsonSiegel::default();
+        let tenors = [0.25, 1.0, 2.0, 5.0, 10.0, 30.0];
+        let today = date!(2024 - 01 - 02);
+
+        let curve = SyntheticMarket::new(5).discount_curve(today, &zero_curve, &tenors, 0.0);
+        let factors: Vec<f64> = curve.nodes.values().copied().collect();
+
+        assert!(factors.windows(2).all(|df| df[1] < df[0]));
+        assert_approx_equal!(
+            *curve.get(today + Duration::days(3650)).unwrap(),
+            zero_curve.discount_factor(10.0),
+            1e-12
+        );
+
+        let noisy = SyntheticMarket::new(5).discount_curve(today, &zero_curve, &tenors, 5.0);
+        let date = today + Duration::days(1825);
+        let implied_rate = -noisy.get(date).unwrap().ln() / 5.0;
+        assert!((implied_rate - zero_curve.zero_rate(5.0)).abs() < 25e-4);
+        assert_ne!(noisy.get(date), curve.get(date));
+    }
+}
@@ -0,0 +1,200 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Vega bucketing of option portfolios.
+//!
+//! Instead of a single parallel vega, the portfolio is repriced after
+//! bumping each parameter of the volatility surface in turn. For a
+//! [PillarVolatilitySurface](crate::PillarVolatilitySurface) the buckets are
+//! the (expiry, strike) nodes, which map directly to hedge options; for an
+//! [SviVolatilitySurface](crate::SviVolatilitySurface) they are the SVI
+//! parameters of each slice, so the shifted surface stays a valid smile.
+
+use crate::volatility_surface::VolatilitySurface;
+use RustQuant_instruments::BlackScholesMerton;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sensitivity of a portfolio to one parameter of a volatility surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VegaBucket {
+    /// The parameter label (see [VolatilitySurface::parameter_labels]).
+    pub label: String,
+
+    /// Change in portfolio value per unit change in the parameter.
+    pub vega: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Value of a portfolio of `(option, quantity)` pairs, with each option
+/// priced at the surface's volatility for its expiry and strike.
+pub fn surface_value<S>(surface: &S, options: &[(BlackScholesMerton, f64)]) -> f64
+where
+    S: VolatilitySurface,
+{
+    options
+        .iter()
+        .map(|(option, quantity)| {
+            let T = option.year_fraction();
+            let volatility = surface.volatility(T, option.strike_price);
+
+            let repriced = BlackScholesMerton::new(
+                option.cost_of_carry,
+                option.underlying_price,
+                option.strike_price,
+                volatility,
+                option.risk_free_rate,
+                option.evaluation_date,
+                option.expiration_date,
+                option.option_type,
+            );
+
+            quantity * repriced.price()
+        })
+        .sum()
+}
+
+/// Vega per surface parameter, by central differences.
+///
+/// # Arguments
+///
+/// * `surface` - The volatility surface.
+/// * `options` - The portfolio, as `(option, quantity)` pairs.
+/// * `bump` - The size of the parameter bump.
+pub fn vega_buckets<S>(
+    surface: &S,
+    options: &[(BlackScholesMerton, f64)],
+    bump: f64,
+) -> Vec<VegaBucket>
+where
+    S: VolatilitySurface + Clone,
+{
+    let parameters = surface.parameters();
+    let mut bumped = surface.clone();
+
+    surface
+        .parameter_labels()
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let mut shifted = parameters.clone();
+
+            shifted[i] = parameters[i] + bump;
+            bumped.set_parameters(&shifted);
+            let up = surface_value(&bumped, options);
+
+            shifted[i] = parameters[i] - bump;
+            bumped.set_parameters(&shifted);
+            let down = surface_value(&bumped, options);
+
+            VegaBucket {
+                label,
+                vega: (up - down) / (2.0 * bump),
+            }
+        })
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_vega_buckets {
+    use super::*;
+    use crate::volatility_surface::{PillarVolatilitySurface, SviSlice, SviVolatilitySurface};
+    use time::macros::date;
+    use RustQuant_instruments::TypeFlag;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn option(strike: f64, expiry: time::Date) -> BlackScholesMerton {
+        BlackScholesMerton::new(
+            0.03,
+            100.0,
+            strike,
+            0.2,
+            0.03,
+            Some(date!(2024 - 01 - 01)),
+            expiry,
+            TypeFlag::Call,
+        )
+    }
+
+    #[test]
+    fn test_pillar_vega_buckets() {
+        let surface = PillarVolatilitySurface::new(
+            vec![0.5, 1.0, 2.0],
+            vec![90.0, 100.0, 110.0],
+            vec![vec![0.20; 3], vec![0.20; 3], vec![0.20; 3]],
+        );
+
+        // Between the 1Y and 2Y pillars, on the 100 strike.
+        let call = option(100.0, date!(2025 - 07 - 02));
+        let T = call.year_fraction();
+        let portfolio = vec![(call, 10.0)];
+
+        let buckets = vega_buckets(&surface, &portfolio, 1e-4);
+        let total: f64 = buckets.iter().map(|b| b.vega).sum();
+
+        // Bucketed vegas add up to the parallel vega.
+        assert_approx_equal!(total, 10.0 * portfolio[0].0.vega(), 1e-4);
+
+        // Only the 100 strike of the bracketing expiries carries risk, split by time.
+        let w = (T - 1.0) / (2.0 - 1.0);
+        assert_eq!(buckets[4].label, "1Y 100");
+        assert_approx_equal!(buckets[4].vega, (1.0 - w) * total, 1e-4);
+        assert_approx_equal!(buckets[7].vega, w * total, 1e-4);
+        assert!(buckets[0].vega.abs() < 1e-8);
+        assert!(buckets[5].vega.abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_svi_vega_buckets() {
+        let slice = SviSlice {
+            expiry: 1.0,
+            a: 0.03,
+            b: 0.1,
+            rho: -0.5,
+            m: 0.0,
+            sigma: 0.1,
+        };
+        let surface = SviVolatilitySurface::new(100.0, 0.03, vec![slice]);
+
+        let call = option(95.0, date!(2025 - 01 - 01));
+        let T = call.year_fraction();
+        let portfolio = vec![(call, 1.0)];
+
+        let buckets = vega_buckets(&surface, &portfolio, 1e-5);
+        assert_eq!(buckets.len(), 5);
+        assert_eq!(buckets[0].label, "1Y a");
+
+        // dV/da = vega * dvol/da, where vol = sqrt(w / 1Y) beyond the last slice.
+        let vol = surface.volatility(T, 95.0);
+        let option = BlackScholesMerton::new(
+            0.03,
+            100.0,
+            95.0,
+            vol,
+            0.03,
+            Some(date!(2024 - 01 - 01)),
+            date!(2025 - 01 - 01),
+            TypeFlag::Call,
+        );
+
+        let dvol_da = 1.0 / (2.0 * vol);
+
+        assert_approx_equal!(surface_value(&surface, &portfolio), option.price(), 1e-10);
+        assert_approx_equal!(buckets[0].vega, option.vega() * dvol_da, 1e-4);
+    }
+}
@@ -0,0 +1,348 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Implied volatility surfaces.
+//!
+//! Two parameterisations are provided:
+//! - [PillarVolatilitySurface]: implied volatilities quoted on an
+//!   (expiry, strike) grid, interpolated bilinearly by a [GridInterpolator].
+//! - [SviVolatilitySurface]: one raw SVI smile per expiry, with total
+//!   variance interpolated linearly in time at fixed log-moneyness.
+//!
+//! Both implement [VolatilitySurface], which exposes the surface's own
+//! parameters so that risk can be computed against them.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use RustQuant_math::interpolation::GridInterpolator;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Volatility surface trait.
+pub trait VolatilitySurface {
+    /// Implied volatility for an expiry (in years) and strike.
+    fn volatility(&self, expiry: f64, strike: f64) -> f64;
+
+    /// The parameters that define the surface.
+    fn parameters(&self) -> Vec<f64>;
+
+    /// Replace the parameters that define the surface
+    /// (same order and length as [VolatilitySurface::parameters]).
+    fn set_parameters(&mut self, parameters: &[f64]);
+
+    /// A label for each parameter, e.g. `"1Y 100"` or `"1Y rho"`.
+    fn parameter_labels(&self) -> Vec<String>;
+}
+
+/// Implied volatilities on an (expiry, strike) grid, interpolated
+/// bilinearly and extrapolated flat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PillarVolatilitySurface {
+    /// Expiries in years, strikes, and implied volatilities.
+    grid: GridInterpolator<f64, f64, f64>,
+}
+
+/// Raw SVI parameterisation of a single smile:
+/// $$ w(k) = a + b \left( \rho (k - m) + \sqrt{(k - m)^2 + \sigma^2} \right) $$
+/// where $w$ is total implied variance and $k = \ln(K / F)$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SviSlice {
+    /// Expiry in years.
+    pub expiry: f64,
+
+    /// Overall variance level ($a$).
+    pub a: f64,
+
+    /// Wing slope ($b$).
+    pub b: f64,
+
+    /// Skew ($\rho$).
+    pub rho: f64,
+
+    /// Horizontal shift ($m$).
+    pub m: f64,
+
+    /// ATM curvature ($\sigma$).
+    pub sigma: f64,
+}
+
+/// Surface made of SVI smiles, one per expiry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SviVolatilitySurface {
+    /// Spot price of the underlying.
+    pub spot: f64,
+
+    /// Cost of carry, so that the forward is $F(t) = S e^{bt}$.
+    pub cost_of_carry: f64,
+
+    /// SVI slices, ordered by expiry.
+    pub slices: Vec<SviSlice>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bilinear interpolator of `values[i][j]` at (`xs[i]`, `ys[j]`).
+///
+/// # Panics
+///
+/// Panics if the grid is empty or the values do not match its shape.
+pub(crate) fn bilinear_grid(
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    values: Vec<Vec<f64>>,
+) -> GridInterpolator<f64, f64, f64> {
+    assert!(!xs.is_empty() && !ys.is_empty());
+
+    let mut grid = GridInterpolator::new(xs, ys, values).expect("The values match the grid.");
+    grid.fit().expect("The grid is not empty.");
+
+    grid
+}
+
+/// Interpolate a grid, extrapolating flat outside it.
+pub(crate) fn interpolate_flat(grid: &GridInterpolator<f64, f64, f64>, x: f64, y: f64) -> f64 {
+    let ((x_first, x_last), (y_first, y_last)) = grid.range();
+
+    grid.interpolate(x.clamp(x_first, x_last), y.clamp(y_first, y_last))
+        .expect("The point is on the grid.")
+}
+
+/// Index of the grid interval containing `x`, and the weight on its upper node.
+/// Points outside the grid are extrapolated flat.
+pub(crate) fn bracket(grid: &[f64], x: f64) -> (usize, f64) {
+    if grid.len() == 1 || x <= grid[0] {
+        return (0, 0.0);
+    }
+    if x >= grid[grid.len() - 1] {
+        return (grid.len() - 2, 1.0);
+    }
+
+    let i = grid.partition_point(|&g| g <= x) - 1;
+
+    (i, (x - grid[i]) / (grid[i + 1] - grid[i]))
+}
+
+impl PillarVolatilitySurface {
+    /// Create a new pillar surface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty or the volatilities do not match its shape.
+    pub fn new(expiries: Vec<f64>, strikes: Vec<f64>, volatilities: Vec<Vec<f64>>) -> Self {
+        Self {
+            grid: bilinear_grid(expiries, strikes, volatilities),
+        }
+    }
+
+    /// Expiries in years (increasing).
+    pub fn expiries(&self) -> &[f64] {
+        &self.grid.xs
+    }
+
+    /// Strikes (increasing).
+    pub fn strikes(&self) -> &[f64] {
+        &self.grid.ys
+    }
+
+    /// Implied volatilities, `volatilities[i][j]` for `expiries[i]` and `strikes[j]`.
+    pub fn volatilities(&self) -> &[Vec<f64>] {
+        &self.grid.values
+    }
+}
+
+impl VolatilitySurface for PillarVolatilitySurface {
+    fn volatility(&self, expiry: f64, strike: f64) -> f64 {
+        interpolate_flat(&self.grid, expiry, strike)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        self.grid.values.iter().flatten().copied().collect()
+    }
+
+    fn set_parameters(&mut self, parameters: &[f64]) {
+        assert_eq!(parameters.len(), self.grid.xs.len() * self.grid.ys.len());
+
+        for (row, values) in self
+            .grid
+            .values
+            .iter_mut()
+            .zip(parameters.chunks(self.grid.ys.len()))
+        {
+            row.copy_from_slice(values);
+        }
+    }
+
+    fn parameter_labels(&self) -> Vec<String> {
+        self.grid
+            .xs
+            .iter()
+            .flat_map(|t| self.grid.ys.iter().map(move |k| format!("{}Y {}", t, k)))
+            .collect()
+    }
+}
+
+impl SviSlice {
+    /// Total implied variance at log-moneyness `k`.
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Implied volatility at log-moneyness `k`.
+    pub fn volatility(&self, k: f64) -> f64 {
+        (self.total_variance(k) / self.expiry).sqrt()
+    }
+}
+
+impl SviVolatilitySurface {
+    /// Create a new SVI surface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no slices.
+    pub fn new(spot: f64, cost_of_carry: f64, mut slices: Vec<SviSlice>) -> Self {
+        assert!(!slices.is_empty());
+        slices.sort_by(|s1, s2| s1.expiry.total_cmp(&s2.expiry));
+
+        Self {
+            spot,
+            cost_of_carry,
+            slices,
+        }
+    }
+
+    /// Forward price at `expiry`.
+    pub fn forward(&self, expiry: f64) -> f64 {
+        self.spot * (self.cost_of_carry * expiry).exp()
+    }
+}
+
+impl VolatilitySurface for SviVolatilitySurface {
+    fn volatility(&self, expiry: f64, strike: f64) -> f64 {
+        let k = (strike / self.forward(expiry)).ln();
+        let (first, last) = (self.slices[0], self.slices[self.slices.len() - 1]);
+
+        // Before the first or after the last slice, keep the slice's implied volatility.
+        if expiry <= first.expiry {
+            return first.volatility(k);
+        }
+        if expiry >= last.expiry {
+            return last.volatility(k);
+        }
+
+        let expiries: Vec<f64> = self.slices.iter().map(|s| s.expiry).collect();
+        let (i, u) = bracket(&expiries, expiry);
+
+        let w =
+            (1.0 - u) * self.slices[i].total_variance(k) + u * self.slices[i + 1].total_variance(k);
+
+        (w / expiry).sqrt()
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        self.slices
+            .iter()
+            .flat_map(|s| [s.a, s.b, s.rho, s.m, s.sigma])
+            .collect()
+    }
+
+    fn set_parameters(&mut self, parameters: &[f64]) {
+        assert_eq!(parameters.len(), 5 * self.slices.len());
+
+        for (slice, p) in self.slices.iter_mut().zip(parameters.chunks(5)) {
+            (slice.a, slice.b, slice.rho, slice.m, slice.sigma) = (p[0], p[1], p[2], p[3], p[4]);
+        }
+    }
+
+    fn parameter_labels(&self) -> Vec<String> {
+        self.slices
+            .iter()
+            .flat_map(|s| {
+                ["a", "b", "rho", "m", "sigma"]
+                    .iter()
+                    .map(move |p| format!("{}Y {}", s.expiry, p))
+            })
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_volatility_surface {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_pillar_surface_interpolation() {
+        let surface = PillarVolatilitySurface::new(
+            vec![0.5, 1.0],
+            vec![90.0, 110.0],
+            vec![vec![0.25, 0.21], vec![0.23, 0.20]],
+        );
+
+        // On a node.
+        assert_approx_equal!(surface.volatility(0.5, 90.0), 0.25, 1e-12);
+
+        // Bilinear in the middle.
+        assert_approx_equal!(surface.volatility(0.75, 100.0), 0.2225, 1e-12);
+
+        // Flat outside the grid.
+        assert_approx_equal!(surface.volatility(2.0, 200.0), 0.20, 1e-12);
+    }
+
+    #[test]
+    fn test_svi_surface() {
+        let slice = |expiry: f64, a: f64| SviSlice {
+            expiry,
+            a,
+            b: 0.1,
+            rho: -0.4,
+            m: 0.0,
+            sigma: 0.2,
+        };
+        let surface =
+            SviVolatilitySurface::new(100.0, 0.0, vec![slice(1.0, 0.02), slice(0.5, 0.01)]);
+
+        // ATM total variance: a + b * sigma.
+        assert_approx_equal!(
+            surface.volatility(0.5, 100.0),
+            (0.03_f64 / 0.5).sqrt(),
+            1e-12
+        );
+        assert_approx_equal!(surface.volatility(1.0, 100.0), 0.04_f64.sqrt(), 1e-12);
+
+        // Linear in total variance between slices.
+        assert_approx_equal!(
+            surface.volatility(0.75, 100.0),
+            (0.035_f64 / 0.75).sqrt(),
+            1e-12
+        );
+
+        // Negative rho: downside skew.
+        assert!(surface.volatility(1.0, 80.0) > surface.volatility(1.0, 120.0));
+
+        let mut bumped = surface.clone();
+        let mut parameters = surface.parameters();
+        parameters[0] += 0.01;
+        bumped.set_parameters(&parameters);
+
+        assert_eq!(bumped.slices[0].a, 0.02);
+        assert_eq!(surface.parameter_labels()[0], "0.5Y a");
+    }
+}
@@ -0,0 +1,288 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Websocket client for the live feeds, on top of `tokio-tungstenite`.
+//!
+//! The protocol (handshake and accept key, fragmentation, ping/pong and
+//! closing) is left to the library. Frames and messages are capped in
+//! size, so that a misbehaving server cannot make the client allocate
+//! without bound.
+
+use futures_util::{SinkExt, StreamExt};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore};
+use tokio_tungstenite::{
+    connect_async_tls_with_config,
+    tungstenite::{
+        self,
+        protocol::{frame::coding::CloseCode, CloseFrame, WebSocketConfig},
+    },
+    Connector, MaybeTlsStream, WebSocketStream,
+};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Largest message accepted from the server (16 MiB).
+const MAX_MESSAGE_SIZE: usize = 16 << 20;
+
+/// Largest single frame accepted from the server (4 MiB).
+const MAX_FRAME_SIZE: usize = 4 << 20;
+
+/// Data message received from the server.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Message {
+    /// UTF-8 text message.
+    Text(String),
+
+    /// Binary message.
+    Binary(Vec<u8>),
+}
+
+/// Client end of a websocket connection.
+pub(crate) struct WebSocket<S> {
+    stream: WebSocketStream<S>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl WebSocket<MaybeTlsStream<TcpStream>> {
+    /// Connect to a `ws://` or `wss://` URL.
+    pub(crate) async fn connect(url: &str) -> Result<Self, RustQuantError> {
+        let connector = Connector::Rustls(Arc::new(tls_config()?));
+
+        let (stream, _) = connect_async_tls_with_config(url, Some(config()), true, Some(connector))
+            .await
+            .map_err(websocket_error)?;
+
+        Ok(Self { stream })
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> WebSocket<S> {
+    /// Run the opening handshake for `url` over an established stream.
+    #[cfg(test)]
+    pub(crate) async fn handshake(stream: S, url: &str) -> Result<Self, RustQuantError> {
+        let (stream, _) = tokio_tungstenite::client_async_with_config(url, stream, Some(config()))
+            .await
+            .map_err(websocket_error)?;
+
+        Ok(Self { stream })
+    }
+
+    /// Next data message. Pings are answered by the library.
+    ///
+    /// Returns `None` once the server has closed the connection.
+    pub(crate) async fn recv(&mut self) -> Result<Option<Message>, RustQuantError> {
+        while let Some(message) = self.stream.next().await {
+            match message {
+                Ok(tungstenite::Message::Text(text)) => {
+                    return Ok(Some(Message::Text(text.to_string())))
+                }
+                Ok(tungstenite::Message::Binary(data)) => {
+                    return Ok(Some(Message::Binary(data.to_vec())))
+                }
+                // Send the close reply the library queued, and stop.
+                Ok(tungstenite::Message::Close(_)) => {
+                    self.stream.flush().await.ok();
+                    break;
+                }
+                Ok(_) => {}
+                Err(tungstenite::Error::ConnectionClosed) => break,
+                Err(error) => return Err(websocket_error(error)),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Start the closing handshake.
+    pub(crate) async fn close(&mut self) -> Result<(), RustQuantError> {
+        let frame = CloseFrame {
+            code: CloseCode::Normal,
+            reason: "".into(),
+        };
+
+        self.stream
+            .close(Some(frame))
+            .await
+            .map_err(websocket_error)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+fn config() -> WebSocketConfig {
+    WebSocketConfig::default()
+        .max_message_size(Some(MAX_MESSAGE_SIZE))
+        .max_frame_size(Some(MAX_FRAME_SIZE))
+}
+
+fn tls_config() -> Result<ClientConfig, RustQuantError> {
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    Ok(
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?
+            .with_root_certificates(roots)
+            .with_no_client_auth(),
+    )
+}
+
+fn websocket_error(error: tungstenite::Error) -> RustQuantError {
+    match error {
+        tungstenite::Error::Io(error) => RustQuantError::IoError(error),
+        tungstenite::Error::Url(error) => RustQuantError::InvalidArgument(error.to_string()),
+        error => RustQuantError::ComputationError(format!("Websocket error: {error}")),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_websocket {
+    use super::*;
+    use futures_util::future::{join, select, Either};
+    use tokio::io::{duplex, AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream};
+    use tokio_tungstenite::accept_async;
+
+    /// Handshake against an in-memory server; returns the client and the server end.
+    async fn connected() -> (WebSocket<DuplexStream>, WebSocketStream<DuplexStream>) {
+        let (client, server) = duplex(1 << 16);
+
+        let (client, server) = join(
+            WebSocket::handshake(client, "ws://example.com/ws"),
+            accept_async(server),
+        )
+        .await;
+
+        (client.unwrap(), server.unwrap())
+    }
+
+    #[test]
+    fn test_messages_and_ping() {
+        tokio_test::block_on(async {
+            let (mut socket, mut server) = connected().await;
+
+            for message in [
+                tungstenite::Message::text("hello, world"),
+                tungstenite::Message::Ping(b"beat".to_vec().into()),
+                tungstenite::Message::binary(vec![7; 300]),
+            ] {
+                server.send(message).await.unwrap();
+            }
+
+            assert_eq!(
+                socket.recv().await.unwrap(),
+                Some(Message::Text("hello, world".into()))
+            );
+            assert_eq!(
+                socket.recv().await.unwrap(),
+                Some(Message::Binary(vec![7; 300]))
+            );
+
+            // The ping was answered with a pong carrying its payload.
+            socket.close().await.unwrap();
+            assert_eq!(
+                server.next().await.unwrap().unwrap(),
+                tungstenite::Message::Pong(b"beat".to_vec().into())
+            );
+        });
+    }
+
+    #[test]
+    fn test_close() {
+        tokio_test::block_on(async {
+            let (mut socket, mut server) = connected().await;
+
+            server.close(None).await.unwrap();
+
+            assert_eq!(socket.recv().await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn test_oversized_frame() {
+        tokio_test::block_on(async {
+            let (mut socket, mut server) = connected().await;
+
+            let frame = tungstenite::Message::binary(vec![0; MAX_FRAME_SIZE + 1]);
+
+            // The client gives up on the frame from its header, before it is all sent.
+            let received = match select(Box::pin(socket.recv()), Box::pin(server.send(frame))).await
+            {
+                Either::Left((received, _)) => received,
+                Either::Right(_) => panic!("The frame was sent in full."),
+            };
+            assert!(received.is_err());
+        });
+    }
+
+    #[test]
+    fn test_rejected_handshake() {
+        tokio_test::block_on(async {
+            let (client, mut server) = duplex(1024);
+            server
+                .write_all(b"HTTP/1.1 403 Forbidden\r\n\r\n")
+                .await
+                .unwrap();
+
+            assert!(WebSocket::handshake(client, "ws://example.com/")
+                .await
+                .is_err());
+        });
+    }
+
+    #[test]
+    fn test_wrong_accept_key() {
+        tokio_test::block_on(async {
+            let (client, server) = duplex(1024);
+            let mut server = BufReader::new(server);
+
+            let respond = async {
+                // Read the request, then answer with an accept key that
+                // does not match its Sec-WebSocket-Key.
+                loop {
+                    let mut line = String::new();
+                    server.read_line(&mut line).await.unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+
+                server
+                    .write_all(
+                        b"HTTP/1.1 101 Switching Protocols\r\n\
+                          Upgrade: websocket\r\n\
+                          Connection: Upgrade\r\n\
+                          Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n\r\n",
+                    )
+                    .await
+                    .unwrap();
+            };
+
+            let (handshake, _) =
+                join(WebSocket::handshake(client, "ws://example.com/"), respond).await;
+
+            assert!(handshake.is_err());
+        });
+    }
+}
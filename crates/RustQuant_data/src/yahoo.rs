@@ -10,11 +10,16 @@
 //! Module to fetch data from Yahoo! Finance,
 //! and store it in a Polars DataFrame object.
 
+use crate::{
+    CorporateActions, Dividend, MarketDataCache, OptionChain, OptionChainProvider, OptionQuote,
+    Split,
+};
 use polars::prelude::*;
 use time::OffsetDateTime;
 use yahoo::YahooError;
 use yahoo_finance_api as yahoo;
 use RustQuant_error::RustQuantError;
+use RustQuant_instruments::options::TypeFlag;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, TRAITS, AND ENUMS
@@ -36,6 +41,10 @@ pub struct YahooFinanceData {
     pub options_chain: Option<DataFrame>,
     /// Latest available quote.
     pub latest_quote: Option<DataFrame>,
+    /// Dividend and split history.
+    pub corporate_actions: Option<CorporateActions>,
+    /// Cache of downloaded price histories, if any.
+    pub cache: Option<MarketDataCache>,
 }
 
 /// Return type for the Yahoo! Finance data struct.
@@ -53,11 +62,14 @@ pub trait YahooFinanceReader {
     /// Retrieves the price history from Yahoo! Finance.
     fn get_price_history(&mut self) -> Result<(), RustQuantError>;
 
-    // /// Retrieves the options chain from Yahoo! Finance.
-    // fn get_options_chain(&mut self) -> Result<(), RustQuantError>;
+    /// Retrieves the options chain from Yahoo! Finance.
+    fn get_options_chain(&mut self) -> Result<(), RustQuantError>;
 
     /// Retrieves the latest quote from Yahoo! Finance.
     fn get_latest_quote(&mut self) -> Result<(), RustQuantError>;
+
+    /// Retrieves the dividend and split history from Yahoo! Finance.
+    fn get_corporate_actions(&mut self) -> Result<(), RustQuantError>;
 }
 
 impl Default for YahooFinanceData {
@@ -70,6 +82,8 @@ impl Default for YahooFinanceData {
             returns: None,
             options_chain: None,
             latest_quote: None,
+            corporate_actions: None,
+            cache: None,
         }
     }
 }
@@ -103,6 +117,50 @@ impl YahooFinanceData {
         self.end = Some(end);
     }
 
+    /// Sets the cache used by `get_price_history`.
+    pub fn set_cache(&mut self, cache: MarketDataCache) {
+        self.cache = Some(cache);
+    }
+
+    /// Downloads the price history, without the cache.
+    fn download_price_history(&self, ticker: &str) -> Result<DataFrame, RustQuantError> {
+        let provider = yahoo::YahooConnector::new()?;
+
+        let response = tokio_test::block_on(provider.get_quote_history(
+            ticker,
+            self.start.unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            self.end.unwrap_or(OffsetDateTime::now_utc()),
+        ))?;
+
+        let quotes = response.quotes()?;
+
+        // The timestamp from Yahoo! Finance is in seconds since UNIX Epoch (1970-01-01).
+        // So we need to divide by the number of seconds in a day (86,400s) to get the date,
+        // otherwise the date basically overflows out of the range.
+        let date = quotes
+            .iter()
+            .map(|q| (q.timestamp / (24 * 60 * 60)) as i32)
+            .collect::<Vec<_>>();
+        let open = quotes.iter().map(|q| q.open).collect::<Vec<_>>();
+        let high = quotes.iter().map(|q| q.high).collect::<Vec<_>>();
+        let low = quotes.iter().map(|q| q.low).collect::<Vec<_>>();
+        let close = quotes.iter().map(|q| q.close).collect::<Vec<_>>();
+        let volume = quotes.iter().map(|q| q.volume as f64).collect::<Vec<_>>();
+        let adjclose = quotes.iter().map(|q| q.adjclose).collect::<Vec<_>>();
+
+        let df = df!(
+            "date" => Series::new("date".into(), date).cast(&DataType::Date)?,
+            "open" => open,
+            "high" => high,
+            "low" => low,
+            "close" => close,
+            "volume" => volume,
+            "adjusted" => adjclose
+        );
+
+        Ok(df?)
+    }
+
     /// Computes the returns from the price history.
     pub fn compute_returns(&mut self, returns_type: ReturnsType) -> Result<(), RustQuantError> {
         if self.price_history.is_none() {
@@ -183,6 +241,36 @@ impl YahooFinanceData {
 
 impl YahooFinanceReader for YahooFinanceData {
     fn get_price_history(&mut self) -> Result<(), RustQuantError> {
+        let ticker = self.ticker.clone().ok_or(RustQuantError::MissingInput(
+            "No ticker provided.".to_string(),
+        ))?;
+
+        let df = match &self.cache {
+            Some(cache) => cache.get_or_fetch(
+                &ticker,
+                self.start.unwrap_or(OffsetDateTime::UNIX_EPOCH).date(),
+                self.end.unwrap_or(OffsetDateTime::now_utc()).date(),
+                || self.download_price_history(&ticker),
+            )?,
+            None => self.download_price_history(&ticker)?,
+        };
+
+        self.price_history = Some(df);
+
+        Ok(())
+    }
+
+    fn get_options_chain(&mut self) -> Result<(), RustQuantError> {
+        let ticker = self.ticker.clone().ok_or(RustQuantError::MissingInput(
+            "No ticker provided.".to_string(),
+        ))?;
+
+        self.options_chain = Some(self.option_chain(&ticker)?.to_dataframe()?);
+
+        Ok(())
+    }
+
+    fn get_corporate_actions(&mut self) -> Result<(), RustQuantError> {
         let provider = yahoo::YahooConnector::new()?;
 
         let response = tokio_test::block_on(provider.get_quote_history(
@@ -193,107 +281,40 @@ impl YahooFinanceReader for YahooFinanceData {
             self.end.unwrap_or(OffsetDateTime::now_utc()),
         ))?;
 
-        let quotes = response.quotes()?;
+        // Event timestamps are in seconds since UNIX Epoch, like the quotes.
+        let date = |timestamp: u64| {
+            OffsetDateTime::from_unix_timestamp(timestamp as i64).map(|t| t.date())
+        };
 
-        // The timestamp from Yahoo! Finance is in seconds since UNIX Epoch (1970-01-01).
-        // So we need to divide by the number of seconds in a day (86,400s) to get the date,
-        // otherwise the date basically overflows out of the range.
-        let date = quotes
+        let dividends = response
+            .dividends()?
             .iter()
-            .map(|q| (q.timestamp / (24 * 60 * 60)) as i32)
-            .collect::<Vec<_>>();
-        let open = quotes.iter().map(|q| q.open).collect::<Vec<_>>();
-        let high = quotes.iter().map(|q| q.high).collect::<Vec<_>>();
-        let low = quotes.iter().map(|q| q.low).collect::<Vec<_>>();
-        let close = quotes.iter().map(|q| q.close).collect::<Vec<_>>();
-        let volume = quotes.iter().map(|q| q.volume as f64).collect::<Vec<_>>();
-        let adjclose = quotes.iter().map(|q| q.adjclose).collect::<Vec<_>>();
-
-        let df = df!(
-            "date" => Series::new("date".into(), date).cast(&DataType::Date)?,
-            "open" => open,
-            "high" => high,
-            "low" => low,
-            "close" => close,
-            "volume" => volume,
-            "adjusted" => adjclose
-        );
+            .map(|d| {
+                Ok(Dividend {
+                    date: date(d.date)?,
+                    amount: d.amount,
+                })
+            })
+            .collect::<Result<Vec<_>, time::error::ComponentRange>>()
+            .map_err(|e| RustQuantError::ComputationError(e.to_string()))?;
+
+        let splits = response
+            .splits()?
+            .iter()
+            .map(|s| {
+                Ok(Split {
+                    date: date(s.date)?,
+                    ratio: s.numerator / s.denominator,
+                })
+            })
+            .collect::<Result<Vec<_>, time::error::ComponentRange>>()
+            .map_err(|e| RustQuantError::ComputationError(e.to_string()))?;
 
-        self.price_history = Some(df?);
+        self.corporate_actions = Some(CorporateActions::new(dividends, splits));
 
         Ok(())
     }
 
-    // fn get_options_chain(&mut self) -> Result<(), RustQuantError> {
-    //     let provider = yahoo::YahooConnector::new()?;
-    //     let response = tokio_test::block_on(provider.search_options(self.ticker.as_ref().ok_or(
-    //         RustQuantError::MissingInput("No ticker provided.".to_string()),
-    //     )?))?;
-
-    //     let options = response.option_chain.result;
-
-    //     // YOptionResult { name: "AAPL230526C00250000", strike: 250.0, last_trade_date: "2023-05-25 3:12PM EDT",
-    //     //                 last_price: 250.0, bid: 250.0, ask: 250.0, change: 250.0, change_pct: 250.0,
-    //     //                 volume: 0, open_interest: 0, impl_volatility: 250.0 }
-
-    //     // CANNOT IMPLEMENT THIS YET, BECAUSE THE YAHOO FINANCE API
-    //     // DOES NOT RETURN THE CORRECT OPTIONS CHAIN.
-    //     // Issue opened: https://github.com/xemwebe/yahoo_finance_api/issues/28
-    //     // Pull request opened: https://github.com/xemwebe/yahoo_finance_api/pull/29
-    //     // Pull request was merged.
-    //     // Now we need to wait for the next release of the yahoo_finance_api crate.
-
-    //     let contract = options
-    //         .iter()
-    //         .map(|o| o.underlying_symbol)
-    //         .collect::<Vec<_>>();
-    //     let strike = options.iter().map(|o| o.strikes).collect::<Vec<_>>();
-    //     let last_trade_date = options
-    //         .iter()
-    //         .map(|o| o.options.last_trade_date.clone()) //(o.last_trade_date / (24 * 60 * 60)) as i32)
-    //         .collect::<Vec<_>>();
-    //     let last_price = options
-    //         .iter()
-    //         .map(|o| o.options..last_price)
-    //         .collect::<Vec<_>>();
-    //     let bid = options.iter().map(|o| o.bid).collect::<Vec<_>>();
-    //     let ask = options.iter().map(|o| o.ask).collect::<Vec<_>>();
-    //     let change = options.iter().map(|o| o.change).collect::<Vec<_>>();
-    //     let change_pct = options.iter().map(|o| o.change_pct).collect::<Vec<_>>();
-    //     let volume = options.iter().map(|o| o.volume as u64).collect::<Vec<_>>();
-    //     let open_interest = options
-    //         .iter()
-    //         .map(|o| o.open_interest as u64)
-    //         .collect::<Vec<_>>();
-    //     let impl_volatility = options
-    //         .iter()
-    //         .map(|o| o.impl_volatility)
-    //         .collect::<Vec<_>>();
-
-    //     let df = df!(
-    //         "contract" => contract,
-    //         "strike" => strike,
-    //         "last_trade_date" => Series::new("last_trade_date".into(), last_trade_date)
-    //             .cast(&DataType::Date)
-    //             ?,
-    //         "last_price" => last_price,
-    //         "bid" => bid,
-    //         "ask" => ask,
-    //         "change" => change,
-    //         "change_pct" => change_pct,
-    //         "volume" => volume,
-    //         "open_interest" => open_interest,
-    //         "impl_volatility" => impl_volatility
-    //         // "contract" => Series::new("date", date).cast(&DataType::Date)?,
-    //     );
-
-    //     self.options_chain = Some(df?);
-
-    //     println!("{:?}", self.options_chain);
-
-    //     Ok(())
-    // }
-
     fn get_latest_quote(&mut self) -> Result<(), RustQuantError> {
         let provider = yahoo::YahooConnector::new()?;
         let response = tokio_test::block_on(
@@ -335,6 +356,134 @@ impl YahooFinanceReader for YahooFinanceData {
     }
 }
 
+impl OptionChainProvider for YahooFinanceData {
+    /// Every expiry of the chain. Yahoo! Finance returns one expiry per
+    /// request, so this makes one request per listed expiry.
+    fn option_chain(&self, underlying: &str) -> Result<OptionChain, RustQuantError> {
+        let url = format!("{}/{}", YAHOO_OPTIONS_URL, underlying);
+        let get = |query: &[(&str, String)]| {
+            let request = reqwest::Client::new().get(&url).query(query).send();
+
+            tokio_test::block_on(async {
+                request
+                    .await?
+                    .error_for_status()?
+                    .json::<serde_json::Value>()
+                    .await
+            })
+        };
+
+        let (mut chain, expirations) = parse_option_chain(&get(&[])?)?;
+        let listed = chain.expiries();
+
+        for expiration in expirations {
+            let expiry = OffsetDateTime::from_unix_timestamp(expiration)
+                .map_err(|e| RustQuantError::ComputationError(e.to_string()))?
+                .date();
+
+            if !listed.contains(&expiry) {
+                let (slice, _) = parse_option_chain(&get(&[("date", expiration.to_string())])?)?;
+                chain.quotes.extend(slice.quotes);
+            }
+        }
+
+        Ok(OptionChain::new(
+            &chain.underlying,
+            chain.spot,
+            chain.valuation_date,
+            chain.quotes,
+        ))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const YAHOO_OPTIONS_URL: &str = "https://query2.finance.yahoo.com/v7/finance/options";
+
+/// Parses one response of the Yahoo! Finance options endpoint into the
+/// chain of the expiries it contains, and the timestamps of every listed expiry.
+fn parse_option_chain(
+    response: &serde_json::Value,
+) -> Result<(OptionChain, Vec<i64>), RustQuantError> {
+    let missing =
+        |what: &str| RustQuantError::MissingInput(format!("No {} in the options chain.", what));
+    let date = |timestamp: i64| {
+        OffsetDateTime::from_unix_timestamp(timestamp)
+            .map(|t| t.date())
+            .map_err(|e| RustQuantError::ComputationError(e.to_string()))
+    };
+    let number = |value: &serde_json::Value, key: &str| value.get(key).and_then(|v| v.as_f64());
+
+    let result = response
+        .pointer("/optionChain/result/0")
+        .ok_or_else(|| missing("result"))?;
+
+    let underlying = result
+        .get("underlyingSymbol")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| missing("underlying symbol"))?;
+    let quote = result.get("quote").ok_or_else(|| missing("quote"))?;
+    let spot = number(quote, "regularMarketPrice").ok_or_else(|| missing("spot price"))?;
+    let valuation_date = match quote.get("regularMarketTime").and_then(|v| v.as_i64()) {
+        Some(timestamp) => date(timestamp)?,
+        None => OffsetDateTime::now_utc().date(),
+    };
+
+    let expirations = result
+        .get("expirationDates")
+        .and_then(|v| v.as_array())
+        .map(|dates| dates.iter().filter_map(|d| d.as_i64()).collect())
+        .unwrap_or_default();
+
+    let mut quotes = Vec::new();
+
+    for options in result
+        .get("options")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let expiry = date(
+            options
+                .get("expirationDate")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| missing("expiration date"))?,
+        )?;
+
+        for (side, option_type) in [("calls", TypeFlag::Call), ("puts", TypeFlag::Put)] {
+            for contract in options
+                .get(side)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                let Some(strike) = number(contract, "strike") else {
+                    continue;
+                };
+
+                quotes.push(OptionQuote {
+                    expiry,
+                    strike,
+                    option_type,
+                    bid: number(contract, "bid").unwrap_or(0.0),
+                    ask: number(contract, "ask").unwrap_or(0.0),
+                    last: number(contract, "lastPrice").unwrap_or(0.0),
+                    volume: number(contract, "volume").unwrap_or(0.0),
+                    open_interest: number(contract, "openInterest").unwrap_or(0.0),
+                    implied_volatility: number(contract, "impliedVolatility"),
+                });
+            }
+        }
+    }
+
+    Ok((
+        OptionChain::new(underlying, spot, valuation_date, quotes),
+        expirations,
+    ))
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -357,6 +506,37 @@ mod test_yahoo {
         println!("Apple's quotes: {:?}", yfd.price_history)
     }
 
+    #[test]
+    fn test_get_price_history_offline_cache() {
+        let dir = std::env::temp_dir().join(format!("rustquant_yahoo_{}", std::process::id()));
+        let cache = crate::MarketDataCache::new(&dir).with_mode(crate::CacheMode::Offline);
+
+        let mut yfd = YahooFinanceData::new("AAPL".to_string());
+        yfd.set_date_range(
+            time::macros::datetime!(2019 - 01 - 01 0:00 UTC),
+            time::macros::datetime!(2020 - 01 - 01 0:00 UTC),
+        );
+        yfd.set_cache(cache.clone());
+
+        // Nothing cached yet, and offline mode never downloads.
+        assert!(yfd.get_price_history().is_err());
+
+        let mut cached = df!("close" => [157.92, 142.19]).unwrap();
+        cache
+            .store(
+                "AAPL",
+                time::macros::date!(2019 - 01 - 01),
+                time::macros::date!(2020 - 01 - 01),
+                &mut cached,
+            )
+            .unwrap();
+
+        yfd.get_price_history().unwrap();
+        assert!(yfd.price_history.unwrap().equals(&cached));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_compute_returns() {
         let mut yfd = YahooFinanceData::new("AAPL".to_string());
@@ -368,6 +548,18 @@ mod test_yahoo {
 
         println!("Apple's returns: {:?}", yfd.returns)
     }
+    #[test]
+    fn test_get_corporate_actions() {
+        let mut yfd = YahooFinanceData::new("AAPL".to_string());
+
+        yfd.set_start_date(time::macros::datetime!(2020 - 01 - 01 0:00 UTC));
+        yfd.set_end_date(time::macros::datetime!(2021 - 01 - 01 0:00 UTC));
+
+        let _ = yfd.get_corporate_actions();
+
+        println!("Apple's corporate actions: {:?}", yfd.corporate_actions)
+    }
+
     #[test]
     fn test_get_latest_quote() {
         let mut yfd = YahooFinanceData::new("AAPL".to_string());
@@ -377,12 +569,53 @@ mod test_yahoo {
         println!("Apple's latest quote: {:?}", yfd.latest_quote)
     }
 
-    // #[test]
-    // fn test_get_options_chain() {
-    //     let mut yfd = YahooFinanceData::new("AAPL".to_string());
+    #[test]
+    fn test_get_options_chain() {
+        let mut yfd = YahooFinanceData::new("AAPL".to_string());
 
-    //     let _ = yfd.get_options_chain();
+        let _ = yfd.get_options_chain();
+
+        println!("Apple's options chain: {:?}", yfd.options_chain)
+    }
 
-    //     // println!("Apple's options chain: {:?}", yfd.options_chain)
-    // }
+    #[test]
+    fn test_parse_option_chain() {
+        let response = serde_json::json!({
+            "optionChain": {
+                "result": [{
+                    "underlyingSymbol": "AAPL",
+                    "expirationDates": [1718928000, 1719532800],
+                    "quote": { "regularMarketPrice": 190.5, "regularMarketTime": 1717790400 },
+                    "options": [{
+                        "expirationDate": 1718928000,
+                        "calls": [{
+                            "strike": 195.0, "bid": 2.1, "ask": 2.2, "lastPrice": 2.15,
+                            "volume": 1200, "openInterest": 5400, "impliedVolatility": 0.21
+                        }],
+                        "puts": [{
+                            "strike": 185.0, "bid": 1.4, "ask": 1.5, "lastPrice": 1.45,
+                            "openInterest": 3100, "impliedVolatility": 0.23
+                        }]
+                    }]
+                }],
+                "error": null
+            }
+        });
+
+        let (chain, expirations) = parse_option_chain(&response).unwrap();
+
+        assert_eq!(chain.underlying, "AAPL");
+        assert_eq!(chain.spot, 190.5);
+        assert_eq!(chain.valuation_date, time::macros::date!(2024 - 06 - 07));
+        assert_eq!(chain.expiries(), vec![time::macros::date!(2024 - 06 - 21)]);
+        assert_eq!(chain.quotes[1].option_type, TypeFlag::Call);
+        assert_eq!(chain.quotes[0].volume, 0.0);
+        assert_eq!(chain.smile(chain.expiries()[0]).1, vec![0.23, 0.21]);
+        assert_eq!(expirations, vec![1718928000, 1719532800]);
+
+        let failed = serde_json::json!({
+            "optionChain": { "result": [], "error": { "description": "Not Found" } }
+        });
+        assert!(parse_option_chain(&failed).is_err());
+    }
 }
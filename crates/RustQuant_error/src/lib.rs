@@ -47,6 +47,14 @@ pub enum RustQuantError {
     #[error("Yahoo! Finance error: {0}")]
     YahooError(#[from] yahoo_finance_api::YahooError),
 
+    /// Error variant arising from HTTP requests to market data providers.
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    /// Error variant arising from parsing JSON responses.
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     /// Error variant arising from Polars.
     #[error("Polars error: {0}")]
     PolarsError(#[from] polars::error::PolarsError),
@@ -58,6 +66,10 @@ pub enum RustQuantError {
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Statistical distribution related errors
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    /// Error variant from constructing Beta distribution.
+    #[error("{0}")]
+    Beta(#[from] rand_distr::BetaError),
+
     /// Error variant from constructing Bernoulli distribution.
     #[error("{0}")]
     Bernoulli(#[from] rand_distr::BernoulliError),
@@ -82,10 +94,18 @@ pub enum RustQuantError {
     #[error("{0}")]
     Gaussian(#[from] rand_distr::NormalError),
 
+    /// Error variant from constructing Pareto distribution.
+    #[error("{0}")]
+    Pareto(#[from] rand_distr::ParetoError),
+
     /// Error variant from constructing Poisson distribution.
     #[error("{0}")]
     Poisson(#[from] rand_distr::PoissonError),
 
+    /// Error variant from constructing Weibull distribution.
+    #[error("{0}")]
+    Weibull(#[from] rand_distr::WeibullError),
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Linear regression related errors
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -0,0 +1,182 @@
+#!/usr/bin/env python3
+"""
+Generate golden files for the differential tests in `src/golden.rs`.
+
+Usage:
+
+    python3 generate.py [--backend quantlib|reference] [--output european.csv]
+
+Backends:
+
+    quantlib   QuantLib's AnalyticEuropeanEngine and bachelierBlackFormula
+               (`pip install QuantLib`).
+    reference  Closed-form Black-Scholes-Merton and Bachelier formulas in pure
+               Python, following QuantLib's conventions. No dependencies.
+
+Conventions (same for both backends):
+
+    - `s` is the spot, or the forward for black76, asay82 and bachelier.
+    - `q` is the continuous yield, so the cost of carry is b = r - q
+      (q = r for black76, q = r_f for gk83).
+    - `v` is the lognormal volatility, or the normal volatility for bachelier.
+    - Greeks are per unit change: vega per 1.00 of volatility, rho per 1.00
+      of rate (spot and q fixed), theta per year (-dV/dT).
+    - Empty cells are not checked.
+"""
+
+import argparse
+import itertools
+import math
+
+MODELS = {
+    # model: (r, q) as functions of the base rate and yield.
+    "bs73": lambda r, q: (r, 0.0),
+    "merton73": lambda r, q: (r, q),
+    "black76": lambda r, q: (r, r),
+    "asay82": lambda r, q: (0.0, 0.0),
+    "gk83": lambda r, q: (r, q),
+}
+
+OPTION_TYPES = ["call", "put"]
+STRIKES = [80.0, 100.0, 120.0]
+MATURITIES = [0.2, 2.0]
+VOLATILITIES = [0.15, 0.4]
+NORMAL_VOLATILITIES = [10.0, 30.0]
+RATE, YIELD = 0.05, 0.02
+SPOT = 100.0
+
+MEASURES = ["price", "delta", "gamma", "vega", "theta", "rho"]
+
+
+def cases():
+    for model, rates in MODELS.items():
+        r, q = rates(RATE, YIELD)
+        for option_type, k, t, v in itertools.product(
+            OPTION_TYPES, STRIKES, MATURITIES, VOLATILITIES
+        ):
+            yield model, option_type, SPOT, k, t, r, q, v
+
+    for option_type, k, t, v in itertools.product(
+        OPTION_TYPES, STRIKES, MATURITIES, NORMAL_VOLATILITIES
+    ):
+        yield "bachelier", option_type, SPOT, k, t, RATE, 0.0, v
+
+
+# ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+# Reference backend
+# ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+
+def cdf(x):
+    return 0.5 * math.erfc(-x / math.sqrt(2.0))
+
+
+def pdf(x):
+    return math.exp(-0.5 * x * x) / math.sqrt(2.0 * math.pi)
+
+
+def reference_bsm(option_type, s, k, t, r, q, v):
+    w = 1.0 if option_type == "call" else -1.0
+    sqrt_t = math.sqrt(t)
+    d1 = (math.log(s / k) + (r - q + 0.5 * v * v) * t) / (v * sqrt_t)
+    d2 = d1 - v * sqrt_t
+    dq, dr = math.exp(-q * t), math.exp(-r * t)
+
+    return {
+        "price": w * (s * dq * cdf(w * d1) - k * dr * cdf(w * d2)),
+        "delta": w * dq * cdf(w * d1),
+        "gamma": dq * pdf(d1) / (s * v * sqrt_t),
+        "vega": s * dq * pdf(d1) * sqrt_t,
+        "theta": -s * dq * pdf(d1) * v / (2.0 * sqrt_t)
+        + w * (q * s * dq * cdf(w * d1) - r * k * dr * cdf(w * d2)),
+        "rho": w * k * t * dr * cdf(w * d2),
+    }
+
+
+def reference_bachelier(option_type, f, k, t, r, v):
+    w = 1.0 if option_type == "call" else -1.0
+    std_dev = v * math.sqrt(t)
+    d = (f - k) / std_dev
+
+    return {"price": math.exp(-r * t) * (w * (f - k) * cdf(w * d) + std_dev * pdf(d))}
+
+
+def reference(model, option_type, s, k, t, r, q, v):
+    if model == "bachelier":
+        return reference_bachelier(option_type, s, k, t, r, v)
+    return reference_bsm(option_type, s, k, t, r, q, v)
+
+
+# ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+# QuantLib backend
+# ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+
+def quantlib(model, option_type, s, k, t, r, q, v):
+    import QuantLib as ql
+
+    ql_type = ql.Option.Call if option_type == "call" else ql.Option.Put
+
+    if model == "bachelier":
+        discount = math.exp(-r * t)
+        price = ql.bachelierBlackFormula(ql_type, k, s, v * math.sqrt(t), discount)
+        return {"price": price}
+
+    # Flat curves on Actual/365 Fixed: the year fraction to expiry is exactly
+    # t as long as t * 365 is a whole number of days.
+    today = ql.Date(1, 1, 2024)
+    ql.Settings.instance().evaluationDate = today
+    day_counter = ql.Actual365Fixed()
+    days = round(t * 365.0)
+    assert abs(days - t * 365.0) < 1e-9, "Maturities must be whole days."
+
+    spot = ql.QuoteHandle(ql.SimpleQuote(s))
+    rates = ql.YieldTermStructureHandle(ql.FlatForward(today, r, day_counter))
+    yields = ql.YieldTermStructureHandle(ql.FlatForward(today, q, day_counter))
+    vols = ql.BlackVolTermStructureHandle(
+        ql.BlackConstantVol(today, ql.NullCalendar(), v, day_counter)
+    )
+    process = ql.BlackScholesMertonProcess(spot, yields, rates, vols)
+
+    option = ql.VanillaOption(
+        ql.PlainVanillaPayoff(ql_type, k), ql.EuropeanExercise(today + days)
+    )
+    option.setPricingEngine(ql.AnalyticEuropeanEngine(process))
+
+    return {
+        "price": option.NPV(),
+        "delta": option.delta(),
+        "gamma": option.gamma(),
+        "vega": option.vega(),
+        "theta": option.theta(),
+        "rho": option.rho(),
+    }
+
+
+# ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+# Main
+# ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[1])
+    parser.add_argument("--backend", choices=["quantlib", "reference"], default="quantlib")
+    parser.add_argument("--output", default="european.csv")
+    args = parser.parse_args()
+
+    backend = quantlib if args.backend == "quantlib" else reference
+
+    with open(args.output, "w") as file:
+        file.write(f"# Generated by generate.py --backend {args.backend}\n")
+        file.write(",".join(["model", "option_type", "s", "k", "t", "r", "q", "v"] + MEASURES))
+        file.write("\n")
+
+        for case in cases():
+            values = backend(*case)
+            inputs = [case[0], case[1]] + [repr(x) for x in case[2:]]
+            outputs = [repr(values[m]) if m in values else "" for m in MEASURES]
+            file.write(",".join(inputs + outputs) + "\n")
+
+
+if __name__ == "__main__":
+    main()
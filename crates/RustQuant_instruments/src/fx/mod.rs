@@ -15,3 +15,6 @@ pub use exchange::*;
 
 pub mod money;
 pub use money::*;
+
+pub mod settlement;
+pub use settlement::*;
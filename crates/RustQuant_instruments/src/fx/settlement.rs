@@ -0,0 +1,277 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! FX spot and forward settlement dates.
+//!
+//! The spot date is a number of business days (the spot lag) after the
+//! trade date, T+2 for most currency pairs and T+1 for a few, e.g. USD/CAD.
+//! The market conventions are:
+//!     - The days up to spot are counted in the calendars of the non-USD
+//!       currencies, so a US holiday before the spot date is skipped over,
+//!       except for some Latin American currencies (e.g. MXN).
+//!     - The spot date itself must be a business day for both currencies
+//!       and for USD, since all FX trades ultimately settle through USD.
+//!     - Forward dates are a tenor after spot, and a spot date on the last
+//!       business day of a month gives forwards on the last business day.
+
+use super::{Currency, CurrencyPair};
+use super::{ARS, BRL, CAD, CLP, COP, KZT, MXN, PEN, PHP, RUB, TRY, USD};
+use time::Date;
+use RustQuant_time::{
+    Calendar, DateRollingConvention, JointCalendar, Tenor, TenorUnit, UnitedStatesCalendar,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Currencies that spot against USD in one business day.
+const SPOT_NEXT_DAY: [Currency; 5] = [CAD, TRY, PHP, RUB, KZT];
+
+/// Currencies whose spot lag also counts USD holidays.
+const USD_HOLIDAYS_COUNTED: [Currency; 6] = [MXN, ARS, CLP, BRL, COP, PEN];
+
+/// Settlement date calculator for an FX currency pair.
+pub struct FxSettlement<B, Q> {
+    /// Currency pair.
+    pub pair: CurrencyPair,
+
+    /// Settlement calendar: the base currency calendar, joined with the
+    /// quote currency and USD calendars.
+    pub calendar: JointCalendar<B, JointCalendar<Q, UnitedStatesCalendar>>,
+
+    /// Business days from the trade date to the spot date.
+    pub spot_lag: u8,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<B: Calendar, Q: Calendar> FxSettlement<B, Q> {
+    /// Create a settlement date calculator from the holiday calendars of
+    /// the base and quote currencies, with the market spot lag of the pair.
+    ///
+    /// # Example
+    /// ```
+    /// use RustQuant::instruments::fx::*;
+    /// use RustQuant::time::{TargetCalendar, UnitedStatesCalendar};
+    /// use time::macros::date;
+    ///
+    /// let eur_usd = FxSettlement::new(
+    ///     CurrencyPair::new(EUR, USD),
+    ///     TargetCalendar,
+    ///     UnitedStatesCalendar,
+    /// );
+    ///
+    /// // Independence Day is skipped over, but cannot be the spot date.
+    /// assert_eq!(eur_usd.spot_date(date!(2024 - 07 - 03)), date!(2024 - 07 - 05));
+    /// assert_eq!(eur_usd.spot_date(date!(2024 - 07 - 02)), date!(2024 - 07 - 05));
+    /// ```
+    pub fn new(pair: CurrencyPair, base_calendar: B, quote_calendar: Q) -> Self {
+        Self {
+            pair,
+            calendar: JointCalendar::join(
+                base_calendar,
+                JointCalendar::join(quote_calendar, UnitedStatesCalendar),
+            ),
+            spot_lag: default_spot_lag(&pair),
+        }
+    }
+
+    /// Set the spot lag, in business days.
+    pub fn with_spot_lag(mut self, spot_lag: u8) -> Self {
+        self.spot_lag = spot_lag;
+        self
+    }
+
+    /// Check if the date is a settlement day: a business day for both
+    /// currencies and for USD.
+    pub fn is_settlement_day(&self, date: Date) -> bool {
+        self.calendar.is_business_day(date)
+    }
+
+    /// Spot date of a trade on the trade date.
+    pub fn spot_date(&self, trade_date: Date) -> Date {
+        let mut date = trade_date;
+
+        for _ in 0..self.spot_lag {
+            date = date.next_day().unwrap();
+
+            while !self.counts_towards_spot(date) {
+                date = date.next_day().unwrap();
+            }
+        }
+
+        while !self.is_settlement_day(date) {
+            date = date.next_day().unwrap();
+        }
+
+        date
+    }
+
+    /// Forward settlement date of a trade on the trade date, a tenor after spot.
+    ///
+    /// Day and week tenors roll to the following settlement day, month and
+    /// year tenors to the modified following one.
+    pub fn forward_date(&self, trade_date: Date, tenor: Tenor) -> Date {
+        let convention = match tenor.unit {
+            TenorUnit::Days | TenorUnit::Weeks => DateRollingConvention::Following,
+            TenorUnit::Months | TenorUnit::Years => DateRollingConvention::ModifiedFollowing,
+        };
+
+        tenor.advance(
+            self.spot_date(trade_date),
+            &self.calendar,
+            &convention,
+            true,
+        )
+    }
+
+    // Whether the date counts as one of the business days up to spot.
+    fn counts_towards_spot(&self, date: Date) -> bool {
+        let (base, quote) = (&self.calendar.first, &self.calendar.second.first);
+        let counts_usd_holidays = USD_HOLIDAYS_COUNTED.contains(&self.pair.base)
+            || USD_HOLIDAYS_COUNTED.contains(&self.pair.quote);
+
+        (self.pair.base == USD || base.is_business_day(date))
+            && (self.pair.quote == USD || quote.is_business_day(date))
+            && (!counts_usd_holidays || UnitedStatesCalendar.is_business_day(date))
+    }
+}
+
+/// Market spot lag of a currency pair, in business days.
+///
+/// T+1 for USD against CAD, TRY, PHP, RUB and KZT, and T+2 otherwise.
+pub fn default_spot_lag(pair: &CurrencyPair) -> u8 {
+    let next_day = (pair.base == USD && SPOT_NEXT_DAY.contains(&pair.quote))
+        || (pair.quote == USD && SPOT_NEXT_DAY.contains(&pair.base));
+
+    match next_day {
+        true => 1,
+        false => 2,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_fx_settlement {
+    use super::*;
+    use crate::fx::{EUR, GBP};
+    use time::macros::date;
+    use RustQuant_time::{CanadaCalendar, MexicoCalendar, TargetCalendar, UnitedKingdomCalendar};
+
+    #[test]
+    fn test_default_spot_lag() {
+        assert_eq!(default_spot_lag(&CurrencyPair::new(USD, CAD)), 1);
+        assert_eq!(default_spot_lag(&CurrencyPair::new(TRY, USD)), 1);
+        assert_eq!(default_spot_lag(&CurrencyPair::new(EUR, CAD)), 2);
+        assert_eq!(default_spot_lag(&CurrencyPair::new(EUR, USD)), 2);
+    }
+
+    #[test]
+    fn test_usd_holiday_before_spot() {
+        let eur_usd = FxSettlement::new(
+            CurrencyPair::new(EUR, USD),
+            TargetCalendar,
+            UnitedStatesCalendar,
+        );
+        let usd_mxn = FxSettlement::new(
+            CurrencyPair::new(USD, MXN),
+            UnitedStatesCalendar,
+            MexicoCalendar,
+        );
+
+        // Independence Day (Thursday) is T+1: it counts for EUR/USD,
+        // but not for USD/MXN.
+        assert_eq!(
+            eur_usd.spot_date(date!(2024 - 07 - 03)),
+            date!(2024 - 07 - 05)
+        );
+        assert_eq!(
+            usd_mxn.spot_date(date!(2024 - 07 - 03)),
+            date!(2024 - 07 - 08)
+        );
+    }
+
+    #[test]
+    fn test_currency_holidays() {
+        let gbp_usd = FxSettlement::new(
+            CurrencyPair::new(GBP, USD),
+            UnitedKingdomCalendar,
+            UnitedStatesCalendar,
+        );
+        let usd_cad = FxSettlement::new(
+            CurrencyPair::new(USD, CAD),
+            UnitedStatesCalendar,
+            CanadaCalendar,
+        );
+        let eur_gbp = FxSettlement::new(
+            CurrencyPair::new(EUR, GBP),
+            TargetCalendar,
+            UnitedKingdomCalendar,
+        );
+
+        // Summer Bank Holiday on T+1.
+        assert_eq!(
+            gbp_usd.spot_date(date!(2024 - 08 - 23)),
+            date!(2024 - 08 - 28)
+        );
+
+        // T+1 spot, over Canada Day.
+        assert_eq!(
+            usd_cad.spot_date(date!(2024 - 06 - 28)),
+            date!(2024 - 07 - 02)
+        );
+        assert_eq!(
+            usd_cad.spot_date(date!(2024 - 07 - 02)),
+            date!(2024 - 07 - 03)
+        );
+
+        // Crosses do not settle on US holidays.
+        assert_eq!(
+            eur_gbp.spot_date(date!(2024 - 07 - 02)),
+            date!(2024 - 07 - 05)
+        );
+        assert!(!eur_gbp.is_settlement_day(date!(2024 - 07 - 04)));
+    }
+
+    #[test]
+    fn test_forward_date() {
+        let eur_usd = FxSettlement::new(
+            CurrencyPair::new(EUR, USD),
+            TargetCalendar,
+            UnitedStatesCalendar,
+        );
+
+        // Spot on the last business day of January, so the one month
+        // forward is on the last business day of February.
+        let trade_date = date!(2024 - 01 - 29);
+        assert_eq!(eur_usd.spot_date(trade_date), date!(2024 - 01 - 31));
+        assert_eq!(
+            eur_usd.forward_date(trade_date, Tenor::months(1)),
+            date!(2024 - 02 - 29)
+        );
+        assert_eq!(
+            eur_usd.forward_date(trade_date, Tenor::weeks(1)),
+            date!(2024 - 02 - 07)
+        );
+
+        // Modified following stays in the month.
+        let trade_date = date!(2024 - 05 - 28);
+        assert_eq!(eur_usd.spot_date(trade_date), date!(2024 - 05 - 30));
+        assert_eq!(
+            eur_usd.forward_date(trade_date, Tenor::months(3)),
+            date!(2024 - 08 - 30)
+        );
+    }
+}
@@ -0,0 +1,451 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Differential tests against external pricers.
+//!
+//! Golden files are CSV files of prices and Greeks produced by another
+//! library (QuantLib, py_vollib, ...) for a matrix of models and parameters.
+//! The files in `golden/` are produced by `golden/generate.py`, which also
+//! documents the column conventions. Each row is repriced with the matching
+//! RustQuant model and compared under a [`GoldenTolerances`].
+//!
+//! The unit tests check every `*.csv` file in `golden/`, and in the directory
+//! given by the `RUSTQUANT_GOLDEN_DIR` environment variable if it is set, so
+//! files generated elsewhere can be checked without adding them to the repo.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::options::{
+    Asay82, Bachelier, Black76, BlackScholes73, GarmanKohlhagen83, GeneralisedBlackScholesMerton,
+    Merton73, TypeFlag,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Measures a golden file can contain, in column order.
+pub const GOLDEN_MEASURES: [&str; 6] = ["price", "delta", "gamma", "vega", "theta", "rho"];
+
+/// One row of a golden file.
+#[derive(Debug, Clone)]
+pub struct GoldenCase {
+    /// Model name: `bs73`, `merton73`, `black76`, `asay82`, `gk83` or `bachelier`.
+    pub model: String,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// Spot (or forward, for the futures models and Bachelier).
+    pub s: f64,
+
+    /// Strike.
+    pub k: f64,
+
+    /// Time to expiry, in years.
+    pub t: f64,
+
+    /// Risk-free rate.
+    pub r: f64,
+
+    /// Continuous yield, so the cost of carry is `r - q`.
+    pub q: f64,
+
+    /// Volatility (normal volatility for Bachelier).
+    pub v: f64,
+
+    /// Expected values by measure. Measures without a value are not checked.
+    pub expected: HashMap<String, f64>,
+}
+
+/// Tolerance on a single value: `|actual - expected| <= absolute + relative * |expected|`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoldenTolerance {
+    /// Absolute tolerance.
+    pub absolute: f64,
+
+    /// Relative tolerance.
+    pub relative: f64,
+}
+
+/// Tolerances by measure, with a default for the others.
+#[derive(Debug, Clone)]
+pub struct GoldenTolerances {
+    /// Tolerance for measures without their own.
+    pub default: GoldenTolerance,
+
+    /// Tolerances by measure name.
+    pub measures: HashMap<String, GoldenTolerance>,
+}
+
+/// A value that differs from its golden value by more than the tolerance.
+#[derive(Debug, Clone)]
+pub struct GoldenMismatch {
+    /// The case that failed.
+    pub case: GoldenCase,
+
+    /// Measure that failed.
+    pub measure: String,
+
+    /// Golden value.
+    pub expected: f64,
+
+    /// RustQuant value.
+    pub actual: f64,
+}
+
+/// Result of checking a set of golden cases.
+#[derive(Debug, Clone, Default)]
+pub struct GoldenReport {
+    /// Number of values compared.
+    pub checked: usize,
+
+    /// Values outside the tolerance.
+    pub mismatches: Vec<GoldenMismatch>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl GoldenCase {
+    /// Value of a measure under the matching RustQuant model.
+    pub fn evaluate(&self, measure: &str) -> Result<f64, RustQuantError> {
+        let (k, t, flag) = (self.k, self.t, self.option_type);
+
+        if self.model == "bachelier" {
+            let model = Bachelier::new(self.s, self.r, self.v);
+
+            return match measure {
+                "price" => Ok(model.price(k, t, flag)),
+                "delta" => Ok(model.delta(k, t, flag)),
+                "gamma" => Ok(model.gamma(k, t, flag)),
+                "vega" => Ok(model.vega(k, t, flag)),
+                "theta" => Ok(model.theta(k, t, flag)),
+                _ => Err(unsupported(&self.model, measure)),
+            };
+        }
+
+        let model: Box<dyn GeneralisedBlackScholesMerton> = match self.model.as_str() {
+            "bs73" => Box::new(BlackScholes73::new(self.s, self.r, self.v)),
+            "merton73" => Box::new(Merton73::new(self.s, self.r, self.q, self.v)),
+            "black76" => Box::new(Black76::new(self.s, self.r, self.v)),
+            "asay82" => Box::new(Asay82::new(self.s, self.v)),
+            "gk83" => Box::new(GarmanKohlhagen83::new(self.s, self.r, self.q, self.v)),
+            other => {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Unknown golden model: {}.",
+                    other
+                )))
+            }
+        };
+
+        match measure {
+            "price" => Ok(model.price(k, t, flag)),
+            "delta" => Ok(model.delta(k, t, flag)),
+            "gamma" => Ok(model.gamma(k, t, flag)),
+            "vega" => Ok(model.vega(k, t, flag)),
+            "theta" => Ok(model.theta(k, t, flag)),
+            "rho" => Ok(model.rho(k, t, flag)),
+            _ => Err(unsupported(&self.model, measure)),
+        }
+    }
+}
+
+impl fmt::Display for GoldenCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} s={} k={} t={} r={} q={} v={}",
+            self.model, self.option_type, self.s, self.k, self.t, self.r, self.q, self.v
+        )
+    }
+}
+
+impl GoldenTolerance {
+    /// Create a new tolerance.
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Check if `actual` is within the tolerance of `expected`.
+    pub fn accepts(&self, actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= self.absolute + self.relative * expected.abs()
+    }
+}
+
+impl GoldenTolerances {
+    /// Use the same tolerance for every measure.
+    pub fn new(default: GoldenTolerance) -> Self {
+        Self {
+            default,
+            measures: HashMap::new(),
+        }
+    }
+
+    /// Set the tolerance of a measure.
+    pub fn with_measure(mut self, measure: &str, tolerance: GoldenTolerance) -> Self {
+        self.measures.insert(measure.to_string(), tolerance);
+        self
+    }
+
+    /// Tolerance of a measure.
+    pub fn get(&self, measure: &str) -> GoldenTolerance {
+        self.measures.get(measure).copied().unwrap_or(self.default)
+    }
+}
+
+impl Default for GoldenTolerances {
+    /// Absolute and relative tolerances of `1e-10`.
+    fn default() -> Self {
+        Self::new(GoldenTolerance::new(1e-10, 1e-10))
+    }
+}
+
+impl fmt::Display for GoldenMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} expected {}, got {} (difference {:e})",
+            self.case,
+            self.measure,
+            self.expected,
+            self.actual,
+            self.actual - self.expected
+        )
+    }
+}
+
+impl GoldenReport {
+    /// Check if every value was within its tolerance.
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+fn unsupported(model: &str, measure: &str) -> RustQuantError {
+    RustQuantError::NotImplemented(format!("{} is not available for {}.", measure, model))
+}
+
+/// Parse a golden file. Lines starting with `#` are comments.
+pub fn parse_golden(text: &str) -> Result<Vec<GoldenCase>, RustQuantError> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let header: Vec<&str> = lines
+        .next()
+        .ok_or_else(|| RustQuantError::MissingInput("Golden file has no header.".to_string()))?
+        .split(',')
+        .collect();
+
+    let column = |name: &str| {
+        header.iter().position(|c| *c == name).ok_or_else(|| {
+            RustQuantError::MissingInput(format!("Golden file has no {} column.", name))
+        })
+    };
+
+    let inputs = ["model", "option_type", "s", "k", "t", "r", "q", "v"]
+        .map(column)
+        .into_iter()
+        .collect::<Result<Vec<usize>, _>>()?;
+    let measures: Vec<(&str, usize)> = GOLDEN_MEASURES
+        .iter()
+        .filter_map(|measure| column(measure).ok().map(|i| (*measure, i)))
+        .collect();
+
+    lines
+        .enumerate()
+        .map(|(row, line)| {
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            let invalid = |what: &str| {
+                RustQuantError::InvalidArgument(format!(
+                    "Golden file row {}: invalid {}.",
+                    row + 1,
+                    what
+                ))
+            };
+
+            if cells.len() != header.len() {
+                return Err(invalid("number of columns"));
+            }
+
+            let number = |i: usize| {
+                cells[inputs[i]]
+                    .parse::<f64>()
+                    .map_err(|_| invalid(header[inputs[i]]))
+            };
+
+            let option_type = match cells[inputs[1]] {
+                "call" => TypeFlag::Call,
+                "put" => TypeFlag::Put,
+                _ => return Err(invalid("option type")),
+            };
+
+            let mut expected = HashMap::new();
+            for (measure, i) in &measures {
+                if !cells[*i].is_empty() {
+                    let value = cells[*i].parse().map_err(|_| invalid(measure))?;
+                    expected.insert(measure.to_string(), value);
+                }
+            }
+
+            Ok(GoldenCase {
+                model: cells[inputs[0]].to_string(),
+                option_type,
+                s: number(2)?,
+                k: number(3)?,
+                t: number(4)?,
+                r: number(5)?,
+                q: number(6)?,
+                v: number(7)?,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Read and parse a golden file.
+pub fn load_golden(path: &Path) -> Result<Vec<GoldenCase>, RustQuantError> {
+    let text = std::fs::read_to_string(path).map_err(|error| {
+        RustQuantError::FileOperationFailed(format!("{}: {}", path.display(), error))
+    })?;
+
+    parse_golden(&text)
+}
+
+/// Reprice every case and compare it with its golden values.
+///
+/// Errors if a case uses a model or measure RustQuant does not support.
+pub fn check_golden(
+    cases: &[GoldenCase],
+    tolerances: &GoldenTolerances,
+) -> Result<GoldenReport, RustQuantError> {
+    let mut report = GoldenReport::default();
+
+    for case in cases {
+        // Iterate in column order so mismatches are reported deterministically.
+        for measure in GOLDEN_MEASURES {
+            let Some(&expected) = case.expected.get(measure) else {
+                continue;
+            };
+
+            let actual = case.evaluate(measure)?;
+            report.checked += 1;
+
+            if !tolerances.get(measure).accepts(actual, expected) {
+                report.mismatches.push(GoldenMismatch {
+                    case: case.clone(),
+                    measure: measure.to_string(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_golden {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn golden_files() -> Vec<PathBuf> {
+        let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("golden")];
+        if let Ok(dir) = std::env::var("RUSTQUANT_GOLDEN_DIR") {
+            dirs.push(PathBuf::from(dir));
+        }
+
+        let mut files: Vec<PathBuf> = dirs
+            .iter()
+            .flat_map(|dir| std::fs::read_dir(dir).unwrap())
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "csv"))
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_golden_files() {
+        let tolerances = GoldenTolerances::default();
+        let files = golden_files();
+        assert!(!files.is_empty());
+
+        for file in files {
+            let cases = load_golden(&file).unwrap();
+            let report = check_golden(&cases, &tolerances).unwrap();
+
+            assert!(report.checked > 0, "{}: nothing to check", file.display());
+            assert!(
+                report.passed(),
+                "{}: {} of {} values differ:\n{}",
+                file.display(),
+                report.mismatches.len(),
+                report.checked,
+                report
+                    .mismatches
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn test_golden_mismatch() {
+        let text = "\
+# A put priced with the wrong sign on the strike.
+model,option_type,s,k,t,r,q,v,price,delta
+bs73,put,100,100,1,0.05,0,0.2,6.0,
+";
+        let cases = parse_golden(text).unwrap();
+        assert_eq!(cases.len(), 1);
+        assert!(!cases[0].expected.contains_key("delta"));
+
+        let report = check_golden(&cases, &GoldenTolerances::default()).unwrap();
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.mismatches.len(), 1);
+
+        // The Black-Scholes put is 5.5735, so a loose price tolerance accepts it.
+        let tolerances =
+            GoldenTolerances::default().with_measure("price", GoldenTolerance::new(0.0, 0.1));
+        assert!(check_golden(&cases, &tolerances).unwrap().passed());
+    }
+
+    #[test]
+    fn test_golden_invalid() {
+        let header = "model,option_type,s,k,t,r,q,v,price\n";
+
+        assert!(parse_golden("model,s\n").is_err());
+        assert!(parse_golden(&format!("{header}bs73,straddle,100,100,1,0,0,0.2,1\n")).is_err());
+        assert!(parse_golden(&format!("{header}bs73,call,100,100,1,0,0\n")).is_err());
+
+        let cases = parse_golden(&format!("{header}heston,call,100,100,1,0,0,0.2,1\n")).unwrap();
+        assert!(check_golden(&cases, &GoldenTolerances::default()).is_err());
+    }
+}
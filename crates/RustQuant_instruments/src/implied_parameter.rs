@@ -0,0 +1,169 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Solve for the input of a pricer that makes its NPV zero.
+//!
+//! Par swap rates, fair CDS spreads, break-even inflation rates and
+//! forward prices are all the value of one input at which an instrument
+//! is worth nothing (or a given price). Rather than each instrument
+//! implementing its own solver, wrap the pricer in a closure of that input
+//! and use [implied_parameter].
+//!
+//! ```rust
+//! use RustQuant::instruments::implied_parameter;
+//! use RustQuant::math::rootfinder::RootfinderData;
+//!
+//! // Forward contract: NPV(K) = S - K exp(-rT).
+//! let (S, r, T): (f64, f64, f64) = (100.0, 0.05, 1.0);
+//! let npv = |K: f64| S - K * (-r * T).exp();
+//!
+//! let forward = implied_parameter(npv, S, RootfinderData::default()).unwrap();
+//!
+//! assert!((forward - S * (r * T).exp()).abs() < 1e-8);
+//! ```
+
+use crate::Instrument;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::brent::Brent;
+use RustQuant_math::rootfinder::{Rootfinder, RootfinderData};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Find the input `x` at which `npv(x) = 0`, using Brent's method.
+///
+/// # Arguments
+///
+/// * `npv` - The pricer, as a function of the input to solve for.
+/// * `guess` - Initial guess, used to bracket the root.
+/// * `data` - Accuracy, step size and bounds for the root-finder.
+///
+/// # Errors
+///
+/// Returns `RustQuantError::ComputationError` if no root is found.
+pub fn implied_parameter<F>(npv: F, guess: f64, data: RootfinderData) -> Result<f64, RustQuantError>
+where
+    F: Fn(f64) -> f64,
+{
+    let accuracy = data.accuracy;
+    let root = Brent::new(&npv, guess, data).solve();
+
+    // The solver does not report failure, so check that `root` brackets a sign change.
+    let (lower, upper) = (npv(root - 2.0 * accuracy), npv(root + 2.0 * accuracy));
+
+    if npv(root) == 0.0 || lower * upper <= 0.0 {
+        Ok(root)
+    } else {
+        Err(RustQuantError::ComputationError(format!(
+            "No root found for the implied parameter (last iterate: {}).",
+            root
+        )))
+    }
+}
+
+/// Find the input `x` at which the instrument built from it has price `target`.
+///
+/// Use `target = 0.0` for par/fair rates, or a market price for
+/// implied quantities (volatility, yield, etc).
+///
+/// # Arguments
+///
+/// * `build` - Builds the instrument from the input to solve for.
+/// * `target` - The price to match.
+/// * `guess` - Initial guess, used to bracket the root.
+///
+/// # Errors
+///
+/// Returns `RustQuantError::ComputationError` if no root is found.
+pub fn implied_instrument_parameter<I, B>(
+    build: B,
+    target: f64,
+    guess: f64,
+) -> Result<f64, RustQuantError>
+where
+    I: Instrument,
+    B: Fn(f64) -> I,
+{
+    implied_parameter(
+        |x| build(x).price() - target,
+        guess,
+        RootfinderData::default(),
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_implied_parameter {
+    use super::*;
+    use crate::options::{implied_volatility, BlackScholesMertonBuilder, TypeFlag};
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_par_swap_rate() {
+        // Annual fixed leg against a floating leg on a flat 4% curve:
+        // NPV(c) = c * sum(DF_i) - (1 - DF_n).
+        let dfs: Vec<f64> = (1..=5).map(|i| (-0.04 * i as f64).exp()).collect();
+        let npv = |c: f64| c * dfs.iter().sum::<f64>() - (1.0 - dfs[4]);
+
+        let par = implied_parameter(npv, 0.01, RootfinderData::default()).unwrap();
+
+        assert_approx_equal!(par, (1.0 - dfs[4]) / dfs.iter().sum::<f64>(), 1e-10);
+    }
+
+    #[test]
+    fn test_implied_volatility_from_instrument() {
+        let option = |v: f64| {
+            BlackScholesMertonBuilder::default()
+                .underlying_price(100.0)
+                .strike_price(110.0)
+                .volatility(v)
+                .risk_free_rate(0.05)
+                .cost_of_carry(0.05)
+                .evaluation_date(Some(date!(2024 - 01 - 01)))
+                .expiration_date(date!(2025 - 01 - 01))
+                .option_type(TypeFlag::Call)
+                .build()
+                .unwrap()
+        };
+
+        let price = option(0.25).price();
+        let vol = implied_instrument_parameter(option, price, 0.1).unwrap();
+
+        assert_approx_equal!(vol, 0.25, 1e-8);
+        assert_approx_equal!(
+            vol,
+            implied_volatility(
+                price,
+                100.0,
+                110.0,
+                option(0.25).year_fraction(),
+                0.05,
+                TypeFlag::Call
+            ),
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_no_root() {
+        let npv = |x: f64| x * x + 1.0;
+
+        assert!(implied_parameter(
+            npv,
+            0.5,
+            RootfinderData::new(1e-10, 1e-3, -10.0, 10.0, true)
+        )
+        .is_err());
+    }
+}
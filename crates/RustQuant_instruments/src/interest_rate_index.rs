@@ -0,0 +1,346 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Benchmark interest rate indices (SOFR, ESTR, SONIA, EURIBOR, etc).
+//!
+//! An `InterestRateIndex` bundles the conventions needed to fix and accrue
+//! a floating rate (currency, tenor, day count, fixing calendar and lag)
+//! together with the history of published fixings.
+
+use crate::{Currency, EUR, GBP, USD};
+use std::collections::BTreeMap;
+use std::fmt;
+use time::{Date, Duration};
+use RustQuant_time::{
+    Calendar, DateRollingConvention, DayCountConvention, TargetCalendar, Tenor,
+    UnitedKingdomCalendar, UnitedStatesCalendar,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Rule used to obtain a fixing for a date with no published value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixingInterpolation {
+    /// Only published fixings are returned.
+    #[default]
+    Exact,
+
+    /// Use the most recent fixing on or before the date
+    /// (e.g. overnight rates over weekends and holidays).
+    Previous,
+
+    /// Linearly interpolate between the surrounding fixings.
+    Linear,
+}
+
+/// Interest rate index.
+pub struct InterestRateIndex<C: Calendar> {
+    /// Name of the index, e.g. "SOFR" or "EURIBOR 3M".
+    pub name: String,
+
+    /// Currency of the index.
+    pub currency: Currency,
+
+    /// Tenor of the index (one day for overnight indices).
+    pub tenor: Tenor,
+
+    /// Day count convention used to accrue the rate.
+    pub day_count_convention: DayCountConvention,
+
+    /// Calendar on which the index is fixed.
+    pub fixing_calendar: C,
+
+    /// Number of business days between the fixing date and the value date.
+    pub fixing_lag: u32,
+
+    /// Date rolling convention for the maturity date of the index.
+    pub date_rolling_convention: DateRollingConvention,
+
+    /// Rule for fixings on dates with no published value.
+    pub fixing_interpolation: FixingInterpolation,
+
+    /// Historical fixings, keyed by fixing date.
+    pub fixings: BTreeMap<Date, f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<C: Calendar> InterestRateIndex<C> {
+    /// Create a new interest rate index with an empty fixing history.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: &str,
+        currency: Currency,
+        tenor: Tenor,
+        day_count_convention: DayCountConvention,
+        fixing_calendar: C,
+        fixing_lag: u32,
+        date_rolling_convention: DateRollingConvention,
+        fixing_interpolation: FixingInterpolation,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            currency,
+            tenor,
+            day_count_convention,
+            fixing_calendar,
+            fixing_lag,
+            date_rolling_convention,
+            fixing_interpolation,
+            fixings: BTreeMap::new(),
+        }
+    }
+
+    /// Set the rule used for dates without a published fixing.
+    pub fn with_fixing_interpolation(mut self, fixing_interpolation: FixingInterpolation) -> Self {
+        self.fixing_interpolation = fixing_interpolation;
+        self
+    }
+
+    /// Add (or overwrite) a historical fixing.
+    pub fn add_fixing(&mut self, date: Date, rate: f64) {
+        self.fixings.insert(date, rate);
+    }
+
+    /// Add (or overwrite) several historical fixings.
+    pub fn add_fixings<I>(&mut self, fixings: I)
+    where
+        I: IntoIterator<Item = (Date, f64)>,
+    {
+        self.fixings.extend(fixings);
+    }
+
+    /// Check if the index fixes on the given date.
+    pub fn is_valid_fixing_date(&self, date: Date) -> bool {
+        self.fixing_calendar.is_business_day(date)
+    }
+
+    /// Check if the index is an overnight rate (SOFR, SONIA, ESTR), whose
+    /// coupons compound the daily fixings over the accrual period.
+    pub fn is_overnight(&self) -> bool {
+        self.tenor == Tenor::days(1)
+    }
+
+    /// Value (start) date of the deposit underlying a fixing.
+    pub fn value_date(&self, fixing_date: Date) -> Date {
+        self.shift_business_days(fixing_date, self.fixing_lag as i64)
+    }
+
+    /// Fixing date for a given value (start) date.
+    pub fn fixing_date(&self, value_date: Date) -> Date {
+        self.shift_business_days(value_date, -(self.fixing_lag as i64))
+    }
+
+    /// Maturity date of the deposit underlying a fixing with the given value date.
+    /// Month arithmetic is clamped to the end of the month, e.g. 31 Jan + 1M = 28/29 Feb.
+    pub fn maturity_date(&self, value_date: Date) -> Date {
+        self.tenor.advance(
+            value_date,
+            &self.fixing_calendar,
+            &self.date_rolling_convention,
+            false,
+        )
+    }
+
+    /// Accrual year fraction between two dates in the index day count.
+    pub fn year_fraction(&self, start: Date, end: Date) -> f64 {
+        self.day_count_convention.day_count_factor(start, end)
+    }
+
+    /// Historical fixing for a date, applying the index's interpolation rule
+    /// when no fixing was published on that date.
+    /// Returns `None` if the fixing cannot be determined from the history.
+    pub fn fixing(&self, date: Date) -> Option<f64> {
+        if let Some(rate) = self.fixings.get(&date) {
+            return Some(*rate);
+        }
+
+        let previous = self.fixings.range(..date).next_back();
+
+        match self.fixing_interpolation {
+            FixingInterpolation::Exact => None,
+            FixingInterpolation::Previous => previous.map(|(_, rate)| *rate),
+            FixingInterpolation::Linear => {
+                let ((d0, r0), (d1, r1)) = (previous?, self.fixings.range(date..).next()?);
+
+                let w = (date - *d0).whole_days() as f64 / (*d1 - *d0).whole_days() as f64;
+
+                Some(r0 + w * (r1 - r0))
+            }
+        }
+    }
+
+    /// Date of the most recent fixing in the history.
+    pub fn last_fixing_date(&self) -> Option<Date> {
+        self.fixings.keys().next_back().copied()
+    }
+
+    /// Move a date by a number of business days on the fixing calendar.
+    fn shift_business_days(&self, date: Date, days: i64) -> Date {
+        let step = Duration::days(days.signum());
+        let mut remaining = days.abs();
+        let mut shifted = date;
+
+        while remaining > 0 {
+            shifted += step;
+
+            if self.fixing_calendar.is_business_day(shifted) {
+                remaining -= 1;
+            }
+        }
+
+        shifted
+    }
+}
+
+impl InterestRateIndex<UnitedStatesCalendar> {
+    /// Secured Overnight Financing Rate (SOFR).
+    pub fn sofr() -> Self {
+        Self::new(
+            "SOFR",
+            USD,
+            Tenor::days(1),
+            DayCountConvention::Actual_360,
+            UnitedStatesCalendar,
+            0,
+            DateRollingConvention::Following,
+            FixingInterpolation::Previous,
+        )
+    }
+}
+
+impl InterestRateIndex<UnitedKingdomCalendar> {
+    /// Sterling Overnight Index Average (SONIA).
+    pub fn sonia() -> Self {
+        Self::new(
+            "SONIA",
+            GBP,
+            Tenor::days(1),
+            DayCountConvention::Actual_365_Fixed,
+            UnitedKingdomCalendar,
+            0,
+            DateRollingConvention::Following,
+            FixingInterpolation::Previous,
+        )
+    }
+}
+
+impl InterestRateIndex<TargetCalendar> {
+    /// Euro Short-Term Rate (€STR).
+    pub fn estr() -> Self {
+        Self::new(
+            "ESTR",
+            EUR,
+            Tenor::days(1),
+            DayCountConvention::Actual_360,
+            TargetCalendar,
+            0,
+            DateRollingConvention::Following,
+            FixingInterpolation::Previous,
+        )
+    }
+
+    /// Euro Interbank Offered Rate (EURIBOR) for a given tenor.
+    pub fn euribor(tenor: Tenor) -> Self {
+        Self::new(
+            &format!("EURIBOR {}", tenor),
+            EUR,
+            tenor,
+            DayCountConvention::Actual_360,
+            TargetCalendar,
+            2,
+            DateRollingConvention::ModifiedFollowing,
+            FixingInterpolation::Exact,
+        )
+    }
+}
+
+impl<C: Calendar> fmt::Display for InterestRateIndex<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, {}, {})",
+            self.name, self.currency.code, self.tenor, self.day_count_convention
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_interest_rate_index {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_euribor_dates() {
+        let euribor = InterestRateIndex::euribor(Tenor::months(3));
+
+        assert_eq!(euribor.name, "EURIBOR 3M");
+
+        // Friday fixing, T+2 value date is the following Tuesday.
+        let fixing_date = date!(2024 - 03 - 01);
+        let value_date = euribor.value_date(fixing_date);
+        assert_eq!(value_date, date!(2024 - 03 - 05));
+        assert_eq!(euribor.fixing_date(value_date), fixing_date);
+
+        // 5 June 2024 is a Wednesday.
+        assert_eq!(euribor.maturity_date(value_date), date!(2024 - 06 - 05));
+    }
+
+    #[test]
+    fn test_end_of_month_tenor() {
+        let euribor = InterestRateIndex::euribor(Tenor::months(1));
+
+        // 31 Jan + 1M = 29 Feb (leap year), which is a Thursday.
+        assert_eq!(
+            euribor.maturity_date(date!(2024 - 01 - 31)),
+            date!(2024 - 02 - 29)
+        );
+    }
+
+    #[test]
+    fn test_overnight_maturity() {
+        let sofr = InterestRateIndex::sofr();
+
+        // Friday before Labor Day rolls to Tuesday.
+        assert_eq!(
+            sofr.maturity_date(date!(2024 - 08 - 30)),
+            date!(2024 - 09 - 03)
+        );
+    }
+
+    #[test]
+    fn test_fixing_interpolation() {
+        let mut sonia = InterestRateIndex::sonia();
+        sonia.add_fixings([
+            (date!(2024 - 03 - 01), 0.0519),
+            (date!(2024 - 03 - 04), 0.0522),
+        ]);
+
+        // Weekend uses the previous (Friday) fixing.
+        assert_eq!(sonia.fixing(date!(2024 - 03 - 02)), Some(0.0519));
+        assert_eq!(sonia.fixing(date!(2024 - 02 - 29)), None);
+
+        let sonia = sonia.with_fixing_interpolation(FixingInterpolation::Linear);
+        assert_approx_equal!(sonia.fixing(date!(2024 - 03 - 02)).unwrap(), 0.0520, EPS);
+        assert_eq!(sonia.fixing(date!(2024 - 03 - 05)), None);
+
+        let sonia = sonia.with_fixing_interpolation(FixingInterpolation::Exact);
+        assert_eq!(sonia.fixing(date!(2024 - 03 - 02)), None);
+        assert_eq!(sonia.last_fixing_date(), Some(date!(2024 - 03 - 04)));
+    }
+}
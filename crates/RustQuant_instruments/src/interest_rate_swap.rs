@@ -0,0 +1,419 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Interest rate swaps and floating rate notes with dual-curve pricing.
+//!
+//! Cash flows are discounted on the curve of their currency (e.g. the OIS
+//! curve), while floating coupons are projected on a separate curve for the
+//! index and tenor (e.g. 3M EURIBOR). The curves come from a [`CurveContext`],
+//! which is implemented by `RustQuant_data::MarketDataContext`.
+//!
+//! Floating coupons accrue over the schedule periods with the index's day
+//! count. Coupons that fix after the valuation date use the projected forward
+//! rate over the accrual period; seasoned coupons are resolved by the
+//! context's [`CurveContext::coupon_rate`] against the fixing history.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Currency, InterestRateIndex};
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_time::{Calendar, DayCountConvention, Tenor};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Discounting and projection curves that rate instruments are priced on.
+pub trait CurveContext {
+    /// Valuation date of the market data.
+    fn valuation_date(&self) -> Date;
+
+    /// Discount factor for cash flows in `currency` paid on `date`.
+    fn discount_factor(&self, currency: Currency, date: Date) -> Result<f64, RustQuantError>;
+
+    /// Discount factor on the projection curve of an index and tenor.
+    fn projection_discount_factor(
+        &self,
+        index: &str,
+        tenor: Tenor,
+        date: Date,
+    ) -> Result<f64, RustQuantError>;
+
+    /// Projected simple forward rate of an index from `start` to `end`,
+    /// accrued with the index's day count.
+    fn forward_rate<C: Calendar>(
+        &self,
+        index: &InterestRateIndex<C>,
+        start: Date,
+        end: Date,
+    ) -> Result<f64, RustQuantError>
+    where
+        Self: Sized,
+    {
+        let p_start = self.projection_discount_factor(&index.name, index.tenor, start)?;
+        let p_end = self.projection_discount_factor(&index.name, index.tenor, end)?;
+
+        Ok((p_start / p_end - 1.0) / index.year_fraction(start, end))
+    }
+
+    /// Rate of a floating coupon of an index accruing from `start` to `end`,
+    /// as seen on the valuation date.
+    ///
+    /// Coupons fixing on or after the valuation date are projected (today's
+    /// fixing is taken as not yet published). Contexts without
+    /// a fixing history cannot price seasoned coupons, so the default returns
+    /// an error for them; `RustQuant_data::MarketDataContext` resolves them
+    /// with its `FixingStore`.
+    fn coupon_rate<C: Calendar>(
+        &self,
+        index: &InterestRateIndex<C>,
+        start: Date,
+        end: Date,
+    ) -> Result<f64, RustQuantError>
+    where
+        Self: Sized,
+    {
+        let fixing_date = index.fixing_date(start);
+
+        match fixing_date >= self.valuation_date() {
+            true => self.forward_rate(index, start, end),
+            false => Err(RustQuantError::MissingInput(format!(
+                "No fixings to price the {} coupon fixing on {}.",
+                index.name, fixing_date
+            ))),
+        }
+    }
+}
+
+/// Floating rate note paying an index plus a spread, and the notional at maturity.
+pub struct FloatingRateNote<C: Calendar> {
+    /// Notional (face value).
+    pub notional: f64,
+
+    /// Floating rate index.
+    pub index: InterestRateIndex<C>,
+
+    /// Spread over the index.
+    pub spread: f64,
+
+    /// Accrual period boundaries, from the start date to the maturity.
+    pub schedule: Vec<Date>,
+}
+
+/// Fixed-for-floating interest rate swap.
+pub struct InterestRateSwap<C: Calendar> {
+    /// Notional of both legs.
+    pub notional: f64,
+
+    /// `true` to pay the fixed leg and receive the floating leg.
+    pub pay_fixed: bool,
+
+    /// Fixed rate.
+    pub fixed_rate: f64,
+
+    /// Fixed leg period boundaries.
+    pub fixed_schedule: Vec<Date>,
+
+    /// Fixed leg day count convention.
+    pub fixed_day_count_convention: DayCountConvention,
+
+    /// Floating rate index.
+    pub index: InterestRateIndex<C>,
+
+    /// Spread over the index on the floating leg.
+    pub spread: f64,
+
+    /// Floating leg period boundaries.
+    pub floating_schedule: Vec<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<C: Calendar> FloatingRateNote<C> {
+    /// Create a new floating rate note.
+    pub fn new(
+        notional: f64,
+        index: InterestRateIndex<C>,
+        spread: f64,
+        schedule: Vec<Date>,
+    ) -> Self {
+        Self {
+            notional,
+            index,
+            spread,
+            schedule,
+        }
+    }
+
+    /// Present value of the note: floating coupons plus the notional.
+    pub fn npv<M: CurveContext>(&self, context: &M) -> Result<f64, RustQuantError> {
+        let coupons = floating_leg_npv(context, &self.index, self.spread, &self.schedule)?;
+
+        let redemption = match self.schedule.last() {
+            Some(maturity) if *maturity > context.valuation_date() => {
+                context.discount_factor(self.index.currency, *maturity)?
+            }
+            _ => 0.0,
+        };
+
+        Ok(self.notional * (coupons + redemption))
+    }
+}
+
+impl<C: Calendar> InterestRateSwap<C> {
+    /// Create a new swap with no spread on the floating leg.
+    pub fn new(
+        notional: f64,
+        pay_fixed: bool,
+        fixed_rate: f64,
+        fixed_schedule: Vec<Date>,
+        fixed_day_count_convention: DayCountConvention,
+        index: InterestRateIndex<C>,
+        floating_schedule: Vec<Date>,
+    ) -> Self {
+        Self {
+            notional,
+            pay_fixed,
+            fixed_rate,
+            fixed_schedule,
+            fixed_day_count_convention,
+            index,
+            spread: 0.0,
+            floating_schedule,
+        }
+    }
+
+    /// Set the spread over the index on the floating leg.
+    pub fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Present value of one unit of fixed rate on the fixed leg, per unit notional.
+    pub fn annuity<M: CurveContext>(&self, context: &M) -> Result<f64, RustQuantError> {
+        let mut annuity = 0.0;
+
+        for period in self.fixed_schedule.windows(2) {
+            if period[1] > context.valuation_date() {
+                let tau = self
+                    .fixed_day_count_convention
+                    .day_count_factor(period[0], period[1]);
+
+                annuity += tau * context.discount_factor(self.index.currency, period[1])?;
+            }
+        }
+
+        Ok(annuity)
+    }
+
+    /// Present value of the fixed leg (positive, as received).
+    pub fn fixed_leg_npv<M: CurveContext>(&self, context: &M) -> Result<f64, RustQuantError> {
+        Ok(self.notional * self.fixed_rate * self.annuity(context)?)
+    }
+
+    /// Present value of the floating leg (positive, as received).
+    pub fn floating_leg_npv<M: CurveContext>(&self, context: &M) -> Result<f64, RustQuantError> {
+        let npv = floating_leg_npv(context, &self.index, self.spread, &self.floating_schedule)?;
+
+        Ok(self.notional * npv)
+    }
+
+    /// Present value of the swap to the holder.
+    pub fn npv<M: CurveContext>(&self, context: &M) -> Result<f64, RustQuantError> {
+        let receive_floating = self.floating_leg_npv(context)? - self.fixed_leg_npv(context)?;
+
+        Ok(match self.pay_fixed {
+            true => receive_floating,
+            false => -receive_floating,
+        })
+    }
+
+    /// Fixed rate at which the swap is worth zero.
+    pub fn par_rate<M: CurveContext>(&self, context: &M) -> Result<f64, RustQuantError> {
+        Ok(self.floating_leg_npv(context)? / (self.notional * self.annuity(context)?))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Present value per unit notional of floating coupons over a schedule.
+/// Periods paid on or before the valuation date are ignored.
+fn floating_leg_npv<M, C>(
+    context: &M,
+    index: &InterestRateIndex<C>,
+    spread: f64,
+    schedule: &[Date],
+) -> Result<f64, RustQuantError>
+where
+    M: CurveContext,
+    C: Calendar,
+{
+    let valuation_date = context.valuation_date();
+    let mut npv = 0.0;
+
+    for period in schedule.windows(2) {
+        let (start, end) = (period[0], period[1]);
+
+        if end <= valuation_date {
+            continue;
+        }
+
+        let rate = context.coupon_rate(index, start, end)?;
+
+        npv += (rate + spread)
+            * index.year_fraction(start, end)
+            * context.discount_factor(index.currency, end)?;
+    }
+
+    Ok(npv)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_interest_rate_swap {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    const TODAY: Date = date!(2024 - 03 - 05);
+    const SPOT: Date = date!(2024 - 03 - 07);
+
+    /// Flat continuously compounded discounting and projection rates.
+    struct FlatContext {
+        discount_rate: f64,
+        projection_rate: f64,
+    }
+
+    impl FlatContext {
+        fn df(rate: f64, date: Date) -> f64 {
+            (-rate * DayCountConvention::Actual_365_Fixed.day_count_factor(TODAY, date)).exp()
+        }
+    }
+
+    impl CurveContext for FlatContext {
+        fn valuation_date(&self) -> Date {
+            TODAY
+        }
+
+        fn discount_factor(&self, _currency: Currency, date: Date) -> Result<f64, RustQuantError> {
+            Ok(Self::df(self.discount_rate, date))
+        }
+
+        fn projection_discount_factor(
+            &self,
+            _index: &str,
+            _tenor: Tenor,
+            date: Date,
+        ) -> Result<f64, RustQuantError> {
+            Ok(Self::df(self.projection_rate, date))
+        }
+    }
+
+    fn quarterly(start: Date, quarters: u32) -> Vec<Date> {
+        (0..quarters).fold(vec![start], |mut dates, _| {
+            let last = *dates.last().unwrap();
+            dates.push(last + Tenor::months(3));
+            dates
+        })
+    }
+
+    #[test]
+    fn test_single_curve_floater_at_par() {
+        let context = FlatContext {
+            discount_rate: 0.03,
+            projection_rate: 0.03,
+        };
+        let frn = FloatingRateNote::new(
+            100.0,
+            InterestRateIndex::euribor(Tenor::months(3)),
+            0.0,
+            quarterly(SPOT, 20),
+        );
+
+        // Worth par on its start date.
+        let index = &frn.index;
+        assert_eq!(index.value_date(index.fixing_date(SPOT)), SPOT);
+        assert_approx_equal!(
+            frn.npv(&context).unwrap(),
+            100.0 * FlatContext::df(0.03, SPOT),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_dual_curve_swap() {
+        let index = || InterestRateIndex::euribor(Tenor::months(3));
+        let swap = |context: &FlatContext| {
+            let schedule = quarterly(SPOT, 20);
+            let fixed = schedule.iter().step_by(4).copied().collect();
+            let swap = InterestRateSwap::new(
+                1e6,
+                true,
+                0.0,
+                fixed,
+                DayCountConvention::Thirty_360_ISDA,
+                index(),
+                schedule,
+            );
+            let par = swap.par_rate(context).unwrap();
+
+            (swap, par)
+        };
+
+        let single = FlatContext {
+            discount_rate: 0.03,
+            projection_rate: 0.03,
+        };
+        let dual = FlatContext {
+            discount_rate: 0.03,
+            projection_rate: 0.035,
+        };
+
+        // A higher projection curve raises the par rate by about the basis.
+        let (_, par_single) = swap(&single);
+        let (mut at_par, par_dual) = swap(&dual);
+        assert_approx_equal!(par_dual - par_single, 0.005, 2e-4);
+
+        at_par.fixed_rate = par_dual;
+        assert_approx_equal!(at_par.npv(&dual).unwrap(), 0.0, 1e-6);
+
+        // Paying fixed gains when projected rates rise.
+        at_par.fixed_rate = par_single;
+        assert!(at_par.npv(&dual).unwrap() > 0.0);
+        at_par.pay_fixed = false;
+        assert!(at_par.npv(&dual).unwrap() < 0.0);
+    }
+
+    #[test]
+    fn test_seasoned_floater_needs_fixings() {
+        let context = FlatContext {
+            discount_rate: 0.03,
+            projection_rate: 0.03,
+        };
+        let start = date!(2024 - 01 - 05);
+        let schedule = vec![start, date!(2024 - 04 - 05), date!(2024 - 07 - 05)];
+
+        // The context has no fixing history, even if the index has the fixing.
+        let mut index = InterestRateIndex::euribor(Tenor::months(3));
+        index.add_fixing(index.fixing_date(start), 0.039);
+        let frn = FloatingRateNote::new(1.0, index, 0.0, schedule);
+
+        assert!(frn.npv(&context).is_err());
+    }
+}
@@ -52,6 +52,10 @@
 pub mod instrument;
 pub use instrument::*;
 
+/// Solver for par rates, fair spreads and other implied inputs.
+pub mod implied_parameter;
+pub use implied_parameter::*;
+
 /// Bond pricing models.
 pub mod bonds;
 // pub use bonds::*;
@@ -68,6 +72,14 @@ pub use fx::*;
 pub mod equities;
 pub use equities::*;
 
+/// Benchmark interest rate indices.
+pub mod interest_rate_index;
+pub use interest_rate_index::*;
+
+/// Interest rate swaps and floating rate notes.
+pub mod interest_rate_swap;
+pub use interest_rate_swap::*;
+
 /// Ticker symbol.
 pub mod ticker;
 pub use ticker::*;
@@ -83,3 +95,7 @@ pub use analytic_option_pricer::*;
 /// Monte-Carlo pricer.
 pub mod monte_carlo_pricer;
 pub use monte_carlo_pricer::*;
+
+/// Differential tests against golden files from external pricers.
+pub mod golden;
+pub use golden::*;
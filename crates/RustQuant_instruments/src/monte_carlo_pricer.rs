@@ -10,13 +10,35 @@
 //! Monte-Carlo pricer trait.
 
 use crate::Payoff;
-use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig, Trajectories};
+
+/// Monte-Carlo estimate produced by a variance-reduced pricer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloEstimate {
+    /// The estimated (discounted) price.
+    pub price: f64,
+
+    /// Standard error of the price estimate.
+    pub standard_error: f64,
+
+    /// Ratio of the crude Monte-Carlo estimator variance to the variance
+    /// of the variance-reduced estimator, for the same number of paths.
+    /// Values greater than one mean the technique helped.
+    pub variance_reduction: f64,
+}
 
 /// Monte-Carlo pricer trait.
 pub trait MonteCarloPricer<S>: Payoff
 where
     S: StochasticProcess,
 {
+    /// Undiscounted payoff of the instrument along each simulated path.
+    ///
+    /// # Arguments
+    ///
+    /// * `trajectories` - The simulated sample paths.
+    fn path_payoffs(&self, trajectories: &Trajectories) -> Vec<f64>;
+
     /// Price the instrument using a Monte-Carlo method.
     ///
     /// # Arguments
@@ -24,7 +46,211 @@ where
     /// * `process` - The [StochasticProcess] to use for the sample paths.
     /// * `config` - The [StochasticProcessConfig] for the simulation.
     /// * `rate` - The interest rate used to discount the payoff.
-    fn price_monte_carlo(&self, process: &S, config: &StochasticProcessConfig, rate: f64) -> f64;
+    fn price_monte_carlo(&self, process: &S, config: &StochasticProcessConfig, rate: f64) -> f64 {
+        let out = process.euler_maruyama(config);
+        let payoffs = self.path_payoffs(&out);
+
+        let df = (-rate * (config.t_n - config.t_0)).exp();
+
+        df * payoffs.iter().sum::<f64>() / payoffs.len() as f64
+    }
+
+    /// Price the instrument using antithetic variates.
+    ///
+    /// Each antithetic pair of paths is averaged into a single sample, and
+    /// the reported variance reduction compares the variance of the pair
+    /// averages with that of independent paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The [StochasticProcess] to use for the sample paths.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `rate` - The interest rate used to discount the payoff.
+    fn price_monte_carlo_antithetic(
+        &self,
+        process: &S,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate {
+        let out = process.antithetic_euler_maruyama(config);
+
+        let df = (-rate * (config.t_n - config.t_0)).exp();
+
+        let payoffs: Vec<f64> = self.path_payoffs(&out).iter().map(|p| df * p).collect();
+        let pairs: Vec<f64> = payoffs
+            .chunks(2)
+            .map(|pair| pair.iter().sum::<f64>() / pair.len() as f64)
+            .collect();
+
+        let (price, pair_variance) = mean_and_variance(&pairs);
+        let (_, crude_variance) = mean_and_variance(&payoffs);
+
+        // Crude: Var[Y] / n.  Antithetic: Var[(Y + Y') / 2] / (n / 2).
+        let crude_error = crude_variance / payoffs.len() as f64;
+        let antithetic_error = pair_variance / pairs.len() as f64;
+
+        MonteCarloEstimate {
+            price,
+            standard_error: antithetic_error.sqrt(),
+            variance_reduction: crude_error / antithetic_error,
+        }
+    }
+
+    /// Price the instrument using a control variate.
+    ///
+    /// The control is another instrument with a known (typically analytic)
+    /// price, such as a geometric Asian option when pricing an arithmetic
+    /// Asian option. Both payoffs are evaluated on the same paths and the
+    /// optimal control coefficient is estimated from the sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The control instrument.
+    /// * `control_price` - The known price of the control instrument.
+    /// * `process` - The [StochasticProcess] to use for the sample paths.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `rate` - The interest rate used to discount the payoff.
+    fn price_monte_carlo_control_variate<C>(
+        &self,
+        control: &C,
+        control_price: f64,
+        process: &S,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        C: MonteCarloPricer<S>,
+    {
+        let out = process.euler_maruyama(config);
+
+        let df = (-rate * (config.t_n - config.t_0)).exp();
+
+        let ys: Vec<f64> = self.path_payoffs(&out).iter().map(|p| df * p).collect();
+        let xs: Vec<f64> = control.path_payoffs(&out).iter().map(|p| df * p).collect();
+
+        let n = ys.len() as f64;
+        let (y_mean, y_variance) = mean_and_variance(&ys);
+        let (x_mean, x_variance) = mean_and_variance(&xs);
+
+        let covariance = ys
+            .iter()
+            .zip(&xs)
+            .map(|(y, x)| (y - y_mean) * (x - x_mean))
+            .sum::<f64>()
+            / (n - 1.0);
+
+        let beta = if x_variance > 0.0 {
+            covariance / x_variance
+        } else {
+            0.0
+        };
+
+        let price = y_mean - beta * (x_mean - control_price);
+        let controlled_variance = y_variance - beta * covariance;
+
+        MonteCarloEstimate {
+            price,
+            standard_error: (controlled_variance / n).sqrt(),
+            variance_reduction: y_variance / controlled_variance,
+        }
+    }
+
+    /// Price the instrument using importance sampling.
+    ///
+    /// The driving Brownian motion is shifted by a constant drift (Girsanov)
+    /// so that more paths end up where the payoff is non-zero, and each
+    /// payoff is weighted by the path's likelihood ratio.
+    /// This is most effective for deep out-of-the-money options.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The [StochasticProcess] to use for the sample paths.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `rate` - The interest rate used to discount the payoff.
+    /// * `shift` - The Brownian drift shift. If `None`, the shift is chosen
+    ///   by [MonteCarloPricer::optimal_importance_shift].
+    fn price_monte_carlo_importance_sampling(
+        &self,
+        process: &S,
+        config: &StochasticProcessConfig,
+        rate: f64,
+        shift: Option<f64>,
+    ) -> MonteCarloEstimate {
+        let shift = shift.unwrap_or_else(|| self.optimal_importance_shift(process, config));
+
+        let (out, weights) = process.importance_sampled_euler_maruyama(config, shift);
+
+        let df = (-rate * (config.t_n - config.t_0)).exp();
+
+        let payoffs: Vec<f64> = self.path_payoffs(&out).iter().map(|p| df * p).collect();
+        let weighted: Vec<f64> = payoffs.iter().zip(&weights).map(|(p, w)| p * w).collect();
+
+        let n = weighted.len() as f64;
+        let (price, weighted_variance) = mean_and_variance(&weighted);
+
+        // Crude variance under the original measure: E_Q[Y^2 L] - E_P[Y]^2.
+        let crude_variance = payoffs
+            .iter()
+            .zip(&weights)
+            .map(|(p, w)| p * p * w)
+            .sum::<f64>()
+            / n
+            - price * price;
+
+        MonteCarloEstimate {
+            price,
+            standard_error: (weighted_variance / n).sqrt(),
+            variance_reduction: crude_variance / weighted_variance,
+        }
+    }
+
+    /// Select the importance sampling drift shift.
+    ///
+    /// Runs a pilot simulation (with common random numbers) over a grid of
+    /// shifts spanning $\pm 5$ standard deviations of the terminal Brownian
+    /// motion, and returns the shift with the smallest relative error.
+    /// Returns `0.0` if no pilot path produces a non-zero payoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The [StochasticProcess] to use for the sample paths.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    fn optimal_importance_shift(&self, process: &S, config: &StochasticProcessConfig) -> f64 {
+        const GRID_POINTS: usize = 41;
+        const PILOT_PATHS: usize = 2_000;
+        const PILOT_SEED: u64 = 2_718_281_828;
+
+        let pilot = StochasticProcessConfig::new(
+            config.x_0,
+            config.t_0,
+            config.t_n,
+            config.n_steps,
+            PILOT_PATHS.min(config.m_paths.max(2)),
+            config.parallel,
+        )
+        .with_seed(PILOT_SEED);
+
+        let bound = 5.0 / (config.t_n - config.t_0).sqrt();
+
+        (0..GRID_POINTS)
+            .map(|i| -bound + 2.0 * bound * i as f64 / (GRID_POINTS - 1) as f64)
+            .filter_map(|shift| {
+                let (out, weights) = process.importance_sampled_euler_maruyama(&pilot, shift);
+
+                let weighted: Vec<f64> = self
+                    .path_payoffs(&out)
+                    .iter()
+                    .zip(&weights)
+                    .map(|(p, w)| p * w)
+                    .collect();
+
+                let (mean, variance) = mean_and_variance(&weighted);
+
+                (mean.abs() > 0.0).then(|| (shift, variance.sqrt() / mean.abs()))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0.0, |(shift, _)| shift)
+    }
 }
 
 /// Macro to implement `MonteCarloPricer` for a given instrument type.
@@ -35,31 +261,26 @@ macro_rules! impl_monte_carlo_pricer {
         where
             S: StochasticProcess,
         {
-            fn price_monte_carlo(
-                &self,
-                process: &S,
-                config: &StochasticProcessConfig,
-                rate: f64,
-            ) -> f64 {
-                let out = process.euler_maruyama(&config);
-
-                let n = out.paths.len();
-
-                let df = (-rate * (config.t_n - config.t_0)).exp();
-
-                let payoffs = out.paths.iter().fold(0.0, |acc, path| {
-                    let underlying = $underlying(&*path);
-                    let payoff = self.payoff(underlying);
-
-                    acc + payoff
-                });
-
-                df * payoffs / n as f64
+            fn path_payoffs(&self, trajectories: &Trajectories) -> Vec<f64> {
+                trajectories
+                    .paths
+                    .iter()
+                    .map(|path| self.payoff($underlying(&*path)))
+                    .collect()
             }
         }
     };
 }
 
+/// Sample mean and (unbiased) sample variance.
+fn mean_and_variance(xs: &[f64]) -> (f64, f64) {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    let variance = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    (mean, variance)
+}
+
 fn path_independent(path: &[f64]) -> f64 {
     path.last().cloned().unwrap_or(0.0)
 }
@@ -80,3 +301,131 @@ impl_monte_carlo_pricer!(crate::PoweredOption, path_independent);
 impl_monte_carlo_pricer!(crate::LogMoneynessContract, path_independent);
 impl_monte_carlo_pricer!(crate::LogUnderlyingContract, path_independent);
 impl_monte_carlo_pricer!(crate::LogOption, path_independent);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_variance_reduction {
+    use super::*;
+    use crate::{
+        AsianOption, AveragingMethod, EuropeanVanillaOption, ExerciseFlag, OptionContractBuilder,
+        StrikeFlag, TypeFlag,
+    };
+    use time::macros::date;
+    use RustQuant_math::{Distribution, Gaussian};
+    use RustQuant_stochastics::{GeometricBrownianMotion, MertonJumpDiffusion};
+
+    const S: f64 = 100.0;
+    const K: f64 = 100.0;
+    const R: f64 = 0.05;
+    const V: f64 = 0.2;
+    const T: f64 = 1.0;
+
+    // Closed-form price of a discretely monitored geometric Asian call
+    // under GBM, with fixings at t_i = i * T / n for i = 0, ..., n.
+    fn geometric_asian_call(n: usize) -> f64 {
+        let dt = T / n as f64;
+        let m = (n + 1) as f64;
+
+        let min_sum: f64 = (0..=n)
+            .flat_map(|i| (0..=n).map(move |j| i.min(j) as f64))
+            .sum();
+
+        let mu = S.ln() + (R - 0.5 * V * V) * T / 2.0;
+        let sigma = (V * V * dt * min_sum).sqrt() / m;
+
+        let d1 = (mu - K.ln() + sigma * sigma) / sigma;
+        let d2 = d1 - sigma;
+
+        let N = Gaussian::default();
+
+        (-R * T).exp() * ((mu + 0.5 * sigma * sigma).exp() * N.cdf(d1) - K * N.cdf(d2))
+    }
+
+    fn asian_call(averaging_method: AveragingMethod) -> AsianOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .strike_flag(Some(StrikeFlag::Fixed))
+            .build()
+            .unwrap();
+
+        AsianOption::new(contract, averaging_method, Some(K))
+    }
+
+    #[test]
+    fn test_antithetic_vanilla_call() {
+        let option = EuropeanVanillaOption::new(K, date!(2025 - 01 - 01), TypeFlag::Call);
+        let process = GeometricBrownianMotion::new(R, V);
+        let config = StochasticProcessConfig::new(S, 0.0, T, 100, 20_000, true);
+
+        let estimate = option.price_monte_carlo_antithetic(&process, &config, R);
+
+        // Black-Scholes price: 10.4506.
+        assert!((estimate.price - 10.4506).abs() < 0.5);
+        assert!(estimate.standard_error > 0.0);
+        assert!(estimate.variance_reduction > 1.0);
+    }
+
+    #[test]
+    fn test_antithetic_merton_call() {
+        let option = EuropeanVanillaOption::new(K, date!(2025 - 01 - 01), TypeFlag::Call);
+        let process = MertonJumpDiffusion::new(R, V, 1.0, 0.0, 4.0);
+        let plain = StochasticProcessConfig::new(S, 0.0, T, 50, 100_000, true).with_seed(1);
+        let config = StochasticProcessConfig::new(S, 0.0, T, 50, 20_000, true).with_seed(2);
+
+        let price = option.price_monte_carlo(&process, &plain, R);
+        let estimate = option.price_monte_carlo_antithetic(&process, &config, R);
+
+        // The antithetic paths keep their jumps, so both estimate the same price.
+        assert!((estimate.price - price).abs() < 0.3);
+        assert!(estimate.variance_reduction > 1.0);
+    }
+
+    #[test]
+    fn test_control_variate_arithmetic_asian() {
+        let n_steps = 50;
+
+        let arithmetic = asian_call(AveragingMethod::ArithmeticDiscrete);
+        let geometric = asian_call(AveragingMethod::GeometricDiscrete);
+        let geometric_price = geometric_asian_call(n_steps);
+
+        let process = GeometricBrownianMotion::new(R, V);
+        let config = StochasticProcessConfig::new(S, 0.0, T, n_steps, 10_000, true);
+
+        let estimate = arithmetic.price_monte_carlo_control_variate(
+            &geometric,
+            geometric_price,
+            &process,
+            &config,
+            R,
+        );
+
+        // The arithmetic average always dominates the geometric average.
+        assert!(estimate.price > geometric_price);
+        assert!(estimate.price - geometric_price < 0.5);
+
+        // The two payoffs are almost perfectly correlated.
+        assert!(estimate.variance_reduction > 10.0);
+    }
+
+    #[test]
+    fn test_importance_sampling_deep_otm_call() {
+        let strike = 200.0;
+        let option = EuropeanVanillaOption::new(strike, date!(2025 - 01 - 01), TypeFlag::Call);
+        let process = GeometricBrownianMotion::new(R, V);
+        let config = StochasticProcessConfig::new(S, 0.0, T, 50, 20_000, true);
+
+        let shift = option.optimal_importance_shift(&process, &config);
+        let estimate = option.price_monte_carlo_importance_sampling(&process, &config, R, None);
+
+        // Black-Scholes price: 0.005885.
+        assert!(shift > 0.0);
+        assert!((estimate.price - 0.005885).abs() < 0.002);
+        assert!(estimate.variance_reduction > 10.0);
+    }
+}
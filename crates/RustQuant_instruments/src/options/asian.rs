@@ -15,6 +15,7 @@ use crate::Payoff;
 
 /// Asian option.
 #[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsianOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -52,7 +53,10 @@ impl Payoff for AsianOption {
 
         let average = match self.averaging_method {
             AveragingMethod::ArithmeticDiscrete => path.sum::<f64>() / n as f64,
-            AveragingMethod::GeometricDiscrete => path.product::<f64>().powf(1.0 / n as f64),
+            AveragingMethod::GeometricDiscrete => {
+                // Average in log-space to avoid overflowing the product.
+                (path.map(|x| x.ln()).sum::<f64>() / n as f64).exp()
+            }
 
             // Continuous averaging (i.e. integral of the path).
             _ => panic!("Continuous averaging not implemented."),
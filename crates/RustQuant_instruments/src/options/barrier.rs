@@ -13,6 +13,7 @@ use super::{BarrierType, OptionContract, TypeFlag};
 
 /// Barrier option.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BarrierOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -91,3 +92,46 @@ impl Payoff for BarrierOption {
         }
     }
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_barrier_serde {
+    use super::*;
+    use crate::ExerciseFlag;
+    use time::macros::date;
+
+    #[test]
+    fn test_barrier_option_json_round_trip() {
+        let option = BarrierOption {
+            contract: OptionContract {
+                type_flag: TypeFlag::Call,
+                exercise_flag: ExerciseFlag::European {
+                    expiry: date!(2025 - 06 - 20),
+                },
+                strike_flag: None,
+                settlement_flag: None,
+            },
+            barrier_type: BarrierType::UpAndOut,
+            barrier: 120.0,
+            strike: 100.0,
+            rebate: Some(1.5),
+        };
+
+        let json = serde_json::to_string(&option).unwrap();
+        let restored: BarrierOption = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            restored.contract.exercise_flag.expiry(),
+            date!(2025 - 06 - 20)
+        );
+        assert!(matches!(restored.barrier_type, BarrierType::UpAndOut));
+        assert_eq!(restored.rebate, Some(1.5));
+
+        // The payoff is unchanged after the round trip.
+        let path = vec![100.0, 110.0, 115.0];
+        assert_eq!(restored.payoff(path.clone()), option.payoff(path));
+    }
+}
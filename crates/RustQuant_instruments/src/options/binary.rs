@@ -13,6 +13,7 @@ use super::{BinaryType, OptionContract, TypeFlag};
 
 /// Binary option.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinaryOption {
     /// The option contract.
     pub contract: OptionContract,
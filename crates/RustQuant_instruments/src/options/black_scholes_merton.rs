@@ -37,6 +37,7 @@ use RustQuant_time::{utilities::today, DayCountConvention};
 
 /// Generalised Black-Scholes-Merton European Option pricing model.
 #[derive(derive_builder::Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlackScholesMerton {
     /// The cost of carry factor.
     /// For the generalised Black-Scholes-Merton model there are five options:
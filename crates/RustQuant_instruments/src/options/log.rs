@@ -12,6 +12,7 @@ use crate::Payoff;
 
 /// Log Moneyness Contract.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogMoneynessContract {
     /// The option contract.
     pub contract: OptionContract,
@@ -22,6 +23,7 @@ pub struct LogMoneynessContract {
 
 /// Log Underlying Contract.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogUnderlyingContract {
     /// The option contract.
     pub contract: OptionContract,
@@ -32,6 +34,7 @@ pub struct LogUnderlyingContract {
 
 /// Log Option.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -13,6 +13,7 @@ use crate::Payoff;
 
 /// Lookback option.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LookbackOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -12,6 +12,7 @@ use derive_builder::Builder;
 
 /// Option contract data.
 #[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OptionContract {
     /// Mandatory: Option type (call or put).
     pub type_flag: TypeFlag,
@@ -10,7 +10,8 @@
 use time::Date;
 
 /// Option type enum.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeFlag {
     /// Call option (right to BUY the underlying asset).
     Call = 1,
@@ -21,6 +22,7 @@ pub enum TypeFlag {
 
 /// American/European option type enum.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExerciseFlag {
     /// European option (can only be exercised at expiry).
     /// Most index options are European.
@@ -64,6 +66,7 @@ impl ExerciseFlag {
 /// These are used for options such as
 /// Asian options (average) or Lookback options (extreme).
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StrikeFlag {
     /// Strike is fixed.
     Fixed,
@@ -74,6 +77,7 @@ pub enum StrikeFlag {
 
 /// Instrument settlement flag.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SettlementFlag {
     /// Cash settlement.
     Cash,
@@ -84,6 +88,7 @@ pub enum SettlementFlag {
 
 /// Method of averaging (arithmetic or geometric, and continuous or discrete).
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AveragingMethod {
     /// Arithmetic Asian option with discrete averaging.
     ArithmeticDiscrete,
@@ -100,6 +105,7 @@ pub enum AveragingMethod {
 
 /// Barrier type flag.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BarrierType {
     /// Up-and-out barrier option.
     UpAndOut,
@@ -116,6 +122,7 @@ pub enum BarrierType {
 
 /// Binary type enum.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryType {
     /// Asset-or-nothing binary option.
     AssetOrNothing,
@@ -13,6 +13,7 @@ use crate::Payoff;
 /// Power Option.
 /// Not to be confused with the [PoweredOption].
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PowerOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -27,6 +28,7 @@ pub struct PowerOption {
 /// Powered Option.
 /// Not to be confused with the [PowerOption].
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PoweredOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -40,6 +42,7 @@ pub struct PoweredOption {
 
 /// Capped Power Option.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CappedPowerOption {
     /// The option contract.
     pub contract: OptionContract,
@@ -58,6 +61,7 @@ pub struct CappedPowerOption {
 ///
 ///
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PowerContract {
     /// Strike price of the option.
     pub strike: f64,
@@ -12,6 +12,7 @@ use crate::Payoff;
 
 /// Supershare option.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SupershareOption {
     /// The option contract.
     pub contract: OptionContract,
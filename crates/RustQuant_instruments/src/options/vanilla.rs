@@ -28,6 +28,7 @@ use RustQuant_time::{today, year_fraction};
 
 /// European vanilla option.
 #[derive(Debug, Clone, Builder, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EuropeanVanillaOption {
     /// The strike price of the option.
     pub strike: f64,
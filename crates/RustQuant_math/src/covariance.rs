@@ -0,0 +1,360 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Covariance matrix estimators for a matrix of returns, with one row per
+//! observation (in time order) and one column per asset.
+//!
+//! With few observations per asset the sample covariance matrix is badly
+//! conditioned (singular if there are fewer observations than assets), and
+//! optimised portfolios amplify its estimation errors. The Ledoit-Wolf
+//! estimator shrinks it towards a multiple of the identity, and the
+//! exponentially weighted estimator favours recent observations.
+
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Shrinkage estimate of a covariance matrix.
+#[derive(Debug, Clone)]
+pub struct ShrunkCovariance {
+    /// The covariance matrix estimate.
+    pub covariance: DMatrix<f64>,
+
+    /// Weight $\delta \in [0, 1]$ of the shrinkage target.
+    pub shrinkage: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks that there are at least `minimum` observations of at least one
+/// asset, all finite.
+fn check_returns(returns: &DMatrix<f64>, minimum: usize) -> Result<(), RustQuantError> {
+    if returns.ncols() == 0 || returns.nrows() < minimum {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected at least {minimum} observations of at least one asset, got {:?}.",
+            returns.shape()
+        )));
+    }
+
+    if returns.iter().any(|r| !r.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "Returns must be finite.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns with the mean of each column subtracted.
+fn demeaned(returns: &DMatrix<f64>) -> DMatrix<f64> {
+    let mut centred = returns.clone();
+
+    for mut column in centred.column_iter_mut() {
+        let mean = column.mean();
+        column.add_scalar_mut(-mean);
+    }
+
+    centred
+}
+
+/// Sample covariance matrix (with the $T - 1$ denominator) of returns with
+/// $T$ observations in the rows.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two observations, no assets, or
+/// the returns are not finite.
+pub fn sample_covariance(returns: &DMatrix<f64>) -> Result<DMatrix<f64>, RustQuantError> {
+    check_returns(returns, 2)?;
+
+    let centred = demeaned(returns);
+
+    Ok(centred.transpose() * &centred / (returns.nrows() - 1) as f64)
+}
+
+/// Ledoit-Wolf (2004) shrinkage of the covariance matrix towards a multiple
+/// of the identity:
+///
+/// $$
+/// \hat{\Sigma} = \delta \mu I + (1 - \delta) S,
+/// $$
+///
+/// where $S$ is the (maximum likelihood) sample covariance matrix and
+/// $\mu = \text{tr}(S) / N$. The weight $\delta$ is the estimate of the
+/// optimal one under the Frobenius loss,
+/// $\delta = \min(\bar{b}^2, d^2) / d^2$, with $d^2 = \|S - \mu I\|^2 / N$
+/// and $\bar{b}^2 = \sum_t \|x_t x_t^T - S\|^2 / (N T^2)$ for the demeaned
+/// observations $x_t$.
+///
+/// The estimate is positive definite, even with fewer observations than
+/// assets (unless the returns are all constant).
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use nalgebra::DMatrix;
+/// // Three observations of four assets: the sample covariance is singular.
+/// let returns = DMatrix::from_row_slice(3, 4, &[
+///      0.010, -0.020,  0.005,  0.015,
+///     -0.005,  0.010,  0.000, -0.010,
+///      0.020,  0.005, -0.010,  0.000,
+/// ]);
+/// assert_eq!(sample_covariance(&returns).unwrap().rank(1e-12), 2);
+///
+/// let estimate = ledoit_wolf(&returns).unwrap();
+///
+/// assert!(estimate.shrinkage > 0.0 && estimate.shrinkage <= 1.0);
+/// assert!(nalgebra::Cholesky::new(estimate.covariance).is_some());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two observations, no assets, or
+/// the returns are not finite.
+pub fn ledoit_wolf(returns: &DMatrix<f64>) -> Result<ShrunkCovariance, RustQuantError> {
+    check_returns(returns, 2)?;
+
+    let (t, n) = returns.shape();
+    let centred = demeaned(returns);
+    let sample = centred.transpose() * &centred / t as f64;
+
+    let mu = sample.trace() / n as f64;
+    let target = DMatrix::from_diagonal_element(n, n, mu);
+
+    let d2 = (&sample - &target).norm_squared() / n as f64;
+
+    // ||x x^T - S||^2 = ||x||^4 - 2 x^T S x + ||S||^2.
+    let sample_norm = sample.norm_squared();
+    let b2 = centred
+        .row_iter()
+        .map(|row| {
+            let x = row.transpose();
+            x.norm_squared().powi(2) - 2.0 * (&sample * &x).dot(&x) + sample_norm
+        })
+        .sum::<f64>()
+        / (n * t * t) as f64;
+
+    // If S is already a multiple of the identity, there is nothing to do.
+    let shrinkage = if d2 > 0.0 { b2.min(d2) / d2 } else { 0.0 };
+
+    Ok(ShrunkCovariance {
+        covariance: target * shrinkage + sample * (1.0 - shrinkage),
+        shrinkage,
+    })
+}
+
+/// Exponentially weighted (RiskMetrics) covariance matrix, with decay
+/// factor $\lambda$ (e.g. 0.94 for daily returns):
+///
+/// $$
+/// \hat{\Sigma} = \frac{\sum_{t=1}^{T} \lambda^{T-t} r_t r_t^T}{\sum_{t=1}^{T} \lambda^{T-t}},
+/// $$
+///
+/// where the last row of the returns is the most recent observation. As in
+/// RiskMetrics, the returns are assumed to have zero mean; demean them
+/// first otherwise.
+///
+/// # Errors
+///
+/// Returns an error if there are no observations or assets, the returns
+/// are not finite, or $\lambda$ is not in $(0, 1]$.
+pub fn ewma_covariance(
+    returns: &DMatrix<f64>,
+    lambda: f64,
+) -> Result<DMatrix<f64>, RustQuantError> {
+    check_returns(returns, 1)?;
+
+    if lambda.is_nan() || lambda <= 0.0 || lambda > 1.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Decay factor must be in (0, 1], got {lambda}."
+        )));
+    }
+
+    let (t, n) = returns.shape();
+    let mut covariance = DMatrix::zeros(n, n);
+    let mut total = 0.0;
+
+    // From the most recent observation backwards.
+    let mut weight = 1.0;
+    for row in (0..t).rev() {
+        let r = returns.row(row).transpose();
+        covariance.ger(weight, &r, &r, 1.0);
+        total += weight;
+        weight *= lambda;
+    }
+
+    Ok(covariance / total)
+}
+
+/// Random $n \times n$ correlation matrix: $C = V V^T$ for a matrix $V$ whose
+/// rows are independent, uniformly distributed unit vectors. Useful for
+/// testing and simulation; positive definite with probability one.
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let c = random_correlation_matrix(5, &mut StdRng::seed_from_u64(42));
+///
+/// assert!(c.diagonal().iter().all(|d| (d - 1.0).abs() < 1e-12));
+/// assert!(nalgebra::Cholesky::new(c).is_some());
+/// ```
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn random_correlation_matrix<R: Rng + ?Sized>(n: usize, rng: &mut R) -> DMatrix<f64> {
+    assert!(n > 0, "Dimension must be positive.");
+
+    let mut v = DMatrix::from_fn(n, n, |_, _| rng.sample::<f64, _>(StandardNormal));
+
+    for mut row in v.row_iter_mut() {
+        let norm = row.norm();
+        row /= norm;
+    }
+
+    let mut c = &v * v.transpose();
+    c.fill_diagonal(1.0);
+    c
+}
+
+/// Correlation matrix of a covariance matrix,
+/// $\rho_{ij} = \Sigma_{ij} / \sqrt{\Sigma_{ii} \Sigma_{jj}}$.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is not square or a variance is not
+/// positive.
+pub fn covariance_to_correlation(
+    covariance: &DMatrix<f64>,
+) -> Result<DMatrix<f64>, RustQuantError> {
+    if !covariance.is_square() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected a square matrix, got {:?}.",
+            covariance.shape()
+        )));
+    }
+
+    let diagonal = covariance.diagonal();
+
+    if diagonal.iter().any(|v| v.is_nan() || *v <= 0.0) {
+        return Err(RustQuantError::InvalidArgument(
+            "Variances must be positive.".to_string(),
+        ));
+    }
+
+    let scale: DVector<f64> = diagonal.map(|v| 1.0 / v.sqrt());
+    let n = covariance.nrows();
+
+    let mut correlation = DMatrix::from_fn(n, n, |i, j| covariance[(i, j)] * scale[i] * scale[j]);
+    correlation.fill_diagonal(1.0);
+
+    Ok(correlation)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_covariance {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use RustQuant_utils::assert_approx_equal;
+
+    fn returns() -> DMatrix<f64> {
+        DMatrix::from_row_slice(
+            5,
+            3,
+            &[
+                0.01, 0.02, -0.01, //
+                -0.02, 0.01, 0.00, //
+                0.03, -0.01, 0.02, //
+                0.00, 0.02, -0.02, //
+                0.01, -0.03, 0.01, //
+            ],
+        )
+    }
+
+    #[test]
+    fn test_sample_covariance() {
+        let covariance = sample_covariance(&returns()).unwrap();
+
+        assert_approx_equal!(covariance[(0, 0)], 0.000_33, 1e-15);
+        assert_approx_equal!(covariance[(0, 1)], -0.000_165, 1e-15);
+        assert_approx_equal!(covariance[(1, 2)], -0.000_275, 1e-15);
+        assert_eq!(covariance, covariance.transpose());
+    }
+
+    #[test]
+    fn test_ledoit_wolf() {
+        let estimate = ledoit_wolf(&returns()).unwrap();
+
+        assert_approx_equal!(estimate.shrinkage, 0.411_796_733_212_341_17, 1e-12);
+        assert_approx_equal!(
+            estimate.covariance[(0, 0)],
+            0.000_270_588_747_731_397_5,
+            1e-15
+        );
+        assert_approx_equal!(
+            estimate.covariance[(0, 1)],
+            -0.000_077_642_831_215_970_96,
+            1e-15
+        );
+
+        // A multiple of the identity is left alone.
+        let orthogonal =
+            DMatrix::from_row_slice(4, 2, &[1.0, 1.0, 1.0, -1.0, -1.0, 1.0, -1.0, -1.0]);
+        let estimate = ledoit_wolf(&orthogonal).unwrap();
+        assert_eq!(estimate.shrinkage, 0.0);
+        assert_eq!(estimate.covariance, DMatrix::identity(2, 2));
+
+        assert!(ledoit_wolf(&DMatrix::zeros(1, 3)).is_err());
+    }
+
+    #[test]
+    fn test_ewma_covariance() {
+        let r = returns();
+
+        // No decay: the (zero mean) second moment matrix.
+        let flat = ewma_covariance(&r, 1.0).unwrap();
+        assert!((flat - r.transpose() * &r / 5.0).norm() < 1e-15);
+
+        // Weights 1, 0.5, 0.25, 0.125, 0.0625 from the last observation.
+        let covariance = ewma_covariance(&r, 0.5).unwrap();
+        let variance = (0.01_f64.powi(2)
+            + 0.5 * 0.0
+            + 0.25 * 0.03_f64.powi(2)
+            + 0.125 * 0.02_f64.powi(2)
+            + 0.0625 * 0.01_f64.powi(2))
+            / 1.9375;
+        assert_approx_equal!(covariance[(0, 0)], variance, 1e-15);
+
+        assert!(ewma_covariance(&r, 0.0).is_err());
+        assert!(ewma_covariance(&r, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_random_correlation_matrix() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let c = random_correlation_matrix(10, &mut rng);
+
+        assert_eq!(c, c.transpose());
+        assert!(c.iter().all(|x| x.abs() <= 1.0 + 1e-15));
+        assert!(nalgebra::Cholesky::new(c.clone()).is_some());
+
+        let correlation = covariance_to_correlation(&(&c * 4.0)).unwrap();
+        assert!((correlation - c).norm() < 1e-14);
+    }
+}
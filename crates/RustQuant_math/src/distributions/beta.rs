@@ -0,0 +1,316 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::distributions::Distribution;
+use num::Complex;
+use statrs::function::beta::{beta_reg, inv_beta_reg, ln_beta};
+use statrs::function::gamma::digamma;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Beta distribution: X ~ Beta(alpha, beta)
+///
+/// A distribution on [0, 1], e.g. for recovery rates and probabilities.
+pub struct Beta {
+    /// Alpha: the first shape parameter.
+    alpha: f64,
+    /// Beta: the second shape parameter.
+    beta: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Beta {
+    /// New instance of a Beta distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let beta = Beta::new(2.0, 6.0);
+    ///
+    /// assert_approx_equal!(beta.mean(), 0.25, 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` or `beta` are not positive.
+    #[must_use]
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        assert!(alpha > 0.0 && beta > 0.0);
+
+        Self { alpha, beta }
+    }
+}
+
+/// Kummer's confluent hypergeometric function, 1F1(a; b; z),
+/// summed from its power series.
+///
+/// The series converges for all z, but loses precision to cancellation
+/// for large |z|, where its terms grow like exp(|z|).
+fn hypergeometric_1f1(a: f64, b: f64, z: Complex<f64>) -> Complex<f64> {
+    let mut term = Complex::new(1.0, 0.0);
+    let mut sum = term;
+
+    for n in 0..1000 {
+        let n = f64::from(n);
+        term *= z * (a + n) / ((b + n) * (n + 1.0));
+        sum += term;
+
+        if term.norm() <= f64::EPSILON * sum.norm() {
+            break;
+        }
+    }
+
+    sum
+}
+
+impl Distribution for Beta {
+    /// Characteristic function of the Beta distribution:
+    /// cf(t) = 1F1(alpha; alpha + beta; i t).
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// // Uniform distribution on [0, 1]: cf(t) = (exp(i t) - 1) / (i t)
+    /// let beta = Beta::new(1.0, 1.0);
+    /// let cf = beta.cf(2.0);
+    ///
+    /// assert_approx_equal!(cf.re, 2_f64.sin() / 2.0, 1e-12);
+    /// assert_approx_equal!(cf.im, (1.0 - 2_f64.cos()) / 2.0, 1e-12);
+    /// ```
+    fn cf(&self, t: f64) -> Complex<f64> {
+        hypergeometric_1f1(self.alpha, self.alpha + self.beta, Complex::new(0.0, t))
+    }
+
+    /// Probability density function of the Beta distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let beta = Beta::new(2.0, 2.0);
+    ///
+    /// assert_approx_equal!(beta.pdf(0.5), 1.5, 1e-12);
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return 0.0;
+        }
+
+        let (a, b) = (self.alpha, self.beta);
+
+        ((a - 1.0) * x.ln() + (b - 1.0) * (-x).ln_1p() - ln_beta(a, b)).exp()
+    }
+
+    /// Probability mass function of the Beta distribution.
+    /// Using this method will call `self.pdf(x)` instead.
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Distribution function of the Beta distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let beta = Beta::new(2.0, 2.0);
+    ///
+    /// assert_approx_equal!(beta.cdf(0.5), 0.5, 1e-12);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        beta_reg(self.alpha, self.beta, x.clamp(0.0, 1.0))
+    }
+
+    /// Inverse distribution function of the Beta distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let beta = Beta::new(2.0, 2.0);
+    ///
+    /// assert_approx_equal!(beta.inv_cdf(0.5), 0.5, 1e-12);
+    /// ```
+    fn inv_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+
+        inv_beta_reg(self.alpha, self.beta, p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.alpha / (self.alpha + self.beta)
+    }
+
+    /// Median of the Beta distribution, which has no closed form,
+    /// so it is computed from the inverse distribution function.
+    fn median(&self) -> f64 {
+        self.inv_cdf(0.5)
+    }
+
+    /// Mode of the Beta distribution.
+    /// For `alpha, beta < 1` the density is bimodal, and 0 is returned.
+    fn mode(&self) -> f64 {
+        let (a, b) = (self.alpha, self.beta);
+
+        if a > 1.0 && b > 1.0 {
+            (a - 1.0) / (a + b - 2.0)
+        } else if a == 1.0 && b == 1.0 {
+            0.5
+        } else if a >= 1.0 && b <= 1.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let (a, b) = (self.alpha, self.beta);
+
+        a * b / ((a + b).powi(2) * (a + b + 1.0))
+    }
+
+    fn skewness(&self) -> f64 {
+        let (a, b) = (self.alpha, self.beta);
+
+        2.0 * (b - a) * (a + b + 1.0).sqrt() / ((a + b + 2.0) * (a * b).sqrt())
+    }
+
+    fn kurtosis(&self) -> f64 {
+        let (a, b) = (self.alpha, self.beta);
+
+        6.0 * ((a - b).powi(2) * (a + b + 1.0) - a * b * (a + b + 2.0))
+            / (a * b * (a + b + 2.0) * (a + b + 3.0))
+    }
+
+    fn entropy(&self) -> f64 {
+        let (a, b) = (self.alpha, self.beta);
+
+        ln_beta(a, b) - (a - 1.0) * digamma(a) - (b - 1.0) * digamma(b)
+            + (a + b - 2.0) * digamma(a + b)
+    }
+
+    /// Moment generating function of the Beta distribution:
+    /// M(t) = 1F1(alpha; alpha + beta; t).
+    fn mgf(&self, t: f64) -> f64 {
+        let (a, b) = (self.alpha, self.alpha + self.beta);
+
+        // Kummer's transformation avoids the alternating series for t < 0.
+        if t < 0.0 {
+            t.exp() * hypergeometric_1f1(b - a, b, Complex::new(-t, 0.0)).re
+        } else {
+            hypergeometric_1f1(a, b, Complex::new(t, 0.0)).re
+        }
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError> {
+        // IMPORT HERE TO AVOID CLASH WITH
+        // `RustQuant::distributions::Distribution`
+        use rand::thread_rng;
+        use rand_distr::{Beta, Distribution};
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let dist = Beta::new(self.alpha, self.beta)?;
+        let mut variates: Vec<f64> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            variates.push(dist.sample(&mut rng));
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_beta {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_beta_characteristic_function() {
+        let dist = Beta::new(2.0, 5.0);
+
+        // Values computed using mpmath.
+        let cf = dist.cf(0.7);
+        assert_approx_equal!(cf.re, 0.973_986_960_829_463_7, 1e-12);
+        assert_approx_equal!(cf.im, 0.197_295_891_215_619_24, 1e-12);
+
+        let cf = dist.cf(3.0);
+        assert_approx_equal!(cf.re, 0.591_009_917_212_221_6, 1e-12);
+        assert_approx_equal!(cf.im, 0.667_237_066_766_098_8, 1e-12);
+
+        assert_approx_equal!(dist.mgf(3.0), 2.670_544_349_258_928, 1e-12);
+        assert_approx_equal!(dist.mgf(-3.0), 0.470_818_225_241_568_8, 1e-12);
+    }
+
+    #[test]
+    fn test_beta_density_function() {
+        let dist = Beta::new(2.0, 5.0);
+
+        assert_approx_equal!(dist.pdf(0.2), 2.4576, 1e-12);
+        assert_approx_equal!(dist.pdf(0.6), 0.4608, 1e-12);
+        assert_eq!(dist.pdf(1.5), 0.0);
+    }
+
+    #[test]
+    fn test_beta_distribution_function() {
+        let dist = Beta::new(2.0, 5.0);
+
+        assert_approx_equal!(dist.cdf(0.2), 0.34464, 1e-12);
+        assert_approx_equal!(dist.cdf(0.6), 0.95904, 1e-12);
+        assert_eq!(dist.cdf(-1.0), 0.0);
+        assert_eq!(dist.cdf(2.0), 1.0);
+
+        for p in [0.001, 0.1, 0.5, 0.8, 0.999] {
+            assert_approx_equal!(dist.cdf(dist.inv_cdf(p)), p, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_beta_moments() {
+        let dist = Beta::new(2.0, 5.0);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.mean(), 2.0 / 7.0, EPS);
+        assert_approx_equal!(dist.median(), 0.264_449_983_295_66, 1e-10);
+        assert_approx_equal!(dist.mode(), 0.2, EPS);
+        assert_approx_equal!(dist.variance(), 10.0 / 392.0, EPS);
+        assert_approx_equal!(dist.skewness(), 0.596_284_793_999_943_9, 1e-12);
+        assert_approx_equal!(dist.kurtosis(), -0.12, 1e-12);
+        assert_approx_equal!(dist.entropy(), -0.484_530_714_995_488_7, 1e-12);
+    }
+
+    #[test]
+    fn test_beta_variate_generator() -> Result<(), RustQuantError> {
+        let dist = Beta::new(2.0, 5.0);
+
+        let v = dist.sample(1000)?;
+        let mean = v.iter().sum::<f64>() / v.len() as f64;
+
+        assert!(v.iter().all(|x| (0.0..=1.0).contains(x)));
+        assert_approx_equal!(mean, 2.0 / 7.0, 0.05);
+
+        Ok(())
+    }
+}
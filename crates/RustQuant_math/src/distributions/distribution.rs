@@ -7,7 +7,9 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::integration::integrate;
 use num::Complex;
+use rand::{distributions::Open01, Rng};
 use RustQuant_error::RustQuantError;
 
 /// Imaginary unit.
@@ -92,4 +94,119 @@ pub trait Distribution {
 
     /// Generates a random sample from the distribution.
     fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError>;
+
+    /// Generates a random sample from the distribution, with the given
+    /// random number generator (e.g. a seeded `StdRng`, for reproducible
+    /// samples), by inverse transform sampling.
+    ///
+    /// ```
+    /// # use RustQuant::math::distributions::*;
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// let normal = Gaussian::new(0.0, 1.0);
+    ///
+    /// let a = normal.sample_with(&mut StdRng::seed_from_u64(1), 10).unwrap();
+    /// let b = normal.sample_with(&mut StdRng::seed_from_u64(1), 10).unwrap();
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As for [`Distribution::quantile_transform`].
+    fn sample_with<R>(&self, rng: &mut R, n: usize) -> Result<Vec<f64>, RustQuantError>
+    where
+        R: Rng + ?Sized,
+        Self: Sized,
+    {
+        let uniforms: Vec<f64> = (0..n).map(|_| rng.sample(Open01)).collect();
+
+        self.quantile_transform(&uniforms)
+    }
+
+    /// Transforms uniform variates on (0, 1) into variates of the
+    /// distribution with the inverse distribution function,
+    /// $X = F^{-1}(U)$.
+    ///
+    /// The uniforms need not be random: with points of a low-discrepancy
+    /// sequence (see [`crate::Sobol`] and [`crate::Halton`]), this gives
+    /// quasi-Monte Carlo samples of the distribution.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::math::distributions::*;
+    /// // 4095 points of the one dimensional Sobol sequence.
+    /// let uniforms: Vec<f64> = Sobol::new(1).unwrap().take(4095).map(|u| u[0]).collect();
+    ///
+    /// let variates = Exponential::new(2.0).quantile_transform(&uniforms).unwrap();
+    /// let mean = variates.iter().sum::<f64>() / variates.len() as f64;
+    ///
+    /// assert!((mean - 0.5).abs() < 2e-3);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a uniform variate is not in (0, 1).
+    fn quantile_transform(&self, uniforms: &[f64]) -> Result<Vec<f64>, RustQuantError> {
+        uniforms
+            .iter()
+            .map(|&u| {
+                if u > 0.0 && u < 1.0 {
+                    Ok(self.inv_cdf(u))
+                } else {
+                    Err(RustQuantError::InvalidArgument(format!(
+                        "Uniform variates must be in (0, 1), got {u}."
+                    )))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Integral of `f` over (0, 1), split into panels that halve in width
+/// towards both ends, where the inverse distribution function of an
+/// unbounded distribution diverges and its integrands oscillate.
+fn quantile_integral<F>(f: F) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    const PANELS: i32 = 40;
+
+    let mut integral = integrate(&f, 0.25, 0.75);
+
+    for k in 2..PANELS {
+        let (a, b) = (0.5_f64.powi(k + 1), 0.5_f64.powi(k));
+
+        integral += integrate(&f, a, b) + integrate(&f, 1.0 - b, 1.0 - a);
+    }
+
+    integral
+}
+
+/// Characteristic function of a continuous distribution, from its inverse
+/// distribution function, for distributions without a closed form:
+///
+/// $$
+/// \varphi(t) = \mathbb{E}[e^{itX}] = \int_0^1 e^{it F^{-1}(u)} du
+/// $$
+pub(crate) fn quantile_cf<F>(inv_cdf: F, t: f64) -> Complex<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    Complex::new(
+        quantile_integral(|u| (t * inv_cdf(u)).cos()),
+        quantile_integral(|u| (t * inv_cdf(u)).sin()),
+    )
+}
+
+/// Moment generating function of a continuous distribution, from its
+/// inverse distribution function, where it is finite:
+///
+/// $$
+/// M(t) = \mathbb{E}[e^{tX}] = \int_0^1 e^{t F^{-1}(u)} du
+/// $$
+pub(crate) fn quantile_mgf<F>(inv_cdf: F, t: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    quantile_integral(|u| (t * inv_cdf(u)).exp())
 }
@@ -82,8 +82,12 @@ impl Distribution for Gamma {
         gamma_li(alpha, beta * x) / gamma(alpha)
     }
 
-    fn inv_cdf(&self, _p: f64) -> f64 {
-        unimplemented!()
+    fn inv_cdf(&self, p: f64) -> f64 {
+        use statrs::distribution::{ContinuousCDF, Gamma};
+
+        Gamma::new(self.alpha, self.beta)
+            .expect("Gamma parameters are positive.")
+            .inverse_cdf(p)
     }
 
     fn mean(&self) -> f64 {
@@ -189,4 +193,14 @@ mod tests {
         assert_approx_equal!(dist.cdf(3.0), 0.950_212_931_632_136, EPS);
         assert_approx_equal!(dist.cdf(4.0), 0.981_684_361_111_265_8, EPS);
     }
+
+    #[test]
+    fn test_gamma_inverse_distribution_function() {
+        // Gamma(1,1) is equivalent to Exp(1): F^-1(p) = -ln(1 - p).
+        let dist: Gamma = Gamma::new(1.0, 1.0);
+        assert_approx_equal!(dist.inv_cdf(0.5), 2_f64.ln(), 1e-10);
+
+        let dist: Gamma = Gamma::new(2.5, 2.0);
+        assert_approx_equal!(dist.inv_cdf(dist.cdf(1.5)), 1.5, 1e-8);
+    }
 }
@@ -0,0 +1,273 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::distributions::{quantile_cf, quantile_mgf, Distribution};
+use errorfunctions::RealErrorFunctions;
+use num::Complex;
+use statrs::function::erf;
+use std::f64::consts::{PI, SQRT_2};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Log-normal distribution: X ~ LogNormal(mu, sigma)
+///
+/// ln(X) is Gaussian with mean mu and standard deviation sigma,
+/// e.g. the terminal asset price under geometric Brownian motion.
+pub struct LogNormal {
+    /// Mu: mean of ln(X).
+    mu: f64,
+    /// Sigma: standard deviation of ln(X).
+    sigma: f64,
+}
+
+impl Default for LogNormal {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LogNormal {
+    /// New instance of a log-normal distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let log_normal = LogNormal::new(0.0, 1.0);
+    ///
+    /// assert_approx_equal!(log_normal.mean(), 0.5_f64.exp(), 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is not positive.
+    #[must_use]
+    pub fn new(mu: f64, sigma: f64) -> Self {
+        assert!(sigma > 0.0);
+
+        Self { mu, sigma }
+    }
+}
+
+impl Distribution for LogNormal {
+    /// Characteristic function of the log-normal distribution.
+    /// It has no closed form, so it is integrated numerically.
+    fn cf(&self, t: f64) -> Complex<f64> {
+        quantile_cf(|p| self.inv_cdf(p), t)
+    }
+
+    /// Probability density function of the log-normal distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let log_normal = LogNormal::new(0.0, 1.0);
+    ///
+    /// assert_approx_equal!(log_normal.pdf(1.0), 0.3989423, 1e-7);
+    /// assert_eq!(log_normal.pdf(-1.0), 0.0);
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        let z = (x.ln() - self.mu) / self.sigma;
+
+        (-0.5 * z * z).exp() / (x * self.sigma * (2.0 * PI).sqrt())
+    }
+
+    /// Probability mass function of the log-normal distribution.
+    /// Using this method will call `self.pdf(x)` instead.
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Distribution function of the log-normal distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let log_normal = LogNormal::new(0.0, 1.0);
+    ///
+    /// assert_approx_equal!(log_normal.cdf(1.0), 0.5, 1e-12);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        0.5 * RealErrorFunctions::erfc(-(x.ln() - self.mu) / (SQRT_2 * self.sigma))
+    }
+
+    /// Inverse distribution function of the log-normal distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let log_normal = LogNormal::new(0.0, 1.0);
+    ///
+    /// assert_approx_equal!(log_normal.inv_cdf(0.975), 7.0990714, 1e-7);
+    /// ```
+    fn inv_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+
+        (self.mu + SQRT_2 * self.sigma * erf::erf_inv(2.0 * p - 1.0)).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        (self.mu + 0.5 * self.sigma.powi(2)).exp()
+    }
+
+    fn median(&self) -> f64 {
+        self.mu.exp()
+    }
+
+    fn mode(&self) -> f64 {
+        (self.mu - self.sigma.powi(2)).exp()
+    }
+
+    fn variance(&self) -> f64 {
+        let s2 = self.sigma.powi(2);
+
+        s2.exp_m1() * (2.0 * self.mu + s2).exp()
+    }
+
+    fn skewness(&self) -> f64 {
+        let s2 = self.sigma.powi(2);
+
+        (s2.exp() + 2.0) * s2.exp_m1().sqrt()
+    }
+
+    fn kurtosis(&self) -> f64 {
+        let s2 = self.sigma.powi(2);
+
+        (4.0 * s2).exp() + 2.0 * (3.0 * s2).exp() + 3.0 * (2.0 * s2).exp() - 6.0
+    }
+
+    fn entropy(&self) -> f64 {
+        self.mu + 0.5 + (self.sigma * (2.0 * PI).sqrt()).ln()
+    }
+
+    /// Moment generating function of the log-normal distribution.
+    /// It is infinite for `t > 0`, and has no closed form for `t < 0`,
+    /// where it is integrated numerically.
+    fn mgf(&self, t: f64) -> f64 {
+        match t {
+            t if t > 0.0 => f64::INFINITY,
+            t if t < 0.0 => quantile_mgf(|p| self.inv_cdf(p), t),
+            _ => 1.0,
+        }
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError> {
+        // IMPORT HERE TO AVOID CLASH WITH
+        // `RustQuant::distributions::Distribution`
+        use rand::thread_rng;
+        use rand_distr::{Distribution, LogNormal};
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let dist = LogNormal::new(self.mu, self.sigma)?;
+        let mut variates: Vec<f64> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            variates.push(dist.sample(&mut rng));
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_log_normal {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_log_normal_characteristic_function() {
+        let dist = LogNormal::new(0.5, 0.8);
+
+        // Values computed using mpmath.
+        let cf = dist.cf(0.7);
+
+        assert_approx_equal!(cf.re, 0.251_568_347_139_141, 1e-6);
+        assert_approx_equal!(cf.im, 0.585_007_723_754_990_6, 1e-6);
+        assert_approx_equal!(dist.mgf(-0.7), 0.335_477_825_806_535_4, 1e-8);
+        assert!(dist.mgf(0.7).is_infinite());
+    }
+
+    #[test]
+    fn test_log_normal_density_function() {
+        let dist = LogNormal::new(0.5, 0.8);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.pdf(1.0), 0.410_201_210_687_968_8, EPS);
+        assert_approx_equal!(dist.pdf(2.0), 0.242_176_774_884_833_4, EPS);
+        assert_approx_equal!(dist.pdf(5.0), 0.038_126_854_770_019_86, EPS);
+        assert_eq!(dist.pdf(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_log_normal_distribution_function() {
+        let dist = LogNormal::new(0.5, 0.8);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.cdf(1.0), 0.265_985_529_048_700_5, 1e-12);
+        assert_approx_equal!(dist.cdf(2.0), 0.595_390_608_679_215, 1e-12);
+        assert_approx_equal!(dist.cdf(5.0), 0.917_248_223_478_709, 1e-12);
+
+        for p in [0.001, 0.1, 0.5, 0.8, 0.999] {
+            assert_approx_equal!(dist.cdf(dist.inv_cdf(p)), p, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_log_normal_moments() {
+        let dist = LogNormal::new(0.5, 0.8);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.mean(), 0.82_f64.exp(), EPS);
+        assert_approx_equal!(dist.median(), 0.5_f64.exp(), EPS);
+        assert_approx_equal!(dist.mode(), (-0.14_f64).exp(), EPS);
+        assert_approx_equal!(dist.variance(), 4.621_510_897_294_224, 1e-12);
+        assert_approx_equal!(dist.skewness(), 3.689_292_296_091_297, 1e-12);
+        assert_approx_equal!(dist.kurtosis(), 31.367_653_430_832_42, 1e-12);
+        assert_approx_equal!(dist.entropy(), 1.695_794_981_890_463, 1e-12);
+    }
+
+    #[test]
+    fn test_log_normal_variate_generator() -> Result<(), RustQuantError> {
+        let dist = LogNormal::new(0.0, 0.25);
+
+        let v = dist.sample(1000)?;
+        let mean = v.iter().sum::<f64>() / v.len() as f64;
+
+        assert!(v.iter().all(|&x| x > 0.0));
+        assert_approx_equal!(mean, dist.mean(), 0.05);
+
+        Ok(())
+    }
+}
@@ -15,6 +15,10 @@ pub use distribution::*;
 pub mod bernoulli;
 pub use bernoulli::*;
 
+/// Beta distribution.
+pub mod beta;
+pub use beta::*;
+
 /// Binomial distribution.
 pub mod binomial;
 pub use binomial::*;
@@ -35,10 +39,34 @@ pub use gamma::*;
 pub mod gaussian;
 pub use gaussian::*;
 
+/// Log-normal distribution.
+pub mod log_normal;
+pub use log_normal::*;
+
+/// Multivariate normal distribution.
+pub mod multivariate_normal;
+pub use multivariate_normal::*;
+
+/// Multivariate Student's t distribution.
+pub mod multivariate_t;
+pub use multivariate_t::*;
+
+/// Pareto distribution.
+pub mod pareto;
+pub use pareto::*;
+
 /// Poisson distribution.
 pub mod poisson;
 pub use poisson::*;
 
+/// Student's t distribution.
+pub mod students_t;
+pub use students_t::*;
+
 /// Uniform distribution.
 pub mod uniform;
 pub use uniform::*;
+
+/// Weibull distribution.
+pub mod weibull;
+pub use weibull::*;
@@ -0,0 +1,433 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::linear_algebra::check_symmetric;
+use nalgebra::{Cholesky, DMatrix, DVector, Dyn};
+use std::f64::consts::PI;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Multivariate normal distribution: X ~ N(mu, Sigma)
+///
+/// The covariance matrix is factorised as Sigma = L L^T on construction,
+/// so correlated variates are mu + L Z for independent standard normals Z.
+#[derive(Debug, Clone)]
+pub struct MultivariateNormal {
+    /// Mean vector.
+    mean: DVector<f64>,
+    /// Covariance matrix.
+    covariance: DMatrix<f64>,
+    /// Cholesky factorisation of the covariance matrix.
+    cholesky: Cholesky<f64, Dyn>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Cholesky factorisation of a location vector's scale matrix,
+/// checking that the matrix is square, symmetric and positive definite.
+pub(crate) fn factorise(
+    location: &DVector<f64>,
+    scale: &DMatrix<f64>,
+) -> Result<Cholesky<f64, Dyn>, RustQuantError> {
+    let n = location.len();
+
+    if n == 0 || scale.shape() != (n, n) {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected a {n} x {n} matrix for a vector of length {n}, got {:?}.",
+            scale.shape()
+        )));
+    }
+
+    check_symmetric(scale)?;
+
+    Cholesky::new(scale.clone()).ok_or(RustQuantError::InvalidArgument(
+        "The matrix must be positive definite.".to_string(),
+    ))
+}
+
+/// Checks that the component `indices` are non-empty, in range for
+/// dimension `n`, and not repeated.
+pub(crate) fn check_indices(n: usize, indices: &[usize]) -> Result<(), RustQuantError> {
+    let mut seen = vec![false; n];
+
+    if indices.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "Expected at least one component.".to_string(),
+        ));
+    }
+
+    for &i in indices {
+        if i >= n || seen[i] {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Component {i} is out of range or repeated, for dimension {n}."
+            )));
+        }
+        seen[i] = true;
+    }
+
+    Ok(())
+}
+
+/// The components `0..n` not in `given`, which must be a proper subset.
+pub(crate) fn partition(n: usize, given: &[usize]) -> Result<Vec<usize>, RustQuantError> {
+    check_indices(n, given)?;
+
+    let rest: Vec<usize> = (0..n).filter(|i| !given.contains(i)).collect();
+
+    if rest.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "Cannot condition on every component.".to_string(),
+        ));
+    }
+
+    Ok(rest)
+}
+
+/// Partitioned scale matrix quantities for conditioning the components
+/// `rest` on `given` taking `values`:
+///
+/// - the location shift, Sigma_12 Sigma_22^-1 (a - mu_2),
+/// - the Schur complement, Sigma_11 - Sigma_12 Sigma_22^-1 Sigma_21,
+/// - the Mahalanobis distance, (a - mu_2)^T Sigma_22^-1 (a - mu_2).
+pub(crate) fn condition(
+    location: &DVector<f64>,
+    scale: &DMatrix<f64>,
+    rest: &[usize],
+    given: &[usize],
+    values: &DVector<f64>,
+) -> Result<(DVector<f64>, DMatrix<f64>, f64), RustQuantError> {
+    if values.len() != given.len() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected {} conditioning values, got {}.",
+            given.len(),
+            values.len()
+        )));
+    }
+
+    let select = |rows: &[usize], cols: &[usize]| scale.select_rows(rows).select_columns(cols);
+
+    let deviation = values - location.select_rows(given);
+    let sigma_22 =
+        Cholesky::new(select(given, given)).ok_or(RustQuantError::MatrixInversionFailed)?;
+    let sigma_21 = select(given, rest);
+
+    // Sigma_22^-1 Sigma_21 and Sigma_22^-1 (a - mu_2)
+    let weights = sigma_22.solve(&sigma_21);
+    let whitened = sigma_22.solve(&deviation);
+
+    // Symmetrise, to remove the rounding errors of the product.
+    let schur = select(rest, rest) - sigma_21.transpose() * &weights;
+    let schur = (&schur + schur.transpose()) * 0.5;
+
+    Ok((
+        weights.transpose() * &deviation,
+        schur,
+        deviation.dot(&whitened),
+    ))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MultivariateNormal {
+    /// New instance of a multivariate normal distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::math::distributions::*;
+    /// # use nalgebra::{DMatrix, DVector};
+    ///
+    /// let mean = DVector::from_vec(vec![0.0, 1.0]);
+    /// let covariance = DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.5, 2.0]);
+    ///
+    /// let mvn = MultivariateNormal::new(mean, covariance).unwrap();
+    ///
+    /// assert_eq!(mvn.dimension(), 2);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if the covariance matrix is not
+    ///   square, symmetric and positive definite, or its dimension does
+    ///   not match the mean vector.
+    pub fn new(mean: DVector<f64>, covariance: DMatrix<f64>) -> Result<Self, RustQuantError> {
+        let cholesky = factorise(&mean, &covariance)?;
+
+        Ok(Self {
+            mean,
+            covariance,
+            cholesky,
+        })
+    }
+
+    /// Number of components.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Mean vector.
+    #[must_use]
+    pub fn mean(&self) -> &DVector<f64> {
+        &self.mean
+    }
+
+    /// Covariance matrix.
+    #[must_use]
+    pub fn covariance(&self) -> &DMatrix<f64> {
+        &self.covariance
+    }
+
+    /// Lower triangular Cholesky factor L of the covariance matrix.
+    #[must_use]
+    pub fn cholesky_factor(&self) -> DMatrix<f64> {
+        self.cholesky.l()
+    }
+
+    /// Logarithm of the probability density function.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    /// # use nalgebra::{DMatrix, DVector};
+    ///
+    /// // Independent standard normals.
+    /// let mvn = MultivariateNormal::new(DVector::zeros(2), DMatrix::identity(2, 2)).unwrap();
+    /// let x = DVector::from_vec(vec![1.0, -1.0]);
+    ///
+    /// let expected = 2.0 * Gaussian::default().pdf(1.0).ln();
+    ///
+    /// assert_approx_equal!(mvn.log_pdf(&x), expected, 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` does not have one value per component.
+    #[must_use]
+    pub fn log_pdf(&self, x: &DVector<f64>) -> f64 {
+        assert_eq!(x.len(), self.dimension());
+
+        let n = self.dimension() as f64;
+        let deviation = x - &self.mean;
+        let distance = deviation.dot(&self.cholesky.solve(&deviation));
+
+        -0.5 * (n * (2.0 * PI).ln() + self.cholesky.ln_determinant() + distance)
+    }
+
+    /// Probability density function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` does not have one value per component.
+    #[must_use]
+    pub fn pdf(&self, x: &DVector<f64>) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    /// Marginal distribution of the `indices` components, in that order.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::math::distributions::*;
+    /// # use nalgebra::{DMatrix, DVector};
+    ///
+    /// let mean = DVector::from_vec(vec![0.0, 1.0, 2.0]);
+    /// let covariance = DMatrix::from_diagonal(&DVector::from_vec(vec![1.0, 2.0, 3.0]));
+    /// let mvn = MultivariateNormal::new(mean, covariance).unwrap();
+    ///
+    /// let marginal = mvn.marginal(&[2, 0]).unwrap();
+    ///
+    /// assert_eq!(marginal.mean().as_slice(), &[2.0, 0.0]);
+    /// assert_eq!(marginal.covariance()[(0, 0)], 3.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if the indices are empty,
+    ///   out of range, or repeated.
+    pub fn marginal(&self, indices: &[usize]) -> Result<Self, RustQuantError> {
+        check_indices(self.dimension(), indices)?;
+
+        Self::new(
+            self.mean.select_rows(indices),
+            self.covariance.select_rows(indices).select_columns(indices),
+        )
+    }
+
+    /// Conditional distribution of the other components, in increasing
+    /// order, given that the `given` components take the `values`.
+    ///
+    /// $$
+    /// \mu_{1|2} = \mu_1 + \Sigma_{12} \Sigma_{22}^{-1} (a - \mu_2), \quad
+    /// \Sigma_{1|2} = \Sigma_{11} - \Sigma_{12} \Sigma_{22}^{-1} \Sigma_{21}
+    /// $$
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    /// # use nalgebra::{DMatrix, DVector};
+    ///
+    /// // Bivariate normal with correlation 0.5.
+    /// let covariance = DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.5, 1.0]);
+    /// let mvn = MultivariateNormal::new(DVector::zeros(2), covariance).unwrap();
+    ///
+    /// let conditional = mvn.conditional(&[1], &DVector::from_vec(vec![2.0])).unwrap();
+    ///
+    /// assert_approx_equal!(conditional.mean()[0], 1.0, 1e-12);
+    /// assert_approx_equal!(conditional.covariance()[(0, 0)], 0.75, 1e-12);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if `given` is not a proper,
+    ///   non-empty subset of the components, or there is not one value
+    ///   per given component.
+    pub fn conditional(
+        &self,
+        given: &[usize],
+        values: &DVector<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let rest = partition(self.dimension(), given)?;
+        let (shift, covariance, _) = condition(&self.mean, &self.covariance, &rest, given, values)?;
+
+        Self::new(self.mean.select_rows(&rest) + shift, covariance)
+    }
+
+    /// Generates `n` correlated variates, as mu + L Z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn sample(&self, n: usize) -> Result<Vec<DVector<f64>>, RustQuantError> {
+        use rand::{thread_rng, Rng};
+        use rand_distr::StandardNormal;
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let lower = self.cholesky.l();
+        let mut variates: Vec<DVector<f64>> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            let z = DVector::from_iterator(
+                self.dimension(),
+                (&mut rng)
+                    .sample_iter(StandardNormal)
+                    .take(self.dimension()),
+            );
+
+            variates.push(&self.mean + &lower * z);
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_multivariate_normal {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn mvn() -> MultivariateNormal {
+        let mean = DVector::from_vec(vec![0.5, -1.0, 2.0]);
+        let covariance =
+            DMatrix::from_row_slice(3, 3, &[2.0, 0.6, -0.4, 0.6, 1.0, 0.3, -0.4, 0.3, 1.5]);
+
+        MultivariateNormal::new(mean, covariance).unwrap()
+    }
+
+    #[test]
+    fn test_multivariate_normal_density() {
+        let x = DVector::from_vec(vec![1.0, 0.0, 1.0]);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(mvn().log_pdf(&x), -4.340_394_397_252_565, 1e-12);
+        assert_approx_equal!(mvn().pdf(&x), 0.013_031_387_646_308_753, 1e-12);
+    }
+
+    #[test]
+    fn test_multivariate_normal_marginal() {
+        let marginal = mvn().marginal(&[1]).unwrap();
+
+        assert_eq!(marginal.dimension(), 1);
+        assert_eq!(marginal.mean()[0], -1.0);
+        assert_eq!(marginal.covariance()[(0, 0)], 1.0);
+
+        assert!(mvn().marginal(&[0, 0]).is_err());
+        assert!(mvn().marginal(&[3]).is_err());
+        assert!(mvn().marginal(&[]).is_err());
+    }
+
+    #[test]
+    fn test_multivariate_normal_conditional() {
+        let conditional = mvn()
+            .conditional(&[2, 0], &DVector::from_vec(vec![1.0, 1.0]))
+            .unwrap();
+
+        // Values computed using mpmath.
+        assert_eq!(conditional.dimension(), 1);
+        assert_approx_equal!(conditional.mean()[0], -1.116_197_183_098_591_5, 1e-12);
+        assert_approx_equal!(
+            conditional.covariance()[(0, 0)],
+            0.695_774_647_887_324,
+            1e-12
+        );
+
+        assert!(mvn().conditional(&[0, 1, 2], &DVector::zeros(3)).is_err());
+        assert!(mvn().conditional(&[0], &DVector::zeros(2)).is_err());
+    }
+
+    #[test]
+    fn test_multivariate_normal_errors() {
+        let mean = DVector::zeros(2);
+
+        // Not positive definite.
+        let covariance = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        assert!(MultivariateNormal::new(mean.clone(), covariance).is_err());
+
+        // Not symmetric.
+        let covariance = DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.0, 1.0]);
+        assert!(MultivariateNormal::new(mean.clone(), covariance).is_err());
+
+        // Wrong dimension.
+        assert!(MultivariateNormal::new(mean, DMatrix::identity(3, 3)).is_err());
+    }
+
+    #[test]
+    fn test_multivariate_normal_variate_generator() -> Result<(), RustQuantError> {
+        let dist = mvn();
+        let n = 20_000;
+
+        let v = dist.sample(n)?;
+        let mean = v.iter().sum::<DVector<f64>>() / n as f64;
+        let covariance = v
+            .iter()
+            .map(|x| (x - &mean) * (x - &mean).transpose())
+            .sum::<DMatrix<f64>>()
+            / (n - 1) as f64;
+
+        for i in 0..3 {
+            assert_approx_equal!(mean[i], dist.mean()[i], 0.05);
+
+            for j in 0..3 {
+                assert_approx_equal!(covariance[(i, j)], dist.covariance()[(i, j)], 0.1);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::distributions::{check_indices, condition, factorise, partition};
+use nalgebra::{Cholesky, DMatrix, DVector, Dyn};
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::PI;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Multivariate Student's t distribution: X ~ t(nu, mu, Sigma)
+///
+/// X = mu + L Z / sqrt(W / nu), where Sigma = L L^T is the scale matrix,
+/// Z is a vector of independent standard normals and W ~ ChiSquared(nu).
+/// All components share the same W, so their tails are dependent, even
+/// when they are uncorrelated.
+#[derive(Debug, Clone)]
+pub struct MultivariateT {
+    /// Nu: degrees of freedom.
+    nu: f64,
+    /// Mu: the location vector.
+    location: DVector<f64>,
+    /// Sigma: the scale matrix.
+    scale: DMatrix<f64>,
+    /// Cholesky factorisation of the scale matrix.
+    cholesky: Cholesky<f64, Dyn>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MultivariateT {
+    /// New instance of a multivariate Student's t distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::math::distributions::*;
+    /// # use nalgebra::{DMatrix, DVector};
+    ///
+    /// let scale = DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.5, 2.0]);
+    /// let mvt = MultivariateT::new(4.0, DVector::zeros(2), scale).unwrap();
+    ///
+    /// // The covariance is nu / (nu - 2) times the scale matrix.
+    /// assert_eq!(mvt.covariance()[(1, 1)], 4.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if `nu` is not positive, or the
+    ///   scale matrix is not square, symmetric and positive definite, or
+    ///   its dimension does not match the location vector.
+    pub fn new(
+        nu: f64,
+        location: DVector<f64>,
+        scale: DMatrix<f64>,
+    ) -> Result<Self, RustQuantError> {
+        if nu.is_nan() || nu <= 0.0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Degrees of freedom must be positive, got {nu}."
+            )));
+        }
+
+        let cholesky = factorise(&location, &scale)?;
+
+        Ok(Self {
+            nu,
+            location,
+            scale,
+            cholesky,
+        })
+    }
+
+    /// Number of components.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.location.len()
+    }
+
+    /// Degrees of freedom.
+    #[must_use]
+    pub fn degrees_of_freedom(&self) -> f64 {
+        self.nu
+    }
+
+    /// Location vector.
+    #[must_use]
+    pub fn location(&self) -> &DVector<f64> {
+        &self.location
+    }
+
+    /// Scale matrix.
+    #[must_use]
+    pub fn scale(&self) -> &DMatrix<f64> {
+        &self.scale
+    }
+
+    /// Lower triangular Cholesky factor L of the scale matrix.
+    #[must_use]
+    pub fn cholesky_factor(&self) -> DMatrix<f64> {
+        self.cholesky.l()
+    }
+
+    /// Mean vector, which only exists for `nu > 1`.
+    #[must_use]
+    pub fn mean(&self) -> DVector<f64> {
+        if self.nu > 1.0 {
+            self.location.clone()
+        } else {
+            DVector::from_element(self.dimension(), f64::NAN)
+        }
+    }
+
+    /// Covariance matrix, nu / (nu - 2) Sigma,
+    /// which is infinite for `1 < nu <= 2` and does not exist for `nu <= 1`.
+    #[must_use]
+    pub fn covariance(&self) -> DMatrix<f64> {
+        match self.nu {
+            nu if nu > 2.0 => &self.scale * (nu / (nu - 2.0)),
+            nu if nu > 1.0 => self.scale.map(|x| x * f64::INFINITY),
+            _ => self.scale.map(|_| f64::NAN),
+        }
+    }
+
+    /// Logarithm of the probability density function.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    /// # use nalgebra::{DMatrix, DVector};
+    ///
+    /// // In one dimension, this is Student's t distribution.
+    /// let mvt = MultivariateT::new(3.0, DVector::zeros(1), DMatrix::identity(1, 1)).unwrap();
+    /// let t = StudentT::new(3.0, 0.0, 1.0);
+    ///
+    /// let x = DVector::from_vec(vec![1.5]);
+    ///
+    /// assert_approx_equal!(mvt.log_pdf(&x), t.pdf(1.5).ln(), 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` does not have one value per component.
+    #[must_use]
+    pub fn log_pdf(&self, x: &DVector<f64>) -> f64 {
+        assert_eq!(x.len(), self.dimension());
+
+        let (nu, n) = (self.nu, self.dimension() as f64);
+        let deviation = x - &self.location;
+        let distance = deviation.dot(&self.cholesky.solve(&deviation));
+
+        ln_gamma(0.5 * (nu + n))
+            - ln_gamma(0.5 * nu)
+            - 0.5 * n * (nu * PI).ln()
+            - 0.5 * self.cholesky.ln_determinant()
+            - 0.5 * (nu + n) * (distance / nu).ln_1p()
+    }
+
+    /// Probability density function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` does not have one value per component.
+    #[must_use]
+    pub fn pdf(&self, x: &DVector<f64>) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    /// Marginal distribution of the `indices` components, in that order,
+    /// which has the same degrees of freedom.
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if the indices are empty,
+    ///   out of range, or repeated.
+    pub fn marginal(&self, indices: &[usize]) -> Result<Self, RustQuantError> {
+        check_indices(self.dimension(), indices)?;
+
+        Self::new(
+            self.nu,
+            self.location.select_rows(indices),
+            self.scale.select_rows(indices).select_columns(indices),
+        )
+    }
+
+    /// Conditional distribution of the other components, in increasing
+    /// order, given that the `given` components take the `values`.
+    ///
+    /// Conditioning on `d_2` components adds `d_2` degrees of freedom, and
+    /// scales the Schur complement by the distance of the values from the
+    /// location:
+    ///
+    /// $$
+    /// \nu_{1|2} = \nu + d_2, \quad
+    /// \Sigma_{1|2} = \frac{\nu + (a - \mu_2)^T \Sigma_{22}^{-1} (a - \mu_2)}{\nu + d_2}
+    ///     \left( \Sigma_{11} - \Sigma_{12} \Sigma_{22}^{-1} \Sigma_{21} \right)
+    /// $$
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if `given` is not a proper,
+    ///   non-empty subset of the components, or there is not one value
+    ///   per given component.
+    pub fn conditional(
+        &self,
+        given: &[usize],
+        values: &DVector<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let rest = partition(self.dimension(), given)?;
+        let (shift, schur, distance) =
+            condition(&self.location, &self.scale, &rest, given, values)?;
+
+        let nu = self.nu + given.len() as f64;
+
+        Self::new(
+            nu,
+            self.location.select_rows(&rest) + shift,
+            schur * ((self.nu + distance) / nu),
+        )
+    }
+
+    /// Generates `n` variates, as mu + L Z / sqrt(W / nu).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn sample(&self, n: usize) -> Result<Vec<DVector<f64>>, RustQuantError> {
+        use rand::{thread_rng, Rng};
+        use rand_distr::{ChiSquared, StandardNormal};
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let chi_squared = ChiSquared::new(self.nu)?;
+        let lower = self.cholesky.l();
+        let mut variates: Vec<DVector<f64>> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            let z = DVector::from_iterator(
+                self.dimension(),
+                (&mut rng)
+                    .sample_iter(StandardNormal)
+                    .take(self.dimension()),
+            );
+            let w: f64 = rng.sample(chi_squared);
+
+            variates.push(&self.location + &lower * z / (w / self.nu).sqrt());
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_multivariate_t {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn mvt() -> MultivariateT {
+        let location = DVector::from_vec(vec![0.5, -1.0, 2.0]);
+        let scale = DMatrix::from_row_slice(3, 3, &[2.0, 0.6, -0.4, 0.6, 1.0, 0.3, -0.4, 0.3, 1.5]);
+
+        MultivariateT::new(5.0, location, scale).unwrap()
+    }
+
+    #[test]
+    fn test_multivariate_t_density() {
+        let x = DVector::from_vec(vec![1.0, 0.0, 1.0]);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(mvt().log_pdf(&x), -4.579_143_533_568_696, 1e-12);
+        assert_approx_equal!(mvt().pdf(&x), 0.010_263_683_033_073_786, 1e-12);
+    }
+
+    #[test]
+    fn test_multivariate_t_marginal() {
+        let marginal = mvt().marginal(&[2, 1]).unwrap();
+
+        assert_eq!(marginal.degrees_of_freedom(), 5.0);
+        assert_eq!(marginal.location().as_slice(), &[2.0, -1.0]);
+        assert_eq!(marginal.scale()[(0, 1)], 0.3);
+
+        assert!(mvt().marginal(&[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_multivariate_t_conditional() {
+        let conditional = mvt()
+            .conditional(&[2, 0], &DVector::from_vec(vec![1.0, 1.0]))
+            .unwrap();
+
+        // Values computed using mpmath.
+        assert_eq!(conditional.degrees_of_freedom(), 7.0);
+        assert_approx_equal!(conditional.location()[0], -1.116_197_183_098_591_5, 1e-12);
+        assert_approx_equal!(conditional.scale()[(0, 0)], 0.566_104_372_715_164_2, 1e-12);
+    }
+
+    #[test]
+    fn test_multivariate_t_moments() {
+        let dist = mvt();
+
+        assert_eq!(dist.mean(), dist.location().clone());
+        assert_approx_equal!(dist.covariance()[(0, 1)], 1.0, 1e-12);
+
+        let dist = MultivariateT::new(1.5, DVector::zeros(2), DMatrix::identity(2, 2)).unwrap();
+        assert!(dist.covariance()[(0, 0)].is_infinite());
+        assert!(dist.covariance()[(0, 1)].is_nan());
+
+        assert!(MultivariateT::new(0.0, DVector::zeros(2), DMatrix::identity(2, 2)).is_err());
+    }
+
+    #[test]
+    fn test_multivariate_t_variate_generator() -> Result<(), RustQuantError> {
+        let dist = mvt();
+        let n = 20_000;
+
+        let v = dist.sample(n)?;
+        let mean = v.iter().sum::<DVector<f64>>() / n as f64;
+
+        for i in 0..3 {
+            assert_approx_equal!(mean[i], dist.location()[i], 0.05);
+        }
+
+        // Correlation of the first two components, from the scale matrix.
+        let (sx, sy, sxy) = v.iter().fold((0.0, 0.0, 0.0), |(sx, sy, sxy), x| {
+            let (a, b) = (x[0] - mean[0], x[1] - mean[1]);
+            (sx + a * a, sy + b * b, sxy + a * b)
+        });
+
+        assert_approx_equal!(sxy / (sx * sy).sqrt(), 0.6 / 2_f64.sqrt(), 0.05);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::distributions::{quantile_cf, quantile_mgf, Distribution};
+use num::Complex;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Pareto (type I) distribution: X ~ Pareto(x_m, alpha)
+///
+/// A power law on [x_m, inf), e.g. for the sizes of large losses.
+/// The moments of order alpha and higher are infinite.
+pub struct Pareto {
+    /// x_m: the scale parameter, which is the minimum of the support.
+    x_m: f64,
+    /// Alpha: the shape parameter, or tail index.
+    alpha: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Pareto {
+    /// New instance of a Pareto distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let pareto = Pareto::new(1.0, 3.0);
+    ///
+    /// assert_approx_equal!(pareto.mean(), 1.5, 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_m` or `alpha` are not positive.
+    #[must_use]
+    pub fn new(x_m: f64, alpha: f64) -> Self {
+        assert!(x_m > 0.0 && alpha > 0.0);
+
+        Self { x_m, alpha }
+    }
+}
+
+impl Distribution for Pareto {
+    /// Characteristic function of the Pareto distribution.
+    /// It is an incomplete gamma function of imaginary argument,
+    /// so it is integrated numerically instead.
+    fn cf(&self, t: f64) -> Complex<f64> {
+        quantile_cf(|p| self.inv_cdf(p), t)
+    }
+
+    /// Probability density function of the Pareto distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let pareto = Pareto::new(1.0, 3.0);
+    ///
+    /// assert_approx_equal!(pareto.pdf(2.0), 0.1875, 1e-12);
+    /// assert_eq!(pareto.pdf(0.5), 0.0);
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.x_m {
+            return 0.0;
+        }
+
+        self.alpha / x * (self.x_m / x).powf(self.alpha)
+    }
+
+    /// Probability mass function of the Pareto distribution.
+    /// Using this method will call `self.pdf(x)` instead.
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Distribution function of the Pareto distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let pareto = Pareto::new(1.0, 3.0);
+    ///
+    /// assert_approx_equal!(pareto.cdf(2.0), 0.875, 1e-12);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.x_m {
+            return 0.0;
+        }
+
+        1.0 - (self.x_m / x).powf(self.alpha)
+    }
+
+    /// Inverse distribution function of the Pareto distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let pareto = Pareto::new(1.0, 3.0);
+    ///
+    /// assert_approx_equal!(pareto.inv_cdf(0.875), 2.0, 1e-12);
+    /// ```
+    fn inv_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+
+        self.x_m * (1.0 - p).powf(-1.0 / self.alpha)
+    }
+
+    /// Mean of the Pareto distribution, which is infinite for `alpha <= 1`.
+    fn mean(&self) -> f64 {
+        if self.alpha > 1.0 {
+            self.alpha * self.x_m / (self.alpha - 1.0)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn median(&self) -> f64 {
+        self.x_m * 2_f64.powf(1.0 / self.alpha)
+    }
+
+    fn mode(&self) -> f64 {
+        self.x_m
+    }
+
+    /// Variance of the Pareto distribution, which is infinite for `alpha <= 2`.
+    fn variance(&self) -> f64 {
+        let a = self.alpha;
+
+        if a > 2.0 {
+            self.x_m.powi(2) * a / ((a - 1.0).powi(2) * (a - 2.0))
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Skewness of the Pareto distribution, which only exists for `alpha > 3`.
+    fn skewness(&self) -> f64 {
+        let a = self.alpha;
+
+        if a > 3.0 {
+            2.0 * (1.0 + a) / (a - 3.0) * ((a - 2.0) / a).sqrt()
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Excess kurtosis of the Pareto distribution,
+    /// which only exists for `alpha > 4`.
+    fn kurtosis(&self) -> f64 {
+        let a = self.alpha;
+
+        if a > 4.0 {
+            6.0 * (a.powi(3) + a.powi(2) - 6.0 * a - 2.0) / (a * (a - 3.0) * (a - 4.0))
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn entropy(&self) -> f64 {
+        (self.x_m / self.alpha).ln() + 1.0 / self.alpha + 1.0
+    }
+
+    /// Moment generating function of the Pareto distribution.
+    /// It is infinite for `t > 0`, and is integrated numerically for `t < 0`.
+    fn mgf(&self, t: f64) -> f64 {
+        match t {
+            t if t > 0.0 => f64::INFINITY,
+            t if t < 0.0 => quantile_mgf(|p| self.inv_cdf(p), t),
+            _ => 1.0,
+        }
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError> {
+        // IMPORT HERE TO AVOID CLASH WITH
+        // `RustQuant::distributions::Distribution`
+        use rand::thread_rng;
+        use rand_distr::{Distribution, Pareto};
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let dist = Pareto::new(self.x_m, self.alpha)?;
+        let mut variates: Vec<f64> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            variates.push(dist.sample(&mut rng));
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_pareto {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_pareto_characteristic_function() {
+        let dist = Pareto::new(1.0, 3.0);
+
+        // Values computed using mpmath.
+        let cf = dist.cf(0.7);
+
+        assert_approx_equal!(cf.re, 0.504_541_624_248_332_2, 1e-6);
+        assert_approx_equal!(cf.im, 0.771_317_377_477_873_2, 1e-6);
+        assert_approx_equal!(dist.mgf(-0.7), 0.380_342_490_278_764_7, 1e-8);
+        assert!(dist.mgf(0.7).is_infinite());
+    }
+
+    #[test]
+    fn test_pareto_density_function() {
+        let dist = Pareto::new(1.0, 3.0);
+
+        assert_approx_equal!(dist.pdf(1.5), 16.0 / 27.0, EPS);
+        assert_approx_equal!(dist.pdf(1.0), 3.0, EPS);
+        assert_eq!(dist.pdf(0.9), 0.0);
+    }
+
+    #[test]
+    fn test_pareto_distribution_function() {
+        let dist = Pareto::new(2.0, 3.0);
+
+        assert_approx_equal!(dist.cdf(3.0), 19.0 / 27.0, EPS);
+        assert_eq!(dist.cdf(1.0), 0.0);
+
+        for p in [0.001, 0.1, 0.5, 0.8, 0.999] {
+            assert_approx_equal!(dist.cdf(dist.inv_cdf(p)), p, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_pareto_moments() {
+        let dist = Pareto::new(2.0, 5.0);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.mean(), 2.5, EPS);
+        assert_approx_equal!(dist.median(), 2.0 * 2_f64.powf(0.2), EPS);
+        assert_approx_equal!(dist.variance(), 20.0 / 48.0, EPS);
+        assert_approx_equal!(dist.skewness(), 4.647_580_015_448_9, 1e-12);
+        assert_approx_equal!(dist.kurtosis(), 70.8, 1e-12);
+        assert_approx_equal!(dist.entropy(), 0.4_f64.ln() + 1.2, EPS);
+
+        // Heavy tails.
+        let dist = Pareto::new(1.0, 1.5);
+        assert!(dist.variance().is_infinite());
+        assert!(dist.skewness().is_nan());
+        assert!(Pareto::new(1.0, 1.0).mean().is_infinite());
+    }
+
+    #[test]
+    fn test_pareto_variate_generator() -> Result<(), RustQuantError> {
+        let dist = Pareto::new(2.0, 5.0);
+
+        let v = dist.sample(1000)?;
+        let mean = v.iter().sum::<f64>() / v.len() as f64;
+
+        assert!(v.iter().all(|&x| x >= 2.0));
+        assert_approx_equal!(mean, 2.5, 0.1);
+
+        Ok(())
+    }
+}
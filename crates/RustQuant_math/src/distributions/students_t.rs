@@ -0,0 +1,330 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::distributions::{quantile_cf, Distribution};
+use num::Complex;
+use statrs::function::beta::{beta_reg, inv_beta_reg, ln_beta};
+use statrs::function::gamma::{digamma, ln_gamma};
+use std::f64::consts::PI;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Student's t distribution: X ~ t(nu, mu, sigma)
+///
+/// The location-scale form, X = mu + sigma * T, where T has the standard
+/// Student's t distribution with nu degrees of freedom. Its tails are
+/// heavier than the Gaussian's, and the moments of order nu and higher
+/// do not exist.
+pub struct StudentT {
+    /// Nu: degrees of freedom.
+    nu: f64,
+    /// Mu: the location parameter.
+    mu: f64,
+    /// Sigma: the scale parameter.
+    sigma: f64,
+}
+
+impl Default for StudentT {
+    // The standard Student's t distribution with one degree of freedom,
+    // which is the Cauchy distribution.
+    fn default() -> Self {
+        Self::new(1.0, 0.0, 1.0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StudentT {
+    /// New instance of a Student's t distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let t = StudentT::new(5.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(t.mean(), 0.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nu` or `sigma` are not positive.
+    #[must_use]
+    pub fn new(nu: f64, mu: f64, sigma: f64) -> Self {
+        assert!(nu > 0.0 && sigma > 0.0);
+
+        Self { nu, mu, sigma }
+    }
+
+    /// Distribution function of the standard Student's t distribution.
+    fn standard_cdf(&self, z: f64) -> f64 {
+        let tail = 0.5 * beta_reg(0.5 * self.nu, 0.5, self.nu / (self.nu + z * z));
+
+        if z < 0.0 {
+            tail
+        } else {
+            1.0 - tail
+        }
+    }
+
+    /// Inverse distribution function of the standard Student's t distribution.
+    fn standard_inv_cdf(&self, p: f64) -> f64 {
+        if p > 0.5 {
+            return -self.standard_inv_cdf(1.0 - p);
+        }
+
+        let x = inv_beta_reg(0.5 * self.nu, 0.5, 2.0 * p);
+
+        -(self.nu * (1.0 - x) / x).sqrt()
+    }
+}
+
+impl Distribution for StudentT {
+    /// Characteristic function of Student's t distribution.
+    /// It has no elementary closed form, so it is integrated numerically,
+    /// which loses accuracy for very heavy tails, i.e. small `nu`.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// // The Cauchy distribution: cf(t) = exp(-|t|)
+    /// let t = StudentT::new(1.0, 0.0, 1.0);
+    /// let cf = t.cf(1.0);
+    ///
+    /// assert_approx_equal!(cf.re, (-1.0_f64).exp(), 1e-3);
+    /// assert_approx_equal!(cf.im, 0.0, 1e-6);
+    /// ```
+    fn cf(&self, t: f64) -> Complex<f64> {
+        quantile_cf(|p| self.inv_cdf(p), t)
+    }
+
+    /// Probability density function of Student's t distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let t = StudentT::new(5.0, 0.0, 1.0);
+    ///
+    /// assert_approx_equal!(t.pdf(1.0), 0.2196798, 1e-7);
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        let nu = self.nu;
+        let z = (x - self.mu) / self.sigma;
+
+        let ln_norm = ln_gamma(0.5 * (nu + 1.0)) - ln_gamma(0.5 * nu) - 0.5 * (nu * PI).ln();
+
+        (ln_norm - 0.5 * (nu + 1.0) * (z * z / nu).ln_1p()).exp() / self.sigma
+    }
+
+    /// Probability mass function of Student's t distribution.
+    /// Using this method will call `self.pdf(x)` instead.
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Distribution function of Student's t distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let t = StudentT::new(5.0, 0.0, 1.0);
+    ///
+    /// assert_approx_equal!(t.cdf(0.0), 0.5, 1e-10);
+    /// assert_approx_equal!(t.cdf(2.0), 0.9490303, 1e-7);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        self.standard_cdf((x - self.mu) / self.sigma)
+    }
+
+    /// Inverse distribution function of Student's t distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let t = StudentT::new(5.0, 0.0, 1.0);
+    ///
+    /// assert_approx_equal!(t.inv_cdf(0.975), 2.5705818, 1e-7);
+    /// ```
+    fn inv_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+
+        self.mu + self.sigma * self.standard_inv_cdf(p)
+    }
+
+    /// Mean of Student's t distribution, which only exists for `nu > 1`.
+    fn mean(&self) -> f64 {
+        if self.nu > 1.0 {
+            self.mu
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn median(&self) -> f64 {
+        self.mu
+    }
+
+    fn mode(&self) -> f64 {
+        self.mu
+    }
+
+    /// Variance of Student's t distribution,
+    /// which is infinite for `1 < nu <= 2` and does not exist for `nu <= 1`.
+    fn variance(&self) -> f64 {
+        match self.nu {
+            nu if nu > 2.0 => self.sigma.powi(2) * nu / (nu - 2.0),
+            nu if nu > 1.0 => f64::INFINITY,
+            _ => f64::NAN,
+        }
+    }
+
+    /// Skewness of Student's t distribution, which only exists for `nu > 3`.
+    fn skewness(&self) -> f64 {
+        if self.nu > 3.0 {
+            0.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Excess kurtosis of Student's t distribution,
+    /// which is infinite for `2 < nu <= 4` and does not exist for `nu <= 2`.
+    fn kurtosis(&self) -> f64 {
+        match self.nu {
+            nu if nu > 4.0 => 6.0 / (nu - 4.0),
+            nu if nu > 2.0 => f64::INFINITY,
+            _ => f64::NAN,
+        }
+    }
+
+    fn entropy(&self) -> f64 {
+        let nu = self.nu;
+
+        0.5 * (nu + 1.0) * (digamma(0.5 * (nu + 1.0)) - digamma(0.5 * nu))
+            + 0.5 * nu.ln()
+            + ln_beta(0.5 * nu, 0.5)
+            + self.sigma.ln()
+    }
+
+    /// The moment generating function of Student's t distribution
+    /// is infinite, except at `t = 0`.
+    fn mgf(&self, t: f64) -> f64 {
+        if t == 0.0 {
+            1.0
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError> {
+        // IMPORT HERE TO AVOID CLASH WITH
+        // `RustQuant::distributions::Distribution`
+        use rand::thread_rng;
+        use rand_distr::{Distribution, StudentT};
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let dist = StudentT::new(self.nu)?;
+        let mut variates: Vec<f64> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            variates.push(self.mu + self.sigma * dist.sample(&mut rng));
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_students_t {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_students_t_characteristic_function() {
+        let dist = StudentT::new(3.0, 0.5, 2.0);
+
+        // For three degrees of freedom, with s = sqrt(3) sigma |t|:
+        // cf(t) = exp(i mu t) (1 + s) exp(-s)
+        let s = 3_f64.sqrt() * 2.0 * 0.3;
+        let cf = dist.cf(0.3);
+        let expected = Complex::new(0.0, 0.5 * 0.3).exp() * (1.0 + s) * (-s).exp();
+
+        assert_approx_equal!(cf.re, expected.re, 1e-6);
+        assert_approx_equal!(cf.im, expected.im, 1e-6);
+    }
+
+    #[test]
+    fn test_students_t_density_function() {
+        let dist = StudentT::new(3.0, 1.0, 2.0);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.pdf(1.0), 0.183_776_298_473_930_7, EPS);
+        assert_approx_equal!(dist.pdf(-2.0), 0.060_008_587_256_793_69, EPS);
+        assert_approx_equal!(dist.pdf(6.0), 0.019_330_742_863_583_65, EPS);
+    }
+
+    #[test]
+    fn test_students_t_distribution_function() {
+        let dist = StudentT::new(3.0, 1.0, 2.0);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.cdf(1.0), 0.5, EPS);
+        assert_approx_equal!(dist.cdf(-2.0), 0.115_291_932_622_411_53, 1e-12);
+        assert_approx_equal!(dist.cdf(6.0), 0.956_146_676_495_967_2, 1e-12);
+
+        for p in [0.001, 0.1, 0.5, 0.8, 0.999] {
+            assert_approx_equal!(dist.cdf(dist.inv_cdf(p)), p, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_students_t_moments() {
+        let dist = StudentT::new(6.0, 1.0, 2.0);
+
+        assert_approx_equal!(dist.mean(), 1.0, EPS);
+        assert_approx_equal!(dist.median(), 1.0, EPS);
+        assert_approx_equal!(dist.variance(), 6.0, EPS);
+        assert_approx_equal!(dist.kurtosis(), 3.0, EPS);
+        assert_approx_equal!(dist.entropy(), 2.284_868_505_725_26, 1e-12);
+
+        // Heavy tails.
+        let dist = StudentT::new(2.0, 0.0, 1.0);
+        assert!(dist.variance().is_infinite());
+        assert!(dist.skewness().is_nan());
+        assert!(StudentT::default().mean().is_nan());
+    }
+
+    #[test]
+    fn test_students_t_variate_generator() -> Result<(), RustQuantError> {
+        let dist = StudentT::new(10.0, 2.0, 0.5);
+
+        let v = dist.sample(1000)?;
+        let mean = v.iter().sum::<f64>() / v.len() as f64;
+
+        assert_approx_equal!(mean, 2.0, 0.1);
+
+        Ok(())
+    }
+}
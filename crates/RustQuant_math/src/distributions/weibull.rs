@@ -0,0 +1,292 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::distributions::{quantile_cf, quantile_mgf, Distribution};
+use num::Complex;
+use statrs::function::gamma::gamma;
+use RustQuant_error::RustQuantError;
+
+/// Euler-Mascheroni constant.
+const EULER_MASCHERONI: f64 = 0.577_215_664_901_532_9;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Weibull distribution: X ~ Weibull(lambda, k)
+///
+/// The distribution of times to failure, or default, whose hazard rate
+/// is increasing for k > 1 and decreasing for k < 1.
+/// For k = 1 it is the exponential distribution with rate 1 / lambda.
+pub struct Weibull {
+    /// Lambda: the scale parameter.
+    lambda: f64,
+    /// K: the shape parameter.
+    k: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Weibull {
+    /// New instance of a Weibull distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// // Exponential distribution with rate 1 / 2.
+    /// let weibull = Weibull::new(2.0, 1.0);
+    ///
+    /// assert_approx_equal!(weibull.mean(), 2.0, 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lambda` or `k` are not positive.
+    #[must_use]
+    pub fn new(lambda: f64, k: f64) -> Self {
+        assert!(lambda > 0.0 && k > 0.0);
+
+        Self { lambda, k }
+    }
+
+    /// Raw moments, E[X^n] = lambda^n Gamma(1 + n / k).
+    fn raw_moment(&self, n: i32) -> f64 {
+        self.lambda.powi(n) * gamma(1.0 + f64::from(n) / self.k)
+    }
+}
+
+impl Distribution for Weibull {
+    /// Characteristic function of the Weibull distribution.
+    /// It has no closed form for general `k`, so it is integrated numerically.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    /// # use num::Complex;
+    ///
+    /// // Exponential distribution: cf(t) = 1 / (1 - i t lambda)
+    /// let weibull = Weibull::new(2.0, 1.0);
+    /// let cf = weibull.cf(0.5);
+    /// let expected = Complex::new(1.0, 0.0) / Complex::new(1.0, -1.0);
+    ///
+    /// assert_approx_equal!(cf.re, expected.re, 1e-6);
+    /// assert_approx_equal!(cf.im, expected.im, 1e-6);
+    /// ```
+    fn cf(&self, t: f64) -> Complex<f64> {
+        quantile_cf(|p| self.inv_cdf(p), t)
+    }
+
+    /// Probability density function of the Weibull distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let weibull = Weibull::new(1.0, 2.0);
+    ///
+    /// assert_approx_equal!(weibull.pdf(1.0), 2.0 * (-1.0_f64).exp(), 1e-12);
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+
+        let z = x / self.lambda;
+
+        self.k / self.lambda * z.powf(self.k - 1.0) * (-z.powf(self.k)).exp()
+    }
+
+    /// Probability mass function of the Weibull distribution.
+    /// Using this method will call `self.pdf(x)` instead.
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Distribution function of the Weibull distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let weibull = Weibull::new(1.0, 2.0);
+    ///
+    /// assert_approx_equal!(weibull.cdf(1.0), 1.0 - (-1.0_f64).exp(), 1e-12);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        -(-(x / self.lambda).powf(self.k)).exp_m1()
+    }
+
+    /// Inverse distribution function of the Weibull distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let weibull = Weibull::new(1.0, 2.0);
+    ///
+    /// assert_approx_equal!(weibull.inv_cdf(1.0 - (-1.0_f64).exp()), 1.0, 1e-12);
+    /// ```
+    fn inv_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+
+        self.lambda * (-(-p).ln_1p()).powf(1.0 / self.k)
+    }
+
+    fn mean(&self) -> f64 {
+        self.raw_moment(1)
+    }
+
+    fn median(&self) -> f64 {
+        self.lambda * std::f64::consts::LN_2.powf(1.0 / self.k)
+    }
+
+    fn mode(&self) -> f64 {
+        if self.k > 1.0 {
+            self.lambda * ((self.k - 1.0) / self.k).powf(1.0 / self.k)
+        } else {
+            0.0
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        self.raw_moment(2) - self.raw_moment(1).powi(2)
+    }
+
+    fn skewness(&self) -> f64 {
+        let mu = self.mean();
+        let var = self.variance();
+
+        (self.raw_moment(3) - 3.0 * mu * var - mu.powi(3)) / var.powf(1.5)
+    }
+
+    fn kurtosis(&self) -> f64 {
+        let mu = self.mean();
+        let var = self.variance();
+
+        let m4 = self.raw_moment(4) - 4.0 * mu * self.raw_moment(3)
+            + 6.0 * mu.powi(2) * self.raw_moment(2)
+            - 3.0 * mu.powi(4);
+
+        m4 / var.powi(2) - 3.0
+    }
+
+    fn entropy(&self) -> f64 {
+        EULER_MASCHERONI * (1.0 - 1.0 / self.k) + (self.lambda / self.k).ln() + 1.0
+    }
+
+    /// Moment generating function of the Weibull distribution.
+    /// It is infinite for `t > 0` when `k < 1`, and for `t >= 1 / lambda`
+    /// when `k = 1`. Elsewhere it is integrated numerically.
+    fn mgf(&self, t: f64) -> f64 {
+        if t > 0.0 && (self.k < 1.0 || (self.k == 1.0 && t * self.lambda >= 1.0)) {
+            return f64::INFINITY;
+        }
+
+        quantile_mgf(|p| self.inv_cdf(p), t)
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError> {
+        // IMPORT HERE TO AVOID CLASH WITH
+        // `RustQuant::distributions::Distribution`
+        use rand::thread_rng;
+        use rand_distr::{Distribution, Weibull};
+
+        assert!(n > 0);
+
+        let mut rng = thread_rng();
+        let dist = Weibull::new(self.lambda, self.k)?;
+        let mut variates: Vec<f64> = Vec::with_capacity(n);
+
+        for _ in 0..variates.capacity() {
+            variates.push(dist.sample(&mut rng));
+        }
+
+        Ok(variates)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_weibull {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON as EPS};
+
+    #[test]
+    fn test_weibull_characteristic_function() {
+        let dist = Weibull::new(2.0, 1.5);
+
+        // Values computed using mpmath.
+        let cf = dist.cf(0.7);
+
+        assert_approx_equal!(cf.re, 0.286_015_125_426_833_9, 1e-6);
+        assert_approx_equal!(cf.im, 0.653_371_671_108_028_8, 1e-6);
+        assert_approx_equal!(dist.mgf(0.7), 5.955_965_587_508_385, 1e-6);
+        assert!(Weibull::new(2.0, 0.5).mgf(0.1).is_infinite());
+    }
+
+    #[test]
+    fn test_weibull_density_function() {
+        let dist = Weibull::new(2.0, 1.5);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.pdf(1.0), 0.372_391_688_219_422, EPS);
+        assert_approx_equal!(dist.pdf(3.0), 0.146_304_264_044_542_3, EPS);
+        assert_eq!(dist.pdf(-1.0), 0.0);
+    }
+
+    #[test]
+    fn test_weibull_distribution_function() {
+        let dist = Weibull::new(2.0, 1.5);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.cdf(1.0), 0.297_811_498_673_440_4, 1e-12);
+        assert_approx_equal!(dist.cdf(3.0), 0.840_724_091_509_978_6, 1e-12);
+
+        for p in [0.001, 0.1, 0.5, 0.8, 0.999] {
+            assert_approx_equal!(dist.cdf(dist.inv_cdf(p)), p, 1e-12);
+        }
+        assert_approx_equal!(dist.cdf(dist.median()), 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_weibull_moments() {
+        let dist = Weibull::new(2.0, 1.5);
+
+        // Values computed using mpmath.
+        assert_approx_equal!(dist.mean(), 1.805_490_585_901_867_2, 1e-12);
+        assert_approx_equal!(dist.variance(), 1.502_761_139_255_728, 1e-12);
+        assert_approx_equal!(dist.skewness(), 1.071_986_572_890_956_3, 1e-10);
+        assert_approx_equal!(dist.kurtosis(), 1.390_403_561_595_788_3, 1e-10);
+        assert_approx_equal!(dist.entropy(), 1.480_087_294_085_625_2, 1e-12);
+    }
+
+    #[test]
+    fn test_weibull_variate_generator() -> Result<(), RustQuantError> {
+        let dist = Weibull::new(2.0, 1.5);
+
+        let v = dist.sample(1000)?;
+        let mean = v.iter().sum::<f64>() / v.len() as f64;
+
+        assert_approx_equal!(mean, dist.mean(), 0.15);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,482 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Adaptive Gauss-Kronrod quadrature.
+//!
+//! The 15 point Kronrod rule extends the 7 point Gauss-Legendre rule, so
+//! each interval gives two estimates of the integral for the price of one,
+//! and their difference estimates the error. The interval with the largest
+//! error is bisected until the total error is within the tolerance.
+//!
+//! Infinite intervals are mapped to $(0, 1]$, with $x = a + (1 - t) / t$.
+//!
+//! Double integrals are computed as iterated integrals, each adaptive.
+//! The inner limits may depend on the outer variable, so a region such as
+//! the exercise region of a spread option can be integrated over directly,
+//! rather than integrating a payoff with a kink.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use std::cell::{Cell, RefCell};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Adaptive Gauss-Kronrod (G7, K15) integrator.
+#[derive(Debug, Clone, Copy)]
+pub struct GaussKronrod {
+    /// Absolute error tolerance.
+    absolute_tolerance: f64,
+    /// Relative error tolerance.
+    relative_tolerance: f64,
+    /// Maximum number of subintervals.
+    max_subintervals: usize,
+}
+
+/// Result of an adaptive quadrature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quadrature {
+    /// Estimate of the integral.
+    pub value: f64,
+    /// Estimate of the absolute error.
+    pub error: f64,
+    /// Number of function evaluations.
+    pub evaluations: usize,
+}
+
+impl Default for GaussKronrod {
+    fn default() -> Self {
+        Self {
+            absolute_tolerance: 1e-10,
+            relative_tolerance: 1e-10,
+            max_subintervals: 1000,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Nodes and weights from QUADPACK (qk15.f).
+
+/// Kronrod nodes on [0, 1], the odd indices are also the Gauss nodes.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.0,
+];
+
+/// Kronrod weights.
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_225,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_18,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_83,
+];
+
+/// Gauss weights of the nodes 1, 3, 5 and 7.
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Kronrod estimate of the integral over [a, b],
+/// and its difference from the Gauss estimate.
+fn kronrod<F>(f: &F, a: f64, b: f64) -> (f64, f64)
+where
+    F: Fn(f64) -> f64,
+{
+    let c = 0.5 * (b - a);
+    let d = 0.5 * (a + b);
+
+    let centre = f(d);
+    let mut kronrod = KRONROD_WEIGHTS[7] * centre;
+    let mut gauss = GAUSS_WEIGHTS[3] * centre;
+
+    for i in 0..7 {
+        let pair = f(d - c * KRONROD_NODES[i]) + f(d + c * KRONROD_NODES[i]);
+
+        kronrod += KRONROD_WEIGHTS[i] * pair;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * pair;
+        }
+    }
+
+    (c * kronrod, (c * (kronrod - gauss)).abs())
+}
+
+/// A subinterval, with its integral and error estimates.
+struct Subinterval {
+    a: f64,
+    b: f64,
+    value: f64,
+    error: f64,
+}
+
+impl GaussKronrod {
+    /// New adaptive Gauss-Kronrod integrator.
+    ///
+    /// # Arguments:
+    /// * `absolute_tolerance` - The absolute error tolerance.
+    /// * `relative_tolerance` - The error tolerance relative to the integral.
+    /// * `max_subintervals` - The maximum number of subintervals.
+    ///
+    /// The integration stops when the error estimate is within either tolerance.
+    #[must_use]
+    pub fn new(absolute_tolerance: f64, relative_tolerance: f64, max_subintervals: usize) -> Self {
+        Self {
+            absolute_tolerance,
+            relative_tolerance,
+            max_subintervals,
+        }
+    }
+
+    /// Integrates a function from `a` to `b`.
+    /// Either limit may be infinite.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// let quadrature = GaussKronrod::default()
+    ///     .integrate(|x| (-x * x).exp(), f64::NEG_INFINITY, f64::INFINITY)
+    ///     .unwrap();
+    ///
+    /// assert_approx_equal!(quadrature.value, std::f64::consts::PI.sqrt(), 1e-10);
+    /// assert!(quadrature.error < 1e-9);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RustQuantError::InvalidArgument` if a limit is NaN.
+    /// - `RustQuantError::ComputationError` if the tolerance is not met
+    ///   within the maximum number of subintervals, or the integrand is
+    ///   not finite.
+    pub fn integrate<F>(&self, f: F, a: f64, b: f64) -> Result<Quadrature, RustQuantError>
+    where
+        F: Fn(f64) -> f64,
+    {
+        if a.is_nan() || b.is_nan() {
+            return Err(RustQuantError::InvalidArgument(
+                "Integration limits must not be NaN.".to_string(),
+            ));
+        }
+
+        if a == b {
+            return Ok(Quadrature {
+                value: 0.0,
+                error: 0.0,
+                evaluations: 0,
+            });
+        }
+
+        if a > b {
+            let quadrature = self.integrate(f, b, a)?;
+            return Ok(Quadrature {
+                value: -quadrature.value,
+                ..quadrature
+            });
+        }
+
+        match (a.is_finite(), b.is_finite()) {
+            (true, true) => self.adapt(&f, a, b),
+            (true, false) => self.adapt(&|t: f64| f(a + (1.0 - t) / t) / (t * t), 0.0, 1.0),
+            (false, true) => self.adapt(&|t: f64| f(b - (1.0 - t) / t) / (t * t), 0.0, 1.0),
+            (false, false) => self.adapt(
+                &|t: f64| {
+                    let x = (1.0 - t) / t;
+                    (f(x) + f(-x)) / (t * t)
+                },
+                0.0,
+                1.0,
+            ),
+        }
+    }
+
+    /// Integrates a function of two variables over the region
+    /// $a \le x \le b$, $c(x) \le y \le d(x)$, as the iterated integral
+    ///
+    /// $$
+    /// \int_{a}^{b} \left( \int_{c(x)}^{d(x)} f(x, y) dy \right) dx
+    /// $$
+    ///
+    /// where `x_limits` is $(a, b)$ and `y_limits` maps $x$ to $(c(x), d(x))$.
+    /// Any of the limits may be infinite. Each inner integral is computed
+    /// to the same tolerance as the outer one; the reported error is the
+    /// error estimate of the outer integral.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// // Bivariate standard normal density with correlation 0.5.
+    /// let rho: f64 = 0.5;
+    /// let density = |x: f64, y: f64| {
+    ///     let q = (x * x - 2.0 * rho * x * y + y * y) / (1.0 - rho * rho);
+    ///     (-0.5 * q).exp() / (2.0 * std::f64::consts::PI * (1.0 - rho * rho).sqrt())
+    /// };
+    ///
+    /// // P(X < 0, Y < 0) = 1 / 4 + asin(rho) / (2 pi) = 1 / 3
+    /// let quadrature = GaussKronrod::default()
+    ///     .integrate_2d(density, (f64::NEG_INFINITY, 0.0), |_| (f64::NEG_INFINITY, 0.0))
+    ///     .unwrap();
+    ///
+    /// assert_approx_equal!(quadrature.value, 1.0 / 3.0, 1e-9);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As for [`GaussKronrod::integrate`], for the outer integral or any
+    /// of the inner integrals.
+    pub fn integrate_2d<F, L>(
+        &self,
+        f: F,
+        x_limits: (f64, f64),
+        y_limits: L,
+    ) -> Result<Quadrature, RustQuantError>
+    where
+        F: Fn(f64, f64) -> f64,
+        L: Fn(f64) -> (f64, f64),
+    {
+        // The outer integrand can only return a number, so the first inner
+        // failure is kept aside, and NaN stops the outer integration.
+        let failure = RefCell::new(None);
+        let evaluations = Cell::new(0);
+
+        let inner = |x: f64| {
+            let (c, d) = y_limits(x);
+
+            match self.integrate(|y| f(x, y), c, d) {
+                Ok(quadrature) => {
+                    evaluations.set(evaluations.get() + quadrature.evaluations);
+                    quadrature.value
+                }
+                Err(error) => {
+                    failure.borrow_mut().get_or_insert(error);
+                    f64::NAN
+                }
+            }
+        };
+
+        let outer = self.integrate(inner, x_limits.0, x_limits.1);
+
+        if let Some(error) = failure.into_inner() {
+            return Err(error);
+        }
+
+        outer.map(|quadrature| Quadrature {
+            evaluations: evaluations.get(),
+            ..quadrature
+        })
+    }
+
+    /// Bisects the subinterval with the largest error, until the total
+    /// error is within the tolerance.
+    fn adapt<F>(&self, f: &F, a: f64, b: f64) -> Result<Quadrature, RustQuantError>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (value, error) = kronrod(f, a, b);
+        let mut intervals = vec![Subinterval { a, b, value, error }];
+        let mut evaluations = 15;
+
+        loop {
+            let value: f64 = intervals.iter().map(|s| s.value).sum();
+            let error: f64 = intervals.iter().map(|s| s.error).sum();
+
+            if !value.is_finite() || !error.is_finite() {
+                return Err(RustQuantError::ComputationError(
+                    "The integrand is not finite.".to_string(),
+                ));
+            }
+
+            let tolerance = self
+                .absolute_tolerance
+                .max(self.relative_tolerance * value.abs());
+
+            if error <= tolerance {
+                return Ok(Quadrature {
+                    value,
+                    error,
+                    evaluations,
+                });
+            }
+
+            if intervals.len() >= self.max_subintervals {
+                return Err(RustQuantError::ComputationError(format!(
+                    "Tolerance not met in {} subintervals: integral {value}, error {error}.",
+                    intervals.len()
+                )));
+            }
+
+            let worst = intervals
+                .iter()
+                .enumerate()
+                .max_by(|x, y| x.1.error.total_cmp(&y.1.error))
+                .map(|(i, _)| i)
+                .unwrap_or_default();
+
+            let Subinterval { a, b, .. } = intervals.swap_remove(worst);
+            let m = 0.5 * (a + b);
+
+            for (a, b) in [(a, m), (m, b)] {
+                let (value, error) = kronrod(f, a, b);
+                intervals.push(Subinterval { a, b, value, error });
+            }
+
+            evaluations += 30;
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_gauss_kronrod {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_quadrature() {
+        let quadrature = GaussKronrod::default()
+            .integrate(|x| x.sin().exp(), 0.0, 5.0)
+            .unwrap();
+
+        assert_approx_equal!(quadrature.value, 7.189_119_253_631_281, 1e-10);
+        assert!(quadrature.error <= 1e-9);
+
+        // Reversed limits.
+        let quadrature = GaussKronrod::default()
+            .integrate(|x| x.sin().exp(), 5.0, 0.0)
+            .unwrap();
+
+        assert_approx_equal!(quadrature.value, -7.189_119_253_631_281, 1e-10);
+    }
+
+    #[test]
+    fn test_singularity() {
+        // Integrable singularity at 0: the adaptive rule refines towards it.
+        let quadrature = GaussKronrod::default()
+            .integrate(|x| 1.0 / x.sqrt(), 0.0, 1.0)
+            .unwrap();
+
+        assert_approx_equal!(quadrature.value, 2.0, 1e-9);
+        assert!(quadrature.evaluations > 15);
+    }
+
+    #[test]
+    fn test_infinite_limits() {
+        let gk = GaussKronrod::new(1e-12, 1e-12, 1000);
+
+        let quadrature = gk.integrate(|x| (-x).exp(), 0.0, f64::INFINITY).unwrap();
+        assert_approx_equal!(quadrature.value, 1.0, 1e-11);
+
+        let quadrature = gk
+            .integrate(|x| 1.0 / (1.0 + x * x), f64::NEG_INFINITY, 1.0)
+            .unwrap();
+        assert_approx_equal!(quadrature.value, 0.75 * std::f64::consts::PI, 1e-11);
+    }
+
+    #[test]
+    fn test_heston_style_integrand() {
+        // Oscillating integrand on [0, inf): the integral of sin(x) / x is pi / 2,
+        // but here damped as in transform pricing: exp(-x / 4) sin(x) / x.
+        let quadrature = GaussKronrod::default()
+            .integrate(|x| (-0.25 * x).exp() * x.sin() / x, 0.0, f64::INFINITY)
+            .unwrap();
+
+        // atan(1 / (1 / 4)) = atan(4)
+        assert_approx_equal!(quadrature.value, 4.0_f64.atan(), 1e-9);
+    }
+
+    #[test]
+    fn test_errors() {
+        // Not enough subintervals.
+        let result = GaussKronrod::new(1e-14, 0.0, 2).integrate(|x| 1.0 / x.sqrt(), 0.0, 1.0);
+        assert!(matches!(result, Err(RustQuantError::ComputationError(_))));
+
+        let result = GaussKronrod::default().integrate(|x| x, f64::NAN, 1.0);
+        assert!(matches!(result, Err(RustQuantError::InvalidArgument(_))));
+
+        // Inner integral failures are passed through.
+        let result =
+            GaussKronrod::default().integrate_2d(|x, y| x * y, (0.0, 1.0), |_| (f64::NAN, 1.0));
+        assert!(matches!(result, Err(RustQuantError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_integrate_2d() {
+        let quadrature = GaussKronrod::default()
+            .integrate_2d(|x, y| x * y * y, (0.0, 2.0), |_| (-1.0, 2.0))
+            .unwrap();
+
+        assert_approx_equal!(quadrature.value, 6.0, 1e-12);
+        assert!(quadrature.evaluations >= 15 * 15);
+
+        // Triangle 0 <= y <= x <= 1.
+        let quadrature = GaussKronrod::default()
+            .integrate_2d(|x, y| x + y, (0.0, 1.0), |x| (0.0, x))
+            .unwrap();
+
+        assert_approx_equal!(quadrature.value, 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_exchange_option() {
+        // Margrabe exchange option, max(S1 - S2, 0), by integrating over the
+        // exercise region of the two standard normal drivers, z2 < boundary(z1).
+        let (s1, s2) = (100.0_f64, 95.0_f64);
+        let (sigma1, sigma2, rho) = (0.2_f64, 0.3_f64, 0.5_f64);
+        let rho_bar = (1.0 - rho * rho).sqrt();
+
+        let integrand = |z1: f64, z2: f64| {
+            let q = 0.5 * (z1 * z1 + z2 * z2);
+            let a1 = sigma1 * z1 - 0.5 * sigma1 * sigma1 - q;
+            let a2 = sigma2 * (rho * z1 + rho_bar * z2) - 0.5 * sigma2 * sigma2 - q;
+
+            (s1 * a1.exp() - s2 * a2.exp()) / (2.0 * std::f64::consts::PI)
+        };
+
+        let boundary = |z1: f64| {
+            let log_moneyness = (s1 / s2).ln() + sigma1 * z1 - 0.5 * sigma1 * sigma1;
+            let z2 =
+                (log_moneyness + 0.5 * sigma2 * sigma2 - sigma2 * rho * z1) / (sigma2 * rho_bar);
+
+            (f64::NEG_INFINITY, z2)
+        };
+
+        let quadrature = GaussKronrod::default()
+            .integrate_2d(integrand, (f64::NEG_INFINITY, f64::INFINITY), boundary)
+            .unwrap();
+
+        assert_approx_equal!(quadrature.value, 12.952_272_612_274_53, 1e-8);
+    }
+}
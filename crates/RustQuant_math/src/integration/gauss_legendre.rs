@@ -0,0 +1,275 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Gauss-Legendre quadrature.
+//!
+//! The $n$ point rule
+//!
+//! $$
+//! \int_{-1}^{+1} f(x) dx \approx \sum_{i=1}^n w_i f(x_i)
+//! $$
+//!
+//! is exact for polynomials of degree $2n - 1$. The nodes $x_i$ are the
+//! roots of the Legendre polynomial $P_n$, and the weights are
+//!
+//! $$
+//! w_i = \frac{2}{(1 - x_i^2) P_n'(x_i)^2}
+//! $$
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Gauss-Legendre quadrature rule of a fixed order.
+#[derive(Debug, Clone)]
+pub struct GaussLegendre {
+    /// Nodes on [-1, +1], in increasing order.
+    nodes: Vec<f64>,
+    /// Weights of the nodes.
+    weights: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Legendre polynomial P_n(x) and its derivative, from the recurrence
+/// (k + 1) P_{k+1}(x) = (2k + 1) x P_k(x) - k P_{k-1}(x).
+fn legendre(n: usize, x: f64) -> (f64, f64) {
+    let (mut p0, mut p1) = (1.0, x);
+
+    for k in 1..n {
+        let k = k as f64;
+        (p0, p1) = (p1, ((2.0 * k + 1.0) * x * p1 - k * p0) / (k + 1.0));
+    }
+
+    let n = n as f64;
+
+    (p1, n * (x * p1 - p0) / (x * x - 1.0))
+}
+
+impl GaussLegendre {
+    /// New Gauss-Legendre rule with `order` nodes.
+    ///
+    /// The nodes are found by Newton's method, starting from the
+    /// asymptotic approximation $x_i \approx \cos(\pi (i - 1/4) / (n + 1/2))$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is zero.
+    #[must_use]
+    pub fn new(order: usize) -> Self {
+        assert!(order > 0, "Gauss-Legendre rules need at least one node.");
+
+        if order == 1 {
+            return Self {
+                nodes: vec![0.0],
+                weights: vec![2.0],
+            };
+        }
+
+        let n = order;
+        let mut nodes = vec![0.0; n];
+        let mut weights = vec![0.0; n];
+
+        // The nodes are symmetric, so only the positive half is computed.
+        for i in 0..n.div_ceil(2) {
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+            let mut dp = 1.0;
+
+            for _ in 0..100 {
+                let (p, derivative) = legendre(n, x);
+                let step = p / derivative;
+
+                x -= step;
+                dp = derivative;
+
+                if step.abs() <= f64::EPSILON {
+                    break;
+                }
+            }
+
+            let w = 2.0 / ((1.0 - x * x) * dp * dp);
+
+            nodes[i] = -x;
+            nodes[n - 1 - i] = x;
+            weights[i] = w;
+            weights[n - 1 - i] = w;
+        }
+
+        Self { nodes, weights }
+    }
+
+    /// Number of nodes.
+    #[must_use]
+    pub fn order(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Nodes on [-1, +1], in increasing order.
+    #[must_use]
+    pub fn nodes(&self) -> &[f64] {
+        &self.nodes
+    }
+
+    /// Weights of the nodes.
+    #[must_use]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Integrates a function from `a` to `b`.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// let rule = GaussLegendre::new(10);
+    ///
+    /// // Exact for polynomials up to degree 19.
+    /// assert_approx_equal!(rule.integrate(|x| x.powi(19) + 1.0, 0.0, 1.0), 1.05, 1e-14);
+    /// assert_approx_equal!(rule.integrate(f64::sin, 0.0, std::f64::consts::PI), 2.0, 1e-14);
+    /// ```
+    pub fn integrate<F>(&self, f: F, a: f64, b: f64) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        let c = 0.5 * (b - a);
+        let d = 0.5 * (a + b);
+
+        c * self
+            .nodes
+            .iter()
+            .zip(&self.weights)
+            .map(|(x, w)| w * f(c * x + d))
+            .sum::<f64>()
+    }
+
+    /// Integrates a function of two variables over the rectangle
+    /// `x_limits` x `y_limits`, with the tensor product of the rule
+    /// with itself ($n^2$ evaluations).
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// let rule = GaussLegendre::new(10);
+    ///
+    /// let integral = rule.integrate_2d(|x, y| (x + y).exp(), (0.0, 1.0), (0.0, 2.0));
+    ///
+    /// let e = std::f64::consts::E;
+    /// assert_approx_equal!(integral, (e - 1.0) * (e * e - 1.0), 1e-12);
+    /// ```
+    pub fn integrate_2d<F>(&self, f: F, x_limits: (f64, f64), y_limits: (f64, f64)) -> f64
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        let (a, b) = x_limits;
+        let (c, d) = y_limits;
+
+        self.integrate(|x| self.integrate(|y| f(x, y), c, d), a, b)
+    }
+
+    /// Integrates a function from `a` to `b`, applying the rule on each of
+    /// `panels` equal subintervals, for integrands that are only piecewise
+    /// well approximated by polynomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `panels` is zero.
+    pub fn integrate_composite<F>(&self, f: F, a: f64, b: f64, panels: usize) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        assert!(panels > 0);
+
+        let h = (b - a) / panels as f64;
+
+        (0..panels)
+            .map(|i| {
+                let left = a + i as f64 * h;
+                self.integrate(&f, left, left + h)
+            })
+            .sum()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_gauss_legendre {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_nodes_and_weights() {
+        // Three point rule: nodes 0, +/- sqrt(3/5), weights 8/9, 5/9.
+        let rule = GaussLegendre::new(3);
+
+        assert_approx_equal!(rule.nodes()[0], -(0.6_f64).sqrt(), 1e-15);
+        assert_approx_equal!(rule.nodes()[1], 0.0, 1e-15);
+        assert_approx_equal!(rule.weights()[0], 5.0 / 9.0, 1e-15);
+        assert_approx_equal!(rule.weights()[1], 8.0 / 9.0, 1e-15);
+
+        for n in [1, 2, 7, 20, 64] {
+            let rule = GaussLegendre::new(n);
+
+            assert_eq!(rule.order(), n);
+            assert_approx_equal!(rule.weights().iter().sum::<f64>(), 2.0, 1e-13);
+            assert!(rule.nodes().windows(2).all(|x| x[0] < x[1]));
+        }
+    }
+
+    #[test]
+    fn test_polynomial_exactness() {
+        let rule = GaussLegendre::new(5);
+
+        // Degree 9 is exact, degree 10 is not.
+        let exact = |k: i32| (2.0_f64.powi(k + 1) - 1.0) / f64::from(k + 1);
+
+        assert_approx_equal!(rule.integrate(|x| x.powi(9), 1.0, 2.0), exact(9), 1e-12);
+        assert!((rule.integrate(|x| x.powi(10), 1.0, 2.0) - exact(10)).abs() > 1e-9);
+    }
+
+    #[test]
+    fn test_quadrature() {
+        let rule = GaussLegendre::new(32);
+
+        let integral = rule.integrate(|x| x.sin().exp(), 0.0, 5.0);
+        assert_approx_equal!(integral, 7.189_119_253_631_281, 1e-12);
+
+        let integral = rule.integrate_composite(|x| x.sin().exp(), 0.0, 5.0, 4);
+        assert_approx_equal!(integral, 7.189_119_253_631_281, 1e-12);
+
+        // A kink at x = 1 needs panels with an endpoint there.
+        let integral = GaussLegendre::new(4).integrate_composite(|x| (x - 1.0).abs(), 0.0, 3.0, 3);
+        assert_approx_equal!(integral, 2.5, 1e-12);
+    }
+
+    #[test]
+    fn test_integrate_2d() {
+        let rule = GaussLegendre::new(3);
+
+        // Exact for degree 5 in each variable.
+        let integral = rule.integrate_2d(|x, y| x.powi(5) * y * y, (0.0, 1.0), (-1.0, 2.0));
+        assert_approx_equal!(integral, 0.5, 1e-14);
+
+        // Bivariate normal probability P(X < 0, Y < 0) with correlation 0.5 is 1 / 3.
+        let rho: f64 = 0.5;
+        let density = |x: f64, y: f64| {
+            let q = (x * x - 2.0 * rho * x * y + y * y) / (1.0 - rho * rho);
+            (-0.5 * q).exp() / (2.0 * std::f64::consts::PI * (1.0 - rho * rho).sqrt())
+        };
+
+        let integral = GaussLegendre::new(64).integrate_2d(density, (-9.0, 0.0), (-9.0, 0.0));
+        assert_approx_equal!(integral, 1.0 / 3.0, 1e-12);
+    }
+}
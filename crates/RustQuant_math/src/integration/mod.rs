@@ -0,0 +1,39 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Numerical integration routines.
+//!
+//! - [`integrate`]: Tanh-Sinh (double exponential) quadrature, a good
+//!   default for smooth integrands, including endpoint singularities.
+//! - [`GaussLegendre`]: fixed order rule on a finite interval. The nodes
+//!   are computed once and reused, e.g. across strikes in transform pricing.
+//! - [`GaussHermite`]: fixed order rule for Gaussian weighted integrals,
+//!   i.e. expectations of functions of normal random variables.
+//! - [`GaussKronrod`]: adaptive quadrature to a requested tolerance, with
+//!   an error estimate, on finite or infinite intervals.
+//!
+//! The fixed order rules and [`GaussKronrod`] also have two-dimensional
+//! versions (`integrate_2d`, `expectation_2d`), e.g. for pricing products
+//! on two assets against their joint density.
+
+/// Tanh-Sinh (double exponential) quadrature.
+pub mod tanh_sinh;
+pub use tanh_sinh::*;
+
+/// Gauss-Legendre quadrature.
+pub mod gauss_legendre;
+pub use gauss_legendre::*;
+
+/// Gauss-Hermite quadrature.
+pub mod gauss_hermite;
+pub use gauss_hermite::*;
+
+/// Adaptive Gauss-Kronrod quadrature.
+pub mod gauss_kronrod;
+pub use gauss_kronrod::*;
@@ -0,0 +1,258 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module containing functionality for interpolation.
+
+use crate::interpolation::{InterpolationIndex, InterpolationValue, Interpolator};
+use num::Float;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Akima interpolator (Akima, 1970): a piecewise cubic, continuously
+/// differentiable, whose slope at each node is a weighted average of the
+/// neighbouring secant slopes. Each piece only depends on the nearby
+/// points, so an outlier does not cause oscillations along the whole curve
+/// as it does with a cubic spline.
+pub struct AkimaInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    /// X-axis values for the interpolator.
+    pub xs: Vec<IndexType>,
+
+    /// Y-axis values for the interpolator.
+    pub ys: Vec<ValueType>,
+
+    /// Whether the interpolator has been fitted.
+    pub fitted: bool,
+
+    /// First derivatives at the nodes, with the x-axis rescaled to [0, 1].
+    derivatives: Vec<ValueType>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, FUNCTIONS, AND MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<IndexType, ValueType> AkimaInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue + Float,
+{
+    /// Create a new AkimaInterpolator.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if ```xs.length() != ys.length()```.
+    ///
+    /// # Panics
+    /// Panics if NaN is in the index.
+    pub fn new(
+        xs: Vec<IndexType>,
+        ys: Vec<ValueType>,
+    ) -> Result<AkimaInterpolator<IndexType, ValueType>, RustQuantError> {
+        if xs.len() != ys.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let mut tmp: Vec<_> = xs.into_iter().zip(ys).collect();
+
+        tmp.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let (xs, ys): (Vec<IndexType>, Vec<ValueType>) = tmp.into_iter().unzip();
+
+        Ok(Self {
+            xs,
+            ys,
+            fitted: false,
+            derivatives: Vec::new(),
+        })
+    }
+
+    /// Position of a point with the x-axis rescaled to [0, 1].
+    fn position(&self, point: IndexType) -> ValueType {
+        let (first, last) = self.range();
+
+        (point - first) / (last - first)
+    }
+}
+
+impl<IndexType, ValueType> Interpolator<IndexType, ValueType>
+    for AkimaInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue + Float,
+{
+    fn fit(&mut self) -> Result<(), RustQuantError> {
+        let n = self.xs.len();
+
+        if n < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "An Akima interpolator needs at least two points.".to_string(),
+            ));
+        }
+
+        let two = ValueType::from(2.0).unwrap();
+        let u: Vec<ValueType> = self.xs.iter().map(|x| self.position(*x)).collect();
+
+        // Secant slopes, extended by two on each side by linear extrapolation:
+        // m[k + 2] is the slope of the k-th interval.
+        let mut m = vec![ValueType::zero(); n + 3];
+
+        for i in 0..n - 1 {
+            m[i + 2] = (self.ys[i + 1] - self.ys[i]) / (u[i + 1] - u[i]);
+        }
+
+        m[1] = two * m[2] - m[3.min(n)];
+        m[0] = two * m[1] - m[2];
+        m[n + 1] = two * m[n] - m[n - 1];
+        m[n + 2] = two * m[n + 1] - m[n];
+
+        let derivatives = (0..n)
+            .map(|i| {
+                let w_left = (m[i + 3] - m[i + 2]).abs();
+                let w_right = (m[i + 1] - m[i]).abs();
+
+                if w_left + w_right > ValueType::zero() {
+                    (w_left * m[i + 1] + w_right * m[i + 2]) / (w_left + w_right)
+                } else {
+                    (m[i + 1] + m[i + 2]) / two
+                }
+            })
+            .collect();
+
+        self.derivatives = derivatives;
+        self.fitted = true;
+
+        Ok(())
+    }
+
+    fn range(&self) -> (IndexType, IndexType) {
+        (*self.xs.first().unwrap(), *self.xs.last().unwrap())
+    }
+
+    fn add_point(&mut self, point: (IndexType, ValueType)) {
+        let idx = self.xs.partition_point(|&x| x < point.0);
+
+        self.xs.insert(idx, point.0);
+        self.ys.insert(idx, point.1);
+        self.fitted = false;
+    }
+
+    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        if !self.fitted {
+            return Err(RustQuantError::Unfitted);
+        }
+
+        let range = self.range();
+
+        if point < range.0 || point > range.1 {
+            return Err(RustQuantError::OutsideOfRange);
+        }
+
+        let idx_r = self.xs.partition_point(|&x| x < point).max(1);
+        let idx_l = idx_r - 1;
+
+        let (u_l, u_r) = (self.position(self.xs[idx_l]), self.position(self.xs[idx_r]));
+        let h = u_r - u_l;
+        let t = (self.position(point) - u_l) / h;
+
+        // Cubic Hermite basis.
+        let (two, three) = (ValueType::from(2.0).unwrap(), ValueType::from(3.0).unwrap());
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        let h00 = two * t3 - three * t2 + ValueType::one();
+        let h10 = t3 - two * t2 + t;
+        let h01 = three * t2 - two * t3;
+        let h11 = t3 - t2;
+
+        Ok(h00 * self.ys[idx_l]
+            + h10 * h * self.derivatives[idx_l]
+            + h01 * self.ys[idx_r]
+            + h11 * h * self.derivatives[idx_r])
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Unit tests
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_akima_interpolation {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_akima_nodes_and_lines() {
+        // A straight line is reproduced exactly.
+        let xs = vec![0.0, 1.0, 2.5, 4.0];
+        let ys = vec![1.0, 3.0, 6.0, 9.0];
+
+        let mut interpolator = AkimaInterpolator::new(xs, ys).unwrap();
+        assert!(interpolator.interpolate(1.0).is_err());
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(
+            interpolator.interpolate(2.5).unwrap(),
+            6.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(interpolator.interpolate(3.2).unwrap(), 7.4, 1e-12);
+        assert!(interpolator.interpolate(4.5).is_err());
+
+        // Two points give a straight line too.
+        let mut interpolator = AkimaInterpolator::new(vec![0.0, 2.0], vec![1.0, 2.0]).unwrap();
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(interpolator.interpolate(0.5).unwrap(), 1.25, 1e-12);
+    }
+
+    #[test]
+    fn test_akima_no_overshoot() {
+        // A step: flat on both sides, with no overshoot, unlike a cubic spline.
+        let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+
+        let mut interpolator = AkimaInterpolator::new(xs, ys).unwrap();
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(interpolator.interpolate(0.5).unwrap(), 0.0, 1e-12);
+        assert_approx_equal!(interpolator.interpolate(1.7).unwrap(), 0.0, 1e-12);
+        assert_approx_equal!(interpolator.interpolate(4.2).unwrap(), 1.0, 1e-12);
+
+        // Zero slopes at both ends of the step: 3t^2 - 2t^3.
+        assert_approx_equal!(interpolator.interpolate(2.25).unwrap(), 0.15625, 1e-12);
+        assert_approx_equal!(interpolator.interpolate(2.5).unwrap(), 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_akima_dates() {
+        let dates = vec![
+            date!(2024 - 01 - 01),
+            date!(2024 - 07 - 01),
+            date!(2025 - 01 - 01),
+            date!(2026 - 01 - 01),
+        ];
+        let rates = vec![0.05, 0.048, 0.045, 0.042];
+
+        let mut interpolator = AkimaInterpolator::new(dates, rates).unwrap();
+        interpolator.fit().unwrap();
+
+        let rate = interpolator.interpolate(date!(2024 - 10 - 01)).unwrap();
+        assert!(rate < 0.048 && rate > 0.045);
+
+        interpolator.add_point((date!(2025 - 06 - 01), 0.044));
+        assert!(!interpolator.fitted);
+    }
+}
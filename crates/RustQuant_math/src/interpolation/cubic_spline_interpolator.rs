@@ -0,0 +1,324 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module containing functionality for interpolation.
+
+use crate::interpolation::{InterpolationIndex, InterpolationValue, Interpolator};
+use num::Float;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Cubic spline interpolator: twice continuously differentiable.
+///
+/// By default the spline is natural, with zero second derivative at both
+/// ends. A clamped spline, with given first derivatives at both ends, is
+/// made with [`CubicSplineInterpolator::with_end_slopes`].
+pub struct CubicSplineInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    /// X-axis values for the interpolator.
+    pub xs: Vec<IndexType>,
+
+    /// Y-axis values for the interpolator.
+    pub ys: Vec<ValueType>,
+
+    /// Whether the interpolator has been fitted.
+    pub fitted: bool,
+
+    /// Second derivatives at the nodes, with the x-axis rescaled to [0, 1].
+    second_derivatives: Vec<ValueType>,
+
+    /// First derivatives at the ends for a clamped spline, with the x-axis
+    /// rescaled to [0, 1], and the range used for the rescaling.
+    end_slopes: Option<(ValueType, ValueType, (IndexType, IndexType))>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, FUNCTIONS, AND MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<IndexType, ValueType> CubicSplineInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue + Float,
+{
+    /// Create a new CubicSplineInterpolator.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if ```xs.length() != ys.length()```.
+    ///
+    /// # Panics
+    /// Panics if NaN is in the index.
+    pub fn new(
+        xs: Vec<IndexType>,
+        ys: Vec<ValueType>,
+    ) -> Result<CubicSplineInterpolator<IndexType, ValueType>, RustQuantError> {
+        if xs.len() != ys.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let mut tmp: Vec<_> = xs.into_iter().zip(ys).collect();
+
+        tmp.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let (xs, ys): (Vec<IndexType>, Vec<ValueType>) = tmp.into_iter().unzip();
+
+        Ok(Self {
+            xs,
+            ys,
+            fitted: false,
+            second_derivatives: Vec::new(),
+            end_slopes: None,
+        })
+    }
+
+    /// Position of a point with the x-axis rescaled to [0, 1].
+    fn position(&self, point: IndexType) -> ValueType {
+        let (first, last) = self.range();
+
+        (point - first) / (last - first)
+    }
+}
+
+impl<IndexType, ValueType> CubicSplineInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<Delta = ValueType, DeltaDiv = ValueType>,
+    ValueType: InterpolationValue + Float,
+{
+    /// Clamp the spline, with first derivatives `left` and `right`
+    /// at the first and last points, in place of the natural end conditions.
+    ///
+    /// # Panics
+    /// Panics if there are no points.
+    #[must_use]
+    pub fn with_end_slopes(mut self, left: ValueType, right: ValueType) -> Self {
+        let range = self.range();
+        let width = range.1 - range.0;
+
+        self.end_slopes = Some((left * width, right * width, range));
+        self.fitted = false;
+        self
+    }
+}
+
+impl<IndexType, ValueType> Interpolator<IndexType, ValueType>
+    for CubicSplineInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue + Float,
+{
+    fn fit(&mut self) -> Result<(), RustQuantError> {
+        let n = self.xs.len();
+
+        if n < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "A cubic spline needs at least two points.".to_string(),
+            ));
+        }
+
+        let (two, six) = (ValueType::from(2.0).unwrap(), ValueType::from(6.0).unwrap());
+        let u: Vec<ValueType> = self.xs.iter().map(|x| self.position(*x)).collect();
+        let h: Vec<ValueType> = u.windows(2).map(|w| w[1] - w[0]).collect();
+        let slopes: Vec<ValueType> = (0..n - 1)
+            .map(|i| (self.ys[i + 1] - self.ys[i]) / h[i])
+            .collect();
+
+        // Tridiagonal system for the second derivatives: row i is
+        // lower[i] M[i-1] + diagonal[i] M[i] + upper[i] M[i+1] = rhs[i].
+        let mut lower = vec![ValueType::zero(); n];
+        let mut diagonal = vec![ValueType::one(); n];
+        let mut upper = vec![ValueType::zero(); n];
+        let mut rhs = vec![ValueType::zero(); n];
+
+        for i in 1..n - 1 {
+            lower[i] = h[i - 1];
+            diagonal[i] = two * (h[i - 1] + h[i]);
+            upper[i] = h[i];
+            rhs[i] = six * (slopes[i] - slopes[i - 1]);
+        }
+
+        if let Some((left, right, (first, last))) = self.end_slopes {
+            // The end slopes were rescaled with the range at the time,
+            // which may have grown since.
+            let (start, end) = self.range();
+            let scale = (end - start) / (last - first);
+
+            diagonal[0] = two * h[0];
+            upper[0] = h[0];
+            rhs[0] = six * (slopes[0] - left * scale);
+
+            lower[n - 1] = h[n - 2];
+            diagonal[n - 1] = two * h[n - 2];
+            rhs[n - 1] = six * (right * scale - slopes[n - 2]);
+        }
+
+        // Thomas algorithm.
+        for i in 1..n {
+            let w = lower[i] / diagonal[i - 1];
+            diagonal[i] = diagonal[i] - w * upper[i - 1];
+            rhs[i] = rhs[i] - w * rhs[i - 1];
+        }
+
+        let mut second_derivatives = vec![ValueType::zero(); n];
+        second_derivatives[n - 1] = rhs[n - 1] / diagonal[n - 1];
+
+        for i in (0..n - 1).rev() {
+            second_derivatives[i] = (rhs[i] - upper[i] * second_derivatives[i + 1]) / diagonal[i];
+        }
+
+        self.second_derivatives = second_derivatives;
+        self.fitted = true;
+
+        Ok(())
+    }
+
+    fn range(&self) -> (IndexType, IndexType) {
+        (*self.xs.first().unwrap(), *self.xs.last().unwrap())
+    }
+
+    fn add_point(&mut self, point: (IndexType, ValueType)) {
+        let idx = self.xs.partition_point(|&x| x < point.0);
+
+        self.xs.insert(idx, point.0);
+        self.ys.insert(idx, point.1);
+        self.fitted = false;
+    }
+
+    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        if !self.fitted {
+            return Err(RustQuantError::Unfitted);
+        }
+
+        let range = self.range();
+
+        if point < range.0 || point > range.1 {
+            return Err(RustQuantError::OutsideOfRange);
+        }
+
+        let idx_r = self.xs.partition_point(|&x| x < point).max(1);
+        let idx_l = idx_r - 1;
+
+        let (u, u_l, u_r) = (
+            self.position(point),
+            self.position(self.xs[idx_l]),
+            self.position(self.xs[idx_r]),
+        );
+        let h = u_r - u_l;
+        let a = (u_r - u) / h;
+        let b = (u - u_l) / h;
+
+        let linear = a * self.ys[idx_l] + b * self.ys[idx_r];
+        let curvature = ((a * a * a - a) * self.second_derivatives[idx_l]
+            + (b * b * b - b) * self.second_derivatives[idx_r])
+            * h
+            * h
+            / ValueType::from(6.0).unwrap();
+
+        Ok(linear + curvature)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Unit tests
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cubic_spline_interpolation {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_cubic_spline_nodes_and_lines() {
+        // A straight line is reproduced exactly.
+        let xs = vec![0.0, 1.0, 2.5, 4.0];
+        let ys = vec![1.0, 3.0, 6.0, 9.0];
+
+        let mut interpolator = CubicSplineInterpolator::new(xs, ys).unwrap();
+        assert!(interpolator.interpolate(1.0).is_err());
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(
+            interpolator.interpolate(2.5).unwrap(),
+            6.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(interpolator.interpolate(3.2).unwrap(), 7.4, 1e-12);
+        assert!(interpolator.interpolate(4.5).is_err());
+    }
+
+    #[test]
+    fn test_cubic_spline_numbers() {
+        // Natural spline through (0, 0), (1, 1), (2, 0): M_1 = -3.
+        let mut interpolator =
+            CubicSplineInterpolator::new(vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 0.0]).unwrap();
+        interpolator.fit().unwrap();
+
+        // y(0.5) = 0.5 + (0.125 - 0.5) * (-3) / 6.
+        assert_approx_equal!(interpolator.interpolate(0.5).unwrap(), 0.6875, 1e-12);
+        assert_approx_equal!(interpolator.interpolate(1.5).unwrap(), 0.6875, 1e-12);
+    }
+
+    #[test]
+    fn test_cubic_spline_dates() {
+        let dates = vec![
+            date!(2024 - 01 - 01),
+            date!(2024 - 07 - 01),
+            date!(2025 - 01 - 01),
+            date!(2026 - 01 - 01),
+        ];
+        let rates = vec![0.05, 0.048, 0.045, 0.042];
+
+        let mut interpolator = CubicSplineInterpolator::new(dates, rates).unwrap();
+        interpolator.fit().unwrap();
+
+        let rate = interpolator.interpolate(date!(2024 - 10 - 01)).unwrap();
+        assert!(rate < 0.048 && rate > 0.045);
+
+        interpolator.add_point((date!(2025 - 06 - 01), 0.044));
+        assert!(!interpolator.fitted);
+    }
+
+    #[test]
+    fn test_clamped_cubic_spline() {
+        // A clamped spline reproduces a cubic given its end slopes.
+        let f = |x: f64| x * x * x - 2.0 * x + 1.0;
+        let xs = vec![-1.0, 0.0, 0.5, 2.0, 3.0];
+        let ys = xs.iter().map(|x| f(*x)).collect();
+
+        let mut interpolator = CubicSplineInterpolator::new(xs, ys)
+            .unwrap()
+            .with_end_slopes(1.0, 25.0);
+        interpolator.fit().unwrap();
+
+        for x in [-0.7, 0.25, 1.3, 2.9] {
+            assert_approx_equal!(interpolator.interpolate(x).unwrap(), f(x), 1e-12);
+        }
+
+        // The end slopes apply to the ends at the time of fitting.
+        let xs = vec![-1.0, 0.0, 2.0];
+        let ys = xs.iter().map(|x| f(*x)).collect();
+
+        let mut interpolator = CubicSplineInterpolator::new(xs, ys)
+            .unwrap()
+            .with_end_slopes(10.0, 46.0);
+        interpolator.add_point((-2.0, f(-2.0)));
+        interpolator.add_point((4.0, f(4.0)));
+        interpolator.fit().unwrap();
+
+        for x in [-1.5, 1.0, 3.5] {
+            assert_approx_equal!(interpolator.interpolate(x).unwrap(), f(x), 1e-12);
+        }
+    }
+}
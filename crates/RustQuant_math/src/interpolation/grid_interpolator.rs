@@ -0,0 +1,478 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module containing functionality for two dimensional interpolation.
+
+use crate::interpolation::{InterpolationIndex, InterpolationValue};
+use num::Float;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Interpolation method of a [`GridInterpolator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum GridInterpolation {
+    /// Linear in each direction within a grid cell.
+    #[default]
+    Bilinear,
+
+    /// Bicubic Hermite within a grid cell: continuously differentiable,
+    /// with the derivatives at the nodes estimated by local quadratic fits.
+    /// Reproduces functions that are quadratic in each direction.
+    Bicubic,
+}
+
+/// Interpolator on a regular (rectilinear) grid, e.g. a volatility surface
+/// with `values[i][j]` at (`xs[i]`, `ys[j]`) = (expiry, strike).
+/// Along an axis with a single node, the values are constant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridInterpolator<XType, YType, ValueType>
+where
+    XType: InterpolationIndex<DeltaDiv = ValueType>,
+    YType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    /// First axis values for the interpolator.
+    pub xs: Vec<XType>,
+
+    /// Second axis values for the interpolator.
+    pub ys: Vec<YType>,
+
+    /// Values on the grid, one row per value of the first axis.
+    pub values: Vec<Vec<ValueType>>,
+
+    /// Interpolation method.
+    pub interpolation: GridInterpolation,
+
+    /// Whether the interpolator has been fitted.
+    pub fitted: bool,
+
+    /// Derivatives at the nodes along x, along y, and the cross derivative,
+    /// with both axes rescaled to [0, 1]. Only used by the bicubic method.
+    derivatives: [Vec<Vec<ValueType>>; 3],
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, FUNCTIONS, AND MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Derivatives of `f` at the points `u`, from the quadratic through each
+/// point and its neighbours (one sided at the ends), or the secant slope
+/// if there are only two points.
+fn node_derivatives<T>(u: &[T], f: &[T]) -> Vec<T>
+where
+    T: InterpolationValue + Float,
+{
+    let n = u.len();
+
+    if n == 1 {
+        return vec![T::zero()];
+    }
+
+    if n == 2 {
+        let slope = (f[1] - f[0]) / (u[1] - u[0]);
+        return vec![slope, slope];
+    }
+
+    (0..n)
+        .map(|i| {
+            let k = i.clamp(1, n - 2) - 1;
+            let (x, y) = (&u[k..k + 3], &f[k..k + 3]);
+            let p = u[i];
+
+            // Derivative of the Lagrange quadratic through the three points.
+            (0..3)
+                .map(|a| {
+                    let (b, c) = ((a + 1) % 3, (a + 2) % 3);
+                    y[a] * ((p - x[b]) + (p - x[c])) / ((x[a] - x[b]) * (x[a] - x[c]))
+                })
+                .fold(T::zero(), |sum, term| sum + term)
+        })
+        .collect()
+}
+
+/// Nodes (left, right) of the cell containing a point of a sorted axis,
+/// which are the same node if the axis has only one.
+fn cell<T: PartialOrd + Copy>(axis: &[T], point: T) -> (usize, usize) {
+    let n = axis.len();
+    let left = axis.partition_point(|&x| x < point).clamp(1, n.max(2) - 1) - 1;
+
+    (left, (left + 1).min(n - 1))
+}
+
+impl<XType, YType, ValueType> GridInterpolator<XType, YType, ValueType>
+where
+    XType: InterpolationIndex<DeltaDiv = ValueType>,
+    YType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue + Float,
+{
+    /// Create a new bilinear GridInterpolator, with `values[i][j]` at
+    /// (`xs[i]`, `ys[j]`). The axes need not be sorted.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if the values do not match the grid.
+    ///
+    /// # Panics
+    /// Panics if NaN is in either axis.
+    pub fn new(
+        xs: Vec<XType>,
+        ys: Vec<YType>,
+        values: Vec<Vec<ValueType>>,
+    ) -> Result<GridInterpolator<XType, YType, ValueType>, RustQuantError> {
+        if values.len() != xs.len() || values.iter().any(|row| row.len() != ys.len()) {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let mut rows: Vec<_> = xs.into_iter().zip(values).collect();
+        rows.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut order: Vec<usize> = (0..ys.len()).collect();
+        order.sort_by(|a, b| ys[*a].partial_cmp(&ys[*b]).unwrap());
+
+        let ys = order.iter().map(|j| ys[*j]).collect();
+        let (xs, values) = rows
+            .into_iter()
+            .map(|(x, row)| (x, order.iter().map(|j| row[*j]).collect()))
+            .unzip();
+
+        Ok(Self {
+            xs,
+            ys,
+            values,
+            interpolation: GridInterpolation::default(),
+            fitted: false,
+            derivatives: [Vec::new(), Vec::new(), Vec::new()],
+        })
+    }
+
+    /// Set the interpolation method.
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: GridInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self.fitted = false;
+        self
+    }
+
+    /// Fit the interpolator to the grid.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if an axis has no points.
+    pub fn fit(&mut self) -> Result<(), RustQuantError> {
+        if self.xs.is_empty() || self.ys.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "A grid interpolator needs at least one point on each axis.".to_string(),
+            ));
+        }
+
+        if self.interpolation == GridInterpolation::Bicubic {
+            let u: Vec<ValueType> = self.xs.iter().map(|x| self.position_x(*x)).collect();
+            let v: Vec<ValueType> = self.ys.iter().map(|y| self.position_y(*y)).collect();
+
+            let along_x = |grid: &[Vec<ValueType>]| -> Vec<Vec<ValueType>> {
+                let columns: Vec<Vec<ValueType>> = (0..v.len())
+                    .map(|j| {
+                        let column: Vec<ValueType> = grid.iter().map(|row| row[j]).collect();
+                        node_derivatives(&u, &column)
+                    })
+                    .collect();
+
+                (0..u.len())
+                    .map(|i| columns.iter().map(|column| column[i]).collect())
+                    .collect()
+            };
+
+            let dy: Vec<Vec<ValueType>> = self
+                .values
+                .iter()
+                .map(|row| node_derivatives(&v, row))
+                .collect();
+            let dx = along_x(&self.values);
+            let dxy = along_x(&dy);
+
+            self.derivatives = [dx, dy, dxy];
+        }
+
+        self.fitted = true;
+
+        Ok(())
+    }
+
+    /// Return the ranges of interpolation, along each axis.
+    ///
+    /// # Panics
+    /// Panics if an axis is empty.
+    pub fn range(&self) -> ((XType, XType), (YType, YType)) {
+        (
+            (*self.xs.first().unwrap(), *self.xs.last().unwrap()),
+            (*self.ys.first().unwrap(), *self.ys.last().unwrap()),
+        )
+    }
+
+    /// Interpolate at the point (`x`, `y`).
+    ///
+    /// # Errors
+    /// - `RustQuantError::Unfitted` when the interpolator has not been fitted.
+    /// - `RustQuantError::OutsideOfRange` when the point is outside the grid.
+    pub fn interpolate(&self, x: XType, y: YType) -> Result<ValueType, RustQuantError> {
+        if !self.fitted {
+            return Err(RustQuantError::Unfitted);
+        }
+
+        let ((x_first, x_last), (y_first, y_last)) = self.range();
+
+        if x < x_first || x > x_last || y < y_first || y > y_last {
+            return Err(RustQuantError::OutsideOfRange);
+        }
+
+        let ((i, i_r), (j, j_r)) = (cell(&self.xs, x), cell(&self.ys, y));
+
+        let (u_l, u_r) = (self.position_x(self.xs[i]), self.position_x(self.xs[i_r]));
+        let (v_l, v_r) = (self.position_y(self.ys[j]), self.position_y(self.ys[j_r]));
+        let (h, k) = (u_r - u_l, v_r - v_l);
+        let fraction = |position: ValueType, left: ValueType, width: ValueType| {
+            if width == ValueType::zero() {
+                ValueType::zero()
+            } else {
+                (position - left) / width
+            }
+        };
+        let s = fraction(self.position_x(x), u_l, h);
+        let t = fraction(self.position_y(y), v_l, k);
+
+        let f = &self.values;
+
+        match self.interpolation {
+            GridInterpolation::Bilinear => {
+                let one = ValueType::one();
+
+                Ok((one - s) * (one - t) * f[i][j]
+                    + s * (one - t) * f[i_r][j]
+                    + (one - s) * t * f[i][j_r]
+                    + s * t * f[i_r][j_r])
+            }
+            GridInterpolation::Bicubic => {
+                let [dx, dy, dxy] = &self.derivatives;
+                let (hs, ht) = (hermite_basis(s), hermite_basis(t));
+
+                // Corners (i + a, j + b): value and derivative basis functions.
+                let mut result = ValueType::zero();
+
+                for (a, b) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let (p, q) = ([i, i_r][a], [j, j_r][b]);
+                    let (value_s, slope_s) = hs[a];
+                    let (value_t, slope_t) = ht[b];
+
+                    result = result
+                        + value_s * value_t * f[p][q]
+                        + slope_s * value_t * h * dx[p][q]
+                        + value_s * slope_t * k * dy[p][q]
+                        + slope_s * slope_t * h * k * dxy[p][q];
+                }
+
+                Ok(result)
+            }
+        }
+    }
+
+    /// Position of a point on the first axis rescaled to [0, 1] (zero if
+    /// the axis has a single node).
+    fn position_x(&self, point: XType) -> ValueType {
+        let (first, last) = (*self.xs.first().unwrap(), *self.xs.last().unwrap());
+
+        if self.xs.len() == 1 {
+            return ValueType::zero();
+        }
+
+        (point - first) / (last - first)
+    }
+
+    /// Position of a point on the second axis rescaled to [0, 1] (zero if
+    /// the axis has a single node).
+    fn position_y(&self, point: YType) -> ValueType {
+        let (first, last) = (*self.ys.first().unwrap(), *self.ys.last().unwrap());
+
+        if self.ys.len() == 1 {
+            return ValueType::zero();
+        }
+
+        (point - first) / (last - first)
+    }
+}
+
+/// Cubic Hermite basis at `t` in [0, 1]: (value, slope) functions
+/// of the left node, then of the right node.
+fn hermite_basis<T>(t: T) -> [(T, T); 2]
+where
+    T: InterpolationValue + Float,
+{
+    let (one, two, three) = (T::one(), T::from(2.0).unwrap(), T::from(3.0).unwrap());
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    [
+        (two * t3 - three * t2 + one, t3 - two * t2 + t),
+        (three * t2 - two * t3, t3 - t2),
+    ]
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Unit tests
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_grid_interpolation {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    fn grid<F: Fn(f64, f64) -> f64>(f: F) -> GridInterpolator<f64, f64, f64> {
+        // Unsorted and unevenly spaced axes.
+        let xs = vec![0.5, 0.0, 1.0, 2.0];
+        let ys = vec![-1.0, 3.0, 0.0, 1.5, 2.0];
+        let values = xs
+            .iter()
+            .map(|x| ys.iter().map(|y| f(*x, *y)).collect())
+            .collect();
+
+        GridInterpolator::new(xs, ys, values).unwrap()
+    }
+
+    #[test]
+    fn test_grid_bilinear() {
+        let f = |x: f64, y: f64| 1.0 + 2.0 * x - 3.0 * y + 0.5 * x * y;
+
+        let mut interpolator = grid(f);
+        assert!(interpolator.interpolate(0.3, 0.3).is_err());
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(
+            interpolator.interpolate(1.0, 1.5).unwrap(),
+            f(1.0, 1.5),
+            RUSTQUANT_EPSILON
+        );
+
+        for (x, y) in [(0.0, -1.0), (0.3, 0.7), (1.7, 2.9), (2.0, 3.0)] {
+            assert_approx_equal!(interpolator.interpolate(x, y).unwrap(), f(x, y), 1e-12);
+        }
+
+        assert!(matches!(
+            interpolator.interpolate(2.1, 0.0),
+            Err(RustQuantError::OutsideOfRange)
+        ));
+        assert!(matches!(
+            interpolator.interpolate(1.0, -1.1),
+            Err(RustQuantError::OutsideOfRange)
+        ));
+    }
+
+    #[test]
+    fn test_grid_bicubic() {
+        // Quadratic in each direction: reproduced exactly.
+        let f = |x: f64, y: f64| 1.0 + x * x - x * y + 2.0 * y * y - 0.5 * x * x * y * y;
+
+        let mut interpolator = grid(f).with_interpolation(GridInterpolation::Bicubic);
+        interpolator.fit().unwrap();
+
+        for (x, y) in [
+            (0.0, -1.0),
+            (0.3, 0.7),
+            (0.75, -0.2),
+            (1.7, 2.9),
+            (2.0, 3.0),
+        ] {
+            assert_approx_equal!(interpolator.interpolate(x, y).unwrap(), f(x, y), 1e-12);
+        }
+
+        // Bilinear interpolation is not exact for it.
+        let mut bilinear = grid(f);
+        bilinear.fit().unwrap();
+
+        assert!((bilinear.interpolate(0.3, 0.7).unwrap() - f(0.3, 0.7)).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_grid_two_by_two() {
+        // With two points per axis, both methods are bilinear.
+        let values = vec![vec![1.0, 2.0], vec![3.0, 5.0]];
+
+        for interpolation in [GridInterpolation::Bilinear, GridInterpolation::Bicubic] {
+            let mut interpolator =
+                GridInterpolator::new(vec![0.0, 1.0], vec![0.0, 1.0], values.clone())
+                    .unwrap()
+                    .with_interpolation(interpolation);
+            interpolator.fit().unwrap();
+
+            assert_approx_equal!(interpolator.interpolate(0.5, 0.5).unwrap(), 2.75, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_grid_volatility_surface() {
+        let expiries = vec![
+            date!(2024 - 07 - 01),
+            date!(2025 - 01 - 01),
+            date!(2026 - 01 - 01),
+        ];
+        let strikes = vec![90.0, 100.0, 110.0];
+        let volatilities = vec![
+            vec![0.25, 0.20, 0.22],
+            vec![0.24, 0.21, 0.22],
+            vec![0.23, 0.215, 0.22],
+        ];
+
+        let mut interpolator = GridInterpolator::new(expiries, strikes, volatilities)
+            .unwrap()
+            .with_interpolation(GridInterpolation::Bicubic);
+        interpolator.fit().unwrap();
+
+        let volatility = interpolator
+            .interpolate(date!(2025 - 01 - 01), 100.0)
+            .unwrap();
+        assert_approx_equal!(volatility, 0.21, 1e-12);
+
+        let volatility = interpolator
+            .interpolate(date!(2025 - 06 - 01), 95.0)
+            .unwrap();
+        assert!(volatility > 0.2 && volatility < 0.25);
+    }
+
+    #[test]
+    fn test_grid_errors() {
+        let result = GridInterpolator::new(vec![0.0, 1.0], vec![0.0, 1.0], vec![vec![1.0, 2.0]]);
+        assert!(matches!(result, Err(RustQuantError::UnequalLength)));
+
+        let mut interpolator =
+            GridInterpolator::<f64, f64, f64>::new(vec![], vec![0.0, 1.0], vec![]).unwrap();
+        assert!(interpolator.fit().is_err());
+    }
+
+    #[test]
+    fn test_grid_single_node_axis() {
+        // Constant along an axis with a single node.
+        for interpolation in [GridInterpolation::Bilinear, GridInterpolation::Bicubic] {
+            let mut interpolator =
+                GridInterpolator::new(vec![1.0], vec![0.0, 1.0, 3.0], vec![vec![1.0, 2.0, 4.0]])
+                    .unwrap()
+                    .with_interpolation(interpolation);
+            interpolator.fit().unwrap();
+
+            assert_approx_equal!(interpolator.interpolate(1.0, 0.5).unwrap(), 1.5, 1e-12);
+            assert_approx_equal!(interpolator.interpolate(1.0, 3.0).unwrap(), 4.0, 1e-12);
+            assert!(interpolator.interpolate(1.5, 0.5).is_err());
+        }
+
+        let mut interpolator =
+            GridInterpolator::new(vec![2.0], vec![5.0], vec![vec![0.3]]).unwrap();
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(interpolator.interpolate(2.0, 5.0).unwrap(), 0.3, 1e-12);
+    }
+}
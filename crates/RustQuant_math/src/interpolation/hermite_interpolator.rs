@@ -0,0 +1,253 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module containing functionality for interpolation.
+
+use crate::interpolation::{InterpolationIndex, InterpolationValue, Interpolator};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Cubic Hermite interpolator, through given values and first derivatives.
+///
+/// On each interval the interpolant is the unique cubic matching the values
+/// and derivatives at both ends, so it is continuously differentiable with
+/// the derivatives that were supplied. For example, interpolating
+/// $-\ln P(t)$ with the instantaneous forward rates as derivatives gives a
+/// curve that reprices the discount factors and matches the forwards at
+/// the nodes.
+///
+/// The derivatives are taken with respect to the index itself, so the
+/// index must be a number (e.g. a year fraction), not a date.
+pub struct HermiteInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<Delta = ValueType, DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    /// X-axis values for the interpolator.
+    pub xs: Vec<IndexType>,
+
+    /// Y-axis values for the interpolator.
+    pub ys: Vec<ValueType>,
+
+    /// First derivatives dy/dx at the x-axis values.
+    pub derivatives: Vec<ValueType>,
+
+    /// Whether the interpolator has been fitted.
+    pub fitted: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, FUNCTIONS, AND MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<IndexType, ValueType> HermiteInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<Delta = ValueType, DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    /// Create a new HermiteInterpolator from (x, y, dy/dx) triples.
+    ///
+    /// # Panics
+    /// Panics if NaN is in the index.
+    #[must_use]
+    pub fn new(
+        points: Vec<(IndexType, ValueType, ValueType)>,
+    ) -> HermiteInterpolator<IndexType, ValueType> {
+        let mut points = points;
+
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut xs = Vec::with_capacity(points.len());
+        let mut ys = Vec::with_capacity(points.len());
+        let mut derivatives = Vec::with_capacity(points.len());
+
+        for (x, y, derivative) in points {
+            xs.push(x);
+            ys.push(y);
+            derivatives.push(derivative);
+        }
+
+        Self {
+            xs,
+            ys,
+            derivatives,
+            fitted: false,
+        }
+    }
+
+    /// Add a point with its derivative to the interpolator.
+    pub fn add_point_with_derivative(&mut self, point: (IndexType, ValueType, ValueType)) {
+        let idx = self.xs.partition_point(|&x| x < point.0);
+
+        self.xs.insert(idx, point.0);
+        self.ys.insert(idx, point.1);
+        self.derivatives.insert(idx, point.2);
+        self.fitted = false;
+    }
+}
+
+impl<IndexType, ValueType> Interpolator<IndexType, ValueType>
+    for HermiteInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<Delta = ValueType, DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    fn fit(&mut self) -> Result<(), RustQuantError> {
+        if self.xs.len() < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "A Hermite interpolator needs at least two points.".to_string(),
+            ));
+        }
+
+        self.fitted = true;
+
+        Ok(())
+    }
+
+    fn range(&self) -> (IndexType, IndexType) {
+        (*self.xs.first().unwrap(), *self.xs.last().unwrap())
+    }
+
+    /// Add a point to the interpolator. Its derivative is not known, so it
+    /// is estimated by the slope between its neighbours (or its neighbour,
+    /// at the ends). Use `add_point_with_derivative` when it is known.
+    fn add_point(&mut self, point: (IndexType, ValueType)) {
+        let idx = self.xs.partition_point(|&x| x < point.0);
+        let n = self.xs.len();
+
+        let (left, right) = match (idx > 0, idx < n) {
+            (true, true) => (
+                (self.xs[idx - 1], self.ys[idx - 1]),
+                (self.xs[idx], self.ys[idx]),
+            ),
+            (true, false) => ((self.xs[idx - 1], self.ys[idx - 1]), point),
+            (false, true) => (point, (self.xs[idx], self.ys[idx])),
+            (false, false) => (point, point),
+        };
+
+        let derivative = if right.0 > left.0 {
+            (right.1 - left.1) / (right.0 - left.0)
+        } else {
+            ValueType::zero()
+        };
+
+        self.add_point_with_derivative((point.0, point.1, derivative));
+    }
+
+    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        if !self.fitted {
+            return Err(RustQuantError::Unfitted);
+        }
+
+        let range = self.range();
+
+        if point < range.0 || point > range.1 {
+            return Err(RustQuantError::OutsideOfRange);
+        }
+
+        let idx_r = self.xs.partition_point(|&x| x < point).max(1);
+        let idx_l = idx_r - 1;
+
+        let h = self.xs[idx_r] - self.xs[idx_l];
+        let t = (point - self.xs[idx_l]) / h;
+
+        // Cubic Hermite basis.
+        let one = ValueType::one();
+        let two = one + one;
+        let three = two + one;
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        let h00 = two * t3 - three * t2 + one;
+        let h10 = t3 - two * t2 + t;
+        let h01 = three * t2 - two * t3;
+        let h11 = t3 - t2;
+
+        Ok(h00 * self.ys[idx_l]
+            + h10 * h * self.derivatives[idx_l]
+            + h01 * self.ys[idx_r]
+            + h11 * h * self.derivatives[idx_r])
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Unit tests
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hermite_interpolation {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_hermite_cubic() {
+        // A cubic is reproduced exactly from its values and derivatives.
+        let f = |x: f64| 2.0 * x * x * x - x * x + 3.0;
+        let df = |x: f64| 6.0 * x * x - 2.0 * x;
+
+        let points = [2.0, -1.0, 0.5]
+            .iter()
+            .map(|x| (*x, f(*x), df(*x)))
+            .collect();
+
+        let mut interpolator = HermiteInterpolator::new(points);
+        assert!(interpolator.interpolate(0.0).is_err());
+        interpolator.fit().unwrap();
+
+        for x in [-1.0, -0.3, 0.5, 1.7, 2.0] {
+            assert_approx_equal!(interpolator.interpolate(x).unwrap(), f(x), 1e-12);
+        }
+        assert!(interpolator.interpolate(2.5).is_err());
+
+        interpolator.add_point_with_derivative((3.0, f(3.0), df(3.0)));
+        interpolator.fit().unwrap();
+        assert_approx_equal!(interpolator.interpolate(2.5).unwrap(), f(2.5), 1e-12);
+    }
+
+    #[test]
+    fn test_hermite_forward_curve() {
+        // Integrated forward rate, -ln P(t), for f(t) = 0.04 - 0.01 exp(-t).
+        let forward = |t: f64| 0.04 - 0.01 * (-t).exp();
+        let integral = |t: f64| 0.04 * t - 0.01 * (1.0 - (-t).exp());
+
+        let points = [0.0, 1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|t| (*t, integral(*t), forward(*t)))
+            .collect();
+
+        let mut interpolator = HermiteInterpolator::new(points);
+        interpolator.fit().unwrap();
+
+        for t in [0.5, 1.5, 3.0, 7.5] {
+            assert_approx_equal!(interpolator.interpolate(t).unwrap(), integral(t), 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_hermite_add_point() {
+        let mut interpolator = HermiteInterpolator::new(vec![(0.0, 0.0, 1.0), (2.0, 2.0, 1.0)]);
+
+        // The derivative of the new point is the slope between its neighbours.
+        interpolator.add_point((1.0, 1.0));
+        assert_approx_equal!(interpolator.derivatives[1], 1.0, 1e-15);
+
+        interpolator.add_point((4.0, 0.0));
+        assert_approx_equal!(interpolator.derivatives[3], -1.0, 1e-15);
+
+        interpolator.fit().unwrap();
+        assert_approx_equal!(interpolator.interpolate(0.5).unwrap(), 0.5, 1e-15);
+
+        assert!(HermiteInterpolator::new(vec![(0.0, 0.0, 0.0)])
+            .fit()
+            .is_err());
+    }
+}
@@ -16,6 +16,21 @@ pub use linear_interpolator::*;
 pub mod exponential_interpolator;
 pub use exponential_interpolator::*;
 
+pub mod cubic_spline_interpolator;
+pub use cubic_spline_interpolator::*;
+
+pub mod akima_interpolator;
+pub use akima_interpolator::*;
+
+pub mod hermite_interpolator;
+pub use hermite_interpolator::*;
+
+pub mod grid_interpolator;
+pub use grid_interpolator::*;
+
+pub mod monotone_convex_interpolator;
+pub use monotone_convex_interpolator::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
@@ -0,0 +1,455 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module containing functionality for interpolation.
+
+use crate::interpolation::{InterpolationIndex, InterpolationValue, Interpolator};
+use num::Float;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Monotone convex interpolator (Hagan and West, 2006) for zero rates.
+///
+/// The `ys` are zero rates from the time origin, e.g. the valuation date,
+/// to the `xs`, and times are year fractions from the origin: the distance
+/// from it divided by the length of a year (e.g. `1.0` for times already in
+/// years, or `Duration::days(365)` for dates, i.e. Actual/365 Fixed).
+/// The nodes may start after the origin; a value given at the origin is
+/// returned there, but does not shape the curve, since `r(0) * 0 = 0`.
+///
+/// The instantaneous forward curve implied by the interpolated zero rates is
+/// continuous, keeps the average forward rate of every interval between
+/// nodes (and between the origin and the first node), and is monotone
+/// wherever these discrete forwards are, which avoids the oscillations of
+/// splines.
+pub struct MonotoneConvexInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    ValueType: InterpolationValue,
+{
+    /// X-axis values for the interpolator.
+    pub xs: Vec<IndexType>,
+
+    /// Y-axis values (zero rates) for the interpolator.
+    pub ys: Vec<ValueType>,
+
+    /// Time origin.
+    pub origin: IndexType,
+
+    /// Length of a year, in the units of the x-axis.
+    pub year: IndexType::Delta,
+
+    /// Whether the interpolator has been fitted.
+    pub fitted: bool,
+
+    /// Times of the origin (unless it is a node) and of the nodes.
+    times: Vec<ValueType>,
+
+    /// Zero rate times time, `r(t) t`, at each of the `times`.
+    areas: Vec<ValueType>,
+
+    /// Average forward rate over each interval.
+    discrete_forwards: Vec<ValueType>,
+
+    /// Instantaneous forward rate at each node.
+    node_forwards: Vec<ValueType>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, FUNCTIONS, AND MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<IndexType, ValueType> MonotoneConvexInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    IndexType::Delta: Copy,
+    ValueType: InterpolationValue + Float,
+{
+    /// Create a new MonotoneConvexInterpolator.
+    ///
+    /// # Arguments
+    /// * `xs` - The nodes, on or after the origin.
+    /// * `ys` - The zero rates from the origin to the nodes.
+    /// * `origin` - The time origin, e.g. the valuation date.
+    /// * `year` - The length of a year, in the units of the x-axis.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if ```xs.length() != ys.length()```.
+    ///
+    /// # Panics
+    /// Panics if NaN is in the index.
+    pub fn new(
+        xs: Vec<IndexType>,
+        ys: Vec<ValueType>,
+        origin: IndexType,
+        year: IndexType::Delta,
+    ) -> Result<MonotoneConvexInterpolator<IndexType, ValueType>, RustQuantError> {
+        if xs.len() != ys.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let mut tmp: Vec<_> = xs.into_iter().zip(ys).collect();
+
+        tmp.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let (xs, ys): (Vec<IndexType>, Vec<ValueType>) = tmp.into_iter().unzip();
+
+        Ok(Self {
+            xs,
+            ys,
+            origin,
+            year,
+            fitted: false,
+            times: Vec::new(),
+            areas: Vec::new(),
+            discrete_forwards: Vec::new(),
+            node_forwards: Vec::new(),
+        })
+    }
+
+    /// Instantaneous forward rate at a point.
+    ///
+    /// # Errors
+    /// - `RustQuantError::Unfitted` when the interpolator has not been fitted.
+    /// - `RustQuantError::OutsideOfRange` outside the nodes.
+    pub fn forward(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        let (i, x) = self.locate(point)?;
+        let (g, _) = self.g(i, x);
+
+        Ok(self.discrete_forwards[i] + g)
+    }
+
+    /// Year fraction from the origin to a point.
+    fn time(&self, point: IndexType) -> ValueType {
+        (point - self.origin) / self.year
+    }
+
+    /// Interval index and position within the interval of a point.
+    fn locate(&self, point: IndexType) -> Result<(usize, ValueType), RustQuantError> {
+        if !self.fitted {
+            return Err(RustQuantError::Unfitted);
+        }
+
+        let range = self.range();
+
+        if point < range.0 || point > range.1 {
+            return Err(RustQuantError::OutsideOfRange);
+        }
+
+        let t = self.time(point);
+        let idx_r = self.times.partition_point(|&x| x < t).max(1);
+        let (t_l, t_r) = (self.times[idx_r - 1], self.times[idx_r]);
+
+        Ok((idx_r - 1, (t - t_l) / (t_r - t_l)))
+    }
+
+    /// Forward rate minus the interval's discrete forward, and its integral
+    /// from the start of the interval, at position `x` in interval `i`.
+    fn g(&self, i: usize, x: ValueType) -> (ValueType, ValueType) {
+        let zero = ValueType::zero();
+        let one = ValueType::one();
+        let (two, three) = (one + one, one + one + one);
+        let half = one / two;
+
+        let g0 = self.node_forwards[i] - self.discrete_forwards[i];
+        let g1 = self.node_forwards[i + 1] - self.discrete_forwards[i];
+
+        if g0 == zero && g1 == zero {
+            return (zero, zero);
+        }
+
+        let region_i = (g0 < zero && -half * g0 <= g1 && g1 <= -two * g0)
+            || (g0 > zero && -half * g0 >= g1 && g1 >= -two * g0);
+        let region_ii = (g0 < zero && g1 > -two * g0) || (g0 > zero && g1 < -two * g0);
+        let region_iii = (g0 > zero && zero > g1 && g1 > -half * g0)
+            || (g0 < zero && zero < g1 && g1 < -half * g0);
+
+        if region_i {
+            let g = g0 * (one - (two + two) * x + three * x * x) + g1 * (-two * x + three * x * x);
+            let integral = g0 * (x - two * x * x + x * x * x) + g1 * (-x * x + x * x * x);
+
+            (g, integral)
+        } else if region_ii {
+            let eta = (g1 + two * g0) / (g1 - g0);
+
+            if x <= eta {
+                (g0, g0 * x)
+            } else {
+                let s = (x - eta) / (one - eta);
+                let g = g0 + (g1 - g0) * s * s;
+                let integral = g0 * x + (g1 - g0) * (x - eta) * s * s / three;
+
+                (g, integral)
+            }
+        } else if region_iii {
+            let eta = three * g1 / (g1 - g0);
+
+            if x < eta {
+                let s = (eta - x) / eta;
+                let g = g1 + (g0 - g1) * s * s;
+                let integral = g1 * x + (g0 - g1) * eta / three * (one - s * s * s);
+
+                (g, integral)
+            } else {
+                (g1, g1 * x + (g0 - g1) * eta / three)
+            }
+        } else {
+            let eta = g1 / (g1 + g0);
+            let a = -g0 * g1 / (g0 + g1);
+
+            if x <= eta {
+                let s = (eta - x) / eta;
+                let g = a + (g0 - a) * s * s;
+                let integral = a * x + (g0 - a) * eta / three * (one - s * s * s);
+
+                (g, integral)
+            } else {
+                let s = (x - eta) / (one - eta);
+                let g = a + (g1 - a) * s * s;
+                let integral =
+                    a * x + (g0 - a) * eta / three + (g1 - a) * (x - eta) * s * s / three;
+
+                (g, integral)
+            }
+        }
+    }
+}
+
+impl<IndexType, ValueType> Interpolator<IndexType, ValueType>
+    for MonotoneConvexInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex<DeltaDiv = ValueType>,
+    IndexType::Delta: Copy,
+    ValueType: InterpolationValue + Float,
+{
+    fn fit(&mut self) -> Result<(), RustQuantError> {
+        let zero = ValueType::zero();
+
+        if self.xs.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "Monotone convex interpolation needs at least one point.".to_string(),
+            ));
+        }
+        if self.xs[0] < self.origin {
+            return Err(RustQuantError::InvalidArgument(
+                "Monotone convex interpolation needs the points on or after the origin."
+                    .to_string(),
+            ));
+        }
+
+        let half = ValueType::from(0.5).unwrap();
+
+        // Zero rates times time, r(t) t, from the origin.
+        let mut t = Vec::with_capacity(self.xs.len() + 1);
+        let mut rt = Vec::with_capacity(self.xs.len() + 1);
+
+        if self.xs[0] > self.origin {
+            t.push(zero);
+            rt.push(zero);
+        }
+        for (x, y) in self.xs.iter().zip(&self.ys) {
+            let time = self.time(*x);
+            t.push(time);
+            rt.push(*y * time);
+        }
+
+        let n = t.len();
+
+        if n < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "Monotone convex interpolation needs a point after the origin.".to_string(),
+            ));
+        }
+
+        // Average forward over each interval, from r(t) t.
+        let discrete_forwards: Vec<ValueType> = (1..n)
+            .map(|i| (rt[i] - rt[i - 1]) / (t[i] - t[i - 1]))
+            .collect();
+
+        // Node forwards: weighted averages of the neighbouring intervals,
+        // with the end values chosen so the end intervals match their averages.
+        let mut node_forwards = vec![ValueType::zero(); n];
+
+        for i in 1..n - 1 {
+            let width = t[i + 1] - t[i - 1];
+            node_forwards[i] = (t[i] - t[i - 1]) / width * discrete_forwards[i]
+                + (t[i + 1] - t[i]) / width * discrete_forwards[i - 1];
+        }
+
+        let (first, last) = (discrete_forwards[0], discrete_forwards[n - 2]);
+
+        if n == 2 {
+            node_forwards[0] = first;
+            node_forwards[1] = first;
+        } else {
+            node_forwards[0] = first - half * (node_forwards[1] - first);
+            node_forwards[n - 1] = last - half * (node_forwards[n - 2] - last);
+        }
+
+        self.times = t;
+        self.areas = rt;
+        self.discrete_forwards = discrete_forwards;
+        self.node_forwards = node_forwards;
+        self.fitted = true;
+
+        Ok(())
+    }
+
+    fn range(&self) -> (IndexType, IndexType) {
+        (self.origin, *self.xs.last().unwrap())
+    }
+
+    fn add_point(&mut self, point: (IndexType, ValueType)) {
+        let idx = self.xs.partition_point(|&x| x < point.0);
+
+        self.xs.insert(idx, point.0);
+        self.ys.insert(idx, point.1);
+        self.fitted = false;
+    }
+
+    /// The zero rate at a point: the given value at the nodes, and the
+    /// instantaneous forward rate at the origin if it is not a node.
+    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        let (i, x) = self.locate(point)?;
+
+        let node = self.xs.partition_point(|&x| x < point);
+        if node < self.xs.len() && self.xs[node] == point {
+            return Ok(self.ys[node]);
+        }
+
+        let t = self.time(point);
+
+        if t == ValueType::zero() {
+            return Ok(self.node_forwards[0]);
+        }
+
+        let (t_l, t_r) = (self.times[i], self.times[i + 1]);
+        let (_, integral) = self.g(i, x);
+
+        let area = self.areas[i] + (t_r - t_l) * (self.discrete_forwards[i] * x + integral);
+
+        Ok(area / t)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Unit tests
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_monotone_convex_interpolation {
+    use super::*;
+    use time::macros::date;
+    use time::Duration;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn interpolator() -> MonotoneConvexInterpolator<f64, f64> {
+        // Hagan and West's example curve.
+        let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = vec![0.0, 0.03, 0.04, 0.047, 0.06, 0.06];
+
+        let mut interpolator = MonotoneConvexInterpolator::new(xs, ys, 0.0, 1.0).unwrap();
+        interpolator.fit().unwrap();
+        interpolator
+    }
+
+    #[test]
+    fn test_monotone_convex_origin_before_nodes() {
+        // 1Y at 2% and 2Y at 3%: the 1Y forward 1Y is 4%.
+        let xs = vec![2025.0, 2026.0];
+        let ys = vec![0.02, 0.03];
+
+        let mut interpolator = MonotoneConvexInterpolator::new(xs, ys, 2024.0, 1.0).unwrap();
+        interpolator.fit().unwrap();
+
+        assert_approx_equal!(interpolator.interpolate(2025.0).unwrap(), 0.02, 1e-14);
+        assert_approx_equal!(interpolator.interpolate(2026.0).unwrap(), 0.03, 1e-14);
+
+        let n = 2000;
+        let mean = (0..n)
+            .map(|k| {
+                interpolator
+                    .forward(2025.0 + (k as f64 + 0.5) / n as f64)
+                    .unwrap()
+            })
+            .sum::<f64>()
+            / n as f64;
+        assert_approx_equal!(mean, 0.04, 1e-6);
+
+        // The zero rate at the origin is the instantaneous forward rate there.
+        assert_approx_equal!(
+            interpolator.interpolate(2024.0).unwrap(),
+            interpolator.forward(2024.0).unwrap(),
+            1e-14
+        );
+        assert!(interpolator.interpolate(2023.5).is_err());
+
+        let mut before =
+            MonotoneConvexInterpolator::new(vec![2025.0], vec![0.02], 2026.0, 1.0).unwrap();
+        assert!(before.fit().is_err());
+    }
+
+    #[test]
+    fn test_monotone_convex_nodes() {
+        let interpolator = interpolator();
+
+        for (x, y) in [(1.0, 0.03), (2.0, 0.04), (3.0, 0.047), (5.0, 0.06)] {
+            assert_approx_equal!(interpolator.interpolate(x).unwrap(), y, 1e-14);
+        }
+        assert!(interpolator.interpolate(5.5).is_err());
+    }
+
+    #[test]
+    fn test_monotone_convex_forwards() {
+        let interpolator = interpolator();
+
+        // Discrete forwards are 3%, 5%, 6.1%, 9.9% and 6%.
+        let averages = [0.03, 0.05, 0.061, 0.099, 0.06];
+
+        for (i, average) in averages.iter().enumerate() {
+            // Midpoint rule on a fine grid.
+            let n = 2000;
+            let mean = (0..n)
+                .map(|k| {
+                    let x = i as f64 + (k as f64 + 0.5) / n as f64;
+                    interpolator.forward(x).unwrap()
+                })
+                .sum::<f64>()
+                / n as f64;
+
+            assert_approx_equal!(mean, *average, 1e-6);
+        }
+
+        // Continuous forward curve at the nodes.
+        for x in [1.0, 2.0, 3.0, 4.0] {
+            let left = interpolator.forward(x - 1e-9).unwrap();
+            let right = interpolator.forward(x + 1e-9).unwrap();
+            assert_approx_equal!(left, right, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_monotone_convex_dates() {
+        let dates = vec![date!(2025 - 01 - 01), date!(2026 - 01 - 01)];
+        let rates = vec![0.04, 0.04];
+        let origin = date!(2024 - 01 - 01);
+
+        let mut interpolator =
+            MonotoneConvexInterpolator::new(dates, rates, origin, Duration::days(365)).unwrap();
+        interpolator.fit().unwrap();
+
+        // A flat curve stays flat, back to the origin.
+        for date in [date!(2024 - 06 - 15), date!(2025 - 06 - 15)] {
+            assert_approx_equal!(interpolator.interpolate(date).unwrap(), 0.04, 1e-12);
+        }
+    }
+}
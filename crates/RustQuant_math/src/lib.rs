@@ -8,6 +8,12 @@
 //!
 //! - [x] Gradient Descent
 //! - [x] Newton-Raphson
+//! - [x] Bisection, Brent, secant, Ridders and TOMS748 root-finding
+//! - [x] Polynomial roots (quadratic, cubic, Durand-Kerner)
+//! - [x] Nelder-Mead
+//! - [x] BFGS and L-BFGS (with autodiff or finite-difference gradients)
+//! - [x] Differential evolution and simulated annealing (box constraints)
+//! - [x] Augmented Lagrangian (box and linear constraints)
 //!
 //! Note: the reason you need to specify the lifetimes and use the type `Variable` is because the gradient descent optimiser uses the `RustQuant::autodiff` module to compute the gradients. This is a slight inconvenience, but the speed-up is enormous when working with functions with many inputs (when compared with using finite-difference quotients).
 //!
@@ -42,6 +48,10 @@
 //!
 //! - Numerical Integration (needed for Heston model, for example):
 //!   - [x] Tanh-Sinh (double exponential) quadrature
+//!   - [x] Gauss-Legendre and Gauss-Hermite quadrature
+//!   - [x] Adaptive Gauss-Kronrod quadrature, with error estimates
+//!   - [x] Two-dimensional (iterated and tensor product) quadrature
+//! - [x] Low-discrepancy sequences (Halton, Sobol) for quasi-Monte Carlo
 //!
 //! ```rust
 //! use RustQuant::math::*;
@@ -58,16 +68,29 @@
 //! println!("Integral = {}", integral);
 //! ```
 //!
+//! ### Linear Algebra
+//!
+//! - [x] Cholesky (with pivoted fallback), QR and SVD decompositions
+//! - [x] Linear and least squares solvers
+//! - [x] Nearest correlation matrix (Higham)
+//! - [x] Covariance estimators (sample, Ledoit-Wolf shrinkage, EWMA)
+//! - [x] Random correlation matrices
+//!
 //! ### Risk-Reward Metrics
 //!
 //! - [x] Risk-Reward Measures (Sharpe, Treynor, Sortino, etc)
+//! - [x] Value-at-Risk and Expected Shortfall (historical, Gaussian, Cornish-Fisher, Monte Carlo)
+//! - [x] Drawdowns, Calmar and Omega ratios
+
+/// Covariance matrix estimators.
+pub mod covariance;
+pub use covariance::*;
 
 /// Statistical distributions.
 pub mod distributions;
 pub use distributions::*;
 
 /// Numerical integration routines.
-/// The primary (useful) integrator is the Tanh-Sinh (double exponential) implementation.
 pub mod integration;
 pub use integration::*;
 
@@ -83,10 +106,22 @@ pub use fft::*;
 pub mod interpolation;
 pub use interpolation::*;
 
+/// Matrix decompositions and linear solvers.
+pub mod linear_algebra;
+pub use linear_algebra::*;
+
+/// Low-discrepancy sequences for quasi-Monte Carlo.
+pub mod low_discrepancy;
+pub use low_discrepancy::*;
+
 /// Simple risk/reward measures.
 pub mod risk_reward;
 pub use risk_reward::*;
 
+/// Value-at-Risk, expected shortfall and drawdown measures.
+pub mod risk_measures;
+pub use risk_measures::*;
+
 /// Root-finding routines.
 pub mod rootfinding;
 pub use rootfinding::*;
@@ -0,0 +1,503 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Matrix decompositions and linear solvers on `nalgebra` matrices, with
+//! the checks and fallbacks needed for covariance and correlation matrices
+//! estimated from data, which are often only positive semi-definite, or
+//! not even that.
+
+use nalgebra::{Cholesky, DMatrix, DVector, SymmetricEigen, LU, QR, SVD};
+use RustQuant_error::RustQuantError;
+
+/// Maximum number of iterations of the nearest correlation matrix.
+const MAX_ITERATIONS: usize = 1000;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Cholesky factorisation with complete pivoting of a positive
+/// semi-definite matrix: $P^T A P = L L^T$, where only the first `rank`
+/// columns of the lower triangular $L$ are non-zero.
+#[derive(Debug, Clone)]
+pub struct PivotedCholesky {
+    /// Lower triangular factor of the permuted matrix.
+    pub lower: DMatrix<f64>,
+
+    /// Row `i` of the permuted matrix is row `permutation[i]` of $A$.
+    pub permutation: Vec<usize>,
+
+    /// Numerical rank of the matrix.
+    pub rank: usize,
+}
+
+/// Thin singular value decomposition $A = U \Sigma V^T$ of an $m \times n$
+/// matrix, with $k = \min(m, n)$ singular values in decreasing order.
+#[derive(Debug, Clone)]
+pub struct SingularValueDecomposition {
+    /// Left singular vectors, $m \times k$.
+    pub u: DMatrix<f64>,
+
+    /// Singular values, the diagonal of $\Sigma$.
+    pub singular_values: DVector<f64>,
+
+    /// Transposed right singular vectors, $k \times n$.
+    pub v_t: DMatrix<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PivotedCholesky {
+    /// The factor $F = P L$ of the original matrix, $A = F F^T$. It is not
+    /// triangular, but serves as one for sampling: $F Z$ has covariance
+    /// $A$ for independent standard normals $Z$.
+    #[must_use]
+    pub fn factor(&self) -> DMatrix<f64> {
+        let mut factor = DMatrix::zeros(self.lower.nrows(), self.lower.ncols());
+
+        for (i, &row) in self.permutation.iter().enumerate() {
+            factor.set_row(row, &self.lower.row(i));
+        }
+
+        factor
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks that the matrix is non-empty, square and symmetric.
+pub(crate) fn check_symmetric(matrix: &DMatrix<f64>) -> Result<(), RustQuantError> {
+    if matrix.is_empty() || !matrix.is_square() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected a non-empty square matrix, got {:?}.",
+            matrix.shape()
+        )));
+    }
+
+    if !matrix.relative_eq(&matrix.transpose(), 1e-12, 1e-12) {
+        return Err(RustQuantError::InvalidArgument(
+            "The matrix must be symmetric.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// A factor $L$ of a symmetric positive semi-definite matrix, $A = L L^T$.
+///
+/// This is the lower triangular Cholesky factor if the matrix is positive
+/// definite. Otherwise (e.g. perfectly correlated assets) the pivoted
+/// Cholesky factorisation is used, and the factor is a row permutation of
+/// a lower triangular matrix whose trailing columns are zero.
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use nalgebra::DMatrix;
+/// // Singular: the third variable is the sum of the first two.
+/// let a = DMatrix::from_row_slice(3, 3, &[
+///     1.0, 0.5, 1.5,
+///     0.5, 1.0, 1.5,
+///     1.5, 1.5, 3.0,
+/// ]);
+///
+/// let l = cholesky(&a).unwrap();
+///
+/// assert!((&l * l.transpose() - a).norm() < 1e-12);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the matrix is not square and symmetric, or not
+/// positive semi-definite.
+pub fn cholesky(matrix: &DMatrix<f64>) -> Result<DMatrix<f64>, RustQuantError> {
+    check_symmetric(matrix)?;
+
+    match Cholesky::new(matrix.clone()) {
+        Some(cholesky) => Ok(cholesky.l()),
+        None => Ok(pivoted_cholesky(matrix)?.factor()),
+    }
+}
+
+/// Cholesky factorisation with complete pivoting: at each step the largest
+/// remaining diagonal element is the pivot, and the factorisation stops
+/// when it falls below $n \epsilon \max_i A_{ii}$, giving the numerical
+/// rank.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is not square and symmetric, or not
+/// positive semi-definite.
+pub fn pivoted_cholesky(matrix: &DMatrix<f64>) -> Result<PivotedCholesky, RustQuantError> {
+    check_symmetric(matrix)?;
+
+    let n = matrix.nrows();
+    let mut schur = matrix.clone();
+    let mut lower = DMatrix::zeros(n, n);
+    let mut permutation: Vec<usize> = (0..n).collect();
+
+    let tolerance = n as f64 * f64::EPSILON * matrix.diagonal().max().max(0.0);
+    let mut rank = n;
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|a, b| schur[(*a, *a)].total_cmp(&schur[(*b, *b)]))
+            .unwrap_or(k);
+
+        if schur[(pivot, pivot)] <= tolerance {
+            rank = k;
+            break;
+        }
+
+        schur.swap_rows(k, pivot);
+        schur.swap_columns(k, pivot);
+        lower.swap_rows(k, pivot);
+        permutation.swap(k, pivot);
+
+        let diagonal = schur[(k, k)].sqrt();
+        lower[(k, k)] = diagonal;
+
+        for i in k + 1..n {
+            lower[(i, k)] = schur[(i, k)] / diagonal;
+        }
+
+        for j in k + 1..n {
+            for i in k + 1..n {
+                schur[(i, j)] -= lower[(i, k)] * lower[(j, k)];
+            }
+        }
+    }
+
+    // The remaining Schur complement must be (numerically) zero.
+    let remainder = schur
+        .view((rank, rank), (n - rank, n - rank))
+        .iter()
+        .fold(0.0, |m: f64, x| m.max(x.abs()));
+
+    if remainder > 1e3 * tolerance {
+        return Err(RustQuantError::InvalidArgument(
+            "The matrix must be positive semi-definite.".to_string(),
+        ));
+    }
+
+    Ok(PivotedCholesky {
+        lower,
+        permutation,
+        rank,
+    })
+}
+
+/// Thin QR decomposition of an $m \times n$ matrix: $A = Q R$, with the
+/// $m \times \min(m, n)$ matrix $Q$ having orthonormal columns and the
+/// upper triangular (trapezoidal) $R$.
+#[must_use]
+pub fn qr_decomposition(matrix: &DMatrix<f64>) -> (DMatrix<f64>, DMatrix<f64>) {
+    let qr = QR::new(matrix.clone());
+
+    (qr.q(), qr.r())
+}
+
+/// Thin singular value decomposition: $A = U \Sigma V^T$.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is empty or the iteration does not
+/// converge (e.g. if the matrix is not finite).
+pub fn svd_decomposition(
+    matrix: &DMatrix<f64>,
+) -> Result<SingularValueDecomposition, RustQuantError> {
+    if matrix.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "Expected a non-empty matrix.".to_string(),
+        ));
+    }
+
+    let mut svd = SVD::try_new(matrix.clone(), true, true, f64::EPSILON, 0).ok_or(
+        RustQuantError::ComputationError("SVD did not converge.".to_string()),
+    )?;
+    svd.sort_by_singular_values();
+
+    match (svd.u, svd.v_t) {
+        (Some(u), Some(v_t)) => Ok(SingularValueDecomposition {
+            u,
+            singular_values: svd.singular_values,
+            v_t,
+        }),
+        _ => Err(RustQuantError::ComputationError(
+            "SVD did not compute the singular vectors.".to_string(),
+        )),
+    }
+}
+
+/// Solves the square linear system $A x = b$ by LU decomposition with
+/// partial pivoting.
+///
+/// # Errors
+///
+/// Returns an error if the dimensions do not match, or the matrix is
+/// singular.
+pub fn solve_linear_system(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+) -> Result<DVector<f64>, RustQuantError> {
+    if !a.is_square() || a.nrows() != b.len() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected a {n} x {n} matrix for a vector of length {n}, got {:?}.",
+            a.shape(),
+            n = b.len()
+        )));
+    }
+
+    LU::new(a.clone())
+        .solve(b)
+        .ok_or(RustQuantError::MatrixInversionFailed)
+}
+
+/// Least squares solution of $A x = b$ (minimum norm if $A$ is rank
+/// deficient), by singular value decomposition. Singular values below
+/// $\max(m, n) \epsilon \sigma_{\max}$ are treated as zero.
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use nalgebra::{DMatrix, DVector};
+/// // Fit y = a + b x to (0, 1), (1, 3), (2, 5.5).
+/// let a = DMatrix::from_row_slice(3, 2, &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0]);
+/// let b = DVector::from_vec(vec![1.0, 3.0, 5.5]);
+///
+/// let x = least_squares(&a, &b).unwrap();
+///
+/// assert!((x[0] - 0.916_666_666_666_666_7).abs() < 1e-12);
+/// assert!((x[1] - 2.25).abs() < 1e-12);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the dimensions do not match, or the singular value
+/// decomposition fails.
+pub fn least_squares(a: &DMatrix<f64>, b: &DVector<f64>) -> Result<DVector<f64>, RustQuantError> {
+    if a.nrows() != b.len() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Expected a matrix with {} rows, got {:?}.",
+            b.len(),
+            a.shape()
+        )));
+    }
+
+    let SingularValueDecomposition {
+        u,
+        singular_values,
+        v_t,
+    } = svd_decomposition(a)?;
+
+    let cutoff = a.nrows().max(a.ncols()) as f64 * f64::EPSILON * singular_values[0];
+    let projected = u.transpose() * b;
+    let scaled = DVector::from_iterator(
+        singular_values.len(),
+        singular_values
+            .iter()
+            .zip(projected.iter())
+            .map(|(s, p)| if *s > cutoff { p / s } else { 0.0 }),
+    );
+
+    Ok(v_t.transpose() * scaled)
+}
+
+/// Nearest correlation matrix (in the Frobenius norm) to a symmetric
+/// matrix with unit diagonal, by Higham's (2002) alternating projections
+/// with Dykstra's correction.
+///
+/// Projections onto the positive semi-definite matrices (clipping the
+/// eigenvalues) and onto the unit diagonal matrices are alternated until
+/// successive iterates differ by less than `tolerance` (relative). The
+/// eigenvalues are clipped at `tolerance` rather than zero, so the result
+/// is positive definite and can be Cholesky factorised.
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use nalgebra::DMatrix;
+/// // Pairwise estimated correlations, inconsistent as a whole.
+/// let a = DMatrix::from_row_slice(3, 3, &[
+///     1.0, 0.9, 0.7,
+///     0.9, 1.0, -0.3,
+///     0.7, -0.3, 1.0,
+/// ]);
+/// assert!(nalgebra::Cholesky::new(a.clone()).is_none());
+///
+/// let x = nearest_correlation_matrix(&a, 1e-10).unwrap();
+///
+/// assert!(nalgebra::Cholesky::new(x.clone()).is_some());
+/// assert!(x.diagonal().iter().all(|d| (d - 1.0).abs() < 1e-12));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the matrix is not square and symmetric, the
+/// tolerance is not positive, or the iteration does not converge.
+pub fn nearest_correlation_matrix(
+    matrix: &DMatrix<f64>,
+    tolerance: f64,
+) -> Result<DMatrix<f64>, RustQuantError> {
+    check_symmetric(matrix)?;
+
+    if tolerance.is_nan() || tolerance <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Tolerance must be positive, got {tolerance}."
+        )));
+    }
+
+    let n = matrix.nrows();
+    let mut y = matrix.clone();
+    let mut correction = DMatrix::zeros(n, n);
+
+    for _ in 0..MAX_ITERATIONS {
+        let r = &y - &correction;
+
+        // Projection onto the positive (semi-)definite matrices.
+        let eigen = SymmetricEigen::new(r.clone());
+        let clipped = eigen.eigenvalues.map(|lambda| lambda.max(tolerance));
+        let x =
+            &eigen.eigenvectors * DMatrix::from_diagonal(&clipped) * eigen.eigenvectors.transpose();
+        let x = (&x + x.transpose()) * 0.5;
+
+        correction = &x - r;
+
+        // Projection onto the unit diagonal matrices.
+        let previous = y;
+        y = x.clone();
+        y.fill_diagonal(1.0);
+
+        let change = (&y - &previous).norm() / y.norm();
+
+        if change < tolerance && (&y - &x).norm() / y.norm() < tolerance {
+            // Rescale the positive definite iterate to a unit diagonal.
+            let scale = x.diagonal().map(|d| 1.0 / d.sqrt());
+            let mut result = DMatrix::from_fn(n, n, |i, j| x[(i, j)] * scale[i] * scale[j]);
+            result.fill_diagonal(1.0);
+
+            return Ok(result);
+        }
+    }
+
+    Err(RustQuantError::ComputationError(
+        "Nearest correlation matrix did not converge.".to_string(),
+    ))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_linear_algebra {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_cholesky_positive_definite() {
+        let a = DMatrix::from_row_slice(2, 2, &[4.0, 2.0, 2.0, 3.0]);
+        let l = cholesky(&a).unwrap();
+
+        assert_approx_equal!(l[(0, 0)], 2.0, 1e-15);
+        assert_approx_equal!(l[(1, 0)], 1.0, 1e-15);
+        assert_approx_equal!(l[(1, 1)], 2_f64.sqrt(), 1e-15);
+        assert_eq!(l[(0, 1)], 0.0);
+    }
+
+    #[test]
+    fn test_pivoted_cholesky() {
+        // Rank 2: the middle variable duplicates the first.
+        let a = DMatrix::from_row_slice(3, 3, &[1.0, 1.0, 0.2, 1.0, 1.0, 0.2, 0.2, 0.2, 4.0]);
+        let pivoted = pivoted_cholesky(&a).unwrap();
+
+        assert_eq!(pivoted.rank, 2);
+        // The largest diagonal element is the first pivot.
+        assert_eq!(pivoted.permutation[0], 2);
+
+        let f = pivoted.factor();
+        assert!((&f * f.transpose() - &a).norm() < 1e-14);
+        assert!((cholesky(&a).unwrap() - f).norm() < 1e-15);
+    }
+
+    #[test]
+    fn test_cholesky_errors() {
+        let indefinite = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        assert!(cholesky(&indefinite).is_err());
+
+        let hollow = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        assert!(cholesky(&hollow).is_err());
+
+        let asymmetric = DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.0, 1.0]);
+        assert!(cholesky(&asymmetric).is_err());
+
+        assert!(cholesky(&DMatrix::zeros(2, 3)).is_err());
+        assert!(cholesky(&DMatrix::zeros(0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_qr_and_svd() {
+        let a = DMatrix::from_row_slice(3, 2, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let (q, r) = qr_decomposition(&a);
+        assert_eq!(q.shape(), (3, 2));
+        assert!((q.transpose() * &q - DMatrix::identity(2, 2)).norm() < 1e-14);
+        assert_eq!(r[(1, 0)], 0.0);
+        assert!((&q * &r - &a).norm() < 1e-13);
+
+        let SingularValueDecomposition {
+            u,
+            singular_values: s,
+            v_t,
+        } = svd_decomposition(&a).unwrap();
+        assert!(s[0] >= s[1]);
+        assert!((u * DMatrix::from_diagonal(&s) * v_t - &a).norm() < 1e-13);
+        // Frobenius norm is the norm of the singular values.
+        assert_approx_equal!(s.norm(), a.norm(), 1e-13);
+    }
+
+    #[test]
+    fn test_solvers() {
+        let a = DMatrix::from_row_slice(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+        let b = DVector::from_vec(vec![3.0, 5.0]);
+
+        let x = solve_linear_system(&a, &b).unwrap();
+        assert_approx_equal!(x[0], 0.8, 1e-15);
+        assert_approx_equal!(x[1], 1.4, 1e-15);
+        assert!((least_squares(&a, &b).unwrap() - x).norm() < 1e-14);
+
+        let singular = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        assert!(solve_linear_system(&singular, &b).is_err());
+        assert!(solve_linear_system(&a, &DVector::zeros(3)).is_err());
+
+        // Minimum norm solution of x + 2 y = 1 (twice).
+        let x = least_squares(&singular, &DVector::from_vec(vec![1.0, 2.0])).unwrap();
+        assert_approx_equal!(x[0], 0.2, 1e-14);
+        assert_approx_equal!(x[1], 0.4, 1e-14);
+    }
+
+    #[test]
+    fn test_nearest_correlation_matrix() {
+        // Higham (2002), Section 4.
+        let a = DMatrix::from_row_slice(3, 3, &[1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+        let x = nearest_correlation_matrix(&a, 1e-10).unwrap();
+
+        assert_approx_equal!(x[(0, 1)], 0.7607, 1e-4);
+        assert_approx_equal!(x[(0, 2)], 0.1573, 1e-4);
+        assert_approx_equal!(x[(1, 2)], 0.7607, 1e-4);
+        assert!(Cholesky::new(x).is_some());
+
+        // A correlation matrix is its own nearest.
+        let c = DMatrix::from_row_slice(2, 2, &[1.0, 0.3, 0.3, 1.0]);
+        assert!((nearest_correlation_matrix(&c, 1e-12).unwrap() - &c).norm() < 1e-12);
+
+        assert!(nearest_correlation_matrix(&c, 0.0).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Low-discrepancy (quasi-random) sequences on the unit hypercube, for
+//! quasi-Monte Carlo integration.
+//!
+//! Both sequences are iterators of points in $(0, 1)^d$: the initial point
+//! at the origin is skipped, so every coordinate can be passed to an
+//! inverse distribution function (see
+//! [`crate::distributions::Distribution::quantile_transform`]).
+
+use RustQuant_error::RustQuantError;
+
+/// Number of bits of the Sobol direction numbers.
+const BITS: usize = 32;
+
+/// Primitive polynomials and initial direction numbers of the Sobol
+/// sequence for dimensions 2 and up (Joe and Kuo, 2008): the degree $s$,
+/// the inner coefficients $a$ of the polynomial (as bits), and the initial
+/// $m_1, \dots, m_s$.
+const SOBOL_PARAMETERS: [(usize, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// Maximum dimension of the Sobol sequence.
+pub const SOBOL_MAX_DIMENSION: usize = SOBOL_PARAMETERS.len() + 1;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Halton sequence: coordinate $j$ of point $k$ is the radical inverse of
+/// $k$ in the $j$-th prime base (the digits of $k$ reflected about the
+/// radix point).
+///
+/// Simple and of any dimension, but the coordinates in large bases are
+/// strongly correlated over the first points; prefer [`Sobol`] beyond a
+/// few dimensions.
+#[derive(Debug, Clone)]
+pub struct Halton {
+    /// Prime base of each coordinate.
+    bases: Vec<u64>,
+
+    /// Index of the next point.
+    index: u64,
+}
+
+/// Sobol sequence, with the Joe-Kuo direction numbers, generated in Gray
+/// code order (Antonov and Saleev): each point differs from the previous
+/// one by a single direction number in each coordinate.
+///
+/// Any $2^m$ consecutive points, from a multiple of $2^m$, place one point
+/// in each interval $[k 2^{-m}, (k + 1) 2^{-m})$ of each coordinate.
+#[derive(Debug, Clone)]
+pub struct Sobol {
+    /// Direction numbers of each coordinate, as 32-bit binary fractions.
+    directions: Vec<[u32; BITS]>,
+
+    /// Current point, as 32-bit binary fractions.
+    state: Vec<u32>,
+
+    /// Index of the current point.
+    index: u32,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Halton {
+    /// Returns the Halton sequence of the given dimension, in bases 2, 3,
+    /// 5, 7, ..., from its first point after the origin.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// let points: Vec<Vec<f64>> = Halton::new(2).unwrap().take(3).collect();
+    ///
+    /// assert_eq!(points[0], vec![0.5, 1.0 / 3.0]);
+    /// assert_eq!(points[1], vec![0.25, 2.0 / 3.0]);
+    /// assert_eq!(points[2], vec![0.75, 1.0 / 9.0]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dimension is zero.
+    pub fn new(dimension: usize) -> Result<Self, RustQuantError> {
+        if dimension == 0 {
+            return Err(RustQuantError::InvalidArgument(
+                "Dimension must be positive.".to_string(),
+            ));
+        }
+
+        let mut bases = Vec::with_capacity(dimension);
+        let mut candidate = 2;
+
+        while bases.len() < dimension {
+            if bases.iter().all(|p| candidate % p != 0) {
+                bases.push(candidate);
+            }
+            candidate += 1;
+        }
+
+        Ok(Self { bases, index: 1 })
+    }
+
+    /// Skips the next `n` points (e.g. to discard the first, most
+    /// correlated, points of the sequence).
+    #[must_use]
+    pub fn skip_points(mut self, n: u64) -> Self {
+        self.index += n;
+        self
+    }
+
+    /// Dimension of the sequence.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.bases.len()
+    }
+}
+
+impl Iterator for Halton {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self
+            .bases
+            .iter()
+            .map(|&base| {
+                let (mut k, mut value, mut scale) = (self.index, 0.0, 1.0);
+
+                while k > 0 {
+                    scale /= base as f64;
+                    value += (k % base) as f64 * scale;
+                    k /= base;
+                }
+
+                value
+            })
+            .collect();
+
+        self.index += 1;
+
+        Some(point)
+    }
+}
+
+impl Sobol {
+    /// Returns the Sobol sequence of the given dimension, from its first
+    /// point after the origin.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// let points: Vec<Vec<f64>> = Sobol::new(2).unwrap().take(4).collect();
+    ///
+    /// assert_eq!(points[0], vec![0.5, 0.5]);
+    /// assert_eq!(points[1], vec![0.75, 0.25]);
+    /// assert_eq!(points[2], vec![0.25, 0.75]);
+    /// assert_eq!(points[3], vec![0.375, 0.375]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dimension is zero or greater than
+    /// [`SOBOL_MAX_DIMENSION`].
+    pub fn new(dimension: usize) -> Result<Self, RustQuantError> {
+        if dimension == 0 || dimension > SOBOL_MAX_DIMENSION {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Sobol dimension must be between 1 and {SOBOL_MAX_DIMENSION}, got {dimension}."
+            )));
+        }
+
+        // The first coordinate is the van der Corput sequence in base 2.
+        let mut directions = vec![std::array::from_fn(|k| 1 << (BITS - 1 - k))];
+
+        for &(degree, coefficients, initial) in &SOBOL_PARAMETERS[..dimension - 1] {
+            let mut v = [0_u32; BITS];
+
+            for k in 0..BITS {
+                v[k] = if k < degree {
+                    initial[k] << (BITS - 1 - k)
+                } else {
+                    // Bratley and Fox's recurrence for the direction numbers.
+                    (1..degree).fold(v[k - degree] ^ (v[k - degree] >> degree), |acc, j| {
+                        if (coefficients >> (degree - 1 - j)) & 1 == 1 {
+                            acc ^ v[k - j]
+                        } else {
+                            acc
+                        }
+                    })
+                };
+            }
+
+            directions.push(v);
+        }
+
+        Ok(Self {
+            directions,
+            state: vec![0; dimension],
+            index: 0,
+        })
+    }
+
+    /// Dimension of the sequence.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.state.len()
+    }
+}
+
+impl Iterator for Sobol {
+    type Item = Vec<f64>;
+
+    /// The next point, until the $2^{32} - 1$ points are exhausted.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == u32::MAX {
+            return None;
+        }
+
+        // The lowest zero bit of the index selects the direction number.
+        let bit = self.index.trailing_ones() as usize;
+        self.index += 1;
+
+        Some(
+            self.state
+                .iter_mut()
+                .zip(&self.directions)
+                .map(|(x, v)| {
+                    *x ^= v[bit];
+                    f64::from(*x) / 2_f64.powi(BITS as i32)
+                })
+                .collect(),
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_low_discrepancy {
+    use super::*;
+    use crate::distributions::{Distribution, Gaussian};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_halton() {
+        let mut halton = Halton::new(3).unwrap();
+        assert_eq!(halton.dimension(), 3);
+
+        // Index 5 = 101 (base 2) = 12 (base 3) = 10 (base 5).
+        let point = halton.nth(4).unwrap();
+        assert_approx_equal!(point[0], 0.625, 1e-15);
+        assert_approx_equal!(point[1], 7.0 / 9.0, 1e-15);
+        assert_approx_equal!(point[2], 0.04, 1e-15);
+
+        let skipped = Halton::new(1).unwrap().skip_points(4).next().unwrap();
+        assert_approx_equal!(skipped[0], 0.625, 1e-15);
+
+        assert!(Halton::new(0).is_err());
+    }
+
+    #[test]
+    fn test_sobol_stratification() {
+        // The origin and the next 2^m - 1 points hit every interval
+        // [k / 2^m, (k + 1) / 2^m) of every coordinate exactly once.
+        let m = 10;
+        let points: Vec<Vec<f64>> = Sobol::new(SOBOL_MAX_DIMENSION)
+            .unwrap()
+            .take((1 << m) - 1)
+            .collect();
+
+        for j in 0..SOBOL_MAX_DIMENSION {
+            let mut seen = vec![false; 1 << m];
+            seen[0] = true;
+
+            for point in &points {
+                let x = point[j];
+                assert!(x > 0.0 && x < 1.0);
+
+                let k = (x * f64::from(1 << m)) as usize;
+                assert!(!seen[k], "dimension {j}: interval {k} hit twice");
+                seen[k] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_sobol_two_dimensional_stratification() {
+        // The first 2^4 points (with the origin) of the first two
+        // coordinates form a (0, 4, 2)-net: one point in each elementary
+        // rectangle of area 1 / 16.
+        let mut points: Vec<Vec<f64>> = Sobol::new(2).unwrap().take(15).collect();
+        points.push(vec![0.0, 0.0]);
+
+        for a in 0..=4 {
+            let (columns, rows) = (1 << a, 1 << (4 - a));
+            let mut seen = [false; 16];
+
+            for point in &points {
+                let cell = (point[0] * f64::from(columns)) as usize * rows
+                    + (point[1] * f64::from(rows as u32)) as usize;
+                assert!(!seen[cell]);
+                seen[cell] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_sobol_gaussian_moments() {
+        let uniforms: Vec<f64> = Sobol::new(1).unwrap().take(4095).map(|u| u[0]).collect();
+        let z = Gaussian::default().quantile_transform(&uniforms).unwrap();
+
+        let n = z.len() as f64;
+        let mean = z.iter().sum::<f64>() / n;
+        let variance = z.iter().map(|z| z * z).sum::<f64>() / n;
+
+        assert_approx_equal!(mean, 0.0, 1e-12);
+        // Slightly low: the tails beyond the extreme points are missing.
+        assert_approx_equal!(variance, 1.0, 5e-3);
+
+        assert!(Sobol::new(0).is_err());
+        assert!(Sobol::new(SOBOL_MAX_DIMENSION + 1).is_err());
+    }
+}
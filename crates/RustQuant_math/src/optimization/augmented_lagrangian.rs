@@ -0,0 +1,601 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Augmented Lagrangian method for box and linear constraints.
+//!
+//! Solves
+//!
+//! $$
+//! \min_x f(x) \quad \text{s.t.} \quad
+//! a_i^T x = b_i, \quad c_j^T x \leq d_j, \quad l \leq x \leq u.
+//! $$
+//!
+//! The box is kept by projection, and the linear constraints are moved into
+//! the objective: each outer iteration minimizes over the box
+//!
+//! $$
+//! L_\rho(x, \lambda, \mu) = f(x)
+//! + \sum_i \left( \lambda_i h_i(x) + \frac{\rho}{2} h_i(x)^2 \right)
+//! + \frac{1}{2\rho} \sum_j \left( \max(0, \mu_j + \rho g_j(x))^2 - \mu_j^2 \right)
+//! $$
+//!
+//! with $h_i(x) = a_i^T x - b_i$ and $g_j(x) = c_j^T x - d_j$, by the
+//! spectral projected gradient method (Birgin, Martinez and Raydan, 2000),
+//! then updates the multipliers $\lambda_i \leftarrow \lambda_i + \rho h_i$,
+//! $\mu_j \leftarrow \max(0, \mu_j + \rho g_j)$, and increases the penalty
+//! $\rho$ if the constraint violation did not decrease enough.
+//!
+//! For example, long-only Markowitz weights are the minimizer of
+//! $w^T \Sigma w$ with the equality $\sum_i w_i = 1$ and the box $w \geq 0$.
+
+use super::line_search::{autodiff_gradient, finite_difference_gradient};
+use super::optimizer::{OptimizationResult, Optimizer};
+use std::collections::VecDeque;
+use std::time::Instant;
+use RustQuant_autodiff::variable::Variable;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Augmented Lagrangian optimizer, with box and linear constraints.
+#[derive(Debug, Clone)]
+pub struct AugmentedLagrangian {
+    /// Lower and upper bound of each parameter (possibly infinite).
+    pub bounds: Vec<(f64, f64)>,
+
+    /// Linear equality constraints $a^T x = b$, as $(a, b)$.
+    pub equalities: Vec<(Vec<f64>, f64)>,
+
+    /// Linear inequality constraints $c^T x \leq d$, as $(c, d)$.
+    pub inequalities: Vec<(Vec<f64>, f64)>,
+
+    /// Maximum number of outer (multiplier update) iterations.
+    pub max_iterations: usize,
+
+    /// Maximum number of projected gradient iterations per outer iteration.
+    pub max_inner_iterations: usize,
+
+    /// Tolerance for the constraint violation and for the projected
+    /// gradient of the augmented Lagrangian.
+    pub tolerance: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+impl AugmentedLagrangian {
+    /// Returns a new optimizer over $\mathbb{R}^n$, without constraints.
+    #[must_use]
+    pub fn new(n: usize) -> Self {
+        Self {
+            bounds: vec![(f64::NEG_INFINITY, f64::INFINITY); n],
+            equalities: Vec::new(),
+            inequalities: Vec::new(),
+            max_iterations: 100,
+            max_inner_iterations: 10_000,
+            tolerance: 1e-8,
+        }
+    }
+
+    /// Set the bounds of the parameters. Infinite bounds are allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of bounds is not the dimension, or a
+    /// lower bound is above its upper bound.
+    pub fn with_bounds(mut self, bounds: Vec<(f64, f64)>) -> Result<Self, RustQuantError> {
+        if bounds.len() != self.bounds.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        if let Some(i) = bounds
+            .iter()
+            .position(|(lower, upper)| lower.is_nan() || upper.is_nan() || lower > upper)
+        {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Invalid bounds {:?} for parameter {i}.",
+                bounds[i]
+            )));
+        }
+
+        self.bounds = bounds;
+        Ok(self)
+    }
+
+    /// Add the equality constraint $a^T x = b$.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `a` is not of the dimension of the problem.
+    pub fn with_equality(mut self, a: Vec<f64>, b: f64) -> Result<Self, RustQuantError> {
+        if a.len() != self.bounds.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        self.equalities.push((a, b));
+        Ok(self)
+    }
+
+    /// Add the inequality constraint $c^T x \leq d$.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `c` is not of the dimension of the problem.
+    pub fn with_inequality(mut self, c: Vec<f64>, d: f64) -> Result<Self, RustQuantError> {
+        if c.len() != self.bounds.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        self.inequalities.push((c, d));
+        Ok(self)
+    }
+
+    /// Set the maximum number of outer and inner iterations.
+    #[must_use]
+    pub fn with_max_iterations(mut self, outer: usize, inner: usize) -> Self {
+        self.max_iterations = outer;
+        self.max_inner_iterations = inner;
+        self
+    }
+
+    /// Set the tolerance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tolerance is not positive.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0);
+
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Largest violation of the linear constraints at `x`. The bounds are
+    /// always satisfied by the minimizer.
+    #[must_use]
+    pub fn violation(&self, x: &[f64]) -> f64 {
+        let equalities = self.equalities.iter().map(|(a, b)| (dot(a, x) - b).abs());
+        let inequalities = self
+            .inequalities
+            .iter()
+            .map(|(c, d)| (dot(c, x) - d).max(0.0));
+
+        equalities.chain(inequalities).fold(0.0, f64::max)
+    }
+
+    /// Minimizes a function subject to the constraints, with gradients from
+    /// automatic differentiation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x0` is not of the dimension of the problem.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::autodiff::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// // Long-only minimum variance portfolio of three assets.
+    /// fn variance<'v>(w: &[Variable<'v>]) -> Variable<'v> {
+    ///     let covariance = [[0.04, 0.006, 0.02], [0.006, 0.09, 0.03], [0.02, 0.03, 0.0225]];
+    ///     let mut sum = w[0] * 0.0;
+    ///
+    ///     for (j, row) in covariance.iter().enumerate() {
+    ///         for (k, c) in row.iter().enumerate() {
+    ///             sum = sum + *c * w[j] * w[k];
+    ///         }
+    ///     }
+    ///
+    ///     sum
+    /// }
+    ///
+    /// let result = AugmentedLagrangian::new(3)
+    ///     .with_bounds(vec![(0.0, f64::INFINITY); 3])
+    ///     .and_then(|problem| problem.with_equality(vec![1.0; 3], 1.0))
+    ///     .unwrap()
+    ///     .optimize(variance, &[1.0 / 3.0; 3]);
+    ///
+    /// // The unconstrained minimum variance portfolio is short the first
+    /// // two assets; the long-only one holds none of the second.
+    /// assert!(result.converged);
+    /// assert_approx_equal!(result.minimizer.iter().sum::<f64>(), 1.0, 1e-8);
+    /// assert_approx_equal!(result.minimizer[1], 0.0, 1e-8);
+    /// ```
+    pub fn optimize<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+    {
+        self.minimize(|x| autodiff_gradient(&f, x), x0)
+    }
+
+    /// Minimizes a function subject to the constraints, with gradients from
+    /// central finite differences (each costs $2n$ extra function
+    /// evaluations, which may be slightly outside the bounds).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x0` is not of the dimension of the problem.
+    pub fn optimize_numerical<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let n = x0.len();
+        let mut result = self.minimize(|x| finite_difference_gradient(&f, x), x0);
+
+        result.evaluations *= 2 * n + 1;
+        result
+    }
+
+    /// Minimizes a function subject to the constraints, given a closure
+    /// returning its value and gradient (e.g. $\frac{1}{2} x^T Q x$ and
+    /// $Q x$ for a quadratic program).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x0` is not of the dimension of the problem.
+    pub fn optimize_with_gradient<G>(&self, objective: G, x0: &[f64]) -> OptimizationResult
+    where
+        G: Fn(&[f64]) -> (f64, Vec<f64>),
+    {
+        self.minimize(objective, x0)
+    }
+
+    /// Projects a point onto the box.
+    fn project(&self, x: &mut [f64]) {
+        x.iter_mut()
+            .zip(&self.bounds)
+            .for_each(|(x, (lower, upper))| *x = x.clamp(*lower, *upper));
+    }
+
+    /// Outer iteration, given a function returning the value and gradient.
+    fn minimize<G>(&self, objective: G, x0: &[f64]) -> OptimizationResult
+    where
+        G: Fn(&[f64]) -> (f64, Vec<f64>),
+    {
+        assert_eq!(x0.len(), self.bounds.len());
+
+        let start = Instant::now();
+
+        let mut x = x0.to_vec();
+        self.project(&mut x);
+
+        let mut lambda = vec![0.0; self.equalities.len()];
+        let mut mu = vec![0.0; self.inequalities.len()];
+        let mut rho = 10.0;
+        let mut violation = self.violation(&x);
+
+        let mut evaluations = 0;
+        let mut converged = false;
+        let mut iterations = 0;
+        let mut value = f64::NAN;
+
+        while iterations < self.max_iterations {
+            iterations += 1;
+
+            // Augmented Lagrangian, its gradient, and the objective value.
+            let lagrangian = |x: &[f64]| {
+                let (value, mut gradient) = objective(x);
+                let mut augmented = value;
+
+                for ((a, b), lambda) in self.equalities.iter().zip(&lambda) {
+                    let h = dot(a, x) - b;
+                    augmented += lambda * h + 0.5 * rho * h * h;
+                    let weight = lambda + rho * h;
+                    gradient
+                        .iter_mut()
+                        .zip(a)
+                        .for_each(|(g, a)| *g += weight * a);
+                }
+
+                for ((c, d), mu) in self.inequalities.iter().zip(&mu) {
+                    let shifted = (mu + rho * (dot(c, x) - d)).max(0.0);
+                    augmented += (shifted * shifted - mu * mu) / (2.0 * rho);
+                    gradient
+                        .iter_mut()
+                        .zip(c)
+                        .for_each(|(g, c)| *g += shifted * c);
+                }
+
+                (augmented, gradient, value)
+            };
+
+            let (point, stationary) = self.spectral_projected_gradient(
+                &lagrangian,
+                x,
+                self.max_inner_iterations,
+                &mut evaluations,
+            );
+            x = point.0;
+            value = point.1;
+
+            let previous = violation;
+            violation = self.violation(&x);
+
+            if stationary && violation <= self.tolerance {
+                converged = true;
+                break;
+            }
+
+            for ((a, b), lambda) in self.equalities.iter().zip(lambda.iter_mut()) {
+                *lambda += rho * (dot(a, &x) - b);
+            }
+            for ((c, d), mu) in self.inequalities.iter().zip(mu.iter_mut()) {
+                *mu = (*mu + rho * (dot(c, &x) - d)).max(0.0);
+            }
+
+            if violation > 0.25 * previous {
+                rho = (10.0 * rho).min(1e12);
+            }
+        }
+
+        OptimizationResult {
+            minimizer: x,
+            minimum: value,
+            iterations,
+            evaluations,
+            converged,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    /// Spectral projected gradient method, with a non-monotone line search,
+    /// minimizing `lagrangian` over the box. Returns the minimizer and the
+    /// objective value there, and whether the projected gradient is below
+    /// the tolerance.
+    fn spectral_projected_gradient<L>(
+        &self,
+        lagrangian: &L,
+        mut x: Vec<f64>,
+        max_iterations: usize,
+        evaluations: &mut usize,
+    ) -> ((Vec<f64>, f64), bool)
+    where
+        L: Fn(&[f64]) -> (f64, Vec<f64>, f64),
+    {
+        // Number of past values for the non-monotone line search.
+        const MEMORY: usize = 10;
+        const GAMMA: f64 = 1e-4;
+        const STEP_MIN: f64 = 1e-12;
+        const STEP_MAX: f64 = 1e12;
+
+        let (mut augmented, mut gradient, mut value) = lagrangian(&x);
+        *evaluations += 1;
+
+        let mut history = VecDeque::from([augmented]);
+
+        // Projected gradient step P(x - t g) - x.
+        let projected_step = |x: &[f64], gradient: &[f64], t: f64| -> Vec<f64> {
+            let mut point: Vec<f64> = x.iter().zip(gradient).map(|(x, g)| x - t * g).collect();
+            self.project(&mut point);
+            point.iter_mut().zip(x).for_each(|(p, x)| *p -= x);
+            point
+        };
+        let norm = |v: &[f64]| v.iter().fold(0.0, |m: f64, v| m.max(v.abs()));
+
+        let mut step = {
+            let d = norm(&projected_step(&x, &gradient, 1.0));
+            if d > 0.0 {
+                (1.0 / d).clamp(STEP_MIN, STEP_MAX)
+            } else {
+                1.0
+            }
+        };
+
+        for _ in 0..max_iterations {
+            if norm(&projected_step(&x, &gradient, 1.0)) <= self.tolerance {
+                return ((x, value), true);
+            }
+
+            let direction = projected_step(&x, &gradient, step);
+            let slope = dot(&gradient, &direction);
+            let reference = history.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+            // Backtracking, with safeguarded quadratic interpolation.
+            let mut alpha = 1.0;
+            let (next, next_augmented, next_gradient, next_value) = loop {
+                let next: Vec<f64> = x
+                    .iter()
+                    .zip(&direction)
+                    .map(|(x, d)| x + alpha * d)
+                    .collect();
+                let (trial, trial_gradient, trial_value) = lagrangian(&next);
+                *evaluations += 1;
+
+                if trial <= reference + GAMMA * alpha * slope || alpha < STEP_MIN {
+                    break (next, trial, trial_gradient, trial_value);
+                }
+
+                let quadratic = -0.5 * alpha * alpha * slope / (trial - augmented - alpha * slope);
+                alpha = if quadratic.is_finite() && (0.1 * alpha..=0.5 * alpha).contains(&quadratic)
+                {
+                    quadratic
+                } else {
+                    0.5 * alpha
+                };
+            };
+
+            if alpha < STEP_MIN {
+                break;
+            }
+
+            let s: Vec<f64> = next.iter().zip(&x).map(|(a, b)| a - b).collect();
+            let y: Vec<f64> = next_gradient
+                .iter()
+                .zip(&gradient)
+                .map(|(a, b)| a - b)
+                .collect();
+            let sy = dot(&s, &y);
+
+            step = if sy > 0.0 {
+                (dot(&s, &s) / sy).clamp(STEP_MIN, STEP_MAX)
+            } else {
+                STEP_MAX
+            };
+
+            x = next;
+            augmented = next_augmented;
+            gradient = next_gradient;
+            value = next_value;
+
+            if history.len() == MEMORY {
+                history.pop_front();
+            }
+            history.push_back(augmented);
+        }
+
+        let stationary = norm(&projected_step(&x, &gradient, 1.0)) <= self.tolerance;
+        ((x, value), stationary)
+    }
+}
+
+impl Optimizer for AugmentedLagrangian {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult {
+        self.optimize_numerical(f, x0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_augmented_lagrangian {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_equality_constraint() {
+        // min x^2 + 2y^2 + 3z^2 s.t. x + y + z = 1: x = 6/11, y = 3/11, z = 2/11.
+        fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+            x[0] * x[0] + 2.0 * x[1] * x[1] + 3.0 * x[2] * x[2]
+        }
+
+        let result = AugmentedLagrangian::new(3)
+            .with_equality(vec![1.0; 3], 1.0)
+            .unwrap()
+            .optimize(f, &[0.0; 3]);
+
+        assert!(result.converged);
+        for (x, expected) in result.minimizer.iter().zip([6.0, 3.0, 2.0]) {
+            assert_approx_equal!(*x, expected / 11.0, 1e-8);
+        }
+        assert_approx_equal!(result.minimum, 6.0 / 11.0, 1e-8);
+    }
+
+    #[test]
+    fn test_inequality_constraints() {
+        // min (x - 2)^2 + (y - 2)^2 s.t. x + y <= 2 (active) and
+        // x - y <= 5 (inactive): minimum at (1, 1).
+        let f = |x: &[f64]| (x[0] - 2.0).powi(2) + (x[1] - 2.0).powi(2);
+
+        let problem = AugmentedLagrangian::new(2)
+            .with_inequality(vec![1.0, 1.0], 2.0)
+            .and_then(|problem| problem.with_inequality(vec![1.0, -1.0], 5.0))
+            .unwrap()
+            .with_tolerance(1e-7);
+        let result = problem.optimize_numerical(f, &[0.0, 0.0]);
+
+        assert!(result.converged);
+        assert!(problem.violation(&result.minimizer) <= 1e-7);
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-6);
+        assert_approx_equal!(result.minimizer[1], 1.0, 1e-6);
+    }
+
+    #[test]
+    fn test_bounds() {
+        // Rosenbrock function with x <= 0.5: minimum at (0.5, 0.25).
+        fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+            (1.0 - x[0]) * (1.0 - x[0]) + 100.0 * (x[1] - x[0] * x[0]) * (x[1] - x[0] * x[0])
+        }
+
+        let result = AugmentedLagrangian::new(2)
+            .with_bounds(vec![(-2.0, 0.5), (-2.0, 2.0)])
+            .unwrap()
+            .optimize(f, &[-1.2, 1.0]);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 0.5, 1e-12);
+        assert_approx_equal!(result.minimizer[1], 0.25, 1e-7);
+    }
+
+    #[test]
+    fn test_analytic_gradient() {
+        // Closest point to the origin on x + 2y = 5: (1, 2).
+        let objective = |x: &[f64]| (0.5 * dot(x, x), x.to_vec());
+
+        let result = AugmentedLagrangian::new(2)
+            .with_equality(vec![1.0, 2.0], 5.0)
+            .unwrap()
+            .optimize_with_gradient(objective, &[0.0, 0.0]);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-8);
+        assert_approx_equal!(result.minimizer[1], 2.0, 1e-8);
+    }
+
+    #[test]
+    fn test_long_only_markowitz() {
+        // Minimum variance with a target return: weights sum to one,
+        // non-negative, and mean return at least 10%. The fourth asset
+        // would be held without the target, and the first is only held
+        // with the bound on the fourth.
+        let covariance = [
+            [0.0400, 0.0060, 0.0020, 0.0000],
+            [0.0060, 0.0900, 0.0100, 0.0050],
+            [0.0020, 0.0100, 0.0625, 0.0150],
+            [0.0000, 0.0050, 0.0150, 0.0225],
+        ];
+        let means = [0.06, 0.12, 0.10, 0.05];
+
+        let variance = |w: &[f64]| {
+            (0..4)
+                .map(|j| (0..4).map(|k| covariance[j][k] * w[j] * w[k]).sum::<f64>())
+                .sum::<f64>()
+        };
+
+        let problem = AugmentedLagrangian::new(4)
+            .with_bounds(vec![(0.0, 1.0); 4])
+            .and_then(|problem| problem.with_equality(vec![1.0; 4], 1.0))
+            .and_then(|problem| problem.with_inequality(means.iter().map(|m| -m).collect(), -0.1))
+            .unwrap();
+        let result = problem.optimize_numerical(variance, &[0.25; 4]);
+
+        assert!(result.converged);
+        for (w, expected) in result.minimizer.iter().zip([
+            0.193_680_514_921_006_32,
+            0.387_361_029_842_013,
+            0.418_958_455_236_980_8,
+            0.0,
+        ]) {
+            assert_approx_equal!(*w, expected, 1e-6);
+        }
+        assert_approx_equal!(result.minimum, 0.030_445_874_780_573_44, 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_constraints() {
+        assert!(AugmentedLagrangian::new(2)
+            .with_bounds(vec![(0.0, 1.0)])
+            .is_err());
+        assert!(AugmentedLagrangian::new(2)
+            .with_bounds(vec![(0.0, 1.0), (1.0, 0.0)])
+            .is_err());
+        assert!(AugmentedLagrangian::new(2)
+            .with_equality(vec![1.0; 3], 1.0)
+            .is_err());
+    }
+}
@@ -0,0 +1,252 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Broyden-Fletcher-Goldfarb-Shanno (BFGS) quasi-Newton method.
+//!
+//! The search direction is $d_k = -H_k \nabla f(x_k)$, where $H_k$ is an
+//! approximation of the inverse Hessian, updated from the step
+//! $s_k = x_{k+1} - x_k$ and the change in gradient
+//! $y_k = \nabla f(x_{k+1}) - \nabla f(x_k)$:
+//!
+//! $$
+//! H_{k+1} = (I - \rho_k s_k y_k^T) H_k (I - \rho_k y_k s_k^T) + \rho_k s_k s_k^T
+//! \qquad \rho_k = \frac{1}{y_k^T s_k}
+//! $$
+//!
+//! The step length along $d_k$ satisfies the strong Wolfe conditions,
+//! which keeps $H_k$ positive definite.
+
+use super::line_search::{autodiff_gradient, finite_difference_gradient, wolfe_line_search};
+use super::optimizer::{OptimizationResult, Optimizer};
+use nalgebra::{DMatrix, DVector};
+use std::time::Instant;
+use RustQuant_autodiff::variable::Variable;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// BFGS optimizer.
+#[derive(Debug, Clone, Copy)]
+pub struct Bfgs {
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+
+    /// Tolerance for the norm of the gradient.
+    pub tolerance: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for Bfgs {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1000,
+            tolerance: f64::EPSILON.sqrt(),
+        }
+    }
+}
+
+impl Bfgs {
+    /// Returns a new BFGS optimizer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if tolerance is not positive.
+    #[must_use]
+    pub fn new(max_iterations: usize, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0);
+
+        Self {
+            max_iterations,
+            tolerance,
+        }
+    }
+
+    /// Minimizes a function, with gradients from automatic differentiation.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::autodiff::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// // Rosenbrock function, minimum at (1, 1).
+    /// fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+    ///     (1.0 - x[0]) * (1.0 - x[0]) + 100.0 * (x[1] - x[0] * x[0]) * (x[1] - x[0] * x[0])
+    /// }
+    ///
+    /// let result = Bfgs::default().optimize(f, &[-1.2, 1.0]);
+    ///
+    /// assert!(result.converged);
+    /// assert_approx_equal!(result.minimizer[0], 1.0, 1e-6);
+    /// assert_approx_equal!(result.minimizer[1], 1.0, 1e-6);
+    /// ```
+    pub fn optimize<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+    {
+        self.minimize(|x| autodiff_gradient(&f, x), x0)
+    }
+
+    /// Minimizes a function, with gradients from central finite differences
+    /// (each costs $2n$ extra function evaluations).
+    pub fn optimize_numerical<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let n = x0.len();
+        let mut result = self.minimize(|x| finite_difference_gradient(&f, x), x0);
+
+        result.evaluations *= 2 * n + 1;
+        result
+    }
+
+    /// BFGS iteration, given a function returning the value and gradient.
+    fn minimize<G>(&self, objective: G, x0: &[f64]) -> OptimizationResult
+    where
+        G: Fn(&[f64]) -> (f64, Vec<f64>),
+    {
+        let start = Instant::now();
+        let n = x0.len();
+
+        let mut x = x0.to_vec();
+        let (mut value, mut gradient) = objective(&x);
+        let mut evaluations = 1;
+        let mut inverse_hessian = DMatrix::<f64>::identity(n, n);
+        let mut first_step = true;
+
+        let mut converged = false;
+        let mut iterations = 0;
+
+        while iterations < self.max_iterations {
+            if DVector::from_column_slice(&gradient).norm() < self.tolerance {
+                converged = true;
+                break;
+            }
+
+            iterations += 1;
+
+            let g = DVector::from_column_slice(&gradient);
+            let direction = -&inverse_hessian * &g;
+
+            let Some(point) = wolfe_line_search(
+                &objective,
+                &x,
+                value,
+                &gradient,
+                direction.as_slice(),
+                &mut evaluations,
+            ) else {
+                break;
+            };
+
+            let s = direction * point.alpha;
+            let y = DVector::from_column_slice(&point.gradient) - g;
+            let sy = s.dot(&y);
+
+            if sy > f64::EPSILON * s.norm() * y.norm() {
+                if first_step {
+                    // Scale the initial approximation to the curvature seen so far.
+                    inverse_hessian *= sy / y.dot(&y);
+                    first_step = false;
+                }
+
+                let rho = 1.0 / sy;
+                let hy = &inverse_hessian * &y;
+
+                // Expanded form of the update, with H symmetric.
+                inverse_hessian += (&s * s.transpose()) * (rho + rho * rho * y.dot(&hy))
+                    - (&hy * s.transpose() + &s * hy.transpose()) * rho;
+            }
+
+            x.iter_mut().zip(s.iter()).for_each(|(x, s)| *x += s);
+            value = point.value;
+            gradient = point.gradient;
+        }
+
+        OptimizationResult {
+            minimizer: x,
+            minimum: value,
+            iterations,
+            evaluations,
+            converged,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl Optimizer for Bfgs {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult {
+        self.optimize_numerical(f, x0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bfgs {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn rosenbrock<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+        (1.0 - x[0]) * (1.0 - x[0]) + 100.0 * (x[1] - x[0] * x[0]) * (x[1] - x[0] * x[0])
+    }
+
+    #[test]
+    fn test_bfgs_rosenbrock() {
+        let result = Bfgs::default().optimize(rosenbrock, &[-1.2, 1.0]);
+
+        assert!(result.converged);
+        assert!(result.iterations < 100);
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-7);
+        assert_approx_equal!(result.minimizer[1], 1.0, 1e-7);
+        assert_approx_equal!(result.minimum, 0.0, 1e-14);
+    }
+
+    #[test]
+    fn test_bfgs_numerical() {
+        // Himmelblau's function, minimum at (3, 2) from this start.
+        let f =
+            |x: &[f64]| (x[0] * x[0] + x[1] - 11.0).powi(2) + (x[0] + x[1] * x[1] - 7.0).powi(2);
+
+        let result = Bfgs::new(200, 1e-7).optimize_numerical(f, &[1.0, 1.0]);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 3.0, 1e-7);
+        assert_approx_equal!(result.minimizer[1], 2.0, 1e-7);
+    }
+
+    #[test]
+    fn test_bfgs_quadratic() {
+        // Convex quadratic: 1/2 x'Ax - b'x, minimum at A^{-1} b = (1, -1, 2).
+        fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+            0.5 * (4.0 * x[0] * x[0] + 3.0 * x[1] * x[1] + 2.0 * x[2] * x[2]) + x[0] * x[1]
+                - (3.0 * x[0] - 2.0 * x[1] + 4.0 * x[2])
+        }
+
+        let result = Bfgs::default().optimize(f, &[0.0, 0.0, 0.0]);
+
+        assert!(result.converged);
+        for (x, expected) in result.minimizer.iter().zip([1.0, -1.0, 2.0]) {
+            assert_approx_equal!(*x, expected, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bfgs_max_iterations() {
+        let result = Bfgs::new(3, 1e-12).optimize(rosenbrock, &[-1.2, 1.0]);
+
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 3);
+    }
+}
@@ -0,0 +1,332 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Differential evolution (Storn and Price, 1997).
+//!
+//! A population of points in the box is evolved: for each member $x_i$, a
+//! mutant $v = x_{r_1} + F (x_{r_2} - x_{r_3})$ is built from three other
+//! random members, crossed over with $x_i$ coordinate by coordinate with
+//! probability $CR$, and the trial point replaces $x_i$ if it is no worse
+//! (the DE/rand/1/bin scheme). The population explores the whole box, so
+//! it is not trapped by the local minima of multi-modal objectives.
+
+use super::optimizer::{
+    check_bounds, clamp_to_bounds, OptimizationResult, Optimizer, StoppingCriteria,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::time::Instant;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Differential evolution optimizer, with box constraints.
+#[derive(Debug, Clone)]
+pub struct DifferentialEvolution {
+    /// Lower and upper bound of each parameter.
+    pub bounds: Vec<(f64, f64)>,
+
+    /// Number of members of the population.
+    pub population_size: usize,
+
+    /// Differential weight $F \in (0, 2]$.
+    pub mutation: f64,
+
+    /// Crossover probability $CR \in [0, 1]$.
+    pub crossover: f64,
+
+    /// Stopping criteria. The tolerance is for the spread of the function
+    /// values over the population.
+    pub stopping: StoppingCriteria,
+
+    /// Seed of the random number generator (from entropy if `None`).
+    pub seed: Option<u64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DifferentialEvolution {
+    /// Returns a new differential evolution optimizer over the box `bounds`,
+    /// with a population of $\max(15 n, 10)$ members, $F = 0.8$ and
+    /// $CR = 0.9$.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds are empty, not finite, or a lower
+    /// bound is not below its upper bound.
+    pub fn new(bounds: Vec<(f64, f64)>) -> Result<Self, RustQuantError> {
+        check_bounds(&bounds)?;
+
+        Ok(Self {
+            population_size: (15 * bounds.len()).max(10),
+            bounds,
+            mutation: 0.8,
+            crossover: 0.9,
+            stopping: StoppingCriteria::default(),
+            seed: None,
+        })
+    }
+
+    /// Set the population size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the population has fewer than four members.
+    #[must_use]
+    pub fn with_population_size(mut self, population_size: usize) -> Self {
+        assert!(population_size >= 4);
+
+        self.population_size = population_size;
+        self
+    }
+
+    /// Set the differential weight and crossover probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is not in $(0, 2]$ or the probability not in
+    /// $[0, 1]$.
+    #[must_use]
+    pub fn with_strategy(mut self, mutation: f64, crossover: f64) -> Self {
+        assert!(mutation > 0.0 && mutation <= 2.0);
+        assert!((0.0..=1.0).contains(&crossover));
+
+        self.mutation = mutation;
+        self.crossover = crossover;
+        self
+    }
+
+    /// Set the stopping criteria.
+    #[must_use]
+    pub fn with_stopping_criteria(mut self, stopping: StoppingCriteria) -> Self {
+        self.stopping = stopping;
+        self
+    }
+
+    /// Set the seed of the random number generator.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Minimizes a function over the box. The initial guess `x0`, if not
+    /// empty, is projected onto the box and included in the initial
+    /// population, which is otherwise uniform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x0` is neither empty nor of the dimension of the bounds.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use std::f64::consts::PI;
+    ///
+    /// // Rastrigin function: many local minima, global minimum at the origin.
+    /// let f = |x: &[f64]| {
+    ///     10.0 * x.len() as f64
+    ///         + x.iter().map(|x| x * x - 10.0 * (2.0 * PI * x).cos()).sum::<f64>()
+    /// };
+    ///
+    /// let result = DifferentialEvolution::new(vec![(-5.12, 5.12); 2])
+    ///     .unwrap()
+    ///     .with_seed(42)
+    ///     .optimize(f, &[3.0, -2.0]);
+    ///
+    /// assert!(result.converged);
+    /// assert_approx_equal!(result.minimizer[0], 0.0, 1e-4);
+    /// assert_approx_equal!(result.minimizer[1], 0.0, 1e-4);
+    /// ```
+    pub fn optimize<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let start = Instant::now();
+        let n = self.bounds.len();
+        assert!(x0.is_empty() || x0.len() == n);
+
+        let size = self.population_size.max(4);
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let mut population: Vec<Vec<f64>> = (0..size)
+            .map(|_| {
+                self.bounds
+                    .iter()
+                    .map(|(lower, upper)| rng.gen_range(*lower..*upper))
+                    .collect()
+            })
+            .collect();
+
+        if !x0.is_empty() {
+            population[0] = x0.to_vec();
+            clamp_to_bounds(&mut population[0], &self.bounds);
+        }
+
+        let mut values: Vec<f64> = population.iter().map(|x| f(x)).collect();
+        let mut evaluations = size;
+        let mut converged = false;
+        let mut iterations = 0;
+
+        while iterations < self.stopping.max_iterations {
+            let (lowest, highest) = values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                    (lo.min(*v), hi.max(*v))
+                });
+
+            if highest - lowest <= self.stopping.tolerance || self.stopping.reached(lowest) {
+                converged = true;
+                break;
+            }
+
+            if self.stopping.exhausted(evaluations, start.elapsed()) {
+                break;
+            }
+
+            iterations += 1;
+
+            for i in 0..size {
+                // Three distinct members, other than i.
+                let mut picks = [i; 3];
+                for k in 0..3 {
+                    while picks[k] == i || picks[..k].contains(&picks[k]) {
+                        picks[k] = rng.gen_range(0..size);
+                    }
+                }
+                let [a, b, c] = picks;
+
+                // At least one coordinate comes from the mutant.
+                let forced = rng.gen_range(0..n);
+                let mut trial: Vec<f64> = (0..n)
+                    .map(|j| {
+                        if j == forced || rng.gen::<f64>() < self.crossover {
+                            population[a][j] + self.mutation * (population[b][j] - population[c][j])
+                        } else {
+                            population[i][j]
+                        }
+                    })
+                    .collect();
+                clamp_to_bounds(&mut trial, &self.bounds);
+
+                let value = f(&trial);
+                evaluations += 1;
+
+                if value <= values[i] {
+                    population[i] = trial;
+                    values[i] = value;
+                }
+            }
+        }
+
+        let index = (0..size)
+            .min_by(|a, b| values[*a].total_cmp(&values[*b]))
+            .unwrap_or(0);
+
+        OptimizationResult {
+            minimizer: population.swap_remove(index),
+            minimum: values[index],
+            iterations,
+            evaluations,
+            converged,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl Optimizer for DifferentialEvolution {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult {
+        self.optimize(f, x0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_differential_evolution {
+    use super::*;
+    use std::f64::consts::{E, PI};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_differential_evolution_ackley() {
+        // Ackley function: global minimum 0 at the origin.
+        let f = |x: &[f64]| {
+            let n = x.len() as f64;
+            let squares = x.iter().map(|x| x * x).sum::<f64>() / n;
+            let cosines = x.iter().map(|x| (2.0 * PI * x).cos()).sum::<f64>() / n;
+
+            -20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20.0 + E
+        };
+
+        let result = DifferentialEvolution::new(vec![(-5.0, 5.0); 3])
+            .unwrap()
+            .with_seed(7)
+            .optimize(f, &[]);
+
+        assert!(result.converged);
+        for x in &result.minimizer {
+            assert_approx_equal!(*x, 0.0, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_differential_evolution_bounds() {
+        // Unconstrained minimum at (-3, 4), outside the box.
+        let f = |x: &[f64]| (x[0] + 3.0).powi(2) + (x[1] - 4.0).powi(2);
+
+        let result = DifferentialEvolution::new(vec![(-1.0, 1.0), (0.0, 2.0)])
+            .unwrap()
+            .with_seed(1)
+            .optimize(f, &[10.0, 10.0]);
+
+        assert_approx_equal!(result.minimizer[0], -1.0, 1e-8);
+        assert_approx_equal!(result.minimizer[1], 2.0, 1e-8);
+    }
+
+    #[test]
+    fn test_differential_evolution_stopping_criteria() {
+        let f = |x: &[f64]| x[0] * x[0] + x[1] * x[1];
+        let optimizer = DifferentialEvolution::new(vec![(-1.0, 1.0); 2])
+            .unwrap()
+            .with_population_size(10)
+            .with_seed(3);
+
+        let result = optimizer
+            .clone()
+            .with_stopping_criteria(StoppingCriteria::default().with_max_evaluations(50))
+            .optimize(f, &[]);
+
+        assert!(!result.converged);
+        assert_eq!(result.evaluations, 50);
+
+        let result = optimizer
+            .with_stopping_criteria(StoppingCriteria::default().with_target(1e-2))
+            .optimize(f, &[]);
+
+        assert!(result.converged);
+        assert!(result.minimum <= 1e-2);
+    }
+
+    #[test]
+    fn test_differential_evolution_invalid_bounds() {
+        assert!(DifferentialEvolution::new(vec![]).is_err());
+        assert!(DifferentialEvolution::new(vec![(1.0, 0.0)]).is_err());
+        assert!(DifferentialEvolution::new(vec![(0.0, f64::INFINITY)]).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Limited memory BFGS (L-BFGS) quasi-Newton method.
+//!
+//! Instead of the $n \times n$ inverse Hessian approximation of BFGS, only
+//! the last $m$ steps and gradient changes $(s_k, y_k)$ are kept, and the
+//! product $H_k \nabla f(x_k)$ is computed from them with the two-loop
+//! recursion. Memory and work per iteration are $O(mn)$, so it suits
+//! problems with many parameters.
+
+use super::line_search::{autodiff_gradient, finite_difference_gradient, wolfe_line_search};
+use super::optimizer::{OptimizationResult, Optimizer};
+use std::collections::VecDeque;
+use std::time::Instant;
+use RustQuant_autodiff::variable::Variable;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// L-BFGS optimizer.
+#[derive(Debug, Clone, Copy)]
+pub struct Lbfgs {
+    /// Number of past steps kept.
+    pub memory: usize,
+
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+
+    /// Tolerance for the norm of the gradient.
+    pub tolerance: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for Lbfgs {
+    fn default() -> Self {
+        Self {
+            memory: 10,
+            max_iterations: 1000,
+            tolerance: f64::EPSILON.sqrt(),
+        }
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+impl Lbfgs {
+    /// Returns a new L-BFGS optimizer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if memory is zero or tolerance is not positive.
+    #[must_use]
+    pub fn new(memory: usize, max_iterations: usize, tolerance: f64) -> Self {
+        assert!(memory > 0);
+        assert!(tolerance > 0.0);
+
+        Self {
+            memory,
+            max_iterations,
+            tolerance,
+        }
+    }
+
+    /// Minimizes a function, with gradients from automatic differentiation.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::autodiff::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// // Sum of squares, minimum at (1, 2, ..., 20).
+    /// fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+    ///     let mut sum = (x[0] - 1.0) * (x[0] - 1.0);
+    ///
+    ///     for k in 1..x.len() {
+    ///         let target = (k + 1) as f64;
+    ///         sum = sum + (x[k] - target) * (x[k] - target);
+    ///     }
+    ///
+    ///     sum
+    /// }
+    ///
+    /// let result = Lbfgs::default().optimize(f, &[0.0; 20]);
+    ///
+    /// assert!(result.converged);
+    /// assert_approx_equal!(result.minimizer[19], 20.0, 1e-8);
+    /// ```
+    pub fn optimize<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+    {
+        self.minimize(|x| autodiff_gradient(&f, x), x0)
+    }
+
+    /// Minimizes a function, with gradients from central finite differences
+    /// (each costs $2n$ extra function evaluations).
+    pub fn optimize_numerical<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let n = x0.len();
+        let mut result = self.minimize(|x| finite_difference_gradient(&f, x), x0);
+
+        result.evaluations *= 2 * n + 1;
+        result
+    }
+
+    /// Two-loop recursion: the search direction $-H_k \nabla f(x_k)$.
+    fn direction(history: &VecDeque<(Vec<f64>, Vec<f64>, f64)>, gradient: &[f64]) -> Vec<f64> {
+        let mut q = gradient.to_vec();
+        let mut alphas = Vec::with_capacity(history.len());
+
+        for (s, y, rho) in history.iter().rev() {
+            let alpha = rho * dot(s, &q);
+            q.iter_mut().zip(y).for_each(|(q, y)| *q -= alpha * y);
+            alphas.push(alpha);
+        }
+
+        // Initial inverse Hessian: gamma I, from the latest step.
+        if let Some((s, y, _)) = history.back() {
+            let gamma = dot(s, y) / dot(y, y);
+            q.iter_mut().for_each(|q| *q *= gamma);
+        }
+
+        for ((s, y, rho), alpha) in history.iter().zip(alphas.iter().rev()) {
+            let beta = rho * dot(y, &q);
+            q.iter_mut()
+                .zip(s)
+                .for_each(|(q, s)| *q += (alpha - beta) * s);
+        }
+
+        q.iter().map(|q| -q).collect()
+    }
+
+    /// L-BFGS iteration, given a function returning the value and gradient.
+    fn minimize<G>(&self, objective: G, x0: &[f64]) -> OptimizationResult
+    where
+        G: Fn(&[f64]) -> (f64, Vec<f64>),
+    {
+        let start = Instant::now();
+
+        let mut x = x0.to_vec();
+        let (mut value, mut gradient) = objective(&x);
+        let mut evaluations = 1;
+        let mut history = VecDeque::with_capacity(self.memory);
+
+        let mut converged = false;
+        let mut iterations = 0;
+
+        while iterations < self.max_iterations {
+            if dot(&gradient, &gradient).sqrt() < self.tolerance {
+                converged = true;
+                break;
+            }
+
+            iterations += 1;
+
+            let direction = Self::direction(&history, &gradient);
+
+            let Some(point) = wolfe_line_search(
+                &objective,
+                &x,
+                value,
+                &gradient,
+                &direction,
+                &mut evaluations,
+            ) else {
+                break;
+            };
+
+            let s: Vec<f64> = direction.iter().map(|d| point.alpha * d).collect();
+            let y: Vec<f64> = point
+                .gradient
+                .iter()
+                .zip(&gradient)
+                .map(|(a, b)| a - b)
+                .collect();
+            let sy = dot(&s, &y);
+
+            if sy > f64::EPSILON * dot(&s, &s).sqrt() * dot(&y, &y).sqrt() {
+                if history.len() == self.memory {
+                    history.pop_front();
+                }
+                history.push_back((s.clone(), y, 1.0 / sy));
+            }
+
+            x.iter_mut().zip(&s).for_each(|(x, s)| *x += s);
+            value = point.value;
+            gradient = point.gradient;
+        }
+
+        OptimizationResult {
+            minimizer: x,
+            minimum: value,
+            iterations,
+            evaluations,
+            converged,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl Optimizer for Lbfgs {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult {
+        self.optimize_numerical(f, x0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_lbfgs {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_lbfgs_extended_rosenbrock() {
+        // Sum of n - 1 coupled Rosenbrock terms, minimum at (1, ..., 1).
+        fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+            x.windows(2)
+                .map(|w| {
+                    (1.0 - w[0]) * (1.0 - w[0])
+                        + 100.0 * (w[1] - w[0] * w[0]) * (w[1] - w[0] * w[0])
+                })
+                .reduce(|a, b| a + b)
+                .unwrap()
+        }
+
+        let x0: Vec<f64> = (0..10)
+            .map(|i| if i % 2 == 0 { -1.2 } else { 1.0 })
+            .collect();
+        let result = Lbfgs::new(5, 1000, 1e-9).optimize(f, &x0);
+
+        assert!(result.converged);
+        for x in &result.minimizer {
+            assert_approx_equal!(*x, 1.0, 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_lbfgs_numerical() {
+        // Beale's function, minimum at (3, 0.5).
+        let f = |x: &[f64]| {
+            (1.5 - x[0] + x[0] * x[1]).powi(2)
+                + (2.25 - x[0] + x[0] * x[1] * x[1]).powi(2)
+                + (2.625 - x[0] + x[0] * x[1].powi(3)).powi(2)
+        };
+
+        let result = Lbfgs::new(5, 500, 1e-7).optimize_numerical(f, &[1.0, 1.0]);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 3.0, 1e-6);
+        assert_approx_equal!(result.minimizer[1], 0.5, 1e-6);
+    }
+}
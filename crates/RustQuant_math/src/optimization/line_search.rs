@@ -0,0 +1,276 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Line search and gradients for the quasi-Newton methods.
+//!
+//! The line search finds a step length $\alpha$ along a descent direction
+//! $d$ satisfying the strong Wolfe conditions:
+//!
+//! $$
+//! f(x + \alpha d) \leq f(x) + c_1 \alpha \nabla f(x)^T d
+//! \qquad
+//! |\nabla f(x + \alpha d)^T d| \leq c_2 |\nabla f(x)^T d|
+//! $$
+//!
+//! (Nocedal and Wright, Numerical Optimization, algorithms 3.5 and 3.6.)
+
+use RustQuant_autodiff::{variable::Variable, Accumulate, Gradient, Graph};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sufficient decrease constant.
+const C1: f64 = 1e-4;
+
+/// Curvature constant.
+const C2: f64 = 0.9;
+
+/// A point of the line search: step length, function value, gradient,
+/// and directional derivative.
+pub(crate) struct LinePoint {
+    pub(crate) alpha: f64,
+    pub(crate) value: f64,
+    pub(crate) gradient: Vec<f64>,
+    slope: f64,
+}
+
+/// Value and gradient of a function by reverse mode automatic differentiation.
+pub(crate) fn autodiff_gradient<F>(f: &F, x: &[f64]) -> (f64, Vec<f64>)
+where
+    F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+{
+    let graph = Graph::new();
+    let location = graph.vars(x);
+    let function = f(&location);
+    let gradient = function.accumulate().wrt(&location);
+
+    (function.value, gradient)
+}
+
+/// Value and gradient of a function by central finite differences.
+pub(crate) fn finite_difference_gradient<F>(f: &F, x: &[f64]) -> (f64, Vec<f64>)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let mut point = x.to_vec();
+
+    let gradient = (0..x.len())
+        .map(|i| {
+            let h = f64::EPSILON.cbrt() * x[i].abs().max(1.0);
+
+            point[i] = x[i] + h;
+            let up = f(&point);
+            point[i] = x[i] - h;
+            let down = f(&point);
+            point[i] = x[i];
+
+            (up - down) / (2.0 * h)
+        })
+        .collect();
+
+    (f(x), gradient)
+}
+
+/// Step along `direction` from `x` satisfying the strong Wolfe conditions.
+///
+/// Returns `None` if the direction is not a descent direction, or no step
+/// decreasing the function was found.
+pub(crate) fn wolfe_line_search<G>(
+    objective: &G,
+    x: &[f64],
+    value: f64,
+    gradient: &[f64],
+    direction: &[f64],
+    evaluations: &mut usize,
+) -> Option<LinePoint>
+where
+    G: Fn(&[f64]) -> (f64, Vec<f64>),
+{
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+
+    let origin = LinePoint {
+        alpha: 0.0,
+        value,
+        gradient: gradient.to_vec(),
+        slope: dot(gradient, direction),
+    };
+
+    if origin.slope >= 0.0 || !origin.slope.is_finite() {
+        return None;
+    }
+
+    let evaluate = |alpha: f64, evaluations: &mut usize| {
+        let point: Vec<f64> = x
+            .iter()
+            .zip(direction)
+            .map(|(x, d)| x + alpha * d)
+            .collect();
+        let (value, gradient) = objective(&point);
+        let slope = dot(&gradient, direction);
+        *evaluations += 1;
+
+        LinePoint {
+            alpha,
+            value,
+            gradient,
+            slope,
+        }
+    };
+
+    let armijo = |p: &LinePoint| p.value <= origin.value + C1 * p.alpha * origin.slope;
+    let curvature = |p: &LinePoint| p.slope.abs() <= -C2 * origin.slope;
+
+    // Bracketing phase: increase the step until it brackets a Wolfe point.
+    let mut previous = LinePoint {
+        gradient: origin.gradient.clone(),
+        ..origin
+    };
+    let mut alpha = 1.0;
+
+    for i in 0..50 {
+        let current = evaluate(alpha, evaluations);
+
+        if !current.value.is_finite() {
+            // Overshot into a region where the function is not defined.
+            alpha = 0.5 * (previous.alpha + alpha);
+            continue;
+        }
+
+        if !armijo(&current) || (i > 0 && current.value >= previous.value) {
+            return zoom(
+                &evaluate,
+                &armijo,
+                &curvature,
+                previous,
+                current,
+                evaluations,
+            );
+        }
+
+        if curvature(&current) {
+            return Some(current);
+        }
+
+        if current.slope >= 0.0 {
+            return zoom(
+                &evaluate,
+                &armijo,
+                &curvature,
+                current,
+                previous,
+                evaluations,
+            );
+        }
+
+        previous = current;
+        alpha *= 2.0;
+    }
+
+    (previous.alpha > 0.0).then_some(previous)
+}
+
+/// Zoom phase: shrink the bracket [lo, hi] until a Wolfe point is found.
+/// `lo` always satisfies the sufficient decrease condition.
+fn zoom<E, A, C>(
+    evaluate: &E,
+    armijo: &A,
+    curvature: &C,
+    mut lo: LinePoint,
+    mut hi: LinePoint,
+    evaluations: &mut usize,
+) -> Option<LinePoint>
+where
+    E: Fn(f64, &mut usize) -> LinePoint,
+    A: Fn(&LinePoint) -> bool,
+    C: Fn(&LinePoint) -> bool,
+{
+    for _ in 0..50 {
+        // Minimiser of the quadratic through lo (value and slope) and hi,
+        // kept away from the ends of the bracket, else bisection.
+        let width = hi.alpha - lo.alpha;
+        let denominator = 2.0 * (hi.value - lo.value - lo.slope * width);
+        let mut alpha = lo.alpha - lo.slope * width * width / denominator;
+
+        let (a, b) = (lo.alpha.min(hi.alpha), lo.alpha.max(hi.alpha));
+        if !alpha.is_finite() || alpha < a + 0.1 * (b - a) || alpha > b - 0.1 * (b - a) {
+            alpha = 0.5 * (a + b);
+        }
+
+        let current = evaluate(alpha, evaluations);
+
+        if !armijo(&current) || current.value >= lo.value {
+            hi = current;
+        } else {
+            if curvature(&current) {
+                return Some(current);
+            }
+
+            if current.slope * (hi.alpha - lo.alpha) >= 0.0 {
+                hi = lo;
+            }
+
+            lo = current;
+        }
+
+        if (hi.alpha - lo.alpha).abs() <= f64::EPSILON * lo.alpha.abs().max(1.0) {
+            break;
+        }
+    }
+
+    // No Wolfe point, but lo still decreases the function.
+    (lo.alpha > 0.0).then_some(lo)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_line_search {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_gradients() {
+        let f = |x: &[f64]| x[0] * x[0] * x[1] + x[1].exp();
+        fn g<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+            x[0] * x[0] * x[1] + x[1].exp()
+        }
+
+        let (value, numerical) = finite_difference_gradient(&f, &[1.5, 0.5]);
+        let (_, exact) = autodiff_gradient(&g, &[1.5, 0.5]);
+
+        assert_approx_equal!(value, 1.125 + 0.5_f64.exp(), 1e-15);
+        assert_approx_equal!(exact[0], 1.5, 1e-15);
+        assert_approx_equal!(exact[1], 2.25 + 0.5_f64.exp(), 1e-15);
+
+        for (numerical, exact) in numerical.iter().zip(&exact) {
+            assert_approx_equal!(numerical, exact, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_wolfe_conditions() {
+        // f(x) = (x - 3)^2 from 0, along d = 1 (slope -6).
+        let objective = |x: &[f64]| ((x[0] - 3.0).powi(2), vec![2.0 * (x[0] - 3.0)]);
+        let mut evaluations = 0;
+
+        let point =
+            wolfe_line_search(&objective, &[0.0], 9.0, &[-6.0], &[1.0], &mut evaluations).unwrap();
+
+        assert!(point.value <= 9.0 - C1 * point.alpha * 6.0);
+        assert!(point.gradient[0].abs() <= C2 * 6.0);
+        assert!(evaluations > 0);
+
+        // Not a descent direction.
+        let result = wolfe_line_search(&objective, &[0.0], 9.0, &[-6.0], &[-1.0], &mut evaluations);
+        assert!(result.is_none());
+    }
+}
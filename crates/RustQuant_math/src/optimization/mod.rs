@@ -10,3 +10,34 @@
 /// Gradient descent method.
 pub mod gradient_descent;
 pub use gradient_descent::*;
+
+/// Nelder-Mead (downhill simplex) method.
+pub mod nelder_mead;
+pub use nelder_mead::*;
+
+/// BFGS quasi-Newton method.
+pub mod bfgs;
+pub use bfgs::*;
+
+/// Limited memory BFGS quasi-Newton method.
+pub mod lbfgs;
+pub use lbfgs::*;
+
+/// Differential evolution global optimizer.
+pub mod differential_evolution;
+pub use differential_evolution::*;
+
+/// Simulated annealing global optimizer.
+pub mod simulated_annealing;
+pub use simulated_annealing::*;
+
+/// Augmented Lagrangian method for box and linear constraints.
+pub mod augmented_lagrangian;
+pub use augmented_lagrangian::*;
+
+/// Line search and gradients for the quasi-Newton methods.
+mod line_search;
+
+/// Types shared by the optimizers.
+pub mod optimizer;
+pub use optimizer::*;
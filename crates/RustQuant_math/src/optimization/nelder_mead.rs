@@ -0,0 +1,269 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Nelder-Mead (downhill simplex) method.
+//!
+//! A derivative-free method for minimizing a function $f: \mathbb{R}^n
+//! \rightarrow \mathbb{R}$. A simplex of $n + 1$ points is moved towards
+//! the minimum by reflecting, expanding and contracting its worst point
+//! through the centroid of the others, or shrinking it towards its best
+//! point. It only needs function values, so it suits noisy or
+//! non-differentiable objectives, such as calibration errors of models
+//! priced by simulation.
+
+use super::optimizer::{OptimizationResult, Optimizer};
+use std::time::Instant;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Nelder-Mead optimizer.
+#[derive(Debug, Clone, Copy)]
+pub struct NelderMead {
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+
+    /// Tolerance for the spread of the function values over the simplex,
+    /// and for the size of the simplex.
+    pub tolerance: f64,
+
+    /// Size of the initial simplex, relative to the initial guess
+    /// (absolute for its zero coordinates).
+    pub initial_step: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        Self {
+            max_iterations: 10_000,
+            tolerance: 1e-10,
+            initial_step: 0.05,
+        }
+    }
+}
+
+impl NelderMead {
+    /// Returns a new Nelder-Mead optimizer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if tolerance or initial step is not positive.
+    #[must_use]
+    pub fn new(max_iterations: usize, tolerance: f64, initial_step: f64) -> Self {
+        assert!(tolerance > 0.0);
+        assert!(initial_step > 0.0);
+
+        Self {
+            max_iterations,
+            tolerance,
+            initial_step,
+        }
+    }
+
+    /// Minimizes a function, starting from a simplex around `x0`.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    ///
+    /// // Himmelblau's function, minimum at (3, 2) from this start.
+    /// let f = |x: &[f64]| (x[0] * x[0] + x[1] - 11.0).powi(2) + (x[0] + x[1] * x[1] - 7.0).powi(2);
+    ///
+    /// let result = NelderMead::default().optimize(f, &[1.0, 1.0]);
+    ///
+    /// assert!(result.converged);
+    /// assert_approx_equal!(result.minimizer[0], 3.0, 1e-5);
+    /// assert_approx_equal!(result.minimizer[1], 2.0, 1e-5);
+    /// ```
+    pub fn optimize<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let start = Instant::now();
+        let n = x0.len();
+
+        // Reflection, expansion, contraction and shrink coefficients, adapted
+        // to the dimension (Gao and Han, 2012); the standard ones for n = 2.
+        let dimension = n.max(2) as f64;
+        let (alpha, gamma, rho, sigma) = (
+            1.0,
+            1.0 + 2.0 / dimension,
+            0.75 - 0.5 / dimension,
+            1.0 - 1.0 / dimension,
+        );
+
+        let mut simplex: Vec<(Vec<f64>, f64)> = (0..=n)
+            .map(|i| {
+                let mut point = x0.to_vec();
+                if i > 0 {
+                    let x = &mut point[i - 1];
+                    *x += if *x == 0.0 {
+                        self.initial_step
+                    } else {
+                        self.initial_step * *x
+                    };
+                }
+                let value = f(&point);
+                (point, value)
+            })
+            .collect();
+
+        let mut evaluations = n + 1;
+        let mut converged = false;
+        let mut iterations = 0;
+
+        // Point on the line from the centroid through the worst point.
+        let along = |centroid: &[f64], worst: &[f64], t: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(worst)
+                .map(|(c, w)| c + t * (c - w))
+                .collect()
+        };
+
+        while iterations < self.max_iterations {
+            simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+            let best = &simplex[0];
+            let spread = (simplex[n].1 - best.1).abs();
+            let size = simplex[1..]
+                .iter()
+                .flat_map(|(x, _)| x.iter().zip(&best.0).map(|(x, b)| (x - b).abs()))
+                .fold(0.0, f64::max);
+
+            if spread <= self.tolerance && size <= self.tolerance {
+                converged = true;
+                break;
+            }
+
+            iterations += 1;
+
+            let centroid: Vec<f64> = (0..n)
+                .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+                .collect();
+            let (worst, worst_value) = simplex[n].clone();
+
+            let reflected = along(&centroid, &worst, alpha);
+            let reflected_value = f(&reflected);
+            evaluations += 1;
+
+            if reflected_value < simplex[0].1 {
+                let expanded = along(&centroid, &worst, alpha * gamma);
+                let expanded_value = f(&expanded);
+                evaluations += 1;
+
+                simplex[n] = if expanded_value < reflected_value {
+                    (expanded, expanded_value)
+                } else {
+                    (reflected, reflected_value)
+                };
+                continue;
+            }
+
+            if reflected_value < simplex[n - 1].1 {
+                simplex[n] = (reflected, reflected_value);
+                continue;
+            }
+
+            // Contract outside the simplex if the reflection improved on
+            // the worst point, else inside.
+            let (contracted, target) = if reflected_value < worst_value {
+                (along(&centroid, &worst, alpha * rho), reflected_value)
+            } else {
+                (along(&centroid, &worst, -rho), worst_value)
+            };
+            let contracted_value = f(&contracted);
+            evaluations += 1;
+
+            if contracted_value < target {
+                simplex[n] = (contracted, contracted_value);
+                continue;
+            }
+
+            // Shrink towards the best point.
+            let best = simplex[0].0.clone();
+            for (x, value) in simplex.iter_mut().skip(1) {
+                x.iter_mut()
+                    .zip(&best)
+                    .for_each(|(x, b)| *x = b + sigma * (*x - b));
+                *value = f(x);
+            }
+            evaluations += n;
+        }
+
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (minimizer, minimum) = simplex.swap_remove(0);
+
+        OptimizationResult {
+            minimizer,
+            minimum,
+            iterations,
+            evaluations,
+            converged,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl Optimizer for NelderMead {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult {
+        self.optimize(f, x0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_nelder_mead {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_nelder_mead_rosenbrock() {
+        let f = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+
+        let result = NelderMead::default().optimize(f, &[-1.2, 1.0]);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-5);
+        assert_approx_equal!(result.minimizer[1], 1.0, 1e-5);
+        assert!(result.minimum < 1e-10);
+    }
+
+    #[test]
+    fn test_nelder_mead_non_smooth() {
+        // |x - 1| + 2|y + 0.5| + |z|: no gradient at the minimum.
+        let f = |x: &[f64]| (x[0] - 1.0).abs() + 2.0 * (x[1] + 0.5).abs() + x[2].abs();
+
+        let result = NelderMead::default().optimize(f, &[0.0, 0.0, 3.0]);
+
+        assert!(result.converged);
+        for (x, expected) in result.minimizer.iter().zip([1.0, -0.5, 0.0]) {
+            assert_approx_equal!(*x, expected, 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_nelder_mead_max_iterations() {
+        let f = |x: &[f64]| x[0] * x[0] + x[1] * x[1];
+
+        let result = NelderMead::new(5, 1e-12, 0.5).optimize(f, &[1.0, 1.0]);
+
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 5);
+        assert!(result.minimum < 2.0);
+    }
+}
@@ -0,0 +1,174 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Types shared by the optimizers.
+//!
+//! The [`Optimizer`] trait lets calibration code take any of the
+//! derivative-free or finite-difference methods interchangeably, e.g. a
+//! global method to find the basin of the minimum, then a local method to
+//! refine it.
+
+use std::time::Duration;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Optimizer of functions $f: \mathbb{R}^n \rightarrow \mathbb{R}$,
+/// given only function values.
+pub trait Optimizer {
+    /// Minimizes `f`, starting from (or, for population based methods,
+    /// including) the initial guess `x0`.
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult;
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Result of an optimization.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone)]
+pub struct OptimizationResult {
+    /// Minimizer of the function.
+    pub minimizer: Vec<f64>,
+
+    /// Value of the function at the minimum.
+    pub minimum: f64,
+
+    /// Number of iterations.
+    pub iterations: usize,
+
+    /// Number of function evaluations (a gradient evaluation counts as one).
+    pub evaluations: usize,
+
+    /// Whether the stopping criterion was met before the maximum
+    /// number of iterations.
+    pub converged: bool,
+
+    /// Time elapsed during optimization.
+    pub elapsed: Duration,
+}
+
+/// Stopping criteria of the global optimizers.
+///
+/// The optimization stops at the first criterion met. It has converged
+/// if it stopped on the tolerance or the target value.
+#[derive(Debug, Clone, Copy)]
+pub struct StoppingCriteria {
+    /// Maximum number of iterations (generations, or temperature steps).
+    pub max_iterations: usize,
+
+    /// Maximum number of function evaluations.
+    pub max_evaluations: Option<usize>,
+
+    /// Convergence tolerance, whose meaning depends on the method.
+    pub tolerance: f64,
+
+    /// Stop as soon as the function value is at most this value.
+    pub target: Option<f64>,
+
+    /// Maximum time.
+    pub max_time: Option<Duration>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for StoppingCriteria {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1000,
+            max_evaluations: None,
+            tolerance: 1e-8,
+            target: None,
+            max_time: None,
+        }
+    }
+}
+
+impl StoppingCriteria {
+    /// Set the maximum number of iterations.
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the maximum number of function evaluations.
+    #[must_use]
+    pub fn with_max_evaluations(mut self, max_evaluations: usize) -> Self {
+        self.max_evaluations = Some(max_evaluations);
+        self
+    }
+
+    /// Set the convergence tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the target function value.
+    #[must_use]
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Set the maximum time.
+    #[must_use]
+    pub fn with_max_time(mut self, max_time: Duration) -> Self {
+        self.max_time = Some(max_time);
+        self
+    }
+
+    /// Whether the budget (evaluations or time) is exhausted.
+    pub(crate) fn exhausted(&self, evaluations: usize, elapsed: Duration) -> bool {
+        self.max_evaluations.is_some_and(|max| evaluations >= max)
+            || self.max_time.is_some_and(|max| elapsed >= max)
+    }
+
+    /// Whether the target value is reached.
+    pub(crate) fn reached(&self, value: f64) -> bool {
+        self.target.is_some_and(|target| value <= target)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks box constraints: finite, and lower bound below upper bound.
+pub(crate) fn check_bounds(bounds: &[(f64, f64)]) -> Result<(), RustQuantError> {
+    if bounds.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "At least one bound is needed.".to_string(),
+        ));
+    }
+
+    for (i, (lower, upper)) in bounds.iter().enumerate() {
+        if !lower.is_finite() || !upper.is_finite() || lower >= upper {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Invalid bounds ({lower}, {upper}) for parameter {i}."
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Projects a point onto the box.
+pub(crate) fn clamp_to_bounds(x: &mut [f64], bounds: &[(f64, f64)]) {
+    x.iter_mut()
+        .zip(bounds)
+        .for_each(|(x, (lower, upper))| *x = x.clamp(*lower, *upper));
+}
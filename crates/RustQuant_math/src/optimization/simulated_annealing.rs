@@ -0,0 +1,385 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Simulated annealing (Kirkpatrick, Gelatt and Vecchi, 1983).
+//!
+//! A random walk in the box, where a step from $x$ to $y$ is accepted with
+//! the Metropolis probability $\min(1, e^{-(f(y) - f(x)) / T})$. Uphill
+//! steps, which let the walk escape local minima, become rarer as the
+//! temperature $T$ is lowered geometrically, $T_{k+1} = c T_k$. The steps
+//! are Gaussian, scaled by the width of the box and by $\sqrt{T_k / T_0}$,
+//! so the walk settles into the best basin found.
+
+use super::optimizer::{
+    check_bounds, clamp_to_bounds, OptimizationResult, Optimizer, StoppingCriteria,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::time::Instant;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Simulated annealing optimizer, with box constraints.
+#[derive(Debug, Clone)]
+pub struct SimulatedAnnealing {
+    /// Lower and upper bound of each parameter.
+    pub bounds: Vec<(f64, f64)>,
+
+    /// Initial temperature $T_0$. If `None`, the standard deviation of the
+    /// function over random points of the box.
+    pub initial_temperature: Option<f64>,
+
+    /// Cooling factor $c \in (0, 1)$.
+    pub cooling: f64,
+
+    /// Number of steps at each temperature.
+    pub steps_per_temperature: usize,
+
+    /// Initial standard deviation of the steps, relative to the width of
+    /// the box.
+    pub step_size: f64,
+
+    /// Stopping criteria. An iteration is one temperature, and the
+    /// tolerance is for the ratio $T_k / T_0$.
+    pub stopping: StoppingCriteria,
+
+    /// Seed of the random number generator (from entropy if `None`).
+    pub seed: Option<u64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SimulatedAnnealing {
+    /// Returns a new simulated annealing optimizer over the box `bounds`,
+    /// with cooling factor 0.95, $20 n$ steps per temperature, and initial
+    /// steps of half the width of the box.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds are empty, not finite, or a lower
+    /// bound is not below its upper bound.
+    pub fn new(bounds: Vec<(f64, f64)>) -> Result<Self, RustQuantError> {
+        check_bounds(&bounds)?;
+
+        Ok(Self {
+            steps_per_temperature: 20 * bounds.len(),
+            bounds,
+            initial_temperature: None,
+            cooling: 0.95,
+            step_size: 0.5,
+            stopping: StoppingCriteria::default(),
+            seed: None,
+        })
+    }
+
+    /// Set the initial temperature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temperature is not positive.
+    #[must_use]
+    pub fn with_initial_temperature(mut self, temperature: f64) -> Self {
+        assert!(temperature > 0.0);
+
+        self.initial_temperature = Some(temperature);
+        self
+    }
+
+    /// Set the cooling schedule: the cooling factor and the number of
+    /// steps at each temperature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factor is not in $(0, 1)$ or the number of steps is zero.
+    #[must_use]
+    pub fn with_schedule(mut self, cooling: f64, steps_per_temperature: usize) -> Self {
+        assert!(cooling > 0.0 && cooling < 1.0);
+        assert!(steps_per_temperature > 0);
+
+        self.cooling = cooling;
+        self.steps_per_temperature = steps_per_temperature;
+        self
+    }
+
+    /// Set the initial step size, relative to the width of the box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step size is not positive.
+    #[must_use]
+    pub fn with_step_size(mut self, step_size: f64) -> Self {
+        assert!(step_size > 0.0);
+
+        self.step_size = step_size;
+        self
+    }
+
+    /// Set the stopping criteria.
+    #[must_use]
+    pub fn with_stopping_criteria(mut self, stopping: StoppingCriteria) -> Self {
+        self.stopping = stopping;
+        self
+    }
+
+    /// Set the seed of the random number generator.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Minimizes a function over the box, starting from `x0` projected onto
+    /// the box, or from the centre of the box if `x0` is empty. Returns the
+    /// best point visited.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x0` is neither empty nor of the dimension of the bounds.
+    ///
+    /// ```
+    /// # use RustQuant::math::*;
+    /// # use RustQuant::utils::assert_approx_equal;
+    /// # use std::f64::consts::PI;
+    ///
+    /// // Rastrigin function: many local minima, global minimum at the origin.
+    /// let f = |x: &[f64]| {
+    ///     10.0 * x.len() as f64
+    ///         + x.iter().map(|x| x * x - 10.0 * (2.0 * PI * x).cos()).sum::<f64>()
+    /// };
+    ///
+    /// let result = SimulatedAnnealing::new(vec![(-5.12, 5.12); 2])
+    ///     .unwrap()
+    ///     .with_seed(42)
+    ///     .optimize(f, &[3.0, -2.0]);
+    ///
+    /// assert!(result.converged);
+    /// assert_approx_equal!(result.minimizer[0], 0.0, 1e-3);
+    /// assert_approx_equal!(result.minimizer[1], 0.0, 1e-3);
+    /// ```
+    pub fn optimize<F>(&self, f: F, x0: &[f64]) -> OptimizationResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let start = Instant::now();
+        let n = self.bounds.len();
+        assert!(x0.is_empty() || x0.len() == n);
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let mut x = if x0.is_empty() {
+            self.bounds.iter().map(|(a, b)| 0.5 * (a + b)).collect()
+        } else {
+            x0.to_vec()
+        };
+        clamp_to_bounds(&mut x, &self.bounds);
+
+        let mut value = f(&x);
+        let mut evaluations = 1;
+
+        let initial_temperature = self.initial_temperature.unwrap_or_else(|| {
+            let samples: Vec<f64> = (0..10 * n)
+                .map(|_| {
+                    let point: Vec<f64> = self
+                        .bounds
+                        .iter()
+                        .map(|(lower, upper)| rng.gen_range(*lower..*upper))
+                        .collect();
+                    f(&point)
+                })
+                .filter(|v| v.is_finite())
+                .collect();
+            evaluations += 10 * n;
+
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+            let variance =
+                samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+
+            if variance.sqrt() > 0.0 {
+                variance.sqrt()
+            } else {
+                1.0
+            }
+        });
+
+        let (mut minimizer, mut minimum) = (x.clone(), value);
+        let mut temperature = initial_temperature;
+        let mut converged = false;
+        let mut iterations = 0;
+
+        'annealing: while iterations < self.stopping.max_iterations {
+            if temperature <= self.stopping.tolerance * initial_temperature
+                || self.stopping.reached(minimum)
+            {
+                converged = true;
+                break;
+            }
+
+            iterations += 1;
+
+            let scale = self.step_size * (temperature / initial_temperature).sqrt();
+
+            for _ in 0..self.steps_per_temperature {
+                if self.stopping.exhausted(evaluations, start.elapsed()) {
+                    break 'annealing;
+                }
+
+                let mut candidate: Vec<f64> = x
+                    .iter()
+                    .zip(&self.bounds)
+                    .map(|(x, (lower, upper))| {
+                        x + scale * (upper - lower) * rng.sample::<f64, _>(StandardNormal)
+                    })
+                    .collect();
+                clamp_to_bounds(&mut candidate, &self.bounds);
+
+                let candidate_value = f(&candidate);
+                evaluations += 1;
+
+                // Metropolis criterion (NaN values are rejected).
+                let accept = candidate_value <= value
+                    || rng.gen::<f64>() < (-(candidate_value - value) / temperature).exp();
+
+                if accept {
+                    x = candidate;
+                    value = candidate_value;
+
+                    if value < minimum {
+                        minimizer.clone_from(&x);
+                        minimum = value;
+                    }
+                }
+            }
+
+            temperature *= self.cooling;
+        }
+
+        OptimizationResult {
+            minimizer,
+            minimum,
+            iterations,
+            evaluations,
+            converged,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl Optimizer for SimulatedAnnealing {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimizationResult {
+        self.optimize(f, x0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_simulated_annealing {
+    use super::*;
+    use crate::optimization::{DifferentialEvolution, NelderMead};
+    use std::f64::consts::PI;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Rastrigin function: global minimum 0 at the origin, and local minima
+    // near every integer point.
+    fn rastrigin(x: &[f64]) -> f64 {
+        10.0 * x.len() as f64
+            + x.iter()
+                .map(|x| x * x - 10.0 * (2.0 * PI * x).cos())
+                .sum::<f64>()
+    }
+
+    #[test]
+    fn test_simulated_annealing_rastrigin() {
+        let result = SimulatedAnnealing::new(vec![(-5.12, 5.12); 2])
+            .unwrap()
+            .with_seed(11)
+            .optimize(rastrigin, &[4.0, -3.0]);
+
+        assert!(result.converged);
+        for x in &result.minimizer {
+            assert_approx_equal!(*x, 0.0, 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_simulated_annealing_bounds() {
+        // Unconstrained minimum at (2, -2), outside the box.
+        let f = |x: &[f64]| (x[0] - 2.0).powi(2) + (x[1] + 2.0).powi(2);
+
+        let result = SimulatedAnnealing::new(vec![(-1.0, 1.0); 2])
+            .unwrap()
+            .with_initial_temperature(1.0)
+            .with_seed(5)
+            .optimize(f, &[]);
+
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-8);
+        assert_approx_equal!(result.minimizer[1], -1.0, 1e-8);
+    }
+
+    #[test]
+    fn test_simulated_annealing_stopping_criteria() {
+        let optimizer = SimulatedAnnealing::new(vec![(-5.12, 5.12); 2])
+            .unwrap()
+            .with_seed(2);
+
+        let result = optimizer
+            .clone()
+            .with_stopping_criteria(StoppingCriteria::default().with_max_evaluations(100))
+            .optimize(rastrigin, &[]);
+
+        assert!(!result.converged);
+        assert_eq!(result.evaluations, 100);
+
+        let result = optimizer
+            .with_stopping_criteria(StoppingCriteria::default().with_max_iterations(3))
+            .optimize(rastrigin, &[]);
+
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 3);
+    }
+
+    #[test]
+    fn test_optimizer_trait_objects() {
+        // A local method started in a local minimum stays there; the
+        // global methods find the global minimum.
+        let bounds = vec![(-5.12, 5.12); 2];
+        let optimizers: Vec<(Box<dyn Optimizer>, bool)> = vec![
+            (Box::new(NelderMead::default()), false),
+            (
+                Box::new(
+                    DifferentialEvolution::new(bounds.clone())
+                        .unwrap()
+                        .with_seed(1),
+                ),
+                true,
+            ),
+            (
+                Box::new(SimulatedAnnealing::new(bounds).unwrap().with_seed(1)),
+                true,
+            ),
+        ];
+
+        for (optimizer, global) in &optimizers {
+            let result = optimizer.minimize(&rastrigin, &[2.0, -1.0]);
+
+            assert!(result.converged);
+            assert_eq!(result.minimum < 1e-4, *global);
+        }
+    }
+}
@@ -0,0 +1,448 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Tail risk and drawdown measures of a series of (periodic) returns.
+//!
+//! Value-at-Risk and expected shortfall are reported as positive losses,
+//! as fractions of the initial value: at confidence level $\alpha$,
+//! $\text{VaR}_\alpha$ is the loss exceeded with probability $1 - \alpha$,
+//! and $\text{ES}_\alpha$ is the expected loss given that it is at least
+//! $\text{VaR}_\alpha$.
+
+use crate::distributions::{Distribution, Gaussian};
+use crate::statistic::Statistic;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS AND ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Method for Value-at-Risk and expected shortfall.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueAtRiskMethod {
+    /// Empirical quantile of the returns.
+    Historical,
+
+    /// Normal distribution with the sample mean and standard deviation.
+    Gaussian,
+
+    /// Normal quantile adjusted for the sample skewness and excess
+    /// kurtosis by the Cornish-Fisher expansion (modified VaR).
+    CornishFisher,
+
+    /// Historical measures of simulated returns over `horizon` periods,
+    /// each compounded from returns drawn (with replacement) from the
+    /// series.
+    MonteCarlo {
+        /// Number of simulated returns.
+        simulations: usize,
+
+        /// Number of periods of each simulated return.
+        horizon: usize,
+
+        /// Seed of the random number generator (from entropy if `None`).
+        seed: Option<u64>,
+    },
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Value-at-Risk of a series of returns, at `confidence` (e.g. 0.99).
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use RustQuant::utils::assert_approx_equal;
+/// let returns = vec![0.01, -0.02, 0.015, -0.035, 0.005, 0.02, -0.01, 0.0, 0.03, -0.05];
+///
+/// // The loss exceeded with probability 10%: the second worst return.
+/// let var = value_at_risk(&returns, 0.9, ValueAtRiskMethod::Historical).unwrap();
+///
+/// assert_approx_equal!(var, 0.035, 1e-15);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the confidence is not in (0, 1), or the series is
+/// too short for the method (one return for the historical and Monte
+/// Carlo methods, two for the Gaussian, and four for Cornish-Fisher), or
+/// the Monte Carlo simulation is empty.
+pub fn value_at_risk(
+    returns: &[f64],
+    confidence: f64,
+    method: ValueAtRiskMethod,
+) -> Result<f64, RustQuantError> {
+    tail_risk(returns, confidence, method).map(|(var, _)| var)
+}
+
+/// Expected shortfall (conditional Value-at-Risk) of a series of returns,
+/// at `confidence` (e.g. 0.975).
+///
+/// For the parametric methods, with $p = 1 - \alpha$ and the mean $\mu$,
+/// standard deviation $\sigma$, skewness $S$ and excess kurtosis $K$ of
+/// the returns,
+///
+/// $$
+/// \text{ES}_\alpha = -\mu + \frac{\sigma \phi(z_p)}{p}
+/// \left(1 + \frac{S z_p}{6} + \frac{K (z_p^2 - 1)}{24} + \frac{S^2 (1 - 2 z_p^2)}{36}\right)
+/// $$
+///
+/// the mean loss beyond the Cornish-Fisher quantile, where
+/// $z_p = \Phi^{-1}(p)$ ($S = K = 0$ for the Gaussian method).
+///
+/// ```
+/// # use RustQuant::math::*;
+/// let returns = vec![0.01, -0.02, 0.015, -0.035, 0.005, 0.02, -0.01, 0.0, 0.03, -0.05];
+///
+/// let var = value_at_risk(&returns, 0.9, ValueAtRiskMethod::Gaussian).unwrap();
+/// let es = expected_shortfall(&returns, 0.9, ValueAtRiskMethod::Gaussian).unwrap();
+///
+/// assert!(es > var);
+/// ```
+///
+/// # Errors
+///
+/// As for [`value_at_risk`].
+pub fn expected_shortfall(
+    returns: &[f64],
+    confidence: f64,
+    method: ValueAtRiskMethod,
+) -> Result<f64, RustQuantError> {
+    tail_risk(returns, confidence, method).map(|(_, es)| es)
+}
+
+/// Value-at-Risk and expected shortfall.
+fn tail_risk(
+    returns: &[f64],
+    confidence: f64,
+    method: ValueAtRiskMethod,
+) -> Result<(f64, f64), RustQuantError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Confidence level must be in (0, 1), got {confidence}."
+        )));
+    }
+
+    let minimum = match method {
+        ValueAtRiskMethod::Historical | ValueAtRiskMethod::MonteCarlo { .. } => 1,
+        ValueAtRiskMethod::Gaussian => 2,
+        ValueAtRiskMethod::CornishFisher => 4,
+    };
+
+    if returns.len() < minimum {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "{method:?} Value-at-Risk needs at least {minimum} returns, got {}.",
+            returns.len()
+        )));
+    }
+
+    let p = 1.0 - confidence;
+
+    match method {
+        ValueAtRiskMethod::Historical => Ok(historical_tail_risk(
+            returns.iter().map(|r| -r).collect(),
+            confidence,
+        )),
+        ValueAtRiskMethod::Gaussian | ValueAtRiskMethod::CornishFisher => {
+            let returns = returns.to_vec();
+            let (mean, std_dev) = (returns.mean(), returns.standard_deviation());
+
+            let (skewness, kurtosis) = if method == ValueAtRiskMethod::CornishFisher {
+                (returns.skewness(), returns.kurtosis())
+            } else {
+                (0.0, 0.0)
+            };
+
+            let normal = Gaussian::default();
+            let z = normal.inv_cdf(p);
+
+            let quantile =
+                z + (z * z - 1.0) * skewness / 6.0 + (z.powi(3) - 3.0 * z) * kurtosis / 24.0
+                    - (2.0 * z.powi(3) - 5.0 * z) * skewness.powi(2) / 36.0;
+
+            let tail = 1.0
+                + skewness * z / 6.0
+                + kurtosis * (z * z - 1.0) / 24.0
+                + skewness.powi(2) * (1.0 - 2.0 * z * z) / 36.0;
+
+            Ok((
+                -(mean + std_dev * quantile),
+                -mean + std_dev * normal.pdf(z) * tail / p,
+            ))
+        }
+        ValueAtRiskMethod::MonteCarlo {
+            simulations,
+            horizon,
+            seed,
+        } => {
+            if simulations == 0 || horizon == 0 {
+                return Err(RustQuantError::InvalidArgument(
+                    "Monte Carlo Value-at-Risk needs a simulation and a horizon.".to_string(),
+                ));
+            }
+
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+
+            let losses = (0..simulations)
+                .map(|_| {
+                    let growth = (0..horizon)
+                        .map(|_| 1.0 + returns[rng.gen_range(0..returns.len())])
+                        .product::<f64>();
+                    1.0 - growth
+                })
+                .collect();
+
+            Ok(historical_tail_risk(losses, confidence))
+        }
+    }
+}
+
+/// Empirical Value-at-Risk and expected shortfall of a sample of losses.
+fn historical_tail_risk(mut losses: Vec<f64>, confidence: f64) -> (f64, f64) {
+    losses.sort_by(f64::total_cmp);
+
+    let n = losses.len();
+    let index = ((confidence * n as f64).ceil() as usize).clamp(1, n) - 1;
+    let tail = &losses[index..];
+
+    (losses[index], tail.iter().sum::<f64>() / tail.len() as f64)
+}
+
+/// Drawdowns of a series of returns: the fall of the compounded value from
+/// its running maximum after each period, as a positive fraction.
+///
+/// ```
+/// # use RustQuant::math::*;
+/// # use RustQuant::utils::assert_approx_equal;
+/// // Value: 1.1, 0.88, 0.968, 1.1616.
+/// let drawdowns = drawdowns(&[0.1, -0.2, 0.1, 0.2]);
+///
+/// assert_approx_equal!(drawdowns[1], 0.2, 1e-15);
+/// assert_approx_equal!(drawdowns[2], 0.12, 1e-15);
+/// assert_eq!(drawdowns[3], 0.0);
+/// ```
+#[must_use]
+pub fn drawdowns(returns: &[f64]) -> Vec<f64> {
+    let mut value = 1.0;
+    let mut peak = 1.0;
+
+    returns
+        .iter()
+        .map(|r| {
+            value *= 1.0 + r;
+            peak = f64::max(peak, value);
+            1.0 - value / peak
+        })
+        .collect()
+}
+
+/// Maximum drawdown of a series of returns (zero if it is empty).
+#[must_use]
+pub fn max_drawdown(returns: &[f64]) -> f64 {
+    drawdowns(returns).into_iter().fold(0.0, f64::max)
+}
+
+/// Calmar ratio: the annualised (compound) return over the maximum
+/// drawdown, for returns with `periods_per_year` periods per year (e.g. 12
+/// for monthly returns). Infinite if there is no drawdown.
+///
+/// # Panics
+///
+/// Panics if the series is empty.
+#[must_use]
+pub fn calmar_ratio(returns: &[f64], periods_per_year: f64) -> f64 {
+    assert!(!returns.is_empty(), "Returns must not be empty.");
+
+    let growth = returns.iter().map(|r| 1.0 + r).product::<f64>();
+    let annual_return = growth.powf(periods_per_year / returns.len() as f64) - 1.0;
+
+    annual_return / max_drawdown(returns)
+}
+
+/// Omega ratio (Keating and Shadwick, 2002): the expected gain over the
+/// expected loss relative to `threshold`,
+/// $\Omega(\tau) = E[(r - \tau)^+] / E[(\tau - r)^+]$.
+/// Infinite if no return is below the threshold.
+#[must_use]
+pub fn omega_ratio(returns: &[f64], threshold: f64) -> f64 {
+    let gains = returns
+        .iter()
+        .map(|r| (r - threshold).max(0.0))
+        .sum::<f64>();
+    let losses = returns
+        .iter()
+        .map(|r| (threshold - r).max(0.0))
+        .sum::<f64>();
+
+    gains / losses
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_risk_measures {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Returns from -4.95% to 4.95% in steps of 0.1%, shuffled.
+    fn uniform_returns() -> Vec<f64> {
+        (0..100)
+            .map(|k| ((37 * k) % 100) as f64 / 1000.0 - 0.0495)
+            .collect()
+    }
+
+    #[test]
+    fn test_historical() {
+        let returns = uniform_returns();
+
+        let var = value_at_risk(&returns, 0.95, ValueAtRiskMethod::Historical).unwrap();
+        let es = expected_shortfall(&returns, 0.95, ValueAtRiskMethod::Historical).unwrap();
+
+        // The 95th of 100 sorted losses, and the mean of the losses from
+        // it up: 4.95%, 4.85%, ..., 4.45%.
+        assert_approx_equal!(var, 0.0445, 1e-15);
+        assert_approx_equal!(es, 0.047, 1e-15);
+    }
+
+    #[test]
+    fn test_gaussian() {
+        // Mean 0.001 and sample standard deviation 0.02.
+        let returns = vec![
+            0.021,
+            -0.019,
+            0.001 + 0.02 * 0.5_f64.sqrt(),
+            0.001 - 0.02 * 0.5_f64.sqrt(),
+        ];
+        let (mean, std_dev) = (0.001, 0.02);
+        assert_approx_equal!(returns.to_vec().standard_deviation(), std_dev, 1e-15);
+
+        let var = value_at_risk(&returns, 0.99, ValueAtRiskMethod::Gaussian).unwrap();
+        let es = expected_shortfall(&returns, 0.99, ValueAtRiskMethod::Gaussian).unwrap();
+
+        // z_{0.01} = -2.3263478740408408, phi(z) / 0.01 = 2.6652142203458067.
+        assert_approx_equal!(var, -mean + std_dev * 2.326_347_874_040_840_8, 1e-12);
+        assert_approx_equal!(es, -mean + std_dev * 2.665_214_220_345_806_7, 1e-12);
+    }
+
+    #[test]
+    fn test_cornish_fisher() {
+        // Symmetric returns with fat tails: the modified VaR at 99% is
+        // larger than the Gaussian one.
+        let returns = vec![-0.1, -0.01, -0.005, 0.0, 0.0, 0.005, 0.01, 0.1];
+
+        let gaussian = value_at_risk(&returns, 0.99, ValueAtRiskMethod::Gaussian).unwrap();
+        let modified = value_at_risk(&returns, 0.99, ValueAtRiskMethod::CornishFisher).unwrap();
+        assert!(modified > gaussian);
+
+        // Against the expansion with the sample moments.
+        let v = returns.to_vec();
+        let (s, k, sd) = (v.skewness(), v.kurtosis(), v.standard_deviation());
+        let z = -2.326_347_874_040_840_8_f64;
+        let expected = -(v.mean()
+            + sd * (z + (z * z - 1.0) * s / 6.0 + (z.powi(3) - 3.0 * z) * k / 24.0
+                - (2.0 * z.powi(3) - 5.0 * z) * s * s / 36.0));
+        assert_approx_equal!(modified, expected, 1e-12);
+
+        // The expected shortfall is the mean loss beyond the VaR: check
+        // by integrating the Cornish-Fisher quantile function.
+        let es = expected_shortfall(&returns, 0.99, ValueAtRiskMethod::CornishFisher).unwrap();
+        let n = 100_000;
+        let normal = Gaussian::default();
+        let integral = (0..n)
+            .map(|i| {
+                let u = 0.01 * (i as f64 + 0.5) / n as f64;
+                let z = normal.inv_cdf(u);
+                -(v.mean()
+                    + sd * (z + (z * z - 1.0) * s / 6.0 + (z.powi(3) - 3.0 * z) * k / 24.0
+                        - (2.0 * z.powi(3) - 5.0 * z) * s * s / 36.0))
+            })
+            .sum::<f64>()
+            / n as f64;
+        assert_approx_equal!(es, integral, 1e-5);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let returns = uniform_returns();
+
+        // One period: converges to the historical measures.
+        let method = ValueAtRiskMethod::MonteCarlo {
+            simulations: 200_000,
+            horizon: 1,
+            seed: Some(1),
+        };
+        let var = value_at_risk(&returns, 0.95, method).unwrap();
+        assert_approx_equal!(var, 0.0445, 1e-3);
+
+        // Ten periods: about sqrt(10) times larger.
+        let method = ValueAtRiskMethod::MonteCarlo {
+            simulations: 200_000,
+            horizon: 10,
+            seed: Some(1),
+        };
+        let var_10 = value_at_risk(&returns, 0.95, method).unwrap();
+        assert!(var_10 > 2.5 * var && var_10 < 3.5 * var);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let returns = vec![0.01, -0.02, 0.03];
+
+        assert!(value_at_risk(&returns, 1.0, ValueAtRiskMethod::Historical).is_err());
+        assert!(value_at_risk(&returns, 0.0, ValueAtRiskMethod::Gaussian).is_err());
+        assert!(value_at_risk(&[], 0.95, ValueAtRiskMethod::Historical).is_err());
+        assert!(value_at_risk(&returns, 0.95, ValueAtRiskMethod::CornishFisher).is_err());
+        assert!(expected_shortfall(
+            &returns,
+            0.95,
+            ValueAtRiskMethod::MonteCarlo {
+                simulations: 0,
+                horizon: 1,
+                seed: None
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_drawdowns() {
+        let returns = vec![0.1, -0.2, 0.1, 0.2, -0.1, -0.25, 0.5];
+
+        // Value: 1.1, 0.88, 0.968, 1.1616, 1.04544, 0.78408, 1.17612.
+        assert_approx_equal!(max_drawdown(&returns), 1.0 - 0.78408 / 1.1616, 1e-15);
+        assert_eq!(max_drawdown(&[]), 0.0);
+        assert_eq!(max_drawdown(&[0.01, 0.02]), 0.0);
+
+        // Monthly returns over 7 months.
+        let annual = 1.17612_f64.powf(12.0 / 7.0) - 1.0;
+        assert_approx_equal!(
+            calmar_ratio(&returns, 12.0),
+            annual / (1.0 - 0.78408 / 1.1616),
+            1e-14
+        );
+    }
+
+    #[test]
+    fn test_omega_ratio() {
+        let returns = vec![0.03, -0.01, 0.02, -0.02, 0.01];
+
+        // Gains 0.06, losses 0.03 against zero.
+        assert_approx_equal!(omega_ratio(&returns, 0.0), 2.0, 1e-15);
+        // Gains 0.03, losses 0.05 against 1%.
+        assert_approx_equal!(omega_ratio(&returns, 0.01), 0.6, 1e-14);
+        assert!(omega_ratio(&[0.01, 0.02], 0.0).is_infinite());
+    }
+}
@@ -16,5 +16,17 @@ pub mod brent;
 /// Newton-Raphson root-finding algorithm.
 pub mod newton_raphson;
 
+/// Polynomial roots: quadratic, cubic, and Durand-Kerner.
+pub mod polynomial;
+
+/// Ridders root-finding algorithm.
+pub mod ridders;
+
 /// Root-finding trait and data.
 pub mod rootfinder;
+
+/// Secant root-finding algorithm.
+pub mod secant;
+
+/// TOMS748 root-finding algorithm.
+pub mod toms748;
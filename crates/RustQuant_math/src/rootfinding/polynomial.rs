@@ -0,0 +1,286 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use num::Complex;
+use std::f64::consts::PI;
+use RustQuant_error::RustQuantError;
+
+/// Maximum number of Durand-Kerner iterations.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Real roots of $a x^2 + b x + c$, in increasing order (a double root is
+/// repeated). Falls back to the linear equation if $a = 0$.
+///
+/// The root of larger magnitude is computed first and the other from the
+/// product of the roots, $c / a$, which avoids cancellation.
+///
+/// ```
+/// # use RustQuant::math::polynomial::solve_quadratic;
+/// // 2 year bond with a 5% annual coupon priced at 98:
+/// // 105 v^2 + 5 v - 98 = 0, with discount factor v = 1 / (1 + y).
+/// let roots = solve_quadratic(105.0, 5.0, -98.0);
+/// let y = 1.0 / roots[1] - 1.0;
+///
+/// assert!((5.0 / (1.0 + y) + 105.0 / (1.0 + y).powi(2) - 98.0).abs() < 1e-12);
+/// ```
+#[must_use]
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return if b == 0.0 { vec![] } else { vec![-c / b] };
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < 0.0 {
+        return vec![];
+    }
+
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+
+    let mut roots = if q == 0.0 {
+        // b = 0 and c = 0.
+        vec![0.0, 0.0]
+    } else {
+        vec![q / a, c / q]
+    };
+
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// Real roots of $a x^3 + b x^2 + c x + d$, in increasing order (repeated
+/// roots are repeated). Falls back to the quadratic equation if $a = 0$.
+///
+/// The depressed cubic is solved with Cardano's formula if it has one real
+/// root, else with the trigonometric method, and each root is refined with
+/// a Newton step on the original cubic.
+///
+/// ```
+/// # use RustQuant::math::polynomial::solve_cubic;
+/// // (x - 1)(x - 2)(x - 3)
+/// let roots = solve_cubic(1.0, -6.0, 11.0, -6.0);
+///
+/// for (root, expected) in roots.iter().zip([1.0, 2.0, 3.0]) {
+///     assert!((root - expected).abs() < 1e-12);
+/// }
+/// ```
+#[must_use]
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return solve_quadratic(b, c, d);
+    }
+
+    let (b, c, d) = (b / a, c / a, d / a);
+
+    // x = t - b / 3 gives t^3 + p t + q = 0.
+    let shift = b / 3.0;
+    let p = c - b * shift;
+    let q = 2.0 * shift.powi(3) - c * shift + d;
+
+    let discriminant = (0.5 * q).powi(2) + (p / 3.0).powi(3);
+
+    let depressed = if p == 0.0 && q == 0.0 {
+        vec![0.0; 3]
+    } else if discriminant > 0.0 {
+        // One real root: Cardano, with u^3 chosen to avoid cancellation.
+        let u = (-0.5 * q - q.signum() * discriminant.sqrt()).cbrt();
+        vec![if u == 0.0 { 0.0 } else { u - p / (3.0 * u) }]
+    } else {
+        // Three real roots (p < 0).
+        let r = (-p / 3.0).sqrt();
+        let phi = (-0.5 * q / r.powi(3)).clamp(-1.0, 1.0).acos();
+
+        (0..3)
+            .map(|k| 2.0 * r * ((phi - 2.0 * PI * k as f64) / 3.0).cos())
+            .collect()
+    };
+
+    let mut roots: Vec<f64> = depressed
+        .into_iter()
+        .map(|t| {
+            let x = t - shift;
+            let value = ((x + b) * x + c) * x + d;
+            let slope = (3.0 * x + 2.0 * b) * x + c;
+
+            if slope.abs() > f64::EPSILON * (1.0 + x.abs()) {
+                x - value / slope
+            } else {
+                x
+            }
+        })
+        .collect();
+
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// All complex roots of the polynomial
+/// $c_0 x^n + c_1 x^{n-1} + \dots + c_n$, given the coefficients
+/// `[c_0, c_1, ..., c_n]` (highest degree first), by the Durand-Kerner
+/// (Weierstrass) method.
+///
+/// All roots are improved simultaneously:
+/// $z_k \leftarrow z_k - p(z_k) / \prod_{j \neq k} (z_k - z_j)$,
+/// from points on a circle containing the roots. Convergence is quadratic
+/// for simple roots, and only linear for repeated ones, which are found to
+/// about half the precision.
+///
+/// ```
+/// # use RustQuant::math::polynomial::polynomial_roots;
+/// // x^4 - 1: roots 1, -1, i and -i.
+/// let roots = polynomial_roots(&[1.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+///
+/// assert_eq!(roots.len(), 4);
+/// assert!(roots.iter().all(|z| (z.norm() - 1.0).abs() < 1e-12));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the coefficients are all zero or not finite.
+pub fn polynomial_roots(coefficients: &[f64]) -> Result<Vec<Complex<f64>>, RustQuantError> {
+    if coefficients.iter().any(|c| !c.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "Polynomial coefficients must be finite.".to_string(),
+        ));
+    }
+
+    // Drop leading zeros, and factor out x^m for trailing zeros.
+    let Some(first) = coefficients.iter().position(|c| *c != 0.0) else {
+        return Err(RustQuantError::InvalidArgument(
+            "Polynomial coefficients are all zero.".to_string(),
+        ));
+    };
+    let last = coefficients
+        .iter()
+        .rposition(|c| *c != 0.0)
+        .unwrap_or(first);
+
+    let mut roots = vec![Complex::new(0.0, 0.0); coefficients.len() - 1 - last];
+
+    // Monic polynomial of the remaining degree.
+    let monic: Vec<f64> = coefficients[first + 1..=last]
+        .iter()
+        .map(|c| c / coefficients[first])
+        .collect();
+    let degree = monic.len();
+
+    if degree == 0 {
+        return Ok(roots);
+    }
+
+    let evaluate = |z: Complex<f64>| monic.iter().fold(Complex::new(1.0, 0.0), |p, c| p * z + c);
+
+    // Cauchy bound on the moduli of the roots, and starting points on a
+    // circle of that radius, rotated away from the axes.
+    let radius = 1.0 + monic.iter().fold(0.0, |m: f64, c| m.max(c.abs()));
+    let mut z: Vec<Complex<f64>> = (0..degree)
+        .map(|k| Complex::from_polar(radius, (2.0 * PI * k as f64 + 0.4) / degree as f64))
+        .collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut change: f64 = 0.0;
+
+        for k in 0..degree {
+            let denominator = (0..degree)
+                .filter(|j| *j != k)
+                .fold(Complex::new(1.0, 0.0), |product, j| product * (z[k] - z[j]));
+
+            if denominator.norm() == 0.0 {
+                continue;
+            }
+
+            let step = evaluate(z[k]) / denominator;
+            z[k] -= step;
+            change = change.max(step.norm() / z[k].norm().max(1.0));
+        }
+
+        if change <= 4.0 * f64::EPSILON {
+            break;
+        }
+    }
+
+    roots.extend(z);
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod TESTS_polynomial {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_solve_quadratic() {
+        // Roots 1e-8 and 1e8: the small one is lost by the textbook formula.
+        let roots = solve_quadratic(1.0, -(1e8 + 1e-8), 1.0);
+        assert_approx_equal!(roots[0], 1e-8, 1e-22);
+        assert_approx_equal!(roots[1], 1e8, 1e-6);
+
+        assert_eq!(solve_quadratic(1.0, 0.0, 1.0), Vec::<f64>::new());
+        assert_eq!(solve_quadratic(1.0, -4.0, 4.0), vec![2.0, 2.0]);
+        assert_eq!(solve_quadratic(0.0, 2.0, -1.0), vec![0.5]);
+        assert_eq!(solve_quadratic(0.0, 0.0, 1.0), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn test_solve_cubic() {
+        // One real root: x^3 + x + 1.
+        let roots = solve_cubic(1.0, 0.0, 1.0, 1.0);
+        assert_eq!(roots.len(), 1);
+        assert_approx_equal!(roots[0], -0.682_327_803_828_019_3, 1e-15);
+
+        // Three real roots: 2 (x + 1)(x - 0.5)(x - 4).
+        let roots = solve_cubic(2.0, -7.0, -5.0, 4.0);
+        for (root, expected) in roots.iter().zip([-1.0, 0.5, 4.0]) {
+            assert_approx_equal!(*root, expected, 1e-14);
+        }
+
+        // Triple root: (x - 2)^3.
+        let roots = solve_cubic(1.0, -6.0, 12.0, -8.0);
+        assert_eq!(roots.len(), 3);
+        assert!(roots.iter().all(|root| (root - 2.0).abs() < 1e-12));
+
+        // Degenerate to a quadratic.
+        assert_eq!(solve_cubic(0.0, 1.0, -3.0, 2.0), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_polynomial_roots() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)(x - 5)
+        let mut roots = polynomial_roots(&[1.0, -15.0, 85.0, -225.0, 274.0, -120.0]).unwrap();
+        roots.sort_by(|a, b| a.re.total_cmp(&b.re));
+
+        for (root, expected) in roots.iter().zip(1..=5) {
+            assert_approx_equal!(root.re, f64::from(expected), 1e-10);
+            assert_approx_equal!(root.im, 0.0, 1e-10);
+        }
+
+        // x^2 + 2x + 5: roots -1 +/- 2i.
+        let roots = polynomial_roots(&[1.0, 2.0, 5.0]).unwrap();
+        for root in &roots {
+            assert_approx_equal!(root.re, -1.0, 1e-14);
+            assert_approx_equal!(root.im.abs(), 2.0, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_polynomial_roots_zero_coefficients() {
+        // 0 x^4 + x^3 - x: leading zero dropped, and roots 0, 1, -1.
+        let mut roots = polynomial_roots(&[0.0, 1.0, 0.0, -1.0, 0.0]).unwrap();
+        roots.sort_by(|a, b| a.re.total_cmp(&b.re));
+
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip([-1.0, 0.0, 1.0]) {
+            assert_approx_equal!(root.re, expected, 1e-14);
+        }
+
+        assert!(polynomial_roots(&[0.0, 0.0]).is_err());
+        assert!(polynomial_roots(&[1.0, f64::NAN]).is_err());
+        assert!(polynomial_roots(&[3.0]).unwrap().is_empty());
+    }
+}
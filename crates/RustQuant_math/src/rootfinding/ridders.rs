@@ -0,0 +1,216 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::rootfinder::{Rootfinder, RootfinderData};
+
+/// Ridders root-finding algorithm.
+///
+/// Evaluates the function at the midpoint of the bracket, and fits an
+/// exponential through the three points to get the next iterate, which
+/// always stays within the bracket. Converges quadratically, with two
+/// function evaluations per iteration.
+pub struct Ridders<F>
+where
+    F: Fn(f64) -> f64,
+{
+    function: F,
+    guess: f64,
+    data: RootfinderData,
+}
+
+impl<F> Ridders<F>
+where
+    F: Fn(f64) -> f64,
+{
+    /// Create a new Ridders solver.
+    pub fn new(function: F, guess: f64, data: RootfinderData) -> Self {
+        Self {
+            function,
+            guess,
+            data,
+        }
+    }
+}
+
+impl<F> Rootfinder<F> for Ridders<F>
+where
+    F: Fn(f64) -> f64,
+{
+    fn value(&self, x: f64) -> f64 {
+        (self.function)(x)
+    }
+
+    fn derivative(&self, _: f64) -> f64 {
+        0.0
+    }
+
+    fn solve_impl(&mut self) -> f64 {
+        let mut x_mid: f64;
+        let mut f_mid: f64;
+        let mut froot: f64;
+        let mut s: f64;
+        let mut next_root: f64;
+
+        // Any value outside the bracket, so the first step is never small.
+        self.data.root = f64::MIN;
+
+        while self.data.iteration_count <= Self::MAX_ITERATIONS {
+            x_mid = 0.5 * (self.data.x_min + self.data.x_max);
+            f_mid = self.value(x_mid);
+            self.data.increment_evaluation_count();
+
+            s = (f_mid * f_mid - self.data.y_min * self.data.y_max).sqrt();
+
+            if RootfinderData::close(s, 0.0) {
+                return x_mid;
+            }
+
+            let sign = if self.data.y_min >= self.data.y_max {
+                1.0
+            } else {
+                -1.0
+            };
+            next_root = x_mid + (x_mid - self.data.x_min) * sign * f_mid / s;
+
+            if (next_root - self.data.root).abs() <= self.data.accuracy {
+                return next_root;
+            }
+
+            self.data.root = next_root;
+            froot = self.value(self.data.root);
+            self.data.increment_evaluation_count();
+
+            if RootfinderData::close(froot, 0.0) {
+                return self.data.root;
+            }
+
+            // Keep the root bracketed.
+            if RootfinderData::nrsign(f_mid, froot) != f_mid {
+                self.data.x_min = x_mid;
+                self.data.y_min = f_mid;
+                self.data.x_max = self.data.root;
+                self.data.y_max = froot;
+            } else if RootfinderData::nrsign(self.data.y_min, froot) != self.data.y_min {
+                self.data.x_max = self.data.root;
+                self.data.y_max = froot;
+            } else {
+                self.data.x_min = self.data.root;
+                self.data.y_min = froot;
+            }
+
+            if (self.data.x_max - self.data.x_min).abs() <= self.data.accuracy {
+                return self.data.root;
+            }
+        }
+
+        0.0
+    }
+
+    fn solve(&mut self) -> f64 {
+        assert!(self.data.accuracy > 0., "accuracy must be positive");
+
+        self.data.accuracy = f64::max(self.data.accuracy, f64::EPSILON);
+
+        let growth_factor = 1.6;
+        let mut flipflop = -1;
+
+        self.data.root = self.guess;
+        self.data.y_max = self.value(self.data.root);
+
+        if RootfinderData::close(self.data.y_max, 0.0) {
+            return self.data.root;
+        } else if self.data.y_max > 0.0 {
+            self.data.x_min = self
+                .data
+                .enforce_bounds(self.data.root - self.data.stepsize);
+            self.data.y_min = self.value(self.data.x_min);
+            self.data.x_max = self.data.root;
+        } else {
+            self.data.x_min = self.data.root;
+            self.data.y_min = self.data.y_max;
+            self.data.x_max = self
+                .data
+                .enforce_bounds(self.data.root + self.data.stepsize);
+            self.data.y_max = self.value(self.data.x_max);
+        }
+
+        self.data.iteration_count = 2;
+
+        while self.data.iteration_count <= Self::MAX_ITERATIONS {
+            // Check if we can solve.
+            if self.data.y_min * self.data.y_max <= 0.0 {
+                if RootfinderData::close(self.data.y_min, 0.0) {
+                    return self.data.x_min;
+                }
+                if RootfinderData::close(self.data.y_max, 0.0) {
+                    return self.data.x_max;
+                }
+                self.data.root = 0.5 * (self.data.x_max + self.data.x_min);
+
+                return self.solve_impl();
+            }
+
+            // If we can't solve, adjust.
+            if self.data.y_min.abs() < self.data.y_max.abs() {
+                self.data.x_min = self.data.enforce_bounds(
+                    self.data.x_min + growth_factor * (self.data.x_min - self.data.x_max),
+                );
+                self.data.y_min = self.value(self.data.x_min);
+            } else if self.data.y_min.abs() > self.data.y_max.abs() {
+                self.data.x_max = self.data.enforce_bounds(
+                    self.data.x_max + growth_factor * (self.data.x_max - self.data.x_min),
+                );
+                self.data.y_max = self.value(self.data.x_max);
+            } else if flipflop == -1 {
+                self.data.x_min = self.data.enforce_bounds(
+                    self.data.x_min + growth_factor * (self.data.x_min - self.data.x_max),
+                );
+                self.data.y_min = self.value(self.data.x_min);
+                self.data.increment_evaluation_count();
+                flipflop = 1;
+            } else if flipflop == 1 {
+                self.data.x_max = self.data.enforce_bounds(
+                    self.data.x_max + growth_factor * (self.data.x_max - self.data.x_min),
+                );
+                self.data.y_max = self.value(self.data.x_max);
+                flipflop = -1;
+            }
+
+            self.data.increment_evaluation_count();
+        }
+
+        0.0
+    }
+}
+#[cfg(test)]
+mod TESTS_ridders_solver {
+    use super::*;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_ridders_solver() {
+        // f(x) = x^2 - 2
+        let f = |x: f64| x.powi(2) - 2.0;
+
+        let data = RootfinderData::new(1e-15, 1e-5, 0.0, 2.0, true);
+        let mut solver = Ridders::new(f, 1.0, data);
+        let root = solver.solve();
+        assert!((root - SQRT_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_ridders_solver_steep() {
+        // f(x) = exp(10 x) - 5, root at ln(5) / 10.
+        let f = |x: f64| (10.0 * x).exp() - 5.0;
+
+        let mut solver = Ridders::new(f, 0.0, RootfinderData::default());
+        let root = solver.solve();
+        assert!((root - 5.0_f64.ln() / 10.0).abs() < 1e-12);
+    }
+}
@@ -7,6 +7,9 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::toms748::Toms748;
+use RustQuant_error::RustQuantError;
+
 /// Root-finding base trait.
 pub trait Rootfinder<F>
 where
@@ -158,3 +161,66 @@ impl RootfinderData {
         diff <= tolerance * f64::abs(x) || diff <= tolerance * f64::abs(y)
     }
 }
+
+/// Find a root of `function`, starting from `guess`, with a robust default
+/// method: TOMS748, which keeps the root bracketed and converges quickly
+/// on smooth functions, such as bond prices in the yield or z-spread.
+///
+/// ```
+/// use RustQuant::math::rootfinder::{find_root, RootfinderData};
+///
+/// // Yield of a 2 year 4% semi-annual coupon bond priced at 99.
+/// let price = |y: f64| {
+///     (1..=4).map(|k| 2.0 / (1.0 + y / 2.0).powi(k)).sum::<f64>() + 100.0 / (1.0 + y / 2.0).powi(4)
+/// };
+///
+/// let root = find_root(|y| price(y) - 99.0, 0.04, RootfinderData::default()).unwrap();
+///
+/// assert!((price(root) - 99.0).abs() < 1e-10);
+/// ```
+///
+/// # Errors
+///
+/// Returns `RustQuantError::ComputationError` if no root is found.
+pub fn find_root<F>(function: F, guess: f64, data: RootfinderData) -> Result<f64, RustQuantError>
+where
+    F: Fn(f64) -> f64,
+{
+    let mut solver = Toms748::new(&function, guess, data);
+    let root = solver.solve();
+
+    // The solver does not report failure, so check the sign of the function
+    // on either side of the root.
+    let step = data.accuracy.max(f64::EPSILON * root.abs());
+    let (lower, upper) = (function(root - step), function(root + step));
+
+    if function(root) == 0.0 || lower * upper <= 0.0 {
+        Ok(root)
+    } else {
+        Err(RustQuantError::ComputationError(format!(
+            "No root found (last iterate: {root})."
+        )))
+    }
+}
+
+#[cfg(test)]
+mod TESTS_find_root {
+    use super::*;
+
+    #[test]
+    fn test_find_root() {
+        // f(x) = x^3 - 2x - 5 (Wallis' example).
+        let root = find_root(
+            |x| x.powi(3) - 2.0 * x - 5.0,
+            0.0,
+            RootfinderData::default(),
+        );
+        assert!((root.unwrap() - 2.094_551_481_542_326_5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_find_root_no_root() {
+        let root = find_root(|x| x * x + 1.0, 0.0, RootfinderData::default());
+        assert!(root.is_err());
+    }
+}
@@ -0,0 +1,188 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::rootfinder::{Rootfinder, RootfinderData};
+
+/// Secant root-finding algorithm.
+///
+/// Replaces the derivative in Newton-Raphson by the slope through the last
+/// two iterates. It converges superlinearly near a simple root, but does
+/// not keep the root bracketed, so it can diverge from a poor bracket.
+pub struct Secant<F>
+where
+    F: Fn(f64) -> f64,
+{
+    function: F,
+    guess: f64,
+    data: RootfinderData,
+}
+
+impl<F> Secant<F>
+where
+    F: Fn(f64) -> f64,
+{
+    /// Create a new Secant solver.
+    pub fn new(function: F, guess: f64, data: RootfinderData) -> Self {
+        Self {
+            function,
+            guess,
+            data,
+        }
+    }
+}
+
+impl<F> Rootfinder<F> for Secant<F>
+where
+    F: Fn(f64) -> f64,
+{
+    fn value(&self, x: f64) -> f64 {
+        (self.function)(x)
+    }
+
+    fn derivative(&self, _: f64) -> f64 {
+        0.0
+    }
+
+    fn solve_impl(&mut self) -> f64 {
+        let mut dx: f64;
+        let mut froot: f64;
+        let mut xl: f64;
+        let mut fl: f64;
+
+        // Start from the end of the bracket with the smaller function value.
+        if self.data.y_min.abs() < self.data.y_max.abs() {
+            self.data.root = self.data.x_min;
+            froot = self.data.y_min;
+            xl = self.data.x_max;
+            fl = self.data.y_max;
+        } else {
+            self.data.root = self.data.x_max;
+            froot = self.data.y_max;
+            xl = self.data.x_min;
+            fl = self.data.y_min;
+        }
+
+        while self.data.iteration_count <= Self::MAX_ITERATIONS {
+            dx = (xl - self.data.root) * froot / (froot - fl);
+            xl = self.data.root;
+            fl = froot;
+            self.data.root += dx;
+            froot = self.value(self.data.root);
+            self.data.increment_evaluation_count();
+
+            if dx.abs() < self.data.accuracy || RootfinderData::close(froot, 0.0) {
+                return self.data.root;
+            }
+        }
+
+        0.0
+    }
+
+    fn solve(&mut self) -> f64 {
+        assert!(self.data.accuracy > 0., "accuracy must be positive");
+
+        self.data.accuracy = f64::max(self.data.accuracy, f64::EPSILON);
+
+        let growth_factor = 1.6;
+        let mut flipflop = -1;
+
+        self.data.root = self.guess;
+        self.data.y_max = self.value(self.data.root);
+
+        if RootfinderData::close(self.data.y_max, 0.0) {
+            return self.data.root;
+        } else if self.data.y_max > 0.0 {
+            self.data.x_min = self
+                .data
+                .enforce_bounds(self.data.root - self.data.stepsize);
+            self.data.y_min = self.value(self.data.x_min);
+            self.data.x_max = self.data.root;
+        } else {
+            self.data.x_min = self.data.root;
+            self.data.y_min = self.data.y_max;
+            self.data.x_max = self
+                .data
+                .enforce_bounds(self.data.root + self.data.stepsize);
+            self.data.y_max = self.value(self.data.x_max);
+        }
+
+        self.data.iteration_count = 2;
+
+        while self.data.iteration_count <= Self::MAX_ITERATIONS {
+            // Check if we can solve.
+            if self.data.y_min * self.data.y_max <= 0.0 {
+                if RootfinderData::close(self.data.y_min, 0.0) {
+                    return self.data.x_min;
+                }
+                if RootfinderData::close(self.data.y_max, 0.0) {
+                    return self.data.x_max;
+                }
+                self.data.root = 0.5 * (self.data.x_max + self.data.x_min);
+
+                return self.solve_impl();
+            }
+
+            // If we can't solve, adjust.
+            if self.data.y_min.abs() < self.data.y_max.abs() {
+                self.data.x_min = self.data.enforce_bounds(
+                    self.data.x_min + growth_factor * (self.data.x_min - self.data.x_max),
+                );
+                self.data.y_min = self.value(self.data.x_min);
+            } else if self.data.y_min.abs() > self.data.y_max.abs() {
+                self.data.x_max = self.data.enforce_bounds(
+                    self.data.x_max + growth_factor * (self.data.x_max - self.data.x_min),
+                );
+                self.data.y_max = self.value(self.data.x_max);
+            } else if flipflop == -1 {
+                self.data.x_min = self.data.enforce_bounds(
+                    self.data.x_min + growth_factor * (self.data.x_min - self.data.x_max),
+                );
+                self.data.y_min = self.value(self.data.x_min);
+                self.data.increment_evaluation_count();
+                flipflop = 1;
+            } else if flipflop == 1 {
+                self.data.x_max = self.data.enforce_bounds(
+                    self.data.x_max + growth_factor * (self.data.x_max - self.data.x_min),
+                );
+                self.data.y_max = self.value(self.data.x_max);
+                flipflop = -1;
+            }
+
+            self.data.increment_evaluation_count();
+        }
+
+        0.0
+    }
+}
+#[cfg(test)]
+mod TESTS_secant_solver {
+    use super::*;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_secant_solver() {
+        // f(x) = x^2 - 2
+        let f = |x: f64| x.powi(2) - 2.0;
+
+        let data = RootfinderData::new(1e-15, 1e-5, 0.0, 2.0, true);
+        let mut solver = Secant::new(f, 1.0, data);
+        let root = solver.solve();
+        assert!((root - SQRT_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_secant_solver_transcendental() {
+        // f(x) = cos(x) - x, root at the Dottie number.
+        let f = |x: f64| x.cos() - x;
+
+        let mut solver = Secant::new(f, 0.5, RootfinderData::default());
+        let root = solver.solve();
+        assert!((root - 0.739_085_133_215_160_6).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,441 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::rootfinder::{Rootfinder, RootfinderData};
+
+/// TOMS748 root-finding algorithm (Alefeld, Potra and Shi, 1995).
+///
+/// Each iteration takes two steps of inverse cubic interpolation (falling
+/// back to quadratic or secant steps), a double-length secant step, and a
+/// bisection if the bracket did not shrink by at least half. The root stays
+/// bracketed, and it needs fewer function evaluations than Brent's method
+/// on smooth functions.
+pub struct Toms748<F>
+where
+    F: Fn(f64) -> f64,
+{
+    function: F,
+    guess: f64,
+    data: RootfinderData,
+}
+
+/// Bracket `[a, b]`, the last point `d` removed from it, and the one `e`
+/// removed before that, with their function values.
+#[derive(Debug, Clone, Copy)]
+struct Bracket {
+    a: f64,
+    b: f64,
+    d: f64,
+    e: f64,
+    fa: f64,
+    fb: f64,
+    fd: f64,
+    fe: f64,
+}
+
+impl<F> Toms748<F>
+where
+    F: Fn(f64) -> f64,
+{
+    /// Create a new TOMS748 solver.
+    pub fn new(function: F, guess: f64, data: RootfinderData) -> Self {
+        Self {
+            function,
+            guess,
+            data,
+        }
+    }
+
+    /// Shrinks the bracket to `[a, c]` or `[c, b]`, keeping `c` away from
+    /// the ends.
+    fn bracket(&mut self, bracket: &mut Bracket, mut c: f64) {
+        let tolerance = 2.0 * f64::EPSILON;
+        let Bracket { a, b, .. } = *bracket;
+
+        if b - a < 2.0 * tolerance * a.abs() {
+            c = a + 0.5 * (b - a);
+        } else if c <= a + a.abs() * tolerance {
+            c = a + a.abs() * tolerance;
+        } else if c >= b - b.abs() * tolerance {
+            c = b - b.abs() * tolerance;
+        }
+
+        let fc = self.value(c);
+        self.data.increment_evaluation_count();
+
+        bracket.e = bracket.d;
+        bracket.fe = bracket.fd;
+
+        if fc == 0.0 {
+            bracket.a = c;
+            bracket.fa = 0.0;
+        } else if bracket.fa.signum() * fc.signum() < 0.0 {
+            bracket.d = b;
+            bracket.fd = bracket.fb;
+            bracket.b = c;
+            bracket.fb = fc;
+        } else {
+            bracket.d = a;
+            bracket.fd = bracket.fa;
+            bracket.a = c;
+            bracket.fa = fc;
+        }
+    }
+
+    /// Whether the bracket is small enough, or a root was hit.
+    fn done(&self, bracket: &Bracket) -> bool {
+        bracket.fa == 0.0
+            || bracket.fb == 0.0
+            || RootfinderData::close(bracket.fa, 0.0)
+            || RootfinderData::close(bracket.fb, 0.0)
+            || bracket.b - bracket.a
+                <= self.data.accuracy + 4.0 * f64::EPSILON * bracket.a.abs().max(bracket.b.abs())
+    }
+}
+
+/// Division returning `fallback` if the result would overflow.
+fn safe_div(numerator: f64, denominator: f64, fallback: f64) -> f64 {
+    if denominator.abs() < 1.0 && (denominator * f64::MAX).abs() <= numerator.abs() {
+        fallback
+    } else {
+        numerator / denominator
+    }
+}
+
+/// Secant step, or bisection if it lands too close to the ends.
+fn secant_interpolate(a: f64, b: f64, fa: f64, fb: f64) -> f64 {
+    let tolerance = 5.0 * f64::EPSILON;
+    let c = a - (fa / (fb - fa)) * (b - a);
+
+    if c <= a + a.abs() * tolerance || c >= b - b.abs() * tolerance {
+        0.5 * (a + b)
+    } else {
+        c
+    }
+}
+
+/// Newton steps on the quadratic through `a`, `b` and `d`.
+fn quadratic_interpolate(bracket: &Bracket, steps: usize) -> f64 {
+    let Bracket {
+        a,
+        b,
+        d,
+        fa,
+        fb,
+        fd,
+        ..
+    } = *bracket;
+
+    let slope = safe_div(fb - fa, b - a, f64::MAX);
+    let curvature = safe_div(safe_div(fd - fb, d - b, f64::MAX) - slope, d - a, 0.0);
+
+    if curvature == 0.0 {
+        return secant_interpolate(a, b, fa, fb);
+    }
+
+    // Start from the end where the quadratic is convex towards the root.
+    let mut c = if curvature.signum() * fa.signum() > 0.0 {
+        a
+    } else {
+        b
+    };
+
+    for _ in 0..steps {
+        c -= safe_div(
+            fa + (slope + curvature * (c - b)) * (c - a),
+            slope + curvature * (2.0 * c - a - b),
+            1.0 + c - a,
+        );
+    }
+
+    if c <= a || c >= b {
+        secant_interpolate(a, b, fa, fb)
+    } else {
+        c
+    }
+}
+
+/// Inverse cubic interpolation through `a`, `b`, `d` and `e`.
+fn cubic_interpolate(bracket: &Bracket) -> f64 {
+    let Bracket {
+        a,
+        b,
+        d,
+        e,
+        fa,
+        fb,
+        fd,
+        fe,
+    } = *bracket;
+
+    let q11 = (d - e) * fd / (fe - fd);
+    let q21 = (b - d) * fb / (fd - fb);
+    let q31 = (a - b) * fa / (fb - fa);
+    let d21 = (b - d) * fd / (fd - fb);
+    let d31 = (a - b) * fb / (fb - fa);
+    let q22 = (d21 - q11) * fb / (fe - fb);
+    let q32 = (d31 - q21) * fa / (fd - fa);
+    let d32 = (d31 - q21) * fd / (fd - fa);
+    let q33 = (d32 - q22) * fa / (fe - fa);
+
+    let c = a + q31 + q32 + q33;
+
+    if c <= a || c >= b || !c.is_finite() {
+        quadratic_interpolate(bracket, 3)
+    } else {
+        c
+    }
+}
+
+impl<F> Rootfinder<F> for Toms748<F>
+where
+    F: Fn(f64) -> f64,
+{
+    fn value(&self, x: f64) -> f64 {
+        (self.function)(x)
+    }
+
+    fn derivative(&self, _: f64) -> f64 {
+        0.0
+    }
+
+    fn solve_impl(&mut self) -> f64 {
+        let (a, fa, b, fb) = if self.data.x_min < self.data.x_max {
+            (
+                self.data.x_min,
+                self.data.y_min,
+                self.data.x_max,
+                self.data.y_max,
+            )
+        } else {
+            (
+                self.data.x_max,
+                self.data.y_max,
+                self.data.x_min,
+                self.data.y_min,
+            )
+        };
+
+        // The points removed from the bracket are unknown at first: any
+        // distinct values with large function values make the
+        // interpolations fall back to lower order.
+        let mut bracket = Bracket {
+            a,
+            b,
+            d: 1e5,
+            e: 1e5,
+            fa,
+            fb,
+            fd: 1e5,
+            fe: 1e5,
+        };
+
+        // Start with a secant step, then a quadratic one.
+        if !self.done(&bracket) {
+            let c = secant_interpolate(a, b, fa, fb);
+            self.bracket(&mut bracket, c);
+        }
+        if !self.done(&bracket) {
+            let c = quadratic_interpolate(&bracket, 2);
+            self.bracket(&mut bracket, c);
+        }
+
+        while !self.done(&bracket) && self.data.iteration_count <= Self::MAX_ITERATIONS {
+            let width = bracket.b - bracket.a;
+
+            // Two interpolation steps, cubic unless two of the function
+            // values are too close to divide by their difference.
+            for steps in [2, 3] {
+                let Bracket { fa, fb, fd, fe, .. } = bracket;
+                let tiny = 32.0 * f64::MIN_POSITIVE;
+                let degenerate = [fa - fb, fa - fd, fa - fe, fb - fd, fb - fe, fd - fe]
+                    .iter()
+                    .any(|difference| difference.abs() < tiny);
+
+                let c = if degenerate {
+                    quadratic_interpolate(&bracket, steps)
+                } else {
+                    cubic_interpolate(&bracket)
+                };
+                self.bracket(&mut bracket, c);
+
+                if self.done(&bracket) {
+                    break;
+                }
+            }
+            if self.done(&bracket) {
+                break;
+            }
+
+            // Double-length secant step from the best end.
+            let (u, fu) = if bracket.fa.abs() < bracket.fb.abs() {
+                (bracket.a, bracket.fa)
+            } else {
+                (bracket.b, bracket.fb)
+            };
+            let mut c = u - 2.0 * (fu / (bracket.fb - bracket.fa)) * (bracket.b - bracket.a);
+            if (c - u).abs() > 0.5 * (bracket.b - bracket.a) || !c.is_finite() {
+                c = bracket.a + 0.5 * (bracket.b - bracket.a);
+            }
+            self.bracket(&mut bracket, c);
+            if self.done(&bracket) {
+                break;
+            }
+
+            // Bisect if the bracket did not shrink enough.
+            if bracket.b - bracket.a >= 0.5 * width {
+                let c = bracket.a + 0.5 * (bracket.b - bracket.a);
+                self.bracket(&mut bracket, c);
+            }
+        }
+
+        self.data.x_min = bracket.a;
+        self.data.y_min = bracket.fa;
+        self.data.x_max = bracket.b;
+        self.data.y_max = bracket.fb;
+
+        self.data.root = if bracket.fa.abs() <= bracket.fb.abs() {
+            bracket.a
+        } else {
+            bracket.b
+        };
+
+        self.data.root
+    }
+
+    fn solve(&mut self) -> f64 {
+        assert!(self.data.accuracy > 0., "accuracy must be positive");
+
+        self.data.accuracy = f64::max(self.data.accuracy, f64::EPSILON);
+
+        let growth_factor = 1.6;
+        let mut flipflop = -1;
+
+        self.data.root = self.guess;
+        self.data.y_max = self.value(self.data.root);
+
+        if RootfinderData::close(self.data.y_max, 0.0) {
+            return self.data.root;
+        } else if self.data.y_max > 0.0 {
+            self.data.x_min = self
+                .data
+                .enforce_bounds(self.data.root - self.data.stepsize);
+            self.data.y_min = self.value(self.data.x_min);
+            self.data.x_max = self.data.root;
+        } else {
+            self.data.x_min = self.data.root;
+            self.data.y_min = self.data.y_max;
+            self.data.x_max = self
+                .data
+                .enforce_bounds(self.data.root + self.data.stepsize);
+            self.data.y_max = self.value(self.data.x_max);
+        }
+
+        self.data.iteration_count = 2;
+
+        while self.data.iteration_count <= Self::MAX_ITERATIONS {
+            // Check if we can solve.
+            if self.data.y_min * self.data.y_max <= 0.0 {
+                if RootfinderData::close(self.data.y_min, 0.0) {
+                    return self.data.x_min;
+                }
+                if RootfinderData::close(self.data.y_max, 0.0) {
+                    return self.data.x_max;
+                }
+                self.data.root = 0.5 * (self.data.x_max + self.data.x_min);
+
+                return self.solve_impl();
+            }
+
+            // If we can't solve, adjust.
+            if self.data.y_min.abs() < self.data.y_max.abs() {
+                self.data.x_min = self.data.enforce_bounds(
+                    self.data.x_min + growth_factor * (self.data.x_min - self.data.x_max),
+                );
+                self.data.y_min = self.value(self.data.x_min);
+            } else if self.data.y_min.abs() > self.data.y_max.abs() {
+                self.data.x_max = self.data.enforce_bounds(
+                    self.data.x_max + growth_factor * (self.data.x_max - self.data.x_min),
+                );
+                self.data.y_max = self.value(self.data.x_max);
+            } else if flipflop == -1 {
+                self.data.x_min = self.data.enforce_bounds(
+                    self.data.x_min + growth_factor * (self.data.x_min - self.data.x_max),
+                );
+                self.data.y_min = self.value(self.data.x_min);
+                self.data.increment_evaluation_count();
+                flipflop = 1;
+            } else if flipflop == 1 {
+                self.data.x_max = self.data.enforce_bounds(
+                    self.data.x_max + growth_factor * (self.data.x_max - self.data.x_min),
+                );
+                self.data.y_max = self.value(self.data.x_max);
+                flipflop = -1;
+            }
+
+            self.data.increment_evaluation_count();
+        }
+
+        0.0
+    }
+}
+#[cfg(test)]
+mod TESTS_toms748_solver {
+    use super::*;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_toms748_solver() {
+        // f(x) = x^2 - 2
+        let f = |x: f64| x.powi(2) - 2.0;
+
+        let data = RootfinderData::new(1e-15, 1e-5, 0.0, 2.0, true);
+        let mut solver = Toms748::new(f, 1.0, data);
+        let root = solver.solve();
+        assert!((root - SQRT_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_toms748_solver_yield_to_maturity() {
+        // Yield of a 10 year 5% annual coupon bond priced at 92.5.
+        let price = |y: f64| {
+            (1..=10).map(|t| 5.0 / (1.0 + y).powi(t)).sum::<f64>() + 100.0 / (1.0 + y).powi(10)
+        };
+        let f = |y: f64| price(y) - 92.5;
+
+        let mut solver = Toms748::new(f, 0.05, RootfinderData::default());
+        let root = solver.solve();
+        assert!(f(root).abs() < 1e-10);
+        assert!((root - 0.060_199_742_218_751_71).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_toms748_solver_evaluations() {
+        // Fewer evaluations than Brent's method on a smooth function.
+        use crate::brent::Brent;
+        use std::cell::Cell;
+
+        let count = Cell::new(0);
+        let f = |x: f64| {
+            count.set(count.get() + 1);
+            x * x.exp() - 1.0
+        };
+
+        let data = RootfinderData::new(1e-14, 0.5, 0.0, 1.0, true);
+        let toms748 = Toms748::new(&f, 0.5, data).solve();
+        let toms748_count = count.replace(0);
+        let brent = Brent::new(&f, 0.5, data).solve();
+        let brent_count = count.get();
+
+        // Omega constant: x exp(x) = 1.
+        assert!((toms748 - 0.567_143_290_409_783_8).abs() < 1e-14);
+        assert!((brent - 0.567_143_290_409_783_8).abs() < 1e-14);
+        assert!(toms748_count <= brent_count);
+    }
+}
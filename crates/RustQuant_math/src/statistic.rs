@@ -8,6 +8,32 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use num::Float;
+use statrs::function::gamma::gamma_ur;
+use RustQuant_error::RustQuantError;
+
+/// Result of a statistical hypothesis test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HypothesisTest {
+    /// Value of the test statistic.
+    pub statistic: f64,
+
+    /// Probability of a statistic at least as extreme under the null
+    /// hypothesis.
+    pub p_value: f64,
+
+    /// Degrees of freedom of the (asymptotic) chi-squared distribution of
+    /// the statistic.
+    pub degrees_of_freedom: usize,
+}
+
+impl HypothesisTest {
+    /// Whether the null hypothesis is rejected at the `significance` level
+    /// (e.g. 0.05).
+    #[must_use]
+    pub fn rejects(&self, significance: f64) -> bool {
+        self.p_value < significance
+    }
+}
 
 /// Statistics trait for vectors of floating point numbers.
 pub trait Statistic<T: Float> {
@@ -46,11 +72,67 @@ pub trait Statistic<T: Float> {
     /// Calculate the correlation of two vectors.
     fn correlation(&self, other: &Self) -> T;
 
+    // HIGHER MOMENT FUNCTIONS ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     /// Calculate the skewness of a vector.
+    /// Simply a wrapper for `sample_skewness`.
     fn skewness(&self) -> T;
-
-    /// Calculate the kurtosis of a vector.
+    /// Calculate the sample (adjusted Fisher-Pearson) skewness of a vector.
+    fn sample_skewness(&self) -> f64;
+    /// Calculate the population skewness of a vector,
+    /// $m_3 / m_2^{3/2}$ with the central moments $m_k$.
+    fn population_skewness(&self) -> f64;
+
+    /// Calculate the excess kurtosis of a vector.
+    /// Simply a wrapper for `sample_kurtosis`.
     fn kurtosis(&self) -> T;
+    /// Calculate the sample (adjusted) excess kurtosis of a vector.
+    fn sample_kurtosis(&self) -> f64;
+    /// Calculate the population excess kurtosis of a vector,
+    /// $m_4 / m_2^2 - 3$ with the central moments $m_k$.
+    fn population_kurtosis(&self) -> f64;
+
+    // SERIAL DEPENDENCE AND NORMALITY ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    /// Calculate the autocorrelation function of a vector at lags
+    /// $0, 1, \dots$, `max_lag`:
+    /// $\rho_k = \sum_{t=k}^{n-1} (x_t - \bar{x})(x_{t-k} - \bar{x}) / \sum_{t=0}^{n-1} (x_t - \bar{x})^2$.
+    fn autocorrelation(&self, max_lag: usize) -> Vec<T>;
+
+    /// Ljung-Box test of the null hypothesis that the first `lags`
+    /// autocorrelations are zero:
+    /// $Q = n (n + 2) \sum_{k=1}^{h} \rho_k^2 / (n - k)$,
+    /// asymptotically chi-squared with $h$ degrees of freedom.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lags` is zero or not less than the length, or
+    /// the vector is constant.
+    fn ljung_box(&self, lags: usize) -> Result<HypothesisTest, RustQuantError>;
+
+    /// Jarque-Bera test of the null hypothesis of normality:
+    /// $JB = \frac{n}{6} \left(S^2 + \frac{K^2}{4}\right)$ with the
+    /// population skewness $S$ and excess kurtosis $K$, asymptotically
+    /// chi-squared with 2 degrees of freedom.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vector has fewer than two elements or is
+    /// constant.
+    fn jarque_bera(&self) -> Result<HypothesisTest, RustQuantError>;
+
+    // ROLLING WINDOW FUNCTIONS ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    /// Calculate a statistic over each window of `window` consecutive
+    /// elements, the first ending at element `window - 1`.
+    fn rolling<F>(&self, window: usize, statistic: F) -> Vec<T>
+    where
+        F: Fn(&Self) -> T;
+    /// Calculate the rolling mean of a vector.
+    fn rolling_mean(&self, window: usize) -> Vec<T>;
+    /// Calculate the rolling (sample) standard deviation of a vector.
+    fn rolling_standard_deviation(&self, window: usize) -> Vec<T>;
+    /// Calculate the rolling (sample) skewness of a vector.
+    fn rolling_skewness(&self, window: usize) -> Vec<T>;
+    /// Calculate the rolling (sample) excess kurtosis of a vector.
+    fn rolling_kurtosis(&self, window: usize) -> Vec<T>;
 
     /// Calculate the minimum value of a vector.
     fn min(&self) -> T;
@@ -174,6 +256,12 @@ impl Statistic<f64> for Vec<f64> {
     fn skewness(&self) -> f64 {
         assert!(self.len() > 2, "Vector must have at least three elements.");
 
+        self.sample_skewness()
+    }
+
+    fn sample_skewness(&self) -> f64 {
+        assert!(self.len() > 2, "Vector must have at least three elements.");
+
         let n = self.len() as f64;
         let mean = self.mean();
         let std_dev = self.standard_deviation();
@@ -187,9 +275,23 @@ impl Statistic<f64> for Vec<f64> {
         skew * n / ((n - 1.0) * (n - 2.0))
     }
 
+    fn population_skewness(&self) -> f64 {
+        assert!(self.len() > 1, "Vector must have at least two elements.");
+
+        let (m2, m3, _) = central_moments(self);
+
+        m3 / m2.powf(1.5)
+    }
+
     fn kurtosis(&self) -> f64 {
         assert!(self.len() > 3, "Vector must have at least four elements.");
 
+        self.sample_kurtosis()
+    }
+
+    fn sample_kurtosis(&self) -> f64 {
+        assert!(self.len() > 3, "Vector must have at least four elements.");
+
         let n = self.len() as f64;
         let mean = self.mean();
         let std_dev = self.standard_deviation();
@@ -204,6 +306,121 @@ impl Statistic<f64> for Vec<f64> {
             - 3.0 * (n - 1.0).powi(2) / ((n - 2.0) * (n - 3.0))
     }
 
+    fn population_kurtosis(&self) -> f64 {
+        assert!(self.len() > 1, "Vector must have at least two elements.");
+
+        let (m2, _, m4) = central_moments(self);
+
+        m4 / (m2 * m2) - 3.0
+    }
+
+    fn autocorrelation(&self, max_lag: usize) -> Vec<f64> {
+        assert!(
+            max_lag < self.len(),
+            "Maximum lag must be less than the length of the vector."
+        );
+
+        let mean = self.mean();
+        let deviations: Vec<f64> = self.iter().map(|x| x - mean).collect();
+        let total = deviations.iter().map(|d| d * d).sum::<f64>();
+
+        assert!(total > 0.0, "Vector must not be constant.");
+
+        (0..=max_lag)
+            .map(|k| {
+                deviations[k..]
+                    .iter()
+                    .zip(&deviations)
+                    .map(|(a, b)| a * b)
+                    .sum::<f64>()
+                    / total
+            })
+            .collect()
+    }
+
+    fn ljung_box(&self, lags: usize) -> Result<HypothesisTest, RustQuantError> {
+        let n = self.len();
+
+        if lags == 0 || lags >= n {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Ljung-Box test needs between 1 and {} lags, got {lags}.",
+                n.saturating_sub(1)
+            )));
+        }
+        if self.population_variance() == 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "Ljung-Box test needs a non-constant vector.".to_string(),
+            ));
+        }
+
+        let rho = self.autocorrelation(lags);
+        let n = n as f64;
+
+        let statistic = n
+            * (n + 2.0)
+            * (1..=lags)
+                .map(|k| rho[k] * rho[k] / (n - k as f64))
+                .sum::<f64>();
+
+        Ok(HypothesisTest {
+            statistic,
+            p_value: gamma_ur(0.5 * lags as f64, 0.5 * statistic),
+            degrees_of_freedom: lags,
+        })
+    }
+
+    fn jarque_bera(&self) -> Result<HypothesisTest, RustQuantError> {
+        if self.len() < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "Jarque-Bera test needs at least two elements.".to_string(),
+            ));
+        }
+        if self.population_variance() == 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "Jarque-Bera test needs a non-constant vector.".to_string(),
+            ));
+        }
+
+        let skewness = self.population_skewness();
+        let kurtosis = self.population_kurtosis();
+
+        let statistic =
+            self.len() as f64 / 6.0 * (skewness * skewness + 0.25 * kurtosis * kurtosis);
+
+        Ok(HypothesisTest {
+            statistic,
+            p_value: (-0.5 * statistic).exp(),
+            degrees_of_freedom: 2,
+        })
+    }
+
+    fn rolling<F>(&self, window: usize, statistic: F) -> Vec<f64>
+    where
+        F: Fn(&Self) -> f64,
+    {
+        assert!(window > 0, "Window must have at least one element.");
+
+        self.windows(window)
+            .map(|w| statistic(&w.to_vec()))
+            .collect()
+    }
+
+    fn rolling_mean(&self, window: usize) -> Vec<f64> {
+        self.rolling(window, Statistic::mean)
+    }
+
+    fn rolling_standard_deviation(&self, window: usize) -> Vec<f64> {
+        self.rolling(window, Statistic::standard_deviation)
+    }
+
+    fn rolling_skewness(&self, window: usize) -> Vec<f64> {
+        self.rolling(window, Statistic::skewness)
+    }
+
+    fn rolling_kurtosis(&self, window: usize) -> Vec<f64> {
+        self.rolling(window, Statistic::kurtosis)
+    }
+
     fn min(&self) -> f64 {
         assert!(!self.is_empty(), "Vector must have at least one element.");
 
@@ -283,6 +500,17 @@ impl Statistic<f64> for Vec<f64> {
     }
 }
 
+/// Second, third and fourth central moments, $m_k = \frac{1}{n} \sum (x - \bar{x})^k$.
+fn central_moments(x: &[f64]) -> (f64, f64, f64) {
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+
+    x.iter().fold((0.0, 0.0, 0.0), |(m2, m3, m4), x| {
+        let d = x - mean;
+        (m2 + d * d / n, m3 + d.powi(3) / n, m4 + d.powi(4) / n)
+    })
+}
+
 #[cfg(test)]
 mod tests_statistics {
 
@@ -462,17 +690,70 @@ mod tests_statistics {
         assert_approx_equal!(v.max(), 42.0, EPS);
     }
 
-    // #[test]
-    // fn test_skewness() {
-    //     let v = vec![1.0, 2.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
-    //     let skew = v.skewness();
-    //     assert_approx_equal!(skew, 0.2824751, 1e-6);
-    // }
+    #[test]
+    fn test_skewness() {
+        let v = vec![1.0, 2.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_approx_equal!(v.population_skewness(), 0.282_475_084_858_352_8, EPS);
+        assert_approx_equal!(v.skewness(), 0.352_308_329_279_950_6, EPS);
+    }
+
+    #[test]
+    fn test_kurtosis() {
+        let v = vec![1.0, 2.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_approx_equal!(v.population_kurtosis(), -0.648_174_683_901_432_2, EPS);
+        assert_approx_equal!(v.kurtosis(), 0.038_833_163_806_992_4, EPS);
+    }
+
+    #[test]
+    fn test_autocorrelation() {
+        let v = vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0, 7.0, 9.0, 10.0];
+        let rho = v.autocorrelation(3);
+
+        assert_approx_equal!(rho[0], 1.0, EPS);
+        assert_approx_equal!(rho[1], 0.578_787_878_787_878_8, EPS);
+        assert_approx_equal!(rho[2], 0.418_181_818_181_818_2, EPS);
+        assert_approx_equal!(rho[3], 0.166_666_666_666_666_7, EPS);
+    }
+
+    #[test]
+    fn test_ljung_box() {
+        let v = vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0, 7.0, 9.0, 10.0];
+        let test = v.ljung_box(2).unwrap();
+
+        assert_approx_equal!(test.statistic, 7.089_745_944_291_399, EPS);
+        assert_approx_equal!(test.p_value, 0.028_872_289_861_782_1, EPS);
+        assert_eq!(test.degrees_of_freedom, 2);
+        assert!(test.rejects(0.05));
+        assert!(!test.rejects(0.01));
+
+        assert!(v.ljung_box(0).is_err());
+        assert!(v.ljung_box(10).is_err());
+        assert!(vec![1.0; 5].ljung_box(1).is_err());
+    }
+
+    #[test]
+    fn test_jarque_bera() {
+        let v = vec![1.0, 2.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let test = v.jarque_bera().unwrap();
+
+        assert_approx_equal!(test.statistic, 0.246_433_038_371_218_67, EPS);
+        assert_approx_equal!(test.p_value, 0.884_072_223_238_630_2, EPS);
+        assert_eq!(test.degrees_of_freedom, 2);
+
+        assert!(vec![1.0].jarque_bera().is_err());
+        assert!(vec![2.0; 4].jarque_bera().is_err());
+    }
+
+    #[test]
+    fn test_rolling() {
+        let v = vec![1.0, 2.0, 3.0, 4.0, 6.0];
 
-    // #[test]
-    // fn test_kurtosis() {
-    //     let v = vec![1.0, 2.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
-    //     let kurt = v.kurtosis();
-    //     assert_approx_equal!(kurt, 2.351825, 1e-6);
-    // }
+        assert_eq!(v.rolling_mean(3), vec![2.0, 3.0, 13.0 / 3.0]);
+        assert_eq!(v.rolling_standard_deviation(2).len(), 4);
+        assert_approx_equal!(v.rolling_standard_deviation(2)[3], 2_f64.sqrt(), EPS);
+        assert_eq!(v.rolling(5, Statistic::max), vec![6.0]);
+        assert!(v.rolling_mean(6).is_empty());
+        assert_eq!(v.rolling_skewness(4)[0], 0.0);
+        assert_eq!(v.rolling_kurtosis(4).len(), 2);
+    }
 }
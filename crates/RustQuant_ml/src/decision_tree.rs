@@ -0,0 +1,694 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for classification and regression trees (CART).
+//!
+//! The tree is grown greedily: each node is split on the feature and
+//! threshold with the largest decrease in impurity (the Gini index for
+//! classification, the variance for regression), until a stopping rule is
+//! met.
+//!
+//! References:
+//!     - Classification and Regression Trees (Breiman, Friedman, Olshen, Stone 1984)
+//!     - Elements of Statistical Learning (Hastie, Tibshirani, Friedman 2009), chapter 9.2
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, seq::index::sample, Rng, SeedableRng};
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Learning task of a tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TreeTask {
+    /// Classification: the response vector holds class labels
+    /// (e.g. 0, 1, 2, read in as f64), and nodes are split by the Gini index.
+    Classification,
+    /// Regression: nodes are split by the variance of the response.
+    Regression,
+}
+
+/// Parameters (stopping rules) of a decision tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DecisionTreeParameters {
+    /// Learning task.
+    pub task: TreeTask,
+    /// Maximum depth of the tree (unlimited if `None`).
+    pub max_depth: Option<usize>,
+    /// Minimum number of samples in a node to split it.
+    pub min_samples_split: usize,
+    /// Minimum number of samples in each leaf.
+    pub min_samples_leaf: usize,
+    /// Number of features, drawn at random, considered at each split
+    /// (all of them if `None`).
+    pub max_features: Option<usize>,
+    /// Seed of the random draws of features (from entropy if `None`).
+    pub seed: Option<u64>,
+}
+
+/// Struct to hold the input data for a decision tree.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct DecisionTreeInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to data points, and each column is a different
+    /// feature of the data.
+    pub x: DMatrix<T>,
+    /// The response vector: class labels for classification,
+    /// or values for regression.
+    pub y: DVector<T>,
+}
+
+/// Struct to hold a fitted decision tree.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct DecisionTreeOutput<T> {
+    /// Learning task.
+    pub task: TreeTask,
+    /// Sorted class labels (empty for regression).
+    pub classes: Vec<T>,
+    /// Feature importances: the total decrease in impurity from the splits
+    /// on each feature, normalised to sum to one.
+    pub feature_importances: DVector<T>,
+    /// Nodes of the tree, the root first.
+    nodes: Vec<TreeNode>,
+}
+
+/// Node of a fitted tree.
+#[derive(Clone, Debug)]
+enum TreeNode {
+    /// Terminal node, with its prediction and class probabilities
+    /// (empty for regression).
+    Leaf { value: f64, probabilities: Vec<f64> },
+    /// Internal node: samples with `x[feature] <= threshold` go left.
+    Split {
+        feature: usize,
+        threshold: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+/// Best split of a node.
+struct Split {
+    feature: usize,
+    threshold: f64,
+    position: usize,
+    decrease: f64,
+}
+
+/// Recursive tree construction.
+struct TreeBuilder<'a, R: Rng> {
+    x: &'a DMatrix<f64>,
+    /// Class indices (as f64) for classification, values for regression.
+    targets: &'a [f64],
+    n_classes: usize,
+    parameters: &'a DecisionTreeParameters,
+    max_features: usize,
+    rng: R,
+    nodes: Vec<TreeNode>,
+    importances: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DecisionTreeParameters {
+    /// Fully grown tree (to pure leaves), considering all features.
+    #[must_use]
+    pub fn new(task: TreeTask) -> Self {
+        Self {
+            task,
+            max_depth: None,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            max_features: None,
+            seed: None,
+        }
+    }
+
+    /// Set the maximum depth.
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set the minimum number of samples to split a node.
+    #[must_use]
+    pub fn with_min_samples_split(mut self, min_samples_split: usize) -> Self {
+        self.min_samples_split = min_samples_split;
+        self
+    }
+
+    /// Set the minimum number of samples in a leaf.
+    #[must_use]
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+
+    /// Set the number of features considered at each split.
+    #[must_use]
+    pub fn with_max_features(mut self, max_features: usize) -> Self {
+        self.max_features = Some(max_features);
+        self
+    }
+
+    /// Set the seed of the random draws of features.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl DecisionTreeInput<f64> {
+    /// Create a new `DecisionTreeInput` struct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rows in x are not equal to the length of y.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>, y: DVector<f64>) -> Self {
+        assert_eq!(x.nrows(), y.len());
+
+        Self { x, y }
+    }
+
+    /// Grows a decision tree on the input data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is empty or not finite, or the
+    /// parameters are invalid.
+    pub fn fit(
+        &self,
+        parameters: &DecisionTreeParameters,
+    ) -> Result<DecisionTreeOutput<f64>, RustQuantError> {
+        validate_tree_input(&self.x, &self.y, parameters)?;
+
+        let (classes, targets) = encode_targets(&self.y, parameters.task);
+        let rng = parameters
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        let mut samples: Vec<usize> = (0..self.x.nrows()).collect();
+
+        Ok(grow_tree(
+            &self.x,
+            &targets,
+            classes,
+            &mut samples,
+            parameters,
+            parameters.max_features.unwrap_or(self.x.ncols()),
+            rng,
+        ))
+    }
+}
+
+impl DecisionTreeOutput<f64> {
+    /// Predicts the output for the given input data: the majority class of
+    /// the leaf for classification, or its mean for regression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input has fewer columns than the training data.
+    #[must_use]
+    pub fn predict(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        DVector::from_fn(input.nrows(), |i, _| match self.leaf(input, i) {
+            TreeNode::Leaf { value, .. } => *value,
+            TreeNode::Split { .. } => unreachable!(),
+        })
+    }
+
+    /// Predicts the class probabilities for the given input data: the
+    /// class frequencies of the leaf, in the order of `classes`. The matrix
+    /// has no columns for regression trees.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input has fewer columns than the training data.
+    #[must_use]
+    pub fn predict_proba(&self, input: &DMatrix<f64>) -> DMatrix<f64> {
+        DMatrix::from_fn(input.nrows(), self.classes.len(), |i, k| {
+            match self.leaf(input, i) {
+                TreeNode::Leaf { probabilities, .. } => probabilities[k],
+                TreeNode::Split { .. } => unreachable!(),
+            }
+        })
+    }
+
+    /// Depth of the tree (zero for a single leaf).
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        fn depth_from(nodes: &[TreeNode], node: usize) -> usize {
+            match nodes[node] {
+                TreeNode::Leaf { .. } => 0,
+                TreeNode::Split { left, right, .. } => {
+                    1 + depth_from(nodes, left).max(depth_from(nodes, right))
+                }
+            }
+        }
+
+        depth_from(&self.nodes, 0)
+    }
+
+    /// Number of leaves of the tree.
+    #[must_use]
+    pub fn n_leaves(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| matches!(node, TreeNode::Leaf { .. }))
+            .count()
+    }
+
+    /// The leaf reached by row `row` of the input.
+    fn leaf(&self, input: &DMatrix<f64>, row: usize) -> &TreeNode {
+        let mut node = &self.nodes[0];
+
+        while let TreeNode::Split {
+            feature,
+            threshold,
+            left,
+            right,
+        } = node
+        {
+            node = if input[(row, *feature)] <= *threshold {
+                &self.nodes[*left]
+            } else {
+                &self.nodes[*right]
+            };
+        }
+
+        node
+    }
+}
+
+impl<R: Rng> TreeBuilder<'_, R> {
+    /// Grows the subtree of the given samples and returns the index of its
+    /// root.
+    fn build(&mut self, samples: &mut [usize], depth: usize) -> usize {
+        let index = self.nodes.len();
+        let (value, probabilities) = self.leaf_value(samples);
+        self.nodes.push(TreeNode::Leaf {
+            value,
+            probabilities,
+        });
+
+        let can_split = samples.len() >= self.parameters.min_samples_split
+            && samples.len() >= 2 * self.parameters.min_samples_leaf
+            && self.parameters.max_depth.is_none_or(|max| depth < max);
+
+        let Some(split) = can_split.then(|| self.best_split(samples)).flatten() else {
+            return index;
+        };
+
+        self.importances[split.feature] += split.decrease;
+
+        samples.sort_by(|a, b| self.x[(*a, split.feature)].total_cmp(&self.x[(*b, split.feature)]));
+        let (left_samples, right_samples) = samples.split_at_mut(split.position);
+
+        let left = self.build(left_samples, depth + 1);
+        let right = self.build(right_samples, depth + 1);
+
+        self.nodes[index] = TreeNode::Split {
+            feature: split.feature,
+            threshold: split.threshold,
+            left,
+            right,
+        };
+
+        index
+    }
+
+    /// Prediction and class probabilities of a leaf.
+    fn leaf_value(&self, samples: &[usize]) -> (f64, Vec<f64>) {
+        let n = samples.len() as f64;
+
+        if self.n_classes == 0 {
+            let mean = samples.iter().map(|s| self.targets[*s]).sum::<f64>() / n;
+
+            return (mean, Vec::new());
+        }
+
+        let mut probabilities = vec![0.0; self.n_classes];
+        for s in samples {
+            probabilities[self.targets[*s] as usize] += 1.0 / n;
+        }
+
+        // Index of the majority class, the first on ties.
+        let class = probabilities
+            .iter()
+            .enumerate()
+            .fold(
+                (0, -1.0),
+                |best, (k, p)| if *p > best.1 { (k, *p) } else { best },
+            )
+            .0;
+
+        (class as f64, probabilities)
+    }
+
+    /// Impurity of a node times its number of samples: $n (1 - \sum p_k^2)$
+    /// from the class counts, or $\sum y^2 - (\sum y)^2 / n$ from the sums.
+    fn weighted_impurity(&self, n: f64, counts: &[f64], sum: f64, sum_squares: f64) -> f64 {
+        if self.n_classes == 0 {
+            sum_squares - sum * sum / n
+        } else {
+            n - counts.iter().map(|c| c * c).sum::<f64>() / n
+        }
+    }
+
+    /// The split with the largest decrease in impurity, if any decreases
+    /// it.
+    fn best_split(&mut self, samples: &mut [usize]) -> Option<Split> {
+        let n = samples.len();
+        let n_features = self.x.ncols();
+        let min_leaf = self.parameters.min_samples_leaf;
+
+        let features: Vec<usize> = if self.max_features < n_features {
+            sample(&mut self.rng, n_features, self.max_features).into_vec()
+        } else {
+            (0..n_features).collect()
+        };
+
+        // Statistics of the whole node.
+        let mut counts = vec![0.0; self.n_classes];
+        let (mut sum, mut sum_squares) = (0.0, 0.0);
+        for s in samples.iter() {
+            let y = self.targets[*s];
+            if self.n_classes == 0 {
+                sum += y;
+                sum_squares += y * y;
+            } else {
+                counts[y as usize] += 1.0;
+            }
+        }
+        let parent = self.weighted_impurity(n as f64, &counts, sum, sum_squares);
+
+        let mut best: Option<Split> = None;
+
+        for feature in features {
+            samples.sort_by(|a, b| self.x[(*a, feature)].total_cmp(&self.x[(*b, feature)]));
+
+            let mut left_counts = vec![0.0; self.n_classes];
+            let (mut left_sum, mut left_sum_squares) = (0.0, 0.0);
+
+            for position in 1..n {
+                let y = self.targets[samples[position - 1]];
+                if self.n_classes == 0 {
+                    left_sum += y;
+                    left_sum_squares += y * y;
+                } else {
+                    left_counts[y as usize] += 1.0;
+                }
+
+                let (below, above) = (
+                    self.x[(samples[position - 1], feature)],
+                    self.x[(samples[position], feature)],
+                );
+
+                if position < min_leaf || n - position < min_leaf || below == above {
+                    continue;
+                }
+
+                let right_counts: Vec<f64> = counts
+                    .iter()
+                    .zip(&left_counts)
+                    .map(|(c, l)| c - l)
+                    .collect();
+                let children = self.weighted_impurity(
+                    position as f64,
+                    &left_counts,
+                    left_sum,
+                    left_sum_squares,
+                ) + self.weighted_impurity(
+                    (n - position) as f64,
+                    &right_counts,
+                    sum - left_sum,
+                    sum_squares - left_sum_squares,
+                );
+                let decrease = parent - children;
+
+                if decrease > 1e-12 * parent.abs().max(1.0)
+                    && best.as_ref().is_none_or(|b| decrease > b.decrease)
+                {
+                    best = Some(Split {
+                        feature,
+                        threshold: 0.5 * (below + above),
+                        position,
+                        decrease,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks the training data and the parameters of a tree.
+pub(crate) fn validate_tree_input(
+    x: &DMatrix<f64>,
+    y: &DVector<f64>,
+    parameters: &DecisionTreeParameters,
+) -> Result<(), RustQuantError> {
+    if x.nrows() == 0 || x.ncols() == 0 {
+        return Err(RustQuantError::InvalidArgument(
+            "The input data should not be empty.".to_string(),
+        ));
+    }
+
+    if x.nrows() != y.len() {
+        return Err(RustQuantError::InvalidArgument(
+            "The number of rows in the data matrix should match the length of the response vector."
+                .to_string(),
+        ));
+    }
+
+    if x.iter().chain(y.iter()).any(|v| !v.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "The input data should be finite.".to_string(),
+        ));
+    }
+
+    if parameters.min_samples_leaf == 0 || parameters.min_samples_split < 2 {
+        return Err(RustQuantError::InvalidArgument(
+            "A leaf needs at least one sample, and a split at least two.".to_string(),
+        ));
+    }
+
+    if parameters
+        .max_features
+        .is_some_and(|m| m == 0 || m > x.ncols())
+    {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "The number of features per split should be between 1 and {}.",
+            x.ncols()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Sorted class labels and the class index of each sample for
+/// classification, or no classes and the response for regression.
+pub(crate) fn encode_targets(y: &DVector<f64>, task: TreeTask) -> (Vec<f64>, Vec<f64>) {
+    match task {
+        TreeTask::Regression => (Vec::new(), y.iter().copied().collect()),
+        TreeTask::Classification => {
+            let mut classes: Vec<f64> = y.iter().copied().collect();
+            classes.sort_by(f64::total_cmp);
+            classes.dedup();
+
+            let targets = y
+                .iter()
+                .map(|label| classes.partition_point(|c| c < label) as f64)
+                .collect();
+
+            (classes, targets)
+        }
+    }
+}
+
+/// Grows a tree on the given samples (which may repeat, for bootstrap
+/// samples) of the encoded targets.
+pub(crate) fn grow_tree<R: Rng>(
+    x: &DMatrix<f64>,
+    targets: &[f64],
+    classes: Vec<f64>,
+    samples: &mut [usize],
+    parameters: &DecisionTreeParameters,
+    max_features: usize,
+    rng: R,
+) -> DecisionTreeOutput<f64> {
+    let mut builder = TreeBuilder {
+        x,
+        targets,
+        n_classes: classes.len(),
+        parameters,
+        max_features,
+        rng,
+        nodes: Vec::new(),
+        importances: vec![0.0; x.ncols()],
+    };
+
+    builder.build(samples, 0);
+
+    // Class indices to labels.
+    if !classes.is_empty() {
+        for node in &mut builder.nodes {
+            if let TreeNode::Leaf { value, .. } = node {
+                *value = classes[*value as usize];
+            }
+        }
+    }
+
+    let total: f64 = builder.importances.iter().sum();
+    let feature_importances = DVector::from_iterator(
+        x.ncols(),
+        builder
+            .importances
+            .iter()
+            .map(|i| if total > 0.0 { i / total } else { 0.0 }),
+    );
+
+    DecisionTreeOutput {
+        task: parameters.task,
+        classes,
+        feature_importances,
+        nodes: builder.nodes,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_decision_tree {
+    use super::*;
+    use nalgebra::dmatrix;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_classification_tree() {
+        // Class 0 for a negative first feature, else class 1 for a negative
+        // second feature, else class 2.
+        let x = dmatrix![
+            -1.0, -1.0;
+            -2.0, 1.0;
+            -1.5, 2.0;
+            1.0, -1.0;
+            2.0, -2.0;
+            1.0, 1.0;
+            2.0, 2.0;
+            1.5, 0.5
+        ];
+        let y = DVector::from_vec(vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 2.0]);
+
+        let tree = DecisionTreeInput::new(x.clone(), y.clone())
+            .fit(&DecisionTreeParameters::new(TreeTask::Classification))
+            .unwrap();
+
+        assert_eq!(tree.predict(&x), y);
+        assert_eq!(tree.classes, vec![0.0, 1.0, 2.0]);
+        assert_eq!((tree.depth(), tree.n_leaves()), (2, 3));
+
+        let probabilities = tree.predict_proba(&dmatrix![1.5, 1.5; -1.5, 1.5]);
+        assert_eq!(probabilities, dmatrix![0.0, 0.0, 1.0; 1.0, 0.0, 0.0]);
+
+        // Gini decreases: 2.85 at the root, 2.4 for the second split.
+        assert_approx_equal!(tree.feature_importances[0], 2.85 / 5.25, 1e-12);
+        assert_approx_equal!(tree.feature_importances[1], 2.4 / 5.25, 1e-12);
+    }
+
+    #[test]
+    fn test_regression_tree_step() {
+        // Step function of the first feature, with an irrelevant second
+        // feature.
+        let n = 40;
+        let x = DMatrix::from_fn(n, 2, |i, j| {
+            if j == 0 {
+                i as f64 / n as f64
+            } else {
+                ((i * 7) % 11) as f64
+            }
+        });
+        let y = DVector::from_fn(n, |i, _| if x[(i, 0)] < 0.5 { 1.0 } else { 3.0 });
+
+        let tree = DecisionTreeInput::new(x.clone(), y.clone())
+            .fit(&DecisionTreeParameters::new(TreeTask::Regression))
+            .unwrap();
+
+        assert_eq!(tree.depth(), 1);
+        assert_eq!(tree.predict(&x), y);
+        assert_approx_equal!(tree.feature_importances[0], 1.0, 1e-12);
+        assert_approx_equal!(tree.feature_importances[1], 0.0, 1e-12);
+        assert!(tree.predict_proba(&x).is_empty());
+    }
+
+    #[test]
+    fn test_tree_stopping_rules() {
+        let n = 64;
+        let x = DMatrix::from_fn(n, 1, |i, _| i as f64);
+        let y = DVector::from_fn(n, |i, _| (i as f64).sin());
+
+        let input = DecisionTreeInput::new(x.clone(), y);
+
+        let shallow = input
+            .fit(&DecisionTreeParameters::new(TreeTask::Regression).with_max_depth(3))
+            .unwrap();
+        assert_eq!(shallow.depth(), 3);
+        assert!(shallow.n_leaves() <= 8);
+
+        let coarse = input
+            .fit(&DecisionTreeParameters::new(TreeTask::Regression).with_min_samples_leaf(10))
+            .unwrap();
+        assert!(coarse.n_leaves() <= n / 10);
+
+        // The mean of a leaf is a prediction for all of its samples.
+        let stump = input
+            .fit(&DecisionTreeParameters::new(TreeTask::Regression).with_max_depth(0))
+            .unwrap();
+        let mean = input.y.mean();
+        assert!(stump.predict(&x).iter().all(|p| (p - mean).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_tree_invalid_input() {
+        let x = dmatrix![1.0, 2.0; 3.0, 4.0];
+        let parameters = DecisionTreeParameters::new(TreeTask::Classification);
+
+        let input = DecisionTreeInput {
+            x: x.clone(),
+            y: DVector::from_vec(vec![0.0, 1.0, 1.0]),
+        };
+        assert!(input.fit(&parameters).is_err());
+
+        let input = DecisionTreeInput::new(x, DVector::from_vec(vec![0.0, f64::NAN]));
+        assert!(input.fit(&parameters).is_err());
+
+        let input = DecisionTreeInput::new(dmatrix![1.0; 2.0], DVector::from_vec(vec![0.0, 1.0]));
+        assert!(input.fit(&parameters.with_max_features(2)).is_err());
+        assert!(input.fit(&parameters.with_min_samples_leaf(0)).is_err());
+    }
+}
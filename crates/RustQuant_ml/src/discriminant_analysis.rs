@@ -0,0 +1,480 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for Gaussian generative classifiers: Gaussian naive Bayes, and
+//! linear and quadratic discriminant analysis (LDA and QDA).
+//!
+//! Each class $k$ has a prior probability $\pi_k$ and a Gaussian density
+//! $N(\mu_k, \Sigma_k)$ of the features, and points are assigned to the
+//! class with the largest posterior probability,
+//!
+//! $$
+//! P(k \mid x) \propto \pi_k \, N(x; \mu_k, \Sigma_k).
+//! $$
+//!
+//! The models differ in the covariance matrices:
+//!     - naive Bayes: diagonal $\Sigma_k$ (independent features),
+//!     - LDA: a single covariance $\Sigma$ pooled over the classes (linear
+//!       decision boundaries),
+//!     - QDA: one covariance per class (quadratic decision boundaries).
+//!
+//! References:
+//!     - Elements of Statistical Learning (Hastie, Tibshirani, Friedman 2009), chapters 4.3 and 6.6.3
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Covariance structure of the class densities.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DiscriminantModel {
+    /// Gaussian naive Bayes: per-class variances, estimated by maximum
+    /// likelihood (divisor $n_k$).
+    GaussianNaiveBayes,
+    /// Linear discriminant analysis: one covariance pooled over the classes
+    /// (divisor $n - K$).
+    Linear,
+    /// Quadratic discriminant analysis: per-class covariances
+    /// (divisor $n_k - 1$).
+    Quadratic,
+}
+
+/// Parameters of a discriminant analysis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscriminantAnalysisParameters {
+    /// Covariance structure.
+    pub model: DiscriminantModel,
+    /// Prior probabilities of the classes, in sorted order of the labels
+    /// (the class frequencies if `None`). They are normalised to sum to one.
+    pub priors: Option<Vec<f64>>,
+    /// Shrinkage $\lambda \in [0, 1]$ of each covariance matrix towards a
+    /// multiple of the identity:
+    /// $(1 - \lambda) \Sigma + \lambda \frac{\operatorname{tr} \Sigma}{p} I$.
+    pub shrinkage: f64,
+}
+
+/// Struct to hold the input data for a discriminant analysis.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct DiscriminantAnalysisInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to data points, and each column is a different
+    /// feature of the data.
+    pub x: DMatrix<T>,
+    /// The response vector: class labels (e.g. 0, 1, 2, read in as f64).
+    pub y: DVector<T>,
+}
+
+/// Struct to hold a fitted discriminant analysis.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct DiscriminantAnalysisOutput<T> {
+    /// Covariance structure.
+    pub model: DiscriminantModel,
+    /// Sorted class labels.
+    pub classes: Vec<T>,
+    /// Prior probabilities of the classes.
+    pub priors: DVector<T>,
+    /// Means of the classes, one per row.
+    pub means: DMatrix<T>,
+    /// Covariance matrices of the classes (all equal for LDA, and diagonal
+    /// for naive Bayes).
+    pub covariances: Vec<DMatrix<T>>,
+    /// Lower Cholesky factors of the covariance matrices.
+    cholesky_factors: Vec<DMatrix<T>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DiscriminantAnalysisParameters {
+    /// Parameters of the given model, with the class frequencies as priors
+    /// and no shrinkage.
+    #[must_use]
+    pub fn new(model: DiscriminantModel) -> Self {
+        Self {
+            model,
+            priors: None,
+            shrinkage: 0.0,
+        }
+    }
+
+    /// Set the prior probabilities of the classes.
+    #[must_use]
+    pub fn with_priors(mut self, priors: Vec<f64>) -> Self {
+        self.priors = Some(priors);
+        self
+    }
+
+    /// Set the shrinkage of the covariance matrices.
+    #[must_use]
+    pub fn with_shrinkage(mut self, shrinkage: f64) -> Self {
+        self.shrinkage = shrinkage;
+        self
+    }
+}
+
+impl DiscriminantAnalysisInput<f64> {
+    /// Create a new `DiscriminantAnalysisInput` struct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rows in x are not equal to the length of y.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>, y: DVector<f64>) -> Self {
+        assert_eq!(x.nrows(), y.len());
+
+        Self { x, y }
+    }
+
+    /// Estimates the priors, means and covariances of the classes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is empty or not finite, there are fewer
+    /// than two classes (or too few points per class for the covariance
+    /// estimates), the priors or shrinkage are invalid, or a covariance
+    /// matrix is singular (which shrinkage can fix).
+    pub fn fit(
+        &self,
+        parameters: &DiscriminantAnalysisParameters,
+    ) -> Result<DiscriminantAnalysisOutput<f64>, RustQuantError> {
+        let (n, p) = self.x.shape();
+
+        if n == 0 || p == 0 || n != self.y.len() {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be non-empty, with one response per row.".to_string(),
+            ));
+        }
+
+        if self.x.iter().chain(self.y.iter()).any(|v| !v.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be finite.".to_string(),
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&parameters.shrinkage) {
+            return Err(RustQuantError::InvalidArgument(
+                "The shrinkage should be in [0, 1].".to_string(),
+            ));
+        }
+
+        let mut classes: Vec<f64> = self.y.iter().copied().collect();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        let k = classes.len();
+
+        let members: Vec<Vec<usize>> = classes
+            .iter()
+            .map(|c| (0..n).filter(|i| self.y[*i] == *c).collect())
+            .collect();
+
+        let minimum = match parameters.model {
+            DiscriminantModel::GaussianNaiveBayes | DiscriminantModel::Linear => 1,
+            DiscriminantModel::Quadratic => 2,
+        };
+
+        if k < 2 || members.iter().any(|m| m.len() < minimum) || n <= k {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Discriminant analysis needs at least two classes, with at least {minimum} point(s) each, got {k} classes."
+            )));
+        }
+
+        let priors = match &parameters.priors {
+            None => DVector::from_iterator(k, members.iter().map(|m| m.len() as f64 / n as f64)),
+            Some(priors) => {
+                if priors.len() != k || priors.iter().any(|p| !p.is_finite() || *p <= 0.0) {
+                    return Err(RustQuantError::InvalidArgument(format!(
+                        "There should be {k} positive prior probabilities."
+                    )));
+                }
+                let total: f64 = priors.iter().sum();
+                DVector::from_iterator(k, priors.iter().map(|p| p / total))
+            }
+        };
+
+        let means = DMatrix::from_fn(k, p, |c, j| {
+            members[c].iter().map(|i| self.x[(*i, j)]).sum::<f64>() / members[c].len() as f64
+        });
+
+        // Scatter matrices of the classes about their means.
+        let scatters: Vec<DMatrix<f64>> = members
+            .iter()
+            .enumerate()
+            .map(|(c, m)| {
+                let centred =
+                    DMatrix::from_fn(m.len(), p, |r, j| self.x[(m[r], j)] - means[(c, j)]);
+                centred.transpose() * centred
+            })
+            .collect();
+
+        let covariances: Vec<DMatrix<f64>> = match parameters.model {
+            DiscriminantModel::GaussianNaiveBayes => scatters
+                .iter()
+                .zip(members.iter())
+                .map(|(s, m)| DMatrix::from_diagonal(&(s.diagonal() / m.len() as f64)))
+                .collect(),
+            DiscriminantModel::Linear => {
+                let pooled = scatters
+                    .iter()
+                    .fold(DMatrix::zeros(p, p), |total, s| total + s)
+                    / (n - k) as f64;
+                vec![pooled; k]
+            }
+            DiscriminantModel::Quadratic => scatters
+                .iter()
+                .zip(members.iter())
+                .map(|(s, m)| s / (m.len() - 1) as f64)
+                .collect(),
+        };
+
+        let covariances: Vec<DMatrix<f64>> = covariances
+            .into_iter()
+            .map(|sigma| {
+                let target = sigma.trace() / p as f64;
+                sigma * (1.0 - parameters.shrinkage)
+                    + DMatrix::identity(p, p) * (parameters.shrinkage * target)
+            })
+            .collect();
+
+        // Factors with a (numerically) zero pivot are singular too.
+        let cholesky_factors = covariances
+            .iter()
+            .map(|sigma| {
+                sigma
+                    .clone()
+                    .cholesky()
+                    .map(|c| c.l())
+                    .filter(|l| l.diagonal().min() > f64::EPSILON.sqrt() * l.diagonal().max())
+                    .ok_or_else(|| {
+                        RustQuantError::ComputationError(
+                            "A covariance matrix is singular; consider adding shrinkage."
+                                .to_string(),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DiscriminantAnalysisOutput {
+            model: parameters.model,
+            classes,
+            priors,
+            means,
+            covariances,
+            cholesky_factors,
+        })
+    }
+}
+
+impl DiscriminantAnalysisOutput<f64> {
+    /// Predicts the class labels of the given input data: the classes with
+    /// the largest posterior probabilities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the training
+    /// data.
+    #[must_use]
+    pub fn predict(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        let scores = self.log_joint(input);
+
+        DVector::from_fn(input.nrows(), |i, _| {
+            self.classes[scores.row(i).transpose().argmax().0]
+        })
+    }
+
+    /// Predicts the posterior probabilities of the classes for the given
+    /// input data, one row per point, in the order of `classes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the training
+    /// data.
+    #[must_use]
+    pub fn predict_proba(&self, input: &DMatrix<f64>) -> DMatrix<f64> {
+        let mut scores = self.log_joint(input);
+
+        for mut row in scores.row_iter_mut() {
+            let max = row.max();
+            row.apply(|s| *s = (*s - max).exp());
+            let total = row.sum();
+            row /= total;
+        }
+
+        scores
+    }
+
+    /// Log of the joint densities $\pi_k N(x; \mu_k, \Sigma_k)$ of each
+    /// point (row) and class (column).
+    fn log_joint(&self, input: &DMatrix<f64>) -> DMatrix<f64> {
+        let p = self.means.ncols();
+        assert_eq!(input.ncols(), p);
+
+        let log_normaliser = 0.5 * p as f64 * (2.0 * std::f64::consts::PI).ln();
+
+        DMatrix::from_fn(input.nrows(), self.classes.len(), |i, c| {
+            let l = &self.cholesky_factors[c];
+            let centred = (input.row(i) - self.means.row(c)).transpose();
+            let whitened = l
+                .solve_lower_triangular(&centred)
+                .expect("Cholesky factors have a positive diagonal.");
+            let half_log_determinant: f64 = l.diagonal().iter().map(|d| d.ln()).sum();
+
+            self.priors[c].ln()
+                - log_normaliser
+                - half_log_determinant
+                - 0.5 * whitened.norm_squared()
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_discriminant_analysis {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use RustQuant_utils::assert_approx_equal;
+
+    /// Normal density.
+    fn normal_pdf(x: f64, mean: f64, variance: f64) -> f64 {
+        (-(x - mean).powi(2) / (2.0 * variance)).exp()
+            / (2.0 * std::f64::consts::PI * variance).sqrt()
+    }
+
+    #[test]
+    fn test_linear_discriminant_analysis() {
+        // Class means 0 and 2, pooled variance (2 + 2) / (6 - 2) = 1: the
+        // log-odds of class 1 are 2x - 2.
+        let x = dmatrix![-1.0; 0.0; 1.0; 1.0; 2.0; 3.0];
+        let y = dvector![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+
+        let lda = DiscriminantAnalysisInput::new(x, y)
+            .fit(&DiscriminantAnalysisParameters::new(
+                DiscriminantModel::Linear,
+            ))
+            .unwrap();
+
+        assert_eq!(lda.means, dmatrix![0.0; 2.0]);
+        assert_approx_equal!(lda.covariances[0][(0, 0)], 1.0, 1e-15);
+        assert_approx_equal!(lda.priors[1], 0.5, 1e-15);
+
+        let proba = lda.predict_proba(&dmatrix![0.0; 1.0; 3.0]);
+        assert_approx_equal!(proba[(0, 1)], 1.0 / (1.0 + 2.0_f64.exp()), 1e-12);
+        assert_approx_equal!(proba[(1, 1)], 0.5, 1e-12);
+        assert_approx_equal!(proba[(2, 1)], 1.0 / (1.0 + (-4.0_f64).exp()), 1e-12);
+        assert_approx_equal!(proba.row(0).sum(), 1.0, 1e-12);
+
+        assert_eq!(lda.predict(&dmatrix![-0.5; 1.5]), dvector![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_quadratic_discriminant_analysis_and_naive_bayes() {
+        // Class 0: mean 0, variance 1 (ML 2/3). Class 1: mean 2, variance
+        // 16/5 (ML 8/3). Priors 1/3 and 2/3.
+        let x = dmatrix![-1.0; 0.0; 1.0; 0.0; 0.0; 2.0; 2.0; 4.0; 4.0];
+        let y = dvector![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let input = DiscriminantAnalysisInput::new(x, y);
+        let point = dmatrix![0.5];
+
+        let qda = input
+            .fit(&DiscriminantAnalysisParameters::new(
+                DiscriminantModel::Quadratic,
+            ))
+            .unwrap();
+        let a = normal_pdf(0.5, 0.0, 1.0) / 3.0;
+        let b = 2.0 * normal_pdf(0.5, 2.0, 3.2) / 3.0;
+        assert_approx_equal!(qda.covariances[1][(0, 0)], 3.2, 1e-12);
+        assert_approx_equal!(qda.predict_proba(&point)[(0, 0)], a / (a + b), 1e-12);
+
+        let nb = input
+            .fit(&DiscriminantAnalysisParameters::new(
+                DiscriminantModel::GaussianNaiveBayes,
+            ))
+            .unwrap();
+        let a = normal_pdf(0.5, 0.0, 2.0 / 3.0) / 3.0;
+        let b = 2.0 * normal_pdf(0.5, 2.0, 8.0 / 3.0) / 3.0;
+        assert_approx_equal!(nb.covariances[1][(0, 0)], 8.0 / 3.0, 1e-12);
+        assert_approx_equal!(nb.predict_proba(&point)[(0, 0)], a / (a + b), 1e-12);
+
+        // Equal priors move the point towards class 0.
+        let equal = input
+            .fit(
+                &DiscriminantAnalysisParameters::new(DiscriminantModel::GaussianNaiveBayes)
+                    .with_priors(vec![1.0, 1.0]),
+            )
+            .unwrap();
+        assert!(equal.predict_proba(&point)[(0, 0)] > nb.predict_proba(&point)[(0, 0)]);
+    }
+
+    #[test]
+    fn test_discriminant_analysis_regimes() {
+        // Three volatility regimes of (return, absolute return) features.
+        let x = dmatrix![
+            0.010, 0.010; 0.012, 0.012; 0.008, 0.008; 0.011, 0.011; 0.009, 0.009;
+            -0.030, 0.030; 0.035, 0.035; -0.040, 0.040; 0.025, 0.025; -0.028, 0.028;
+            -0.080, 0.080; -0.090, 0.090; 0.070, 0.070; -0.100, 0.100; 0.085, 0.085
+        ];
+        let y = DVector::from_fn(15, |i, _| (i / 5) as f64);
+        let input = DiscriminantAnalysisInput::new(x.clone(), y.clone());
+
+        for model in [
+            DiscriminantModel::GaussianNaiveBayes,
+            DiscriminantModel::Linear,
+            DiscriminantModel::Quadratic,
+        ] {
+            let fitted = input
+                .fit(&DiscriminantAnalysisParameters::new(model).with_shrinkage(0.1))
+                .unwrap();
+
+            assert_eq!(fitted.classes, vec![0.0, 1.0, 2.0]);
+            assert_eq!(fitted.predict(&x), y);
+
+            let proba = fitted.predict_proba(&x);
+            assert_eq!(proba.shape(), (15, 3));
+            for row in proba.row_iter() {
+                assert_approx_equal!(row.sum(), 1.0, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_discriminant_analysis_invalid_input() {
+        let x = dmatrix![0.0, 1.0; 1.0, 2.0; 2.0, 3.0; 3.0, 4.0];
+        let y = dvector![0.0, 0.0, 1.0, 1.0];
+        let input = DiscriminantAnalysisInput::new(x.clone(), y);
+        let lda = DiscriminantAnalysisParameters::new(DiscriminantModel::Linear);
+
+        // Collinear features make the covariance singular, unless shrunk.
+        assert!(input.fit(&lda).is_err());
+        assert!(input.fit(&lda.clone().with_shrinkage(0.5)).is_ok());
+
+        assert!(input.fit(&lda.clone().with_shrinkage(1.5)).is_err());
+        assert!(input.fit(&lda.clone().with_priors(vec![1.0])).is_err());
+        assert!(input
+            .fit(&lda.clone().with_priors(vec![1.0, -1.0]))
+            .is_err());
+        assert!(
+            DiscriminantAnalysisInput::new(x, dvector![0.0, 0.0, 0.0, 0.0])
+                .fit(&lda)
+                .is_err()
+        );
+    }
+}
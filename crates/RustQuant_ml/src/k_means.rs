@@ -0,0 +1,369 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for k-means clustering.
+//!
+//! Lloyd's algorithm alternates between assigning each point to its nearest
+//! centroid and moving each centroid to the mean of its points, which
+//! decreases the within-cluster sum of squares (the inertia) to a local
+//! minimum. The centroids are initialised by k-means++, and the best of
+//! several initialisations is kept.
+//!
+//! References:
+//!     - k-means++: The Advantages of Careful Seeding (Arthur, Vassilvitskii 2007)
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::DMatrix;
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parameters of k-means clustering.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KMeansParameters {
+    /// Number of clusters.
+    pub k: usize,
+    /// Maximum number of iterations of each run.
+    pub max_iterations: usize,
+    /// Convergence tolerance on the largest move of a centroid.
+    pub tolerance: f64,
+    /// Number of runs from different initial centroids.
+    pub n_init: usize,
+    /// Seed of the initialisations (from entropy if `None`).
+    pub seed: Option<u64>,
+}
+
+/// Struct to hold the input data for k-means clustering.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct KMeansInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to data points, and each column is a different
+    /// feature of the data.
+    pub x: DMatrix<T>,
+}
+
+/// Struct to hold the output of k-means clustering.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct KMeansOutput<T> {
+    /// Centroids of the clusters, one per row.
+    pub centroids: DMatrix<T>,
+    /// Cluster of each data point.
+    pub labels: Vec<usize>,
+    /// Within-cluster sum of squared distances to the centroids.
+    pub inertia: T,
+    /// Number of iterations of the best run.
+    pub iterations: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl KMeansParameters {
+    /// `k` clusters, with at most 300 iterations of each of 10 runs.
+    #[must_use]
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            max_iterations: 300,
+            tolerance: 1e-8,
+            n_init: 10,
+            seed: None,
+        }
+    }
+
+    /// Set the maximum number of iterations.
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the convergence tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the number of runs.
+    #[must_use]
+    pub fn with_n_init(mut self, n_init: usize) -> Self {
+        self.n_init = n_init;
+        self
+    }
+
+    /// Set the seed of the initialisations.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl KMeansInput<f64> {
+    /// Create a new `KMeansInput` struct.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>) -> Self {
+        Self { x }
+    }
+
+    /// Clusters the input data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not finite, there are fewer data
+    /// points than clusters, or no clusters or runs.
+    pub fn fit(&self, parameters: &KMeansParameters) -> Result<KMeansOutput<f64>, RustQuantError> {
+        if self.x.iter().any(|v| !v.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be finite.".to_string(),
+            ));
+        }
+
+        if parameters.k == 0 || parameters.n_init == 0 || self.x.ncols() == 0 {
+            return Err(RustQuantError::InvalidArgument(
+                "k-means needs at least one cluster, run and feature.".to_string(),
+            ));
+        }
+
+        if self.x.nrows() < parameters.k {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Cannot form {} clusters from {} data points.",
+                parameters.k,
+                self.x.nrows()
+            )));
+        }
+
+        let mut rng = parameters
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
+        let mut best: Option<KMeansOutput<f64>> = None;
+
+        for _ in 0..parameters.n_init {
+            let run = self.lloyd(self.initialise(parameters.k, &mut rng), parameters);
+
+            if best.as_ref().is_none_or(|b| run.inertia < b.inertia) {
+                best = Some(run);
+            }
+        }
+
+        Ok(best.expect("At least one run."))
+    }
+
+    /// k-means++ initial centroids: the first is a uniformly drawn point,
+    /// and each next one a point drawn with probability proportional to
+    /// its squared distance to the nearest centroid so far.
+    fn initialise<R: Rng>(&self, k: usize, rng: &mut R) -> DMatrix<f64> {
+        let (n, p) = self.x.shape();
+        let mut centroids = DMatrix::zeros(k, p);
+        centroids.set_row(0, &self.x.row(rng.gen_range(0..n)));
+
+        let mut distances: Vec<f64> = (0..n)
+            .map(|i| (self.x.row(i) - centroids.row(0)).norm_squared())
+            .collect();
+
+        for c in 1..k {
+            // All points coincide with a centroid: any point will do.
+            let next = match WeightedIndex::new(&distances) {
+                Ok(weights) => weights.sample(rng),
+                Err(_) => rng.gen_range(0..n),
+            };
+            centroids.set_row(c, &self.x.row(next));
+
+            for (i, distance) in distances.iter_mut().enumerate() {
+                *distance = distance.min((self.x.row(i) - centroids.row(c)).norm_squared());
+            }
+        }
+
+        centroids
+    }
+
+    /// Lloyd's iterations from the given centroids.
+    fn lloyd(
+        &self,
+        mut centroids: DMatrix<f64>,
+        parameters: &KMeansParameters,
+    ) -> KMeansOutput<f64> {
+        let (n, p) = self.x.shape();
+        let k = centroids.nrows();
+        let mut iterations = 0;
+
+        let (mut labels, mut distances) = assign(&self.x, &centroids);
+
+        while iterations < parameters.max_iterations {
+            iterations += 1;
+
+            let mut sums = DMatrix::zeros(k, p);
+            let mut counts = vec![0_usize; k];
+            for i in 0..n {
+                let mut row = sums.row_mut(labels[i]);
+                row += self.x.row(i);
+                counts[labels[i]] += 1;
+            }
+
+            let mut updated = centroids.clone();
+            for (c, count) in counts.iter().enumerate() {
+                if *count > 0 {
+                    updated.set_row(c, &(sums.row(c) / *count as f64));
+                } else {
+                    // Empty cluster: move it to the point farthest from its
+                    // centroid.
+                    let farthest = (0..n)
+                        .max_by(|a, b| distances[*a].total_cmp(&distances[*b]))
+                        .expect("Non-empty data.");
+                    updated.set_row(c, &self.x.row(farthest));
+                    distances[farthest] = 0.0;
+                }
+            }
+
+            let shift = (0..k)
+                .map(|c| (updated.row(c) - centroids.row(c)).norm())
+                .fold(0.0, f64::max);
+            centroids = updated;
+            (labels, distances) = assign(&self.x, &centroids);
+
+            if shift <= parameters.tolerance {
+                break;
+            }
+        }
+
+        KMeansOutput {
+            centroids,
+            labels,
+            inertia: distances.iter().sum(),
+            iterations,
+        }
+    }
+}
+
+impl KMeansOutput<f64> {
+    /// Assigns each row of the input data to the nearest centroid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the centroids.
+    #[must_use]
+    pub fn predict(&self, input: &DMatrix<f64>) -> Vec<usize> {
+        assert_eq!(input.ncols(), self.centroids.ncols());
+
+        assign(input, &self.centroids).0
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Nearest centroid of each row, and the squared distance to it.
+fn assign(x: &DMatrix<f64>, centroids: &DMatrix<f64>) -> (Vec<usize>, Vec<f64>) {
+    (0..x.nrows())
+        .map(|i| {
+            (0..centroids.nrows())
+                .map(|c| (c, (x.row(i) - centroids.row(c)).norm_squared()))
+                .fold((0, f64::INFINITY), |best, candidate| {
+                    if candidate.1 < best.1 {
+                        candidate
+                    } else {
+                        best
+                    }
+                })
+        })
+        .unzip()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_k_means {
+    use super::*;
+    use nalgebra::dmatrix;
+    use rand_distr::StandardNormal;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_k_means_separated_clusters() {
+        // Three well separated blobs.
+        let centres = dmatrix![0.0, 0.0; 10.0, 0.0; 0.0, 10.0];
+        let mut rng = StdRng::seed_from_u64(1);
+        let x = DMatrix::from_fn(150, 2, |i, j| {
+            centres[(i / 50, j)] + 0.5 * rng.sample::<f64, _>(StandardNormal)
+        });
+
+        let output = KMeansInput::new(x.clone())
+            .fit(&KMeansParameters::new(3).with_seed(2))
+            .unwrap();
+
+        // Each blob is one cluster, whose centroid is its mean.
+        for blob in 0..3 {
+            let label = output.labels[50 * blob];
+            assert!(output.labels[50 * blob..50 * (blob + 1)]
+                .iter()
+                .all(|l| *l == label));
+
+            let mean = x.rows(50 * blob, 50).row_mean();
+            assert_approx_equal!((output.centroids.row(label) - &mean).norm(), 0.0, 1e-12);
+        }
+
+        let mut labels = output.labels.clone();
+        labels.dedup();
+        assert_eq!(labels.len(), 3);
+
+        assert_eq!(output.predict(&dmatrix![9.0, 1.0]), vec![output.labels[50]]);
+    }
+
+    #[test]
+    fn test_k_means_inertia() {
+        // Two pairs of points: the optimal inertia is the sum of the squared
+        // half-distances.
+        let x = dmatrix![0.0, 0.0; 0.0, 2.0; 10.0, 0.0; 10.0, 4.0];
+
+        let output = KMeansInput::new(x)
+            .fit(&KMeansParameters::new(2).with_seed(3))
+            .unwrap();
+
+        assert_approx_equal!(output.inertia, 2.0 * 1.0 + 2.0 * 4.0, 1e-12);
+        assert_eq!(output.labels[0], output.labels[1]);
+        assert_ne!(output.labels[1], output.labels[2]);
+    }
+
+    #[test]
+    fn test_k_means_invalid_input() {
+        let x = dmatrix![0.0, 0.0; 1.0, 1.0];
+
+        assert!(KMeansInput::new(x.clone())
+            .fit(&KMeansParameters::new(3))
+            .is_err());
+        assert!(KMeansInput::new(x.clone())
+            .fit(&KMeansParameters::new(0))
+            .is_err());
+        assert!(KMeansInput::new(dmatrix![f64::NAN, 0.0])
+            .fit(&KMeansParameters::new(1))
+            .is_err());
+
+        // Duplicate points: as many clusters as points is still fine.
+        let duplicates = dmatrix![1.0, 1.0; 1.0, 1.0];
+        let output = KMeansInput::new(duplicates)
+            .fit(&KMeansParameters::new(2).with_seed(4))
+            .unwrap();
+        assert_approx_equal!(output.inertia, 0.0, 1e-12);
+    }
+}
@@ -13,15 +13,46 @@
 //!
 //! - [x] Linear (using QR or SVD decomposition)
 //! - [x] Logistic (via IRLS, adding MLE in the future).
+//! - [x] Decision trees (CART) and random forests.
+//! - [x] Support vector regression (linear and RBF kernels, via SMO).
 //!
 //! ### Classification
 //!
 //! - [x] K-Nearest Neighbours
+//! - [x] Decision trees (CART) and random forests.
+//! - [x] Support vector machines (linear and RBF kernels, via SMO).
+//! - [x] Gaussian naive Bayes, linear and quadratic discriminant analysis.
+//!
+//! ### Neural Networks
+//!
+//! - [x] Multi-layer perceptron, trained by backpropagation with `RustQuant_autodiff`.
+//!
+//! ### Unsupervised
+//!
+//! - [x] K-means clustering (with k-means++ initialisation).
+//! - [x] Principal component analysis.
+//!
+//! ### Model Selection
+//!
+//! - [x] Train/test splits, k-fold, stratified and walk-forward cross-validation.
+//! - [x] Metrics: MSE, MAE, R squared, accuracy, log-loss and ROC AUC.
 
 /// Submodule of `ml`: activation functions.
 pub mod activations;
 pub use activations::*;
 
+/// Classification and regression trees.
+pub mod decision_tree;
+pub use decision_tree::*;
+
+/// Naive Bayes, linear and quadratic discriminant analysis.
+pub mod discriminant_analysis;
+pub use discriminant_analysis::*;
+
+/// K-means clustering.
+pub mod k_means;
+pub use k_means::*;
+
 /// K Nearest Neighbor classifier
 pub mod k_nearest_neighbors;
 pub use k_nearest_neighbors::*;
@@ -33,3 +64,27 @@ pub use linear_regression::*;
 /// Logistic regression.
 pub mod logistic_regression;
 pub use logistic_regression::*;
+
+/// Evaluation metrics.
+pub mod metrics;
+pub use metrics::*;
+
+/// Train/test splits and cross-validation.
+pub mod model_selection;
+pub use model_selection::*;
+
+/// Feed-forward neural networks.
+pub mod neural_network;
+pub use neural_network::*;
+
+/// Principal component analysis.
+pub mod principal_components;
+pub use principal_components::*;
+
+/// Random forests.
+pub mod random_forest;
+pub use random_forest::*;
+
+/// Support vector machines.
+pub mod support_vector_machine;
+pub use support_vector_machine::*;
@@ -0,0 +1,250 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for the evaluation metrics of regression and classification
+//! models.
+//!
+//! All metrics take the observed responses first, and the predictions (or
+//! predicted probabilities or scores) second.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::DVector;
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Mean squared error: $\frac{1}{n} \sum (y_i - \hat{y}_i)^2$.
+///
+/// # Errors
+///
+/// Returns an error if the vectors are empty or of different lengths.
+pub fn mean_squared_error(y: &DVector<f64>, y_hat: &DVector<f64>) -> Result<f64, RustQuantError> {
+    check_lengths(y, y_hat)?;
+
+    Ok((y - y_hat).norm_squared() / y.len() as f64)
+}
+
+/// Mean absolute error: $\frac{1}{n} \sum |y_i - \hat{y}_i|$.
+///
+/// # Errors
+///
+/// Returns an error if the vectors are empty or of different lengths.
+pub fn mean_absolute_error(y: &DVector<f64>, y_hat: &DVector<f64>) -> Result<f64, RustQuantError> {
+    check_lengths(y, y_hat)?;
+
+    Ok((y - y_hat).lp_norm(1) / y.len() as f64)
+}
+
+/// Coefficient of determination: $R^2 = 1 - SS_{res} / SS_{tot}$.
+///
+/// # Errors
+///
+/// Returns an error if the vectors are empty or of different lengths, or
+/// the responses are constant.
+pub fn r_squared(y: &DVector<f64>, y_hat: &DVector<f64>) -> Result<f64, RustQuantError> {
+    check_lengths(y, y_hat)?;
+
+    let total = y.add_scalar(-y.mean()).norm_squared();
+
+    if total == 0.0 {
+        return Err(RustQuantError::InvalidArgument(
+            "R squared is undefined for constant responses.".to_string(),
+        ));
+    }
+
+    Ok(1.0 - (y - y_hat).norm_squared() / total)
+}
+
+/// Accuracy: the fraction of predicted labels equal to the observed ones.
+///
+/// # Errors
+///
+/// Returns an error if the vectors are empty or of different lengths.
+pub fn accuracy(y: &DVector<f64>, y_hat: &DVector<f64>) -> Result<f64, RustQuantError> {
+    check_lengths(y, y_hat)?;
+
+    let correct = y.iter().zip(y_hat.iter()).filter(|(a, b)| a == b).count();
+
+    Ok(correct as f64 / y.len() as f64)
+}
+
+/// Log-loss (binary cross-entropy) of the predicted probabilities of
+/// class 1: $-\frac{1}{n} \sum y_i \ln p_i + (1 - y_i) \ln(1 - p_i)$,
+/// with the probabilities clipped to $[10^{-15}, 1 - 10^{-15}]$.
+///
+/// # Errors
+///
+/// Returns an error if the vectors are empty or of different lengths, the
+/// responses are not 0 or 1, or the probabilities are not in [0, 1].
+pub fn log_loss(y: &DVector<f64>, p_hat: &DVector<f64>) -> Result<f64, RustQuantError> {
+    check_lengths(y, p_hat)?;
+    check_binary(y)?;
+
+    if p_hat.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err(RustQuantError::InvalidArgument(
+            "Probabilities should be in [0, 1].".to_string(),
+        ));
+    }
+
+    let loss = y
+        .iter()
+        .zip(p_hat.iter())
+        .map(|(y, p)| {
+            let p = p.clamp(1e-15, 1.0 - 1e-15);
+            -(y * p.ln() + (1.0 - y) * (1.0 - p).ln())
+        })
+        .sum::<f64>();
+
+    Ok(loss / y.len() as f64)
+}
+
+/// Area under the ROC curve of the scores (e.g. probabilities of class 1):
+/// the probability that a random positive scores higher than a random
+/// negative, with ties counting one half. Computed from the ranks of the
+/// scores (the Mann-Whitney U statistic).
+///
+/// # Errors
+///
+/// Returns an error if the vectors are empty or of different lengths, the
+/// responses are not 0 or 1, or only one class is present.
+pub fn roc_auc(y: &DVector<f64>, scores: &DVector<f64>) -> Result<f64, RustQuantError> {
+    check_lengths(y, scores)?;
+    check_binary(y)?;
+
+    let positives = y.iter().filter(|y| **y == 1.0).count();
+    let negatives = y.len() - positives;
+
+    if positives == 0 || negatives == 0 {
+        return Err(RustQuantError::InvalidArgument(
+            "The ROC AUC needs both classes.".to_string(),
+        ));
+    }
+
+    let mut order: Vec<usize> = (0..y.len()).collect();
+    order.sort_by(|a, b| scores[*a].total_cmp(&scores[*b]));
+
+    // Sum of the (average, for ties) ranks of the positives.
+    let mut rank_sum = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && scores[order[end]] == scores[order[start]] {
+            end += 1;
+        }
+
+        let rank = 0.5 * (start + 1 + end) as f64;
+        rank_sum += rank * order[start..end].iter().filter(|i| y[**i] == 1.0).count() as f64;
+        start = end;
+    }
+
+    let u = rank_sum - (positives * (positives + 1)) as f64 / 2.0;
+
+    Ok(u / (positives * negatives) as f64)
+}
+
+/// Checks that two vectors are non-empty and of the same length.
+fn check_lengths(y: &DVector<f64>, y_hat: &DVector<f64>) -> Result<(), RustQuantError> {
+    if y.is_empty() || y.len() != y_hat.len() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Responses and predictions should be non-empty and of the same length, got {} and {}.",
+            y.len(),
+            y_hat.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks that the responses are 0 or 1.
+fn check_binary(y: &DVector<f64>) -> Result<(), RustQuantError> {
+    if y.iter().any(|y| *y != 0.0 && *y != 1.0) {
+        return Err(RustQuantError::InvalidArgument(
+            "The elements of the response vector should be either 0 or 1.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_metrics {
+    use super::*;
+    use nalgebra::dvector;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_regression_metrics() {
+        let y = dvector![3.0, -0.5, 2.0, 7.0];
+        let y_hat = dvector![2.5, 0.0, 2.0, 8.0];
+
+        assert_approx_equal!(mean_squared_error(&y, &y_hat).unwrap(), 0.375, 1e-15);
+        assert_approx_equal!(mean_absolute_error(&y, &y_hat).unwrap(), 0.5, 1e-15);
+        assert_approx_equal!(
+            r_squared(&y, &y_hat).unwrap(),
+            0.948_608_137_044_967_9,
+            1e-15
+        );
+
+        assert!(mean_squared_error(&y, &dvector![1.0]).is_err());
+        assert!(r_squared(&dvector![1.0, 1.0], &dvector![1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_classification_metrics() {
+        let y = dvector![0.0, 1.0, 1.0, 0.0];
+
+        assert_approx_equal!(
+            accuracy(&y, &dvector![0.0, 1.0, 0.0, 0.0]).unwrap(),
+            0.75,
+            1e-15
+        );
+
+        // -(ln 0.9 + ln 0.8 + ln 0.3 + ln 0.6) / 4.
+        let p = dvector![0.1, 0.8, 0.3, 0.4];
+        assert_approx_equal!(log_loss(&y, &p).unwrap(), 0.510_825_623_765_990_7, 1e-15);
+        assert!(log_loss(&y, &dvector![0.0, 1.0, 1.0, 0.0]).unwrap() < 1e-13);
+        assert!(log_loss(&y, &dvector![0.1, 0.8, 1.3, 0.4]).is_err());
+        assert!(log_loss(&dvector![0.0, 2.0], &dvector![0.1, 0.8]).is_err());
+    }
+
+    #[test]
+    fn test_roc_auc() {
+        // Three of the four (positive, negative) pairs are ordered.
+        let y = dvector![0.0, 0.0, 1.0, 1.0];
+        assert_approx_equal!(
+            roc_auc(&y, &dvector![0.1, 0.4, 0.35, 0.8]).unwrap(),
+            0.75,
+            1e-15
+        );
+
+        // Ties count one half.
+        assert_approx_equal!(
+            roc_auc(&y, &dvector![0.5, 0.5, 0.5, 0.5]).unwrap(),
+            0.5,
+            1e-15
+        );
+        assert_approx_equal!(
+            roc_auc(&y, &dvector![0.1, 0.2, 0.3, 0.4]).unwrap(),
+            1.0,
+            1e-15
+        );
+
+        assert!(roc_auc(&dvector![1.0, 1.0], &dvector![0.1, 0.2]).is_err());
+    }
+}
@@ -0,0 +1,508 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for model selection: train/test splits and cross-validation.
+//!
+//! Splits are sets of row indices, so they apply to any data. The
+//! estimators of this crate have different `fit` and `predict` signatures,
+//! so [`cross_validate`] takes a closure that fits a model on the training
+//! rows and predicts the test rows, and scores the predictions with a
+//! metric from [`crate::metrics`].
+//!
+//! ```
+//! # use RustQuant::ml::*;
+//! # use nalgebra::{DMatrix, DVector};
+//! let x = DMatrix::from_fn(20, 2, |i, j| (i * (j + 1) % 7) as f64);
+//! let y = DVector::from_fn(20, |i, _| 1.0 + 2.0 * x[(i, 0)] - x[(i, 1)]);
+//!
+//! let folds = k_fold(x.nrows(), 5, Some(1)).unwrap();
+//!
+//! let scores = cross_validate(
+//!     &x,
+//!     &y,
+//!     &folds,
+//!     |x_train, y_train, x_test| {
+//!         LinearRegressionInput::new(x_train.clone(), y_train.clone())
+//!             .fit(Decomposition::QR)?
+//!             .predict(x_test.clone())
+//!     },
+//!     mean_squared_error,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(scores.scores.len(), 5);
+//! assert!(scores.mean() < 1e-12);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Row indices of a training set and a test set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrainTestSplit {
+    /// Rows of the training set.
+    pub train: Vec<usize>,
+    /// Rows of the test set.
+    pub test: Vec<usize>,
+}
+
+/// Scores of a model on the test sets of a cross-validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossValidationScores {
+    /// Score on each test set.
+    pub scores: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TrainTestSplit {
+    /// The training rows of the data and responses.
+    #[must_use]
+    pub fn train_data(&self, x: &DMatrix<f64>, y: &DVector<f64>) -> (DMatrix<f64>, DVector<f64>) {
+        (x.select_rows(&self.train), y.select_rows(&self.train))
+    }
+
+    /// The test rows of the data and responses.
+    #[must_use]
+    pub fn test_data(&self, x: &DMatrix<f64>, y: &DVector<f64>) -> (DMatrix<f64>, DVector<f64>) {
+        (x.select_rows(&self.test), y.select_rows(&self.test))
+    }
+}
+
+impl CrossValidationScores {
+    /// Mean score.
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.scores.iter().sum::<f64>() / self.scores.len() as f64
+    }
+
+    /// Sample standard deviation of the scores (zero for a single score).
+    #[must_use]
+    pub fn standard_deviation(&self) -> f64 {
+        let n = self.scores.len();
+        if n < 2 {
+            return 0.0;
+        }
+
+        let mean = self.mean();
+        let variance = self.scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+
+        variance.sqrt()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Splits `n` rows into a training set and a test set of
+/// $\lceil n \cdot$ `test_fraction` $\rceil$ rows, shuffled with the given
+/// seed, or the last rows if `seed` is `None` (as for time series).
+///
+/// # Errors
+///
+/// Returns an error if the fraction is not in (0, 1), or either set would
+/// be empty.
+pub fn train_test_split(
+    n: usize,
+    test_fraction: f64,
+    seed: Option<u64>,
+) -> Result<TrainTestSplit, RustQuantError> {
+    let n_test = test_size(n, test_fraction)?;
+
+    let mut rows: Vec<usize> = (0..n).collect();
+    if let Some(seed) = seed {
+        rows.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+
+    let test = rows.split_off(n - n_test);
+
+    Ok(TrainTestSplit { train: rows, test })
+}
+
+/// Splits rows into a training set and a test set with (as nearly as
+/// possible) the same proportions of each class of `y` as the whole data.
+///
+/// # Errors
+///
+/// Returns an error if the fraction is not in (0, 1), or either set would
+/// be empty.
+pub fn stratified_train_test_split(
+    y: &DVector<f64>,
+    test_fraction: f64,
+    seed: u64,
+) -> Result<TrainTestSplit, RustQuantError> {
+    test_size(y.len(), test_fraction)?;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut split = TrainTestSplit {
+        train: Vec::new(),
+        test: Vec::new(),
+    };
+
+    for mut rows in class_rows(y) {
+        rows.shuffle(&mut rng);
+
+        let n_test = (rows.len() as f64 * test_fraction).round() as usize;
+        split.test.extend(rows.drain(..n_test));
+        split.train.extend(rows);
+    }
+
+    if split.train.is_empty() || split.test.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "Too few rows for a stratified split with this fraction.".to_string(),
+        ));
+    }
+
+    split.train.sort_unstable();
+    split.test.sort_unstable();
+
+    Ok(split)
+}
+
+/// K-fold cross-validation splits: the rows (shuffled with the given seed,
+/// or in order if `None`) are cut into `k` folds of sizes differing by at
+/// most one, and each fold is the test set of one split.
+///
+/// # Errors
+///
+/// Returns an error if `k` is less than 2 or more than the number of rows.
+pub fn k_fold(
+    n: usize,
+    k: usize,
+    seed: Option<u64>,
+) -> Result<Vec<TrainTestSplit>, RustQuantError> {
+    check_folds(n, k)?;
+
+    let mut rows: Vec<usize> = (0..n).collect();
+    if let Some(seed) = seed {
+        rows.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+
+    let folds: Vec<Vec<usize>> = (0..k)
+        .map(|f| rows[f * n / k..(f + 1) * n / k].to_vec())
+        .collect();
+
+    Ok(splits_from_folds(&folds))
+}
+
+/// Stratified k-fold cross-validation splits: the rows of each class of
+/// `y` are dealt out to the `k` folds in turn, so each fold has (as nearly
+/// as possible) the class proportions of the whole data.
+///
+/// # Errors
+///
+/// Returns an error if `k` is less than 2 or more than the number of rows.
+pub fn stratified_k_fold(
+    y: &DVector<f64>,
+    k: usize,
+    seed: u64,
+) -> Result<Vec<TrainTestSplit>, RustQuantError> {
+    check_folds(y.len(), k)?;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut folds = vec![Vec::new(); k];
+    let mut next = 0;
+
+    for mut rows in class_rows(y) {
+        rows.shuffle(&mut rng);
+
+        for row in rows {
+            folds[next % k].push(row);
+            next += 1;
+        }
+    }
+
+    for fold in &mut folds {
+        fold.sort_unstable();
+    }
+
+    Ok(splits_from_folds(&folds))
+}
+
+/// Walk-forward (time series) splits: the last `n_splits * test_size`
+/// rows are cut into consecutive test sets, each trained on the rows
+/// before it (at most the last `max_train_size` of them, for a rolling
+/// window, or all of them, for an expanding one). Test rows never precede
+/// training rows.
+///
+/// # Errors
+///
+/// Returns an error if there are no splits, the test sets are empty, or
+/// there are too few rows to leave a training set for the first split.
+pub fn walk_forward(
+    n: usize,
+    n_splits: usize,
+    test_size: usize,
+    max_train_size: Option<usize>,
+) -> Result<Vec<TrainTestSplit>, RustQuantError> {
+    if n_splits == 0 || test_size == 0 || max_train_size == Some(0) {
+        return Err(RustQuantError::InvalidArgument(
+            "Walk-forward splits need at least one split, test row and training row.".to_string(),
+        ));
+    }
+
+    if n <= n_splits * test_size {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "{n} rows are too few for {n_splits} test sets of {test_size} rows."
+        )));
+    }
+
+    Ok((0..n_splits)
+        .map(|s| {
+            let test_start = n - (n_splits - s) * test_size;
+            let train_start = max_train_size.map_or(0, |m| test_start.saturating_sub(m));
+
+            TrainTestSplit {
+                train: (train_start..test_start).collect(),
+                test: (test_start..test_start + test_size).collect(),
+            }
+        })
+        .collect())
+}
+
+/// Cross-validates a model: for each split, `fit_predict` fits the model
+/// on the training rows and predicts the test rows (given the training data,
+/// training responses and test data), and `metric` scores the predictions
+/// against the test responses (e.g. [`crate::mean_squared_error`]).
+///
+/// # Errors
+///
+/// Returns an error if there are no splits, or if fitting or scoring fails
+/// for any split.
+pub fn cross_validate<F, M>(
+    x: &DMatrix<f64>,
+    y: &DVector<f64>,
+    splits: &[TrainTestSplit],
+    fit_predict: F,
+    metric: M,
+) -> Result<CrossValidationScores, RustQuantError>
+where
+    F: Fn(&DMatrix<f64>, &DVector<f64>, &DMatrix<f64>) -> Result<DVector<f64>, RustQuantError>,
+    M: Fn(&DVector<f64>, &DVector<f64>) -> Result<f64, RustQuantError>,
+{
+    if splits.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "Cross-validation needs at least one split.".to_string(),
+        ));
+    }
+
+    if x.nrows() != y.len() {
+        return Err(RustQuantError::InvalidArgument(
+            "The number of rows in the data matrix should match the length of the response vector."
+                .to_string(),
+        ));
+    }
+
+    let scores = splits
+        .iter()
+        .map(|split| {
+            let (x_train, y_train) = split.train_data(x, y);
+            let (x_test, y_test) = split.test_data(x, y);
+
+            metric(&y_test, &fit_predict(&x_train, &y_train, &x_test)?)
+        })
+        .collect::<Result<Vec<f64>, RustQuantError>>()?;
+
+    Ok(CrossValidationScores { scores })
+}
+
+/// Number of test rows of a split.
+fn test_size(n: usize, test_fraction: f64) -> Result<usize, RustQuantError> {
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "The test fraction should be in (0, 1), got {test_fraction}."
+        )));
+    }
+
+    let n_test = (n as f64 * test_fraction).ceil() as usize;
+
+    if n_test >= n {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Cannot split {n} rows into non-empty training and test sets."
+        )));
+    }
+
+    Ok(n_test)
+}
+
+/// Checks the number of folds.
+fn check_folds(n: usize, k: usize) -> Result<(), RustQuantError> {
+    if k < 2 || k > n {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "The number of folds should be between 2 and the number of rows ({n}), got {k}."
+        )));
+    }
+
+    Ok(())
+}
+
+/// Rows of each class, in increasing order of the class labels.
+fn class_rows(y: &DVector<f64>) -> Vec<Vec<usize>> {
+    let mut classes: Vec<f64> = y.iter().copied().collect();
+    classes.sort_by(f64::total_cmp);
+    classes.dedup();
+
+    classes
+        .iter()
+        .map(|class| (0..y.len()).filter(|i| y[*i] == *class).collect())
+        .collect()
+}
+
+/// Splits with each fold as the test set, and the other folds as the
+/// training set.
+fn splits_from_folds(folds: &[Vec<usize>]) -> Vec<TrainTestSplit> {
+    (0..folds.len())
+        .map(|f| {
+            let mut train: Vec<usize> = folds
+                .iter()
+                .enumerate()
+                .filter(|(g, _)| *g != f)
+                .flat_map(|(_, fold)| fold.iter().copied())
+                .collect();
+            train.sort_unstable();
+
+            TrainTestSplit {
+                train,
+                test: folds[f].clone(),
+            }
+        })
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_model_selection {
+    use super::*;
+    use crate::{accuracy, DecisionTreeInput, DecisionTreeParameters, TreeTask};
+    use nalgebra::dvector;
+
+    #[test]
+    fn test_train_test_split() {
+        let split = train_test_split(10, 0.25, None).unwrap();
+        assert_eq!(split.train, (0..7).collect::<Vec<_>>());
+        assert_eq!(split.test, vec![7, 8, 9]);
+
+        let shuffled = train_test_split(10, 0.25, Some(1)).unwrap();
+        let mut all: Vec<usize> = shuffled
+            .train
+            .iter()
+            .chain(&shuffled.test)
+            .copied()
+            .collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+        assert_eq!(shuffled, train_test_split(10, 0.25, Some(1)).unwrap());
+
+        assert!(train_test_split(10, 1.0, None).is_err());
+        assert!(train_test_split(1, 0.5, None).is_err());
+    }
+
+    #[test]
+    fn test_k_fold() {
+        let splits = k_fold(11, 3, Some(2)).unwrap();
+        assert_eq!(splits.len(), 3);
+
+        // The test sets partition the rows.
+        let mut tests: Vec<usize> = splits.iter().flat_map(|s| s.test.clone()).collect();
+        tests.sort_unstable();
+        assert_eq!(tests, (0..11).collect::<Vec<_>>());
+
+        for split in &splits {
+            assert!((3..=4).contains(&split.test.len()));
+            assert_eq!(split.train.len() + split.test.len(), 11);
+            assert!(split.test.iter().all(|t| !split.train.contains(t)));
+        }
+
+        assert!(k_fold(5, 1, None).is_err());
+        assert!(k_fold(5, 6, None).is_err());
+    }
+
+    #[test]
+    fn test_stratified_splits() {
+        // 12 of class 0 and 6 of class 1.
+        let y = DVector::from_fn(18, |i, _| if i % 3 == 0 { 1.0 } else { 0.0 });
+
+        for split in stratified_k_fold(&y, 3, 3).unwrap() {
+            let ones = split.test.iter().filter(|i| y[**i] == 1.0).count();
+            assert_eq!((split.test.len(), ones), (6, 2));
+        }
+
+        let split = stratified_train_test_split(&y, 0.5, 4).unwrap();
+        let ones = split.test.iter().filter(|i| y[**i] == 1.0).count();
+        assert_eq!((split.test.len(), ones), (9, 3));
+    }
+
+    #[test]
+    fn test_walk_forward() {
+        let expanding = walk_forward(10, 3, 2, None).unwrap();
+        assert_eq!(expanding[0].train, (0..4).collect::<Vec<_>>());
+        assert_eq!(expanding[0].test, vec![4, 5]);
+        assert_eq!(expanding[2].train, (0..8).collect::<Vec<_>>());
+        assert_eq!(expanding[2].test, vec![8, 9]);
+
+        let rolling = walk_forward(10, 3, 2, Some(3)).unwrap();
+        assert_eq!(rolling[1].train, vec![3, 4, 5]);
+        assert_eq!(rolling[1].test, vec![6, 7]);
+
+        for split in rolling.iter().chain(&expanding) {
+            assert!(split.train.iter().max() < split.test.iter().min());
+        }
+
+        assert!(walk_forward(6, 3, 2, None).is_err());
+    }
+
+    #[test]
+    fn test_cross_validate_classifier() {
+        let x = DMatrix::from_fn(40, 1, |i, _| i as f64);
+        let y = DVector::from_fn(40, |i, _| if i < 20 { 0.0 } else { 1.0 });
+
+        let scores = cross_validate(
+            &x,
+            &y,
+            &stratified_k_fold(&y, 4, 5).unwrap(),
+            |x_train, y_train, x_test| {
+                let tree = DecisionTreeInput::new(x_train.clone(), y_train.clone())
+                    .fit(&DecisionTreeParameters::new(TreeTask::Classification))?;
+                Ok(tree.predict(x_test))
+            },
+            accuracy,
+        )
+        .unwrap();
+
+        assert_eq!(scores.scores.len(), 4);
+        assert!(scores.mean() > 0.9);
+        assert!(scores.standard_deviation() >= 0.0);
+
+        // Errors from the model are propagated.
+        let failing = cross_validate(
+            &x,
+            &y,
+            &k_fold(40, 2, None).unwrap(),
+            |_, _, _| Ok(dvector![0.0]),
+            accuracy,
+        );
+        assert!(failing.is_err());
+    }
+}
@@ -0,0 +1,686 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for feed-forward neural networks (multi-layer perceptrons).
+//!
+//! The network maps the features through dense hidden layers,
+//! $a^{(l)} = \sigma_l(W^{(l)} a^{(l-1)} + b^{(l)})$, to a single linear
+//! output. For classification, the output is the logit of the probability
+//! of class 1.
+//!
+//! Training is by mini-batch gradient descent, with the gradient of the loss
+//! of each batch computed by backpropagation: the forward pass is recorded
+//! on a [`Graph`] of `RustQuant_autodiff`, and reverse accumulation gives the
+//! derivatives with respect to every weight at once.
+//!
+//! ```
+//! # use RustQuant::ml::*;
+//! # use nalgebra::{dmatrix, dvector};
+//! // XOR, which is not linearly separable.
+//! let x = dmatrix![0.0, 0.0; 0.0, 1.0; 1.0, 0.0; 1.0, 1.0];
+//! let y = dvector![0.0, 1.0, 1.0, 0.0];
+//!
+//! let parameters = MultilayerPerceptronParameters::new(NetworkLoss::CrossEntropy)
+//!     .with_hidden_layer(8, Activation::Tanh)
+//!     .with_epochs(1000)
+//!     .with_seed(1);
+//!
+//! let network = MultilayerPerceptronInput::new(x.clone(), y.clone())
+//!     .fit(&parameters)
+//!     .unwrap();
+//!
+//! assert_eq!(network.predict(&x), y);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+use crate::ActivationFunction;
+use RustQuant_autodiff::{Accumulate, Gradient, Graph, Variable};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Activation function of a hidden layer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Activation {
+    /// No activation (a linear layer).
+    Identity,
+    /// Logistic sigmoid: $1 / (1 + e^{-x})$.
+    Sigmoid,
+    /// Hyperbolic tangent.
+    Tanh,
+    /// Rectified linear unit: $\max(x, 0)$.
+    Relu,
+    /// Softplus: $\ln(1 + e^x)$.
+    Softplus,
+    /// Gaussian error linear unit: $x \Phi(x)$.
+    Gelu,
+}
+
+/// Loss function minimised in training.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NetworkLoss {
+    /// Mean squared error, for regression.
+    MeanSquaredError,
+    /// Mean binary cross-entropy (log-loss), for classification of 0/1
+    /// responses.
+    CrossEntropy,
+}
+
+/// Gradient-based optimiser of the weights.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TrainingOptimizer {
+    /// Stochastic gradient descent with momentum:
+    /// $v \leftarrow \mu v - \eta g$, $w \leftarrow w + v$.
+    Sgd {
+        /// Learning rate $\eta$.
+        learning_rate: f64,
+        /// Momentum $\mu$ (zero for plain gradient descent).
+        momentum: f64,
+    },
+    /// Adam: gradient descent scaled by bias-corrected moving averages of
+    /// the gradient and its square.
+    ///
+    /// References:
+    ///     - Adam: A Method for Stochastic Optimization (Kingma, Ba 2015)
+    Adam {
+        /// Learning rate.
+        learning_rate: f64,
+        /// Decay of the average of the gradient.
+        beta1: f64,
+        /// Decay of the average of the squared gradient.
+        beta2: f64,
+        /// Constant added to the denominator for numerical stability.
+        epsilon: f64,
+    },
+}
+
+/// Dense layer of a network.
+#[derive(Clone, Debug)]
+pub struct DenseLayer {
+    /// Weights, one row per unit and one column per input.
+    pub weights: DMatrix<f64>,
+    /// Bias of each unit.
+    pub biases: DVector<f64>,
+    /// Activation function.
+    pub activation: Activation,
+}
+
+/// Architecture and training parameters of a multi-layer perceptron.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultilayerPerceptronParameters {
+    /// Number of units and activation of each hidden layer.
+    pub hidden_layers: Vec<(usize, Activation)>,
+    /// Loss function.
+    pub loss: NetworkLoss,
+    /// Optimiser of the weights.
+    pub optimizer: TrainingOptimizer,
+    /// Number of passes over the data.
+    pub epochs: usize,
+    /// Number of samples per gradient step (all of them if `None`).
+    pub batch_size: Option<usize>,
+    /// Seed of the initial weights and the shuffling of the batches (from
+    /// entropy if `None`).
+    pub seed: Option<u64>,
+}
+
+/// Struct to hold the input data for a multi-layer perceptron.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct MultilayerPerceptronInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to data points, and each column is a different
+    /// feature of the data.
+    pub x: DMatrix<T>,
+    /// The response vector: values for regression, or 0/1 labels for
+    /// classification.
+    pub y: DVector<T>,
+}
+
+/// Struct to hold a trained multi-layer perceptron.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct MultilayerPerceptronOutput {
+    /// Layers, the last being the linear output layer of one unit.
+    pub layers: Vec<DenseLayer>,
+    /// Loss function the network was trained on.
+    pub loss: NetworkLoss,
+    /// Loss over the training data after each epoch.
+    pub loss_history: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Activation {
+    /// Applies the activation function.
+    #[must_use]
+    pub fn apply<A: ActivationFunction>(&self, x: &A) -> A {
+        match self {
+            Self::Identity => x.identity(),
+            Self::Sigmoid => x.sigmoid(),
+            Self::Tanh => x.tanh(),
+            Self::Relu => x.relu(),
+            Self::Softplus => x.softplus(),
+            Self::Gelu => x.gelu(),
+        }
+    }
+}
+
+impl TrainingOptimizer {
+    /// Adam with the usual defaults: $\beta_1 = 0.9$, $\beta_2 = 0.999$
+    /// and $\epsilon = 10^{-8}$.
+    #[must_use]
+    pub fn adam(learning_rate: f64) -> Self {
+        Self::Adam {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+
+    /// Stochastic gradient descent with momentum.
+    #[must_use]
+    pub fn sgd(learning_rate: f64, momentum: f64) -> Self {
+        Self::Sgd {
+            learning_rate,
+            momentum,
+        }
+    }
+}
+
+impl MultilayerPerceptronParameters {
+    /// Network without hidden layers, trained by Adam (learning rate 0.01)
+    /// on full batches for 100 epochs.
+    #[must_use]
+    pub fn new(loss: NetworkLoss) -> Self {
+        Self {
+            hidden_layers: Vec::new(),
+            loss,
+            optimizer: TrainingOptimizer::adam(0.01),
+            epochs: 100,
+            batch_size: None,
+            seed: None,
+        }
+    }
+
+    /// Append a hidden layer.
+    #[must_use]
+    pub fn with_hidden_layer(mut self, units: usize, activation: Activation) -> Self {
+        self.hidden_layers.push((units, activation));
+        self
+    }
+
+    /// Set the optimiser.
+    #[must_use]
+    pub fn with_optimizer(mut self, optimizer: TrainingOptimizer) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+
+    /// Set the number of epochs.
+    #[must_use]
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    /// Set the batch size.
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Set the seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl MultilayerPerceptronInput<f64> {
+    /// Create a new `MultilayerPerceptronInput` struct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rows in x are not equal to the length of y.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>, y: DVector<f64>) -> Self {
+        assert_eq!(x.nrows(), y.len());
+
+        Self { x, y }
+    }
+
+    /// Trains a network on the input data. The weights are initialised
+    /// with the Glorot (Xavier) normal scheme, and the biases at zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is empty or not finite, the responses
+    /// are not 0 or 1 for classification, a layer has no units, or the
+    /// batch size is zero.
+    pub fn fit(
+        &self,
+        parameters: &MultilayerPerceptronParameters,
+    ) -> Result<MultilayerPerceptronOutput, RustQuantError> {
+        self.validate(parameters)?;
+
+        let (n, p) = self.x.shape();
+        let mut rng = parameters
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
+        // Glorot initialisation: N(0, 2 / (fan_in + fan_out)).
+        let mut layers = Vec::with_capacity(parameters.hidden_layers.len() + 1);
+        let mut fan_in = p;
+        for (units, activation) in parameters
+            .hidden_layers
+            .iter()
+            .copied()
+            .chain([(1, Activation::Identity)])
+        {
+            let deviation = (2.0 / (fan_in + units) as f64).sqrt();
+            layers.push(DenseLayer {
+                weights: DMatrix::from_fn(units, fan_in, |_, _| {
+                    deviation * rng.sample::<f64, _>(StandardNormal)
+                }),
+                biases: DVector::zeros(units),
+                activation,
+            });
+            fan_in = units;
+        }
+
+        let mut network = MultilayerPerceptronOutput {
+            layers,
+            loss: parameters.loss,
+            loss_history: Vec::with_capacity(parameters.epochs),
+        };
+
+        let batch_size = parameters.batch_size.unwrap_or(n).min(n);
+        let mut weights = network.weights();
+        let mut first_moment = vec![0.0; weights.len()];
+        let mut second_moment = vec![0.0; weights.len()];
+        let mut step = 0;
+        let mut samples: Vec<usize> = (0..n).collect();
+
+        for _ in 0..parameters.epochs {
+            if batch_size < n {
+                samples.shuffle(&mut rng);
+            }
+
+            for batch in samples.chunks(batch_size) {
+                let (_, gradient) = network.loss_and_gradient(&weights, &self.x, &self.y, batch);
+                step += 1;
+
+                match parameters.optimizer {
+                    TrainingOptimizer::Sgd {
+                        learning_rate,
+                        momentum,
+                    } => {
+                        for k in 0..weights.len() {
+                            first_moment[k] =
+                                momentum * first_moment[k] - learning_rate * gradient[k];
+                            weights[k] += first_moment[k];
+                        }
+                    }
+                    TrainingOptimizer::Adam {
+                        learning_rate,
+                        beta1,
+                        beta2,
+                        epsilon,
+                    } => {
+                        let correction1 = 1.0 - beta1.powi(step);
+                        let correction2 = 1.0 - beta2.powi(step);
+
+                        for k in 0..weights.len() {
+                            first_moment[k] = beta1 * first_moment[k] + (1.0 - beta1) * gradient[k];
+                            second_moment[k] =
+                                beta2 * second_moment[k] + (1.0 - beta2) * gradient[k].powi(2);
+
+                            weights[k] -= learning_rate * (first_moment[k] / correction1)
+                                / ((second_moment[k] / correction2).sqrt() + epsilon);
+                        }
+                    }
+                }
+            }
+
+            network.set_weights(&weights);
+            network
+                .loss_history
+                .push(network.loss_value(&self.x, &self.y));
+        }
+
+        if network.weights().iter().any(|w| !w.is_finite()) {
+            return Err(RustQuantError::ComputationError(
+                "Training diverged: try a smaller learning rate.".to_string(),
+            ));
+        }
+
+        Ok(network)
+    }
+
+    /// Checks the data and the parameters.
+    fn validate(&self, parameters: &MultilayerPerceptronParameters) -> Result<(), RustQuantError> {
+        if self.x.nrows() == 0 || self.x.ncols() == 0 || self.x.nrows() != self.y.len() {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be non-empty, with one response per row.".to_string(),
+            ));
+        }
+
+        if self.x.iter().chain(self.y.iter()).any(|v| !v.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be finite.".to_string(),
+            ));
+        }
+
+        if parameters.loss == NetworkLoss::CrossEntropy
+            && self.y.iter().any(|y| *y != 0.0 && *y != 1.0)
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "The elements of the response vector should be either 0 or 1.".to_string(),
+            ));
+        }
+
+        if parameters
+            .hidden_layers
+            .iter()
+            .any(|(units, _)| *units == 0)
+            || parameters.batch_size == Some(0)
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Layers need at least one unit, and batches at least one sample.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl MultilayerPerceptronOutput {
+    /// Predicts the output for the given input data: the network output
+    /// for regression, or the class (0 or 1) of probability above one half
+    /// for classification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the training
+    /// data.
+    #[must_use]
+    pub fn predict(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        let outputs = self.forward(input);
+
+        match self.loss {
+            NetworkLoss::MeanSquaredError => outputs,
+            NetworkLoss::CrossEntropy => outputs.map(|z| if z > 0.0 { 1.0 } else { 0.0 }),
+        }
+    }
+
+    /// Predicts the probabilities of class 1 for the given input data (for
+    /// networks trained on the cross-entropy).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the training
+    /// data.
+    #[must_use]
+    pub fn predict_proba(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        self.forward(input).logistic()
+    }
+
+    /// Network outputs (logits, for classification), one per row of the
+    /// input.
+    fn forward(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        assert_eq!(input.ncols(), self.layers[0].weights.ncols());
+
+        DVector::from_fn(input.nrows(), |i, _| {
+            let mut activations = input.row(i).transpose();
+
+            for layer in &self.layers {
+                activations = (&layer.weights * activations + &layer.biases)
+                    .map(|z| layer.activation.apply(&z));
+            }
+
+            activations[0]
+        })
+    }
+
+    /// Loss over the given data.
+    fn loss_value(&self, x: &DMatrix<f64>, y: &DVector<f64>) -> f64 {
+        let outputs = self.forward(x);
+
+        outputs
+            .iter()
+            .zip(y.iter())
+            .map(|(z, y)| match self.loss {
+                NetworkLoss::MeanSquaredError => (z - y).powi(2),
+                // ln(1 + e^z) - y z, computed without overflow.
+                NetworkLoss::CrossEntropy => z.max(0.0) + (-z.abs()).exp().ln_1p() - y * z,
+            })
+            .sum::<f64>()
+            / y.len() as f64
+    }
+
+    /// Loss of the given batch of samples at the given (flattened)
+    /// weights, and its gradient by reverse mode automatic differentiation.
+    fn loss_and_gradient(
+        &self,
+        weights: &[f64],
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+        batch: &[usize],
+    ) -> (f64, Vec<f64>) {
+        let graph = Graph::new();
+        let variables = graph.vars(weights);
+
+        let losses = batch.iter().map(|&i| {
+            let mut activations: Vec<Variable> =
+                x.row(i).iter().map(|value| graph.var(*value)).collect();
+            let mut offset = 0;
+
+            for layer in &self.layers {
+                let (units, inputs) = layer.weights.shape();
+                let biases = &variables[offset + units * inputs..offset + units * (inputs + 1)];
+
+                activations = (0..units)
+                    .map(|j| {
+                        let row = &variables[offset + j * inputs..offset + (j + 1) * inputs];
+                        let z = row
+                            .iter()
+                            .zip(&activations)
+                            .map(|(w, a)| *w * *a)
+                            .sum::<Variable>()
+                            + biases[j];
+
+                        layer.activation.apply(&z)
+                    })
+                    .collect();
+                offset += units * (inputs + 1);
+            }
+
+            let z = activations[0];
+            match self.loss {
+                NetworkLoss::MeanSquaredError => (z - y[i]) * (z - y[i]),
+                NetworkLoss::CrossEntropy => (z.exp() + 1.0).ln() - z * y[i],
+            }
+        });
+
+        let loss = losses.sum::<Variable>() * (1.0 / batch.len() as f64);
+
+        (loss.value(), loss.accumulate().wrt(&variables))
+    }
+
+    /// All weights and biases, layer by layer: the weights row by row, then
+    /// the biases.
+    fn weights(&self) -> Vec<f64> {
+        self.layers
+            .iter()
+            .flat_map(|layer| {
+                layer
+                    .weights
+                    .transpose()
+                    .iter()
+                    .chain(layer.biases.iter())
+                    .copied()
+                    .collect::<Vec<f64>>()
+            })
+            .collect()
+    }
+
+    /// Sets the weights and biases from their flattened values.
+    fn set_weights(&mut self, weights: &[f64]) {
+        let mut offset = 0;
+
+        for layer in &mut self.layers {
+            let (units, inputs) = layer.weights.shape();
+            layer.weights =
+                DMatrix::from_row_slice(units, inputs, &weights[offset..offset + units * inputs]);
+            offset += units * inputs;
+            layer.biases = DVector::from_column_slice(&weights[offset..offset + units]);
+            offset += units;
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_neural_network {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_backpropagation_against_finite_differences() {
+        let x = dmatrix![0.5, -1.0; 1.5, 0.3; -0.7, 2.0];
+
+        for (loss, y) in [
+            (NetworkLoss::MeanSquaredError, dvector![0.2, -0.4, 1.1]),
+            (NetworkLoss::CrossEntropy, dvector![1.0, 0.0, 1.0]),
+        ] {
+            let parameters = MultilayerPerceptronParameters::new(loss)
+                .with_hidden_layer(3, Activation::Tanh)
+                .with_hidden_layer(2, Activation::Softplus)
+                .with_epochs(0)
+                .with_seed(1);
+            let network = MultilayerPerceptronInput::new(x.clone(), y.clone())
+                .fit(&parameters)
+                .unwrap();
+
+            let weights = network.weights();
+            let (value, gradient) = network.loss_and_gradient(&weights, &x, &y, &[0, 1, 2]);
+            assert_approx_equal!(value, network.loss_value(&x, &y), 1e-12);
+
+            let h = 1e-6;
+            for k in 0..weights.len() {
+                let mut shifted = network.clone();
+                let mut bumped = weights.clone();
+
+                bumped[k] += h;
+                shifted.set_weights(&bumped);
+                let up = shifted.loss_value(&x, &y);
+
+                bumped[k] -= 2.0 * h;
+                shifted.set_weights(&bumped);
+                let down = shifted.loss_value(&x, &y);
+
+                assert_approx_equal!(gradient[k], (up - down) / (2.0 * h), 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_regression_network() {
+        // Fit sin on [-3, 3].
+        let n = 40;
+        let x = DMatrix::from_fn(n, 1, |i, _| -3.0 + 6.0 * i as f64 / (n - 1) as f64);
+        let y = x.column(0).map(f64::sin);
+
+        let parameters = MultilayerPerceptronParameters::new(NetworkLoss::MeanSquaredError)
+            .with_hidden_layer(10, Activation::Tanh)
+            .with_optimizer(TrainingOptimizer::adam(0.05))
+            .with_epochs(1500)
+            .with_seed(2);
+        let network = MultilayerPerceptronInput::new(x.clone(), y.clone())
+            .fit(&parameters)
+            .unwrap();
+
+        let mse = (network.predict(&x) - &y).norm_squared() / n as f64;
+        assert!(mse < 1e-2);
+        assert_approx_equal!(mse, *network.loss_history.last().unwrap(), 1e-12);
+        assert!(network.loss_history[0] > 10.0 * mse);
+    }
+
+    #[test]
+    fn test_sgd_mini_batches() {
+        // A linear model, with momentum and mini-batches.
+        let x = DMatrix::from_fn(50, 2, |i, j| ((i * (j + 3)) % 7) as f64 / 7.0);
+        let y = DVector::from_fn(50, |i, _| 1.0 + 2.0 * x[(i, 0)] - x[(i, 1)]);
+
+        let parameters = MultilayerPerceptronParameters::new(NetworkLoss::MeanSquaredError)
+            .with_optimizer(TrainingOptimizer::sgd(0.05, 0.9))
+            .with_batch_size(10)
+            .with_epochs(300)
+            .with_seed(3);
+        let network = MultilayerPerceptronInput::new(x, y)
+            .fit(&parameters)
+            .unwrap();
+
+        let output = &network.layers[0];
+        assert_approx_equal!(output.weights[(0, 0)], 2.0, 1e-3);
+        assert_approx_equal!(output.weights[(0, 1)], -1.0, 1e-3);
+        assert_approx_equal!(output.biases[0], 1.0, 1e-3);
+    }
+
+    #[test]
+    fn test_classification_probabilities() {
+        let x = dmatrix![-2.0; -1.0; -0.5; 0.5; 1.0; 2.0];
+        let y = dvector![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+
+        let network = MultilayerPerceptronInput::new(x.clone(), y.clone())
+            .fit(
+                &MultilayerPerceptronParameters::new(NetworkLoss::CrossEntropy)
+                    .with_epochs(300)
+                    .with_seed(4),
+            )
+            .unwrap();
+
+        let probabilities = network.predict_proba(&x);
+        assert!(probabilities.as_slice().windows(2).all(|p| p[0] < p[1]));
+        assert_eq!(network.predict(&x), y);
+    }
+
+    #[test]
+    fn test_network_invalid_input() {
+        let x = dmatrix![0.0; 1.0];
+        let parameters = MultilayerPerceptronParameters::new(NetworkLoss::CrossEntropy);
+
+        let input = MultilayerPerceptronInput::new(x.clone(), dvector![0.0, 0.5]);
+        assert!(input.fit(&parameters).is_err());
+
+        let input = MultilayerPerceptronInput::new(x, dvector![0.0, 1.0]);
+        assert!(input
+            .fit(&parameters.clone().with_hidden_layer(0, Activation::Relu))
+            .is_err());
+        assert!(input.fit(&parameters.with_batch_size(0)).is_err());
+    }
+}
@@ -0,0 +1,364 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for principal component analysis (PCA).
+//!
+//! The principal components are the eigenvectors of the sample covariance
+//! (or correlation) matrix of the data, in decreasing order of their
+//! eigenvalues, the variances they explain. They are computed from the
+//! singular value decomposition of the centred data matrix
+//! $X_c = U S V^T$: the components are the columns of $V$, with variances
+//! $s_i^2 / (n - 1)$.
+//!
+//! For daily changes of a yield curve, the first three components are the
+//! familiar level, slope and curvature factors.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Struct to hold the input data for a principal component analysis.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct PrincipalComponentsInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to observations, and each column is a different
+    /// variable.
+    pub x: DMatrix<T>,
+}
+
+/// Struct to hold the output of a principal component analysis.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct PrincipalComponentsOutput<T> {
+    /// Mean of each variable.
+    pub mean: DVector<T>,
+    /// Standard deviation of each variable, if the variables were
+    /// standardised (the analysis of the correlation matrix).
+    pub scale: Option<DVector<T>>,
+    /// Principal components (loadings), one per column, of unit length.
+    /// The sign of each is chosen to make its largest entry positive.
+    pub components: DMatrix<T>,
+    /// Variance explained by each component.
+    pub explained_variance: DVector<T>,
+    /// Fraction of the total variance explained by each component.
+    pub explained_variance_ratio: DVector<T>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PrincipalComponentsInput<f64> {
+    /// Create a new `PrincipalComponentsInput` struct.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>) -> Self {
+        Self { x }
+    }
+
+    /// Computes the first `n_components` principal components, of the
+    /// covariance matrix, or of the correlation matrix if `standardise` is
+    /// true.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not finite, there are fewer than two
+    /// observations, the number of components is not between one and the
+    /// number of variables, or a variable to standardise is constant.
+    pub fn fit(
+        &self,
+        n_components: usize,
+        standardise: bool,
+    ) -> Result<PrincipalComponentsOutput<f64>, RustQuantError> {
+        let (n, p) = self.x.shape();
+
+        if self.x.iter().any(|v| !v.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be finite.".to_string(),
+            ));
+        }
+
+        if n < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "PCA needs at least two observations.".to_string(),
+            ));
+        }
+
+        if n_components == 0 || n_components > p {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "The number of components should be between 1 and {p}."
+            )));
+        }
+
+        let mean = self.x.row_mean().transpose();
+        let mut centred = self.x.clone();
+        for mut row in centred.row_iter_mut() {
+            row -= mean.transpose();
+        }
+
+        let scale = if standardise {
+            let deviations = centred
+                .column_iter()
+                .map(|column| (column.norm_squared() / (n - 1) as f64).sqrt());
+            let deviations = DVector::from_iterator(p, deviations);
+
+            if deviations.iter().any(|d| *d == 0.0) {
+                return Err(RustQuantError::InvalidArgument(
+                    "Cannot standardise a constant variable.".to_string(),
+                ));
+            }
+
+            for (mut column, deviation) in centred.column_iter_mut().zip(deviations.iter()) {
+                column /= *deviation;
+            }
+
+            Some(deviations)
+        } else {
+            None
+        };
+
+        let total = centred.norm_squared() / (n - 1) as f64;
+        let svd = centred.svd(false, true);
+        let v_t = svd.v_t.ok_or(RustQuantError::SvdDecompositionFailed)?;
+
+        // Components in decreasing order of the singular values.
+        let mut order: Vec<usize> = (0..svd.singular_values.len()).collect();
+        order.sort_by(|a, b| svd.singular_values[*b].total_cmp(&svd.singular_values[*a]));
+
+        // The data has at most min(n - 1, p) non-zero variances, and the SVD
+        // of a short matrix only min(n, p) components.
+        let variances: Vec<f64> = order
+            .iter()
+            .map(|k| svd.singular_values[*k].powi(2) / (n - 1) as f64)
+            .chain(std::iter::repeat(0.0))
+            .take(p)
+            .collect();
+
+        if n_components > order.len() {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Only {} components can be computed from {n} observations.",
+                order.len()
+            )));
+        }
+
+        let mut components = DMatrix::zeros(p, n_components);
+        for (c, k) in order.iter().take(n_components).enumerate() {
+            let mut component = v_t.row(*k).transpose();
+
+            let largest = component.iamax();
+            if component[largest] < 0.0 {
+                component.neg_mut();
+            }
+
+            components.set_column(c, &component);
+        }
+
+        let explained_variance = DVector::from_row_slice(&variances[..n_components]);
+        let explained_variance_ratio = if total > 0.0 {
+            &explained_variance / total
+        } else {
+            DVector::zeros(n_components)
+        };
+
+        Ok(PrincipalComponentsOutput {
+            mean,
+            scale,
+            components,
+            explained_variance,
+            explained_variance_ratio,
+        })
+    }
+}
+
+impl PrincipalComponentsOutput<f64> {
+    /// Projects the rows of the input data on the principal components:
+    /// the scores, one column per component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as variables.
+    #[must_use]
+    pub fn transform(&self, input: &DMatrix<f64>) -> DMatrix<f64> {
+        assert_eq!(input.ncols(), self.mean.len());
+
+        let mut centred = input.clone();
+        for mut row in centred.row_iter_mut() {
+            row -= self.mean.transpose();
+
+            if let Some(scale) = &self.scale {
+                row.component_div_assign(&scale.transpose());
+            }
+        }
+
+        centred * &self.components
+    }
+
+    /// Maps scores back to the variables: the best approximation of the
+    /// data from the retained components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scores do not have one column per component.
+    #[must_use]
+    pub fn inverse_transform(&self, scores: &DMatrix<f64>) -> DMatrix<f64> {
+        assert_eq!(scores.ncols(), self.components.ncols());
+
+        let mut data = scores * self.components.transpose();
+        for mut row in data.row_iter_mut() {
+            if let Some(scale) = &self.scale {
+                row.component_mul_assign(&scale.transpose());
+            }
+
+            row += self.mean.transpose();
+        }
+
+        data
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_principal_components {
+    use super::*;
+    use nalgebra::dmatrix;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_pca_two_variables() {
+        // Points on the line y = x, plus a small orthogonal perturbation.
+        let x = dmatrix![
+            1.0, 1.0;
+            2.0, 2.0;
+            3.0, 3.0;
+            4.0, 4.0;
+            2.6, 2.4;
+            2.4, 2.6
+        ];
+
+        let output = PrincipalComponentsInput::new(x.clone())
+            .fit(2, false)
+            .unwrap();
+
+        let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+        assert_approx_equal!(output.components[(0, 0)], diagonal, 1e-12);
+        assert_approx_equal!(output.components[(1, 0)], diagonal, 1e-12);
+        assert!(output.explained_variance_ratio[0] > 0.99);
+        assert_approx_equal!(output.explained_variance_ratio.sum(), 1.0, 1e-12);
+
+        // The total variance is the trace of the covariance matrix.
+        let centred = &x - DMatrix::from_fn(6, 2, |_, j| x.column(j).mean());
+        let trace = centred.norm_squared() / 5.0;
+        assert_approx_equal!(output.explained_variance.sum(), trace, 1e-12);
+
+        // With all components, the projection is invertible.
+        let reconstructed = output.inverse_transform(&output.transform(&x));
+        assert_approx_equal!((&reconstructed - &x).norm(), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_pca_yield_curve_factors() {
+        // Curve changes driven by a level and a slope factor, over tenors
+        // of 1 to 10 years, with a little noise.
+        let tenors = [1.0, 2.0, 3.0, 5.0, 7.0, 10.0];
+        let mut rng = StdRng::seed_from_u64(1);
+        let factors: Vec<(f64, f64)> = (0..500)
+            .map(|_| {
+                (
+                    rng.sample::<f64, _>(StandardNormal),
+                    0.3 * rng.sample::<f64, _>(StandardNormal),
+                )
+            })
+            .collect();
+        let x = DMatrix::from_fn(500, tenors.len(), |i, j| {
+            factors[i].0
+                + factors[i].1 * (tenors[j] - 5.0) / 5.0
+                + 0.01 * rng.sample::<f64, _>(StandardNormal)
+        });
+
+        let output = PrincipalComponentsInput::new(x.clone())
+            .fit(3, false)
+            .unwrap();
+
+        // The first component is a level shift: loadings of equal sign and
+        // similar size.
+        let level = output.components.column(0);
+        assert!(level.iter().all(|l| *l > 0.3));
+
+        // The second one is a slope: monotone in the tenor.
+        let slope = output.components.column(1);
+        assert!(
+            slope.as_slice().windows(2).all(|w| w[0] < w[1])
+                || slope.as_slice().windows(2).all(|w| w[0] > w[1])
+        );
+
+        // Two factors explain almost everything.
+        assert!(output.explained_variance_ratio.rows(0, 2).sum() > 0.999);
+        assert!(output.explained_variance[1] > output.explained_variance[2]);
+
+        // Scores are uncorrelated, with the explained variances.
+        let scores = output.transform(&x);
+        assert_eq!(scores.shape(), (500, 3));
+        let covariance = scores.transpose() * &scores / 499.0;
+        assert_approx_equal!(covariance[(0, 1)], 0.0, 1e-10);
+        assert_approx_equal!(covariance[(0, 0)], output.explained_variance[0], 1e-10);
+    }
+
+    #[test]
+    fn test_pca_standardised() {
+        // Correlation matrix of two variables on very different scales.
+        let x = dmatrix![
+            1.0, 100.0;
+            2.0, 300.0;
+            3.0, 200.0;
+            4.0, 500.0
+        ];
+
+        let output = PrincipalComponentsInput::new(x.clone())
+            .fit(2, true)
+            .unwrap();
+
+        // The eigenvalues of a 2x2 correlation matrix are 1 +/- rho.
+        let scale = output.scale.clone().unwrap();
+        let centred = &x - DMatrix::from_fn(4, 2, |_, j| x.column(j).mean());
+        let rho = centred.column(0).dot(&centred.column(1)) / 3.0 / (scale[0] * scale[1]);
+        assert_approx_equal!(output.explained_variance[0], 1.0 + rho, 1e-12);
+        assert_approx_equal!(output.explained_variance[1], 1.0 - rho, 1e-12);
+
+        let reconstructed = output.inverse_transform(&output.transform(&x));
+        assert_approx_equal!((&reconstructed - &x).norm(), 0.0, 1e-9);
+    }
+
+    #[test]
+    fn test_pca_invalid_input() {
+        let x = dmatrix![1.0, 2.0; 3.0, 4.0; 5.0, 7.0];
+        let input = PrincipalComponentsInput::new(x);
+
+        assert!(input.fit(0, false).is_err());
+        assert!(input.fit(3, false).is_err());
+        assert!(PrincipalComponentsInput::new(dmatrix![1.0, 2.0])
+            .fit(1, false)
+            .is_err());
+        assert!(PrincipalComponentsInput::new(dmatrix![1.0, 2.0; 1.0, 3.0])
+            .fit(1, true)
+            .is_err());
+    }
+}
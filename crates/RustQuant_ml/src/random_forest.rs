@@ -0,0 +1,336 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for random forests: ensembles of decision trees, each grown on a
+//! bootstrap sample of the data and considering a random subset of the
+//! features at each split.
+//!
+//! References:
+//!     - Random Forests (Breiman 2001), Machine Learning 45(1)
+//!     - Elements of Statistical Learning (Hastie, Tibshirani, Friedman 2009), chapter 15
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::decision_tree::{encode_targets, grow_tree, validate_tree_input};
+use crate::{DecisionTreeOutput, DecisionTreeParameters, TreeTask};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parameters of a random forest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RandomForestParameters {
+    /// Parameters of each tree. If the number of features per split is not
+    /// set, it is $\lfloor \sqrt{p} \rfloor$ for classification and
+    /// $\lfloor p / 3 \rfloor$ for regression (at least one), for $p$
+    /// features.
+    pub tree: DecisionTreeParameters,
+    /// Number of trees.
+    pub n_trees: usize,
+    /// Whether each tree is grown on a bootstrap sample (with replacement)
+    /// of the data, rather than all of it.
+    pub bootstrap: bool,
+    /// Seed of the bootstrap samples and feature draws (from entropy if
+    /// `None`).
+    pub seed: Option<u64>,
+}
+
+/// Struct to hold the input data for a random forest.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct RandomForestInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to data points, and each column is a different
+    /// feature of the data.
+    pub x: DMatrix<T>,
+    /// The response vector: class labels for classification,
+    /// or values for regression.
+    pub y: DVector<T>,
+}
+
+/// Struct to hold a fitted random forest.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct RandomForestOutput<T> {
+    /// Learning task.
+    pub task: TreeTask,
+    /// Sorted class labels (empty for regression).
+    pub classes: Vec<T>,
+    /// Feature importances: the mean of the importances of the trees.
+    pub feature_importances: DVector<T>,
+    /// The trees of the forest.
+    pub trees: Vec<DecisionTreeOutput<T>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RandomForestParameters {
+    /// Forest of `n_trees` fully grown trees on bootstrap samples.
+    #[must_use]
+    pub fn new(task: TreeTask, n_trees: usize) -> Self {
+        Self {
+            tree: DecisionTreeParameters::new(task),
+            n_trees,
+            bootstrap: true,
+            seed: None,
+        }
+    }
+
+    /// Set the parameters of each tree.
+    #[must_use]
+    pub fn with_tree(mut self, tree: DecisionTreeParameters) -> Self {
+        self.tree = tree;
+        self
+    }
+
+    /// Set whether the trees are grown on bootstrap samples.
+    #[must_use]
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    /// Set the seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl RandomForestInput<f64> {
+    /// Create a new `RandomForestInput` struct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rows in x are not equal to the length of y.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>, y: DVector<f64>) -> Self {
+        assert_eq!(x.nrows(), y.len());
+
+        Self { x, y }
+    }
+
+    /// Grows a random forest on the input data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is empty or not finite, or the
+    /// parameters are invalid.
+    pub fn fit(
+        &self,
+        parameters: &RandomForestParameters,
+    ) -> Result<RandomForestOutput<f64>, RustQuantError> {
+        validate_tree_input(&self.x, &self.y, &parameters.tree)?;
+
+        if parameters.n_trees == 0 {
+            return Err(RustQuantError::InvalidArgument(
+                "A random forest needs at least one tree.".to_string(),
+            ));
+        }
+
+        let (n, p) = self.x.shape();
+        let max_features = parameters
+            .tree
+            .max_features
+            .unwrap_or(match parameters.tree.task {
+                TreeTask::Classification => (p as f64).sqrt() as usize,
+                TreeTask::Regression => p / 3,
+            })
+            .max(1);
+
+        let (classes, targets) = encode_targets(&self.y, parameters.tree.task);
+        let mut rng = parameters
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
+        let trees: Vec<DecisionTreeOutput<f64>> = (0..parameters.n_trees)
+            .map(|_| {
+                let mut samples: Vec<usize> = if parameters.bootstrap {
+                    (0..n).map(|_| rng.gen_range(0..n)).collect()
+                } else {
+                    (0..n).collect()
+                };
+
+                grow_tree(
+                    &self.x,
+                    &targets,
+                    classes.clone(),
+                    &mut samples,
+                    &parameters.tree,
+                    max_features,
+                    StdRng::seed_from_u64(rng.gen()),
+                )
+            })
+            .collect();
+
+        let feature_importances = trees.iter().fold(DVector::zeros(p), |sum, tree| {
+            sum + &tree.feature_importances
+        }) / trees.len() as f64;
+
+        Ok(RandomForestOutput {
+            task: parameters.tree.task,
+            classes,
+            feature_importances,
+            trees,
+        })
+    }
+}
+
+impl RandomForestOutput<f64> {
+    /// Predicts the output for the given input data: the class with the
+    /// largest mean probability over the trees for classification, or the
+    /// mean prediction for regression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input has fewer columns than the training data.
+    #[must_use]
+    pub fn predict(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        match self.task {
+            TreeTask::Regression => {
+                self.trees
+                    .iter()
+                    .fold(DVector::zeros(input.nrows()), |sum, tree| {
+                        sum + tree.predict(input)
+                    })
+                    / self.trees.len() as f64
+            }
+            TreeTask::Classification => {
+                let probabilities = self.predict_proba(input);
+
+                DVector::from_fn(input.nrows(), |i, _| {
+                    self.classes[probabilities.row(i).transpose().argmax().0]
+                })
+            }
+        }
+    }
+
+    /// Predicts the class probabilities for the given input data: the mean
+    /// of the probabilities of the trees, in the order of `classes`. The
+    /// matrix has no columns for regression forests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input has fewer columns than the training data.
+    #[must_use]
+    pub fn predict_proba(&self, input: &DMatrix<f64>) -> DMatrix<f64> {
+        self.trees.iter().fold(
+            DMatrix::zeros(input.nrows(), self.classes.len()),
+            |sum, tree| sum + tree.predict_proba(input),
+        ) / self.trees.len() as f64
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_random_forest {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    /// Data where only the first two of five features matter.
+    fn data(n: usize, seed: u64) -> (DMatrix<f64>, DVector<f64>, DVector<f64>) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let x: DMatrix<f64> = DMatrix::from_fn(n, 5, |_, _| rng.gen_range(-1.0..1.0));
+
+        // Labels -1 and 1, by the sign of x0 + x1.
+        let labels = DVector::from_fn(n, |i, _| {
+            if x[(i, 0)] + x[(i, 1)] > 0.0 {
+                1.0
+            } else {
+                -1.0
+            }
+        });
+        let values = DVector::from_fn(n, |i, _| x[(i, 0)].powi(2) + 0.5 * x[(i, 1)]);
+
+        (x, labels, values)
+    }
+
+    #[test]
+    fn test_random_forest_classification() {
+        let (x, y, _) = data(300, 1);
+        let (x_test, y_test, _) = data(200, 2);
+
+        let parameters = RandomForestParameters::new(TreeTask::Classification, 50).with_seed(3);
+        let forest = RandomForestInput::new(x, y).fit(&parameters).unwrap();
+
+        assert_eq!(forest.classes, vec![-1.0, 1.0]);
+        assert_eq!(forest.trees.len(), 50);
+
+        let predictions = forest.predict(&x_test);
+        let accuracy = predictions
+            .iter()
+            .zip(y_test.iter())
+            .filter(|(p, y)| p == y)
+            .count() as f64
+            / y_test.len() as f64;
+        assert!(accuracy > 0.9);
+
+        let probabilities = forest.predict_proba(&x_test);
+        assert!(probabilities
+            .row_iter()
+            .all(|row| (row.sum() - 1.0).abs() < 1e-12));
+
+        // The informative features carry almost all of the importance.
+        assert_approx_equal!(forest.feature_importances.sum(), 1.0, 1e-12);
+        assert!(forest.feature_importances[0] + forest.feature_importances[1] > 0.8);
+    }
+
+    #[test]
+    fn test_random_forest_regression() {
+        let (x, _, y) = data(400, 4);
+        let (x_test, _, y_test) = data(200, 5);
+
+        let parameters = RandomForestParameters::new(TreeTask::Regression, 50)
+            .with_tree(
+                DecisionTreeParameters::new(TreeTask::Regression)
+                    .with_min_samples_leaf(3)
+                    .with_max_features(3),
+            )
+            .with_seed(6);
+        let forest = RandomForestInput::new(x, y).fit(&parameters).unwrap();
+
+        let predictions = forest.predict(&x_test);
+        let mse = (predictions - &y_test).norm_squared() / y_test.len() as f64;
+        let variance = y_test.variance();
+        assert!(mse < 0.2 * variance);
+
+        assert!(forest.feature_importances.argmax().0 < 2);
+        assert!(forest.predict_proba(&x_test).is_empty());
+    }
+
+    #[test]
+    fn test_random_forest_reproducible() {
+        let (x, y, _) = data(100, 7);
+        let input = RandomForestInput::new(x.clone(), y);
+        let parameters = RandomForestParameters::new(TreeTask::Classification, 10).with_seed(8);
+
+        let first = input.fit(&parameters).unwrap();
+        let second = input.fit(&parameters).unwrap();
+
+        assert_eq!(first.predict_proba(&x), second.predict_proba(&x));
+        assert_eq!(first.feature_importances, second.feature_importances);
+
+        assert!(input
+            .fit(&RandomForestParameters::new(TreeTask::Classification, 0))
+            .is_err());
+    }
+}
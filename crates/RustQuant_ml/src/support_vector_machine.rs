@@ -0,0 +1,676 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Module for support vector machines (SVM): C-support vector
+//! classification and epsilon-support vector regression.
+//!
+//! Both are fitted by solving their dual problems,
+//!
+//! $$
+//! \min_\alpha \frac{1}{2} \alpha^T Q \alpha + p^T \alpha
+//! \quad \text{s.t.} \quad y^T \alpha = 0, \quad 0 \le \alpha_i \le C,
+//! $$
+//!
+//! with $Q_{ij} = y_i y_j K(x_i, x_j)$, by sequential minimal optimisation
+//! (SMO): each step optimises the pair of variables that most violates the
+//! optimality conditions, analytically.
+//!
+//! References:
+//!     - Sequential Minimal Optimization (Platt 1998)
+//!     - LIBSVM: A Library for Support Vector Machines (Chang, Lin 2011)
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector, Dyn, MatrixView, U1};
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Row of a data matrix.
+type Row<'a> = MatrixView<'a, f64, U1, Dyn, U1, Dyn>;
+
+/// Kernel of a support vector machine.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SvmKernel {
+    /// Linear kernel: $K(x, x') = x^T x'$.
+    Linear,
+    /// Radial basis function (Gaussian) kernel:
+    /// $K(x, x') = \exp(-\gamma \|x - x'\|^2)$.
+    Rbf {
+        /// Inverse length scale $\gamma > 0$.
+        gamma: f64,
+    },
+}
+
+/// Learning task of a support vector machine.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SvmTask {
+    /// Binary classification, with the hinge loss.
+    Classification,
+    /// Regression, with the epsilon-insensitive loss.
+    Regression {
+        /// Width $\epsilon$ of the tube inside which errors are not
+        /// penalised.
+        epsilon: f64,
+    },
+}
+
+/// Parameters of a support vector machine.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SupportVectorMachineParameters {
+    /// Learning task.
+    pub task: SvmTask,
+    /// Kernel.
+    pub kernel: SvmKernel,
+    /// Penalty $C > 0$ of the errors (the inverse of the regularisation).
+    pub c: f64,
+    /// Tolerance on the violation of the optimality conditions.
+    pub tolerance: f64,
+    /// Maximum number of SMO steps.
+    pub max_iterations: usize,
+}
+
+/// Struct to hold the input data for a support vector machine.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct SupportVectorMachineInput<T> {
+    /// Input data matrix.
+    /// Rows correspond to data points, and each column is a different
+    /// feature of the data.
+    pub x: DMatrix<T>,
+    /// The response vector: two class labels (e.g. 0 and 1, or -1 and 1)
+    /// for classification, or values for regression.
+    pub y: DVector<T>,
+}
+
+/// Struct to hold a fitted support vector machine, with decision function
+/// $f(x) = \sum_i c_i K(s_i, x) + b$ over the support vectors $s_i$.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct SupportVectorMachineOutput<T> {
+    /// Learning task.
+    pub task: SvmTask,
+    /// Kernel.
+    pub kernel: SvmKernel,
+    /// Support vectors, one per row.
+    pub support_vectors: DMatrix<T>,
+    /// Dual coefficients $c_i$ of the support vectors: $y_i \alpha_i$ for
+    /// classification, or $\alpha_i - \alpha_i^*$ for regression.
+    pub dual_coefficients: DVector<T>,
+    /// Intercept $b$.
+    pub intercept: T,
+    /// The two class labels, mapped to -1 and +1 (classification only).
+    pub classes: Option<(T, T)>,
+    /// Number of SMO steps.
+    pub iterations: usize,
+}
+
+/// Dual problem, and the state of its solution.
+struct SmoSolver<'a> {
+    /// Kernel matrix of the data.
+    kernel: &'a DMatrix<f64>,
+    /// Signs $y_i$ of the variables.
+    signs: Vec<f64>,
+    /// Upper bound $C$.
+    c: f64,
+    /// Variables $\alpha$.
+    alpha: Vec<f64>,
+    /// Gradient $Q \alpha + p$.
+    gradient: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SvmKernel {
+    /// Evaluates the kernel at two points.
+    #[must_use]
+    pub fn evaluate(&self, a: Row, b: Row) -> f64 {
+        match self {
+            Self::Linear => a.dot(&b),
+            Self::Rbf { gamma } => (-gamma * (a - b).norm_squared()).exp(),
+        }
+    }
+}
+
+impl SupportVectorMachineParameters {
+    /// Classifier with penalty `c`.
+    #[must_use]
+    pub fn classification(kernel: SvmKernel, c: f64) -> Self {
+        Self {
+            task: SvmTask::Classification,
+            kernel,
+            c,
+            tolerance: 1e-3,
+            max_iterations: 100_000,
+        }
+    }
+
+    /// Regression with penalty `c` and tube width `epsilon`.
+    #[must_use]
+    pub fn regression(kernel: SvmKernel, c: f64, epsilon: f64) -> Self {
+        Self {
+            task: SvmTask::Regression { epsilon },
+            ..Self::classification(kernel, c)
+        }
+    }
+
+    /// Set the tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the maximum number of SMO steps.
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+impl SupportVectorMachineInput<f64> {
+    /// Create a new `SupportVectorMachineInput` struct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rows in x are not equal to the length of y.
+    #[must_use]
+    pub fn new(x: DMatrix<f64>, y: DVector<f64>) -> Self {
+        assert_eq!(x.nrows(), y.len());
+
+        Self { x, y }
+    }
+
+    /// Fits the support vector machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is empty or not finite, the responses
+    /// are not two classes for classification, the parameters are not
+    /// positive, or SMO does not converge within the maximum number of
+    /// steps.
+    pub fn fit(
+        &self,
+        parameters: &SupportVectorMachineParameters,
+    ) -> Result<SupportVectorMachineOutput<f64>, RustQuantError> {
+        self.validate(parameters)?;
+
+        let n = self.x.nrows();
+        let kernel = DMatrix::from_fn(n, n, |i, j| {
+            parameters.kernel.evaluate(self.x.row(i), self.x.row(j))
+        });
+
+        let (classes, mut solver) = match parameters.task {
+            SvmTask::Classification => {
+                let low = self.y.min();
+                let high = self.y.max();
+                let signs: Vec<f64> = self
+                    .y
+                    .iter()
+                    .map(|y| if *y == high { 1.0 } else { -1.0 })
+                    .collect();
+
+                (
+                    Some((low, high)),
+                    SmoSolver::new(&kernel, signs, vec![-1.0; n], parameters.c),
+                )
+            }
+            // Variables alpha_i (sign +1) and alpha_i^* (sign -1).
+            SvmTask::Regression { epsilon } => {
+                let signs = [vec![1.0; n], vec![-1.0; n]].concat();
+                let linear = self
+                    .y
+                    .iter()
+                    .map(|y| epsilon - y)
+                    .chain(self.y.iter().map(|y| epsilon + y))
+                    .collect();
+
+                (None, SmoSolver::new(&kernel, signs, linear, parameters.c))
+            }
+        };
+
+        let iterations = solver.solve(parameters.tolerance, parameters.max_iterations)?;
+        let intercept = -solver.rho();
+
+        let coefficients: Vec<f64> = match parameters.task {
+            SvmTask::Classification => (0..n).map(|i| solver.signs[i] * solver.alpha[i]).collect(),
+            SvmTask::Regression { .. } => (0..n)
+                .map(|i| solver.alpha[i] - solver.alpha[i + n])
+                .collect(),
+        };
+
+        let support: Vec<usize> = (0..n).filter(|i| coefficients[*i] != 0.0).collect();
+
+        Ok(SupportVectorMachineOutput {
+            task: parameters.task,
+            kernel: parameters.kernel,
+            support_vectors: self.x.select_rows(&support),
+            dual_coefficients: DVector::from_iterator(
+                support.len(),
+                support.iter().map(|i| coefficients[*i]),
+            ),
+            intercept,
+            classes,
+            iterations,
+        })
+    }
+
+    /// Checks the data and the parameters.
+    fn validate(&self, parameters: &SupportVectorMachineParameters) -> Result<(), RustQuantError> {
+        if self.x.nrows() == 0 || self.x.ncols() == 0 || self.x.nrows() != self.y.len() {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be non-empty, with one response per row.".to_string(),
+            ));
+        }
+
+        if self.x.iter().chain(self.y.iter()).any(|v| !v.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(
+                "The input data should be finite.".to_string(),
+            ));
+        }
+
+        let gamma = match parameters.kernel {
+            SvmKernel::Linear => 1.0,
+            SvmKernel::Rbf { gamma } => gamma,
+        };
+        let epsilon = match parameters.task {
+            SvmTask::Classification => 0.0,
+            SvmTask::Regression { epsilon } => epsilon,
+        };
+
+        if parameters.c.is_nan()
+            || parameters.c <= 0.0
+            || gamma.is_nan()
+            || gamma <= 0.0
+            || epsilon.is_nan()
+            || epsilon < 0.0
+            || parameters.tolerance.is_nan()
+            || parameters.tolerance <= 0.0
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "The penalty, kernel width and tolerance should be positive, and epsilon non-negative."
+                    .to_string(),
+            ));
+        }
+
+        if parameters.task == SvmTask::Classification {
+            let mut classes: Vec<f64> = self.y.iter().copied().collect();
+            classes.sort_by(f64::total_cmp);
+            classes.dedup();
+
+            if classes.len() != 2 {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Support vector classification needs two classes, got {}.",
+                    classes.len()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SupportVectorMachineOutput<f64> {
+    /// Decision function $f(x)$ at each row of the input data: the signed
+    /// (scaled) distance to the separating hyperplane for classification,
+    /// or the prediction for regression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the training
+    /// data.
+    #[must_use]
+    pub fn decision_function(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        assert_eq!(input.ncols(), self.support_vectors.ncols());
+
+        DVector::from_fn(input.nrows(), |i, _| {
+            self.support_vectors
+                .row_iter()
+                .zip(self.dual_coefficients.iter())
+                .map(|(s, c)| c * self.kernel.evaluate(s, input.row(i)))
+                .sum::<f64>()
+                + self.intercept
+        })
+    }
+
+    /// Predicts the output for the given input data: the class label on
+    /// the side of the hyperplane for classification, or the regression
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input does not have as many columns as the training
+    /// data.
+    #[must_use]
+    pub fn predict(&self, input: &DMatrix<f64>) -> DVector<f64> {
+        let decision = self.decision_function(input);
+
+        match self.classes {
+            Some((low, high)) => decision.map(|f| if f > 0.0 { high } else { low }),
+            None => decision,
+        }
+    }
+
+    /// Weights $w = \sum_i c_i s_i$ of the primal problem, for the linear
+    /// kernel ($f(x) = w^T x + b$).
+    #[must_use]
+    pub fn weights(&self) -> Option<DVector<f64>> {
+        (self.kernel == SvmKernel::Linear)
+            .then(|| self.support_vectors.transpose() * &self.dual_coefficients)
+    }
+}
+
+impl<'a> SmoSolver<'a> {
+    /// Solver started from $\alpha = 0$, where the gradient is $p$.
+    fn new(kernel: &'a DMatrix<f64>, signs: Vec<f64>, linear: Vec<f64>, c: f64) -> Self {
+        let m = signs.len();
+
+        Self {
+            kernel,
+            signs,
+            gradient: linear,
+            c,
+            alpha: vec![0.0; m],
+        }
+    }
+
+    /// Element $Q_{ij}$.
+    fn q(&self, i: usize, j: usize) -> f64 {
+        let n = self.kernel.nrows();
+
+        self.signs[i] * self.signs[j] * self.kernel[(i % n, j % n)]
+    }
+
+    /// Whether $y_t \alpha_t$ can increase.
+    fn is_up(&self, t: usize) -> bool {
+        (self.signs[t] > 0.0 && self.alpha[t] < self.c)
+            || (self.signs[t] < 0.0 && self.alpha[t] > 0.0)
+    }
+
+    /// Whether $y_t \alpha_t$ can decrease.
+    fn is_low(&self, t: usize) -> bool {
+        (self.signs[t] > 0.0 && self.alpha[t] > 0.0)
+            || (self.signs[t] < 0.0 && self.alpha[t] < self.c)
+    }
+
+    /// Runs SMO steps on maximal violating pairs until the violation is
+    /// below the tolerance, and returns the number of steps.
+    fn solve(&mut self, tolerance: f64, max_iterations: usize) -> Result<usize, RustQuantError> {
+        let m = self.alpha.len();
+
+        for iteration in 0..max_iterations {
+            // i maximises -y G over the variables that can go up, and j
+            // minimises it over those that can go down.
+            let (mut i, mut up) = (usize::MAX, f64::NEG_INFINITY);
+            let (mut j, mut low) = (usize::MAX, f64::INFINITY);
+
+            for t in 0..m {
+                let value = -self.signs[t] * self.gradient[t];
+                if self.is_up(t) && value > up {
+                    (i, up) = (t, value);
+                }
+                if self.is_low(t) && value < low {
+                    (j, low) = (t, value);
+                }
+            }
+
+            if i == usize::MAX || j == usize::MAX || up - low < tolerance {
+                return Ok(iteration);
+            }
+
+            self.update_pair(i, j);
+        }
+
+        Err(RustQuantError::ComputationError(format!(
+            "SMO did not converge in {max_iterations} iterations."
+        )))
+    }
+
+    /// Minimises the objective over $\alpha_i$ and $\alpha_j$, keeping
+    /// $y^T \alpha$ fixed and the bounds satisfied.
+    fn update_pair(&mut self, i: usize, j: usize) {
+        let c = self.c;
+        let (old_i, old_j) = (self.alpha[i], self.alpha[j]);
+        let curvature = |value: f64| if value > 0.0 { value } else { 1e-12 };
+
+        if self.signs[i] == self.signs[j] {
+            let quadratic = curvature(self.q(i, i) + self.q(j, j) - 2.0 * self.q(i, j));
+            let delta = (self.gradient[i] - self.gradient[j]) / quadratic;
+            let sum = old_i + old_j;
+
+            let mut new_i = old_i - delta;
+            let mut new_j = old_j + delta;
+
+            if sum > c {
+                if new_i > c {
+                    (new_i, new_j) = (c, sum - c);
+                }
+            } else if new_j < 0.0 {
+                (new_i, new_j) = (sum, 0.0);
+            }
+            if sum > c {
+                if new_j > c {
+                    (new_i, new_j) = (sum - c, c);
+                }
+            } else if new_i < 0.0 {
+                (new_i, new_j) = (0.0, sum);
+            }
+
+            (self.alpha[i], self.alpha[j]) = (new_i, new_j);
+        } else {
+            let quadratic = curvature(self.q(i, i) + self.q(j, j) + 2.0 * self.q(i, j));
+            let delta = (-self.gradient[i] - self.gradient[j]) / quadratic;
+            let difference = old_i - old_j;
+
+            let mut new_i = old_i + delta;
+            let mut new_j = old_j + delta;
+
+            if difference > 0.0 {
+                if new_j < 0.0 {
+                    (new_i, new_j) = (difference, 0.0);
+                }
+            } else if new_i < 0.0 {
+                (new_i, new_j) = (0.0, -difference);
+            }
+            if difference > 0.0 {
+                if new_i > c {
+                    (new_i, new_j) = (c, c - difference);
+                }
+            } else if new_j > c {
+                (new_i, new_j) = (c + difference, c);
+            }
+
+            (self.alpha[i], self.alpha[j]) = (new_i, new_j);
+        }
+
+        let (delta_i, delta_j) = (self.alpha[i] - old_i, self.alpha[j] - old_j);
+        for t in 0..self.alpha.len() {
+            self.gradient[t] += self.q(t, i) * delta_i + self.q(t, j) * delta_j;
+        }
+    }
+
+    /// Offset $\rho = -b$: $y_t G_t$ averaged over the free variables, or
+    /// the midpoint of its feasible interval if there are none.
+    fn rho(&self) -> f64 {
+        let (mut sum, mut free) = (0.0, 0);
+        let (mut upper, mut lower) = (f64::INFINITY, f64::NEG_INFINITY);
+
+        for t in 0..self.alpha.len() {
+            let value = self.signs[t] * self.gradient[t];
+
+            if self.alpha[t] > 0.0 && self.alpha[t] < self.c {
+                sum += value;
+                free += 1;
+            } else if (self.alpha[t] >= self.c) == (self.signs[t] > 0.0) {
+                lower = lower.max(value);
+            } else {
+                upper = upper.min(value);
+            }
+        }
+
+        if free > 0 {
+            sum / f64::from(free)
+        } else {
+            0.5 * (upper + lower)
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_support_vector_machine {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_linear_svm_maximum_margin() {
+        // The maximum margin separator of {-2, -1} and {1, 2} is x = 0,
+        // with margin 1: w = 1, b = 0, and support vectors -1 and 1.
+        let x = dmatrix![-2.0; -1.0; 1.0; 2.0];
+        let y = dvector![0.0, 0.0, 1.0, 1.0];
+
+        let parameters = SupportVectorMachineParameters::classification(SvmKernel::Linear, 1000.0)
+            .with_tolerance(1e-8);
+        let svm = SupportVectorMachineInput::new(x.clone(), y.clone())
+            .fit(&parameters)
+            .unwrap();
+
+        assert_approx_equal!(svm.weights().unwrap()[0], 1.0, 1e-6);
+        assert_approx_equal!(svm.intercept, 0.0, 1e-6);
+        assert_eq!(svm.support_vectors, dmatrix![-1.0; 1.0]);
+        assert_approx_equal!(svm.dual_coefficients.sum(), 0.0, 1e-12);
+
+        assert_eq!(svm.predict(&x), y);
+        assert_eq!(svm.classes, Some((0.0, 1.0)));
+        assert_approx_equal!(svm.decision_function(&dmatrix![0.5])[0], 0.5, 1e-6);
+    }
+
+    #[test]
+    fn test_rbf_svm_circles() {
+        // Inner disc against an outer ring, not linearly separable.
+        let n = 40;
+        let x = DMatrix::from_fn(2 * n, 2, |i, j| {
+            let angle = 2.0 * std::f64::consts::PI * (i % n) as f64 / n as f64;
+            let radius = if i < n { 0.5 } else { 2.0 };
+            radius * if j == 0 { angle.cos() } else { angle.sin() }
+        });
+        let y = DVector::from_fn(2 * n, |i, _| if i < n { -1.0 } else { 1.0 });
+
+        let rbf = SupportVectorMachineInput::new(x.clone(), y.clone())
+            .fit(&SupportVectorMachineParameters::classification(
+                SvmKernel::Rbf { gamma: 1.0 },
+                10.0,
+            ))
+            .unwrap();
+        assert_eq!(rbf.predict(&x), y);
+        assert_eq!(
+            rbf.predict(&dmatrix![0.0, 0.0; 3.0, 0.0]),
+            dvector![-1.0, 1.0]
+        );
+        assert!(rbf.weights().is_none());
+
+        // A linear classifier cannot do much better than chance.
+        let linear = SupportVectorMachineInput::new(x.clone(), y.clone())
+            .fit(&SupportVectorMachineParameters::classification(
+                SvmKernel::Linear,
+                1.0,
+            ))
+            .unwrap();
+        let correct = linear
+            .predict(&x)
+            .iter()
+            .zip(y.iter())
+            .filter(|(p, y)| p == y)
+            .count();
+        assert!(correct < 3 * n / 2);
+    }
+
+    #[test]
+    fn test_support_vector_regression() {
+        // A line, fitted within the tube: the errors are at most epsilon.
+        let x = DMatrix::from_fn(20, 1, |i, _| i as f64 / 10.0);
+        let y = x.column(0).map(|x| 2.0 * x + 1.0);
+        let epsilon = 0.1;
+
+        let parameters =
+            SupportVectorMachineParameters::regression(SvmKernel::Linear, 100.0, epsilon)
+                .with_tolerance(1e-8);
+        let svr = SupportVectorMachineInput::new(x.clone(), y.clone())
+            .fit(&parameters)
+            .unwrap();
+
+        let errors = svr.predict(&x) - &y;
+        assert!(errors.amax() <= epsilon + 1e-6);
+        assert_approx_equal!(svr.weights().unwrap()[0], 2.0, 0.15);
+
+        // RBF regression of a smooth function.
+        let z = x.column(0).map(|x| (3.0 * x).sin());
+        let svr = SupportVectorMachineInput::new(x.clone(), z.clone())
+            .fit(&SupportVectorMachineParameters::regression(
+                SvmKernel::Rbf { gamma: 2.0 },
+                100.0,
+                0.01,
+            ))
+            .unwrap();
+        assert!((svr.predict(&x) - &z).amax() < 0.02);
+        assert!(svr.classes.is_none());
+    }
+
+    #[test]
+    fn test_svm_invalid_input() {
+        let x = dmatrix![0.0; 1.0; 2.0];
+
+        let three_classes = SupportVectorMachineInput::new(x.clone(), dvector![0.0, 1.0, 2.0]);
+        assert!(three_classes
+            .fit(&SupportVectorMachineParameters::classification(
+                SvmKernel::Linear,
+                1.0
+            ))
+            .is_err());
+
+        let input = SupportVectorMachineInput::new(x, dvector![0.0, 1.0, 1.0]);
+        assert!(input
+            .fit(&SupportVectorMachineParameters::classification(
+                SvmKernel::Linear,
+                0.0
+            ))
+            .is_err());
+        assert!(input
+            .fit(&SupportVectorMachineParameters::classification(
+                SvmKernel::Rbf { gamma: -1.0 },
+                1.0
+            ))
+            .is_err());
+        assert!(input
+            .fit(&SupportVectorMachineParameters::regression(
+                SvmKernel::Linear,
+                1.0,
+                -0.1
+            ))
+            .is_err());
+        assert!(input
+            .fit(
+                &SupportVectorMachineParameters::classification(SvmKernel::Linear, 1.0)
+                    .with_max_iterations(0)
+            )
+            .is_err());
+    }
+}
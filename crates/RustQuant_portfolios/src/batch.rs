@@ -0,0 +1,696 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! End-of-day batch valuation.
+//!
+//! An [`EodBatch`] run:
+//!
+//! 1. loads a portfolio of European options from a CSV, JSON or Parquet file,
+//! 2. loads a market snapshot (quoted unit prices by position name),
+//! 3. values the portfolio in parallel against the snapshot, repricing the
+//!    unquoted options at its valuation date
+//!    (see [`Portfolio::value_parallel_with_model`]),
+//! 4. computes position Greeks and a Monte Carlo full-revaluation VaR,
+//! 5. writes a positions file and a one-row summary file with the run metadata
+//!    (timestamps, library version, seed).
+//!
+//! The portfolio file has the columns `name`, `option_type` (`call` or `put`),
+//! `underlying_price`, `strike`, `volatility`, `risk_free_rate`,
+//! `cost_of_carry`, `expiry` (`YYYY-MM-DD`) and `quantity`.
+//! The market file has the columns `name` and `price`.
+//!
+//! The VaR scenarios shock every underlying by its own volatility over the
+//! horizon, with a single common factor: the log-returns of any two
+//! underlyings have correlation `correlation`. Options are repriced at the
+//! valuation date, so the P&L excludes time decay.
+//!
+//! The seed is always recorded; if none is given, one is drawn from entropy,
+//! so every run can be reproduced from its summary file.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{MarketSnapshot, Portfolio, PortfolioValuation, Position, ValuationSource};
+use polars::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use time::{Date, OffsetDateTime};
+use RustQuant_data::{parse_date, Data, DataFormat, DataReader, DataWriter};
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::options::{BlackScholesMerton, TypeFlag};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Configuration of an end-of-day batch run.
+#[derive(Debug, Clone)]
+pub struct EodBatch {
+    /// Valuation date.
+    pub valuation_date: Date,
+
+    /// Path to the portfolio file.
+    pub portfolio_path: PathBuf,
+
+    /// Path to the market snapshot file (`None` to mark every position to model).
+    pub market_path: Option<PathBuf>,
+
+    /// Directory the results are written to.
+    pub output_dir: PathBuf,
+
+    /// Extension (and format) of the output files: `csv`, `json` or `parquet`.
+    pub output_format: String,
+
+    /// VaR confidence level (e.g. 0.99).
+    pub confidence: f64,
+
+    /// VaR horizon, in business days.
+    pub horizon_days: f64,
+
+    /// Number of VaR scenarios.
+    pub scenarios: usize,
+
+    /// Correlation between the underlyings' log-returns in the VaR scenarios.
+    pub correlation: f64,
+
+    /// Seed for the VaR scenarios (`None` to draw one from entropy).
+    pub seed: Option<u64>,
+}
+
+/// Metadata of a batch run, written to the summary file.
+#[derive(Debug, Clone)]
+pub struct RunMetadata {
+    /// Start of the run (UTC).
+    pub started_at: OffsetDateTime,
+
+    /// End of the run (UTC).
+    pub finished_at: OffsetDateTime,
+
+    /// Version of the library that produced the results.
+    pub library_version: &'static str,
+
+    /// Seed used for the VaR scenarios.
+    pub seed: u64,
+}
+
+/// Greeks of a position (quantity times the per-unit Greeks).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionGreeks {
+    /// Sensitivity to the underlying price.
+    pub delta: f64,
+
+    /// Second order sensitivity to the underlying price.
+    pub gamma: f64,
+
+    /// Sensitivity to the volatility.
+    pub vega: f64,
+
+    /// Sensitivity to the passage of time.
+    pub theta: f64,
+}
+
+/// Results of a batch run.
+#[derive(Debug)]
+pub struct BatchReport {
+    /// Portfolio valuation against the snapshot.
+    pub valuation: PortfolioValuation,
+
+    /// Greeks by position name.
+    pub greeks: BTreeMap<String, PositionGreeks>,
+
+    /// Value-at-Risk (a positive number is a loss).
+    pub value_at_risk: f64,
+
+    /// Expected shortfall beyond the VaR.
+    pub expected_shortfall: f64,
+
+    /// Run metadata.
+    pub metadata: RunMetadata,
+
+    /// Path of the positions file.
+    pub positions_path: PathBuf,
+
+    /// Path of the summary file.
+    pub summary_path: PathBuf,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl EodBatch {
+    /// Create a batch run with the default settings:
+    /// CSV output and a 1-day 99% VaR from 10,000 perfectly correlated scenarios.
+    pub fn new(
+        valuation_date: Date,
+        portfolio_path: impl Into<PathBuf>,
+        market_path: Option<PathBuf>,
+        output_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            valuation_date,
+            portfolio_path: portfolio_path.into(),
+            market_path,
+            output_dir: output_dir.into(),
+            output_format: "csv".to_string(),
+            confidence: 0.99,
+            horizon_days: 1.0,
+            scenarios: 10_000,
+            correlation: 1.0,
+            seed: None,
+        }
+    }
+
+    /// Set the output format (`csv`, `json` or `parquet`).
+    pub fn with_output_format(mut self, format: &str) -> Self {
+        self.output_format = format.to_ascii_lowercase();
+        self
+    }
+
+    /// Set the VaR confidence level, horizon (in business days) and number of scenarios.
+    pub fn with_var(mut self, confidence: f64, horizon_days: f64, scenarios: usize) -> Self {
+        self.confidence = confidence;
+        self.horizon_days = horizon_days;
+        self.scenarios = scenarios;
+        self
+    }
+
+    /// Set the correlation between the underlyings in the VaR scenarios.
+    pub fn with_correlation(mut self, correlation: f64) -> Self {
+        self.correlation = correlation;
+        self
+    }
+
+    /// Set the seed for the VaR scenarios.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Run the batch and write the results.
+    pub fn run(&self) -> Result<BatchReport, RustQuantError> {
+        let started_at = OffsetDateTime::now_utc();
+
+        if !(0.0..1.0).contains(&self.confidence) || self.scenarios == 0 {
+            return Err(RustQuantError::InvalidArgument(
+                "VaR needs a confidence in [0, 1) and at least one scenario.".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.correlation) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Correlation must be in [0, 1], got {}.",
+                self.correlation
+            )));
+        }
+
+        let portfolio = load_option_portfolio(&self.portfolio_path, self.valuation_date)?;
+        let snapshot = match &self.market_path {
+            Some(path) => load_market_snapshot(path, self.valuation_date)?,
+            None => MarketSnapshot::new(self.valuation_date, BTreeMap::new()),
+        };
+
+        let valuation = portfolio.value_parallel_with_model(&snapshot, reprice);
+
+        let greeks: BTreeMap<String, PositionGreeks> = portfolio
+            .positions
+            .iter()
+            .map(|(name, position)| {
+                let q = position.quantity as f64;
+                let option = &position.instrument;
+
+                let greeks = PositionGreeks {
+                    delta: q * option.delta(),
+                    gamma: q * option.gamma(),
+                    vega: q * option.vega(),
+                    theta: q * option.theta(),
+                };
+
+                (name.clone(), greeks)
+            })
+            .collect();
+
+        let seed = self.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+        let losses = self.scenario_losses(&portfolio, seed);
+        let (value_at_risk, expected_shortfall) = tail_measures(losses, self.confidence);
+
+        std::fs::create_dir_all(&self.output_dir)?;
+
+        let metadata = RunMetadata {
+            started_at,
+            finished_at: OffsetDateTime::now_utc(),
+            library_version: env!("CARGO_PKG_VERSION"),
+            seed,
+        };
+
+        let positions_path = self.output_path("positions");
+        let summary_path = self.output_path("summary");
+
+        write_frame(&positions_path, positions_frame(&valuation, &greeks)?)?;
+        write_frame(
+            &summary_path,
+            df!(
+                "valuation_date" => [self.valuation_date.to_string()],
+                "started_at" => [metadata.started_at.to_string()],
+                "finished_at" => [metadata.finished_at.to_string()],
+                "library_version" => [metadata.library_version],
+                "seed" => [metadata.seed.to_string()],
+                "positions" => [valuation.positions.len() as u64],
+                "failures" => [valuation.failures.len() as u64],
+                "npv" => [valuation.npv],
+                "confidence" => [self.confidence],
+                "horizon_days" => [self.horizon_days],
+                "scenarios" => [self.scenarios as u64],
+                "correlation" => [self.correlation],
+                "value_at_risk" => [value_at_risk],
+                "expected_shortfall" => [expected_shortfall]
+            )?,
+        )?;
+
+        Ok(BatchReport {
+            valuation,
+            greeks,
+            value_at_risk,
+            expected_shortfall,
+            metadata,
+            positions_path,
+            summary_path,
+        })
+    }
+
+    /// Portfolio losses (negative P&L) in each VaR scenario.
+    fn scenario_losses(&self, portfolio: &Portfolio<BlackScholesMerton>, seed: u64) -> Vec<f64> {
+        // Iterate in name order, so the same seed gives the same draws per position.
+        let positions: BTreeMap<&String, &Position<BlackScholesMerton>> =
+            portfolio.positions.iter().collect();
+        let base: Vec<f64> = positions
+            .values()
+            .map(|position| position.instrument.price())
+            .collect();
+
+        let h = self.horizon_days / 252.0;
+        let rho = self.correlation;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..self.scenarios)
+            .map(|_| {
+                let common: f64 = rng.sample(StandardNormal);
+
+                let pnl: f64 = positions
+                    .values()
+                    .zip(&base)
+                    .map(|(position, price)| {
+                        let option = &position.instrument;
+                        let idiosyncratic: f64 = rng.sample(StandardNormal);
+                        let z = rho.sqrt() * common + (1.0 - rho).sqrt() * idiosyncratic;
+
+                        let sigma = option.volatility;
+                        let shocked_spot = option.underlying_price
+                            * (-0.5 * sigma * sigma * h + sigma * h.sqrt() * z).exp();
+
+                        let shocked = BlackScholesMerton::new(
+                            option.cost_of_carry,
+                            shocked_spot,
+                            option.strike_price,
+                            sigma,
+                            option.risk_free_rate,
+                            option.evaluation_date,
+                            option.expiration_date,
+                            option.option_type,
+                        );
+
+                        position.quantity as f64 * (shocked.price() - price)
+                    })
+                    .sum();
+
+                -pnl
+            })
+            .collect()
+    }
+
+    /// Output file path, e.g. `<output_dir>/positions_2024-01-31.csv`.
+    fn output_path(&self, stem: &str) -> PathBuf {
+        self.output_dir.join(format!(
+            "{}_{}.{}",
+            stem, self.valuation_date, self.output_format
+        ))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Load a portfolio of European options (see the module documentation for the columns).
+///
+/// The options are valued as of `valuation_date`.
+pub fn load_option_portfolio(
+    path: &Path,
+    valuation_date: Date,
+) -> Result<Portfolio<BlackScholesMerton>, RustQuantError> {
+    let df = read_frame(path)?;
+
+    let names = string_column(&df, "name")?;
+    let option_types = string_column(&df, "option_type")?;
+    let underlying_prices = f64_column(&df, "underlying_price")?;
+    let strikes = f64_column(&df, "strike")?;
+    let volatilities = f64_column(&df, "volatility")?;
+    let risk_free_rates = f64_column(&df, "risk_free_rate")?;
+    let costs_of_carry = f64_column(&df, "cost_of_carry")?;
+    let expiries = string_column(&df, "expiry")?;
+    let quantities = f64_column(&df, "quantity")?;
+
+    let mut positions = HashMap::with_capacity(names.len());
+
+    for i in 0..names.len() {
+        let option_type = match option_types[i].to_ascii_lowercase().as_str() {
+            "call" | "c" => TypeFlag::Call,
+            "put" | "p" => TypeFlag::Put,
+            other => {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Unknown option type {} for {}.",
+                    other, names[i]
+                )))
+            }
+        };
+
+        let quantity = quantities[i];
+        if quantity < 0.0 || quantity.fract() != 0.0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Quantity of {} must be a non-negative integer, got {}.",
+                names[i], quantity
+            )));
+        }
+
+        let option = BlackScholesMerton::new(
+            costs_of_carry[i],
+            underlying_prices[i],
+            strikes[i],
+            volatilities[i],
+            risk_free_rates[i],
+            Some(valuation_date),
+            parse_date(&expiries[i])?,
+            option_type,
+        );
+
+        let position = Position::new(option, quantity as u64, 0.0, 0.0, None);
+
+        if positions.insert(names[i].clone(), position).is_some() {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Duplicate position {}.",
+                names[i]
+            )));
+        }
+    }
+
+    Ok(Portfolio::new(positions))
+}
+
+/// Load a market snapshot of quoted unit prices (columns `name` and `price`).
+pub fn load_market_snapshot(
+    path: &Path,
+    valuation_date: Date,
+) -> Result<MarketSnapshot, RustQuantError> {
+    let df = read_frame(path)?;
+
+    let quotes = string_column(&df, "name")?
+        .into_iter()
+        .zip(f64_column(&df, "price")?)
+        .collect();
+
+    Ok(MarketSnapshot::new(valuation_date, quotes))
+}
+
+/// Data format from a file extension.
+fn data_format(path: &Path) -> Result<DataFormat, RustQuantError> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("csv") => Ok(DataFormat::CSV),
+        Some("json") => Ok(DataFormat::JSON),
+        Some("parquet") => Ok(DataFormat::PARQUET),
+        _ => Err(RustQuantError::InvalidArgument(format!(
+            "Unsupported file type: {}.",
+            path.display()
+        ))),
+    }
+}
+
+fn read_frame(path: &Path) -> Result<DataFrame, RustQuantError> {
+    let mut data = Data::new(data_format(path)?, path.display().to_string());
+    data.read()?;
+
+    Ok(data.data)
+}
+
+fn write_frame(path: &Path, frame: DataFrame) -> Result<(), RustQuantError> {
+    let mut data = Data {
+        format: data_format(path)?,
+        path: path.display().to_string(),
+        data: frame,
+    };
+
+    data.write()
+}
+
+fn f64_column(df: &DataFrame, name: &str) -> Result<Vec<f64>, RustQuantError> {
+    let column = df.column(name)?.cast(&DataType::Float64)?;
+
+    column
+        .f64()?
+        .into_iter()
+        .map(|value| value.ok_or_else(|| missing_value(name)))
+        .collect()
+}
+
+fn string_column(df: &DataFrame, name: &str) -> Result<Vec<String>, RustQuantError> {
+    let column = df.column(name)?.cast(&DataType::String)?;
+
+    column
+        .str()?
+        .into_iter()
+        .map(|value| value.map(str::to_string).ok_or_else(|| missing_value(name)))
+        .collect()
+}
+
+fn missing_value(column: &str) -> RustQuantError {
+    RustQuantError::MissingInput(format!("Missing value in column {}.", column))
+}
+
+/// Price of an option at the snapshot's valuation date.
+fn reprice(option: &BlackScholesMerton, snapshot: &MarketSnapshot) -> Result<f64, RustQuantError> {
+    let option = BlackScholesMerton::new(
+        option.cost_of_carry,
+        option.underlying_price,
+        option.strike_price,
+        option.volatility,
+        option.risk_free_rate,
+        Some(snapshot.valuation_date),
+        option.expiration_date,
+        option.option_type,
+    );
+
+    Ok(option.price())
+}
+
+/// Positions frame: valued positions, then failures (with an error message).
+fn positions_frame(
+    valuation: &PortfolioValuation,
+    greeks: &BTreeMap<String, PositionGreeks>,
+) -> Result<DataFrame, RustQuantError> {
+    let n = valuation.positions.len() + valuation.failures.len();
+
+    let mut names = Vec::with_capacity(n);
+    let mut quantities = Vec::with_capacity(n);
+    let mut unit_prices = Vec::with_capacity(n);
+    let mut values = Vec::with_capacity(n);
+    let mut sources = Vec::with_capacity(n);
+    let mut errors = Vec::with_capacity(n);
+
+    for position in &valuation.positions {
+        names.push(position.name.clone());
+        quantities.push(Some(position.quantity));
+        unit_prices.push(Some(position.unit_price));
+        values.push(Some(position.value));
+        sources.push(Some(match position.source {
+            ValuationSource::Quote => "quote",
+            ValuationSource::Model => "model",
+        }));
+        errors.push(None);
+    }
+
+    for (name, error) in &valuation.failures {
+        names.push(name.clone());
+        quantities.push(None);
+        unit_prices.push(None);
+        values.push(None);
+        sources.push(None);
+        errors.push(Some(error.to_string()));
+    }
+
+    let greek = |f: fn(&PositionGreeks) -> f64| -> Vec<Option<f64>> {
+        names.iter().map(|name| greeks.get(name).map(f)).collect()
+    };
+
+    Ok(df!(
+        "name" => &names,
+        "quantity" => quantities,
+        "unit_price" => unit_prices,
+        "value" => values,
+        "source" => sources,
+        "delta" => greek(|g| g.delta),
+        "gamma" => greek(|g| g.gamma),
+        "vega" => greek(|g| g.vega),
+        "theta" => greek(|g| g.theta),
+        "error" => errors
+    )?)
+}
+
+/// Value-at-Risk and expected shortfall of a sample of losses.
+fn tail_measures(mut losses: Vec<f64>, confidence: f64) -> (f64, f64) {
+    losses.sort_by(f64::total_cmp);
+
+    let n = losses.len();
+    let index = ((confidence * n as f64).ceil() as usize).clamp(1, n) - 1;
+    let tail = &losses[index..];
+
+    (losses[index], tail.iter().sum::<f64>() / tail.len() as f64)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_batch {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    const PORTFOLIO: &str = "\
+name,option_type,underlying_price,strike,volatility,risk_free_rate,cost_of_carry,expiry,quantity
+Long call,call,100,100,0.2,0.05,0.05,2025-07-31,100
+Short put,put,100,90,0.25,0.05,0.05,2025-04-30,50
+Far call,call,50,60,0.3,0.05,0.05,2026-01-30,200
+";
+
+    const MARKET: &str = "\
+name,price
+Short put,1.5
+";
+
+    fn write_inputs(dir: &Path) -> (PathBuf, PathBuf) {
+        std::fs::create_dir_all(dir).unwrap();
+
+        let portfolio = dir.join("portfolio.csv");
+        let market = dir.join("market.csv");
+        std::fs::write(&portfolio, PORTFOLIO).unwrap();
+        std::fs::write(&market, MARKET).unwrap();
+
+        (portfolio, market)
+    }
+
+    #[test]
+    fn test_eod_batch() {
+        let dir = std::env::temp_dir().join("rustquant_eod_batch_test");
+        let (portfolio, market) = write_inputs(&dir);
+
+        let batch = EodBatch::new(date!(2025 - 01 - 31), &portfolio, Some(market), &dir)
+            .with_var(0.99, 1.0, 2000)
+            .with_seed(42);
+        let report = batch.run().unwrap();
+
+        assert!(report.valuation.is_complete());
+        assert_eq!(report.valuation.positions.len(), 3);
+        assert_eq!(report.metadata.seed, 42);
+        assert_eq!(
+            report.valuation.position("Short put").unwrap().source,
+            ValuationSource::Quote
+        );
+
+        // Long options only: the loss is bounded by the premium, and the VaR is positive.
+        assert!(report.value_at_risk > 0.0);
+        assert!(report.expected_shortfall >= report.value_at_risk);
+        assert!(report.value_at_risk < report.valuation.npv);
+
+        // Same seed, same VaR.
+        let rerun = batch.run().unwrap();
+        assert_eq!(rerun.value_at_risk, report.value_at_risk);
+
+        // Results are persisted with the metadata.
+        let positions = read_frame(&report.positions_path).unwrap();
+        assert_eq!(positions.height(), 3);
+        assert_eq!(string_column(&positions, "name").unwrap()[0], "Far call");
+
+        let summary = read_frame(&report.summary_path).unwrap();
+        assert_approx_equal!(
+            f64_column(&summary, "npv").unwrap()[0],
+            report.valuation.npv,
+            1e-9
+        );
+        assert_eq!(
+            string_column(&summary, "library_version").unwrap()[0],
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(string_column(&summary, "seed").unwrap()[0], "42");
+    }
+
+    #[test]
+    fn test_eod_batch_parquet() {
+        let dir = std::env::temp_dir().join("rustquant_eod_batch_parquet_test");
+        let (portfolio, _) = write_inputs(&dir);
+
+        let report = EodBatch::new(date!(2025 - 01 - 31), &portfolio, None, &dir)
+            .with_output_format("parquet")
+            .with_var(0.95, 10.0, 500)
+            .with_correlation(0.5)
+            .run()
+            .unwrap();
+
+        assert!(report.summary_path.ends_with("summary_2025-01-31.parquet"));
+        assert!(report
+            .valuation
+            .positions
+            .iter()
+            .all(|position| position.source == ValuationSource::Model));
+
+        let positions = read_frame(&report.positions_path).unwrap();
+        let deltas = f64_column(&positions, "delta").unwrap();
+        assert_approx_equal!(deltas[0], report.greeks["Far call"].delta, 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(parse_date("2025-02-30").is_err());
+        assert!(parse_date("2025/01/01").is_err());
+        assert_eq!(parse_date("2025-01-31").unwrap(), date!(2025 - 01 - 31));
+        assert!(data_format(Path::new("portfolio.xlsx")).is_err());
+
+        let dir = std::env::temp_dir().join("rustquant_eod_batch_invalid_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("portfolio.csv");
+        std::fs::write(&path, PORTFOLIO.replace("put,100,90", "straddle,100,90")).unwrap();
+
+        assert!(load_option_portfolio(&path, date!(2025 - 01 - 31)).is_err());
+    }
+
+    #[test]
+    fn test_tail_measures() {
+        let losses: Vec<f64> = (1..=100).map(f64::from).collect();
+        let (var, es) = tail_measures(losses, 0.95);
+
+        assert_eq!(var, 95.0);
+        assert_eq!(es, 97.5);
+    }
+}
@@ -0,0 +1,401 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Black-Litterman model.
+//!
+//! The prior expected excess returns are the equilibrium returns implied
+//! by the market portfolio, $\Pi = \delta \Sigma w_{mkt}$, with uncertainty
+//! $\tau \Sigma$. Views $P \mu = Q + \varepsilon$, $\varepsilon \sim N(0, \Omega)$,
+//! are blended with them by Bayes' rule:
+//!
+//! $$
+//! \mu_{BL} = \Pi + \tau \Sigma P^T (P \tau \Sigma P^T + \Omega)^{-1} (Q - P \Pi),
+//! $$
+//!
+//! $$
+//! \Sigma_{BL} = \Sigma + \tau \Sigma - \tau \Sigma P^T (P \tau \Sigma P^T + \Omega)^{-1} P \tau \Sigma.
+//! $$
+//!
+//! The uncertainty of a view with confidence $c \in (0, 1]$ is
+//! $\omega = \frac{1 - c}{c} \, p^T \tau \Sigma p$: a view held with full
+//! confidence is matched exactly, and one with confidence one half moves
+//! the returns halfway from the prior to it.
+//!
+//! References:
+//!     - Global Portfolio Optimization (Black, Litterman 1992)
+//!     - The Black-Litterman Model in Detail (Walters 2014)
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::MeanVariance;
+use nalgebra::{DMatrix, DVector};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// View on the expected excess return of a portfolio of the assets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct View {
+    /// Assets (by index) and their weights in the view portfolio.
+    pub weights: Vec<(usize, f64)>,
+
+    /// Expected excess return of the view portfolio.
+    pub expected_return: f64,
+
+    /// Confidence in the view, in (0, 1].
+    pub confidence: f64,
+}
+
+/// Black-Litterman model.
+#[derive(Debug, Clone)]
+pub struct BlackLitterman {
+    /// Covariance matrix $\Sigma$ of the returns of the assets.
+    pub covariance: DMatrix<f64>,
+
+    /// Equilibrium (prior) expected excess returns $\Pi$.
+    pub equilibrium_returns: DVector<f64>,
+
+    /// Scale $\tau$ of the uncertainty of the prior.
+    pub tau: f64,
+
+    /// Views.
+    pub views: Vec<View>,
+}
+
+/// Posterior distribution of the returns of the assets.
+#[derive(Debug, Clone)]
+pub struct BlackLittermanPosterior {
+    /// Posterior expected excess returns $\mu_{BL}$.
+    pub expected_returns: DVector<f64>,
+
+    /// Posterior covariance matrix $\Sigma_{BL}$ of the returns.
+    pub covariance: DMatrix<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl View {
+    /// View on a portfolio of the assets.
+    pub fn new(weights: Vec<(usize, f64)>, expected_return: f64, confidence: f64) -> Self {
+        Self {
+            weights,
+            expected_return,
+            confidence,
+        }
+    }
+
+    /// Absolute view: the asset has the given expected excess return.
+    pub fn absolute(asset: usize, expected_return: f64, confidence: f64) -> Self {
+        Self::new(vec![(asset, 1.0)], expected_return, confidence)
+    }
+
+    /// Relative view: one asset outperforms another by the given spread.
+    pub fn relative(
+        outperformer: usize,
+        underperformer: usize,
+        spread: f64,
+        confidence: f64,
+    ) -> Self {
+        Self::new(
+            vec![(outperformer, 1.0), (underperformer, -1.0)],
+            spread,
+            confidence,
+        )
+    }
+}
+
+impl BlackLitterman {
+    /// Create a model with the given prior returns, $\tau = 0.05$ and no
+    /// views.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no assets, the dimensions do not
+    /// match, or the inputs are not finite.
+    pub fn new(
+        covariance: DMatrix<f64>,
+        equilibrium_returns: DVector<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let n = equilibrium_returns.len();
+
+        if n == 0 || covariance.shape() != (n, n) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Expected a {n} x {n} covariance matrix, got {:?}.",
+                covariance.shape()
+            )));
+        }
+
+        if equilibrium_returns
+            .iter()
+            .chain(covariance.iter())
+            .any(|v| !v.is_finite())
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Returns and covariances must be finite.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            covariance,
+            equilibrium_returns,
+            tau: 0.05,
+            views: Vec::new(),
+        })
+    }
+
+    /// Create a model with the equilibrium returns implied by the market
+    /// weights and risk aversion: $\Pi = \delta \Sigma w_{mkt}$.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dimensions do not match, or the inputs are
+    /// not finite.
+    pub fn from_market_weights(
+        covariance: DMatrix<f64>,
+        market_weights: &DVector<f64>,
+        risk_aversion: f64,
+    ) -> Result<Self, RustQuantError> {
+        if covariance.ncols() != market_weights.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let equilibrium_returns = &covariance * market_weights * risk_aversion;
+
+        Self::new(covariance, equilibrium_returns)
+    }
+
+    /// Risk aversion implied by the market portfolio:
+    /// $\delta = (E[r_m] - r_f) / \sigma_m^2$.
+    pub fn implied_risk_aversion(market_excess_return: f64, market_variance: f64) -> f64 {
+        market_excess_return / market_variance
+    }
+
+    /// Set the scale $\tau$ of the uncertainty of the prior.
+    #[must_use]
+    pub fn with_tau(mut self, tau: f64) -> Self {
+        self.tau = tau;
+        self
+    }
+
+    /// Add a view.
+    #[must_use]
+    pub fn with_view(mut self, view: View) -> Self {
+        self.views.push(view);
+        self
+    }
+
+    /// Posterior distribution of the returns, given the views.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if $\tau$ is not positive, a view refers to an
+    /// unknown asset or has a confidence outside (0, 1], or the views are
+    /// redundant (e.g. the same view twice with full confidence).
+    pub fn posterior(&self) -> Result<BlackLittermanPosterior, RustQuantError> {
+        let n = self.equilibrium_returns.len();
+        let k = self.views.len();
+
+        if !(self.tau > 0.0 && self.tau.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Tau must be positive, got {}.",
+                self.tau
+            )));
+        }
+
+        for view in &self.views {
+            if view.weights.iter().any(|(asset, _)| *asset >= n)
+                || view.weights.iter().any(|(_, w)| !w.is_finite())
+                || !view.expected_return.is_finite()
+                || !(view.confidence > 0.0 && view.confidence <= 1.0)
+            {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Invalid view {view:?} on {n} assets."
+                )));
+            }
+        }
+
+        let prior_covariance = &self.covariance * self.tau;
+
+        if k == 0 {
+            return Ok(BlackLittermanPosterior {
+                expected_returns: self.equilibrium_returns.clone(),
+                covariance: &self.covariance + prior_covariance,
+            });
+        }
+
+        let mut pick = DMatrix::zeros(k, n);
+        for (row, view) in self.views.iter().enumerate() {
+            for (asset, weight) in &view.weights {
+                pick[(row, *asset)] += weight;
+            }
+        }
+        let targets = DVector::from_iterator(k, self.views.iter().map(|v| v.expected_return));
+
+        let projected = &prior_covariance * pick.transpose();
+        let mut system = &pick * &projected;
+        for (row, view) in self.views.iter().enumerate() {
+            system[(row, row)] *= 1.0 / view.confidence;
+        }
+
+        let inverse = system
+            .try_inverse()
+            .ok_or(RustQuantError::MatrixInversionFailed)?;
+        let gain = &projected * inverse;
+
+        let expected_returns =
+            &self.equilibrium_returns + &gain * (targets - &pick * &self.equilibrium_returns);
+        let covariance = &self.covariance + &prior_covariance - &gain * projected.transpose();
+
+        Ok(BlackLittermanPosterior {
+            expected_returns,
+            covariance,
+        })
+    }
+}
+
+impl BlackLittermanPosterior {
+    /// Mean-variance optimiser for the posterior returns. They are excess
+    /// returns, so use a zero risk-free rate for the maximum Sharpe ratio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the posterior is not finite.
+    pub fn mean_variance(&self) -> Result<MeanVariance, RustQuantError> {
+        // Symmetrise away the rounding errors of the update.
+        let covariance = (&self.covariance + self.covariance.transpose()) * 0.5;
+
+        MeanVariance::new(self.expected_returns.clone(), covariance)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_black_litterman {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use RustQuant_utils::assert_approx_equal;
+
+    fn covariance() -> DMatrix<f64> {
+        dmatrix![
+            0.0400, 0.0120, 0.0060;
+            0.0120, 0.0900, 0.0180;
+            0.0060, 0.0180, 0.0225
+        ]
+    }
+
+    #[test]
+    fn test_no_views_recovers_market() {
+        let market = dvector![0.5, 0.2, 0.3];
+        let model = BlackLitterman::from_market_weights(covariance(), &market, 2.5).unwrap();
+        let posterior = model.posterior().unwrap();
+
+        assert_eq!(posterior.expected_returns, &covariance() * &market * 2.5);
+        assert_eq!(posterior.covariance, covariance() * 1.05);
+
+        // The tangency portfolio of the prior is the market portfolio.
+        let tangency = posterior
+            .mean_variance()
+            .unwrap()
+            .maximum_sharpe(0.0)
+            .unwrap();
+        assert!((&tangency.weights - &market).amax() < 1e-6);
+    }
+
+    #[test]
+    fn test_absolute_views() {
+        let model =
+            BlackLitterman::new(dmatrix![0.04, 0.0; 0.0, 0.09], dvector![0.05, 0.07]).unwrap();
+
+        // Confidence one half: halfway to the view. Uncorrelated assets are
+        // unaffected.
+        let half = model
+            .clone()
+            .with_view(View::absolute(0, 0.09, 0.5))
+            .posterior()
+            .unwrap();
+        assert_approx_equal!(half.expected_returns[0], 0.07, 1e-12);
+        assert_approx_equal!(half.expected_returns[1], 0.07, 1e-12);
+        assert_approx_equal!(half.covariance[(0, 0)], 0.04 + 0.5 * 0.05 * 0.04, 1e-12);
+
+        // Full confidence: the view is matched exactly.
+        let full = model
+            .with_view(View::absolute(0, 0.09, 1.0))
+            .posterior()
+            .unwrap();
+        assert_approx_equal!(full.expected_returns[0], 0.09, 1e-12);
+        assert_approx_equal!(full.covariance[(0, 0)], 0.04, 1e-12);
+    }
+
+    #[test]
+    fn test_relative_view() {
+        let market = dvector![0.5, 0.2, 0.3];
+        let model = BlackLitterman::from_market_weights(covariance(), &market, 2.5).unwrap();
+        let prior = model.posterior().unwrap();
+
+        // The second asset outperforms the third by 6% (4.0125% in equilibrium),
+        // fully confidently.
+        let posterior = model
+            .with_view(View::relative(1, 2, 0.06, 1.0))
+            .posterior()
+            .unwrap();
+        let spread = posterior.expected_returns[1] - posterior.expected_returns[2];
+        assert_approx_equal!(spread, 0.06, 1e-12);
+        assert_approx_equal!(
+            prior.expected_returns[1] - prior.expected_returns[2],
+            0.040125,
+            1e-12
+        );
+
+        // The optimiser overweights the second asset relative to the market.
+        let tangency = posterior
+            .mean_variance()
+            .unwrap()
+            .maximum_sharpe(0.0)
+            .unwrap();
+        assert!(tangency.weights[1] > market[1]);
+        assert!(tangency.weights[2] < market[2]);
+        assert_approx_equal!(tangency.weights.sum(), 1.0, 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_views() {
+        let model = BlackLitterman::new(covariance(), dvector![0.05, 0.07, 0.04]).unwrap();
+
+        assert!(model
+            .clone()
+            .with_view(View::absolute(3, 0.1, 0.5))
+            .posterior()
+            .is_err());
+        assert!(model
+            .clone()
+            .with_view(View::absolute(0, 0.1, 0.0))
+            .posterior()
+            .is_err());
+        assert!(model.clone().with_tau(0.0).posterior().is_err());
+
+        // The same view twice, with full confidence, is redundant.
+        assert!(model
+            .clone()
+            .with_view(View::absolute(0, 0.1, 1.0))
+            .with_view(View::absolute(0, 0.1, 1.0))
+            .posterior()
+            .is_err());
+
+        assert!(BlackLitterman::new(covariance(), dvector![0.05]).is_err());
+    }
+}
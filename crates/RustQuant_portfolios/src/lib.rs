@@ -9,7 +9,8 @@
 
 //! A portfolio is a collection of [`Position`]s, which are simply a combination
 //! of an [`Instrument`], a quantity, a purchase price, and a current price.
-//! You may also specify the [`Currency`] of the instrument.
+//! You may also specify the [`Currency`] and sector of the instrument.
+//! Plain holdings, such as shares, can be held as a [`Security`].
 //!
 //! # Example
 //!
@@ -37,6 +38,7 @@
 //!     purchase_price: 2.1045,
 //!     current_price: 3.5,
 //!     currency: Some(USD),
+//!     sector: None,
 //! };
 //!
 //! // Create a position of 100 put options.
@@ -55,6 +57,7 @@
 //!     purchase_price: 2.4524,
 //!     current_price: 2.0,
 //!     currency: Some(USD),
+//!     sector: None,
 //! };
 //!
 //! let positions = HashMap::from([
@@ -77,8 +80,45 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use std::collections::HashMap;
+use time::Date;
 use RustQuant_instruments::{fx::currency::Currency, Instrument};
 
+/// Parallel portfolio valuation against market snapshots.
+pub mod valuation;
+pub use valuation::*;
+
+/// Mean-variance (Markowitz) optimisation and the efficient frontier.
+pub mod mean_variance;
+pub use mean_variance::*;
+
+/// Black-Litterman blending of equilibrium returns with views.
+pub mod black_litterman;
+pub use black_litterman::*;
+
+/// Portfolio VaR and ES, with marginal and component risk.
+pub mod risk;
+pub use risk::*;
+
+/// Performance measurement: returns, attribution, and tracking error.
+pub mod performance;
+pub use performance::*;
+
+/// Rebalancing to target weights, with transaction costs and constraints.
+pub mod rebalance;
+pub use rebalance::*;
+
+/// Stress testing with historical and user-defined factor shocks.
+pub mod stress;
+pub use stress::*;
+
+/// Position sizing: Kelly, volatility targeting, and drawdown constraints.
+pub mod sizing;
+pub use sizing::*;
+
+/// End-of-day batch valuation pipeline.
+pub mod batch;
+pub use batch::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -105,6 +145,23 @@ pub struct Position<I: Instrument> {
 
     /// Currency of the instrument.
     pub currency: Option<Currency>,
+
+    /// Sector (or any other grouping) of the instrument.
+    pub sector: Option<String>,
+}
+
+/// Holding identified only by its ticker, such as a share or a fund,
+/// priced at its last traded price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Security {
+    /// Ticker symbol.
+    pub symbol: String,
+
+    /// Last traded price.
+    pub last_price: f64,
+
+    /// Date of the last traded price.
+    pub date: Date,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -129,9 +186,17 @@ where
             purchase_price,
             current_price,
             currency,
+            sector: None,
         }
     }
 
+    /// Set the sector of the position.
+    #[must_use]
+    pub fn with_sector(mut self, sector: &str) -> Self {
+        self.sector = Some(sector.to_string());
+        self
+    }
+
     /// Returns the value of the position.
     pub fn value(&self) -> f64 {
         self.quantity as f64 * self.current_price
@@ -153,6 +218,36 @@ where
     }
 }
 
+impl Security {
+    /// Create a new security.
+    #[must_use]
+    pub fn new(symbol: &str, last_price: f64, date: Date) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            last_price,
+            date,
+        }
+    }
+}
+
+impl Instrument for Security {
+    fn price(&self) -> f64 {
+        self.last_price
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.date
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "Security"
+    }
+}
+
 impl<I> Portfolio<I>
 where
     I: Instrument,
@@ -222,6 +317,61 @@ where
             })
             .collect()
     }
+
+    /// Returns the value of the positions in each currency (`None` for the
+    /// positions without one).
+    #[must_use]
+    pub fn exposure_by_currency(&self) -> HashMap<Option<Currency>, f64> {
+        let mut exposures = HashMap::new();
+
+        for position in self.positions.values() {
+            *exposures.entry(position.currency).or_insert(0.0) += position.value();
+        }
+
+        exposures
+    }
+
+    /// Returns the value of the positions in each sector (`None` for the
+    /// positions without one).
+    #[must_use]
+    pub fn exposure_by_sector(&self) -> HashMap<Option<String>, f64> {
+        let mut exposures = HashMap::new();
+
+        for position in self.positions.values() {
+            *exposures.entry(position.sector.clone()).or_insert(0.0) += position.value();
+        }
+
+        exposures
+    }
+
+    /// Returns a Greek of each position: the quantity times the per-unit
+    /// Greek of its instrument, e.g. `portfolio.position_greeks(BlackScholesMerton::delta)`.
+    pub fn position_greeks<F>(&self, greek: F) -> HashMap<String, f64>
+    where
+        F: Fn(&I) -> f64,
+    {
+        self.positions
+            .iter()
+            .map(|(name, position)| {
+                (
+                    name.to_string(),
+                    position.quantity as f64 * greek(&position.instrument),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns a Greek of the portfolio: the sum of the Greeks of the
+    /// positions.
+    pub fn aggregate_greek<F>(&self, greek: F) -> f64
+    where
+        F: Fn(&I) -> f64,
+    {
+        self.positions
+            .values()
+            .map(|position| position.quantity as f64 * greek(&position.instrument))
+            .sum()
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -254,6 +404,7 @@ mod tests_portfolio {
             purchase_price: 2.1045,
             current_price: 3.5,
             currency: Some(USD),
+            sector: None,
         };
 
         // Create a position of 100 put options.
@@ -272,6 +423,7 @@ mod tests_portfolio {
             purchase_price: 2.4524,
             current_price: 2.0,
             currency: Some(USD),
+            sector: None,
         };
 
         let positions = HashMap::from([
@@ -307,4 +459,84 @@ mod tests_portfolio {
         assert_eq!(weights.get("Put Options"), Some(&0.36363637));
         assert_eq!(weights.get("Call Options"), Some(&0.6363636));
     }
+
+    #[test]
+    fn test_portfolio_exposures() {
+        let date = today();
+        let positions = HashMap::from([
+            (
+                "AAPL".to_string(),
+                Position::new(
+                    Security::new("AAPL", 200.0, date),
+                    10,
+                    150.0,
+                    200.0,
+                    Some(USD),
+                )
+                .with_sector("Technology"),
+            ),
+            (
+                "MSFT".to_string(),
+                Position::new(
+                    Security::new("MSFT", 400.0, date),
+                    5,
+                    300.0,
+                    400.0,
+                    Some(USD),
+                )
+                .with_sector("Technology"),
+            ),
+            (
+                "SAN".to_string(),
+                Position::new(
+                    Security::new("SAN", 100.0, date),
+                    20,
+                    90.0,
+                    100.0,
+                    Some(EUR),
+                )
+                .with_sector("Healthcare"),
+            ),
+            (
+                "Cash".to_string(),
+                Position::new(Security::new("Cash", 1.0, date), 500, 1.0, 1.0, None),
+            ),
+        ]);
+        let portfolio = Portfolio::new(positions);
+
+        assert_approx_equal!(portfolio.value(), 6500.0, 1e-10);
+        assert_approx_equal!(portfolio.profit(), 1200.0, 1e-10);
+
+        let currencies = portfolio.exposure_by_currency();
+        assert_approx_equal!(currencies[&Some(USD)], 4000.0, 1e-10);
+        assert_approx_equal!(currencies[&Some(EUR)], 2000.0, 1e-10);
+        assert_approx_equal!(currencies[&None], 500.0, 1e-10);
+
+        let sectors = portfolio.exposure_by_sector();
+        assert_eq!(sectors.len(), 3);
+        assert_approx_equal!(sectors[&Some("Technology".to_string())], 4000.0, 1e-10);
+        assert_approx_equal!(sectors[&Some("Healthcare".to_string())], 2000.0, 1e-10);
+        assert_approx_equal!(sectors[&None], 500.0, 1e-10);
+
+        // Securities are priced at their last price.
+        let security = &portfolio.positions["MSFT"].instrument;
+        assert_eq!(security.price(), 400.0);
+        assert_eq!(security.instrument_type(), "Security");
+    }
+
+    #[test]
+    fn test_portfolio_greeks() {
+        let portfolio = setup_test_portfolio();
+
+        let deltas = portfolio.position_greeks(BlackScholesMerton::delta);
+        let call = &portfolio.positions["Call Options"].instrument;
+        assert_approx_equal!(deltas["Call Options"], 100.0 * call.delta(), 1e-12);
+
+        let delta = portfolio.aggregate_greek(BlackScholesMerton::delta);
+        assert_approx_equal!(delta, deltas.values().sum::<f64>(), 1e-12);
+
+        // Long calls and puts are both long gamma and vega.
+        assert!(portfolio.aggregate_greek(BlackScholesMerton::gamma) > 0.0);
+        assert!(portfolio.aggregate_greek(BlackScholesMerton::vega) > 0.0);
+    }
 }
@@ -0,0 +1,580 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Mean-variance (Markowitz) portfolio optimisation.
+//!
+//! Given expected returns $\mu$ and a covariance matrix $\Sigma$, the
+//! efficient portfolios minimise the variance $w^T \Sigma w$ for a target
+//! expected return $\mu^T w$, subject to the budget $\sum_i w_i = 1$ and the
+//! box $l_i \leq w_i \leq u_i$ (e.g. $w \geq 0$ for a long-only portfolio).
+//! Each is a quadratic program, solved with the
+//! [`AugmentedLagrangian`] optimizer.
+//!
+//! The maximum Sharpe ratio portfolio is found from the equivalent convex
+//! problem in $y = \kappa w$, $\kappa > 0$:
+//!
+//! $$
+//! \min_{y, \kappa} y^T \Sigma y \quad \text{s.t.} \quad
+//! (\mu - r_f)^T y = 1, \quad \sum_i y_i = \kappa, \quad
+//! l_i \kappa \leq y_i \leq u_i \kappa.
+//! $$
+//!
+//! # Example
+//!
+//! ```
+//! # use RustQuant::portfolios::MeanVariance;
+//! # use nalgebra::{dmatrix, dvector};
+//! let optimiser = MeanVariance::new(
+//!     dvector![0.06, 0.12, 0.10],
+//!     dmatrix![0.0400, 0.0060, 0.0020;
+//!              0.0060, 0.0900, 0.0100;
+//!              0.0020, 0.0100, 0.0625],
+//! )
+//! .unwrap()
+//! .with_long_only();
+//!
+//! let minimum_variance = optimiser.minimum_variance().unwrap();
+//! let tangency = optimiser.maximum_sharpe(0.02).unwrap();
+//! let frontier = optimiser.efficient_frontier(10).unwrap();
+//!
+//! assert!(tangency.sharpe_ratio(0.02) >= minimum_variance.sharpe_ratio(0.02));
+//! assert!(frontier.iter().all(|p| p.weights.min() >= -1e-8));
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use RustQuant_error::RustQuantError;
+use RustQuant_math::AugmentedLagrangian;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Mean-variance optimiser.
+#[derive(Debug, Clone)]
+pub struct MeanVariance {
+    /// Expected returns of the assets.
+    pub expected_returns: DVector<f64>,
+
+    /// Covariance matrix of the returns of the assets.
+    pub covariance: DMatrix<f64>,
+
+    /// Lower and upper bound of each weight (possibly infinite).
+    pub bounds: Vec<(f64, f64)>,
+}
+
+/// Portfolio on (or below) the efficient frontier.
+#[derive(Debug, Clone)]
+pub struct EfficientPortfolio {
+    /// Weights of the assets, summing to one.
+    pub weights: DVector<f64>,
+
+    /// Expected return $\mu^T w$.
+    pub expected_return: f64,
+
+    /// Volatility $\sqrt{w^T \Sigma w}$.
+    pub volatility: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl EfficientPortfolio {
+    /// Sharpe ratio of the portfolio: $(\mu^T w - r_f) / \sigma$.
+    pub fn sharpe_ratio(&self, risk_free_rate: f64) -> f64 {
+        (self.expected_return - risk_free_rate) / self.volatility
+    }
+}
+
+impl MeanVariance {
+    /// Create an optimiser with the budget constraint only (short positions
+    /// allowed).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no assets, the dimensions do not
+    /// match, or the inputs are not finite or the covariance matrix is not
+    /// symmetric.
+    pub fn new(
+        expected_returns: DVector<f64>,
+        covariance: DMatrix<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let n = expected_returns.len();
+
+        if n == 0 || covariance.shape() != (n, n) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Expected a {n} x {n} covariance matrix, got {:?}.",
+                covariance.shape()
+            )));
+        }
+
+        if expected_returns
+            .iter()
+            .chain(covariance.iter())
+            .any(|v| !v.is_finite())
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Expected returns and covariances must be finite.".to_string(),
+            ));
+        }
+
+        if (&covariance - covariance.transpose()).amax() > 1e-12 * covariance.amax() {
+            return Err(RustQuantError::InvalidArgument(
+                "The covariance matrix must be symmetric.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            expected_returns,
+            covariance,
+            bounds: vec![(f64::NEG_INFINITY, f64::INFINITY); n],
+        })
+    }
+
+    /// Forbid short positions: raise every lower bound to zero.
+    #[must_use]
+    pub fn with_long_only(mut self) -> Self {
+        self.bounds
+            .iter_mut()
+            .for_each(|(lower, _)| *lower = lower.max(0.0));
+        self
+    }
+
+    /// Set the bounds of the weights. Infinite bounds are allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not one pair of bounds per asset, or a
+    /// lower bound is above its upper bound.
+    pub fn with_bounds(mut self, bounds: Vec<(f64, f64)>) -> Result<Self, RustQuantError> {
+        if bounds.len() != self.bounds.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        if bounds
+            .iter()
+            .any(|(lower, upper)| lower.is_nan() || upper.is_nan() || lower > upper)
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Each lower bound must be at most its upper bound.".to_string(),
+            ));
+        }
+
+        self.bounds = bounds;
+        Ok(self)
+    }
+
+    /// Portfolio with the smallest variance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds exclude every fully invested
+    /// portfolio, or the optimizer does not converge.
+    pub fn minimum_variance(&self) -> Result<EfficientPortfolio, RustQuantError> {
+        self.solve(None)
+    }
+
+    /// Portfolio with the smallest variance for a target expected return.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target is not attainable within the bounds,
+    /// or the optimizer does not converge.
+    pub fn efficient_portfolio(
+        &self,
+        target_return: f64,
+    ) -> Result<EfficientPortfolio, RustQuantError> {
+        if self
+            .maximum_return()
+            .is_some_and(|maximum| target_return > maximum + 1e-12)
+            || self
+                .minimum_return()
+                .is_some_and(|minimum| target_return < minimum - 1e-12)
+        {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Target return {target_return} is not attainable within the bounds."
+            )));
+        }
+
+        self.solve(Some(target_return))
+    }
+
+    /// Portfolio with the largest Sharpe ratio (the tangency portfolio).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no portfolio within the bounds has an expected
+    /// return above the risk-free rate, or the optimizer does not converge.
+    pub fn maximum_sharpe(
+        &self,
+        risk_free_rate: f64,
+    ) -> Result<EfficientPortfolio, RustQuantError> {
+        self.check_budget()?;
+
+        let n = self.expected_returns.len();
+        let excess = self.expected_returns.add_scalar(-risk_free_rate);
+
+        if excess.amax() == 0.0
+            || self
+                .maximum_return()
+                .is_some_and(|maximum| maximum <= risk_free_rate)
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "No portfolio has an expected return above the risk-free rate.".to_string(),
+            ));
+        }
+
+        // Variables (y, kappa) with y = kappa * w. Zero bounds stay box
+        // constraints, the others become inequalities in kappa.
+        let mut bounds = vec![(f64::NEG_INFINITY, f64::INFINITY); n];
+        bounds.push((0.0, f64::INFINITY));
+
+        let mut inequalities = Vec::new();
+        for (i, (lower, upper)) in self.bounds.iter().enumerate() {
+            if *lower == 0.0 {
+                bounds[i].0 = 0.0;
+            } else if lower.is_finite() {
+                let mut c = vec![0.0; n + 1];
+                (c[i], c[n]) = (-1.0, *lower);
+                inequalities.push(c);
+            }
+
+            if *upper == 0.0 {
+                bounds[i].1 = 0.0;
+            } else if upper.is_finite() {
+                let mut c = vec![0.0; n + 1];
+                (c[i], c[n]) = (1.0, -upper);
+                inequalities.push(c);
+            }
+        }
+
+        // Normalised so that kappa, and the constraint, are of order one.
+        let mut excess_row: Vec<f64> = (&excess / excess.amax()).iter().copied().collect();
+        excess_row.push(0.0);
+        let mut budget_row = vec![1.0; n];
+        budget_row.push(-1.0);
+
+        let mut problem = AugmentedLagrangian::new(n + 1)
+            .with_bounds(bounds)?
+            .with_equality(excess_row, 1.0)?
+            .with_equality(budget_row, 0.0)?;
+        for c in inequalities {
+            problem = problem.with_inequality(c, 0.0)?;
+        }
+
+        let covariance = self.scaled_covariance();
+        let objective = |z: &[f64]| {
+            let y = DVector::from_column_slice(&z[..n]);
+            let gradient = &covariance * &y;
+            let mut full: Vec<f64> = gradient.iter().copied().collect();
+            full.push(0.0);
+
+            (0.5 * y.dot(&gradient), full)
+        };
+
+        let mut z0 = vec![1.0 / n as f64; n];
+        z0.push(1.0);
+        let result = problem.optimize_with_gradient(objective, &z0);
+
+        let kappa = result.minimizer[n];
+        if !result.converged || kappa <= 0.0 {
+            return Err(RustQuantError::ComputationError(
+                "Maximum Sharpe ratio optimisation did not converge.".to_string(),
+            ));
+        }
+
+        Ok(self.portfolio(DVector::from_iterator(
+            n,
+            result.minimizer[..n].iter().map(|y| y / kappa),
+        )))
+    }
+
+    /// Efficient frontier: `n_points` efficient portfolios with target
+    /// returns evenly spaced from that of the minimum variance portfolio to
+    /// the largest attainable one (the largest expected asset return if
+    /// unbounded short positions make it infinite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two points, the bounds
+    /// exclude every fully invested portfolio, or the optimizer does not
+    /// converge.
+    pub fn efficient_frontier(
+        &self,
+        n_points: usize,
+    ) -> Result<Vec<EfficientPortfolio>, RustQuantError> {
+        if n_points < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "The efficient frontier needs at least two points.".to_string(),
+            ));
+        }
+
+        let minimum_variance = self.minimum_variance()?;
+        let lowest = minimum_variance.expected_return;
+        let highest = self
+            .maximum_return()
+            .unwrap_or_else(|| self.expected_returns.max())
+            .max(lowest);
+
+        let mut frontier = vec![minimum_variance];
+        for i in 1..n_points {
+            let target = lowest + (highest - lowest) * i as f64 / (n_points - 1) as f64;
+            frontier.push(self.solve(Some(target))?);
+        }
+
+        Ok(frontier)
+    }
+
+    /// Checks that some fully invested portfolio is within the bounds.
+    fn check_budget(&self) -> Result<(), RustQuantError> {
+        let lower: f64 = self.bounds.iter().map(|(lower, _)| lower).sum();
+        let upper: f64 = self.bounds.iter().map(|(_, upper)| upper).sum();
+
+        if lower > 1.0 + 1e-12 || upper < 1.0 - 1e-12 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "The bounds allow total weights in [{lower}, {upper}], which excludes one."
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Largest expected return within the bounds (`None` if unbounded):
+    /// start from the lower bounds, and fill the rest of the budget with the
+    /// assets of highest expected return first.
+    fn maximum_return(&self) -> Option<f64> {
+        self.extreme_return(&self.expected_returns, &self.bounds)
+    }
+
+    /// Smallest expected return within the bounds (`None` if unbounded).
+    fn minimum_return(&self) -> Option<f64> {
+        let flipped: Vec<(f64, f64)> = self.bounds.iter().map(|(l, u)| (-u, -l)).collect();
+
+        self.extreme_return(&-&self.expected_returns, &flipped)
+            .map(|r| -r)
+    }
+
+    /// Largest value of $r^T w$ with $\sum_i w_i = 1$ and the given bounds
+    /// (`None` if unbounded), ignoring whether the bounds are feasible.
+    fn extreme_return(&self, returns: &DVector<f64>, bounds: &[(f64, f64)]) -> Option<f64> {
+        if bounds.iter().any(|(lower, _)| lower.is_infinite()) {
+            return None;
+        }
+
+        let mut order: Vec<usize> = (0..returns.len()).collect();
+        order.sort_by(|a, b| returns[*b].total_cmp(&returns[*a]));
+
+        let mut remaining = 1.0 - bounds.iter().map(|(lower, _)| lower).sum::<f64>();
+        let mut total: f64 = bounds
+            .iter()
+            .zip(returns.iter())
+            .map(|((lower, _), r)| lower * r)
+            .sum();
+
+        for i in order {
+            let amount = remaining.min(bounds[i].1 - bounds[i].0).max(0.0);
+            total += amount * returns[i];
+            remaining -= amount;
+        }
+
+        Some(total)
+    }
+
+    /// Minimises the variance with the budget, the bounds, and optionally a
+    /// target return.
+    fn solve(&self, target_return: Option<f64>) -> Result<EfficientPortfolio, RustQuantError> {
+        self.check_budget()?;
+
+        let n = self.expected_returns.len();
+
+        let mut problem = AugmentedLagrangian::new(n)
+            .with_bounds(self.bounds.clone())?
+            .with_equality(vec![1.0; n], 1.0)?;
+        if let Some(target) = target_return {
+            let scale = match self.expected_returns.amax() {
+                scale if scale > 0.0 => scale,
+                _ => 1.0,
+            };
+            let row = (&self.expected_returns / scale).iter().copied().collect();
+            problem = problem.with_equality(row, target / scale)?;
+        }
+
+        let covariance = self.scaled_covariance();
+        let objective = |w: &[f64]| {
+            let w = DVector::from_column_slice(w);
+            let gradient = &covariance * &w;
+
+            (0.5 * w.dot(&gradient), gradient.iter().copied().collect())
+        };
+
+        let result = problem.optimize_with_gradient(objective, &vec![1.0 / n as f64; n]);
+
+        if !result.converged {
+            return Err(RustQuantError::ComputationError(
+                "Mean-variance optimisation did not converge.".to_string(),
+            ));
+        }
+
+        Ok(self.portfolio(DVector::from_vec(result.minimizer)))
+    }
+
+    /// Covariance matrix divided by its average variance, which leaves the
+    /// optimal weights unchanged but brings the gradients to the scale of
+    /// the optimizer tolerance.
+    fn scaled_covariance(&self) -> DMatrix<f64> {
+        let scale = self.covariance.diagonal().mean();
+
+        if scale > 0.0 {
+            &self.covariance / scale
+        } else {
+            self.covariance.clone()
+        }
+    }
+
+    /// Portfolio with the given weights.
+    fn portfolio(&self, weights: DVector<f64>) -> EfficientPortfolio {
+        EfficientPortfolio {
+            expected_return: self.expected_returns.dot(&weights),
+            volatility: (&self.covariance * &weights).dot(&weights).max(0.0).sqrt(),
+            weights,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_mean_variance {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use RustQuant_utils::assert_approx_equal;
+
+    fn optimiser() -> MeanVariance {
+        MeanVariance::new(
+            dvector![0.06, 0.12, 0.10, 0.05],
+            dmatrix![
+                0.0400, 0.0060, 0.0020, 0.0000;
+                0.0060, 0.0900, 0.0100, 0.0050;
+                0.0020, 0.0100, 0.0625, 0.0150;
+                0.0000, 0.0050, 0.0150, 0.0225
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_unconstrained_closed_forms() {
+        let optimiser = optimiser();
+        let inverse = optimiser.covariance.clone().try_inverse().unwrap();
+        let ones = DVector::from_element(4, 1.0);
+
+        // Minimum variance: Sigma^-1 1 / (1' Sigma^-1 1).
+        let expected = &inverse * &ones / ones.dot(&(&inverse * &ones));
+        let minimum_variance = optimiser.minimum_variance().unwrap();
+        assert!((&minimum_variance.weights - &expected).amax() < 1e-7);
+
+        // Tangency: Sigma^-1 (mu - rf) / (1' Sigma^-1 (mu - rf)).
+        let excess = optimiser.expected_returns.add_scalar(-0.02);
+        let expected = &inverse * &excess / ones.dot(&(&inverse * &excess));
+        let tangency = optimiser.maximum_sharpe(0.02).unwrap();
+        assert!((&tangency.weights - &expected).amax() < 1e-6);
+        assert_approx_equal!(
+            tangency.sharpe_ratio(0.02),
+            excess.dot(&(&inverse * &excess)).sqrt(),
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_long_only_target_return() {
+        // The fourth asset would be held for a 10% return without the
+        // long-only constraint.
+        let portfolio = optimiser()
+            .with_long_only()
+            .efficient_portfolio(0.10)
+            .unwrap();
+
+        for (w, expected) in portfolio.weights.iter().zip([
+            0.193_680_514_921_006_32,
+            0.387_361_029_842_013,
+            0.418_958_455_236_980_8,
+            0.0,
+        ]) {
+            assert_approx_equal!(*w, expected, 1e-6);
+        }
+        assert_approx_equal!(portfolio.expected_return, 0.10, 1e-8);
+        assert_approx_equal!(portfolio.weights.sum(), 1.0, 1e-8);
+    }
+
+    #[test]
+    fn test_box_constraints() {
+        let unconstrained = optimiser().maximum_sharpe(0.02).unwrap();
+        let boxed = optimiser().with_bounds(vec![(0.1, 0.3); 4]).unwrap();
+
+        let minimum_variance = boxed.minimum_variance().unwrap();
+        let tangency = boxed.maximum_sharpe(0.02).unwrap();
+
+        for portfolio in [&minimum_variance, &tangency] {
+            assert_approx_equal!(portfolio.weights.sum(), 1.0, 1e-8);
+            assert!(portfolio.weights.min() >= 0.1 - 1e-8);
+            assert!(portfolio.weights.max() <= 0.3 + 1e-8);
+        }
+
+        // The unconstrained tangency portfolio holds 31% of the third asset.
+        assert_approx_equal!(tangency.weights[2], 0.3, 1e-7);
+        assert!(tangency.sharpe_ratio(0.02) < unconstrained.sharpe_ratio(0.02));
+
+        // The largest attainable return is 0.3 * (0.06 + 0.12 + 0.10) + 0.1 * 0.05.
+        assert_approx_equal!(boxed.maximum_return().unwrap(), 0.089, 1e-12);
+        assert!(boxed.efficient_portfolio(0.09).is_err());
+        assert!(boxed.efficient_portfolio(0.089).is_ok());
+    }
+
+    #[test]
+    fn test_efficient_frontier() {
+        let optimiser = optimiser().with_long_only();
+        let frontier = optimiser.efficient_frontier(11).unwrap();
+        let tangency = optimiser.maximum_sharpe(0.02).unwrap();
+
+        assert_eq!(frontier.len(), 11);
+        assert_approx_equal!(frontier[10].expected_return, 0.12, 1e-8);
+        assert_approx_equal!(frontier[10].weights[1], 1.0, 1e-6);
+
+        for pair in frontier.windows(2) {
+            assert!(pair[1].expected_return > pair[0].expected_return);
+            assert!(pair[1].volatility > pair[0].volatility);
+        }
+        for portfolio in &frontier {
+            assert!(portfolio.weights.min() >= -1e-8);
+            assert!(portfolio.sharpe_ratio(0.02) <= tangency.sharpe_ratio(0.02) + 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(MeanVariance::new(dvector![0.1, 0.2], dmatrix![0.04, 0.01; 0.02, 0.09]).is_err());
+        assert!(MeanVariance::new(dvector![0.1, 0.2], dmatrix![0.04]).is_err());
+        assert!(optimiser().with_bounds(vec![(0.0, 1.0); 3]).is_err());
+        assert!(optimiser().with_bounds(vec![(0.5, 0.1); 4]).is_err());
+
+        // Four assets capped at 20% cannot be fully invested.
+        let capped = optimiser().with_bounds(vec![(0.0, 0.2); 4]).unwrap();
+        assert!(capped.minimum_variance().is_err());
+
+        // No asset beats a 15% risk-free rate without shorting.
+        assert!(optimiser().with_long_only().maximum_sharpe(0.15).is_err());
+        assert!(optimiser().efficient_frontier(1).is_err());
+    }
+}
@@ -0,0 +1,543 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Performance measurement and attribution.
+//!
+//! - Returns: time-weighted (removes the effect of the timing of external
+//!   cash flows, which the manager does not control) and money-weighted
+//!   (the internal rate of return of the investor's cash flows).
+//! - Attribution: the Brinson-Fachler decomposition of the active return
+//!   over a benchmark into allocation, selection, and interaction effects
+//!   per segment (e.g. sector), linked over periods with Carino smoothing.
+//! - Risk-adjusted: tracking error and information ratio of the active
+//!   returns.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::rootfinder::{find_root, RootfinderData};
+use RustQuant_time::DayCountConvention;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Holdings and returns of the portfolio and the benchmark, per segment,
+/// over one period.
+#[derive(Debug, Clone)]
+pub struct AttributionPeriod {
+    /// Weights of the segments in the portfolio, at the start of the period.
+    pub portfolio_weights: Vec<f64>,
+
+    /// Weights of the segments in the benchmark, at the start of the period.
+    pub benchmark_weights: Vec<f64>,
+
+    /// Returns of the segments in the portfolio.
+    pub portfolio_returns: Vec<f64>,
+
+    /// Returns of the segments in the benchmark.
+    pub benchmark_returns: Vec<f64>,
+}
+
+/// Brinson-Fachler attribution of the active return, per segment.
+///
+/// The allocation, selection, and interaction effects sum to the active
+/// return: the portfolio return minus the benchmark return.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribution {
+    /// Return of the portfolio.
+    pub portfolio_return: f64,
+
+    /// Return of the benchmark.
+    pub benchmark_return: f64,
+
+    /// Allocation effect: $(w_i - b_i)(s_i - B)$, the value of over-weighting
+    /// the segments that beat the benchmark.
+    pub allocation: Vec<f64>,
+
+    /// Selection effect: $b_i (r_i - s_i)$, the value of picking securities
+    /// that beat their segment of the benchmark.
+    pub selection: Vec<f64>,
+
+    /// Interaction effect: $(w_i - b_i)(r_i - s_i)$.
+    pub interaction: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl AttributionPeriod {
+    /// Create a new attribution period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lengths differ, or the portfolio or benchmark
+    /// weights do not sum to one.
+    pub fn new(
+        portfolio_weights: Vec<f64>,
+        benchmark_weights: Vec<f64>,
+        portfolio_returns: Vec<f64>,
+        benchmark_returns: Vec<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let n = portfolio_weights.len();
+
+        if benchmark_weights.len() != n
+            || portfolio_returns.len() != n
+            || benchmark_returns.len() != n
+        {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        for weights in [&portfolio_weights, &benchmark_weights] {
+            let total = weights.iter().sum::<f64>();
+
+            if (total - 1.0).abs() > 1e-8 {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Segment weights must sum to one, got {total}."
+                )));
+            }
+        }
+
+        Ok(Self {
+            portfolio_weights,
+            benchmark_weights,
+            portfolio_returns,
+            benchmark_returns,
+        })
+    }
+
+    /// Return of the portfolio over the period.
+    pub fn portfolio_return(&self) -> f64 {
+        dot(&self.portfolio_weights, &self.portfolio_returns)
+    }
+
+    /// Return of the benchmark over the period.
+    pub fn benchmark_return(&self) -> f64 {
+        dot(&self.benchmark_weights, &self.benchmark_returns)
+    }
+
+    /// Brinson-Fachler attribution of the active return over the period.
+    pub fn brinson_fachler(&self) -> Attribution {
+        let benchmark_return = self.benchmark_return();
+
+        let mut allocation = Vec::with_capacity(self.portfolio_weights.len());
+        let mut selection = Vec::with_capacity(self.portfolio_weights.len());
+        let mut interaction = Vec::with_capacity(self.portfolio_weights.len());
+
+        for i in 0..self.portfolio_weights.len() {
+            let active_weight = self.portfolio_weights[i] - self.benchmark_weights[i];
+            let active_return = self.portfolio_returns[i] - self.benchmark_returns[i];
+
+            allocation.push(active_weight * (self.benchmark_returns[i] - benchmark_return));
+            selection.push(self.benchmark_weights[i] * active_return);
+            interaction.push(active_weight * active_return);
+        }
+
+        Attribution {
+            portfolio_return: self.portfolio_return(),
+            benchmark_return,
+            allocation,
+            selection,
+            interaction,
+        }
+    }
+}
+
+impl Attribution {
+    /// Active return: the portfolio return minus the benchmark return.
+    pub fn active_return(&self) -> f64 {
+        self.portfolio_return - self.benchmark_return
+    }
+
+    /// Total allocation effect.
+    pub fn total_allocation(&self) -> f64 {
+        self.allocation.iter().sum()
+    }
+
+    /// Total selection effect.
+    pub fn total_selection(&self) -> f64 {
+        self.selection.iter().sum()
+    }
+
+    /// Total interaction effect.
+    pub fn total_interaction(&self) -> f64 {
+        self.interaction.iter().sum()
+    }
+
+    /// Link the attributions of consecutive periods into the attribution of
+    /// the whole horizon, with Carino's logarithmic smoothing.
+    ///
+    /// Returns compound over periods, so the effects of the periods do not
+    /// sum to the active return of the horizon. Carino scales the effects of
+    /// period $t$ by $k_t / k$, with $k_t = \ln(1 + R_t) - \ln(1 + B_t)$ over
+    /// $R_t - B_t$ (and $k$ the same over the horizon), so that they do.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no periods, or they have different
+    /// numbers of segments.
+    pub fn link(periods: &[Attribution]) -> Result<Attribution, RustQuantError> {
+        let first = periods.first().ok_or(RustQuantError::UnequalLength)?;
+        let n = first.allocation.len();
+
+        if periods.iter().any(|period| period.allocation.len() != n) {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let portfolio_return = periods.iter().fold(1.0, |growth, period| {
+            growth * (1.0 + period.portfolio_return)
+        }) - 1.0;
+        let benchmark_return = periods.iter().fold(1.0, |growth, period| {
+            growth * (1.0 + period.benchmark_return)
+        }) - 1.0;
+
+        let k = carino_coefficient(portfolio_return, benchmark_return);
+
+        let mut linked = Attribution {
+            portfolio_return,
+            benchmark_return,
+            allocation: vec![0.0; n],
+            selection: vec![0.0; n],
+            interaction: vec![0.0; n],
+        };
+
+        for period in periods {
+            let scale = carino_coefficient(period.portfolio_return, period.benchmark_return) / k;
+
+            for i in 0..n {
+                linked.allocation[i] += scale * period.allocation[i];
+                linked.selection[i] += scale * period.selection[i];
+                linked.interaction[i] += scale * period.interaction[i];
+            }
+        }
+
+        Ok(linked)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Time-weighted return over the horizon, from the values of the portfolio
+/// at the end of each period (starting with the initial value), and the
+/// external cash flows into the portfolio (negative for withdrawals) at the
+/// end of each period, included in the value.
+///
+/// The return of period $i$ is $(V_i - C_i) / V_{i-1} - 1$, and the
+/// returns of the periods are compounded.
+///
+/// # Errors
+///
+/// Returns an error if there is not one more value than cash flows, or a
+/// value is not positive.
+pub fn time_weighted_return(values: &[f64], flows: &[f64]) -> Result<f64, RustQuantError> {
+    if values.len() != flows.len() + 1 {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    if values[..flows.len()].iter().any(|value| *value <= 0.0) {
+        return Err(RustQuantError::InvalidArgument(
+            "Portfolio values must be positive.".to_string(),
+        ));
+    }
+
+    let growth = values
+        .windows(2)
+        .zip(flows)
+        .map(|(values, flow)| (values[1] - flow) / values[0])
+        .product::<f64>();
+
+    Ok(growth - 1.0)
+}
+
+/// Money-weighted return: the annualised internal rate of return of the
+/// dated cash flows into the portfolio (the first being the initial
+/// investment, and negative for withdrawals) and its final value, with the
+/// Actual/365 Fixed day count.
+///
+/// # Errors
+///
+/// Returns an error if there are no cash flows, a cash flow is after the
+/// final date, or there is no rate of return (e.g. the cash flows never
+/// change sign).
+pub fn money_weighted_return(
+    flows: &[(Date, f64)],
+    final_value: f64,
+    final_date: Date,
+) -> Result<f64, RustQuantError> {
+    let start = flows
+        .iter()
+        .map(|(date, _)| *date)
+        .min()
+        .ok_or(RustQuantError::UnequalLength)?;
+
+    if flows.iter().any(|(date, _)| *date > final_date) {
+        return Err(RustQuantError::InvalidArgument(
+            "Cash flows must not be after the final date.".to_string(),
+        ));
+    }
+
+    let convention = DayCountConvention::Actual_365_Fixed;
+    let horizon = convention.day_count_factor(start, final_date);
+    let times: Vec<(f64, f64)> = flows
+        .iter()
+        .map(|(date, flow)| (convention.day_count_factor(start, *date), *flow))
+        .collect();
+
+    // Value of the cash flows and the final value at the final date.
+    let future_value = |rate: f64| {
+        let growth = 1.0 + rate;
+
+        times
+            .iter()
+            .map(|(time, flow)| flow * growth.powf(horizon - time))
+            .sum::<f64>()
+            - final_value
+    };
+
+    find_root(
+        future_value,
+        0.0,
+        RootfinderData::new(1e-12, 1e-3, -0.999_999, 1e6, true),
+    )
+}
+
+/// Tracking error: the standard deviation of the active returns (the
+/// portfolio returns minus the benchmark returns) per period, annualised
+/// with the number of periods per year (e.g. 12 for monthly returns).
+///
+/// # Errors
+///
+/// Returns an error if the lengths differ or there are fewer than two
+/// periods.
+pub fn tracking_error(
+    portfolio_returns: &[f64],
+    benchmark_returns: &[f64],
+    periods_per_year: f64,
+) -> Result<f64, RustQuantError> {
+    let (_, deviation) = active_moments(portfolio_returns, benchmark_returns)?;
+
+    Ok(deviation * periods_per_year.sqrt())
+}
+
+/// Information ratio: the annualised mean active return per unit of
+/// annualised tracking error.
+///
+/// # Errors
+///
+/// Returns an error if the lengths differ, there are fewer than two
+/// periods, or the tracking error is zero.
+pub fn information_ratio(
+    portfolio_returns: &[f64],
+    benchmark_returns: &[f64],
+    periods_per_year: f64,
+) -> Result<f64, RustQuantError> {
+    let (mean, deviation) = active_moments(portfolio_returns, benchmark_returns)?;
+
+    if deviation == 0.0 {
+        return Err(RustQuantError::ComputationError(
+            "The tracking error is zero.".to_string(),
+        ));
+    }
+
+    Ok(mean / deviation * periods_per_year.sqrt())
+}
+
+/// Mean and sample standard deviation of the active returns.
+fn active_moments(
+    portfolio_returns: &[f64],
+    benchmark_returns: &[f64],
+) -> Result<(f64, f64), RustQuantError> {
+    if portfolio_returns.len() != benchmark_returns.len() || portfolio_returns.len() < 2 {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    let active: Vec<f64> = portfolio_returns
+        .iter()
+        .zip(benchmark_returns)
+        .map(|(portfolio, benchmark)| portfolio - benchmark)
+        .collect();
+
+    let n = active.len() as f64;
+    let mean = active.iter().sum::<f64>() / n;
+    let variance = active.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    Ok((mean, variance.sqrt()))
+}
+
+/// Carino's coefficient $(\ln(1 + R) - \ln(1 + B)) / (R - B)$, and its
+/// limit $1 / (1 + R)$ when the returns are equal.
+fn carino_coefficient(portfolio_return: f64, benchmark_return: f64) -> f64 {
+    let active = portfolio_return - benchmark_return;
+
+    if active.abs() < 1e-12 {
+        1.0 / (1.0 + portfolio_return)
+    } else {
+        (portfolio_return.ln_1p() - benchmark_return.ln_1p()) / active
+    }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(x, y)| x * y).sum()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_performance {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Three sectors, e.g. equities, bonds, and cash.
+    fn period() -> AttributionPeriod {
+        AttributionPeriod::new(
+            vec![0.5, 0.3, 0.2],
+            vec![0.4, 0.4, 0.2],
+            vec![0.06, 0.02, 0.01],
+            vec![0.05, 0.025, 0.01],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_brinson_fachler() {
+        let attribution = period().brinson_fachler();
+
+        // R = 0.038, B = 0.032.
+        assert_approx_equal!(attribution.portfolio_return, 0.038, 1e-12);
+        assert_approx_equal!(attribution.benchmark_return, 0.032, 1e-12);
+
+        // Over-weighting equities, which beat the benchmark.
+        assert_approx_equal!(attribution.allocation[0], 0.1 * (0.05 - 0.032), 1e-12);
+        assert_approx_equal!(attribution.allocation[1], -0.1 * (0.025 - 0.032), 1e-12);
+        assert_approx_equal!(attribution.selection[0], 0.4 * 0.01, 1e-12);
+        assert_approx_equal!(attribution.interaction[1], -0.1 * -0.005, 1e-12);
+
+        let total = attribution.total_allocation()
+            + attribution.total_selection()
+            + attribution.total_interaction();
+
+        assert_approx_equal!(total, attribution.active_return(), 1e-12);
+    }
+
+    #[test]
+    fn test_linked_attribution() {
+        let second = AttributionPeriod::new(
+            vec![0.4, 0.4, 0.2],
+            vec![0.5, 0.3, 0.2],
+            vec![-0.03, 0.01, 0.01],
+            vec![-0.02, 0.015, 0.01],
+        )
+        .unwrap();
+
+        let periods = [period().brinson_fachler(), second.brinson_fachler()];
+        let linked = Attribution::link(&periods).unwrap();
+
+        assert_approx_equal!(
+            linked.portfolio_return,
+            (1.0 + periods[0].portfolio_return) * (1.0 + periods[1].portfolio_return) - 1.0,
+            1e-12
+        );
+
+        let total =
+            linked.total_allocation() + linked.total_selection() + linked.total_interaction();
+
+        assert_approx_equal!(total, linked.active_return(), 1e-12);
+        assert!(Attribution::link(&[]).is_err());
+    }
+
+    #[test]
+    fn test_time_weighted_return() {
+        // +10%, then a deposit of 50, then -5%.
+        let values = [100.0, 160.0, 152.0];
+        let flows = [50.0, 0.0];
+
+        let twr = time_weighted_return(&values, &flows).unwrap();
+
+        assert_approx_equal!(twr, 1.1 * 0.95 - 1.0, 1e-12);
+        assert!(time_weighted_return(&values, &[0.0]).is_err());
+    }
+
+    #[test]
+    fn test_money_weighted_return() {
+        // Without intermediate cash flows, the money-weighted return is the
+        // annualised return.
+        let rate = money_weighted_return(
+            &[(date!(2023 - 01 - 01), 100.0)],
+            110.0,
+            date!(2024 - 01 - 01),
+        )
+        .unwrap();
+
+        assert_approx_equal!(rate, 0.1, 1e-10);
+
+        // A deposit before a loss makes the money-weighted return worse than
+        // the time-weighted return.
+        let flows = [
+            (date!(2023 - 01 - 01), 100.0),
+            (date!(2023 - 07 - 01), 100.0),
+        ];
+        let mwr = money_weighted_return(&flows, 190.0, date!(2024 - 01 - 01)).unwrap();
+
+        assert!(mwr < 0.0);
+
+        let t1 = 181.0 / 365.0;
+        let t2 = 184.0 / 365.0;
+        assert_approx_equal!(
+            100.0 * (1.0 + mwr).powf(t1 + t2) + 100.0 * (1.0 + mwr).powf(t2),
+            190.0,
+            1e-8
+        );
+
+        assert!(money_weighted_return(&[], 100.0, date!(2024 - 01 - 01)).is_err());
+    }
+
+    #[test]
+    fn test_tracking_error_and_information_ratio() {
+        let portfolio = [0.02, -0.01, 0.03, 0.01];
+        let benchmark = [0.01, -0.01, 0.02, 0.02];
+
+        // Active returns 0.01, 0.0, 0.01, -0.01: mean 0.0025, variance
+        // 0.000275 / 3.
+        let deviation = (0.000_275_f64 / 3.0).sqrt();
+
+        assert_approx_equal!(
+            tracking_error(&portfolio, &benchmark, 12.0).unwrap(),
+            deviation * 12.0_f64.sqrt(),
+            1e-12
+        );
+        assert_approx_equal!(
+            information_ratio(&portfolio, &benchmark, 12.0).unwrap(),
+            0.0025 / deviation * 12.0_f64.sqrt(),
+            1e-10
+        );
+
+        assert!(tracking_error(&portfolio, &benchmark[..3], 12.0).is_err());
+        assert!(information_ratio(&portfolio, &portfolio, 12.0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_attribution_period() {
+        assert!(
+            AttributionPeriod::new(vec![0.5, 0.5], vec![1.0], vec![0.0; 2], vec![0.0; 2]).is_err()
+        );
+        assert!(
+            AttributionPeriod::new(vec![0.5, 0.4], vec![0.5, 0.5], vec![0.0; 2], vec![0.0; 2])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,591 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Rebalancing a portfolio to target weights.
+//!
+//! The [`Rebalancer`] computes the orders that move the positions of a
+//! portfolio (and its cash) towards target weights of its total value, in
+//! whole units at the current prices, and their expected transaction costs.
+//!
+//! Orders are then adjusted for the constraints, in order:
+//!
+//! 1. Trades smaller than the minimum trade value are not worth their
+//!    costs, and are dropped.
+//! 2. If the turnover (the value traded over the value of the portfolio)
+//!    exceeds the cap, all trades are scaled down to meet it, so the
+//!    portfolio moves part of the way towards the targets.
+//! 3. Purchases are scaled down if the cash and the proceeds of the sales
+//!    do not cover them and the transaction costs.
+//!
+//! ```
+//! # use RustQuant::portfolios::*;
+//! # use RustQuant::time::today;
+//! # use std::collections::HashMap;
+//! let positions = HashMap::from([
+//!     ("AAA".to_string(), Position::new(Security::new("AAA", 10.0, today()), 700, 9.0, 10.0, None)),
+//!     ("BBB".to_string(), Position::new(Security::new("BBB", 20.0, today()), 150, 25.0, 20.0, None)),
+//! ]);
+//! let portfolio = Portfolio::new(positions);
+//!
+//! let targets = HashMap::from([("AAA".to_string(), 0.5), ("BBB".to_string(), 0.5)]);
+//! let rebalancer = Rebalancer::new(TransactionCostModel::new(1.0, 0.001));
+//! let plan = rebalancer.rebalance(&portfolio, 0.0, &targets).unwrap();
+//!
+//! // Sell 200 AAA to buy 99 BBB, keeping cash for the costs.
+//! assert_eq!(plan.orders[0].side, TradeSide::Sell);
+//! assert_eq!(plan.orders[0].quantity, 200);
+//! assert_eq!(plan.orders[1].quantity, 99);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::Portfolio;
+use std::collections::HashMap;
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::Instrument;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Transaction costs of a trade of value $N$:
+///
+/// $$
+/// \text{cost} = c_0 + c_1 |N| + c_2 |N|^{\beta}
+/// $$
+///
+/// a fixed fee (commission) per order, a proportional cost (half the
+/// bid-ask spread, and fees in basis points), and the market impact. The
+/// square-root law of market impact is $\beta = 3/2$ and
+/// $c_2 = \eta \sigma / \sqrt{\text{ADV}}$, for the daily volatility
+/// $\sigma$ and average daily traded value ADV of the asset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransactionCostModel {
+    /// Fixed cost per order.
+    pub fixed: f64,
+
+    /// Cost per unit of value traded.
+    pub proportional: f64,
+
+    /// Market impact coefficient.
+    pub impact: f64,
+
+    /// Market impact exponent.
+    pub impact_exponent: f64,
+}
+
+/// Side of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    /// Buy.
+    Buy,
+
+    /// Sell.
+    Sell,
+}
+
+/// Order to rebalance a position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalanceOrder {
+    /// Name of the position.
+    pub name: String,
+
+    /// Side of the order.
+    pub side: TradeSide,
+
+    /// Number of units to trade.
+    pub quantity: u64,
+
+    /// Price per unit.
+    pub price: f64,
+
+    /// Value traded.
+    pub notional: f64,
+
+    /// Expected transaction cost.
+    pub cost: f64,
+}
+
+/// Orders to rebalance a portfolio, and their expected cost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalancePlan {
+    /// Orders: the sales, then the purchases, by name.
+    pub orders: Vec<RebalanceOrder>,
+
+    /// Expected transaction cost of the orders.
+    pub expected_cost: f64,
+
+    /// Value traded over the value of the portfolio (including cash).
+    pub turnover: f64,
+
+    /// Cash after the orders and their costs.
+    pub cash: f64,
+}
+
+/// Rebalancer of portfolios to target weights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rebalancer {
+    /// Transaction cost model.
+    pub cost_model: TransactionCostModel,
+
+    /// Minimum value of an order.
+    pub min_trade_value: f64,
+
+    /// Maximum turnover (e.g. 0.2 to trade at most 20% of the value).
+    pub max_turnover: Option<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TransactionCostModel {
+    /// Create a new transaction cost model, without market impact.
+    #[must_use]
+    pub fn new(fixed: f64, proportional: f64) -> Self {
+        Self {
+            fixed,
+            proportional,
+            impact: 0.0,
+            impact_exponent: 1.5,
+        }
+    }
+
+    /// Set the market impact coefficient and exponent.
+    #[must_use]
+    pub fn with_market_impact(mut self, impact: f64, exponent: f64) -> Self {
+        self.impact = impact;
+        self.impact_exponent = exponent;
+        self
+    }
+
+    /// Expected cost of a trade of the given value (zero for no trade).
+    #[must_use]
+    pub fn cost(&self, notional: f64) -> f64 {
+        let notional = notional.abs();
+
+        if notional == 0.0 {
+            return 0.0;
+        }
+
+        self.fixed
+            + self.proportional * notional
+            + self.impact * notional.powf(self.impact_exponent)
+    }
+}
+
+impl RebalancePlan {
+    /// Value bought.
+    #[must_use]
+    pub fn bought(&self) -> f64 {
+        self.notional(TradeSide::Buy)
+    }
+
+    /// Value sold.
+    #[must_use]
+    pub fn sold(&self) -> f64 {
+        self.notional(TradeSide::Sell)
+    }
+
+    fn notional(&self, side: TradeSide) -> f64 {
+        self.orders
+            .iter()
+            .filter(|order| order.side == side)
+            .map(|order| order.notional)
+            .sum()
+    }
+}
+
+impl Rebalancer {
+    /// Create a new rebalancer, without constraints.
+    #[must_use]
+    pub fn new(cost_model: TransactionCostModel) -> Self {
+        Self {
+            cost_model,
+            min_trade_value: 0.0,
+            max_turnover: None,
+        }
+    }
+
+    /// Set the minimum value of an order.
+    #[must_use]
+    pub fn with_min_trade_value(mut self, min_trade_value: f64) -> Self {
+        self.min_trade_value = min_trade_value;
+        self
+    }
+
+    /// Set the maximum turnover.
+    #[must_use]
+    pub fn with_max_turnover(mut self, max_turnover: f64) -> Self {
+        self.max_turnover = Some(max_turnover);
+        self
+    }
+
+    /// Orders to rebalance the portfolio, with the given cash, to the target
+    /// weights of its total value (the rest is held in cash). Positions
+    /// without a target weight are sold.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a weight is negative, the weights sum to more
+    /// than one, a target is not a position of the portfolio, a price is not
+    /// positive, or the maximum turnover is negative.
+    pub fn rebalance<I>(
+        &self,
+        portfolio: &Portfolio<I>,
+        cash: f64,
+        targets: &HashMap<String, f64>,
+    ) -> Result<RebalancePlan, RustQuantError>
+    where
+        I: Instrument,
+    {
+        self.check(portfolio, targets)?;
+
+        let total = portfolio.value() + cash;
+
+        let mut names: Vec<&String> = portfolio.positions.keys().collect();
+        names.sort();
+
+        // Signed number of units to trade (positive to buy).
+        let mut trades: Vec<(&String, f64, i64)> = names
+            .into_iter()
+            .map(|name| {
+                let position = &portfolio.positions[name];
+                let target = targets.get(name).copied().unwrap_or(0.0) * total;
+                let units = ((target - position.value()) / position.current_price).round();
+
+                // Positions cannot go short.
+                let units = (units as i64).max(-(position.quantity as i64));
+
+                (name, position.current_price, units)
+            })
+            .collect();
+
+        self.drop_small_trades(&mut trades);
+
+        if let Some(max_turnover) = self.max_turnover {
+            let traded = traded_value(&trades, |_| true);
+
+            if traded > max_turnover * total {
+                let scale = max_turnover * total / traded;
+
+                for (_, _, units) in &mut trades {
+                    *units = (*units as f64 * scale).trunc() as i64;
+                }
+
+                self.drop_small_trades(&mut trades);
+            }
+        }
+
+        // Fund the purchases and costs with the cash and the proceeds.
+        let sold = traded_value(&trades, |units| units < 0);
+        let bought = traded_value(&trades, |units| units > 0);
+        let costs = self.costs(&trades);
+
+        if bought > 0.0 && bought + costs > cash + sold {
+            let sale_costs: f64 = trades
+                .iter()
+                .filter(|(_, _, units)| *units < 0)
+                .map(|(_, price, units)| self.cost_model.cost(price * *units as f64))
+                .sum();
+            let purchase_costs = costs - sale_costs;
+            let scale = ((cash + sold - sale_costs - purchase_costs) / bought).max(0.0);
+
+            for (_, _, units) in trades.iter_mut().filter(|(_, _, units)| *units > 0) {
+                *units = (*units as f64 * scale).floor() as i64;
+            }
+
+            self.drop_small_trades(&mut trades);
+        }
+
+        let mut orders: Vec<RebalanceOrder> = trades
+            .iter()
+            .map(|(name, price, units)| {
+                let notional = price * units.unsigned_abs() as f64;
+
+                RebalanceOrder {
+                    name: (*name).clone(),
+                    side: if *units > 0 {
+                        TradeSide::Buy
+                    } else {
+                        TradeSide::Sell
+                    },
+                    quantity: units.unsigned_abs(),
+                    price: *price,
+                    notional,
+                    cost: self.cost_model.cost(notional),
+                }
+            })
+            .collect();
+
+        orders.sort_by_key(|order| order.side == TradeSide::Buy);
+
+        let expected_cost = orders.iter().map(|order| order.cost).sum::<f64>();
+        let traded = orders.iter().map(|order| order.notional).sum::<f64>();
+        let net = orders
+            .iter()
+            .map(|order| match order.side {
+                TradeSide::Buy => -order.notional,
+                TradeSide::Sell => order.notional,
+            })
+            .sum::<f64>();
+
+        Ok(RebalancePlan {
+            orders,
+            expected_cost,
+            turnover: if total > 0.0 { traded / total } else { 0.0 },
+            cash: cash + net - expected_cost,
+        })
+    }
+
+    /// Checks the targets and constraints.
+    fn check<I>(
+        &self,
+        portfolio: &Portfolio<I>,
+        targets: &HashMap<String, f64>,
+    ) -> Result<(), RustQuantError>
+    where
+        I: Instrument,
+    {
+        if targets.values().any(|weight| *weight < 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Target weights must be non-negative.".to_string(),
+            ));
+        }
+
+        if targets.values().sum::<f64>() > 1.0 + 1e-8 {
+            return Err(RustQuantError::InvalidArgument(
+                "Target weights must not sum to more than one.".to_string(),
+            ));
+        }
+
+        if let Some(name) = targets
+            .keys()
+            .find(|name| !portfolio.positions.contains_key(*name))
+        {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "No position (and price) for the target {name}."
+            )));
+        }
+
+        if portfolio
+            .positions
+            .values()
+            .any(|position| position.current_price.is_nan() || position.current_price <= 0.0)
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Prices must be positive.".to_string(),
+            ));
+        }
+
+        if self.max_turnover.is_some_and(|turnover| turnover < 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "The maximum turnover must be non-negative.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Drops the trades below the minimum trade value, and the empty ones.
+    fn drop_small_trades(&self, trades: &mut Vec<(&String, f64, i64)>) {
+        trades.retain(|(_, price, units)| {
+            *units != 0 && price * units.unsigned_abs() as f64 >= self.min_trade_value
+        });
+    }
+
+    /// Transaction costs of the trades.
+    fn costs(&self, trades: &[(&String, f64, i64)]) -> f64 {
+        trades
+            .iter()
+            .map(|(_, price, units)| self.cost_model.cost(price * *units as f64))
+            .sum()
+    }
+}
+
+/// Value of the trades whose signed number of units satisfies `filter`.
+fn traded_value<F>(trades: &[(&String, f64, i64)], filter: F) -> f64
+where
+    F: Fn(i64) -> bool,
+{
+    trades
+        .iter()
+        .filter(|(_, _, units)| filter(*units))
+        .map(|(_, price, units)| price * units.unsigned_abs() as f64)
+        .sum()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rebalance {
+    use super::*;
+    use crate::{Position, Security};
+    use RustQuant_time::today;
+    use RustQuant_utils::assert_approx_equal;
+
+    // 50,000 in three stocks, and 10,000 in cash.
+    fn portfolio() -> Portfolio<Security> {
+        let date = today();
+        let position = |symbol: &str, quantity: u64, price: f64| {
+            (
+                symbol.to_string(),
+                Position::new(
+                    Security::new(symbol, price, date),
+                    quantity,
+                    price,
+                    price,
+                    None,
+                ),
+            )
+        };
+
+        Portfolio::new(HashMap::from([
+            position("AAA", 1000, 20.0),
+            position("BBB", 200, 100.0),
+            position("CCC", 100, 100.0),
+        ]))
+    }
+
+    fn targets() -> HashMap<String, f64> {
+        HashMap::from([
+            ("AAA".to_string(), 0.25),
+            ("BBB".to_string(), 0.25),
+            ("CCC".to_string(), 0.4),
+        ])
+    }
+
+    #[test]
+    fn test_transaction_cost_model() {
+        let model = TransactionCostModel::new(5.0, 0.001).with_market_impact(1e-4, 1.5);
+
+        assert_approx_equal!(model.cost(0.0), 0.0, 1e-12);
+        assert_approx_equal!(model.cost(-10_000.0), 5.0 + 10.0 + 100.0, 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_without_costs() {
+        let rebalancer = Rebalancer::new(TransactionCostModel::new(0.0, 0.0));
+        let plan = rebalancer
+            .rebalance(&portfolio(), 10_000.0, &targets())
+            .unwrap();
+
+        // Targets of 15,000, 15,000 and 24,000, out of 60,000.
+        let orders: Vec<(&str, TradeSide, u64)> = plan
+            .orders
+            .iter()
+            .map(|order| (order.name.as_str(), order.side, order.quantity))
+            .collect();
+
+        assert_eq!(
+            orders,
+            vec![
+                ("AAA", TradeSide::Sell, 250),
+                ("BBB", TradeSide::Sell, 50),
+                ("CCC", TradeSide::Buy, 140),
+            ]
+        );
+        assert_approx_equal!(plan.turnover, 24_000.0 / 60_000.0, 1e-12);
+        assert_approx_equal!(plan.cash, 6_000.0, 1e-9);
+        assert_approx_equal!(plan.expected_cost, 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_rebalance_costs_and_funding() {
+        let model = TransactionCostModel::new(10.0, 0.002);
+        let rebalancer = Rebalancer::new(model);
+
+        // Everything in the stocks, so the costs must be funded by buying
+        // less.
+        let targets = HashMap::from([
+            ("AAA".to_string(), 0.3),
+            ("BBB".to_string(), 0.3),
+            ("CCC".to_string(), 0.4),
+        ]);
+        let plan = rebalancer
+            .rebalance(&portfolio(), 10_000.0, &targets)
+            .unwrap();
+
+        let costs: f64 = plan
+            .orders
+            .iter()
+            .map(|order| model.cost(order.notional))
+            .sum();
+
+        assert_approx_equal!(plan.expected_cost, costs, 1e-9);
+        assert!(plan.cash >= 0.0);
+        assert_approx_equal!(
+            plan.cash,
+            10_000.0 + plan.sold() - plan.bought() - plan.expected_cost,
+            1e-9
+        );
+    }
+
+    #[test]
+    fn test_rebalance_constraints() {
+        let model = TransactionCostModel::new(0.0, 0.0);
+
+        // The 5,000 sales of AAA and BBB are below the minimum trade value.
+        let plan = Rebalancer::new(model)
+            .with_min_trade_value(6_000.0)
+            .rebalance(&portfolio(), 10_000.0, &targets())
+            .unwrap();
+
+        assert_eq!(plan.orders.len(), 1);
+        assert_eq!(plan.orders[0].name, "CCC");
+
+        // The turnover of 40% is capped at 20%.
+        let plan = Rebalancer::new(model)
+            .with_max_turnover(0.2)
+            .rebalance(&portfolio(), 10_000.0, &targets())
+            .unwrap();
+
+        assert!(plan.turnover <= 0.2);
+        assert!(plan.turnover > 0.19);
+        assert_eq!(plan.orders[0].quantity, 125);
+    }
+
+    #[test]
+    fn test_rebalance_sells_unlisted_positions() {
+        let targets = HashMap::from([("AAA".to_string(), 0.5)]);
+        let plan = Rebalancer::new(TransactionCostModel::new(0.0, 0.0))
+            .rebalance(&portfolio(), 0.0, &targets)
+            .unwrap();
+
+        let sells: Vec<(&str, u64)> = plan
+            .orders
+            .iter()
+            .filter(|order| order.side == TradeSide::Sell)
+            .map(|order| (order.name.as_str(), order.quantity))
+            .collect();
+
+        assert_eq!(sells, vec![("BBB", 200), ("CCC", 100)]);
+    }
+
+    #[test]
+    fn test_rebalance_invalid_inputs() {
+        let rebalancer = Rebalancer::new(TransactionCostModel::new(0.0, 0.0));
+
+        let too_much = HashMap::from([("AAA".to_string(), 0.6), ("BBB".to_string(), 0.6)]);
+        let negative = HashMap::from([("AAA".to_string(), -0.1)]);
+        let unknown = HashMap::from([("ZZZ".to_string(), 0.1)]);
+
+        assert!(rebalancer.rebalance(&portfolio(), 0.0, &too_much).is_err());
+        assert!(rebalancer.rebalance(&portfolio(), 0.0, &negative).is_err());
+        assert!(rebalancer.rebalance(&portfolio(), 0.0, &unknown).is_err());
+        assert!(rebalancer
+            .with_max_turnover(-0.1)
+            .rebalance(&portfolio(), 0.0, &targets())
+            .is_err());
+    }
+}
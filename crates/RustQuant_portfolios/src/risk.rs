@@ -0,0 +1,337 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Portfolio Value-at-Risk and expected shortfall, and their decomposition
+//! into the contributions of the positions.
+//!
+//! The portfolio is described by its exposures $x_i$ (the values of the
+//! positions), so its profit and loss is $x^T r$ for asset returns $r$.
+//! VaR and ES are positive losses in the units of the exposures, with the
+//! conventions of [`RustQuant_math::value_at_risk`].
+//!
+//! The marginal VaR of a position is $\partial \text{VaR} / \partial x_i$,
+//! and its component VaR is $x_i \, \partial \text{VaR} / \partial x_i$.
+//! Since VaR is homogeneous of degree one in the exposures, the components
+//! sum to the VaR (Euler allocation), and likewise for ES.
+//!
+//! - Parametric (delta-normal): with $\sigma_p = \sqrt{x^T \Sigma x}$,
+//!   $\text{VaR} = z_\alpha \sigma_p - \mu^T x$ and the marginal VaR is
+//!   $z_\alpha (\Sigma x)_i / \sigma_p - \mu_i$.
+//! - Historical: the losses of the scenarios are sorted, and the
+//!   contribution of a position is its loss in the VaR scenario (for VaR),
+//!   or its average loss over the tail scenarios (for ES).
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::Portfolio;
+use nalgebra::{DMatrix, DVector};
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::Instrument;
+use RustQuant_math::{Distribution, Gaussian};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Portfolio VaR and ES, and the contributions of the positions.
+#[derive(Debug, Clone)]
+pub struct RiskDecomposition {
+    /// Value-at-Risk of the portfolio.
+    pub value_at_risk: f64,
+
+    /// Expected shortfall of the portfolio.
+    pub expected_shortfall: f64,
+
+    /// Marginal VaR of each position: the change of the VaR per unit of
+    /// exposure (`NaN` for historical VaR of a position without exposure).
+    pub marginal_var: DVector<f64>,
+
+    /// Component VaR of each position, summing to the VaR.
+    pub component_var: DVector<f64>,
+
+    /// Component ES of each position, summing to the ES.
+    pub component_es: DVector<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RiskDecomposition {
+    /// Share of the VaR contributed by each position.
+    pub fn var_contributions(&self) -> DVector<f64> {
+        &self.component_var / self.value_at_risk
+    }
+
+    /// Share of the ES contributed by each position.
+    pub fn es_contributions(&self) -> DVector<f64> {
+        &self.component_es / self.expected_shortfall
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: Instrument,
+{
+    /// Names of the positions, sorted, and their values in the same order:
+    /// the order of the assets for [`parametric_risk`] and
+    /// [`historical_risk`].
+    pub fn exposures(&self) -> (Vec<String>, DVector<f64>) {
+        let mut names: Vec<String> = self.positions.keys().cloned().collect();
+        names.sort();
+
+        let values = DVector::from_iterator(
+            names.len(),
+            names.iter().map(|name| self.positions[name].value()),
+        );
+
+        (names, values)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parametric (delta-normal) VaR and ES of a portfolio, at `confidence`
+/// (e.g. 0.99), for normally distributed asset returns with the given
+/// covariance matrix and mean (zero if `None`) over the horizon.
+///
+/// # Errors
+///
+/// Returns an error if the confidence is not in (0, 1), the dimensions do
+/// not match, or the portfolio has no variance.
+pub fn parametric_risk(
+    exposures: &DVector<f64>,
+    covariance: &DMatrix<f64>,
+    mean: Option<&DVector<f64>>,
+    confidence: f64,
+) -> Result<RiskDecomposition, RustQuantError> {
+    check_confidence(confidence)?;
+
+    let n = exposures.len();
+    let zeros = DVector::zeros(n);
+    let mean = mean.unwrap_or(&zeros);
+
+    if n == 0 || covariance.shape() != (n, n) || mean.len() != n {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    let covariance_exposures = covariance * exposures;
+    let volatility = exposures.dot(&covariance_exposures).sqrt();
+
+    if volatility.is_nan() || volatility <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(
+            "The portfolio has no variance.".to_string(),
+        ));
+    }
+
+    let normal = Gaussian::default();
+    let z = -normal.inv_cdf(1.0 - confidence);
+    let tail = normal.pdf(z) / (1.0 - confidence);
+
+    // Sensitivity of the volatility to each exposure.
+    let gradient = covariance_exposures / volatility;
+
+    let marginal_var = &gradient * z - mean;
+    let marginal_es = &gradient * tail - mean;
+
+    Ok(RiskDecomposition {
+        value_at_risk: z * volatility - mean.dot(exposures),
+        expected_shortfall: tail * volatility - mean.dot(exposures),
+        component_var: marginal_var.component_mul(exposures),
+        component_es: marginal_es.component_mul(exposures),
+        marginal_var,
+    })
+}
+
+/// Historical VaR and ES of a portfolio, at `confidence` (e.g. 0.99), from
+/// scenarios of asset returns: one row per scenario (e.g. a day), and one
+/// column per asset.
+///
+/// # Errors
+///
+/// Returns an error if the confidence is not in (0, 1), or there are no
+/// scenarios or the dimensions do not match.
+pub fn historical_risk(
+    exposures: &DVector<f64>,
+    returns: &DMatrix<f64>,
+    confidence: f64,
+) -> Result<RiskDecomposition, RustQuantError> {
+    check_confidence(confidence)?;
+
+    let n = exposures.len();
+
+    if n == 0 || returns.nrows() == 0 || returns.ncols() != n {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    // Loss of each position in each scenario.
+    let losses = DMatrix::from_fn(returns.nrows(), n, |t, i| -exposures[i] * returns[(t, i)]);
+    let totals: Vec<f64> = losses.row_iter().map(|row| row.sum()).collect();
+
+    let mut order: Vec<usize> = (0..totals.len()).collect();
+    order.sort_by(|a, b| totals[*a].total_cmp(&totals[*b]));
+
+    let index = ((confidence * order.len() as f64).ceil() as usize).clamp(1, order.len()) - 1;
+    let tail = &order[index..];
+
+    let component_var = losses.row(order[index]).transpose();
+    let component_es = tail
+        .iter()
+        .fold(DVector::zeros(n), |sum, t| sum + losses.row(*t).transpose())
+        / tail.len() as f64;
+
+    Ok(RiskDecomposition {
+        value_at_risk: totals[order[index]],
+        expected_shortfall: tail.iter().map(|t| totals[*t]).sum::<f64>() / tail.len() as f64,
+        marginal_var: component_var.component_div(exposures),
+        component_var,
+        component_es,
+    })
+}
+
+/// Checks that the confidence level is in (0, 1).
+fn check_confidence(confidence: f64) -> Result<(), RustQuantError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Confidence level must be in (0, 1), got {confidence}."
+        )));
+    }
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_risk {
+    use super::*;
+    use crate::{Position, Security};
+    use nalgebra::{dmatrix, dvector};
+    use std::collections::HashMap;
+    use RustQuant_math::{expected_shortfall, value_at_risk, ValueAtRiskMethod};
+    use RustQuant_time::today;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn covariance() -> DMatrix<f64> {
+        dmatrix![
+            0.000400, 0.000120, -0.000050;
+            0.000120, 0.000900, 0.000100;
+            -0.000050, 0.000100, 0.000225
+        ]
+    }
+
+    #[test]
+    fn test_parametric_single_asset() {
+        // 2.326... standard deviations of a 1,000,000 position with 2% daily
+        // volatility.
+        let risk = parametric_risk(&dvector![1e6], &dmatrix![0.0004], None, 0.99).unwrap();
+
+        assert_approx_equal!(risk.value_at_risk, 2.326_347_874_040_841 * 0.02 * 1e6, 1e-4);
+        assert_approx_equal!(
+            risk.expected_shortfall,
+            2.665_214_220_345_808 * 0.02 * 1e6,
+            1e-3
+        );
+        assert_approx_equal!(risk.component_var[0], risk.value_at_risk, 1e-8);
+    }
+
+    #[test]
+    fn test_parametric_euler_allocation() {
+        let exposures = dvector![1e6, -3e5, 5e5];
+        let mean = dvector![0.0005, 0.0002, 0.0001];
+        let risk = parametric_risk(&exposures, &covariance(), Some(&mean), 0.975).unwrap();
+
+        assert_approx_equal!(risk.component_var.sum(), risk.value_at_risk, 1e-8);
+        assert_approx_equal!(risk.component_es.sum(), risk.expected_shortfall, 1e-8);
+        assert_approx_equal!(risk.var_contributions().sum(), 1.0, 1e-12);
+
+        // The marginal VaR is the derivative of the VaR.
+        for i in 0..3 {
+            let h = 1.0;
+            let mut bumped = exposures.clone();
+            bumped[i] += h;
+            let up = parametric_risk(&bumped, &covariance(), Some(&mean), 0.975).unwrap();
+            bumped[i] -= 2.0 * h;
+            let down = parametric_risk(&bumped, &covariance(), Some(&mean), 0.975).unwrap();
+
+            let derivative = (up.value_at_risk - down.value_at_risk) / (2.0 * h);
+            assert_approx_equal!(risk.marginal_var[i], derivative, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_historical_risk() {
+        // 100 scenarios of three assets.
+        let returns = DMatrix::from_fn(100, 3, |t, i| {
+            let phase = (t * (i + 3)) as f64;
+            0.02 * (0.37 * phase).sin() - 0.01 * (0.11 * phase).cos() * (i as f64 - 1.0)
+        });
+        let exposures = dvector![1e6, 2e5, -4e5];
+        let risk = historical_risk(&exposures, &returns, 0.95).unwrap();
+
+        // Same as the historical VaR and ES of the portfolio returns.
+        let portfolio_returns: Vec<f64> = (&returns * &exposures).iter().copied().collect();
+        let var = value_at_risk(&portfolio_returns, 0.95, ValueAtRiskMethod::Historical).unwrap();
+        let es =
+            expected_shortfall(&portfolio_returns, 0.95, ValueAtRiskMethod::Historical).unwrap();
+
+        assert_approx_equal!(risk.value_at_risk, var, 1e-8);
+        assert_approx_equal!(risk.expected_shortfall, es, 1e-8);
+        assert_approx_equal!(risk.component_var.sum(), risk.value_at_risk, 1e-8);
+        assert_approx_equal!(risk.component_es.sum(), risk.expected_shortfall, 1e-8);
+        assert_approx_equal!(risk.es_contributions().sum(), 1.0, 1e-12);
+        assert_approx_equal!(
+            risk.marginal_var[1] * exposures[1],
+            risk.component_var[1],
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_portfolio_exposures() {
+        let date = today();
+        let positions = HashMap::from([
+            (
+                "B".to_string(),
+                Position::new(Security::new("B", 50.0, date), 10, 40.0, 50.0, None),
+            ),
+            (
+                "A".to_string(),
+                Position::new(Security::new("A", 20.0, date), 100, 20.0, 20.0, None),
+            ),
+        ]);
+
+        let (names, exposures) = Portfolio::new(positions).exposures();
+
+        assert_eq!(names, vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(exposures, dvector![2000.0, 500.0]);
+
+        // The larger, more volatile position drives the risk.
+        let risk =
+            parametric_risk(&exposures, &dmatrix![0.0004, 0.0; 0.0, 0.0001], None, 0.99).unwrap();
+        assert!(risk.var_contributions()[0] > 0.9);
+    }
+
+    #[test]
+    fn test_invalid_risk_inputs() {
+        let exposures = dvector![1.0, 2.0];
+
+        assert!(parametric_risk(&exposures, &covariance(), None, 0.99).is_err());
+        assert!(parametric_risk(&exposures, &DMatrix::zeros(2, 2), None, 0.99).is_err());
+        assert!(parametric_risk(&exposures, &DMatrix::identity(2, 2), None, 1.0).is_err());
+        assert!(historical_risk(&exposures, &DMatrix::zeros(0, 2), 0.99).is_err());
+        assert!(historical_risk(&exposures, &DMatrix::zeros(10, 3), 0.99).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Position sizing for systematic strategies.
+//!
+//! Sizes are fractions of the capital (leverage): 0.5 invests half of the
+//! capital, 2.0 borrows as much again, and negative sizes are short.
+//!
+//! - Kelly: the fraction that maximises the expected log growth of the
+//!   capital. Estimation error in the edge makes full Kelly very
+//!   aggressive, so fractional Kelly (e.g. half) is common in practice.
+//! - Volatility targeting: the fraction whose volatility is the target.
+//! - Drawdown constraints: the fraction of Kelly that limits the
+//!   probability of a drawdown, and the Grossman-Zhou exposure that never
+//!   breaches a maximum drawdown.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Kelly fraction of a bet that wins `win_loss_ratio` times the stake with
+/// probability `win_probability`, and loses the stake otherwise:
+/// $f^* = p - (1 - p) / b$.
+///
+/// # Errors
+///
+/// Returns an error if the probability is not in [0, 1] or the ratio is
+/// not positive.
+pub fn kelly_fraction(win_probability: f64, win_loss_ratio: f64) -> Result<f64, RustQuantError> {
+    if !(0.0..=1.0).contains(&win_probability) {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Win probability must be in [0, 1], got {win_probability}."
+        )));
+    }
+
+    if win_loss_ratio.is_nan() || win_loss_ratio <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Win/loss ratio must be positive, got {win_loss_ratio}."
+        )));
+    }
+
+    Ok(win_probability - (1.0 - win_probability) / win_loss_ratio)
+}
+
+/// Fractional Kelly leverage of an asset with continuous returns:
+/// `fraction` times $f^* = \mu / \sigma^2$, for the expected excess return
+/// (the edge) and the variance of the returns, per the same period.
+///
+/// # Errors
+///
+/// Returns an error if the variance is not positive or the fraction is
+/// negative.
+pub fn fractional_kelly(
+    expected_excess_return: f64,
+    variance: f64,
+    fraction: f64,
+) -> Result<f64, RustQuantError> {
+    check_variance(variance)?;
+
+    if fraction.is_nan() || fraction < 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Kelly fraction must be non-negative, got {fraction}."
+        )));
+    }
+
+    Ok(fraction * expected_excess_return / variance)
+}
+
+/// Kelly weights of several assets: $f^* = \Sigma^{-1} \mu$, for the
+/// expected excess returns and the covariance matrix of the returns.
+///
+/// # Errors
+///
+/// Returns an error if the dimensions do not match, or the covariance
+/// matrix is not positive definite.
+pub fn kelly_weights(
+    expected_excess_returns: &DVector<f64>,
+    covariance: &DMatrix<f64>,
+) -> Result<DVector<f64>, RustQuantError> {
+    let n = expected_excess_returns.len();
+
+    if covariance.shape() != (n, n) {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    covariance
+        .clone()
+        .cholesky()
+        .map(|cholesky| cholesky.solve(expected_excess_returns))
+        .ok_or(RustQuantError::MatrixInversionFailed)
+}
+
+/// Leverage that targets a volatility, $\sigma_{target} / \sigma$, capped
+/// at the maximum leverage. Both volatilities are per the same period
+/// (e.g. annualised).
+///
+/// # Errors
+///
+/// Returns an error if a volatility is not positive or the maximum
+/// leverage is negative.
+pub fn volatility_target(
+    target_volatility: f64,
+    volatility: f64,
+    max_leverage: f64,
+) -> Result<f64, RustQuantError> {
+    if target_volatility.is_nan() || target_volatility <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Target volatility must be positive, got {target_volatility}."
+        )));
+    }
+
+    check_variance(volatility)?;
+
+    if max_leverage.is_nan() || max_leverage < 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Maximum leverage must be non-negative, got {max_leverage}."
+        )));
+    }
+
+    Ok((target_volatility / volatility).min(max_leverage))
+}
+
+/// Fraction of Kelly such that the capital falls `max_drawdown` (e.g. 0.2)
+/// below its running maximum with at most the given probability, ever.
+///
+/// In continuous time, betting a fraction $c$ of Kelly, the capital falls
+/// to $x$ times its maximum with probability $x^{2 / c - 1}$, so the
+/// fraction is $c = 2 / (1 + \ln p / \ln(1 - D))$, at most one (betting
+/// more than Kelly only adds risk).
+///
+/// # Errors
+///
+/// Returns an error if the drawdown or probability is not in (0, 1).
+pub fn drawdown_kelly_fraction(max_drawdown: f64, probability: f64) -> Result<f64, RustQuantError> {
+    for (name, value) in [("Drawdown", max_drawdown), ("Probability", probability)] {
+        if !(value > 0.0 && value < 1.0) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "{name} must be in (0, 1), got {value}."
+            )));
+        }
+    }
+
+    let exponent = probability.ln() / (1.0 - max_drawdown).ln();
+
+    Ok((2.0 / (1.0 + exponent)).min(1.0))
+}
+
+/// Grossman-Zhou exposure, which never lets the capital fall more than
+/// `max_drawdown` below its running maximum (`peak`): the Kelly leverage
+/// $\mu / \sigma^2$ applied to the cushion above the floor,
+/// $f^* (W - (1 - D) M) / W$.
+///
+/// # Errors
+///
+/// Returns an error if the variance is not positive, the drawdown is not in
+/// (0, 1), or the capital is not positive or above the peak.
+pub fn drawdown_constrained_exposure(
+    expected_excess_return: f64,
+    variance: f64,
+    max_drawdown: f64,
+    capital: f64,
+    peak: f64,
+) -> Result<f64, RustQuantError> {
+    check_variance(variance)?;
+
+    if !(max_drawdown > 0.0 && max_drawdown < 1.0) {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Drawdown must be in (0, 1), got {max_drawdown}."
+        )));
+    }
+
+    if capital.is_nan() || capital <= 0.0 || capital > peak {
+        return Err(RustQuantError::InvalidArgument(
+            "Capital must be positive and at most its peak.".to_string(),
+        ));
+    }
+
+    let cushion = (capital - (1.0 - max_drawdown) * peak).max(0.0) / capital;
+
+    Ok(expected_excess_return / variance * cushion)
+}
+
+/// Checks that a variance (or volatility) is positive.
+fn check_variance(variance: f64) -> Result<(), RustQuantError> {
+    if variance.is_nan() || variance <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Variance must be positive, got {variance}."
+        )));
+    }
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_sizing {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_kelly_fraction() {
+        // 60% chance of winning an even bet.
+        assert_approx_equal!(kelly_fraction(0.6, 1.0).unwrap(), 0.2, 1e-12);
+
+        // No edge, no bet.
+        assert_approx_equal!(kelly_fraction(0.25, 3.0).unwrap(), 0.0, 1e-12);
+
+        assert!(kelly_fraction(1.1, 1.0).is_err());
+        assert!(kelly_fraction(0.5, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_fractional_kelly() {
+        // 8% excess return and 20% volatility.
+        assert_approx_equal!(fractional_kelly(0.08, 0.04, 1.0).unwrap(), 2.0, 1e-12);
+        assert_approx_equal!(fractional_kelly(0.08, 0.04, 0.5).unwrap(), 1.0, 1e-12);
+
+        assert!(fractional_kelly(0.08, 0.0, 1.0).is_err());
+        assert!(fractional_kelly(0.08, 0.04, -0.5).is_err());
+    }
+
+    #[test]
+    fn test_kelly_weights() {
+        let mu = dvector![0.08, 0.05];
+        let sigma = dmatrix![0.04, 0.006; 0.006, 0.0225];
+
+        let weights = kelly_weights(&mu, &sigma).unwrap();
+
+        assert_approx_equal!((&sigma * &weights - &mu).norm(), 0.0, 1e-12);
+
+        // Uncorrelated assets are sized independently.
+        let weights = kelly_weights(&mu, &dmatrix![0.04, 0.0; 0.0, 0.0225]).unwrap();
+        assert_approx_equal!(weights[0], 2.0, 1e-12);
+        assert_approx_equal!(weights[1], 0.05 / 0.0225, 1e-12);
+
+        assert!(kelly_weights(&mu, &dmatrix![1.0, 1.0; 1.0, 1.0]).is_err());
+        assert!(kelly_weights(&mu, &dmatrix![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_volatility_target() {
+        assert_approx_equal!(volatility_target(0.10, 0.20, 3.0).unwrap(), 0.5, 1e-12);
+        assert_approx_equal!(volatility_target(0.10, 0.02, 3.0).unwrap(), 3.0, 1e-12);
+
+        assert!(volatility_target(0.10, 0.0, 3.0).is_err());
+    }
+
+    #[test]
+    fn test_drawdown_kelly_fraction() {
+        // Full Kelly halves the capital with probability 1/2.
+        assert_approx_equal!(drawdown_kelly_fraction(0.5, 0.5).unwrap(), 1.0, 1e-12);
+
+        // A 20% drawdown with at most 10% probability.
+        let c = drawdown_kelly_fraction(0.2, 0.1).unwrap();
+        assert_approx_equal!(0.8_f64.powf(2.0 / c - 1.0), 0.1, 1e-12);
+
+        assert!(drawdown_kelly_fraction(0.0, 0.1).is_err());
+        assert!(drawdown_kelly_fraction(0.2, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_drawdown_constrained_exposure() {
+        // At the peak, with a 20% cushion, the exposure is 20% of Kelly.
+        let exposure = drawdown_constrained_exposure(0.08, 0.04, 0.2, 100.0, 100.0).unwrap();
+        assert_approx_equal!(exposure, 2.0 * 0.2, 1e-12);
+
+        // At the floor, nothing is at risk.
+        let exposure = drawdown_constrained_exposure(0.08, 0.04, 0.2, 80.0, 100.0).unwrap();
+        assert_approx_equal!(exposure, 0.0, 1e-12);
+
+        assert!(drawdown_constrained_exposure(0.08, 0.04, 0.2, 110.0, 100.0).is_err());
+    }
+}
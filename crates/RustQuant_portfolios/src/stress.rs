@@ -0,0 +1,349 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Stress testing a portfolio with shocks to risk factors.
+//!
+//! A [`StressScenario`] is a set of named shocks to [`RiskFactor`]s: either
+//! one of the historical scenarios, or user-defined. Instruments implement
+//! [`Stressable`] to reprice themselves with their own pricing models after
+//! the shocks, so the P&L captures the non-linearity of options, unlike a
+//! sensitivity-based approximation.
+//!
+//! ```
+//! # use RustQuant::portfolios::*;
+//! # use RustQuant::time::today;
+//! # use std::collections::HashMap;
+//! let position = Position::new(Security::new("AAA", 100.0, today()), 10, 90.0, 100.0, None);
+//! let portfolio = Portfolio::new(HashMap::from([("AAA".to_string(), position)]));
+//!
+//! let result = portfolio.stress_test(&StressScenario::covid_march_2020());
+//!
+//! assert!((result.pnl - 10.0 * 100.0 * -0.34).abs() < 1e-10);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{Portfolio, Security};
+use std::collections::HashMap;
+use RustQuant_instruments::options::BlackScholesMerton;
+use RustQuant_instruments::Instrument;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Risk factor shocked in a stress scenario.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RiskFactor {
+    /// Equity prices: relative shock (e.g. -0.3 for a 30% fall).
+    Equity,
+
+    /// Interest rates: absolute shock (e.g. 0.01 for +100bp).
+    InterestRate,
+
+    /// Credit spreads: absolute shock.
+    CreditSpread,
+
+    /// Implied volatilities: absolute shock (e.g. 0.1 for +10 vol points).
+    Volatility,
+
+    /// Any other factor, by name, for user-defined instruments.
+    Custom(String),
+}
+
+/// Named set of shocks to risk factors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressScenario {
+    /// Name of the scenario.
+    pub name: String,
+
+    /// Shocks, by risk factor.
+    pub shocks: HashMap<RiskFactor, f64>,
+}
+
+/// Instrument that can be repriced after the shocks of a stress scenario.
+pub trait Stressable: Instrument {
+    /// Price of the instrument after the shocks of the scenario.
+    fn stressed_price(&self, scenario: &StressScenario) -> f64;
+}
+
+/// P&L of a position in a stress scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionStress {
+    /// Name of the position.
+    pub name: String,
+
+    /// Value of the position before the shocks.
+    pub base_value: f64,
+
+    /// Value of the position after the shocks.
+    pub stressed_value: f64,
+
+    /// Profit (or loss) of the position.
+    pub pnl: f64,
+}
+
+/// P&L of a portfolio in a stress scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressTestResult {
+    /// Name of the scenario.
+    pub scenario: String,
+
+    /// P&L of the positions, sorted by name.
+    pub positions: Vec<PositionStress>,
+
+    /// Profit (or loss) of the portfolio.
+    pub pnl: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StressScenario {
+    /// Create a new scenario, without shocks.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            shocks: HashMap::new(),
+        }
+    }
+
+    /// Add a shock to a risk factor.
+    #[must_use]
+    pub fn with_shock(mut self, factor: RiskFactor, shock: f64) -> Self {
+        self.shocks.insert(factor, shock);
+        self
+    }
+
+    /// Shock to a risk factor (zero if not shocked).
+    #[must_use]
+    pub fn shock(&self, factor: &RiskFactor) -> f64 {
+        self.shocks.get(factor).copied().unwrap_or(0.0)
+    }
+
+    /// Black Monday, 19 October 1987: the S&P 500 fell 20.5% in a day.
+    #[must_use]
+    pub fn black_monday_1987() -> Self {
+        Self::new("Black Monday 1987")
+            .with_shock(RiskFactor::Equity, -0.205)
+            .with_shock(RiskFactor::InterestRate, -0.005)
+            .with_shock(RiskFactor::Volatility, 0.25)
+    }
+
+    /// Global financial crisis, September to November 2008: equities fell
+    /// about 40%, short rates were cut by 2%, the VIX rose from about 20 to
+    /// 80, and investment grade spreads widened by about 3%.
+    #[must_use]
+    pub fn global_financial_crisis_2008() -> Self {
+        Self::new("Global Financial Crisis 2008")
+            .with_shock(RiskFactor::Equity, -0.40)
+            .with_shock(RiskFactor::InterestRate, -0.02)
+            .with_shock(RiskFactor::CreditSpread, 0.03)
+            .with_shock(RiskFactor::Volatility, 0.40)
+    }
+
+    /// COVID-19 crash, 19 February to 23 March 2020: the S&P 500 fell 34%,
+    /// rates were cut by 1.5%, the VIX rose from about 15 to 80, and
+    /// investment grade spreads widened by about 2%.
+    #[must_use]
+    pub fn covid_march_2020() -> Self {
+        Self::new("COVID March 2020")
+            .with_shock(RiskFactor::Equity, -0.34)
+            .with_shock(RiskFactor::InterestRate, -0.015)
+            .with_shock(RiskFactor::CreditSpread, 0.02)
+            .with_shock(RiskFactor::Volatility, 0.50)
+    }
+
+    /// The historical scenarios.
+    #[must_use]
+    pub fn historical() -> Vec<Self> {
+        vec![
+            Self::black_monday_1987(),
+            Self::global_financial_crisis_2008(),
+            Self::covid_march_2020(),
+        ]
+    }
+}
+
+impl Stressable for Security {
+    fn stressed_price(&self, scenario: &StressScenario) -> f64 {
+        self.last_price * (1.0 + scenario.shock(&RiskFactor::Equity))
+    }
+}
+
+impl Stressable for BlackScholesMerton {
+    /// Reprices the option with the shocked underlying price, volatility
+    /// (floored at zero), and interest rate, which also moves the cost of
+    /// carry.
+    fn stressed_price(&self, scenario: &StressScenario) -> f64 {
+        let rate_shock = scenario.shock(&RiskFactor::InterestRate);
+
+        BlackScholesMerton::new(
+            self.cost_of_carry + rate_shock,
+            self.underlying_price * (1.0 + scenario.shock(&RiskFactor::Equity)),
+            self.strike_price,
+            (self.volatility + scenario.shock(&RiskFactor::Volatility)).max(1e-8),
+            self.risk_free_rate + rate_shock,
+            self.evaluation_date,
+            self.expiration_date,
+            self.option_type,
+        )
+        .price()
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: Stressable,
+{
+    /// P&L of each position and of the portfolio in a stress scenario,
+    /// relative to the model prices of the instruments.
+    pub fn stress_test(&self, scenario: &StressScenario) -> StressTestResult {
+        let mut positions: Vec<PositionStress> = self
+            .positions
+            .iter()
+            .map(|(name, position)| {
+                let quantity = position.quantity as f64;
+                let base_value = quantity * position.instrument.price();
+                let stressed_value = quantity * position.instrument.stressed_price(scenario);
+
+                PositionStress {
+                    name: name.to_string(),
+                    base_value,
+                    stressed_value,
+                    pnl: stressed_value - base_value,
+                }
+            })
+            .collect();
+
+        positions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        StressTestResult {
+            scenario: scenario.name.clone(),
+            pnl: positions.iter().map(|position| position.pnl).sum(),
+            positions,
+        }
+    }
+
+    /// Stress test the portfolio in each scenario.
+    pub fn stress_tests(&self, scenarios: &[StressScenario]) -> Vec<StressTestResult> {
+        scenarios
+            .iter()
+            .map(|scenario| self.stress_test(scenario))
+            .collect()
+    }
+}
+
+impl StressTestResult {
+    /// P&L of the named position, if any.
+    #[must_use]
+    pub fn position(&self, name: &str) -> Option<&PositionStress> {
+        self.positions
+            .binary_search_by(|position| position.name.as_str().cmp(name))
+            .ok()
+            .map(|i| &self.positions[i])
+    }
+
+    /// Position with the largest loss, if any.
+    #[must_use]
+    pub fn worst_position(&self) -> Option<&PositionStress> {
+        self.positions.iter().min_by(|a, b| a.pnl.total_cmp(&b.pnl))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_stress {
+    use super::*;
+    use crate::Position;
+    use time::Duration;
+    use RustQuant_instruments::options::TypeFlag;
+    use RustQuant_time::today;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn put(spot: f64) -> BlackScholesMerton {
+        BlackScholesMerton::new(
+            0.03,
+            spot,
+            100.0,
+            0.2,
+            0.03,
+            None,
+            today() + Duration::days(365),
+            TypeFlag::Put,
+        )
+    }
+
+    fn securities() -> Portfolio<Security> {
+        let date = today();
+
+        Portfolio::new(HashMap::from([
+            (
+                "AAA".to_string(),
+                Position::new(Security::new("AAA", 50.0, date), 100, 40.0, 50.0, None),
+            ),
+            (
+                "BBB".to_string(),
+                Position::new(Security::new("BBB", 20.0, date), 1000, 25.0, 20.0, None),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn test_user_defined_scenario() {
+        let scenario = StressScenario::new("Equities -10%").with_shock(RiskFactor::Equity, -0.1);
+        let result = securities().stress_test(&scenario);
+
+        assert_eq!(result.scenario, "Equities -10%");
+        assert_approx_equal!(result.position("AAA").unwrap().pnl, -500.0, 1e-10);
+        assert_approx_equal!(result.position("BBB").unwrap().pnl, -2000.0, 1e-10);
+        assert_approx_equal!(result.pnl, -2500.0, 1e-10);
+        assert_eq!(result.worst_position().unwrap().name, "BBB");
+        assert!(result.position("CCC").is_none());
+    }
+
+    #[test]
+    fn test_historical_scenarios() {
+        let results = securities().stress_tests(&StressScenario::historical());
+
+        assert_eq!(results.len(), 3);
+        assert_approx_equal!(results[1].pnl, 25_000.0 * -0.40, 1e-9);
+
+        // Factors without a shock are unchanged.
+        let scenario = StressScenario::new("Rates +1%").with_shock(RiskFactor::InterestRate, 0.01);
+        assert_approx_equal!(securities().stress_test(&scenario).pnl, 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_options_are_repriced() {
+        let portfolio = Portfolio::new(HashMap::from([(
+            "Puts".to_string(),
+            Position::new(put(100.0), 10, 8.0, 8.0, None),
+        )]));
+
+        let scenario = StressScenario::new("Crash").with_shock(RiskFactor::Equity, -0.3);
+        let result = portfolio.stress_test(&scenario);
+
+        // The puts gain the full repricing, not only their delta.
+        let expected = 10.0 * (put(70.0).price() - put(100.0).price());
+        assert_approx_equal!(result.pnl, expected, 1e-10);
+        assert!(result.pnl > 10.0 * 30.0 * -put(100.0).delta());
+
+        // Higher volatility makes the options more valuable.
+        let scenario = StressScenario::new("Vol +10").with_shock(RiskFactor::Volatility, 0.1);
+        assert!(portfolio.stress_test(&scenario).pnl > 0.0);
+    }
+}
@@ -0,0 +1,389 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Parallel revaluation of a [`Portfolio`] against a [`MarketSnapshot`].
+//!
+//! The snapshot is immutable once built and cheap to clone, so every thread
+//! values its positions against exactly the same market state. Positions
+//! with a quote in the snapshot are marked to market. The others are marked
+//! to model by a pricer that is given the snapshot (see
+//! [`Portfolio::value_parallel_with_model`]), or reported as failures.
+//!
+//! Results are sorted by position name and the NPV is summed in that order,
+//! so the output does not depend on how the work was split across threads.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::Portfolio;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::Instrument;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Immutable market state used to revalue a portfolio.
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    /// Date the snapshot was taken.
+    pub valuation_date: Date,
+
+    /// Unit prices, keyed by position name.
+    quotes: Arc<BTreeMap<String, f64>>,
+}
+
+/// How a position was valued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValuationSource {
+    /// Quoted price from the snapshot (mark-to-market).
+    Quote,
+
+    /// Price from a pricing model, against the snapshot (mark-to-model).
+    Model,
+}
+
+/// Valuation of a single position.
+#[derive(Debug, Clone)]
+pub struct PositionValuation {
+    /// Position name.
+    pub name: String,
+
+    /// Quantity held.
+    pub quantity: u64,
+
+    /// Price per unit.
+    pub unit_price: f64,
+
+    /// Value of the position (quantity times unit price).
+    pub value: f64,
+
+    /// Where the unit price came from.
+    pub source: ValuationSource,
+}
+
+/// Result of a portfolio revaluation.
+#[derive(Debug)]
+pub struct PortfolioValuation {
+    /// Date of the snapshot the portfolio was valued against.
+    pub valuation_date: Date,
+
+    /// Valued positions, sorted by name.
+    pub positions: Vec<PositionValuation>,
+
+    /// Positions that could not be valued, sorted by name.
+    pub failures: Vec<(String, RustQuantError)>,
+
+    /// Net present value of the valued positions.
+    pub npv: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarketSnapshot {
+    /// Create a snapshot with the given quotes (unit prices by position name).
+    pub fn new(valuation_date: Date, quotes: BTreeMap<String, f64>) -> Self {
+        Self {
+            valuation_date,
+            quotes: Arc::new(quotes),
+        }
+    }
+
+    /// Quoted unit price of a position, if any.
+    pub fn quote(&self, name: &str) -> Option<f64> {
+        self.quotes.get(name).copied()
+    }
+
+    /// All quotes in the snapshot.
+    pub fn quotes(&self) -> &BTreeMap<String, f64> {
+        &self.quotes
+    }
+}
+
+impl PortfolioValuation {
+    /// Valuation of the named position, if it was valued.
+    pub fn position(&self, name: &str) -> Option<&PositionValuation> {
+        self.positions
+            .binary_search_by(|position| position.name.as_str().cmp(name))
+            .ok()
+            .map(|i| &self.positions[i])
+    }
+
+    /// Check if every position was valued.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: Instrument + Sync,
+{
+    /// Revalue every position against a snapshot, in parallel.
+    ///
+    /// Positions quoted in the snapshot use the quote. The others cannot be
+    /// valued from the snapshot alone and are reported in
+    /// [`PortfolioValuation::failures`], as are positions whose quote is not
+    /// finite; none of these count towards the NPV.
+    pub fn value_parallel(&self, snapshot: &MarketSnapshot) -> PortfolioValuation {
+        self.value_parallel_with_model(snapshot, |_, _| {
+            Err(RustQuantError::MissingInput(
+                "No quote in the snapshot, and no pricing model.".to_string(),
+            ))
+        })
+    }
+
+    /// Revalue every position against a snapshot, in parallel, marking
+    /// unquoted positions to model.
+    ///
+    /// Positions quoted in the snapshot use the quote, the others use
+    /// `model`, which prices an instrument against the snapshot (its
+    /// valuation date and market data), rather than the instrument's own
+    /// inputs. A position that cannot be priced, or whose price is not
+    /// finite, is reported in [`PortfolioValuation::failures`] and left out
+    /// of the NPV.
+    pub fn value_parallel_with_model<F>(
+        &self,
+        snapshot: &MarketSnapshot,
+        model: F,
+    ) -> PortfolioValuation
+    where
+        F: Fn(&I, &MarketSnapshot) -> Result<f64, RustQuantError> + Sync,
+    {
+        let mut results: Vec<(&String, Result<PositionValuation, RustQuantError>)> = self
+            .positions
+            .par_iter()
+            .map(|(name, position)| {
+                let price = match snapshot.quote(name) {
+                    Some(quote) => Ok((quote, ValuationSource::Quote)),
+                    None => model(&position.instrument, snapshot)
+                        .map(|price| (price, ValuationSource::Model)),
+                };
+
+                let (unit_price, source) = match price {
+                    Ok(price) => price,
+                    Err(error) => return (name, Err(error)),
+                };
+
+                let valuation = if unit_price.is_finite() {
+                    Ok(PositionValuation {
+                        name: name.clone(),
+                        quantity: position.quantity,
+                        unit_price,
+                        value: position.quantity as f64 * unit_price,
+                        source,
+                    })
+                } else {
+                    Err(RustQuantError::ComputationError(format!(
+                        "Price of {} is not finite: {}.",
+                        name, unit_price
+                    )))
+                };
+
+                (name, valuation)
+            })
+            .collect();
+
+        results.sort_by_key(|(name, _)| *name);
+
+        let mut positions = Vec::with_capacity(results.len());
+        let mut failures = Vec::new();
+
+        for (name, result) in results {
+            match result {
+                Ok(valuation) => positions.push(valuation),
+                Err(error) => failures.push((name.clone(), error)),
+            }
+        }
+
+        let npv = positions.iter().map(|position| position.value).sum();
+
+        PortfolioValuation {
+            valuation_date: snapshot.valuation_date,
+            positions,
+            failures,
+            npv,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_valuation {
+    use super::*;
+    use crate::Position;
+    use std::collections::HashMap;
+    use time::Duration;
+    use RustQuant_instruments::options::{BlackScholesMerton, TypeFlag};
+    use RustQuant_time::today;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn option(strike: f64) -> BlackScholesMerton {
+        BlackScholesMerton::new(
+            0.05,
+            100.0,
+            strike,
+            0.2,
+            0.05,
+            None,
+            today() + Duration::days(182),
+            TypeFlag::Call,
+        )
+    }
+
+    // Reprice an option at the snapshot's valuation date.
+    fn model(
+        option: &BlackScholesMerton,
+        snapshot: &MarketSnapshot,
+    ) -> Result<f64, RustQuantError> {
+        let option = BlackScholesMerton::new(
+            option.cost_of_carry,
+            option.underlying_price,
+            option.strike_price,
+            option.volatility,
+            option.risk_free_rate,
+            Some(snapshot.valuation_date),
+            option.expiration_date,
+            option.option_type,
+        );
+
+        Ok(option.price())
+    }
+
+    fn portfolio() -> Portfolio<BlackScholesMerton> {
+        let positions = (0..200_u64)
+            .map(|i| {
+                let strike = 80.0 + 0.2 * i as f64;
+                let position = Position::new(option(strike), 10 + i, 0.0, 0.0, None);
+
+                (format!("Call {:03}", i), position)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Portfolio::new(positions)
+    }
+
+    #[test]
+    fn test_value_parallel() {
+        let portfolio = portfolio();
+        let snapshot =
+            MarketSnapshot::new(today(), BTreeMap::from([("Call 007".to_string(), 25.0)]));
+
+        let valuation = portfolio.value_parallel_with_model(&snapshot, model);
+
+        assert!(valuation.is_complete());
+        assert_eq!(valuation.positions.len(), 200);
+        assert!(valuation
+            .positions
+            .windows(2)
+            .all(|pair| pair[0].name < pair[1].name));
+
+        // Quoted position is marked to market, the others to model.
+        let quoted = valuation.position("Call 007").unwrap();
+        assert_eq!(quoted.source, ValuationSource::Quote);
+        assert_eq!(quoted.value, 17.0 * 25.0);
+
+        let modelled = valuation.position("Call 100").unwrap();
+        assert_eq!(modelled.source, ValuationSource::Model);
+        assert_approx_equal!(modelled.unit_price, option(100.0).price(), 1e-12);
+
+        let expected: f64 = portfolio
+            .positions
+            .iter()
+            .map(|(name, position)| {
+                position.quantity as f64
+                    * snapshot
+                        .quote(name)
+                        .unwrap_or_else(|| model(&position.instrument, &snapshot).unwrap())
+            })
+            .sum();
+        assert_approx_equal!(valuation.npv, expected, 1e-8);
+
+        // Deterministic: repeated runs give bit-identical results.
+        for _ in 0..5 {
+            assert_eq!(
+                portfolio.value_parallel_with_model(&snapshot, model).npv,
+                valuation.npv
+            );
+        }
+    }
+
+    #[test]
+    fn test_value_parallel_against_snapshot_date() {
+        let portfolio = portfolio();
+        let snapshot = MarketSnapshot::new(today() + Duration::days(91), BTreeMap::new());
+
+        let valuation = portfolio.value_parallel_with_model(&snapshot, model);
+
+        // Priced at the snapshot date, not at the instruments' own (today).
+        let modelled = valuation.position("Call 100").unwrap();
+        assert_approx_equal!(
+            modelled.unit_price,
+            model(&option(100.0), &snapshot).unwrap(),
+            1e-12
+        );
+        assert!(modelled.unit_price < option(100.0).price());
+    }
+
+    #[test]
+    fn test_value_parallel_unquoted() {
+        let portfolio = portfolio();
+        let snapshot =
+            MarketSnapshot::new(today(), BTreeMap::from([("Call 007".to_string(), 25.0)]));
+
+        let valuation = portfolio.value_parallel(&snapshot);
+
+        // Without a model, only the quoted position can be valued.
+        assert_eq!(valuation.positions.len(), 1);
+        assert_eq!(valuation.failures.len(), 199);
+        assert!(matches!(
+            valuation.failures[0].1,
+            RustQuantError::MissingInput(_)
+        ));
+        assert_eq!(valuation.npv, 17.0 * 25.0);
+    }
+
+    #[test]
+    fn test_value_parallel_failures() {
+        let mut positions = HashMap::new();
+        positions.insert(
+            "Good".to_string(),
+            Position::new(option(100.0), 1, 0.0, 0.0, None),
+        );
+        positions.insert(
+            "Bad".to_string(),
+            Position::new(option(100.0), 1, 0.0, 0.0, None),
+        );
+
+        let portfolio = Portfolio::new(positions);
+        let snapshot =
+            MarketSnapshot::new(today(), BTreeMap::from([("Bad".to_string(), f64::NAN)]));
+
+        let valuation = portfolio.value_parallel_with_model(&snapshot, model);
+
+        assert!(!valuation.is_complete());
+        assert_eq!(valuation.failures.len(), 1);
+        assert_eq!(valuation.failures[0].0, "Bad");
+        assert_approx_equal!(
+            valuation.npv,
+            model(&option(100.0), &snapshot).unwrap(),
+            1e-12
+        );
+    }
+}
@@ -0,0 +1,243 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::levy_process::LevyProcess;
+use crate::process::{StochasticProcess, StochasticProcessConfig, Trajectories};
+use num::Complex;
+use rand::{rngs::StdRng, Rng};
+use rand_distr::{Distribution, Exp1, StandardNormal};
+use statrs::function::gamma::{checked_gamma_ui, gamma};
+use RustQuant_math::integrate;
+
+/// Struct containing the CGMY (Carr-Geman-Madan-Yor) process parameters.
+///
+/// The CGMY process is a pure-jump Lévy process with Lévy density:
+/// $$
+/// k(x) = \begin{cases}
+///     C e^{-G|x|} |x|^{-1-Y} & x < 0 \\\\
+///     C e^{-Mx} x^{-1-Y} & x > 0
+/// \end{cases}
+/// $$
+///
+/// Paths are simulated with Rosiński's series representation of each
+/// (tempered stable) side. Jumps smaller than `epsilon` are replaced by
+/// their mean plus a Gaussian term with matching variance.
+pub struct CGMY {
+    /// Overall activity ($C$).
+    pub c: f64,
+
+    /// Exponential decay of the left tail ($G$).
+    pub g: f64,
+
+    /// Exponential decay of the right tail ($M$).
+    pub m: f64,
+
+    /// Fine structure ($Y$), with $0 < Y < 2$ and $Y \neq 1$.
+    pub y: f64,
+
+    /// Truncation level for small jumps in the series representation.
+    pub epsilon: f64,
+}
+
+impl CGMY {
+    /// Create a new CGMY process, with a small-jump truncation of `1e-3`.
+    ///
+    /// # Arguments:
+    /// * `c` - Overall activity ($C$).
+    /// * `g` - Exponential decay of the left tail ($G$).
+    /// * `m` - Exponential decay of the right tail ($M$).
+    /// * `y` - Fine structure ($Y$), with $0 < Y < 2$ and $Y \neq 1$.
+    pub fn new(c: f64, g: f64, m: f64, y: f64) -> Self {
+        assert!(c > 0.0 && g > 0.0 && m > 0.0);
+        assert!(y > 0.0 && y < 2.0 && y != 1.0);
+
+        Self {
+            c,
+            g,
+            m,
+            y,
+            epsilon: 1e-3,
+        }
+    }
+
+    /// Set the truncation level for small jumps.
+    /// Smaller values are more accurate but need more series terms.
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        assert!(epsilon > 0.0);
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Mean of one side of the process per unit time,
+    /// $C \Gamma(1 - Y) \lambda^{Y - 1}$.
+    fn side_mean(&self, lambda: f64) -> f64 {
+        self.c * gamma(1.0 - self.y) * lambda.powf(self.y - 1.0)
+    }
+
+    /// Expected sum of the jumps kept by the truncated series over `dt`.
+    ///
+    /// A kept jump is $\min(s, E V^{1/Y} / \lambda)$ with stable size $s > \epsilon$,
+    /// which gives (with $c = V^{1/Y} / \lambda$):
+    /// $$ C dt \mathbb{E}_V\left[ \frac{c \epsilon^{-Y}}{Y} - c^{1-Y} \Gamma(-Y, \epsilon / c) \right] $$
+    fn truncated_mean(&self, lambda: f64, dt: f64) -> f64 {
+        let (y, eps) = (self.y, self.epsilon);
+
+        let integrand = |v: f64| {
+            let c = v.powf(1.0 / y) / lambda;
+
+            c * eps.powf(-y) / y - c.powf(1.0 - y) * upper_incomplete_gamma(-y, eps / c)
+        };
+
+        self.c * dt * integrate(integrand, 0.0, 1.0)
+    }
+
+    /// Increments of one (tempered stable) side of the process.
+    fn sample_side(&self, lambda: f64, dt: f64, n_steps: usize, rng: &mut StdRng) -> Vec<f64> {
+        let (c, y, eps) = (self.c, self.y, self.epsilon);
+
+        // Series terms with arrival times beyond `tau` have stable size below `epsilon`.
+        let tau = c * dt * eps.powf(-y) / y;
+
+        // Compensate for the truncated series, and the missing small jumps.
+        let shift = self.side_mean(lambda) * dt - self.truncated_mean(lambda, dt);
+        let small_jumps_std = (c * dt * eps.powf(2.0 - y) / (2.0 - y)).sqrt();
+
+        (0..n_steps)
+            .map(|_| {
+                let mut jumps = 0.0;
+                let mut arrival: f64 = Exp1.sample(rng);
+
+                while arrival <= tau {
+                    let stable = (y * arrival / (c * dt)).powf(-1.0 / y);
+                    let e: f64 = Exp1.sample(rng);
+                    let v: f64 = rng.gen();
+                    let next: f64 = Exp1.sample(rng);
+
+                    jumps += stable.min(e * v.powf(1.0 / y) / lambda);
+                    arrival += next;
+                }
+
+                let z: f64 = StandardNormal.sample(rng);
+
+                jumps + shift + small_jumps_std * z
+            })
+            .collect()
+    }
+}
+
+/// Upper incomplete gamma function $\Gamma(a, x)$, extended to
+/// negative non-integer `a` via $\Gamma(a, x) = (\Gamma(a + 1, x) - x^a e^{-x}) / a$.
+fn upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if a > 0.0 {
+        checked_gamma_ui(a, x).unwrap_or(0.0)
+    } else {
+        (upper_incomplete_gamma(a + 1.0, x) - x.powf(a) * (-x).exp()) / a
+    }
+}
+
+impl LevyProcess for CGMY {
+    fn characteristic_exponent(&self, u: Complex<f64>) -> Complex<f64> {
+        let i: Complex<f64> = Complex::i();
+        let (c, g, m, y) = (self.c, self.g, self.m, self.y);
+
+        c * gamma(-y) * ((m - i * u).powf(y) - m.powf(y) + (g + i * u).powf(y) - g.powf(y))
+    }
+
+    fn sample_increments(&self, dt: f64, n_steps: usize, rng: &mut StdRng) -> Vec<f64> {
+        let up = self.sample_side(self.m, dt, n_steps, rng);
+        let down = self.sample_side(self.g, dt, n_steps, rng);
+
+        up.iter().zip(down.iter()).map(|(u, d)| u - d).collect()
+    }
+}
+
+impl StochasticProcess for CGMY {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        self.side_mean(self.m) - self.side_mean(self.g)
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.c, self.g, self.m, self.y]
+    }
+
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate(config)
+    }
+}
+
+#[cfg(test)]
+mod tests_cgmy {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn check_moments(cgmy: &CGMY, tolerance: f64) {
+        let (c, g, m, y) = (cgmy.c, cgmy.g, cgmy.m, cgmy.y);
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, 0.5, 1, 20000, true).with_seed(2024);
+        let output = cgmy.simulate(&config);
+
+        let X_T: Vec<f64> = output
+            .paths
+            .iter()
+            .filter_map(|v| v.last().copied())
+            .collect();
+
+        // E[X_T] = C Gamma(1 - Y) (M^{Y-1} - G^{Y-1}) T
+        // V[X_T] = C Gamma(2 - Y) (M^{Y-2} + G^{Y-2}) T
+        let mean = c * gamma(1.0 - y) * (m.powf(y - 1.0) - g.powf(y - 1.0)) * 0.5;
+        let variance = c * gamma(2.0 - y) * (m.powf(y - 2.0) + g.powf(y - 2.0)) * 0.5;
+
+        assert_approx_equal!(X_T.mean(), mean, tolerance);
+        assert_approx_equal!(X_T.variance(), variance, tolerance);
+
+        // Martingale correction: E[exp(omega * T + X_T)] = 1.
+        let omega = cgmy.martingale_correction();
+        let martingale: Vec<f64> = X_T.iter().map(|x| (omega * 0.5 + x).exp()).collect();
+
+        assert_approx_equal!(martingale.mean(), 1.0, 2.0 * tolerance);
+    }
+
+    #[test]
+    fn test_cgmy_finite_variation() {
+        check_moments(&CGMY::new(1.0, 5.0, 10.0, 0.5), 0.005);
+    }
+
+    #[test]
+    fn test_cgmy_infinite_variation() {
+        check_moments(&CGMY::new(0.5, 5.0, 10.0, 1.5).with_epsilon(1e-2), 0.01);
+    }
+
+    #[test]
+    fn test_cgmy_characteristic_function() {
+        let cgmy = CGMY::new(1.0, 5.0, 10.0, 0.5);
+        let i: Complex<f64> = Complex::i();
+
+        assert_approx_equal!(cgmy.characteristic_function(0.0 * i, 1.0).re, 1.0, 1e-12);
+
+        // The derivative of psi at zero gives the mean.
+        let h = 1e-6;
+        let psi = |u: f64| cgmy.characteristic_exponent(Complex::new(u, 0.0));
+        let derivative = (psi(h) - psi(-h)) / (2.0 * h * i);
+
+        assert_approx_equal!(derivative.re, cgmy.drift(0.0, 0.0), 1e-6);
+    }
+}
@@ -0,0 +1,245 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::two_factor_short_rate::{DiscountFunction, TwoFactorShortRateModel};
+use rand::rngs::StdRng;
+use rand_distr::{ChiSquared, Distribution, Poisson};
+use std::sync::Arc;
+
+/// Parameters of one CIR factor:
+/// $$ dx(t) = \kappa (\theta - x(t)) dt + \sigma \sqrt{x(t)} dW(t) $$
+#[derive(Debug, Clone, Copy)]
+pub struct CIRFactor {
+    /// Mean reversion speed ($\kappa$).
+    pub kappa: f64,
+
+    /// Long-run mean ($\theta$).
+    pub theta: f64,
+
+    /// Volatility ($\sigma$).
+    pub sigma: f64,
+
+    /// Initial value ($x_0$).
+    pub x_0: f64,
+}
+
+/// Struct containing the CIR2++ model parameters (Brigo and Mercurio, 2006):
+/// $$ r(t) = x(t) + y(t) + \varphi(t) $$
+/// where $x$ and $y$ are independent CIR factors.
+/// The factors are simulated exactly from their non-central chi-squared transition.
+#[derive(Clone)]
+pub struct CIR2PlusPlus {
+    /// The first factor.
+    pub x: CIRFactor,
+
+    /// The second factor.
+    pub y: CIRFactor,
+
+    /// Initial discount curve, $P^M(0, t)$.
+    pub discount_curve: DiscountFunction,
+}
+
+impl CIRFactor {
+    /// Create a new CIR factor.
+    pub fn new(kappa: f64, theta: f64, sigma: f64, x_0: f64) -> Self {
+        assert!(kappa > 0.0 && theta >= 0.0 && sigma > 0.0 && x_0 >= 0.0);
+
+        Self {
+            kappa,
+            theta,
+            sigma,
+            x_0,
+        }
+    }
+
+    /// $h = \sqrt{\kappa^2 + 2 \sigma^2}$, and the common denominator of $A$ and $B$.
+    fn h_and_denominator(&self, tau: f64) -> (f64, f64) {
+        let h = (self.kappa * self.kappa + 2.0 * self.sigma * self.sigma).sqrt();
+
+        (h, 2.0 * h + (self.kappa + h) * ((h * tau).exp() - 1.0))
+    }
+
+    /// Zero-coupon bond price $A(\tau) e^{-B(\tau) x}$ in the (unshifted) CIR model.
+    pub fn bond_price(&self, tau: f64, x: f64) -> f64 {
+        let (h, denominator) = self.h_and_denominator(tau);
+
+        let A = (2.0 * h * ((self.kappa + h) * tau / 2.0).exp() / denominator)
+            .powf(2.0 * self.kappa * self.theta / (self.sigma * self.sigma));
+        let B = 2.0 * ((h * tau).exp() - 1.0) / denominator;
+
+        A * (-B * x).exp()
+    }
+
+    /// Instantaneous forward rate at time zero in the (unshifted) CIR model.
+    pub fn forward(&self, t: f64) -> f64 {
+        let (h, denominator) = self.h_and_denominator(t);
+        let growth = (h * t).exp();
+
+        2.0 * self.kappa * self.theta * (growth - 1.0) / denominator
+            + self.x_0 * 4.0 * h * h * growth / (denominator * denominator)
+    }
+
+    /// Sample the factor after a step of length `dt`.
+    pub fn sample(&self, x: f64, dt: f64, rng: &mut StdRng) -> f64 {
+        let decay = (-self.kappa * dt).exp();
+        let scale = self.sigma * self.sigma * (1.0 - decay) / (4.0 * self.kappa);
+        let degrees_of_freedom = 4.0 * self.kappa * self.theta / (self.sigma * self.sigma);
+        let non_centrality = x * decay / scale;
+
+        // Non-central chi-squared as a Poisson mixture of central chi-squared.
+        let poisson: f64 = if non_centrality > 0.0 {
+            Poisson::new(non_centrality / 2.0).unwrap().sample(rng)
+        } else {
+            0.0
+        };
+        let chi_squared: f64 = ChiSquared::new(degrees_of_freedom + 2.0 * poisson)
+            .unwrap()
+            .sample(rng);
+
+        scale * chi_squared
+    }
+}
+
+impl CIR2PlusPlus {
+    /// Create a new CIR2++ model, fitted to an initial discount curve.
+    ///
+    /// # Arguments:
+    /// * `x` - The first factor.
+    /// * `y` - The second factor.
+    /// * `discount_curve` - Initial discount factors, $t \mapsto P^M(0, t)$.
+    pub fn new<F>(x: CIRFactor, y: CIRFactor, discount_curve: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        Self {
+            x,
+            y,
+            discount_curve: Arc::new(discount_curve),
+        }
+    }
+}
+
+impl TwoFactorShortRateModel for CIR2PlusPlus {
+    fn discount_curve(&self) -> &DiscountFunction {
+        &self.discount_curve
+    }
+
+    fn initial_factors(&self) -> (f64, f64) {
+        (self.x.x_0, self.y.x_0)
+    }
+
+    fn step(&self, x: f64, y: f64, dt: f64, rng: &mut StdRng) -> (f64, f64) {
+        (self.x.sample(x, dt, rng), self.y.sample(y, dt, rng))
+    }
+
+    fn shift(&self, t: f64) -> f64 {
+        self.instantaneous_forward(t) - self.x.forward(t) - self.y.forward(t)
+    }
+
+    fn bond_price(&self, t: f64, maturity: f64, x: f64, y: f64) -> f64 {
+        let curve = &self.discount_curve;
+        let model_curve =
+            |t: f64| self.x.bond_price(t, self.x.x_0) * self.y.bond_price(t, self.y.x_0);
+
+        // P(t, T) = [P^M(0, T) P^CIR(0, t)] / [P^M(0, t) P^CIR(0, T)] * P^CIR(t, T).
+        let shift = curve(maturity) * model_curve(t) / (curve(t) * model_curve(maturity));
+
+        shift * self.x.bond_price(maturity - t, x) * self.y.bond_price(maturity - t, y)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.x.kappa,
+            self.x.theta,
+            self.x.sigma,
+            self.x.x_0,
+            self.y.kappa,
+            self.y.theta,
+            self.y.sigma,
+            self.y.x_0,
+        ]
+    }
+
+    fn set_parameters(&mut self, parameters: &[f64]) {
+        assert_eq!(parameters.len(), 8);
+
+        let p = parameters;
+        self.x = CIRFactor::new(p[0], p[1], p[2], p[3]);
+        self.y = CIRFactor::new(p[4], p[5], p[6], p[7]);
+    }
+}
+
+#[cfg(test)]
+mod tests_cir2pp {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn curve(t: f64) -> f64 {
+        (-(0.03 * t - 0.01 * (1.0 - (-t).exp()))).exp()
+    }
+
+    fn cir2pp() -> CIR2PlusPlus {
+        CIR2PlusPlus::new(
+            CIRFactor::new(0.4, 0.01, 0.05, 0.01),
+            CIRFactor::new(1.5, 0.005, 0.08, 0.004),
+            curve,
+        )
+    }
+
+    #[test]
+    fn test_cir_forward() {
+        // The CIR forward curve is consistent with the CIR bond prices.
+        let factor = CIRFactor::new(0.4, 0.03, 0.1, 0.02);
+        let h = 1e-5;
+
+        for t in [0.5, 2.0, 10.0] {
+            let numerical = -(factor.bond_price(t + h, 0.02).ln()
+                - factor.bond_price(t - h, 0.02).ln())
+                / (2.0 * h);
+
+            assert_approx_equal!(factor.forward(t), numerical, 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_cir2pp_fits_initial_curve() {
+        let model = cir2pp();
+
+        for T in [0.5, 1.0, 5.0, 10.0, 30.0] {
+            assert_approx_equal!(model.bond_price(0.0, T, 0.01, 0.004), curve(T), 1e-12);
+        }
+
+        // r(0) = x_0 + y_0 + phi(0) is the instantaneous forward at zero.
+        assert_approx_equal!(model.shift(0.0) + 0.014, 0.02, 1e-6);
+    }
+
+    #[test]
+    fn test_cir2pp_monte_carlo() {
+        let model = cir2pp();
+        let paths = model.simulate(5.0, 50, 20000, Some(42));
+        let discount_factors = paths.discount_factors();
+
+        // The factors stay non-negative.
+        assert!(paths.x.iter().flatten().all(|x| *x >= 0.0));
+
+        // E[exp(-int_0^T r)] = P(0, T).
+        let D_T: Vec<f64> = discount_factors.iter().map(|d| d[50]).collect();
+        assert_approx_equal!(D_T.mean(), curve(5.0), 5e-4);
+
+        // E[D(T) P(T, S)] = P(0, S).
+        let D_S: Vec<f64> = (0..paths.x.len())
+            .map(|i| {
+                discount_factors[i][50]
+                    * model.bond_price(5.0, 10.0, paths.x[i][50], paths.y[i][50])
+            })
+            .collect();
+        assert_approx_equal!(D_S.mean(), curve(10.0), 5e-4);
+    }
+}
@@ -0,0 +1,546 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Economic scenario generator (ESG).
+//!
+//! Jointly simulates the short rate (Hull-White), an equity index
+//! (GBM or Heston), an FX rate (GBM) and the inflation rate
+//! (Ornstein-Uhlenbeck), driven by correlated Brownian motions.
+//! The output is a set of consistent multi-period scenarios that
+//! downstream risk (VaR, XVA) and wealth simulations can consume.
+//!
+//! The models are specified under the risk-neutral measure, where the
+//! equity index drifts at the short rate less its dividend yield and the
+//! FX rate at the domestic less the foreign short rate. Setting a market
+//! price of risk per driver and switching to [Measure::RealWorld]
+//! generates real-world scenarios from the same calibrated models.
+
+use crate::{
+    pathwise_discount_factors, GeometricBrownianMotion, Heston, HullWhite, Measure, ModelParameter,
+    OrnsteinUhlenbeck, StochasticProcess,
+};
+use nalgebra::{Matrix4, Vector4};
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+use rayon::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Equity model used by the [EconomicScenarioGenerator].
+///
+/// Only the volatility is taken from the model: the drift of the index
+/// follows from the short rate and the dividend yield.
+pub enum EquityModel {
+    /// Geometric Brownian Motion.
+    GeometricBrownianMotion(GeometricBrownianMotion),
+
+    /// Heston stochastic volatility model.
+    Heston(Heston),
+}
+
+/// Initial values of the risk factors.
+#[derive(Debug, Clone, Copy)]
+pub struct EconomicState {
+    /// Initial short rate.
+    pub short_rate: f64,
+
+    /// Initial equity index level.
+    pub equity: f64,
+
+    /// Initial FX rate (units of domestic currency per unit of foreign).
+    pub fx: f64,
+
+    /// Initial (annualised) inflation rate.
+    pub inflation: f64,
+}
+
+/// Economic scenario generator.
+///
+/// The correlation matrix and the market prices of risk are ordered as
+/// `[short rate, equity, FX, inflation]`.
+/// For a Heston equity model, the variance process is correlated with the
+/// equity driver through the Heston `correlation` parameter only.
+pub struct EconomicScenarioGenerator {
+    /// Short rate model.
+    pub rates: HullWhite,
+
+    /// Equity model.
+    pub equity: EquityModel,
+
+    /// FX model (only its volatility is used).
+    pub fx: GeometricBrownianMotion,
+
+    /// Inflation rate model.
+    pub inflation: OrnsteinUhlenbeck,
+
+    /// Initial values of the risk factors.
+    pub initial_state: EconomicState,
+
+    /// Continuous dividend yield of the equity index ($q$).
+    pub dividend_yield: ModelParameter,
+
+    /// Foreign short rate of the FX pair ($r_f$).
+    pub foreign_rate: ModelParameter,
+
+    /// Correlation matrix of the risk factor drivers.
+    pub correlation: [[f64; 4]; 4],
+
+    /// Market price of risk of each driver ($\lambda$), used under [Measure::RealWorld].
+    pub market_price_of_risk: [f64; 4],
+
+    /// The measure to generate scenarios under.
+    pub measure: Measure,
+}
+
+/// Scenarios produced by the [EconomicScenarioGenerator].
+///
+/// Each field is indexed as `[scenario][time step]`.
+pub struct EconomicScenarios {
+    /// The measure the scenarios were generated under.
+    pub measure: Measure,
+
+    /// Vector of time points.
+    pub times: Vec<f64>,
+
+    /// Short rate paths.
+    pub short_rates: Vec<Vec<f64>>,
+
+    /// Equity index paths.
+    pub equity: Vec<Vec<f64>>,
+
+    /// Equity variance paths (only for the Heston equity model).
+    pub equity_variance: Option<Vec<Vec<f64>>>,
+
+    /// FX rate paths.
+    pub fx: Vec<Vec<f64>>,
+
+    /// Inflation rate paths.
+    pub inflation: Vec<Vec<f64>>,
+
+    /// Price index (CPI) paths, normalised to 1 at the initial time.
+    pub price_index: Vec<Vec<f64>>,
+}
+
+/// State of a single simulated scenario.
+struct ScenarioPath {
+    short_rate: Vec<f64>,
+    equity: Vec<f64>,
+    variance: Vec<f64>,
+    fx: Vec<f64>,
+    inflation: Vec<f64>,
+    price_index: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl EconomicScenarioGenerator {
+    /// Create a new economic scenario generator, under the risk-neutral measure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the correlation matrix is not symmetric positive definite.
+    pub fn new(
+        rates: HullWhite,
+        equity: EquityModel,
+        fx: GeometricBrownianMotion,
+        inflation: OrnsteinUhlenbeck,
+        initial_state: EconomicState,
+        correlation: [[f64; 4]; 4],
+    ) -> Self {
+        let esg = Self {
+            rates,
+            equity,
+            fx,
+            inflation,
+            initial_state,
+            dividend_yield: 0.0.into(),
+            foreign_rate: 0.0.into(),
+            correlation,
+            market_price_of_risk: [0.0; 4],
+            measure: Measure::RiskNeutral,
+        };
+
+        esg.cholesky();
+
+        esg
+    }
+
+    /// Set the dividend yield of the equity index.
+    pub fn with_dividend_yield(mut self, dividend_yield: impl Into<ModelParameter>) -> Self {
+        self.dividend_yield = dividend_yield.into();
+        self
+    }
+
+    /// Set the foreign short rate of the FX pair.
+    pub fn with_foreign_rate(mut self, foreign_rate: impl Into<ModelParameter>) -> Self {
+        self.foreign_rate = foreign_rate.into();
+        self
+    }
+
+    /// Set the market price of risk of each driver.
+    pub fn with_market_price_of_risk(mut self, market_price_of_risk: [f64; 4]) -> Self {
+        self.market_price_of_risk = market_price_of_risk;
+        self
+    }
+
+    /// Set the measure to generate scenarios under.
+    pub fn with_measure(mut self, measure: Measure) -> Self {
+        self.measure = measure;
+        self
+    }
+
+    /// Lower Cholesky factor of the correlation matrix.
+    fn cholesky(&self) -> Matrix4<f64> {
+        let c = &self.correlation;
+        let matrix = Matrix4::from_fn(|i, j| c[i][j]);
+
+        assert!(
+            matrix.relative_eq(&matrix.transpose(), 1e-12, 1e-12),
+            "Correlation matrix must be symmetric."
+        );
+
+        matrix
+            .cholesky()
+            .expect("Correlation matrix must be positive definite.")
+            .l()
+    }
+
+    /// Generate scenarios.
+    ///
+    /// # Arguments:
+    /// * `t_n` - The horizon (in years).
+    /// * `n_steps` - The number of time steps between `0` and `t_n`.
+    /// * `m_scenarios` - How many scenarios to simulate.
+    /// * `seed` - Optional seed; scenario `i` uses `seed + i` so the output
+    ///   is reproducible regardless of parallel scheduling.
+    pub fn generate(
+        &self,
+        t_n: f64,
+        n_steps: usize,
+        m_scenarios: usize,
+        seed: Option<u64>,
+    ) -> EconomicScenarios {
+        assert!(t_n > 0.0 && n_steps > 0);
+
+        let dt = t_n / n_steps as f64;
+        let times: Vec<f64> = (0..=n_steps).map(|t| dt * t as f64).collect();
+        let lower = self.cholesky();
+
+        let paths: Vec<ScenarioPath> = (0..m_scenarios)
+            .into_par_iter()
+            .map(|i| {
+                let mut rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(i as u64)),
+                    None => StdRng::from_entropy(),
+                };
+
+                self.simulate_scenario(&times, &lower, &mut rng)
+            })
+            .collect();
+
+        let mut scenarios = EconomicScenarios {
+            measure: self.measure,
+            times,
+            short_rates: Vec::with_capacity(m_scenarios),
+            equity: Vec::with_capacity(m_scenarios),
+            equity_variance: match self.equity {
+                EquityModel::Heston { .. } => Some(Vec::with_capacity(m_scenarios)),
+                EquityModel::GeometricBrownianMotion(_) => None,
+            },
+            fx: Vec::with_capacity(m_scenarios),
+            inflation: Vec::with_capacity(m_scenarios),
+            price_index: Vec::with_capacity(m_scenarios),
+        };
+
+        for path in paths {
+            scenarios.short_rates.push(path.short_rate);
+            scenarios.equity.push(path.equity);
+            scenarios.fx.push(path.fx);
+            scenarios.inflation.push(path.inflation);
+            scenarios.price_index.push(path.price_index);
+
+            if let Some(variance) = scenarios.equity_variance.as_mut() {
+                variance.push(path.variance);
+            }
+        }
+
+        scenarios
+    }
+
+    /// Simulate a single scenario.
+    fn simulate_scenario(
+        &self,
+        times: &[f64],
+        lower: &Matrix4<f64>,
+        rng: &mut StdRng,
+    ) -> ScenarioPath {
+        let n_steps = times.len() - 1;
+        let s = &self.initial_state;
+
+        let mut path = ScenarioPath {
+            short_rate: vec![s.short_rate; n_steps + 1],
+            equity: vec![s.equity; n_steps + 1],
+            variance: vec![0.0; n_steps + 1],
+            fx: vec![s.fx; n_steps + 1],
+            inflation: vec![s.inflation; n_steps + 1],
+            price_index: vec![1.0; n_steps + 1],
+        };
+
+        if let EquityModel::Heston(model) = &self.equity {
+            path.variance[0] = model.initial_variance.0(0.0);
+        }
+
+        // Real-world increments, dW^Q = dW^P + lambda dt.
+        let lambda = match self.measure {
+            Measure::RiskNeutral => Vector4::zeros(),
+            Measure::RealWorld => Vector4::from(self.market_price_of_risk),
+        };
+
+        for k in 0..n_steps {
+            let (t, dt) = (times[k], times[k + 1] - times[k]);
+            let sqrt_dt = dt.sqrt();
+
+            let z = Vector4::from_fn(|_, _| StandardNormal.sample(rng));
+            let dW = lower * z * sqrt_dt + lambda * dt;
+
+            // Short rate (Hull-White, Euler).
+            let r = path.short_rate[k];
+            path.short_rate[k + 1] =
+                r + self.rates.drift(r, t) * dt + self.rates.diffusion(r, t) * dW[0];
+
+            // Equity (log-Euler, with full truncation for the Heston variance).
+            let x = path.equity[k];
+            let mu = r - self.dividend_yield.0(t);
+            path.equity[k + 1] = match &self.equity {
+                EquityModel::GeometricBrownianMotion(gbm) => {
+                    let sigma = gbm.diffusion(x, t) / x;
+                    x * ((mu - 0.5 * sigma * sigma) * dt + sigma * dW[1]).exp()
+                }
+                EquityModel::Heston(model) => {
+                    let v = path.variance[k].max(0.0);
+                    let rho = model.correlation.0(t);
+                    let kappa = model.mean_reversion_rate.0(t);
+                    let theta = model.long_run_variance.0(t);
+                    let xi = model.volatility_of_volatility.0(t);
+
+                    let independent: f64 = StandardNormal.sample(rng);
+                    let dZ = rho * dW[1] + (1.0 - rho * rho).sqrt() * independent * sqrt_dt;
+
+                    path.variance[k + 1] =
+                        path.variance[k] + kappa * (theta - v) * dt + xi * v.sqrt() * dZ;
+
+                    x * ((mu - 0.5 * v) * dt + v.sqrt() * dW[1]).exp()
+                }
+            };
+
+            // FX (log-Euler).
+            let f = path.fx[k];
+            let (mu, sigma) = (r - self.foreign_rate.0(t), self.fx.diffusion(f, t) / f);
+            path.fx[k + 1] = f * ((mu - 0.5 * sigma * sigma) * dt + sigma * dW[2]).exp();
+
+            // Inflation rate (Ornstein-Uhlenbeck, Euler) and price index.
+            let i = path.inflation[k];
+            path.inflation[k + 1] =
+                i + self.inflation.drift(i, t) * dt + self.inflation.diffusion(i, t) * dW[3];
+            path.price_index[k + 1] = path.price_index[k] * (i * dt).exp();
+        }
+
+        path
+    }
+}
+
+impl EconomicScenarios {
+    /// Number of scenarios.
+    pub fn len(&self) -> usize {
+        self.short_rates.len()
+    }
+
+    /// Returns `true` if there are no scenarios.
+    pub fn is_empty(&self) -> bool {
+        self.short_rates.is_empty()
+    }
+
+    /// Pathwise stochastic discount factors, $\exp(-\int_0^t r_s ds)$,
+    /// using the trapezoidal rule on the short rate paths.
+    pub fn discount_factors(&self) -> Vec<Vec<f64>> {
+        pathwise_discount_factors(&self.times, &self.short_rates)
+    }
+
+    /// Equity index paths expressed in real (inflation-adjusted) terms.
+    pub fn real_equity(&self) -> Vec<Vec<f64>> {
+        self.equity
+            .iter()
+            .zip(&self.price_index)
+            .map(|(equity, cpi)| equity.iter().zip(cpi).map(|(s, p)| s / p).collect())
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_economic_scenario_generator {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    const CORRELATION: [[f64; 4]; 4] = [
+        [1.0, -0.2, 0.1, 0.3],
+        [-0.2, 1.0, 0.2, 0.0],
+        [0.1, 0.2, 1.0, 0.0],
+        [0.3, 0.0, 0.0, 1.0],
+    ];
+
+    const INITIAL_STATE: EconomicState = EconomicState {
+        short_rate: 0.03,
+        equity: 100.0,
+        fx: 1.1,
+        inflation: 0.02,
+    };
+
+    /// Terminal values of `paths`, discounted at the simulated short rates.
+    fn deflated(scenarios: &EconomicScenarios, paths: &[Vec<f64>]) -> Vec<f64> {
+        let dt = scenarios.times[1];
+
+        paths
+            .iter()
+            .zip(&scenarios.short_rates)
+            .map(|(path, rates)| {
+                let n = path.len() - 1;
+                path[n] * (-rates[..n].iter().sum::<f64>() * dt).exp()
+            })
+            .collect()
+    }
+
+    fn esg(equity: EquityModel) -> EconomicScenarioGenerator {
+        EconomicScenarioGenerator::new(
+            HullWhite::new(0.5, 0.01, 0.015),
+            equity,
+            GeometricBrownianMotion::new(0.0, 0.1),
+            OrnsteinUhlenbeck::new(0.02, 0.01, 1.0),
+            INITIAL_STATE,
+            CORRELATION,
+        )
+    }
+
+    #[test]
+    fn test_esg_gbm() {
+        let esg = esg(EquityModel::GeometricBrownianMotion(
+            GeometricBrownianMotion::new(0.05, 0.2),
+        ));
+
+        let scenarios = esg
+            .with_dividend_yield(0.02)
+            .with_foreign_rate(0.01)
+            .generate(5.0, 60, 5000, Some(42));
+
+        assert_eq!(scenarios.len(), 5000);
+        assert_eq!(scenarios.times.len(), 61);
+        assert!(scenarios.equity_variance.is_none());
+
+        // Discounted equity and FX are martingales, up to the yields paid:
+        // E[D_T S_T] = S_0 exp(-q T), E[D_T X_T] = X_0 exp(-r_f T).
+        let equity = deflated(&scenarios, &scenarios.equity);
+        assert_approx_equal!(equity.mean(), 100.0 * (-0.1_f64).exp(), 2.0);
+
+        let fx = deflated(&scenarios, &scenarios.fx);
+        assert_approx_equal!(fx.mean(), 1.1 * (-0.05_f64).exp(), 0.01);
+
+        // The short rate reverts to theta / alpha = 0.03.
+        let rates: Vec<f64> = scenarios.short_rates.iter().map(|p| p[60]).collect();
+        assert_approx_equal!(rates.mean(), 0.03, 1e-3);
+
+        // Discount factors start at one and are positive.
+        let dfs = scenarios.discount_factors();
+        assert!(dfs.iter().all(|p| p[0] == 1.0 && p.iter().all(|df| *df > 0.0)));
+    }
+
+    #[test]
+    fn test_esg_heston() {
+        let heston = Heston::new(0.04, 0.04, 2.0, -0.7, 0.3);
+        let esg = esg(EquityModel::Heston(heston));
+
+        let scenarios = esg.generate(2.0, 24, 1000, Some(7));
+
+        let variance = scenarios.equity_variance.as_ref().unwrap();
+        assert_eq!(variance.len(), 1000);
+        assert!(scenarios.equity.iter().flatten().all(|s| *s > 0.0));
+    }
+
+    #[test]
+    fn test_esg_real_world() {
+        let equity = || {
+            EquityModel::GeometricBrownianMotion(GeometricBrownianMotion::new(0.03, 0.2))
+        };
+        let lambda = [-0.5, 0.25, 0.0, 0.0];
+
+        let Q = esg(equity())
+            .with_market_price_of_risk(lambda)
+            .generate(5.0, 60, 5000, Some(42));
+        let P = esg(equity())
+            .with_market_price_of_risk(lambda)
+            .with_measure(Measure::RealWorld)
+            .generate(5.0, 60, 5000, Some(42));
+
+        assert_eq!(Q.measure, Measure::RiskNeutral);
+        assert_eq!(P.measure, Measure::RealWorld);
+
+        // Equity earns a risk premium of lambda * sigma = 0.25 * 0.2 over the short rate.
+        assert_approx_equal!(deflated(&Q, &Q.equity).mean(), 100.0, 2.0);
+        assert_approx_equal!(
+            deflated(&P, &P.equity).mean(),
+            100.0 * (0.25_f64).exp(),
+            3.0
+        );
+
+        // Short rate reverts to (theta + lambda * sigma) / alpha = 0.02.
+        let rates: Vec<f64> = P.short_rates.iter().map(|p| p[60]).collect();
+        assert_approx_equal!(rates.mean(), 0.02 + 0.01 * (-2.5_f64).exp(), 1e-3);
+
+        // Drivers without a risk premium are unchanged.
+        assert_eq!(P.inflation, Q.inflation);
+        assert_approx_equal!(deflated(&P, &P.fx).mean(), 1.1, 0.01);
+    }
+
+    #[test]
+    fn test_esg_seed_reproducible() {
+        let esg = esg(EquityModel::GeometricBrownianMotion(
+            GeometricBrownianMotion::new(0.05, 0.2),
+        ));
+
+        let first = esg.generate(1.0, 12, 100, Some(1));
+        let second = esg.generate(1.0, 12, 100, Some(1));
+
+        assert_eq!(first.equity, second.equity);
+        assert_eq!(first.price_index, second.price_index);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive definite")]
+    fn test_esg_invalid_correlation() {
+        let mut correlation = CORRELATION;
+        correlation[0][1] = 1.5;
+        correlation[1][0] = 1.5;
+
+        EconomicScenarioGenerator::new(
+            HullWhite::new(0.5, 0.01, 0.015),
+            EquityModel::GeometricBrownianMotion(GeometricBrownianMotion::new(0.05, 0.2)),
+            GeometricBrownianMotion::new(0.0, 0.1),
+            OrnsteinUhlenbeck::new(0.02, 0.01, 1.0),
+            INITIAL_STATE,
+            correlation,
+        );
+    }
+}
@@ -0,0 +1,481 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Estimation of diffusion parameters from an observed time series.
+//!
+//! The observations are assumed equally spaced, `dt` apart (in years).
+//! Standard errors are asymptotic, from the delta method.
+//!
+//! - [`GeometricBrownianMotion`]: exact MLE from the Gaussian log-returns.
+//! - [`OrnsteinUhlenbeck`]: exact MLE, through the AR(1) representation
+//!   $x_{i+1} = a + b x_i + \varepsilon_i$ with $b = e^{-\theta \Delta t}$.
+//! - [`CoxIngersollRoss`]: quasi-MLE. The conditional mean is the same AR(1)
+//!   as the OU process, fitted by least squares weighted by $1 / x_i$, and
+//!   $\sigma$ by matching the exact conditional variance.
+//!
+//! ```
+//! # use RustQuant_stochastics::*;
+//! let rates = [
+//!     0.050, 0.047, 0.045, 0.042, 0.041, 0.038, 0.037, 0.036, 0.034, 0.034, 0.033, 0.032,
+//! ];
+//! let estimate = OrnsteinUhlenbeck::estimate(&rates, 1.0 / 252.0).unwrap();
+//!
+//! let theta = estimate.parameter("theta").unwrap();
+//! println!("theta = {} ({})", theta.value, theta.standard_error);
+//!
+//! // The fitted process can be simulated directly.
+//! let process: OrnsteinUhlenbeck = estimate.process;
+//! ```
+
+use crate::{CoxIngersollRoss, GeometricBrownianMotion, OrnsteinUhlenbeck};
+use std::f64::consts::PI;
+use RustQuant_error::RustQuantError;
+
+/// A parameter estimate and its standard error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterEstimate {
+    /// Parameter name, as in the process struct.
+    pub name: &'static str,
+
+    /// Point estimate.
+    pub value: f64,
+
+    /// Asymptotic standard error.
+    pub standard_error: f64,
+}
+
+/// A process fitted to historical data.
+#[derive(Debug)]
+pub struct ProcessEstimate<P> {
+    /// The fitted process.
+    pub process: P,
+
+    /// Parameter estimates, in the order of the process' fields.
+    pub parameters: Vec<ParameterEstimate>,
+
+    /// Log-likelihood of the observations at the estimates.
+    pub log_likelihood: f64,
+
+    /// Number of transitions used in the fit.
+    pub transitions: usize,
+}
+
+/// Processes whose parameters can be estimated from a time series.
+pub trait HistoricalEstimation: Sized {
+    /// Fit the process to equally spaced observations, `dt` years apart.
+    fn estimate(observations: &[f64], dt: f64) -> Result<ProcessEstimate<Self>, RustQuantError>;
+}
+
+impl<P> ProcessEstimate<P> {
+    /// Estimate of a parameter, by name.
+    pub fn parameter(&self, name: &str) -> Option<&ParameterEstimate> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name == name)
+    }
+}
+
+impl HistoricalEstimation for GeometricBrownianMotion {
+    fn estimate(observations: &[f64], dt: f64) -> Result<ProcessEstimate<Self>, RustQuantError> {
+        check_inputs(observations, dt, 2)?;
+        if observations.iter().any(|x| *x <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "GBM observations must be positive.".to_string(),
+            ));
+        }
+
+        let returns: Vec<f64> = observations
+            .windows(2)
+            .map(|pair| (pair[1] / pair[0]).ln())
+            .collect();
+        let n = returns.len() as f64;
+
+        let m = returns.iter().sum::<f64>() / n;
+        let v = returns.iter().map(|r| (r - m).powi(2)).sum::<f64>() / n;
+        check_variance(v)?;
+
+        let sigma = (v / dt).sqrt();
+        let mu = m / dt + 0.5 * sigma * sigma;
+
+        // Var(m) = v / n and Var(v) = 2 v^2 / n, independently.
+        let se_sigma = sigma / (2.0 * n).sqrt();
+        let se_mu = (v / n * (1.0 + 0.5 * v)).sqrt() / dt;
+
+        // Density of the prices: the log-returns' density, with the Jacobian of the log.
+        let jacobian: f64 = observations[1..].iter().map(|x| x.ln()).sum();
+        let log_likelihood = -0.5 * n * ((2.0 * PI * v).ln() + 1.0) - jacobian;
+
+        Ok(ProcessEstimate {
+            process: GeometricBrownianMotion::new(mu, sigma),
+            parameters: vec![
+                ParameterEstimate {
+                    name: "mu",
+                    value: mu,
+                    standard_error: se_mu,
+                },
+                ParameterEstimate {
+                    name: "sigma",
+                    value: sigma,
+                    standard_error: se_sigma,
+                },
+            ],
+            log_likelihood,
+            transitions: returns.len(),
+        })
+    }
+}
+
+impl HistoricalEstimation for OrnsteinUhlenbeck {
+    fn estimate(observations: &[f64], dt: f64) -> Result<ProcessEstimate<Self>, RustQuantError> {
+        check_inputs(observations, dt, 3)?;
+
+        let fit = weighted_ar1(observations, |_| 1.0)?;
+        let n = fit.n;
+        let b = fit.b;
+
+        // MLE of the innovation variance.
+        let s2 = fit.weighted_rss / n;
+        check_variance(s2)?;
+
+        let theta = -b.ln() / dt;
+        let mu = fit.a / (1.0 - b);
+        let g = -2.0 * b.ln() / (dt * (1.0 - b * b));
+        let sigma = (s2 * g).sqrt();
+
+        // Delta method, with Var(s2) = 2 s2^2 / n independent of (a, b).
+        let var_a = s2 * fit.inverse[0][0];
+        let var_b = s2 * fit.inverse[1][1];
+        let cov_ab = s2 * fit.inverse[0][1];
+
+        let dmu_da = 1.0 / (1.0 - b);
+        let dmu_db = fit.a / (1.0 - b).powi(2);
+        let dtheta_db = -1.0 / (b * dt);
+        let dg_db = (-2.0 * (1.0 - b * b) / b - 4.0 * b * b.ln()) / (dt * (1.0 - b * b).powi(2));
+        let dsigma_db = s2 * dg_db / (2.0 * sigma);
+        let dsigma_ds2 = sigma / (2.0 * s2);
+
+        let se_mu =
+            (dmu_da * dmu_da * var_a + dmu_db * dmu_db * var_b + 2.0 * dmu_da * dmu_db * cov_ab)
+                .sqrt();
+        let se_theta = dtheta_db.abs() * var_b.sqrt();
+        let se_sigma =
+            (dsigma_db * dsigma_db * var_b + dsigma_ds2 * dsigma_ds2 * 2.0 * s2 * s2 / n).sqrt();
+
+        let log_likelihood = -0.5 * n * ((2.0 * PI * s2).ln() + 1.0);
+
+        Ok(ProcessEstimate {
+            process: OrnsteinUhlenbeck::new(mu, sigma, theta),
+            parameters: vec![
+                ParameterEstimate {
+                    name: "mu",
+                    value: mu,
+                    standard_error: se_mu,
+                },
+                ParameterEstimate {
+                    name: "sigma",
+                    value: sigma,
+                    standard_error: se_sigma,
+                },
+                ParameterEstimate {
+                    name: "theta",
+                    value: theta,
+                    standard_error: se_theta,
+                },
+            ],
+            log_likelihood,
+            transitions: observations.len() - 1,
+        })
+    }
+}
+
+impl HistoricalEstimation for CoxIngersollRoss {
+    fn estimate(observations: &[f64], dt: f64) -> Result<ProcessEstimate<Self>, RustQuantError> {
+        check_inputs(observations, dt, 3)?;
+        if observations.iter().any(|x| *x <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "CIR observations must be positive.".to_string(),
+            ));
+        }
+
+        let fit = weighted_ar1(observations, |x| 1.0 / x)?;
+        let n = fit.n;
+        let b = fit.b;
+
+        let theta = -b.ln() / dt;
+        let mu = fit.a / (1.0 - b);
+
+        // Var(x_{i+1} | x_i) = sigma^2 v_i, with v_i known from theta and mu.
+        let unit_variance =
+            |x: f64| x * (b - b * b) / theta + mu * (1.0 - b).powi(2) / (2.0 * theta);
+        let residuals: Vec<(f64, f64)> = observations
+            .windows(2)
+            .map(|pair| {
+                let error = pair[1] - fit.a - b * pair[0];
+                (error, unit_variance(pair[0]))
+            })
+            .collect();
+
+        let sigma2 = residuals.iter().map(|(e, v)| e * e / v).sum::<f64>() / n;
+        check_variance(sigma2)?;
+        let sigma = sigma2.sqrt();
+
+        // Residual variance per unit weight, for the covariance of (a, b).
+        let s2 = fit.weighted_rss / (n - 2.0);
+        let var_a = s2 * fit.inverse[0][0];
+        let var_b = s2 * fit.inverse[1][1];
+        let cov_ab = s2 * fit.inverse[0][1];
+
+        let dmu_da = 1.0 / (1.0 - b);
+        let dmu_db = fit.a / (1.0 - b).powi(2);
+
+        let se_mu =
+            (dmu_da * dmu_da * var_a + dmu_db * dmu_db * var_b + 2.0 * dmu_da * dmu_db * cov_ab)
+                .sqrt();
+        let se_theta = var_b.sqrt() / (b * dt);
+        let se_sigma = sigma / (2.0 * n).sqrt();
+
+        let log_likelihood = -0.5
+            * residuals
+                .iter()
+                .map(|(e, v)| (2.0 * PI * sigma2 * v).ln() + e * e / (sigma2 * v))
+                .sum::<f64>();
+
+        Ok(ProcessEstimate {
+            process: CoxIngersollRoss::new(mu, sigma, theta),
+            parameters: vec![
+                ParameterEstimate {
+                    name: "mu",
+                    value: mu,
+                    standard_error: se_mu,
+                },
+                ParameterEstimate {
+                    name: "sigma",
+                    value: sigma,
+                    standard_error: se_sigma,
+                },
+                ParameterEstimate {
+                    name: "theta",
+                    value: theta,
+                    standard_error: se_theta,
+                },
+            ],
+            log_likelihood,
+            transitions: observations.len() - 1,
+        })
+    }
+}
+
+/// Weighted least squares fit of $x_{i+1} = a + b x_i$.
+struct Ar1Fit {
+    a: f64,
+    b: f64,
+    n: f64,
+    weighted_rss: f64,
+    /// Inverse of the weighted normal matrix, $(X^T W X)^{-1}$.
+    inverse: [[f64; 2]; 2],
+}
+
+fn weighted_ar1(
+    observations: &[f64],
+    weight: impl Fn(f64) -> f64,
+) -> Result<Ar1Fit, RustQuantError> {
+    let (mut sw, mut swx, mut swxx, mut swy, mut swxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for pair in observations.windows(2) {
+        let (x, y) = (pair[0], pair[1]);
+        let w = weight(x);
+
+        sw += w;
+        swx += w * x;
+        swxx += w * x * x;
+        swy += w * y;
+        swxy += w * x * y;
+    }
+
+    let determinant = sw * swxx - swx * swx;
+    if determinant.abs() <= f64::EPSILON * sw * swxx {
+        return Err(RustQuantError::ComputationError(
+            "Observations are constant; the AR(1) regression is singular.".to_string(),
+        ));
+    }
+
+    let b = (sw * swxy - swx * swy) / determinant;
+    let a = (swy - b * swx) / sw;
+
+    if !(0.0..1.0).contains(&b) || b == 0.0 {
+        return Err(RustQuantError::ComputationError(format!(
+            "No mean reversion in the data: AR(1) coefficient {} is not in (0, 1).",
+            b
+        )));
+    }
+
+    let weighted_rss = observations
+        .windows(2)
+        .map(|pair| weight(pair[0]) * (pair[1] - a - b * pair[0]).powi(2))
+        .sum();
+
+    Ok(Ar1Fit {
+        a,
+        b,
+        n: (observations.len() - 1) as f64,
+        weighted_rss,
+        inverse: [
+            [swxx / determinant, -swx / determinant],
+            [-swx / determinant, sw / determinant],
+        ],
+    })
+}
+
+fn check_inputs(observations: &[f64], dt: f64, minimum: usize) -> Result<(), RustQuantError> {
+    if observations.len() < minimum {
+        return Err(RustQuantError::MissingInput(format!(
+            "At least {} observations are needed, got {}.",
+            minimum,
+            observations.len()
+        )));
+    }
+    if dt <= 0.0 || !dt.is_finite() {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Time step must be positive, got {}.",
+            dt
+        )));
+    }
+    if observations.iter().any(|x| !x.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "Observations must be finite.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_variance(variance: f64) -> Result<(), RustQuantError> {
+    if variance > 0.0 {
+        Ok(())
+    } else {
+        Err(RustQuantError::ComputationError(
+            "Observations have no variance.".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests_estimation {
+    use super::*;
+    use crate::CIRFactor;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+    use RustQuant_utils::assert_approx_equal;
+
+    const DT: f64 = 1.0 / 252.0;
+    const N: usize = 20_000;
+
+    fn assert_within(estimate: &ParameterEstimate, truth: f64) {
+        assert!(
+            (estimate.value - truth).abs() < 4.0 * estimate.standard_error,
+            "{}: {} +/- {} vs {}",
+            estimate.name,
+            estimate.value,
+            estimate.standard_error,
+            truth
+        );
+    }
+
+    #[test]
+    fn test_gbm_estimation() {
+        let (mu, sigma) = (0.08, 0.25);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let mut prices = vec![100.0];
+        for _ in 0..N {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            let last = *prices.last().unwrap();
+            prices.push(last * ((mu - 0.5 * sigma * sigma) * DT + sigma * DT.sqrt() * z).exp());
+        }
+
+        let estimate = GeometricBrownianMotion::estimate(&prices, DT).unwrap();
+        assert_within(estimate.parameter("sigma").unwrap(), sigma);
+        assert_within(estimate.parameter("mu").unwrap(), mu);
+        assert_approx_equal!(
+            estimate.process.sigma.0(0.0),
+            estimate.parameters[1].value,
+            1e-15
+        );
+        assert_eq!(estimate.transitions, N);
+    }
+
+    #[test]
+    fn test_ou_estimation() {
+        let (mu, sigma, theta) = (0.03, 0.01, 2.0);
+        let b = (-theta * DT).exp();
+        let sd = sigma * ((1.0 - b * b) / (2.0 * theta)).sqrt();
+        let mut rng = StdRng::seed_from_u64(2);
+
+        let mut rates = vec![0.05];
+        for _ in 0..N {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            let last = *rates.last().unwrap();
+            rates.push(mu + (last - mu) * b + sd * z);
+        }
+
+        let estimate = OrnsteinUhlenbeck::estimate(&rates, DT).unwrap();
+        assert_within(estimate.parameter("mu").unwrap(), mu);
+        assert_within(estimate.parameter("sigma").unwrap(), sigma);
+        assert_within(estimate.parameter("theta").unwrap(), theta);
+
+        // The MLE maximises the likelihood: perturbing theta lowers it.
+        let log_likelihood = |theta: f64| {
+            let b = (-theta * DT).exp();
+            let s2 = estimate.parameters[1].value.powi(2) * (1.0 - b * b) / (2.0 * theta);
+            let m = estimate.parameters[0].value;
+
+            rates
+                .windows(2)
+                .map(|p| {
+                    let e = p[1] - m - (p[0] - m) * b;
+                    -0.5 * ((2.0 * PI * s2).ln() + e * e / s2)
+                })
+                .sum::<f64>()
+        };
+        let theta_hat = estimate.parameters[2].value;
+        assert_approx_equal!(log_likelihood(theta_hat), estimate.log_likelihood, 1e-6);
+        assert!(log_likelihood(theta_hat * 1.1) < estimate.log_likelihood);
+        assert!(log_likelihood(theta_hat * 0.9) < estimate.log_likelihood);
+    }
+
+    #[test]
+    fn test_cir_estimation() {
+        let (mu, sigma, theta) = (0.04, 0.08, 1.5);
+        let factor = CIRFactor::new(theta, mu, sigma, 0.02);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let mut rates = vec![factor.x_0];
+        for _ in 0..N {
+            let last = *rates.last().unwrap();
+            rates.push(factor.sample(last, DT, &mut rng));
+        }
+
+        let estimate = CoxIngersollRoss::estimate(&rates, DT).unwrap();
+        assert_within(estimate.parameter("mu").unwrap(), mu);
+        assert_within(estimate.parameter("sigma").unwrap(), sigma);
+        assert_within(estimate.parameter("theta").unwrap(), theta);
+    }
+
+    #[test]
+    fn test_estimation_errors() {
+        assert!(OrnsteinUhlenbeck::estimate(&[1.0, 2.0], DT).is_err());
+        assert!(OrnsteinUhlenbeck::estimate(&[1.0; 10], DT).is_err());
+        assert!(GeometricBrownianMotion::estimate(&[1.0, -1.0, 2.0], DT).is_err());
+        assert!(CoxIngersollRoss::estimate(&[0.01, 0.02, 0.03], 0.0).is_err());
+
+        // Explosive series: no mean reversion to estimate.
+        let explosive: Vec<f64> = (0..50).map(|i| 1.1_f64.powi(i)).collect();
+        assert!(OrnsteinUhlenbeck::estimate(&explosive, DT).is_err());
+    }
+}
@@ -51,7 +51,7 @@ impl FractionalBrownianMotion {
 }
 
 /// Method used to generate the Fractional Brownian Motion.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum FractionalProcessGeneratorMethod {
     /// Chooses the Cholesky decomposition method.
     CHOLESKY,
@@ -98,21 +98,23 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise.
     pub fn fgn_cholesky(&self, n: usize, t_n: f64) -> Vec<f64> {
-        let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = rand::thread_rng()
-            .sample_iter::<f64, StandardNormal>(StandardNormal)
-            .take(n)
-            .collect();
-        let noise = DVector::<f64>::from_vec(noise);
-        let noise = (acf_sqrt * noise).transpose() * (1.0 * t_n / n as f64).powf(self.hurst);
-
-        noise.data.as_vec().clone()
+        self.fgn_cholesky_with_rng(n, t_n, &mut rand::thread_rng())
     }
 
     /// Seedable Fractional Gaussian noise.
     pub fn seedable_fgn_cholesky(&self, n: usize, t_n: f64, seed: u64) -> Vec<f64> {
+        self.fgn_cholesky_with_rng(n, t_n, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Fractional Gaussian noise, drawn from the given random number generator.
+    pub fn fgn_cholesky_with_rng<R: Rng + ?Sized>(
+        &self,
+        n: usize,
+        t_n: f64,
+        rng: &mut R,
+    ) -> Vec<f64> {
         let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = StdRng::seed_from_u64(seed)
+        let noise = rng
             .sample_iter::<f64, StandardNormal>(StandardNormal)
             .take(n)
             .collect();
@@ -124,6 +126,11 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise via FFT.
     pub fn fgn_fft(&self, n: usize, t_n: f64) -> Vec<f64> {
+        self.fgn_fft_with_rng(n, t_n, &mut rand::thread_rng())
+    }
+
+    /// Fractional Gaussian noise via FFT, drawn from the given random number generator.
+    pub fn fgn_fft_with_rng<R: Rng + ?Sized>(&self, n: usize, t_n: f64, rng: &mut R) -> Vec<f64> {
         if !(0.0..=1.0).contains(&self.hurst) {
             panic!("Hurst parameter must be between 0 and 1");
         }
@@ -153,9 +160,10 @@ impl FractionalBrownianMotion {
 
         sqrt_eigenvalues.par_mapv_inplace(|x| Complex::new((x.re / (2.0 * n as f64)).sqrt(), x.im));
 
-        let rnd = Array1::<Complex<f64>>::random(
+        let rnd = Array1::<Complex<f64>>::random_using(
             2 * n,
             ComplexDistribution::new(StandardNormal, StandardNormal),
+            rng,
         );
         let fgn = &sqrt_eigenvalues * &rnd;
         let fft_handler = FftHandler::new(2 * n);
@@ -168,6 +176,68 @@ impl FractionalBrownianMotion {
             .mapv(|x: Complex<f64>| (x.re * (n as f64).powf(-self.hurst)) * t_n.powf(self.hurst));
         fgn.to_vec()
     }
+
+    /// Riemann-Liouville fractional Brownian motion via the hybrid scheme
+    /// (Bennedsen, Lunde and Pakkanen, 2017), with $\kappa = 1$:
+    /// $$ Y_t = \sqrt{2H} \int_0^t (t - s)^{H - 1/2} dW_s $$
+    ///
+    /// Returns the increments of the driving Brownian motion $W$ (length `n`)
+    /// and $Y$ on the time grid (length `n + 1`, starting at zero).
+    /// Unlike the other generators, the driving noise is returned so that
+    /// it can be correlated with other processes (e.g. rough volatility models).
+    pub fn hybrid_scheme(&self, n: usize, t_n: f64, rng: &mut StdRng) -> (Vec<f64>, Vec<f64>) {
+        let alpha = self.hurst - 0.5;
+        let dt = t_n / n as f64;
+
+        let (dW, dI): (Vec<f64>, Vec<f64>) = (0..n)
+            .map(|_| hybrid_scheme_increments(alpha, dt, rng))
+            .unzip();
+        let weights = hybrid_scheme_weights(alpha, dt, n);
+
+        let mut Y = vec![0.0; n + 1];
+
+        for i in 1..=n {
+            let convolution: f64 = (2..=i).map(|k| weights[k] * dW[i - k]).sum();
+
+            Y[i] = (2.0 * self.hurst).sqrt() * (dI[i - 1] + convolution);
+        }
+
+        (dW, Y)
+    }
+}
+
+/// Draw the Brownian increment $\Delta W$ over a step of length `dt`, together with
+/// the kernel-weighted increment $\int_0^{dt} (dt - s)^{\alpha} dW_s$ (jointly Gaussian).
+pub(crate) fn hybrid_scheme_increments(alpha: f64, dt: f64, rng: &mut StdRng) -> (f64, f64) {
+    let var_W = dt;
+    let covariance = dt.powf(alpha + 1.0) / (alpha + 1.0);
+    let var_I = dt.powf(2.0 * alpha + 1.0) / (2.0 * alpha + 1.0);
+
+    let z_1: f64 = rng.sample(StandardNormal);
+    let z_2: f64 = rng.sample(StandardNormal);
+
+    let dW = var_W.sqrt() * z_1;
+    let dI = covariance / var_W.sqrt() * z_1
+        + (var_I - covariance * covariance / var_W).max(0.0).sqrt() * z_2;
+
+    (dW, dI)
+}
+
+/// Hybrid scheme weights $(b_k \Delta t)^{\alpha}$ for the kernel $t^{\alpha}$ evaluated
+/// at the optimal points $b_k = \left(\frac{k^{\alpha + 1} - (k - 1)^{\alpha + 1}}{\alpha + 1}\right)^{1 / \alpha}$
+/// of cells `k = 2..=n` back (entries 0 and 1 are unused).
+pub(crate) fn hybrid_scheme_weights(alpha: f64, dt: f64, n: usize) -> Vec<f64> {
+    let mut weights = vec![0.0; n + 1];
+
+    for (k, weight) in weights.iter_mut().enumerate().skip(2) {
+        let k = k as f64;
+        let b_k =
+            ((k.powf(alpha + 1.0) - (k - 1.0).powf(alpha + 1.0)) / (alpha + 1.0)).powf(1.0 / alpha);
+
+        *weight = (b_k * dt).powf(alpha);
+    }
+
+    weights
 }
 
 impl StochasticProcess for FractionalBrownianMotion {
@@ -187,8 +257,13 @@ impl StochasticProcess for FractionalBrownianMotion {
         vec![self.hurst]
     }
 
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
         let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        config.assert_equal_steps("Fractional Brownian motion");
 
         assert!(t_0 < t_n);
 
@@ -198,10 +273,15 @@ impl StochasticProcess for FractionalBrownianMotion {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng: StdRng = config.path_rng(i);
             let fgn = match self.method {
-                FractionalProcessGeneratorMethod::FFT => self.fgn_fft(n_steps, t_n),
-                FractionalProcessGeneratorMethod::CHOLESKY => self.fgn_cholesky(n_steps, t_n),
+                FractionalProcessGeneratorMethod::FFT => {
+                    self.fgn_fft_with_rng(n_steps, t_n, &mut rng)
+                }
+                FractionalProcessGeneratorMethod::CHOLESKY => {
+                    self.fgn_cholesky_with_rng(n_steps, t_n, &mut rng)
+                }
             };
 
             for t in 0..n_steps {
@@ -212,46 +292,9 @@ impl StochasticProcess for FractionalBrownianMotion {
         };
 
         if parallel {
-            paths.par_iter_mut().for_each(path_generator);
+            paths.par_iter_mut().enumerate().for_each(path_generator);
         } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
-    }
-
-    fn seedable_euler_maruyama(
-        &self,
-        x_0: f64,
-        t_0: f64,
-        t_n: f64,
-        n_steps: usize,
-        m_paths: usize,
-        parallel: bool,
-        seed: u64,
-    ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let fgn = self.seedable_fgn_cholesky(n_steps, t_n, seed);
-
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
+            paths.iter_mut().enumerate().for_each(path_generator);
         }
 
         Trajectories { times, paths }
@@ -320,6 +363,20 @@ mod test_fractional_brownian_motion {
         }
     }
 
+    #[test]
+    fn test_hybrid_scheme() {
+        let fbm = FractionalBrownianMotion::new(0.1, FractionalProcessGeneratorMethod::FFT);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let Y_T: Vec<f64> = (0..5000)
+            .map(|_| *fbm.hybrid_scheme(100, 2.0, &mut rng).1.last().unwrap())
+            .collect();
+
+        // E[Y_T] = 0, V[Y_T] = T^{2H}
+        assert_approx_equal!(Y_T.mean(), 0.0, 0.05);
+        assert_approx_equal!(Y_T.variance(), 2.0_f64.powf(0.2), 0.05);
+    }
+
     #[test]
     fn test_brownian_motion() {
         let fbm = FractionalBrownianMotion::new(0.7, FractionalProcessGeneratorMethod::FFT);
@@ -14,6 +14,7 @@ use crate::{
     fractional_brownian_motion::FractionalBrownianMotion,
     process::{StochasticProcess, Trajectories},
 };
+use rand::rngs::StdRng;
 use rayon::prelude::*;
 
 /// Struct containing the Ornstein-Uhlenbeck process parameters.
@@ -79,25 +80,15 @@ impl StochasticProcess for FractionalOrnsteinUhlenbeck {
         ]
     }
 
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
         let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        config.assert_equal_steps("The fractional Ornstein-Uhlenbeck process");
 
-        let fgn = match self.method {
-            FractionalProcessGeneratorMethod::CHOLESKY => {
-                let fbm = FractionalBrownianMotion::new(
-                    self.hurst,
-                    FractionalProcessGeneratorMethod::CHOLESKY,
-                );
-                FractionalBrownianMotion::fgn_cholesky(&fbm, n_steps, t_n)
-            }
-            FractionalProcessGeneratorMethod::FFT => {
-                let fbm = FractionalBrownianMotion::new(
-                    self.hurst,
-                    FractionalProcessGeneratorMethod::FFT,
-                );
-                FractionalBrownianMotion::fgn_fft(&fbm, n_steps, t_n)
-            }
-        };
+        let fbm = FractionalBrownianMotion::new(self.hurst, self.method);
 
         let dt: f64 = (t_n - t_0) / (n_steps as f64);
 
@@ -105,7 +96,17 @@ impl StochasticProcess for FractionalOrnsteinUhlenbeck {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng: StdRng = config.path_rng(i);
+            let fgn = match self.method {
+                FractionalProcessGeneratorMethod::CHOLESKY => {
+                    fbm.fgn_cholesky_with_rng(n_steps, t_n, &mut rng)
+                }
+                FractionalProcessGeneratorMethod::FFT => {
+                    fbm.fgn_fft_with_rng(n_steps, t_n, &mut rng)
+                }
+            };
+
             for t in 0..n_steps {
                 path[t + 1] = path[t]
                     + self.drift(path[t], times[t]) * dt
@@ -114,9 +115,9 @@ impl StochasticProcess for FractionalOrnsteinUhlenbeck {
         };
 
         if parallel {
-            paths.par_iter_mut().for_each(path_generator);
+            paths.par_iter_mut().enumerate().for_each(path_generator);
         } else {
-            paths.iter_mut().for_each(path_generator);
+            paths.iter_mut().enumerate().for_each(path_generator);
         }
 
         Trajectories { times, paths }
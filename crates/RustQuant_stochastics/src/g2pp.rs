@@ -0,0 +1,252 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::two_factor_short_rate::{DiscountFunction, TwoFactorShortRateModel};
+use rand::{rngs::StdRng, Rng};
+use rand_distr::StandardNormal;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::sync::Arc;
+
+/// Struct containing the G2++ (two-factor additive Gaussian) model parameters
+/// (Brigo and Mercurio, 2006):
+/// $$
+/// \begin{aligned}
+/// r(t) &= x(t) + y(t) + \varphi(t) \\\\
+/// dx(t) &= -a x(t) dt + \sigma dW_1(t) \\\\
+/// dy(t) &= -b y(t) dt + \eta dW_2(t)
+/// \end{aligned}
+/// $$
+/// with $x(0) = y(0) = 0$ and $dW_1 dW_2 = \rho dt$.
+/// The factors are simulated with their exact Gaussian transition.
+#[derive(Clone)]
+pub struct G2PlusPlus {
+    /// Mean reversion of the first factor ($a$).
+    pub a: f64,
+
+    /// Volatility of the first factor ($\sigma$).
+    pub sigma: f64,
+
+    /// Mean reversion of the second factor ($b$).
+    pub b: f64,
+
+    /// Volatility of the second factor ($\eta$).
+    pub eta: f64,
+
+    /// Correlation between the factors ($\rho$).
+    pub rho: f64,
+
+    /// Initial discount curve, $P^M(0, t)$.
+    pub discount_curve: DiscountFunction,
+}
+
+impl G2PlusPlus {
+    /// Create a new G2++ model, fitted to an initial discount curve.
+    ///
+    /// # Arguments:
+    /// * `a` - Mean reversion of the first factor.
+    /// * `sigma` - Volatility of the first factor.
+    /// * `b` - Mean reversion of the second factor.
+    /// * `eta` - Volatility of the second factor.
+    /// * `rho` - Correlation between the factors.
+    /// * `discount_curve` - Initial discount factors, $t \mapsto P^M(0, t)$.
+    pub fn new<F>(a: f64, sigma: f64, b: f64, eta: f64, rho: f64, discount_curve: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        assert!(a > 0.0 && b > 0.0 && sigma >= 0.0 && eta >= 0.0);
+        assert!((-1.0..=1.0).contains(&rho));
+
+        Self {
+            a,
+            sigma,
+            b,
+            eta,
+            rho,
+            discount_curve: Arc::new(discount_curve),
+        }
+    }
+
+    /// Variance of $\int_t^T (x(u) + y(u)) du$ given the factors at $t$, as a function of $\tau = T - t$.
+    fn integrated_variance(&self, tau: f64) -> f64 {
+        let (a, b, sigma, eta, rho) = (self.a, self.b, self.sigma, self.eta, self.rho);
+
+        let single = |k: f64, vol: f64| {
+            vol * vol / (k * k)
+                * (tau + 2.0 / k * (-k * tau).exp() - 0.5 / k * (-2.0 * k * tau).exp() - 1.5 / k)
+        };
+        let cross = 2.0 * rho * sigma * eta / (a * b)
+            * (tau + ((-a * tau).exp() - 1.0) / a + ((-b * tau).exp() - 1.0) / b
+                - ((-(a + b) * tau).exp() - 1.0) / (a + b));
+
+        single(a, sigma) + single(b, eta) + cross
+    }
+
+    /// Price at time zero of a European call with expiry `expiry` and strike
+    /// `strike` on the zero-coupon bond maturing at `maturity`.
+    ///
+    /// Caplets and floorlets are puts and calls on zero-coupon bonds, so this
+    /// gives closed-form targets for calibrating the volatility parameters.
+    pub fn zero_coupon_bond_call(&self, expiry: f64, maturity: f64, strike: f64) -> f64 {
+        let (a, b, sigma, eta, rho) = (self.a, self.b, self.sigma, self.eta, self.rho);
+        let (T, S) = (expiry, maturity);
+
+        let variance = sigma * sigma / (2.0 * a.powi(3))
+            * (1.0 - (-a * (S - T)).exp()).powi(2)
+            * (1.0 - (-2.0 * a * T).exp())
+            + eta * eta / (2.0 * b.powi(3))
+                * (1.0 - (-b * (S - T)).exp()).powi(2)
+                * (1.0 - (-2.0 * b * T).exp())
+            + 2.0 * rho * sigma * eta / (a * b * (a + b))
+                * (1.0 - (-a * (S - T)).exp())
+                * (1.0 - (-b * (S - T)).exp())
+                * (1.0 - (-(a + b) * T).exp());
+        let Sigma = variance.sqrt();
+
+        let (P_T, P_S) = ((self.discount_curve)(T), (self.discount_curve)(S));
+        let N = Normal::new(0.0, 1.0).unwrap();
+        let d = (P_S / (strike * P_T)).ln() / Sigma + 0.5 * Sigma;
+
+        P_S * N.cdf(d) - strike * P_T * N.cdf(d - Sigma)
+    }
+}
+
+impl TwoFactorShortRateModel for G2PlusPlus {
+    fn discount_curve(&self) -> &DiscountFunction {
+        &self.discount_curve
+    }
+
+    fn initial_factors(&self) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    fn step(&self, x: f64, y: f64, dt: f64, rng: &mut StdRng) -> (f64, f64) {
+        let (a, b, sigma, eta, rho) = (self.a, self.b, self.sigma, self.eta, self.rho);
+
+        let std_x = sigma * ((1.0 - (-2.0 * a * dt).exp()) / (2.0 * a)).sqrt();
+        let std_y = eta * ((1.0 - (-2.0 * b * dt).exp()) / (2.0 * b)).sqrt();
+        let covariance = rho * sigma * eta * (1.0 - (-(a + b) * dt).exp()) / (a + b);
+        let correlation = if std_x * std_y > 0.0 {
+            covariance / (std_x * std_y)
+        } else {
+            0.0
+        };
+
+        let z_1: f64 = rng.sample(StandardNormal);
+        let z_2: f64 = rng.sample(StandardNormal);
+
+        (
+            x * (-a * dt).exp() + std_x * z_1,
+            y * (-b * dt).exp()
+                + std_y * (correlation * z_1 + (1.0 - correlation * correlation).sqrt() * z_2),
+        )
+    }
+
+    fn shift(&self, t: f64) -> f64 {
+        let (a, b, sigma, eta, rho) = (self.a, self.b, self.sigma, self.eta, self.rho);
+        let (decay_a, decay_b) = (1.0 - (-a * t).exp(), 1.0 - (-b * t).exp());
+
+        self.instantaneous_forward(t)
+            + sigma * sigma / (2.0 * a * a) * decay_a * decay_a
+            + eta * eta / (2.0 * b * b) * decay_b * decay_b
+            + rho * sigma * eta / (a * b) * decay_a * decay_b
+    }
+
+    fn bond_price(&self, t: f64, maturity: f64, x: f64, y: f64) -> f64 {
+        let tau = maturity - t;
+        let curve = &self.discount_curve;
+
+        let convexity = 0.5
+            * (self.integrated_variance(tau) - self.integrated_variance(maturity)
+                + self.integrated_variance(t));
+        let B_a = (1.0 - (-self.a * tau).exp()) / self.a;
+        let B_b = (1.0 - (-self.b * tau).exp()) / self.b;
+
+        curve(maturity) / curve(t) * (convexity - B_a * x - B_b * y).exp()
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.a, self.sigma, self.b, self.eta, self.rho]
+    }
+
+    fn set_parameters(&mut self, parameters: &[f64]) {
+        assert_eq!(parameters.len(), 5);
+
+        (self.a, self.sigma, self.b, self.eta, self.rho) = (
+            parameters[0],
+            parameters[1],
+            parameters[2],
+            parameters[3],
+            parameters[4],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_g2pp {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Upward sloping curve: r(t) = 0.02 + 0.01 (1 - exp(-t)).
+    fn curve(t: f64) -> f64 {
+        (-(0.03 * t - 0.01 * (1.0 - (-t).exp()))).exp()
+    }
+
+    fn g2pp() -> G2PlusPlus {
+        G2PlusPlus::new(0.5, 0.01, 0.1, 0.008, -0.7, curve)
+    }
+
+    #[test]
+    fn test_g2pp_fits_initial_curve() {
+        let model = g2pp();
+
+        for T in [0.5, 1.0, 5.0, 10.0, 30.0] {
+            assert_approx_equal!(model.bond_price(0.0, T, 0.0, 0.0), curve(T), 1e-12);
+        }
+
+        // r(0) is the instantaneous forward at zero.
+        assert_approx_equal!(model.shift(0.0), 0.02, 1e-6);
+    }
+
+    #[test]
+    fn test_g2pp_monte_carlo() {
+        let model = g2pp();
+        let paths = model.simulate(5.0, 100, 20000, Some(42));
+        let discount_factors = paths.discount_factors();
+
+        // E[exp(-int_0^T r)] = P(0, T).
+        let D_T: Vec<f64> = discount_factors.iter().map(|d| d[100]).collect();
+        assert_approx_equal!(D_T.mean(), curve(5.0), 5e-4);
+
+        // Call on the 10Y zero-coupon bond, expiring in 5Y, struck at the forward.
+        let K = curve(10.0) / curve(5.0);
+        let payoffs: Vec<f64> = (0..paths.x.len())
+            .map(|i| {
+                let P = model.bond_price(5.0, 10.0, paths.x[i][100], paths.y[i][100]);
+                discount_factors[i][100] * (P - K).max(0.0)
+            })
+            .collect();
+
+        let call = model.zero_coupon_bond_call(5.0, 10.0, K);
+        assert!(call > 0.0);
+        assert_approx_equal!(payoffs.mean(), call, 5e-4);
+    }
+
+    #[test]
+    fn test_g2pp_parameters() {
+        let mut model = g2pp();
+        let mut parameters = model.parameters();
+        parameters[1] = 0.02;
+        model.set_parameters(&parameters);
+
+        // The curve fit does not depend on the volatility parameters.
+        assert_eq!(model.sigma, 0.02);
+        assert_approx_equal!(model.bond_price(0.0, 7.0, 0.0, 0.0), curve(7.0), 1e-12);
+    }
+}
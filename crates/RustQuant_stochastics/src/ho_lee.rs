@@ -15,7 +15,7 @@ pub struct HoLee {
     /// The diffusion, or instantaneous volatility ($\sigma$).
     pub sigma: ModelParameter,
 
-    /// Non-negative time-varying mean reversion function ($\theta_t$)
+    /// Time-varying drift ($\theta_t$).
     pub theta: ModelParameter,
 }
 
@@ -31,7 +31,6 @@ impl HoLee {
 
 impl StochasticProcess for HoLee {
     fn drift(&self, _x: f64, t: f64) -> f64 {
-        assert!(self.theta.0(t) >= 0.0);
         (self.theta.0)(t)
     }
 
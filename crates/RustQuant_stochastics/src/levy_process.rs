@@ -0,0 +1,111 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! `LevyProcess` is the base trait for pure-jump Lévy processes.
+//!
+//! A Lévy process $X_t$ is fully described by its characteristic exponent
+//! $\psi$, where $\mathbb{E}\left[e^{iuX_t}\right] = e^{t \psi(u)}$.
+//! Increments are independent and stationary, so paths are simulated
+//! exactly on the time grid rather than by Euler-Maruyama.
+
+use crate::process::{StochasticProcessConfig, Trajectories};
+use num::Complex;
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Trait to implement Lévy processes.
+pub trait LevyProcess: Sync {
+    /// Characteristic exponent $\psi(u)$ of the process.
+    fn characteristic_exponent(&self, u: Complex<f64>) -> Complex<f64>;
+
+    /// Sample `n_steps` independent increments over steps of length `dt`.
+    fn sample_increments(&self, dt: f64, n_steps: usize, rng: &mut StdRng) -> Vec<f64>;
+
+    /// Characteristic function of $X_t$:
+    /// $$ \phi(u, t) = \mathbb{E}\left[e^{iuX_t}\right] = e^{t \psi(u)} $$
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        (t * self.characteristic_exponent(u)).exp()
+    }
+
+    /// Drift correction $\omega = -\psi(-i)$, so that
+    /// $e^{\omega t + X_t}$ is a martingale.
+    fn martingale_correction(&self) -> f64 {
+        let i: Complex<f64> = Complex::i();
+
+        -self.characteristic_exponent(-i).re
+    }
+
+    /// Characteristic function of $\ln S_t$ for the exponential Lévy model
+    /// $$ S_t = S_0 e^{(r - q + \omega) t + X_t} $$
+    /// as used by Fourier transform pricing methods (e.g. Carr-Madan).
+    ///
+    /// # Arguments:
+    /// * `u` - Argument of the characteristic function.
+    /// * `s_0` - Initial asset price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `t` - Time to maturity.
+    fn log_price_characteristic_function(
+        &self,
+        u: Complex<f64>,
+        s_0: f64,
+        r: f64,
+        q: f64,
+        t: f64,
+    ) -> Complex<f64> {
+        let i: Complex<f64> = Complex::i();
+        let drift = s_0.ln() + (r - q + self.martingale_correction()) * t;
+
+        (i * u * drift).exp() * self.characteristic_function(u, t)
+    }
+
+    /// Simulate paths of the process from its exact increments.
+    ///
+    /// Each path uses its own generator, created by [StochasticProcessConfig::path_rng].
+    ///
+    /// # Arguments:
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    fn simulate(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        assert!(t_0 < t_n);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times = config.times();
+        let dt = config.time_steps();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng: StdRng = config.path_rng(i);
+            // Equal steps are sampled in one batch, a time grid step by step.
+            let dX: Vec<f64> = match config.time_grid {
+                None => self.sample_increments(dt[0], n_steps, &mut rng),
+                Some(_) => dt
+                    .iter()
+                    .flat_map(|dt| self.sample_increments(*dt, 1, &mut rng))
+                    .collect(),
+            };
+
+            for t in 0..n_steps {
+                path[t + 1] = path[t] + dX[t];
+            }
+        };
+
+        if parallel {
+            paths.par_iter_mut().enumerate().for_each(path_generator);
+        } else {
+            paths.iter_mut().enumerate().for_each(path_generator);
+        }
+
+        Trajectories { times, paths }
+    }
+}
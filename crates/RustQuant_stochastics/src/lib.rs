@@ -22,10 +22,20 @@
 //!   - $dX(t) = \theta(t) dt + \sigma dW(t)$
 //! - Hull-White (1990)
 //!   - $dX(t) = \left[ \theta(t) - \alpha X(t) \right]dt + \sigma dW(t)$
+//! - Two-factor short rate models, fitted to an initial discount curve:
+//!   - G2++
+//!   - CIR2++
 //! - Extended Vasicek (1990)
 //!   - $dX(t) = \left[ \theta(t) - \alpha(t) X(t) \right] dt + \sigma dW(t)$
 //! - Black-Derman-Toy (1990)
 //!   - $d\ln[X(t)] = \left[ \theta(t) + \frac{\sigma'(t)}{\sigma(t)}\ln[X(t)] \right]dt + \sigma_t dW(t)$
+//! - Rough volatility models (hybrid scheme):
+//!   - Rough Bergomi (2016)
+//!   - Rough Heston (2019)
+//! - Lévy processes (pure jump):
+//!   - Variance Gamma
+//!   - Normal Inverse Gaussian
+//!   - CGMY
 //!
 //! ```rust
 //! use RustQuant::stochastics::*;
@@ -53,6 +63,14 @@ pub use black_derman_toy::*;
 pub mod brownian_motion;
 pub use brownian_motion::*;
 
+/// CGMY (Carr-Geman-Madan-Yor) Lévy process.
+pub mod cgmy;
+pub use cgmy::*;
+
+/// CIR2++ two-factor short rate model.
+pub mod cir2pp;
+pub use cir2pp::*;
+
 /// Constant Elasticity of Variance process.
 pub mod constant_elasticity_of_variance;
 pub use constant_elasticity_of_variance::*;
@@ -61,6 +79,10 @@ pub use constant_elasticity_of_variance::*;
 pub mod cox_ingersoll_ross;
 pub use cox_ingersoll_ross::*;
 
+/// Economic scenario generator (rates, equity, FX, inflation).
+pub mod economic_scenario_generator;
+pub use economic_scenario_generator::*;
+
 /// Extended Vasicek process.
 pub mod extended_vasicek;
 pub use extended_vasicek::*;
@@ -77,6 +99,10 @@ pub use fractional_cox_ingersoll_ross::*;
 pub mod fractional_ornstein_uhlenbeck;
 pub use fractional_ornstein_uhlenbeck::*;
 
+/// G2++ two-factor short rate model.
+pub mod g2pp;
+pub use g2pp::*;
+
 /// Geometric brownian bridge process.
 pub mod geometric_brownian_bridge;
 pub use geometric_brownian_bridge::*;
@@ -97,22 +123,58 @@ pub use ho_lee::*;
 pub mod hull_white;
 pub use hull_white::*;
 
+/// Defines the `LevyProcess` trait.
+pub mod levy_process;
+pub use levy_process::*;
+
+/// Risk-neutral and real-world measures.
+pub mod measure;
+pub use measure::*;
+
 /// Merton jump diffusion process.
 pub mod merton_jump_diffusion;
 pub use merton_jump_diffusion::*;
 
+/// Normal Inverse Gaussian Lévy process.
+pub mod normal_inverse_gaussian;
+pub use normal_inverse_gaussian::*;
+
 /// Ornstein-Uhlenbeck process.
 pub mod ornstein_uhlenbeck;
 pub use ornstein_uhlenbeck::*;
 
+/// Rough Bergomi model process.
+pub mod rough_bergomi;
+pub use rough_bergomi::*;
+
+/// Rough Heston model process.
+pub mod rough_heston;
+pub use rough_heston::*;
+
 /// SABR model process.
 pub mod sabr;
 pub use sabr::*;
 
+/// Defines the `TwoFactorShortRateModel` trait.
+pub mod two_factor_short_rate;
+pub use two_factor_short_rate::*;
+
+/// Variance Gamma Lévy process.
+pub mod variance_gamma;
+pub use variance_gamma::*;
+
 /// Defines `Trajectories` and `StochasticProcess`.
 pub mod process;
 pub use process::*;
 
+/// Summary statistics and conversions of `Trajectories`.
+pub mod trajectories;
+pub use trajectories::*;
+
+/// Estimation of diffusion parameters from historical data.
+pub mod estimation;
+pub use estimation::*;
+
 /// Model parameter struct.
 pub mod model_parameter;
 pub use model_parameter::*;
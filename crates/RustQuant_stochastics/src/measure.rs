@@ -0,0 +1,168 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Switching between the risk-neutral ($\mathbb{Q}$) and real-world ($\mathbb{P}$) measures.
+//!
+//! Models are calibrated to prices, so their parameters live under $\mathbb{Q}$.
+//! Forecasting and risk need the dynamics under $\mathbb{P}$, which by Girsanov's
+//! theorem differ only in the drift: with market price of risk $\lambda_t$,
+//! $$ dW^{\mathbb{Q}}_t = dW^{\mathbb{P}}_t + \lambda_t dt $$
+//! so a process $dX_t = \mu^{\mathbb{Q}} dt + \sigma dW^{\mathbb{Q}}_t$ has
+//! real-world drift $\mu^{\mathbb{P}} = \mu^{\mathbb{Q}} + \lambda_t \sigma$.
+//!
+//! ```rust
+//! use RustQuant::stochastics::*;
+//!
+//! // GBM calibrated under Q (drift = risk-free rate).
+//! let gbm = GeometricBrownianMotion::new(0.03, 0.2);
+//!
+//! // Real-world dynamics with a Sharpe ratio of 0.25: drift 0.03 + 0.25 * 0.2.
+//! let real_world = MeasureChange::new(&gbm, 0.25, Measure::RealWorld);
+//! assert!((real_world.drift(1.0, 0.0) - 0.08).abs() < 1e-12);
+//! ```
+
+use crate::model_parameter::ModelParameter;
+use crate::process::StochasticProcess;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Probability measure under which paths are simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Measure {
+    /// Risk-neutral measure ($\mathbb{Q}$), for pricing.
+    #[default]
+    RiskNeutral,
+
+    /// Real-world (physical) measure ($\mathbb{P}$), for forecasting and risk.
+    RealWorld,
+}
+
+/// A risk-neutral process viewed under a chosen measure.
+///
+/// The wrapped process is assumed to be specified under $\mathbb{Q}$.
+/// Paths are generated from the adjusted drift and the wrapped diffusion
+/// with the default Euler-Maruyama scheme.
+pub struct MeasureChange<'a, P: StochasticProcess> {
+    /// The (risk-neutral) process.
+    pub process: &'a P,
+
+    /// Market price of risk ($\lambda_t$).
+    pub market_price_of_risk: ModelParameter,
+
+    /// The measure to simulate under.
+    pub measure: Measure,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<'a, P: StochasticProcess> MeasureChange<'a, P> {
+    /// Create a new measure change.
+    ///
+    /// # Arguments:
+    /// * `process` - The process, specified under the risk-neutral measure.
+    /// * `market_price_of_risk` - Market price of risk ($\lambda_t$).
+    /// * `measure` - The measure to simulate under.
+    pub fn new(
+        process: &'a P,
+        market_price_of_risk: impl Into<ModelParameter>,
+        measure: Measure,
+    ) -> Self {
+        Self {
+            process,
+            market_price_of_risk: market_price_of_risk.into(),
+            measure,
+        }
+    }
+
+    /// Switch to another measure, keeping the market price of risk.
+    pub fn under(mut self, measure: Measure) -> Self {
+        self.measure = measure;
+        self
+    }
+}
+
+impl<P: StochasticProcess> StochasticProcess for MeasureChange<'_, P> {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        let drift = self.process.drift(x, t);
+
+        match self.measure {
+            Measure::RiskNeutral => drift,
+            Measure::RealWorld => {
+                drift + self.market_price_of_risk.0(t) * self.process.diffusion(x, t)
+            }
+        }
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.process.diffusion(x, t)
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        self.process.jump(x, t)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        self.process.parameters()
+    }
+
+    fn is_diffusion(&self) -> bool {
+        self.process.is_diffusion()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_measure {
+    use super::*;
+    use crate::{GeometricBrownianMotion, HullWhite, StochasticProcessConfig};
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_gbm_real_world() {
+        let gbm = GeometricBrownianMotion::new(0.03, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 10000, true);
+
+        let real_world = MeasureChange::new(&gbm, 0.25, Measure::RealWorld);
+        let P = real_world.euler_maruyama(&config);
+
+        let risk_neutral = real_world.under(Measure::RiskNeutral);
+        let Q = risk_neutral.euler_maruyama(&config);
+
+        let S_P: Vec<f64> = P.paths.iter().map(|p| p[50]).collect();
+        let S_Q: Vec<f64> = Q.paths.iter().map(|p| p[50]).collect();
+
+        assert_approx_equal!(S_P.mean(), 100.0 * 0.08_f64.exp(), 1.5);
+        assert_approx_equal!(S_Q.mean(), 100.0 * 0.03_f64.exp(), 1.5);
+
+        // Under Q the wrapper is the original process.
+        assert_eq!(risk_neutral.drift(100.0, 0.0), gbm.drift(100.0, 0.0));
+    }
+
+    #[test]
+    fn test_hull_white_real_world() {
+        // Under P the short rate reverts to (theta + lambda * sigma) / alpha.
+        let hw = HullWhite::new(0.5, 0.01, 0.015);
+        let real_world = MeasureChange::new(&hw, |t: f64| -0.5 * t.min(1.0), Measure::RealWorld);
+
+        assert_approx_equal!(
+            real_world.drift(0.02, 1.0),
+            0.015 - 0.5 * 0.02 - 0.5 * 0.01,
+            1e-12
+        );
+        assert_eq!(real_world.diffusion(0.02, 1.0), 0.01);
+    }
+}
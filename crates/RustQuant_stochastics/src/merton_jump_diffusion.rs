@@ -10,10 +10,12 @@
 use super::StochasticProcessConfig;
 use crate::model_parameter::ModelParameter;
 use crate::process::{StochasticProcess, Trajectories};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 use rand_distr::Distribution;
 use rayon::prelude::*;
+use RustQuant_math::Distribution as LocalDistribution;
 use RustQuant_math::Gaussian;
-use RustQuant_math::{Distribution as LocalDistribution, Poisson};
 
 /// Struct containing the Merton Jump Diffusion parameters.
 /// The Merton Jump Diffusion is a stochastic process that models a path-dependent option.
@@ -74,53 +76,137 @@ impl StochasticProcess for MertonJumpDiffusion {
         vec![self.mu.0(0.0), self.sigma.0(0.0), self.lambda.0(0.0)]
     }
 
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate::<StdRng>(config, 0.0, false).0
+    }
+
+    fn euler_maruyama_with_rng<R>(&self, config: &StochasticProcessConfig) -> Trajectories
+    where
+        Self: Sized,
+        R: RngCore + SeedableRng,
+    {
+        self.simulate::<R>(config, 0.0, false).0
+    }
+
+    /// Antithetic paths share their jumps, and only the Brownian increments
+    /// are negated.
+    fn antithetic_euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate::<StdRng>(config, 0.0, true).0
+    }
+
+    /// Only the Brownian motion is shifted: the jumps keep their law, so the
+    /// likelihood ratio is the same as for a diffusion.
+    fn importance_sampled_euler_maruyama(
+        &self,
+        config: &StochasticProcessConfig,
+        shift: f64,
+    ) -> (Trajectories, Vec<f64>) {
+        self.simulate::<StdRng>(config, shift, false)
+    }
+}
+
+impl MertonJumpDiffusion {
+    /// Euler-Maruyama paths with jumps, and their likelihood ratios.
+    ///
+    /// The Brownian motion is given the drift `shift` (see
+    /// [StochasticProcess::importance_sampled_euler_maruyama]). If
+    /// `antithetic`, paths are generated in pairs driven by opposite
+    /// Brownian increments and the same jumps, from the generator of the
+    /// pair; otherwise path `i` uses the generator of path `i`.
+    fn simulate<R>(
+        &self,
+        config: &StochasticProcessConfig,
+        shift: f64,
+        antithetic: bool,
+    ) -> (Trajectories, Vec<f64>)
+    where
+        R: RngCore + SeedableRng,
+    {
         let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
 
         assert!(t_0 < t_n);
 
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
         // Initialise empty paths and fill in the time points.
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+        let mut weights = vec![1.0; m_paths];
+        let times = config.times();
+        let dt = config.time_steps();
+        let group = if antithetic { 2 } else { 1 };
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
-            let scale = dt.sqrt();
+        let jump_size = rand_distr::Normal::new(
+            LocalDistribution::mean(&self.gaussian),
+            LocalDistribution::variance(&self.gaussian).sqrt(),
+        )
+        .unwrap();
+
+        let group_generator = |(i, (paths, weights)): (usize, (&mut [Vec<f64>], &mut [f64]))| {
+            let mut rng: R = config.path_rng(i);
 
             let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
                 .unwrap()
                 .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
+                .zip(&dt)
+                .map(|(z, dt)| z * dt.sqrt())
+                .collect();
+
+            let jumps: Vec<f64> = dt
+                .iter()
+                .map(|dt| {
+                    rand_distr::Poisson::new(self.lambda.0(0.0) * dt)
+                        .unwrap()
+                        .sample(&mut rng)
+                })
+                .collect();
+
+            let jump_sizes: Vec<f64> = jumps
+                .iter()
+                .map(|n| {
+                    if *n > 0.0 {
+                        jump_size.sample(&mut rng)
+                    } else {
+                        0.0
+                    }
+                })
                 .collect();
 
-            let jumps = Poisson::new(self.lambda.0(0.0) * dt)
-                .sample(n_steps)
-                .unwrap();
+            for ((path, weight), sign) in paths.iter_mut().zip(weights).zip([1.0, -1.0]) {
+                let dW: Vec<f64> = dW
+                    .iter()
+                    .zip(&dt)
+                    .map(|(dW, dt)| sign * dW + shift * dt)
+                    .collect();
 
-            for t in 0..n_steps {
-                if jumps[t] > 0.0 {
+                for t in 0..n_steps {
                     path[t + 1] = path[t]
-                        + self.drift(path[t], times[t]) * dt
+                        + self.drift(path[t], times[t]) * dt[t]
                         + self.diffusion(path[t], times[t]) * dW[t]
-                        + self.jump(path[t], times[t]).unwrap_or(0.0);
-                } else {
-                    path[t + 1] = path[t]
-                        + self.drift(path[t], times[t]) * dt
-                        + self.diffusion(path[t], times[t]) * dW[t];
+                        + jump_sizes[t];
                 }
+
+                let W_T: f64 = dW.iter().sum();
+                *weight = (-shift * W_T + 0.5 * shift * shift * (t_n - t_0)).exp();
             }
         };
 
         if parallel {
-            paths.par_iter_mut().for_each(path_generator);
+            paths
+                .par_chunks_mut(group)
+                .zip(weights.par_chunks_mut(group))
+                .enumerate()
+                .for_each(group_generator);
         } else {
-            paths.iter_mut().for_each(path_generator);
+            paths
+                .chunks_mut(group)
+                .zip(weights.chunks_mut(group))
+                .enumerate()
+                .for_each(group_generator);
         }
 
-        Trajectories { times, paths }
+        (Trajectories { times, paths }, weights)
     }
 }
 
@@ -0,0 +1,144 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::levy_process::LevyProcess;
+use crate::process::{StochasticProcess, StochasticProcessConfig, Trajectories};
+use num::Complex;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, InverseGaussian, StandardNormal};
+
+/// Struct containing the Normal Inverse Gaussian process parameters.
+///
+/// The NIG process is a Brownian motion with drift $\beta$, time-changed
+/// by an Inverse Gaussian process $I_t$ with mean rate $\delta / \gamma$,
+/// where $\gamma = \sqrt{\alpha^2 - \beta^2}$:
+/// $$ X_t = \mu t + \beta I_t + W(I_t) $$
+pub struct NormalInverseGaussian {
+    /// Tail heaviness ($\alpha$).
+    pub alpha: f64,
+
+    /// Asymmetry ($\beta$), with $|\beta| < \alpha$.
+    pub beta: f64,
+
+    /// Scale ($\delta$).
+    pub delta: f64,
+
+    /// Location ($\mu$).
+    pub mu: f64,
+}
+
+impl NormalInverseGaussian {
+    /// Create a new Normal Inverse Gaussian process.
+    ///
+    /// # Arguments:
+    /// * `alpha` - Tail heaviness ($\alpha$).
+    /// * `beta` - Asymmetry ($\beta$), with $|\beta| < \alpha$.
+    /// * `delta` - Scale ($\delta$).
+    /// * `mu` - Location ($\mu$).
+    pub fn new(alpha: f64, beta: f64, delta: f64, mu: f64) -> Self {
+        assert!(alpha > beta.abs());
+        assert!(delta > 0.0);
+
+        Self {
+            alpha,
+            beta,
+            delta,
+            mu,
+        }
+    }
+
+    fn gamma(&self) -> f64 {
+        (self.alpha.powi(2) - self.beta.powi(2)).sqrt()
+    }
+}
+
+impl LevyProcess for NormalInverseGaussian {
+    fn characteristic_exponent(&self, u: Complex<f64>) -> Complex<f64> {
+        let i: Complex<f64> = Complex::i();
+
+        i * u * self.mu
+            + self.delta
+                * (self.gamma() - (self.alpha.powi(2) - (self.beta + i * u).powi(2)).sqrt())
+    }
+
+    fn sample_increments(&self, dt: f64, n_steps: usize, rng: &mut StdRng) -> Vec<f64> {
+        let inverse_gaussian =
+            InverseGaussian::new(self.delta * dt / self.gamma(), (self.delta * dt).powi(2))
+                .unwrap();
+
+        (0..n_steps)
+            .map(|_| {
+                let s: f64 = inverse_gaussian.sample(rng);
+                let z: f64 = StandardNormal.sample(rng);
+
+                self.mu * dt + self.beta * s + s.sqrt() * z
+            })
+            .collect()
+    }
+}
+
+impl StochasticProcess for NormalInverseGaussian {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        self.mu + self.delta * self.beta / self.gamma()
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.alpha, self.beta, self.delta, self.mu]
+    }
+
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate(config)
+    }
+}
+
+#[cfg(test)]
+mod tests_normal_inverse_gaussian {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_normal_inverse_gaussian_moments() {
+        let (alpha, beta, delta, mu) = (15.0, -5.0, 0.5, 0.02);
+        let nig = NormalInverseGaussian::new(alpha, beta, delta, mu);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 50, 20000, true).with_seed(1234);
+        let output = nig.simulate(&config);
+
+        let X_T: Vec<f64> = output
+            .paths
+            .iter()
+            .filter_map(|v| v.last().copied())
+            .collect();
+
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+
+        // E[X_T] = (mu + delta * beta / gamma) * T
+        // V[X_T] = delta * alpha^2 / gamma^3 * T
+        assert_approx_equal!(X_T.mean(), mu + delta * beta / gamma, 0.01);
+        assert_approx_equal!(X_T.variance(), delta * alpha.powi(2) / gamma.powi(3), 0.002);
+
+        // Martingale correction: E[exp(omega * T + X_T)] = 1.
+        let omega = nig.martingale_correction();
+        let martingale: Vec<f64> = X_T.iter().map(|x| (omega + x).exp()).collect();
+
+        assert_approx_equal!(martingale.mean(), 1.0, 0.01);
+    }
+}
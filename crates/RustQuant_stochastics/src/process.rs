@@ -15,8 +15,10 @@
 //! do not explicitly depend on the time `t`.
 
 use rand::prelude::Distribution;
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use rayon::prelude::*;
+use time::Date;
+use RustQuant_time::DayCountConvention;
 // use statrs::distribution::Normal;
 
 /// Struct to contain the time points and path values of the process.
@@ -110,6 +112,16 @@ pub trait StochasticVolatilityProcess: Sync {
 /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
 /// * `m_paths` - How many process trajectories to simulate.
 /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+///
+/// A seed can be set with [StochasticProcessConfig::with_seed]. Each path
+/// then draws from its own generator, seeded with `seed + i` for path `i`,
+/// so the output does not depend on `parallel` or on thread scheduling.
+///
+/// Instead of `n_steps` equal steps, the paths can be simulated on explicit
+/// time points (e.g. option monitoring dates) with
+/// [StochasticProcessConfig::with_time_grid] or
+/// [StochasticProcessConfig::with_dates]. The fractional and rough
+/// volatility processes need equal steps and panic on such a grid.
 pub struct StochasticProcessConfig {
     /// Initial value of the process.
     pub x_0: f64,
@@ -128,6 +140,12 @@ pub struct StochasticProcessConfig {
 
     /// Run in parallel or not (recommended for > 1000 paths).
     pub parallel: bool,
+
+    /// Seed for the random number generators (`None` for entropy).
+    pub seed: Option<u64>,
+
+    /// Explicit time points, from `t_0` to `t_n` (`None` for equal steps).
+    pub time_grid: Option<Vec<f64>>,
 }
 
 impl StochasticProcessConfig {
@@ -147,6 +165,97 @@ impl StochasticProcessConfig {
             n_steps,
             m_paths,
             parallel,
+            seed: None,
+            time_grid: None,
+        }
+    }
+
+    /// Set the seed, making the simulation reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Simulate on explicit time points instead of equal steps.
+    ///
+    /// `t_0`, `t_n` and `n_steps` are replaced by the first point, the last
+    /// point and the number of intervals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two points, or they are not increasing.
+    pub fn with_time_grid(mut self, times: Vec<f64>) -> Self {
+        assert!(times.len() >= 2, "A time grid needs at least two points.");
+        assert!(
+            times.windows(2).all(|pair| pair[0] < pair[1]),
+            "Time grid points must be strictly increasing."
+        );
+
+        self.t_0 = times[0];
+        self.t_n = times[times.len() - 1];
+        self.n_steps = times.len() - 1;
+        self.time_grid = Some(times);
+        self
+    }
+
+    /// Simulate on calendar dates, e.g. monitoring or fixing dates from a
+    /// `Schedule`, measured in years from `start`.
+    ///
+    /// The grid starts at `t = 0` on `start`, followed by one point per date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dates` is empty, or the dates are not increasing and after `start`.
+    pub fn with_dates(self, start: Date, dates: &[Date], day_count: DayCountConvention) -> Self {
+        let times = std::iter::once(0.0)
+            .chain(
+                dates
+                    .iter()
+                    .map(|date| day_count.day_count_factor(start, *date)),
+            )
+            .collect();
+
+        self.with_time_grid(times)
+    }
+
+    /// Time points of the simulation.
+    pub fn times(&self) -> Vec<f64> {
+        match &self.time_grid {
+            Some(times) => times.clone(),
+            None => {
+                let dt = (self.t_n - self.t_0) / (self.n_steps as f64);
+                (0..=self.n_steps)
+                    .map(|t| self.t_0 + dt * (t as f64))
+                    .collect()
+            }
+        }
+    }
+
+    /// Lengths of the time steps, `times[i + 1] - times[i]`.
+    pub fn time_steps(&self) -> Vec<f64> {
+        match &self.time_grid {
+            Some(times) => times.windows(2).map(|pair| pair[1] - pair[0]).collect(),
+            None => vec![(self.t_n - self.t_0) / (self.n_steps as f64); self.n_steps],
+        }
+    }
+
+    /// Panics if the configuration has an explicit time grid.
+    pub(crate) fn assert_equal_steps(&self, process: &str) {
+        assert!(
+            self.time_grid.is_none(),
+            "{} can only be simulated with equal time steps.",
+            process
+        );
+    }
+
+    /// Random number generator for path `path`.
+    ///
+    /// Seeded with `seed + path` if the configuration has a seed,
+    /// and from entropy otherwise.
+    pub fn path_rng<R: SeedableRng>(&self, path: usize) -> R {
+        match self.seed {
+            Some(seed) => R::seed_from_u64(seed.wrapping_add(path as u64)),
+            None => R::from_entropy(),
         }
     }
 
@@ -179,6 +288,16 @@ pub trait StochasticProcess: Sync {
         vec![]
     }
 
+    /// Whether the process is a diffusion, simulated from its drift and
+    /// diffusion alone by the default schemes below.
+    ///
+    /// Processes with jumps or their own simulation scheme return `false`,
+    /// and the default schemes panic for them instead of simulating a
+    /// diffusion, unless the process overrides them.
+    fn is_diffusion(&self) -> bool {
+        true
+    }
+
     /// Euler-Maruyama discretisation scheme.
     ///
     /// # Arguments:
@@ -189,43 +308,154 @@ pub trait StochasticProcess: Sync {
     /// * `m_paths` - How many process trajectories to simulate.
     /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        euler_maruyama_paths::<Self, StdRng>(self, config)
+    }
+
+    /// Euler-Maruyama discretisation scheme with a custom random number generator.
+    ///
+    /// Each path uses its own generator, created by [StochasticProcessConfig::path_rng].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the process is not a diffusion and does not override this
+    /// method (see [StochasticProcess::is_diffusion]).
+    ///
+    /// # Arguments:
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    fn euler_maruyama_with_rng<R>(&self, config: &StochasticProcessConfig) -> Trajectories
+    where
+        Self: Sized,
+        R: RngCore + SeedableRng,
+    {
+        euler_maruyama_paths::<Self, R>(self, config)
+    }
+
+    /// Euler-Maruyama discretisation scheme with antithetic variates.
+    ///
+    /// Paths are generated in pairs: the second path of each pair is driven
+    /// by the negated Brownian increments of the first, so `paths[2k]` and
+    /// `paths[2k + 1]` are antithetic. If `m_paths` is odd, the last path
+    /// has no partner.
+    ///
+    /// # Arguments:
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the process is not a diffusion and does not override this
+    /// method (see [StochasticProcess::is_diffusion]).
+    fn antithetic_euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        assert_diffusion(self, "Antithetic sampling");
+
         let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
         assert!(t_0 < t_n);
 
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times = config.times();
+        let dt = config.time_steps();
+
+        let pair_generator = |(i, pair): (usize, &mut [Vec<f64>])| {
+            let mut rng: StdRng = config.path_rng(i);
+            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
+                .unwrap()
+                .sample_iter(&mut rng)
+                .zip(&dt)
+                .map(|(z, dt)| z * dt.sqrt())
+                .collect();
+
+            for (path, sign) in pair.iter_mut().zip([1.0, -1.0]) {
+                for t in 0..n_steps {
+                    path[t + 1] = path[t]
+                        + self.drift(path[t], times[t]) * dt[t]
+                        + self.diffusion(path[t], times[t]) * sign * dW[t];
+                }
+            }
+        };
+
+        if parallel {
+            paths.par_chunks_mut(2).enumerate().for_each(pair_generator);
+        } else {
+            paths.chunks_mut(2).enumerate().for_each(pair_generator);
+        }
+
+        Trajectories { times, paths }
+    }
+
+    /// Euler-Maruyama discretisation scheme under a shifted measure.
+    ///
+    /// The driving Brownian motion is given a constant drift `shift`
+    /// (Girsanov), i.e. $dW_t = d\tilde{W}_t + \theta dt$, and the
+    /// likelihood ratio of each path is returned alongside the paths:
+    /// $$ L = \exp\left(-\theta W_T + \frac{1}{2} \theta^2 (t_n - t_0)\right) $$
+    /// Weighting payoffs by $L$ gives unbiased estimates under the
+    /// original measure.
+    ///
+    /// # Arguments:
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `shift` - The drift ($\theta$) added to the Brownian motion.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the process is not a diffusion and does not override this
+    /// method (see [StochasticProcess::is_diffusion]).
+    fn importance_sampled_euler_maruyama(
+        &self,
+        config: &StochasticProcessConfig,
+        shift: f64,
+    ) -> (Trajectories, Vec<f64>) {
+        assert_diffusion(self, "Importance sampling");
+
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        assert!(t_0 < t_n);
 
         // Initialise empty paths and fill in the time points.
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+        let mut weights = vec![1.0; m_paths];
+        let times = config.times();
+        let dt = config.time_steps();
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
-            let scale = dt.sqrt();
+        let path_generator = |(i, (path, weight)): (usize, (&mut Vec<f64>, &mut f64))| {
+            let mut rng: StdRng = config.path_rng(i);
             let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
                 .unwrap()
                 .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
+                .zip(&dt)
+                .map(|(z, dt)| z * dt.sqrt() + shift * dt)
                 .collect();
 
             for t in 0..n_steps {
                 path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
+                    + self.drift(path[t], times[t]) * dt[t]
                     + self.diffusion(path[t], times[t]) * dW[t];
             }
+
+            let W_T: f64 = dW.iter().sum();
+            *weight = (-shift * W_T + 0.5 * shift * shift * (t_n - t_0)).exp();
         };
 
         if parallel {
-            paths.par_iter_mut().for_each(path_generator);
+            paths
+                .par_iter_mut()
+                .zip(weights.par_iter_mut())
+                .enumerate()
+                .for_each(path_generator);
         } else {
-            paths.iter_mut().for_each(path_generator);
+            paths
+                .iter_mut()
+                .zip(weights.iter_mut())
+                .enumerate()
+                .for_each(path_generator);
         }
 
-        Trajectories { times, paths }
+        (Trajectories { times, paths }, weights)
     }
 
     /// Euler-Maruyama discretisation scheme with a choice of random seed.
     ///
+    /// Equivalent to [StochasticProcess::euler_maruyama] with the seed set
+    /// in the configuration: path `i` uses `seed + i`.
+    ///
     /// # Arguments:
     /// * `x_0` - The process' initial value at `t_0`.
     /// * `t_0` - The initial time point.
@@ -244,43 +474,67 @@ pub trait StochasticProcess: Sync {
         parallel: bool,
         seed: u64,
     ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+        let config =
+            StochasticProcessConfig::new(x_0, t_0, t_n, n_steps, m_paths, parallel).with_seed(seed);
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = StdRng::seed_from_u64(seed);
-            let scale = dt.sqrt();
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
+        self.euler_maruyama(&config)
+    }
+}
 
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
-            }
-        };
+/// Panics if the process cannot be simulated by the default schemes.
+fn assert_diffusion<P: StochasticProcess + ?Sized>(process: &P, scheme: &str) {
+    assert!(
+        process.is_diffusion(),
+        "{} with the default Euler-Maruyama scheme needs a diffusion: \
+         this process has jumps or its own simulation scheme.",
+        scheme
+    );
+}
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
+/// Euler-Maruyama paths of a process, with one generator of type `R` per path.
+fn euler_maruyama_paths<P, R>(process: &P, config: &StochasticProcessConfig) -> Trajectories
+where
+    P: StochasticProcess + ?Sized,
+    R: RngCore + SeedableRng,
+{
+    assert_diffusion(process, "Simulation");
+
+    let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+    assert!(t_0 < t_n);
+
+    // Initialise empty paths and fill in the time points.
+    let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+    let times = config.times();
+    let dt = config.time_steps();
+
+    let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+        let mut rng: R = config.path_rng(i);
+        let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
+            .unwrap()
+            .sample_iter(&mut rng)
+            .zip(&dt)
+            .map(|(z, dt)| z * dt.sqrt())
+            .collect();
+
+        for t in 0..n_steps {
+            path[t + 1] = path[t]
+                + process.drift(path[t], times[t]) * dt[t]
+                + process.diffusion(path[t], times[t]) * dW[t];
         }
+    };
 
-        Trajectories { times, paths }
+    if parallel {
+        paths.par_iter_mut().enumerate().for_each(path_generator);
+    } else {
+        paths.iter_mut().enumerate().for_each(path_generator);
     }
+
+    Trajectories { times, paths }
 }
 
 #[cfg(test)]
 mod test_process {
+    use crate::arithmetic_brownian_motion::ArithmeticBrownianMotion;
     use crate::geometric_brownian_motion::GeometricBrownianMotion;
     use crate::process::StochasticProcess;
     use crate::StochasticProcessConfig;
@@ -333,4 +587,165 @@ mod test_process {
         // To see the output of this "test", run:
         // cargo test test_process -- --nocapture
     }
+
+    #[test]
+    fn test_antithetic_euler_maruyama() {
+        let abm = ArithmeticBrownianMotion::new(0.05, 0.9);
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 125, 1001, true);
+
+        let output = abm.antithetic_euler_maruyama(&config);
+
+        assert_eq!(output.paths.len(), 1001);
+
+        // With constant coefficients, each antithetic pair is symmetric
+        // around the deterministic drift path.
+        for pair in output.paths.chunks_exact(2) {
+            for (t, (x, y)) in pair[0].iter().zip(&pair[1]).enumerate() {
+                let expected = 2.0 * (10.0 + 0.05 * output.times[t]);
+                assert!((x + y - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_importance_sampled_euler_maruyama() {
+        let abm = ArithmeticBrownianMotion::new(0.0, 1.0);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 50, 20_000, true).with_seed(42);
+
+        let (output, weights) = abm.importance_sampled_euler_maruyama(&config, 1.5);
+
+        // The shifted paths drift upwards...
+        let n = output.paths.len() as f64;
+        let mean = output.paths.iter().map(|p| p[50]).sum::<f64>() / n;
+        assert!((mean - 1.5).abs() < 0.05);
+
+        // ...but the weights have unit expectation and undo the shift.
+        let weighted_mean = output
+            .paths
+            .iter()
+            .zip(&weights)
+            .map(|(p, w)| p[50] * w)
+            .sum::<f64>()
+            / n;
+        assert!((weights.iter().sum::<f64>() / n - 1.0).abs() < 0.1);
+        assert!(weighted_mean.abs() < 0.1);
+    }
+
+    // Minimal SplitMix64 generator, to check that any `SeedableRng` can drive the simulation.
+    struct SplitMix64(u64);
+
+    impl rand::RngCore for SplitMix64 {
+        fn next_u32(&mut self) -> u32 {
+            (self.next_u64() >> 32) as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(8) {
+                let bytes = self.next_u64().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl rand::SeedableRng for SplitMix64 {
+        type Seed = [u8; 8];
+
+        fn from_seed(seed: Self::Seed) -> Self {
+            Self(u64::from_le_bytes(seed))
+        }
+    }
+
+    #[test]
+    fn test_seeded_config() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.9);
+        let serial = StochasticProcessConfig::new(10.0, 0.0, 1.0, 50, 500, false).with_seed(7);
+        let parallel = StochasticProcessConfig::new(10.0, 0.0, 1.0, 50, 500, true).with_seed(7);
+
+        // Same seed, same paths, regardless of threading.
+        let output = gbm.euler_maruyama(&serial);
+        assert_eq!(output.paths, gbm.euler_maruyama(&parallel).paths);
+        assert_eq!(
+            output.paths,
+            gbm.seedable_euler_maruyama(10.0, 0.0, 1.0, 50, 500, true, 7)
+                .paths
+        );
+
+        // Each path has its own stream.
+        assert_ne!(output.paths[0], output.paths[1]);
+
+        // Antithetic pairs are reproducible too.
+        assert_eq!(
+            gbm.antithetic_euler_maruyama(&serial).paths,
+            gbm.antithetic_euler_maruyama(&parallel).paths
+        );
+
+        // Pluggable generator.
+        let custom = gbm.euler_maruyama_with_rng::<SplitMix64>(&parallel);
+        assert_eq!(
+            custom.paths,
+            gbm.euler_maruyama_with_rng::<SplitMix64>(&serial).paths
+        );
+        assert_ne!(custom.paths, output.paths);
+    }
+
+    #[test]
+    fn test_time_grid() {
+        use crate::{FractionalBrownianMotion, FractionalProcessGeneratorMethod};
+        use time::macros::date;
+        use RustQuant_time::DayCountConvention;
+
+        let grid = vec![0.0, 0.1, 0.25, 0.5, 1.0];
+        let config = StochasticProcessConfig::new(1.0, 0.0, 1.0, 10, 20_000, true)
+            .with_time_grid(grid.clone())
+            .with_seed(3);
+
+        assert_eq!((config.t_0, config.t_n, config.n_steps), (0.0, 1.0, 4));
+        assert_eq!(config.time_steps(), vec![0.1, 0.15, 0.25, 0.5]);
+
+        // Without diffusion the path is the drift line at the grid points.
+        let drift_only = ArithmeticBrownianMotion::new(0.5, 0.0).euler_maruyama(&config);
+        assert_eq!(drift_only.times, grid);
+        for (t, x) in grid.iter().zip(&drift_only.paths[0]) {
+            assert!((x - (1.0 + 0.5 * t)).abs() < 1e-12);
+        }
+
+        // Brownian increments scale with each step's length.
+        let abm = ArithmeticBrownianMotion::new(0.0, 1.0).euler_maruyama(&config);
+        let n = abm.paths.len() as f64;
+        for (step, t) in grid.iter().enumerate().skip(1) {
+            let variance = abm
+                .paths
+                .iter()
+                .map(|p| (p[step] - 1.0).powi(2))
+                .sum::<f64>()
+                / n;
+            assert!((variance - t).abs() < 0.05 * t, "{} {}", t, variance);
+        }
+
+        // Dates are converted to year fractions from the start date.
+        let dated = StochasticProcessConfig::new(1.0, 0.0, 1.0, 1, 1, false).with_dates(
+            date!(2025 - 01 - 01),
+            &[date!(2025 - 04 - 11), date!(2026 - 01 - 01)],
+            DayCountConvention::Actual_365_Fixed,
+        );
+        assert_eq!(dated.times(), vec![0.0, 100.0 / 365.0, 1.0]);
+
+        // Fractional processes need equal steps.
+        let fbm = FractionalBrownianMotion::new(0.7, FractionalProcessGeneratorMethod::FFT);
+        let result = std::panic::catch_unwind(|| fbm.euler_maruyama(&dated));
+        assert!(result.is_err());
+    }
 }
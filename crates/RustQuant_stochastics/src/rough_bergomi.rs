@@ -0,0 +1,204 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::fractional_brownian_motion::{
+    FractionalBrownianMotion, FractionalProcessGeneratorMethod,
+};
+use crate::process::{StochasticProcess, StochasticProcessConfig, Trajectories};
+use rand::{rngs::StdRng, Rng};
+use rand_distr::StandardNormal;
+use rayon::prelude::*;
+
+/// Struct containing the rough Bergomi model parameters
+/// (Bayer, Friz and Gatheral, 2016):
+/// $$
+/// \begin{aligned}
+/// \frac{dS_t}{S_t} &= \mu dt + \sqrt{V_t} \left( \rho dW_t + \sqrt{1 - \rho^2} dB_t \right) \\\\
+/// V_t &= \xi \exp\left( \eta Y_t - \frac{1}{2} \eta^2 t^{2H} \right)
+/// \end{aligned}
+/// $$
+/// where $Y_t = \sqrt{2H} \int_0^t (t - s)^{H - 1/2} dW_s$ is a
+/// Riemann-Liouville fractional Brownian motion, simulated with the hybrid scheme.
+#[derive(Debug, Clone, Copy)]
+pub struct RoughBergomi {
+    /// Drift of the asset ($\mu$).
+    pub mu: f64,
+
+    /// Flat forward variance ($\xi$).
+    pub xi: f64,
+
+    /// Volatility of volatility ($\eta$).
+    pub eta: f64,
+
+    /// Correlation between the asset and the variance ($\rho$).
+    pub rho: f64,
+
+    /// Hurst parameter of the variance ($H$), with $0 < H < 1/2$.
+    pub hurst: f64,
+}
+
+impl RoughBergomi {
+    /// Create a new rough Bergomi process.
+    ///
+    /// # Arguments:
+    /// * `mu` - Drift of the asset ($\mu$).
+    /// * `xi` - Flat forward variance ($\xi$).
+    /// * `eta` - Volatility of volatility ($\eta$).
+    /// * `rho` - Correlation between the asset and the variance ($\rho$).
+    /// * `hurst` - Hurst parameter of the variance ($H$), with $0 < H < 1/2$.
+    pub fn new(mu: f64, xi: f64, eta: f64, rho: f64, hurst: f64) -> Self {
+        assert!(xi > 0.0 && eta >= 0.0);
+        assert!((-1.0..=1.0).contains(&rho));
+        assert!(hurst > 0.0 && hurst < 0.5);
+
+        Self {
+            mu,
+            xi,
+            eta,
+            rho,
+            hurst,
+        }
+    }
+
+    /// Simulate asset and variance paths.
+    ///
+    /// # Arguments:
+    /// * `config` - The [StochasticProcessConfig] for the simulation (`x_0` is the initial asset price).
+    ///
+    /// Returns the asset and the variance trajectories, in that order.
+    pub fn simulate(&self, config: &StochasticProcessConfig) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        config.assert_equal_steps("The rough Bergomi model");
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let fbm = FractionalBrownianMotion::new(self.hurst, FractionalProcessGeneratorMethod::FFT);
+        let rho_bar = (1.0 - self.rho * self.rho).sqrt();
+
+        let path_generator = |i: usize| {
+            let mut rng: StdRng = config.path_rng(i);
+            let (dW, Y) = fbm.hybrid_scheme(n_steps, t_n - t_0, &mut rng);
+
+            let variance: Vec<f64> = (0..=n_steps)
+                .map(|t| {
+                    let s = dt * t as f64;
+                    self.xi
+                        * (self.eta * Y[t] - 0.5 * self.eta.powi(2) * s.powf(2.0 * self.hurst))
+                            .exp()
+                })
+                .collect();
+
+            // Log-Euler on the asset, with the variance frozen over each step.
+            let mut asset = vec![x_0; n_steps + 1];
+            for t in 0..n_steps {
+                let z: f64 = rng.sample(StandardNormal);
+                let dB = dt.sqrt() * z;
+
+                asset[t + 1] = asset[t]
+                    * ((self.mu - 0.5 * variance[t]) * dt
+                        + variance[t].sqrt() * (self.rho * dW[t] + rho_bar * dB))
+                        .exp();
+            }
+
+            (asset, variance)
+        };
+
+        let (asset, variance): (Vec<Vec<f64>>, Vec<Vec<f64>>) = if parallel {
+            (0..m_paths).into_par_iter().map(path_generator).unzip()
+        } else {
+            (0..m_paths).map(path_generator).unzip()
+        };
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: asset,
+            },
+            Trajectories {
+                times,
+                paths: variance,
+            },
+        )
+    }
+}
+
+impl StochasticProcess for RoughBergomi {
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        self.mu * x
+    }
+
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.xi.sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.mu, self.xi, self.eta, self.rho, self.hurst]
+    }
+
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate(config).0
+    }
+}
+
+#[cfg(test)]
+mod tests_rough_bergomi {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_rough_bergomi_moments() {
+        let rbergomi = RoughBergomi::new(0.05, 0.04, 1.5, -0.7, 0.1);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 10000, true).with_seed(42);
+
+        let (asset, variance) = rbergomi.simulate(&config);
+
+        let S_T: Vec<f64> = asset.paths.iter().map(|p| p[100]).collect();
+        let V_T: Vec<f64> = variance.paths.iter().map(|p| p[100]).collect();
+        let V_half: Vec<f64> = variance.paths.iter().map(|p| p[50]).collect();
+
+        // E[S_T] = S_0 exp(mu T) and E[V_t] = xi.
+        assert_approx_equal!(S_T.mean(), 100.0 * 0.05_f64.exp(), 0.5);
+        assert_approx_equal!(V_half.mean(), 0.04, 0.003);
+        assert_approx_equal!(V_T.mean(), 0.04, 0.004);
+
+        // Negative correlation gives a negative (leverage) relationship.
+        let log_S: Vec<f64> = S_T.iter().map(|s| s.ln()).collect();
+        let (mean_log_S, mean_V_T) = (log_S.mean(), V_T.mean());
+        let covariance = log_S
+            .iter()
+            .zip(V_T.iter())
+            .map(|(s, v)| (s - mean_log_S) * (v - mean_V_T))
+            .sum::<f64>()
+            / S_T.len() as f64;
+
+        assert!(covariance < 0.0);
+    }
+
+    #[test]
+    fn test_rough_bergomi_seed() {
+        let rbergomi = RoughBergomi::new(0.0, 0.04, 1.9, -0.9, 0.07);
+
+        let path_1 = rbergomi.seedable_euler_maruyama(100.0, 0.0, 0.5, 20, 2, false, 1);
+        let path_2 = rbergomi.seedable_euler_maruyama(100.0, 0.0, 0.5, 20, 2, true, 1);
+
+        assert_eq!(path_1.paths, path_2.paths);
+        assert_eq!(path_1.times.len(), 21);
+    }
+}
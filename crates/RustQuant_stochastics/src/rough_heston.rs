@@ -0,0 +1,247 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::fractional_brownian_motion::{hybrid_scheme_increments, hybrid_scheme_weights};
+use crate::process::{StochasticProcess, StochasticProcessConfig, Trajectories};
+use rand::{rngs::StdRng, Rng};
+use rand_distr::StandardNormal;
+use rayon::prelude::*;
+use statrs::function::gamma::gamma;
+
+/// Struct containing the rough Heston model parameters
+/// (El Euch and Rosenbaum, 2019):
+/// $$
+/// \begin{aligned}
+/// \frac{dS_t}{S_t} &= \mu dt + \sqrt{V_t} \left( \rho dW_t + \sqrt{1 - \rho^2} dB_t \right) \\\\
+/// V_t &= V_0 + \frac{1}{\Gamma(H + 1/2)} \int_0^t (t - s)^{H - 1/2}
+///     \left( \kappa (\theta - V_s) ds + \nu \sqrt{V_s} dW_s \right)
+/// \end{aligned}
+/// $$
+///
+/// The Volterra integral is discretised with the hybrid scheme: the most
+/// recent step is integrated exactly against the kernel, and earlier steps
+/// use the kernel evaluated at optimal points. Negative variances are floored at zero.
+#[derive(Debug, Clone, Copy)]
+pub struct RoughHeston {
+    /// Drift of the asset ($\mu$).
+    pub mu: f64,
+
+    /// Initial variance ($V_0$).
+    pub initial_variance: f64,
+
+    /// Long-run variance ($\theta$).
+    pub long_run_variance: f64,
+
+    /// Mean reversion rate ($\kappa$).
+    pub mean_reversion_rate: f64,
+
+    /// Volatility of volatility ($\nu$).
+    pub volatility_of_volatility: f64,
+
+    /// Correlation between the asset and the variance ($\rho$).
+    pub correlation: f64,
+
+    /// Hurst parameter of the variance ($H$), with $0 < H < 1/2$.
+    pub hurst: f64,
+}
+
+impl RoughHeston {
+    /// Create a new rough Heston process.
+    ///
+    /// # Arguments:
+    /// * `mu` - Drift of the asset ($\mu$).
+    /// * `initial_variance` - Initial variance ($V_0$).
+    /// * `long_run_variance` - Long-run variance ($\theta$).
+    /// * `mean_reversion_rate` - Mean reversion rate ($\kappa$).
+    /// * `volatility_of_volatility` - Volatility of volatility ($\nu$).
+    /// * `correlation` - Correlation between the asset and the variance ($\rho$).
+    /// * `hurst` - Hurst parameter of the variance ($H$), with $0 < H < 1/2$.
+    pub fn new(
+        mu: f64,
+        initial_variance: f64,
+        long_run_variance: f64,
+        mean_reversion_rate: f64,
+        volatility_of_volatility: f64,
+        correlation: f64,
+        hurst: f64,
+    ) -> Self {
+        assert!(initial_variance >= 0.0 && long_run_variance >= 0.0);
+        assert!(mean_reversion_rate >= 0.0 && volatility_of_volatility >= 0.0);
+        assert!((-1.0..=1.0).contains(&correlation));
+        assert!(hurst > 0.0 && hurst < 0.5);
+
+        Self {
+            mu,
+            initial_variance,
+            long_run_variance,
+            mean_reversion_rate,
+            volatility_of_volatility,
+            correlation,
+            hurst,
+        }
+    }
+
+    /// Simulate asset and variance paths.
+    ///
+    /// The cost per path is quadratic in the number of steps,
+    /// since the variance depends on its whole history.
+    ///
+    /// # Arguments:
+    /// * `config` - The [StochasticProcessConfig] for the simulation (`x_0` is the initial asset price).
+    ///
+    /// Returns the asset and the variance trajectories, in that order.
+    pub fn simulate(&self, config: &StochasticProcessConfig) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        config.assert_equal_steps("The rough Heston model");
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let alpha = self.hurst - 0.5;
+        let weights = hybrid_scheme_weights(alpha, dt, n_steps);
+        let scale = 1.0 / gamma(self.hurst + 0.5);
+
+        // Integral of the kernel over step j, seen from k steps later: depends only on k = i - j.
+        let drift_weights: Vec<f64> = (0..=n_steps)
+            .map(|k| {
+                let (upper, lower) = (k as f64 * dt, (k as f64 - 1.0).max(0.0) * dt);
+                (upper.powf(alpha + 1.0) - lower.powf(alpha + 1.0)) / (alpha + 1.0)
+            })
+            .collect();
+
+        let (kappa, theta, nu) = (
+            self.mean_reversion_rate,
+            self.long_run_variance,
+            self.volatility_of_volatility,
+        );
+        let rho_bar = (1.0 - self.correlation * self.correlation).sqrt();
+
+        let path_generator = |i: usize| {
+            let mut rng: StdRng = config.path_rng(i);
+
+            let mut variance = vec![self.initial_variance; n_steps + 1];
+            let mut asset = vec![x_0; n_steps + 1];
+            let mut dW = vec![0.0; n_steps];
+
+            for t in 0..n_steps {
+                let (dW_t, dI_t) = hybrid_scheme_increments(alpha, dt, &mut rng);
+                dW[t] = dW_t;
+
+                let z: f64 = rng.sample(StandardNormal);
+                let v_t = variance[t].max(0.0);
+
+                asset[t + 1] = asset[t]
+                    * ((self.mu - 0.5 * v_t) * dt
+                        + v_t.sqrt() * (self.correlation * dW_t + rho_bar * dt.sqrt() * z))
+                        .exp();
+
+                // Volterra sum over the history up to t_{t+1}.
+                let i = t + 1;
+                let drift: f64 = (0..i)
+                    .map(|j| kappa * (theta - variance[j]) * drift_weights[i - j])
+                    .sum();
+                let diffusion: f64 = (0..i.saturating_sub(1))
+                    .map(|j| variance[j].max(0.0).sqrt() * weights[i - j] * dW[j])
+                    .sum::<f64>()
+                    + v_t.sqrt() * dI_t;
+
+                variance[i] = self.initial_variance + scale * (drift + nu * diffusion);
+            }
+
+            (asset, variance)
+        };
+
+        let (asset, variance): (Vec<Vec<f64>>, Vec<Vec<f64>>) = if parallel {
+            (0..m_paths).into_par_iter().map(path_generator).unzip()
+        } else {
+            (0..m_paths).map(path_generator).unzip()
+        };
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: asset,
+            },
+            Trajectories {
+                times,
+                paths: variance,
+            },
+        )
+    }
+}
+
+impl StochasticProcess for RoughHeston {
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        self.mu * x
+    }
+
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.initial_variance.sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.mu,
+            self.initial_variance,
+            self.long_run_variance,
+            self.mean_reversion_rate,
+            self.volatility_of_volatility,
+            self.correlation,
+            self.hurst,
+        ]
+    }
+
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate(config).0
+    }
+}
+
+#[cfg(test)]
+mod tests_rough_heston {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_rough_heston_moments() {
+        // V_0 = theta, so E[V_t] = theta for all t.
+        let rheston = RoughHeston::new(0.03, 0.04, 0.04, 1.0, 0.2, -0.6, 0.1);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 10000, true).with_seed(42);
+
+        let (asset, variance) = rheston.simulate(&config);
+
+        let S_T: Vec<f64> = asset.paths.iter().map(|p| p[100]).collect();
+        let V_T: Vec<f64> = variance.paths.iter().map(|p| p[100]).collect();
+
+        assert_approx_equal!(S_T.mean(), 100.0 * 0.03_f64.exp(), 0.5);
+        assert_approx_equal!(V_T.mean(), 0.04, 0.003);
+    }
+
+    #[test]
+    fn test_rough_heston_mean_reversion() {
+        // Without vol-of-vol the variance solves the fractional ODE, and reverts towards theta.
+        let rheston = RoughHeston::new(0.0, 0.09, 0.04, 2.0, 0.0, 0.0, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 2.0, 200, 1, false).with_seed(1);
+
+        let (_, variance) = rheston.simulate(&config);
+        let v = &variance.paths[0];
+
+        assert!(v.windows(2).all(|w| w[1] <= w[0] + 1e-12));
+        assert!(v[200] > 0.04 && v[200] < 0.06);
+    }
+}
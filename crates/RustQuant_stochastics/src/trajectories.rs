@@ -0,0 +1,318 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Cross-sectional statistics of simulated [`Trajectories`].
+//!
+//! Every statistic is computed per time step, across paths, so the output
+//! has one value per entry of `times`. Quantiles use the same linear
+//! interpolation as [`RustQuant_math::Statistic::quantile`].
+
+use crate::process::Trajectories;
+use ndarray::Array2;
+use polars::prelude::*;
+
+/// Histogram of a sample, with equal-width bins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bin edges (one more than the number of bins).
+    pub edges: Vec<f64>,
+
+    /// Number of values in each bin.
+    pub counts: Vec<usize>,
+}
+
+impl Trajectories {
+    /// Number of simulated paths.
+    pub fn n_paths(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Values of every path at a time step.
+    pub fn values_at(&self, step: usize) -> Vec<f64> {
+        self.paths.iter().map(|path| path[step]).collect()
+    }
+
+    /// Values of every path at the last time step.
+    pub fn terminal_values(&self) -> Vec<f64> {
+        self.values_at(self.times.len() - 1)
+    }
+
+    /// Mean across paths at each time step.
+    pub fn mean(&self) -> Vec<f64> {
+        let n = self.n_paths() as f64;
+
+        (0..self.times.len())
+            .map(|step| self.paths.iter().map(|path| path[step]).sum::<f64>() / n)
+            .collect()
+    }
+
+    /// Sample standard deviation across paths at each time step.
+    pub fn std(&self) -> Vec<f64> {
+        let n = self.n_paths() as f64;
+
+        self.mean()
+            .iter()
+            .enumerate()
+            .map(|(step, mean)| {
+                let squares: f64 = self
+                    .paths
+                    .iter()
+                    .map(|path| (path[step] - mean).powi(2))
+                    .sum();
+
+                (squares / (n - 1.0)).sqrt()
+            })
+            .collect()
+    }
+
+    /// Quantiles across paths at each time step.
+    ///
+    /// Returns one vector per level, each with one value per time step,
+    /// e.g. `quantiles(&[0.05, 0.5, 0.95])` gives the lower band, median
+    /// and upper band of a fan chart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no paths, or a level is outside `[0, 1]`.
+    pub fn quantiles(&self, levels: &[f64]) -> Vec<Vec<f64>> {
+        assert!(
+            self.n_paths() > 0,
+            "Trajectories must have at least one path."
+        );
+        assert!(
+            levels.iter().all(|level| (0.0..=1.0).contains(level)),
+            "Quantiles must be between 0 and 1."
+        );
+
+        let mut fan = vec![Vec::with_capacity(self.times.len()); levels.len()];
+
+        for step in 0..self.times.len() {
+            let mut sorted = self.values_at(step);
+            sorted.sort_by(f64::total_cmp);
+
+            for (band, level) in fan.iter_mut().zip(levels) {
+                band.push(interpolated_quantile(&sorted, *level));
+            }
+        }
+
+        fan
+    }
+
+    /// Histogram of the terminal values, with `bins` equal-width bins
+    /// between the smallest and largest value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no paths or `bins` is zero.
+    pub fn terminal_histogram(&self, bins: usize) -> Histogram {
+        assert!(bins > 0, "Histogram must have at least one bin.");
+
+        let values = self.terminal_values();
+        assert!(
+            !values.is_empty(),
+            "Trajectories must have at least one path."
+        );
+
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let width = (max - min) / bins as f64;
+
+        let edges = (0..=bins).map(|i| min + width * i as f64).collect();
+        let mut counts = vec![0; bins];
+
+        for value in values {
+            // The maximum belongs to the last bin, and all values to the
+            // first if they are equal.
+            let bin = if width > 0.0 {
+                (((value - min) / width) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+
+        Histogram { edges, counts }
+    }
+
+    /// Paths as an array, with one row per path and one column per time step.
+    pub fn to_array2(&self) -> Array2<f64> {
+        Array2::from_shape_fn((self.n_paths(), self.times.len()), |(i, j)| {
+            self.paths[i][j]
+        })
+    }
+
+    /// Paths as a DataFrame, with a `time` column and one column per path
+    /// (`path_0`, `path_1`, ...).
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let mut columns = Vec::with_capacity(self.n_paths() + 1);
+        columns.push(Column::new("time".into(), &self.times));
+
+        for (i, path) in self.paths.iter().enumerate() {
+            columns.push(Column::new(format!("path_{}", i).into(), path));
+        }
+
+        DataFrame::new(columns)
+    }
+
+    /// Summary statistics as a DataFrame: `time`, `mean`, `std`, and one
+    /// column per quantile level (`q0.05`, `q0.5`, ...).
+    pub fn summary_dataframe(&self, levels: &[f64]) -> PolarsResult<DataFrame> {
+        let mut columns = vec![
+            Column::new("time".into(), &self.times),
+            Column::new("mean".into(), self.mean()),
+            Column::new("std".into(), self.std()),
+        ];
+
+        for (level, band) in levels.iter().zip(self.quantiles(levels)) {
+            columns.push(Column::new(format!("q{}", level).into(), band));
+        }
+
+        DataFrame::new(columns)
+    }
+}
+
+/// Pathwise stochastic discount factors, $\exp(-\int_0^t r_s ds)$,
+/// using the trapezoidal rule on short rate paths sampled at `times`.
+pub fn pathwise_discount_factors(times: &[f64], short_rates: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    short_rates
+        .iter()
+        .map(|rates| {
+            let mut integral = 0.0;
+            let mut dfs = Vec::with_capacity(rates.len());
+            dfs.push(1.0);
+
+            for k in 1..rates.len() {
+                let dt = times[k] - times[k - 1];
+                integral += 0.5 * (rates[k - 1] + rates[k]) * dt;
+                dfs.push((-integral).exp());
+            }
+
+            dfs
+        })
+        .collect()
+}
+
+/// Linearly interpolated quantile of a sorted, non-empty sample.
+fn interpolated_quantile(sorted: &[f64], level: f64) -> f64 {
+    let index = level * (sorted.len() - 1) as f64;
+    let lower = sorted[index.floor() as usize];
+    let upper = sorted[index.ceil() as usize];
+
+    lower + (upper - lower) * (index - index.floor())
+}
+
+#[cfg(test)]
+mod tests_trajectories {
+    use super::*;
+    use crate::{GeometricBrownianMotion, StochasticProcess, StochasticProcessConfig};
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn trajectories() -> Trajectories {
+        Trajectories {
+            times: vec![0.0, 0.5, 1.0],
+            paths: vec![
+                vec![1.0, 2.0, 4.0],
+                vec![1.0, 0.0, 1.0],
+                vec![1.0, 1.0, 0.0],
+                vec![1.0, 3.0, 3.0],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_moments_and_quantiles() {
+        let trajectories = trajectories();
+
+        assert_eq!(trajectories.mean(), vec![1.0, 1.5, 2.0]);
+        assert_eq!(trajectories.std()[0], 0.0);
+        assert_approx_equal!(
+            trajectories.std()[2],
+            trajectories.terminal_values().standard_deviation(),
+            1e-12
+        );
+
+        let fan = trajectories.quantiles(&[0.0, 0.5, 1.0]);
+        assert_eq!(fan[0], vec![1.0, 0.0, 0.0]);
+        assert_eq!(fan[1], vec![1.0, 1.5, 2.0]);
+        assert_eq!(fan[2], vec![1.0, 3.0, 4.0]);
+
+        let terminal = trajectories.terminal_values();
+        assert_approx_equal!(
+            trajectories.quantiles(&[0.3])[0][2],
+            terminal.quantile(0.3),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_terminal_histogram() {
+        let histogram = trajectories().terminal_histogram(2);
+
+        assert_eq!(histogram.edges, vec![0.0, 2.0, 4.0]);
+        assert_eq!(histogram.counts, vec![2, 2]);
+
+        let flat = Trajectories {
+            times: vec![0.0],
+            paths: vec![vec![1.0]; 3],
+        };
+        assert_eq!(flat.terminal_histogram(4).counts, vec![3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let trajectories = trajectories();
+
+        let array = trajectories.to_array2();
+        assert_eq!(array.shape(), &[4, 3]);
+        assert_eq!(array[[3, 1]], 3.0);
+
+        let df = trajectories.to_dataframe().unwrap();
+        assert_eq!(df.shape(), (3, 5));
+        assert_eq!(
+            df.column("path_0").unwrap().f64().unwrap().get(2),
+            Some(4.0)
+        );
+
+        let summary = trajectories.summary_dataframe(&[0.05, 0.95]).unwrap();
+        assert_eq!(
+            summary.get_column_names_str(),
+            vec!["time", "mean", "std", "q0.05", "q0.95"]
+        );
+    }
+
+    #[test]
+    fn test_gbm_fan() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 2000, false).with_seed(7);
+        let trajectories = gbm.euler_maruyama(&config);
+
+        // E[S_T] = S_0 exp(mu T).
+        assert_approx_equal!(
+            *trajectories.mean().last().unwrap(),
+            100.0 * 0.05_f64.exp(),
+            1.5
+        );
+
+        // The fan widens over time and its bands are ordered.
+        let fan = trajectories.quantiles(&[0.05, 0.5, 0.95]);
+        assert!(fan[2][50] - fan[0][50] > fan[2][10] - fan[0][10]);
+        assert!((0..=50).all(|t| fan[0][t] <= fan[1][t] && fan[1][t] <= fan[2][t]));
+    }
+
+    #[test]
+    fn test_pathwise_discount_factors() {
+        let times = [0.0, 0.5, 1.5];
+        let dfs = pathwise_discount_factors(&times, &[vec![0.02; 3], vec![0.0, 0.02, 0.04]]);
+
+        assert_approx_equal!(dfs[0][2], (-0.03_f64).exp(), 1e-12);
+        assert_approx_equal!(dfs[1][1], (-0.005_f64).exp(), 1e-12);
+        assert_approx_equal!(dfs[1][2], (-0.035_f64).exp(), 1e-12);
+    }
+}
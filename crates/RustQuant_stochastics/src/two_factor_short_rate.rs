@@ -0,0 +1,147 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! `TwoFactorShortRateModel` is the base trait for shifted two-factor short rate models:
+//! $$ r(t) = x(t) + y(t) + \varphi(t) $$
+//! where $x$ and $y$ are the model factors and the deterministic shift
+//! $\varphi$ is chosen so that the model reproduces an initial discount
+//! curve $P^M(0, t)$ exactly.
+
+use crate::pathwise_discount_factors;
+use rand::{rngs::StdRng, SeedableRng};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Initial (market) discount curve, $t \mapsto P^M(0, t)$.
+pub type DiscountFunction = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
+
+/// Paths of a two-factor short rate model.
+///
+/// Each field is indexed as `[path][time step]`.
+pub struct TwoFactorTrajectories {
+    /// Vector of time points.
+    pub times: Vec<f64>,
+
+    /// First factor paths ($x$).
+    pub x: Vec<Vec<f64>>,
+
+    /// Second factor paths ($y$).
+    pub y: Vec<Vec<f64>>,
+
+    /// Short rate paths, $r = x + y + \varphi$.
+    pub short_rates: Vec<Vec<f64>>,
+}
+
+/// Trait to implement shifted two-factor short rate models.
+pub trait TwoFactorShortRateModel: Sync {
+    /// The initial discount curve the model is fitted to.
+    fn discount_curve(&self) -> &DiscountFunction;
+
+    /// Initial values of the factors, $(x_0, y_0)$.
+    fn initial_factors(&self) -> (f64, f64);
+
+    /// Advance the factors over a step of length `dt`.
+    fn step(&self, x: f64, y: f64, dt: f64, rng: &mut StdRng) -> (f64, f64);
+
+    /// Deterministic shift $\varphi(t)$ that fits the initial discount curve.
+    fn shift(&self, t: f64) -> f64;
+
+    /// Price at `t` of the zero-coupon bond maturing at `maturity`,
+    /// given the factor values $x(t)$ and $y(t)$.
+    fn bond_price(&self, t: f64, maturity: f64, x: f64, y: f64) -> f64;
+
+    /// The model (factor) parameters, excluding the discount curve.
+    fn parameters(&self) -> Vec<f64>;
+
+    /// Replace the model parameters (same order as [TwoFactorShortRateModel::parameters]).
+    /// The fit to the discount curve is kept, so calibrating to option
+    /// prices only needs to search over these.
+    fn set_parameters(&mut self, parameters: &[f64]);
+
+    /// Instantaneous forward rate of the initial curve,
+    /// $f^M(0, t) = -\partial_t \ln P^M(0, t)$, by finite differences.
+    fn instantaneous_forward(&self, t: f64) -> f64 {
+        let curve = self.discount_curve();
+        let h = 1e-5;
+        let lower = (t - h).max(0.0);
+
+        -(curve(t + h).ln() - curve(lower).ln()) / (t + h - lower)
+    }
+
+    /// Simulate the factors and the short rate.
+    ///
+    /// # Arguments:
+    /// * `t_n` - The horizon (in years).
+    /// * `n_steps` - The number of time steps between `0` and `t_n`.
+    /// * `m_paths` - How many paths to simulate.
+    /// * `seed` - Optional seed; path `i` uses `seed + i`.
+    fn simulate(
+        &self,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        seed: Option<u64>,
+    ) -> TwoFactorTrajectories {
+        assert!(t_n > 0.0 && n_steps > 0);
+
+        let dt = t_n / n_steps as f64;
+        let times: Vec<f64> = (0..=n_steps).map(|t| dt * t as f64).collect();
+        let shifts: Vec<f64> = times.iter().map(|&t| self.shift(t)).collect();
+        let (x_0, y_0) = self.initial_factors();
+
+        let paths: Vec<(Vec<f64>, Vec<f64>)> = (0..m_paths)
+            .into_par_iter()
+            .map(|i| {
+                let mut rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(i as u64)),
+                    None => StdRng::from_entropy(),
+                };
+
+                let mut x = vec![x_0; n_steps + 1];
+                let mut y = vec![y_0; n_steps + 1];
+
+                for t in 0..n_steps {
+                    (x[t + 1], y[t + 1]) = self.step(x[t], y[t], dt, &mut rng);
+                }
+
+                (x, y)
+            })
+            .collect();
+
+        let (x, y): (Vec<Vec<f64>>, Vec<Vec<f64>>) = paths.into_iter().unzip();
+        let short_rates = x
+            .iter()
+            .zip(&y)
+            .map(|(x, y)| (0..=n_steps).map(|t| x[t] + y[t] + shifts[t]).collect())
+            .collect();
+
+        TwoFactorTrajectories {
+            times,
+            x,
+            y,
+            short_rates,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TwoFactorTrajectories {
+    /// Pathwise stochastic discount factors, $\exp(-\int_0^t r_s ds)$,
+    /// using the trapezoidal rule on the short rate paths.
+    pub fn discount_factors(&self) -> Vec<Vec<f64>> {
+        pathwise_discount_factors(&self.times, &self.short_rates)
+    }
+}
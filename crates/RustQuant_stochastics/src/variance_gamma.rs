@@ -0,0 +1,183 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::levy_process::LevyProcess;
+use crate::process::{StochasticProcess, StochasticProcessConfig, Trajectories};
+use num::Complex;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Gamma, StandardNormal};
+
+/// Struct containing the Variance Gamma process parameters.
+///
+/// The Variance Gamma process is a Brownian motion with drift,
+/// time-changed by a Gamma process $G_t$ with unit mean rate and
+/// variance rate $\nu$:
+/// $$ X_t = \theta G_t + \sigma W(G_t) $$
+pub struct VarianceGamma {
+    /// The volatility of the Brownian motion ($\sigma$).
+    pub sigma: f64,
+
+    /// The variance rate of the Gamma time change ($\nu$).
+    pub nu: f64,
+
+    /// The drift of the Brownian motion ($\theta$).
+    pub theta: f64,
+}
+
+impl VarianceGamma {
+    /// Create a new Variance Gamma process.
+    ///
+    /// # Arguments:
+    /// * `sigma` - The volatility of the Brownian motion ($\sigma$).
+    /// * `nu` - The variance rate of the Gamma time change ($\nu$).
+    /// * `theta` - The drift of the Brownian motion ($\theta$).
+    pub fn new(sigma: f64, nu: f64, theta: f64) -> Self {
+        assert!(sigma >= 0.0);
+        assert!(nu > 0.0);
+
+        Self { sigma, nu, theta }
+    }
+}
+
+impl LevyProcess for VarianceGamma {
+    fn characteristic_exponent(&self, u: Complex<f64>) -> Complex<f64> {
+        let i: Complex<f64> = Complex::i();
+
+        -(1.0 - i * u * self.theta * self.nu + 0.5 * self.sigma.powi(2) * self.nu * u * u).ln()
+            / self.nu
+    }
+
+    fn sample_increments(&self, dt: f64, n_steps: usize, rng: &mut StdRng) -> Vec<f64> {
+        let gamma = Gamma::new(dt / self.nu, self.nu).unwrap();
+
+        (0..n_steps)
+            .map(|_| {
+                let g: f64 = gamma.sample(rng);
+                let z: f64 = StandardNormal.sample(rng);
+
+                self.theta * g + self.sigma * g.sqrt() * z
+            })
+            .collect()
+    }
+}
+
+impl StochasticProcess for VarianceGamma {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        self.theta
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.sigma, self.nu, self.theta]
+    }
+
+    fn is_diffusion(&self) -> bool {
+        false
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate(config)
+    }
+}
+
+#[cfg(test)]
+mod tests_variance_gamma {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_variance_gamma_moments() {
+        let vg = VarianceGamma::new(0.2, 0.3, -0.15);
+        let output = vg.seedable_euler_maruyama(0.0, 0.0, 1.0, 50, 20000, true, 1234);
+
+        assert_eq!(output.paths.len(), 20000);
+
+        let X_T: Vec<f64> = output
+            .paths
+            .iter()
+            .filter_map(|v| v.last().copied())
+            .collect();
+
+        // E[X_T] = theta * T
+        // V[X_T] = (sigma^2 + nu * theta^2) * T
+        assert_approx_equal!(X_T.mean(), -0.15, 0.01);
+        assert_approx_equal!(X_T.variance(), 0.04 + 0.3 * 0.0225, 0.005);
+    }
+
+    #[test]
+    fn test_variance_gamma_characteristic_function() {
+        let vg = VarianceGamma::new(0.2, 0.3, -0.15);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 0.5, 1, 50000, true).with_seed(42);
+        let output = vg.simulate(&config);
+
+        // Empirical characteristic function vs. closed form.
+        for u in [0.5, 2.0, 5.0] {
+            let phi = vg.characteristic_function(Complex::new(u, 0.0), 0.5);
+
+            let n = output.paths.len() as f64;
+            let re = output.paths.iter().map(|p| (u * p[1]).cos()).sum::<f64>() / n;
+            let im = output.paths.iter().map(|p| (u * p[1]).sin()).sum::<f64>() / n;
+
+            assert_approx_equal!(re, phi.re, 0.01);
+            assert_approx_equal!(im, phi.im, 0.01);
+        }
+
+        // exp(omega * t + X_t) is a martingale.
+        let omega = vg.martingale_correction();
+        let n = output.paths.len() as f64;
+        let mean = output
+            .paths
+            .iter()
+            .map(|p| (omega * 0.5 + p[1]).exp())
+            .sum::<f64>()
+            / n;
+
+        assert_approx_equal!(mean, 1.0, 0.01);
+        assert_approx_equal!(
+            vg.characteristic_function(Complex::new(0.0, 0.0), 0.5).re,
+            1.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Antithetic sampling")]
+    fn test_variance_gamma_antithetic_panics() {
+        let vg = VarianceGamma::new(0.2, 0.3, -0.15);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 10, false);
+
+        vg.antithetic_euler_maruyama(&config);
+    }
+
+    #[test]
+    #[should_panic(expected = "Importance sampling")]
+    fn test_variance_gamma_importance_sampling_panics() {
+        let vg = VarianceGamma::new(0.2, 0.3, -0.15);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 10, false);
+
+        vg.importance_sampled_euler_maruyama(&config, 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Simulation")]
+    fn test_variance_gamma_custom_rng_panics() {
+        let vg = VarianceGamma::new(0.2, 0.3, -0.15);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 10, false);
+
+        vg.euler_maruyama_with_rng::<rand::rngs::StdRng>(&config);
+    }
+}
@@ -0,0 +1,306 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Calendars caching their business days.
+//!
+//! Holiday rules are evaluated date by date, so counting the business days
+//! between two dates decades apart, or over every period of a large
+//! schedule, checks thousands of dates. A [`CachedCalendar`] evaluates each
+//! year of the wrapped calendar once, into a bitmap of its business days.
+//! Business day checks are then a bit lookup, and counting is a few
+//! popcounts per year in the range.
+//!
+//! ```
+//! use RustQuant_time::{CachedCalendar, Calendar, DayCounter, UnitedStatesCalendar};
+//! use time::macros::date;
+//!
+//! let calendar = CachedCalendar::wrap(UnitedStatesCalendar);
+//!
+//! assert!(!calendar.is_business_day(date!(2024 - 07 - 04)));
+//! assert_eq!(
+//!     calendar.business_day_count(date!(2000 - 01 - 01), date!(2049 - 12 - 31)),
+//!     UnitedStatesCalendar.business_day_count(date!(2000 - 01 - 01), date!(2049 - 12 - 31)),
+//! );
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::{assert_supported, Calendar};
+use crate::holiday::HolidayName;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Calendar caching the business days of another calendar, per year.
+///
+/// Years are evaluated the first time a date in them is looked up.
+/// The cache is behind a lock, so a cached calendar can be shared
+/// between threads.
+///
+/// Looking up a date in a year outside of the wrapped calendar's
+/// [`Calendar::supported_years`] panics, rather than caching a year of
+/// missing holidays.
+#[derive(Debug)]
+pub struct CachedCalendar<C> {
+    /// The wrapped calendar.
+    pub calendar: C,
+
+    /// Business day bitmaps, by year.
+    years: RwLock<HashMap<i32, BusinessDays>>,
+}
+
+/// Business days of a year, one bit per day of the year.
+#[derive(Clone, Copy, Debug)]
+struct BusinessDays {
+    bits: [u64; 6],
+    count: i64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<C: Calendar> CachedCalendar<C> {
+    /// Cache the business days of a calendar.
+    pub fn wrap(calendar: C) -> Self {
+        Self {
+            calendar,
+            years: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Number of years currently cached.
+    pub fn cached_years(&self) -> usize {
+        self.years.read().unwrap().len()
+    }
+
+    fn business_days(&self, year: i32) -> BusinessDays {
+        if let Some(&days) = self.years.read().unwrap().get(&year) {
+            return days;
+        }
+
+        let days = BusinessDays::new(&self.calendar, year);
+        self.years.write().unwrap().insert(year, days);
+
+        days
+    }
+}
+
+impl BusinessDays {
+    fn new<C: Calendar>(calendar: &C, year: i32) -> Self {
+        let mut bits = [0_u64; 6];
+        let mut date = Date::from_calendar_date(year, Month::January, 1).unwrap();
+        assert_supported(calendar, date, date);
+
+        while date.year() == year {
+            if calendar.is_business_day(date) {
+                let day = date.ordinal() as usize - 1;
+                bits[day / 64] |= 1 << (day % 64);
+            }
+
+            match date.next_day() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+
+        let count = bits.iter().map(|word| word.count_ones() as i64).sum();
+
+        Self { bits, count }
+    }
+
+    fn contains(&self, day: usize) -> bool {
+        self.bits[day / 64] & (1 << (day % 64)) != 0
+    }
+
+    // Business days among the days of the year in [from, to), zero-based.
+    fn count_between(&self, from: usize, to: usize) -> i64 {
+        (from / 64..to.div_ceil(64))
+            .map(|i| {
+                let lower = from.saturating_sub(i * 64).min(64);
+                let upper = (to - i * 64).min(64);
+
+                (self.bits[i] & mask(lower, upper)).count_ones() as i64
+            })
+            .sum()
+    }
+}
+
+// Bits [lower, upper) of a word.
+fn mask(lower: usize, upper: usize) -> u64 {
+    let below_upper = match upper {
+        64 => u64::MAX,
+        _ => (1 << upper) - 1,
+    };
+
+    below_upper & !((1 << lower) - 1)
+}
+
+impl<C: Calendar> Calendar for CachedCalendar<C> {
+    fn new() -> Self {
+        Self::wrap(C::new())
+    }
+
+    fn name(&self) -> &'static str {
+        self.calendar.name()
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        self.calendar.country_code()
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        self.calendar.market_identifier_code()
+    }
+
+    fn supported_years(&self) -> (i32, i32) {
+        self.calendar.supported_years()
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        self.calendar.is_holiday(date)
+    }
+
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        self.calendar.holiday_name(date)
+    }
+
+    fn is_business_day(&self, date: Date) -> bool {
+        self.business_days(date.year())
+            .contains(date.ordinal() as usize - 1)
+    }
+
+    fn count_business_days(&self, start_date: Date, end_date: Date) -> i64 {
+        if end_date <= start_date {
+            return 0;
+        }
+
+        let (start_year, end_year) = (start_date.year(), end_date.year());
+        let (from, to) = (
+            start_date.ordinal() as usize - 1,
+            end_date.ordinal() as usize - 1,
+        );
+
+        if start_year == end_year {
+            return self.business_days(start_year).count_between(from, to);
+        }
+
+        let first = self.business_days(start_year).count_between(from, 366);
+        let whole: i64 = (start_year + 1..end_year)
+            .map(|year| self.business_days(year).count)
+            .sum();
+        // The range ends on the 1st of January: nothing to count (or check) in the last year.
+        let last = match to {
+            0 => 0,
+            _ => self.business_days(end_year).count_between(0, to),
+        };
+
+        first + whole + last
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_cached_calendar {
+    use super::*;
+    use crate::{
+        DateRoller, DateRollingConvention, DayCounter, IsraelCalendar, TurkeyCalendar,
+        UnitedKingdomCalendar,
+    };
+    use time::macros::date;
+
+    #[test]
+    fn test_matches_wrapped_calendar() {
+        let calendar = CachedCalendar::wrap(IsraelCalendar);
+
+        let mut date = date!(2019 - 12 - 01);
+        while date < date!(2021 - 02 - 01) {
+            assert_eq!(
+                calendar.is_business_day(date),
+                IsraelCalendar.is_business_day(date),
+                "{date}"
+            );
+            date = date.next_day().unwrap();
+        }
+
+        assert_eq!(calendar.cached_years(), 3);
+        assert_eq!(calendar.name(), IsraelCalendar.name());
+    }
+
+    #[test]
+    fn test_count_business_days() {
+        let calendar = CachedCalendar::wrap(UnitedKingdomCalendar);
+
+        // Ranges within a year, across a word boundary, across years,
+        // and ending on the last day of a leap year.
+        let ranges = [
+            (date!(2024 - 01 - 01), date!(2024 - 01 - 01)),
+            (date!(2024 - 01 - 10), date!(2024 - 02 - 20)),
+            (date!(2024 - 03 - 01), date!(2024 - 03 - 09)),
+            (date!(2023 - 06 - 15), date!(2026 - 09 - 01)),
+            (date!(2023 - 12 - 31), date!(2024 - 12 - 31)),
+            (date!(1990 - 02 - 28), date!(2040 - 11 - 30)),
+        ];
+
+        for (start, end) in ranges {
+            assert_eq!(
+                calendar.count_business_days(start, end),
+                UnitedKingdomCalendar.count_business_days(start, end),
+                "{start} to {end}"
+            );
+            assert_eq!(
+                calendar.business_day_count(start, end),
+                UnitedKingdomCalendar.business_day_count(start, end),
+            );
+        }
+
+        assert_eq!(
+            calendar.count_business_days(date!(2024 - 02 - 20), date!(2024 - 01 - 10)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_roll_date() {
+        let calendar = CachedCalendar::wrap(UnitedKingdomCalendar);
+
+        // Christmas 2021 fell on a Saturday, so the holidays moved to the
+        // following Monday and Tuesday.
+        let start = date!(2021 - 12 - 25);
+        assert_eq!(
+            calendar.roll_date(start, &DateRollingConvention::Following),
+            date!(2021 - 12 - 29)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_year() {
+        let calendar = CachedCalendar::wrap(TurkeyCalendar);
+
+        // Up to the end of the holiday tables, exclusive.
+        assert_eq!(
+            calendar.count_business_days(date!(2030 - 01 - 01), date!(2031 - 01 - 01)),
+            TurkeyCalendar.count_business_days(date!(2030 - 01 - 01), date!(2031 - 01 - 01)),
+        );
+
+        let after = std::panic::catch_unwind(|| calendar.is_business_day(date!(2031 - 03 - 03)));
+        assert!(after.is_err());
+        assert_eq!(calendar.cached_years(), 1);
+    }
+}
@@ -9,8 +9,11 @@
 
 //! This module defines a `Calendar` type and its methods.
 
+use crate::constants::{MAX_YEAR, MIN_YEAR};
+use crate::holiday::{HolidayName, UNNAMED_HOLIDAY};
 use crate::utilities::is_weekend;
 use time::Date;
+use RustQuant_error::RustQuantError;
 use RustQuant_iso::*;
 
 /// Calendar trait.
@@ -32,14 +35,85 @@ pub trait Calendar {
     /// Returns the ISO 10383 market identifier code.
     fn market_identifier_code(&self) -> ISO_10383;
 
+    /// First and last years (inclusive) for which the calendar knows its
+    /// holidays. Calendars built on tables of holiday dates, such as lunar
+    /// or Islamic holidays, only know the years of their tables.
+    fn supported_years(&self) -> (i32, i32) {
+        (MIN_YEAR, MAX_YEAR)
+    }
+
+    /// Check that the calendar knows the holidays of the date's year.
+    ///
+    /// Outside of [`Calendar::supported_years`], [`Calendar::is_holiday`]
+    /// misses the tabulated holidays, so callers that need correct business
+    /// days should check their dates first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustQuantError::InvalidArgument` if the year is not supported.
+    fn check_date(&self, date: Date) -> Result<(), RustQuantError> {
+        let (first, last) = self.supported_years();
+
+        if (first..=last).contains(&date.year()) {
+            Ok(())
+        } else {
+            Err(RustQuantError::InvalidArgument(format!(
+                "The {} calendar only knows the holidays of {}-{}, not of {}.",
+                self.name(),
+                first,
+                last,
+                date.year()
+            )))
+        }
+    }
+
+    /// Name of the holiday on the date, or `None` if it is not a holiday.
+    ///
+    /// Calendars that do not name their holidays return [`UNNAMED_HOLIDAY`].
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        self.is_holiday(date).then_some(UNNAMED_HOLIDAY)
+    }
+
     /// Check if the date is a business day.
     /// A business day is a day that is not a holiday and not a weekend.
     fn is_business_day(&self, date: Date) -> bool {
         !is_weekend(date) && !self.is_holiday(date)
     }
 
+    /// Count the business days in `[start_date, end_date)`.
+    ///
+    /// Zero if `end_date` is not after `start_date`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range has years outside of [`Calendar::supported_years`].
+    fn count_business_days(&self, start_date: Date, end_date: Date) -> i64 {
+        if end_date > start_date {
+            assert_supported(self, start_date, end_date.previous_day().unwrap());
+        }
+
+        let mut count = 0;
+        let mut temp_date = start_date;
+
+        while temp_date < end_date {
+            if self.is_business_day(temp_date) {
+                count += 1;
+            }
+
+            temp_date = temp_date.next_day().unwrap();
+        }
+
+        count
+    }
+
     /// Function to list all holidays for a given range of `Date`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range has years outside of [`Calendar::supported_years`].
     fn all_holidays_between(&self, start_date: Date, end_date: Date) -> Vec<Date> {
+        assert_supported(self, start_date, end_date);
+
         let mut holidays = Vec::with_capacity((end_date - start_date).whole_days() as usize);
 
         let mut temp_date = start_date;
@@ -57,8 +131,40 @@ pub trait Calendar {
         holidays
     }
 
+    /// Function to list all holidays for a given range of `Date`s, with their names.
+    ///
+    /// Useful to audit which holidays a calendar recognises, e.g. when
+    /// settlement dates disagree with another system.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range has years outside of [`Calendar::supported_years`].
+    fn holidays_between(&self, start_date: Date, end_date: Date) -> Vec<(Date, HolidayName)> {
+        assert_supported(self, start_date, end_date);
+
+        let mut holidays = Vec::new();
+
+        let mut temp_date = start_date;
+
+        while temp_date <= end_date {
+            if let Some(name) = self.holiday_name(temp_date) {
+                holidays.push((temp_date, name));
+            }
+
+            temp_date = temp_date.next_day().unwrap();
+        }
+
+        holidays
+    }
+
     /// Function to list all business days for a given range of `Date`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range has years outside of [`Calendar::supported_years`].
     fn all_business_days_between(&self, start_date: Date, end_date: Date) -> Vec<Date> {
+        assert_supported(self, start_date, end_date);
+
         let mut business_days = Vec::with_capacity((end_date - start_date).whole_days() as usize);
 
         let mut temp_date = start_date;
@@ -76,3 +182,17 @@ pub trait Calendar {
         business_days
     }
 }
+
+/// Panic if the calendar does not know the holidays of the dates in
+/// `[first, last]` (nothing to check if the range is empty).
+pub(crate) fn assert_supported<C: Calendar + ?Sized>(calendar: &C, first: Date, last: Date) {
+    if first > last {
+        return;
+    }
+
+    for date in [first, last] {
+        if let Err(error) = calendar.check_date(date) {
+            panic!("{}", error);
+        }
+    }
+}
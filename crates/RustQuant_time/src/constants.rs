@@ -9,6 +9,8 @@
 
 //! This module defines calendar and holiday related constants.
 
+use time::Month;
+
 // Minimum and maximum years for the library
 pub(crate) const MIN_YEAR: i32 = 1901;
 pub(crate) const MAX_YEAR: i32 = 2199;
@@ -113,3 +115,55 @@ pub const EASTER_MONDAYS: [[u16; 299]; 2] = [
         116, 108, 128, 119, 104, 124, 116, 100, 120, 112, // 2190-2199
     ],
 ];
+
+/// First day of Eid al-Fitr for the years 2010-2030, as observed in
+/// Turkey and Saudi Arabia (dates after 2025 are projected).
+pub(crate) const EID_AL_FITR: [(i32, Month, u8); 21] = [
+    (2010, Month::September, 9),
+    (2011, Month::August, 30),
+    (2012, Month::August, 19),
+    (2013, Month::August, 8),
+    (2014, Month::July, 28),
+    (2015, Month::July, 17),
+    (2016, Month::July, 5),
+    (2017, Month::June, 25),
+    (2018, Month::June, 15),
+    (2019, Month::June, 4),
+    (2020, Month::May, 24),
+    (2021, Month::May, 13),
+    (2022, Month::May, 2),
+    (2023, Month::April, 21),
+    (2024, Month::April, 10),
+    (2025, Month::March, 30),
+    (2026, Month::March, 20),
+    (2027, Month::March, 9),
+    (2028, Month::February, 26),
+    (2029, Month::February, 14),
+    (2030, Month::February, 4),
+];
+
+/// First day of Eid al-Adha for the years 2010-2030, as observed in
+/// Turkey and Saudi Arabia (dates after 2025 are projected).
+pub(crate) const EID_AL_ADHA: [(i32, Month, u8); 21] = [
+    (2010, Month::November, 16),
+    (2011, Month::November, 6),
+    (2012, Month::October, 25),
+    (2013, Month::October, 15),
+    (2014, Month::October, 4),
+    (2015, Month::September, 24),
+    (2016, Month::September, 12),
+    (2017, Month::September, 1),
+    (2018, Month::August, 21),
+    (2019, Month::August, 11),
+    (2020, Month::July, 31),
+    (2021, Month::July, 20),
+    (2022, Month::July, 9),
+    (2023, Month::June, 28),
+    (2024, Month::June, 16),
+    (2025, Month::June, 6),
+    (2026, Month::May, 27),
+    (2027, Month::May, 16),
+    (2028, Month::May, 5),
+    (2029, Month::April, 24),
+    (2030, Month::April, 13),
+];
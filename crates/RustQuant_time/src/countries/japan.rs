@@ -0,0 +1,193 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Japan national holiday calendar (including the bank holidays of
+/// January 2nd, January 3rd and December 31st).
+pub struct JapanCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for JapanCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Japan"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        JAPAN
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XJPX
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, wd, _, _) = unpack_date(date, false);
+
+        // Holidays falling on a Sunday are observed on the following Monday.
+        let on = |day: u8, month: Month| {
+            m == month && (d == day || (d == day + 1 && wd == Weekday::Monday))
+        };
+
+        let (vernal_equinox, autumnal_equinox) = Self::equinoxes(y);
+
+        if (
+            // New Year's Day and the bank holidays
+            (d <= 3 && m == Month::January)
+            || (d == 31 && m == Month::December)
+            // Coming of Age Day (second Monday of January since 2000)
+            || (y >= 2000 && (8..=14).contains(&d) && wd == Weekday::Monday && m == Month::January)
+            || (y < 2000 && on(15, Month::January))
+            // National Foundation Day
+            || on(11, Month::February)
+            // Emperor's Birthday
+            || (y >= 2020 && on(23, Month::February))
+            || ((1989..=2018).contains(&y) && on(23, Month::December))
+            // Vernal Equinox
+            || on(vernal_equinox, Month::March)
+            // Showa Day (Greenery Day until 2006)
+            || on(29, Month::April)
+            // Constitution Memorial Day, Greenery Day and Children's Day
+            || ((3..=5).contains(&d) && m == Month::May)
+            || (d == 6 && m == Month::May
+                && (wd == Weekday::Monday || (y >= 2007 && (wd == Weekday::Tuesday || wd == Weekday::Wednesday))))
+            // Marine Day (third Monday of July since 2003)
+            || (y >= 2003 && y != 2020 && y != 2021 && (15..=21).contains(&d) && wd == Weekday::Monday && m == Month::July)
+            || ((1996..=2002).contains(&y) && on(20, Month::July))
+            // Mountain Day
+            || (y >= 2016 && y != 2020 && y != 2021 && on(11, Month::August))
+            // Respect for the Aged Day (third Monday of September since 2003)
+            || (y >= 2003 && (15..=21).contains(&d) && wd == Weekday::Monday && m == Month::September)
+            || (y < 2003 && on(15, Month::September))
+            // Tuesday between Respect for the Aged Day and the Autumnal Equinox
+            || (y >= 2003 && wd == Weekday::Tuesday && d + 1 == autumnal_equinox && (16..=22).contains(&d) && m == Month::September)
+            // Autumnal Equinox
+            || on(autumnal_equinox, Month::September)
+            // Sports Day (second Monday of October since 2000)
+            || (y >= 2000 && y != 2020 && y != 2021 && (8..=14).contains(&d) && wd == Weekday::Monday && m == Month::October)
+            || (y < 2000 && on(10, Month::October))
+            // Culture Day
+            || on(3, Month::November)
+            // Labour Thanksgiving Day
+            || on(23, Month::November)
+            // Marine, Sports and Mountain Days moved for the Tokyo Olympics
+            || (y == 2020 && ((m == Month::July && (d == 23 || d == 24)) || (d == 10 && m == Month::August)))
+            || (y == 2021 && ((m == Month::July && (d == 22 || d == 23)) || (d == 9 && m == Month::August)))
+            // Special holidays
+            || (y == 1989 && d == 24 && m == Month::February)
+            || (y == 1990 && d == 12 && m == Month::November)
+            || (y == 1993 && d == 9 && m == Month::June)
+            // Enthronement of Emperor Naruhito
+            || (y == 2019 && ((d == 30 && m == Month::April) || (d <= 2 && m == Month::May) || (d == 22 && m == Month::October)))
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+impl JapanCalendar {
+    /// Days of the vernal (March) and autumnal (September) equinoxes,
+    /// from the approximation used by the National Astronomical Observatory.
+    fn equinoxes(year: i32) -> (u8, u8) {
+        let moving_amount = (year - 2000) as f64 * 0.242194;
+        let leap_years = ((year - 2000) / 4 + (year - 2000) / 100 - (year - 2000) / 400) as f64;
+
+        (
+            (20.69115 + moving_amount - leap_years) as u8,
+            (23.09 + moving_amount - leap_years) as u8,
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_japan {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = JapanCalendar;
+        assert_eq!(calendar.name(), "Japan");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = JapanCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = JapanCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 02), // Bank holiday
+            date!(2024 - 01 - 08), // Coming of Age Day
+            date!(2024 - 02 - 12), // National Foundation Day (observed)
+            date!(2024 - 02 - 23), // Emperor's Birthday
+            date!(2024 - 03 - 20), // Vernal Equinox
+            date!(2024 - 04 - 29), // Showa Day
+            date!(2024 - 05 - 03), // Constitution Memorial Day
+            date!(2024 - 05 - 06), // Children's Day (observed)
+            date!(2024 - 07 - 15), // Marine Day
+            date!(2024 - 08 - 12), // Mountain Day (observed)
+            date!(2024 - 09 - 16), // Respect for the Aged Day
+            date!(2024 - 09 - 23), // Autumnal Equinox (observed)
+            date!(2024 - 10 - 14), // Sports Day
+            date!(2024 - 11 - 04), // Culture Day (observed)
+            date!(2024 - 12 - 31), // Bank holiday
+            date!(2015 - 09 - 22), // Between Respect for the Aged Day and the Equinox
+            date!(2019 - 05 - 01), // Enthronement
+            date!(2021 - 07 - 23), // Sports Day (Olympics)
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = JapanCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 01 - 04)));
+        assert!(calendar.is_business_day(date!(2024 - 05 - 07)));
+        assert!(calendar.is_business_day(date!(2019 - 12 - 23)));
+        assert!(calendar.is_business_day(date!(2021 - 10 - 11)));
+    }
+}
@@ -66,6 +66,9 @@ pub use indonesia::*;
 /// Israeli (Jewish) holidays and calander, implemented with an external API.
 pub mod israel;
 pub use israel::*;
+/// Japan holidays and calendars.
+pub mod japan;
+pub use japan::*;
 /// Mexico holidays and calendars
 pub mod mexico;
 pub use mexico::*;
@@ -75,9 +78,39 @@ pub use netherlands::*;
 /// New Zealand holidays and calendars.
 pub mod new_zealand;
 pub use new_zealand::*;
+/// Norway holidays and calendars.
+pub mod norway;
+pub use norway::*;
+/// Poland holidays and calendars.
+pub mod poland;
+pub use poland::*;
+/// Saudi Arabia holidays and calendars.
+pub mod saudi_arabia;
+pub use saudi_arabia::*;
 /// Singapore holidays and calendars.
 pub mod singapore;
 pub use singapore::*;
+/// South Africa holidays and calendars.
+pub mod south_africa;
+pub use south_africa::*;
+/// South Korea holidays and calendars.
+pub mod south_korea;
+pub use south_korea::*;
+/// Spain holidays and calendars.
+pub mod spain;
+pub use spain::*;
+/// Sweden holidays and calendars.
+pub mod sweden;
+pub use sweden::*;
+/// Switzerland holidays and calendars.
+pub mod switzerland;
+pub use switzerland::*;
+/// Euro area TARGET holidays and calendar.
+pub mod target;
+pub use target::*;
+/// Turkey holidays and calendars.
+pub mod turkey;
+pub use turkey::*;
 /// United Kingdom holidays and calendars.
 pub mod united_kingdom;
 pub use united_kingdom::*;
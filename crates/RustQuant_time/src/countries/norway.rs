@@ -0,0 +1,135 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Norway national holiday calendar.
+pub struct NorwayCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for NorwayCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Norway"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        NORWAY
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XOSL
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (_, m, d, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day
+            (d == 1 && m == Month::January)
+            // Maundy Thursday
+            || (yd == em - 4)
+            // Good Friday
+            || (yd == em - 3)
+            // Easter Monday
+            || (yd == em)
+            // Ascension Day
+            || (yd == em + 38)
+            // Whit Monday
+            || (yd == em + 49)
+            // Labour Day
+            || (d == 1 && m == Month::May)
+            // Constitution Day
+            || (d == 17 && m == Month::May)
+            // Christmas Eve, Christmas Day and Boxing Day
+            || ((24..=26).contains(&d) && m == Month::December)
+            // New Year's Eve
+            || (d == 31 && m == Month::December)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_norway {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = NorwayCalendar;
+        assert_eq!(calendar.name(), "Norway");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = NorwayCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = NorwayCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 03 - 28), // Maundy Thursday
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 04 - 01), // Easter Monday
+            date!(2024 - 05 - 01), // Labour Day
+            date!(2024 - 05 - 09), // Ascension Day
+            date!(2024 - 05 - 17), // Constitution Day
+            date!(2024 - 05 - 20), // Whit Monday
+            date!(2024 - 12 - 24), // Christmas Eve
+            date!(2024 - 12 - 31), // New Year's Eve
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = NorwayCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 03 - 27)));
+        assert!(calendar.is_business_day(date!(2024 - 06 - 21)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 30)));
+    }
+}
@@ -0,0 +1,141 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Poland national holiday calendar.
+pub struct PolandCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for PolandCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Poland"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        POLAND
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XWAR
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day
+            (d == 1 && m == Month::January)
+            // Epiphany (since 2011)
+            || (d == 6 && m == Month::January && y >= 2011)
+            // Easter Monday
+            || (yd == em)
+            // Labour Day
+            || (d == 1 && m == Month::May)
+            // Constitution Day
+            || (d == 3 && m == Month::May)
+            // Corpus Christi
+            || (yd == em + 59)
+            // Assumption of Mary
+            || (d == 15 && m == Month::August)
+            // All Saints' Day
+            || (d == 1 && m == Month::November)
+            // Independence Day
+            || (d == 11 && m == Month::November)
+            // Centenary of independence
+            || (d == 12 && m == Month::November && y == 2018)
+            // Christmas Eve (since 2025)
+            || (d == 24 && m == Month::December && y >= 2025)
+            // Christmas Day and Boxing Day
+            || ((d == 25 || d == 26) && m == Month::December)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_poland {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = PolandCalendar;
+        assert_eq!(calendar.name(), "Poland");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = PolandCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = PolandCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2025 - 01 - 06), // Epiphany
+            date!(2024 - 04 - 01), // Easter Monday
+            date!(2024 - 05 - 01), // Labour Day
+            date!(2024 - 05 - 03), // Constitution Day
+            date!(2024 - 05 - 30), // Corpus Christi
+            date!(2024 - 08 - 15), // Assumption of Mary
+            date!(2024 - 11 - 01), // All Saints' Day
+            date!(2024 - 11 - 11), // Independence Day
+            date!(2018 - 11 - 12), // Centenary of independence
+            date!(2025 - 12 - 24), // Christmas Eve
+            date!(2024 - 12 - 26), // Boxing Day
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = PolandCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 12 - 24)));
+        assert!(calendar.is_business_day(date!(2010 - 01 - 06)));
+        assert!(calendar.is_business_day(date!(2024 - 03 - 29)));
+    }
+}
@@ -0,0 +1,161 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::constants::{EID_AL_ADHA, EID_AL_FITR};
+use crate::utilities::{is_in_holiday_period, unpack_date};
+use time::{macros::date, Date, Month, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Saudi Arabia national holiday calendar.
+///
+/// The weekend is Friday and Saturday (Thursday and Friday before
+/// June 29th, 2013). The Eid holidays are tabulated for the years 2010-2030,
+/// and other years are rejected by [`Calendar::check_date`].
+pub struct SaudiArabiaCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SaudiArabiaCalendar {
+    fn is_weekend(&self, date: Date) -> bool {
+        let wd = date.weekday();
+
+        if date < date!(2013 - 06 - 29) {
+            wd == Weekday::Thursday || wd == Weekday::Friday
+        } else {
+            wd == Weekday::Friday || wd == Weekday::Saturday
+        }
+    }
+}
+
+impl Calendar for SaudiArabiaCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Saudi Arabia"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        SAUDI_ARABIA
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XSAU
+    }
+
+    fn is_business_day(&self, date: Date) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    fn supported_years(&self) -> (i32, i32) {
+        (EID_AL_FITR[0].0, EID_AL_FITR[EID_AL_FITR.len() - 1].0)
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, wd, _, _) = unpack_date(date, false);
+
+        // National days falling on a Friday are observed on the Thursday,
+        // and on a Saturday on the Sunday.
+        let on = |day: u8, month: Month| {
+            m == month
+                && (d == day
+                    || (d + 1 == day && wd == Weekday::Thursday)
+                    || (d == day + 1 && wd == Weekday::Sunday))
+        };
+
+        if (
+            // Founding Day (since 2022)
+            (y >= 2022 && on(22, Month::February))
+            // National Day (since 2005)
+            || (y >= 2005 && on(23, Month::September))
+            // Eid al-Fitr (four days)
+            || is_in_holiday_period(date, &EID_AL_FITR, 0, 4)
+            // Day of Arafah and Eid al-Adha (four days)
+            || is_in_holiday_period(date, &EID_AL_ADHA, -1, 4)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_saudi_arabia {
+    use super::*;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = SaudiArabiaCalendar;
+        assert_eq!(calendar.name(), "Saudi Arabia");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = SaudiArabiaCalendar;
+
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 07)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(calendar.is_business_day(date!(2024 - 06 - 09)));
+
+        // Thursday and Friday before the change.
+        assert!(!calendar.is_business_day(date!(2013 - 06 - 20)));
+        assert!(calendar.is_business_day(date!(2013 - 06 - 22)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = SaudiArabiaCalendar;
+
+        let holidays = [
+            date!(2024 - 02 - 22), // Founding Day
+            date!(2024 - 04 - 10), // Eid al-Fitr
+            date!(2024 - 04 - 11), // Eid al-Fitr
+            date!(2024 - 06 - 15), // Day of Arafah
+            date!(2024 - 06 - 18), // Eid al-Adha
+            date!(2024 - 09 - 23), // National Day
+            date!(2023 - 09 - 24), // National Day (observed)
+            date!(2022 - 09 - 22), // National Day (observed)
+        ];
+
+        for holiday in holidays {
+            assert!(calendar.is_holiday(holiday), "{holiday}");
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = SaudiArabiaCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 04 - 15)));
+        assert!(calendar.is_business_day(date!(2024 - 06 - 19)));
+        assert!(calendar.is_business_day(date!(2024 - 09 - 24)));
+    }
+}
@@ -0,0 +1,171 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// South Africa national holiday calendar.
+pub struct SouthAfricaCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for SouthAfricaCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "South Africa"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        SOUTH_AFRICA
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XJSE
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, wd, yd, em) = unpack_date(date, false);
+
+        // Public holidays falling on a Sunday are observed on the Monday.
+        let on = |day: u8, month: Month| {
+            m == month && (d == day || (d == day + 1 && wd == Weekday::Monday))
+        };
+
+        if (
+            // New Year's Day
+            on(1, Month::January)
+            // Good Friday
+            || (yd == em - 3)
+            // Family Day
+            || (yd == em)
+            // Human Rights Day
+            || on(21, Month::March)
+            // Freedom Day
+            || on(27, Month::April)
+            // Workers' Day
+            || on(1, Month::May)
+            // Youth Day
+            || on(16, Month::June)
+            // National Women's Day
+            || (y >= 1995 && on(9, Month::August))
+            // Heritage Day
+            || on(24, Month::September)
+            // Day of Reconciliation
+            || on(16, Month::December)
+            // Christmas Day
+            || (d == 25 && m == Month::December)
+            // Day of Goodwill
+            || on(26, Month::December)
+            // Election days and special holidays
+            || self.is_special_holiday(y, d, m)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+impl SouthAfricaCalendar {
+    fn is_special_holiday(&self, year: i32, day: u8, month: Month) -> bool {
+        matches!(
+            (year, day, month),
+            // Election days
+            (1999, 2, Month::June)
+                | (2004, 14, Month::April)
+                | (2006, 1, Month::March)
+                | (2009, 22, Month::April)
+                | (2011, 18, Month::May)
+                | (2014, 7, Month::May)
+                | (2016, 3, Month::August)
+                | (2019, 8, Month::May)
+                | (2021, 1, Month::November)
+                | (2024, 29, Month::May)
+                // Special holidays
+                | (2008, 2, Month::May)
+                | (2011, 27, Month::December)
+                | (2022, 27, Month::December)
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_south_africa {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = SouthAfricaCalendar;
+        assert_eq!(calendar.name(), "South Africa");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = SouthAfricaCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = SouthAfricaCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 03 - 21), // Human Rights Day
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 04 - 01), // Family Day
+            date!(2024 - 05 - 01), // Workers' Day
+            date!(2024 - 05 - 29), // General election
+            date!(2024 - 06 - 17), // Youth Day (observed)
+            date!(2024 - 08 - 09), // National Women's Day
+            date!(2024 - 09 - 24), // Heritage Day
+            date!(2024 - 12 - 16), // Day of Reconciliation
+            date!(2024 - 12 - 26), // Day of Goodwill
+            date!(2022 - 12 - 27), // Special holiday
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = SouthAfricaCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 04 - 29)));
+        assert!(calendar.is_business_day(date!(2024 - 06 - 18)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 27)));
+    }
+}
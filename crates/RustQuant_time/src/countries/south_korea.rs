@@ -0,0 +1,407 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::{is_in_holiday_period, is_weekend, unpack_date};
+use time::{Date, Duration, Month, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// South Korea national holiday calendar.
+///
+/// The lunar holidays (Seollal, Buddha's Birthday and Chuseok) are
+/// tabulated for the years 2000-2030, and other years are rejected by
+/// [`Calendar::check_date`].
+pub struct SouthKoreaCalendar;
+
+/// Seollal (Lunar New Year's Day).
+const SEOLLAL: [(i32, Month, u8); 31] = [
+    (2000, Month::February, 5),
+    (2001, Month::January, 24),
+    (2002, Month::February, 12),
+    (2003, Month::February, 1),
+    (2004, Month::January, 22),
+    (2005, Month::February, 9),
+    (2006, Month::January, 29),
+    (2007, Month::February, 18),
+    (2008, Month::February, 7),
+    (2009, Month::January, 26),
+    (2010, Month::February, 14),
+    (2011, Month::February, 3),
+    (2012, Month::January, 23),
+    (2013, Month::February, 10),
+    (2014, Month::January, 31),
+    (2015, Month::February, 19),
+    (2016, Month::February, 8),
+    (2017, Month::January, 28),
+    (2018, Month::February, 16),
+    (2019, Month::February, 5),
+    (2020, Month::January, 25),
+    (2021, Month::February, 12),
+    (2022, Month::February, 1),
+    (2023, Month::January, 22),
+    (2024, Month::February, 10),
+    (2025, Month::January, 29),
+    (2026, Month::February, 17),
+    (2027, Month::February, 7),
+    (2028, Month::January, 27),
+    (2029, Month::February, 13),
+    (2030, Month::February, 3),
+];
+
+/// Buddha's Birthday.
+const BUDDHAS_BIRTHDAY: [(i32, Month, u8); 31] = [
+    (2000, Month::May, 11),
+    (2001, Month::May, 1),
+    (2002, Month::May, 19),
+    (2003, Month::May, 8),
+    (2004, Month::May, 26),
+    (2005, Month::May, 15),
+    (2006, Month::May, 5),
+    (2007, Month::May, 24),
+    (2008, Month::May, 12),
+    (2009, Month::May, 2),
+    (2010, Month::May, 21),
+    (2011, Month::May, 10),
+    (2012, Month::May, 28),
+    (2013, Month::May, 17),
+    (2014, Month::May, 6),
+    (2015, Month::May, 25),
+    (2016, Month::May, 14),
+    (2017, Month::May, 3),
+    (2018, Month::May, 22),
+    (2019, Month::May, 12),
+    (2020, Month::April, 30),
+    (2021, Month::May, 19),
+    (2022, Month::May, 8),
+    (2023, Month::May, 27),
+    (2024, Month::May, 15),
+    (2025, Month::May, 5),
+    (2026, Month::May, 24),
+    (2027, Month::May, 13),
+    (2028, Month::May, 2),
+    (2029, Month::May, 20),
+    (2030, Month::May, 9),
+];
+
+/// Chuseok (Korean Thanksgiving Day).
+const CHUSEOK: [(i32, Month, u8); 31] = [
+    (2000, Month::September, 12),
+    (2001, Month::October, 1),
+    (2002, Month::September, 21),
+    (2003, Month::September, 11),
+    (2004, Month::September, 28),
+    (2005, Month::September, 18),
+    (2006, Month::October, 6),
+    (2007, Month::September, 25),
+    (2008, Month::September, 14),
+    (2009, Month::October, 3),
+    (2010, Month::September, 22),
+    (2011, Month::September, 12),
+    (2012, Month::September, 30),
+    (2013, Month::September, 19),
+    (2014, Month::September, 8),
+    (2015, Month::September, 27),
+    (2016, Month::September, 15),
+    (2017, Month::October, 4),
+    (2018, Month::September, 24),
+    (2019, Month::September, 13),
+    (2020, Month::October, 1),
+    (2021, Month::September, 21),
+    (2022, Month::September, 10),
+    (2023, Month::September, 29),
+    (2024, Month::September, 17),
+    (2025, Month::October, 6),
+    (2026, Month::September, 25),
+    (2027, Month::September, 15),
+    (2028, Month::October, 3),
+    (2029, Month::September, 22),
+    (2030, Month::September, 12),
+];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for SouthKoreaCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "South Korea"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        KOREA_REPUBLIC
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XKRX
+    }
+
+    fn supported_years(&self) -> (i32, i32) {
+        (SEOLLAL[0].0, SEOLLAL[SEOLLAL.len() - 1].0)
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        self.is_statutory_holiday(date) || self.is_substitute_holiday(date)
+    }
+}
+
+impl SouthKoreaCalendar {
+    /// Holidays on their own dates, without substitute holidays.
+    fn is_statutory_holiday(&self, date: Date) -> bool {
+        self.is_solar_holiday(date) || self.is_lunar_holiday(date)
+    }
+
+    /// Seollal and Chuseok: the day itself, the day before and the day after.
+    fn is_lunar_holiday(&self, date: Date) -> bool {
+        is_in_holiday_period(date, &SEOLLAL, -1, 3) || is_in_holiday_period(date, &CHUSEOK, -1, 3)
+    }
+
+    fn is_solar_holiday(&self, date: Date) -> bool {
+        let (y, m, d, _, _, _) = unpack_date(date, false);
+
+        (
+            // New Year's Day and Independence Movement Day
+            (d == 1 && (m == Month::January || m == Month::March))
+            // Arbor Day
+            || (y <= 2005 && d == 5 && m == Month::April)
+            // Children's Day
+            || (d == 5 && m == Month::May)
+            // Buddha's Birthday
+            || is_in_holiday_period(date, &BUDDHAS_BIRTHDAY, 0, 1)
+            // Memorial Day
+            || (d == 6 && m == Month::June)
+            // Constitution Day
+            || (y <= 2007 && d == 17 && m == Month::July)
+            // Liberation Day
+            || (d == 15 && m == Month::August)
+            // National Foundation Day
+            || (d == 3 && m == Month::October)
+            // Hangul Day
+            || ((y <= 1990 || y >= 2013) && d == 9 && m == Month::October)
+            // Christmas Day
+            || (d == 25 && m == Month::December)
+            // Election days
+            || self.is_election_day(y, d, m)
+            // Temporary public holidays
+            || matches!(
+                (y, d, m),
+                (2015, 14, Month::August)
+                    | (2016, 6, Month::May)
+                    | (2017, 2, Month::October)
+                    | (2020, 17, Month::August)
+                    | (2023, 2, Month::October)
+                    | (2024, 1, Month::October)
+                    | (2025, 27, Month::January)
+            )
+        )
+    }
+
+    fn is_election_day(&self, year: i32, day: u8, month: Month) -> bool {
+        matches!(
+            (year, day, month),
+            // Presidential elections
+            (2002, 19, Month::December)
+                | (2007, 19, Month::December)
+                | (2012, 19, Month::December)
+                | (2017, 9, Month::May)
+                | (2022, 9, Month::March)
+                | (2025, 3, Month::June)
+                // National Assembly elections
+                | (2004, 15, Month::April)
+                | (2008, 9, Month::April)
+                | (2012, 11, Month::April)
+                | (2016, 13, Month::April)
+                | (2020, 15, Month::April)
+                | (2024, 10, Month::April)
+                // Local elections
+                | (2002, 13, Month::June)
+                | (2006, 31, Month::May)
+                | (2010, 2, Month::June)
+                | (2014, 4, Month::June)
+                | (2018, 13, Month::June)
+                | (2022, 1, Month::June)
+        )
+    }
+
+    fn is_substitute_holiday(&self, date: Date) -> bool {
+        date.year() >= 2014
+            && !is_weekend(date)
+            && !self.is_statutory_holiday(date)
+            && self.substitute_holidays(date.year()).contains(&date)
+    }
+
+    /// Substitute holidays of a year.
+    ///
+    /// Since 2014, when Seollal or Chuseok overlaps a Sunday or another
+    /// holiday, or Children's Day falls on a weekend or another holiday, the
+    /// next non-holiday weekday is a holiday. The rule covers the national
+    /// days falling on a weekend since 2021, and Buddha's Birthday and
+    /// Christmas since 2023.
+    fn substitute_holidays(&self, year: i32) -> Vec<Date> {
+        // (last day of the holiday, number of substitute days)
+        let mut periods: Vec<(Date, usize)> = Vec::new();
+
+        for table in [&SEOLLAL, &CHUSEOK] {
+            if let Some(&(y, m, d)) = table.iter().find(|&&(y, _, _)| y == year) {
+                let Ok(day) = Date::from_calendar_date(y, m, d) else {
+                    continue;
+                };
+
+                let days = [day - Duration::days(1), day, day + Duration::days(1)];
+                let overlaps = days
+                    .iter()
+                    .filter(|&&day| day.weekday() == Weekday::Sunday || self.is_solar_holiday(day))
+                    .count();
+
+                periods.push((days[2], overlaps));
+            }
+        }
+
+        let fixed = |month: Month, day: u8| Date::from_calendar_date(year, month, day).ok();
+
+        if let Some(childrens_day) = fixed(Month::May, 5) {
+            let buddhas_birthday = is_in_holiday_period(childrens_day, &BUDDHAS_BIRTHDAY, 0, 1);
+            periods.push((
+                childrens_day,
+                usize::from(is_weekend(childrens_day) || buddhas_birthday),
+            ));
+        }
+
+        let mut weekend_rule = Vec::new();
+
+        if year >= 2021 {
+            weekend_rule.extend(
+                [
+                    (Month::March, 1),
+                    (Month::August, 15),
+                    (Month::October, 3),
+                    (Month::October, 9),
+                ]
+                .iter()
+                .filter_map(|&(month, day)| fixed(month, day)),
+            );
+        }
+
+        if year >= 2023 {
+            weekend_rule.extend(fixed(Month::December, 25));
+            weekend_rule.extend(
+                BUDDHAS_BIRTHDAY
+                    .iter()
+                    .filter(|&&(y, _, _)| y == year)
+                    .filter_map(|&(y, m, d)| Date::from_calendar_date(y, m, d).ok()),
+            );
+        }
+
+        periods.extend(
+            weekend_rule
+                .into_iter()
+                .map(|day| (day, usize::from(is_weekend(day)))),
+        );
+        periods.sort();
+
+        let mut substitutes = Vec::new();
+
+        for (last_day, count) in periods {
+            let mut day = last_day;
+
+            for _ in 0..count {
+                day += Duration::days(1);
+
+                while is_weekend(day)
+                    || self.is_statutory_holiday(day)
+                    || substitutes.contains(&day)
+                {
+                    day += Duration::days(1);
+                }
+
+                substitutes.push(day);
+            }
+        }
+
+        substitutes
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_south_korea {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = SouthKoreaCalendar;
+        assert_eq!(calendar.name(), "South Korea");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = SouthKoreaCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = SouthKoreaCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 02 - 09), // Seollal
+            date!(2024 - 02 - 12), // Seollal (substitute)
+            date!(2024 - 04 - 10), // National Assembly election
+            date!(2024 - 05 - 06), // Children's Day (substitute)
+            date!(2024 - 05 - 15), // Buddha's Birthday
+            date!(2024 - 09 - 16), // Chuseok
+            date!(2024 - 09 - 18), // Chuseok
+            date!(2024 - 10 - 01), // Armed Forces Day (temporary)
+            date!(2024 - 10 - 09), // Hangul Day
+            date!(2017 - 10 - 06), // Chuseok overlapping National Foundation Day
+            date!(2021 - 08 - 16), // Liberation Day (substitute)
+            date!(2023 - 05 - 29), // Buddha's Birthday (substitute)
+            date!(2025 - 03 - 03), // Independence Movement Day (substitute)
+            date!(2025 - 05 - 06), // Children's Day on Buddha's Birthday
+            date!(2025 - 10 - 08), // Chuseok (substitute)
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = SouthKoreaCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 02 - 13)));
+        assert!(calendar.is_business_day(date!(2024 - 09 - 19)));
+        // Christmas on a Saturday had no substitute before 2023.
+        assert!(calendar.is_business_day(date!(2021 - 12 - 27)));
+        // Chuseok on a Saturday has no substitute.
+        assert!(calendar.is_business_day(date!(2023 - 10 - 04)));
+    }
+}
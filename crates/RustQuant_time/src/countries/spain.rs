@@ -0,0 +1,133 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Spain national holiday calendar.
+pub struct SpainCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for SpainCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Spain"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        SPAIN
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XMAD
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (_, m, d, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day and Epiphany
+            ((d == 1 || d == 6) && m == Month::January)
+            // Good Friday
+            || (yd == em - 3)
+            // Labour Day
+            || (d == 1 && m == Month::May)
+            // Assumption of Mary
+            || (d == 15 && m == Month::August)
+            // National Day
+            || (d == 12 && m == Month::October)
+            // All Saints' Day
+            || (d == 1 && m == Month::November)
+            // Constitution Day
+            || (d == 6 && m == Month::December)
+            // Immaculate Conception
+            || (d == 8 && m == Month::December)
+            // Christmas Day
+            || (d == 25 && m == Month::December)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_spain {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = SpainCalendar;
+        assert_eq!(calendar.name(), "Spain");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = SpainCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = SpainCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2025 - 01 - 06), // Epiphany
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 05 - 01), // Labour Day
+            date!(2024 - 08 - 15), // Assumption of Mary
+            date!(2023 - 10 - 12), // National Day
+            date!(2024 - 11 - 01), // All Saints' Day
+            date!(2024 - 12 - 06), // Constitution Day
+            date!(2025 - 12 - 08), // Immaculate Conception
+            date!(2024 - 12 - 25), // Christmas Day
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = SpainCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 04 - 01)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 26)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 31)));
+    }
+}
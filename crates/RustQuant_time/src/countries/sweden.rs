@@ -0,0 +1,136 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sweden national holiday calendar.
+pub struct SwedenCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for SwedenCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Sweden"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        SWEDEN
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XSTO
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, wd, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day and Epiphany
+            ((d == 1 || d == 6) && m == Month::January)
+            // Good Friday
+            || (yd == em - 3)
+            // Easter Monday
+            || (yd == em)
+            // Ascension Day
+            || (yd == em + 38)
+            // Whit Monday (until 2004)
+            || (yd == em + 49 && y < 2005)
+            // Labour Day
+            || (d == 1 && m == Month::May)
+            // National Day (since 2005)
+            || (d == 6 && m == Month::June && y >= 2005)
+            // Midsummer Eve (Friday between June 19th and 25th)
+            || ((19..=25).contains(&d) && wd == Weekday::Friday && m == Month::June)
+            // Christmas Eve, Christmas Day and Boxing Day
+            || ((24..=26).contains(&d) && m == Month::December)
+            // New Year's Eve
+            || (d == 31 && m == Month::December)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_sweden {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = SwedenCalendar;
+        assert_eq!(calendar.name(), "Sweden");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = SwedenCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = SwedenCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2025 - 01 - 06), // Epiphany
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 04 - 01), // Easter Monday
+            date!(2024 - 05 - 01), // Labour Day
+            date!(2024 - 05 - 09), // Ascension Day
+            date!(2024 - 06 - 06), // National Day
+            date!(2024 - 06 - 21), // Midsummer Eve
+            date!(2024 - 12 - 24), // Christmas Eve
+            date!(2024 - 12 - 31), // New Year's Eve
+            date!(2004 - 05 - 31), // Whit Monday
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = SwedenCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 05 - 20)));
+        assert!(calendar.is_business_day(date!(2024 - 06 - 20)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 30)));
+    }
+}
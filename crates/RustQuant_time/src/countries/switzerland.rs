@@ -0,0 +1,131 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Switzerland national holiday calendar.
+pub struct SwitzerlandCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for SwitzerlandCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Switzerland"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        SWITZERLAND
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XSWX
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (_, m, d, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day and Berchtoldstag
+            ((d == 1 || d == 2) && m == Month::January)
+            // Good Friday
+            || (yd == em - 3)
+            // Easter Monday
+            || (yd == em)
+            // Ascension Day
+            || (yd == em + 38)
+            // Whit Monday
+            || (yd == em + 49)
+            // Labour Day
+            || (d == 1 && m == Month::May)
+            // National Day
+            || (d == 1 && m == Month::August)
+            // Christmas Day
+            || (d == 25 && m == Month::December)
+            // St. Stephen's Day
+            || (d == 26 && m == Month::December)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_switzerland {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = SwitzerlandCalendar;
+        assert_eq!(calendar.name(), "Switzerland");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = SwitzerlandCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = SwitzerlandCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 02), // Berchtoldstag
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 04 - 01), // Easter Monday
+            date!(2024 - 05 - 01), // Labour Day
+            date!(2024 - 05 - 09), // Ascension Day
+            date!(2024 - 05 - 20), // Whit Monday
+            date!(2024 - 08 - 01), // National Day
+            date!(2024 - 12 - 26), // St. Stephen's Day
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = SwitzerlandCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 01 - 03)));
+        assert!(calendar.is_business_day(date!(2024 - 04 - 02)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 27)));
+    }
+}
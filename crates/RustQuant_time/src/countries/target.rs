@@ -0,0 +1,142 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::holiday::HolidayName;
+use crate::utilities::unpack_date;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Euro area TARGET (Trans-European Automated Real-time Gross settlement
+/// Express Transfer) calendar, used for EUR fixings and settlement.
+/// The ECB is based in Frankfurt, so the German country code is reported.
+pub struct TargetCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for TargetCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "TARGET"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        GERMANY
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XFRA
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        self.holiday_name(date).is_some()
+    }
+
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        let (y, m, d, _wd, yd, em) = unpack_date(date, false);
+
+        let name = if (d == 1 && m == Month::January) {
+            "New Year's Day"
+        } else if (yd == em - 3 && y >= 2000) {
+            "Good Friday"
+        } else if (yd == em && y >= 2000) {
+            "Easter Monday"
+        } else if (d == 1 && m == Month::May && y >= 2000) {
+            "Labour Day"
+        } else if (d == 25 && m == Month::December) {
+            "Christmas Day"
+        } else if (d == 26 && m == Month::December && y >= 2000) {
+            "St. Stephen's Day"
+        } else if (d == 31 && m == Month::December && (y == 1998 || y == 1999 || y == 2001)) {
+            // December 31st, 1998, 1999, and 2001 only
+            "New Year's Eve"
+        } else {
+            return None;
+        };
+
+        Some(name)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_target {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the holidays_between() method.
+    #[test]
+    fn test_holidays_between() {
+        let calendar = TargetCalendar;
+
+        assert_eq!(
+            calendar.holidays_between(date!(2024 - 03 - 01), date!(2024 - 05 - 31)),
+            vec![
+                (date!(2024 - 03 - 29), "Good Friday"),
+                (date!(2024 - 04 - 01), "Easter Monday"),
+                (date!(2024 - 05 - 01), "Labour Day"),
+            ]
+        );
+    }
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = TargetCalendar;
+        assert_eq!(calendar.name(), "TARGET");
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for TARGET holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = TargetCalendar;
+        let new_years_day = date!(2024 - 01 - 01);
+        let good_friday = date!(2024 - 03 - 29);
+        let easter_monday = date!(2024 - 04 - 01);
+        let labour_day = date!(2024 - 05 - 01);
+        let christmas = date!(2024 - 12 - 25);
+        let st_stephens_day = date!(2024 - 12 - 26);
+
+        assert!(!calendar.is_business_day(new_years_day));
+        assert!(!calendar.is_business_day(good_friday));
+        assert!(!calendar.is_business_day(easter_monday));
+        assert!(!calendar.is_business_day(labour_day));
+        assert!(!calendar.is_business_day(christmas));
+        assert!(!calendar.is_business_day(st_stephens_day));
+    }
+
+    // Test to verify that national holidays are TARGET business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = TargetCalendar;
+        let german_unity_day = date!(2024 - 10 - 03);
+        let ascension_day = date!(2024 - 05 - 09);
+        let assumption_day = date!(2024 - 08 - 15);
+
+        assert!(calendar.is_business_day(german_unity_day));
+        assert!(calendar.is_business_day(ascension_day));
+        assert!(calendar.is_business_day(assumption_day));
+    }
+}
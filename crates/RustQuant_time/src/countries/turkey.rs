@@ -0,0 +1,163 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::constants::{EID_AL_ADHA, EID_AL_FITR};
+use crate::utilities::{is_in_holiday_period, unpack_date};
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Turkey national holiday calendar.
+///
+/// Holidays are not moved when they fall on a weekend. The religious
+/// holidays are tabulated for the years 2010-2030 (other years are rejected
+/// by [`Calendar::check_date`]), and the half days before them and before
+/// Republic Day are business days.
+pub struct TurkeyCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for TurkeyCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Turkey"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        TURKEY
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XIST
+    }
+
+    fn supported_years(&self) -> (i32, i32) {
+        (EID_AL_FITR[0].0, EID_AL_FITR[EID_AL_FITR.len() - 1].0)
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, _, _, _) = unpack_date(date, false);
+
+        if (
+            // New Year's Day
+            (d == 1 && m == Month::January)
+            // National Sovereignty and Children's Day
+            || (d == 23 && m == Month::April)
+            // Labour and Solidarity Day (since 2009)
+            || (d == 1 && m == Month::May && y >= 2009)
+            // Commemoration of Atatürk, Youth and Sports Day
+            || (d == 19 && m == Month::May)
+            // Democracy and National Unity Day (since 2017)
+            || (d == 15 && m == Month::July && y >= 2017)
+            // Victory Day
+            || (d == 30 && m == Month::August)
+            // Republic Day
+            || (d == 29 && m == Month::October)
+            // Ramadan Feast (three days)
+            || is_in_holiday_period(date, &EID_AL_FITR, 0, 3)
+            // Feast of the Sacrifice (four days)
+            || is_in_holiday_period(date, &EID_AL_ADHA, 0, 4)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_turkey {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = TurkeyCalendar;
+        assert_eq!(calendar.name(), "Turkey");
+    }
+
+    // Test to verify if weekends are not considered business days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = TurkeyCalendar;
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_business_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = TurkeyCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 04 - 10), // Ramadan Feast
+            date!(2024 - 04 - 12), // Ramadan Feast
+            date!(2024 - 04 - 23), // National Sovereignty and Children's Day
+            date!(2024 - 05 - 01), // Labour and Solidarity Day
+            date!(2024 - 06 - 17), // Feast of the Sacrifice
+            date!(2024 - 06 - 19), // Feast of the Sacrifice
+            date!(2024 - 07 - 15), // Democracy and National Unity Day
+            date!(2024 - 08 - 30), // Victory Day
+            date!(2024 - 10 - 29), // Republic Day
+            date!(2020 - 08 - 03), // Feast of the Sacrifice
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_business_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = TurkeyCalendar;
+
+        // Eves of the feasts are half days.
+        assert!(calendar.is_business_day(date!(2024 - 04 - 09)));
+        assert!(calendar.is_business_day(date!(2024 - 06 - 20)));
+        assert!(calendar.is_business_day(date!(2024 - 10 - 28)));
+    }
+
+    // Test to verify that years outside of the holiday tables are rejected.
+    #[test]
+    fn test_supported_years() {
+        let calendar = TurkeyCalendar;
+
+        assert_eq!(calendar.supported_years(), (2010, 2030));
+        assert!(calendar.check_date(date!(2030 - 12 - 31)).is_ok());
+        assert!(calendar.check_date(date!(2031 - 01 - 01)).is_err());
+        assert!(calendar.check_date(date!(2009 - 12 - 31)).is_err());
+    }
+
+    // Test to verify that ranges outside of the holiday tables fail.
+    #[test]
+    #[should_panic(expected = "only knows the holidays of 2010-2030")]
+    fn test_unsupported_range() {
+        TurkeyCalendar.count_business_days(date!(2030 - 06 - 01), date!(2031 - 06 - 01));
+    }
+}
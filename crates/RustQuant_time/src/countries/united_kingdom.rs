@@ -12,6 +12,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::calendar::Calendar;
+use crate::holiday::HolidayName;
 use crate::utilities::unpack_date;
 use time::{Date, Month, Weekday};
 use RustQuant_iso::*;
@@ -45,52 +46,86 @@ impl Calendar for UnitedKingdomCalendar {
     }
 
     fn is_holiday(&self, date: Date) -> bool {
-        let (y, m, d, wd, yd, em) = unpack_date(date, false);
+        self.holiday_name(date).is_some()
+    }
 
-        if (
-            // New Year's Day (possibly moved to Monday)
-            ((d == 1 || ((d == 2 || d == 3) && wd == Weekday::Monday)) && m == Month::January)
-             // Good Friday
-             || (yd == em - 3)
-             // Easter Monday
-             || (yd == em)
-             // Bank Holidays
-             || self.is_bank_holiday(d, wd, m, y)
-             // Christmas (possibly moved to Monday or Tuesday)
-             || ((d == 25 || (d == 27 && (wd == Weekday::Monday || wd == Weekday::Tuesday))) && m == Month::December)
-             // Boxing Day (possibly moved to Monday or Tuesday)
-             || ((d == 26 || (d == 28 && (wd == Weekday::Monday || wd == Weekday::Tuesday))) && m == Month::December)
-             // December 31st, 1999 only
-             || (d == 31 && m == Month::December && y == 1999)
-        ) {
-            return true;
-        }
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        let (y, m, d, wd, yd, em) = unpack_date(date, false);
 
-        false
+        let name =
+            if ((d == 1 || ((d == 2 || d == 3) && wd == Weekday::Monday)) && m == Month::January) {
+                // possibly moved to Monday
+                "New Year's Day"
+            } else if (yd == em - 3) {
+                "Good Friday"
+            } else if (yd == em) {
+                "Easter Monday"
+            } else if let Some(name) = self.bank_holiday_name(d, wd, m, y) {
+                name
+            } else if ((d == 25 || (d == 27 && (wd == Weekday::Monday || wd == Weekday::Tuesday)))
+                && m == Month::December)
+            {
+                // possibly moved to Monday or Tuesday
+                "Christmas Day"
+            } else if ((d == 26 || (d == 28 && (wd == Weekday::Monday || wd == Weekday::Tuesday)))
+                && m == Month::December)
+            {
+                // possibly moved to Monday or Tuesday
+                "Boxing Day"
+            } else if (d == 31 && m == Month::December && y == 1999) {
+                // December 31st, 1999 only
+                "Millennium Day"
+            } else {
+                return None;
+            };
+
+        Some(name)
     }
 }
 
 impl UnitedKingdomCalendar {
-    fn is_bank_holiday(&self, d: u8, w: Weekday, m: Month, y: i32) -> bool {
-        // first Monday of May (Early May Bank Holiday)
-        // moved to May 8th in 1995 and 2020 for V.E. day
-        (d <= 7 && w == Weekday::Monday && m == Month::May && y != 1995 && y != 2020)
-            || (d == 8 && m == Month::May && (y == 1995 || y == 2020))
-            // last Monday of May (Spring Bank Holiday)
+    fn bank_holiday_name(&self, d: u8, w: Weekday, m: Month, y: i32) -> Option<HolidayName> {
+        let name = if (
+            // first Monday of May
+            // moved to May 8th in 1995 and 2020 for V.E. day
+            (d <= 7 && w == Weekday::Monday && m == Month::May && y != 1995 && y != 2020)
+                || (d == 8 && m == Month::May && (y == 1995 || y == 2020))
+        ) {
+            "Early May Bank Holiday"
+        } else if (
+            // last Monday of May
             // moved to in 2002, 2012 and 2022 for the Golden, Diamond and Platinum
             // Jubilee with an additional holiday
-            || (d >= 25 && w == Weekday::Monday && m == Month::May && y != 2002 && y != 2012 && y != 2022)
-            || ((d == 3 || d == 4) && m == Month::June && y == 2002)
-            || ((d == 4 || d == 5) && m == Month::June && y == 2012)
-            || ((d == 2 || d == 3) && m == Month::June && y == 2022)
-            // last Monday of August (Summer Bank Holiday)
-            || (d >= 25 && w == Weekday::Monday && m == Month::August)
-            // April 29th, 2011 only (Royal Wedding Bank Holiday)
-            || (d == 29 && m == Month::April && y == 2011)
-            // September 19th, 2022 only (The Queen's Funeral Bank Holiday)
-            || (d == 19 && m == Month::September && y == 2022)
-            // May 8th, 2023 (King Charles III Coronation Bank Holiday)
-            || (d == 8 && m == Month::May && y == 2023)
+            (d >= 25
+                && w == Weekday::Monday
+                && m == Month::May
+                && y != 2002
+                && y != 2012
+                && y != 2022)
+                || (d == 4 && m == Month::June && y == 2002)
+                || (d == 4 && m == Month::June && y == 2012)
+                || (d == 2 && m == Month::June && y == 2022)
+        ) {
+            "Spring Bank Holiday"
+        } else if ((d == 3 && m == Month::June && y == 2002)
+            || (d == 5 && m == Month::June && y == 2012)
+            || (d == 3 && m == Month::June && y == 2022))
+        {
+            "Jubilee Bank Holiday"
+        } else if (d >= 25 && w == Weekday::Monday && m == Month::August) {
+            // last Monday of August
+            "Summer Bank Holiday"
+        } else if (d == 29 && m == Month::April && y == 2011) {
+            "Royal Wedding Bank Holiday"
+        } else if (d == 19 && m == Month::September && y == 2022) {
+            "The Queen's Funeral Bank Holiday"
+        } else if (d == 8 && m == Month::May && y == 2023) {
+            "Coronation Bank Holiday"
+        } else {
+            return None;
+        };
+
+        Some(name)
     }
 }
 
@@ -103,6 +138,26 @@ mod test_united_kingdom {
     use super::*;
     use time::macros::date;
 
+    // Test to verify the holiday_name() method for the Jubilee years.
+    #[test]
+    fn test_holiday_name() {
+        let calendar = UnitedKingdomCalendar;
+
+        assert_eq!(
+            calendar.holiday_name(date!(2022 - 06 - 02)),
+            Some("Spring Bank Holiday")
+        );
+        assert_eq!(
+            calendar.holiday_name(date!(2022 - 06 - 03)),
+            Some("Jubilee Bank Holiday")
+        );
+        assert_eq!(
+            calendar.holiday_name(date!(2020 - 05 - 08)),
+            Some("Early May Bank Holiday")
+        );
+        assert_eq!(calendar.holiday_name(date!(2022 - 05 - 30)), None);
+    }
+
     // Test to verify the name() method.
     #[test]
     fn test_name() {
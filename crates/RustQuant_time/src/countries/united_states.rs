@@ -12,6 +12,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::calendar::Calendar;
+use crate::holiday::HolidayName;
 use crate::utilities::unpack_date;
 use time::{Date, Month, Weekday};
 use RustQuant_iso::*;
@@ -45,49 +46,60 @@ impl Calendar for UnitedStatesCalendar {
     }
 
     fn is_holiday(&self, date: Date) -> bool {
+        self.holiday_name(date).is_some()
+    }
+
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
         let (y, m, d, wd, _, _) = unpack_date(date, false);
 
-        if (
+        let name = if (
             // New Year's Day (possibly moved to Monday if on Sunday)
             ((d == 1 || (d == 2 && wd == Weekday::Monday)) && m == Month::January)
-
             // (or to Friday if on Saturday)
             || (d == 31 && wd == Weekday::Friday && m == Month::December)
-
-            // Martin Luther King's birthday (third Monday in January)
-            || ((15..=21).contains(&d) && wd == Weekday::Monday && m == Month::January && y >= 1983)
-
-            // Washington's birthday (third Monday in February)
-            || self.is_washington_birthday(date)
-
-            // Memorial Day (last Monday in May)
-            || self.is_memorial_day(date)
-
-            // Juneteenth (Monday if Sunday or Friday if Saturday)
-            || self.is_juneteenth(date, true)
-
-            // Independence Day (Monday if Sunday or Friday if Saturday)
-            || ((d == 4 || (d == 5 && wd == Weekday::Monday) || (d == 3 && wd == Weekday::Friday)) && m == Month::July)
-
-            // Labor Day (first Monday in September)
-            || self.is_labor_day(date)
-
-            // Columbus Day (second Monday in October)
-            || self.is_columbus_day(date)
-
-            // Veteran's Day (Monday if Sunday or Friday if Saturday)
-            || self.is_veterans_day(date)
-
-            // Thanksgiving Day (fourth Thursday in November)
-            || ((22..=28).contains(&d) && wd == Weekday::Thursday && m == Month::November)
-
-            // Christmas (Monday if Sunday or Friday if Saturday)
-            || ((d == 25 || (d == 26 && wd == Weekday::Monday) || (d == 24 && wd == Weekday::Friday)) && m == Month::December)
         ) {
-            return true;
-        }
+            "New Year's Day"
+        } else if ((15..=21).contains(&d)
+            && wd == Weekday::Monday
+            && m == Month::January
+            && y >= 1983)
+        {
+            // third Monday in January
+            "Martin Luther King Jr. Day"
+        } else if self.is_washington_birthday(date) {
+            "Washington's Birthday"
+        } else if self.is_memorial_day(date) {
+            "Memorial Day"
+        } else if self.is_juneteenth(date, true) {
+            "Juneteenth"
+        } else if ((d == 4
+            || (d == 5 && wd == Weekday::Monday)
+            || (d == 3 && wd == Weekday::Friday))
+            && m == Month::July)
+        {
+            // Monday if Sunday or Friday if Saturday
+            "Independence Day"
+        } else if self.is_labor_day(date) {
+            "Labor Day"
+        } else if self.is_columbus_day(date) {
+            "Columbus Day"
+        } else if self.is_veterans_day(date) {
+            "Veterans Day"
+        } else if ((22..=28).contains(&d) && wd == Weekday::Thursday && m == Month::November) {
+            // fourth Thursday in November
+            "Thanksgiving Day"
+        } else if ((d == 25
+            || (d == 26 && wd == Weekday::Monday)
+            || (d == 24 && wd == Weekday::Friday))
+            && m == Month::December)
+        {
+            // Monday if Sunday or Friday if Saturday
+            "Christmas Day"
+        } else {
+            return None;
+        };
 
-        false
+        Some(name)
     }
 }
 
@@ -177,6 +189,31 @@ mod test_united_states {
     use super::*;
     use time::macros::date;
 
+    // Test to verify the holiday_name() and holidays_between() methods.
+    #[test]
+    fn test_holiday_names() {
+        let calendar = UnitedStatesCalendar;
+
+        assert_eq!(
+            calendar.holiday_name(date!(2023 - 07 - 04)),
+            Some("Independence Day")
+        );
+        assert_eq!(calendar.holiday_name(date!(2023 - 07 - 05)), None);
+
+        // Christmas Day 2021 and New Year's Day 2022 fall on a Saturday,
+        // so are also observed on the Fridays.
+        assert_eq!(
+            calendar.holidays_between(date!(2021 - 11 - 01), date!(2021 - 12 - 31)),
+            vec![
+                (date!(2021 - 11 - 11), "Veterans Day"),
+                (date!(2021 - 11 - 25), "Thanksgiving Day"),
+                (date!(2021 - 12 - 24), "Christmas Day"),
+                (date!(2021 - 12 - 25), "Christmas Day"),
+                (date!(2021 - 12 - 31), "New Year's Day"),
+            ]
+        );
+    }
+
     // Test to verify the name() method.
     #[test]
     fn test_name() {
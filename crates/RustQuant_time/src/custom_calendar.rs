@@ -0,0 +1,253 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Calendars defined by the user.
+//!
+//! A [`CustomCalendar`] is built from explicit data rather than rules:
+//! a list of holidays, holidays that fall on the same day every year, the
+//! weekend days, and ad-hoc closures (e.g. an exchange closed for a
+//! national day of mourning). Half days (early closes) are business days,
+//! but can be queried with [`CustomCalendar::is_half_day`].
+//!
+//! ```
+//! use RustQuant_time::{Calendar, CustomCalendar, DateRoller, DateRollingConvention};
+//! use time::{macros::date, Month, Weekday};
+//!
+//! let calendar = CustomCalendar::new()
+//!     .with_name("Dubai")
+//!     .with_weekend(&[Weekday::Saturday, Weekday::Sunday])
+//!     .with_annual_holiday(Month::December, 2)
+//!     .with_holidays([date!(2024 - 04 - 10)]);
+//!
+//! assert_eq!(
+//!     calendar.roll_date(date!(2024 - 04 - 10), &DateRollingConvention::Following),
+//!     date!(2024 - 04 - 11)
+//! );
+//! ```
+
+use crate::calendar::Calendar;
+use std::collections::BTreeSet;
+use time::{Date, Month, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// User-assigned ISO 3166 code ("ZZ") for calendars with no country.
+const NO_COUNTRY: ISO_3166 = ISO_3166 {
+    alpha_2: "ZZ",
+    alpha_3: "ZZZ",
+    numeric: "999",
+};
+
+/// Calendar with user-supplied holidays, weekend and closures.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomCalendar {
+    /// Name of the calendar.
+    pub name: &'static str,
+
+    /// Weekend days.
+    pub weekend: Vec<Weekday>,
+
+    /// Holidays on specific dates.
+    pub holidays: BTreeSet<Date>,
+
+    /// Holidays on the same (month, day) every year.
+    pub annual_holidays: Vec<(Month, u8)>,
+
+    /// Ad-hoc closures.
+    pub closures: BTreeSet<Date>,
+
+    /// Business days with an early close.
+    pub half_days: BTreeSet<Date>,
+
+    /// ISO 3166 country code.
+    pub country_code: ISO_3166,
+
+    /// ISO 10383 market identifier code.
+    pub market_identifier_code: ISO_10383,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for CustomCalendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CustomCalendar {
+    /// Custom calendar with the holidays of another calendar between two
+    /// dates, which can then be amended.
+    pub fn from_calendar<C: Calendar>(calendar: &C, start_date: Date, end_date: Date) -> Self {
+        Self::new()
+            .with_name(calendar.name())
+            .with_holidays(calendar.all_holidays_between(start_date, end_date))
+            .with_codes(calendar.country_code(), calendar.market_identifier_code())
+    }
+
+    /// Set the name.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Set the weekend days (Saturday and Sunday by default).
+    pub fn with_weekend(mut self, weekend: &[Weekday]) -> Self {
+        self.weekend = weekend.to_vec();
+        self
+    }
+
+    /// Add holidays.
+    pub fn with_holidays<I: IntoIterator<Item = Date>>(mut self, holidays: I) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    /// Add a holiday on the same day every year (not moved when it falls
+    /// on a weekend).
+    pub fn with_annual_holiday(mut self, month: Month, day: u8) -> Self {
+        self.annual_holidays.push((month, day));
+        self
+    }
+
+    /// Add ad-hoc closures.
+    pub fn with_closures<I: IntoIterator<Item = Date>>(mut self, closures: I) -> Self {
+        self.closures.extend(closures);
+        self
+    }
+
+    /// Add half days (early closes).
+    pub fn with_half_days<I: IntoIterator<Item = Date>>(mut self, half_days: I) -> Self {
+        self.half_days.extend(half_days);
+        self
+    }
+
+    /// Set the country and market identifier codes.
+    pub fn with_codes(mut self, country_code: ISO_3166, mic: ISO_10383) -> Self {
+        self.country_code = country_code;
+        self.market_identifier_code = mic;
+        self
+    }
+
+    /// Check if the date is a weekend day of this calendar.
+    pub fn is_weekend(&self, date: Date) -> bool {
+        self.weekend.contains(&date.weekday())
+    }
+
+    /// Check if the date is an ad-hoc closure.
+    pub fn is_closure(&self, date: Date) -> bool {
+        self.closures.contains(&date)
+    }
+
+    /// Check if the date is a half day (early close).
+    pub fn is_half_day(&self, date: Date) -> bool {
+        self.half_days.contains(&date) && self.is_business_day(date)
+    }
+}
+
+impl Calendar for CustomCalendar {
+    fn new() -> Self {
+        Self {
+            name: "Custom",
+            weekend: vec![Weekday::Saturday, Weekday::Sunday],
+            holidays: BTreeSet::new(),
+            annual_holidays: Vec::new(),
+            closures: BTreeSet::new(),
+            half_days: BTreeSet::new(),
+            country_code: NO_COUNTRY,
+            market_identifier_code: XXXX,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        self.country_code
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        self.market_identifier_code
+    }
+
+    fn is_business_day(&self, date: Date) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        self.holidays.contains(&date)
+            || self.closures.contains(&date)
+            || self
+                .annual_holidays
+                .iter()
+                .any(|&(month, day)| date.month() == month && date.day() == day)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_custom_calendar {
+    use super::*;
+    use crate::{DateRoller, DateRollingConvention, DayCounter, UnitedStatesCalendar};
+    use time::macros::date;
+
+    #[test]
+    fn test_weekend_and_holidays() {
+        let calendar = CustomCalendar::new()
+            .with_weekend(&[Weekday::Friday, Weekday::Saturday])
+            .with_holidays([date!(2024 - 03 - 12)])
+            .with_annual_holiday(Month::January, 1);
+
+        assert!(!calendar.is_business_day(date!(2024 - 03 - 08)));
+        assert!(calendar.is_business_day(date!(2024 - 03 - 10)));
+        assert!(!calendar.is_business_day(date!(2024 - 03 - 12)));
+        assert!(calendar.is_holiday(date!(2031 - 01 - 01)));
+        assert_eq!(calendar.name(), "Custom");
+        assert_eq!(calendar.country_code().alpha_2, "ZZ");
+    }
+
+    #[test]
+    fn test_closures_and_half_days() {
+        // NYSE: closed for the national day of mourning for President Carter,
+        // and an early close on Christmas Eve.
+        let calendar = CustomCalendar::from_calendar(
+            &UnitedStatesCalendar,
+            date!(2024 - 01 - 01),
+            date!(2025 - 12 - 31),
+        )
+        .with_name("NYSE")
+        .with_closures([date!(2025 - 01 - 09)])
+        .with_half_days([date!(2024 - 12 - 24)]);
+
+        assert_eq!(calendar.name(), "NYSE");
+        assert!(calendar.is_closure(date!(2025 - 01 - 09)));
+        assert!(!calendar.is_business_day(date!(2025 - 01 - 09)));
+        assert!(calendar.is_half_day(date!(2024 - 12 - 24)));
+        assert!(calendar.is_business_day(date!(2024 - 12 - 24)));
+        assert!(!calendar.is_business_day(date!(2024 - 12 - 25)));
+
+        assert_eq!(
+            calendar.roll_date(date!(2025 - 01 - 09), &DateRollingConvention::Following),
+            date!(2025 - 01 - 10)
+        );
+        assert_eq!(
+            calendar.business_day_count(date!(2025 - 01 - 06), date!(2025 - 01 - 13)),
+            UnitedStatesCalendar.business_day_count(date!(2025 - 01 - 06), date!(2025 - 01 - 13))
+                - 1
+        );
+    }
+}
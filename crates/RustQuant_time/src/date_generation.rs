@@ -9,6 +9,7 @@
 
 /// Date generation conventions.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DateGenerationConvention {
     /// Forward from the issue date.
     Forward,
@@ -18,4 +19,12 @@ pub enum DateGenerationConvention {
 
     /// Zero date generation.
     Zero,
+
+    /// Dates on the IMM dates (third Wednesday of March, June, September
+    /// and December), stepping forward from the issue date.
+    IMM,
+
+    /// Backward from the maturity date, with every date except the issue
+    /// and maturity dates moved to the third Wednesday of its month.
+    ThirdWednesday,
 }
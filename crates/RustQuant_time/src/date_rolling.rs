@@ -23,6 +23,7 @@ use time::Date;
 /// same business calendar.
 /// """
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DateRollingConvention {
     /// Actual: paid on the actual day, even if it is a non-business day.
     Actual,
@@ -51,6 +52,16 @@ pub enum DateRollingConvention {
     /// business day. The adjusted week date is used for the next coupon date.
     /// So adjustments are cumulative (excluding month change).
     ModifiedRolling,
+
+    /// Nearest business day: the payment date is rolled to the nearest
+    /// business day, or to the next business day if the previous and next
+    /// are equally near.
+    Nearest,
+
+    /// Half-month modified following business day: as modified following,
+    /// but a date in the first half of the month is also rolled to the
+    /// previous business day if the next one falls after the 15th.
+    HalfMonthModifiedFollowing,
 }
 
 /// Date roller trait for rolling coupon/payment dates according to a given convention.
@@ -75,6 +86,8 @@ where
             DateRollingConvention::Preceding            => DateRollingConvention::roll_date_preceding(date, self),
             DateRollingConvention::ModifiedPreceding    => DateRollingConvention::roll_date_modified_preceding(date, self),
             DateRollingConvention::ModifiedRolling      => DateRollingConvention::roll_date_modified_rolling(date, self),
+            DateRollingConvention::Nearest              => DateRollingConvention::roll_date_nearest(date, self),
+            DateRollingConvention::HalfMonthModifiedFollowing => DateRollingConvention::roll_date_half_month_modified_following(date, self),
         }
     }
 
@@ -103,6 +116,8 @@ impl fmt::Display for DateRollingConvention {
             Self::Preceding             => write!(f, "Preceding"),
             Self::ModifiedPreceding     => write!(f, "Modified Preceding"),
             Self::ModifiedRolling       => write!(f, "Modified Rolling"),
+            Self::Nearest               => write!(f, "Nearest"),
+            Self::HalfMonthModifiedFollowing => write!(f, "Half-Month Modified Following"),
         }
     }
 }
@@ -155,4 +170,98 @@ impl DateRollingConvention {
     pub(crate) fn roll_date_preceding<C: Calendar>(date: Date, calendar: &C) -> Date {
         previous_business_day(date, calendar)
     }
+
+    /// Adjust (roll) the date according: Nearest convention.
+    pub(crate) fn roll_date_nearest<C: Calendar>(date: Date, calendar: &C) -> Date {
+        let (mut next, mut previous) = (date, date);
+
+        // Step out in both directions, checking the later date first.
+        loop {
+            if calendar.is_business_day(next) {
+                return next;
+            }
+            if calendar.is_business_day(previous) {
+                return previous;
+            }
+
+            next = next.next_day().unwrap();
+            previous = previous.previous_day().unwrap();
+        }
+    }
+
+    /// Adjust (roll) the date according: Half-month modified following convention.
+    pub(crate) fn roll_date_half_month_modified_following<C: Calendar>(
+        date: Date,
+        calendar: &C,
+    ) -> Date {
+        let new_date = Self::roll_date_modified_following(date, calendar);
+
+        if date.day() <= 15 && new_date.day() > 15 {
+            return previous_business_day(date, calendar);
+        }
+
+        new_date
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_date_rolling {
+    use super::*;
+    use crate::countries::UnitedKingdomCalendar;
+    use time::macros::date;
+
+    #[test]
+    fn test_nearest() {
+        let calendar = UnitedKingdomCalendar;
+        let convention = DateRollingConvention::Nearest;
+
+        // Saturday to Friday, Sunday to Monday.
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 06 - 15), &convention),
+            date!(2024 - 06 - 14)
+        );
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 06 - 16), &convention),
+            date!(2024 - 06 - 17)
+        );
+
+        // Easter Sunday: Good Friday and Easter Monday are holidays, so the
+        // next business day (Tuesday) is nearer than the previous (Thursday).
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 03 - 31), &convention),
+            date!(2024 - 04 - 02)
+        );
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 06 - 17), &convention),
+            date!(2024 - 06 - 17)
+        );
+    }
+
+    #[test]
+    fn test_half_month_modified_following() {
+        let calendar = UnitedKingdomCalendar;
+        let convention = DateRollingConvention::HalfMonthModifiedFollowing;
+
+        // Saturday 15th rolls back to Friday 14th, rather than across the mid-month.
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 06 - 15), &convention),
+            date!(2024 - 06 - 14)
+        );
+
+        // Within a half of the month, the same as following.
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 06 - 08), &convention),
+            date!(2024 - 06 - 10)
+        );
+
+        // Month end, the same as modified following.
+        assert_eq!(
+            calendar.roll_date(date!(2024 - 08 - 31), &convention),
+            date!(2024 - 08 - 30)
+        );
+    }
 }
@@ -11,8 +11,13 @@
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use super::{contains_leap_year, days_between, is_last_day_of_february, leap_year_count};
+use super::{
+    contains_leap_year, days_between, is_last_day_of_february, is_weekend, leap_year_count,
+};
 use crate::Calendar;
+use crate::Frequency;
+use crate::SerialDate;
+use crate::Tenor;
 use std::fmt;
 use time::{util::is_leap_year, Date, Duration, Month};
 
@@ -35,6 +40,7 @@ use time::{util::is_leap_year, Date, Duration, Month};
 /// """
 #[allow(non_camel_case_types)]
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DayCountConvention {
     /// The '1/1' day count, which always returns a day count of 1.
     One_One,
@@ -73,6 +79,10 @@ pub enum DayCountConvention {
     /// leap year by 366 and the actual number of days in a standard year by 365.
     Actual_Actual_ISDA,
 
+    /// The 'BUS/252' day count, which divides the number of business days by 252.
+    /// Used for Brazilian instruments, and for scaling volatility by trading days.
+    Business_252,
+
     /// The 'NL/360' day count, which divides the actual number of days omitting leap days by 360.
     No_Leap_360,
 
@@ -114,6 +124,18 @@ pub trait DayCounter {
     /// Compute the day count factor between two dates.
     fn day_count_factor(&self, date1: Date, date2: Date, convention: &DayCountConvention) -> f64;
 
+    /// Compute the day count factor between two dates, within the coupon
+    /// reference period `[reference_start, reference_end]` of the given frequency.
+    fn day_count_factor_with_reference(
+        &self,
+        date1: Date,
+        date2: Date,
+        reference_start: Date,
+        reference_end: Date,
+        frequency: Frequency,
+        convention: &DayCountConvention,
+    ) -> f64;
+
     /// Compute the number of calendar days between each date in a vector of dates.
     fn calendar_day_counts(&self, dates: &[Date]) -> Vec<i64>;
 
@@ -139,6 +161,7 @@ impl fmt::Display for DayCountConvention {
             Self::Actual_Actual_AFB     => write!(f, "Actual / Actual AFB"),
             Self::Actual_Actual_ICMA    => write!(f, "Actual / Actual ICMA"),
             Self::Actual_Actual_ISDA    => write!(f, "Actual / Actual ISDA"),
+            Self::Business_252          => write!(f, "Business / 252"),
             Self::No_Leap_360           => write!(f, "No Leap / 360"),
             Self::No_Leap_365           => write!(f, "No Leap / 365"),
             Self::Thirty_360_ISDA       => write!(f, "30 / 360 ISDA"),
@@ -206,17 +229,8 @@ where
     /// assert_eq!(calendar.business_day_count(date1, date2), 21);
     /// ```
     fn business_day_count(&self, date1: Date, date2: Date) -> i64 {
-        let mut count = 0;
-        let mut temp_date = date1;
-
-        while temp_date <= date2 {
-            if self.is_business_day(temp_date) {
-                count += 1;
-            }
-            temp_date += Duration::days(1);
-        }
-
-        count
+        // Both dates are included.
+        self.count_business_days(date1, date2 + Duration::days(1))
     }
 
     /// Computes the day count factor between two dates.
@@ -244,7 +258,69 @@ where
     /// assert_eq!(calendar.day_count_factor(date1, date2, &convention), 0.997_267_759_562_841_5);
     /// ```
     fn day_count_factor(&self, date1: Date, date2: Date, convention: &DayCountConvention) -> f64 {
-        convention.day_count_factor(date1, date2)
+        match convention {
+            DayCountConvention::Business_252 => {
+                DayCountConvention::business_252_factor(date1, date2, |from, to| {
+                    self.count_business_days(from, to)
+                })
+            }
+            _ => convention.day_count_factor(date1, date2),
+        }
+    }
+
+    /// Computes the day count factor between two dates, given the coupon
+    /// reference period they accrue in.
+    ///
+    /// Only `Actual_Actual_ICMA` uses the reference period; other conventions
+    /// are the same as [`DayCounter::day_count_factor`].
+    ///
+    /// # Arguments
+    ///
+    /// * `date1` - The first date.
+    /// * `date2` - The second date.
+    /// * `reference_start` - The start of the coupon reference period.
+    /// * `reference_end` - The end of the coupon reference period.
+    /// * `frequency` - The coupon frequency.
+    /// * `convention` - The day count convention.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use time::macros::date;
+    /// use RustQuant_time::{AustraliaCalendar, DayCountConvention, DayCounter, Frequency};
+    ///
+    /// let calendar = AustraliaCalendar;
+    /// let convention = DayCountConvention::Actual_Actual_ICMA;
+    ///
+    /// // A full semi-annual coupon period accrues exactly half a year.
+    /// let (start, end) = (date!(2023-11-01), date!(2024-05-01));
+    ///
+    /// assert_eq!(
+    ///     calendar.day_count_factor_with_reference(
+    ///         start, end, start, end, Frequency::SemiAnnually, &convention
+    ///     ),
+    ///     0.5
+    /// );
+    /// ```
+    fn day_count_factor_with_reference(
+        &self,
+        date1: Date,
+        date2: Date,
+        reference_start: Date,
+        reference_end: Date,
+        frequency: Frequency,
+        convention: &DayCountConvention,
+    ) -> f64 {
+        match convention {
+            DayCountConvention::Actual_Actual_ICMA => convention.day_count_factor_with_reference(
+                date1,
+                date2,
+                reference_start,
+                reference_end,
+                frequency,
+            ),
+            _ => self.day_count_factor(date1, date2, convention),
+        }
     }
 
     /// Compute the number of calendar days between each date in a vector of dates.
@@ -352,6 +428,13 @@ impl Default for DayCountConvention {
 
 impl DayCountConvention {
     /// Entry point for day count factor calculation.
+    ///
+    /// Without a calendar, `Business_252` only excludes weekends.
+    /// Use [`DayCounter::day_count_factor`] to also exclude holidays.
+    ///
+    /// Without a reference period, `Actual_Actual_ICMA` uses annual periods
+    /// starting on the start date. Use [`Self::day_count_factor_with_reference`]
+    /// for coupon accruals.
     #[rustfmt::skip]
     pub fn day_count_factor(&self, start_date: Date, end_date: Date) -> f64 {
         match self {
@@ -366,6 +449,7 @@ impl DayCountConvention {
             Self::Actual_Actual_AFB     => Self::day_count_factor_actual_actual_afb(start_date, end_date),
             Self::Actual_Actual_ICMA    => Self::day_count_factor_actual_actual_icma(start_date, end_date),
             Self::Actual_Actual_ISDA    => Self::day_count_factor_actual_actual_isda(start_date, end_date),
+            Self::Business_252          => Self::day_count_factor_business_252(start_date, end_date),
             Self::No_Leap_360           => Self::day_count_factor_nl_360(start_date, end_date),
             Self::No_Leap_365           => Self::day_count_factor_nl_365(start_date, end_date),
             Self::Thirty_360_ISDA       => Self::day_count_factor_thirty_360_isda(start_date, end_date),
@@ -378,6 +462,180 @@ impl DayCountConvention {
     }
 }
 
+impl DayCountConvention {
+    /// Day count factor between two dates, given the coupon reference period
+    /// `[reference_start, reference_end]` and the coupon frequency.
+    ///
+    /// For `Actual_Actual_ICMA`, the days in each reference period are divided
+    /// by the days in that period times the frequency. Accruals extending
+    /// outside the reference period (long stubs) are split across notional
+    /// periods of one frequency tenor before and after it.
+    /// `Daily` and `Zero` frequencies have no such tenor, so fall back to
+    /// Actual/Actual ISDA.
+    ///
+    /// Other conventions ignore the reference period.
+    pub fn day_count_factor_with_reference(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        reference_start: Date,
+        reference_end: Date,
+        frequency: Frequency,
+    ) -> f64 {
+        match self {
+            Self::Actual_Actual_ICMA => match Tenor::from_frequency(frequency) {
+                Some(tenor) => Self::icma_factor(
+                    start_date,
+                    end_date,
+                    reference_start,
+                    reference_end,
+                    frequency,
+                    tenor,
+                ),
+                None => Self::day_count_factor_actual_actual_isda(start_date, end_date),
+            },
+            _ => self.day_count_factor(start_date, end_date),
+        }
+    }
+
+    // Actual/Actual ICMA over the reference period and the notional periods
+    // either side of it.
+    fn icma_factor(
+        start_date: Date,
+        end_date: Date,
+        reference_start: Date,
+        reference_end: Date,
+        frequency: Frequency,
+        tenor: Tenor,
+    ) -> f64 {
+        if start_date > end_date {
+            return -Self::icma_factor(
+                end_date,
+                start_date,
+                reference_start,
+                reference_end,
+                frequency,
+                tenor,
+            );
+        }
+
+        let periods_per_year = frequency.times_in_year() as f64;
+
+        // Fraction of the period [period_start, period_end] that is accrued.
+        let accrued = |period_start: Date, period_end: Date| {
+            let (from, to) = (start_date.max(period_start), end_date.min(period_end));
+
+            match from < to {
+                true => {
+                    days_between(from, to) as f64
+                        / (days_between(period_start, period_end) as f64 * periods_per_year)
+                }
+                false => 0.0,
+            }
+        };
+
+        let mut factor = accrued(reference_start, reference_end);
+
+        let mut k = 1;
+        let mut period_end = reference_start;
+        while start_date < period_end {
+            let period_start = (-tenor * k).add_to(reference_start, false);
+            factor += accrued(period_start, period_end);
+            period_end = period_start;
+            k += 1;
+        }
+
+        let mut k = 1;
+        let mut period_start = reference_end;
+        while end_date > period_start {
+            let period_end = (tenor * k).add_to(reference_end, false);
+            factor += accrued(period_start, period_end);
+            period_start = period_end;
+            k += 1;
+        }
+
+        factor
+    }
+
+    /// Day count factor between two serial dates.
+    ///
+    /// The Actual/constant, Actual/Actual ISDA and 30/360 conventions are
+    /// computed from the serial numbers directly; the remaining conventions
+    /// convert back to `time::Date`.
+    pub fn day_count_factor_serial(&self, start_date: SerialDate, end_date: SerialDate) -> f64 {
+        let days = (end_date - start_date) as f64;
+
+        match self {
+            Self::One_One => 1.0,
+            Self::Actual_360 => days / 360.0,
+            Self::Actual_364 => days / 364.0,
+            Self::Actual_366 => days / 366.0,
+            Self::Actual_365_25 => days / 365.25,
+            Self::Actual_365_Fixed => days / 365.0,
+            Self::Actual_Actual_ISDA => {
+                Self::day_count_factor_actual_actual_isda_serial(start_date, end_date)
+            }
+            Self::Thirty_360_ISDA
+            | Self::Thirty_E_360
+            | Self::Thirty_E_360_ISDA
+            | Self::Thirty_E_365
+            | Self::Thirty_E_Plus_360
+            | Self::Thirty_U_360 => self.thirty_360_factor(
+                Self::thirty_360_unpack_serial(start_date),
+                Self::thirty_360_unpack_serial(end_date),
+            ),
+            _ => self.day_count_factor(start_date.to_date(), end_date.to_date()),
+        }
+    }
+
+    /// Day count factors for arrays of period start and end dates,
+    /// e.g. the accrual periods of all the swaps in a book.
+    ///
+    /// Convert the dates once with [SerialDate::from_dates] and reuse them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arrays have different lengths.
+    pub fn day_count_factors_serial(
+        &self,
+        start_dates: &[SerialDate],
+        end_dates: &[SerialDate],
+    ) -> Vec<f64> {
+        assert_eq!(start_dates.len(), end_dates.len());
+
+        start_dates
+            .iter()
+            .zip(end_dates)
+            .map(|(&start_date, &end_date)| self.day_count_factor_serial(start_date, end_date))
+            .collect()
+    }
+
+    // Actual/Actual ISDA day count factor calculation, on serial dates.
+    fn day_count_factor_actual_actual_isda_serial(
+        start_date: SerialDate,
+        end_date: SerialDate,
+    ) -> f64 {
+        if start_date == end_date {
+            return 0.0;
+        }
+
+        let (y1, y2) = (start_date.year_month_day().0, end_date.year_month_day().0);
+        let days_in_year = |year: i32| if is_leap_year(year) { 366.0 } else { 365.0 };
+
+        (y2 - y1 - 1) as f64
+            + (SerialDate::from_ymd(y1 + 1, 1, 1) - start_date) as f64 / days_in_year(y1)
+            + (end_date - SerialDate::from_ymd(y2, 1, 1)) as f64 / days_in_year(y2)
+    }
+
+    /// Unpack a serial date for 30/360 calculation.
+    fn thirty_360_unpack_serial(date: SerialDate) -> (i32, i32, i32, bool) {
+        let (year, month, day) = date.year_month_day();
+        let is_last_day_of_february = month == 2 && day == 28 + is_leap_year(year) as u8;
+
+        (year, month as i32, day as i32, is_last_day_of_february)
+    }
+}
+
 impl DayCountConvention {
     // Actual/Actual AFB day count factor calculation.
     pub(crate) fn day_count_factor_actual_actual_afb(start_date: Date, end_date: Date) -> f64 {
@@ -403,8 +661,14 @@ impl DayCountConvention {
     }
 
     // Actual/Actual ICMA day count factor calculation.
-    pub(crate) fn day_count_factor_actual_actual_icma(_start_date: Date, _end_date: Date) -> f64 {
-        todo!()
+    pub(crate) fn day_count_factor_actual_actual_icma(start_date: Date, end_date: Date) -> f64 {
+        Self::Actual_Actual_ICMA.day_count_factor_with_reference(
+            start_date,
+            end_date,
+            start_date,
+            Tenor::years(1).add_to(start_date, false),
+            Frequency::Annually,
+        )
     }
 
     // Actual/Actual ISDA day count factor calculation.
@@ -437,6 +701,39 @@ impl DayCountConvention {
         sum
     }
 
+    // BUS/252 day count factor calculation, counting weekdays only.
+    pub(crate) fn day_count_factor_business_252(start_date: Date, end_date: Date) -> f64 {
+        Self::business_252_factor(start_date, end_date, |from, to| {
+            // Whole weeks have five weekdays, then check the remaining days.
+            let days = (to - from).whole_days();
+            let remainder = (0..days % 7)
+                .filter(|&i| !is_weekend(from + Duration::days(i)))
+                .count() as i64;
+
+            days / 7 * 5 + remainder
+        })
+    }
+
+    /// BUS/252 day count factor: the business days in `[start_date, end_date)`
+    /// divided by 252, negative if the dates are reversed.
+    ///
+    /// `count_business_days(from, to)` counts the business days in `[from, to)`.
+    pub(crate) fn business_252_factor<F>(
+        start_date: Date,
+        end_date: Date,
+        count_business_days: F,
+    ) -> f64
+    where
+        F: Fn(Date, Date) -> i64,
+    {
+        let (from, to, sign) = match start_date <= end_date {
+            true => (start_date, end_date, 1.0),
+            false => (end_date, start_date, -1.0),
+        };
+
+        sign * count_business_days(from, to) as f64 / 252.0
+    }
+
     // NL/360 day count factor calculation.
     pub(crate) fn day_count_factor_nl_360(start_date: Date, end_date: Date) -> f64 {
         let day_count = (end_date - start_date).whole_days() as f64;
@@ -501,98 +798,117 @@ impl DayCountConvention {
 
     // 30/360 ISDA day count factor calculation.
     pub(crate) fn day_count_factor_thirty_360_isda(start_date: Date, end_date: Date) -> f64 {
-        let (y1, m1, mut d1) = Self::thirty_360_unpack_date(start_date);
-        let (y2, m2, mut d2) = Self::thirty_360_unpack_date(end_date);
-
-        if d1 == 31 {
-            d1 = 30;
-        }
-
-        if d1 == 30 && d2 == 31 {
-            d2 = 30;
-        }
-
-        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / 360.0
+        Self::Thirty_360_ISDA.thirty_360_factor(
+            Self::thirty_360_unpack_date(start_date),
+            Self::thirty_360_unpack_date(end_date),
+        )
     }
 
     // 30E/360 day count factor calculation.
     pub(crate) fn day_count_factor_thirty_e_360(start_date: Date, end_date: Date) -> f64 {
-        let (y1, m1, mut d1) = Self::thirty_360_unpack_date(start_date);
-        let (y2, m2, mut d2) = Self::thirty_360_unpack_date(end_date);
-
-        if d1 == 31 {
-            d1 = 30;
-        }
-
-        if d2 == 31 {
-            d2 = 30;
-        }
-
-        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / 360.0
+        Self::Thirty_E_360.thirty_360_factor(
+            Self::thirty_360_unpack_date(start_date),
+            Self::thirty_360_unpack_date(end_date),
+        )
     }
 
     // 30E/360 ISDA day count factor calculation.
     pub(crate) fn day_count_factor_thirty_e_360_isda(start_date: Date, end_date: Date) -> f64 {
-        let (y1, m1, mut d1) = Self::thirty_360_unpack_date(start_date);
-        let (y2, m2, mut d2) = Self::thirty_360_unpack_date(end_date);
-
-        if d1 == 31 || is_last_day_of_february(start_date) {
-            d1 = 30;
-        }
-
-        if d2 == 31 || is_last_day_of_february(end_date) {
-            d2 = 30;
-        }
-
-        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / 360.0
+        Self::Thirty_E_360_ISDA.thirty_360_factor(
+            Self::thirty_360_unpack_date(start_date),
+            Self::thirty_360_unpack_date(end_date),
+        )
     }
 
     // 30E+/360 day count factor calculation.
     pub(crate) fn day_count_factor_thirty_e_plus_360(start_date: Date, end_date: Date) -> f64 {
-        let (y1, m1, mut d1) = Self::thirty_360_unpack_date(start_date);
-        let (mut y2, mut m2, mut d2) = Self::thirty_360_unpack_date(end_date);
-
-        if d1 == 31 {
-            d1 = 30;
-        }
-
-        if d2 == 31 {
-            (y2, m2, d2) = Self::thirty_360_unpack_date(end_date.next_day().unwrap());
-        }
-
-        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / 360.0
+        Self::Thirty_E_Plus_360.thirty_360_factor(
+            Self::thirty_360_unpack_date(start_date),
+            Self::thirty_360_unpack_date(end_date),
+        )
     }
 
     // 30U/360 day count factor calculation.
     pub(crate) fn day_count_factor_thirty_u_360(start_date: Date, end_date: Date) -> f64 {
-        let (y1, m1, mut d1) = Self::thirty_360_unpack_date(start_date);
-        let (y2, m2, mut d2) = Self::thirty_360_unpack_date(end_date);
-
-        if d1 == 31 || is_last_day_of_february(start_date) {
-            d1 = 30;
-        }
-
-        if d2 == 31 && d1 == 30 || is_last_day_of_february(end_date) {
-            d2 = 30;
-        }
-
-        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / 360.0
+        Self::Thirty_U_360.thirty_360_factor(
+            Self::thirty_360_unpack_date(start_date),
+            Self::thirty_360_unpack_date(end_date),
+        )
     }
 
     // 30E/365 day count factor calculation.
     pub(crate) fn day_count_factor_thirty_e_365(start_date: Date, end_date: Date) -> f64 {
-        let (y1, m1, mut d1) = Self::thirty_360_unpack_date(start_date);
-        let (y2, m2, mut d2) = Self::thirty_360_unpack_date(end_date);
+        Self::Thirty_E_365.thirty_360_factor(
+            Self::thirty_360_unpack_date(start_date),
+            Self::thirty_360_unpack_date(end_date),
+        )
+    }
 
-        if d1 == 31 || is_last_day_of_february(start_date) {
-            d1 = 30;
-        }
+    /// 30/360 family day count factor, from dates unpacked as
+    /// `(year, month, day, is_last_day_of_february)`.
+    pub(crate) fn thirty_360_factor(
+        &self,
+        start: (i32, i32, i32, bool),
+        end: (i32, i32, i32, bool),
+    ) -> f64 {
+        let (y1, m1, mut d1, start_is_end_of_february) = start;
+        let (mut y2, mut m2, mut d2, end_is_end_of_february) = end;
 
-        if d2 == 31 || is_last_day_of_february(end_date) {
-            d2 = 30;
+        match self {
+            Self::Thirty_360_ISDA => {
+                if d1 == 31 {
+                    d1 = 30;
+                }
+                if d1 == 30 && d2 == 31 {
+                    d2 = 30;
+                }
+            }
+            Self::Thirty_E_360 => {
+                if d1 == 31 {
+                    d1 = 30;
+                }
+                if d2 == 31 {
+                    d2 = 30;
+                }
+            }
+            Self::Thirty_E_360_ISDA | Self::Thirty_E_365 => {
+                if d1 == 31 || start_is_end_of_february {
+                    d1 = 30;
+                }
+                if d2 == 31 || end_is_end_of_february {
+                    d2 = 30;
+                }
+            }
+            Self::Thirty_E_Plus_360 => {
+                if d1 == 31 {
+                    d1 = 30;
+                }
+                // Roll the end date to the first of the next month.
+                if d2 == 31 {
+                    (y2, m2, d2) = if m2 == 12 {
+                        (y2 + 1, 1, 1)
+                    } else {
+                        (y2, m2 + 1, 1)
+                    };
+                }
+            }
+            Self::Thirty_U_360 => {
+                if d1 == 31 || start_is_end_of_february {
+                    d1 = 30;
+                }
+                if d2 == 31 && d1 == 30 || end_is_end_of_february {
+                    d2 = 30;
+                }
+            }
+            _ => unreachable!("Not a 30/360 day count convention."),
         }
 
-        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / 365.0
+        let denominator = match self {
+            Self::Thirty_E_365 => 365.0,
+            _ => 360.0,
+        };
+
+        Self::thirty_360_numerator(y1, y2, m1, m2, d1, d2) / denominator
     }
 
     /// Function to comput the 30/360 numerator.
@@ -608,8 +924,13 @@ impl DayCountConvention {
     }
 
     /// Function to unpack the date components for 30/360 calculation.
-    pub(crate) fn thirty_360_unpack_date(date: Date) -> (i32, i32, i32) {
-        (date.year(), date.month() as i32, date.day() as i32)
+    pub(crate) fn thirty_360_unpack_date(date: Date) -> (i32, i32, i32, bool) {
+        (
+            date.year(),
+            date.month() as i32,
+            date.day() as i32,
+            is_last_day_of_february(date),
+        )
     }
 }
 
@@ -775,3 +1096,193 @@ mod TESTS_actual_actual {
         );
     }
 }
+
+#[cfg(test)]
+mod TESTS_actual_actual_icma {
+    use crate::{DayCountConvention, Frequency};
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+    use RustQuant_utils::RUSTQUANT_EPSILON;
+
+    fn icma(
+        start: time::Date,
+        end: time::Date,
+        reference: (time::Date, time::Date),
+        frequency: Frequency,
+    ) -> f64 {
+        DayCountConvention::Actual_Actual_ICMA.day_count_factor_with_reference(
+            start,
+            end,
+            reference.0,
+            reference.1,
+            frequency,
+        )
+    }
+
+    #[test]
+    fn icma_regular_period() {
+        // Test cases from QuantLib (ISDA 1998 "Actual/Actual" memo).
+        assert_approx_equal!(
+            icma(
+                date!(2003 - 11 - 01),
+                date!(2004 - 05 - 01),
+                (date!(2003 - 11 - 01), date!(2004 - 05 - 01)),
+                Frequency::SemiAnnually
+            ),
+            0.5,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn icma_short_first_period() {
+        assert_approx_equal!(
+            icma(
+                date!(1999 - 02 - 01),
+                date!(1999 - 07 - 01),
+                (date!(1998 - 07 - 01), date!(1999 - 07 - 01)),
+                Frequency::Annually
+            ),
+            0.410958904110,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn icma_long_first_period() {
+        assert_approx_equal!(
+            icma(
+                date!(2002 - 08 - 15),
+                date!(2003 - 07 - 15),
+                (date!(2003 - 01 - 15), date!(2003 - 07 - 15)),
+                Frequency::SemiAnnually
+            ),
+            0.915760869565,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn icma_short_final_period() {
+        assert_approx_equal!(
+            icma(
+                date!(1999 - 07 - 30),
+                date!(2000 - 01 - 30),
+                (date!(1999 - 07 - 30), date!(2000 - 01 - 30)),
+                Frequency::SemiAnnually
+            ),
+            0.5,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            icma(
+                date!(2000 - 01 - 30),
+                date!(2000 - 06 - 30),
+                (date!(2000 - 01 - 30), date!(2000 - 07 - 30)),
+                Frequency::SemiAnnually
+            ),
+            0.417582417582,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn icma_without_reference() {
+        assert_approx_equal!(
+            DayCountConvention::Actual_Actual_ICMA
+                .day_count_factor(date!(2003 - 11 - 01), date!(2004 - 05 - 01)),
+            182.0 / 366.0,
+            RUSTQUANT_EPSILON
+        );
+    }
+}
+
+#[cfg(test)]
+mod TESTS_business_252 {
+    use crate::countries::BrazilCalendar;
+    use crate::{DayCountConvention, DayCounter};
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+    use RustQuant_utils::RUSTQUANT_EPSILON;
+
+    #[test]
+    fn business_252_weekdays() {
+        // 2024-02-01 (Thursday) to 2024-03-01 (Friday) spans 21 weekdays.
+        let dcf = DayCountConvention::Business_252
+            .day_count_factor(date!(2024 - 02 - 01), date!(2024 - 03 - 01));
+
+        assert_approx_equal!(dcf, 21.0 / 252.0, RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn business_252_calendar() {
+        let calendar = BrazilCalendar;
+        let convention = DayCountConvention::Business_252;
+
+        // Carnival Monday and Tuesday (12th and 13th) are not business days.
+        let (start, end) = (date!(2024 - 02 - 01), date!(2024 - 03 - 01));
+
+        assert_approx_equal!(
+            calendar.day_count_factor(start, end, &convention),
+            19.0 / 252.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            calendar.day_count_factor(end, start, &convention),
+            -19.0 / 252.0,
+            RUSTQUANT_EPSILON
+        );
+
+        // A start date on a holiday does not accrue.
+        assert_approx_equal!(
+            calendar.day_count_factor(date!(2024 - 01 - 01), date!(2024 - 01 - 03), &convention),
+            1.0 / 252.0,
+            RUSTQUANT_EPSILON
+        );
+    }
+}
+
+#[cfg(test)]
+mod TESTS_serial {
+    use crate::{DayCountConvention, SerialDate};
+    use time::{macros::date, Duration};
+
+    #[test]
+    fn serial_matches_date() {
+        let conventions = [
+            DayCountConvention::Actual_360,
+            DayCountConvention::Actual_365_Fixed,
+            DayCountConvention::Actual_Actual_ISDA,
+            DayCountConvention::Thirty_360_ISDA,
+            DayCountConvention::Thirty_E_360,
+            DayCountConvention::Thirty_E_360_ISDA,
+            DayCountConvention::Thirty_E_365,
+            DayCountConvention::Thirty_E_Plus_360,
+            DayCountConvention::Thirty_U_360,
+            DayCountConvention::No_Leap_365,
+        ];
+
+        // Month ends (including the end of February) and mid-month dates.
+        let starts: Vec<time::Date> = (0..400)
+            .map(|i| date!(2023 - 01 - 31) + Duration::days(i * 29))
+            .collect();
+        let ends: Vec<time::Date> = starts
+            .iter()
+            .enumerate()
+            .map(|(i, d)| *d + Duration::days(30 + 37 * i as i64 % 800))
+            .collect();
+
+        let (serial_starts, serial_ends) = (
+            SerialDate::from_dates(&starts),
+            SerialDate::from_dates(&ends),
+        );
+
+        for convention in conventions {
+            let serial = convention.day_count_factors_serial(&serial_starts, &serial_ends);
+
+            for i in 0..starts.len() {
+                assert_eq!(serial[i], convention.day_count_factor(starts[i], ends[i]));
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Exchange trading calendars.
+//!
+//! The country calendars describe settlement (banking) days. Exchanges
+//! follow their own schedules: the NYSE closes on Good Friday but not on
+//! Columbus or Veterans Day, Eurex closes on Christmas Eve and New Year's Eve,
+//! and most exchanges shorten the session before major holidays.
+//!
+//! An [`ExchangeCalendar`] is a [`Calendar`] whose business days are the
+//! trading days of the exchange, with the session closing times on top.
+//! The [`Exchange`] enum selects a calendar by its ISO 10383 MIC.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::exchanges::*;
+use crate::holiday::HolidayName;
+use time::{Date, Time};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Trading calendar of an exchange.
+///
+/// Times are in the local time of the exchange.
+pub trait ExchangeCalendar: Calendar {
+    /// Closing time of a regular trading session on the given date.
+    fn regular_close(&self, date: Date) -> Time;
+
+    /// Closing time if the session on the given date closes early,
+    /// or `None` if it is a regular session.
+    /// Only consulted for trading days.
+    fn early_close(&self, date: Date) -> Option<Time>;
+
+    /// Check if the exchange is open for trading on the date.
+    fn is_trading_day(&self, date: Date) -> bool {
+        self.is_business_day(date)
+    }
+
+    /// Check if the date is a trading day with a shortened session.
+    fn is_early_close(&self, date: Date) -> bool {
+        self.is_trading_day(date) && self.early_close(date).is_some()
+    }
+
+    /// Closing time of the session on the date, or `None` if the
+    /// exchange is closed.
+    fn session_close(&self, date: Date) -> Option<Time> {
+        if !self.is_trading_day(date) {
+            return None;
+        }
+
+        Some(
+            self.early_close(date)
+                .unwrap_or_else(|| self.regular_close(date)),
+        )
+    }
+
+    /// List all early-close days in the given range of `Date`s.
+    fn all_early_closes_between(&self, start_date: Date, end_date: Date) -> Vec<Date> {
+        let mut early_closes = Vec::new();
+
+        let mut temp_date = start_date;
+
+        while temp_date <= end_date {
+            if self.is_early_close(temp_date) {
+                early_closes.push(temp_date);
+            }
+
+            temp_date = temp_date.next_day().unwrap();
+        }
+
+        early_closes
+    }
+}
+
+/// Exchanges with a trading calendar, keyed by their ISO 10383 MIC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Exchange {
+    /// New York Stock Exchange (XNYS).
+    #[default]
+    NYSE,
+
+    /// Chicago Mercantile Exchange (XCME).
+    CME,
+
+    /// London Stock Exchange (XLON).
+    LSE,
+
+    /// Eurex Deutschland (XEUR).
+    Eurex,
+
+    /// Japan Exchange Group (XJPX).
+    JPX,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Forward a method call to the calendar of the exchange.
+macro_rules! dispatch {
+    ($exchange:expr, $calendar:ident => $body:expr) => {
+        match $exchange {
+            Exchange::NYSE => {
+                let $calendar = NyseCalendar;
+                $body
+            }
+            Exchange::CME => {
+                let $calendar = CmeCalendar;
+                $body
+            }
+            Exchange::LSE => {
+                let $calendar = LseCalendar;
+                $body
+            }
+            Exchange::Eurex => {
+                let $calendar = EurexCalendar;
+                $body
+            }
+            Exchange::JPX => {
+                let $calendar = JpxCalendar;
+                $body
+            }
+        }
+    };
+}
+
+impl Exchange {
+    /// All supported exchanges.
+    pub const ALL: [Exchange; 5] = [
+        Exchange::NYSE,
+        Exchange::CME,
+        Exchange::LSE,
+        Exchange::Eurex,
+        Exchange::JPX,
+    ];
+
+    /// Look up the exchange for a market identifier code.
+    ///
+    /// Segment MICs resolve to their operating MIC, so the Tokyo Stock
+    /// Exchange (XTKS) and Osaka Exchange (XOSE) both map to JPX.
+    pub fn from_mic(mic: &ISO_10383) -> Option<Self> {
+        Self::from_mic_code(mic.operating_mic)
+    }
+
+    /// Look up the exchange for a four-letter operating MIC, e.g. `"XNYS"`.
+    pub fn from_mic_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|exchange| exchange.market_identifier_code().operating_mic == code)
+    }
+}
+
+impl Calendar for Exchange {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn name(&self) -> &'static str {
+        dispatch!(self, calendar => calendar.name())
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        dispatch!(self, calendar => calendar.country_code())
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        dispatch!(self, calendar => calendar.market_identifier_code())
+    }
+
+    fn supported_years(&self) -> (i32, i32) {
+        dispatch!(self, calendar => calendar.supported_years())
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        dispatch!(self, calendar => calendar.is_holiday(date))
+    }
+
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        dispatch!(self, calendar => calendar.holiday_name(date))
+    }
+
+    fn is_business_day(&self, date: Date) -> bool {
+        dispatch!(self, calendar => calendar.is_business_day(date))
+    }
+}
+
+impl ExchangeCalendar for Exchange {
+    fn regular_close(&self, date: Date) -> Time {
+        dispatch!(self, calendar => calendar.regular_close(date))
+    }
+
+    fn early_close(&self, date: Date) -> Option<Time> {
+        dispatch!(self, calendar => calendar.early_close(date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_exchange_calendar {
+    use super::*;
+    use time::macros::{date, time};
+
+    #[test]
+    fn test_from_mic() {
+        assert_eq!(Exchange::from_mic(&XNYS), Some(Exchange::NYSE));
+        assert_eq!(Exchange::from_mic(&XCME), Some(Exchange::CME));
+        assert_eq!(Exchange::from_mic(&XLON), Some(Exchange::LSE));
+        assert_eq!(Exchange::from_mic(&XEUR), Some(Exchange::Eurex));
+        assert_eq!(Exchange::from_mic(&XJPX), Some(Exchange::JPX));
+        assert_eq!(Exchange::from_mic(&XTKS), Some(Exchange::JPX));
+        assert_eq!(Exchange::from_mic(&XCBT), None);
+        assert_eq!(Exchange::from_mic_code("XEUR"), Some(Exchange::Eurex));
+    }
+
+    #[test]
+    fn test_mic_round_trip() {
+        for exchange in Exchange::ALL {
+            let mic = exchange.market_identifier_code();
+            assert_eq!(Exchange::from_mic(&mic), Some(exchange));
+        }
+    }
+
+    #[test]
+    fn test_session_close() {
+        let nyse = Exchange::NYSE;
+
+        // Regular session, early close and holiday.
+        assert_eq!(
+            nyse.session_close(date!(2024 - 11 - 27)),
+            Some(time!(16:00))
+        );
+        assert_eq!(
+            nyse.session_close(date!(2024 - 11 - 29)),
+            Some(time!(13:00))
+        );
+        assert_eq!(nyse.session_close(date!(2024 - 11 - 28)), None);
+        assert!(!nyse.is_early_close(date!(2024 - 11 - 28)));
+    }
+
+    #[test]
+    fn test_trading_days_differ_from_settlement_days() {
+        use crate::countries::UnitedStatesCalendar;
+
+        let settlement = UnitedStatesCalendar;
+        let nyse = Exchange::NYSE;
+
+        // Good Friday: banks open, exchange closed.
+        assert!(settlement.is_business_day(date!(2024 - 03 - 29)));
+        assert!(!nyse.is_trading_day(date!(2024 - 03 - 29)));
+
+        // Columbus Day: banks closed, exchange open.
+        assert!(!settlement.is_business_day(date!(2024 - 10 - 14)));
+        assert!(nyse.is_trading_day(date!(2024 - 10 - 14)));
+    }
+
+    #[test]
+    fn test_all_early_closes_between() {
+        let early_closes =
+            Exchange::NYSE.all_early_closes_between(date!(2024 - 01 - 01), date!(2024 - 12 - 31));
+
+        assert_eq!(
+            early_closes,
+            vec![
+                date!(2024 - 07 - 03),
+                date!(2024 - 11 - 29),
+                date!(2024 - 12 - 24)
+            ]
+        );
+    }
+}
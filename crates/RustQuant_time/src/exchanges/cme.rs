@@ -0,0 +1,184 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::exchange_calendar::ExchangeCalendar;
+use crate::exchanges::NyseCalendar;
+use crate::utilities::unpack_date;
+use time::{macros::time, Date, Month, Time, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Chicago Mercantile Exchange trading calendar, following the CME Globex
+/// schedule for equity index and interest rate futures.
+///
+/// Globex only closes for New Year's Day, Good Friday and Christmas.
+/// On the other US holidays the session halts early at 12:00 Chicago time,
+/// and on the day after Thanksgiving and Christmas Eve at 12:15.
+/// Regular sessions end at 16:00.
+pub struct CmeCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for CmeCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Chicago Mercantile Exchange"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        UNITED_STATES_OF_AMERICA
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XCME
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (_, _, _, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day (moved to Monday if on Sunday)
+            NyseCalendar::is_new_years_day(date)
+            // Good Friday
+            || (yd == em - 3)
+            // Christmas (Monday if Sunday or Friday if Saturday)
+            || NyseCalendar::is_christmas(date)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+impl ExchangeCalendar for CmeCalendar {
+    fn regular_close(&self, _date: Date) -> Time {
+        time!(16:00)
+    }
+
+    fn early_close(&self, date: Date) -> Option<Time> {
+        let (y, m, d, wd, _, _) = unpack_date(date, false);
+
+        if (
+            // Martin Luther King's birthday (third Monday in January)
+            ((15..=21).contains(&d) && wd == Weekday::Monday && m == Month::January && y >= 1998)
+            // Washington's birthday (third Monday in February)
+            || NyseCalendar::is_washington_birthday(date)
+            // Memorial Day (last Monday in May)
+            || NyseCalendar::is_memorial_day(date)
+            // Juneteenth (Monday if Sunday or Friday if Saturday)
+            || NyseCalendar::is_juneteenth(date)
+            // Independence Day (Monday if Sunday or Friday if Saturday)
+            || NyseCalendar::is_independence_day(date)
+            // Labor Day (first Monday in September)
+            || (d <= 7 && wd == Weekday::Monday && m == Month::September)
+            // Thanksgiving Day (fourth Thursday in November)
+            || NyseCalendar::is_thanksgiving(date)
+        ) {
+            return Some(time!(12:00));
+        }
+
+        if (
+            // Day after Thanksgiving
+            ((23..=29).contains(&d) && wd == Weekday::Friday && m == Month::November)
+            // Christmas Eve
+            || (d == 24 && m == Month::December)
+        ) {
+            return Some(time!(12:15));
+        }
+
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_cme {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = CmeCalendar;
+        assert_eq!(calendar.name(), "Chicago Mercantile Exchange");
+    }
+
+    // Test to verify if weekends are not considered trading days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = CmeCalendar;
+        assert!(!calendar.is_trading_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_trading_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_trading_day() method properly accounts for exchange holidays.
+    #[test]
+    fn test_is_exchange_holiday() {
+        let calendar = CmeCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 12 - 25), // Christmas
+            date!(2023 - 01 - 02), // New Year's Day (observed)
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_trading_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify the early closes on US holidays.
+    #[test]
+    fn test_early_close() {
+        let calendar = CmeCalendar;
+
+        let noon = [
+            date!(2024 - 01 - 15), // Martin Luther King Jr. Day
+            date!(2024 - 05 - 27), // Memorial Day
+            date!(2024 - 07 - 04), // Independence Day
+            date!(2024 - 11 - 28), // Thanksgiving Day
+        ];
+
+        for day in noon {
+            assert!(calendar.is_trading_day(day), "{day}");
+            assert_eq!(calendar.session_close(day), Some(time!(12:00)), "{day}");
+        }
+
+        assert_eq!(
+            calendar.session_close(date!(2024 - 11 - 29)),
+            Some(time!(12:15))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 24)),
+            Some(time!(12:15))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 10 - 14)),
+            Some(time!(16:00))
+        );
+    }
+}
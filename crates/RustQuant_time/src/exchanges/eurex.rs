@@ -0,0 +1,157 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::exchange_calendar::ExchangeCalendar;
+use crate::utilities::unpack_date;
+use time::{macros::time, Date, Month, Time, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Eurex Deutschland trading calendar.
+///
+/// Eurex closes on the TARGET holidays and also on Christmas Eve and
+/// New Year's Eve, but trades on the German public holidays.
+/// Equity index derivatives trade until 22:00 Frankfurt time, and until
+/// 14:00 on the last trading day of the year.
+pub struct EurexCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for EurexCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Eurex"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        GERMANY
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XEUR
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (_, m, d, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day
+            (d == 1 && m == Month::January)
+            // Good Friday
+            || (yd == em - 3)
+            // Easter Monday
+            || (yd == em)
+            // Labour Day
+            || (d == 1 && m == Month::May)
+            // Christmas Eve, Christmas, St. Stephen's Day and New Year's Eve
+            || ((d == 24 || d == 25 || d == 26 || d == 31) && m == Month::December)
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+impl ExchangeCalendar for EurexCalendar {
+    fn regular_close(&self, _date: Date) -> Time {
+        time!(22:00)
+    }
+
+    fn early_close(&self, date: Date) -> Option<Time> {
+        let (m, d, wd) = (date.month(), date.day(), date.weekday());
+
+        // Last trading day of the year: December 30th, or the Friday
+        // before when it falls on a weekend.
+        if m == Month::December && (d == 30 || ((d == 28 || d == 29) && wd == Weekday::Friday)) {
+            return Some(time!(14:00));
+        }
+
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_eurex {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = EurexCalendar;
+        assert_eq!(calendar.name(), "Eurex");
+    }
+
+    // Test to verify if the is_trading_day() method properly accounts for exchange holidays.
+    #[test]
+    fn test_is_exchange_holiday() {
+        let calendar = EurexCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 04 - 01), // Easter Monday
+            date!(2024 - 05 - 01), // Labour Day
+            date!(2024 - 12 - 24), // Christmas Eve
+            date!(2024 - 12 - 25), // Christmas
+            date!(2024 - 12 - 26), // St. Stephen's Day
+            date!(2024 - 12 - 31), // New Year's Eve
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_trading_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify that German public holidays are trading days.
+    #[test]
+    fn test_is_regular_trading_day() {
+        let calendar = EurexCalendar;
+
+        assert!(calendar.is_trading_day(date!(2024 - 05 - 20))); // Whit Monday
+        assert!(calendar.is_trading_day(date!(2024 - 10 - 03))); // German Unity Day
+    }
+
+    // Test to verify the early closes.
+    #[test]
+    fn test_early_close() {
+        let calendar = EurexCalendar;
+
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 30)),
+            Some(time!(14:00))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2023 - 12 - 29)),
+            Some(time!(14:00))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 27)),
+            Some(time!(22:00))
+        );
+    }
+}
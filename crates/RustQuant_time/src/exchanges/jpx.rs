@@ -0,0 +1,117 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::countries::JapanCalendar;
+use crate::exchange_calendar::ExchangeCalendar;
+use time::{
+    macros::{date, time},
+    Date, Time,
+};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Japan Exchange Group (Tokyo Stock Exchange) trading calendar.
+///
+/// The exchange is closed on the Japanese national holidays and over the
+/// New Year (December 31st to January 3rd). There are no early closes;
+/// the cash session ends at 15:30 Tokyo time (15:00 before November 5th, 2024).
+pub struct JpxCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for JpxCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Japan Exchange Group"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        JAPAN
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XJPX
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        JapanCalendar.is_holiday(date)
+    }
+}
+
+impl ExchangeCalendar for JpxCalendar {
+    fn regular_close(&self, date: Date) -> Time {
+        if date < date!(2024 - 11 - 05) {
+            time!(15:00)
+        } else {
+            time!(15:30)
+        }
+    }
+
+    fn early_close(&self, _date: Date) -> Option<Time> {
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_jpx {
+    use super::*;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = JpxCalendar;
+        assert_eq!(calendar.name(), "Japan Exchange Group");
+    }
+
+    // Test to verify if the is_trading_day() method properly accounts for exchange holidays.
+    #[test]
+    fn test_is_exchange_holiday() {
+        let calendar = JpxCalendar;
+
+        assert!(!calendar.is_trading_day(date!(2024 - 01 - 03)));
+        assert!(!calendar.is_trading_day(date!(2024 - 12 - 31)));
+        assert!(!calendar.is_trading_day(date!(2024 - 11 - 04)));
+        assert!(calendar.is_trading_day(date!(2024 - 01 - 04)));
+    }
+
+    // Test to verify the closing time change.
+    #[test]
+    fn test_session_close() {
+        let calendar = JpxCalendar;
+
+        assert_eq!(
+            calendar.session_close(date!(2024 - 11 - 01)),
+            Some(time!(15:00))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 11 - 05)),
+            Some(time!(15:30))
+        );
+        assert!(calendar
+            .all_early_closes_between(date!(2024 - 01 - 01), date!(2024 - 12 - 31))
+            .is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::countries::UnitedKingdomCalendar;
+use crate::exchange_calendar::ExchangeCalendar;
+use crate::holiday::HolidayName;
+use time::{macros::time, Date, Month, Time};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// London Stock Exchange trading calendar.
+///
+/// The exchange is closed on the English bank holidays. Sessions close at
+/// 16:30 London time, or 12:30 on Christmas Eve and New Year's Eve.
+pub struct LseCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for LseCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "London Stock Exchange"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        UNITED_KINGDOM_OF_GREAT_BRITAIN_AND_NORTHERN_IRELAND
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XLON
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        UnitedKingdomCalendar.is_holiday(date)
+    }
+
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        UnitedKingdomCalendar.holiday_name(date)
+    }
+}
+
+impl ExchangeCalendar for LseCalendar {
+    fn regular_close(&self, _date: Date) -> Time {
+        time!(16:30)
+    }
+
+    fn early_close(&self, date: Date) -> Option<Time> {
+        let (m, d) = (date.month(), date.day());
+
+        if (d == 24 || d == 31) && m == Month::December {
+            return Some(time!(12:30));
+        }
+
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_lse {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = LseCalendar;
+        assert_eq!(calendar.name(), "London Stock Exchange");
+    }
+
+    // Test to verify if the is_trading_day() method properly accounts for exchange holidays.
+    #[test]
+    fn test_is_exchange_holiday() {
+        let calendar = LseCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 04 - 01), // Easter Monday
+            date!(2024 - 05 - 06), // Early May Bank Holiday
+            date!(2024 - 08 - 26), // Summer Bank Holiday
+            date!(2024 - 12 - 26), // Boxing Day
+            date!(2023 - 05 - 08), // Coronation
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_trading_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify the early closes.
+    #[test]
+    fn test_early_close() {
+        let calendar = LseCalendar;
+
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 24)),
+            Some(time!(12:30))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 31)),
+            Some(time!(12:30))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 30)),
+            Some(time!(16:30))
+        );
+
+        // Christmas Eve on a Sunday.
+        assert!(!calendar.is_early_close(date!(2023 - 12 - 24)));
+    }
+}
@@ -0,0 +1,26 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Trading calendars for specific exchanges.
+
+/// Chicago Mercantile Exchange trading calendar.
+pub mod cme;
+pub use cme::*;
+/// Eurex trading calendar.
+pub mod eurex;
+pub use eurex::*;
+/// Japan Exchange Group trading calendar.
+pub mod jpx;
+pub use jpx::*;
+/// London Stock Exchange trading calendar.
+pub mod lse;
+pub use lse::*;
+/// New York Stock Exchange trading calendar.
+pub mod nyse;
+pub use nyse::*;
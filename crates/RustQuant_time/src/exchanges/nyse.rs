@@ -0,0 +1,313 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::exchange_calendar::ExchangeCalendar;
+use crate::holiday::HolidayName;
+use crate::utilities::unpack_date;
+use time::{macros::time, Date, Month, Time, Weekday};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// New York Stock Exchange trading calendar.
+///
+/// Unlike the settlement calendar, the exchange closes on Good Friday and
+/// trades on Columbus Day and Veterans Day. A Saturday New Year's Day is
+/// not observed on the preceding Friday. Sessions close at 16:00 New York
+/// time, or 13:00 on the eve of Independence Day, the day after
+/// Thanksgiving and Christmas Eve.
+pub struct NyseCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for NyseCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "New York Stock Exchange"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        UNITED_STATES_OF_AMERICA
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XNYS
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        self.holiday_name(date).is_some()
+    }
+
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        let (y, m, d, wd, yd, em) = unpack_date(date, false);
+
+        let name = if Self::is_new_years_day(date) {
+            // moved to Monday if on Sunday
+            "New Year's Day"
+        } else if ((15..=21).contains(&d)
+            && wd == Weekday::Monday
+            && m == Month::January
+            && y >= 1998)
+        {
+            // third Monday in January, since 1998
+            "Martin Luther King Jr. Day"
+        } else if Self::is_washington_birthday(date) {
+            "Washington's Birthday"
+        } else if (yd == em - 3) {
+            "Good Friday"
+        } else if Self::is_memorial_day(date) {
+            "Memorial Day"
+        } else if Self::is_juneteenth(date) {
+            "Juneteenth"
+        } else if Self::is_independence_day(date) {
+            "Independence Day"
+        } else if (d <= 7 && wd == Weekday::Monday && m == Month::September) {
+            // first Monday in September
+            "Labor Day"
+        } else if Self::is_thanksgiving(date) {
+            "Thanksgiving Day"
+        } else if Self::is_christmas(date) {
+            "Christmas Day"
+        } else if (y <= 1980
+            && y % 4 == 0
+            && (2..=8).contains(&d)
+            && wd == Weekday::Tuesday
+            && m == Month::November)
+        {
+            // until 1980
+            "Presidential Election Day"
+        } else {
+            // unscheduled closures
+            return self.special_closure_name(y, d, m);
+        };
+
+        Some(name)
+    }
+}
+
+impl ExchangeCalendar for NyseCalendar {
+    fn regular_close(&self, _date: Date) -> Time {
+        time!(16:00)
+    }
+
+    fn early_close(&self, date: Date) -> Option<Time> {
+        let (_, m, d, wd, _, _) = unpack_date(date, false);
+
+        if (
+            // Day before Independence Day
+            (d == 3 && m == Month::July && wd != Weekday::Friday)
+            // Day after Thanksgiving
+            || ((23..=29).contains(&d) && wd == Weekday::Friday && m == Month::November)
+            // Christmas Eve
+            || (d == 24 && m == Month::December && wd != Weekday::Friday)
+        ) {
+            return Some(time!(13:00));
+        }
+
+        None
+    }
+}
+
+impl NyseCalendar {
+    pub(crate) fn is_new_years_day(date: Date) -> bool {
+        let (_, m, d, wd, _, _) = unpack_date(date, false);
+
+        (d == 1 || (d == 2 && wd == Weekday::Monday)) && m == Month::January
+    }
+
+    pub(crate) fn is_washington_birthday(date: Date) -> bool {
+        let (y, m, d, wd, _, _) = unpack_date(date, false);
+
+        if (y >= 1971) {
+            // third Monday in February
+            (15..=21).contains(&d) && wd == Weekday::Monday && m == Month::February
+        } else {
+            // February 22nd, possibly adjusted
+            (d == 22 || (d == 23 && wd == Weekday::Monday) || (d == 21 && wd == Weekday::Friday))
+                && m == Month::February
+        }
+    }
+
+    pub(crate) fn is_memorial_day(date: Date) -> bool {
+        let (y, m, d, wd, _, _) = unpack_date(date, false);
+
+        if (y >= 1971) {
+            // last Monday in May
+            d >= 25 && wd == Weekday::Monday && m == Month::May
+        } else {
+            // May 30th, possibly adjusted
+            (d == 30 || (d == 31 && wd == Weekday::Monday) || (d == 29 && wd == Weekday::Friday))
+                && m == Month::May
+        }
+    }
+
+    pub(crate) fn is_juneteenth(date: Date) -> bool {
+        let (y, m, d, wd, _, _) = unpack_date(date, false);
+
+        // declared in 2021, but only observed by exchanges since 2022
+        (d == 19 || (d == 20 && wd == Weekday::Monday) || (d == 18 && wd == Weekday::Friday))
+            && m == Month::June
+            && y >= 2022
+    }
+
+    pub(crate) fn is_independence_day(date: Date) -> bool {
+        let (_, m, d, wd, _, _) = unpack_date(date, false);
+
+        (d == 4 || (d == 5 && wd == Weekday::Monday) || (d == 3 && wd == Weekday::Friday))
+            && m == Month::July
+    }
+
+    pub(crate) fn is_thanksgiving(date: Date) -> bool {
+        let (_, m, d, wd, _, _) = unpack_date(date, false);
+
+        (22..=28).contains(&d) && wd == Weekday::Thursday && m == Month::November
+    }
+
+    pub(crate) fn is_christmas(date: Date) -> bool {
+        let (_, m, d, wd, _, _) = unpack_date(date, false);
+
+        (d == 25 || (d == 26 && wd == Weekday::Monday) || (d == 24 && wd == Weekday::Friday))
+            && m == Month::December
+    }
+
+    fn special_closure_name(&self, year: i32, day: u8, month: Month) -> Option<HolidayName> {
+        match (year, day, month) {
+            (1977, 14, Month::July) => Some("New York City Blackout"),
+            (1985, 27, Month::September) => Some("Hurricane Gloria"),
+            (1994, 27, Month::April) => Some("President Nixon's Funeral"),
+            (2001, 11..=14, Month::September) => Some("September 11th Attacks"),
+            (2004, 11, Month::June) => Some("President Reagan's Funeral"),
+            (2007, 2, Month::January) => Some("President Ford's Funeral"),
+            (2012, 29..=30, Month::October) => Some("Hurricane Sandy"),
+            (2018, 5, Month::December) => Some("President George H. W. Bush's Funeral"),
+            (2025, 9, Month::January) => Some("President Carter's Funeral"),
+            _ => None,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_nyse {
+    use super::*;
+    use time::macros::date;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = NyseCalendar;
+        assert_eq!(calendar.name(), "New York Stock Exchange");
+    }
+
+    // Test to verify if weekends are not considered trading days.
+    #[test]
+    fn test_is_weekend() {
+        let calendar = NyseCalendar;
+        assert!(!calendar.is_trading_day(date!(2024 - 06 - 08)));
+        assert!(!calendar.is_trading_day(date!(2024 - 06 - 09)));
+    }
+
+    // Test to verify if the is_trading_day() method properly accounts for exchange holidays.
+    #[test]
+    fn test_is_exchange_holiday() {
+        let calendar = NyseCalendar;
+
+        let holidays = [
+            date!(2024 - 01 - 01), // New Year's Day
+            date!(2024 - 01 - 15), // Martin Luther King Jr. Day
+            date!(2024 - 02 - 19), // Washington's Birthday
+            date!(2024 - 03 - 29), // Good Friday
+            date!(2024 - 05 - 27), // Memorial Day
+            date!(2024 - 06 - 19), // Juneteenth
+            date!(2024 - 07 - 04), // Independence Day
+            date!(2024 - 09 - 02), // Labor Day
+            date!(2024 - 11 - 28), // Thanksgiving Day
+            date!(2024 - 12 - 25), // Christmas
+            date!(2021 - 12 - 24), // Christmas (observed)
+            date!(2012 - 10 - 30), // Hurricane Sandy
+            date!(2025 - 01 - 09), // President Carter's funeral
+        ];
+
+        for holiday in holidays {
+            assert!(!calendar.is_trading_day(holiday), "{holiday}");
+        }
+    }
+
+    // Test to verify if the is_trading_day() method properly accounts for regular trading days.
+    #[test]
+    fn test_is_regular_trading_day() {
+        let calendar = NyseCalendar;
+
+        // Columbus Day, Veterans Day and a Friday before a Saturday New Year's Day.
+        assert!(calendar.is_trading_day(date!(2024 - 10 - 14)));
+        assert!(calendar.is_trading_day(date!(2024 - 11 - 11)));
+        assert!(calendar.is_trading_day(date!(2021 - 12 - 31)));
+    }
+
+    // Test to verify the holiday names.
+    #[test]
+    fn test_holiday_name() {
+        let calendar = NyseCalendar;
+
+        assert_eq!(
+            calendar.holiday_name(date!(2024 - 03 - 29)),
+            Some("Good Friday")
+        );
+        assert_eq!(
+            calendar.holiday_name(date!(2012 - 10 - 29)),
+            Some("Hurricane Sandy")
+        );
+        assert_eq!(calendar.holiday_name(date!(2024 - 10 - 14)), None);
+    }
+
+    // Test to verify the early closes.
+    #[test]
+    fn test_early_close() {
+        let calendar = NyseCalendar;
+
+        assert_eq!(
+            calendar.session_close(date!(2023 - 07 - 03)),
+            Some(time!(13:00))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2023 - 11 - 24)),
+            Some(time!(13:00))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 24)),
+            Some(time!(13:00))
+        );
+        assert_eq!(
+            calendar.session_close(date!(2024 - 12 - 23)),
+            Some(time!(16:00))
+        );
+
+        // Independence Day on a Saturday is observed on Friday July 3rd.
+        assert_eq!(calendar.session_close(date!(2020 - 07 - 03)), None);
+        assert_eq!(
+            calendar.session_close(date!(2020 - 07 - 02)),
+            Some(time!(16:00))
+        );
+    }
+}
@@ -19,6 +19,7 @@ use crate::constants::{
 /// a cash flow is paid in a year, and thus affects the present value
 /// of the cash flows.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Frequency {
     /// Daily (252 per year).
     Daily = DAILY,
@@ -11,6 +11,12 @@
 
 use time::Date;
 
+/// Name of a holiday, e.g. "Christmas Day".
+pub type HolidayName = &'static str;
+
+/// Name given to the holidays of calendars that do not name them.
+pub const UNNAMED_HOLIDAY: HolidayName = "Holiday";
+
 /// Holiday type.
 #[derive(Debug, Clone)]
 pub struct Holiday {
@@ -0,0 +1,179 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! IMM futures dates and CDS standard roll dates.
+//!
+//! Both follow the quarterly March, June, September, December cycle:
+//!     - IMM dates fall on the third Wednesday of the month.
+//!     - CDS roll dates fall on the 20th of the month.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::utilities::get_third_wednesday_of_month;
+use time::{Date, Month, Weekday};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Check if the month is one of March, June, September or December.
+pub fn is_imm_month(month: Month) -> bool {
+    matches!(
+        month,
+        Month::March | Month::June | Month::September | Month::December
+    )
+}
+
+/// Check if the date is an IMM date (third Wednesday of March, June,
+/// September or December).
+pub fn is_imm_date(date: Date) -> bool {
+    is_imm_month(date.month())
+        && date.weekday() == Weekday::Wednesday
+        && (15..=21).contains(&date.day())
+}
+
+/// First IMM date strictly after the date.
+pub fn next_imm_date(date: Date) -> Date {
+    next_quarterly_date(date, imm_date)
+}
+
+/// Last IMM date strictly before the date.
+pub fn previous_imm_date(date: Date) -> Date {
+    previous_quarterly_date(date, imm_date)
+}
+
+/// Check if the date is a CDS standard roll date (20th of March, June,
+/// September or December).
+pub fn is_cds_date(date: Date) -> bool {
+    is_imm_month(date.month()) && date.day() == 20
+}
+
+/// First CDS roll date strictly after the date.
+pub fn next_cds_date(date: Date) -> Date {
+    next_quarterly_date(date, cds_date)
+}
+
+/// Last CDS roll date strictly before the date.
+pub fn previous_cds_date(date: Date) -> Date {
+    previous_quarterly_date(date, cds_date)
+}
+
+fn imm_date(year: i32, month: Month) -> Date {
+    get_third_wednesday_of_month(year, month).unwrap()
+}
+
+fn cds_date(year: i32, month: Month) -> Date {
+    Date::from_calendar_date(year, month, 20).unwrap()
+}
+
+// Walks forward month by month from the date's own month. The candidate in
+// the starting month may still lie before the date, hence the comparison.
+fn next_quarterly_date(date: Date, date_in: fn(i32, Month) -> Date) -> Date {
+    let (mut year, mut month) = (date.year(), date.month());
+
+    loop {
+        if is_imm_month(month) {
+            let candidate = date_in(year, month);
+
+            if candidate > date {
+                return candidate;
+            }
+        }
+
+        month = month.next();
+        if month == Month::January {
+            year += 1;
+        }
+    }
+}
+
+fn previous_quarterly_date(date: Date, date_in: fn(i32, Month) -> Date) -> Date {
+    let (mut year, mut month) = (date.year(), date.month());
+
+    loop {
+        if is_imm_month(month) {
+            let candidate = date_in(year, month);
+
+            if candidate < date {
+                return candidate;
+            }
+        }
+
+        month = month.previous();
+        if month == Month::December {
+            year -= 1;
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_imm {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_is_imm_date() {
+        assert!(is_imm_date(date!(2024 - 03 - 20)));
+        assert!(is_imm_date(date!(2024 - 06 - 19)));
+        assert!(is_imm_date(date!(2024 - 09 - 18)));
+        assert!(is_imm_date(date!(2024 - 12 - 18)));
+
+        // Third Wednesday, but not a quarterly month.
+        assert!(!is_imm_date(date!(2024 - 01 - 17)));
+        // Quarterly month, but not the third Wednesday.
+        assert!(!is_imm_date(date!(2024 - 03 - 13)));
+    }
+
+    #[test]
+    fn test_next_and_previous_imm_date() {
+        assert_eq!(next_imm_date(date!(2024 - 01 - 10)), date!(2024 - 03 - 20));
+        assert_eq!(next_imm_date(date!(2024 - 03 - 19)), date!(2024 - 03 - 20));
+        assert_eq!(next_imm_date(date!(2024 - 03 - 20)), date!(2024 - 06 - 19));
+        assert_eq!(next_imm_date(date!(2024 - 12 - 20)), date!(2025 - 03 - 19));
+
+        assert_eq!(
+            previous_imm_date(date!(2024 - 03 - 20)),
+            date!(2023 - 12 - 20)
+        );
+        assert_eq!(
+            previous_imm_date(date!(2024 - 03 - 21)),
+            date!(2024 - 03 - 20)
+        );
+        assert_eq!(
+            previous_imm_date(date!(2024 - 08 - 01)),
+            date!(2024 - 06 - 19)
+        );
+    }
+
+    #[test]
+    fn test_cds_dates() {
+        assert!(is_cds_date(date!(2024 - 06 - 20)));
+        assert!(!is_cds_date(date!(2024 - 07 - 20)));
+        assert!(!is_cds_date(date!(2024 - 06 - 21)));
+
+        assert_eq!(next_cds_date(date!(2024 - 06 - 19)), date!(2024 - 06 - 20));
+        assert_eq!(next_cds_date(date!(2024 - 06 - 20)), date!(2024 - 09 - 20));
+        assert_eq!(next_cds_date(date!(2024 - 12 - 21)), date!(2025 - 03 - 20));
+
+        assert_eq!(
+            previous_cds_date(date!(2024 - 06 - 20)),
+            date!(2024 - 03 - 20)
+        );
+        assert_eq!(
+            previous_cds_date(date!(2024 - 02 - 01)),
+            date!(2023 - 12 - 20)
+        );
+    }
+}
@@ -0,0 +1,228 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Calendars combining the holidays of other calendars.
+//!
+//! Cross-currency instruments usually settle only on days that are business
+//! days in every currency involved, e.g. USD/JPY needs both a US and a
+//! Japanese business day. A [`JointCalendar`] joins two calendars, and since
+//! it is a calendar itself, joint calendars nest to combine more than two:
+//!
+//! ```
+//! use RustQuant_time::{Calendar, JointCalendar, TargetCalendar};
+//! use RustQuant_time::{UnitedKingdomCalendar, UnitedStatesCalendar};
+//! use time::macros::date;
+//!
+//! let calendar = JointCalendar::join(
+//!     UnitedStatesCalendar,
+//!     JointCalendar::join(UnitedKingdomCalendar, TargetCalendar),
+//! );
+//!
+//! // Independence Day.
+//! assert!(!calendar.is_business_day(date!(2024 - 07 - 04)));
+//! ```
+
+use crate::calendar::Calendar;
+use crate::holiday::HolidayName;
+use time::Date;
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How a [`JointCalendar`] combines the days of its calendars.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JointCalendarRule {
+    /// Union of the holidays: a business day must be a business day in
+    /// both calendars (e.g. settlement of cross-currency trades).
+    #[default]
+    JoinHolidays,
+
+    /// Intersection of the holidays: a business day only has to be a
+    /// business day in one of the calendars.
+    JoinBusinessDays,
+}
+
+/// Calendar combining two calendars.
+#[derive(Clone, Copy, Debug)]
+pub struct JointCalendar<A, B> {
+    /// First calendar (gives the country and market codes).
+    pub first: A,
+
+    /// Second calendar.
+    pub second: B,
+
+    /// How the calendars are combined.
+    pub rule: JointCalendarRule,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<A: Calendar, B: Calendar> JointCalendar<A, B> {
+    /// Join two calendars, with the union of their holidays.
+    pub fn join(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            rule: JointCalendarRule::default(),
+        }
+    }
+
+    /// Set how the calendars are combined.
+    pub fn with_rule(mut self, rule: JointCalendarRule) -> Self {
+        self.rule = rule;
+        self
+    }
+}
+
+impl<A: Calendar, B: Calendar> Calendar for JointCalendar<A, B> {
+    fn new() -> Self {
+        Self::join(A::new(), B::new())
+    }
+
+    fn name(&self) -> &'static str {
+        match self.rule {
+            JointCalendarRule::JoinHolidays => "Joint (holidays)",
+            JointCalendarRule::JoinBusinessDays => "Joint (business days)",
+        }
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        self.first.country_code()
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        self.first.market_identifier_code()
+    }
+
+    // Both calendars are consulted under either rule.
+    fn supported_years(&self) -> (i32, i32) {
+        let (first, second) = (self.first.supported_years(), self.second.supported_years());
+
+        (first.0.max(second.0), first.1.min(second.1))
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        match self.rule {
+            JointCalendarRule::JoinHolidays => {
+                self.first.is_holiday(date) || self.second.is_holiday(date)
+            }
+            JointCalendarRule::JoinBusinessDays => {
+                self.first.is_holiday(date) && self.second.is_holiday(date)
+            }
+        }
+    }
+
+    // Name the holiday after the first calendar that observes it.
+    fn holiday_name(&self, date: Date) -> Option<HolidayName> {
+        match self.rule {
+            JointCalendarRule::JoinHolidays => self
+                .first
+                .holiday_name(date)
+                .or_else(|| self.second.holiday_name(date)),
+            JointCalendarRule::JoinBusinessDays => self
+                .first
+                .holiday_name(date)
+                .filter(|_| self.second.is_holiday(date)),
+        }
+    }
+
+    // Combine the business days rather than rely on the default, since the
+    // calendars may have different weekends.
+    fn is_business_day(&self, date: Date) -> bool {
+        match self.rule {
+            JointCalendarRule::JoinHolidays => {
+                self.first.is_business_day(date) && self.second.is_business_day(date)
+            }
+            JointCalendarRule::JoinBusinessDays => {
+                self.first.is_business_day(date) || self.second.is_business_day(date)
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_joint_calendar {
+    use super::*;
+    use crate::{
+        DateRoller, DateRollingConvention, IsraelCalendar, TargetCalendar, UnitedKingdomCalendar,
+        UnitedStatesCalendar,
+    };
+    use time::macros::date;
+
+    #[test]
+    fn test_join_holidays() {
+        let calendar = JointCalendar::<UnitedStatesCalendar, UnitedKingdomCalendar>::new();
+
+        // Independence Day (US) and the Summer Bank Holiday (UK).
+        assert!(!calendar.is_business_day(date!(2023 - 07 - 04)));
+        assert!(!calendar.is_business_day(date!(2023 - 08 - 28)));
+        assert!(calendar.is_business_day(date!(2023 - 08 - 29)));
+        assert_eq!(calendar.country_code(), UnitedStatesCalendar.country_code());
+        assert_eq!(
+            calendar.holidays_between(date!(2023 - 07 - 01), date!(2023 - 08 - 31)),
+            vec![
+                (date!(2023 - 07 - 04), "Independence Day"),
+                (date!(2023 - 08 - 28), "Summer Bank Holiday"),
+            ]
+        );
+
+        // Rolling skips the holidays of both calendars.
+        assert_eq!(
+            calendar.roll_date(date!(2023 - 08 - 26), &DateRollingConvention::Following),
+            date!(2023 - 08 - 29)
+        );
+    }
+
+    #[test]
+    fn test_join_business_days() {
+        let calendar = JointCalendar::join(UnitedStatesCalendar, UnitedKingdomCalendar)
+            .with_rule(JointCalendarRule::JoinBusinessDays);
+
+        assert!(calendar.is_business_day(date!(2023 - 07 - 04)));
+        assert!(calendar.is_business_day(date!(2023 - 08 - 28)));
+        assert!(!calendar.is_business_day(date!(2023 - 12 - 25)));
+        assert_eq!(calendar.holiday_name(date!(2023 - 07 - 04)), None);
+        assert_eq!(
+            calendar.holiday_name(date!(2023 - 12 - 25)),
+            Some("Christmas Day")
+        );
+        assert!(!calendar.is_business_day(date!(2023 - 08 - 26)));
+    }
+
+    #[test]
+    fn test_nested_and_different_weekends() {
+        let calendar = JointCalendar::join(
+            IsraelCalendar,
+            JointCalendar::join(UnitedStatesCalendar, TargetCalendar),
+        );
+
+        // Friday is a weekend day in Israel only, Sunday in the others only.
+        assert!(!calendar.is_business_day(date!(2024 - 01 - 05)));
+        assert!(!calendar.is_business_day(date!(2024 - 01 - 07)));
+        assert!(calendar.is_business_day(date!(2024 - 01 - 08)));
+
+        // Labour Day is a TARGET holiday.
+        assert!(calendar.is_holiday(date!(2024 - 05 - 01)));
+        assert_eq!(
+            calendar
+                .all_business_days_between(date!(2024 - 01 - 01), date!(2024 - 01 - 07))
+                .len(),
+            3
+        );
+    }
+}
@@ -13,6 +13,18 @@
 pub mod calendar;
 pub use calendar::*;
 
+/// Calendars combining the holidays of other calendars.
+pub mod joint_calendar;
+pub use joint_calendar::*;
+
+/// Calendars built from user-supplied holidays and closures.
+pub mod custom_calendar;
+pub use custom_calendar::*;
+
+/// Calendars caching their business days for fast lookups and counting.
+pub mod cached_calendar;
+pub use cached_calendar::*;
+
 /// Constants for calendars and holidays.
 #[allow(dead_code)]
 pub mod constants;
@@ -23,6 +35,15 @@ pub use constants::*;
 pub mod countries;
 pub use countries::*;
 
+/// Trading calendars of exchanges, with early closes.
+pub mod exchange_calendar;
+pub use exchange_calendar::*;
+
+/// Trading calendars implemented for specific exchanges.
+#[allow(unused_parens)]
+pub mod exchanges;
+pub use exchanges::*;
+
 /// Date rolling conventions and methods.
 pub mod date_rolling;
 pub use date_rolling::*;
@@ -35,6 +56,14 @@ pub use day_counting::*;
 pub mod frequency;
 pub use frequency::*;
 
+/// Tenors and period arithmetic.
+pub mod tenor;
+pub use tenor::*;
+
+/// IMM futures dates and CDS roll dates.
+pub mod imm;
+pub use imm::*;
+
 /// The `Holiday` trait.
 pub mod holiday;
 pub use holiday::*;
@@ -43,6 +72,10 @@ pub use holiday::*;
 pub mod utilities;
 pub use utilities::*;
 
+/// Serial day numbers for fast date arithmetic.
+pub mod serial_date;
+pub use serial_date::*;
+
 /// The `Schedule` type.
 pub mod schedule;
 pub use schedule::*;
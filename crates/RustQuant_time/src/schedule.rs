@@ -7,11 +7,21 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::date_generation::DateGenerationConvention;
 use crate::date_rolling::{DateRoller, DateRollingConvention};
 use crate::day_counting::{DayCountConvention, DayCounter};
+use crate::frequency::Frequency;
+use crate::imm::{is_imm_date, next_imm_date};
+use crate::stub_generation::StubGeneration;
+use crate::tenor::{Tenor, TenorUnit};
+use crate::utilities::{
+    get_third_wednesday_of_month, is_last_business_day_of_month, is_last_day_of_month,
+    previous_business_day,
+};
 use crate::Calendar;
 use std::fmt;
 use time::Date;
+use RustQuant_error::RustQuantError;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
@@ -27,10 +37,20 @@ use time::Date;
 /// The Schedule struct is used to represent these schedules,
 /// and pricing methods should be implemented using date/time functionality.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Schedule {
     /// The dates of the schedule.
     pub dates: Vec<Date>,
 
+    /// The dates of the schedule before business day adjustment.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unadjusted_dates: Vec<Date>,
+
+    /// Whether each period (between consecutive dates) is a whole tenor,
+    /// i.e. not a stub.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_regular: Vec<bool>,
+
     /// The day count factors of the schedule.
     pub day_count_factors: Vec<f64>,
 
@@ -41,6 +61,68 @@ pub struct Schedule {
     pub date_rolling_convention: DateRollingConvention,
 }
 
+/// Generates a schedule from an effective date, a termination date and a
+/// tenor, following a date generation rule, stub rule and end-of-month rule,
+/// and adjusts each date to a business day.
+///
+/// ```
+/// use RustQuant_time::*;
+/// use time::macros::date;
+///
+/// // Semi-annual periods rolled back from the termination date,
+/// // leaving a short front stub.
+/// let schedule = ScheduleGenerator::new(
+///     date!(2024 - 02 - 15),
+///     date!(2026 - 06 - 15),
+///     "6M".parse().unwrap(),
+///     UnitedStatesCalendar,
+/// )
+/// .generate()
+/// .unwrap();
+///
+/// assert_eq!(schedule.dates[1], date!(2024 - 06 - 17));
+/// assert_eq!(schedule.is_regular, vec![false, true, true, true, true]);
+/// ```
+pub struct ScheduleGenerator<C: Calendar> {
+    /// Start of the first period.
+    pub effective_date: Date,
+
+    /// End of the last period.
+    pub termination_date: Date,
+
+    /// Length of a regular period.
+    pub tenor: Tenor,
+
+    /// Calendar used to adjust the dates.
+    pub calendar: C,
+
+    /// Rule used to generate the unadjusted dates.
+    pub date_generation_convention: DateGenerationConvention,
+
+    /// Stub rule. If not set, the stub is short and on the side
+    /// where the generation ends.
+    pub stub_generation: Option<StubGeneration>,
+
+    /// Adjustment of the dates, except the termination date.
+    pub date_rolling_convention: DateRollingConvention,
+
+    /// Adjustment of the termination date.
+    pub termination_date_convention: DateRollingConvention,
+
+    /// End-of-month rule: if the generation starts on the last business
+    /// day of a month, every date is on the last business day of its month.
+    pub end_of_month: bool,
+
+    /// First regular date, ending an explicit front stub.
+    pub first_date: Option<Date>,
+
+    /// Next-to-last date, starting an explicit back stub.
+    pub next_to_last_date: Option<Date>,
+
+    /// Day count convention of the periods.
+    pub day_counting_convention: DayCountConvention,
+}
+
 /// The `Scheduler` trait.
 /// This trait is used to generate schedules for a `Calendar`.
 pub trait Scheduler {
@@ -85,6 +167,8 @@ where
         );
 
         Schedule {
+            unadjusted_dates: dates.to_vec(),
+            is_regular: vec![true; dates.len().saturating_sub(1)],
             dates: rolled_dates,
             day_count_factors,
             day_counting_convention,
@@ -113,6 +197,432 @@ impl fmt::Display for Schedule {
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl Schedule {
+    /// Start and end dates of each period.
+    pub fn periods(&self) -> Vec<(Date, Date)> {
+        self.dates
+            .windows(2)
+            .map(|window| (window[0], window[1]))
+            .collect()
+    }
+}
+
+impl<C: Calendar> ScheduleGenerator<C> {
+    /// Create a new schedule generator, rolling backward from the
+    /// termination date with a short front stub, Modified Following
+    /// adjustment and no end-of-month rule.
+    pub fn new(effective_date: Date, termination_date: Date, tenor: Tenor, calendar: C) -> Self {
+        Self {
+            effective_date,
+            termination_date,
+            tenor,
+            calendar,
+            date_generation_convention: DateGenerationConvention::Backward,
+            stub_generation: None,
+            date_rolling_convention: DateRollingConvention::ModifiedFollowing,
+            termination_date_convention: DateRollingConvention::ModifiedFollowing,
+            end_of_month: false,
+            first_date: None,
+            next_to_last_date: None,
+            day_counting_convention: DayCountConvention::default(),
+        }
+    }
+
+    /// Set the date generation rule.
+    pub fn with_date_generation_convention(mut self, convention: DateGenerationConvention) -> Self {
+        self.date_generation_convention = convention;
+        self
+    }
+
+    /// Set the stub rule.
+    pub fn with_stub_generation(mut self, stub_generation: StubGeneration) -> Self {
+        self.stub_generation = Some(stub_generation);
+        self
+    }
+
+    /// Set the adjustment of all dates, including the termination date.
+    pub fn with_date_rolling_convention(mut self, convention: DateRollingConvention) -> Self {
+        self.date_rolling_convention = convention;
+        self.termination_date_convention = convention;
+        self
+    }
+
+    /// Set the adjustment of the termination date only.
+    pub fn with_termination_date_convention(mut self, convention: DateRollingConvention) -> Self {
+        self.termination_date_convention = convention;
+        self
+    }
+
+    /// Set the end-of-month rule.
+    pub fn with_end_of_month(mut self, end_of_month: bool) -> Self {
+        self.end_of_month = end_of_month;
+        self
+    }
+
+    /// Set the first regular date, ending a front stub.
+    pub fn with_first_date(mut self, first_date: Date) -> Self {
+        self.first_date = Some(first_date);
+        self
+    }
+
+    /// Set the next-to-last date, starting a back stub.
+    pub fn with_next_to_last_date(mut self, next_to_last_date: Date) -> Self {
+        self.next_to_last_date = Some(next_to_last_date);
+        self
+    }
+
+    /// Set the day count convention of the periods.
+    pub fn with_day_counting_convention(mut self, convention: DayCountConvention) -> Self {
+        self.day_counting_convention = convention;
+        self
+    }
+
+    /// Generate the schedule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dates are out of order, the tenor is not
+    /// positive, the stub rule does not match the generation direction,
+    /// or `StubGeneration::None` is used and the dates do not fit a whole
+    /// number of periods.
+    pub fn generate(&self) -> Result<Schedule, RustQuantError> {
+        self.validate()?;
+
+        let (unadjusted_dates, is_regular) = match self.date_generation_convention {
+            DateGenerationConvention::Zero => {
+                (vec![self.effective_date, self.termination_date], vec![true])
+            }
+            DateGenerationConvention::Forward => self.roll(false)?,
+            DateGenerationConvention::Backward => self.roll(true)?,
+            DateGenerationConvention::ThirdWednesday => {
+                let (mut dates, is_regular) = self.roll(true)?;
+                let last = dates.len() - 1;
+
+                for date in &mut dates[1..last] {
+                    *date = get_third_wednesday_of_month(date.year(), date.month()).unwrap();
+                }
+
+                (dates, is_regular)
+            }
+            DateGenerationConvention::IMM => self.roll_imm()?,
+        };
+
+        if self.stub_generation == Some(StubGeneration::None) && is_regular.contains(&false) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "The dates from {} to {} do not fit a whole number of {} periods.",
+                self.effective_date, self.termination_date, self.tenor
+            )));
+        }
+
+        let dates = self.adjust(&unadjusted_dates);
+        let day_count_factors = match (self.day_counting_convention, self.frequency()) {
+            (DayCountConvention::Actual_Actual_ICMA, Some(frequency))
+                if self.date_generation_convention != DateGenerationConvention::Zero =>
+            {
+                self.icma_factors(&dates, &unadjusted_dates, &is_regular, frequency)
+            }
+            _ => self
+                .calendar
+                .day_count_factors(&dates, &self.day_counting_convention),
+        };
+
+        Ok(Schedule {
+            dates,
+            unadjusted_dates,
+            is_regular,
+            day_count_factors,
+            day_counting_convention: self.day_counting_convention,
+            date_rolling_convention: self.date_rolling_convention,
+        })
+    }
+
+    fn validate(&self) -> Result<(), RustQuantError> {
+        let (effective, termination) = (self.effective_date, self.termination_date);
+
+        if effective >= termination {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Effective date {} must be before termination date {}.",
+                effective, termination
+            )));
+        }
+
+        if self.date_generation_convention != DateGenerationConvention::Zero
+            && self.tenor.length <= 0
+        {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Schedule tenor must be positive, got {}.",
+                self.tenor
+            )));
+        }
+
+        self.calendar.check_date(effective)?;
+        self.calendar.check_date(termination)?;
+
+        let first = self.first_date.unwrap_or(effective);
+        let next_to_last = self.next_to_last_date.unwrap_or(termination);
+
+        if first < effective || next_to_last > termination || first > next_to_last {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "First date {} and next-to-last date {} must lie between {} and {}, in order.",
+                first, next_to_last, effective, termination
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Unadjusted dates and period regularity from rolling the tenor
+    /// forward from the effective date, or backward from the termination date.
+    fn roll(&self, backward: bool) -> Result<(Vec<Date>, Vec<bool>), RustQuantError> {
+        let long_stub =
+            match (self.stub_generation, backward) {
+                (None | Some(StubGeneration::None) | Some(StubGeneration::Both), _) => false,
+                (Some(StubGeneration::ShortFront), true)
+                | (Some(StubGeneration::ShortBack), false) => false,
+                (Some(StubGeneration::LongFront), true)
+                | (Some(StubGeneration::LongBack), false) => true,
+                (Some(stub), _) => {
+                    return Err(RustQuantError::InvalidArgument(format!(
+                        "A {:?} stub cannot be generated {}.",
+                        stub,
+                        if backward { "backward" } else { "forward" }
+                    )))
+                }
+            };
+
+        // Generation starts at `start`, or at the explicit stub date `near`,
+        // and runs towards `far` (or `end`).
+        let (start, end, near, far) = if backward {
+            (
+                self.termination_date,
+                self.effective_date,
+                self.next_to_last_date,
+                self.first_date,
+            )
+        } else {
+            (
+                self.effective_date,
+                self.termination_date,
+                self.first_date,
+                self.next_to_last_date,
+            )
+        };
+
+        let anchor = near.unwrap_or(start);
+        let limit = far.unwrap_or(end);
+        let (rolled, hit) = self.period_dates(anchor, limit, backward);
+        let rolled_count = rolled.len();
+
+        let mut dates = Vec::with_capacity(rolled_count + 3);
+        let mut is_regular = Vec::with_capacity(rolled_count + 2);
+
+        if near.is_some() && anchor != start {
+            dates.push(start);
+            is_regular.push(self.is_whole_period(start, anchor));
+        }
+
+        dates.extend(rolled);
+        is_regular.extend(vec![true; rolled_count - 1]);
+
+        // Generated stub between the last rolled date and the limit,
+        // merged into the previous period if it is a long stub.
+        if anchor != limit {
+            if long_stub && !hit && rolled_count > 1 {
+                dates.pop();
+                is_regular.pop();
+            }
+            dates.push(limit);
+            is_regular.push(hit);
+        }
+
+        if far.is_some() && limit != end {
+            dates.push(end);
+            is_regular.push(self.is_whole_period(limit, end));
+        }
+
+        if backward {
+            dates.reverse();
+            is_regular.reverse();
+        }
+
+        Ok((dates, is_regular))
+    }
+
+    /// Dates from `anchor` in steps of the tenor, stopping before reaching
+    /// `limit`, and whether the next step lands exactly on `limit`.
+    fn period_dates(&self, anchor: Date, limit: Date, backward: bool) -> (Vec<Date>, bool) {
+        let monthly = matches!(self.tenor.unit, TenorUnit::Months | TenorUnit::Years);
+        let end_of_month =
+            self.end_of_month && monthly && is_last_business_day_of_month(anchor, &self.calendar);
+        let step = if backward { -self.tenor } else { self.tenor };
+
+        let mut dates = vec![anchor];
+
+        for i in 1.. {
+            let mut date = (step * i).add_to(anchor, false);
+
+            if end_of_month {
+                date = month_end(date);
+            }
+
+            if (backward && date <= limit) || (!backward && date >= limit) {
+                let same_month = (date.year(), date.month()) == (limit.year(), limit.month());
+                return (dates, date == limit || (end_of_month && same_month));
+            }
+
+            dates.push(date);
+        }
+
+        unreachable!()
+    }
+
+    /// Unadjusted dates and period regularity for the IMM rule.
+    fn roll_imm(&self) -> Result<(Vec<Date>, Vec<bool>), RustQuantError> {
+        let months = match self.tenor.unit {
+            TenorUnit::Months => self.tenor.length,
+            TenorUnit::Years => 12 * self.tenor.length,
+            _ => 0,
+        };
+
+        if months % 3 != 0 || months == 0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "IMM schedules need a tenor of a whole number of quarters, got {}.",
+                self.tenor
+            )));
+        }
+
+        let (effective, termination) = (self.effective_date, self.termination_date);
+
+        let mut dates = vec![effective];
+        let mut is_regular = Vec::new();
+        let mut current = effective;
+
+        if !is_imm_date(effective) {
+            current = next_imm_date(effective);
+
+            if current < termination {
+                dates.push(current);
+                is_regular.push(false);
+            }
+        }
+
+        while current < termination {
+            let next = Tenor::months(months).add_to(current, false);
+            let next = get_third_wednesday_of_month(next.year(), next.month()).unwrap();
+
+            if next >= termination {
+                is_regular.push(next == termination);
+                break;
+            }
+
+            dates.push(next);
+            is_regular.push(true);
+            current = next;
+        }
+
+        if is_regular.len() < dates.len() {
+            is_regular.push(false);
+        }
+        dates.push(termination);
+
+        Ok((dates, is_regular))
+    }
+
+    /// Adjust the dates to business days, keeping month-end dates in
+    /// their month under the end-of-month rule.
+    fn adjust(&self, dates: &[Date]) -> Vec<Date> {
+        let last = dates.len() - 1;
+
+        dates
+            .iter()
+            .enumerate()
+            .map(|(i, &date)| {
+                let convention = if i == last {
+                    &self.termination_date_convention
+                } else {
+                    &self.date_rolling_convention
+                };
+
+                if self.end_of_month
+                    && *convention != DateRollingConvention::Actual
+                    && is_last_day_of_month(date)
+                {
+                    previous_business_day(date, &self.calendar)
+                } else {
+                    self.calendar.roll_date(date, convention)
+                }
+            })
+            .collect()
+    }
+
+    /// Check if the dates are exactly one tenor apart.
+    // Payment frequency matching the tenor, if any.
+    fn frequency(&self) -> Option<Frequency> {
+        [
+            Frequency::Weekly,
+            Frequency::BiWeekly,
+            Frequency::Monthly,
+            Frequency::Quarterly,
+            Frequency::TriAnnually,
+            Frequency::SemiAnnually,
+            Frequency::Annually,
+        ]
+        .into_iter()
+        .find(|&frequency| {
+            Tenor::from_frequency(frequency).map(|tenor| tenor.normalized())
+                == Some(self.tenor.normalized())
+        })
+    }
+
+    // Act/Act ICMA factors, using each regular period as its own reference
+    // period and the notional regular period for the stubs.
+    fn icma_factors(
+        &self,
+        dates: &[Date],
+        unadjusted_dates: &[Date],
+        is_regular: &[bool],
+        frequency: Frequency,
+    ) -> Vec<f64> {
+        let last = is_regular.len() - 1;
+
+        (0..=last)
+            .map(|i| {
+                let (start, end) = (dates[i], dates[i + 1]);
+
+                let reference = match (is_regular[i], i) {
+                    (true, _) => (start, end),
+                    (false, 0) => (
+                        (-self.tenor).add_to(unadjusted_dates[1], self.end_of_month),
+                        end,
+                    ),
+                    (false, _) => (
+                        start,
+                        self.tenor.add_to(unadjusted_dates[i], self.end_of_month),
+                    ),
+                };
+
+                self.calendar.day_count_factor_with_reference(
+                    start,
+                    end,
+                    reference.0,
+                    reference.1,
+                    frequency,
+                    &self.day_counting_convention,
+                )
+            })
+            .collect()
+    }
+
+    fn is_whole_period(&self, start: Date, end: Date) -> bool {
+        self.tenor.add_to(start.min(end), self.end_of_month) == start.max(end)
+    }
+}
+
+/// Last day of the month of the date.
+fn month_end(date: Date) -> Date {
+    date.replace_day(date.month().length(date.year())).unwrap()
+}
+
 // impl Schedule {
 //     /// Create a new schedule from a vector of dates.
 //     ///
@@ -198,6 +708,302 @@ impl fmt::Display for Schedule {
 //     }
 // }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_schedule_generator {
+    use super::*;
+    use crate::countries::{TargetCalendar, TurkeyCalendar, UnitedStatesCalendar};
+    use time::macros::date;
+
+    fn generator(start: Date, end: Date, tenor: &str) -> ScheduleGenerator<TargetCalendar> {
+        ScheduleGenerator::new(start, end, tenor.parse().unwrap(), TargetCalendar)
+    }
+
+    #[test]
+    fn test_regular_backward() {
+        let schedule = generator(date!(2024 - 03 - 15), date!(2025 - 03 - 15), "3M")
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![
+                date!(2024 - 03 - 15),
+                date!(2024 - 06 - 15),
+                date!(2024 - 09 - 15),
+                date!(2024 - 12 - 15),
+                date!(2025 - 03 - 15),
+            ]
+        );
+        assert_eq!(
+            schedule.dates,
+            vec![
+                date!(2024 - 03 - 15),
+                date!(2024 - 06 - 17),
+                date!(2024 - 09 - 16),
+                date!(2024 - 12 - 16),
+                date!(2025 - 03 - 17),
+            ]
+        );
+        assert_eq!(schedule.is_regular, vec![true; 4]);
+        assert_eq!(schedule.day_count_factors.len(), 4);
+        assert_eq!(
+            schedule.periods()[0],
+            (date!(2024 - 03 - 15), date!(2024 - 06 - 17))
+        );
+    }
+
+    #[test]
+    fn test_short_and_long_stubs() {
+        let (start, end) = (date!(2024 - 01 - 10), date!(2025 - 03 - 15));
+
+        // Backward: short front stub.
+        let schedule = generator(start, end, "6M").generate().unwrap();
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![start, date!(2024 - 03 - 15), date!(2024 - 09 - 15), end]
+        );
+        assert_eq!(schedule.is_regular, vec![false, true, true]);
+
+        // Backward: long front stub.
+        let schedule = generator(start, end, "6M")
+            .with_stub_generation(StubGeneration::LongFront)
+            .generate()
+            .unwrap();
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![start, date!(2024 - 09 - 15), end]
+        );
+        assert_eq!(schedule.is_regular, vec![false, true]);
+
+        // Forward: short back stub.
+        let schedule = generator(start, end, "6M")
+            .with_date_generation_convention(DateGenerationConvention::Forward)
+            .generate()
+            .unwrap();
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![start, date!(2024 - 07 - 10), date!(2025 - 01 - 10), end]
+        );
+        assert_eq!(schedule.is_regular, vec![true, true, false]);
+
+        // Forward: long back stub.
+        let schedule = generator(start, end, "6M")
+            .with_date_generation_convention(DateGenerationConvention::Forward)
+            .with_stub_generation(StubGeneration::LongBack)
+            .generate()
+            .unwrap();
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![start, date!(2024 - 07 - 10), end]
+        );
+        assert_eq!(schedule.is_regular, vec![true, false]);
+    }
+
+    #[test]
+    fn test_explicit_front_and_back_stubs() {
+        let schedule = generator(date!(2024 - 01 - 10), date!(2025 - 03 - 15), "6M")
+            .with_stub_generation(StubGeneration::Both)
+            .with_first_date(date!(2024 - 02 - 15))
+            .with_next_to_last_date(date!(2025 - 02 - 15))
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![
+                date!(2024 - 01 - 10),
+                date!(2024 - 02 - 15),
+                date!(2024 - 08 - 15),
+                date!(2025 - 02 - 15),
+                date!(2025 - 03 - 15),
+            ]
+        );
+        assert_eq!(schedule.is_regular, vec![false, true, true, false]);
+    }
+
+    #[test]
+    fn test_stub_errors() {
+        let irregular = generator(date!(2024 - 01 - 10), date!(2025 - 03 - 15), "6M");
+
+        assert!(irregular
+            .with_stub_generation(StubGeneration::None)
+            .generate()
+            .is_err());
+        assert!(
+            generator(date!(2024 - 01 - 10), date!(2025 - 03 - 15), "6M")
+                .with_stub_generation(StubGeneration::ShortBack)
+                .generate()
+                .is_err()
+        );
+        assert!(
+            generator(date!(2025 - 01 - 10), date!(2024 - 03 - 15), "6M")
+                .generate()
+                .is_err()
+        );
+        assert!(
+            generator(date!(2024 - 01 - 10), date!(2025 - 01 - 10), "6M")
+                .with_stub_generation(StubGeneration::None)
+                .generate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_end_of_month() {
+        // 2024-02-29 is the last business day of February.
+        let schedule = ScheduleGenerator::new(
+            date!(2024 - 02 - 29),
+            date!(2024 - 08 - 31),
+            Tenor::months(1),
+            UnitedStatesCalendar,
+        )
+        .with_date_generation_convention(DateGenerationConvention::Forward)
+        .with_date_rolling_convention(DateRollingConvention::Following)
+        .with_end_of_month(true)
+        .generate()
+        .unwrap();
+
+        assert_eq!(
+            schedule.dates,
+            vec![
+                date!(2024 - 02 - 29),
+                date!(2024 - 03 - 29),
+                date!(2024 - 04 - 30),
+                date!(2024 - 05 - 31),
+                date!(2024 - 06 - 28),
+                date!(2024 - 07 - 31),
+                date!(2024 - 08 - 30),
+            ]
+        );
+        assert_eq!(schedule.is_regular, vec![true; 6]);
+    }
+
+    #[test]
+    fn test_imm_and_third_wednesday() {
+        let schedule = generator(date!(2024 - 01 - 10), date!(2024 - 12 - 18), "3M")
+            .with_date_generation_convention(DateGenerationConvention::IMM)
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![
+                date!(2024 - 01 - 10),
+                date!(2024 - 03 - 20),
+                date!(2024 - 06 - 19),
+                date!(2024 - 09 - 18),
+                date!(2024 - 12 - 18),
+            ]
+        );
+        assert_eq!(schedule.is_regular, vec![false, true, true, true]);
+
+        let schedule = generator(date!(2024 - 01 - 10), date!(2024 - 07 - 10), "2M")
+            .with_date_generation_convention(DateGenerationConvention::ThirdWednesday)
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            schedule.unadjusted_dates,
+            vec![
+                date!(2024 - 01 - 10),
+                date!(2024 - 03 - 20),
+                date!(2024 - 05 - 15),
+                date!(2024 - 07 - 10),
+            ]
+        );
+
+        assert!(
+            generator(date!(2024 - 01 - 10), date!(2024 - 12 - 18), "2M")
+                .with_date_generation_convention(DateGenerationConvention::IMM)
+                .generate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_actual_actual_icma() {
+        let schedule = generator(date!(2024 - 01 - 10), date!(2025 - 03 - 15), "6M")
+            .with_day_counting_convention(DayCountConvention::Actual_Actual_ICMA)
+            .generate()
+            .unwrap();
+
+        // Short front stub of 65 days (2024-01-10 to 2024-03-15) in the
+        // notional period from 2023-09-15, then regular semi-annual periods
+        // between the adjusted dates.
+        let expected = [65.0 / (182.0 * 2.0), 0.5, 0.5];
+
+        for (factor, expected) in schedule.day_count_factors.iter().zip(expected) {
+            assert!((factor - expected).abs() < 1e-12, "{factor} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_zero() {
+        let schedule = generator(date!(2024 - 01 - 10), date!(2029 - 01 - 10), "1Y")
+            .with_date_generation_convention(DateGenerationConvention::Zero)
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            schedule.dates,
+            vec![date!(2024 - 01 - 10), date!(2029 - 01 - 10)]
+        );
+        assert_eq!(schedule.is_regular, vec![true]);
+    }
+
+    #[test]
+    fn test_unsupported_calendar_years() {
+        let generator = |end| {
+            ScheduleGenerator::new(
+                date!(2024 - 01 - 10),
+                end,
+                "6M".parse().unwrap(),
+                TurkeyCalendar,
+            )
+        };
+
+        // The Eid holidays are only tabulated to 2030.
+        assert!(generator(date!(2030 - 01 - 10)).generate().is_ok());
+        assert!(matches!(
+            generator(date!(2031 - 01 - 10)).generate(),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_schedule_serde {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_schedule_json_round_trip() {
+        let schedule = Schedule {
+            dates: vec![date!(2024 - 06 - 03), date!(2024 - 12 - 02)],
+            unadjusted_dates: vec![date!(2024 - 06 - 01), date!(2024 - 12 - 01)],
+            is_regular: vec![true],
+            day_count_factors: vec![0.5, 0.4972],
+            day_counting_convention: DayCountConvention::Actual_365_Fixed,
+            date_rolling_convention: DateRollingConvention::ModifiedFollowing,
+        };
+
+        let json = serde_json::to_string(&schedule).unwrap();
+        assert!(json.contains("\"2024-06-03\""));
+
+        let restored: Schedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.dates, schedule.dates);
+        assert_eq!(restored.day_count_factors, schedule.day_count_factors);
+        assert_eq!(
+            restored.date_rolling_convention,
+            DateRollingConvention::ModifiedFollowing
+        );
+    }
+}
+
 // // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // // UNIT TESTS
 // // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -0,0 +1,235 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Serial day numbers for date arithmetic in hot loops.
+//!
+//! A [SerialDate] is the number of days since 1899-12-30, the same
+//! convention as spreadsheet date serials. Differences are integer
+//! subtractions, and the calendar fields are recovered with a few integer
+//! operations, so large schedules can be converted once and then processed
+//! without going through `time::Date`.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use std::fmt;
+use std::ops::{Add, Sub};
+use time::{util::is_leap_year, Date, Weekday};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Date stored as a serial day number (days since 1899-12-30).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SerialDate(pub i32);
+
+/// Julian day number of the serial epoch, 1899-12-30.
+const JULIAN_DAY_OF_EPOCH: i32 = 2_415_019;
+
+/// Serial number of 1970-01-01, the epoch of the civil date algorithms below.
+const UNIX_EPOCH_SERIAL: i32 = 25_569;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SerialDate {
+    /// Create a serial date from a (year, month, day) triple.
+    ///
+    /// The triple is not validated (e.g. day 31 of a 30 day month
+    /// gives the first day of the next month).
+    pub fn from_ymd(year: i32, month: u8, day: u8) -> Self {
+        Self(days_from_civil(year, month, day) + UNIX_EPOCH_SERIAL)
+    }
+
+    /// Convert to a `time::Date`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the serial is outside the range supported by `time::Date`.
+    pub fn to_date(self) -> Date {
+        Date::from_julian_day(self.0 + JULIAN_DAY_OF_EPOCH).expect("Serial date out of range.")
+    }
+
+    /// Year, month (1-12) and day of the month.
+    pub fn year_month_day(self) -> (i32, u8, u8) {
+        civil_from_days(self.0 - UNIX_EPOCH_SERIAL)
+    }
+
+    /// Day of the week.
+    pub fn weekday(self) -> Weekday {
+        // The epoch (1899-12-30) was a Saturday.
+        match (self.0 + 5).rem_euclid(7) {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    /// Check if the date is a Saturday or Sunday.
+    pub fn is_weekend(self) -> bool {
+        (self.0 + 5).rem_euclid(7) >= 5
+    }
+
+    /// Check if the date is the last day of February.
+    pub fn is_last_day_of_february(self) -> bool {
+        let (year, month, day) = self.year_month_day();
+
+        month == 2 && day == 28 + is_leap_year(year) as u8
+    }
+
+    /// Convert a slice of dates to serial dates.
+    pub fn from_dates(dates: &[Date]) -> Vec<Self> {
+        dates.iter().map(|&date| Self::from(date)).collect()
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+/// See: <https://howardhinnant.github.io/date_algorithms.html>
+pub(crate) fn days_from_civil(year: i32, month: u8, day: u8) -> i32 {
+    let (month, day) = (month as i32, day as i32);
+    let year = if month <= 2 { year - 1 } else { year };
+
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a number of days since 1970-01-01.
+/// See: <https://howardhinnant.github.io/date_algorithms.html>
+pub(crate) fn civil_from_days(days: i32) -> (i32, u8, u8) {
+    let days = days + 719_468;
+
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i32;
+
+    (year, month as u8, day as u8)
+}
+
+impl From<Date> for SerialDate {
+    fn from(date: Date) -> Self {
+        Self(date.to_julian_day() - JULIAN_DAY_OF_EPOCH)
+    }
+}
+
+impl From<SerialDate> for Date {
+    fn from(serial: SerialDate) -> Self {
+        serial.to_date()
+    }
+}
+
+impl Add<i32> for SerialDate {
+    type Output = Self;
+
+    fn add(self, days: i32) -> Self {
+        Self(self.0 + days)
+    }
+}
+
+impl Sub<i32> for SerialDate {
+    type Output = Self;
+
+    fn sub(self, days: i32) -> Self {
+        Self(self.0 - days)
+    }
+}
+
+impl Sub for SerialDate {
+    type Output = i32;
+
+    /// Number of days between two serial dates.
+    fn sub(self, other: Self) -> i32 {
+        self.0 - other.0
+    }
+}
+
+impl fmt::Display for SerialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.year_month_day();
+
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_serial_date {
+    use super::*;
+    use crate::utilities::is_last_day_of_february;
+    use time::{macros::date, Duration};
+
+    #[test]
+    fn test_serial_numbers() {
+        assert_eq!(SerialDate::from(date!(1899 - 12 - 30)), SerialDate(0));
+        assert_eq!(SerialDate::from(date!(1900 - 03 - 01)), SerialDate(61));
+        assert_eq!(SerialDate::from(date!(2024 - 01 - 01)), SerialDate(45292));
+        assert_eq!(SerialDate::from_ymd(2024, 1, 1), SerialDate(45292));
+        assert_eq!(SerialDate(45292).to_string(), "2024-01-01");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut date = date!(1850 - 01 - 01);
+
+        while date < date!(2150 - 01 - 01) {
+            let serial = SerialDate::from(date);
+
+            assert_eq!(serial.to_date(), date);
+            assert_eq!(
+                serial.year_month_day(),
+                (date.year(), date.month() as u8, date.day())
+            );
+            assert_eq!(serial.weekday(), date.weekday());
+            assert_eq!(
+                serial.is_last_day_of_february(),
+                is_last_day_of_february(date)
+            );
+
+            date += Duration::days(1);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let start = SerialDate::from(date!(2024 - 02 - 28));
+        let end = SerialDate::from(date!(2025 - 02 - 28));
+
+        assert_eq!(end - start, 366);
+        assert_eq!((start + 2).to_date(), date!(2024 - 03 - 01));
+        assert!(SerialDate::from(date!(2024 - 03 - 02)).is_weekend());
+        assert_eq!(
+            SerialDate::from_dates(&[date!(2024 - 01 - 01), date!(2024 - 01 - 02)]),
+            vec![SerialDate(45292), SerialDate(45293)]
+        );
+    }
+}
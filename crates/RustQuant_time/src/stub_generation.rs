@@ -8,8 +8,10 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Stub generation rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StubGeneration {
-    /// No stubs.
+    /// No stubs: every period must be a whole tenor.
     None,
 
     /// Short stub at the beginning.
@@ -18,12 +20,15 @@ pub enum StubGeneration {
     /// Short stub at the end.
     ShortBack,
 
-    /// Long stub at the beginning.
+    /// Long stub at the beginning: a short front stub is merged into
+    /// the first regular period.
     LongFront,
 
-    /// Long stub at the end.
+    /// Long stub at the end: a short back stub is merged into
+    /// the last regular period.
     LongBack,
 
-    /// Front and back stubs.
+    /// Front and back stubs, given by an explicit first regular date
+    /// and next-to-last date.
     Both,
 }
@@ -0,0 +1,428 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Tenors (periods such as "3M" or "10Y") and period arithmetic on dates.
+//!
+//! Day and week tenors add calendar days. Month and year tenors move the
+//! month and keep the day, clamped to the length of the target month
+//! (31 Jan + 1M = 29 Feb 2024). With the end-of-month rule, a date on the
+//! last day of its month maps to the last day of the target month
+//! (29 Feb 2024 + 1M = 31 Mar 2024).
+//!
+//! ```
+//! use RustQuant_time::{Tenor, TenorUnit};
+//! use time::macros::date;
+//!
+//! let tenor: Tenor = "3M".parse().unwrap();
+//!
+//! assert_eq!(tenor, Tenor::new(3, TenorUnit::Months));
+//! assert_eq!(date!(2024 - 11 - 30) + tenor, date!(2025 - 02 - 28));
+//! assert_eq!(tenor.to_string(), "3M");
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::date_rolling::{DateRoller, DateRollingConvention};
+use crate::frequency::Frequency;
+use crate::utilities::{
+    is_last_business_day_of_month, is_last_day_of_month, previous_business_day,
+};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+use time::{Date, Duration, Month};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Unit of a tenor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TenorUnit {
+    /// Calendar days ("D").
+    Days,
+
+    /// Weeks ("W").
+    Weeks,
+
+    /// Months ("M").
+    Months,
+
+    /// Years ("Y").
+    Years,
+}
+
+/// A period of time, such as "1D", "3M" or "10Y".
+///
+/// The length may be negative to move backwards in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tenor {
+    /// Number of units.
+    pub length: i32,
+
+    /// Unit of the tenor.
+    pub unit: TenorUnit,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TenorUnit {
+    /// Single-letter code of the unit.
+    pub fn code(&self) -> char {
+        match self {
+            Self::Days => 'D',
+            Self::Weeks => 'W',
+            Self::Months => 'M',
+            Self::Years => 'Y',
+        }
+    }
+}
+
+impl Tenor {
+    /// Create a new tenor.
+    pub fn new(length: i32, unit: TenorUnit) -> Self {
+        Self { length, unit }
+    }
+
+    /// A tenor of `n` days.
+    pub fn days(n: i32) -> Self {
+        Self::new(n, TenorUnit::Days)
+    }
+
+    /// A tenor of `n` weeks.
+    pub fn weeks(n: i32) -> Self {
+        Self::new(n, TenorUnit::Weeks)
+    }
+
+    /// A tenor of `n` months.
+    pub fn months(n: i32) -> Self {
+        Self::new(n, TenorUnit::Months)
+    }
+
+    /// A tenor of `n` years.
+    pub fn years(n: i32) -> Self {
+        Self::new(n, TenorUnit::Years)
+    }
+
+    /// Tenor of one period of a payment frequency, e.g. "3M" for quarterly.
+    ///
+    /// Returns `None` for `Daily` (which counts business days) and `Zero`.
+    pub fn from_frequency(frequency: Frequency) -> Option<Self> {
+        match frequency {
+            Frequency::Weekly => Some(Self::weeks(1)),
+            Frequency::BiWeekly => Some(Self::weeks(2)),
+            Frequency::SemiMonthly => Some(Self::days(15)),
+            Frequency::Monthly => Some(Self::months(1)),
+            Frequency::SemiQuarterly => Some(Self::weeks(6)),
+            Frequency::Quarterly => Some(Self::months(3)),
+            Frequency::TriAnnually => Some(Self::months(4)),
+            Frequency::SemiAnnually => Some(Self::months(6)),
+            Frequency::Annually => Some(Self::years(1)),
+            Frequency::Daily | Frequency::Zero => None,
+        }
+    }
+
+    /// Check if the tenor has zero length.
+    pub fn is_zero(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Express the tenor in the largest exact unit,
+    /// e.g. "12M" becomes "1Y" and "14D" becomes "2W".
+    pub fn normalized(&self) -> Self {
+        match self.unit {
+            TenorUnit::Days if self.length % 7 == 0 => Self::weeks(self.length / 7),
+            TenorUnit::Months if self.length % 12 == 0 => Self::years(self.length / 12),
+            _ => *self,
+        }
+    }
+
+    /// Approximate length of the tenor in years
+    /// (365 days or 52 weeks per year).
+    pub fn years_fraction(&self) -> f64 {
+        let length = self.length as f64;
+
+        match self.unit {
+            TenorUnit::Days => length / 365.0,
+            TenorUnit::Weeks => length / 52.0,
+            TenorUnit::Months => length / 12.0,
+            TenorUnit::Years => length,
+        }
+    }
+
+    /// Add the tenor to a date, without business day adjustment.
+    ///
+    /// With `end_of_month`, month and year tenors map the last day of a
+    /// month to the last day of the target month.
+    pub fn add_to(&self, date: Date, end_of_month: bool) -> Date {
+        let length = self.length as i64;
+
+        match self.unit {
+            TenorUnit::Days => date + Duration::days(length),
+            TenorUnit::Weeks => date + Duration::weeks(length),
+            TenorUnit::Months => add_months(date, self.length, end_of_month),
+            TenorUnit::Years => add_months(date, 12 * self.length, end_of_month),
+        }
+    }
+
+    /// Add the tenor to a date and roll the result to a business day.
+    ///
+    /// With `end_of_month`, month and year tenors map the last business day
+    /// of a month to the last business day of the target month.
+    pub fn advance<C: Calendar>(
+        &self,
+        date: Date,
+        calendar: &C,
+        convention: &DateRollingConvention,
+        end_of_month: bool,
+    ) -> Date {
+        let monthly = matches!(self.unit, TenorUnit::Months | TenorUnit::Years);
+
+        if end_of_month && monthly && is_last_business_day_of_month(date, calendar) {
+            let target = self.add_to(date, false);
+            let month_end = Date::from_calendar_date(
+                target.year(),
+                target.month(),
+                target.month().length(target.year()),
+            );
+
+            return previous_business_day(month_end.unwrap(), calendar);
+        }
+
+        calendar.roll_date(self.add_to(date, false), convention)
+    }
+}
+
+impl FromStr for Tenor {
+    type Err = RustQuantError;
+
+    /// Parse a tenor such as "1D", "2W", "3M", "10Y" or "-6M"
+    /// (case insensitive). "ON" is parsed as one day.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RustQuantError::InvalidArgument(format!("Invalid tenor: '{}'.", s));
+
+        let text = s.trim().to_ascii_uppercase();
+
+        if text == "ON" || text == "O/N" {
+            return Ok(Self::days(1));
+        }
+
+        let code = text.chars().last().ok_or_else(invalid)?;
+
+        let unit = match code {
+            'D' => TenorUnit::Days,
+            'W' => TenorUnit::Weeks,
+            'M' => TenorUnit::Months,
+            'Y' => TenorUnit::Years,
+            _ => return Err(invalid()),
+        };
+
+        let length = text[..text.len() - 1]
+            .parse::<i32>()
+            .map_err(|_| invalid())?;
+
+        Ok(Self::new(length, unit))
+    }
+}
+
+impl fmt::Display for Tenor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.length, self.unit.code())
+    }
+}
+
+impl Neg for Tenor {
+    type Output = Tenor;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.length, self.unit)
+    }
+}
+
+impl Mul<i32> for Tenor {
+    type Output = Tenor;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::new(self.length * rhs, self.unit)
+    }
+}
+
+impl Add<Tenor> for Date {
+    type Output = Date;
+
+    fn add(self, rhs: Tenor) -> Self::Output {
+        rhs.add_to(self, false)
+    }
+}
+
+impl Sub<Tenor> for Date {
+    type Output = Date;
+
+    fn sub(self, rhs: Tenor) -> Self::Output {
+        (-rhs).add_to(self, false)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Add (or subtract) a number of months, clamping to the end of the month.
+fn add_months(date: Date, months: i32, end_of_month: bool) -> Date {
+    let total = date.year() * 12 + (date.month() as i32 - 1) + months;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u8 + 1);
+    let month = Month::try_from(month).unwrap();
+
+    let day = if end_of_month && is_last_day_of_month(date) {
+        month.length(year)
+    } else {
+        date.day().min(month.length(year))
+    };
+
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_tenor {
+    use super::*;
+    use crate::countries::UnitedKingdomCalendar;
+    use time::macros::date;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1D".parse::<Tenor>().unwrap(), Tenor::days(1));
+        assert_eq!("2w".parse::<Tenor>().unwrap(), Tenor::weeks(2));
+        assert_eq!(" 3M ".parse::<Tenor>().unwrap(), Tenor::months(3));
+        assert_eq!("10Y".parse::<Tenor>().unwrap(), Tenor::years(10));
+        assert_eq!("-6M".parse::<Tenor>().unwrap(), Tenor::months(-6));
+        assert_eq!("ON".parse::<Tenor>().unwrap(), Tenor::days(1));
+
+        for text in ["", "M", "3", "3Q", "1.5Y", "Y3"] {
+            assert!(text.parse::<Tenor>().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for text in ["1D", "2W", "3M", "10Y", "-6M"] {
+            assert_eq!(text.parse::<Tenor>().unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(Tenor::months(24).normalized(), Tenor::years(2));
+        assert_eq!(Tenor::days(14).normalized(), Tenor::weeks(2));
+        assert_eq!(Tenor::months(18).normalized(), Tenor::months(18));
+    }
+
+    #[test]
+    fn test_date_arithmetic() {
+        assert_eq!(
+            date!(2024 - 01 - 31) + Tenor::days(1),
+            date!(2024 - 02 - 01)
+        );
+        assert_eq!(
+            date!(2024 - 01 - 31) + Tenor::weeks(2),
+            date!(2024 - 02 - 14)
+        );
+        assert_eq!(
+            date!(2024 - 01 - 31) + Tenor::months(1),
+            date!(2024 - 02 - 29)
+        );
+        assert_eq!(
+            date!(2024 - 02 - 29) + Tenor::years(1),
+            date!(2025 - 02 - 28)
+        );
+        assert_eq!(
+            date!(2024 - 03 - 31) - Tenor::months(1),
+            date!(2024 - 02 - 29)
+        );
+        assert_eq!(
+            date!(2024 - 01 - 15) + Tenor::months(3) * 4,
+            date!(2025 - 01 - 15)
+        );
+    }
+
+    #[test]
+    fn test_end_of_month() {
+        let tenor = Tenor::months(1);
+
+        assert_eq!(
+            tenor.add_to(date!(2024 - 02 - 29), false),
+            date!(2024 - 03 - 29)
+        );
+        assert_eq!(
+            tenor.add_to(date!(2024 - 02 - 29), true),
+            date!(2024 - 03 - 31)
+        );
+        assert_eq!(
+            tenor.add_to(date!(2024 - 02 - 28), true),
+            date!(2024 - 03 - 28)
+        );
+    }
+
+    #[test]
+    fn test_advance() {
+        let calendar = UnitedKingdomCalendar;
+
+        // 2024-06-30 is a Sunday: Following moves into July, Modified Following stays in June.
+        let start = date!(2024 - 03 - 30);
+        let tenor = Tenor::months(3);
+        assert_eq!(
+            tenor.advance(start, &calendar, &DateRollingConvention::Following, false),
+            date!(2024 - 07 - 01)
+        );
+        assert_eq!(
+            tenor.advance(
+                start,
+                &calendar,
+                &DateRollingConvention::ModifiedFollowing,
+                false
+            ),
+            date!(2024 - 06 - 28)
+        );
+
+        // Last business day of April to the last business day of May.
+        assert_eq!(
+            Tenor::months(1).advance(
+                date!(2024 - 04 - 30),
+                &calendar,
+                &DateRollingConvention::ModifiedFollowing,
+                true
+            ),
+            date!(2024 - 05 - 31)
+        );
+    }
+
+    #[test]
+    fn test_from_frequency() {
+        assert_eq!(
+            Tenor::from_frequency(Frequency::Quarterly),
+            Some(Tenor::months(3))
+        );
+        assert_eq!(
+            Tenor::from_frequency(Frequency::Annually),
+            Some(Tenor::years(1))
+        );
+        assert_eq!(Tenor::from_frequency(Frequency::Zero), None);
+    }
+}
@@ -165,6 +165,20 @@ pub fn next_business_day<C: Calendar>(date: Date, calendar: &C) -> Date {
     new_date
 }
 
+/// Function to check if no business day follows the date in its month.
+pub fn is_last_business_day_of_month<C: Calendar>(date: Date, calendar: &C) -> bool {
+    let mut next = date.next_day().unwrap();
+
+    while next.month() == date.month() {
+        if calendar.is_business_day(next) {
+            return false;
+        }
+        next = next.next_day().unwrap();
+    }
+
+    true
+}
+
 /// Function to get the previous business day for a given date and calendar.
 pub fn previous_business_day<C: Calendar>(date: Date, calendar: &C) -> Date {
     let mut new_date = date;
@@ -176,6 +190,23 @@ pub fn previous_business_day<C: Calendar>(date: Date, calendar: &C) -> Date {
     new_date
 }
 
+/// Check if a date falls in a holiday period of `length` days, starting
+/// `offset` days from the date a table gives for the date's year.
+///
+/// Used for holidays following a lunar calendar, whose dates are tabulated.
+pub(crate) fn is_in_holiday_period(
+    date: Date,
+    table: &[(i32, Month, u8)],
+    offset: i64,
+    length: i64,
+) -> bool {
+    table
+        .iter()
+        .filter(|&&(year, _, _)| year == date.year())
+        .filter_map(|&(year, month, day)| Date::from_calendar_date(year, month, day).ok())
+        .any(|start| (0..length).contains(&((date - start).whole_days() - offset)))
+}
+
 /// Function to generate a sequence of dates from a start date, end date.
 pub fn date_sequence(start: Date, end: Date) -> Vec<Date> {
     let mut dates = Vec::with_capacity((end - start).whole_days() as usize);
@@ -233,6 +264,11 @@ pub fn get_first_wednesday_of_month(year: i32, month: Month) -> Result<Date, Err
     }
 }
 
+/// Function to get the date of the third Wednesday of the month.
+pub fn get_third_wednesday_of_month(year: i32, month: Month) -> Result<Date, Error> {
+    Ok(get_first_wednesday_of_month(year, month)? + Duration::weeks(2))
+}
+
 /// Function to get the date of the first Thursday of the month.
 pub fn get_first_thursday_of_month(year: i32, month: Month) -> Result<Date, Error> {
     let first_day_date = Date::from_calendar_date(year, month, 1)?;
@@ -391,6 +427,10 @@ mod test_utilities {
             get_first_sunday_of_month(y, m).unwrap(),
             Date::from_calendar_date(y, m, 7).unwrap()
         );
+        assert_eq!(
+            get_third_wednesday_of_month(y, m).unwrap(),
+            Date::from_calendar_date(y, m, 17).unwrap()
+        );
     }
 
     #[test]
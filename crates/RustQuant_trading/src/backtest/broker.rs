@@ -0,0 +1,883 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::MarketEvent;
+use crate::order::OrderID;
+use crate::order_side::OrderSide;
+use crate::order_type::OrderType;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Order emitted by a strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    /// Symbol to trade.
+    pub symbol: String,
+
+    /// Side: `BID` to buy, `ASK` to sell.
+    pub side: OrderSide,
+
+    /// Number of units.
+    pub quantity: u64,
+
+    /// Order type.
+    pub order_type: OrderType,
+
+    /// Limit price, for limit, stop-limit, and iceberg orders (the current
+    /// price of pegged orders).
+    pub limit_price: Option<f64>,
+
+    /// Stop (trigger) price, for stop and stop-limit orders (the current
+    /// stop of trailing stop orders).
+    pub stop_price: Option<f64>,
+
+    /// Distance of the stop from the best price since submission, for
+    /// trailing stop orders.
+    pub trailing_amount: Option<f64>,
+
+    /// Quantity shown at a time, for iceberg orders.
+    pub display_quantity: Option<u64>,
+
+    /// Distance of the price from the last price, away from the market,
+    /// for pegged orders.
+    pub peg_offset: Option<f64>,
+}
+
+/// Execution of an order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fill {
+    /// Identifier of the order.
+    pub order_id: OrderID,
+
+    /// Symbol traded.
+    pub symbol: String,
+
+    /// Side of the order.
+    pub side: OrderSide,
+
+    /// Number of units traded.
+    pub quantity: u64,
+
+    /// Price per unit, including slippage.
+    pub price: f64,
+
+    /// Commission charged.
+    pub commission: f64,
+
+    /// Time of the execution.
+    pub timestamp: OffsetDateTime,
+}
+
+/// Slippage of market orders: how much worse than the quoted price they
+/// are filled, for the spread and the market impact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlippageModel {
+    /// No slippage.
+    None,
+
+    /// Fixed amount per unit.
+    Fixed(f64),
+
+    /// Fraction of the price (e.g. 0.0005 for 5bp).
+    Proportional(f64),
+}
+
+/// Commission of an order: $\max(m, c_0 + c_1 q + c_2 q p)$ for $q$ units
+/// at price $p$.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CommissionModel {
+    /// Commission per order, $c_0$.
+    pub per_order: f64,
+
+    /// Commission per unit, $c_1$.
+    pub per_unit: f64,
+
+    /// Commission per unit of value traded, $c_2$.
+    pub proportional: f64,
+
+    /// Minimum commission per order, $m$.
+    pub minimum: f64,
+}
+
+/// Position in a symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Holding {
+    /// Number of units: positive if long, negative if short.
+    pub quantity: i64,
+
+    /// Average price of the open units.
+    pub average_price: f64,
+
+    /// P&L realised by closing units.
+    pub realized_pnl: f64,
+}
+
+/// Cash, positions, and last prices of a trading account.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Account {
+    /// Cash (negative if borrowed).
+    pub cash: f64,
+
+    /// Positions, by symbol.
+    pub positions: HashMap<String, Holding>,
+
+    /// Last prices, by symbol.
+    pub prices: HashMap<String, f64>,
+
+    /// Number of orders not filled or cancelled yet.
+    pub open_orders: usize,
+}
+
+/// Broker that fills orders against market events, for backtesting.
+///
+/// Orders are filled by the first event of their symbol after they were
+/// submitted, in the order they were submitted:
+///
+/// - Market orders at the open of a bar (or the price of a tick), with
+///   slippage.
+/// - Limit orders when the price reaches the limit, at the limit or the
+///   open if better, without slippage. Until then, they stay open.
+/// - Stop orders when the price reaches the stop, at the stop or the open if
+///   worse, with slippage. Stop-limit orders then become limit orders, filled
+///   at the same price if it is within the limit.
+/// - Trailing stop orders like stop orders, with the stop moved after each
+///   event towards the best price (the high to sell, the low to buy).
+/// - Iceberg orders like limit orders, but at most one displayed quantity
+///   per event, the next one being shown from the following event.
+/// - Pegged orders like limit orders, re-priced after each event from the
+///   last price.
+///
+/// Fills are not limited by the volume of the events.
+#[derive(Debug, Clone)]
+pub struct SimulatedBroker {
+    account: Account,
+    slippage: SlippageModel,
+    commission: CommissionModel,
+    open_orders: Vec<(OrderID, OrderRequest)>,
+    fills: Vec<Fill>,
+    next_id: OrderID,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl OrderRequest {
+    fn new(symbol: &str, side: OrderSide, quantity: u64, order_type: OrderType) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            side,
+            quantity,
+            order_type,
+            limit_price: None,
+            stop_price: None,
+            trailing_amount: None,
+            display_quantity: None,
+            peg_offset: None,
+        }
+    }
+
+    /// Market order.
+    #[must_use]
+    pub fn market(symbol: &str, side: OrderSide, quantity: u64) -> Self {
+        Self::new(symbol, side, quantity, OrderType::Market)
+    }
+
+    /// Limit order.
+    #[must_use]
+    pub fn limit(symbol: &str, side: OrderSide, quantity: u64, limit_price: f64) -> Self {
+        Self {
+            limit_price: Some(limit_price),
+            ..Self::new(symbol, side, quantity, OrderType::Limit)
+        }
+    }
+
+    /// Stop order: a market order once the price reaches the stop.
+    #[must_use]
+    pub fn stop(symbol: &str, side: OrderSide, quantity: u64, stop_price: f64) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            ..Self::new(symbol, side, quantity, OrderType::Stop)
+        }
+    }
+
+    /// Stop-limit order: a limit order once the price reaches the stop.
+    #[must_use]
+    pub fn stop_limit(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        stop_price: f64,
+        limit_price: f64,
+    ) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            limit_price: Some(limit_price),
+            ..Self::new(symbol, side, quantity, OrderType::StopLimit)
+        }
+    }
+
+    /// Trailing stop order: a stop order whose stop follows the best price
+    /// since submission, at a fixed distance.
+    #[must_use]
+    pub fn trailing_stop(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        trailing_amount: f64,
+    ) -> Self {
+        Self {
+            trailing_amount: Some(trailing_amount),
+            ..Self::new(symbol, side, quantity, OrderType::TrailingStop)
+        }
+    }
+
+    /// Iceberg order: a limit order that shows (and fills) at most
+    /// `display_quantity` units at a time.
+    #[must_use]
+    pub fn iceberg(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        limit_price: f64,
+        display_quantity: u64,
+    ) -> Self {
+        Self {
+            limit_price: Some(limit_price),
+            display_quantity: Some(display_quantity),
+            ..Self::new(symbol, side, quantity, OrderType::Iceberg)
+        }
+    }
+
+    /// Pegged order: a limit order re-priced after each event at the last
+    /// price, `peg_offset` below it to buy, or above it to sell.
+    #[must_use]
+    pub fn pegged(symbol: &str, side: OrderSide, quantity: u64, peg_offset: f64) -> Self {
+        Self {
+            peg_offset: Some(peg_offset),
+            ..Self::new(symbol, side, quantity, OrderType::Pegged)
+        }
+    }
+}
+
+impl Fill {
+    /// Signed number of units: positive for a purchase.
+    #[must_use]
+    pub fn signed_quantity(&self) -> i64 {
+        match self.side {
+            OrderSide::BID => self.quantity as i64,
+            OrderSide::ASK => -(self.quantity as i64),
+        }
+    }
+}
+
+impl SlippageModel {
+    /// Price paid (or received) for a market order at the quoted price.
+    #[must_use]
+    pub fn apply(&self, price: f64, side: OrderSide) -> f64 {
+        let slippage = match self {
+            SlippageModel::None => 0.0,
+            SlippageModel::Fixed(amount) => *amount,
+            SlippageModel::Proportional(fraction) => fraction * price,
+        };
+
+        match side {
+            OrderSide::BID => price + slippage,
+            OrderSide::ASK => price - slippage,
+        }
+    }
+}
+
+impl CommissionModel {
+    /// Commission of `quantity` units at `price`.
+    #[must_use]
+    pub fn commission(&self, quantity: u64, price: f64) -> f64 {
+        let quantity = quantity as f64;
+
+        (self.per_order + self.per_unit * quantity + self.proportional * quantity * price.abs())
+            .max(self.minimum)
+    }
+}
+
+impl Account {
+    /// Create an account with cash only.
+    #[must_use]
+    pub fn new(cash: f64) -> Self {
+        Self {
+            cash,
+            ..Default::default()
+        }
+    }
+
+    /// Number of units held of a symbol.
+    #[must_use]
+    pub fn position(&self, symbol: &str) -> i64 {
+        self.positions
+            .get(symbol)
+            .map_or(0, |holding| holding.quantity)
+    }
+
+    /// Value of the positions at the last prices (at the average prices for
+    /// the symbols without one).
+    #[must_use]
+    pub fn market_value(&self) -> f64 {
+        self.positions
+            .iter()
+            .map(|(symbol, holding)| {
+                let price = self
+                    .prices
+                    .get(symbol)
+                    .copied()
+                    .unwrap_or(holding.average_price);
+
+                holding.quantity as f64 * price
+            })
+            .sum()
+    }
+
+    /// Cash plus the value of the positions.
+    #[must_use]
+    pub fn equity(&self) -> f64 {
+        self.cash + self.market_value()
+    }
+
+    /// Realised P&L of all positions.
+    #[must_use]
+    pub fn realized_pnl(&self) -> f64 {
+        self.positions
+            .values()
+            .map(|holding| holding.realized_pnl)
+            .sum()
+    }
+
+    /// Update the cash and position for a fill.
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        let units = fill.signed_quantity();
+        let holding = self.positions.entry(fill.symbol.clone()).or_default();
+
+        if holding.quantity == 0 || holding.quantity.signum() == units.signum() {
+            // Opening or adding to the position.
+            let total = holding.quantity.unsigned_abs() + fill.quantity;
+
+            holding.average_price = (holding.average_price
+                * holding.quantity.unsigned_abs() as f64
+                + fill.price * fill.quantity as f64)
+                / total as f64;
+        } else {
+            // Reducing, closing, or reversing the position.
+            let closed = holding.quantity.unsigned_abs().min(fill.quantity);
+
+            holding.realized_pnl += closed as f64
+                * (fill.price - holding.average_price)
+                * holding.quantity.signum() as f64;
+
+            if fill.quantity > closed {
+                holding.average_price = fill.price;
+            }
+        }
+
+        holding.quantity += units;
+
+        if holding.quantity == 0 {
+            holding.average_price = 0.0;
+        }
+
+        self.cash -= units as f64 * fill.price + fill.commission;
+    }
+}
+
+impl SimulatedBroker {
+    /// Create a broker with an account of the given cash, without slippage
+    /// or commission.
+    #[must_use]
+    pub fn new(initial_cash: f64) -> Self {
+        Self {
+            account: Account::new(initial_cash),
+            slippage: SlippageModel::None,
+            commission: CommissionModel::default(),
+            open_orders: Vec::new(),
+            fills: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Set the slippage model.
+    #[must_use]
+    pub fn with_slippage(mut self, slippage: SlippageModel) -> Self {
+        self.slippage = slippage;
+        self
+    }
+
+    /// Set the commission model.
+    #[must_use]
+    pub fn with_commission(mut self, commission: CommissionModel) -> Self {
+        self.commission = commission;
+        self
+    }
+
+    /// State of the account.
+    #[must_use]
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Orders not filled or cancelled yet, in the order they were submitted.
+    #[must_use]
+    pub fn open_orders(&self) -> &[(OrderID, OrderRequest)] {
+        &self.open_orders
+    }
+
+    /// All fills so far.
+    #[must_use]
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    /// Submit an order, to be filled by the next events of its symbol.
+    ///
+    /// The stop of trailing stop orders and the price of pegged orders are
+    /// set from the last price of the symbol, or the first event if there
+    /// is none yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quantity is zero, the prices or amounts
+    /// required by the order type are missing or not positive (the peg
+    /// offset can be zero), or the order type is not supported.
+    pub fn submit(&mut self, mut order: OrderRequest) -> Result<OrderID, RustQuantError> {
+        if order.quantity == 0 {
+            return Err(RustQuantError::InvalidArgument(
+                "Order quantity must be positive.".to_string(),
+            ));
+        }
+
+        let positive = |value: Option<f64>| value.is_some_and(|value| value > 0.0);
+
+        let valid = match order.order_type {
+            OrderType::Market => true,
+            OrderType::Limit => positive(order.limit_price),
+            OrderType::Stop => positive(order.stop_price),
+            OrderType::StopLimit => positive(order.stop_price) && positive(order.limit_price),
+            OrderType::TrailingStop => positive(order.trailing_amount),
+            OrderType::Iceberg => {
+                positive(order.limit_price) && order.display_quantity.is_some_and(|q| q > 0)
+            }
+            OrderType::Pegged => order.peg_offset.is_some_and(|offset| offset >= 0.0),
+            OrderType::TrailingStopLimit => {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "{} orders are not supported by the simulated broker.",
+                    order.order_type
+                )));
+            }
+        };
+
+        if !valid {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Invalid {} order: {order:?}.",
+                order.order_type
+            )));
+        }
+
+        if let Some(&price) = self.account.prices.get(&order.symbol) {
+            Self::reprice(&mut order, price, price, price);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.open_orders.push((id, order));
+        self.account.open_orders = self.open_orders.len();
+
+        Ok(id)
+    }
+
+    /// Cancel an open order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no open order with this identifier.
+    pub fn cancel(&mut self, id: OrderID) -> Result<(), RustQuantError> {
+        let index = self
+            .open_orders
+            .iter()
+            .position(|(order_id, _)| *order_id == id)
+            .ok_or_else(|| RustQuantError::InvalidArgument(format!("No open order {id}.")))?;
+
+        self.open_orders.remove(index);
+        self.account.open_orders = self.open_orders.len();
+
+        Ok(())
+    }
+
+    /// Fill the open orders of the symbol of the event, and update its last
+    /// price. Returns the fills.
+    pub fn process(&mut self, event: &MarketEvent) -> Vec<Fill> {
+        let mut fills = Vec::new();
+        let mut remaining = Vec::with_capacity(self.open_orders.len());
+
+        for (id, mut order) in std::mem::take(&mut self.open_orders) {
+            if order.symbol != event.symbol() {
+                remaining.push((id, order));
+                continue;
+            }
+
+            if let Some((quantity, price)) = self.execute(&mut order, event) {
+                let fill = Fill {
+                    order_id: id,
+                    commission: self.commission.commission(quantity, price),
+                    symbol: order.symbol.clone(),
+                    side: order.side,
+                    quantity,
+                    price,
+                    timestamp: event.timestamp(),
+                };
+
+                self.account.apply_fill(&fill);
+                fills.push(fill);
+                order.quantity -= quantity;
+            }
+
+            if order.quantity > 0 {
+                Self::reprice(&mut order, event.high(), event.low(), event.price());
+                remaining.push((id, order));
+            }
+        }
+
+        self.open_orders = remaining;
+        self.account.open_orders = self.open_orders.len();
+        self.account
+            .prices
+            .insert(event.symbol().to_string(), event.price());
+        self.fills.extend(fills.iter().cloned());
+
+        fills
+    }
+
+    /// Quantity and price at which an order is filled by an event of its
+    /// symbol, if it is. Triggered stop-limit orders become limit orders.
+    fn execute(&self, order: &mut OrderRequest, event: &MarketEvent) -> Option<(u64, f64)> {
+        let quantity = order.quantity;
+
+        match order.order_type {
+            OrderType::Market => Some((quantity, self.slippage.apply(event.open(), order.side))),
+            OrderType::Stop | OrderType::TrailingStop => {
+                let price = Self::trigger_price(order, event)?;
+
+                Some((quantity, self.slippage.apply(price, order.side)))
+            }
+            OrderType::StopLimit => {
+                let price = Self::trigger_price(order, event)?;
+                let limit = order.limit_price?;
+                order.order_type = OrderType::Limit;
+
+                let within = match order.side {
+                    OrderSide::BID => price <= limit,
+                    OrderSide::ASK => price >= limit,
+                };
+
+                within.then_some((quantity, price))
+            }
+            OrderType::Limit | OrderType::Iceberg | OrderType::Pegged => {
+                let limit = order.limit_price?;
+                let price = match order.side {
+                    OrderSide::BID => (event.low() <= limit).then(|| event.open().min(limit)),
+                    OrderSide::ASK => (event.high() >= limit).then(|| event.open().max(limit)),
+                }?;
+
+                let quantity = match order.display_quantity {
+                    Some(display) if order.order_type == OrderType::Iceberg => {
+                        display.min(quantity)
+                    }
+                    _ => quantity,
+                };
+
+                Some((quantity, price))
+            }
+            OrderType::TrailingStopLimit => None,
+        }
+    }
+
+    /// Price at which a stop is triggered by an event, if it is: the stop,
+    /// or the open if the price gapped through it.
+    fn trigger_price(order: &OrderRequest, event: &MarketEvent) -> Option<f64> {
+        let stop = order.stop_price?;
+
+        match order.side {
+            OrderSide::BID => (event.high() >= stop).then(|| event.open().max(stop)),
+            OrderSide::ASK => (event.low() <= stop).then(|| event.open().min(stop)),
+        }
+    }
+
+    /// Move the stop of a trailing stop order, or the price of a pegged
+    /// order, after an event.
+    fn reprice(order: &mut OrderRequest, high: f64, low: f64, last: f64) {
+        match order.order_type {
+            OrderType::TrailingStop => {
+                let Some(trail) = order.trailing_amount else {
+                    return;
+                };
+
+                order.stop_price = Some(match (order.side, order.stop_price) {
+                    (OrderSide::ASK, Some(stop)) => stop.max(high - trail),
+                    (OrderSide::ASK, None) => high - trail,
+                    (OrderSide::BID, Some(stop)) => stop.min(low + trail),
+                    (OrderSide::BID, None) => low + trail,
+                });
+            }
+            OrderType::Pegged => {
+                let offset = order.peg_offset.unwrap_or(0.0);
+
+                order.limit_price = Some(match order.side {
+                    OrderSide::BID => last - offset,
+                    OrderSide::ASK => last + offset,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_broker {
+    use super::*;
+    use crate::backtest::Bar;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> MarketEvent {
+        MarketEvent::Bar(Bar::new(
+            "AAA",
+            OffsetDateTime::UNIX_EPOCH,
+            open,
+            high,
+            low,
+            close,
+            1000.0,
+        ))
+    }
+
+    #[test]
+    fn test_market_order_with_costs() {
+        let commission = CommissionModel {
+            per_order: 1.0,
+            per_unit: 0.01,
+            ..Default::default()
+        };
+        let mut broker = SimulatedBroker::new(10_000.0)
+            .with_slippage(SlippageModel::Proportional(0.001))
+            .with_commission(commission);
+
+        broker
+            .submit(OrderRequest::market("AAA", OrderSide::BID, 100))
+            .unwrap();
+
+        // Orders for other symbols are not filled.
+        let other = MarketEvent::Bar(Bar::new(
+            "BBB",
+            OffsetDateTime::UNIX_EPOCH,
+            1.0,
+            1.0,
+            1.0,
+            1.0,
+            1.0,
+        ));
+        assert!(broker.process(&other).is_empty());
+
+        let fills = broker.process(&bar(50.0, 52.0, 49.0, 51.0));
+
+        assert_eq!(fills.len(), 1);
+        assert_approx_equal!(fills[0].price, 50.05, 1e-12);
+        assert_approx_equal!(fills[0].commission, 2.0, 1e-12);
+        assert_approx_equal!(broker.account().cash, 10_000.0 - 5005.0 - 2.0, 1e-9);
+        assert_approx_equal!(broker.account().equity(), 4993.0 + 5100.0, 1e-9);
+        assert_eq!(broker.account().open_orders, 0);
+    }
+
+    #[test]
+    fn test_limit_orders() {
+        let mut broker = SimulatedBroker::new(10_000.0);
+
+        let id = broker
+            .submit(OrderRequest::limit("AAA", OrderSide::BID, 10, 48.0))
+            .unwrap();
+        broker
+            .submit(OrderRequest::limit("AAA", OrderSide::ASK, 10, 51.0))
+            .unwrap();
+
+        // Neither limit is reached.
+        assert!(broker.process(&bar(50.0, 50.5, 49.0, 50.0)).is_empty());
+
+        // The sell limit is reached, and filled at the limit.
+        let fills = broker.process(&bar(50.0, 52.0, 49.0, 51.0));
+        assert_eq!(fills.len(), 1);
+        assert_approx_equal!(fills[0].price, 51.0, 1e-12);
+
+        // The bar gaps below the buy limit, which is filled at the open.
+        let fills = broker.process(&bar(47.0, 47.5, 46.0, 47.0));
+        assert_eq!(fills[0].order_id, id);
+        assert_approx_equal!(fills[0].price, 47.0, 1e-12);
+        assert_eq!(broker.account().position("AAA"), 0);
+        assert_approx_equal!(broker.account().realized_pnl(), 40.0, 1e-12);
+    }
+
+    #[test]
+    fn test_position_tracking() {
+        let mut account = Account::new(0.0);
+        let fill = |side, quantity, price| Fill {
+            order_id: 1,
+            symbol: "AAA".to_string(),
+            side,
+            quantity,
+            price,
+            commission: 0.0,
+            timestamp: OffsetDateTime::UNIX_EPOCH,
+        };
+
+        account.apply_fill(&fill(OrderSide::BID, 10, 100.0));
+        account.apply_fill(&fill(OrderSide::BID, 10, 110.0));
+        assert_approx_equal!(account.positions["AAA"].average_price, 105.0, 1e-12);
+
+        // Reverse from 20 long to 10 short.
+        account.apply_fill(&fill(OrderSide::ASK, 30, 120.0));
+
+        let holding = account.positions["AAA"];
+        assert_eq!(holding.quantity, -10);
+        assert_approx_equal!(holding.average_price, 120.0, 1e-12);
+        assert_approx_equal!(holding.realized_pnl, 20.0 * 15.0, 1e-12);
+        assert_approx_equal!(account.cash, -2100.0 + 3600.0, 1e-9);
+    }
+
+    #[test]
+    fn test_cancel_and_invalid_orders() {
+        let mut broker = SimulatedBroker::new(0.0);
+
+        let id = broker
+            .submit(OrderRequest::limit("AAA", OrderSide::BID, 1, 10.0))
+            .unwrap();
+        broker.cancel(id).unwrap();
+
+        assert!(broker.cancel(id).is_err());
+        assert!(broker.open_orders().is_empty());
+        assert!(broker
+            .submit(OrderRequest::market("AAA", OrderSide::BID, 0))
+            .is_err());
+        assert!(broker
+            .submit(OrderRequest::limit("AAA", OrderSide::BID, 1, -1.0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_stop_orders() {
+        let mut broker = SimulatedBroker::new(10_000.0);
+
+        let stop = broker
+            .submit(OrderRequest::stop("AAA", OrderSide::ASK, 10, 48.0))
+            .unwrap();
+        let stop_limit = broker
+            .submit(OrderRequest::stop_limit(
+                "AAA",
+                OrderSide::BID,
+                10,
+                52.0,
+                52.5,
+            ))
+            .unwrap();
+
+        // Neither stop is reached.
+        assert!(broker.process(&bar(50.0, 51.0, 49.0, 50.0)).is_empty());
+
+        // The sell stop is triggered, and filled at the stop.
+        let fills = broker.process(&bar(50.0, 51.0, 47.0, 48.0));
+        assert_eq!(fills[0].order_id, stop);
+        assert_approx_equal!(fills[0].price, 48.0, 1e-12);
+
+        // The bar gaps above the buy stop and its limit: it becomes a limit
+        // order, filled once the price comes back to the limit.
+        assert!(broker.process(&bar(53.0, 54.0, 52.8, 53.0)).is_empty());
+        assert_eq!(broker.open_orders()[0].1.order_type, OrderType::Limit);
+
+        let fills = broker.process(&bar(53.0, 53.0, 52.0, 52.0));
+        assert_eq!(fills[0].order_id, stop_limit);
+        assert_approx_equal!(fills[0].price, 52.5, 1e-12);
+
+        // Gapping through a stop fills at the open.
+        broker
+            .submit(OrderRequest::stop("AAA", OrderSide::ASK, 10, 50.0))
+            .unwrap();
+        let fills = broker.process(&bar(45.0, 46.0, 44.0, 45.0));
+        assert_approx_equal!(fills[0].price, 45.0, 1e-12);
+    }
+
+    #[test]
+    fn test_trailing_stop() {
+        let mut broker = SimulatedBroker::new(0.0);
+
+        // Sets the last price to 100, so the stop starts at 95.
+        broker.process(&bar(100.0, 100.0, 100.0, 100.0));
+        broker
+            .submit(OrderRequest::trailing_stop("AAA", OrderSide::ASK, 5, 5.0))
+            .unwrap();
+        assert_eq!(broker.open_orders()[0].1.stop_price, Some(95.0));
+
+        // The stop follows the high up to 105, but not back down.
+        assert!(broker.process(&bar(100.0, 110.0, 99.0, 108.0)).is_empty());
+        assert!(broker.process(&bar(108.0, 108.0, 106.0, 106.0)).is_empty());
+        assert_eq!(broker.open_orders()[0].1.stop_price, Some(105.0));
+
+        let fills = broker.process(&bar(106.0, 106.0, 100.0, 101.0));
+        assert_approx_equal!(fills[0].price, 105.0, 1e-12);
+        assert!(broker.open_orders().is_empty());
+    }
+
+    #[test]
+    fn test_iceberg_and_pegged_orders() {
+        let mut broker = SimulatedBroker::new(10_000.0);
+
+        let iceberg = broker
+            .submit(OrderRequest::iceberg("AAA", OrderSide::BID, 25, 50.0, 10))
+            .unwrap();
+
+        // One displayed quantity per event, until the order is filled.
+        let quantities: Vec<u64> = (0..4)
+            .flat_map(|_| broker.process(&bar(50.0, 50.0, 49.0, 49.5)))
+            .map(|fill| fill.quantity)
+            .collect();
+
+        assert_eq!(quantities, vec![10, 10, 5]);
+        assert_eq!(broker.account().position("AAA"), 25);
+        assert!(broker.fills().iter().all(|fill| fill.order_id == iceberg));
+
+        // Pegged 1.0 above the last price (49.5) to sell, then re-priced.
+        broker
+            .submit(OrderRequest::pegged("AAA", OrderSide::ASK, 25, 1.0))
+            .unwrap();
+        assert_eq!(broker.open_orders()[0].1.limit_price, Some(50.5));
+
+        assert!(broker.process(&bar(49.5, 50.0, 48.0, 48.0)).is_empty());
+        assert_eq!(broker.open_orders()[0].1.limit_price, Some(49.0));
+
+        let fills = broker.process(&bar(48.0, 49.5, 48.0, 49.0));
+        assert_approx_equal!(fills[0].price, 49.0, 1e-12);
+        assert_eq!(broker.account().position("AAA"), 0);
+
+        assert!(broker
+            .submit(OrderRequest::iceberg("AAA", OrderSide::BID, 1, 50.0, 0))
+            .is_err());
+        assert!(broker
+            .submit(OrderRequest::pegged("AAA", OrderSide::BID, 1, -1.0))
+            .is_err());
+    }
+}
@@ -0,0 +1,247 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::{BacktestReport, MarketEvent, SimulatedBroker, Strategy};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Event-driven backtester.
+#[derive(Debug, Clone)]
+pub struct Backtester {
+    /// Broker that fills the orders.
+    pub broker: SimulatedBroker,
+
+    /// Number of periods (timestamps) per year, to annualise the statistics.
+    pub periods_per_year: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Backtester {
+    /// Create a backtester with a broker, for daily events (252 per year).
+    #[must_use]
+    pub fn new(broker: SimulatedBroker) -> Self {
+        Self {
+            broker,
+            periods_per_year: 252.0,
+        }
+    }
+
+    /// Set the number of periods per year.
+    #[must_use]
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Run a strategy over market events, in time order.
+    ///
+    /// For each event, the broker first fills the open orders of its symbol,
+    /// the strategy is told of the fills, and then it sees the event and
+    /// submits new orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the events are not in time order, or the broker
+    /// rejects an order.
+    pub fn run<S, E>(
+        mut self,
+        strategy: &mut S,
+        events: E,
+    ) -> Result<BacktestReport, RustQuantError>
+    where
+        S: Strategy + ?Sized,
+        E: IntoIterator<Item = MarketEvent>,
+    {
+        let initial_equity = self.broker.account().equity();
+        let mut equity_curve = Vec::new();
+
+        for event in events {
+            let timestamp = event.timestamp();
+
+            if let Some((last, _)) = equity_curve.last() {
+                if timestamp < *last {
+                    return Err(RustQuantError::InvalidArgument(format!(
+                        "Events are not in time order: {timestamp} is before {last}."
+                    )));
+                }
+            }
+
+            for fill in self.broker.process(&event) {
+                strategy.on_fill(&fill);
+            }
+
+            for order in strategy.on_event(&event, self.broker.account()) {
+                self.broker.submit(order)?;
+            }
+
+            // One point per timestamp, after all its events.
+            let equity = self.broker.account().equity();
+
+            match equity_curve.last_mut() {
+                Some((last, value)) if *last == timestamp => *value = equity,
+                _ => equity_curve.push((timestamp, equity)),
+            }
+        }
+
+        Ok(BacktestReport::new(
+            initial_equity,
+            equity_curve,
+            self.broker.fills().to_vec(),
+            self.periods_per_year,
+        ))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_backtester {
+    use super::*;
+    use crate::backtest::{Account, Bar, CommissionModel, Fill, OrderRequest, Tick};
+    use crate::order_side::OrderSide;
+    use std::collections::VecDeque;
+    use time::{Duration, OffsetDateTime};
+    use RustQuant_utils::assert_approx_equal;
+
+    /// Long 100 units when the short moving average is above the long one,
+    /// flat otherwise.
+    struct MovingAverageCrossover {
+        prices: VecDeque<f64>,
+        short: usize,
+        long: usize,
+        fills: usize,
+    }
+
+    impl Strategy for MovingAverageCrossover {
+        fn on_event(&mut self, event: &MarketEvent, account: &Account) -> Vec<OrderRequest> {
+            self.prices.push_back(event.price());
+
+            if self.prices.len() > self.long {
+                self.prices.pop_front();
+            }
+
+            if self.prices.len() < self.long || account.open_orders > 0 {
+                return vec![];
+            }
+
+            let average = |n: usize| self.prices.iter().rev().take(n).sum::<f64>() / n as f64;
+            let position = account.position(event.symbol());
+
+            match (average(self.short) > average(self.long), position) {
+                (true, 0) => vec![OrderRequest::market(event.symbol(), OrderSide::BID, 100)],
+                (false, p) if p > 0 => {
+                    vec![OrderRequest::market(
+                        event.symbol(),
+                        OrderSide::ASK,
+                        p as u64,
+                    )]
+                }
+                _ => vec![],
+            }
+        }
+
+        fn on_fill(&mut self, _fill: &Fill) {
+            self.fills += 1;
+        }
+    }
+
+    fn bars(closes: &[f64]) -> Vec<MarketEvent> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| {
+                MarketEvent::Bar(Bar::new(
+                    "AAA",
+                    OffsetDateTime::UNIX_EPOCH + Duration::days(i as i64),
+                    *close,
+                    *close,
+                    *close,
+                    *close,
+                    1e6,
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_moving_average_crossover() {
+        // Up, then down.
+        let closes: Vec<f64> = (0..20)
+            .map(|i| 100.0 + i as f64)
+            .chain((0..20).map(|i| 119.0 - 2.0 * i as f64))
+            .collect();
+
+        let mut strategy = MovingAverageCrossover {
+            prices: VecDeque::new(),
+            short: 2,
+            long: 5,
+            fills: 0,
+        };
+
+        let broker = SimulatedBroker::new(100_000.0).with_commission(CommissionModel {
+            per_order: 1.0,
+            ..Default::default()
+        });
+        let report = Backtester::new(broker)
+            .run(&mut strategy, bars(&closes))
+            .unwrap();
+
+        // Bought on day 5 at 105, sold after the top.
+        assert_eq!(report.trades(), 2);
+        assert_eq!(strategy.fills, 2);
+        assert_eq!(report.fills[0].side, OrderSide::BID);
+        assert_approx_equal!(report.fills[0].price, 105.0, 1e-12);
+
+        let pnl = 100.0 * (report.fills[1].price - 105.0) - 2.0;
+        assert_approx_equal!(report.final_equity, 100_000.0 + pnl, 1e-8);
+        assert_approx_equal!(report.total_commission, 2.0, 1e-12);
+        assert_eq!(report.equity_curve.len(), closes.len());
+        assert!(report.max_drawdown > 0.0);
+    }
+
+    #[test]
+    fn test_events_out_of_order() {
+        struct Idle;
+
+        impl Strategy for Idle {
+            fn on_event(&mut self, _: &MarketEvent, _: &Account) -> Vec<OrderRequest> {
+                vec![]
+            }
+        }
+
+        let start = OffsetDateTime::UNIX_EPOCH;
+        let events = vec![
+            MarketEvent::Tick(Tick::new("AAA", start + Duration::hours(1), 10.0, 1.0)),
+            MarketEvent::Tick(Tick::new("AAA", start, 10.0, 1.0)),
+        ];
+
+        assert!(Backtester::new(SimulatedBroker::new(0.0))
+            .run(&mut Idle, events)
+            .is_err());
+
+        // Events at the same time make one point of the equity curve.
+        let events = vec![
+            MarketEvent::Tick(Tick::new("AAA", start, 10.0, 1.0)),
+            MarketEvent::Tick(Tick::new("BBB", start, 20.0, 1.0)),
+        ];
+        let report = Backtester::new(SimulatedBroker::new(0.0))
+            .run(&mut Idle, events)
+            .unwrap();
+
+        assert_eq!(report.equity_curve.len(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Open, high, low, and close prices and the volume of a symbol over an
+/// interval, timestamped at its end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bar {
+    /// Symbol.
+    pub symbol: String,
+
+    /// End of the interval.
+    pub timestamp: OffsetDateTime,
+
+    /// Open price.
+    pub open: f64,
+
+    /// High price.
+    pub high: f64,
+
+    /// Low price.
+    pub low: f64,
+
+    /// Close price.
+    pub close: f64,
+
+    /// Volume traded.
+    pub volume: f64,
+}
+
+/// Trade of a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tick {
+    /// Symbol.
+    pub symbol: String,
+
+    /// Time of the trade.
+    pub timestamp: OffsetDateTime,
+
+    /// Price of the trade.
+    pub price: f64,
+
+    /// Size of the trade.
+    pub size: f64,
+}
+
+/// Market data event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketEvent {
+    /// Bar.
+    Bar(Bar),
+
+    /// Tick.
+    Tick(Tick),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Bar {
+    /// Create a new bar.
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        symbol: &str,
+        timestamp: OffsetDateTime,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+}
+
+impl Tick {
+    /// Create a new tick.
+    #[must_use]
+    pub fn new(symbol: &str, timestamp: OffsetDateTime, price: f64, size: f64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            timestamp,
+            price,
+            size,
+        }
+    }
+}
+
+impl MarketEvent {
+    /// Symbol of the event.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        match self {
+            MarketEvent::Bar(bar) => &bar.symbol,
+            MarketEvent::Tick(tick) => &tick.symbol,
+        }
+    }
+
+    /// Time of the event.
+    #[must_use]
+    pub fn timestamp(&self) -> OffsetDateTime {
+        match self {
+            MarketEvent::Bar(bar) => bar.timestamp,
+            MarketEvent::Tick(tick) => tick.timestamp,
+        }
+    }
+
+    /// Last price: the close of a bar, or the price of a tick.
+    #[must_use]
+    pub fn price(&self) -> f64 {
+        match self {
+            MarketEvent::Bar(bar) => bar.close,
+            MarketEvent::Tick(tick) => tick.price,
+        }
+    }
+
+    /// Volume traded: the volume of a bar, or the size of a tick.
+    #[must_use]
+    pub fn volume(&self) -> f64 {
+        match self {
+            MarketEvent::Bar(bar) => bar.volume,
+            MarketEvent::Tick(tick) => tick.size,
+        }
+    }
+
+    /// First price of the event: the open of a bar, or the price of a tick.
+    #[must_use]
+    pub fn open(&self) -> f64 {
+        match self {
+            MarketEvent::Bar(bar) => bar.open,
+            MarketEvent::Tick(tick) => tick.price,
+        }
+    }
+
+    /// Highest price of the event.
+    #[must_use]
+    pub fn high(&self) -> f64 {
+        match self {
+            MarketEvent::Bar(bar) => bar.high,
+            MarketEvent::Tick(tick) => tick.price,
+        }
+    }
+
+    /// Lowest price of the event.
+    #[must_use]
+    pub fn low(&self) -> f64 {
+        match self {
+            MarketEvent::Bar(bar) => bar.low,
+            MarketEvent::Tick(tick) => tick.price,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Event-driven backtesting.
+//!
+//! A [`Strategy`] receives market events ([`Bar`]s or [`Tick`]s) in time
+//! order, with the state of its [`Account`], and emits [`OrderRequest`]s.
+//! The [`SimulatedBroker`] fills the orders against the following events
+//! of their symbol, so a strategy never trades on prices it has not seen
+//! yet, and charges slippage and commission. The [`Backtester`] runs the
+//! loop and reports the equity curve and statistics in a
+//! [`BacktestReport`].
+//!
+//! ```
+//! use RustQuant::trading::backtest::*;
+//! use RustQuant::trading::order_side::OrderSide;
+//! use time::{Duration, OffsetDateTime};
+//!
+//! // Buy 10 shares on the first event, and hold.
+//! struct BuyAndHold;
+//!
+//! impl Strategy for BuyAndHold {
+//!     fn on_event(&mut self, event: &MarketEvent, account: &Account) -> Vec<OrderRequest> {
+//!         if account.position(event.symbol()) == 0 && account.open_orders == 0 {
+//!             vec![OrderRequest::market(event.symbol(), OrderSide::BID, 10)]
+//!         } else {
+//!             vec![]
+//!         }
+//!     }
+//! }
+//!
+//! let events = (0..5).map(|day| {
+//!     let price = 100.0 + day as f64;
+//!     let timestamp = OffsetDateTime::UNIX_EPOCH + Duration::days(day);
+//!     MarketEvent::Tick(Tick::new("AAA", timestamp, price, 100.0))
+//! });
+//!
+//! let report = Backtester::new(SimulatedBroker::new(10_000.0))
+//!     .run(&mut BuyAndHold, events)
+//!     .unwrap();
+//!
+//! // Bought at 101 (the tick after the signal), last price 104.
+//! assert!((report.final_equity - 10_030.0).abs() < 1e-10);
+//! ```
+
+/// Market data events: bars and ticks.
+pub mod events;
+pub use events::*;
+
+/// Simulated broker: orders, fills, slippage, and commission.
+pub mod broker;
+pub use broker::*;
+
+/// Strategy trait.
+pub mod strategy;
+pub use strategy::*;
+
+/// Backtest report: equity curve and statistics.
+pub mod report;
+pub use report::*;
+
+/// Backtesting engine.
+pub mod engine;
+pub use engine::*;
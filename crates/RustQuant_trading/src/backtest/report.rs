@@ -0,0 +1,183 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::Fill;
+use std::fmt;
+use time::OffsetDateTime;
+use RustQuant_math::{max_drawdown, Statistic};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Equity curve, fills, and statistics of a backtest.
+///
+/// Returns are computed between consecutive points of the equity curve (one
+/// per timestamp), and annualised with the number of periods per year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    /// Equity (cash plus positions at the last prices) after each timestamp.
+    pub equity_curve: Vec<(OffsetDateTime, f64)>,
+
+    /// All fills.
+    pub fills: Vec<Fill>,
+
+    /// Equity at the start.
+    pub initial_equity: f64,
+
+    /// Equity at the end.
+    pub final_equity: f64,
+
+    /// Total return.
+    pub total_return: f64,
+
+    /// Total return, annualised over the calendar time of the equity curve.
+    pub annualised_return: f64,
+
+    /// Annualised volatility of the returns.
+    pub volatility: f64,
+
+    /// Annualised Sharpe ratio of the returns (with a zero risk-free rate).
+    pub sharpe_ratio: f64,
+
+    /// Largest fall of the equity from a previous maximum, as a fraction.
+    pub max_drawdown: f64,
+
+    /// Total commission paid.
+    pub total_commission: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl BacktestReport {
+    /// Compute the statistics of an equity curve.
+    #[must_use]
+    pub fn new(
+        initial_equity: f64,
+        equity_curve: Vec<(OffsetDateTime, f64)>,
+        fills: Vec<Fill>,
+        periods_per_year: f64,
+    ) -> Self {
+        let final_equity = equity_curve
+            .last()
+            .map_or(initial_equity, |(_, equity)| *equity);
+        let total_return = final_equity / initial_equity - 1.0;
+
+        let years = match (equity_curve.first(), equity_curve.last()) {
+            (Some((start, _)), Some((end, _))) => {
+                (*end - *start).as_seconds_f64() / (365.25 * 86_400.0)
+            }
+            _ => 0.0,
+        };
+        let annualised_return = if years > 0.0 {
+            (1.0 + total_return).powf(1.0 / years) - 1.0
+        } else {
+            total_return
+        };
+
+        let returns: Vec<f64> = std::iter::once(initial_equity)
+            .chain(equity_curve.iter().map(|(_, equity)| *equity))
+            .collect::<Vec<f64>>()
+            .windows(2)
+            .map(|window| window[1] / window[0] - 1.0)
+            .collect();
+
+        // One return has no dispersion (and the sample variance is undefined).
+        let (mean, deviation) = match returns.len() {
+            0 => (0.0, 0.0),
+            1 => (returns[0], 0.0),
+            _ => (returns.mean(), returns.standard_deviation()),
+        };
+        let volatility = deviation * periods_per_year.sqrt();
+        let sharpe_ratio = if deviation > 0.0 {
+            mean / deviation * periods_per_year.sqrt()
+        } else {
+            0.0
+        };
+
+        Self {
+            total_commission: fills.iter().map(|fill| fill.commission).sum(),
+            equity_curve,
+            fills,
+            initial_equity,
+            final_equity,
+            total_return,
+            annualised_return,
+            volatility,
+            sharpe_ratio,
+            max_drawdown: max_drawdown(&returns),
+        }
+    }
+
+    /// Number of fills.
+    #[must_use]
+    pub fn trades(&self) -> usize {
+        self.fills.len()
+    }
+}
+
+impl fmt::Display for BacktestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Initial equity:     {:.2}", self.initial_equity)?;
+        writeln!(f, "Final equity:       {:.2}", self.final_equity)?;
+        writeln!(f, "Total return:       {:.2}%", 100.0 * self.total_return)?;
+        writeln!(
+            f,
+            "Annualised return:  {:.2}%",
+            100.0 * self.annualised_return
+        )?;
+        writeln!(f, "Volatility:         {:.2}%", 100.0 * self.volatility)?;
+        writeln!(f, "Sharpe ratio:       {:.2}", self.sharpe_ratio)?;
+        writeln!(f, "Maximum drawdown:   {:.2}%", 100.0 * self.max_drawdown)?;
+        writeln!(f, "Trades:             {}", self.trades())?;
+        write!(f, "Commission:         {:.2}", self.total_commission)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_report {
+    use super::*;
+    use time::Duration;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_report_statistics() {
+        let start = OffsetDateTime::UNIX_EPOCH;
+        let curve = [110.0, 99.0, 121.0, 133.1]
+            .iter()
+            .enumerate()
+            .map(|(i, equity)| (start + Duration::days(i as i64), *equity))
+            .collect();
+
+        let report = BacktestReport::new(100.0, curve, vec![], 252.0);
+
+        assert_approx_equal!(report.total_return, 0.331, 1e-12);
+        assert_approx_equal!(report.max_drawdown, 0.1, 1e-12);
+
+        // Returns 10%, -10%, 22.2%, 10%.
+        let returns = [0.1, -0.1, 121.0 / 99.0 - 1.0, 0.1];
+        let mean = returns.iter().sum::<f64>() / 4.0;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 3.0;
+
+        assert_approx_equal!(report.volatility, (variance * 252.0).sqrt(), 1e-12);
+        assert_approx_equal!(
+            report.sharpe_ratio,
+            mean / variance.sqrt() * 252.0_f64.sqrt(),
+            1e-12
+        );
+        assert_eq!(report.trades(), 0);
+        assert!(report.to_string().contains("Maximum drawdown:   10.00%"));
+    }
+}
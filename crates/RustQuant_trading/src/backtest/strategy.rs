@@ -0,0 +1,23 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::{Account, Fill, MarketEvent, OrderRequest};
+
+/// Trading strategy, driven by market events.
+///
+/// The strategy only sees the events up to the current one and the state of
+/// its account, and trades by returning orders, so the same strategy can be
+/// backtested or traded.
+pub trait Strategy {
+    /// Orders to submit after a market event.
+    fn on_event(&mut self, event: &MarketEvent, account: &Account) -> Vec<OrderRequest>;
+
+    /// Called when an order is filled (by default, does nothing).
+    fn on_fill(&mut self, _fill: &Fill) {}
+}
@@ -0,0 +1,479 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Execution algorithms, which slice a large (parent) order into smaller
+//! (child) orders over time, to reduce its market impact.
+//!
+//! - TWAP (time-weighted average price): equal slices at regular intervals.
+//! - VWAP (volume-weighted average price): slices following the expected
+//!   volume profile of the trading window, e.g. larger at the open and the
+//!   close.
+//! - POV (percentage of volume): a fixed fraction of the volume traded by
+//!   the market, as it is observed.
+//!
+//! An [`ExecutionAlgorithm`] is a [`Strategy`], so it can be run by the
+//! [`Backtester`](crate::backtest::Backtester) against the simulated broker,
+//! or with any other source of market events and broker.
+//!
+//! ```
+//! use RustQuant::trading::backtest::*;
+//! use RustQuant::trading::execution::ExecutionAlgorithm;
+//! use RustQuant::trading::order_side::OrderSide;
+//! use time::{Duration, OffsetDateTime};
+//!
+//! let start = OffsetDateTime::UNIX_EPOCH;
+//! let end = start + Duration::hours(4);
+//!
+//! // Buy 1,000 shares in 4 hourly slices, with a tick every 10 minutes.
+//! let mut twap = ExecutionAlgorithm::twap("AAA", OrderSide::BID, 1000, start, end, 4).unwrap();
+//!
+//! let ticks = (0..=24).map(|i| {
+//!     let timestamp = start + Duration::minutes(10 * i);
+//!     MarketEvent::Tick(Tick::new("AAA", timestamp, 100.0, 500.0))
+//! });
+//!
+//! let report = Backtester::new(SimulatedBroker::new(1e6)).run(&mut twap, ticks).unwrap();
+//!
+//! assert_eq!(report.trades(), 4);
+//! assert_eq!(twap.filled(), 1000);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::{Account, Fill, MarketEvent, OrderRequest, Strategy};
+use crate::order_side::OrderSide;
+use time::OffsetDateTime;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How the parent order is sliced over the trading window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionSchedule {
+    /// Equal slices, released at the start of each of `slices` intervals.
+    Twap {
+        /// Number of slices.
+        slices: usize,
+    },
+
+    /// Slices proportional to the expected volume of each of the
+    /// equal-length intervals of the window.
+    Vwap {
+        /// Expected volume (or fraction of it) in each interval.
+        profile: Vec<f64>,
+    },
+
+    /// A fraction of the market volume observed since the start.
+    Pov {
+        /// Participation rate (e.g. 0.1 for 10% of the volume).
+        participation: f64,
+    },
+}
+
+/// Execution algorithm for a parent order.
+///
+/// Child orders are released when the algorithm sees market events of the
+/// symbol in the trading window. TWAP and VWAP release what remains at the
+/// end of the window, while POV never exceeds its participation, and may
+/// leave part of the order unexecuted.
+///
+/// Fills of the symbol and side of the parent order are attributed to it,
+/// so run one algorithm per symbol and side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionAlgorithm {
+    /// Symbol to trade.
+    pub symbol: String,
+
+    /// Side of the parent order.
+    pub side: OrderSide,
+
+    /// Quantity of the parent order.
+    pub quantity: u64,
+
+    /// Start of the trading window.
+    pub start: OffsetDateTime,
+
+    /// End of the trading window.
+    pub end: OffsetDateTime,
+
+    /// Slicing schedule.
+    pub schedule: ExecutionSchedule,
+
+    /// Limit price of the child orders (market orders if `None`).
+    pub limit_price: Option<f64>,
+
+    submitted: u64,
+    filled: u64,
+    market_volume: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ExecutionAlgorithm {
+    /// Create an execution algorithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quantity is zero, the window is empty, or the
+    /// schedule is invalid: no slices, a volume profile with negative
+    /// volumes or none at all, or a participation rate not in (0, 1].
+    pub fn new(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        schedule: ExecutionSchedule,
+    ) -> Result<Self, RustQuantError> {
+        if quantity == 0 || end <= start {
+            return Err(RustQuantError::InvalidArgument(
+                "Parent orders need a positive quantity and a non-empty window.".to_string(),
+            ));
+        }
+
+        let valid = match &schedule {
+            ExecutionSchedule::Twap { slices } => *slices > 0,
+            ExecutionSchedule::Vwap { profile } => {
+                profile.iter().all(|volume| *volume >= 0.0) && profile.iter().sum::<f64>() > 0.0
+            }
+            ExecutionSchedule::Pov { participation } => {
+                *participation > 0.0 && *participation <= 1.0
+            }
+        };
+
+        if !valid {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Invalid execution schedule: {schedule:?}."
+            )));
+        }
+
+        Ok(Self {
+            symbol: symbol.to_string(),
+            side,
+            quantity,
+            start,
+            end,
+            schedule,
+            limit_price: None,
+            submitted: 0,
+            filled: 0,
+            market_volume: 0.0,
+        })
+    }
+
+    /// TWAP algorithm with the given number of slices.
+    ///
+    /// # Errors
+    ///
+    /// See [`ExecutionAlgorithm::new`].
+    pub fn twap(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        slices: usize,
+    ) -> Result<Self, RustQuantError> {
+        Self::new(
+            symbol,
+            side,
+            quantity,
+            start,
+            end,
+            ExecutionSchedule::Twap { slices },
+        )
+    }
+
+    /// VWAP algorithm with the given volume profile.
+    ///
+    /// # Errors
+    ///
+    /// See [`ExecutionAlgorithm::new`].
+    pub fn vwap(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        profile: Vec<f64>,
+    ) -> Result<Self, RustQuantError> {
+        Self::new(
+            symbol,
+            side,
+            quantity,
+            start,
+            end,
+            ExecutionSchedule::Vwap { profile },
+        )
+    }
+
+    /// POV algorithm with the given participation rate.
+    ///
+    /// # Errors
+    ///
+    /// See [`ExecutionAlgorithm::new`].
+    pub fn pov(
+        symbol: &str,
+        side: OrderSide,
+        quantity: u64,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        participation: f64,
+    ) -> Result<Self, RustQuantError> {
+        Self::new(
+            symbol,
+            side,
+            quantity,
+            start,
+            end,
+            ExecutionSchedule::Pov { participation },
+        )
+    }
+
+    /// Send the child orders as limit orders at this price.
+    #[must_use]
+    pub fn with_limit_price(mut self, limit_price: f64) -> Self {
+        self.limit_price = Some(limit_price);
+        self
+    }
+
+    /// Quantity released in child orders so far.
+    #[must_use]
+    pub fn submitted(&self) -> u64 {
+        self.submitted
+    }
+
+    /// Quantity filled so far.
+    #[must_use]
+    pub fn filled(&self) -> u64 {
+        self.filled
+    }
+
+    /// Quantity not filled yet.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.quantity - self.filled
+    }
+
+    /// Check if the parent order is completely filled.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.filled == self.quantity
+    }
+
+    /// Quantity that the schedule releases by `timestamp`.
+    #[must_use]
+    pub fn target(&self, timestamp: OffsetDateTime) -> u64 {
+        if timestamp < self.start {
+            return 0;
+        }
+
+        let fraction = match &self.schedule {
+            ExecutionSchedule::Pov { participation } => {
+                return ((participation * self.market_volume).floor() as u64).min(self.quantity);
+            }
+            _ if timestamp >= self.end => return self.quantity,
+            ExecutionSchedule::Twap { slices } => {
+                (self.interval(timestamp, *slices) + 1) as f64 / *slices as f64
+            }
+            ExecutionSchedule::Vwap { profile } => {
+                let released = profile[..=self.interval(timestamp, profile.len())]
+                    .iter()
+                    .sum::<f64>();
+
+                released / profile.iter().sum::<f64>()
+            }
+        };
+
+        ((fraction * self.quantity as f64).floor() as u64).min(self.quantity)
+    }
+
+    /// Index of the interval of the window that contains `timestamp`, out
+    /// of `intervals` equal ones.
+    fn interval(&self, timestamp: OffsetDateTime, intervals: usize) -> usize {
+        let elapsed = (timestamp - self.start) / (self.end - self.start);
+
+        ((elapsed * intervals as f64) as usize).min(intervals - 1)
+    }
+}
+
+impl Strategy for ExecutionAlgorithm {
+    fn on_event(&mut self, event: &MarketEvent, _account: &Account) -> Vec<OrderRequest> {
+        let timestamp = event.timestamp();
+
+        if event.symbol() != self.symbol || timestamp < self.start {
+            return vec![];
+        }
+
+        // POV stops at the end of the window.
+        if let ExecutionSchedule::Pov { .. } = self.schedule {
+            if timestamp > self.end {
+                return vec![];
+            }
+
+            self.market_volume += event.volume();
+        }
+
+        let target = self.target(timestamp);
+
+        if target <= self.submitted {
+            return vec![];
+        }
+
+        let quantity = target - self.submitted;
+        self.submitted = target;
+
+        let order = match self.limit_price {
+            Some(price) => OrderRequest::limit(&self.symbol, self.side, quantity, price),
+            None => OrderRequest::market(&self.symbol, self.side, quantity),
+        };
+
+        vec![order]
+    }
+
+    fn on_fill(&mut self, fill: &Fill) {
+        if fill.symbol == self.symbol && fill.side == self.side {
+            self.filled = (self.filled + fill.quantity).min(self.quantity);
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_execution {
+    use super::*;
+    use crate::backtest::{Backtester, SimulatedBroker, Tick};
+    use time::Duration;
+
+    fn start() -> OffsetDateTime {
+        OffsetDateTime::UNIX_EPOCH
+    }
+
+    fn end() -> OffsetDateTime {
+        start() + Duration::hours(6)
+    }
+
+    // A tick every 15 minutes, with the given volume.
+    fn ticks<F: Fn(i64) -> f64>(volume: F) -> Vec<MarketEvent> {
+        (0..=24)
+            .map(|i| {
+                MarketEvent::Tick(Tick::new(
+                    "AAA",
+                    start() + Duration::minutes(15 * i),
+                    100.0,
+                    volume(i),
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_twap() {
+        let mut twap =
+            ExecutionAlgorithm::twap("AAA", OrderSide::ASK, 1001, start(), end(), 3).unwrap();
+
+        assert_eq!(twap.target(start() - Duration::minutes(1)), 0);
+        assert_eq!(twap.target(start()), 333);
+        assert_eq!(twap.target(start() + Duration::hours(2)), 667);
+        assert_eq!(twap.target(end()), 1001);
+
+        let report = Backtester::new(SimulatedBroker::new(0.0))
+            .run(&mut twap, ticks(|_| 100.0))
+            .unwrap();
+
+        let quantities: Vec<u64> = report.fills.iter().map(|fill| fill.quantity).collect();
+
+        assert_eq!(quantities, vec![333, 334, 334]);
+        assert!(twap.is_complete());
+        assert_eq!(twap.remaining(), 0);
+    }
+
+    #[test]
+    fn test_vwap() {
+        // U-shaped profile over three 2-hour intervals.
+        let profile = vec![0.4, 0.2, 0.4];
+        let mut vwap =
+            ExecutionAlgorithm::vwap("AAA", OrderSide::BID, 1000, start(), end(), profile).unwrap();
+
+        let report = Backtester::new(SimulatedBroker::new(1e6))
+            .run(&mut vwap, ticks(|_| 100.0))
+            .unwrap();
+
+        let quantities: Vec<u64> = report.fills.iter().map(|fill| fill.quantity).collect();
+
+        assert_eq!(quantities, vec![400, 200, 400]);
+    }
+
+    #[test]
+    fn test_pov() {
+        let mut pov =
+            ExecutionAlgorithm::pov("AAA", OrderSide::BID, 1000, start(), end(), 0.1).unwrap();
+
+        // 25 ticks of 200 units: 5,000 traded, so 500 at 10%.
+        let report = Backtester::new(SimulatedBroker::new(1e6))
+            .run(&mut pov, ticks(|_| 200.0))
+            .unwrap();
+
+        assert!(report.fills.iter().all(|fill| fill.quantity == 20));
+        assert_eq!(pov.submitted(), 500);
+        assert_eq!(pov.remaining(), 520);
+        assert!(!pov.is_complete());
+
+        // Capped at the parent quantity.
+        let mut pov =
+            ExecutionAlgorithm::pov("AAA", OrderSide::BID, 100, start(), end(), 0.5).unwrap();
+
+        Backtester::new(SimulatedBroker::new(1e6))
+            .run(&mut pov, ticks(|_| 200.0))
+            .unwrap();
+
+        assert!(pov.is_complete());
+    }
+
+    #[test]
+    fn test_limit_child_orders() {
+        // The limit is never reached, so nothing is filled.
+        let mut twap = ExecutionAlgorithm::twap("AAA", OrderSide::BID, 100, start(), end(), 2)
+            .unwrap()
+            .with_limit_price(99.0);
+
+        let report = Backtester::new(SimulatedBroker::new(1e6))
+            .run(&mut twap, ticks(|_| 100.0))
+            .unwrap();
+
+        assert_eq!(report.trades(), 0);
+        assert_eq!(twap.submitted(), 100);
+        assert_eq!(twap.filled(), 0);
+    }
+
+    #[test]
+    fn test_invalid_schedules() {
+        let new =
+            |schedule| ExecutionAlgorithm::new("AAA", OrderSide::BID, 1, start(), end(), schedule);
+
+        assert!(new(ExecutionSchedule::Twap { slices: 0 }).is_err());
+        assert!(new(ExecutionSchedule::Vwap { profile: vec![] }).is_err());
+        assert!(new(ExecutionSchedule::Vwap {
+            profile: vec![1.0, -1.0]
+        })
+        .is_err());
+        assert!(new(ExecutionSchedule::Pov { participation: 1.5 }).is_err());
+        assert!(ExecutionAlgorithm::twap("AAA", OrderSide::BID, 0, start(), end(), 1).is_err());
+        assert!(ExecutionAlgorithm::twap("AAA", OrderSide::BID, 1, end(), start(), 1).is_err());
+    }
+}
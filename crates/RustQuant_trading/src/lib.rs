@@ -9,9 +9,18 @@
 
 //! Trading related items.
 
+/// Event-driven backtesting of trading strategies.
+pub mod backtest;
+
+/// Execution algorithms (TWAP, VWAP, and POV).
+pub mod execution;
+
 /// Contains limit order book implementation
 pub mod limit_order_book;
 
+/// Live and paper trading with brokers.
+pub mod live;
+
 /// Order definition.
 pub mod order;
 
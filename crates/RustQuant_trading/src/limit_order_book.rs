@@ -9,6 +9,7 @@
 
 mod limit;
 mod order;
+mod stop;
 mod test;
 
 use limit::Limit;
@@ -17,16 +18,30 @@ use std::{
     collections::{btree_map::BTreeMap, HashMap},
     fmt,
 };
+use stop::StopOrder;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Limit order book
+///
+/// Besides limit orders, the book holds:
+///     - Iceberg orders, of which only the display quantity is at their limit
+///       at a time, refreshed at the back of the queue when executed.
+///     - Stop, stop-limit, and trailing stop orders, outside of the limits
+///       until triggered by a market execution at or through their stop.
+///     - Pegged orders, at the best price of their side (ignoring other
+///       pegged orders), re-priced whenever it changes.
 pub struct Book {
     buy_limits: BTreeMap<u64, Limit>,
     sell_limits: BTreeMap<u64, Limit>,
     order_map: HashMap<u64, Order>,
+    stop_orders: Vec<StopOrder>,
+    pegged: HashMap<u64, u64>,
+    parked: Vec<u64>,
+    last_price: Option<u64>,
+    stop_executions: Vec<(u64, Vec<(u64, u64)>)>,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -69,6 +84,31 @@ impl fmt::Display for NonExistingIdError {
     }
 }
 
+/// Error for when an iceberg order cannot be added to the book
+#[derive(Debug)]
+pub enum IcebergOrderError {
+    /// The book already contains the id
+    ExistingId(ExistingIdError),
+
+    /// The order displays no shares (with the id), so it could never execute
+    ZeroDisplay(u64),
+}
+
+impl From<ExistingIdError> for IcebergOrderError {
+    fn from(error: ExistingIdError) -> Self {
+        Self::ExistingId(error)
+    }
+}
+
+impl fmt::Display for IcebergOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ExistingId(error) => error.fmt(f),
+            Self::ZeroDisplay(id) => write!(f, "Iceberg order {} displays no shares", id),
+        }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // FUNCTIONS ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -81,9 +121,20 @@ impl Book {
             buy_limits: BTreeMap::new(),
             sell_limits: BTreeMap::new(),
             order_map: HashMap::new(),
+            stop_orders: Vec::new(),
+            pegged: HashMap::new(),
+            parked: Vec::new(),
+            last_price: None,
+            stop_executions: Vec::new(),
         }
     }
 
+    /// Price of the last market execution, if any.
+    #[must_use]
+    pub fn last_price(&self) -> Option<u64> {
+        self.last_price
+    }
+
     /// Adds order to Book
     /// `order_id` must be a unique id.
     /// `is_buy` order is buy or sell.
@@ -102,26 +153,162 @@ impl Book {
         limit_value: u64,
         timestamp: u64,
     ) -> Result<(), ExistingIdError> {
-        if self.order_map.contains_key(&order_id) {
+        if self.contains(order_id) {
             return Err(ExistingIdError::new(order_id));
         }
 
-        let order = Order::new(order_id, is_buy, shares, limit_value, timestamp);
-        self.order_map.insert(order_id, order);
+        self.insert(Order::new(order_id, is_buy, shares, limit_value, timestamp));
+        self.reprice_pegged();
 
-        let limit_tree = if is_buy {
-            &mut self.buy_limits
-        } else {
-            &mut self.sell_limits
-        };
+        Ok(())
+    }
 
-        if let Some(l) = limit_tree.get_mut(&limit_value) {
-            l.add(order_id);
-        } else {
-            let mut limit = Limit::new(limit_value);
-            limit.add(order_id);
-            let _ = limit_tree.insert(limit_value, limit);
-        };
+    /// Adds iceberg order to Book
+    /// Only `display` shares are at the limit at a time: when they are
+    /// executed, the next `display` shares (or fewer) are added at the back
+    /// of the limit. A `display` above `shares` shows the whole order.
+    /// Other parameters as in `add_order`.
+    ///
+    /// # Errors
+    ///
+    /// `IcebergOrderError::ExistingId` when order book already contains order with `order_id`,
+    /// and `IcebergOrderError::ZeroDisplay` when `display` is zero
+    pub fn add_iceberg_order(
+        &mut self,
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        display: u64,
+        limit_value: u64,
+        timestamp: u64,
+    ) -> Result<(), IcebergOrderError> {
+        if self.contains(order_id) {
+            return Err(ExistingIdError::new(order_id).into());
+        }
+
+        if display == 0 {
+            return Err(IcebergOrderError::ZeroDisplay(order_id));
+        }
+
+        self.insert(Order::iceberg(
+            order_id,
+            is_buy,
+            shares,
+            display,
+            limit_value,
+            timestamp,
+        ));
+        self.reprice_pegged();
+
+        Ok(())
+    }
+
+    /// Adds stop order to Book
+    /// Executed at market when a market execution is at or through
+    /// `stop_value` (at or above it to buy, at or below it to sell).
+    /// Other parameters as in `add_order`.
+    ///
+    /// # Errors
+    ///
+    /// `ExistingIdError` when order book already contains order with `order_id`
+    pub fn add_stop_order(
+        &mut self,
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        stop_value: u64,
+        timestamp: u64,
+    ) -> Result<(), ExistingIdError> {
+        self.add_stop(StopOrder::new(
+            order_id,
+            is_buy,
+            shares,
+            Some(stop_value),
+            None,
+            None,
+            timestamp,
+        ))
+    }
+
+    /// Adds stop-limit order to Book
+    /// Added as a limit order at `limit_value` when a market execution is at
+    /// or through `stop_value`.
+    /// Other parameters as in `add_order`.
+    ///
+    /// # Errors
+    ///
+    /// `ExistingIdError` when order book already contains order with `order_id`
+    pub fn add_stop_limit_order(
+        &mut self,
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        stop_value: u64,
+        limit_value: u64,
+        timestamp: u64,
+    ) -> Result<(), ExistingIdError> {
+        self.add_stop(StopOrder::new(
+            order_id,
+            is_buy,
+            shares,
+            Some(stop_value),
+            Some(limit_value),
+            None,
+            timestamp,
+        ))
+    }
+
+    /// Adds trailing stop order to Book
+    /// A stop order whose stop is `trail` away from the best market
+    /// execution since it was added (the highest to sell, the lowest to buy).
+    /// Other parameters as in `add_order`.
+    ///
+    /// # Errors
+    ///
+    /// `ExistingIdError` when order book already contains order with `order_id`
+    pub fn add_trailing_stop_order(
+        &mut self,
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        trail: u64,
+        timestamp: u64,
+    ) -> Result<(), ExistingIdError> {
+        let mut stop = StopOrder::new(order_id, is_buy, shares, None, None, Some(trail), timestamp);
+
+        if let Some(last_price) = self.last_price {
+            stop.follow(last_price);
+        }
+
+        self.add_stop(stop)
+    }
+
+    /// Adds pegged order to Book
+    /// A limit order at the best price of its side (bid to buy, ask to sell)
+    /// among the orders that are not pegged, `offset` away from the market.
+    /// Until there is such a price, the order is not at any limit.
+    /// Other parameters as in `add_order`.
+    ///
+    /// # Errors
+    ///
+    /// `ExistingIdError` when order book already contains order with `order_id`
+    pub fn add_pegged_order(
+        &mut self,
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        offset: u64,
+        timestamp: u64,
+    ) -> Result<(), ExistingIdError> {
+        if self.contains(order_id) {
+            return Err(ExistingIdError::new(order_id));
+        }
+
+        self.order_map
+            .insert(order_id, Order::new(order_id, is_buy, shares, 0, timestamp));
+        self.pegged.insert(order_id, offset);
+        self.parked.push(order_id);
+        self.reprice_pegged();
 
         Ok(())
     }
@@ -137,23 +324,24 @@ impl Book {
     ///
     /// Panics if limit is not in `limit_tree`.
     pub fn cancel_order(&mut self, order_id: u64) -> Result<(), NonExistingIdError> {
+        if let Some(index) = self.stop_orders.iter().position(|s| s.order_id == order_id) {
+            self.stop_orders.remove(index);
+            return Ok(());
+        }
+
         match self.order_map.remove(&order_id) {
             Some(o) => {
-                let limit_tree = if o.is_buy {
-                    &mut self.buy_limits
-                } else {
-                    &mut self.sell_limits
-                };
+                self.pegged.remove(&order_id);
 
-                let is_empty = match limit_tree.get_mut(&o.limit) {
-                    Some(l) => l.cancel(o.order_id),
-                    None => panic!(""),
-                };
-
-                if is_empty {
-                    limit_tree.remove(&o.limit);
+                match self.parked.iter().position(|id| *id == order_id) {
+                    Some(index) => {
+                        self.parked.remove(index);
+                    }
+                    None => self.unlink(&o),
                 }
 
+                self.reprice_pegged();
+
                 Ok(())
             }
             None => Err(NonExistingIdError::new(order_id)),
@@ -168,7 +356,164 @@ impl Book {
     /// shares.
     /// Second item is vector of tuples. First item in tuple is price executed, second item is
     /// number of shares executed at price.
+    /// Stop orders triggered by the execution are then executed (see
+    /// `take_stop_executions`), and pegged orders re-priced.
     pub fn execute_market_order(&mut self, shares: u64, is_buy: bool) -> (bool, Vec<(u64, u64)>) {
+        let result = self.match_market_order(shares, is_buy);
+
+        self.trigger_stops();
+        self.reprice_pegged();
+
+        result
+    }
+
+    /// Returns the executions of the stop orders triggered since the last
+    /// call, as order id and executions as in `execute_market_order` (empty
+    /// for stop-limit orders, which are added to the book instead).
+    pub fn take_stop_executions(&mut self) -> Vec<(u64, Vec<(u64, u64)>)> {
+        std::mem::take(&mut self.stop_executions)
+    }
+
+    fn contains(&self, order_id: u64) -> bool {
+        self.order_map.contains_key(&order_id)
+            || self.stop_orders.iter().any(|s| s.order_id == order_id)
+    }
+
+    fn add_stop(&mut self, stop: StopOrder) -> Result<(), ExistingIdError> {
+        if self.contains(stop.order_id) {
+            return Err(ExistingIdError::new(stop.order_id));
+        }
+
+        self.stop_orders.push(stop);
+
+        Ok(())
+    }
+
+    /// Adds order to `order_map` and to the back of its limit.
+    fn insert(&mut self, order: Order) {
+        let limit_tree = if order.is_buy {
+            &mut self.buy_limits
+        } else {
+            &mut self.sell_limits
+        };
+
+        limit_tree
+            .entry(order.limit)
+            .or_insert_with(|| Limit::new(order.limit))
+            .add(order.order_id);
+
+        self.order_map.insert(order.order_id, order);
+    }
+
+    /// Removes order from its limit (but not from `order_map`).
+    fn unlink(&mut self, order: &Order) {
+        let limit_tree = if order.is_buy {
+            &mut self.buy_limits
+        } else {
+            &mut self.sell_limits
+        };
+
+        let is_empty = match limit_tree.get_mut(&order.limit) {
+            Some(l) => l.cancel(order.order_id),
+            None => panic!(""),
+        };
+
+        if is_empty {
+            limit_tree.remove(&order.limit);
+        }
+    }
+
+    /// Executes triggered stop orders, in the order they were added, until
+    /// none is triggered by the last price.
+    fn trigger_stops(&mut self) {
+        while let Some(last_price) = self.last_price {
+            for stop in &mut self.stop_orders {
+                stop.follow(last_price);
+            }
+
+            let Some(index) = self
+                .stop_orders
+                .iter()
+                .position(|s| s.is_triggered(last_price))
+            else {
+                break;
+            };
+
+            let stop = self.stop_orders.remove(index);
+
+            if let Some(limit) = stop.limit {
+                self.insert(Order::new(
+                    stop.order_id,
+                    stop.is_buy,
+                    stop.shares,
+                    limit,
+                    stop.timestamp,
+                ));
+                self.stop_executions.push((stop.order_id, vec![]));
+            } else {
+                let (_, executions) = self.match_market_order(stop.shares, stop.is_buy);
+                self.stop_executions.push((stop.order_id, executions));
+            }
+        }
+    }
+
+    /// Best price of a side among the orders that are not pegged.
+    fn reference_price(&self, is_buy: bool) -> Option<u64> {
+        let is_reference = |limit: &&Limit| limit.orders().any(|id| !self.pegged.contains_key(id));
+
+        let limit = if is_buy {
+            self.buy_limits.values().rev().find(is_reference)
+        } else {
+            self.sell_limits.values().find(is_reference)
+        };
+
+        limit.map(|l| l.limit_price)
+    }
+
+    /// Moves pegged orders whose price changed to the back of their new limit.
+    fn reprice_pegged(&mut self) {
+        self.pegged.retain(|id, _| self.order_map.contains_key(id));
+
+        let mut ids: Vec<u64> = self.pegged.keys().copied().collect();
+        ids.sort_unstable_by_key(|id| (self.order_map[id].timestamp, *id));
+
+        for order_id in ids {
+            let offset = self.pegged[&order_id];
+            let order = &self.order_map[&order_id];
+
+            let Some(reference) = self.reference_price(order.is_buy) else {
+                continue;
+            };
+
+            let price = if order.is_buy {
+                reference.saturating_sub(offset)
+            } else {
+                reference + offset
+            };
+
+            let parked = self.parked.iter().position(|id| *id == order_id);
+
+            if parked.is_none() && order.limit == price {
+                continue;
+            }
+
+            let order = self.order_map.remove(&order_id).unwrap();
+
+            match parked {
+                Some(index) => {
+                    self.parked.remove(index);
+                }
+                None => self.unlink(&order),
+            }
+
+            self.insert(Order {
+                limit: price,
+                ..order
+            });
+        }
+    }
+
+    fn match_market_order(&mut self, shares: u64, is_buy: bool) -> (bool, Vec<(u64, u64)>) {
         let mut shares_left = shares;
         let mut result: Vec<(u64, u64)> = vec![];
 
@@ -193,6 +538,7 @@ impl Book {
 
             shares_left -= shares_executed;
             result.push((limit.limit_price, shares_executed));
+            self.last_price = Some(limit.limit_price);
 
             if is_empty {
                 if is_buy {
@@ -46,21 +46,31 @@ impl Limit {
         self.orders.is_empty()
     }
 
+    pub fn orders(&self) -> impl Iterator<Item = &u64> {
+        self.orders.iter()
+    }
+
     pub fn execute(&mut self, shares: u64, order_map: &mut HashMap<u64, Order>) -> (u64, bool) {
         let mut executed_shares = 0;
 
         while executed_shares < shares && !self.orders.is_empty() {
-            let order_id = self.orders.front().unwrap();
-            let order_shares = order_map.get(order_id).unwrap().shares;
+            let order_id = *self.orders.front().unwrap();
+            let order = order_map.get_mut(&order_id).unwrap();
 
-            if order_shares > shares - executed_shares {
-                let order = order_map.get_mut(order_id).unwrap();
+            if order.shares > shares - executed_shares {
                 order.shares -= shares - executed_shares;
                 executed_shares += shares - executed_shares;
+            } else if order.hidden > 0 {
+                // Refresh the displayed quantity of an iceberg, which loses
+                // its time priority.
+                executed_shares += order.shares;
+                order.shares = order.display.min(order.hidden);
+                order.hidden -= order.shares;
+                self.orders.rotate_left(1);
             } else {
-                order_map.remove(order_id);
+                executed_shares += order.shares;
+                order_map.remove(&order_id);
                 self.orders.pop_front();
-                executed_shares += order_shares;
             }
         }
 
@@ -18,6 +18,10 @@ pub struct Order {
     pub shares: u64,
     pub limit: u64,
     pub timestamp: u64,
+    /// Quantity shown at a time (`shares` for orders that are not icebergs).
+    pub display: u64,
+    /// Quantity not shown yet, for iceberg orders.
+    pub hidden: u64,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -32,6 +36,26 @@ impl Order {
             shares,
             limit,
             timestamp,
+            display: shares,
+            hidden: 0,
+        }
+    }
+
+    pub fn iceberg(
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        display: u64,
+        limit: u64,
+        timestamp: u64,
+    ) -> Self {
+        let shown = display.min(shares);
+
+        Self {
+            shares: shown,
+            display,
+            hidden: shares - shown,
+            ..Self::new(order_id, is_buy, shares, limit, timestamp)
         }
     }
 }
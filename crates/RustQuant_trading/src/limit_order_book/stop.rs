@@ -0,0 +1,75 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[derive(Debug)]
+pub struct StopOrder {
+    pub order_id: u64,
+    pub is_buy: bool,
+    pub shares: u64,
+    /// Trigger price (`None` for a trailing stop before the first trade).
+    pub stop: Option<u64>,
+    /// Limit price of the order entered when triggered (market if `None`).
+    pub limit: Option<u64>,
+    /// Distance of the stop from the last price, for trailing stops.
+    pub trail: Option<u64>,
+    pub timestamp: u64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StopOrder {
+    pub fn new(
+        order_id: u64,
+        is_buy: bool,
+        shares: u64,
+        stop: Option<u64>,
+        limit: Option<u64>,
+        trail: Option<u64>,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            order_id,
+            is_buy,
+            shares,
+            stop,
+            limit,
+            trail,
+            timestamp,
+        }
+    }
+
+    /// Moves the stop of a trailing stop towards the last price, never away
+    /// from it.
+    pub fn follow(&mut self, last_price: u64) {
+        let Some(trail) = self.trail else {
+            return;
+        };
+
+        self.stop = Some(match (self.is_buy, self.stop) {
+            (true, Some(stop)) => stop.min(last_price + trail),
+            (true, None) => last_price + trail,
+            (false, Some(stop)) => stop.max(last_price.saturating_sub(trail)),
+            (false, None) => last_price.saturating_sub(trail),
+        });
+    }
+
+    /// Whether a trade at the last price reaches the stop.
+    pub fn is_triggered(&self, last_price: u64) -> bool {
+        match self.stop {
+            Some(stop) if self.is_buy => last_price >= stop,
+            Some(stop) => last_price <= stop,
+            None => false,
+        }
+    }
+}
@@ -8,7 +8,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 #[cfg(test)]
-use super::Book;
+use super::{Book, IcebergOrderError};
 
 #[test]
 fn add_order_buy() {
@@ -121,3 +121,137 @@ fn unable_execute_market_buy() {
 
     assert!(!book.order_map.contains_key(&1));
 }
+
+#[test]
+fn reject_iceberg_without_display() {
+    let mut book = Book::new();
+
+    assert!(matches!(
+        book.add_iceberg_order(1, false, 10, 0, 10, 1000),
+        Err(IcebergOrderError::ZeroDisplay(1))
+    ));
+    assert!(!book.order_map.contains_key(&1));
+
+    // A market order against the limit would otherwise never finish.
+    book.add_order(2, false, 5, 10, 1001).unwrap();
+    assert!(matches!(
+        book.add_iceberg_order(2, false, 10, 3, 10, 1002),
+        Err(IcebergOrderError::ExistingId(_))
+    ));
+    assert_eq!(book.execute_market_order(5, true).1, vec![(10, 5)]);
+}
+
+#[test]
+fn execute_iceberg_refresh() {
+    let mut book = Book::new();
+
+    book.add_iceberg_order(1, false, 10, 3, 10, 1000).unwrap();
+    book.add_order(2, false, 5, 10, 1001).unwrap();
+
+    assert_eq!(book.order_map.get(&1).unwrap().shares, 3);
+    assert_eq!(book.order_map.get(&1).unwrap().hidden, 7);
+
+    // The displayed 3 shares are executed, and the refresh goes behind order 2.
+    let (is_executed, share_status) = book.execute_market_order(4, true);
+
+    assert!(is_executed);
+    assert_eq!(share_status, vec![(10, 4)]);
+    assert_eq!(book.order_map.get(&1).unwrap().shares, 3);
+    assert_eq!(book.order_map.get(&1).unwrap().hidden, 4);
+    assert_eq!(book.order_map.get(&2).unwrap().shares, 4);
+
+    // Order 2 first, then the rest of the iceberg.
+    let (is_executed, share_status) = book.execute_market_order(20, true);
+
+    assert!(!is_executed);
+    assert_eq!(share_status, vec![(10, 11)]);
+    assert!(book.order_map.is_empty());
+    assert!(book.sell_limits.is_empty());
+}
+
+#[test]
+fn trigger_stop_orders() {
+    let mut book = Book::new();
+
+    book.add_order(1, false, 3, 10, 1000).unwrap();
+    book.add_order(2, false, 5, 11, 1000).unwrap();
+    book.add_stop_order(3, true, 3, 10, 1000).unwrap();
+    book.add_stop_limit_order(4, true, 2, 11, 12, 1000).unwrap();
+
+    assert!(book.add_order(3, true, 1, 1, 1000).is_err());
+    assert!(!book.order_map.contains_key(&3));
+
+    // Trades at 10, which triggers the stop, which trades at 10 and 11,
+    // which triggers the stop-limit.
+    book.execute_market_order(1, true);
+
+    assert_eq!(book.last_price(), Some(11));
+    assert_eq!(
+        book.take_stop_executions(),
+        vec![(3, vec![(10, 2), (11, 1)]), (4, vec![])]
+    );
+    assert!(book.take_stop_executions().is_empty());
+    assert!(book.buy_limits.contains_key(&12));
+    assert_eq!(book.order_map.get(&4).unwrap().shares, 2);
+}
+
+#[test]
+fn trigger_trailing_stop_order() {
+    let mut book = Book::new();
+
+    book.add_order(1, true, 10, 100, 1000).unwrap();
+    book.add_order(2, false, 10, 105, 1000).unwrap();
+    book.add_trailing_stop_order(3, false, 5, 3, 1000).unwrap();
+
+    // Trades at 105, which moves the stop to 102.
+    book.execute_market_order(1, true);
+    assert!(book.take_stop_executions().is_empty());
+
+    // Trades at 100, through the stop.
+    book.execute_market_order(1, false);
+
+    assert_eq!(book.take_stop_executions(), vec![(3, vec![(100, 5)])]);
+    assert_eq!(book.order_map.get(&1).unwrap().shares, 4);
+
+    // Cancelling a stop order.
+    book.add_stop_order(4, false, 1, 90, 1000).unwrap();
+    book.cancel_order(4).unwrap();
+    assert!(book.cancel_order(4).is_err());
+}
+
+#[test]
+fn reprice_pegged_orders() {
+    let mut book = Book::new();
+
+    book.add_order(1, true, 5, 100, 1000).unwrap();
+    book.add_pegged_order(2, true, 5, 1, 1000).unwrap();
+
+    assert_eq!(book.order_map.get(&2).unwrap().limit, 99);
+    assert!(book
+        .buy_limits
+        .get(&99)
+        .unwrap()
+        .orders()
+        .any(|id| *id == 2));
+
+    // Follows the best bid up and back down.
+    book.add_order(3, true, 5, 101, 1000).unwrap();
+    assert_eq!(book.order_map.get(&2).unwrap().limit, 100);
+    assert!(!book.buy_limits.contains_key(&99));
+
+    book.cancel_order(3).unwrap();
+    assert_eq!(book.order_map.get(&2).unwrap().limit, 99);
+
+    // Without an ask, a pegged sell order waits outside the limits.
+    book.add_pegged_order(4, false, 5, 0, 1000).unwrap();
+    assert!(book.sell_limits.is_empty());
+
+    book.add_order(5, false, 5, 110, 1000).unwrap();
+    assert_eq!(book.order_map.get(&4).unwrap().limit, 110);
+    assert_eq!(book.sell_limits.get(&110).unwrap().orders().count(), 2);
+
+    book.cancel_order(2).unwrap();
+    book.cancel_order(4).unwrap();
+    assert!(!book.buy_limits.contains_key(&99));
+    assert_eq!(book.sell_limits.get(&110).unwrap().orders().count(), 1);
+}
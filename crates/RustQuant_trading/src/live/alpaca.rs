@@ -0,0 +1,640 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::Broker;
+use crate::backtest::{Account, Fill, Holding, MarketEvent, OrderRequest};
+use crate::order::OrderID;
+use crate::order_lifespan::OrderTimeInForce;
+use crate::order_side::OrderSide;
+use crate::order_type::OrderType;
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Broker for the [Alpaca](https://alpaca.markets) trading API (v2), by
+/// default on a paper trading account.
+///
+/// Market, limit, stop, stop-limit, and trailing stop orders are supported.
+/// Fills are found by polling the fill activities of the account at each
+/// update, page by page back to the last fill reported, and only those of
+/// orders submitted through this broker are reported. Alpaca does not report realised P&L by position,
+/// so it is zero in the holdings.
+///
+/// Quantities are whole numbers of units: fractional positions or fills
+/// are an error rather than being rounded.
+#[derive(Clone)]
+pub struct AlpacaBroker {
+    key_id: String,
+    secret_key: String,
+
+    /// Base URL of the API.
+    pub base_url: String,
+
+    /// Time in force of the orders (day orders if `None`).
+    pub time_in_force: Option<OrderTimeInForce>,
+
+    order_ids: HashMap<String, OrderID>,
+    alpaca_ids: HashMap<OrderID, String>,
+    /// Id of the newest fill activity seen.
+    last_fill: Option<String>,
+    next_id: OrderID,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Number of fill activities requested per page.
+const FILL_PAGE_SIZE: usize = 100;
+
+impl AlpacaBroker {
+    /// Base URL of the paper trading API.
+    pub const PAPER_URL: &'static str = "https://paper-api.alpaca.markets";
+
+    /// Environment variable of the API key ID, read by
+    /// [`AlpacaBroker::paper_from_env`].
+    pub const KEY_ID_VARIABLE: &'static str = "APCA_API_KEY_ID";
+
+    /// Environment variable of the API secret key, read by
+    /// [`AlpacaBroker::paper_from_env`].
+    pub const SECRET_KEY_VARIABLE: &'static str = "APCA_API_SECRET_KEY";
+
+    /// Create a broker for a paper trading account.
+    #[must_use]
+    pub fn paper(key_id: &str, secret_key: &str) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            secret_key: secret_key.to_string(),
+            base_url: Self::PAPER_URL.to_string(),
+            time_in_force: None,
+            order_ids: HashMap::new(),
+            alpaca_ids: HashMap::new(),
+            last_fill: None,
+            next_id: 1,
+        }
+    }
+
+    /// Create a broker for a paper trading account, with the keys from the
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustQuantError::MissingInput` if a variable is not set.
+    pub fn paper_from_env() -> Result<Self, RustQuantError> {
+        let variable = |name: &str| {
+            std::env::var(name)
+                .map_err(|_| RustQuantError::MissingInput(format!("Set {name} to an API key.")))
+        };
+
+        Ok(Self::paper(
+            &variable(Self::KEY_ID_VARIABLE)?,
+            &variable(Self::SECRET_KEY_VARIABLE)?,
+        ))
+    }
+
+    /// Use another base URL, e.g. the live trading API.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the time in force of the orders.
+    #[must_use]
+    pub fn with_time_in_force(mut self, time_in_force: OrderTimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Send an authenticated request, and get the JSON response (null if
+    /// empty).
+    fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value, RustQuantError> {
+        let mut request = reqwest::Client::new()
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("APCA-API-KEY-ID", &self.key_id)
+            .header("APCA-API-SECRET-KEY", &self.secret_key);
+
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        let response = request.send();
+
+        tokio_test::block_on(async {
+            let text = response.await?.error_for_status()?.text().await?;
+
+            if text.trim().is_empty() {
+                Ok(Value::Null)
+            } else {
+                Ok::<Value, RustQuantError>(serde_json::from_str(&text)?)
+            }
+        })
+    }
+}
+
+impl fmt::Debug for AlpacaBroker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlpacaBroker")
+            .field("key_id", &self.key_id)
+            .field("secret_key", &"<redacted>")
+            .field("base_url", &self.base_url)
+            .field("time_in_force", &self.time_in_force)
+            .field("orders", &self.order_ids.len())
+            .finish()
+    }
+}
+
+impl Broker for AlpacaBroker {
+    fn submit(&mut self, order: OrderRequest) -> Result<OrderID, RustQuantError> {
+        let body = order_body(&order, self.time_in_force)?;
+        let response = self.request(Method::POST, "/v2/orders", Some(&body))?;
+
+        let alpaca_id = response
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RustQuantError::MissingInput("Order without an id.".to_string()))?;
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.order_ids.insert(alpaca_id.to_string(), id);
+        self.alpaca_ids.insert(id, alpaca_id.to_string());
+
+        Ok(id)
+    }
+
+    fn cancel(&mut self, id: OrderID) -> Result<(), RustQuantError> {
+        let alpaca_id = self
+            .alpaca_ids
+            .get(&id)
+            .ok_or_else(|| RustQuantError::InvalidArgument(format!("No order {id}.")))?;
+
+        self.request(Method::DELETE, &format!("/v2/orders/{alpaca_id}"), None)?;
+
+        Ok(())
+    }
+
+    fn positions(&self) -> Result<HashMap<String, Holding>, RustQuantError> {
+        let positions = self.request(Method::GET, "/v2/positions", None)?;
+
+        Ok(parse_positions(&positions)?
+            .into_iter()
+            .map(|(symbol, holding, _)| (symbol, holding))
+            .collect())
+    }
+
+    fn account(&self) -> Result<Account, RustQuantError> {
+        let account = self.request(Method::GET, "/v2/account", None)?;
+        let positions = self.request(Method::GET, "/v2/positions", None)?;
+        let orders = self.request(Method::GET, "/v2/orders?status=open&limit=500", None)?;
+
+        parse_account(&account, &positions, &orders)
+    }
+
+    fn update(&mut self, _event: &MarketEvent) -> Result<Vec<Fill>, RustQuantError> {
+        let activities = fill_activities(self.last_fill.as_deref(), |page_token| {
+            let mut path =
+                format!("/v2/account/activities/FILL?direction=desc&page_size={FILL_PAGE_SIZE}");
+
+            if let Some(page_token) = page_token {
+                path.push_str(&format!("&page_token={page_token}"));
+            }
+
+            self.request(Method::GET, &path, None)
+        })?;
+
+        parse_fills(&activities, &self.order_ids, &mut self.last_fill)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Body of a new order request.
+fn order_body(
+    order: &OrderRequest,
+    time_in_force: Option<OrderTimeInForce>,
+) -> Result<Value, RustQuantError> {
+    let unsupported = |what: String| {
+        RustQuantError::InvalidArgument(format!("{what} orders are not supported by Alpaca."))
+    };
+
+    let (order_type, prices) = match order.order_type {
+        OrderType::Market => ("market", vec![]),
+        OrderType::Limit => ("limit", vec![("limit_price", order.limit_price)]),
+        OrderType::Stop => ("stop", vec![("stop_price", order.stop_price)]),
+        OrderType::StopLimit => (
+            "stop_limit",
+            vec![
+                ("stop_price", order.stop_price),
+                ("limit_price", order.limit_price),
+            ],
+        ),
+        OrderType::TrailingStop => (
+            "trailing_stop",
+            vec![("trail_price", order.trailing_amount)],
+        ),
+        order_type => return Err(unsupported(order_type.to_string())),
+    };
+
+    let time_in_force = match time_in_force {
+        None => "day",
+        Some(OrderTimeInForce::GoodTillCancelled) => "gtc",
+        Some(OrderTimeInForce::ImmediateOrCancel) => "ioc",
+        Some(OrderTimeInForce::FillOrKill) => "fok",
+        Some(time_in_force) => return Err(unsupported(time_in_force.to_string())),
+    };
+
+    let mut body = json!({
+        "symbol": order.symbol,
+        "qty": order.quantity.to_string(),
+        "side": side_name(order.side),
+        "type": order_type,
+        "time_in_force": time_in_force,
+    });
+
+    for (key, price) in prices {
+        let price = price.ok_or_else(|| {
+            RustQuantError::MissingInput(format!("{} order without a {key}.", order.order_type))
+        })?;
+
+        body[key] = json!(price.to_string());
+    }
+
+    Ok(body)
+}
+
+/// Positions, with their current prices.
+fn parse_positions(positions: &Value) -> Result<Vec<(String, Holding, f64)>, RustQuantError> {
+    let positions = positions
+        .as_array()
+        .ok_or_else(|| RustQuantError::MissingInput("No positions.".to_string()))?;
+
+    positions
+        .iter()
+        .map(|position| {
+            let symbol = text(position, "symbol")?;
+            let quantity = i64::try_from(units(position, "qty")?)
+                .map_err(|_| RustQuantError::InvalidArgument("Position too large.".to_string()))?;
+            let sign = if text(position, "side")? == "short" {
+                -1
+            } else {
+                1
+            };
+
+            let holding = Holding {
+                quantity: sign * quantity,
+                average_price: number(position, "avg_entry_price")?,
+                realized_pnl: 0.0,
+            };
+
+            Ok((
+                symbol.to_string(),
+                holding,
+                number(position, "current_price")?,
+            ))
+        })
+        .collect()
+}
+
+/// Account from the responses of the account, positions, and open orders.
+fn parse_account(
+    account: &Value,
+    positions: &Value,
+    orders: &Value,
+) -> Result<Account, RustQuantError> {
+    let mut state = Account::new(number(account, "cash")?);
+
+    for (symbol, holding, price) in parse_positions(positions)? {
+        state.prices.insert(symbol.clone(), price);
+        state.positions.insert(symbol, holding);
+    }
+
+    state.open_orders = orders.as_array().map_or(0, Vec::len);
+
+    Ok(state)
+}
+
+/// Fills of the given orders, in time order, that were not reported yet.
+/// Fill activities, the most recent first, back to the activity `last_fill`
+/// (the newest one seen), or to the first activity of the account.
+///
+/// `fetch` gets a page of activities, after the given page token.
+fn fill_activities<F>(last_fill: Option<&str>, mut fetch: F) -> Result<Vec<Value>, RustQuantError>
+where
+    F: FnMut(Option<&str>) -> Result<Value, RustQuantError>,
+{
+    let mut activities: Vec<Value> = Vec::new();
+
+    loop {
+        let page_token = match activities.last() {
+            Some(activity) => Some(text(activity, "id")?.to_string()),
+            None => None,
+        };
+
+        let page = fetch(page_token.as_deref())?;
+        let page = page
+            .as_array()
+            .ok_or_else(|| RustQuantError::MissingInput("No fill activities.".to_string()))?;
+
+        // Activity ids are ordered by time.
+        let mut seen = false;
+        for activity in page {
+            if last_fill
+                .is_some_and(|last_fill| text(activity, "id").is_ok_and(|id| id <= last_fill))
+            {
+                seen = true;
+                break;
+            }
+
+            activities.push(activity.clone());
+        }
+
+        if seen || page.len() < FILL_PAGE_SIZE {
+            return Ok(activities);
+        }
+    }
+}
+
+/// New fills of the orders in `order_ids`, oldest first, from activities
+/// the most recent first.
+///
+/// Activities up to `last_fill` (the id of the newest activity seen) are
+/// skipped, and it is moved to the newest of `activities`.
+fn parse_fills(
+    activities: &[Value],
+    order_ids: &HashMap<String, OrderID>,
+    last_fill: &mut Option<String>,
+) -> Result<Vec<Fill>, RustQuantError> {
+    let mut fills = Vec::new();
+
+    for activity in activities.iter().rev() {
+        let id = text(activity, "id")?;
+
+        if last_fill
+            .as_deref()
+            .is_some_and(|last_fill| id <= last_fill)
+        {
+            continue;
+        }
+
+        let Some(&order_id) = order_ids.get(text(activity, "order_id")?) else {
+            continue;
+        };
+
+        let side = match text(activity, "side")? {
+            "buy" => OrderSide::BID,
+            _ => OrderSide::ASK,
+        };
+
+        let timestamp = OffsetDateTime::parse(text(activity, "transaction_time")?, &Rfc3339)
+            .map_err(|error| RustQuantError::InvalidArgument(error.to_string()))?;
+
+        fills.push(Fill {
+            order_id,
+            symbol: text(activity, "symbol")?.to_string(),
+            side,
+            quantity: units(activity, "qty")?,
+            price: number(activity, "price")?,
+            commission: 0.0,
+            timestamp,
+        });
+    }
+
+    // Only mark the activities once they have all been parsed, so that an
+    // invalid fill fails again at the next update.
+    if let Some(activity) = activities.first() {
+        let id = text(activity, "id")?;
+
+        if last_fill.as_deref().is_none_or(|last_fill| id > last_fill) {
+            *last_fill = Some(id.to_string());
+        }
+    }
+
+    Ok(fills)
+}
+
+fn side_name(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::BID => "buy",
+        OrderSide::ASK => "sell",
+    }
+}
+
+/// String field of a JSON object.
+fn text<'a>(value: &'a Value, key: &str) -> Result<&'a str, RustQuantError> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RustQuantError::MissingInput(format!("No {key}.")))
+}
+
+/// Number field of a JSON object, which Alpaca sends as a string.
+fn number(value: &Value, key: &str) -> Result<f64, RustQuantError> {
+    match value.get(key) {
+        Some(Value::String(number)) => number
+            .parse()
+            .map_err(|_| RustQuantError::InvalidArgument(format!("Invalid {key}: {number}."))),
+        Some(Value::Number(number)) => number
+            .as_f64()
+            .ok_or_else(|| RustQuantError::InvalidArgument(format!("Invalid {key}."))),
+        _ => Err(RustQuantError::MissingInput(format!("No {key}."))),
+    }
+}
+
+/// Whole number of units of a JSON object, ignoring the sign.
+fn units(value: &Value, key: &str) -> Result<u64, RustQuantError> {
+    let quantity = number(value, key)?.abs();
+
+    if quantity.fract() != 0.0 || quantity >= u64::MAX as f64 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "Invalid {key} {quantity}: not a whole number of units."
+        )));
+    }
+
+    Ok(quantity as u64)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_alpaca {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_order_body() {
+        let order = OrderRequest::stop_limit("AAPL", OrderSide::ASK, 10, 180.0, 179.5);
+        let body = order_body(&order, Some(OrderTimeInForce::GoodTillCancelled)).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "symbol": "AAPL",
+                "qty": "10",
+                "side": "sell",
+                "type": "stop_limit",
+                "time_in_force": "gtc",
+                "stop_price": "180",
+                "limit_price": "179.5",
+            })
+        );
+
+        let order = OrderRequest::trailing_stop("AAPL", OrderSide::BID, 1, 2.5);
+        let body = order_body(&order, None).unwrap();
+
+        assert_eq!(body["time_in_force"], "day");
+        assert_eq!(body["trail_price"], "2.5");
+
+        let order = OrderRequest::iceberg("AAPL", OrderSide::BID, 100, 180.0, 10);
+        assert!(order_body(&order, None).is_err());
+
+        let order = OrderRequest::market("AAPL", OrderSide::BID, 1);
+        assert!(order_body(&order, Some(OrderTimeInForce::AllOrNone)).is_err());
+    }
+
+    #[test]
+    fn test_parse_account() {
+        let account = json!({ "id": "abc", "cash": "5000.25", "equity": "8060.25" });
+        let positions = json!([
+            {
+                "symbol": "AAPL", "qty": "10", "side": "long",
+                "avg_entry_price": "150.0", "current_price": "156.0"
+            },
+            {
+                "symbol": "TSLA", "qty": "-5", "side": "short",
+                "avg_entry_price": "200.0", "current_price": "190.0"
+            }
+        ]);
+        let orders = json!([{ "id": "o1" }, { "id": "o2" }]);
+
+        let account = parse_account(&account, &positions, &orders).unwrap();
+
+        assert_approx_equal!(account.cash, 5000.25, 1e-12);
+        assert_eq!(account.position("AAPL"), 10);
+        assert_eq!(account.position("TSLA"), -5);
+        assert_approx_equal!(account.positions["AAPL"].average_price, 150.0, 1e-12);
+        assert_approx_equal!(account.equity(), 5000.25 + 1560.0 - 950.0, 1e-9);
+        assert_eq!(account.open_orders, 2);
+
+        assert!(parse_account(&json!({}), &positions, &orders).is_err());
+
+        let fractional = json!([{
+            "symbol": "AAPL", "qty": "0.5", "side": "long",
+            "avg_entry_price": "150.0", "current_price": "156.0"
+        }]);
+        assert!(parse_account(&json!({ "cash": "0" }), &fractional, &orders).is_err());
+    }
+
+    #[test]
+    fn test_parse_fills() {
+        let activities = json!([
+            {
+                "id": "f3", "order_id": "b", "symbol": "AAPL", "side": "sell",
+                "qty": "5", "price": "151.5", "transaction_time": "2024-03-01T15:00:00Z"
+            },
+            {
+                "id": "f2", "order_id": "other", "symbol": "MSFT", "side": "buy",
+                "qty": "1", "price": "400", "transaction_time": "2024-03-01T14:45:00Z"
+            },
+            {
+                "id": "f1", "order_id": "a", "symbol": "AAPL", "side": "buy",
+                "qty": "10", "price": "150.25", "transaction_time": "2024-03-01T14:30:00.123Z"
+            }
+        ]);
+        let activities = activities.as_array().unwrap();
+        let order_ids = HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+        let mut last_fill = None;
+
+        let fills = parse_fills(activities, &order_ids, &mut last_fill).unwrap();
+
+        // Oldest first, without the fills of other orders.
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].order_id, 1);
+        assert_eq!(fills[0].side, OrderSide::BID);
+        assert_eq!(fills[0].quantity, 10);
+        assert_approx_equal!(fills[0].price, 150.25, 1e-12);
+        assert_eq!(fills[1].signed_quantity(), -5);
+        assert!(fills[0].timestamp < fills[1].timestamp);
+
+        // Fills are reported once.
+        assert_eq!(last_fill.as_deref(), Some("f3"));
+        assert!(parse_fills(activities, &order_ids, &mut last_fill)
+            .unwrap()
+            .is_empty());
+
+        let fractional = json!([{
+            "id": "f4", "order_id": "a", "symbol": "AAPL", "side": "buy",
+            "qty": "2.5", "price": "150", "transaction_time": "2024-03-01T16:00:00Z"
+        }]);
+        let fractional = fractional.as_array().unwrap();
+        assert!(parse_fills(fractional, &order_ids, &mut last_fill).is_err());
+
+        // An invalid fill is not marked as reported, and fails again.
+        assert_eq!(last_fill.as_deref(), Some("f3"));
+        assert!(parse_fills(fractional, &order_ids, &mut last_fill).is_err());
+    }
+
+    #[test]
+    fn test_fill_activities() {
+        // 250 activities, the most recent first.
+        let ids: Vec<String> = (0..250).rev().map(|i| format!("{i:04}")).collect();
+
+        let mut tokens = Vec::new();
+        let mut fetch = |page_token: Option<&str>| {
+            tokens.push(page_token.map(str::to_string));
+
+            let start = page_token.map_or(0, |token| {
+                ids.iter().position(|id| id == token).unwrap() + 1
+            });
+            let page = &ids[start..(start + FILL_PAGE_SIZE).min(ids.len())];
+
+            Ok(Value::from_iter(page.iter().map(|id| json!({ "id": id }))))
+        };
+
+        // Without a last fill, every page is read.
+        let activities = fill_activities(None, &mut fetch).unwrap();
+        assert_eq!(activities.len(), 250);
+        assert_eq!(activities[249]["id"], "0000");
+
+        // Otherwise, only back to the last fill.
+        let activities = fill_activities(Some("0120"), &mut fetch).unwrap();
+        assert_eq!(activities.len(), 129);
+        assert_eq!(activities[128]["id"], "0121");
+
+        assert_eq!(
+            tokens,
+            [None, Some("0150"), Some("0050"), None, Some("0150")].map(|t| t.map(str::to_string))
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_secret_key() {
+        let broker = AlpacaBroker::paper("key", "very-secret");
+        let debug = format!("{broker:?}");
+
+        assert!(debug.contains("key"));
+        assert!(!debug.contains("very-secret"));
+    }
+}
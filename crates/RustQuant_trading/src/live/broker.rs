@@ -0,0 +1,83 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::{Account, Fill, Holding, MarketEvent, OrderRequest, SimulatedBroker};
+use crate::order::OrderID;
+use std::collections::HashMap;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Broker that executes orders: simulated, paper, or live.
+pub trait Broker {
+    /// Submit an order, and return its identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the order is invalid, or rejected by the broker.
+    fn submit(&mut self, order: OrderRequest) -> Result<OrderID, RustQuantError>;
+
+    /// Cancel an open order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the order is not open, or cannot be cancelled.
+    fn cancel(&mut self, id: OrderID) -> Result<(), RustQuantError>;
+
+    /// Positions, by symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker cannot be reached.
+    fn positions(&self) -> Result<HashMap<String, Holding>, RustQuantError>;
+
+    /// Cash, positions, last prices, and number of open orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker cannot be reached.
+    fn account(&self) -> Result<Account, RustQuantError>;
+
+    /// Update the broker with a market event, and return the fills of the
+    /// orders submitted through it since the last update.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker cannot be reached.
+    fn update(&mut self, event: &MarketEvent) -> Result<Vec<Fill>, RustQuantError>;
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The simulated broker fills the open orders against each event.
+impl Broker for SimulatedBroker {
+    fn submit(&mut self, order: OrderRequest) -> Result<OrderID, RustQuantError> {
+        SimulatedBroker::submit(self, order)
+    }
+
+    fn cancel(&mut self, id: OrderID) -> Result<(), RustQuantError> {
+        SimulatedBroker::cancel(self, id)
+    }
+
+    fn positions(&self) -> Result<HashMap<String, Holding>, RustQuantError> {
+        Ok(SimulatedBroker::account(self).positions.clone())
+    }
+
+    fn account(&self) -> Result<Account, RustQuantError> {
+        Ok(SimulatedBroker::account(self).clone())
+    }
+
+    fn update(&mut self, event: &MarketEvent) -> Result<Vec<Fill>, RustQuantError> {
+        Ok(self.process(event))
+    }
+}
@@ -0,0 +1,54 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Live and paper trading.
+//!
+//! A [`Broker`] submits and cancels orders, and reports the fills and the
+//! state of the account. The [`LiveTrader`] runs a backtest
+//! [`Strategy`](crate::backtest::Strategy) with any broker, so the same
+//! strategy can be backtested with the
+//! [`SimulatedBroker`](crate::backtest::SimulatedBroker), then traded on a
+//! paper (or live) account, e.g. with the [`AlpacaBroker`].
+//!
+//! ```no_run
+//! use RustQuant::trading::backtest::*;
+//! use RustQuant::trading::live::*;
+//! use RustQuant::trading::order_side::OrderSide;
+//!
+//! struct BuyAndHold;
+//!
+//! impl Strategy for BuyAndHold {
+//!     fn on_event(&mut self, event: &MarketEvent, account: &Account) -> Vec<OrderRequest> {
+//!         if account.position(event.symbol()) == 0 && account.open_orders == 0 {
+//!             vec![OrderRequest::market(event.symbol(), OrderSide::BID, 10)]
+//!         } else {
+//!             vec![]
+//!         }
+//!     }
+//! }
+//!
+//! # fn events() -> Vec<MarketEvent> { vec![] }
+//! // Market events from a live feed.
+//! let events = events();
+//!
+//! let broker = AlpacaBroker::paper_from_env().unwrap();
+//! LiveTrader::new(broker).run(&mut BuyAndHold, events).unwrap();
+//! ```
+
+/// Broker trait.
+pub mod broker;
+pub use broker::*;
+
+/// Runner of strategies with a broker.
+pub mod trader;
+pub use trader::*;
+
+/// Alpaca REST API broker.
+pub mod alpaca;
+pub use alpaca::*;
@@ -0,0 +1,125 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::Broker;
+use crate::backtest::{MarketEvent, Strategy};
+use crate::order::OrderID;
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Runs a strategy with a broker, as market events arrive.
+#[derive(Debug, Clone)]
+pub struct LiveTrader<B: Broker> {
+    /// Broker that executes the orders.
+    pub broker: B,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<B: Broker> LiveTrader<B> {
+    /// Create a trader with a broker.
+    #[must_use]
+    pub fn new(broker: B) -> Self {
+        Self { broker }
+    }
+
+    /// Handle a market event, as the backtester does: the strategy is told
+    /// of the new fills, then sees the event and the account, and its
+    /// orders are submitted. Returns the identifiers of the orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker cannot be reached, or rejects an order.
+    pub fn on_event<S>(
+        &mut self,
+        strategy: &mut S,
+        event: &MarketEvent,
+    ) -> Result<Vec<OrderID>, RustQuantError>
+    where
+        S: Strategy + ?Sized,
+    {
+        for fill in self.broker.update(event)? {
+            strategy.on_fill(&fill);
+        }
+
+        let account = self.broker.account()?;
+
+        strategy
+            .on_event(event, &account)
+            .into_iter()
+            .map(|order| self.broker.submit(order))
+            .collect()
+    }
+
+    /// Handle market events until there are no more, e.g. when a live feed
+    /// is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of [`LiveTrader::on_event`].
+    pub fn run<S, E>(&mut self, strategy: &mut S, events: E) -> Result<(), RustQuantError>
+    where
+        S: Strategy + ?Sized,
+        E: IntoIterator<Item = MarketEvent>,
+    {
+        for event in events {
+            self.on_event(strategy, &event)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_trader {
+    use super::*;
+    use crate::backtest::{Backtester, SimulatedBroker, Tick};
+    use crate::execution::ExecutionAlgorithm;
+    use crate::order_side::OrderSide;
+    use time::{Duration, OffsetDateTime};
+
+    #[test]
+    fn test_same_fills_as_backtest() {
+        let start = OffsetDateTime::UNIX_EPOCH;
+        let end = start + Duration::hours(1);
+        let events: Vec<MarketEvent> = (0..=12)
+            .map(|i| {
+                let timestamp = start + Duration::minutes(5 * i);
+                MarketEvent::Tick(Tick::new("AAA", timestamp, 100.0 + i as f64, 50.0))
+            })
+            .collect();
+
+        let twap = ExecutionAlgorithm::twap("AAA", OrderSide::BID, 100, start, end, 4).unwrap();
+
+        let mut backtested = twap.clone();
+        let report = Backtester::new(SimulatedBroker::new(1e5))
+            .run(&mut backtested, events.clone())
+            .unwrap();
+
+        let mut traded = twap;
+        let mut trader = LiveTrader::new(SimulatedBroker::new(1e5));
+        trader.run(&mut traded, events).unwrap();
+
+        assert_eq!(trader.broker.fills(), report.fills.as_slice());
+        assert_eq!(traded, backtested);
+        assert_eq!(
+            Broker::positions(&trader.broker).unwrap()["AAA"].quantity,
+            100
+        );
+    }
+}
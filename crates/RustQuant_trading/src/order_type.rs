@@ -13,7 +13,7 @@ use std::fmt;
 /// Definitions from:   
 ///     - <https://www.interactivebrokers.com/en/trading/ordertypes.php>
 ///     - <https://www.nasdaqtrader.com/content/productsservices/trading/ordertypesg.pdf>
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderType {
     /// """
     /// A Market order is an order to buy or sell at the market bid or
@@ -84,6 +84,23 @@ pub enum OrderType {
     /// sell trailing stop limit, and is generally used in falling markets.
     /// """
     TrailingStopLimit,
+
+    /// """
+    /// An Iceberg order is a limit order of which only a portion (the display
+    /// quantity) is shown in the order book, while the rest stays hidden.
+    /// When the displayed portion is executed, it is refreshed from the
+    /// hidden quantity, and the new portion takes its place at the back of
+    /// the queue of its price level.
+    /// """
+    Iceberg,
+
+    /// """
+    /// A Pegged order is a limit order whose price follows a reference
+    /// price, such as the best bid for a buy order or the best offer for a
+    /// sell order, with an optional offset away from it. The order is
+    /// re-priced whenever the reference price changes.
+    /// """
+    Pegged,
 }
 
 impl fmt::Display for OrderType {
@@ -95,6 +112,8 @@ impl fmt::Display for OrderType {
             OrderType::StopLimit => write!(f, "STOP_LIMIT"),
             OrderType::TrailingStop => write!(f, "TRAILING_STOP"),
             OrderType::TrailingStopLimit => write!(f, "TRAILING_STOP_LIMIT"),
+            OrderType::Iceberg => write!(f, "ICEBERG"),
+            OrderType::Pegged => write!(f, "PEGGED"),
         }
     }
 }
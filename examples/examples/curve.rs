@@ -1,6 +1,6 @@
 use time::macros::date;
 use time::{Date, Duration};
-use RustQuant::data::Curve;
+use RustQuant::data::{Curve, CurveInterpolation};
 
 fn main() {
     // Date	        1 Mo	2 Mo	3 Mo	4 Mo	6 Mo	1 Yr	2 Yr	3 Yr	5 Yr	7 Yr	10 Yr	20 Yr	30 Yr
@@ -28,7 +28,8 @@ fn main() {
         5.55, 5.46, 5.37, 5.28, 5.08, 4.62, 4.16, 3.96, 3.84, 3.89, 3.99, 4.35, 4.27,
     ];
 
-    let mut curve = Curve::<Date>::new_from_slice(&dates, &rates);
+    let mut curve = Curve::<Date>::new_from_slice(&dates, &rates)
+        .with_interpolation(CurveInterpolation::CubicSpline);
 
     let new_dates = vec![
         today + Duration::days(45),
@@ -46,7 +47,7 @@ fn main() {
         today + Duration::days(365 * 30),
     ];
 
-    curve.interpolate_many(&new_dates);
+    curve.interpolate_many(&new_dates).unwrap();
 
     curve.plot();
 }
@@ -0,0 +1,96 @@
+// End-of-day batch valuation.
+//
+// Usage:
+//
+//     cargo run --example eod_batch -- \
+//         --portfolio portfolio.csv --market market.csv \
+//         --date 2025-01-31 --output results --format parquet --seed 42
+//
+// Without arguments, a demo portfolio is written to a temporary directory and valued.
+
+use std::path::PathBuf;
+use RustQuant::data::parse_date;
+use RustQuant::portfolios::EodBatch;
+
+const DEMO_PORTFOLIO: &str = "\
+name,option_type,underlying_price,strike,volatility,risk_free_rate,cost_of_carry,expiry,quantity
+AAPL Jun25 C200,call,230,200,0.25,0.045,0.04,2025-06-20,100
+AAPL Jun25 P210,put,230,210,0.28,0.045,0.04,2025-06-20,50
+MSFT Sep25 C450,call,415,450,0.22,0.045,0.037,2025-09-19,80
+SPX Dec25 P5500,put,6040,5500,0.18,0.045,0.032,2025-12-19,10
+";
+
+const DEMO_MARKET: &str = "\
+name,price
+AAPL Jun25 C200,33.10
+";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let arg = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+
+    let batch = match arg("--portfolio") {
+        Some(portfolio) => {
+            let date = arg("--date").expect("--date is required with --portfolio.");
+            let output = arg("--output").unwrap_or_else(|| ".".to_string());
+
+            EodBatch::new(
+                parse_date(&date).unwrap(),
+                portfolio,
+                arg("--market").map(PathBuf::from),
+                output,
+            )
+        }
+        None => {
+            let dir = std::env::temp_dir().join("rustquant_eod_batch");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("portfolio.csv"), DEMO_PORTFOLIO).unwrap();
+            std::fs::write(dir.join("market.csv"), DEMO_MARKET).unwrap();
+
+            EodBatch::new(
+                parse_date("2025-01-31").unwrap(),
+                dir.join("portfolio.csv"),
+                Some(dir.join("market.csv")),
+                &dir,
+            )
+        }
+    };
+
+    let batch = match arg("--format") {
+        Some(format) => batch.with_output_format(&format),
+        None => batch,
+    };
+    let batch = match arg("--seed") {
+        Some(seed) => batch.with_seed(seed.parse().expect("--seed must be an integer.")),
+        None => batch,
+    };
+
+    let report = batch.run().unwrap();
+
+    for position in &report.valuation.positions {
+        let greeks = &report.greeks[&position.name];
+        println!(
+            "{:<18} {:>12.2} ({:?})  delta {:>9.2}  vega {:>9.2}",
+            position.name, position.value, position.source, greeks.delta, greeks.vega
+        );
+    }
+    for (name, error) in &report.valuation.failures {
+        println!("{:<18} FAILED: {}", name, error);
+    }
+
+    println!("NPV:   {:.2}", report.valuation.npv);
+    println!(
+        "VaR:   {:.2} ({}% / {} day)",
+        report.value_at_risk,
+        100.0 * batch.confidence,
+        batch.horizon_days
+    );
+    println!("ES:    {:.2}", report.expected_shortfall);
+    println!("Seed:  {}", report.metadata.seed);
+    println!("Written to {}", report.summary_path.display());
+}
@@ -58,6 +58,12 @@ fn main() {
         power.price_monte_carlo(&process, &config, rate)
     );
 
+    // VARIANCE REDUCTION
+    println!(
+        "Vanilla (antithetic): {:?}",
+        vanilla.price_monte_carlo_antithetic(&process, &config, rate)
+    );
+
     // let start = Instant::now();
     // let price = option.price_monte_carlo(process, &config, interest_rate);
     // println!("Elapsed time: {:?}", start.elapsed());
@@ -0,0 +1,67 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2022-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Compares day count factor computation over a large swap book using
+// `time::Date` per period against serial dates computed in a batch.
+//
+// Run with: cargo run --release --example serial_dates
+
+use std::time::Instant;
+use time::{macros::date, Duration};
+use RustQuant::time::*;
+
+fn main() {
+    // 10,000 quarterly swaps of 10 years, with staggered start dates.
+    let n_swaps = 10_000;
+    let n_periods = 40;
+
+    let mut starts = Vec::with_capacity(n_swaps * n_periods);
+    let mut ends = Vec::with_capacity(n_swaps * n_periods);
+
+    for swap in 0..n_swaps {
+        let effective = date!(2024 - 01 - 15) + Duration::days(swap as i64 % 730);
+
+        for period in 0..n_periods {
+            starts.push(effective + Duration::days(91 * period as i64));
+            ends.push(effective + Duration::days(91 * (period + 1) as i64));
+        }
+    }
+
+    for convention in [
+        DayCountConvention::Actual_360,
+        DayCountConvention::Actual_Actual_ISDA,
+        DayCountConvention::Thirty_E_360,
+    ] {
+        let timer = Instant::now();
+        let per_date: Vec<f64> = starts
+            .iter()
+            .zip(&ends)
+            .map(|(&start, &end)| convention.day_count_factor(start, end))
+            .collect();
+        let per_date_time = timer.elapsed();
+
+        let timer = Instant::now();
+        let (serial_starts, serial_ends) = (
+            SerialDate::from_dates(&starts),
+            SerialDate::from_dates(&ends),
+        );
+        let conversion_time = timer.elapsed();
+
+        let timer = Instant::now();
+        let serial = convention.day_count_factors_serial(&serial_starts, &serial_ends);
+        let serial_time = timer.elapsed();
+
+        assert_eq!(per_date, serial);
+
+        println!("{} ({} periods):", convention, starts.len());
+        println!("\tDate per period:    {:?}", per_date_time);
+        println!("\tSerial conversion:  {:?}", conversion_time);
+        println!("\tSerial batch:       {:?}", serial_time);
+    }
+}
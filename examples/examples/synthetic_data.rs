@@ -0,0 +1,61 @@
+// Synthetic market data, generated offline from a seed.
+//
+// Usage:
+//
+//     cargo run --example synthetic_data
+
+use time::macros::date;
+use RustQuant::data::*;
+
+fn main() {
+    let mut market = SyntheticMarket::new(2024);
+    let today = date!(2024 - 01 - 02);
+
+    // A year of daily prices with volatility clustering and jumps.
+    let prices = market
+        .price_history(today, 252, &PriceHistoryConfig::default())
+        .unwrap();
+    println!("{}", prices.tail(Some(5)));
+
+    // An option chain consistent with an SVI smile.
+    let slice = |expiry: f64| SviSlice {
+        expiry,
+        a: 0.02 * expiry,
+        b: 0.1,
+        rho: -0.4,
+        m: 0.0,
+        sigma: 0.2,
+    };
+    let surface = SviVolatilitySurface::new(100.0, 0.02, vec![slice(0.25), slice(1.0)]);
+    let chain = OptionChainConfig {
+        spot: 100.0,
+        risk_free_rate: 0.04,
+        dividend_yield: 0.02,
+        expiries: vec![0.25, 1.0],
+        strikes: vec![80.0, 90.0, 100.0, 110.0, 120.0],
+        volatility_noise: 0.002,
+        volatility_spread: 0.01,
+    };
+
+    println!("\nexpiry  strike  type      vol      bid      ask");
+    for quote in market.option_chain(&surface, &chain) {
+        println!(
+            "{:>6.2}  {:>6.1}  {:<6}  {:>6.4}  {:>7.3}  {:>7.3}",
+            quote.expiry,
+            quote.strike,
+            format!("{:?}", quote.option_type),
+            quote.implied_volatility,
+            quote.bid,
+            quote.ask
+        );
+    }
+
+    // Discount factor quotes with 2bp of noise.
+    let curve = market.discount_curve(
+        today,
+        &NelsonSiegel::default(),
+        &[0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0],
+        2.0,
+    );
+    println!("\n{:?}", curve.nodes);
+}